@@ -0,0 +1,500 @@
+//! 上游响应异常检测
+//!
+//! 上游返回成功状态码但内容已经"退化"时，普通的错误率统计发现不了。
+//! 这里在后置步骤检查非流式响应：
+//! - 空响应：没有文本内容也没有工具调用
+//! - 重复 Token：同一个词或短片段连续重复多次
+//! - JSON 截断：响应体或工具调用参数不是完整的 JSON
+//! - 延迟突增：耗时远超该 Provider 近期延迟的中位数
+//!
+//! 异常按 Provider 记录，时间窗口内的异常次数达到阈值时视为降级。
+
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::RwLock;
+use proxycast_core::ProviderType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+
+/// 最多保留的异常记录数
+const MAX_RECORDS: usize = 1000;
+
+/// 每个 Provider 参与计算延迟基线的样本数
+const LATENCY_SAMPLES: usize = 50;
+
+/// 重复检测的最长片段（Token 数）
+const MAX_REPEAT_UNIT: usize = 8;
+
+/// 异常类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    /// 空响应
+    EmptyResponse,
+    /// 重复 Token
+    RepeatedTokens,
+    /// JSON 截断
+    TruncatedJson,
+    /// 延迟突增
+    LatencySpike,
+}
+
+impl std::fmt::Display for AnomalyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnomalyKind::EmptyResponse => write!(f, "empty_response"),
+            AnomalyKind::RepeatedTokens => write!(f, "repeated_tokens"),
+            AnomalyKind::TruncatedJson => write!(f, "truncated_json"),
+            AnomalyKind::LatencySpike => write!(f, "latency_spike"),
+        }
+    }
+}
+
+/// 异常检测配置
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnomalyConfig {
+    /// 同一片段连续重复多少次视为异常
+    pub repeat_threshold: usize,
+    /// 延迟超过中位数的倍数视为突增
+    pub latency_spike_factor: f64,
+    /// 低于该延迟（毫秒）时不判定为突增，避免快速请求的正常抖动
+    pub latency_min_ms: u64,
+    /// 延迟样本少于该数量时不判定突增
+    pub min_latency_samples: usize,
+    /// 时间窗口内异常次数达到该值时视为降级
+    pub degraded_threshold: usize,
+    /// 降级判定的时间窗口
+    pub window: Duration,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            repeat_threshold: 20,
+            latency_spike_factor: 3.0,
+            latency_min_ms: 5000,
+            min_latency_samples: 10,
+            degraded_threshold: 3,
+            window: Duration::minutes(10),
+        }
+    }
+}
+
+/// 检测到的异常
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Anomaly {
+    pub kind: AnomalyKind,
+    /// 说明
+    pub detail: String,
+}
+
+impl Anomaly {
+    fn new(kind: AnomalyKind, detail: impl Into<String>) -> Self {
+        Self {
+            kind,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// 异常记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyRecord {
+    pub request_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub provider: ProviderType,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_id: Option<String>,
+    pub kind: AnomalyKind,
+    pub detail: String,
+}
+
+/// 单个 Provider 的异常统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderAnomalyStats {
+    pub provider: ProviderType,
+    /// 时间窗口内的异常数
+    pub recent_anomalies: u64,
+    /// 时间窗口内各类型的异常数
+    pub by_kind: HashMap<AnomalyKind, u64>,
+    /// 累计异常数（受记录上限影响）
+    pub total_anomalies: u64,
+    /// 是否已降级
+    pub degraded: bool,
+    /// 近期延迟中位数（毫秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub median_latency_ms: Option<u64>,
+    /// 最近一次异常
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_anomaly: Option<AnomalyRecord>,
+}
+
+/// 检查响应体是否退化
+///
+/// 支持 OpenAI、Anthropic 和 Gemini 格式的非流式响应
+pub fn detect_response(body: &[u8], config: &AnomalyConfig) -> Vec<Anomaly> {
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return vec![Anomaly::new(AnomalyKind::EmptyResponse, "响应体为空")];
+    }
+    let json: Value = match serde_json::from_slice(body) {
+        Ok(json) => json,
+        Err(e) => {
+            return vec![Anomaly::new(
+                AnomalyKind::TruncatedJson,
+                format!("响应体不是完整的 JSON: {}", e),
+            )]
+        }
+    };
+
+    let content = ResponseContent::extract(&json);
+    let mut anomalies = Vec::new();
+
+    if content.text.trim().is_empty() && content.tool_calls.is_empty() {
+        anomalies.push(Anomaly::new(
+            AnomalyKind::EmptyResponse,
+            "响应中没有文本内容和工具调用",
+        ));
+    }
+
+    if let Some((unit, count)) = find_repetition(&content.text, config.repeat_threshold) {
+        anomalies.push(Anomaly::new(
+            AnomalyKind::RepeatedTokens,
+            format!("片段 \"{}\" 连续重复 {} 次", unit, count),
+        ));
+    }
+
+    for (name, arguments) in &content.tool_calls {
+        if let Some(arguments) = arguments {
+            if !arguments.trim().is_empty() && serde_json::from_str::<Value>(arguments).is_err() {
+                anomalies.push(Anomaly::new(
+                    AnomalyKind::TruncatedJson,
+                    format!("工具调用 {} 的参数不是完整的 JSON", name),
+                ));
+            }
+        }
+    }
+
+    anomalies
+}
+
+/// 响应中的文本和工具调用
+#[derive(Default)]
+struct ResponseContent {
+    text: String,
+    /// (工具名, 字符串形式的参数)，参数已是 JSON 对象时为 `None`
+    tool_calls: Vec<(String, Option<String>)>,
+}
+
+impl ResponseContent {
+    fn extract(json: &Value) -> Self {
+        let mut content = Self::default();
+
+        // OpenAI: choices[].message
+        for choice in json["choices"].as_array().into_iter().flatten() {
+            let message = &choice["message"];
+            match &message["content"] {
+                Value::String(text) => content.text.push_str(text),
+                Value::Array(parts) => {
+                    for part in parts {
+                        if let Some(text) = part["text"].as_str() {
+                            content.text.push_str(text);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            for call in message["tool_calls"].as_array().into_iter().flatten() {
+                let function = &call["function"];
+                content.tool_calls.push((
+                    function["name"].as_str().unwrap_or_default().to_string(),
+                    function["arguments"].as_str().map(str::to_string),
+                ));
+            }
+        }
+
+        // Anthropic: content[] 内容块
+        for block in json["content"].as_array().into_iter().flatten() {
+            match block["type"].as_str() {
+                Some("text") => content.text.push_str(block["text"].as_str().unwrap_or("")),
+                Some("tool_use") => content
+                    .tool_calls
+                    .push((block["name"].as_str().unwrap_or_default().to_string(), None)),
+                _ => {}
+            }
+        }
+
+        // Gemini: candidates[].content.parts[]
+        for candidate in json["candidates"].as_array().into_iter().flatten() {
+            for part in candidate["content"]["parts"]
+                .as_array()
+                .into_iter()
+                .flatten()
+            {
+                if let Some(text) = part["text"].as_str() {
+                    content.text.push_str(text);
+                }
+                if let Some(name) = part["functionCall"]["name"].as_str() {
+                    content.tool_calls.push((name.to_string(), None));
+                }
+            }
+        }
+
+        content
+    }
+}
+
+/// 切分为 Token：连续的字母数字为一个 Token，其他非空白字符（含中文）各为一个 Token
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut word_start = None;
+    for (i, c) in text.char_indices() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            word_start.get_or_insert(i);
+            continue;
+        }
+        if let Some(start) = word_start.take() {
+            tokens.push(&text[start..i]);
+        }
+        if !c.is_whitespace() {
+            tokens.push(&text[i..i + c.len_utf8()]);
+        }
+    }
+    if let Some(start) = word_start {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+/// 查找连续重复至少 `threshold` 次的片段，返回 (片段, 重复次数)
+///
+/// 片段最长 [`MAX_REPEAT_UNIT`] 个 Token，且须包含文字，
+/// 分隔线、表格边框之类纯标点的重复不算异常
+fn find_repetition(text: &str, threshold: usize) -> Option<(String, usize)> {
+    let tokens = tokenize(text);
+    let threshold = threshold.max(2);
+    let mut best: Option<(usize, usize, usize)> = None;
+
+    for unit in 1..=MAX_REPEAT_UNIT {
+        let mut run = 0;
+        for i in unit..tokens.len() {
+            if tokens[i] == tokens[i - unit] {
+                run += 1;
+            } else {
+                run = 0;
+            }
+            let count = run / unit + 1;
+            if count >= threshold && best.is_none_or(|(_, _, c)| count > c) {
+                let start = i + 1 - unit;
+                let segment = &tokens[start..=i];
+                if segment.iter().any(|t| t.chars().any(char::is_alphanumeric)) {
+                    best = Some((start, unit, count));
+                }
+            }
+        }
+    }
+
+    best.map(|(start, unit, count)| (tokens[start..start + unit].concat(), count))
+}
+
+/// 中位数，`samples` 须非空
+fn median(samples: &VecDeque<u64>) -> u64 {
+    let mut sorted: Vec<u64> = samples.iter().copied().collect();
+    sorted.sort_unstable();
+    sorted[sorted.len() / 2]
+}
+
+/// 异常追踪器
+///
+/// 按 Provider 记录异常和延迟基线，判断 Provider 是否降级
+pub struct AnomalyTracker {
+    config: RwLock<AnomalyConfig>,
+    records: RwLock<VecDeque<AnomalyRecord>>,
+    latencies: RwLock<HashMap<ProviderType, VecDeque<u64>>>,
+}
+
+impl AnomalyTracker {
+    /// 创建新的异常追踪器
+    pub fn new(config: AnomalyConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            records: RwLock::new(VecDeque::new()),
+            latencies: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 使用默认配置创建
+    pub fn with_defaults() -> Self {
+        Self::new(AnomalyConfig::default())
+    }
+
+    /// 当前配置
+    pub fn config(&self) -> AnomalyConfig {
+        self.config.read().clone()
+    }
+
+    /// 更新配置
+    pub fn set_config(&self, config: AnomalyConfig) {
+        *self.config.write() = config;
+    }
+
+    /// 记录延迟样本，超过基线时返回延迟突增异常
+    pub fn observe_latency(&self, provider: ProviderType, latency_ms: u64) -> Option<Anomaly> {
+        let config = self.config.read().clone();
+        let mut latencies = self.latencies.write();
+        let samples = latencies.entry(provider).or_default();
+
+        let anomaly = if samples.len() >= config.min_latency_samples.max(1) {
+            let baseline = median(samples);
+            let limit = (baseline as f64 * config.latency_spike_factor) as u64;
+            (latency_ms >= config.latency_min_ms && latency_ms > limit).then(|| {
+                Anomaly::new(
+                    AnomalyKind::LatencySpike,
+                    format!("耗时 {}ms，近期中位数 {}ms", latency_ms, baseline),
+                )
+            })
+        } else {
+            None
+        };
+
+        samples.push_back(latency_ms);
+        while samples.len() > LATENCY_SAMPLES {
+            samples.pop_front();
+        }
+        anomaly
+    }
+
+    /// 检查一次成功的非流式响应，记录并返回检测到的异常
+    ///
+    /// `latency_ms` 为空时不参与延迟基线
+    pub fn inspect(
+        &self,
+        request_id: &str,
+        provider: ProviderType,
+        model: &str,
+        credential_id: Option<&str>,
+        body: &[u8],
+        latency_ms: Option<u64>,
+    ) -> Vec<AnomalyRecord> {
+        let mut anomalies = detect_response(body, &self.config.read());
+        if let Some(anomaly) = latency_ms.and_then(|ms| self.observe_latency(provider, ms)) {
+            anomalies.push(anomaly);
+        }
+
+        let now = Utc::now();
+        let records: Vec<AnomalyRecord> = anomalies
+            .into_iter()
+            .map(|anomaly| AnomalyRecord {
+                request_id: request_id.to_string(),
+                timestamp: now,
+                provider,
+                model: model.to_string(),
+                credential_id: credential_id.map(str::to_string),
+                kind: anomaly.kind,
+                detail: anomaly.detail,
+            })
+            .collect();
+        for record in &records {
+            self.record(record.clone());
+        }
+        records
+    }
+
+    /// 保存异常记录，超出上限时淘汰最旧的
+    pub fn record(&self, record: AnomalyRecord) {
+        let mut records = self.records.write();
+        records.push_back(record);
+        while records.len() > MAX_RECORDS {
+            records.pop_front();
+        }
+    }
+
+    /// 最近的异常记录（最新的在前）
+    pub fn recent(&self, provider: Option<ProviderType>, limit: usize) -> Vec<AnomalyRecord> {
+        self.records
+            .read()
+            .iter()
+            .rev()
+            .filter(|r| provider.is_none_or(|p| r.provider == p))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Provider 是否已降级
+    pub fn is_degraded(&self, provider: ProviderType) -> bool {
+        let config = self.config.read().clone();
+        let cutoff = Utc::now() - config.window;
+        let recent = self
+            .records
+            .read()
+            .iter()
+            .filter(|r| r.provider == provider && r.timestamp >= cutoff)
+            .count();
+        recent >= config.degraded_threshold.max(1)
+    }
+
+    /// 各 Provider 的异常统计，降级的排在前面
+    pub fn provider_stats(&self) -> Vec<ProviderAnomalyStats> {
+        let config = self.config.read().clone();
+        let cutoff = Utc::now() - config.window;
+        let latencies = self.latencies.read();
+        let mut stats: HashMap<ProviderType, ProviderAnomalyStats> = HashMap::new();
+
+        let entry = |provider: ProviderType| ProviderAnomalyStats {
+            provider,
+            recent_anomalies: 0,
+            by_kind: HashMap::new(),
+            total_anomalies: 0,
+            degraded: false,
+            median_latency_ms: latencies
+                .get(&provider)
+                .filter(|s| !s.is_empty())
+                .map(median),
+            last_anomaly: None,
+        };
+
+        for record in self.records.read().iter() {
+            let stat = stats
+                .entry(record.provider)
+                .or_insert_with(|| entry(record.provider));
+            stat.total_anomalies += 1;
+            if record.timestamp >= cutoff {
+                stat.recent_anomalies += 1;
+                *stat.by_kind.entry(record.kind).or_default() += 1;
+            }
+            stat.last_anomaly = Some(record.clone());
+        }
+        for provider in latencies.keys() {
+            stats.entry(*provider).or_insert_with(|| entry(*provider));
+        }
+
+        let threshold = config.degraded_threshold.max(1) as u64;
+        let mut result: Vec<ProviderAnomalyStats> = stats
+            .into_values()
+            .map(|mut s| {
+                s.degraded = s.recent_anomalies >= threshold;
+                s
+            })
+            .collect();
+        result.sort_by(|a, b| {
+            b.degraded
+                .cmp(&a.degraded)
+                .then_with(|| b.recent_anomalies.cmp(&a.recent_anomalies))
+                .then_with(|| a.provider.to_string().cmp(&b.provider.to_string()))
+        });
+        result
+    }
+
+    /// 清空异常记录和延迟基线
+    pub fn clear(&self) {
+        self.records.write().clear();
+        self.latencies.write().clear();
+    }
+}
+
+impl Default for AnomalyTracker {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
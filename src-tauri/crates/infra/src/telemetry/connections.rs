@@ -0,0 +1,122 @@
+//! 上游连接复用统计
+//!
+//! 短对话的耗时经常被 TCP 和 TLS 握手占据。这里按 HTTP 客户端（每个 Provider 一个）统计：
+//! - 请求数，以及响应使用的 HTTP 协议版本（确认是否协商到 HTTP/2）
+//! - 新建连接数（每次新建都要重新握手），失败的连接尝试单独计数
+//!
+//! 复用的请求数 = 请求数 - 新建连接数。HTTP/2 下多个请求共用一个连接，同样计为复用。
+
+use parking_lot::RwLock;
+use reqwest::Version;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// 单个客户端的计数
+#[derive(Debug, Clone, Default)]
+struct ConnectionCounters {
+    requests: u64,
+    failed_requests: u64,
+    new_connections: u64,
+    failed_connections: u64,
+    http2_responses: u64,
+    http1_responses: u64,
+}
+
+/// 单个客户端的连接复用统计
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionStats {
+    /// 客户端名称（Provider）
+    pub client: String,
+    /// 请求数（含未收到响应的请求）
+    pub requests: u64,
+    /// 未收到响应的请求数（连接失败、超时等）
+    pub failed_requests: u64,
+    /// 新建的连接数（每个都经过完整握手）
+    pub new_connections: u64,
+    /// 建立失败的连接数
+    pub failed_connections: u64,
+    /// 复用已有连接的请求数
+    pub reused_requests: u64,
+    /// 连接复用率（0-1），没有请求时为 0
+    pub reuse_rate: f64,
+    /// 使用 HTTP/2 的响应数
+    pub http2_responses: u64,
+    /// 使用 HTTP/1.x 的响应数
+    pub http1_responses: u64,
+}
+
+/// 连接复用统计器
+#[derive(Debug, Default)]
+pub struct ConnectionTracker {
+    clients: RwLock<HashMap<String, ConnectionCounters>>,
+}
+
+impl ConnectionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一次新建连接
+    pub fn record_connection(&self, client: &str, success: bool) {
+        let mut clients = self.clients.write();
+        let counters = clients.entry(client.to_string()).or_default();
+        if success {
+            counters.new_connections += 1;
+        } else {
+            counters.failed_connections += 1;
+        }
+    }
+
+    /// 记录一次请求，`version` 为响应的协议版本，未收到响应时为 `None`
+    pub fn record_request(&self, client: &str, version: Option<Version>) {
+        let mut clients = self.clients.write();
+        let counters = clients.entry(client.to_string()).or_default();
+        counters.requests += 1;
+        match version {
+            Some(Version::HTTP_2) => counters.http2_responses += 1,
+            Some(Version::HTTP_09 | Version::HTTP_10 | Version::HTTP_11) => {
+                counters.http1_responses += 1
+            }
+            Some(_) => {}
+            None => counters.failed_requests += 1,
+        }
+    }
+
+    /// 各客户端的统计，按请求数从多到少排列
+    pub fn stats(&self) -> Vec<ConnectionStats> {
+        let mut stats: Vec<_> = self
+            .clients
+            .read()
+            .iter()
+            .map(|(client, c)| {
+                let reused_requests = c.requests.saturating_sub(c.new_connections);
+                ConnectionStats {
+                    client: client.clone(),
+                    requests: c.requests,
+                    failed_requests: c.failed_requests,
+                    new_connections: c.new_connections,
+                    failed_connections: c.failed_connections,
+                    reused_requests,
+                    reuse_rate: if c.requests == 0 {
+                        0.0
+                    } else {
+                        reused_requests as f64 / c.requests as f64
+                    },
+                    http2_responses: c.http2_responses,
+                    http1_responses: c.http1_responses,
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| {
+            b.requests
+                .cmp(&a.requests)
+                .then_with(|| a.client.cmp(&b.client))
+        });
+        stats
+    }
+
+    /// 清空统计
+    pub fn clear(&self) {
+        self.clients.write().clear();
+    }
+}
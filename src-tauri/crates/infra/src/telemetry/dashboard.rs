@@ -0,0 +1,310 @@
+//! 监控面板聚合查询
+//!
+//! 在后端完成按小时请求数、错误率、延迟分位数、热门模型等聚合，
+//! 前端只拿到绘图所需的汇总数据，不需要拉取原始日志。
+//!
+//! 错误率只统计失败和超时，用户主动取消的请求不算错误。
+
+use super::stats::StatsAggregator;
+use super::types::{RequestLog, RequestStatus, TimeRange};
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use proxycast_core::ProviderType;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// 日志过滤条件
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    /// 时间范围，为空时不限制
+    pub range: Option<TimeRange>,
+    /// Provider
+    pub provider: Option<ProviderType>,
+    /// 模型名称
+    pub model: Option<String>,
+    /// 请求状态
+    pub status: Option<RequestStatus>,
+}
+
+impl LogFilter {
+    /// 检查日志是否满足过滤条件
+    pub fn matches(&self, log: &RequestLog) -> bool {
+        self.range.is_none_or(|r| r.contains(&log.timestamp))
+            && self.provider.is_none_or(|p| log.provider == p)
+            && self.model.as_ref().is_none_or(|m| &log.model == m)
+            && self.status.is_none_or(|s| log.status == s)
+    }
+}
+
+/// 每小时请求统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HourlyRequestStats {
+    /// 小时起点（UTC 整点）
+    pub hour: DateTime<Utc>,
+    /// 请求数
+    pub total_requests: u64,
+    /// 失败请求数（含超时）
+    pub failed_requests: u64,
+    /// 错误率（0.0 - 1.0）
+    pub error_rate: f64,
+}
+
+/// 错误率统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ErrorRateStats {
+    /// 总请求数
+    pub total_requests: u64,
+    /// 失败请求数
+    pub failed_requests: u64,
+    /// 超时请求数
+    pub timeout_requests: u64,
+    /// 已取消请求数（不计入错误）
+    pub cancelled_requests: u64,
+    /// 错误率（0.0 - 1.0）
+    pub error_rate: f64,
+}
+
+/// 延迟分位数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencyPercentiles {
+    /// 样本数
+    pub count: u64,
+    /// P50 延迟（毫秒）
+    pub p50_ms: Option<u64>,
+    /// P95 延迟（毫秒）
+    pub p95_ms: Option<u64>,
+}
+
+/// 模型用量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelUsage {
+    /// 模型名称
+    pub model: String,
+    /// 请求数
+    pub requests: u64,
+    /// 输入 Token 数
+    pub input_tokens: u64,
+    /// 输出 Token 数
+    pub output_tokens: u64,
+    /// 错误率（0.0 - 1.0）
+    pub error_rate: f64,
+}
+
+/// Provider + 模型的 Token 用量（用于计算费用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderModelUsage {
+    /// Provider
+    pub provider: ProviderType,
+    /// 模型名称
+    pub model: String,
+    /// 请求数
+    pub requests: u64,
+    /// 输入 Token 数
+    pub input_tokens: u64,
+    /// 输出 Token 数
+    pub output_tokens: u64,
+}
+
+/// 客户端密钥 + 模型的 Token 用量（用于按成员统计费用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientModelUsage {
+    /// 客户端密钥 ID，使用主密钥的请求为空
+    pub client_key_id: Option<String>,
+    /// 模型名称
+    pub model: String,
+    /// 请求数
+    pub requests: u64,
+    /// 失败和超时的请求数
+    pub failed_requests: u64,
+    /// 输入 Token 数
+    pub input_tokens: u64,
+    /// 输出 Token 数
+    pub output_tokens: u64,
+}
+
+/// 是否计为错误
+fn is_error(log: &RequestLog) -> bool {
+    matches!(log.status, RequestStatus::Failed | RequestStatus::Timeout)
+}
+
+fn ratio(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+/// 最近秩法计算分位数，`sorted` 须已升序排列
+fn percentile(sorted: &[u64], p: usize) -> Option<u64> {
+    (!sorted.is_empty()).then(|| sorted[(sorted.len() * p).div_ceil(100).max(1) - 1])
+}
+
+fn truncate_hour(timestamp: DateTime<Utc>) -> DateTime<Utc> {
+    timestamp
+        .duration_trunc(Duration::hours(1))
+        .unwrap_or(timestamp)
+}
+
+impl StatsAggregator {
+    /// 获取满足过滤条件的日志
+    pub fn filtered(&self, filter: &LogFilter) -> Vec<RequestLog> {
+        self.get_all()
+            .into_iter()
+            .filter(|l| filter.matches(l))
+            .collect()
+    }
+
+    /// 按小时统计请求数和错误率
+    ///
+    /// 没有请求的小时补零，便于前端直接绘制连续曲线。
+    /// 指定时间范围时覆盖整个范围，否则从第一条到最后一条日志。
+    pub fn requests_per_hour(&self, filter: &LogFilter) -> Vec<HourlyRequestStats> {
+        let logs = self.filtered(filter);
+        let mut buckets: BTreeMap<DateTime<Utc>, (u64, u64)> = BTreeMap::new();
+        for log in &logs {
+            let bucket = buckets.entry(truncate_hour(log.timestamp)).or_default();
+            bucket.0 += 1;
+            if is_error(log) {
+                bucket.1 += 1;
+            }
+        }
+
+        let bounds = match filter.range {
+            Some(r) => Some((truncate_hour(r.start), truncate_hour(r.end))),
+            None => buckets
+                .keys()
+                .next()
+                .zip(buckets.keys().next_back())
+                .map(|(first, last)| (*first, *last)),
+        };
+        let Some((mut hour, last)) = bounds else {
+            return Vec::new();
+        };
+
+        let mut result = Vec::new();
+        while hour <= last {
+            let (total, failed) = buckets.get(&hour).copied().unwrap_or_default();
+            result.push(HourlyRequestStats {
+                hour,
+                total_requests: total,
+                failed_requests: failed,
+                error_rate: ratio(failed, total),
+            });
+            hour += Duration::hours(1);
+        }
+        result
+    }
+
+    /// 统计错误率
+    pub fn error_rate(&self, filter: &LogFilter) -> ErrorRateStats {
+        let logs = self.filtered(filter);
+        let count = |status: RequestStatus| logs.iter().filter(|l| l.status == status).count();
+        let failed_requests = count(RequestStatus::Failed) as u64;
+        let timeout_requests = count(RequestStatus::Timeout) as u64;
+        let total_requests = logs.len() as u64;
+
+        ErrorRateStats {
+            total_requests,
+            failed_requests,
+            timeout_requests,
+            cancelled_requests: count(RequestStatus::Cancelled) as u64,
+            error_rate: ratio(failed_requests + timeout_requests, total_requests),
+        }
+    }
+
+    /// 计算请求延迟的 P50 / P95
+    pub fn latency_percentiles(&self, filter: &LogFilter) -> LatencyPercentiles {
+        let mut latencies: Vec<u64> = self
+            .filtered(filter)
+            .iter()
+            .map(|l| l.duration_ms)
+            .collect();
+        latencies.sort_unstable();
+
+        LatencyPercentiles {
+            count: latencies.len() as u64,
+            p50_ms: percentile(&latencies, 50),
+            p95_ms: percentile(&latencies, 95),
+        }
+    }
+
+    /// 按请求数降序返回前 `limit` 个模型
+    pub fn top_models(&self, filter: &LogFilter, limit: usize) -> Vec<ModelUsage> {
+        let mut by_model: HashMap<String, (ModelUsage, u64)> = HashMap::new();
+        for log in self.filtered(filter) {
+            let (usage, errors) = by_model.entry(log.model.clone()).or_insert_with(|| {
+                let usage = ModelUsage {
+                    model: log.model.clone(),
+                    requests: 0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    error_rate: 0.0,
+                };
+                (usage, 0)
+            });
+            usage.requests += 1;
+            usage.input_tokens += log.input_tokens.unwrap_or(0) as u64;
+            usage.output_tokens += log.output_tokens.unwrap_or(0) as u64;
+            if is_error(&log) {
+                *errors += 1;
+            }
+        }
+
+        let mut result: Vec<ModelUsage> = by_model
+            .into_values()
+            .map(|(mut usage, errors)| {
+                usage.error_rate = ratio(errors, usage.requests);
+                usage
+            })
+            .collect();
+        result.sort_by(|a, b| {
+            b.requests
+                .cmp(&a.requests)
+                .then_with(|| a.model.cmp(&b.model))
+        });
+        result.truncate(limit);
+        result
+    }
+
+    /// 按 Provider + 模型汇总 Token 用量
+    pub fn usage_by_provider_model(&self, filter: &LogFilter) -> Vec<ProviderModelUsage> {
+        let mut groups: HashMap<(ProviderType, String), ProviderModelUsage> = HashMap::new();
+        for log in self.filtered(filter) {
+            let usage = groups
+                .entry((log.provider, log.model.clone()))
+                .or_insert_with(|| ProviderModelUsage {
+                    provider: log.provider,
+                    model: log.model.clone(),
+                    requests: 0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                });
+            usage.requests += 1;
+            usage.input_tokens += log.input_tokens.unwrap_or(0) as u64;
+            usage.output_tokens += log.output_tokens.unwrap_or(0) as u64;
+        }
+        groups.into_values().collect()
+    }
+
+    /// 按客户端密钥 + 模型汇总 Token 用量
+    pub fn usage_by_client_model(&self, filter: &LogFilter) -> Vec<ClientModelUsage> {
+        let mut groups: HashMap<(Option<String>, String), ClientModelUsage> = HashMap::new();
+        for log in self.filtered(filter) {
+            let usage = groups
+                .entry((log.client_key_id.clone(), log.model.clone()))
+                .or_insert_with(|| ClientModelUsage {
+                    client_key_id: log.client_key_id.clone(),
+                    model: log.model.clone(),
+                    requests: 0,
+                    failed_requests: 0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                });
+            usage.requests += 1;
+            usage.failed_requests += is_error(&log) as u64;
+            usage.input_tokens += log.input_tokens.unwrap_or(0) as u64;
+            usage.output_tokens += log.output_tokens.unwrap_or(0) as u64;
+        }
+        groups.into_values().collect()
+    }
+}
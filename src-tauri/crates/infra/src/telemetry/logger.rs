@@ -151,6 +151,23 @@ impl RequestLogger {
         Ok(())
     }
 
+    /// 更新内存中请求日志的流式时间指标（已写入文件的记录不再修改）
+    pub fn update_stream_timing(
+        &self,
+        id: &str,
+        first_token_ms: u64,
+        last_token_ms: u64,
+        output_tokens: u32,
+    ) -> bool {
+        super::types::update_stream_timing(
+            &mut self.logs.write(),
+            id,
+            first_token_ms,
+            last_token_ms,
+            output_tokens,
+        )
+    }
+
     /// 获取所有内存中的日志
     pub fn get_all(&self) -> Vec<RequestLog> {
         self.logs.read().iter().cloned().collect()
@@ -1,19 +1,37 @@
 //! 监控与日志模块
 //!
-//! 提供请求日志记录、统计聚合和 Token 追踪功能
+//! 提供请求日志记录、统计聚合、监控面板聚合查询、上游响应异常检测、连接复用统计、
+//! 管道开销统计和 Token 追踪功能
 
+mod anomaly;
+mod connections;
+mod dashboard;
 mod logger;
+mod pipeline;
 mod stats;
 mod tokens;
 mod types;
 
+pub use anomaly::{
+    detect_response, Anomaly, AnomalyConfig, AnomalyKind, AnomalyRecord, AnomalyTracker,
+    ProviderAnomalyStats,
+};
+pub use connections::{ConnectionStats, ConnectionTracker};
+pub use dashboard::{
+    ClientModelUsage, ErrorRateStats, HourlyRequestStats, LatencyPercentiles, LogFilter,
+    ModelUsage, ProviderModelUsage,
+};
 pub use logger::{LogRotationConfig, LoggerError, RequestLogger};
+pub use pipeline::{sampled, PipelineOverheadTracker, StepOverhead, DEFAULT_OVERHEAD_WINDOW};
 pub use stats::StatsAggregator;
 pub use tokens::{
-    ModelTokenStats, PeriodTokenStats, ProviderTokenStats, TokenSource, TokenStatsSummary,
-    TokenTracker, TokenUsageRecord,
+    ModelTokenStats, PeriodTokenStats, ProviderTokenStats, TokenEstimator, TokenEstimatorError,
+    TokenSource, TokenStatsSummary, TokenTracker, TokenUsageRecord,
+};
+pub use types::{
+    ModelStats, ProviderModelStats, ProviderStats, RequestLog, RequestStatus, StatsSummary,
+    TimeRange,
 };
-pub use types::{ModelStats, ProviderStats, RequestLog, RequestStatus, StatsSummary, TimeRange};
 
 #[cfg(test)]
 mod tests;
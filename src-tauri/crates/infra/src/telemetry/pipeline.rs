@@ -0,0 +1,130 @@
+//! 管道自身开销统计与昂贵遥测的采样
+//!
+//! 按步骤记录 ProxyCast 在请求路径上自己花掉的时间（Token 计数、参数注入、Flow 捕获等），
+//! 每个步骤保留最近的样本，报告 P50 / P95 / 最大值，用于确认代理只增加了可忽略的延迟。
+//! 开销通常在微秒级，统计单位为微秒。
+//!
+//! [`sampled`] 按请求 ID 决定是否执行昂贵的遥测（请求体捕获、Token 重新计数），
+//! 同一请求的结果固定，不同采样项对同一请求的判断一致。
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// 每个步骤默认保留的样本数
+pub const DEFAULT_OVERHEAD_WINDOW: usize = 1000;
+
+/// 单个步骤的开销统计
+#[derive(Debug, Clone, Serialize)]
+pub struct StepOverhead {
+    /// 步骤名称
+    pub step: String,
+    /// 统计窗口内的样本数
+    pub count: u64,
+    /// P50 耗时（微秒）
+    pub p50_us: u64,
+    /// P95 耗时（微秒）
+    pub p95_us: u64,
+    /// 最大耗时（微秒）
+    pub max_us: u64,
+}
+
+/// 管道开销统计器
+#[derive(Debug)]
+pub struct PipelineOverheadTracker {
+    /// 每个步骤保留的样本数
+    window: AtomicUsize,
+    /// 按步骤名称保存的最近样本（微秒）
+    samples: RwLock<HashMap<String, VecDeque<u64>>>,
+}
+
+impl Default for PipelineOverheadTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_OVERHEAD_WINDOW)
+    }
+}
+
+impl PipelineOverheadTracker {
+    /// 创建统计器，每个步骤最多保留 `window` 个样本
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: AtomicUsize::new(window.max(1)),
+            samples: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 修改每个步骤保留的样本数，超出的最早样本立即丢弃
+    pub fn set_window(&self, window: usize) {
+        let window = window.max(1);
+        self.window.store(window, Ordering::Relaxed);
+        for samples in self.samples.write().values_mut() {
+            let excess = samples.len().saturating_sub(window);
+            samples.drain(..excess);
+        }
+    }
+
+    /// 记录一次步骤耗时
+    pub fn record(&self, step: &str, elapsed: Duration) {
+        let micros = elapsed.as_micros().min(u64::MAX as u128) as u64;
+        let limit = self.window.load(Ordering::Relaxed);
+        let mut samples = self.samples.write();
+        let window = samples.entry(step.to_string()).or_default();
+        while window.len() >= limit {
+            window.pop_front();
+        }
+        window.push_back(micros);
+    }
+
+    /// 各步骤的开销统计，按 P95 从高到低排列
+    pub fn report(&self) -> Vec<StepOverhead> {
+        let mut report: Vec<_> = self
+            .samples
+            .read()
+            .iter()
+            .filter(|(_, window)| !window.is_empty())
+            .map(|(step, window)| {
+                let mut sorted: Vec<u64> = window.iter().copied().collect();
+                sorted.sort_unstable();
+                StepOverhead {
+                    step: step.clone(),
+                    count: sorted.len() as u64,
+                    p50_us: percentile(&sorted, 50),
+                    p95_us: percentile(&sorted, 95),
+                    max_us: sorted[sorted.len() - 1],
+                }
+            })
+            .collect();
+        report.sort_by(|a, b| b.p95_us.cmp(&a.p95_us).then_with(|| a.step.cmp(&b.step)));
+        report
+    }
+
+    /// 清空统计
+    pub fn clear(&self) {
+        self.samples.write().clear();
+    }
+}
+
+/// 有序样本的百分位数（`sorted` 非空）
+fn percentile(sorted: &[u64], p: usize) -> u64 {
+    sorted[(sorted.len() * p).div_ceil(100).max(1) - 1]
+}
+
+/// 按 `rate`（0-1）对 `key` 采样
+///
+/// 对 `key` 做 FNV-1a 哈希后映射到 [0, 1)，小于 `rate` 时采样；
+/// `rate` 不小于 1 时全部采样，不大于 0 时全部跳过
+pub fn sampled(rate: f64, key: &str) -> bool {
+    if rate >= 1.0 {
+        return true;
+    }
+    if rate <= 0.0 || rate.is_nan() {
+        return false;
+    }
+    let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    let fraction = (hash >> 11) as f64 / (1u64 << 53) as f64;
+    fraction < rate
+}
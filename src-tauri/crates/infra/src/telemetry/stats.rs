@@ -62,6 +62,26 @@ impl StatsAggregator {
         }
     }
 
+    /// 更新已记录请求的流式时间指标
+    ///
+    /// 流式请求在响应开始时即记录日志，首 Token 延迟和生成速度需在流结束后补充。
+    /// 返回是否找到对应请求
+    pub fn update_stream_timing(
+        &self,
+        id: &str,
+        first_token_ms: u64,
+        last_token_ms: u64,
+        output_tokens: u32,
+    ) -> bool {
+        super::types::update_stream_timing(
+            &mut self.logs.write(),
+            id,
+            first_token_ms,
+            last_token_ms,
+            output_tokens,
+        )
+    }
+
     /// 获取统计摘要
     ///
     /// # Arguments
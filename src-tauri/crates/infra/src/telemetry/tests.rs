@@ -3,7 +3,9 @@
 //! 使用 proptest 进行属性测试
 
 use super::{
-    LogRotationConfig, RequestLog, RequestLogger, RequestStatus, StatsAggregator, TimeRange,
+    detect_response, sampled, AnomalyConfig, AnomalyKind, AnomalyTracker, ConnectionTracker,
+    LogFilter, LogRotationConfig, PipelineOverheadTracker, RequestLog, RequestLogger,
+    RequestStatus, StatsAggregator, TimeRange,
 };
 use chrono::{Duration, Utc};
 use proptest::prelude::*;
@@ -840,3 +842,474 @@ fn test_stats_aggregator_max_logs_limit() {
     // 验证日志数量不超过限制
     assert_eq!(aggregator.len(), 10);
 }
+
+#[test]
+fn test_stats_aggregator_update_stream_timing() {
+    let aggregator = create_test_aggregator();
+
+    let mut log = RequestLog::new(
+        "stream-1".to_string(),
+        ProviderType::OpenAI,
+        "gpt-4o".to_string(),
+        true,
+    );
+    log.mark_success(50, 200);
+    aggregator.record(log);
+
+    assert!(aggregator.update_stream_timing("stream-1", 200, 1200, 40));
+    assert!(!aggregator.update_stream_timing("missing", 200, 1200, 40));
+
+    let summary = aggregator.summary(None);
+    assert_eq!(summary.avg_first_token_ms, Some(200.0));
+    assert_eq!(summary.avg_tokens_per_second, Some(40.0));
+}
+
+// ========== 监控面板聚合查询测试 ==========
+
+fn record_log(
+    aggregator: &StatsAggregator,
+    provider: ProviderType,
+    model: &str,
+    status: RequestStatus,
+    duration_ms: u64,
+    hours_ago: i64,
+) {
+    let mut log = RequestLog::new(
+        uuid::Uuid::new_v4().to_string(),
+        provider,
+        model.to_string(),
+        false,
+    );
+    match status {
+        RequestStatus::Success => log.mark_success(duration_ms, 200),
+        RequestStatus::Failed => log.mark_failed(duration_ms, Some(500), "error".to_string()),
+        RequestStatus::Timeout => log.mark_timeout(duration_ms),
+        RequestStatus::Cancelled => log.mark_cancelled(duration_ms),
+        RequestStatus::Retrying => log.duration_ms = duration_ms,
+    }
+    log.set_tokens(Some(100), Some(50));
+    log.timestamp -= Duration::hours(hours_ago);
+    aggregator.record(log);
+}
+
+#[test]
+fn test_dashboard_requests_per_hour_fills_gaps() {
+    let aggregator = create_test_aggregator();
+    record_log(
+        &aggregator,
+        ProviderType::Kiro,
+        "a",
+        RequestStatus::Success,
+        100,
+        3,
+    );
+    record_log(
+        &aggregator,
+        ProviderType::Kiro,
+        "a",
+        RequestStatus::Failed,
+        100,
+        3,
+    );
+    record_log(
+        &aggregator,
+        ProviderType::Kiro,
+        "a",
+        RequestStatus::Success,
+        100,
+        0,
+    );
+
+    let hours = aggregator.requests_per_hour(&LogFilter::default());
+    assert_eq!(hours.len(), 4);
+    assert_eq!(hours[0].total_requests, 2);
+    assert_eq!(hours[0].failed_requests, 1);
+    assert_eq!(hours[0].error_rate, 0.5);
+    assert_eq!(hours[1].total_requests, 0);
+    assert_eq!(hours[2].total_requests, 0);
+    assert_eq!(hours[3].total_requests, 1);
+    assert_eq!(hours[1].hour - hours[0].hour, Duration::hours(1));
+
+    let range = TimeRange::last_hours(24);
+    let filter = LogFilter {
+        range: Some(range),
+        ..Default::default()
+    };
+    assert_eq!(aggregator.requests_per_hour(&filter).len(), 25);
+}
+
+#[test]
+fn test_dashboard_error_rate_ignores_cancelled() {
+    let aggregator = create_test_aggregator();
+    record_log(
+        &aggregator,
+        ProviderType::Kiro,
+        "a",
+        RequestStatus::Success,
+        100,
+        0,
+    );
+    record_log(
+        &aggregator,
+        ProviderType::Kiro,
+        "a",
+        RequestStatus::Failed,
+        100,
+        0,
+    );
+    record_log(
+        &aggregator,
+        ProviderType::Kiro,
+        "a",
+        RequestStatus::Timeout,
+        100,
+        0,
+    );
+    record_log(
+        &aggregator,
+        ProviderType::Kiro,
+        "a",
+        RequestStatus::Cancelled,
+        100,
+        0,
+    );
+
+    let stats = aggregator.error_rate(&LogFilter::default());
+    assert_eq!(stats.total_requests, 4);
+    assert_eq!(stats.failed_requests, 1);
+    assert_eq!(stats.timeout_requests, 1);
+    assert_eq!(stats.cancelled_requests, 1);
+    assert_eq!(stats.error_rate, 0.5);
+
+    let empty = StatsAggregator::with_defaults().error_rate(&LogFilter::default());
+    assert_eq!(empty.error_rate, 0.0);
+}
+
+#[test]
+fn test_dashboard_latency_percentiles() {
+    let aggregator = create_test_aggregator();
+    for duration in 1..=100 {
+        record_log(
+            &aggregator,
+            ProviderType::Kiro,
+            "a",
+            RequestStatus::Success,
+            duration * 10,
+            0,
+        );
+    }
+
+    let latency = aggregator.latency_percentiles(&LogFilter::default());
+    assert_eq!(latency.count, 100);
+    assert_eq!(latency.p50_ms, Some(500));
+    assert_eq!(latency.p95_ms, Some(950));
+
+    let empty = StatsAggregator::with_defaults().latency_percentiles(&LogFilter::default());
+    assert_eq!(empty.p50_ms, None);
+}
+
+#[test]
+fn test_dashboard_top_models_and_filters() {
+    let aggregator = create_test_aggregator();
+    record_log(
+        &aggregator,
+        ProviderType::Kiro,
+        "a",
+        RequestStatus::Success,
+        100,
+        0,
+    );
+    record_log(
+        &aggregator,
+        ProviderType::Gemini,
+        "b",
+        RequestStatus::Success,
+        100,
+        0,
+    );
+    record_log(
+        &aggregator,
+        ProviderType::Gemini,
+        "b",
+        RequestStatus::Failed,
+        100,
+        0,
+    );
+    record_log(
+        &aggregator,
+        ProviderType::Gemini,
+        "c",
+        RequestStatus::Success,
+        100,
+        0,
+    );
+
+    let top = aggregator.top_models(&LogFilter::default(), 2);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[0].model, "b");
+    assert_eq!(top[0].requests, 2);
+    assert_eq!(top[0].input_tokens, 200);
+    assert_eq!(top[0].error_rate, 0.5);
+    assert_eq!(top[1].model, "a");
+
+    let filter = LogFilter {
+        provider: Some(ProviderType::Gemini),
+        status: Some(RequestStatus::Success),
+        ..Default::default()
+    };
+    let usage = aggregator.usage_by_provider_model(&filter);
+    assert_eq!(usage.len(), 2);
+    assert!(usage
+        .iter()
+        .all(|u| u.provider == ProviderType::Gemini && u.requests == 1));
+
+    let filter = LogFilter {
+        model: Some("a".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(aggregator.error_rate(&filter).total_requests, 1);
+}
+
+#[test]
+fn test_dashboard_usage_by_client_model() {
+    let aggregator = create_test_aggregator();
+    for (client, status) in [
+        (Some("alice"), RequestStatus::Success),
+        (Some("alice"), RequestStatus::Failed),
+        (Some("bob"), RequestStatus::Success),
+        (None, RequestStatus::Success),
+    ] {
+        let mut log = RequestLog::new(
+            uuid::Uuid::new_v4().to_string(),
+            ProviderType::Kiro,
+            "a".to_string(),
+            false,
+        );
+        match status {
+            RequestStatus::Failed => log.mark_failed(100, Some(500), "error".to_string()),
+            _ => log.mark_success(100, 200),
+        }
+        log.set_tokens(Some(100), Some(50));
+        if let Some(client) = client {
+            log.set_client_key_id(client.to_string());
+        }
+        aggregator.record(log);
+    }
+
+    let mut usage = aggregator.usage_by_client_model(&LogFilter::default());
+    usage.sort_by(|a, b| a.client_key_id.cmp(&b.client_key_id));
+    assert_eq!(usage.len(), 3);
+    assert_eq!(usage[0].client_key_id, None);
+    assert_eq!(usage[1].client_key_id.as_deref(), Some("alice"));
+    assert_eq!(usage[1].requests, 2);
+    assert_eq!(usage[1].failed_requests, 1);
+    assert_eq!(usage[1].input_tokens, 200);
+    assert_eq!(usage[2].client_key_id.as_deref(), Some("bob"));
+    assert_eq!(usage[2].requests, 1);
+}
+
+// ========== 响应异常检测 ==========
+
+fn anomaly_kinds(body: &str) -> Vec<AnomalyKind> {
+    detect_response(body.as_bytes(), &AnomalyConfig::default())
+        .into_iter()
+        .map(|a| a.kind)
+        .collect()
+}
+
+#[test]
+fn test_detect_normal_responses() {
+    let openai =
+        r#"{"choices": [{"message": {"role": "assistant", "content": "你好，有什么可以帮你？"}}]}"#;
+    assert!(anomaly_kinds(openai).is_empty());
+
+    let tool_call = r#"{"choices": [{"message": {"content": null, "tool_calls": [
+        {"function": {"name": "read_file", "arguments": "{\"path\": \"a.rs\"}"}}]}}]}"#;
+    assert!(anomaly_kinds(tool_call).is_empty());
+
+    let anthropic = r#"{"content": [{"type": "tool_use", "name": "search", "input": {}}]}"#;
+    assert!(anomaly_kinds(anthropic).is_empty());
+
+    // 分隔线和表格边框的重复不算异常
+    let separator = format!(
+        r#"{{"content": [{{"type": "text", "text": "| a | b |\n{}"}}]}}"#,
+        "-".repeat(80)
+    );
+    assert!(anomaly_kinds(&separator).is_empty());
+}
+
+#[test]
+fn test_detect_degenerate_responses() {
+    assert_eq!(anomaly_kinds("  "), vec![AnomalyKind::EmptyResponse]);
+    assert_eq!(
+        anomaly_kinds(r#"{"choices": [{"message": {"content": ""}}]}"#),
+        vec![AnomalyKind::EmptyResponse]
+    );
+    assert_eq!(
+        anomaly_kinds(r#"{"choices": [{"message": {"content": "ok"#),
+        vec![AnomalyKind::TruncatedJson]
+    );
+    assert_eq!(
+        anomaly_kinds(
+            r#"{"choices": [{"message": {"tool_calls": [
+                {"function": {"name": "write", "arguments": "{\"path\": \"a"}}]}}]}"#
+        ),
+        vec![AnomalyKind::TruncatedJson]
+    );
+
+    let repeated = format!(
+        r#"{{"content": [{{"type": "text", "text": "答案是{}"}}]}}"#,
+        "the the ".repeat(30)
+    );
+    assert_eq!(anomaly_kinds(&repeated), vec![AnomalyKind::RepeatedTokens]);
+
+    let repeated_phrase = format!(
+        r#"{{"candidates": [{{"content": {{"parts": [{{"text": "{}"}}]}}}}]}}"#,
+        "我不知道。".repeat(25)
+    );
+    assert_eq!(
+        anomaly_kinds(&repeated_phrase),
+        vec![AnomalyKind::RepeatedTokens]
+    );
+}
+
+#[test]
+fn test_anomaly_tracker_latency_and_degraded() {
+    let tracker = AnomalyTracker::new(AnomalyConfig {
+        min_latency_samples: 3,
+        latency_min_ms: 1000,
+        degraded_threshold: 2,
+        ..Default::default()
+    });
+    let ok = br#"{"choices": [{"message": {"content": "ok"}}]}"#;
+
+    for _ in 0..3 {
+        let records = tracker.inspect("r", ProviderType::OpenAI, "gpt", None, ok, Some(800));
+        assert!(records.is_empty());
+    }
+    let records = tracker.inspect("r1", ProviderType::OpenAI, "gpt", None, ok, Some(5000));
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].kind, AnomalyKind::LatencySpike);
+    assert!(!tracker.is_degraded(ProviderType::OpenAI));
+
+    tracker.inspect(
+        "r2",
+        ProviderType::OpenAI,
+        "gpt",
+        Some("cred"),
+        b"",
+        Some(900),
+    );
+    assert!(tracker.is_degraded(ProviderType::OpenAI));
+    assert!(!tracker.is_degraded(ProviderType::Claude));
+
+    tracker.inspect("r3", ProviderType::Claude, "claude", None, ok, Some(500));
+    let stats = tracker.provider_stats();
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats[0].provider, ProviderType::OpenAI);
+    assert!(stats[0].degraded);
+    assert_eq!(stats[0].recent_anomalies, 2);
+    assert_eq!(stats[0].by_kind[&AnomalyKind::EmptyResponse], 1);
+    assert_eq!(stats[0].last_anomaly.as_ref().unwrap().request_id, "r2");
+    assert!(!stats[1].degraded);
+    assert_eq!(stats[1].median_latency_ms, Some(500));
+
+    let recent = tracker.recent(Some(ProviderType::OpenAI), 10);
+    assert_eq!(recent.len(), 2);
+    assert_eq!(recent[0].credential_id.as_deref(), Some("cred"));
+}
+
+// ========== 连接复用统计 ==========
+
+#[test]
+fn test_connection_reuse_stats() {
+    let tracker = ConnectionTracker::new();
+    tracker.record_connection("claude_custom", true);
+    tracker.record_connection("claude_custom", false);
+    for _ in 0..3 {
+        tracker.record_request("claude_custom", Some(reqwest::Version::HTTP_2));
+    }
+    tracker.record_request("claude_custom", None);
+    tracker.record_connection("kiro", true);
+    tracker.record_request("kiro", Some(reqwest::Version::HTTP_11));
+
+    let stats = tracker.stats();
+    assert_eq!(stats[0].client, "claude_custom");
+    assert_eq!(stats[0].requests, 4);
+    assert_eq!(stats[0].failed_requests, 1);
+    assert_eq!(stats[0].new_connections, 1);
+    assert_eq!(stats[0].failed_connections, 1);
+    // 一次握手服务了四个请求
+    assert_eq!(stats[0].reused_requests, 3);
+    assert!((stats[0].reuse_rate - 0.75).abs() < f64::EPSILON);
+    assert_eq!(stats[0].http2_responses, 3);
+
+    assert_eq!(stats[1].client, "kiro");
+    assert_eq!(stats[1].reused_requests, 0);
+    assert_eq!(stats[1].http1_responses, 1);
+
+    tracker.clear();
+    assert!(tracker.stats().is_empty());
+}
+
+#[test]
+fn test_connection_stats_without_requests() {
+    let tracker = ConnectionTracker::new();
+    tracker.record_connection("gemini", true);
+    let stats = tracker.stats();
+    assert_eq!(stats[0].reused_requests, 0);
+    assert_eq!(stats[0].reuse_rate, 0.0);
+}
+
+// ========== 管道开销统计 ==========
+
+#[test]
+fn test_pipeline_overhead_percentiles() {
+    let tracker = PipelineOverheadTracker::new(100);
+    for micros in 1..=100 {
+        tracker.record("token_count", std::time::Duration::from_micros(micros));
+    }
+    tracker.record("injection", std::time::Duration::from_micros(5));
+
+    let report = tracker.report();
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[0].step, "token_count");
+    assert_eq!(report[0].count, 100);
+    assert_eq!(report[0].p50_us, 50);
+    assert_eq!(report[0].p95_us, 95);
+    assert_eq!(report[0].max_us, 100);
+    assert_eq!(report[1].step, "injection");
+    assert_eq!(report[1].p95_us, 5);
+
+    tracker.clear();
+    assert!(tracker.report().is_empty());
+}
+
+#[test]
+fn test_pipeline_overhead_window() {
+    // 超出窗口时丢弃最早的样本
+    let tracker = PipelineOverheadTracker::new(10);
+    tracker.record("flow_capture", std::time::Duration::from_millis(50));
+    for _ in 0..10 {
+        tracker.record("flow_capture", std::time::Duration::from_micros(20));
+    }
+    let report = tracker.report();
+    assert_eq!(report[0].count, 10);
+    assert_eq!(report[0].max_us, 20);
+
+    tracker.set_window(3);
+    assert_eq!(tracker.report()[0].count, 3);
+}
+
+#[test]
+fn test_sampling() {
+    assert!(sampled(1.0, "request"));
+    assert!(!sampled(0.0, "request"));
+    // 同一请求的结果固定
+    assert_eq!(sampled(0.5, "request-1"), sampled(0.5, "request-1"));
+
+    let hits = (0..10_000)
+        .filter(|i| sampled(0.1, &format!("request-{}", i)))
+        .count();
+    assert!((800..=1200).contains(&hits), "{}", hits);
+}
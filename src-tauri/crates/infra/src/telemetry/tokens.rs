@@ -497,12 +497,29 @@ impl TokenEstimator {
         total_tokens
     }
 
+    /// 模型使用的编码名称（o200k_base 或 cl100k_base）
+    pub fn encoding_name(model: Option<&str>) -> &'static str {
+        if model.is_some_and(Self::uses_o200k) {
+            "o200k_base"
+        } else {
+            "cl100k_base"
+        }
+    }
+
+    /// 是否为使用 o200k_base 编码的模型（GPT-4o、GPT-4.1、GPT-5、o 系列）
+    fn uses_o200k(model: &str) -> bool {
+        let m = model.to_ascii_lowercase();
+        let m = m.rsplit('/').next().unwrap_or(&m);
+        ["gpt-4o", "gpt-4.1", "gpt-5", "chatgpt-4o", "o1", "o3", "o4"]
+            .iter()
+            .any(|p| m.starts_with(p))
+            || m.contains("gpt-4o")
+    }
+
     /// 根据模型名称选择合适的 BPE 编码器
     fn select_bpe(&self, model: Option<&str>) -> &tiktoken_rs::CoreBPE {
         match model {
-            Some(m) if m.contains("gpt-4o") || m.contains("o1") || m.contains("o3") => {
-                &self.o200k_bpe
-            }
+            Some(m) if Self::uses_o200k(m) => &self.o200k_bpe,
             _ => &self.default_bpe,
         }
     }
@@ -832,6 +849,28 @@ mod token_tests {
         assert!(tokens > 0);
     }
 
+    #[test]
+    fn test_token_estimator_encoding_name() {
+        assert_eq!(
+            TokenEstimator::encoding_name(Some("gpt-4o-mini")),
+            "o200k_base"
+        );
+        assert_eq!(
+            TokenEstimator::encoding_name(Some("openai/gpt-5")),
+            "o200k_base"
+        );
+        assert_eq!(TokenEstimator::encoding_name(Some("o3-mini")), "o200k_base");
+        assert_eq!(
+            TokenEstimator::encoding_name(Some("gpt-4-turbo")),
+            "cl100k_base"
+        );
+        assert_eq!(
+            TokenEstimator::encoding_name(Some("claude-sonnet-4-5")),
+            "cl100k_base"
+        );
+        assert_eq!(TokenEstimator::encoding_name(None), "cl100k_base");
+    }
+
     #[test]
     fn test_token_estimator_estimate_with_model() {
         let estimator = TokenEstimator::new().unwrap();
@@ -5,6 +5,7 @@
 use chrono::{DateTime, Utc};
 use proxycast_core::ProviderType;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 /// 请求状态
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -67,6 +68,21 @@ pub struct RequestLog {
     pub credential_id: Option<String>,
     /// 重试次数
     pub retry_count: u32,
+    /// 请求中的图片数量
+    #[serde(default)]
+    pub image_count: u32,
+    /// 请求中内联图片的总字节数
+    #[serde(default)]
+    pub image_bytes: u64,
+    /// 首 Token 延迟（毫秒，仅流式请求）
+    #[serde(default)]
+    pub first_token_ms: Option<u64>,
+    /// 生成速度（输出 Token / 秒，仅流式请求）
+    #[serde(default)]
+    pub tokens_per_second: Option<f64>,
+    /// 发起请求的客户端密钥 ID（局域网共享时区分成员，使用主密钥时为空）
+    #[serde(default)]
+    pub client_key_id: Option<String>,
 }
 
 impl RequestLog {
@@ -87,6 +103,11 @@ impl RequestLog {
             is_streaming,
             credential_id: None,
             retry_count: 0,
+            image_count: 0,
+            image_bytes: 0,
+            first_token_ms: None,
+            tokens_per_second: None,
+            client_key_id: None,
         }
     }
 
@@ -133,6 +154,32 @@ impl RequestLog {
         self.credential_id = Some(id);
     }
 
+    /// 设置发起请求的客户端密钥 ID
+    pub fn set_client_key_id(&mut self, id: String) {
+        self.client_key_id = Some(id);
+    }
+
+    /// 设置图片负载统计
+    pub fn set_image_payload(&mut self, count: u32, bytes: u64) {
+        self.image_count = count;
+        self.image_bytes = bytes;
+    }
+
+    /// 设置流式时间指标
+    ///
+    /// 生成速度按首个到最后一个增量之间的时间窗口计算，窗口为 0 时不记录
+    pub fn set_stream_timing(
+        &mut self,
+        first_token_ms: u64,
+        last_token_ms: u64,
+        output_tokens: u32,
+    ) {
+        self.first_token_ms = Some(first_token_ms);
+        let window_ms = last_token_ms.saturating_sub(first_token_ms);
+        self.tokens_per_second = (window_ms > 0 && output_tokens > 0)
+            .then(|| output_tokens as f64 * 1000.0 / window_ms as f64);
+    }
+
     /// 增加重试次数
     pub fn increment_retry(&mut self) {
         self.retry_count += 1;
@@ -144,6 +191,25 @@ impl RequestLog {
     }
 }
 
+/// 在日志队列中查找请求（从最新的开始）并设置流式时间指标，返回是否找到
+///
+/// 统计聚合器和请求日志记录器共用
+pub(super) fn update_stream_timing(
+    logs: &mut VecDeque<RequestLog>,
+    id: &str,
+    first_token_ms: u64,
+    last_token_ms: u64,
+    output_tokens: u32,
+) -> bool {
+    match logs.iter_mut().rev().find(|l| l.id == id) {
+        Some(log) => {
+            log.set_stream_timing(first_token_ms, last_token_ms, output_tokens);
+            true
+        }
+        None => false,
+    }
+}
+
 /// 时间范围
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct TimeRange {
@@ -204,6 +270,15 @@ pub struct StatsSummary {
     pub total_output_tokens: u64,
     /// 总 Token 数
     pub total_tokens: u64,
+    /// 平均首 Token 延迟（毫秒）
+    #[serde(default)]
+    pub avg_first_token_ms: Option<f64>,
+    /// P95 首 Token 延迟（毫秒）
+    #[serde(default)]
+    pub p95_first_token_ms: Option<u64>,
+    /// 平均生成速度（输出 Token / 秒）
+    #[serde(default)]
+    pub avg_tokens_per_second: Option<f64>,
 }
 
 impl StatsSummary {
@@ -252,6 +327,17 @@ impl StatsSummary {
             .sum();
         let total_tokens = total_input_tokens + total_output_tokens;
 
+        let mut first_token: Vec<u64> = logs.iter().filter_map(|l| l.first_token_ms).collect();
+        first_token.sort_unstable();
+        let avg_first_token_ms = (!first_token.is_empty())
+            .then(|| first_token.iter().sum::<u64>() as f64 / first_token.len() as f64);
+        let p95_first_token_ms = (!first_token.is_empty())
+            .then(|| first_token[(first_token.len() * 95).div_ceil(100) - 1]);
+
+        let throughput: Vec<f64> = logs.iter().filter_map(|l| l.tokens_per_second).collect();
+        let avg_tokens_per_second = (!throughput.is_empty())
+            .then(|| throughput.iter().sum::<f64>() / throughput.len() as f64);
+
         Self {
             total_requests,
             successful_requests,
@@ -264,6 +350,9 @@ impl StatsSummary {
             total_input_tokens,
             total_output_tokens,
             total_tokens,
+            avg_first_token_ms,
+            p95_first_token_ms,
+            avg_tokens_per_second,
         }
     }
 }
@@ -308,6 +397,18 @@ impl ModelStats {
     }
 }
 
+/// Provider + 模型组合统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProviderModelStats {
+    /// Provider 类型
+    pub provider: Option<ProviderType>,
+    /// 模型名称
+    pub model: String,
+    /// 统计摘要
+    #[serde(flatten)]
+    pub summary: StatsSummary,
+}
+
 #[cfg(test)]
 mod type_tests {
     use super::*;
@@ -443,4 +544,39 @@ mod type_tests {
         assert_eq!(summary.total_input_tokens, 150);
         assert_eq!(summary.total_output_tokens, 75);
     }
+
+    #[test]
+    fn test_stream_timing_stats() {
+        let logs: Vec<RequestLog> = [(300, 1300, 50), (500, 500, 10), (100, 2100, 100)]
+            .iter()
+            .enumerate()
+            .map(|(i, (first, last, output))| {
+                let mut log = RequestLog::new(
+                    i.to_string(),
+                    ProviderType::OpenAI,
+                    "gpt-4o".to_string(),
+                    true,
+                );
+                log.mark_success(*last, 200);
+                log.set_stream_timing(*first, *last, *output);
+                log
+            })
+            .collect();
+
+        assert_eq!(logs[0].tokens_per_second, Some(50.0));
+        assert_eq!(logs[1].tokens_per_second, None);
+
+        let summary = StatsSummary::from_logs(&logs);
+        assert_eq!(summary.avg_first_token_ms, Some(300.0));
+        assert_eq!(summary.p95_first_token_ms, Some(500));
+        assert_eq!(summary.avg_tokens_per_second, Some(50.0));
+
+        let no_stream = StatsSummary::from_logs(&[RequestLog::new(
+            "x".to_string(),
+            ProviderType::OpenAI,
+            "gpt-4o".to_string(),
+            false,
+        )]);
+        assert_eq!(no_stream.avg_first_token_ms, None);
+    }
 }
@@ -0,0 +1,252 @@
+//! Anthropic Messages 协议 Provider
+
+use serde_json::Value;
+
+use crate::error::ProviderError;
+use crate::traits::Provider;
+use crate::types::{
+    HttpMethod, ProviderEndpoint, ProviderRequest, StreamEvent, TokenUsage, WireFormat,
+};
+
+pub const ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com";
+pub const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// 构建完整的 API URL
+/// 智能处理用户输入的 base_url，无论是否带 /v1 都能正确工作
+pub fn build_url(base_url: &str, endpoint: &str) -> String {
+    let base = base_url.trim_end_matches('/');
+    if base.ends_with("/v1") {
+        format!("{}/{}", base, endpoint)
+    } else {
+        format!("{}/v1/{}", base, endpoint)
+    }
+}
+
+/// Anthropic Provider
+#[derive(Debug, Clone, Default)]
+pub struct AnthropicProvider;
+
+impl AnthropicProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Provider for AnthropicProvider {
+    fn id(&self) -> &str {
+        "anthropic"
+    }
+
+    fn wire_format(&self) -> WireFormat {
+        WireFormat::Anthropic
+    }
+
+    fn default_base_url(&self) -> &str {
+        ANTHROPIC_BASE_URL
+    }
+
+    fn build_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+        body: &Value,
+    ) -> Result<ProviderRequest, ProviderError> {
+        if endpoint.api_key.is_empty() {
+            return Err(ProviderError::InvalidEndpoint("缺少 API Key".to_string()));
+        }
+        if !body.is_object() {
+            return Err(ProviderError::InvalidRequest(
+                "请求体必须是 JSON 对象".to_string(),
+            ));
+        }
+
+        let mut headers = vec![
+            ("x-api-key".to_string(), endpoint.api_key.clone()),
+            (
+                "anthropic-version".to_string(),
+                ANTHROPIC_VERSION.to_string(),
+            ),
+            ("Content-Type".to_string(), "application/json".to_string()),
+        ];
+        headers.extend(endpoint.extra_headers.iter().cloned());
+
+        Ok(ProviderRequest {
+            method: HttpMethod::Post,
+            url: build_url(self.base_url(endpoint), "messages"),
+            headers,
+            body: body.clone(),
+        })
+    }
+
+    fn parse_stream_chunk(&self, data: &str) -> Result<Vec<StreamEvent>, ProviderError> {
+        let data = data.trim();
+        if data.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let event: Value =
+            serde_json::from_str(data).map_err(|e| ProviderError::StreamParse(e.to_string()))?;
+        let index = event.get("index").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+
+        let events = match event.get("type").and_then(|t| t.as_str()) {
+            Some("message_start") => event
+                .get("message")
+                .and_then(|m| self.extract_usage(m))
+                .map(StreamEvent::Usage)
+                .into_iter()
+                .collect(),
+            Some("content_block_start") => {
+                let block = event.get("content_block");
+                match block.and_then(|b| b.get("type")).and_then(|t| t.as_str()) {
+                    Some("tool_use") => vec![StreamEvent::ToolCallDelta {
+                        index,
+                        id: block
+                            .and_then(|b| b.get("id"))
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string),
+                        name: block
+                            .and_then(|b| b.get("name"))
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string),
+                        arguments: String::new(),
+                    }],
+                    _ => Vec::new(),
+                }
+            }
+            Some("content_block_delta") => {
+                let delta = event.get("delta");
+                let text = |key: &str| {
+                    delta
+                        .and_then(|d| d.get(key))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                match delta.and_then(|d| d.get("type")).and_then(|t| t.as_str()) {
+                    Some("text_delta") => vec![StreamEvent::TextDelta(text("text"))],
+                    Some("thinking_delta") => vec![StreamEvent::ReasoningDelta(text("thinking"))],
+                    Some("input_json_delta") => vec![StreamEvent::ToolCallDelta {
+                        index,
+                        id: None,
+                        name: None,
+                        arguments: text("partial_json"),
+                    }],
+                    _ => Vec::new(),
+                }
+            }
+            Some("message_delta") => {
+                let mut events = Vec::new();
+                if let Some(reason) = event
+                    .get("delta")
+                    .and_then(|d| d.get("stop_reason"))
+                    .and_then(|v| v.as_str())
+                {
+                    events.push(StreamEvent::Finish(reason.to_string()));
+                }
+                if let Some(usage) = self.extract_usage(&event) {
+                    events.push(StreamEvent::Usage(usage));
+                }
+                events
+            }
+            Some("message_stop") => vec![StreamEvent::Done],
+            Some("error") => {
+                let message = event
+                    .get("error")
+                    .and_then(|e| e.get("message"))
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("未知错误");
+                return Err(ProviderError::Upstream(message.to_string()));
+            }
+            // ping 等事件无需处理
+            _ => Vec::new(),
+        };
+
+        Ok(events)
+    }
+
+    fn extract_usage(&self, response: &Value) -> Option<TokenUsage> {
+        let usage = response.get("usage").filter(|u| u.is_object())?;
+        let get = |key: &str| usage.get(key).and_then(|v| v.as_u64()).map(|v| v as u32);
+
+        Some(TokenUsage {
+            input_tokens: get("input_tokens").unwrap_or(0),
+            output_tokens: get("output_tokens").unwrap_or(0),
+            cache_read_tokens: get("cache_read_input_tokens"),
+        })
+    }
+
+    fn build_models_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+    ) -> Result<ProviderRequest, ProviderError> {
+        if endpoint.api_key.is_empty() {
+            return Err(ProviderError::InvalidEndpoint("缺少 API Key".to_string()));
+        }
+
+        let mut headers = vec![
+            ("x-api-key".to_string(), endpoint.api_key.clone()),
+            (
+                "anthropic-version".to_string(),
+                ANTHROPIC_VERSION.to_string(),
+            ),
+        ];
+        headers.extend(endpoint.extra_headers.iter().cloned());
+
+        Ok(ProviderRequest {
+            method: HttpMethod::Get,
+            url: build_url(self.base_url(endpoint), "models"),
+            headers,
+            body: Value::Null,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_request() {
+        let provider = AnthropicProvider::new();
+        let endpoint =
+            ProviderEndpoint::new("sk-ant").with_base_url("https://proxy.example.com/v1/");
+        let request = provider
+            .build_request(&endpoint, &json!({"model": "claude-sonnet-4"}))
+            .unwrap();
+
+        assert_eq!(request.url, "https://proxy.example.com/v1/messages");
+        assert_eq!(request.header("x-api-key"), Some("sk-ant"));
+        assert_eq!(request.header("anthropic-version"), Some(ANTHROPIC_VERSION));
+    }
+
+    #[test]
+    fn test_parse_stream_events() {
+        let provider = AnthropicProvider::new();
+        let parse = |data: &str| provider.parse_stream_chunk(data).unwrap();
+
+        assert_eq!(
+            parse(
+                r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#
+            ),
+            vec![StreamEvent::TextDelta("Hi".to_string())]
+        );
+        assert_eq!(
+            parse(
+                r#"{"type":"message_delta","delta":{"stop_reason":"end_turn"},"usage":{"output_tokens":7}}"#
+            ),
+            vec![
+                StreamEvent::Finish("end_turn".to_string()),
+                StreamEvent::Usage(TokenUsage {
+                    input_tokens: 0,
+                    output_tokens: 7,
+                    cache_read_tokens: None,
+                }),
+            ]
+        );
+        assert_eq!(parse(r#"{"type":"ping"}"#), Vec::new());
+        assert_eq!(parse(r#"{"type":"message_stop"}"#), vec![StreamEvent::Done]);
+        assert!(provider
+            .parse_stream_chunk(r#"{"type":"error","error":{"message":"overloaded"}}"#)
+            .is_err());
+    }
+}
@@ -0,0 +1,262 @@
+//! 流式响应捕获
+//!
+//! 将上游 SSE 字节流逐块喂给 [`StreamAccumulator`]，由 Provider 解析为事件后
+//! 累积出完整的响应文本、推理内容、工具调用、Token 用量和时间信息。
+//! 累积器本身不做 IO，调用方负责传入每块数据到达时的耗时。
+
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::traits::Provider;
+use crate::types::{StreamEvent, TokenUsage};
+
+/// 捕获到的工具调用
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CapturedToolCall {
+    pub index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub arguments: String,
+}
+
+/// 一次流式响应的完整捕获结果
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StreamCapture {
+    /// 正文
+    pub text: String,
+    /// 推理内容
+    pub reasoning: String,
+    /// 工具调用（按 index 排序）
+    pub tool_calls: Vec<CapturedToolCall>,
+    /// Token 用量（上游未返回时为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+    /// 结束原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finish_reason: Option<String>,
+    /// 是否收到流结束标记（客户端中途断开时为 false）
+    pub completed: bool,
+    /// 收到的 chunk 数
+    pub chunk_count: u32,
+    /// 收到的总字节数
+    pub total_bytes: usize,
+    /// 首字节耗时（毫秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_byte_ms: Option<u64>,
+    /// 首个正文/推理增量耗时（毫秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_token_ms: Option<u64>,
+    /// 最后一个增量耗时（毫秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_token_ms: Option<u64>,
+    /// 每个正文/推理增量到达时的耗时（毫秒）
+    pub delta_offsets_ms: Vec<u64>,
+    /// 解析失败的 data 行数
+    pub parse_errors: u32,
+}
+
+/// 流式响应累积器
+pub struct StreamAccumulator {
+    provider: Arc<dyn Provider>,
+    /// 未以换行结尾的残余数据
+    pending: Vec<u8>,
+    capture: StreamCapture,
+}
+
+impl StreamAccumulator {
+    pub fn new(provider: Arc<dyn Provider>) -> Self {
+        Self {
+            provider,
+            pending: Vec::new(),
+            capture: StreamCapture::default(),
+        }
+    }
+
+    /// 喂入一块数据，`elapsed_ms` 为请求开始到该块到达的耗时
+    pub fn feed(&mut self, bytes: &[u8], elapsed_ms: u64) {
+        self.capture.chunk_count += 1;
+        self.capture.total_bytes += bytes.len();
+        self.capture.first_byte_ms.get_or_insert(elapsed_ms);
+
+        self.pending.extend_from_slice(bytes);
+        let Some(last_newline) = self.pending.iter().rposition(|b| *b == b'\n') else {
+            return;
+        };
+        let complete: Vec<u8> = self.pending.drain(..=last_newline).collect();
+        for line in complete.split(|b| *b == b'\n') {
+            self.process_line(line, elapsed_ms);
+        }
+    }
+
+    /// 结束累积，处理残余数据并返回捕获结果
+    pub fn finish(mut self, elapsed_ms: u64) -> StreamCapture {
+        let rest = std::mem::take(&mut self.pending);
+        self.process_line(&rest, elapsed_ms);
+        self.capture.tool_calls.sort_by_key(|c| c.index);
+        self.capture
+    }
+
+    fn process_line(&mut self, line: &[u8], elapsed_ms: u64) {
+        let line = String::from_utf8_lossy(line);
+        let Some(data) = line.trim().strip_prefix("data:") else {
+            return;
+        };
+        let data = data.trim();
+        if data.is_empty() {
+            return;
+        }
+
+        match self.provider.parse_stream_chunk(data) {
+            Ok(events) => {
+                for event in events {
+                    self.apply_event(event, elapsed_ms);
+                }
+            }
+            Err(_) => self.capture.parse_errors += 1,
+        }
+    }
+
+    fn apply_event(&mut self, event: StreamEvent, elapsed_ms: u64) {
+        let capture = &mut self.capture;
+        match event {
+            StreamEvent::TextDelta(text) => {
+                capture.text.push_str(&text);
+                Self::record_delta(capture, elapsed_ms);
+            }
+            StreamEvent::ReasoningDelta(text) => {
+                capture.reasoning.push_str(&text);
+                Self::record_delta(capture, elapsed_ms);
+            }
+            StreamEvent::ToolCallDelta {
+                index,
+                id,
+                name,
+                arguments,
+            } => {
+                let position = match capture.tool_calls.iter().position(|c| c.index == index) {
+                    Some(position) => position,
+                    None => {
+                        capture.tool_calls.push(CapturedToolCall {
+                            index,
+                            ..Default::default()
+                        });
+                        capture.tool_calls.len() - 1
+                    }
+                };
+                let call = &mut capture.tool_calls[position];
+                if id.is_some() {
+                    call.id = id;
+                }
+                if name.is_some() {
+                    call.name = name;
+                }
+                call.arguments.push_str(&arguments);
+            }
+            StreamEvent::Usage(usage) => {
+                let merged = capture.usage.get_or_insert_with(TokenUsage::default);
+                if usage.input_tokens > 0 {
+                    merged.input_tokens = usage.input_tokens;
+                }
+                if usage.output_tokens > 0 {
+                    merged.output_tokens = usage.output_tokens;
+                }
+                if usage.cache_read_tokens.is_some() {
+                    merged.cache_read_tokens = usage.cache_read_tokens;
+                }
+            }
+            StreamEvent::Finish(reason) => capture.finish_reason = Some(reason),
+            StreamEvent::Done => capture.completed = true,
+        }
+    }
+
+    fn record_delta(capture: &mut StreamCapture, elapsed_ms: u64) {
+        capture.first_token_ms.get_or_insert(elapsed_ms);
+        capture.last_token_ms = Some(elapsed_ms);
+        capture.delta_offsets_ms.push(elapsed_ms);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnthropicProvider, OpenAiCompatibleProvider};
+
+    #[test]
+    fn test_accumulate_openai_stream_across_chunks() {
+        let mut acc = StreamAccumulator::new(Arc::new(OpenAiCompatibleProvider::openai()));
+        acc.feed(
+            b"data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\ndata: {\"choices\":[{\"del",
+            120,
+        );
+        acc.feed(
+            b"ta\":{\"content\":\"lo\"},\"finish_reason\":\"stop\"}]}\n\n",
+            150,
+        );
+        acc.feed(
+            b"data: {\"choices\":[],\"usage\":{\"prompt_tokens\":5,\"completion_tokens\":2}}\n\ndata: [DONE]\n\n",
+            160,
+        );
+        let capture = acc.finish(170);
+
+        assert_eq!(capture.text, "Hello");
+        assert_eq!(capture.finish_reason.as_deref(), Some("stop"));
+        assert!(capture.completed);
+        assert_eq!(capture.chunk_count, 3);
+        assert_eq!(capture.first_byte_ms, Some(120));
+        assert_eq!(capture.first_token_ms, Some(120));
+        assert_eq!(capture.last_token_ms, Some(150));
+        assert_eq!(capture.delta_offsets_ms, vec![120, 150]);
+        let usage = capture.usage.unwrap();
+        assert_eq!((usage.input_tokens, usage.output_tokens), (5, 2));
+    }
+
+    #[test]
+    fn test_accumulate_tool_calls() {
+        let mut acc = StreamAccumulator::new(Arc::new(OpenAiCompatibleProvider::openai()));
+        acc.feed(
+            b"data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",\"function\":{\"name\":\"search\",\"arguments\":\"{\\\"q\\\":\"}}]}}]}\n",
+            10,
+        );
+        acc.feed(
+            b"data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"\\\"rust\\\"}\"}}]}}]}\n",
+            20,
+        );
+        let capture = acc.finish(30);
+
+        assert!(!capture.completed);
+        assert_eq!(capture.tool_calls.len(), 1);
+        assert_eq!(capture.tool_calls[0].id.as_deref(), Some("call_1"));
+        assert_eq!(capture.tool_calls[0].name.as_deref(), Some("search"));
+        assert_eq!(capture.tool_calls[0].arguments, "{\"q\":\"rust\"}");
+    }
+
+    #[test]
+    fn test_anthropic_usage_is_merged() {
+        let mut acc = StreamAccumulator::new(Arc::new(AnthropicProvider::new()));
+        acc.feed(
+            b"event: message_start\ndata: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":12,\"output_tokens\":1}}}\n\n",
+            5,
+        );
+        acc.feed(
+            b"event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}\n\n",
+            8,
+        );
+        acc.feed(
+            b"event: message_delta\ndata: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"end_turn\"},\"usage\":{\"output_tokens\":7}}\n\n",
+            9,
+        );
+        acc.feed(
+            b"event: message_stop\ndata: {\"type\":\"message_stop\"}",
+            10,
+        );
+        let capture = acc.finish(11);
+
+        assert_eq!(capture.text, "Hi");
+        assert!(capture.completed);
+        let usage = capture.usage.unwrap();
+        assert_eq!((usage.input_tokens, usage.output_tokens), (12, 7));
+    }
+}
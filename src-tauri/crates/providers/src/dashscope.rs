@@ -0,0 +1,625 @@
+//! 阿里云百炼 DashScope Provider
+//!
+//! 支持两种接入方式：
+//! - 兼容模式：`/compatible-mode/v1/chat/completions`，OpenAI 兼容协议
+//! - 原生模式：`/api/v1/services/aigc/*/generation`，DashScope 原生协议
+//!
+//! 两种模式的请求体都接受 OpenAI 格式，原生模式在构建请求时转换，
+//! 响应用 [`native_to_openai_response`] 和 [`NativeStreamTranslator`] 转回 OpenAI 格式。
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::error::ProviderError;
+use crate::openai;
+use crate::traits::Provider;
+use crate::types::{ProviderEndpoint, ProviderRequest, StreamEvent, TokenUsage, WireFormat};
+
+pub const DASHSCOPE_COMPATIBLE_BASE_URL: &str = "https://dashscope.aliyuncs.com/compatible-mode/v1";
+pub const DASHSCOPE_NATIVE_BASE_URL: &str = "https://dashscope.aliyuncs.com";
+
+/// 内置模型列表
+pub const DASHSCOPE_MODELS: &[&str] = &[
+    "qwen3-max",
+    "qwen-max",
+    "qwen-plus",
+    "qwen-turbo",
+    "qwen-flash",
+    "qwen-long",
+    "qwen3-coder-plus",
+    "qwq-plus",
+    "qwen-vl-max",
+    "qwen3-235b-a22b",
+];
+
+/// 原生模式下直接放入 `parameters` 的 OpenAI 参数
+const NATIVE_PARAMETERS: &[&str] = &[
+    "temperature",
+    "top_p",
+    "top_k",
+    "max_tokens",
+    "stop",
+    "seed",
+    "presence_penalty",
+    "repetition_penalty",
+    "tools",
+    "tool_choice",
+    "parallel_tool_calls",
+    "enable_thinking",
+    "thinking_budget",
+    "enable_search",
+    "response_format",
+];
+
+/// DashScope 接入方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DashScopeMode {
+    /// OpenAI 兼容模式
+    #[default]
+    Compatible,
+    /// 原生模式
+    Native,
+}
+
+/// DashScope Provider
+#[derive(Debug, Clone)]
+pub struct DashScopeProvider {
+    mode: DashScopeMode,
+}
+
+impl DashScopeProvider {
+    /// 兼容模式，标识与系统 Provider `alibaba` 一致
+    pub fn compatible() -> Self {
+        Self {
+            mode: DashScopeMode::Compatible,
+        }
+    }
+
+    /// 原生模式
+    pub fn native() -> Self {
+        Self {
+            mode: DashScopeMode::Native,
+        }
+    }
+
+    /// 指定接入方式
+    pub fn with_mode(mode: DashScopeMode) -> Self {
+        Self { mode }
+    }
+
+    pub fn mode(&self) -> DashScopeMode {
+        self.mode
+    }
+}
+
+/// Qwen3 开源模型（如 qwen3-235b-a22b）非流式调用时必须关闭思考模式
+fn is_qwen3_open_source(model: &str) -> bool {
+    let model = model.to_lowercase();
+    model.starts_with("qwen3-") && model.split('-').any(|part| part.ends_with('b'))
+}
+
+/// 基础地址是否指向 DashScope（含国际站 dashscope-intl）
+pub fn is_dashscope_url(base_url: &str) -> bool {
+    base_url.to_lowercase().contains("dashscope")
+}
+
+/// 原生接口的根地址
+///
+/// 系统 Provider 配置的是兼容模式地址，切换到原生模式时去掉 `/compatible-mode` 及之后的路径
+fn native_root(base_url: &str) -> &str {
+    let base = base_url.trim_end_matches('/');
+    match base.find("/compatible-mode") {
+        Some(index) => &base[..index],
+        None => base.trim_end_matches("/api/v1"),
+    }
+}
+
+/// 视觉/全模态模型使用 multimodal-generation 接口
+fn is_multimodal(model: &str) -> bool {
+    let model = model.to_lowercase();
+    model.contains("-vl") || model.starts_with("qvq") || model.contains("omni")
+}
+
+/// 兼容模式的请求参数调整
+fn adjust_compatible_body(body: &mut Map<String, Value>) {
+    let stream = body.get("stream").and_then(|v| v.as_bool()) == Some(true);
+    let model = body
+        .get("model")
+        .and_then(|m| m.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    // 流式响应默认不返回用量，需显式开启
+    if stream && !body.contains_key("stream_options") {
+        body.insert("stream_options".to_string(), json!({"include_usage": true}));
+    }
+    if !stream && is_qwen3_open_source(&model) && !body.contains_key("enable_thinking") {
+        body.insert("enable_thinking".to_string(), Value::Bool(false));
+    }
+}
+
+/// 将 OpenAI 请求体转换为原生协议
+fn to_native_body(body: &Map<String, Value>) -> Value {
+    let stream = body.get("stream").and_then(|v| v.as_bool()) == Some(true);
+
+    let mut parameters = Map::new();
+    parameters.insert(
+        "result_format".to_string(),
+        Value::String("message".to_string()),
+    );
+    // 原生流式默认返回累计内容，开启增量输出与 OpenAI 行为保持一致
+    if stream {
+        parameters.insert("incremental_output".to_string(), Value::Bool(true));
+    }
+    for key in NATIVE_PARAMETERS {
+        if let Some(value) = body.get(*key) {
+            parameters.insert(key.to_string(), value.clone());
+        }
+    }
+    if let Some(max_tokens) = body.get("max_completion_tokens") {
+        parameters
+            .entry("max_tokens")
+            .or_insert_with(|| max_tokens.clone());
+    }
+
+    json!({
+        "model": body.get("model").cloned().unwrap_or(Value::Null),
+        "input": {
+            "messages": body.get("messages").cloned().unwrap_or_else(|| json!([])),
+        },
+        "parameters": parameters,
+    })
+}
+
+impl Provider for DashScopeProvider {
+    fn id(&self) -> &str {
+        match self.mode {
+            DashScopeMode::Compatible => "alibaba",
+            DashScopeMode::Native => "alibaba-native",
+        }
+    }
+
+    fn wire_format(&self) -> WireFormat {
+        WireFormat::OpenAi
+    }
+
+    fn default_base_url(&self) -> &str {
+        match self.mode {
+            DashScopeMode::Compatible => DASHSCOPE_COMPATIBLE_BASE_URL,
+            DashScopeMode::Native => DASHSCOPE_NATIVE_BASE_URL,
+        }
+    }
+
+    fn build_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+        body: &Value,
+    ) -> Result<ProviderRequest, ProviderError> {
+        let mut request = openai::build_chat_request(self.base_url(endpoint), endpoint, body)?;
+        let Some(body) = request.body.as_object_mut() else {
+            return Ok(request);
+        };
+
+        match self.mode {
+            DashScopeMode::Compatible => adjust_compatible_body(body),
+            DashScopeMode::Native => {
+                let model = body
+                    .get("model")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or_default();
+                let service = if is_multimodal(model) {
+                    "multimodal-generation"
+                } else {
+                    "text-generation"
+                };
+                let base = native_root(self.base_url(endpoint));
+                request.url = format!("{}/api/v1/services/aigc/{}/generation", base, service);
+                if body.get("stream").and_then(|v| v.as_bool()) == Some(true) {
+                    request
+                        .headers
+                        .push(("X-DashScope-SSE".to_string(), "enable".to_string()));
+                }
+                request.body = to_native_body(body);
+            }
+        }
+
+        Ok(request)
+    }
+
+    fn parse_stream_chunk(&self, data: &str) -> Result<Vec<StreamEvent>, ProviderError> {
+        if self.mode == DashScopeMode::Compatible {
+            return openai::parse_chat_chunk(data);
+        }
+
+        let data = data.trim();
+        if data.is_empty() {
+            return Ok(Vec::new());
+        }
+        let chunk: Value =
+            serde_json::from_str(data).map_err(|e| ProviderError::StreamParse(e.to_string()))?;
+        if let Some(code) = chunk.get("code").and_then(|c| c.as_str()) {
+            let message = chunk
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or_default();
+            return Err(ProviderError::Upstream(format!("{}: {}", code, message)));
+        }
+
+        let mut events = Vec::new();
+        if let Some(choice) = chunk.pointer("/output/choices/0").filter(|c| c.is_object()) {
+            // 原生消息结构与 OpenAI delta 一致，复用兼容模式的解析
+            let compat =
+                json!({"choices": [{"delta": choice.get("message").cloned().unwrap_or_default()}]});
+            events.extend(openai::parse_chat_chunk(&compat.to_string())?);
+
+            // 未结束时 finish_reason 为字符串 "null"
+            if let Some(reason) = choice
+                .get("finish_reason")
+                .and_then(|v| v.as_str())
+                .filter(|r| *r != "null")
+            {
+                events.push(StreamEvent::Finish(reason.to_string()));
+            }
+        }
+        if let Some(usage) = self.extract_usage(&chunk) {
+            events.push(StreamEvent::Usage(usage));
+        }
+
+        Ok(events)
+    }
+
+    fn extract_usage(&self, response: &Value) -> Option<TokenUsage> {
+        if self.mode == DashScopeMode::Compatible {
+            return openai::extract_chat_usage(response);
+        }
+
+        let usage = response.get("usage").filter(|u| u.is_object())?;
+        let get = |key: &str| usage.get(key).and_then(|v| v.as_u64()).map(|v| v as u32);
+        Some(TokenUsage {
+            input_tokens: get("input_tokens").unwrap_or(0),
+            output_tokens: get("output_tokens").unwrap_or(0),
+            cache_read_tokens: usage
+                .pointer("/prompt_tokens_details/cached_tokens")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u32),
+        })
+    }
+
+    fn build_models_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+    ) -> Result<ProviderRequest, ProviderError> {
+        // 模型列表只在兼容模式下提供
+        let base = match self.mode {
+            DashScopeMode::Compatible => self.base_url(endpoint).to_string(),
+            DashScopeMode::Native => {
+                format!(
+                    "{}/compatible-mode/v1",
+                    native_root(self.base_url(endpoint))
+                )
+            }
+        };
+        openai::build_models_request(&base, endpoint)
+    }
+
+    fn builtin_models(&self) -> &[&str] {
+        DASHSCOPE_MODELS
+    }
+}
+
+/// 原生响应中的 `finish_reason`，未结束时为字符串 "null"
+fn native_finish_reason(choice: &Value) -> Value {
+    choice
+        .get("finish_reason")
+        .and_then(|v| v.as_str())
+        .filter(|r| *r != "null")
+        .map(|r| Value::String(r.to_string()))
+        .unwrap_or(Value::Null)
+}
+
+/// 原生用量转换为 OpenAI `usage`
+fn native_usage_to_openai(usage: &Value) -> Option<Value> {
+    let usage = usage.as_object()?;
+    let get = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    let (input, output) = (get("input_tokens"), get("output_tokens"));
+    Some(json!({
+        "prompt_tokens": input,
+        "completion_tokens": output,
+        "total_tokens": usage
+            .get("total_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(input + output),
+    }))
+}
+
+/// 原生非流式响应转换为 OpenAI `chat.completion`
+pub fn native_to_openai_response(response: &Value, model: &str) -> Value {
+    let choices: Vec<Value> = response
+        .pointer("/output/choices")
+        .and_then(|c| c.as_array())
+        .map(|choices| {
+            choices
+                .iter()
+                .enumerate()
+                .map(|(index, choice)| {
+                    json!({
+                        "index": index,
+                        "message": choice.get("message").cloned().unwrap_or_else(|| json!({"role": "assistant", "content": ""})),
+                        "finish_reason": native_finish_reason(choice),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut converted = json!({
+        "id": response.get("request_id").cloned().unwrap_or(Value::Null),
+        "object": "chat.completion",
+        "model": model,
+        "choices": choices,
+    });
+    if let Some(usage) = response.get("usage").and_then(native_usage_to_openai) {
+        converted["usage"] = usage;
+    }
+    converted
+}
+
+/// 原生流式 chunk 转换为 OpenAI `chat.completion.chunk`
+///
+/// 原生流式每个 chunk 都带累计用量，只在结束 chunk 上输出，与 OpenAI 的行为一致
+pub fn native_to_openai_chunk(chunk: &Value, model: &str) -> Value {
+    let mut finished = false;
+    let choices: Vec<Value> = chunk
+        .pointer("/output/choices")
+        .and_then(|c| c.as_array())
+        .map(|choices| {
+            choices
+                .iter()
+                .enumerate()
+                .map(|(index, choice)| {
+                    let finish_reason = native_finish_reason(choice);
+                    finished |= !finish_reason.is_null();
+                    json!({
+                        "index": index,
+                        "delta": choice.get("message").cloned().unwrap_or_else(|| json!({})),
+                        "finish_reason": finish_reason,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut converted = json!({
+        "id": chunk.get("request_id").cloned().unwrap_or(Value::Null),
+        "object": "chat.completion.chunk",
+        "model": model,
+        "choices": choices,
+    });
+    if finished {
+        if let Some(usage) = chunk.get("usage").and_then(native_usage_to_openai) {
+            converted["usage"] = usage;
+        }
+    }
+    converted
+}
+
+/// 将原生 SSE 字节流改写为 OpenAI SSE
+///
+/// 原生 SSE 每个事件带 `id:`、`event:` 和 `:HTTP_STATUS` 行，只保留 `data:` 负载并转换格式；
+/// 不完整的行会缓存到下一次调用，流结束时调用 [`NativeStreamTranslator::finish`]。
+#[derive(Debug, Clone)]
+pub struct NativeStreamTranslator {
+    model: String,
+    /// 未读完整行的 SSE 数据
+    pending: Vec<u8>,
+}
+
+impl NativeStreamTranslator {
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// 改写 SSE 字节流片段，返回可转发的数据
+    pub fn translate(&mut self, bytes: &[u8]) -> Vec<u8> {
+        self.pending.extend_from_slice(bytes);
+        let Some(last_newline) = self.pending.iter().rposition(|b| *b == b'\n') else {
+            return Vec::new();
+        };
+        let complete: Vec<u8> = self.pending.drain(..=last_newline).collect();
+
+        let mut output = Vec::new();
+        for line in complete.split(|b| *b == b'\n') {
+            output.extend_from_slice(&self.translate_line(line));
+        }
+        output
+    }
+
+    /// 输出缓存的剩余数据并结束流
+    pub fn finish(&mut self) -> Vec<u8> {
+        let rest = std::mem::take(&mut self.pending);
+        let mut output = self.translate_line(&rest);
+        output.extend_from_slice(b"data: [DONE]\n\n");
+        output
+    }
+
+    fn translate_line(&self, line: &[u8]) -> Vec<u8> {
+        let line = String::from_utf8_lossy(line);
+        let Some(data) = line.trim().strip_prefix("data:") else {
+            return Vec::new();
+        };
+        let Ok(chunk) = serde_json::from_str::<Value>(data.trim()) else {
+            return Vec::new();
+        };
+
+        let converted = match chunk.get("code").and_then(|c| c.as_str()) {
+            Some(code) => json!({
+                "error": {
+                    "code": code,
+                    "message": chunk.get("message").cloned().unwrap_or(Value::Null),
+                }
+            }),
+            None => native_to_openai_chunk(&chunk, &self.model),
+        };
+        format!("data: {}\n\n", converted).into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::openai::OpenAiCompatibleProvider;
+
+    #[test]
+    fn test_compatible_request_quirks() {
+        let provider = DashScopeProvider::compatible();
+        let endpoint = ProviderEndpoint::new("sk-ds");
+
+        let request = provider
+            .build_request(&endpoint, &json!({"model": "qwen-plus", "stream": true}))
+            .unwrap();
+        assert_eq!(
+            request.url,
+            "https://dashscope.aliyuncs.com/compatible-mode/v1/chat/completions"
+        );
+        assert_eq!(request.body["stream_options"]["include_usage"], true);
+
+        let request = provider
+            .build_request(&endpoint, &json!({"model": "qwen3-235b-a22b"}))
+            .unwrap();
+        assert_eq!(request.body["enable_thinking"], false);
+    }
+
+    #[test]
+    fn test_native_request() {
+        let provider = DashScopeProvider::native();
+        let body = json!({
+            "model": "qwen-vl-max",
+            "stream": true,
+            "temperature": 0.3,
+            "messages": [{"role": "user", "content": "你好"}],
+        });
+        let request = provider
+            .build_request(&ProviderEndpoint::new("sk-ds"), &body)
+            .unwrap();
+
+        assert_eq!(
+            request.url,
+            "https://dashscope.aliyuncs.com/api/v1/services/aigc/multimodal-generation/generation"
+        );
+        assert_eq!(request.header("x-dashscope-sse"), Some("enable"));
+        assert_eq!(request.body["input"]["messages"][0]["content"], "你好");
+        assert_eq!(request.body["parameters"]["incremental_output"], true);
+        assert_eq!(request.body["parameters"]["temperature"], json!(0.3));
+    }
+
+    #[test]
+    fn test_native_stream_chunk() {
+        let provider = DashScopeProvider::native();
+        let events = provider
+            .parse_stream_chunk(
+                r#"{"output":{"choices":[{"message":{"role":"assistant","content":"你"},"finish_reason":"null"}]},"usage":{"input_tokens":5,"output_tokens":1}}"#,
+            )
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![
+                StreamEvent::TextDelta("你".to_string()),
+                StreamEvent::Usage(TokenUsage {
+                    input_tokens: 5,
+                    output_tokens: 1,
+                    cache_read_tokens: None,
+                }),
+            ]
+        );
+
+        assert!(provider
+            .parse_stream_chunk(r#"{"code":"InvalidApiKey","message":"Invalid API-key"}"#)
+            .is_err());
+    }
+
+    #[test]
+    fn test_native_root_from_compatible_base_url() {
+        let provider = DashScopeProvider::with_mode(DashScopeMode::Native);
+        let endpoint = ProviderEndpoint::new("sk-ds")
+            .with_base_url("https://dashscope-intl.aliyuncs.com/compatible-mode/v1/");
+        let request = provider
+            .build_request(&endpoint, &json!({"model": "qwen-plus"}))
+            .unwrap();
+
+        assert_eq!(
+            request.url,
+            "https://dashscope-intl.aliyuncs.com/api/v1/services/aigc/text-generation/generation"
+        );
+        assert_eq!(request.header("x-dashscope-sse"), None);
+        assert!(request.body["parameters"]
+            .get("incremental_output")
+            .is_none());
+        assert!(is_dashscope_url(endpoint.base_url.as_deref().unwrap()));
+        assert!(!is_dashscope_url("https://api.deepseek.com/v1"));
+    }
+
+    #[test]
+    fn test_native_response_to_openai() {
+        let response = json!({
+            "request_id": "req-1",
+            "output": {"choices": [{"message": {"role": "assistant", "content": "你好"}, "finish_reason": "stop"}]},
+            "usage": {"input_tokens": 5, "output_tokens": 2, "total_tokens": 7},
+        });
+        let converted = native_to_openai_response(&response, "qwen-plus");
+
+        assert_eq!(converted["object"], "chat.completion");
+        assert_eq!(converted["choices"][0]["message"]["content"], "你好");
+        assert_eq!(converted["choices"][0]["finish_reason"], "stop");
+        assert_eq!(converted["usage"]["prompt_tokens"], 5);
+        assert_eq!(converted["usage"]["total_tokens"], 7);
+        assert_eq!(
+            OpenAiCompatibleProvider::openai().extract_usage(&converted),
+            DashScopeProvider::native().extract_usage(&response)
+        );
+    }
+
+    #[test]
+    fn test_native_stream_translator() {
+        let mut translator = NativeStreamTranslator::new("qwen-plus");
+        let first = concat!(
+            "id:1\nevent:result\n:HTTP_STATUS/200\n",
+            r#"data:{"output":{"choices":[{"message":{"role":"assistant","content":"你"},"finish_reason":"null"}]},"usage":{"input_tokens":5,"output_tokens":1}}"#,
+            "\n\nid:2\nevent:result\n",
+            r#"data:{"output":{"choices":[{"message":{"role":"assistant","content":"好"},"finish_reason":"stop"}]},"#,
+        );
+        let output = String::from_utf8(translator.translate(first.as_bytes())).unwrap();
+        let events: Vec<&str> = output.split("\n\n").filter(|s| !s.is_empty()).collect();
+        assert_eq!(events.len(), 1);
+        let chunk: Value = serde_json::from_str(events[0].strip_prefix("data: ").unwrap()).unwrap();
+        assert_eq!(chunk["choices"][0]["delta"]["content"], "你");
+        assert!(chunk["choices"][0]["finish_reason"].is_null());
+        assert!(chunk.get("usage").is_none());
+
+        let rest = translator.translate(br#""usage":{"input_tokens":5,"output_tokens":2}}"#);
+        assert!(rest.is_empty());
+        let output = String::from_utf8(translator.finish()).unwrap();
+        let events: Vec<&str> = output.split("\n\n").filter(|s| !s.is_empty()).collect();
+        assert_eq!(events[1], "data: [DONE]");
+
+        // 转换后的 chunk 可以按兼容模式解析
+        let events = DashScopeProvider::compatible()
+            .parse_stream_chunk(events[0].strip_prefix("data: ").unwrap())
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![
+                StreamEvent::TextDelta("好".to_string()),
+                StreamEvent::Finish("stop".to_string()),
+                StreamEvent::Usage(TokenUsage {
+                    input_tokens: 5,
+                    output_tokens: 2,
+                    cache_read_tokens: None,
+                }),
+            ]
+        );
+    }
+}
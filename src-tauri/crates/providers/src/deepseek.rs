@@ -0,0 +1,144 @@
+//! DeepSeek Provider
+//!
+//! DeepSeek 使用 OpenAI 兼容协议，推理模型（deepseek-reasoner）会在流式响应的
+//! `delta.reasoning_content` 中输出思考过程，由 [`crate::ReasoningFilter`]
+//! 按配置的策略处理。
+
+use serde_json::Value;
+
+use crate::error::ProviderError;
+use crate::openai;
+use crate::shaping::{self, ParamLimits};
+use crate::traits::Provider;
+use crate::types::{ProviderEndpoint, ProviderRequest, StreamEvent, TokenUsage, WireFormat};
+
+pub const DEEPSEEK_BASE_URL: &str = "https://api.deepseek.com";
+
+/// 内置模型列表
+pub const DEEPSEEK_MODELS: &[&str] = &["deepseek-chat", "deepseek-reasoner"];
+
+/// 推理模型不支持的参数：采样参数传入后不生效，logprobs 相关参数会直接报错
+const REASONER_UNSUPPORTED_PARAMS: &[&str] = &[
+    "temperature",
+    "top_p",
+    "presence_penalty",
+    "frequency_penalty",
+    "logprobs",
+    "top_logprobs",
+];
+
+/// 是否为 DeepSeek 推理模型
+pub fn is_reasoner_model(model: &str) -> bool {
+    let model = model.to_lowercase();
+    model.contains("reasoner") || model.contains("deepseek-r1")
+}
+
+/// DeepSeek Provider
+#[derive(Debug, Clone, Default)]
+pub struct DeepSeekProvider;
+
+impl DeepSeekProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Provider for DeepSeekProvider {
+    fn id(&self) -> &str {
+        "deepseek"
+    }
+
+    fn wire_format(&self) -> WireFormat {
+        WireFormat::OpenAi
+    }
+
+    fn default_base_url(&self) -> &str {
+        DEEPSEEK_BASE_URL
+    }
+
+    fn build_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+        body: &Value,
+    ) -> Result<ProviderRequest, ProviderError> {
+        let mut request = openai::build_chat_request(self.base_url(endpoint), endpoint, body)?;
+        let model = request
+            .body
+            .get("model")
+            .and_then(|m| m.as_str())
+            .unwrap_or_default()
+            .to_string();
+        shaping::apply(&mut request.body, &self.param_limits(&model));
+        Ok(request)
+    }
+
+    fn param_limits(&self, model: &str) -> ParamLimits {
+        let mut limits = ParamLimits::for_wire_format(self.wire_format());
+        if is_reasoner_model(model) {
+            limits
+                .unsupported
+                .extend_from_slice(REASONER_UNSUPPORTED_PARAMS);
+        }
+        limits
+    }
+
+    fn parse_stream_chunk(&self, data: &str) -> Result<Vec<StreamEvent>, ProviderError> {
+        openai::parse_chat_chunk(data)
+    }
+
+    fn extract_usage(&self, response: &Value) -> Option<TokenUsage> {
+        openai::extract_chat_usage(response)
+    }
+
+    fn build_models_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+    ) -> Result<ProviderRequest, ProviderError> {
+        openai::build_models_request(self.base_url(endpoint), endpoint)
+    }
+
+    fn builtin_models(&self) -> &[&str] {
+        DEEPSEEK_MODELS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_reasoner_drops_unsupported_params() {
+        let provider = DeepSeekProvider::new();
+        let endpoint = ProviderEndpoint::new("sk-ds");
+        let body = json!({"model": "deepseek-reasoner", "temperature": 0.7, "logprobs": true, "messages": []});
+
+        let request = provider.build_request(&endpoint, &body).unwrap();
+        assert_eq!(request.url, "https://api.deepseek.com/v1/chat/completions");
+        assert!(request.body.get("temperature").is_none());
+        assert!(request.body.get("logprobs").is_none());
+
+        let body = json!({"model": "deepseek-chat", "temperature": 0.7});
+        let request = provider.build_request(&endpoint, &body).unwrap();
+        assert_eq!(request.body["temperature"], json!(0.7));
+    }
+
+    #[test]
+    fn test_parse_reasoning_chunk_and_cache_usage() {
+        let provider = DeepSeekProvider::new();
+        let events = provider
+            .parse_stream_chunk(
+                r#"{"choices":[{"delta":{"content":null,"reasoning_content":"先分析"},"finish_reason":null}]}"#,
+            )
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![StreamEvent::ReasoningDelta("先分析".to_string())]
+        );
+
+        let usage = provider
+            .extract_usage(&json!({"usage": {"prompt_tokens": 20, "completion_tokens": 8, "prompt_cache_hit_tokens": 16}}))
+            .unwrap();
+        assert_eq!(usage.cache_read_tokens, Some(16));
+    }
+}
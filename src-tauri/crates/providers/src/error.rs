@@ -0,0 +1,31 @@
+//! Provider 错误类型
+
+use thiserror::Error;
+
+/// Provider 请求构建与响应解析错误
+#[derive(Debug, Error)]
+pub enum ProviderError {
+    /// 端点配置无效（如缺少 API Key）
+    #[error("端点配置无效: {0}")]
+    InvalidEndpoint(String),
+
+    /// 请求体无效
+    #[error("请求体无效: {0}")]
+    InvalidRequest(String),
+
+    /// 流式数据解析失败
+    #[error("流式数据解析失败: {0}")]
+    StreamParse(String),
+
+    /// 上游在流中返回的错误
+    #[error("上游错误: {0}")]
+    Upstream(String),
+
+    /// Provider 不支持该操作
+    #[error("Provider {0} 不支持该操作: {1}")]
+    Unsupported(String, String),
+
+    /// 未注册的 Provider
+    #[error("未知的 Provider: {0}")]
+    UnknownProvider(String),
+}
@@ -0,0 +1,48 @@
+//! Provider 请求构建模块
+//!
+//! 将各上游 Provider 的协议差异（请求 URL、认证头、参数调整、流式响应解析、
+//! Token 用量提取）集中到 [`Provider`] trait 中，处理管道只依赖该 trait：
+//! - openai: OpenAI 兼容协议
+//! - anthropic: Anthropic Messages 协议
+//! - capture: 流式响应捕获（边转发边累积完整响应）
+//! - dashscope: 阿里云百炼（兼容模式与原生模式）
+//! - deepseek: DeepSeek（含推理模型）
+//! - moonshot: Moonshot（Kimi）
+//! - zhipu: 智谱 BigModel（GLM，JWT 认证）
+//! - reasoning: 推理内容处理策略
+//! - shaping: 请求参数整形（截断超出范围的参数、移除不支持的参数）
+//! - registry: Provider 注册表
+//!
+//! 新增 Provider 只需实现 [`Provider`] 并注册到 [`ProviderRegistry`]，
+//! 无需修改处理管道。
+
+pub mod anthropic;
+pub mod capture;
+pub mod dashscope;
+pub mod deepseek;
+pub mod error;
+pub mod moonshot;
+pub mod openai;
+pub mod reasoning;
+pub mod registry;
+pub mod shaping;
+pub mod traits;
+pub mod types;
+pub mod zhipu;
+
+// 重新导出常用类型
+pub use anthropic::AnthropicProvider;
+pub use capture::{CapturedToolCall, StreamAccumulator, StreamCapture};
+pub use dashscope::{DashScopeMode, DashScopeProvider, NativeStreamTranslator};
+pub use deepseek::DeepSeekProvider;
+pub use error::ProviderError;
+pub use moonshot::MoonshotProvider;
+pub use openai::OpenAiCompatibleProvider;
+pub use reasoning::{ReasoningFilter, ReasoningPolicy};
+pub use registry::ProviderRegistry;
+pub use shaping::{ParamAdjustment, ParamLimits, ParamRange};
+pub use traits::Provider;
+pub use types::{
+    HttpMethod, ProviderEndpoint, ProviderRequest, StreamEvent, TokenUsage, WireFormat,
+};
+pub use zhipu::ZhipuProvider;
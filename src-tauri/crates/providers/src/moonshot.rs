@@ -0,0 +1,144 @@
+//! Moonshot（Kimi）Provider
+//!
+//! OpenAI 兼容协议，差异：
+//! - `temperature` 取值范围为 [0, 1]
+//! - 流式响应的用量位于最后一个 chunk 的 `choices[0].usage`
+
+use serde_json::Value;
+
+use crate::error::ProviderError;
+use crate::openai;
+use crate::shaping::{self, ParamLimits, ParamRange};
+use crate::traits::Provider;
+use crate::types::{ProviderEndpoint, ProviderRequest, StreamEvent, TokenUsage, WireFormat};
+
+pub const MOONSHOT_BASE_URL: &str = "https://api.moonshot.cn/v1";
+
+/// 内置模型列表
+pub const MOONSHOT_MODELS: &[&str] = &[
+    "kimi-k2-0905-preview",
+    "kimi-k2-turbo-preview",
+    "kimi-k2-thinking",
+    "kimi-k2-thinking-turbo",
+    "moonshot-v1-8k",
+    "moonshot-v1-32k",
+    "moonshot-v1-128k",
+];
+
+/// Moonshot Provider，标识与系统 Provider `moonshotai` 一致
+#[derive(Debug, Clone, Default)]
+pub struct MoonshotProvider;
+
+impl MoonshotProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Provider for MoonshotProvider {
+    fn id(&self) -> &str {
+        "moonshotai"
+    }
+
+    fn wire_format(&self) -> WireFormat {
+        WireFormat::OpenAi
+    }
+
+    fn default_base_url(&self) -> &str {
+        MOONSHOT_BASE_URL
+    }
+
+    fn build_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+        body: &Value,
+    ) -> Result<ProviderRequest, ProviderError> {
+        let mut request = openai::build_chat_request(self.base_url(endpoint), endpoint, body)?;
+        shaping::apply(&mut request.body, &self.param_limits(""));
+        Ok(request)
+    }
+
+    fn param_limits(&self, _model: &str) -> ParamLimits {
+        ParamLimits {
+            temperature: Some(ParamRange::new(0.0, 1.0)),
+            ..ParamLimits::for_wire_format(self.wire_format())
+        }
+    }
+
+    fn parse_stream_chunk(&self, data: &str) -> Result<Vec<StreamEvent>, ProviderError> {
+        let mut events = openai::parse_chat_chunk(data)?;
+        if events.iter().any(|e| matches!(e, StreamEvent::Usage(_))) {
+            return Ok(events);
+        }
+
+        if let Some(choice) = serde_json::from_str::<Value>(data.trim())
+            .ok()
+            .and_then(|chunk| chunk.pointer("/choices/0").cloned())
+        {
+            if let Some(usage) = self.extract_usage(&choice) {
+                events.push(StreamEvent::Usage(usage));
+            }
+        }
+        Ok(events)
+    }
+
+    fn extract_usage(&self, response: &Value) -> Option<TokenUsage> {
+        let mut usage = openai::extract_chat_usage(response)?;
+        if usage.cache_read_tokens.is_none() {
+            usage.cache_read_tokens = response
+                .pointer("/usage/cached_tokens")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u32);
+        }
+        Some(usage)
+    }
+
+    fn build_models_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+    ) -> Result<ProviderRequest, ProviderError> {
+        openai::build_models_request(self.base_url(endpoint), endpoint)
+    }
+
+    fn builtin_models(&self) -> &[&str] {
+        MOONSHOT_MODELS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_temperature_clamped() {
+        let request = MoonshotProvider::new()
+            .build_request(
+                &ProviderEndpoint::new("sk-moon"),
+                &json!({"model": "kimi-k2-0905-preview", "temperature": 1.6}),
+            )
+            .unwrap();
+        assert_eq!(request.url, "https://api.moonshot.cn/v1/chat/completions");
+        assert_eq!(request.body["temperature"], json!(1.0));
+    }
+
+    #[test]
+    fn test_usage_inside_choice() {
+        let events = MoonshotProvider::new()
+            .parse_stream_chunk(
+                r#"{"choices":[{"index":0,"delta":{},"finish_reason":"stop","usage":{"prompt_tokens":19,"completion_tokens":13,"cached_tokens":10}}]}"#,
+            )
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![
+                StreamEvent::Finish("stop".to_string()),
+                StreamEvent::Usage(TokenUsage {
+                    input_tokens: 19,
+                    output_tokens: 13,
+                    cache_read_tokens: Some(10),
+                }),
+            ]
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! OpenAI 兼容协议 Provider
+
+use serde_json::Value;
+
+use crate::error::ProviderError;
+use crate::traits::Provider;
+use crate::types::{
+    HttpMethod, ProviderEndpoint, ProviderRequest, StreamEvent, TokenUsage, WireFormat,
+};
+
+pub const OPENAI_BASE_URL: &str = "https://api.openai.com";
+
+/// 构建完整的 API URL
+/// 智能处理用户输入的 base_url，支持多种 API 版本格式
+///
+/// 支持的格式：
+/// - `https://api.openai.com` -> `https://api.openai.com/v1/chat/completions`
+/// - `https://api.openai.com/v1` -> `https://api.openai.com/v1/chat/completions`
+/// - `https://open.bigmodel.cn/api/paas/v4` -> `https://open.bigmodel.cn/api/paas/v4/chat/completions`
+pub fn build_url(base_url: &str, endpoint: &str) -> String {
+    let base = base_url.trim_end_matches('/');
+
+    // 检查是否已经包含版本号路径（/v1, /v2, /v3, /v4 等）
+    let has_version = base
+        .rsplit('/')
+        .next()
+        .map(|last_segment| {
+            last_segment.starts_with('v')
+                && last_segment.len() >= 2
+                && last_segment[1..].chars().all(|c| c.is_ascii_digit())
+        })
+        .unwrap_or(false);
+
+    if has_version {
+        format!("{}/{}", base, endpoint)
+    } else {
+        format!("{}/v1/{}", base, endpoint)
+    }
+}
+
+/// OpenAI 兼容 Provider
+///
+/// 大部分国内外厂商都提供 OpenAI 兼容接口，只需不同的标识和默认地址。
+#[derive(Debug, Clone)]
+pub struct OpenAiCompatibleProvider {
+    id: String,
+    default_base_url: String,
+}
+
+impl OpenAiCompatibleProvider {
+    pub fn new(id: impl Into<String>, default_base_url: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            default_base_url: default_base_url.into(),
+        }
+    }
+
+    /// OpenAI 官方
+    pub fn openai() -> Self {
+        Self::new("openai", OPENAI_BASE_URL)
+    }
+}
+
+impl Provider for OpenAiCompatibleProvider {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn wire_format(&self) -> WireFormat {
+        WireFormat::OpenAi
+    }
+
+    fn default_base_url(&self) -> &str {
+        &self.default_base_url
+    }
+
+    fn build_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+        body: &Value,
+    ) -> Result<ProviderRequest, ProviderError> {
+        build_chat_request(self.base_url(endpoint), endpoint, body)
+    }
+
+    fn parse_stream_chunk(&self, data: &str) -> Result<Vec<StreamEvent>, ProviderError> {
+        parse_chat_chunk(data)
+    }
+
+    fn extract_usage(&self, response: &Value) -> Option<TokenUsage> {
+        extract_chat_usage(response)
+    }
+
+    fn build_models_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+    ) -> Result<ProviderRequest, ProviderError> {
+        build_models_request(self.base_url(endpoint), endpoint)
+    }
+}
+
+/// 构建 OpenAI 兼容的模型列表请求
+pub fn build_models_request(
+    base_url: &str,
+    endpoint: &ProviderEndpoint,
+) -> Result<ProviderRequest, ProviderError> {
+    if endpoint.api_key.is_empty() {
+        return Err(ProviderError::InvalidEndpoint("缺少 API Key".to_string()));
+    }
+
+    let mut headers = vec![(
+        "Authorization".to_string(),
+        format!("Bearer {}", endpoint.api_key),
+    )];
+    headers.extend(endpoint.extra_headers.iter().cloned());
+
+    Ok(ProviderRequest {
+        method: HttpMethod::Get,
+        url: build_url(base_url, "models"),
+        headers,
+        body: Value::Null,
+    })
+}
+
+/// 构建 OpenAI 兼容的聊天请求（Bearer 认证）
+pub fn build_chat_request(
+    base_url: &str,
+    endpoint: &ProviderEndpoint,
+    body: &Value,
+) -> Result<ProviderRequest, ProviderError> {
+    if endpoint.api_key.is_empty() {
+        return Err(ProviderError::InvalidEndpoint("缺少 API Key".to_string()));
+    }
+    if !body.is_object() {
+        return Err(ProviderError::InvalidRequest(
+            "请求体必须是 JSON 对象".to_string(),
+        ));
+    }
+
+    let mut headers = vec![
+        (
+            "Authorization".to_string(),
+            format!("Bearer {}", endpoint.api_key),
+        ),
+        ("Content-Type".to_string(), "application/json".to_string()),
+    ];
+    headers.extend(endpoint.extra_headers.iter().cloned());
+
+    Ok(ProviderRequest {
+        method: HttpMethod::Post,
+        url: build_url(base_url, "chat/completions"),
+        headers,
+        body: body.clone(),
+    })
+}
+
+/// 解析 OpenAI 兼容的流式数据块
+pub fn parse_chat_chunk(data: &str) -> Result<Vec<StreamEvent>, ProviderError> {
+    let data = data.trim();
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+    if data == "[DONE]" {
+        return Ok(vec![StreamEvent::Done]);
+    }
+
+    let chunk: Value =
+        serde_json::from_str(data).map_err(|e| ProviderError::StreamParse(e.to_string()))?;
+    if let Some(error) = chunk.get("error") {
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string());
+        return Err(ProviderError::Upstream(message));
+    }
+
+    let mut events = Vec::new();
+    if let Some(choice) = chunk
+        .get("choices")
+        .and_then(|c| c.as_array())
+        .and_then(|c| c.first())
+    {
+        if let Some(delta) = choice.get("delta") {
+            if let Some(reasoning) = delta.get("reasoning_content").and_then(|v| v.as_str()) {
+                if !reasoning.is_empty() {
+                    events.push(StreamEvent::ReasoningDelta(reasoning.to_string()));
+                }
+            }
+            if let Some(content) = delta.get("content").and_then(|v| v.as_str()) {
+                if !content.is_empty() {
+                    events.push(StreamEvent::TextDelta(content.to_string()));
+                }
+            }
+            if let Some(tool_calls) = delta.get("tool_calls").and_then(|v| v.as_array()) {
+                for (i, call) in tool_calls.iter().enumerate() {
+                    let function = call.get("function");
+                    events.push(StreamEvent::ToolCallDelta {
+                        index: call
+                            .get("index")
+                            .and_then(|v| v.as_u64())
+                            .unwrap_or(i as u64) as u32,
+                        id: call.get("id").and_then(|v| v.as_str()).map(str::to_string),
+                        name: function
+                            .and_then(|f| f.get("name"))
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string),
+                        arguments: function
+                            .and_then(|f| f.get("arguments"))
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default()
+                            .to_string(),
+                    });
+                }
+            }
+        }
+        if let Some(reason) = choice.get("finish_reason").and_then(|v| v.as_str()) {
+            events.push(StreamEvent::Finish(reason.to_string()));
+        }
+    }
+
+    if let Some(usage) = extract_chat_usage(&chunk) {
+        events.push(StreamEvent::Usage(usage));
+    }
+
+    Ok(events)
+}
+
+/// 提取 OpenAI 兼容响应中的 `usage`
+pub fn extract_chat_usage(response: &Value) -> Option<TokenUsage> {
+    let usage = response.get("usage").filter(|u| u.is_object())?;
+    let get = |key: &str| usage.get(key).and_then(|v| v.as_u64()).map(|v| v as u32);
+
+    Some(TokenUsage {
+        input_tokens: get("prompt_tokens").unwrap_or(0),
+        output_tokens: get("completion_tokens").unwrap_or(0),
+        // DeepSeek 使用 prompt_cache_hit_tokens 表示缓存命中
+        cache_read_tokens: usage
+            .get("prompt_tokens_details")
+            .and_then(|d| d.get("cached_tokens"))
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32)
+            .or_else(|| get("prompt_cache_hit_tokens")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_url_versions() {
+        assert_eq!(
+            build_url("https://api.openai.com", "chat/completions"),
+            "https://api.openai.com/v1/chat/completions"
+        );
+        assert_eq!(
+            build_url("https://api.openai.com/v1/", "models"),
+            "https://api.openai.com/v1/models"
+        );
+        assert_eq!(
+            build_url("https://open.bigmodel.cn/api/paas/v4", "chat/completions"),
+            "https://open.bigmodel.cn/api/paas/v4/chat/completions"
+        );
+    }
+
+    #[test]
+    fn test_build_request() {
+        let provider = OpenAiCompatibleProvider::openai();
+        let endpoint = ProviderEndpoint::new("sk-test").with_header("X-Trace", "1");
+        let body = json!({"model": "gpt-4o", "messages": []});
+
+        let request = provider.build_request(&endpoint, &body).unwrap();
+        assert_eq!(request.url, "https://api.openai.com/v1/chat/completions");
+        assert_eq!(request.header("authorization"), Some("Bearer sk-test"));
+        assert_eq!(request.header("x-trace"), Some("1"));
+        assert_eq!(request.body, body);
+
+        assert!(provider
+            .build_request(&ProviderEndpoint::default(), &body)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_stream_chunk() {
+        let provider = OpenAiCompatibleProvider::openai();
+        let events = provider
+            .parse_stream_chunk(
+                r#"{"choices":[{"delta":{"content":"你好"},"finish_reason":null}]}"#,
+            )
+            .unwrap();
+        assert_eq!(events, vec![StreamEvent::TextDelta("你好".to_string())]);
+
+        let events = provider
+            .parse_stream_chunk(
+                r#"{"choices":[{"delta":{},"finish_reason":"stop"}],"usage":{"prompt_tokens":10,"completion_tokens":5}}"#,
+            )
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![
+                StreamEvent::Finish("stop".to_string()),
+                StreamEvent::Usage(TokenUsage {
+                    input_tokens: 10,
+                    output_tokens: 5,
+                    cache_read_tokens: None,
+                }),
+            ]
+        );
+
+        assert_eq!(
+            provider.parse_stream_chunk("[DONE]").unwrap(),
+            vec![StreamEvent::Done]
+        );
+        assert!(provider
+            .parse_stream_chunk(r#"{"error":{"message":"quota"}}"#)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_tool_call_delta() {
+        let events = parse_chat_chunk(
+            r#"{"choices":[{"delta":{"tool_calls":[{"index":1,"id":"call_1","function":{"name":"search","arguments":"{\"q\""}}]}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            events,
+            vec![StreamEvent::ToolCallDelta {
+                index: 1,
+                id: Some("call_1".to_string()),
+                name: Some("search".to_string()),
+                arguments: "{\"q\"".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! 推理内容处理策略
+//!
+//! 推理模型（如 deepseek-reasoner）在 OpenAI 兼容响应中通过 `reasoning_content`
+//! 字段输出思考过程。不少客户端不识别该字段，因此按配置的策略改写响应：
+//! - passthrough: 原样保留
+//! - strip: 丢弃思考过程
+//! - inline_think: 用 `<think>` 标签包裹后合并到 `content`
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const THINK_OPEN: &str = "<think>\n";
+const THINK_CLOSE: &str = "\n</think>\n\n";
+
+/// 推理内容处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasoningPolicy {
+    /// 原样保留 `reasoning_content`
+    #[default]
+    Passthrough,
+    /// 丢弃 `reasoning_content`
+    Strip,
+    /// 合并到 `content`，用 `<think>` 标签包裹
+    InlineThink,
+}
+
+/// 按策略改写 OpenAI 兼容响应中的推理内容
+///
+/// 流式响应需要跨 chunk 记录 `<think>` 标签是否已打开，每个响应使用独立实例。
+#[derive(Debug, Clone)]
+pub struct ReasoningFilter {
+    policy: ReasoningPolicy,
+    /// 是否已输出 `<think>` 且尚未闭合
+    in_reasoning: bool,
+    /// 未读完整行的 SSE 数据
+    pending: Vec<u8>,
+}
+
+impl ReasoningFilter {
+    pub fn new(policy: ReasoningPolicy) -> Self {
+        Self {
+            policy,
+            in_reasoning: false,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn policy(&self) -> ReasoningPolicy {
+        self.policy
+    }
+
+    /// 是否需要改写（passthrough 时可直接转发原始数据）
+    pub fn is_active(&self) -> bool {
+        self.policy != ReasoningPolicy::Passthrough
+    }
+
+    /// 改写一个流式 chunk（`chat.completion.chunk` JSON）
+    pub fn apply_chunk(&mut self, chunk: &mut Value) {
+        if !self.is_active() {
+            return;
+        }
+        let Some(choices) = chunk.get_mut("choices").and_then(|c| c.as_array_mut()) else {
+            return;
+        };
+
+        for choice in choices {
+            let finished = choice
+                .get("finish_reason")
+                .is_some_and(|reason| !reason.is_null());
+            // 结束 chunk 可能不带 delta，思考未闭合时补一个用于输出 `</think>`
+            if finished && self.in_reasoning && choice.get("delta").is_none_or(Value::is_null) {
+                if let Some(choice) = choice.as_object_mut() {
+                    choice.insert("delta".to_string(), Value::Object(Default::default()));
+                }
+            }
+            let Some(delta) = choice.get_mut("delta").and_then(|d| d.as_object_mut()) else {
+                continue;
+            };
+            let reasoning = delta
+                .remove("reasoning_content")
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default();
+            if self.policy == ReasoningPolicy::Strip {
+                continue;
+            }
+
+            let content = delta
+                .get("content")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let mut merged = String::new();
+            if !reasoning.is_empty() {
+                if !self.in_reasoning {
+                    merged.push_str(THINK_OPEN);
+                    self.in_reasoning = true;
+                }
+                merged.push_str(&reasoning);
+            }
+            let closes = !content.is_empty() || delta.contains_key("tool_calls") || finished;
+            if self.in_reasoning && closes {
+                merged.push_str(THINK_CLOSE);
+                self.in_reasoning = false;
+            }
+            merged.push_str(&content);
+
+            if !merged.is_empty() {
+                delta.insert("content".to_string(), Value::String(merged));
+            }
+        }
+    }
+
+    /// 改写 SSE 字节流片段，返回可转发的数据
+    ///
+    /// 不完整的行会缓存到下一次调用，流结束时调用 [`ReasoningFilter::finish`]。
+    pub fn rewrite_sse(&mut self, bytes: &[u8]) -> Vec<u8> {
+        if !self.is_active() {
+            return bytes.to_vec();
+        }
+
+        self.pending.extend_from_slice(bytes);
+        let Some(last_newline) = self.pending.iter().rposition(|b| *b == b'\n') else {
+            return Vec::new();
+        };
+        let complete: Vec<u8> = self.pending.drain(..=last_newline).collect();
+
+        let mut output = Vec::with_capacity(complete.len());
+        for line in complete.split_inclusive(|b| *b == b'\n') {
+            output.extend_from_slice(&self.rewrite_line(line));
+        }
+        output
+    }
+
+    /// 输出缓存的剩余数据，思考仍未闭合时补一个闭合标签
+    pub fn finish(&mut self) -> Vec<u8> {
+        let rest = std::mem::take(&mut self.pending);
+        let mut output = if rest.is_empty() {
+            rest
+        } else {
+            self.rewrite_line(&rest)
+        };
+        output.extend(self.close_event());
+        output
+    }
+
+    /// 思考未闭合时生成只含 `</think>` 的 SSE 事件并标记为已闭合
+    fn close_event(&mut self) -> Vec<u8> {
+        if !std::mem::take(&mut self.in_reasoning) {
+            return Vec::new();
+        }
+        let close = serde_json::json!({
+            "choices": [{"index": 0, "delta": {"content": THINK_CLOSE}, "finish_reason": null}]
+        });
+        format!("data: {}\n\n", close).into_bytes()
+    }
+
+    /// 改写非流式响应（`chat.completion` JSON）
+    pub fn apply_response(&self, response: &mut Value) {
+        if !self.is_active() {
+            return;
+        }
+        let Some(choices) = response.get_mut("choices").and_then(|c| c.as_array_mut()) else {
+            return;
+        };
+
+        for choice in choices {
+            let Some(message) = choice.get_mut("message").and_then(|m| m.as_object_mut()) else {
+                continue;
+            };
+            let reasoning = message
+                .remove("reasoning_content")
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default();
+            if self.policy == ReasoningPolicy::InlineThink && !reasoning.is_empty() {
+                let content = message
+                    .get("content")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default();
+                let merged = format!("{}{}{}{}", THINK_OPEN, reasoning, THINK_CLOSE, content);
+                message.insert("content".to_string(), Value::String(merged));
+            }
+        }
+    }
+
+    fn rewrite_line(&mut self, line: &[u8]) -> Vec<u8> {
+        let Ok(text) = std::str::from_utf8(line) else {
+            return line.to_vec();
+        };
+        let Some(data) = text.strip_prefix("data:") else {
+            return line.to_vec();
+        };
+        if data.trim() == "[DONE]" {
+            // 上游没有发送结束 chunk 就结束了流，在 [DONE] 前补一个闭合标签
+            let mut output = self.close_event();
+            output.extend_from_slice(line);
+            return output;
+        }
+        let Ok(mut chunk) = serde_json::from_str::<Value>(data.trim()) else {
+            return line.to_vec();
+        };
+
+        self.apply_chunk(&mut chunk);
+        let ending = &text[text.trim_end_matches(['\r', '\n']).len()..];
+        format!("data: {}{}", chunk, ending).into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn chunk(reasoning: Option<&str>, content: Option<&str>) -> Value {
+        json!({"choices": [{"delta": {"reasoning_content": reasoning, "content": content}, "finish_reason": null}]})
+    }
+
+    #[test]
+    fn test_strip_removes_reasoning() {
+        let mut filter = ReasoningFilter::new(ReasoningPolicy::Strip);
+        let mut value = chunk(Some("思考"), None);
+        filter.apply_chunk(&mut value);
+        assert!(value["choices"][0]["delta"]
+            .get("reasoning_content")
+            .is_none());
+        assert!(value["choices"][0]["delta"]["content"].is_null());
+    }
+
+    #[test]
+    fn test_inline_think_wraps_reasoning() {
+        let mut filter = ReasoningFilter::new(ReasoningPolicy::InlineThink);
+        let mut contents = Vec::new();
+        for mut value in [
+            chunk(Some("先"), None),
+            chunk(Some("想"), None),
+            chunk(None, Some("答案")),
+        ] {
+            filter.apply_chunk(&mut value);
+            contents.push(
+                value["choices"][0]["delta"]["content"]
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+            );
+        }
+        assert_eq!(contents.concat(), "<think>\n先想\n</think>\n\n答案");
+    }
+
+    #[test]
+    fn test_inline_think_closes_on_finish() {
+        // 结束 chunk 带空 delta
+        let mut filter = ReasoningFilter::new(ReasoningPolicy::InlineThink);
+        let mut first = chunk(Some("想"), None);
+        filter.apply_chunk(&mut first);
+        let mut last = json!({"choices": [{"delta": {}, "finish_reason": "length"}]});
+        filter.apply_chunk(&mut last);
+        assert_eq!(last["choices"][0]["delta"]["content"], THINK_CLOSE);
+
+        // 结束 chunk 不带 delta
+        let mut filter = ReasoningFilter::new(ReasoningPolicy::InlineThink);
+        let mut first = chunk(Some("想"), None);
+        filter.apply_chunk(&mut first);
+        let mut last = json!({"choices": [{"index": 0, "finish_reason": "stop"}]});
+        filter.apply_chunk(&mut last);
+        assert_eq!(last["choices"][0]["delta"]["content"], THINK_CLOSE);
+        assert_eq!(last["choices"][0]["finish_reason"], "stop");
+
+        // 未在思考中时结束 chunk 保持原样
+        let mut last = json!({"choices": [{"index": 0, "finish_reason": "stop"}]});
+        filter.apply_chunk(&mut last);
+        assert!(last["choices"][0].get("delta").is_none());
+    }
+
+    #[test]
+    fn test_inline_think_closes_before_done() {
+        let mut filter = ReasoningFilter::new(ReasoningPolicy::InlineThink);
+        let stream = format!("data: {}\n\ndata: [DONE]\n\n", chunk(Some("想"), None));
+        let mut output = filter.rewrite_sse(stream.as_bytes());
+        output.extend(filter.finish());
+
+        let output = String::from_utf8(output).unwrap();
+        let contents: String = output
+            .lines()
+            .filter_map(|l| l.strip_prefix("data: "))
+            .filter_map(|d| serde_json::from_str::<Value>(d).ok())
+            .filter_map(|v| {
+                v["choices"][0]["delta"]["content"]
+                    .as_str()
+                    .map(str::to_string)
+            })
+            .collect();
+        assert_eq!(contents, "<think>\n想\n</think>\n\n");
+        assert!(output.ends_with("data: [DONE]\n\n"));
+
+        // 流没有 [DONE] 就结束时由 finish 补上
+        let mut filter = ReasoningFilter::new(ReasoningPolicy::InlineThink);
+        let stream = format!("data: {}\n\n", chunk(Some("想"), None));
+        filter.rewrite_sse(stream.as_bytes());
+        let output = String::from_utf8(filter.finish()).unwrap();
+        assert!(output.contains("</think>"));
+        assert!(filter.finish().is_empty());
+    }
+
+    #[test]
+    fn test_rewrite_sse_across_fragments() {
+        let mut filter = ReasoningFilter::new(ReasoningPolicy::Strip);
+        let line = format!(
+            "data: {}\n\ndata: [DONE]\n\n",
+            chunk(Some("思考"), Some("hi"))
+        );
+        let (a, b) = line.as_bytes().split_at(20);
+
+        let mut output = filter.rewrite_sse(a);
+        output.extend(filter.rewrite_sse(b));
+        output.extend(filter.finish());
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(!output.contains("reasoning_content"));
+        assert!(output.contains("\"content\":\"hi\""));
+        assert!(output.ends_with("data: [DONE]\n\n"));
+    }
+
+    #[test]
+    fn test_apply_response_inline() {
+        let filter = ReasoningFilter::new(ReasoningPolicy::InlineThink);
+        let mut response = json!({"choices": [{"message": {"role": "assistant", "content": "42", "reasoning_content": "算一下"}}]});
+        filter.apply_response(&mut response);
+        assert_eq!(
+            response["choices"][0]["message"]["content"],
+            "<think>\n算一下\n</think>\n\n42"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Provider 注册表
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::anthropic::AnthropicProvider;
+use crate::dashscope::DashScopeProvider;
+use crate::deepseek::DeepSeekProvider;
+use crate::error::ProviderError;
+use crate::moonshot::MoonshotProvider;
+use crate::openai::OpenAiCompatibleProvider;
+use crate::traits::Provider;
+use crate::zhipu::ZhipuProvider;
+
+/// 按标识查找 Provider
+#[derive(Clone, Default)]
+pub struct ProviderRegistry {
+    providers: HashMap<String, Arc<dyn Provider>>,
+}
+
+impl ProviderRegistry {
+    /// 创建空注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建包含内置 Provider 的注册表
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register(OpenAiCompatibleProvider::openai());
+        registry.register(AnthropicProvider::new());
+        registry.register(DashScopeProvider::compatible());
+        registry.register(DashScopeProvider::native());
+        registry.register(DeepSeekProvider::new());
+        registry.register(MoonshotProvider::new());
+        registry.register(ZhipuProvider::new());
+        registry
+    }
+
+    /// 注册 Provider，同标识的已有 Provider 会被替换
+    pub fn register(&mut self, provider: impl Provider + 'static) {
+        self.providers
+            .insert(provider.id().to_string(), Arc::new(provider));
+    }
+
+    /// 获取 Provider
+    pub fn get(&self, id: &str) -> Result<Arc<dyn Provider>, ProviderError> {
+        self.providers
+            .get(id)
+            .cloned()
+            .ok_or_else(|| ProviderError::UnknownProvider(id.to_string()))
+    }
+
+    /// 所有已注册的 Provider 标识（已排序）
+    pub fn ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.providers.keys().map(|s| s.as_str()).collect();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+/// 按基础地址识别上游对应的 Provider 标识，用于没有 Provider 信息的 API Key 凭证
+pub fn id_for_base_url(base_url: &str) -> Option<&'static str> {
+    const HOSTS: &[(&str, &str)] = &[
+        ("dashscope", "alibaba"),
+        ("deepseek.com", "deepseek"),
+        ("moonshot.", "moonshotai"),
+        ("bigmodel.cn", "zhipuai"),
+        ("api.openai.com", "openai"),
+        ("api.anthropic.com", "anthropic"),
+    ];
+    let base_url = base_url.to_lowercase();
+    HOSTS
+        .iter()
+        .find(|(host, _)| base_url.contains(host))
+        .map(|(_, id)| *id)
+}
+
+impl std::fmt::Debug for ProviderRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProviderRegistry")
+            .field("providers", &self.ids())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_registry() {
+        let registry = ProviderRegistry::builtin();
+        assert_eq!(
+            registry.ids(),
+            vec![
+                "alibaba",
+                "alibaba-native",
+                "anthropic",
+                "deepseek",
+                "moonshotai",
+                "openai",
+                "zhipuai"
+            ]
+        );
+        assert_eq!(registry.get("openai").unwrap().id(), "openai");
+        assert!(matches!(
+            registry.get("unknown"),
+            Err(ProviderError::UnknownProvider(_))
+        ));
+    }
+
+    #[test]
+    fn test_id_for_base_url() {
+        let registry = ProviderRegistry::builtin();
+        for (base_url, id) in [
+            (
+                "https://dashscope.aliyuncs.com/compatible-mode/v1/",
+                "alibaba",
+            ),
+            ("https://api.deepseek.com/v1", "deepseek"),
+            ("https://api.moonshot.cn/v1", "moonshotai"),
+            ("https://open.bigmodel.cn/api/paas/v4", "zhipuai"),
+            ("https://api.openai.com", "openai"),
+        ] {
+            assert_eq!(id_for_base_url(base_url), Some(id));
+            assert!(registry.get(id).is_ok());
+        }
+        assert_eq!(id_for_base_url("https://openrouter.ai/api/v1"), None);
+    }
+}
@@ -0,0 +1,253 @@
+//! 请求参数整形
+//!
+//! 各上游对请求参数的限制不同：超过模型输出上限的 `max_tokens`、超出取值范围的
+//! `temperature`、不支持的惩罚参数等都会被上游直接以 400 拒绝，错误信息往往难以看懂。
+//! [`ParamLimits`] 描述上游和模型的限制，[`apply`] 在发出请求前把超出范围的参数截断到
+//! 范围内、移除不支持的参数，并返回所做的调整供记录日志。
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::types::WireFormat;
+
+/// 输出 Token 上限参数（OpenAI 推理模型使用 `max_completion_tokens`）
+const MAX_TOKENS_PARAMS: &[&str] = &["max_tokens", "max_completion_tokens"];
+
+/// 惩罚参数
+const PENALTY_PARAMS: &[&str] = &["presence_penalty", "frequency_penalty"];
+
+/// 参数取值范围（闭区间）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ParamRange {
+    pub const fn new(min: f64, max: f64) -> Self {
+        Self { min, max }
+    }
+}
+
+/// 上游对请求参数的限制
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParamLimits {
+    /// 最大输出 Token 数
+    pub max_output_tokens: Option<u32>,
+    /// `temperature` 取值范围
+    pub temperature: Option<ParamRange>,
+    /// `top_p` 取值范围
+    pub top_p: Option<ParamRange>,
+    /// `presence_penalty` 和 `frequency_penalty` 的取值范围
+    pub penalty: Option<ParamRange>,
+    /// 不支持的参数，直接移除
+    pub unsupported: Vec<&'static str>,
+}
+
+impl ParamLimits {
+    /// 协议格式的默认限制
+    pub fn for_wire_format(format: WireFormat) -> Self {
+        match format {
+            WireFormat::OpenAi => Self {
+                temperature: Some(ParamRange::new(0.0, 2.0)),
+                top_p: Some(ParamRange::new(0.0, 1.0)),
+                penalty: Some(ParamRange::new(-2.0, 2.0)),
+                ..Default::default()
+            },
+            WireFormat::Anthropic => Self {
+                temperature: Some(ParamRange::new(0.0, 1.0)),
+                top_p: Some(ParamRange::new(0.0, 1.0)),
+                unsupported: PENALTY_PARAMS.to_vec(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// 收紧最大输出 Token 数（已有上限时取较小值）
+    pub fn with_max_output_tokens(mut self, max_output_tokens: Option<u32>) -> Self {
+        self.max_output_tokens = match (self.max_output_tokens, max_output_tokens) {
+            (Some(current), Some(max)) => Some(current.min(max)),
+            (current, max) => current.or(max),
+        };
+        self
+    }
+}
+
+/// 一项参数调整
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ParamAdjustment {
+    /// 超出取值范围，截断到边界
+    Clamped {
+        param: String,
+        from: Value,
+        to: Value,
+    },
+    /// 上游不支持，已移除
+    Removed { param: String },
+}
+
+impl std::fmt::Display for ParamAdjustment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamAdjustment::Clamped { param, from, to } => write!(f, "{}={}->{}", param, from, to),
+            ParamAdjustment::Removed { param } => write!(f, "-{}", param),
+        }
+    }
+}
+
+/// 按限制调整请求体，返回所做的调整
+///
+/// 先移除不支持的参数，再截断超出范围的参数；不是数字的参数原样保留，交给上游报错
+pub fn apply(body: &mut Value, limits: &ParamLimits) -> Vec<ParamAdjustment> {
+    let Some(body) = body.as_object_mut() else {
+        return Vec::new();
+    };
+    let mut adjustments = Vec::new();
+
+    for param in &limits.unsupported {
+        if body.remove(*param).is_some() {
+            adjustments.push(ParamAdjustment::Removed {
+                param: param.to_string(),
+            });
+        }
+    }
+
+    if let Some(max) = limits.max_output_tokens {
+        for param in MAX_TOKENS_PARAMS {
+            clamp(
+                body,
+                param,
+                ParamRange::new(1.0, max as f64),
+                &mut adjustments,
+            );
+        }
+    }
+    if let Some(range) = limits.temperature {
+        clamp(body, "temperature", range, &mut adjustments);
+    }
+    if let Some(range) = limits.top_p {
+        clamp(body, "top_p", range, &mut adjustments);
+    }
+    if let Some(range) = limits.penalty {
+        for param in PENALTY_PARAMS {
+            clamp(body, param, range, &mut adjustments);
+        }
+    }
+
+    adjustments
+}
+
+fn clamp(
+    body: &mut Map<String, Value>,
+    param: &str,
+    range: ParamRange,
+    adjustments: &mut Vec<ParamAdjustment>,
+) {
+    let Some(value) = body.get_mut(param) else {
+        return;
+    };
+    let Some(current) = value.as_f64() else {
+        return;
+    };
+    let clamped = current.clamp(range.min, range.max);
+    if clamped == current {
+        return;
+    }
+
+    // 整数参数（如 max_tokens）保持整数
+    let to = if value.is_f64() {
+        Value::from(clamped)
+    } else {
+        Value::from(clamped as i64)
+    };
+    adjustments.push(ParamAdjustment::Clamped {
+        param: param.to_string(),
+        from: std::mem::replace(value, to.clone()),
+        to,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_clamp_and_remove() {
+        let mut limits = ParamLimits::for_wire_format(WireFormat::OpenAi);
+        limits.unsupported.push("logprobs");
+        let limits = limits.with_max_output_tokens(Some(8192));
+
+        let mut body = json!({
+            "model": "m",
+            "max_tokens": 100000,
+            "temperature": 2.5,
+            "top_p": 0.9,
+            "frequency_penalty": -3,
+            "logprobs": true,
+        });
+        let adjustments = apply(&mut body, &limits);
+
+        assert_eq!(body["max_tokens"], json!(8192));
+        assert_eq!(body["temperature"], json!(2.0));
+        assert_eq!(body["top_p"], json!(0.9));
+        assert_eq!(body["frequency_penalty"], json!(-2));
+        assert!(body.get("logprobs").is_none());
+        assert_eq!(
+            adjustments
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "-logprobs",
+                "max_tokens=100000->8192",
+                "temperature=2.5->2.0",
+                "frequency_penalty=-3->-2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_within_limits_unchanged() {
+        let limits =
+            ParamLimits::for_wire_format(WireFormat::Anthropic).with_max_output_tokens(Some(64000));
+        let mut body = json!({"max_tokens": 4096, "temperature": 1, "top_p": "0.5"});
+        let original = body.clone();
+
+        assert!(apply(&mut body, &limits).is_empty());
+        assert_eq!(body, original);
+    }
+
+    #[test]
+    fn test_max_output_tokens_takes_smaller() {
+        let limits = ParamLimits {
+            max_output_tokens: Some(8192),
+            ..Default::default()
+        };
+        assert_eq!(
+            limits
+                .clone()
+                .with_max_output_tokens(Some(4096))
+                .max_output_tokens,
+            Some(4096)
+        );
+        assert_eq!(
+            limits
+                .clone()
+                .with_max_output_tokens(Some(32000))
+                .max_output_tokens,
+            Some(8192)
+        );
+        assert_eq!(
+            ParamLimits::default()
+                .with_max_output_tokens(Some(4096))
+                .max_output_tokens,
+            Some(4096)
+        );
+        assert_eq!(
+            limits.with_max_output_tokens(None).max_output_tokens,
+            Some(8192)
+        );
+    }
+}
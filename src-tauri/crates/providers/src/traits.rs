@@ -0,0 +1,80 @@
+//! Provider trait 定义
+
+use serde_json::Value;
+
+use crate::error::ProviderError;
+use crate::shaping::ParamLimits;
+use crate::types::{ProviderEndpoint, ProviderRequest, StreamEvent, TokenUsage, WireFormat};
+
+/// 上游 Provider
+///
+/// 请求体在调用前已转换为 [`Provider::wire_format`] 对应的协议格式，
+/// Provider 只负责该上游特有的 URL、认证头和参数调整。
+pub trait Provider: Send + Sync {
+    /// Provider 标识，如 "openai"
+    fn id(&self) -> &str;
+
+    /// 请求体协议格式
+    fn wire_format(&self) -> WireFormat;
+
+    /// 默认 API 基础地址
+    fn default_base_url(&self) -> &str;
+
+    /// 构建聊天请求
+    fn build_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+        body: &Value,
+    ) -> Result<ProviderRequest, ProviderError>;
+
+    /// 解析一条 SSE `data:` 负载
+    fn parse_stream_chunk(&self, data: &str) -> Result<Vec<StreamEvent>, ProviderError>;
+
+    /// 从非流式响应中提取 Token 用量
+    fn extract_usage(&self, response: &Value) -> Option<TokenUsage>;
+
+    /// 模型的请求参数限制，默认为协议格式的通用限制（见 [`crate::shaping`]）
+    fn param_limits(&self, _model: &str) -> ParamLimits {
+        ParamLimits::for_wire_format(self.wire_format())
+    }
+
+    /// 构建模型列表请求
+    fn build_models_request(
+        &self,
+        _endpoint: &ProviderEndpoint,
+    ) -> Result<ProviderRequest, ProviderError> {
+        Err(ProviderError::Unsupported(
+            self.id().to_string(),
+            "获取模型列表".to_string(),
+        ))
+    }
+
+    /// 解析模型列表响应，默认读取 `data[].id`
+    fn parse_models(&self, response: &Value) -> Vec<String> {
+        response
+            .get("data")
+            .and_then(|d| d.as_array())
+            .map(|models| {
+                models
+                    .iter()
+                    .filter_map(|m| m.get("id").and_then(|v| v.as_str()))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 内置模型列表，无法访问模型列表接口时使用
+    fn builtin_models(&self) -> &[&str] {
+        &[]
+    }
+
+    /// 解析端点的基础地址
+    fn base_url<'a>(&'a self, endpoint: &'a ProviderEndpoint) -> &'a str {
+        endpoint
+            .base_url
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| self.default_base_url())
+    }
+}
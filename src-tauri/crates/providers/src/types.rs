@@ -0,0 +1,117 @@
+//! Provider 通用类型
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 请求体协议格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WireFormat {
+    /// OpenAI Chat Completions
+    OpenAi,
+    /// Anthropic Messages
+    Anthropic,
+}
+
+/// 上游端点配置
+#[derive(Debug, Clone, Default)]
+pub struct ProviderEndpoint {
+    /// API 基础地址，为空时使用 Provider 默认地址
+    pub base_url: Option<String>,
+    pub api_key: String,
+    /// 额外请求头
+    pub extra_headers: Vec<(String, String)>,
+}
+
+impl ProviderEndpoint {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// HTTP 方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpMethod {
+    Get,
+    Post,
+}
+
+impl HttpMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+        }
+    }
+}
+
+/// 构建完成、可直接发送的 HTTP 请求
+#[derive(Debug, Clone)]
+pub struct ProviderRequest {
+    pub method: HttpMethod,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// 请求体，GET 请求为 `Value::Null`
+    pub body: Value,
+}
+
+impl ProviderRequest {
+    /// 获取请求头（名称忽略大小写）
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Token 用量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    /// 命中缓存的输入 Token 数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_read_tokens: Option<u32>,
+}
+
+impl TokenUsage {
+    pub fn total(&self) -> u32 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+/// 流式响应中解析出的事件
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    /// 正文增量
+    TextDelta(String),
+    /// 推理过程增量
+    ReasoningDelta(String),
+    /// 工具调用增量
+    ToolCallDelta {
+        index: u32,
+        id: Option<String>,
+        name: Option<String>,
+        arguments: String,
+    },
+    /// Token 用量（可能多次出现，后出现的字段覆盖先前的值）
+    Usage(TokenUsage),
+    /// 结束原因
+    Finish(String),
+    /// 流结束
+    Done,
+}
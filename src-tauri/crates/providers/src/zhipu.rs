@@ -0,0 +1,201 @@
+//! 智谱 BigModel（GLM）Provider
+//!
+//! OpenAI 兼容协议，差异：
+//! - `{id}.{secret}` 格式的 API Key 需签发 JWT 作为 Bearer Token
+//! - `temperature` 取值范围为 (0, 1]，需要贪心解码时使用 `do_sample: false`
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+use crate::error::ProviderError;
+use crate::openai;
+use crate::shaping::{self, ParamLimits, ParamRange};
+use crate::traits::Provider;
+use crate::types::{ProviderEndpoint, ProviderRequest, StreamEvent, TokenUsage, WireFormat};
+
+pub const ZHIPU_BASE_URL: &str = "https://open.bigmodel.cn/api/paas/v4";
+
+/// JWT 有效期（毫秒）
+const TOKEN_TTL_MS: u64 = 30 * 60 * 1000;
+
+/// 内置模型列表
+pub const ZHIPU_MODELS: &[&str] = &[
+    "glm-4.7",
+    "glm-4.6",
+    "glm-4.5",
+    "glm-4.5-air",
+    "glm-4.5-flash",
+    "glm-4.6v",
+    "glm-4.5v",
+];
+
+/// 生成请求使用的认证 Token
+///
+/// `{id}.{secret}` 格式的 Key 签发 HS256 JWT，其他格式原样返回。
+pub fn auth_token(api_key: &str, now_ms: u64) -> Result<String, ProviderError> {
+    let Some((id, secret)) = api_key.split_once('.') else {
+        return Ok(api_key.to_string());
+    };
+    if id.is_empty() || secret.is_empty() {
+        return Err(ProviderError::InvalidEndpoint(
+            "智谱 API Key 格式无效".to_string(),
+        ));
+    }
+
+    let header = json!({"alg": "HS256", "sign_type": "SIGN"});
+    let payload = json!({
+        "api_key": id,
+        "exp": now_ms + TOKEN_TTL_MS,
+        "timestamp": now_ms,
+    });
+    let signing_input = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(header.to_string()),
+        URL_SAFE_NO_PAD.encode(payload.to_string())
+    );
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| ProviderError::InvalidEndpoint(e.to_string()))?;
+    mac.update(signing_input.as_bytes());
+    let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+
+    Ok(format!("{}.{}", signing_input, signature))
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// 智谱 Provider，标识与系统 Provider `zhipuai` 一致
+#[derive(Debug, Clone, Default)]
+pub struct ZhipuProvider;
+
+impl ZhipuProvider {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 替换为签发后的 Token
+    fn signed_endpoint(
+        &self,
+        endpoint: &ProviderEndpoint,
+    ) -> Result<ProviderEndpoint, ProviderError> {
+        let mut signed = endpoint.clone();
+        signed.api_key = auth_token(&endpoint.api_key, now_ms())?;
+        Ok(signed)
+    }
+}
+
+impl Provider for ZhipuProvider {
+    fn id(&self) -> &str {
+        "zhipuai"
+    }
+
+    fn wire_format(&self) -> WireFormat {
+        WireFormat::OpenAi
+    }
+
+    fn default_base_url(&self) -> &str {
+        ZHIPU_BASE_URL
+    }
+
+    fn build_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+        body: &Value,
+    ) -> Result<ProviderRequest, ProviderError> {
+        let signed = self.signed_endpoint(endpoint)?;
+        let mut request = openai::build_chat_request(self.base_url(endpoint), &signed, body)?;
+        shaping::apply(&mut request.body, &self.param_limits(""));
+
+        if let Some(body) = request.body.as_object_mut() {
+            let greedy = body
+                .get("temperature")
+                .and_then(|t| t.as_f64())
+                .is_some_and(|t| t <= 0.0);
+            if greedy {
+                body.remove("temperature");
+                body.insert("do_sample".to_string(), Value::Bool(false));
+            }
+        }
+
+        Ok(request)
+    }
+
+    fn param_limits(&self, _model: &str) -> ParamLimits {
+        ParamLimits {
+            temperature: Some(ParamRange::new(0.0, 1.0)),
+            ..ParamLimits::for_wire_format(self.wire_format())
+        }
+    }
+
+    fn parse_stream_chunk(&self, data: &str) -> Result<Vec<StreamEvent>, ProviderError> {
+        openai::parse_chat_chunk(data)
+    }
+
+    fn extract_usage(&self, response: &Value) -> Option<TokenUsage> {
+        openai::extract_chat_usage(response)
+    }
+
+    fn build_models_request(
+        &self,
+        endpoint: &ProviderEndpoint,
+    ) -> Result<ProviderRequest, ProviderError> {
+        let signed = self.signed_endpoint(endpoint)?;
+        openai::build_models_request(self.base_url(endpoint), &signed)
+    }
+
+    fn builtin_models(&self) -> &[&str] {
+        ZHIPU_MODELS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_token_jwt() {
+        let token = auth_token("my-id.my-secret", 1_700_000_000_000).unwrap();
+        let parts: Vec<&str> = token.split('.').collect();
+        assert_eq!(parts.len(), 3);
+
+        let payload: Value =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(parts[1]).unwrap()).unwrap();
+        assert_eq!(payload["api_key"], "my-id");
+        assert_eq!(payload["exp"], 1_700_000_000_000u64 + TOKEN_TTL_MS);
+
+        // 相同输入签名稳定
+        assert_eq!(
+            token,
+            auth_token("my-id.my-secret", 1_700_000_000_000).unwrap()
+        );
+        assert_eq!(auth_token("plain-key", 0).unwrap(), "plain-key");
+    }
+
+    #[test]
+    fn test_zero_temperature_uses_do_sample() {
+        let request = ZhipuProvider::new()
+            .build_request(
+                &ProviderEndpoint::new("plain-key"),
+                &json!({"model": "glm-4.6", "temperature": 0}),
+            )
+            .unwrap();
+        assert_eq!(
+            request.url,
+            "https://open.bigmodel.cn/api/paas/v4/chat/completions"
+        );
+        assert!(request.body.get("temperature").is_none());
+        assert_eq!(request.body["do_sample"], false);
+        assert!(request
+            .header("authorization")
+            .is_some_and(|h| h.starts_with("Bearer ")));
+    }
+}
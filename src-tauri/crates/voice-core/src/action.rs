@@ -0,0 +1,279 @@
+//! 输出动作
+//!
+//! 除了输入文字，输出还可以包含按键，例如听写"发送"时输入消息后按 Enter。
+//! 动作用简单的脚本描述：普通文字原样输入，花括号内为按键，`+` 连接修饰键：
+//!
+//! ```text
+//! 好的，明天见{Enter}
+//! 第一项{Tab}第二项{Ctrl+Enter}
+//! ```
+//!
+//! 文字中的花括号写作 `{{` 和 `}}`。单个字符键只能与修饰键组合（如 `{Cmd+K}`），
+//! 单独的字符直接写在文字中。
+
+use crate::error::{Result, VoiceError};
+
+/// 修饰键
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    Ctrl,
+    Shift,
+    Alt,
+    /// macOS 上为 Command，Windows 上为 Win
+    Meta,
+}
+
+impl Modifier {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => Some(Self::Ctrl),
+            "shift" => Some(Self::Shift),
+            "alt" | "option" => Some(Self::Alt),
+            "cmd" | "command" | "meta" | "win" | "super" => Some(Self::Meta),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Ctrl => "Ctrl",
+            Self::Shift => "Shift",
+            Self::Alt => "Alt",
+            Self::Meta => "Cmd",
+        }
+    }
+}
+
+/// 按键
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCode {
+    Enter,
+    Tab,
+    Escape,
+    Backspace,
+    Delete,
+    Space,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    /// 字符键（只与修饰键组合使用）
+    Char(char),
+}
+
+impl KeyCode {
+    const NAMED: [(KeyCode, &'static str); 14] = [
+        (Self::Enter, "Enter"),
+        (Self::Tab, "Tab"),
+        (Self::Escape, "Esc"),
+        (Self::Backspace, "Backspace"),
+        (Self::Delete, "Delete"),
+        (Self::Space, "Space"),
+        (Self::Up, "Up"),
+        (Self::Down, "Down"),
+        (Self::Left, "Left"),
+        (Self::Right, "Right"),
+        (Self::Home, "Home"),
+        (Self::End, "End"),
+        (Self::PageUp, "PageUp"),
+        (Self::PageDown, "PageDown"),
+    ];
+
+    fn parse(name: &str) -> Option<Self> {
+        let alias = match name.to_ascii_lowercase().as_str() {
+            "return" => Some(Self::Enter),
+            "escape" => Some(Self::Escape),
+            "del" => Some(Self::Delete),
+            _ => None,
+        };
+        alias.or_else(|| {
+            Self::NAMED
+                .iter()
+                .find(|(_, n)| n.eq_ignore_ascii_case(name))
+                .map(|(key, _)| *key)
+        })
+    }
+
+    fn name(self) -> String {
+        match self {
+            Self::Char(c) => c.to_uppercase().to_string(),
+            key => Self::NAMED
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, n)| n.to_string())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// 输出动作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputAction {
+    /// 输入文字
+    Text(String),
+    /// 按键，修饰键按顺序按下、反序松开
+    Key {
+        modifiers: Vec<Modifier>,
+        key: KeyCode,
+    },
+}
+
+/// 解析动作脚本
+pub fn parse(script: &str) -> Result<Vec<OutputAction>> {
+    let mut actions = Vec::new();
+    let mut text = String::new();
+    let mut chars = script.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut token = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => token.push(c),
+                        None => {
+                            return Err(VoiceError::OutputError(format!(
+                                "按键标记未闭合: {{{}",
+                                token
+                            )))
+                        }
+                    }
+                }
+                if !text.is_empty() {
+                    actions.push(OutputAction::Text(std::mem::take(&mut text)));
+                }
+                actions.push(parse_key(&token)?);
+            }
+            c => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        actions.push(OutputAction::Text(text));
+    }
+    Ok(actions)
+}
+
+/// 解析花括号内的按键，如 `Enter`、`Ctrl+Shift+K`
+fn parse_key(token: &str) -> Result<OutputAction> {
+    let parts: Vec<&str> = token.split('+').map(str::trim).collect();
+    let (key, modifiers) = parts.split_last().unwrap_or((&"", &[]));
+
+    let modifiers = modifiers
+        .iter()
+        .map(|name| {
+            Modifier::parse(name)
+                .ok_or_else(|| VoiceError::OutputError(format!("未知的修饰键: {}", name)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut key_chars = key.chars();
+    let key = match (key_chars.next(), key_chars.next()) {
+        (Some(c), None) if !modifiers.is_empty() => KeyCode::Char(c.to_ascii_lowercase()),
+        (Some(_), None) => {
+            return Err(VoiceError::OutputError(format!(
+                "单个字符请直接写在文字中: {{{}}}",
+                token
+            )))
+        }
+        _ => KeyCode::parse(key)
+            .ok_or_else(|| VoiceError::OutputError(format!("未知的按键: {{{}}}", token)))?,
+    };
+    Ok(OutputAction::Key { modifiers, key })
+}
+
+/// 把动作还原为脚本
+pub fn to_script(actions: &[OutputAction]) -> String {
+    let mut script = String::new();
+    for action in actions {
+        match action {
+            OutputAction::Text(text) => script.push_str(&escape(text)),
+            OutputAction::Key { modifiers, key } => {
+                script.push('{');
+                for modifier in modifiers {
+                    script.push_str(modifier.name());
+                    script.push('+');
+                }
+                script.push_str(&key.name());
+                script.push('}');
+            }
+        }
+    }
+    script
+}
+
+/// 动作中的文字（不含按键）
+pub fn plain_text(actions: &[OutputAction]) -> String {
+    actions
+        .iter()
+        .filter_map(|action| match action {
+            OutputAction::Text(text) => Some(text.as_str()),
+            OutputAction::Key { .. } => None,
+        })
+        .collect()
+}
+
+/// 去掉不在白名单中的按键，返回保留的动作和被去掉的按键（脚本写法）
+///
+/// 白名单条目写法与花括号内相同（如 `Enter`、`Ctrl+Enter`），不区分大小写和修饰键顺序，
+/// 无效的条目忽略。去掉按键后相邻的文字合并
+pub fn retain_keys(
+    actions: Vec<OutputAction>,
+    allowed: &[String],
+) -> (Vec<OutputAction>, Vec<String>) {
+    let allowed: Vec<OutputAction> = allowed
+        .iter()
+        .filter_map(|entry| parse_key(entry.trim()).ok())
+        .collect();
+
+    let mut kept: Vec<OutputAction> = Vec::new();
+    let mut dropped = Vec::new();
+    for action in actions {
+        match action {
+            OutputAction::Key { .. } if !allowed.iter().any(|a| same_key(a, &action)) => {
+                dropped.push(to_script(std::slice::from_ref(&action)));
+            }
+            OutputAction::Text(text) => match kept.last_mut() {
+                Some(OutputAction::Text(last)) => last.push_str(&text),
+                _ => kept.push(OutputAction::Text(text)),
+            },
+            key => kept.push(key),
+        }
+    }
+    (kept, dropped)
+}
+
+/// 两个按键是否相同（修饰键不区分顺序）
+fn same_key(a: &OutputAction, b: &OutputAction) -> bool {
+    let (
+        OutputAction::Key {
+            modifiers: ma,
+            key: ka,
+        },
+        OutputAction::Key {
+            modifiers: mb,
+            key: kb,
+        },
+    ) = (a, b)
+    else {
+        return false;
+    };
+    ka == kb && ma.iter().all(|m| mb.contains(m)) && mb.iter().all(|m| ma.contains(m))
+}
+
+/// 转义文字中的花括号，使其在脚本中原样输入
+pub fn escape(text: &str) -> String {
+    text.replace('{', "{{").replace('}', "}}")
+}
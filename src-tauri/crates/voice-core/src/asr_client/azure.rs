@@ -0,0 +1,362 @@
+//! Azure 语音服务客户端
+//!
+//! 使用 Azure Speech Services 的短音频 REST 接口，单次最长 60 秒，
+//! 请求体为 16kHz WAV，通过 `Ocp-Apim-Subscription-Key` 请求头鉴权。
+//! 接口地址由资源所在地域决定（如 eastasia）。
+//!
+//! 短音频接口不区分说话人。[`AzureSpeechClient::with_diarization`] 开启说话人区分后改用
+//! 快速转写接口：multipart 上传录音和 JSON 参数，单次最长 2 小时，返回带说话人编号和
+//! 时间戳的短语，转换为 [`Segment`]。
+//!
+//! ## 参考文档
+//! - 短音频 REST 接口：https://learn.microsoft.com/azure/ai-services/speech-service/rest-speech-to-text-short
+//! - 快速转写接口：https://learn.microsoft.com/azure/ai-services/speech-service/fast-transcription-create
+
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+
+use super::timeouts::{AsrTimeouts, DEFAULT_RECEIVE_TIMEOUT};
+use super::{AsrCapabilities, AsrClient, AudioFormat, REALTIME_MAX_SECS};
+use crate::error::{Result, VoiceError};
+use crate::language::normalize_language;
+use crate::types::{AudioData, Segment, TranscribeResult};
+
+const API_PATH: &str = "/speech/recognition/conversation/cognitiveservices/v1";
+
+/// 快速转写接口路径和版本
+const FAST_TRANSCRIPTION_PATH: &str =
+    "/speechtotext/transcriptions:transcribe?api-version=2024-11-15";
+
+/// 快速转写单次最长音频（秒）
+const FAST_TRANSCRIPTION_MAX_SECS: f32 = 2.0 * 3600.0;
+
+/// 区分说话人时最多识别的说话人数
+const MAX_SPEAKERS: u32 = 10;
+
+/// 脏话过滤方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AzureProfanity {
+    /// 用星号替换
+    #[default]
+    Masked,
+    /// 从结果中删除
+    Removed,
+    /// 保留原文
+    Raw,
+}
+
+impl AzureProfanity {
+    /// 接口的 `profanity` 参数值
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Masked => "masked",
+            Self::Removed => "removed",
+            Self::Raw => "raw",
+        }
+    }
+
+    /// 快速转写接口的 `profanityFilterMode` 参数值
+    fn filter_mode(&self) -> &'static str {
+        match self {
+            Self::Masked => "Masked",
+            Self::Removed => "Removed",
+            Self::Raw => "None",
+        }
+    }
+}
+
+/// 识别响应（format=detailed）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RecognitionResponse {
+    recognition_status: String,
+    #[serde(default)]
+    display_text: String,
+    #[serde(default, rename = "NBest")]
+    n_best: Vec<NBestEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct NBestEntry {
+    #[serde(default)]
+    confidence: Option<f32>,
+    #[serde(default)]
+    display: String,
+}
+
+/// 快速转写响应
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FastTranscriptionResponse {
+    #[serde(default)]
+    phrases: Vec<FastPhrase>,
+}
+
+/// 快速转写的短语
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FastPhrase {
+    offset_milliseconds: u64,
+    duration_milliseconds: u64,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    confidence: Option<f32>,
+    /// 说话人编号（从 1 开始，开启说话人区分时返回）
+    #[serde(default)]
+    speaker: Option<u32>,
+}
+
+/// Azure 语音客户端
+pub struct AzureSpeechClient {
+    subscription_key: String,
+    region: String,
+    language: String,
+    profanity: AzureProfanity,
+    endpoint: Option<String>,
+    diarize: bool,
+    http: reqwest::Client,
+    timeouts: AsrTimeouts,
+}
+
+impl AzureSpeechClient {
+    /// Azure 短音频识别的能力
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: false,
+        timestamps: false,
+        hotwords: false,
+        punctuation: true,
+        // zh-CN 模型支持中英混说
+        code_switching: true,
+        diarization: false,
+        max_duration_secs: Some(REALTIME_MAX_SECS),
+        // OGG 需要 Opus 编码，没有可用的纯 Rust 编码器
+        formats: &[AudioFormat::Wav],
+        sample_rates: &[16000],
+    };
+
+    /// 开启说话人区分（快速转写接口）时的能力
+    pub const DIARIZATION_CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        timestamps: true,
+        max_duration_secs: Some(FAST_TRANSCRIPTION_MAX_SECS),
+        diarization: true,
+        ..Self::CAPABILITIES
+    };
+
+    /// 创建新的客户端
+    pub fn new(subscription_key: String, region: String) -> Self {
+        Self {
+            subscription_key,
+            region,
+            language: "zh-CN".to_string(),
+            profanity: AzureProfanity::default(),
+            endpoint: None,
+            diarize: false,
+            http: AsrTimeouts::default().http_client(),
+            timeouts: AsrTimeouts::default(),
+        }
+    }
+
+    /// 设置识别语言（BCP-47 代码，如 zh-CN、en-US）
+    pub fn with_language(mut self, language: String) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// 设置脏话过滤方式
+    pub fn with_profanity(mut self, profanity: AzureProfanity) -> Self {
+        self.profanity = profanity;
+        self
+    }
+
+    /// 设置接口地址（用于私有终结点或测试），不含路径
+    pub fn with_endpoint(mut self, endpoint: String) -> Self {
+        self.endpoint = Some(endpoint);
+        self
+    }
+
+    /// 设置是否区分说话人，开启后改用快速转写接口
+    pub fn with_diarization(mut self, diarize: bool) -> Self {
+        self.diarize = diarize;
+        self
+    }
+
+    /// 设置超时，HTTP 请求的计时方式见 [`AsrTimeouts`]
+    pub fn with_timeouts(mut self, timeouts: AsrTimeouts) -> Self {
+        self.http = timeouts.http_client();
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 完整的请求地址
+    fn url(&self) -> String {
+        let base = match &self.endpoint {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_string(),
+            None => format!("https://{}.stt.speech.microsoft.com", self.region.trim()),
+        };
+        format!(
+            "{}{}?language={}&format=detailed&profanity={}",
+            base,
+            API_PATH,
+            urlencoding::encode(&self.language),
+            self.profanity.as_str()
+        )
+    }
+
+    /// 快速转写接口地址
+    fn fast_transcription_url(&self) -> String {
+        let base = match &self.endpoint {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_string(),
+            None => format!("https://{}.api.cognitive.microsoft.com", self.region.trim()),
+        };
+        format!("{}{}", base, FAST_TRANSCRIPTION_PATH)
+    }
+
+    /// 通过快速转写接口识别并区分说话人
+    async fn transcribe_with_speakers(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        let encoded = Self::DIARIZATION_CAPABILITIES.encode(audio)?;
+        let definition = serde_json::json!({
+            "locales": [self.language],
+            "profanityFilterMode": self.profanity.filter_mode(),
+            "diarization": {"enabled": true, "maxSpeakers": MAX_SPEAKERS},
+        });
+
+        let file_name = encoded.file_name("audio");
+        let mime_type = encoded.mime_type();
+        let audio_part = Part::bytes(encoded.bytes)
+            .file_name(file_name)
+            .mime_str(mime_type)
+            .map_err(|e| VoiceError::AsrError(e.to_string()))?;
+        let form = Form::new()
+            .part("audio", audio_part)
+            .text("definition", definition.to_string());
+
+        let response = self
+            .http
+            .post(self.fast_transcription_url())
+            .header("Ocp-Apim-Subscription-Key", &self.subscription_key)
+            .multipart(form)
+            .timeout(self.timeouts.request_timeout(DEFAULT_RECEIVE_TIMEOUT))
+            .send()
+            .await
+            .map_err(|e| VoiceError::NetworkError(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = format!("Azure 快速转写错误: {} - {}", status, body);
+            return Err(match status.as_u16() {
+                401 | 403 => VoiceError::AsrAuthError(message),
+                _ => VoiceError::AsrError(message),
+            });
+        }
+
+        let result: FastTranscriptionResponse = response
+            .json()
+            .await
+            .map_err(|e| VoiceError::AsrError(e.to_string()))?;
+
+        let scores: Vec<f32> = result.phrases.iter().filter_map(|p| p.confidence).collect();
+        let confidence =
+            (!scores.is_empty()).then(|| scores.iter().sum::<f32>() / scores.len() as f32);
+        let segments: Vec<Segment> = result
+            .phrases
+            .into_iter()
+            .filter(|p| !p.text.trim().is_empty())
+            .map(|p| Segment {
+                start: p.offset_milliseconds as f32 / 1000.0,
+                end: (p.offset_milliseconds + p.duration_milliseconds) as f32 / 1000.0,
+                text: p.text,
+                speaker: p.speaker.map(|s| s.saturating_sub(1)),
+            })
+            .collect();
+
+        Ok(TranscribeResult {
+            text: segments.iter().map(|s| s.text.as_str()).collect(),
+            language: Some(normalize_language(&self.language)),
+            confidence,
+            segments,
+            backend: None,
+        })
+    }
+}
+
+#[async_trait]
+impl AsrClient for AzureSpeechClient {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        if self.diarize {
+            return self.transcribe_with_speakers(audio).await;
+        }
+        let encoded = Self::CAPABILITIES.encode(audio)?;
+
+        let response = self
+            .http
+            .post(self.url())
+            .header("Ocp-Apim-Subscription-Key", &self.subscription_key)
+            .header(
+                "Content-Type",
+                format!(
+                    "audio/wav; codecs=audio/pcm; samplerate={}",
+                    encoded.sample_rate
+                ),
+            )
+            .header("Accept", "application/json")
+            .body(encoded.bytes)
+            .timeout(self.timeouts.request_timeout(DEFAULT_RECEIVE_TIMEOUT))
+            .send()
+            .await
+            .map_err(|e| VoiceError::NetworkError(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = format!("Azure 语音错误: {} - {}", status, body);
+            return Err(match status.as_u16() {
+                401 | 403 => VoiceError::AsrAuthError(message),
+                _ => VoiceError::AsrError(message),
+            });
+        }
+
+        let result: RecognitionResponse = response
+            .json()
+            .await
+            .map_err(|e| VoiceError::AsrError(e.to_string()))?;
+
+        let (text, confidence) = match result.recognition_status.as_str() {
+            "Success" => match result.n_best.into_iter().next() {
+                Some(best) if !best.display.is_empty() => (best.display, best.confidence),
+                _ => (result.display_text, None),
+            },
+            // 没有识别出语音
+            "NoMatch" | "InitialSilenceTimeout" | "BabbleTimeout" => (String::new(), None),
+            other => {
+                return Err(VoiceError::AsrError(format!(
+                    "Azure 语音识别失败: {}",
+                    other
+                )))
+            }
+        };
+
+        Ok(TranscribeResult {
+            text,
+            language: Some(normalize_language(&self.language)),
+            confidence,
+            segments: vec![],
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "Azure 语音"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        if self.diarize {
+            Self::DIARIZATION_CAPABILITIES
+        } else {
+            Self::CAPABILITIES
+        }
+    }
+}
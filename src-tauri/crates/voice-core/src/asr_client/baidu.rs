@@ -1,23 +1,87 @@
 //! 百度语音识别客户端
 //!
 //! 使用百度 AI 开放平台的语音识别 API。
+//!
+//! Access Token 有效期约 30 天，按 API Key 缓存在进程内，同一凭证的客户端共用，
+//! 每次听写不再额外请求一次 Token。剩余有效期不足 [`TOKEN_REFRESH_MARGIN`]
+//! （或有效期的十分之一）时提前刷新，刷新失败而旧 Token 尚未过期时继续使用旧 Token；
+//! 识别接口返回 Token 无效时清除缓存，下次重新获取。
+//!
+//! 短语音识别接口没有请求级的热词参数（自定义词表需在百度自训练平台上线模型），
+//! 因此不覆盖 [`AsrClient::with_hotwords`]，设置的热词被忽略。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
 
-use super::AsrClient;
+use super::rate_limit::RateLimit;
+use super::timeouts::{AsrTimeouts, DEFAULT_RECEIVE_TIMEOUT};
+use super::{AsrCapabilities, AsrClient, AudioFormat, REALTIME_MAX_SECS};
 use crate::error::{Result, VoiceError};
 use crate::types::{AudioData, TranscribeResult};
 
+/// 获取 Access Token 的接口
+const TOKEN_ENDPOINT: &str = "https://aip.baidubce.com/oauth/2.0/token";
+
+/// 短语音识别接口
+const API_ENDPOINT: &str = "https://vop.baidu.com/server_api";
+
+/// Token 剩余有效期不足此值时提前刷新
+pub const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(24 * 3600);
+
+/// 识别接口返回的 Token 无效（鉴权失败）错误码
+const ERR_TOKEN_INVALID: i32 = 3302;
+
 /// 百度 Token 响应
 #[derive(Debug, Deserialize)]
 struct TokenResponse {
     access_token: String,
-    #[allow(dead_code)]
     expires_in: u64,
 }
 
+/// 缓存的 Access Token
+#[derive(Debug, Clone)]
+struct CachedToken {
+    token: String,
+    /// 到此时间后提前刷新
+    refresh_at: Instant,
+    /// 过期时间
+    expires_at: Instant,
+}
+
+impl CachedToken {
+    fn new(token: String, expires_in: Duration) -> Self {
+        let now = Instant::now();
+        let margin = TOKEN_REFRESH_MARGIN.min(expires_in / 10);
+        Self {
+            token,
+            refresh_at: now + expires_in - margin,
+            expires_at: now + expires_in,
+        }
+    }
+}
+
+/// 同一凭证的客户端共用的 Token 缓存
+type TokenCache = Arc<RwLock<Option<CachedToken>>>;
+
+/// 按 Token 接口和凭证获取共用的 Token 缓存
+fn shared_token_cache(token_endpoint: &str, api_key: &str, secret_key: &str) -> TokenCache {
+    static CACHES: OnceLock<Mutex<HashMap<String, TokenCache>>> = OnceLock::new();
+    let key = format!("{}\n{}\n{}", token_endpoint, api_key, secret_key);
+    CACHES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(key)
+        .or_default()
+        .clone()
+}
+
 /// 百度 ASR 响应
 #[derive(Debug, Deserialize)]
 struct AsrResponse {
@@ -43,33 +107,113 @@ struct AsrRequest {
 pub struct BaiduClient {
     api_key: String,
     secret_key: String,
-    cached_token: Option<String>,
+    token_endpoint: String,
+    endpoint: String,
+    token_cache: TokenCache,
+    http: reqwest::Client,
+    timeouts: AsrTimeouts,
 }
 
 impl BaiduClient {
+    /// 百度短语音识别的能力
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: false,
+        timestamps: false,
+        hotwords: false,
+        punctuation: true,
+        // 普通话模型对夹杂的英文单词识别较差
+        code_switching: false,
+        diarization: false,
+        max_duration_secs: Some(REALTIME_MAX_SECS),
+        formats: &[
+            AudioFormat::Wav,
+            AudioFormat::Pcm,
+            AudioFormat::Amr,
+            AudioFormat::M4a,
+        ],
+        // 只支持 16k 和 8k
+        sample_rates: &[16000, 8000],
+    };
+
+    /// 默认限流：每秒 2 次，不允许突发
+    ///
+    /// 百度按应用限制 QPS，免费额度的上限较低，超出时返回错误，开通更高配额后可在凭证中调大
+    pub const DEFAULT_RATE_LIMIT: RateLimit = RateLimit {
+        per_second: 2.0,
+        burst: 1,
+    };
+
     /// 创建新的客户端
     pub fn new(api_key: String, secret_key: String) -> Self {
+        let token_cache = shared_token_cache(TOKEN_ENDPOINT, &api_key, &secret_key);
         Self {
             api_key,
             secret_key,
-            cached_token: None,
+            token_endpoint: TOKEN_ENDPOINT.to_string(),
+            endpoint: API_ENDPOINT.to_string(),
+            token_cache,
+            http: AsrTimeouts::default().http_client(),
+            timeouts: AsrTimeouts::default(),
         }
     }
 
-    /// 获取 Access Token
-    async fn get_token(&mut self) -> Result<String> {
-        if let Some(ref token) = self.cached_token {
-            return Ok(token.clone());
+    /// 设置 Token 接口和识别接口地址（用于代理或测试）
+    pub fn with_endpoints(mut self, token_endpoint: String, endpoint: String) -> Self {
+        self.token_cache = shared_token_cache(&token_endpoint, &self.api_key, &self.secret_key);
+        self.token_endpoint = token_endpoint;
+        self.endpoint = endpoint;
+        self
+    }
+
+    /// 设置超时，HTTP 请求的计时方式见 [`AsrTimeouts`]
+    pub fn with_timeouts(mut self, timeouts: AsrTimeouts) -> Self {
+        self.http = timeouts.http_client();
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 获取 Access Token，缓存未到刷新时间时直接使用
+    async fn get_token(&self) -> Result<String> {
+        if let Some(cached) = self.token_cache.read().await.as_ref() {
+            if Instant::now() < cached.refresh_at {
+                return Ok(cached.token.clone());
+            }
         }
 
-        let url = format!(
-            "https://aip.baidubce.com/oauth/2.0/token?grant_type=client_credentials&client_id={}&client_secret={}",
-            self.api_key, self.secret_key
-        );
+        // 持有写锁刷新，并发的识别请求只请求一次 Token
+        let mut cache = self.token_cache.write().await;
+        if let Some(cached) = cache.as_ref() {
+            if Instant::now() < cached.refresh_at {
+                return Ok(cached.token.clone());
+            }
+        }
+        match self.fetch_token().await {
+            Ok(fresh) => {
+                let token = fresh.token.clone();
+                *cache = Some(fresh);
+                Ok(token)
+            }
+            Err(e) => match cache.as_ref() {
+                Some(cached) if Instant::now() < cached.expires_at => {
+                    tracing::warn!("[百度语音] 刷新 Token 失败，继续使用未过期的 Token: {}", e);
+                    Ok(cached.token.clone())
+                }
+                _ => Err(e),
+            },
+        }
+    }
 
-        let client = reqwest::Client::new();
-        let response = client
-            .post(&url)
+    /// 请求新的 Access Token
+    async fn fetch_token(&self) -> Result<CachedToken> {
+        let response = self
+            .http
+            .post(&self.token_endpoint)
+            .query(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", self.api_key.as_str()),
+                ("client_secret", self.secret_key.as_str()),
+            ])
+            .timeout(self.timeouts.request_timeout(DEFAULT_RECEIVE_TIMEOUT))
             .send()
             .await
             .map_err(|e| VoiceError::NetworkError(e.to_string()))?;
@@ -83,35 +227,35 @@ impl BaiduClient {
             .await
             .map_err(|e| VoiceError::AsrAuthError(e.to_string()))?;
 
-        self.cached_token = Some(token_resp.access_token.clone());
-        Ok(token_resp.access_token)
+        Ok(CachedToken::new(
+            token_resp.access_token,
+            Duration::from_secs(token_resp.expires_in),
+        ))
     }
 }
 
 #[async_trait]
 impl AsrClient for BaiduClient {
     async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
-        // 需要可变引用来缓存 token
-        let mut client = BaiduClient::new(self.api_key.clone(), self.secret_key.clone());
-        let token = client.get_token().await?;
+        let token = self.get_token().await?;
 
-        let wav_bytes = audio.to_wav_bytes();
-        let speech = BASE64.encode(&wav_bytes);
+        let encoded = Self::CAPABILITIES.encode(audio)?;
 
         let request = AsrRequest {
-            format: "wav".to_string(),
-            rate: audio.sample_rate,
-            channel: audio.channels,
+            format: encoded.extension().to_string(),
+            rate: encoded.sample_rate,
+            channel: encoded.channels,
             cuid: "proxycast".to_string(),
             token,
-            speech,
-            len: wav_bytes.len(),
+            speech: BASE64.encode(&encoded.bytes),
+            len: encoded.bytes.len(),
         };
 
-        let http_client = reqwest::Client::new();
-        let response = http_client
-            .post("https://vop.baidu.com/server_api")
+        let response = self
+            .http
+            .post(&self.endpoint)
             .json(&request)
+            .timeout(self.timeouts.request_timeout(DEFAULT_RECEIVE_TIMEOUT))
             .send()
             .await
             .map_err(|e| VoiceError::NetworkError(e.to_string()))?;
@@ -121,6 +265,14 @@ impl AsrClient for BaiduClient {
             .await
             .map_err(|e| VoiceError::AsrError(e.to_string()))?;
 
+        if result.err_no == ERR_TOKEN_INVALID {
+            // Token 被吊销或凭证已更换，下次重新获取
+            *self.token_cache.write().await = None;
+            return Err(VoiceError::AsrAuthError(format!(
+                "百度 ASR 鉴权失败: {}",
+                result.err_msg
+            )));
+        }
         if result.err_no != 0 {
             return Err(VoiceError::AsrError(format!(
                 "百度 ASR 错误: {} - {}",
@@ -135,10 +287,15 @@ impl AsrClient for BaiduClient {
             language: Some("zh".to_string()),
             confidence: None,
             segments: vec![],
+            backend: None,
         })
     }
 
     fn name(&self) -> &'static str {
         "百度语音"
     }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        Self::CAPABILITIES
+    }
 }
@@ -0,0 +1,282 @@
+//! 自定义命令 ASR 客户端
+//!
+//! 启动用户配置的外部命令，把录音以 WAV 格式写入其标准输入，从标准输出读取 JSON 识别结果，
+//! 用于接入尚未集成的识别引擎（如本地的 whisper-ctranslate2 脚本）。
+//!
+//! 参数中的占位符：
+//! - `{language}`：识别语言（通用语言代码，未指定时为 `auto`）
+//! - `{hotwords}`：逗号分隔的热词，未设置时为空字符串
+//! - `{file}`：录音的临时 WAV 文件路径。使用此占位符时不再写入标准输入，
+//!   适合只接受文件路径的命令。隐私设置禁止写盘时使用此占位符的识别会失败
+//!
+//! 标准输出格式（`language` 可省略）：
+//!
+//! ```json
+//! {"text": "识别文本", "language": "zh"}
+//! ```
+//!
+//! 命令在结果前打印了日志时，取最后一个非空行解析。
+
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use super::timeouts::AsrTimeouts;
+use super::{AsrCapabilities, AsrClient, AudioFormat};
+use crate::error::{Result, VoiceError};
+use crate::language::{normalize_language, MIXED_LANGUAGE};
+use crate::privacy::{self, TEMP_AUDIO_PREFIX};
+use crate::types::{AudioData, TranscribeResult};
+
+/// 默认接收超时（秒）
+pub const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// 错误信息中保留的标准错误输出长度（字符）
+const STDERR_TAIL_CHARS: usize = 500;
+
+/// 临时文件序号，避免并发识别时文件名冲突
+static TEMP_FILE_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// 命令输出的识别结果
+#[derive(Debug, Deserialize)]
+struct CommandOutput {
+    text: String,
+    #[serde(default)]
+    language: Option<String>,
+}
+
+/// 自定义命令客户端
+pub struct CommandClient {
+    program: String,
+    args: Vec<String>,
+    timeouts: AsrTimeouts,
+    language: Option<String>,
+    hotwords: Vec<String>,
+}
+
+impl CommandClient {
+    /// 自定义命令的能力
+    ///
+    /// 无法预知外部引擎的能力，按最保守的情况声明：不假定带标点，由调用方按需补全
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: false,
+        timestamps: false,
+        hotwords: true,
+        punctuation: false,
+        code_switching: false,
+        diarization: false,
+        max_duration_secs: None,
+        formats: &[AudioFormat::Wav],
+        sample_rates: &[],
+    };
+
+    /// 创建新的客户端
+    pub fn new(program: String, args: Vec<String>) -> Self {
+        Self {
+            program,
+            args,
+            timeouts: AsrTimeouts::default(),
+            language: None,
+            hotwords: Vec::new(),
+        }
+    }
+
+    /// 设置超时时间，超时后终止命令
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.receive = Some(timeout);
+        self
+    }
+
+    /// 设置超时：发送超时用于把录音写入标准输入，接收超时为等待命令退出的最长时间，
+    /// 本地命令没有连接阶段
+    pub fn with_timeouts(mut self, timeouts: AsrTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 设置语言
+    pub fn with_language(mut self, language: String) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// 替换参数中的占位符
+    fn expand_args(&self, file: Option<&str>) -> Vec<String> {
+        let language = self.language.as_deref().unwrap_or("auto");
+        let hotwords = self.hotwords.join(",");
+        self.args
+            .iter()
+            .map(|arg| {
+                let arg = arg
+                    .replace("{language}", language)
+                    .replace("{hotwords}", &hotwords);
+                match file {
+                    Some(path) => arg.replace("{file}", path),
+                    None => arg,
+                }
+            })
+            .collect()
+    }
+
+    /// 运行命令，返回标准输出
+    async fn run(&self, wav_bytes: Vec<u8>) -> Result<String> {
+        let temp_file = self
+            .args
+            .iter()
+            .any(|arg| arg.contains("{file}"))
+            .then(temp_wav_path);
+        if let Some(ref path) = temp_file {
+            privacy::check_disk()?;
+            tokio::fs::write(path, &wav_bytes).await?;
+        }
+
+        let file_arg = temp_file
+            .as_ref()
+            .map(|path| path.to_string_lossy().into_owned());
+        let result = self.spawn_and_wait(wav_bytes, file_arg.as_deref()).await;
+
+        if let Some(path) = temp_file {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+        result
+    }
+
+    /// 启动命令并等待退出，未使用临时文件时把录音写入标准输入
+    async fn spawn_and_wait(&self, wav_bytes: Vec<u8>, file: Option<&str>) -> Result<String> {
+        let use_stdin = file.is_none();
+        let mut child = Command::new(&self.program)
+            .args(self.expand_args(file))
+            .stdin(if use_stdin {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                VoiceError::AsrError(format!("启动识别命令失败 {}: {}", self.program, e))
+            })?;
+
+        let stdin = child.stdin.take();
+        let send_timeout = self.timeouts.send();
+        let write = async move {
+            if let Some(mut stdin) = stdin {
+                // 命令不读取标准输入时写入会失败，不影响结果；写入超时后关闭标准输入
+                match tokio::time::timeout(send_timeout, stdin.write_all(&wav_bytes)).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => tracing::warn!("[自定义命令] 写入标准输入失败: {}", e),
+                    Err(_) => tracing::warn!("[自定义命令] 写入标准输入超时"),
+                }
+            }
+        };
+
+        // 超时后 child 随 future 一起被丢弃，kill_on_drop 会终止命令
+        let timeout = self
+            .timeouts
+            .receive_or(Duration::from_secs(DEFAULT_TIMEOUT_SECS));
+        let (_, output) = tokio::time::timeout(timeout, async {
+            tokio::join!(write, child.wait_with_output())
+        })
+        .await
+        .map_err(|_| VoiceError::AsrError(format!("识别命令超时（{} 秒）", timeout.as_secs())))?;
+        let output = output?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(VoiceError::AsrError(format!(
+                "识别命令退出码 {}: {}",
+                output
+                    .status
+                    .code()
+                    .map_or("未知".to_string(), |c| c.to_string()),
+                tail(stderr.trim(), STDERR_TAIL_CHARS)
+            )));
+        }
+
+        String::from_utf8(output.stdout)
+            .map_err(|_| VoiceError::AsrError("识别命令输出不是有效的 UTF-8".to_string()))
+    }
+}
+
+#[async_trait]
+impl AsrClient for CommandClient {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        let stdout = self.run(audio.to_wav_bytes()).await?;
+        let output = parse_output(&stdout)?;
+
+        Ok(TranscribeResult {
+            text: output.text.trim().to_string(),
+            // 命令未返回语言时使用指定的语言
+            language: output.language.map(|l| normalize_language(&l)).or_else(|| {
+                self.language
+                    .clone()
+                    .filter(|l| l != "auto" && l != MIXED_LANGUAGE)
+            }),
+            confidence: None,
+            segments: Vec::new(),
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "自定义命令"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        Self::CAPABILITIES
+    }
+
+    fn with_hotwords(mut self, hotwords: Vec<String>) -> Self {
+        self.hotwords = hotwords;
+        self
+    }
+}
+
+/// 解析命令输出：整段不是 JSON 时取最后一个非空行
+fn parse_output(stdout: &str) -> Result<CommandOutput> {
+    let stdout = stdout.trim();
+    if let Ok(output) = serde_json::from_str(stdout) {
+        return Ok(output);
+    }
+
+    let last_line = stdout
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or_default();
+    serde_json::from_str(last_line).map_err(|e| {
+        VoiceError::AsrError(format!(
+            "无法解析识别命令输出（需要 {{\"text\": ...}} 格式的 JSON）: {}: {}",
+            e,
+            tail(stdout, STDERR_TAIL_CHARS)
+        ))
+    })
+}
+
+/// 保留字符串末尾最多 `max_chars` 个字符
+fn tail(text: &str, max_chars: usize) -> &str {
+    let count = text.chars().count();
+    match text.char_indices().nth(count.saturating_sub(max_chars)) {
+        Some((index, _)) => &text[index..],
+        None => text,
+    }
+}
+
+/// 临时 WAV 文件路径
+fn temp_wav_path() -> PathBuf {
+    let seq = TEMP_FILE_SEQ.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!(
+        "{}{}-{}.wav",
+        TEMP_AUDIO_PREFIX,
+        std::process::id(),
+        seq
+    ))
+}
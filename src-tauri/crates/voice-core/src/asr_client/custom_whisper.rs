@@ -0,0 +1,218 @@
+//! 自建 Whisper 服务客户端
+//!
+//! 把录音上传到兼容 OpenAI 的 `/v1/audio/transcriptions` 接口，用于接入局域网内自建的
+//! faster-whisper / whisper.cpp 等服务，识别不占用本机算力，也不需要内置的 whisper-rs 模型。
+//!
+//! - 地址只需填到服务根路径（如 `http://192.168.1.10:8000`），以 `/v1` 结尾时同样可用
+//! - 音频以 16kHz WAV 上传，whisper.cpp 服务只接受 WAV
+//! - 请求 `verbose_json` 格式，服务返回分段时带上时间戳和检测到的语言
+//! - API Key 可选，自建服务通常不鉴权
+//! - 热词以 `prompt` 字段上传，Whisper 会倾向于按提示中的写法输出这些词
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+
+use super::timeouts::AsrTimeouts;
+use super::{AsrCapabilities, AsrClient, AudioFormat};
+use crate::error::{Result, VoiceError};
+use crate::language::normalize_language;
+use crate::types::{AudioData, Segment, TranscribeResult};
+
+/// 默认模型名（faster-whisper 服务按名称加载模型，whisper.cpp 忽略该字段）
+pub const DEFAULT_MODEL: &str = "whisper-1";
+
+/// 默认接收超时（秒），CPU 上运行的服务识别较长录音可能需要较长时间
+pub const DEFAULT_TIMEOUT_SECS: u64 = 120;
+
+/// 识别响应（json 或 verbose_json）
+#[derive(Debug, Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    segments: Vec<TranscriptionSegment>,
+}
+
+/// 分段及其时间戳（秒）
+#[derive(Debug, Deserialize)]
+struct TranscriptionSegment {
+    start: f32,
+    end: f32,
+    text: String,
+}
+
+/// 自建 Whisper 服务客户端
+pub struct CustomWhisperClient {
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    language: Option<String>,
+    hotwords: Vec<String>,
+    http: reqwest::Client,
+    timeouts: AsrTimeouts,
+}
+
+impl CustomWhisperClient {
+    /// 自建 Whisper 服务的能力
+    ///
+    /// 局域网内不限上传大小和时长；只声明 WAV 以兼容 whisper.cpp
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: false,
+        timestamps: true,
+        hotwords: true,
+        punctuation: true,
+        code_switching: true,
+        diarization: false,
+        max_duration_secs: None,
+        formats: &[AudioFormat::Wav],
+        sample_rates: &[16000],
+    };
+
+    /// 创建新的客户端
+    pub fn new(base_url: String) -> Self {
+        Self {
+            base_url,
+            api_key: None,
+            model: DEFAULT_MODEL.to_string(),
+            language: None,
+            hotwords: Vec::new(),
+            http: AsrTimeouts::default().http_client(),
+            timeouts: AsrTimeouts::default(),
+        }
+    }
+
+    /// 设置 API Key（以 Bearer 方式发送）
+    pub fn with_api_key(mut self, api_key: String) -> Self {
+        self.api_key = Some(api_key);
+        self
+    }
+
+    /// 设置模型名（如 Systran/faster-whisper-large-v3）
+    pub fn with_model(mut self, model: String) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// 设置识别语言（如 zh、en）
+    pub fn with_language(mut self, language: String) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// 设置等待识别结果的超时
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.receive = Some(timeout);
+        self
+    }
+
+    /// 设置超时，HTTP 请求的计时方式见 [`AsrTimeouts`]
+    pub fn with_timeouts(mut self, timeouts: AsrTimeouts) -> Self {
+        self.http = timeouts.http_client();
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 完整的请求地址
+    pub fn url(&self) -> String {
+        let base = self.base_url.trim_end_matches('/');
+        if base.ends_with("/v1") {
+            format!("{}/audio/transcriptions", base)
+        } else {
+            format!("{}/v1/audio/transcriptions", base)
+        }
+    }
+}
+
+#[async_trait]
+impl AsrClient for CustomWhisperClient {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        let encoded = Self::CAPABILITIES.encode(audio)?;
+        let url = self.url();
+
+        let file_name = encoded.file_name("audio");
+        let mime_type = encoded.mime_type();
+        let file_part = Part::bytes(encoded.bytes)
+            .file_name(file_name)
+            .mime_str(mime_type)
+            .map_err(|e| VoiceError::AsrError(e.to_string()))?;
+        let mut form = Form::new()
+            .part("file", file_part)
+            .text("model", self.model.clone())
+            .text("response_format", "verbose_json");
+        if let Some(language) = &self.language {
+            form = form.text("language", language.clone());
+        }
+        if !self.hotwords.is_empty() {
+            form = form.text("prompt", self.hotwords.join(", "));
+        }
+
+        let mut request = self
+            .http
+            .post(&url)
+            .timeout(
+                self.timeouts
+                    .request_timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS)),
+            )
+            .multipart(form);
+        if let Some(api_key) = self.api_key.as_deref().filter(|k| !k.is_empty()) {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+        let response = request.send().await.map_err(|e| {
+            VoiceError::NetworkError(format!("无法连接 Whisper 服务 {}: {}", url, e))
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = format!("Whisper 服务错误: {} - {}", status, body.trim());
+            return Err(match status.as_u16() {
+                401 | 403 => VoiceError::AsrAuthError(message),
+                _ => VoiceError::AsrError(message),
+            });
+        }
+
+        let result: TranscriptionResponse = response
+            .json()
+            .await
+            .map_err(|e| VoiceError::AsrError(format!("Whisper 服务响应格式错误: {}", e)))?;
+
+        let segments = result
+            .segments
+            .into_iter()
+            .map(|segment| Segment {
+                start: segment.start,
+                end: segment.end,
+                text: segment.text.trim().to_string(),
+                speaker: None,
+            })
+            .collect();
+
+        Ok(TranscribeResult {
+            text: result.text.trim().to_string(),
+            language: result
+                .language
+                .or_else(|| self.language.clone())
+                .map(|l| normalize_language(&l)),
+            confidence: None,
+            segments,
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "自建 Whisper"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        Self::CAPABILITIES
+    }
+
+    fn with_hotwords(mut self, hotwords: Vec<String>) -> Self {
+        self.hotwords = hotwords;
+        self
+    }
+}
@@ -0,0 +1,274 @@
+//! Deepgram 语音识别客户端
+//!
+//! 使用 Deepgram 的预录音频接口（`/v1/listen`），请求体为 16kHz FLAC，
+//! 通过 `Authorization: Token {key}` 请求头鉴权。接口不限时长，识别结果带每个词的
+//! 时间戳，转换为逐词的 [`Segment`]。模型（如 nova-2）通过 [`DeepgramClient::with_model`] 选择。
+//!
+//! [`DeepgramClient::with_diarization`] 开启说话人区分（`diarize=true`）后，每个词带说话人编号，
+//! 同一说话人连续的词合并为一个分段。
+//!
+//! ## 参考文档
+//! - 预录音频接口：https://developers.deepgram.com/reference/listen-file
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::timeouts::{AsrTimeouts, DEFAULT_RECEIVE_TIMEOUT};
+use super::{AsrCapabilities, AsrClient, AudioFormat};
+use crate::error::{Result, VoiceError};
+use crate::language::normalize_language;
+use crate::types::{AudioData, Segment, TranscribeResult};
+
+/// 默认接口地址
+const DEFAULT_ENDPOINT: &str = "https://api.deepgram.com";
+
+/// 默认模型
+pub const DEFAULT_MODEL: &str = "nova-2";
+
+/// 识别响应
+#[derive(Debug, Deserialize)]
+struct ListenResponse {
+    results: ListenResults,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenResults {
+    #[serde(default)]
+    channels: Vec<ListenChannel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenChannel {
+    /// 自动检测到的语言（仅 detect_language=true）
+    #[serde(default)]
+    detected_language: Option<String>,
+    #[serde(default)]
+    alternatives: Vec<ListenAlternative>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenAlternative {
+    #[serde(default)]
+    transcript: String,
+    #[serde(default)]
+    confidence: Option<f32>,
+    #[serde(default)]
+    words: Vec<ListenWord>,
+}
+
+/// 词及其时间戳（秒）
+#[derive(Debug, Deserialize)]
+struct ListenWord {
+    word: String,
+    start: f32,
+    end: f32,
+    /// 带标点和大小写的词（punctuate=true 时返回）
+    #[serde(default)]
+    punctuated_word: Option<String>,
+    /// 说话人编号（diarize=true 时返回）
+    #[serde(default)]
+    speaker: Option<u32>,
+}
+
+/// 错误响应
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    #[serde(default)]
+    err_code: String,
+    #[serde(default)]
+    err_msg: String,
+}
+
+/// Deepgram 客户端
+pub struct DeepgramClient {
+    api_key: String,
+    model: String,
+    /// 识别语言，为空时自动检测
+    language: Option<String>,
+    endpoint: String,
+    diarize: bool,
+    http: reqwest::Client,
+    timeouts: AsrTimeouts,
+}
+
+impl DeepgramClient {
+    /// Deepgram 预录音频接口的能力
+    ///
+    /// 接口不限时长，这里不设时长上限
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: false,
+        timestamps: true,
+        hotwords: false,
+        punctuation: true,
+        code_switching: false,
+        diarization: true,
+        max_duration_secs: None,
+        formats: &[AudioFormat::Flac, AudioFormat::Wav],
+        sample_rates: &[16000],
+    };
+
+    /// 创建新的客户端
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            model: DEFAULT_MODEL.to_string(),
+            language: None,
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            diarize: false,
+            http: AsrTimeouts::default().http_client(),
+            timeouts: AsrTimeouts::default(),
+        }
+    }
+
+    /// 设置模型（如 nova-2、nova-2-general、whisper-large）
+    pub fn with_model(mut self, model: String) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// 设置识别语言（如 zh、en-US）
+    pub fn with_language(mut self, language: String) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// 设置接口地址（用于代理或测试），不含路径
+    pub fn with_endpoint(mut self, endpoint: String) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+
+    /// 设置是否区分说话人
+    pub fn with_diarization(mut self, diarize: bool) -> Self {
+        self.diarize = diarize;
+        self
+    }
+
+    /// 设置超时，HTTP 请求的计时方式见 [`AsrTimeouts`]
+    pub fn with_timeouts(mut self, timeouts: AsrTimeouts) -> Self {
+        self.http = timeouts.http_client();
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 完整的请求地址
+    fn url(&self) -> String {
+        let language = match &self.language {
+            Some(language) => format!("language={}", urlencoding::encode(language)),
+            None => "detect_language=true".to_string(),
+        };
+        let mut url = format!(
+            "{}/v1/listen?model={}&{}&punctuate=true&smart_format=true",
+            self.endpoint.trim_end_matches('/'),
+            urlencoding::encode(&self.model),
+            language
+        );
+        if self.diarize {
+            url.push_str("&diarize=true");
+        }
+        url
+    }
+}
+
+#[async_trait]
+impl AsrClient for DeepgramClient {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        let encoded = Self::CAPABILITIES.encode(audio)?;
+
+        let response = self
+            .http
+            .post(self.url())
+            .header("Authorization", format!("Token {}", self.api_key))
+            .header("Content-Type", encoded.mime_type())
+            .body(encoded.bytes)
+            .timeout(self.timeouts.request_timeout(DEFAULT_RECEIVE_TIMEOUT))
+            .send()
+            .await
+            .map_err(|e| VoiceError::NetworkError(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let detail = match serde_json::from_str::<ErrorResponse>(&body) {
+                Ok(error) => format!("{} {}", error.err_code, error.err_msg),
+                Err(_) => body,
+            };
+            let message = format!("Deepgram 错误: {} - {}", status, detail.trim());
+            return Err(match status.as_u16() {
+                401 | 403 => VoiceError::AsrAuthError(message),
+                _ => VoiceError::AsrError(message),
+            });
+        }
+
+        let result: ListenResponse = response
+            .json()
+            .await
+            .map_err(|e| VoiceError::AsrError(e.to_string()))?;
+
+        let Some(channel) = result.results.channels.into_iter().next() else {
+            return Err(VoiceError::AsrError("Deepgram 未返回识别结果".to_string()));
+        };
+        let language = channel
+            .detected_language
+            .or_else(|| self.language.clone())
+            .map(|l| normalize_language(&l));
+        let Some(best) = channel.alternatives.into_iter().next() else {
+            // 没有识别出语音
+            return Ok(TranscribeResult {
+                text: String::new(),
+                language,
+                confidence: None,
+                segments: vec![],
+                backend: None,
+            });
+        };
+
+        let segments = if self.diarize {
+            speaker_turns(best.words)
+        } else {
+            best.words.into_iter().map(word_segment).collect()
+        };
+
+        Ok(TranscribeResult {
+            text: best.transcript,
+            language,
+            confidence: best.confidence,
+            segments,
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "Deepgram"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        Self::CAPABILITIES
+    }
+}
+
+fn word_segment(word: ListenWord) -> Segment {
+    Segment {
+        start: word.start,
+        end: word.end,
+        text: word.punctuated_word.unwrap_or(word.word),
+        speaker: word.speaker,
+    }
+}
+
+/// 同一说话人连续的词合并为一个分段，词之间以空格分隔（与 `transcript` 一致）
+fn speaker_turns(words: Vec<ListenWord>) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = Vec::new();
+    for word in words {
+        let word = word_segment(word);
+        match segments.last_mut() {
+            Some(last) if last.speaker == word.speaker => {
+                last.end = word.end;
+                last.text.push(' ');
+                last.text.push_str(&word.text);
+            }
+            _ => segments.push(word),
+        }
+    }
+    segments
+}
@@ -0,0 +1,536 @@
+//! ASR 客户端工厂
+//!
+//! 由凭证参数统一构建对应的 [`AsrClient`]。构建前校验必填字段，
+//! 并把通用语言代码（zh / en / auto）转换为各服务的格式。
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use super::rate_limit::RateLimit;
+use super::timeouts::AsrTimeouts;
+use super::{
+    http, AsrCapabilities, AsrClient, AzureProfanity, AzureSpeechClient, BaiduClient,
+    CommandClient, CustomWhisperClient, DeepgramClient, HttpAsrClient, HttpUpload,
+    OpenAIWhisperClient, TencentClient, VolcengineClient, XunfeiClient, XunfeiLfasrClient,
+};
+use crate::error::{Result, VoiceError};
+use crate::language::MIXED_LANGUAGE;
+use crate::privacy;
+
+/// 云端 ASR 客户端配置
+///
+/// `language` 均为通用语言代码（如 "zh"、"en"、"auto"，中英混合为 [`MIXED_LANGUAGE`]）
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsrClientConfig {
+    /// OpenAI Whisper API
+    OpenAI {
+        api_key: String,
+        /// 自定义 API 地址（用于代理）
+        base_url: Option<String>,
+        language: String,
+    },
+    /// 自建 Whisper 服务（兼容 OpenAI 的转写接口）
+    CustomWhisper {
+        /// 服务地址（如 http://192.168.1.10:8000）
+        base_url: String,
+        /// API Key（可选）
+        api_key: Option<String>,
+        /// 模型名（可选）
+        model: Option<String>,
+        /// 超时时间（秒）
+        timeout_secs: u64,
+        language: String,
+    },
+    /// 百度短语音识别
+    Baidu { api_key: String, secret_key: String },
+    /// 腾讯云一句话识别
+    Tencent {
+        secret_id: String,
+        secret_key: String,
+        /// 地域（可选）
+        region: Option<String>,
+        language: String,
+    },
+    /// Azure 语音服务短音频识别
+    Azure {
+        subscription_key: String,
+        /// 资源所在地域（如 eastasia）
+        region: String,
+        /// 识别语言（BCP-47 代码，如 zh-CN），为空时按 `language` 推断
+        locale: Option<String>,
+        profanity: AzureProfanity,
+        /// 区分说话人（改用快速转写接口）
+        diarize: bool,
+        language: String,
+    },
+    /// Deepgram 预录音频识别
+    Deepgram {
+        api_key: String,
+        /// 模型（可选，默认 nova-2）
+        model: Option<String>,
+        /// 区分说话人
+        diarize: bool,
+        language: String,
+    },
+    /// 火山引擎流式识别
+    Volcengine {
+        app_id: String,
+        access_token: String,
+        /// 集群（可选，默认通用流式识别）
+        cluster: Option<String>,
+        language: String,
+    },
+    /// 讯飞听写
+    Xunfei {
+        app_id: String,
+        api_key: String,
+        api_secret: String,
+        language: String,
+    },
+    /// 讯飞录音文件转写
+    XunfeiLfasr {
+        app_id: String,
+        secret_key: String,
+        language: String,
+    },
+    /// 自定义命令
+    Command {
+        /// 可执行文件
+        program: String,
+        /// 参数（支持 `{language}`、`{file}` 占位符）
+        args: Vec<String>,
+        /// 超时时间（秒）
+        timeout_secs: u64,
+        language: String,
+    },
+    /// 通用 HTTP 接口
+    Http {
+        /// 接口地址（支持 `{language}` 占位符）
+        url: String,
+        /// 额外的请求头
+        headers: HashMap<String, String>,
+        /// 识别文本在响应 JSON 中的 JSONPath
+        text_path: String,
+        upload: HttpUpload,
+        /// 超时时间（秒）
+        timeout_secs: u64,
+        language: String,
+    },
+}
+
+impl AsrClientConfig {
+    /// 服务名称（用于错误提示）
+    fn provider_name(&self) -> &'static str {
+        match self {
+            Self::OpenAI { .. } => "OpenAI Whisper",
+            Self::CustomWhisper { .. } => "自建 Whisper",
+            Self::Baidu { .. } => "百度语音",
+            Self::Tencent { .. } => "腾讯云语音",
+            Self::Azure { .. } => "Azure 语音",
+            Self::Deepgram { .. } => "Deepgram",
+            Self::Volcengine { .. } => "火山引擎",
+            Self::Xunfei { .. } => "讯飞语音",
+            Self::XunfeiLfasr { .. } => "讯飞录音文件转写",
+            Self::Command { .. } => "自定义命令",
+            Self::Http { .. } => "HTTP 接口",
+        }
+    }
+
+    /// 服务支持的能力
+    pub fn capabilities(&self) -> AsrCapabilities {
+        match self {
+            Self::OpenAI { .. } => OpenAIWhisperClient::CAPABILITIES,
+            Self::CustomWhisper { .. } => CustomWhisperClient::CAPABILITIES,
+            Self::Baidu { .. } => BaiduClient::CAPABILITIES,
+            Self::Tencent { .. } => TencentClient::CAPABILITIES,
+            Self::Azure { diarize: true, .. } => AzureSpeechClient::DIARIZATION_CAPABILITIES,
+            Self::Azure { .. } => AzureSpeechClient::CAPABILITIES,
+            Self::Deepgram { .. } => DeepgramClient::CAPABILITIES,
+            Self::Volcengine { .. } => VolcengineClient::CAPABILITIES,
+            Self::Xunfei { .. } => XunfeiClient::CAPABILITIES,
+            Self::XunfeiLfasr { .. } => XunfeiLfasrClient::CAPABILITIES,
+            Self::Command { .. } => CommandClient::CAPABILITIES,
+            Self::Http { .. } => HttpAsrClient::CAPABILITIES,
+        }
+    }
+
+    /// 服务的默认限流，未设置时不限流
+    pub fn default_rate_limit(&self) -> Option<RateLimit> {
+        match self {
+            Self::Baidu { .. } => Some(BaiduClient::DEFAULT_RATE_LIMIT),
+            _ => None,
+        }
+    }
+
+    /// 是否在本机完成识别，音频不会离开本机
+    ///
+    /// 自定义命令视为本机；自建 Whisper、HTTP 接口和自定义地址的 OpenAI
+    /// 仅在地址为回环地址（localhost / 127.0.0.1 / ::1）时视为本机
+    pub fn is_local(&self) -> bool {
+        let url = match self {
+            Self::Command { .. } => return true,
+            Self::OpenAI {
+                base_url: Some(url),
+                ..
+            } => url,
+            Self::CustomWhisper { base_url, .. } => base_url,
+            Self::Http { url, .. } => url,
+            _ => return false,
+        };
+        let Ok(url) = reqwest::Url::parse(url) else {
+            return false;
+        };
+        let host = url.host_str().unwrap_or_default();
+        host.eq_ignore_ascii_case("localhost")
+            || host
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| ip.is_loopback())
+    }
+
+    /// 校验必填字段
+    pub fn validate(&self) -> Result<()> {
+        let fields: &[(&str, &str)] = match self {
+            Self::OpenAI { api_key, .. } => &[("API Key", api_key)],
+            Self::CustomWhisper { base_url, .. } => &[("服务地址", base_url)],
+            Self::Baidu {
+                api_key,
+                secret_key,
+            } => &[("API Key", api_key), ("Secret Key", secret_key)],
+            Self::Tencent {
+                secret_id,
+                secret_key,
+                ..
+            } => &[("SecretId", secret_id), ("SecretKey", secret_key)],
+            Self::Azure {
+                subscription_key,
+                region,
+                ..
+            } => &[("订阅密钥", subscription_key), ("地域", region)],
+            Self::Deepgram { api_key, .. } => &[("API Key", api_key)],
+            Self::Volcengine {
+                app_id,
+                access_token,
+                ..
+            } => &[("APPID", app_id), ("Access Token", access_token)],
+            Self::Xunfei {
+                app_id,
+                api_key,
+                api_secret,
+                ..
+            } => &[
+                ("APPID", app_id),
+                ("APIKey", api_key),
+                ("APISecret", api_secret),
+            ],
+            Self::XunfeiLfasr {
+                app_id, secret_key, ..
+            } => &[("APPID", app_id), ("录音文件转写 SecretKey", secret_key)],
+            Self::Command { program, .. } => &[("命令", program)],
+            Self::Http { url, text_path, .. } => &[("接口地址", url), ("文本字段路径", text_path)],
+        };
+
+        let missing: Vec<&str> = fields
+            .iter()
+            .filter(|(_, value)| value.trim().is_empty())
+            .map(|(name, _)| *name)
+            .collect();
+        if !missing.is_empty() {
+            return Err(VoiceError::ConfigError(format!(
+                "{} 缺少 {}",
+                self.provider_name(),
+                missing.join("、")
+            )));
+        }
+
+        let url = match self {
+            Self::OpenAI {
+                base_url: Some(url),
+                ..
+            } => Some(url),
+            Self::CustomWhisper { base_url, .. } => Some(base_url),
+            Self::Http { url, .. } => Some(url),
+            _ => None,
+        };
+        if let Some(url) = url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(VoiceError::ConfigError(format!(
+                    "{} 的 API 地址必须以 http:// 或 https:// 开头: {}",
+                    self.provider_name(),
+                    url
+                )));
+            }
+        }
+
+        if let Self::Http { text_path, .. } = self {
+            http::validate_path(text_path)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// 校验配置并构建客户端，使用各客户端的默认超时
+pub fn create_client(config: &AsrClientConfig) -> Result<Box<dyn AsrClient>> {
+    create_client_with_timeouts(config, AsrTimeouts::default())
+}
+
+/// 校验配置并按 `timeouts` 构建客户端
+///
+/// 自建 Whisper、自定义命令和 HTTP 接口配置中的 `timeout_secs` 作为接收超时，
+/// `timeouts.receive` 已设置时以后者为准
+pub fn create_client_with_timeouts(
+    config: &AsrClientConfig,
+    timeouts: AsrTimeouts,
+) -> Result<Box<dyn AsrClient>> {
+    create_client_with_hotwords(config, timeouts, Vec::new())
+}
+
+/// 校验配置并按 `timeouts` 构建客户端，同时设置热词
+///
+/// 讯飞听写、自建 Whisper、自定义命令和 HTTP 接口使用热词，其他服务忽略
+pub fn create_client_with_hotwords(
+    config: &AsrClientConfig,
+    timeouts: AsrTimeouts,
+    hotwords: Vec<String>,
+) -> Result<Box<dyn AsrClient>> {
+    config.validate()?;
+    if !config.is_local() {
+        privacy::check_upload(config.provider_name())?;
+    }
+    let receive = |timeout_secs: u64| {
+        timeouts
+            .receive
+            .unwrap_or(Duration::from_secs(timeout_secs.max(1)))
+    };
+
+    let client: Box<dyn AsrClient> = match config.clone() {
+        AsrClientConfig::OpenAI {
+            api_key,
+            base_url,
+            language,
+        } => {
+            let mut client = OpenAIWhisperClient::new(api_key).with_timeouts(timeouts);
+            if let Some(url) = base_url.filter(|u| !u.is_empty()) {
+                client = client.with_host(url.trim_end_matches('/').to_string());
+            }
+            // 自动检测和中英混合时不传语言，由服务逐段判断
+            if language != "auto" && language != MIXED_LANGUAGE {
+                client = client.with_language(language);
+            }
+            Box::new(client)
+        }
+        AsrClientConfig::CustomWhisper {
+            base_url,
+            api_key,
+            model,
+            timeout_secs,
+            language,
+        } => {
+            let mut client = CustomWhisperClient::new(base_url.trim().to_string())
+                .with_timeouts(timeouts)
+                .with_timeout(receive(timeout_secs))
+                .with_hotwords(hotwords);
+            if let Some(api_key) = api_key.filter(|k| !k.trim().is_empty()) {
+                client = client.with_api_key(api_key.trim().to_string());
+            }
+            if let Some(model) = model.filter(|m| !m.trim().is_empty()) {
+                client = client.with_model(model.trim().to_string());
+            }
+            // 自动检测和中英混合时不传语言，由模型逐段判断
+            if language != "auto" && language != MIXED_LANGUAGE {
+                client = client.with_language(language);
+            }
+            Box::new(client)
+        }
+        AsrClientConfig::Baidu {
+            api_key,
+            secret_key,
+        } => Box::new(BaiduClient::new(api_key, secret_key).with_timeouts(timeouts)),
+        AsrClientConfig::Tencent {
+            secret_id,
+            secret_key,
+            region,
+            language,
+        } => {
+            let mut client = TencentClient::new(secret_id, secret_key)
+                .with_engine(tencent_engine(&language))
+                .with_timeouts(timeouts);
+            if let Some(region) = region.filter(|r| !r.is_empty()) {
+                client = client.with_region(region);
+            }
+            Box::new(client)
+        }
+        AsrClientConfig::Azure {
+            subscription_key,
+            region,
+            locale,
+            profanity,
+            diarize,
+            language,
+        } => {
+            let locale = locale
+                .filter(|l| !l.trim().is_empty())
+                .unwrap_or_else(|| azure_locale(&language));
+            Box::new(
+                AzureSpeechClient::new(subscription_key, region)
+                    .with_language(locale)
+                    .with_profanity(profanity)
+                    .with_diarization(diarize)
+                    .with_timeouts(timeouts),
+            )
+        }
+        AsrClientConfig::Deepgram {
+            api_key,
+            model,
+            diarize,
+            language,
+        } => {
+            let mut client = DeepgramClient::new(api_key)
+                .with_diarization(diarize)
+                .with_timeouts(timeouts);
+            if let Some(model) = model.filter(|m| !m.trim().is_empty()) {
+                client = client.with_model(model.trim().to_string());
+            }
+            if let Some(language) = deepgram_language(&language) {
+                client = client.with_language(language);
+            }
+            Box::new(client)
+        }
+        AsrClientConfig::Volcengine {
+            app_id,
+            access_token,
+            cluster,
+            language,
+        } => {
+            let mut client = VolcengineClient::new(app_id, access_token)
+                .with_language(volcengine_language(&language))
+                .with_timeouts(timeouts);
+            if let Some(cluster) = cluster.filter(|c| !c.trim().is_empty()) {
+                client = client.with_cluster(cluster);
+            }
+            Box::new(client)
+        }
+        AsrClientConfig::Xunfei {
+            app_id,
+            api_key,
+            api_secret,
+            language,
+        } => Box::new(
+            XunfeiClient::new(app_id, api_key, api_secret)
+                .with_language(xunfei_language(&language))
+                .with_timeouts(timeouts)
+                .with_hotwords(hotwords),
+        ),
+        AsrClientConfig::XunfeiLfasr {
+            app_id,
+            secret_key,
+            language,
+        } => Box::new(
+            XunfeiLfasrClient::new(app_id, secret_key)
+                .with_language(lfasr_language(&language))
+                .with_timeouts(timeouts),
+        ),
+        AsrClientConfig::Command {
+            program,
+            args,
+            timeout_secs,
+            language,
+        } => Box::new(
+            CommandClient::new(program, args)
+                .with_timeouts(timeouts)
+                .with_timeout(receive(timeout_secs))
+                .with_language(language)
+                .with_hotwords(hotwords),
+        ),
+        AsrClientConfig::Http {
+            url,
+            headers,
+            text_path,
+            upload,
+            timeout_secs,
+            language,
+        } => Box::new(
+            HttpAsrClient::new(url, &text_path)?
+                .with_headers(headers)
+                .with_upload(upload)
+                .with_timeouts(timeouts)
+                .with_timeout(receive(timeout_secs))
+                .with_language(language)
+                .with_hotwords(hotwords),
+        ),
+    };
+    Ok(client)
+}
+
+/// 讯飞听写语言代码：zh -> zh_cn，en -> en_us
+///
+/// 中文听写引擎本身支持中英混说，中英混合同样使用 zh_cn
+pub fn xunfei_language(language: &str) -> String {
+    match language {
+        "zh" | MIXED_LANGUAGE => "zh_cn".to_string(),
+        "en" => "en_us".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// 腾讯云一句话识别引擎类型：zh -> 16k_zh，en -> 16k_en，其他语言同理（如 16k_yue）
+///
+/// 中文引擎支持中英混说，中英混合和自动检测使用 16k_zh
+pub fn tencent_engine(language: &str) -> String {
+    match language {
+        "auto" | MIXED_LANGUAGE => "16k_zh".to_string(),
+        other => format!("16k_{}", other),
+    }
+}
+
+/// Azure 识别语言：zh -> zh-CN，en -> en-US，已是 BCP-47 代码（含 `-`）时原样返回
+///
+/// 短音频接口必须指定语言，zh-CN 模型支持中英混说，中英混合和自动检测使用 zh-CN
+pub fn azure_locale(language: &str) -> String {
+    let locale = match language {
+        "zh" | "auto" | MIXED_LANGUAGE => "zh-CN",
+        "en" => "en-US",
+        "yue" => "zh-HK",
+        "ja" => "ja-JP",
+        "ko" => "ko-KR",
+        "fr" => "fr-FR",
+        "de" => "de-DE",
+        "es" => "es-ES",
+        "ru" => "ru-RU",
+        other => other,
+    };
+    locale.to_string()
+}
+
+/// Deepgram 识别语言：自动检测时返回 `None`（由服务检测语言），其他语言代码原样使用
+///
+/// Deepgram 的中文模型不支持中英混说，中英混合按中文（zh）识别
+pub fn deepgram_language(language: &str) -> Option<String> {
+    match language {
+        "auto" => None,
+        MIXED_LANGUAGE => Some("zh".to_string()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// 火山引擎识别语言：en -> en-US，已是语言-地区代码（含 `-`）时原样返回，其余 -> zh-CN
+///
+/// 中文模型支持中英混说，中英混合和自动检测使用 zh-CN
+pub fn volcengine_language(language: &str) -> String {
+    match language {
+        "en" => "en-US".to_string(),
+        other if other.contains('-') => other.to_string(),
+        _ => "zh-CN".to_string(),
+    }
+}
+
+/// 讯飞录音文件转写语言代码：en -> en，其余（含中英混合）-> cn
+fn lfasr_language(language: &str) -> String {
+    match language {
+        "en" => "en".to_string(),
+        _ => "cn".to_string(),
+    }
+}
@@ -0,0 +1,86 @@
+//! ASR 回退链
+//!
+//! 按顺序包装多个客户端：前一个服务连不上（[`VoiceError::NetworkError`]）或鉴权失败
+//! （[`VoiceError::AsrAuthError`]）时改用下一个，其他错误（如音频格式、服务返回的识别错误）
+//! 换服务也无法解决，直接返回。单次时长上限低于录音时长的回退服务会被跳过。
+//! 识别结果的 `backend` 记录实际产出结果的服务。
+
+use async_trait::async_trait;
+
+use super::{AsrCapabilities, AsrClient};
+use crate::error::{Result, VoiceError};
+use crate::types::{AudioData, TranscribeResult};
+
+/// ASR 回退链
+pub struct AsrFallbackChain {
+    clients: Vec<Box<dyn AsrClient>>,
+}
+
+impl AsrFallbackChain {
+    /// 按尝试顺序创建回退链，至少需要一个客户端
+    pub fn new(clients: Vec<Box<dyn AsrClient>>) -> Result<Self> {
+        if clients.is_empty() {
+            return Err(VoiceError::ConfigError("回退链中没有识别服务".to_string()));
+        }
+        Ok(Self { clients })
+    }
+
+    /// 按尝试顺序排列的服务名称
+    pub fn backends(&self) -> Vec<&'static str> {
+        self.clients.iter().map(|client| client.name()).collect()
+    }
+
+    /// 错误是否可以通过换用下一个服务解决
+    fn should_fall_back(error: &VoiceError) -> bool {
+        matches!(
+            error,
+            VoiceError::NetworkError(_) | VoiceError::AsrAuthError(_)
+        )
+    }
+}
+
+#[async_trait]
+impl AsrClient for AsrFallbackChain {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        let mut last_error = None;
+        for client in &self.clients {
+            // 首选服务之后，跳过无法处理这么长录音的服务
+            if last_error.is_some()
+                && client
+                    .capabilities()
+                    .exceeds_max_duration(audio.duration_secs)
+            {
+                tracing::info!(
+                    "[语音识别] 录音 {:.1}s 超过{}单次上限，跳过",
+                    audio.duration_secs,
+                    client.name()
+                );
+                continue;
+            }
+            match client.transcribe(audio).await {
+                Ok(mut result) => {
+                    result.backend = Some(client.name().to_string());
+                    return Ok(result);
+                }
+                Err(e) if Self::should_fall_back(&e) => {
+                    tracing::warn!("[语音识别] {} 不可用: {}", client.name(), e);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.expect("回退链至少包含一个客户端"))
+    }
+
+    /// 首选服务的名称
+    fn name(&self) -> &'static str {
+        self.clients[0].name()
+    }
+
+    /// 首选服务的能力
+    ///
+    /// 调用方按首选服务的单次时长上限分段，回退时跳过处理不了该时长的服务
+    fn capabilities(&self) -> AsrCapabilities {
+        self.clients[0].capabilities()
+    }
+}
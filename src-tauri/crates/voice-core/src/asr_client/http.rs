@@ -0,0 +1,320 @@
+//! 通用 HTTP ASR 客户端
+//!
+//! 把录音以 WAV 格式 POST 到用户配置的地址，按 JSONPath 从响应中取出识别文本，
+//! 用于接入自建的识别服务（FunASR、SenseVoice 等）而无需专门集成。
+//!
+//! - 上传方式：请求体直接为 WAV（`audio/wav`），或 multipart 表单中的一个文件字段
+//! - 地址中的 `{language}` 替换为识别语言（通用语言代码，未指定时为 `auto`）
+//! - 地址中的 `{hotwords}` 替换为逗号分隔的热词（URL 编码）；multipart 上传时热词另放在
+//!   `hotwords` 字段中
+//! - JSONPath 支持 `$`、`.key`、`['key']` 和 `[index]`，如 `$.result[0].text`
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use serde_json::Value;
+
+use super::timeouts::AsrTimeouts;
+use super::{AsrCapabilities, AsrClient, AudioFormat};
+use crate::error::{Result, VoiceError};
+use crate::language::MIXED_LANGUAGE;
+use crate::types::{AudioData, TranscribeResult};
+
+/// 默认接收超时（秒）
+pub const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// 默认的 multipart 文件字段名
+pub const DEFAULT_FILE_FIELD: &str = "file";
+
+/// 错误信息中保留的响应长度（字符）
+const BODY_PREVIEW_CHARS: usize = 500;
+
+/// 录音的上传方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HttpUpload {
+    /// 请求体直接为 WAV
+    Raw,
+    /// multipart 表单，录音放在指定的文件字段中
+    Multipart { field: String },
+}
+
+/// JSONPath 的一段
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// 通用 HTTP ASR 客户端
+pub struct HttpAsrClient {
+    url: String,
+    headers: HashMap<String, String>,
+    text_path: Vec<PathSegment>,
+    upload: HttpUpload,
+    http: reqwest::Client,
+    timeouts: AsrTimeouts,
+    language: Option<String>,
+    hotwords: Vec<String>,
+}
+
+impl HttpAsrClient {
+    /// 通用 HTTP 接口的能力
+    ///
+    /// 无法预知自建服务的能力，按最保守的情况声明：不假定带标点，由调用方按需补全
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: false,
+        timestamps: false,
+        hotwords: true,
+        punctuation: false,
+        code_switching: false,
+        diarization: false,
+        max_duration_secs: None,
+        formats: &[AudioFormat::Wav],
+        sample_rates: &[],
+    };
+
+    /// 创建新的客户端，`text_path` 为识别文本在响应 JSON 中的 JSONPath
+    pub fn new(url: String, text_path: &str) -> Result<Self> {
+        Ok(Self {
+            url,
+            headers: HashMap::new(),
+            text_path: parse_path(text_path)?,
+            upload: HttpUpload::Raw,
+            http: AsrTimeouts::default().http_client(),
+            timeouts: AsrTimeouts::default(),
+            language: None,
+            hotwords: Vec::new(),
+        })
+    }
+
+    /// 设置请求头（如 `Authorization`）
+    pub fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// 设置上传方式
+    pub fn with_upload(mut self, upload: HttpUpload) -> Self {
+        self.upload = upload;
+        self
+    }
+
+    /// 设置等待识别结果的超时
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.receive = Some(timeout);
+        self
+    }
+
+    /// 设置超时，HTTP 请求的计时方式见 [`AsrTimeouts`]
+    pub fn with_timeouts(mut self, timeouts: AsrTimeouts) -> Self {
+        self.http = timeouts.http_client();
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 设置语言
+    pub fn with_language(mut self, language: String) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// 上传录音，返回响应 JSON
+    async fn post(&self, wav_bytes: Vec<u8>) -> Result<Value> {
+        let hotwords = self.hotwords.join(",");
+        let url = self
+            .url
+            .replace("{language}", self.language.as_deref().unwrap_or("auto"))
+            .replace("{hotwords}", &urlencoding::encode(&hotwords));
+
+        let mut request = self.http.post(&url).timeout(
+            self.timeouts
+                .request_timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS)),
+        );
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+
+        request = match &self.upload {
+            HttpUpload::Raw => request.header("Content-Type", "audio/wav").body(wav_bytes),
+            HttpUpload::Multipart { field } => {
+                let part = Part::bytes(wav_bytes)
+                    .file_name("audio.wav")
+                    .mime_str("audio/wav")
+                    .map_err(|e| VoiceError::AsrError(e.to_string()))?;
+                let mut form = Form::new().part(field.clone(), part);
+                if !hotwords.is_empty() {
+                    form = form.text("hotwords", hotwords);
+                }
+                request.multipart(form)
+            }
+        };
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| VoiceError::NetworkError(e.to_string()))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| VoiceError::NetworkError(e.to_string()))?;
+        if !status.is_success() {
+            return Err(VoiceError::AsrError(format!(
+                "HTTP 接口错误: {} - {}",
+                status,
+                preview(&body)
+            )));
+        }
+
+        serde_json::from_str(&body).map_err(|e| {
+            VoiceError::AsrError(format!("响应不是有效的 JSON: {}: {}", e, preview(&body)))
+        })
+    }
+}
+
+#[async_trait]
+impl AsrClient for HttpAsrClient {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        let response = self.post(audio.to_wav_bytes()).await?;
+
+        let text = match select(&response, &self.text_path) {
+            Some(Value::String(text)) => text.trim().to_string(),
+            // 字段存在但为空表示没有识别出内容
+            Some(Value::Null) => String::new(),
+            Some(other) => {
+                return Err(VoiceError::AsrError(format!(
+                    "识别文本字段不是字符串: {}",
+                    preview(&other.to_string())
+                )))
+            }
+            None => {
+                return Err(VoiceError::AsrError(format!(
+                    "响应中没有识别文本字段: {}",
+                    preview(&response.to_string())
+                )))
+            }
+        };
+
+        Ok(TranscribeResult {
+            text,
+            language: self
+                .language
+                .clone()
+                .filter(|l| l != "auto" && l != MIXED_LANGUAGE),
+            confidence: None,
+            segments: Vec::new(),
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "HTTP 接口"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        Self::CAPABILITIES
+    }
+
+    fn with_hotwords(mut self, hotwords: Vec<String>) -> Self {
+        self.hotwords = hotwords;
+        self
+    }
+}
+
+/// 校验 JSONPath 能否解析
+pub fn validate_path(path: &str) -> Result<()> {
+    parse_path(path).map(|_| ())
+}
+
+/// 解析 JSONPath（`$`、`.key`、`['key']`、`[index]`）
+fn parse_path(path: &str) -> Result<Vec<PathSegment>> {
+    let invalid =
+        |reason: &str| VoiceError::ConfigError(format!("无效的 JSONPath \"{}\": {}", path, reason));
+
+    let trimmed = path.trim();
+    let rest = trimmed.strip_prefix('$').unwrap_or(trimmed);
+    let mut chars = rest.chars().peekable();
+    let mut segments = Vec::new();
+
+    // 允许省略开头的 `$.`，如 `result.text`
+    if chars.peek().is_some_and(|c| *c != '.' && *c != '[') {
+        segments.push(PathSegment::Key(take_key(&mut chars)));
+    }
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                let key = take_key(&mut chars);
+                if key.is_empty() {
+                    return Err(invalid("`.` 后缺少字段名"));
+                }
+                segments.push(PathSegment::Key(key));
+            }
+            '[' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(c) => inner.push(c),
+                        None => return Err(invalid("缺少 `]`")),
+                    }
+                }
+                let inner = inner.trim();
+                let quoted = inner
+                    .strip_prefix('\'')
+                    .and_then(|s| s.strip_suffix('\''))
+                    .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')));
+                match quoted {
+                    Some(key) => segments.push(PathSegment::Key(key.to_string())),
+                    None => {
+                        let index = inner
+                            .parse()
+                            .map_err(|_| invalid("下标必须是非负整数或带引号的字段名"))?;
+                        segments.push(PathSegment::Index(index));
+                    }
+                }
+            }
+            other => return Err(invalid(&format!("意外的字符 `{}`", other))),
+        }
+    }
+
+    if segments.is_empty() {
+        return Err(invalid("未指定字段"));
+    }
+    Ok(segments)
+}
+
+/// 读取字段名，直到 `.` 或 `[`
+fn take_key(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut key = String::new();
+    while let Some(&c) = chars.peek() {
+        if c == '.' || c == '[' {
+            break;
+        }
+        key.push(c);
+        chars.next();
+    }
+    key
+}
+
+/// 按路径取值
+fn select<'a>(value: &'a Value, path: &[PathSegment]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, segment| match segment {
+        PathSegment::Key(key) => value.get(key.as_str()),
+        PathSegment::Index(index) => value.get(*index),
+    })
+}
+
+/// 截断过长的响应用于错误信息
+fn preview(body: &str) -> String {
+    let mut chars = body.chars();
+    let head: String = chars.by_ref().take(BODY_PREVIEW_CHARS).collect();
+    if chars.next().is_some() {
+        format!("{}…", head)
+    } else {
+        head
+    }
+}
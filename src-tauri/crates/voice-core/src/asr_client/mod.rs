@@ -1,15 +1,138 @@
 //! 云端 ASR 客户端模块
 //!
-//! 支持讯飞、百度、OpenAI Whisper 等云端语音识别服务。
+//! 支持讯飞、火山引擎、百度、腾讯云、Azure、Deepgram、OpenAI Whisper 等云端语音识别服务，
+//! 局域网内自建的 Whisper 服务（[`CustomWhisperClient`]），以及通过外部命令（[`CommandClient`]）
+//! 或通用 HTTP 接口（[`HttpAsrClient`]）接入的自定义识别引擎。
+//! 超过 [`REALTIME_MAX_SECS`] 的录音，讯飞改用录音文件转写（[`XunfeiLfasrClient`]），
+//! 其他实时接口按停顿分段识别。
+//!
+//! 各客户端通过 [`AsrClient::capabilities`] 声明支持的能力（流式、时间戳、热词、
+//! 标点、单次时长上限、音频格式），调用方据此选择识别路径。
+//! 上传前通过 [`AsrCapabilities::encode`] 按服务偏好的格式和采样率编码音频。
+//! [`AsrClient::with_hotwords`] 设置热词（专业术语、产品名等），提高这些词的识别率，
+//! 不支持热词的服务忽略。
+//! 客户端统一通过 [`create_client`] 由 [`AsrClientConfig`] 构建，
+//! [`create_client_with_timeouts`] 同时按 [`AsrTimeouts`] 设置连接、发送和接收超时。
+//! [`AsrFallbackChain`] 把多个客户端串成回退链，前一个服务连不上或鉴权失败时改用下一个；
+//! [`AsrRace`] 把同一段音频同时发给多个客户端，采用最先成功返回的结果。
+//! [`AsrRetry`] 在网络错误时按 [`RetryPolicy`] 指数退避重试单个客户端。
+//! [`AsrRateLimit`] 按凭证的令牌桶（[`RateLimiter`]）限制每秒请求数，超出的请求排队等待。
+//!
+//! [`AsrClient::transcribe_stream`] 边接收音频边产出 [`PartialTranscript`]，
+//! 讯飞听写和火山引擎会推送中间结果；其他服务默认收齐音频后整段识别，只产出最终结果。
+//!
+//! [`AsrClient::transcribe_cancellable`] 在 [`CancellationToken`] 触发时立即中止识别，
+//! 丢弃进行中的请求（讯飞、火山引擎的 WebSocket 发送循环和接收任务一并中止），返回 [`VoiceError::Cancelled`]。
 
+pub mod azure;
 pub mod baidu;
+pub mod command;
+pub mod custom_whisper;
+pub mod deepgram;
+pub mod factory;
+pub mod fallback;
+pub mod http;
 pub mod openai;
+pub mod race;
+pub mod rate_limit;
+pub mod retry;
+pub mod tencent;
+pub mod timeouts;
+pub mod volcengine;
 pub mod xunfei;
+pub mod xunfei_lfasr;
 
 use async_trait::async_trait;
+use futures_util::stream::{self, BoxStream};
+use serde::Serialize;
+use tokio::sync::mpsc::UnboundedReceiver;
+pub use tokio_util::sync::CancellationToken;
+
+use crate::encode::{self, EncodedAudio, ENCODABLE_FORMATS};
+use crate::error::{Result, VoiceError};
+use crate::types::{AudioData, PartialTranscript, TranscribeResult};
+
+/// 实时识别接口（讯飞听写、火山引擎流式识别、百度短语音、腾讯云一句话识别、Azure 短音频识别）单次支持的最长音频（秒）
+pub const REALTIME_MAX_SECS: f32 = 60.0;
+
+/// 音频格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    /// 16bit 单声道裸 PCM
+    Pcm,
+    Wav,
+    Mp3,
+    Flac,
+    M4a,
+    Ogg,
+    Amr,
+}
+
+/// ASR 服务能力
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct AsrCapabilities {
+    /// 是否支持边录边识别（录音过程中持续上传音频）
+    pub streaming: bool,
+    /// 识别结果是否带分段时间戳
+    pub timestamps: bool,
+    /// 是否支持热词
+    pub hotwords: bool,
+    /// 是否自动添加标点
+    pub punctuation: bool,
+    /// 是否能较好地识别中英混说
+    pub code_switching: bool,
+    /// 是否支持区分说话人（分段带 [`Segment::speaker`]）
+    ///
+    /// [`Segment::speaker`]: crate::types::Segment::speaker
+    pub diarization: bool,
+    /// 单次请求支持的最长音频（秒），`None` 表示不限制
+    pub max_duration_secs: Option<f32>,
+    /// 接受的音频格式，按偏好排列
+    pub formats: &'static [AudioFormat],
+    /// 接受的采样率，按偏好排列，为空表示不限制
+    pub sample_rates: &'static [u32],
+}
 
-use crate::error::Result;
-use crate::types::{AudioData, TranscribeResult};
+impl AsrCapabilities {
+    /// 是否接受指定的音频格式
+    pub fn accepts(&self, format: AudioFormat) -> bool {
+        self.formats.contains(&format)
+    }
+
+    /// 音频时长是否超过单次请求上限
+    pub fn exceeds_max_duration(&self, duration_secs: f32) -> bool {
+        self.max_duration_secs
+            .is_some_and(|max| duration_secs > max)
+    }
+
+    /// 协商上传格式：按偏好顺序取第一个可以编码的格式
+    pub fn negotiate(&self) -> Option<AudioFormat> {
+        self.formats
+            .iter()
+            .copied()
+            .find(|f| ENCODABLE_FORMATS.contains(f))
+    }
+
+    /// 协商采样率：源采样率可接受时保持不变，否则取最偏好的采样率
+    pub fn negotiate_sample_rate(&self, source_rate: u32) -> u32 {
+        match self.sample_rates.first() {
+            Some(&preferred) if !self.sample_rates.contains(&source_rate) => preferred,
+            _ => source_rate,
+        }
+    }
+
+    /// 按协商出的格式和采样率编码音频
+    pub fn encode(&self, audio: &AudioData) -> Result<EncodedAudio> {
+        let format = self.negotiate().ok_or_else(|| {
+            VoiceError::AudioFormatError(format!(
+                "没有可以编码的上传格式，服务接受: {:?}",
+                self.formats
+            ))
+        })?;
+        encode::encode(audio, format, self.negotiate_sample_rate(audio.sample_rate))
+    }
+}
 
 /// ASR 客户端 trait
 #[async_trait]
@@ -17,10 +140,74 @@ pub trait AsrClient: Send + Sync {
     /// 识别音频
     async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult>;
 
+    /// 可取消的识别：`cancel` 触发时丢弃进行中的请求并返回 [`VoiceError::Cancelled`]
+    async fn transcribe_cancellable(
+        &self,
+        audio: &AudioData,
+        cancel: &CancellationToken,
+    ) -> Result<TranscribeResult> {
+        tokio::select! {
+            biased;
+            _ = cancel.cancelled() => Err(VoiceError::Cancelled),
+            result = self.transcribe(audio) => result,
+        }
+    }
+
+    /// 流式识别：边接收音频边产出识别结果
+    ///
+    /// `frames` 为单声道采样，发送端关闭即视为录音结束。流依次产出中间结果，
+    /// 最后一项为最终结果（`is_final`），出错时以错误结束。
+    /// 默认实现收齐音频后调用 [`AsrClient::transcribe`]，只产出最终结果。
+    fn transcribe_stream<'a>(
+        &'a self,
+        mut frames: UnboundedReceiver<Vec<i16>>,
+        sample_rate: u32,
+    ) -> BoxStream<'a, Result<PartialTranscript>> {
+        Box::pin(stream::once(async move {
+            let mut samples = Vec::new();
+            while let Some(chunk) = frames.recv().await {
+                samples.extend(chunk);
+            }
+            let result = self
+                .transcribe(&AudioData::new(samples, sample_rate, 1))
+                .await?;
+            Ok(PartialTranscript::finished(result))
+        }))
+    }
+
     /// 获取服务名称
     fn name(&self) -> &'static str;
+
+    /// 服务支持的能力
+    fn capabilities(&self) -> AsrCapabilities;
+
+    /// 设置热词，提高专业术语、产品名等词的识别率
+    ///
+    /// 默认忽略，支持热词的客户端（[`AsrCapabilities::hotwords`]）覆盖此方法
+    fn with_hotwords(self, _hotwords: Vec<String>) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
 }
 
+pub use azure::{AzureProfanity, AzureSpeechClient};
 pub use baidu::BaiduClient;
+pub use command::CommandClient;
+pub use custom_whisper::CustomWhisperClient;
+pub use deepgram::DeepgramClient;
+pub use factory::{
+    create_client, create_client_with_hotwords, create_client_with_timeouts, AsrClientConfig,
+};
+pub use fallback::AsrFallbackChain;
+pub use http::{HttpAsrClient, HttpUpload};
 pub use openai::OpenAIWhisperClient;
+pub use race::AsrRace;
+pub use rate_limit::{AsrRateLimit, RateLimit, RateLimiter, TokenBucket};
+pub use retry::{AsrRetry, RetryPolicy};
+pub use tencent::TencentClient;
+pub use timeouts::AsrTimeouts;
+pub use volcengine::VolcengineClient;
 pub use xunfei::XunfeiClient;
+pub use xunfei_lfasr::XunfeiLfasrClient;
@@ -1,15 +1,33 @@
 //! OpenAI Whisper API 客户端
 //!
 //! 使用 OpenAI 的 Whisper API 进行语音识别。
+//!
+//! 音频以 16kHz FLAC 上传，体积约为 WAV 的一半。接口拒绝超过 25MB 的文件，
+//! 超出时按停顿把录音切分为多段分别上传，再拼接识别结果。
 
 use async_trait::async_trait;
 use reqwest::multipart::{Form, Part};
 use serde::Deserialize;
 
-use super::AsrClient;
+use super::timeouts::{AsrTimeouts, DEFAULT_RECEIVE_TIMEOUT};
+use super::{AsrCapabilities, AsrClient, AudioFormat};
+use crate::encode::EncodedAudio;
 use crate::error::{Result, VoiceError};
+use crate::language::normalize_language;
+use crate::silence::split_at_pauses;
 use crate::types::{AudioData, TranscribeResult};
 
+/// 接口接受的最大文件大小（字节）
+pub const MAX_UPLOAD_BYTES: usize = 25 * 1024 * 1024;
+
+/// 分段上传时每段按 WAV 计算的目标大小（字节），低于上限留出余量
+///
+/// FLAC 编码后不会大于同样的 WAV，按 WAV 估算可以保证每段都在上限内
+const CHUNK_TARGET_BYTES: usize = 24 * 1024 * 1024;
+
+/// WAV 文件头大小（字节）
+const WAV_HEADER_BYTES: usize = 44;
+
 /// OpenAI Whisper 响应
 #[derive(Debug, Deserialize)]
 struct WhisperResponse {
@@ -24,9 +42,33 @@ pub struct OpenAIWhisperClient {
     api_host: String,
     model: String,
     language: Option<String>,
+    http: reqwest::Client,
+    timeouts: AsrTimeouts,
 }
 
 impl OpenAIWhisperClient {
+    /// OpenAI Whisper API 的能力
+    ///
+    /// 接口按文件大小（25MB）而非时长限制，这里不设时长上限
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: false,
+        timestamps: false,
+        hotwords: false,
+        punctuation: true,
+        code_switching: true,
+        diarization: false,
+        max_duration_secs: None,
+        formats: &[
+            AudioFormat::Flac,
+            AudioFormat::M4a,
+            AudioFormat::Ogg,
+            AudioFormat::Mp3,
+            AudioFormat::Wav,
+        ],
+        // Whisper 内部按 16kHz 处理，更高的采样率只会增加上传体积
+        sample_rates: &[16000],
+    };
+
     /// 创建新的客户端
     pub fn new(api_key: String) -> Self {
         Self {
@@ -34,6 +76,8 @@ impl OpenAIWhisperClient {
             api_host: "https://api.openai.com".to_string(),
             model: "whisper-1".to_string(),
             language: None,
+            http: AsrTimeouts::default().http_client(),
+            timeouts: AsrTimeouts::default(),
         }
     }
 
@@ -48,34 +92,74 @@ impl OpenAIWhisperClient {
         self.language = Some(language);
         self
     }
-}
 
-#[async_trait]
-impl AsrClient for OpenAIWhisperClient {
-    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+    /// 设置超时：连接超时用于建立连接，每段上传请求不超过连接、发送和接收超时之和
+    pub fn with_timeouts(mut self, timeouts: AsrTimeouts) -> Self {
+        self.http = timeouts.http_client();
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 按停顿把音频切分为 WAV 不超过 [`CHUNK_TARGET_BYTES`] 的若干段
+    fn split_for_upload(audio: &AudioData) -> Vec<AudioData> {
+        let channels = audio.channels.max(1) as usize;
+        let bytes_per_sec = audio.sample_rate as usize * channels * 2;
+        let max_secs = (CHUNK_TARGET_BYTES - WAV_HEADER_BYTES) as f32 / bytes_per_sec as f32;
+
+        // 多声道按交错采样切分，切分点对齐到完整的一帧
+        let interleaved_rate = audio.sample_rate * channels as u32;
+        let mut bounds: Vec<usize> = split_at_pauses(&audio.samples, interleaved_rate, max_secs)
+            .into_iter()
+            .map(|range| range.end - range.end % channels)
+            .collect();
+        bounds.dedup();
+
+        let mut start = 0;
+        bounds
+            .into_iter()
+            .filter_map(|end| {
+                let range = start..end.max(start);
+                start = range.end;
+                (!range.is_empty()).then(|| {
+                    AudioData::new(
+                        audio.samples[range].to_vec(),
+                        audio.sample_rate,
+                        audio.channels,
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// 上传一段编码后的音频并识别
+    async fn transcribe_encoded(&self, encoded: EncodedAudio) -> Result<WhisperResponse> {
         let url = format!("{}/v1/audio/transcriptions", self.api_host);
-        let wav_bytes = audio.to_wav_bytes();
 
         // 构建 multipart form
-        let file_part = Part::bytes(wav_bytes)
-            .file_name("audio.wav")
-            .mime_str("audio/wav")
+        let file_name = encoded.file_name("audio");
+        let mime_type = encoded.mime_type();
+        let file_part = Part::bytes(encoded.bytes)
+            .file_name(file_name)
+            .mime_str(mime_type)
             .map_err(|e| VoiceError::AsrError(e.to_string()))?;
 
         let mut form = Form::new()
             .part("file", file_part)
             .text("model", self.model.clone());
 
-        if let Some(ref lang) = self.language {
-            form = form.text("language", lang.clone());
+        // 未指定语言时请求 verbose_json，响应中才会带上检测到的语言
+        match self.language {
+            Some(ref lang) => form = form.text("language", lang.clone()),
+            None => form = form.text("response_format", "verbose_json"),
         }
 
         // 发送请求
-        let client = reqwest::Client::new();
-        let response = client
+        let response = self
+            .http
             .post(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .multipart(form)
+            .timeout(self.timeouts.request_timeout(DEFAULT_RECEIVE_TIMEOUT))
             .send()
             .await
             .map_err(|e| VoiceError::NetworkError(e.to_string()))?;
@@ -89,20 +173,77 @@ impl AsrClient for OpenAIWhisperClient {
             )));
         }
 
-        let result: WhisperResponse = response
+        response
             .json()
             .await
-            .map_err(|e| VoiceError::AsrError(e.to_string()))?;
+            .map_err(|e| VoiceError::AsrError(e.to_string()))
+    }
+}
+
+/// 拼接分段识别文本，英文等以空格分词的文本之间补一个空格
+fn append_text(merged: &mut String, text: &str) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    let needs_space = merged.chars().last().is_some_and(|c| c.is_ascii_graphic())
+        && text.starts_with(|c: char| c.is_ascii_alphanumeric());
+    if needs_space {
+        merged.push(' ');
+    }
+    merged.push_str(text);
+}
+
+#[async_trait]
+impl AsrClient for OpenAIWhisperClient {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        let encoded = Self::CAPABILITIES.encode(audio)?;
+
+        let responses = if encoded.bytes.len() <= MAX_UPLOAD_BYTES {
+            vec![self.transcribe_encoded(encoded).await?]
+        } else {
+            let chunks = Self::split_for_upload(audio);
+            tracing::info!(
+                "[OpenAI] 音频 {:.1}MB 超过 25MB 上限，分 {} 段上传",
+                encoded.bytes.len() as f32 / 1024.0 / 1024.0,
+                chunks.len()
+            );
+            let mut responses = Vec::with_capacity(chunks.len());
+            for (i, chunk) in chunks.iter().enumerate() {
+                let response = self
+                    .transcribe_encoded(Self::CAPABILITIES.encode(chunk)?)
+                    .await
+                    .map_err(|e| {
+                        VoiceError::AsrError(format!("第 {}/{} 段: {}", i + 1, chunks.len(), e))
+                    })?;
+                responses.push(response);
+            }
+            responses
+        };
+
+        let mut text = String::new();
+        let mut language = None;
+        for response in responses {
+            append_text(&mut text, &response.text);
+            language = language.or(response.language);
+        }
 
         Ok(TranscribeResult {
-            text: result.text,
-            language: result.language,
+            text,
+            language: language
+                .or_else(|| self.language.clone())
+                .map(|l| normalize_language(&l)),
             confidence: None,
             segments: vec![],
+            backend: None,
         })
     }
 
     fn name(&self) -> &'static str {
         "OpenAI Whisper"
     }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        Self::CAPABILITIES
+    }
 }
@@ -0,0 +1,92 @@
+//! ASR 竞速识别
+//!
+//! 把同一段音频同时发给多个客户端，最先成功返回的结果胜出，其余请求随即取消（丢弃未完成的 future）。
+//! 单个服务失败不影响其他服务，全部失败时返回排在最前的服务的错误。
+//! 单次时长上限低于录音时长的服务（首选服务除外）不参与竞速。
+//! 识别结果的 `backend` 记录胜出的服务。
+
+use async_trait::async_trait;
+use futures_util::stream::{FuturesUnordered, StreamExt};
+
+use super::{AsrCapabilities, AsrClient};
+use crate::error::{Result, VoiceError};
+use crate::types::{AudioData, TranscribeResult};
+
+/// ASR 竞速识别
+pub struct AsrRace {
+    clients: Vec<Box<dyn AsrClient>>,
+}
+
+impl AsrRace {
+    /// 创建竞速识别，第一个客户端为首选服务，至少需要一个客户端
+    pub fn new(clients: Vec<Box<dyn AsrClient>>) -> Result<Self> {
+        if clients.is_empty() {
+            return Err(VoiceError::ConfigError(
+                "竞速识别中没有识别服务".to_string(),
+            ));
+        }
+        Ok(Self { clients })
+    }
+
+    /// 参与竞速的服务名称
+    pub fn backends(&self) -> Vec<&'static str> {
+        self.clients.iter().map(|client| client.name()).collect()
+    }
+}
+
+#[async_trait]
+impl AsrClient for AsrRace {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        let mut pending: FuturesUnordered<_> = self
+            .clients
+            .iter()
+            .enumerate()
+            .filter(|(index, client)| {
+                let skip = *index > 0
+                    && client
+                        .capabilities()
+                        .exceeds_max_duration(audio.duration_secs);
+                if skip {
+                    tracing::info!(
+                        "[语音识别] 录音 {:.1}s 超过{}单次上限，不参与竞速",
+                        audio.duration_secs,
+                        client.name()
+                    );
+                }
+                !skip
+            })
+            .map(|(index, client)| async move { (index, client.transcribe(audio).await) })
+            .collect();
+
+        let mut first_error: Option<(usize, VoiceError)> = None;
+        while let Some((index, result)) = pending.next().await {
+            let client = &self.clients[index];
+            match result {
+                Ok(mut result) => {
+                    tracing::debug!("[语音识别] {} 最先返回结果", client.name());
+                    result.backend = Some(client.name().to_string());
+                    return Ok(result);
+                }
+                Err(e) => {
+                    tracing::warn!("[语音识别] {} 识别失败: {}", client.name(), e);
+                    if first_error.as_ref().is_none_or(|(i, _)| index < *i) {
+                        first_error = Some((index, e));
+                    }
+                }
+            }
+        }
+        Err(first_error.expect("竞速识别至少包含一个客户端").1)
+    }
+
+    /// 首选服务的名称
+    fn name(&self) -> &'static str {
+        self.clients[0].name()
+    }
+
+    /// 首选服务的能力
+    ///
+    /// 调用方按首选服务的单次时长上限分段，处理不了该时长的其他服务不参与竞速
+    fn capabilities(&self) -> AsrCapabilities {
+        self.clients[0].capabilities()
+    }
+}
@@ -0,0 +1,188 @@
+//! ASR 请求限流
+//!
+//! 部分云端服务按凭证限制每秒请求数（如百度短语音识别的 QPS 配额），连续快速听写、
+//! 长录音分段识别时很容易超出，超出的请求会直接报错。按凭证 ID 维护令牌桶
+//! （[`RateLimiter`]），[`AsrRateLimit`] 在每次请求前取得令牌，令牌不足时排队等待而不是拒绝，
+//! 等待的请求按到达顺序依次发出。
+//!
+//! 同一凭证的所有客户端共用一个令牌桶，包括回退链和竞速中的客户端。
+//! 放在 [`super::AsrRetry`] 内层时，每次重试同样需要令牌。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::time::Instant;
+
+use super::{AsrCapabilities, AsrClient};
+use crate::error::Result;
+use crate::types::{AudioData, PartialTranscript, TranscribeResult};
+
+/// 限流参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// 每秒补充的令牌数，即长期平均的每秒请求数；不大于 0 表示不限制
+    pub per_second: f64,
+    /// 令牌桶容量，即空闲后允许连续发出的请求数（至少为 1）
+    pub burst: u32,
+}
+
+impl RateLimit {
+    pub fn new(per_second: f64, burst: u32) -> Self {
+        Self { per_second, burst }
+    }
+
+    fn capacity(&self) -> f64 {
+        self.burst.max(1) as f64
+    }
+
+    fn is_unlimited(&self) -> bool {
+        self.per_second.is_nan() || self.per_second <= 0.0
+    }
+}
+
+/// 令牌桶
+pub struct TokenBucket {
+    limit: RateLimit,
+    /// 异步锁按加锁顺序唤醒，排队的请求先到先得
+    state: tokio::sync::Mutex<BucketState>,
+}
+
+struct BucketState {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl BucketState {
+    fn refill(&mut self, limit: &RateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.per_second).min(limit.capacity());
+        self.updated = now;
+    }
+}
+
+impl TokenBucket {
+    /// 创建装满令牌的令牌桶
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            state: tokio::sync::Mutex::new(BucketState {
+                tokens: limit.capacity(),
+                updated: Instant::now(),
+            }),
+        }
+    }
+
+    /// 限流参数
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// 取得一个令牌，令牌不足时等待补充，返回排队等待的时间
+    ///
+    /// 等待期间持有锁，后到的请求排在后面；等待被取消时不消耗令牌
+    pub async fn acquire(&self) -> Duration {
+        let start = Instant::now();
+        if self.limit.is_unlimited() {
+            return Duration::ZERO;
+        }
+
+        let mut state = self.state.lock().await;
+        state.refill(&self.limit, Instant::now());
+        if state.tokens < 1.0 {
+            let wait = (1.0 - state.tokens) / self.limit.per_second;
+            tokio::time::sleep(Duration::from_secs_f64(wait)).await;
+            state.refill(&self.limit, Instant::now());
+        }
+        state.tokens = (state.tokens - 1.0).max(0.0);
+        start.elapsed()
+    }
+}
+
+/// 按键（凭证 ID）管理的令牌桶
+#[derive(Default)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<String, Arc<TokenBucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 进程内共用的限流器
+    pub fn shared() -> &'static RateLimiter {
+        static SHARED: OnceLock<RateLimiter> = OnceLock::new();
+        SHARED.get_or_init(RateLimiter::new)
+    }
+
+    /// 获取 `key` 对应的令牌桶，限流参数变化时换成新的令牌桶
+    pub fn bucket(&self, key: &str, limit: RateLimit) -> Arc<TokenBucket> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        match buckets.get(key) {
+            Some(bucket) if bucket.limit() == limit => bucket.clone(),
+            _ => {
+                let bucket = Arc::new(TokenBucket::new(limit));
+                buckets.insert(key.to_string(), bucket.clone());
+                bucket
+            }
+        }
+    }
+}
+
+/// 限流的 ASR 客户端
+pub struct AsrRateLimit {
+    client: Box<dyn AsrClient>,
+    bucket: Arc<TokenBucket>,
+}
+
+impl AsrRateLimit {
+    pub fn new(client: Box<dyn AsrClient>, bucket: Arc<TokenBucket>) -> Self {
+        Self { client, bucket }
+    }
+
+    /// 取得令牌，排队较久时记录日志
+    async fn acquire(&self) {
+        let waited = self.bucket.acquire().await;
+        if waited >= Duration::from_millis(10) {
+            tracing::info!(
+                "[语音识别] {} 请求过于频繁，排队 {}ms 后发出",
+                self.client.name(),
+                waited.as_millis()
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl AsrClient for AsrRateLimit {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        self.acquire().await;
+        self.client.transcribe(audio).await
+    }
+
+    fn transcribe_stream<'a>(
+        &'a self,
+        frames: UnboundedReceiver<Vec<i16>>,
+        sample_rate: u32,
+    ) -> BoxStream<'a, Result<PartialTranscript>> {
+        // 排队期间录音帧缓存在通道中，取得令牌后再建立连接
+        stream::once(async move {
+            self.acquire().await;
+            self.client.transcribe_stream(frames, sample_rate)
+        })
+        .flatten()
+        .boxed()
+    }
+
+    fn name(&self) -> &'static str {
+        self.client.name()
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        self.client.capabilities()
+    }
+}
@@ -0,0 +1,133 @@
+//! ASR 请求重试
+//!
+//! 包装单个客户端，遇到暂时性的网络错误（[`VoiceError::NetworkError`]，如 WebSocket 握手断开、
+//! 连接超时）时按指数退避重新识别同一段音频，避免一次网络抖动丢掉整段录音。
+//! 其他错误（鉴权失败、音频格式、服务返回的识别错误）重试也无法解决，直接返回。
+//!
+//! 流式识别的音频边录边发，无法重放，[`AsrClient::transcribe_stream`] 直接交给被包装的客户端。
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use super::{AsrCapabilities, AsrClient};
+use crate::error::{Result, VoiceError};
+use crate::types::{AudioData, PartialTranscript, TranscribeResult};
+
+/// 重试策略
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// 最多尝试次数（含首次请求），1 表示不重试
+    pub max_attempts: u32,
+    /// 首次重试前的等待时间
+    pub initial_backoff: Duration,
+    /// 单次等待的上限
+    pub max_backoff: Duration,
+    /// 每次重试后等待时间的倍数
+    pub multiplier: f64,
+    /// 随机抖动比例（0-1），实际等待时间在 `backoff × (1 ± jitter)` 之间
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 第 `retry` 次重试（从 1 开始）前的基础等待时间，不含抖动
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .max(1.0)
+            .powi(retry.saturating_sub(1) as i32);
+        self.initial_backoff
+            .mul_f64(factor)
+            .min(self.max_backoff.max(self.initial_backoff))
+    }
+
+    /// 第 `retry` 次重试前实际等待的时间（加上随机抖动）
+    fn delay(&self, retry: u32) -> Duration {
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return self.backoff(retry);
+        }
+        // 不引入随机数依赖：RandomState 每次创建使用不同的随机密钥
+        let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        self.backoff(retry)
+            .mul_f64(1.0 + jitter * (random * 2.0 - 1.0))
+    }
+
+    /// 错误是否值得重试
+    fn should_retry(error: &VoiceError) -> bool {
+        matches!(error, VoiceError::NetworkError(_))
+    }
+}
+
+/// 带重试的 ASR 客户端
+pub struct AsrRetry {
+    client: Box<dyn AsrClient>,
+    policy: RetryPolicy,
+}
+
+impl AsrRetry {
+    pub fn new(client: Box<dyn AsrClient>, policy: RetryPolicy) -> Self {
+        Self { client, policy }
+    }
+
+    /// 重试策略
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+}
+
+#[async_trait]
+impl AsrClient for AsrRetry {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        let mut attempt = 1;
+        loop {
+            match self.client.transcribe(audio).await {
+                Err(e) if attempt < self.policy.max_attempts && RetryPolicy::should_retry(&e) => {
+                    let delay = self.policy.delay(attempt);
+                    tracing::warn!(
+                        "[语音识别] {} 第 {} 次请求失败，{}ms 后重试: {}",
+                        self.client.name(),
+                        attempt,
+                        delay.as_millis(),
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn transcribe_stream<'a>(
+        &'a self,
+        frames: UnboundedReceiver<Vec<i16>>,
+        sample_rate: u32,
+    ) -> BoxStream<'a, Result<PartialTranscript>> {
+        self.client.transcribe_stream(frames, sample_rate)
+    }
+
+    fn name(&self) -> &'static str {
+        self.client.name()
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        self.client.capabilities()
+    }
+}
@@ -0,0 +1,270 @@
+//! 腾讯云一句话识别客户端
+//!
+//! 使用腾讯云语音识别的一句话识别接口（SentenceRecognition），单次最长 60 秒，
+//! 音频以 Base64 放在请求体中上传。请求使用 TC3-HMAC-SHA256 签名：
+//! 1. 拼接规范请求（方法、路径、参与签名的请求头、请求体哈希）
+//! 2. 用 `TC3` + SecretKey 依次对日期、服务名、`tc3_request` 派生签名密钥
+//! 3. 对待签字符串签名，放入 `Authorization` 请求头
+//!
+//! ## 参考文档
+//! - 一句话识别：https://cloud.tencent.com/document/api/1093/35646
+//! - 签名方法 v3：https://cloud.tencent.com/document/api/1093/35640
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::timeouts::{AsrTimeouts, DEFAULT_RECEIVE_TIMEOUT};
+use super::{AsrCapabilities, AsrClient, AudioFormat, REALTIME_MAX_SECS};
+use crate::error::{Result, VoiceError};
+use crate::language::normalize_language;
+use crate::types::{AudioData, TranscribeResult};
+
+const API_ENDPOINT: &str = "https://asr.tencentcloudapi.com";
+const SERVICE: &str = "asr";
+const ACTION: &str = "SentenceRecognition";
+const VERSION: &str = "2019-06-14";
+const ALGORITHM: &str = "TC3-HMAC-SHA256";
+const CONTENT_TYPE: &str = "application/json; charset=utf-8";
+/// 参与签名的请求头
+const SIGNED_HEADERS: &str = "content-type;host;x-tc-action";
+/// 音频数据放在请求体中
+const SOURCE_TYPE_DATA: u8 = 1;
+
+/// 一句话识别请求
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct SentenceRequest<'a> {
+    eng_ser_vice_type: &'a str,
+    source_type: u8,
+    voice_format: &'a str,
+    data: String,
+    data_len: usize,
+}
+
+/// 接口响应
+#[derive(Debug, Deserialize)]
+struct ApiResponse {
+    #[serde(rename = "Response")]
+    response: SentenceResponse,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SentenceResponse {
+    #[serde(default)]
+    result: String,
+    #[serde(default)]
+    error: Option<ApiError>,
+    #[serde(default)]
+    request_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ApiError {
+    code: String,
+    message: String,
+}
+
+/// 腾讯云客户端
+pub struct TencentClient {
+    secret_id: String,
+    secret_key: String,
+    region: Option<String>,
+    engine: String,
+    endpoint: String,
+    http: reqwest::Client,
+    timeouts: AsrTimeouts,
+}
+
+impl TencentClient {
+    /// 腾讯云一句话识别的能力
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: false,
+        timestamps: false,
+        hotwords: false,
+        punctuation: true,
+        // 中文通用引擎支持中英混说
+        code_switching: true,
+        diarization: false,
+        max_duration_secs: Some(REALTIME_MAX_SECS),
+        formats: &[
+            AudioFormat::Pcm,
+            AudioFormat::Wav,
+            AudioFormat::Mp3,
+            AudioFormat::M4a,
+            AudioFormat::Amr,
+        ],
+        // 引擎按 16k 选择，8k 引擎只有中英文
+        sample_rates: &[16000],
+    };
+
+    /// 创建新的客户端
+    pub fn new(secret_id: String, secret_key: String) -> Self {
+        Self {
+            secret_id,
+            secret_key,
+            region: None,
+            engine: "16k_zh".to_string(),
+            endpoint: API_ENDPOINT.to_string(),
+            http: AsrTimeouts::default().http_client(),
+            timeouts: AsrTimeouts::default(),
+        }
+    }
+
+    /// 设置地域（如 ap-shanghai），一句话识别可不填
+    pub fn with_region(mut self, region: String) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// 设置引擎类型（如 16k_zh、16k_en、16k_yue）
+    pub fn with_engine(mut self, engine: String) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// 设置接口地址（用于代理或测试）
+    pub fn with_endpoint(mut self, endpoint: String) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+
+    /// 设置超时，HTTP 请求的计时方式见 [`AsrTimeouts`]
+    pub fn with_timeouts(mut self, timeouts: AsrTimeouts) -> Self {
+        self.http = timeouts.http_client();
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 接口地址中的 Host（含端口），需与实际发送的 Host 请求头一致
+    fn host(&self) -> &str {
+        let without_scheme = self
+            .endpoint
+            .split_once("://")
+            .map_or(self.endpoint.as_str(), |(_, rest)| rest);
+        without_scheme.split('/').next().unwrap_or_default()
+    }
+
+    /// 生成 `Authorization` 请求头
+    fn authorization(&self, payload: &str, time: DateTime<Utc>) -> Result<String> {
+        let date = time.format("%Y-%m-%d").to_string();
+        let canonical_request = format!(
+            "POST\n/\n\ncontent-type:{}\nhost:{}\nx-tc-action:{}\n\n{}\n{}",
+            CONTENT_TYPE,
+            self.host(),
+            ACTION.to_lowercase(),
+            SIGNED_HEADERS,
+            sha256_hex(payload.as_bytes())
+        );
+        let credential_scope = format!("{}/{}/tc3_request", date, SERVICE);
+        let string_to_sign = format!(
+            "{}\n{}\n{}\n{}",
+            ALGORITHM,
+            time.timestamp(),
+            credential_scope,
+            sha256_hex(canonical_request.as_bytes())
+        );
+
+        let secret_date = hmac_sha256(format!("TC3{}", self.secret_key).as_bytes(), &date)?;
+        let secret_service = hmac_sha256(&secret_date, SERVICE)?;
+        let secret_signing = hmac_sha256(&secret_service, "tc3_request")?;
+        let signature = hex(&hmac_sha256(&secret_signing, &string_to_sign)?);
+
+        Ok(format!(
+            "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+            ALGORITHM, self.secret_id, credential_scope, SIGNED_HEADERS, signature
+        ))
+    }
+}
+
+/// HMAC-SHA256
+fn hmac_sha256(key: &[u8], message: &str) -> Result<Vec<u8>> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key).map_err(|e| VoiceError::AsrAuthError(e.to_string()))?;
+    mac.update(message.as_bytes());
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+/// SHA256 十六进制摘要
+fn sha256_hex(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[async_trait]
+impl AsrClient for TencentClient {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        let encoded = Self::CAPABILITIES.encode(audio)?;
+        let payload = serde_json::to_string(&SentenceRequest {
+            eng_ser_vice_type: &self.engine,
+            source_type: SOURCE_TYPE_DATA,
+            voice_format: encoded.extension(),
+            data: BASE64.encode(&encoded.bytes),
+            data_len: encoded.bytes.len(),
+        })
+        .map_err(|e| VoiceError::AsrError(e.to_string()))?;
+
+        let now = Utc::now();
+        let mut request = self
+            .http
+            .post(&self.endpoint)
+            .header("Authorization", self.authorization(&payload, now)?)
+            .header("Content-Type", CONTENT_TYPE)
+            .header("X-TC-Action", ACTION)
+            .header("X-TC-Version", VERSION)
+            .header("X-TC-Timestamp", now.timestamp().to_string());
+        if let Some(region) = self.region.as_deref().filter(|r| !r.is_empty()) {
+            request = request.header("X-TC-Region", region);
+        }
+
+        let response: ApiResponse = request
+            .body(payload)
+            .timeout(self.timeouts.request_timeout(DEFAULT_RECEIVE_TIMEOUT))
+            .send()
+            .await
+            .map_err(|e| VoiceError::NetworkError(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| VoiceError::AsrError(e.to_string()))?;
+        let response = response.response;
+
+        if let Some(error) = response.error {
+            let message = format!(
+                "腾讯云 ASR 错误: {} - {}（RequestId: {}）",
+                error.code,
+                error.message,
+                response.request_id.as_deref().unwrap_or("-")
+            );
+            return Err(if error.code.starts_with("AuthFailure") {
+                VoiceError::AsrAuthError(message)
+            } else {
+                VoiceError::AsrError(message)
+            });
+        }
+
+        let language = self.engine.split_once('_').map(|(_, l)| l).unwrap_or("zh");
+        Ok(TranscribeResult {
+            text: response.result,
+            language: Some(normalize_language(language)),
+            confidence: None,
+            segments: vec![],
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "腾讯云语音"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        Self::CAPABILITIES
+    }
+}
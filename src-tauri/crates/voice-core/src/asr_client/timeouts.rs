@@ -0,0 +1,83 @@
+//! ASR 请求超时
+//!
+//! 识别请求分为三个阶段分别计时：
+//! - 连接：TCP、TLS 和 WebSocket 握手
+//! - 发送：上传音频（WebSocket 按帧计时）
+//! - 接收：音频发送完后等待识别结果
+//!
+//! 未设置的阶段使用客户端的默认值，超时返回 [`VoiceError::NetworkError`]，可由 [`super::AsrRetry`] 重试。
+//! HTTP 接口的上传和等待结果在同一个请求内完成，无法分别计时，整个请求限时为三个阶段之和。
+
+use std::future::Future;
+use std::time::Duration;
+
+use crate::error::{Result, VoiceError};
+
+/// 默认连接超时
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 默认发送超时
+pub const DEFAULT_SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 默认接收超时
+pub const DEFAULT_RECEIVE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 请求超时，`None` 表示使用客户端的默认值
+///
+/// HTTP 接口的连接超时用于建立连接，整个请求不超过连接、发送和接收超时之和
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AsrTimeouts {
+    /// 建立连接
+    pub connect: Option<Duration>,
+    /// 发送音频
+    pub send: Option<Duration>,
+    /// 等待识别结果
+    pub receive: Option<Duration>,
+}
+
+impl AsrTimeouts {
+    /// 连接超时
+    pub fn connect(&self) -> Duration {
+        self.connect.unwrap_or(DEFAULT_CONNECT_TIMEOUT)
+    }
+
+    /// 发送超时
+    pub fn send(&self) -> Duration {
+        self.send.unwrap_or(DEFAULT_SEND_TIMEOUT)
+    }
+
+    /// 接收超时，未设置时为 `default`
+    pub fn receive_or(&self, default: Duration) -> Duration {
+        self.receive.unwrap_or(default)
+    }
+
+    /// HTTP 请求的总超时：连接、发送和接收超时之和，接收超时未设置时为 `default_receive`
+    pub(crate) fn request_timeout(&self, default_receive: Duration) -> Duration {
+        self.connect() + self.send() + self.receive_or(default_receive)
+    }
+
+    /// 按连接超时构建 HTTP 客户端，客户端应在设置超时时构建一次并复用
+    pub(crate) fn http_client(&self) -> reqwest::Client {
+        reqwest::Client::builder()
+            .connect_timeout(self.connect())
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new())
+    }
+}
+
+/// 限时等待 `future`，超时返回网络错误，`stage` 用于错误提示（如 "连接"）
+pub(crate) async fn within<T>(
+    duration: Duration,
+    stage: &str,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    tokio::time::timeout(duration, future)
+        .await
+        .unwrap_or_else(|_| {
+            Err(VoiceError::NetworkError(format!(
+                "{}超时（{:.1} 秒）",
+                stage,
+                duration.as_secs_f32()
+            )))
+        })
+}
@@ -0,0 +1,735 @@
+//! 火山引擎（豆包）语音识别客户端
+//!
+//! 使用火山引擎流式语音识别 WebSocket 接口（v2），通过 `Authorization: Bearer; {token}`
+//! 请求头鉴权，集群（cluster）决定使用的识别模型。
+//!
+//! ## 协议说明
+//!
+//! 与讯飞的 JSON 文本帧不同，火山引擎使用自定义的二进制帧：
+//! 4 字节帧头（协议版本、帧头长度、消息类型、标志位、序列化方式、压缩方式）、
+//! 4 字节大端负载长度和负载，负载（请求参数和音频）均经 gzip 压缩。
+//! 错误响应在帧头后多一个 4 字节错误码。
+//!
+//! 1. 建立 WebSocket 连接
+//! 2. 发送完整请求帧（full client request），负载为应用、音频格式等 JSON 参数
+//! 3. 分包发送音频帧（audio only request），最后一包带结束标志
+//! 4. 服务端每收到一包即返回截至当前的完整识别文本，序号为负表示最终结果
+//!
+//! ## 参考文档
+//! https://www.volcengine.com/docs/6561/80818
+
+use std::io::{Read, Write};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use futures_util::stream::{self, BoxStream};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_util::task::AbortOnDropHandle;
+
+use super::timeouts::{within, AsrTimeouts, DEFAULT_RECEIVE_TIMEOUT};
+use super::{AsrCapabilities, AsrClient, AudioFormat, REALTIME_MAX_SECS};
+use crate::encode::resample;
+use crate::error::{Result, VoiceError};
+use crate::language::normalize_language;
+use crate::types::{AudioData, PartialTranscript, Segment, TranscribeResult};
+
+/// 默认接口地址
+const DEFAULT_ENDPOINT: &str = "wss://openspeech.bytedance.com/api/v2/asr";
+
+/// 默认集群（通用流式识别）
+pub const DEFAULT_CLUSTER: &str = "volcengine_streaming_common";
+
+/// 每包音频大小（字节），200ms 的 16kHz 16bit 单声道音频
+const CHUNK_SIZE: usize = 6400;
+
+/// 协议版本（高 4 位）和帧头长度（低 4 位，单位 4 字节）
+const PROTOCOL_HEADER: u8 = 0b0001_0001;
+
+/// 标志位：最后一包 / 最终结果
+const FLAG_LAST: u8 = 0b0010;
+
+/// 序列化方式：无 / JSON
+const SERIALIZATION_NONE: u8 = 0b0000;
+const SERIALIZATION_JSON: u8 = 0b0001;
+
+/// 压缩方式：无 / gzip
+const COMPRESSION_NONE: u8 = 0b0000;
+const COMPRESSION_GZIP: u8 = 0b0001;
+
+/// 成功
+const CODE_SUCCESS: i64 = 1000;
+/// 无访问权限（Token 或 APPID 无效）
+const CODE_ACCESS_DENIED: i64 = 1002;
+/// 音频中没有有效语音
+const CODE_SILENCE: i64 = 1013;
+
+/// 二进制帧的消息类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageType {
+    /// 完整请求（识别参数）
+    FullClientRequest,
+    /// 纯音频请求
+    AudioOnlyRequest,
+    /// 完整响应（识别结果）
+    FullServerResponse,
+    /// 错误响应
+    ServerError,
+}
+
+impl MessageType {
+    fn code(self) -> u8 {
+        match self {
+            Self::FullClientRequest => 0b0001,
+            Self::AudioOnlyRequest => 0b0010,
+            Self::FullServerResponse => 0b1001,
+            Self::ServerError => 0b1111,
+        }
+    }
+
+    fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0b0001 => Some(Self::FullClientRequest),
+            0b0010 => Some(Self::AudioOnlyRequest),
+            0b1001 => Some(Self::FullServerResponse),
+            0b1111 => Some(Self::ServerError),
+            _ => None,
+        }
+    }
+}
+
+/// 火山引擎二进制帧
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub message_type: MessageType,
+    /// 是否为最后一包音频（或最终结果）
+    pub last: bool,
+    /// 错误码，仅错误响应有值
+    pub error_code: Option<u32>,
+    /// 负载（未压缩）
+    pub payload: Vec<u8>,
+}
+
+impl Frame {
+    /// 创建帧
+    pub fn new(message_type: MessageType, payload: Vec<u8>) -> Self {
+        Self {
+            message_type,
+            last: false,
+            error_code: None,
+            payload,
+        }
+    }
+
+    /// 创建错误响应帧
+    pub fn error(code: u32, message: &str) -> Self {
+        Self {
+            error_code: Some(code),
+            ..Self::new(MessageType::ServerError, message.as_bytes().to_vec())
+        }
+    }
+
+    /// 设置最后一包标志
+    pub fn with_last(mut self, last: bool) -> Self {
+        self.last = last;
+        self
+    }
+
+    /// 编码为二进制帧，负载经 gzip 压缩
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let serialization = match self.message_type {
+            MessageType::AudioOnlyRequest => SERIALIZATION_NONE,
+            _ => SERIALIZATION_JSON,
+        };
+        let flags = if self.last { FLAG_LAST } else { 0 };
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&self.payload)
+            .and_then(|_| encoder.finish())
+            .map(|compressed| {
+                let mut frame = Vec::with_capacity(compressed.len() + 12);
+                frame.extend_from_slice(&[
+                    PROTOCOL_HEADER,
+                    self.message_type.code() << 4 | flags,
+                    serialization << 4 | COMPRESSION_GZIP,
+                    0,
+                ]);
+                if let Some(code) = self.error_code {
+                    frame.extend_from_slice(&code.to_be_bytes());
+                }
+                frame.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+                frame.extend_from_slice(&compressed);
+                frame
+            })
+            .map_err(|e| VoiceError::AsrError(format!("压缩请求失败: {}", e)))
+    }
+
+    /// 解析二进制帧，按帧头声明的压缩方式解压负载
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let invalid =
+            |reason: &str| VoiceError::AsrError(format!("火山引擎响应帧无效: {}", reason));
+
+        if data.len() < 4 {
+            return Err(invalid("帧头不完整"));
+        }
+        let header_size = (data[0] & 0x0f) as usize * 4;
+        let message_type = MessageType::from_code(data[1] >> 4)
+            .ok_or_else(|| invalid(&format!("未知消息类型 {:#06b}", data[1] >> 4)))?;
+        let last = data[1] & FLAG_LAST != 0;
+        let compression = data[2] & 0x0f;
+
+        let mut rest = data
+            .get(header_size..)
+            .ok_or_else(|| invalid("帧头不完整"))?;
+        let read_u32 = |rest: &mut &[u8]| -> Result<u32> {
+            let (value, tail) = rest
+                .split_first_chunk::<4>()
+                .ok_or_else(|| invalid("帧长度不足"))?;
+            *rest = tail;
+            Ok(u32::from_be_bytes(*value))
+        };
+        let error_code = match message_type {
+            MessageType::ServerError => Some(read_u32(&mut rest)?),
+            _ => None,
+        };
+        let size = read_u32(&mut rest)? as usize;
+        let raw = rest.get(..size).ok_or_else(|| invalid("负载长度不足"))?;
+
+        let payload = match compression {
+            COMPRESSION_NONE => raw.to_vec(),
+            COMPRESSION_GZIP => {
+                let mut payload = Vec::new();
+                GzDecoder::new(raw)
+                    .read_to_end(&mut payload)
+                    .map_err(|e| invalid(&format!("解压失败: {}", e)))?;
+                payload
+            }
+            other => return Err(invalid(&format!("不支持的压缩方式 {}", other))),
+        };
+
+        Ok(Self {
+            message_type,
+            last,
+            error_code,
+            payload,
+        })
+    }
+}
+
+/// 火山引擎客户端
+pub struct VolcengineClient {
+    app_id: String,
+    access_token: String,
+    cluster: String,
+    language: String,
+    endpoint: String,
+    timeouts: AsrTimeouts,
+}
+
+impl VolcengineClient {
+    /// 火山引擎流式识别的能力
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: true,
+        timestamps: true,
+        hotwords: false,
+        punctuation: true,
+        code_switching: true,
+        diarization: false,
+        max_duration_secs: Some(REALTIME_MAX_SECS),
+        formats: &[AudioFormat::Pcm],
+        sample_rates: &[16000],
+    };
+
+    /// 创建新的客户端
+    pub fn new(app_id: String, access_token: String) -> Self {
+        Self {
+            app_id,
+            access_token,
+            cluster: DEFAULT_CLUSTER.to_string(),
+            language: "zh-CN".to_string(),
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            timeouts: AsrTimeouts::default(),
+        }
+    }
+
+    /// 设置集群
+    pub fn with_cluster(mut self, cluster: String) -> Self {
+        self.cluster = cluster;
+        self
+    }
+
+    /// 设置识别语言（如 zh-CN、en-US）
+    pub fn with_language(mut self, language: String) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// 设置接口地址（用于测试）
+    pub fn with_endpoint(mut self, endpoint: String) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+
+    /// 设置超时：连接、每包音频的发送、音频发送完后等待最终结果
+    pub fn with_timeouts(mut self, timeouts: AsrTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 构建完整请求帧
+    fn build_full_request(&self) -> Result<Frame> {
+        let request = FullRequest {
+            app: AppParams {
+                appid: &self.app_id,
+                cluster: &self.cluster,
+                token: &self.access_token,
+            },
+            user: UserParams { uid: "proxycast" },
+            audio: AudioParams {
+                format: "raw",
+                codec: "raw",
+                rate: 16000,
+                bits: 16,
+                channel: 1,
+                language: &self.language,
+            },
+            request: RequestParams {
+                reqid: format!(
+                    "proxycast-{}",
+                    Utc::now().timestamp_nanos_opt().unwrap_or(0)
+                ),
+                nbest: 1,
+                workflow: "audio_in,resample,partition,vad,fe,decode,itn,nlu_punctuate",
+                show_utterances: true,
+                result_type: "full",
+                sequence: 1,
+            },
+        };
+        let payload = serde_json::to_vec(&request)
+            .map_err(|e| VoiceError::AsrError(format!("序列化请求失败: {}", e)))?;
+        Ok(Frame::new(MessageType::FullClientRequest, payload))
+    }
+
+    /// 建立连接并发送完整请求帧，返回写端和接收任务
+    async fn open(
+        &self,
+        partials: Option<UnboundedSender<String>>,
+    ) -> Result<(
+        impl Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+        AbortOnDropHandle<Vec<VolcengineResponse>>,
+    )> {
+        let mut request = self
+            .endpoint
+            .as_str()
+            .into_client_request()
+            .map_err(|e| VoiceError::ConfigError(format!("火山引擎接口地址无效: {}", e)))?;
+        let authorization = HeaderValue::from_str(&format!("Bearer; {}", self.access_token))
+            .map_err(|_| {
+                VoiceError::ConfigError("火山引擎 Access Token 含有非法字符".to_string())
+            })?;
+        request.headers_mut().insert("Authorization", authorization);
+
+        tracing::info!("正在连接火山引擎 WebSocket...");
+        let connect = async {
+            connect_async(request).await.map_err(|e| {
+                tracing::error!("火山引擎 WebSocket 连接失败: {:?}", e);
+                match e {
+                    tokio_tungstenite::tungstenite::Error::Http(ref response)
+                        if matches!(response.status().as_u16(), 401 | 403) =>
+                    {
+                        VoiceError::AsrAuthError(format!("火山引擎鉴权失败: {}", response.status()))
+                    }
+                    _ => VoiceError::NetworkError(format!("WebSocket 连接失败: {}", e)),
+                }
+            })
+        };
+        let (ws_stream, _) = within(self.timeouts.connect(), "WebSocket 连接", connect).await?;
+
+        let (mut write, read) = ws_stream.split();
+        self.send_frame(&mut write, &self.build_full_request()?)
+            .await?;
+        // 识别被取消（future 被丢弃）时接收任务随之中止，不在后台等到超时
+        let receive_task = AbortOnDropHandle::new(tokio::spawn(receive_responses(read, partials)));
+        Ok((write, receive_task))
+    }
+
+    /// 编码并发送一帧
+    async fn send_frame<W>(&self, write: &mut W, frame: &Frame) -> Result<()>
+    where
+        W: Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+    {
+        let message = Message::Binary(frame.encode()?);
+        within(self.timeouts.send(), "发送数据", async {
+            write
+                .send(message)
+                .await
+                .map_err(|e| VoiceError::NetworkError(format!("发送数据失败: {}", e)))
+        })
+        .await
+    }
+
+    /// 解析最终识别结果
+    fn parse_result(&self, response: Option<&VolcengineResponse>) -> TranscribeResult {
+        let best = response.and_then(|r| r.result.first());
+        let text = best.map(|r| r.text.clone()).unwrap_or_default();
+        let segments = best
+            .map(|r| {
+                r.utterances
+                    .iter()
+                    .filter(|u| !u.text.is_empty())
+                    .map(|u| Segment {
+                        start: u.start_time as f32 / 1000.0,
+                        end: u.end_time as f32 / 1000.0,
+                        text: u.text.clone(),
+                        speaker: None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        TranscribeResult {
+            text,
+            language: Some(normalize_language(&self.language)),
+            confidence: best.and_then(|r| r.confidence).filter(|c| *c > 0.0),
+            segments,
+            backend: None,
+        }
+    }
+
+    /// 等待接收任务结束，检查错误并解析最终结果
+    async fn finish(
+        &self,
+        receive_task: AbortOnDropHandle<Vec<VolcengineResponse>>,
+        send_error: Option<VoiceError>,
+    ) -> Result<TranscribeResult> {
+        let receive = async {
+            receive_task
+                .await
+                .map_err(|e| VoiceError::AsrError(format!("接收任务失败: {}", e)))
+        };
+        let responses = within(
+            self.timeouts.receive_or(DEFAULT_RECEIVE_TIMEOUT),
+            "等待识别结果",
+            receive,
+        )
+        .await?;
+
+        let last = responses.last();
+        match last.map(|r| r.code) {
+            Some(CODE_SUCCESS) => {}
+            Some(CODE_SILENCE) => {
+                tracing::info!("火山引擎未识别到有效语音");
+                return Ok(self.parse_result(None));
+            }
+            Some(code) => {
+                let message = format!(
+                    "火山引擎 ASR 错误 [{}]: {}",
+                    code,
+                    last.map(|r| r.message.as_str()).unwrap_or_default()
+                );
+                return Err(match code {
+                    CODE_ACCESS_DENIED => VoiceError::AsrAuthError(message),
+                    _ => VoiceError::AsrError(message),
+                });
+            }
+            None => {
+                return Err(send_error
+                    .unwrap_or_else(|| VoiceError::AsrError("火山引擎未返回识别结果".to_string())))
+            }
+        }
+
+        if !last.is_some_and(|r| r.is_final()) {
+            if let Some(err) = send_error {
+                return Err(err);
+            }
+            tracing::warn!("火山引擎连接提前关闭，使用最后一次识别结果");
+        }
+
+        let result = self.parse_result(last);
+        tracing::info!("火山引擎识别完成: {}", result.text);
+        Ok(result)
+    }
+
+    /// 流式识别会话：录音过程中持续发送音频
+    ///
+    /// `frames` 为录音处理线程转发的单声道采样，发送端关闭即视为录音结束，
+    /// 此时发送带结束标志的最后一包并等待最终结果。每次收到识别结果时把当前完整文本发给 `partials`。
+    async fn stream_session(
+        &self,
+        mut frames: UnboundedReceiver<Vec<i16>>,
+        sample_rate: u32,
+        partials: UnboundedSender<String>,
+    ) -> Result<TranscribeResult> {
+        let (mut write, receive_task) = self.open(Some(partials)).await?;
+
+        let mut pending: Vec<u8> = Vec::new();
+        let mut send_error: Option<VoiceError> = None;
+
+        'recv: while let Some(samples) = frames.recv().await {
+            let samples_16k = resample(&samples, sample_rate, 16000);
+            pending.extend(samples_16k.iter().flat_map(|s| s.to_le_bytes()));
+
+            while pending.len() >= CHUNK_SIZE {
+                let chunk: Vec<u8> = pending.drain(..CHUNK_SIZE).collect();
+                let frame = Frame::new(MessageType::AudioOnlyRequest, chunk);
+                if let Err(e) = self.send_frame(&mut write, &frame).await {
+                    send_error = Some(e);
+                    break 'recv;
+                }
+            }
+        }
+
+        // 录音结束：剩余数据作为最后一包发送
+        if send_error.is_none() {
+            let frame = Frame::new(MessageType::AudioOnlyRequest, pending).with_last(true);
+            send_error = self.send_frame(&mut write, &frame).await.err();
+        }
+
+        self.finish(receive_task, send_error).await
+    }
+}
+
+/// 接收识别结果，直到收到最终结果、错误响应或连接关闭
+async fn receive_responses<S>(
+    mut read: S,
+    partials: Option<UnboundedSender<String>>,
+) -> Vec<VolcengineResponse>
+where
+    S: Stream<Item = std::result::Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+{
+    let mut responses: Vec<VolcengineResponse> = Vec::new();
+
+    while let Some(msg) = read.next().await {
+        let data = match msg {
+            Ok(Message::Binary(data)) => data,
+            Ok(Message::Close(frame)) => {
+                tracing::info!("WebSocket 连接关闭: {:?}", frame);
+                break;
+            }
+            Err(e) => {
+                tracing::error!("接收数据失败: {}", e);
+                break;
+            }
+            _ => continue,
+        };
+
+        let frame = match Frame::decode(&data) {
+            Ok(frame) => frame,
+            Err(e) => {
+                tracing::error!("{}", e);
+                continue;
+            }
+        };
+
+        let response = match frame.error_code {
+            Some(code) => VolcengineResponse {
+                code: code as i64,
+                message: String::from_utf8_lossy(&frame.payload).to_string(),
+                sequence: 0,
+                result: Vec::new(),
+            },
+            None => match serde_json::from_slice::<VolcengineResponse>(&frame.payload) {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::error!("解析响应失败: {}", e);
+                    continue;
+                }
+            },
+        };
+        tracing::debug!(
+            "收到火山引擎响应: code={}, sequence={}",
+            response.code,
+            response.sequence
+        );
+
+        let done = frame.last || response.is_final() || response.code != CODE_SUCCESS;
+        if !done {
+            if let (Some(partials), Some(best)) = (&partials, response.result.first()) {
+                if !best.text.is_empty() {
+                    let _ = partials.send(best.text.clone());
+                }
+            }
+        }
+        responses.push(response);
+        if done {
+            break;
+        }
+    }
+
+    responses
+}
+
+#[async_trait]
+impl AsrClient for VolcengineClient {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        // 按协商结果编码为 16kHz 16-bit 裸 PCM
+        let audio_bytes = Self::CAPABILITIES.encode(audio)?.bytes;
+        let (mut write, receive_task) = self.open(None).await?;
+
+        let chunks: Vec<&[u8]> = audio_bytes.chunks(CHUNK_SIZE).collect();
+        tracing::info!(
+            "开始发送音频数据，共 {} 包，总大小 {} 字节",
+            chunks.len().max(1),
+            audio_bytes.len()
+        );
+
+        let mut send_error: Option<VoiceError> = None;
+        let last_index = chunks.len().saturating_sub(1);
+        for (i, chunk) in chunks.iter().enumerate() {
+            let frame = Frame::new(MessageType::AudioOnlyRequest, chunk.to_vec())
+                .with_last(i == last_index);
+            if let Err(e) = self.send_frame(&mut write, &frame).await {
+                tracing::error!("发送第 {} 包失败: {}", i, e);
+                send_error = Some(e);
+                break;
+            }
+        }
+        if chunks.is_empty() {
+            let frame = Frame::new(MessageType::AudioOnlyRequest, Vec::new()).with_last(true);
+            send_error = self.send_frame(&mut write, &frame).await.err();
+        }
+
+        self.finish(receive_task, send_error).await
+    }
+
+    fn transcribe_stream<'a>(
+        &'a self,
+        frames: UnboundedReceiver<Vec<i16>>,
+        sample_rate: u32,
+    ) -> BoxStream<'a, Result<PartialTranscript>> {
+        let (partials, rx) = mpsc::unbounded_channel();
+        let session = Box::pin(self.stream_session(frames, sample_rate, partials));
+
+        // 会话结束前持续转发中间结果，结束后产出最终结果并结束流
+        Box::pin(stream::unfold(
+            (Some(session), rx),
+            |(session, mut rx)| async move {
+                let mut session = session?;
+                tokio::select! {
+                    biased;
+                    Some(text) = rx.recv() => {
+                        Some((Ok(PartialTranscript::partial(text)), (Some(session), rx)))
+                    }
+                    result = &mut session => {
+                        Some((result.map(PartialTranscript::finished), (None, rx)))
+                    }
+                }
+            },
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        "火山引擎"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        Self::CAPABILITIES
+    }
+}
+
+// ============================================================================
+// 火山引擎请求/响应数据结构
+// ============================================================================
+
+/// 完整请求参数
+#[derive(Debug, Serialize)]
+struct FullRequest<'a> {
+    app: AppParams<'a>,
+    user: UserParams<'a>,
+    audio: AudioParams<'a>,
+    request: RequestParams<'a>,
+}
+
+/// 应用参数
+#[derive(Debug, Serialize)]
+struct AppParams<'a> {
+    appid: &'a str,
+    /// 集群（决定识别模型）
+    cluster: &'a str,
+    token: &'a str,
+}
+
+/// 用户参数
+#[derive(Debug, Serialize)]
+struct UserParams<'a> {
+    uid: &'a str,
+}
+
+/// 音频参数
+#[derive(Debug, Serialize)]
+struct AudioParams<'a> {
+    /// 容器格式（raw: 裸 PCM）
+    format: &'a str,
+    /// 编码（raw: 不编码）
+    codec: &'a str,
+    rate: u32,
+    bits: u32,
+    channel: u32,
+    /// 识别语言（如 zh-CN）
+    language: &'a str,
+}
+
+/// 识别参数
+#[derive(Debug, Serialize)]
+struct RequestParams<'a> {
+    /// 请求 ID，每次会话唯一
+    reqid: String,
+    nbest: u32,
+    /// 处理流程（含标点和数字规整）
+    workflow: &'a str,
+    /// 返回分句及时间戳
+    show_utterances: bool,
+    /// full: 每次返回截至当前的完整结果
+    result_type: &'a str,
+    sequence: i32,
+}
+
+/// 识别响应
+#[derive(Debug, Deserialize)]
+struct VolcengineResponse {
+    /// 状态码（1000 表示成功）
+    code: i64,
+    #[serde(default)]
+    message: String,
+    /// 结果序号，负数表示最终结果
+    #[serde(default)]
+    sequence: i64,
+    #[serde(default)]
+    result: Vec<VolcengineResult>,
+}
+
+impl VolcengineResponse {
+    fn is_final(&self) -> bool {
+        self.sequence < 0
+    }
+}
+
+/// 识别结果
+#[derive(Debug, Deserialize)]
+struct VolcengineResult {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    confidence: Option<f32>,
+    #[serde(default)]
+    utterances: Vec<VolcengineUtterance>,
+}
+
+/// 分句
+#[derive(Debug, Deserialize)]
+struct VolcengineUtterance {
+    #[serde(default)]
+    text: String,
+    /// 开始时间（毫秒）
+    #[serde(default)]
+    start_time: i64,
+    /// 结束时间（毫秒）
+    #[serde(default)]
+    end_time: i64,
+}
@@ -10,58 +10,62 @@
 //! 3. 接收识别结果（流式返回）
 //! 4. 发送结束帧，等待最终结果
 //!
+//! 除整段识别外，[`AsrClient::transcribe_stream`] 支持边录音边发送，
+//! 每收到一帧识别结果即产出动态修正后的完整文本，录音结束后只需等待最后几帧的识别结果。
+//!
+//! 开启动态修正（`dwa=wpgs`）后，每帧结果带序号 `sn`，`pgs=rpl` 的结果替换 `rg` 范围内之前的结果。
+//! 每个词带起始帧偏移 `bg`（1 帧 = 10ms），按句末标点切分为带时间戳的分段；
+//! 候选词分数 `sc` 的平均值作为置信度（服务端未返回分数时为空）。
+//!
+//! 热词通过首帧业务参数 `dhw`（会话级动态热词）上传，格式为 `utf-8;热词1|热词2`。
+//!
 //! ## 参考文档
 //! https://www.xfyun.cn/doc/asr/voicedictation/API.html
 
+use std::collections::BTreeMap;
+use std::time::Duration;
+
 use async_trait::async_trait;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use chrono::Utc;
-use futures_util::{SinkExt, StreamExt};
+use futures_util::stream::{self, BoxStream};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
 use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
-
-use super::AsrClient;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use tokio_util::task::AbortOnDropHandle;
+
+use super::timeouts::{within, AsrTimeouts, DEFAULT_RECEIVE_TIMEOUT};
+use super::{AsrCapabilities, AsrClient, AudioFormat, REALTIME_MAX_SECS};
+use crate::encode::resample;
 use crate::error::{Result, VoiceError};
-use crate::types::{AudioData, Segment, TranscribeResult};
+use crate::types::{AudioData, PartialTranscript, Segment, TranscribeResult};
 
 /// 讯飞 WebSocket 帧大小（字节）
 /// 讯飞建议每帧发送 1280 字节（约 40ms 的 16kHz 16bit 单声道音频）
 const FRAME_SIZE: usize = 1280;
 
-/// 简单的线性插值重采样
-///
-/// 将音频从源采样率转换到目标采样率
-fn resample(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
-    if from_rate == to_rate {
-        return samples.to_vec();
-    }
+/// 默认接口地址
+const DEFAULT_ENDPOINT: &str = "wss://iat-api.xfyun.cn/v2/iat";
 
-    let ratio = from_rate as f64 / to_rate as f64;
-    let new_len = (samples.len() as f64 / ratio) as usize;
-    let mut result = Vec::with_capacity(new_len);
-
-    for i in 0..new_len {
-        let src_idx = i as f64 * ratio;
-        let idx_floor = src_idx.floor() as usize;
-        let idx_ceil = (idx_floor + 1).min(samples.len() - 1);
-        let frac = src_idx - idx_floor as f64;
-
-        // 线性插值
-        let sample = if idx_floor < samples.len() {
-            let s1 = samples[idx_floor] as f64;
-            let s2 = samples[idx_ceil] as f64;
-            (s1 + (s2 - s1) * frac) as i16
-        } else {
-            0
-        };
+/// 鉴权签名使用的主机名和路径
+const AUTH_HOST: &str = "iat-api.xfyun.cn";
+const AUTH_PATH: &str = "/v2/iat";
 
-        result.push(sample);
-    }
+/// 词起始时间的单位：帧（10ms）
+const WORD_FRAME_SECS: f32 = 0.01;
 
-    result
-}
+/// 句末标点，按此切分分段
+const SENTENCE_END: &[char] = &['。', '！', '？', '.', '!', '?'];
+
+/// 单个热词的长度范围（字符），超出的热词服务端会拒绝整个请求
+const HOTWORD_CHARS: std::ops::RangeInclusive<usize> = 2..=16;
+
+/// 会话级热词个数上限
+const MAX_HOTWORDS: usize = 300;
 
 /// 讯飞客户端
 pub struct XunfeiClient {
@@ -69,9 +73,25 @@ pub struct XunfeiClient {
     api_key: String,
     api_secret: String,
     language: String,
+    endpoint: String,
+    hotwords: Vec<String>,
+    timeouts: AsrTimeouts,
 }
 
 impl XunfeiClient {
+    /// 讯飞听写的能力
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: true,
+        timestamps: true,
+        hotwords: true,
+        punctuation: true,
+        code_switching: true,
+        diarization: false,
+        max_duration_secs: Some(REALTIME_MAX_SECS),
+        formats: &[AudioFormat::Pcm],
+        sample_rates: &[16000],
+    };
+
     /// 创建新的客户端
     pub fn new(app_id: String, api_key: String, api_secret: String) -> Self {
         Self {
@@ -79,6 +99,9 @@ impl XunfeiClient {
             api_key,
             api_secret,
             language: "zh_cn".to_string(),
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            hotwords: Vec::new(),
+            timeouts: AsrTimeouts::default(),
         }
     }
 
@@ -88,6 +111,34 @@ impl XunfeiClient {
         self
     }
 
+    /// 设置接口地址（用于测试），鉴权参数仍按默认主机签名
+    pub fn with_endpoint(mut self, endpoint: String) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+
+    /// 设置超时：连接、每帧音频的发送、尾帧发送后等待最终结果
+    pub fn with_timeouts(mut self, timeouts: AsrTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 建立 WebSocket 连接
+    async fn connect(&self, url: &str) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
+        let (ws_stream, response) = within(self.timeouts.connect(), "WebSocket 连接", async {
+            connect_async(url).await.map_err(|e| {
+                tracing::error!("讯飞 WebSocket 连接失败: {:?}", e);
+                VoiceError::NetworkError(format!("WebSocket 连接失败: {}", e))
+            })
+        })
+        .await?;
+        tracing::info!(
+            "讯飞 WebSocket 连接成功，HTTP 状态: {:?}",
+            response.status()
+        );
+        Ok(ws_stream)
+    }
+
     /// 生成鉴权 URL
     ///
     /// 讯飞 WebSocket 鉴权使用 URL 参数传递，包含：
@@ -95,8 +146,8 @@ impl XunfeiClient {
     /// - date: RFC1123 格式的时间戳
     /// - host: 主机名
     fn generate_auth_url(&self) -> Result<String> {
-        let host = "iat-api.xfyun.cn";
-        let path = "/v2/iat";
+        let host = AUTH_HOST;
+        let path = AUTH_PATH;
         let date = Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
 
         tracing::debug!("讯飞鉴权 - date: {}", date);
@@ -125,9 +176,8 @@ impl XunfeiClient {
 
         // 构建 URL
         let url = format!(
-            "wss://{}{}?authorization={}&date={}&host={}",
-            host,
-            path,
+            "{}?authorization={}&date={}&host={}",
+            self.endpoint,
             urlencoding::encode(&authorization),
             urlencoding::encode(&date),
             urlencoding::encode(host)
@@ -149,6 +199,7 @@ impl XunfeiClient {
                 vad_eos: 3000,                 // 静音检测时间（毫秒）
                 dwa: Some("wpgs".to_string()), // 动态修正
                 ptt: Some(1),                  // 添加标点
+                dhw: dynamic_hotwords(&self.hotwords),
             }),
             data: XunfeiData {
                 status: 0, // 首帧
@@ -196,89 +247,284 @@ impl XunfeiClient {
     /// 动态修正说明：
     /// - pgs="apd": 追加到之前的结果
     /// - pgs="rpl": 替换之前的部分结果，替换范围由 rg 字段指定
-    fn parse_result(responses: &[XunfeiResponse]) -> TranscribeResult {
-        // 使用 HashMap 存储每个 sn 对应的文本，支持动态修正替换
-        let mut sn_texts: std::collections::HashMap<i32, String> = std::collections::HashMap::new();
-        let mut max_sn: i32 = 0;
-
-        for resp in responses {
-            if let Some(ref data) = resp.data {
-                if let Some(ref result) = data.result {
-                    let sn = result.sn.unwrap_or(0);
-                    max_sn = max_sn.max(sn);
-
-                    // 提取当前结果的文本
-                    let mut current_text = String::new();
-                    for ws in &result.ws {
-                        for cw in &ws.cw {
-                            current_text.push_str(&cw.w);
-                        }
-                    }
-
-                    // 根据 pgs 字段处理
-                    match result.pgs.as_deref() {
-                        Some("rpl") => {
-                            // 替换模式：删除 rg 范围内的结果，然后添加当前结果
-                            if let Some(ref rg) = result.rg {
-                                if rg.len() >= 2 {
-                                    let start = rg[0];
-                                    let end = rg[1];
-                                    // 删除 [start, end] 范围内的所有 sn
-                                    for i in start..=end {
-                                        sn_texts.remove(&i);
-                                    }
-                                    tracing::debug!(
-                                        "动态修正替换: sn={}, rg=[{}, {}], text={}",
-                                        sn,
-                                        start,
-                                        end,
-                                        current_text
-                                    );
-                                }
-                            }
-                            sn_texts.insert(sn, current_text);
-                        }
-                        Some("apd") | None => {
-                            // 追加模式或无标识：直接添加
-                            sn_texts.insert(sn, current_text);
-                        }
-                        _ => {
-                            // 其他情况也直接添加
-                            sn_texts.insert(sn, current_text);
-                        }
-                    }
+    ///
+    /// `duration_secs` 为已发送音频的时长，作为最后一个分段的结束时间
+    fn parse_result(responses: &[XunfeiResponse], duration_secs: Option<f32>) -> TranscribeResult {
+        // 按 sn 保存每帧结果的词，动态修正时替换之前的结果
+        let mut results: BTreeMap<i32, &[XunfeiWord]> = BTreeMap::new();
+        let frames = responses
+            .iter()
+            .filter_map(|resp| resp.data.as_ref()?.result.as_ref());
+        for result in frames {
+            let sn = result.sn.unwrap_or(0);
+            if result.pgs.as_deref() == Some("rpl") {
+                if let Some(&[start, end, ..]) = result.rg.as_deref() {
+                    results.retain(|sn, _| *sn < start || *sn > end);
+                    tracing::debug!("动态修正替换: sn={}, rg=[{}, {}]", sn, start, end);
                 }
             }
+            results.insert(sn, &result.ws);
+        }
+
+        let words: Vec<&XunfeiWord> = results
+            .values()
+            .flat_map(|ws| ws.iter())
+            .filter(|word| !word.text().is_empty())
+            .collect();
+        let full_text: String = words.iter().map(|word| word.text()).collect();
+
+        // 服务端返回分数时取平均值作为置信度
+        let scores: Vec<f32> = words
+            .iter()
+            .filter_map(|word| word.cw.first()?.sc)
+            .filter(|sc| *sc > 0.0)
+            .collect();
+        let confidence = (!scores.is_empty())
+            .then(|| (scores.iter().sum::<f32>() / scores.len() as f32).clamp(0.0, 1.0));
+
+        TranscribeResult {
+            text: full_text,
+            language: Some("zh".to_string()),
+            confidence,
+            segments: build_segments(&words, duration_secs),
+            backend: None,
+        }
+    }
+
+    /// 等待接收任务结束，检查错误并解析最终结果
+    ///
+    /// `duration_secs` 为已发送音频的时长
+    async fn finish(
+        &self,
+        receive_task: AbortOnDropHandle<Vec<XunfeiResponse>>,
+        send_error: Option<VoiceError>,
+        duration_secs: f32,
+    ) -> Result<TranscribeResult> {
+        // 等待接收任务完成（设置超时）
+        let receive = async {
+            receive_task
+                .await
+                .map_err(|e| VoiceError::AsrError(format!("接收任务失败: {}", e)))
+        };
+        let responses = within(
+            self.timeouts.receive_or(DEFAULT_RECEIVE_TIMEOUT),
+            "等待识别结果",
+            receive,
+        )
+        .await?;
+
+        // 如果发送过程中有错误，但仍然收到了响应，则检查响应
+        if let Some(err) = send_error {
+            if responses.is_empty() {
+                return Err(err);
+            }
+            tracing::warn!("发送过程中出现错误，但仍收到 {} 个响应", responses.len());
         }
 
-        // 按 sn 顺序拼接最终文本
-        let mut full_text = String::new();
-        let mut sorted_sns: Vec<i32> = sn_texts.keys().cloned().collect();
-        sorted_sns.sort();
+        // 检查响应中是否有错误
+        for response in &responses {
+            if response.code != 0 {
+                return Err(VoiceError::AsrError(format!(
+                    "讯飞 ASR 错误 [{}]: {}",
+                    response.code,
+                    response.message.clone().unwrap_or_default()
+                )));
+            }
+        }
 
-        for sn in sorted_sns {
-            if let Some(text) = sn_texts.get(&sn) {
-                full_text.push_str(text);
+        // 解析最终结果
+        let result = Self::parse_result(&responses, Some(duration_secs));
+        tracing::info!(
+            "讯飞识别完成: {}（{} 个分段）",
+            result.text,
+            result.segments.len()
+        );
+
+        Ok(result)
+    }
+
+    /// 流式识别会话：录音过程中持续发送音频
+    ///
+    /// `frames` 为录音处理线程转发的单声道采样，发送端关闭即视为录音结束，
+    /// 此时发送尾帧并等待最终结果。每次收到中间结果时把当前完整文本发给 `partials`。
+    /// 讯飞听写单次会话最长 60 秒，超出部分会被服务端忽略。
+    async fn stream_session(
+        &self,
+        mut frames: UnboundedReceiver<Vec<i16>>,
+        sample_rate: u32,
+        partials: UnboundedSender<String>,
+    ) -> Result<TranscribeResult> {
+        let url = self.generate_auth_url()?;
+        tracing::info!("正在连接讯飞 WebSocket（流式）...");
+        let ws_stream = self.connect(&url).await?;
+
+        let (mut write, read) = ws_stream.split();
+        let receive_task =
+            AbortOnDropHandle::new(tokio::spawn(receive_responses(read, Some(partials))));
+
+        let mut pending: Vec<u8> = Vec::new();
+        let mut first = true;
+        let mut sent_frames = 0usize;
+        let mut audio_bytes = 0usize;
+        let mut send_error: Option<VoiceError> = None;
+
+        'recv: while let Some(samples) = frames.recv().await {
+            let samples_16k = resample(&samples, sample_rate, 16000);
+            audio_bytes += samples_16k.len() * 2;
+            pending.extend(samples_16k.iter().flat_map(|s| s.to_le_bytes()));
+
+            while pending.len() >= FRAME_SIZE {
+                let chunk: Vec<u8> = pending.drain(..FRAME_SIZE).collect();
+                let request = if first {
+                    self.build_first_frame(&chunk)
+                } else {
+                    self.build_continue_frame(&chunk)
+                };
+                first = false;
+                if let Err(e) = send_request(&mut write, &request, self.timeouts.send()).await {
+                    tracing::error!("发送第 {} 帧失败: {}", sent_frames, e);
+                    send_error = Some(e);
+                    break 'recv;
+                }
+                sent_frames += 1;
             }
         }
 
-        let mut segments = Vec::new();
-        // 如果有文本，创建一个整体的 segment
-        if !full_text.is_empty() {
+        // 录音结束：发送剩余数据和尾帧（尚未发送首帧时先补发首帧）
+        if send_error.is_none() {
+            let mut tail = Vec::new();
+            if first {
+                tail.push(self.build_first_frame(&pending));
+                tail.push(self.build_last_frame(&[]));
+            } else {
+                tail.push(self.build_last_frame(&pending));
+            }
+            for request in &tail {
+                if let Err(e) = send_request(&mut write, request, self.timeouts.send()).await {
+                    send_error = Some(e);
+                    break;
+                }
+            }
+            tracing::info!("流式发送完成，共 {} 帧", sent_frames + tail.len());
+        }
+
+        // 16kHz 16-bit 单声道
+        let duration_secs = audio_bytes as f32 / 32000.0;
+        self.finish(receive_task, send_error, duration_secs).await
+    }
+}
+
+/// 序列化并发送一帧请求，超过 `timeout` 未发送完返回网络错误
+async fn send_request<W>(write: &mut W, request: &XunfeiRequest, timeout: Duration) -> Result<()>
+where
+    W: Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+{
+    let json = serde_json::to_string(request)
+        .map_err(|e| VoiceError::AsrError(format!("序列化请求失败: {}", e)))?;
+    within(timeout, "发送数据", async {
+        write
+            .send(Message::Text(json))
+            .await
+            .map_err(|e| VoiceError::NetworkError(format!("发送数据失败: {}", e)))
+    })
+    .await
+}
+
+/// 按句末标点把词切分为分段
+///
+/// 分段从第一个词的起始时间开始，到下一个分段开始时结束；最后一个分段到音频结束
+/// （未知时为最后一个词的起始时间）
+fn build_segments(words: &[&XunfeiWord], duration_secs: Option<f32>) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = Vec::new();
+    let mut open = false;
+    for word in words {
+        let start = word.bg as f32 * WORD_FRAME_SECS;
+        if !open {
             segments.push(Segment {
-                start: 0.0,
-                end: 0.0, // 讯飞不返回时间戳
-                text: full_text.clone(),
+                start,
+                end: start,
+                text: String::new(),
+                speaker: None,
             });
+            open = true;
         }
+        if let Some(segment) = segments.last_mut() {
+            segment.text.push_str(word.text());
+            segment.end = segment.end.max(start);
+            open = !word.text().ends_with(SENTENCE_END);
+        }
+    }
 
-        TranscribeResult {
-            text: full_text,
-            language: Some("zh".to_string()),
-            confidence: None,
-            segments,
+    let count = segments.len();
+    for i in 0..count {
+        let next_start = match segments.get(i + 1) {
+            Some(next) => Some(next.start),
+            None => duration_secs,
+        };
+        let segment = &mut segments[i];
+        if let Some(end) = next_start {
+            segment.end = segment.end.max(end);
         }
     }
+    segments
+}
+
+/// 接收识别结果，直到收到最终结果或连接关闭
+async fn receive_responses<S>(
+    mut read: S,
+    partials: Option<UnboundedSender<String>>,
+) -> Vec<XunfeiResponse>
+where
+    S: Stream<Item = std::result::Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+{
+    let mut responses: Vec<XunfeiResponse> = Vec::new();
+
+    while let Some(msg) = read.next().await {
+        match msg {
+            Ok(Message::Text(text)) => {
+                tracing::debug!("收到讯飞响应: {}", text);
+
+                match serde_json::from_str::<XunfeiResponse>(&text) {
+                    Ok(response) => {
+                        // 检查是否是最后一帧
+                        let is_last = response
+                            .data
+                            .as_ref()
+                            .map(|d| d.status == 2)
+                            .unwrap_or(false);
+
+                        responses.push(response);
+
+                        if is_last {
+                            tracing::info!("收到最终识别结果");
+                            break;
+                        }
+                        if let Some(partials) = &partials {
+                            let partial = XunfeiClient::parse_result(&responses, None);
+                            let _ = partials.send(partial.text);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("解析响应失败: {}", e);
+                    }
+                }
+            }
+            Ok(Message::Close(frame)) => {
+                tracing::info!("WebSocket 连接关闭: {:?}", frame);
+                break;
+            }
+            Ok(Message::Ping(_)) => {
+                tracing::debug!("收到 Ping");
+            }
+            Err(e) => {
+                tracing::error!("接收数据失败: {}", e);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    responses
 }
 
 #[async_trait]
@@ -290,45 +536,24 @@ impl AsrClient for XunfeiClient {
 
         // 建立 WebSocket 连接
         tracing::info!("正在连接讯飞 WebSocket...");
-        let (ws_stream, response) = connect_async(&url).await.map_err(|e| {
-            tracing::error!("讯飞 WebSocket 连接失败: {:?}", e);
-            VoiceError::NetworkError(format!("WebSocket 连接失败: {}", e))
-        })?;
+        let ws_stream = self.connect(&url).await?;
 
-        tracing::info!(
-            "讯飞 WebSocket 连接成功，HTTP 状态: {:?}",
-            response.status()
-        );
+        let (mut write, read) = ws_stream.split();
 
-        let (mut write, mut read) = ws_stream.split();
-
-        // 如果采样率不是 16000，需要重采样
-        let samples_16k = if audio.sample_rate != 16000 {
-            tracing::info!(
-                "重采样: {}Hz -> 16000Hz (原始样本数: {})",
-                audio.sample_rate,
-                audio.samples.len()
-            );
-            resample(&audio.samples, audio.sample_rate, 16000)
-        } else {
-            audio.samples.clone()
-        };
-
-        tracing::info!("重采样后样本数: {}", samples_16k.len());
-
-        // 检查重采样后的音频数据是否有效
-        let non_zero_count = samples_16k.iter().filter(|&&s| s != 0).count();
-        let max_sample = samples_16k.iter().map(|&s| s.abs()).max().unwrap_or(0);
+        // 检查音频数据是否有效
+        let non_zero_count = audio.samples.iter().filter(|&&s| s != 0).count();
+        let max_sample = audio.samples.iter().map(|&s| s.abs()).max().unwrap_or(0);
         tracing::info!(
-            "重采样后音频检查: 非零样本={}/{} ({:.1}%), 最大振幅={}",
+            "音频检查: {}Hz, 非零样本={}/{} ({:.1}%), 最大振幅={}",
+            audio.sample_rate,
             non_zero_count,
-            samples_16k.len(),
-            non_zero_count as f64 / samples_16k.len().max(1) as f64 * 100.0,
+            audio.samples.len(),
+            non_zero_count as f64 / audio.samples.len().max(1) as f64 * 100.0,
             max_sample
         );
 
-        // 将音频数据转换为字节（16-bit PCM）
-        let audio_bytes: Vec<u8> = samples_16k.iter().flat_map(|s| s.to_le_bytes()).collect();
+        // 按协商结果编码为 16kHz 16-bit 裸 PCM
+        let audio_bytes = Self::CAPABILITIES.encode(audio)?.bytes;
 
         // 分帧发送音频数据
         let chunks: Vec<&[u8]> = audio_bytes.chunks(FRAME_SIZE).collect();
@@ -341,52 +566,8 @@ impl AsrClient for XunfeiClient {
         );
 
         // 启动接收任务
-        let receive_task = tokio::spawn(async move {
-            let mut responses: Vec<XunfeiResponse> = Vec::new();
-
-            while let Some(msg) = read.next().await {
-                match msg {
-                    Ok(Message::Text(text)) => {
-                        tracing::debug!("收到讯飞响应: {}", text);
-
-                        match serde_json::from_str::<XunfeiResponse>(&text) {
-                            Ok(response) => {
-                                // 检查是否是最后一帧
-                                let is_last = response
-                                    .data
-                                    .as_ref()
-                                    .map(|d| d.status == 2)
-                                    .unwrap_or(false);
-
-                                responses.push(response);
-
-                                if is_last {
-                                    tracing::info!("收到最终识别结果");
-                                    break;
-                                }
-                            }
-                            Err(e) => {
-                                tracing::error!("解析响应失败: {}", e);
-                            }
-                        }
-                    }
-                    Ok(Message::Close(frame)) => {
-                        tracing::info!("WebSocket 连接关闭: {:?}", frame);
-                        break;
-                    }
-                    Ok(Message::Ping(_)) => {
-                        tracing::debug!("收到 Ping");
-                    }
-                    Err(e) => {
-                        tracing::error!("接收数据失败: {}", e);
-                        break;
-                    }
-                    _ => {}
-                }
-            }
-
-            responses
-        });
+        // 识别被取消（future 被丢弃）时接收任务随之中止，不在后台等到超时
+        let receive_task = AbortOnDropHandle::new(tokio::spawn(receive_responses(read, None)));
 
         // 发送音频数据
         let mut send_error: Option<VoiceError> = None;
@@ -403,16 +584,8 @@ impl AsrClient for XunfeiClient {
                 self.build_continue_frame(chunk)
             };
 
-            let json = match serde_json::to_string(&request) {
-                Ok(j) => j,
-                Err(e) => {
-                    send_error = Some(VoiceError::AsrError(format!("序列化请求失败: {}", e)));
-                    break;
-                }
-            };
-
             // 发送数据，如果失败则记录错误但继续尝试
-            match write.send(Message::Text(json)).await {
+            match send_request(&mut write, &request, self.timeouts.send()).await {
                 Ok(_) => {
                     if i == 0 {
                         tracing::debug!("首帧发送成功");
@@ -422,7 +595,7 @@ impl AsrClient for XunfeiClient {
                 }
                 Err(e) => {
                     tracing::error!("发送第 {} 帧失败: {}", i, e);
-                    send_error = Some(VoiceError::NetworkError(format!("发送数据失败: {}", e)));
+                    send_error = Some(e);
                     break;
                 }
             }
@@ -435,47 +608,62 @@ impl AsrClient for XunfeiClient {
             }
         }
 
-        // 等待接收任务完成（设置超时）
-        let responses =
-            match tokio::time::timeout(tokio::time::Duration::from_secs(30), receive_task).await {
-                Ok(Ok(responses)) => responses,
-                Ok(Err(e)) => {
-                    return Err(VoiceError::AsrError(format!("接收任务失败: {}", e)));
-                }
-                Err(_) => {
-                    return Err(VoiceError::AsrError("等待识别结果超时".to_string()));
-                }
-            };
+        self.finish(receive_task, send_error, audio.duration_secs)
+            .await
+    }
 
-        // 如果发送过程中有错误，但仍然收到了响应，则检查响应
-        if let Some(err) = send_error {
-            if responses.is_empty() {
-                return Err(err);
-            }
-            tracing::warn!("发送过程中出现错误，但仍收到 {} 个响应", responses.len());
-        }
+    fn transcribe_stream<'a>(
+        &'a self,
+        frames: UnboundedReceiver<Vec<i16>>,
+        sample_rate: u32,
+    ) -> BoxStream<'a, Result<PartialTranscript>> {
+        let (partials, rx) = mpsc::unbounded_channel();
+        let session = Box::pin(self.stream_session(frames, sample_rate, partials));
+
+        // 会话结束前持续转发中间结果，结束后产出最终结果并结束流
+        Box::pin(stream::unfold(
+            (Some(session), rx),
+            |(session, mut rx)| async move {
+                let mut session = session?;
+                tokio::select! {
+                    biased;
+                    Some(text) = rx.recv() => {
+                        Some((Ok(PartialTranscript::partial(text)), (Some(session), rx)))
+                    }
+                    result = &mut session => {
+                        Some((result.map(PartialTranscript::finished), (None, rx)))
+                    }
+                }
+            },
+        ))
+    }
 
-        // 检查响应中是否有错误
-        for response in &responses {
-            if response.code != 0 {
-                return Err(VoiceError::AsrError(format!(
-                    "讯飞 ASR 错误 [{}]: {}",
-                    response.code,
-                    response.message.clone().unwrap_or_default()
-                )));
-            }
-        }
+    fn name(&self) -> &'static str {
+        "讯飞语音"
+    }
 
-        // 解析最终结果
-        let result = Self::parse_result(&responses);
-        tracing::info!("讯飞识别完成: {}", result.text);
+    fn capabilities(&self) -> AsrCapabilities {
+        Self::CAPABILITIES
+    }
 
-        Ok(result)
+    fn with_hotwords(mut self, hotwords: Vec<String>) -> Self {
+        self.hotwords = hotwords;
+        self
     }
+}
 
-    fn name(&self) -> &'static str {
-        "讯飞语音"
+/// 动态热词参数 `utf-8;热词1|热词2`，跳过长度不符或含分隔符的热词，没有可用热词时为空
+fn dynamic_hotwords(hotwords: &[String]) -> Option<String> {
+    let words: Vec<&str> = hotwords
+        .iter()
+        .map(|w| w.trim())
+        .filter(|w| HOTWORD_CHARS.contains(&w.chars().count()) && !w.contains(['|', ';']))
+        .take(MAX_HOTWORDS)
+        .collect();
+    if words.is_empty() {
+        return None;
     }
+    Some(format!("utf-8;{}", words.join("|")))
 }
 
 // ============================================================================
@@ -518,6 +706,9 @@ struct XunfeiBusiness {
     /// 是否添加标点（1: 添加）
     #[serde(skip_serializing_if = "Option::is_none")]
     ptt: Option<u8>,
+    /// 会话级动态热词（utf-8;热词1|热词2）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dhw: Option<String>,
 }
 
 /// 数据参数
@@ -575,13 +766,26 @@ struct XunfeiResult {
 /// 词
 #[derive(Debug, Deserialize)]
 struct XunfeiWord {
+    /// 起始的端点帧偏移（1 帧 = 10ms）
+    #[serde(default)]
+    bg: u32,
     /// 候选词列表
     cw: Vec<XunfeiCandidate>,
 }
 
+impl XunfeiWord {
+    /// 首个候选词的内容
+    fn text(&self) -> &str {
+        self.cw.first().map(|cw| cw.w.as_str()).unwrap_or_default()
+    }
+}
+
 /// 候选词
 #[derive(Debug, Deserialize)]
 struct XunfeiCandidate {
     /// 词内容
     w: String,
+    /// 分数（0-1，部分服务端不返回或固定为 0）
+    #[serde(default)]
+    sc: Option<f32>,
 }
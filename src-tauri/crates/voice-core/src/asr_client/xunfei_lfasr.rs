@@ -0,0 +1,369 @@
+//! 讯飞录音文件转写客户端（lfasr）
+//!
+//! 实时听写接口单次最长 60 秒，超过时改用录音文件转写 API：
+//! 1. 上传整段音频（FLAC 压缩），获得订单 ID
+//! 2. 轮询转写结果，直到订单完成或失败
+//!
+//! 录音文件转写使用独立的 SecretKey（控制台"录音文件转写"页面），与实时听写的
+//! APISecret 不同。
+//!
+//! ## 参考文档
+//! https://www.xfyun.cn/doc/asr/ifasr_new/API.html
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha1::Sha1;
+
+use super::timeouts::{AsrTimeouts, DEFAULT_RECEIVE_TIMEOUT};
+use super::{AsrCapabilities, AsrClient, AudioFormat};
+use crate::error::{Result, VoiceError};
+use crate::types::{AudioData, Segment, TranscribeResult};
+
+const API_BASE: &str = "https://raasr.xfyun.cn/v2/api";
+/// 接口成功返回码
+const CODE_SUCCESS: &str = "000000";
+/// 订单状态：转写完成
+const STATUS_DONE: i32 = 4;
+/// 订单状态：转写失败
+const STATUS_FAILED: i32 = -1;
+/// 轮询间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+/// 默认最长等待时间
+const POLL_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// 讯飞录音文件转写客户端
+pub struct XunfeiLfasrClient {
+    app_id: String,
+    secret_key: String,
+    language: String,
+    http: reqwest::Client,
+    timeouts: AsrTimeouts,
+}
+
+impl XunfeiLfasrClient {
+    /// 讯飞录音文件转写的能力（单个文件最长 5 小时）
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: false,
+        timestamps: true,
+        hotwords: false,
+        punctuation: true,
+        code_switching: true,
+        diarization: false,
+        max_duration_secs: Some(5.0 * 3600.0),
+        formats: &[
+            AudioFormat::Flac,
+            AudioFormat::M4a,
+            AudioFormat::Ogg,
+            AudioFormat::Mp3,
+            AudioFormat::Wav,
+        ],
+        sample_rates: &[],
+    };
+
+    /// 创建新的客户端
+    pub fn new(app_id: String, secret_key: String) -> Self {
+        Self {
+            app_id,
+            secret_key,
+            language: "cn".to_string(),
+            http: AsrTimeouts::default().http_client(),
+            timeouts: AsrTimeouts::default(),
+        }
+    }
+
+    /// 设置语言（cn: 中文，en: 英文）
+    pub fn with_language(mut self, language: String) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// 设置超时：连接和发送超时用于上传和每次查询，接收超时为等待转写完成的最长时间
+    pub fn with_timeouts(mut self, timeouts: AsrTimeouts) -> Self {
+        self.http = timeouts.http_client();
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 生成签名：Base64(HmacSHA1(MD5(appId + ts), secretKey))
+    fn signa(&self, ts: i64) -> Result<String> {
+        let digest = format!("{:x}", md5::compute(format!("{}{}", self.app_id, ts)));
+        let mut mac = Hmac::<Sha1>::new_from_slice(self.secret_key.as_bytes())
+            .map_err(|e| VoiceError::AsrAuthError(e.to_string()))?;
+        mac.update(digest.as_bytes());
+        Ok(BASE64.encode(mac.finalize().into_bytes()))
+    }
+
+    /// 公共鉴权参数
+    fn auth_query(&self) -> Result<Vec<(&'static str, String)>> {
+        let ts = chrono::Utc::now().timestamp();
+        Ok(vec![
+            ("appId", self.app_id.clone()),
+            ("ts", ts.to_string()),
+            ("signa", self.signa(ts)?),
+        ])
+    }
+
+    /// 上传音频，返回订单 ID
+    async fn upload(&self, audio: &AudioData) -> Result<String> {
+        let encoded = Self::CAPABILITIES.encode(audio)?;
+        let mut query = self.auth_query()?;
+        query.extend([
+            ("fileName", encoded.file_name("proxycast")),
+            ("fileSize", encoded.bytes.len().to_string()),
+            ("duration", (audio.duration_secs.ceil() as u64).to_string()),
+            ("language", self.language.clone()),
+        ]);
+
+        let response: LfasrResponse<UploadContent> = self
+            .http
+            .post(format!("{}/upload", API_BASE))
+            .query(&query)
+            .header("Content-Type", "application/octet-stream")
+            .body(encoded.bytes)
+            .timeout(self.timeouts.request_timeout(DEFAULT_RECEIVE_TIMEOUT))
+            .send()
+            .await
+            .map_err(|e| VoiceError::NetworkError(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| VoiceError::AsrError(format!("解析上传响应失败: {}", e)))?;
+
+        let content = response.into_content()?;
+        tracing::info!(
+            "讯飞录音文件转写上传成功，订单: {}，预计耗时: {}ms",
+            content.order_id,
+            content.task_estimate_time.unwrap_or_default()
+        );
+        Ok(content.order_id)
+    }
+
+    /// 轮询转写结果
+    async fn poll_result(&self, order_id: &str) -> Result<String> {
+        let started = std::time::Instant::now();
+        loop {
+            let mut query = self.auth_query()?;
+            query.extend([
+                ("orderId", order_id.to_string()),
+                ("resultType", "transfer".to_string()),
+            ]);
+
+            let response: LfasrResponse<ResultContent> = self
+                .http
+                .post(format!("{}/getResult", API_BASE))
+                .query(&query)
+                .timeout(self.timeouts.request_timeout(DEFAULT_RECEIVE_TIMEOUT))
+                .send()
+                .await
+                .map_err(|e| VoiceError::NetworkError(e.to_string()))?
+                .json()
+                .await
+                .map_err(|e| VoiceError::AsrError(format!("解析转写结果失败: {}", e)))?;
+
+            let content = response.into_content()?;
+            match content.order_info.status {
+                STATUS_DONE => return Ok(content.order_result.unwrap_or_default()),
+                STATUS_FAILED => {
+                    return Err(VoiceError::AsrError(format!(
+                        "讯飞录音文件转写失败，错误码: {}",
+                        content.order_info.fail_type.unwrap_or_default()
+                    )));
+                }
+                status => {
+                    tracing::debug!(
+                        "讯飞录音文件转写处理中，订单: {}，状态: {}",
+                        order_id,
+                        status
+                    );
+                }
+            }
+
+            if started.elapsed() >= self.timeouts.receive_or(POLL_TIMEOUT) {
+                return Err(VoiceError::AsrError("等待讯飞转写结果超时".to_string()));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// 解析 orderResult（嵌套的 JSON 字符串）
+    fn parse_order_result(order_result: &str) -> Result<TranscribeResult> {
+        if order_result.is_empty() {
+            return Ok(TranscribeResult {
+                text: String::new(),
+                language: None,
+                confidence: None,
+                segments: vec![],
+                backend: None,
+            });
+        }
+
+        let result: OrderResult = serde_json::from_str(order_result)
+            .map_err(|e| VoiceError::AsrError(format!("解析转写结果失败: {}", e)))?;
+
+        let mut segments = Vec::new();
+        for lattice in result.lattice {
+            let sentence: Json1Best = serde_json::from_str(&lattice.json_1best)
+                .map_err(|e| VoiceError::AsrError(format!("解析转写句子失败: {}", e)))?;
+            let text: String = sentence
+                .st
+                .rt
+                .iter()
+                .flat_map(|rt| &rt.ws)
+                .filter_map(|ws| ws.cw.first())
+                .map(|cw| cw.w.as_str())
+                .collect();
+            if text.is_empty() {
+                continue;
+            }
+            segments.push(Segment {
+                start: parse_ms(&sentence.st.bg),
+                end: parse_ms(&sentence.st.ed),
+                text,
+                speaker: None,
+            });
+        }
+
+        Ok(TranscribeResult {
+            text: segments.iter().map(|s| s.text.as_str()).collect(),
+            language: None,
+            confidence: None,
+            segments,
+            backend: None,
+        })
+    }
+}
+
+/// 毫秒字符串转换为秒
+fn parse_ms(value: &str) -> f32 {
+    value.parse::<f32>().unwrap_or(0.0) / 1000.0
+}
+
+#[async_trait]
+impl AsrClient for XunfeiLfasrClient {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        let order_id = self.upload(audio).await?;
+        let order_result = self.poll_result(&order_id).await?;
+        let mut result = Self::parse_order_result(&order_result)?;
+        result.language = Some(self.language.clone());
+        tracing::info!("讯飞录音文件转写完成: {} 字符", result.text.chars().count());
+        Ok(result)
+    }
+
+    fn name(&self) -> &'static str {
+        "讯飞录音文件转写"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        Self::CAPABILITIES
+    }
+}
+
+// ============================================================================
+// 讯飞录音文件转写协议数据结构
+// ============================================================================
+
+/// 接口响应
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LfasrResponse<T> {
+    /// 返回码（000000 表示成功）
+    code: String,
+    /// 描述信息
+    desc_info: Option<String>,
+    /// 内容
+    content: Option<T>,
+}
+
+impl<T> LfasrResponse<T> {
+    fn into_content(self) -> Result<T> {
+        if self.code != CODE_SUCCESS {
+            return Err(VoiceError::AsrError(format!(
+                "讯飞录音文件转写错误 [{}]: {}",
+                self.code,
+                self.desc_info.unwrap_or_default()
+            )));
+        }
+        self.content
+            .ok_or_else(|| VoiceError::AsrError("讯飞录音文件转写响应缺少内容".to_string()))
+    }
+}
+
+/// 上传结果
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadContent {
+    /// 订单 ID
+    order_id: String,
+    /// 预计耗时（毫秒）
+    task_estimate_time: Option<u64>,
+}
+
+/// 查询结果
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResultContent {
+    /// 订单信息
+    order_info: OrderInfo,
+    /// 转写结果（JSON 字符串）
+    order_result: Option<String>,
+}
+
+/// 订单信息
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderInfo {
+    /// 订单状态（0: 已创建，3: 处理中，4: 已完成，-1: 失败）
+    status: i32,
+    /// 失败类型
+    fail_type: Option<i32>,
+}
+
+/// 转写结果
+#[derive(Debug, Deserialize)]
+struct OrderResult {
+    #[serde(default)]
+    lattice: Vec<Lattice>,
+}
+
+/// 句子
+#[derive(Debug, Deserialize)]
+struct Lattice {
+    /// 句子结果（JSON 字符串）
+    json_1best: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Json1Best {
+    st: Sentence,
+}
+
+/// 句子详情
+#[derive(Debug, Deserialize)]
+struct Sentence {
+    /// 开始时间（毫秒）
+    bg: String,
+    /// 结束时间（毫秒）
+    ed: String,
+    #[serde(default)]
+    rt: Vec<SentenceResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentenceResult {
+    #[serde(default)]
+    ws: Vec<Word>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Word {
+    #[serde(default)]
+    cw: Vec<Candidate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Candidate {
+    w: String,
+}
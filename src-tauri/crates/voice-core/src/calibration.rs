@@ -0,0 +1,139 @@
+//! 麦克风电平校准
+//!
+//! 校准分两段录音：先录约 2 秒环境噪声，再录一段正常说话。
+//! 按 20ms 分帧测量电平，由噪声底和语音电平推算建议增益与静音（VAD）阈值。
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VoiceError};
+use crate::level::{AudioLevel, MIN_DBFS};
+
+/// 环境噪声录音时长（毫秒）
+pub const AMBIENT_DURATION_MS: u64 = 2000;
+/// 分帧时长（毫秒）
+const FRAME_MS: u32 = 20;
+/// 期望的语音 RMS 电平（dBFS），留出足够余量避免削波
+pub const TARGET_SPEECH_DBFS: f32 = -20.0;
+/// 建议增益的范围（dB）
+const MIN_GAIN_DB: f32 = -12.0;
+const MAX_GAIN_DB: f32 = 24.0;
+/// 语音帧判定：高于噪声底至少此值
+const SPEECH_MARGIN_DB: f32 = 6.0;
+/// 信噪比低于此值时识别效果会明显下降
+pub const MIN_SNR_DB: f32 = 10.0;
+
+/// 一段校准录音的统计结果
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationSample {
+    /// 帧 RMS 的中位数（dBFS），环境噪声段即噪声底
+    pub median_dbfs: f32,
+    /// 帧 RMS 的 90 分位（dBFS），语音段即说话电平
+    pub loud_dbfs: f32,
+    /// 峰值电平（dBFS）
+    pub peak_dbfs: f32,
+    /// 是否出现削波
+    pub clipping: bool,
+    /// 录音时长（秒）
+    pub duration_secs: f32,
+}
+
+impl CalibrationSample {
+    /// 分析一段单声道 i16 录音
+    pub fn analyze(samples: &[i16], sample_rate: u32) -> Result<Self> {
+        let frame_len = (sample_rate * FRAME_MS / 1000).max(1) as usize;
+        let mut frames: Vec<f32> = samples
+            .chunks(frame_len)
+            .filter(|frame| frame.len() == frame_len)
+            .map(|frame| AudioLevel::measure_i16(frame).rms_dbfs)
+            .collect();
+        if frames.is_empty() {
+            return Err(VoiceError::RecordingTooShort);
+        }
+        frames.sort_by(f32::total_cmp);
+
+        let whole = AudioLevel::measure_i16(samples);
+        Ok(Self {
+            median_dbfs: percentile(&frames, 0.5),
+            loud_dbfs: percentile(&frames, 0.9),
+            peak_dbfs: whole.peak_dbfs,
+            clipping: whole.clipping,
+            duration_secs: samples.len() as f32 / sample_rate.max(1) as f32,
+        })
+    }
+}
+
+/// 已排序序列的分位数
+fn percentile(sorted: &[f32], q: f32) -> f32 {
+    let index = ((sorted.len() - 1) as f32 * q).round() as usize;
+    sorted[index]
+}
+
+/// 校准结果
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationResult {
+    /// 环境噪声底（dBFS）
+    pub noise_floor_dbfs: f32,
+    /// 说话电平（dBFS）
+    pub speech_level_dbfs: f32,
+    /// 信噪比（dB）
+    pub snr_db: f32,
+    /// 建议增益（dB），正数表示放大
+    pub recommended_gain_db: f32,
+    /// 建议的静音阈值（dBFS，已计入建议增益），低于此值视为静音
+    pub silence_threshold_dbfs: f32,
+    /// 说话时是否出现削波
+    pub clipping: bool,
+}
+
+impl CalibrationResult {
+    /// 根据环境噪声和说话两段录音计算校准结果
+    pub fn compute(ambient: &CalibrationSample, speech: &CalibrationSample) -> Result<Self> {
+        let noise_floor = ambient.median_dbfs;
+        let speech_level = speech.loud_dbfs;
+        if speech_level < noise_floor + SPEECH_MARGIN_DB {
+            return Err(VoiceError::CalibrationError(
+                "未检测到明显的说话声音，请靠近麦克风后重试".to_string(),
+            ));
+        }
+
+        // 削波时不再放大，并至少衰减到峰值以下 3dB
+        let mut gain = (TARGET_SPEECH_DBFS - speech_level).clamp(MIN_GAIN_DB, MAX_GAIN_DB);
+        if speech.clipping {
+            gain = gain.min(-3.0);
+        } else {
+            gain = gain.min(-3.0 - speech.peak_dbfs);
+        }
+
+        // 阈值取噪声底与说话电平的中点，但至少高于噪声底 SPEECH_MARGIN_DB
+        let threshold = ((noise_floor + speech_level) / 2.0).max(noise_floor + SPEECH_MARGIN_DB);
+
+        Ok(Self {
+            noise_floor_dbfs: noise_floor,
+            speech_level_dbfs: speech_level,
+            snr_db: speech_level - noise_floor,
+            recommended_gain_db: round_tenth(gain),
+            silence_threshold_dbfs: round_tenth((threshold + gain).clamp(MIN_DBFS, 0.0)),
+            clipping: speech.clipping,
+        })
+    }
+
+    /// 信噪比是否过低
+    pub fn is_noisy(&self) -> bool {
+        self.snr_db < MIN_SNR_DB
+    }
+}
+
+fn round_tenth(value: f32) -> f32 {
+    (value * 10.0).round() / 10.0
+}
+
+/// 按 dB 增益缩放 i16 采样（饱和截断）
+pub fn apply_gain(samples: &mut [i16], gain_db: f32) {
+    if gain_db.abs() < f32::EPSILON {
+        return;
+    }
+    let factor = 10f32.powf(gain_db / 20.0);
+    for sample in samples.iter_mut() {
+        *sample = (*sample as f32 * factor).clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+    }
+}
@@ -0,0 +1,222 @@
+//! 本地推理算力诊断
+//!
+//! 报告 whisper-rs 编译时启用的加速后端（Metal / CUDA）、可用显存，
+//! 并测量实际识别速度（实时率 RTF），帮助选择合适的模型大小。
+//!
+//! 测速优先使用用户现场朗读的录音；未提供时回退到内置的合成音频。合成音频
+//! 不含真实语音，解码器很快结束，测得的实时率偏乐观，结果中会标注 `synthetic`。
+//!
+//! 显存通过系统工具查询：CUDA 使用 `nvidia-smi`；Apple Silicon 的 GPU 与 CPU
+//! 共享内存，报告统一内存总量。查询失败时对应字段为空。
+
+use std::f32::consts::TAU;
+use std::process::Command;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::error::{Result, VoiceError};
+use crate::transcriber::WhisperTranscriber;
+use crate::types::AudioData;
+
+/// 测试音频时长（秒）
+const BENCHMARK_SECS: f32 = 10.0;
+/// 测试音频采样率
+const BENCHMARK_SAMPLE_RATE: u32 = 16000;
+
+/// GPU 信息
+#[derive(Debug, Clone, Serialize)]
+pub struct GpuInfo {
+    /// 设备名称
+    pub name: String,
+    /// 显存总量（MB）
+    pub total_memory_mb: Option<u64>,
+    /// 空闲显存（MB）
+    pub free_memory_mb: Option<u64>,
+    /// 是否与 CPU 共享内存（Apple Silicon）
+    pub unified_memory: bool,
+}
+
+/// 本地推理算力信息
+#[derive(Debug, Clone, Serialize)]
+pub struct ComputeInfo {
+    /// whisper-rs 是否启用了 Metal 加速
+    pub metal: bool,
+    /// whisper-rs 是否启用了 CUDA 加速
+    pub cuda: bool,
+    /// 检测到的 GPU
+    pub gpus: Vec<GpuInfo>,
+    /// CPU 逻辑核心数
+    pub cpu_threads: usize,
+    /// whisper.cpp 报告的系统信息（指令集等）
+    pub system_info: String,
+}
+
+impl ComputeInfo {
+    /// 是否启用了任一 GPU 加速后端
+    pub fn gpu_enabled(&self) -> bool {
+        self.metal || self.cuda
+    }
+}
+
+/// 识别速度测试结果
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkResult {
+    /// 测试音频时长（秒）
+    pub audio_secs: f32,
+    /// 模型加载耗时（秒）
+    pub load_secs: f32,
+    /// 识别耗时（秒）
+    pub elapsed_secs: f32,
+    /// 实时率：识别耗时 / 音频时长，小于 1 表示比实时更快
+    pub rtf: f32,
+    /// 是否使用内置合成音频测得（实时率偏乐观）
+    pub synthetic: bool,
+}
+
+/// 收集本地推理算力信息
+pub fn compute_info() -> ComputeInfo {
+    let metal = cfg!(feature = "metal");
+    let cuda = cfg!(feature = "cuda");
+
+    let mut gpus = query_nvidia_gpus();
+    if gpus.is_empty() {
+        gpus.extend(query_apple_gpu());
+    }
+
+    ComputeInfo {
+        metal,
+        cuda,
+        gpus,
+        cpu_threads: std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+        system_info: whisper_rs::print_system_info().trim().to_string(),
+    }
+}
+
+/// 内置测试音频
+///
+/// 合成 10 秒类似说话的信号（基频起伏的谐波叠加、按音节开合的包络）。
+/// 编码器耗时与真实语音相当，但解码器几乎不产出文字，只适合粗略估计
+pub fn benchmark_sample() -> AudioData {
+    let total = (BENCHMARK_SECS * BENCHMARK_SAMPLE_RATE as f32) as usize;
+    let rate = BENCHMARK_SAMPLE_RATE as f32;
+    let mut phase = 0.0f32;
+
+    let samples = (0..total)
+        .map(|i| {
+            let t = i as f32 / rate;
+            // 基频在 120~180Hz 间缓慢起伏，模拟语调
+            let f0 = 150.0 + 30.0 * (TAU * 0.7 * t).sin();
+            phase = (phase + TAU * f0 / rate) % TAU;
+            let voice: f32 = (1..=5).map(|k| (phase * k as f32).sin() / k as f32).sum();
+            // 每秒约 4 个音节
+            let envelope = (TAU * 2.0 * t).sin().max(0.0);
+            (voice * envelope * 0.3 * i16::MAX as f32) as i16
+        })
+        .collect();
+
+    AudioData::new(samples, BENCHMARK_SAMPLE_RATE, 1)
+}
+
+/// 测量识别速度
+///
+/// `audio` 为空时使用内置合成音频。`load` 负责加载模型，计入 `load_secs`
+/// 但不计入实时率
+pub fn benchmark(
+    audio: Option<&AudioData>,
+    load: impl FnOnce() -> Result<WhisperTranscriber>,
+) -> Result<BenchmarkResult> {
+    let synthetic = audio.is_none();
+    let sample;
+    let audio = match audio {
+        Some(audio) => audio,
+        None => {
+            sample = benchmark_sample();
+            &sample
+        }
+    };
+    if audio.duration_secs <= 0.0 {
+        return Err(VoiceError::AudioFormatError("测速音频为空".to_string()));
+    }
+
+    let start = Instant::now();
+    let transcriber = load()?;
+    let load_secs = start.elapsed().as_secs_f32();
+
+    let start = Instant::now();
+    transcriber.transcribe(audio)?;
+    let elapsed_secs = start.elapsed().as_secs_f32();
+
+    Ok(BenchmarkResult {
+        audio_secs: audio.duration_secs,
+        load_secs,
+        elapsed_secs,
+        rtf: elapsed_secs / audio.duration_secs,
+        synthetic,
+    })
+}
+
+/// 通过 `nvidia-smi` 查询 NVIDIA 显卡
+fn query_nvidia_gpus() -> Vec<GpuInfo> {
+    let Some(output) = run(
+        "nvidia-smi",
+        &[
+            "--query-gpu=name,memory.total,memory.free",
+            "--format=csv,noheader,nounits",
+        ],
+    ) else {
+        return Vec::new();
+    };
+    parse_nvidia_smi(&output)
+}
+
+/// 解析 `nvidia-smi --format=csv,noheader,nounits` 的输出
+///
+/// 每行依次为名称、显存总量、空闲显存；无法解析的数值字段留空
+pub fn parse_nvidia_smi(output: &str) -> Vec<GpuInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(',').map(str::trim);
+            let name = fields.next().filter(|n| !n.is_empty())?;
+            Some(GpuInfo {
+                name: name.to_string(),
+                total_memory_mb: fields.next().and_then(|v| v.parse().ok()),
+                free_memory_mb: fields.next().and_then(|v| v.parse().ok()),
+                unified_memory: false,
+            })
+        })
+        .collect()
+}
+
+/// Apple Silicon 的 GPU 与 CPU 共享内存，报告芯片名称和统一内存总量
+#[cfg(target_os = "macos")]
+fn query_apple_gpu() -> Option<GpuInfo> {
+    if std::env::consts::ARCH != "aarch64" {
+        return None;
+    }
+    let name = run("sysctl", &["-n", "machdep.cpu.brand_string"])?;
+    let total_bytes: u64 = run("sysctl", &["-n", "hw.memsize"])?.trim().parse().ok()?;
+    Some(GpuInfo {
+        name: name.trim().to_string(),
+        total_memory_mb: Some(total_bytes / 1024 / 1024),
+        free_memory_mb: None,
+        unified_memory: true,
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+fn query_apple_gpu() -> Option<GpuInfo> {
+    None
+}
+
+/// 运行系统命令，成功时返回标准输出
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
@@ -0,0 +1,137 @@
+//! 音频文件解码
+//!
+//! 把 mp3 / m4a（AAC）/ ogg（Vorbis）/ flac / wav 文件解码为 16kHz 单声道 [`AudioData`]，
+//! 供文件转写和拖入音频文件使用。多声道混合为单声道，其他采样率线性插值重采样到 16kHz。
+//!
+//! 只解码第一条音轨；个别损坏的数据包会跳过，整个文件都无法解码时返回
+//! [`VoiceError::AudioFormatError`]。
+
+use std::io::Cursor;
+use std::path::Path;
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::encode::resample;
+use crate::error::{Result, VoiceError};
+use crate::types::AudioData;
+
+/// 解码后的采样率
+pub const TARGET_SAMPLE_RATE: u32 = 16000;
+
+/// 支持解码的文件扩展名
+pub const SUPPORTED_EXTENSIONS: &[&str] =
+    &["wav", "mp3", "m4a", "mp4", "aac", "ogg", "oga", "flac"];
+
+/// 是否为支持解码的音频文件（按扩展名判断）
+pub fn is_supported(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| SUPPORTED_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// 读取并解码音频文件
+pub fn decode_file(path: &Path) -> Result<AudioData> {
+    let bytes = std::fs::read(path)?;
+    let extension = path.extension().and_then(|e| e.to_str());
+    decode_bytes(bytes, extension)
+}
+
+/// 解码音频文件内容
+///
+/// `extension` 仅作为格式探测的提示，实际格式按文件内容识别
+pub fn decode_bytes(bytes: Vec<u8>, extension: Option<&str>) -> Result<AudioData> {
+    let source = MediaSourceStream::new(Box::new(Cursor::new(bytes)), Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = extension {
+        hint.with_extension(extension);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| format_error("无法识别的音频格式", e))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| VoiceError::AudioFormatError("文件中没有音轨".to_string()))?;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| format_error("不支持的音频编码", e))?;
+
+    let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+    let mut mono: Vec<f32> = Vec::new();
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(format_error("读取音频失败", e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(SymphoniaError::DecodeError(e)) => {
+                tracing::debug!("[音频解码] 跳过损坏的数据包: {}", e);
+                continue;
+            }
+            Err(e) => return Err(format_error("解码音频失败", e)),
+        };
+
+        let spec = *decoded.spec();
+        sample_rate = spec.rate;
+        let channels = spec.channels.count().max(1);
+        if buffer
+            .as_ref()
+            .is_some_and(|b| b.capacity() < decoded.capacity() * channels)
+        {
+            buffer = None;
+        }
+        let buffer =
+            buffer.get_or_insert_with(|| SampleBuffer::new(decoded.capacity() as u64, spec));
+        buffer.copy_interleaved_ref(decoded);
+        mono.extend(
+            buffer
+                .samples()
+                .chunks(channels)
+                .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32),
+        );
+    }
+
+    if mono.is_empty() || sample_rate == 0 {
+        return Err(VoiceError::AudioFormatError(
+            "文件中没有可解码的音频".to_string(),
+        ));
+    }
+
+    let samples: Vec<i16> = mono
+        .into_iter()
+        .map(|s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+        .collect();
+    let samples = resample(&samples, sample_rate, TARGET_SAMPLE_RATE);
+    Ok(AudioData::new(samples, TARGET_SAMPLE_RATE, 1))
+}
+
+fn format_error(context: &str, error: SymphoniaError) -> VoiceError {
+    VoiceError::AudioFormatError(format!("{}: {}", context, error))
+}
@@ -0,0 +1,174 @@
+//! 本地说话人区分（实验性）
+//!
+//! 给已有时间戳的分段标注说话人，供不支持说话人区分的服务（或本地模型）转写会议录音：
+//!
+//! - 每个分段提取 FBank 特征，取各维的均值和标准差作为声纹向量，减去向量自身的均值
+//!   （去掉音量差异）后归一化
+//! - 按余弦相似度自底向上合并：最相似的两类相似度低于阈值且类数不超过上限时停止
+//! - 过短的分段特征不可靠，沿用前一个分段的说话人
+//!
+//! 只依据频谱的统计特征，音色相近的说话人可能被合并，结果仅供参考。
+
+use crate::encode::{resample, to_mono};
+use crate::fbank::{Fbank, SAMPLE_RATE};
+use crate::types::{AudioData, Segment};
+
+/// 参与聚类的分段最短时长（秒）
+const MIN_SEGMENT_SECS: f32 = 0.5;
+
+/// 本地说话人区分选项
+#[derive(Debug, Clone, Copy)]
+pub struct DiarizationOptions {
+    /// 最多区分的说话人数
+    pub max_speakers: usize,
+    /// 两类的余弦相似度不低于该值时视为同一说话人（0-1）
+    pub threshold: f32,
+}
+
+impl Default for DiarizationOptions {
+    fn default() -> Self {
+        Self {
+            max_speakers: 4,
+            threshold: 0.7,
+        }
+    }
+}
+
+/// 给分段标注说话人（按首次出现的顺序从 0 编号），返回区分出的说话人数
+///
+/// 没有可用于聚类的分段时不修改分段，返回 0
+pub fn diarize(audio: &AudioData, segments: &mut [Segment], options: DiarizationOptions) -> usize {
+    let mono = to_mono(&audio.samples, audio.channels);
+    let samples = resample(&mono, audio.sample_rate, SAMPLE_RATE);
+    let fbank = Fbank::new();
+
+    // 可聚类的分段下标和声纹向量
+    let (indices, embeddings): (Vec<usize>, Vec<Vec<f32>>) = segments
+        .iter()
+        .enumerate()
+        .filter(|(_, segment)| segment.end - segment.start >= MIN_SEGMENT_SECS)
+        .filter_map(|(i, segment)| {
+            let start = (segment.start.max(0.0) * SAMPLE_RATE as f32) as usize;
+            let end = ((segment.end * SAMPLE_RATE as f32) as usize).min(samples.len());
+            let clip: Vec<f32> = samples.get(start..end)?.iter().map(|&s| s as f32).collect();
+            embedding(&fbank, &clip).map(|e| (i, e))
+        })
+        .unzip();
+    if indices.is_empty() {
+        return 0;
+    }
+
+    let labels = cluster(&embeddings, options);
+    let mut speakers: Vec<Option<u32>> = vec![None; segments.len()];
+    for (&i, &label) in indices.iter().zip(&labels) {
+        speakers[i] = Some(label as u32);
+    }
+
+    // 过短的分段沿用前一个分段的说话人，开头的沿用第一个已知说话人
+    let mut previous = speakers.iter().flatten().next().copied();
+    for speaker in speakers.iter_mut() {
+        match speaker {
+            Some(s) => previous = Some(*s),
+            None => *speaker = previous,
+        }
+    }
+
+    // 按首次出现的顺序重新编号
+    let mut order: Vec<u32> = Vec::new();
+    for (segment, speaker) in segments.iter_mut().zip(speakers) {
+        let speaker = speaker.map(|s| match order.iter().position(|&o| o == s) {
+            Some(pos) => pos as u32,
+            None => {
+                order.push(s);
+                (order.len() - 1) as u32
+            }
+        });
+        segment.speaker = speaker;
+    }
+    order.len()
+}
+
+/// 一段音频的声纹向量：FBank 各维的均值和标准差，去均值后归一化
+fn embedding(fbank: &Fbank, samples: &[f32]) -> Option<Vec<f32>> {
+    let frames = fbank.compute(samples);
+    if frames.is_empty() {
+        return None;
+    }
+    let dims = frames[0].len();
+    let count = frames.len() as f32;
+
+    let mean: Vec<f32> = (0..dims)
+        .map(|d| frames.iter().map(|f| f[d]).sum::<f32>() / count)
+        .collect();
+    let std = (0..dims).map(|d| {
+        let variance = frames.iter().map(|f| (f[d] - mean[d]).powi(2)).sum::<f32>() / count;
+        variance.sqrt()
+    });
+    let mut vector: Vec<f32> = mean.iter().copied().chain(std).collect();
+
+    let average = vector.iter().sum::<f32>() / vector.len() as f32;
+    vector.iter_mut().for_each(|v| *v -= average);
+    normalize(&mut vector).then_some(vector)
+}
+
+/// 归一化为单位向量，零向量返回 false
+fn normalize(vector: &mut [f32]) -> bool {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm <= f32::EPSILON {
+        return false;
+    }
+    vector.iter_mut().for_each(|v| *v /= norm);
+    true
+}
+
+/// 余弦相似度（输入为单位向量）
+fn similarity(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// 按质心的余弦相似度自底向上聚类，返回每个向量的类别
+fn cluster(embeddings: &[Vec<f32>], options: DiarizationOptions) -> Vec<usize> {
+    let max_speakers = options.max_speakers.max(1);
+    // 每类的成员和质心
+    let mut clusters: Vec<(Vec<usize>, Vec<f32>)> = embeddings
+        .iter()
+        .enumerate()
+        .map(|(i, e)| (vec![i], e.clone()))
+        .collect();
+
+    while clusters.len() > 1 {
+        let mut best = (0, 1, f32::MIN);
+        for i in 0..clusters.len() {
+            for j in i + 1..clusters.len() {
+                let score = similarity(&clusters[i].1, &clusters[j].1);
+                if score > best.2 {
+                    best = (i, j, score);
+                }
+            }
+        }
+        let (i, j, score) = best;
+        if score < options.threshold && clusters.len() <= max_speakers {
+            break;
+        }
+
+        let (members, _) = clusters.remove(j);
+        clusters[i].0.extend(members);
+        let mut centroid = vec![0.0; embeddings[0].len()];
+        for &m in &clusters[i].0 {
+            centroid
+                .iter_mut()
+                .zip(&embeddings[m])
+                .for_each(|(c, e)| *c += e);
+        }
+        normalize(&mut centroid);
+        clusters[i].1 = centroid;
+    }
+
+    let mut labels = vec![0; embeddings.len()];
+    for (label, (members, _)) in clusters.iter().enumerate() {
+        for &m in members {
+            labels[m] = label;
+        }
+    }
+    labels
+}
@@ -0,0 +1,134 @@
+//! 音频编码
+//!
+//! 录音统一以 16 位 PCM 保存在 [`AudioData`] 中，上传前按服务偏好的格式编码：
+//! - [`AudioFormat::Pcm`]：16 位小端裸 PCM
+//! - [`AudioFormat::Wav`]：16 位 PCM WAV
+//! - [`AudioFormat::Flac`]：无损压缩（见 [`crate::flac`]），体积约为 WAV 的一半
+//!
+//! 其余格式（mp3/m4a/ogg/amr）没有可用的纯 Rust 编码器，不参与协商。
+//! 编码时统一混为单声道，并按服务支持的采样率重采样。
+
+use crate::asr_client::AudioFormat;
+use crate::error::{Result, VoiceError};
+use crate::flac;
+use crate::types::AudioData;
+
+/// 可以编码的格式
+pub const ENCODABLE_FORMATS: &[AudioFormat] =
+    &[AudioFormat::Pcm, AudioFormat::Wav, AudioFormat::Flac];
+
+/// 编码后的音频
+#[derive(Debug, Clone)]
+pub struct EncodedAudio {
+    /// 格式
+    pub format: AudioFormat,
+    /// 编码后的字节
+    pub bytes: Vec<u8>,
+    /// 采样率
+    pub sample_rate: u32,
+    /// 声道数（固定为 1）
+    pub channels: u16,
+}
+
+impl EncodedAudio {
+    /// 文件扩展名
+    pub fn extension(&self) -> &'static str {
+        match self.format {
+            AudioFormat::Pcm => "pcm",
+            AudioFormat::Wav => "wav",
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Flac => "flac",
+            AudioFormat::M4a => "m4a",
+            AudioFormat::Ogg => "ogg",
+            AudioFormat::Amr => "amr",
+        }
+    }
+
+    /// MIME 类型
+    pub fn mime_type(&self) -> &'static str {
+        match self.format {
+            AudioFormat::Pcm => "audio/L16",
+            AudioFormat::Wav => "audio/wav",
+            AudioFormat::Mp3 => "audio/mpeg",
+            AudioFormat::Flac => "audio/flac",
+            AudioFormat::M4a => "audio/mp4",
+            AudioFormat::Ogg => "audio/ogg",
+            AudioFormat::Amr => "audio/amr",
+        }
+    }
+
+    /// 上传用的文件名（如 `audio.flac`）
+    pub fn file_name(&self, stem: &str) -> String {
+        format!("{}.{}", stem, self.extension())
+    }
+}
+
+/// 把音频编码为指定格式和采样率（单声道）
+pub fn encode(audio: &AudioData, format: AudioFormat, sample_rate: u32) -> Result<EncodedAudio> {
+    if !ENCODABLE_FORMATS.contains(&format) {
+        return Err(VoiceError::AudioFormatError(format!(
+            "不支持编码为 {:?} 格式",
+            format
+        )));
+    }
+
+    let mono = to_mono(&audio.samples, audio.channels);
+    let samples = resample(&mono, audio.sample_rate, sample_rate);
+    let bytes = match format {
+        AudioFormat::Wav => AudioData::new(samples, sample_rate, 1).to_wav_bytes(),
+        AudioFormat::Flac => flac::encode(&samples, sample_rate, 1),
+        _ => samples.iter().flat_map(|s| s.to_le_bytes()).collect(),
+    };
+
+    Ok(EncodedAudio {
+        format,
+        bytes,
+        sample_rate,
+        channels: 1,
+    })
+}
+
+/// 多声道交错采样混为单声道
+pub fn to_mono(samples: &[i16], channels: u16) -> Vec<i16> {
+    let channels = channels.max(1) as usize;
+    if channels == 1 {
+        return samples.to_vec();
+    }
+    samples
+        .chunks_exact(channels)
+        .map(|frame| (frame.iter().map(|&s| s as i32).sum::<i32>() / channels as i32) as i16)
+        .collect()
+}
+
+/// 简单的线性插值重采样
+///
+/// 将单声道音频从源采样率转换到目标采样率
+pub fn resample(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let ratio = from_rate as f64 / to_rate as f64;
+    let new_len = (samples.len() as f64 / ratio) as usize;
+    let mut result = Vec::with_capacity(new_len);
+
+    for i in 0..new_len {
+        let src_idx = i as f64 * ratio;
+        let idx_floor = src_idx.floor() as usize;
+        let idx_ceil = (idx_floor + 1).min(samples.len() - 1);
+        let frac = src_idx - idx_floor as f64;
+
+        // 线性插值
+        let sample = if idx_floor < samples.len() {
+            let s1 = samples[idx_floor] as f64;
+            let s2 = samples[idx_ceil] as f64;
+            (s1 + (s2 - s1) * frac) as i16
+        } else {
+            0
+        };
+
+        result.push(sample);
+    }
+
+    result
+}
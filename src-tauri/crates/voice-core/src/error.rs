@@ -27,6 +27,10 @@ pub enum VoiceError {
     #[error("Whisper 模型加载失败: {0}")]
     WhisperModelError(String),
 
+    /// SenseVoice 模型加载错误
+    #[error("SenseVoice 模型加载失败: {0}")]
+    SenseVoiceModelError(String),
+
     /// ASR 服务错误
     #[error("ASR 服务错误: {0}")]
     AsrError(String),
@@ -55,10 +59,26 @@ pub enum VoiceError {
     #[error("录音时间过短（需要至少 0.5 秒）")]
     RecordingTooShort,
 
+    /// ASR 配置错误
+    #[error("ASR 配置错误: {0}")]
+    ConfigError(String),
+
+    /// 电平校准失败
+    #[error("电平校准失败: {0}")]
+    CalibrationError(String),
+
     /// 网络错误
     #[error("网络请求失败: {0}")]
     NetworkError(String),
 
+    /// 隐私设置禁止的操作
+    #[error("{0}")]
+    PrivacyError(String),
+
+    /// 识别被取消
+    #[error("识别已取消")]
+    Cancelled,
+
     /// IO 错误
     #[error("IO 错误: {0}")]
     IoError(#[from] std::io::Error),
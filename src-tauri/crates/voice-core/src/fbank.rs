@@ -0,0 +1,216 @@
+//! FBank 特征提取
+//!
+//! 与 Kaldi `compute-fbank-feats` 默认参数一致的对数梅尔滤波器组特征，
+//! 供 SenseVoice 等 FunASR 系列 ONNX 模型使用：
+//!
+//! - 16kHz 采样，帧长 25ms（400 点），帧移 10ms（160 点），不足一帧的尾部丢弃
+//! - 去直流、预加重 0.97、Hamming 窗，补零到 512 点做 FFT 取功率谱
+//! - 80 个梅尔滤波器（20Hz 到奈奎斯特频率），取自然对数
+//!
+//! 采样值保持 16 位整数的幅度（不归一化到 [-1, 1]），与模型训练时一致。
+//! [`apply_lfr`] 把相邻帧拼接成低帧率特征（LFR）。
+
+use std::f32::consts::PI;
+
+/// 采样率
+pub const SAMPLE_RATE: u32 = 16000;
+
+/// 梅尔滤波器个数（特征维数）
+pub const NUM_MEL_BINS: usize = 80;
+
+/// 帧长（采样点）
+const FRAME_LENGTH: usize = 400;
+
+/// 帧移（采样点）
+const FRAME_SHIFT: usize = 160;
+
+/// FFT 长度（帧长向上取到 2 的幂）
+const FFT_SIZE: usize = 512;
+
+/// 预加重系数
+const PREEMPHASIS: f32 = 0.97;
+
+/// 梅尔滤波器的最低频率（Hz）
+const LOW_FREQ: f32 = 20.0;
+
+/// FBank 特征提取器
+///
+/// 窗函数和滤波器组在创建时计算好，可重复用于多段音频
+pub struct Fbank {
+    /// Hamming 窗
+    window: Vec<f32>,
+    /// 每个滤波器的起始 FFT 频点和权重
+    mel_banks: Vec<(usize, Vec<f32>)>,
+}
+
+impl Default for Fbank {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fbank {
+    /// 创建特征提取器
+    pub fn new() -> Self {
+        let window = (0..FRAME_LENGTH)
+            .map(|i| 0.54 - 0.46 * (2.0 * PI * i as f32 / (FRAME_LENGTH - 1) as f32).cos())
+            .collect();
+
+        Self {
+            window,
+            mel_banks: mel_banks(),
+        }
+    }
+
+    /// 提取特征，每帧 [`NUM_MEL_BINS`] 维
+    ///
+    /// `samples` 为 16kHz 单声道采样，幅度为 16 位整数的范围。不足一帧时返回空
+    pub fn compute(&self, samples: &[f32]) -> Vec<Vec<f32>> {
+        if samples.len() < FRAME_LENGTH {
+            return Vec::new();
+        }
+        let num_frames = 1 + (samples.len() - FRAME_LENGTH) / FRAME_SHIFT;
+
+        let mut re = vec![0.0f32; FFT_SIZE];
+        let mut im = vec![0.0f32; FFT_SIZE];
+        let mut power = vec![0.0f32; FFT_SIZE / 2 + 1];
+
+        (0..num_frames)
+            .map(|index| {
+                let start = index * FRAME_SHIFT;
+                let frame = &samples[start..start + FRAME_LENGTH];
+
+                // 去直流
+                let mean = frame.iter().sum::<f32>() / FRAME_LENGTH as f32;
+                re[..FRAME_LENGTH]
+                    .iter_mut()
+                    .zip(frame)
+                    .for_each(|(out, &s)| *out = s - mean);
+
+                // 预加重（从后往前，首个采样与自身相减）
+                for i in (1..FRAME_LENGTH).rev() {
+                    re[i] -= PREEMPHASIS * re[i - 1];
+                }
+                re[0] -= PREEMPHASIS * re[0];
+
+                // 加窗并补零
+                re[..FRAME_LENGTH]
+                    .iter_mut()
+                    .zip(&self.window)
+                    .for_each(|(s, w)| *s *= w);
+                re[FRAME_LENGTH..].fill(0.0);
+                im.fill(0.0);
+
+                fft(&mut re, &mut im);
+                power
+                    .iter_mut()
+                    .enumerate()
+                    .for_each(|(i, p)| *p = re[i] * re[i] + im[i] * im[i]);
+
+                self.mel_banks
+                    .iter()
+                    .map(|(first, weights)| {
+                        let energy: f32 = weights
+                            .iter()
+                            .zip(&power[*first..])
+                            .map(|(w, p)| w * p)
+                            .sum();
+                        energy.max(f32::EPSILON).ln()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// 低帧率（LFR）拼接：每 `shift` 帧取一次，拼接从该帧开始的 `window` 帧
+///
+/// 输出维数为输入的 `window` 倍，帧数不足 `window` 时返回空
+pub fn apply_lfr(frames: &[Vec<f32>], window: usize, shift: usize) -> Vec<Vec<f32>> {
+    if window == 0 || shift == 0 || frames.len() < window {
+        return Vec::new();
+    }
+    let num_frames = (frames.len() - window) / shift + 1;
+    (0..num_frames)
+        .map(|i| frames[i * shift..i * shift + window].concat())
+        .collect()
+}
+
+/// Kaldi 梅尔刻度
+fn mel_scale(freq: f32) -> f32 {
+    1127.0 * (1.0 + freq / 700.0).ln()
+}
+
+/// 计算梅尔滤波器组（不含奈奎斯特频点）
+fn mel_banks() -> Vec<(usize, Vec<f32>)> {
+    let num_fft_bins = FFT_SIZE / 2;
+    let fft_bin_width = SAMPLE_RATE as f32 / FFT_SIZE as f32;
+    let mel_low = mel_scale(LOW_FREQ);
+    let mel_high = mel_scale(SAMPLE_RATE as f32 / 2.0);
+    let mel_delta = (mel_high - mel_low) / (NUM_MEL_BINS + 1) as f32;
+
+    (0..NUM_MEL_BINS)
+        .map(|bin| {
+            let left = mel_low + bin as f32 * mel_delta;
+            let center = left + mel_delta;
+            let right = center + mel_delta;
+
+            let weights: Vec<(usize, f32)> = (0..num_fft_bins)
+                .filter_map(|i| {
+                    let mel = mel_scale(fft_bin_width * i as f32);
+                    if mel <= left || mel >= right {
+                        return None;
+                    }
+                    let weight = if mel <= center {
+                        (mel - left) / (center - left)
+                    } else {
+                        (right - mel) / (right - center)
+                    };
+                    Some((i, weight))
+                })
+                .collect();
+
+            let first = weights.first().map_or(0, |(i, _)| *i);
+            (first, weights.into_iter().map(|(_, w)| w).collect())
+        })
+        .collect()
+}
+
+/// 原地基 2 FFT，长度必须是 2 的幂
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+
+    // 位反转置换
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let a = start + k;
+                let b = a + len / 2;
+                let tr = re[b] * cos - im[b] * sin;
+                let ti = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
+}
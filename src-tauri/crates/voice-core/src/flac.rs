@@ -0,0 +1,304 @@
+//! FLAC 编码
+//!
+//! 纯 Rust 实现的 16 位 FLAC 编码器，用于减小上传体积（语音通常压缩到 WAV 的一半左右）：
+//!
+//! - 固定块大小 4096，各声道独立编码
+//! - 每个子帧在常量、原样和 0～4 阶固定预测中选取编码后最短的一种
+//! - 残差使用单分区 Rice 编码，参数按实际位数择优
+//!
+//! 不计算 STREAMINFO 中的 MD5（全零表示未知），解码器会跳过校验。
+
+/// 每块的采样数（每声道）
+const BLOCK_SIZE: usize = 4096;
+
+/// 采样位数
+const BITS_PER_SAMPLE: u32 = 16;
+
+/// Rice 参数上限（4 位参数，15 为转义码）
+const MAX_RICE_PARAM: u32 = 14;
+
+/// 最高固定预测阶数
+const MAX_FIXED_ORDER: usize = 4;
+
+/// 把交错的 16 位 PCM 编码为 FLAC 文件
+///
+/// 声道数须在 1～8 之间
+pub fn encode(samples: &[i16], sample_rate: u32, channels: u16) -> Vec<u8> {
+    let channels = channels.clamp(1, 8) as usize;
+    let total_frames = samples.len() / channels;
+
+    let mut out = BitWriter::default();
+    out.write_bytes(b"fLaC");
+
+    // STREAMINFO（唯一的元数据块）
+    out.write(1, 1);
+    out.write(0, 7);
+    out.write(34, 24);
+    out.write(BLOCK_SIZE.min(total_frames.max(16)) as u64, 16);
+    out.write(BLOCK_SIZE as u64, 16);
+    out.write(0, 24);
+    out.write(0, 24);
+    out.write(sample_rate as u64, 20);
+    out.write(channels as u64 - 1, 3);
+    out.write(BITS_PER_SAMPLE as u64 - 1, 5);
+    out.write(total_frames as u64, 36);
+    out.write_bytes(&[0; 16]);
+
+    let mut bytes = out.into_bytes();
+    let mut channel = Vec::with_capacity(BLOCK_SIZE);
+    for (index, block) in samples[..total_frames * channels]
+        .chunks(BLOCK_SIZE * channels)
+        .enumerate()
+    {
+        let block_size = block.len() / channels;
+        let mut frame = BitWriter::default();
+        write_frame_header(&mut frame, index as u64, block_size, channels);
+
+        for ch in 0..channels {
+            channel.clear();
+            channel.extend(block.iter().skip(ch).step_by(channels).map(|&s| s as i32));
+            write_subframe(&mut frame, &channel);
+        }
+
+        let mut frame = frame.into_bytes();
+        let crc = crc16(&frame);
+        frame.extend_from_slice(&crc.to_be_bytes());
+        bytes.extend_from_slice(&frame);
+    }
+    bytes
+}
+
+/// 写入帧头（含 CRC-8）
+fn write_frame_header(out: &mut BitWriter, frame_number: u64, block_size: usize, channels: usize) {
+    // 同步码 + 保留位 + 固定块大小
+    out.write(0b11_1111_1111_1110, 14);
+    out.write(0, 1);
+    out.write(0, 1);
+
+    // 块大小：4096 有专用编码，其余（最后一块）在帧头末尾以 16 位写出
+    let block_code = if block_size == BLOCK_SIZE {
+        0b1100
+    } else {
+        0b0111
+    };
+    out.write(block_code, 4);
+    // 采样率取自 STREAMINFO
+    out.write(0, 4);
+    // 各声道独立
+    out.write(channels as u64 - 1, 4);
+    // 16 位采样
+    out.write(0b100, 3);
+    out.write(0, 1);
+
+    write_utf8_number(out, frame_number);
+    if block_code == 0b0111 {
+        out.write(block_size as u64 - 1, 16);
+    }
+
+    let crc = crc8(out.bytes());
+    out.write(crc as u64, 8);
+}
+
+/// 以 UTF-8 风格的变长编码写入帧号
+fn write_utf8_number(out: &mut BitWriter, value: u64) {
+    if value < 0x80 {
+        out.write(value, 8);
+        return;
+    }
+
+    // 续字节数：每个续字节携带 6 位，首字节携带剩余位
+    let bits = 64 - value.leading_zeros();
+    let mut extra = 1;
+    while bits > 6 * extra + (6 - extra) {
+        extra += 1;
+    }
+    let lead_marker = (0xFF00u64 >> (extra + 1)) & 0xFF;
+    out.write(lead_marker | (value >> (6 * extra)), 8);
+    for i in (0..extra).rev() {
+        out.write(0x80 | ((value >> (6 * i)) & 0x3F), 8);
+    }
+}
+
+/// 写入一个声道的子帧，选择编码后最短的方式
+fn write_subframe(out: &mut BitWriter, samples: &[i32]) {
+    // 子帧头：填充位 + 类型 + 无浪费位
+    if samples.iter().all(|&s| s == samples[0]) {
+        out.write(0, 1);
+        out.write(0b000000, 6);
+        out.write(0, 1);
+        out.write_signed(samples[0] as i64, BITS_PER_SAMPLE);
+        return;
+    }
+
+    let verbatim_bits = samples.len() as u64 * BITS_PER_SAMPLE as u64;
+    let best = (0..=MAX_FIXED_ORDER.min(samples.len().saturating_sub(1)))
+        .map(|order| {
+            let residual = fixed_residual(samples, order);
+            let (param, bits) = best_rice_param(&residual);
+            (
+                order,
+                residual,
+                param,
+                bits + order as u64 * BITS_PER_SAMPLE as u64,
+            )
+        })
+        .min_by_key(|(_, _, _, bits)| *bits);
+
+    match best {
+        Some((order, residual, param, bits)) if bits < verbatim_bits => {
+            out.write(0, 1);
+            out.write(0b001000 | order as u64, 6);
+            out.write(0, 1);
+            for &s in &samples[..order] {
+                out.write_signed(s as i64, BITS_PER_SAMPLE);
+            }
+            // 残差：Rice 编码（4 位参数），分区阶数 0
+            out.write(0b00, 2);
+            out.write(0, 4);
+            out.write(param as u64, 4);
+            for &r in &residual {
+                out.write_rice(r, param);
+            }
+        }
+        _ => {
+            out.write(0, 1);
+            out.write(0b000001, 6);
+            out.write(0, 1);
+            for &s in samples {
+                out.write_signed(s as i64, BITS_PER_SAMPLE);
+            }
+        }
+    }
+}
+
+/// 固定预测的残差（从第 `order` 个采样开始）
+fn fixed_residual(samples: &[i32], order: usize) -> Vec<i64> {
+    (order..samples.len())
+        .map(|i| {
+            let s = |k: usize| samples[i - k] as i64;
+            match order {
+                0 => s(0),
+                1 => s(0) - s(1),
+                2 => s(0) - 2 * s(1) + s(2),
+                3 => s(0) - 3 * s(1) + 3 * s(2) - s(3),
+                _ => s(0) - 4 * s(1) + 6 * s(2) - 4 * s(3) + s(4),
+            }
+        })
+        .collect()
+}
+
+/// 选择编码位数最少的 Rice 参数，返回参数和残差部分的总位数（含参数字段）
+fn best_rice_param(residual: &[i64]) -> (u32, u64) {
+    let folded: Vec<u64> = residual.iter().map(|&r| zigzag(r)).collect();
+    (0..=MAX_RICE_PARAM)
+        .map(|param| {
+            let bits: u64 = folded
+                .iter()
+                .map(|&u| (u >> param) + 1 + param as u64)
+                .sum();
+            (param, bits + 2 + 4 + 4)
+        })
+        .min_by_key(|(_, bits)| *bits)
+        .unwrap_or((0, u64::MAX))
+}
+
+/// 有符号数折叠为无符号数：0, -1, 1, -2, 2 ... → 0, 1, 2, 3, 4 ...
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// CRC-8（多项式 0x07）
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |crc, &byte| {
+        (0..8).fold(crc ^ byte, |crc, _| {
+            if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// CRC-16（多项式 0x8005）
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, &byte| {
+        (0..8).fold(crc ^ ((byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// 按位写入（高位在前）
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    /// 尚未凑满一个字节的位
+    pending: u64,
+    /// `pending` 中的位数
+    pending_bits: u32,
+}
+
+impl BitWriter {
+    /// 写入 `value` 的低 `bits` 位（`bits` 不超过 32）
+    fn write_small(&mut self, value: u64, bits: u32) {
+        self.pending = (self.pending << bits) | (value & ((1 << bits) - 1));
+        self.pending_bits += bits;
+        while self.pending_bits >= 8 {
+            self.pending_bits -= 8;
+            self.bytes.push((self.pending >> self.pending_bits) as u8);
+        }
+        self.pending &= (1 << self.pending_bits) - 1;
+    }
+
+    /// 写入 `value` 的低 `bits` 位
+    fn write(&mut self, value: u64, bits: u32) {
+        if bits > 32 {
+            self.write_small(value >> 32, bits - 32);
+            self.write_small(value & 0xFFFF_FFFF, 32);
+        } else if bits > 0 {
+            self.write_small(value, bits);
+        }
+    }
+
+    /// 以补码写入有符号数
+    fn write_signed(&mut self, value: i64, bits: u32) {
+        self.write(value as u64, bits);
+    }
+
+    /// Rice 编码：商用一元码（若干个 0 后跟 1），余数写低 `param` 位
+    fn write_rice(&mut self, value: i64, param: u32) {
+        let folded = zigzag(value);
+        let mut quotient = folded >> param;
+        while quotient >= 32 {
+            self.write_small(0, 32);
+            quotient -= 32;
+        }
+        self.write_small(1, quotient as u32 + 1);
+        self.write(folded, param);
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.write_small(b as u64, 8);
+        }
+    }
+
+    /// 已凑满的字节（帧头 CRC 在字节对齐处计算）
+    fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// 补零到字节边界并返回全部字节
+    fn into_bytes(mut self) -> Vec<u8> {
+        if self.pending_bits > 0 {
+            let pad = 8 - self.pending_bits;
+            self.write_small(0, pad);
+        }
+        self.bytes
+    }
+}
@@ -0,0 +1,323 @@
+//! 中文逆文本标准化（ITN）
+//!
+//! 识别结果中的数字常常是读法（"二零二五年三月十五日"、"三点五个百分点"），
+//! 输出前按规则改为书面写法（"2025年3月15日"、"3.5个百分点"）：
+//! - 年份：逐位读出的 2-4 位数字加「年」
+//! - 月日：年份后的月份、「X月X日/号」中的月和日
+//! - 百分数：「百分之X」改为 `X%`
+//! - 小数：「X点Y」，后面紧跟「分、秒、十」等时按时间处理，不改写
+//! - 整数：后面有量词或单位时改写；单个数字（如「两个人」「三次」）只在货币和计量单位前改写，
+//!   保留常见的汉字小数目写法
+//!
+//! 没有量词的数字（「统一」「一些」「万一」）不改写，避免误伤成语和常用词。
+
+/// 数字字符
+const DIGITS: &[(char, u64)] = &[
+    ('零', 0),
+    ('〇', 0),
+    ('一', 1),
+    ('二', 2),
+    ('两', 2),
+    ('三', 3),
+    ('四', 4),
+    ('五', 5),
+    ('六', 6),
+    ('七', 7),
+    ('八', 8),
+    ('九', 9),
+];
+
+/// 数位字符
+const UNITS: &[(char, u64)] = &[
+    ('十', 10),
+    ('百', 100),
+    ('千', 1_000),
+    ('万', 10_000),
+    ('亿', 100_000_000),
+];
+
+/// 单个数字前也改写的货币和计量单位
+const STRONG_UNITS: &[&str] = &[
+    "个百分点",
+    "美元",
+    "欧元",
+    "日元",
+    "英镑",
+    "港元",
+    "港币",
+    "人民币",
+    "元",
+    "块钱",
+    "毛钱",
+    "公里",
+    "千米",
+    "厘米",
+    "毫米",
+    "米",
+    "公斤",
+    "千克",
+    "毫克",
+    "克",
+    "斤",
+    "吨",
+    "毫升",
+    "升",
+    "GB",
+    "MB",
+    "KB",
+];
+
+/// 多位数字前改写的量词
+const MEASURE_WORDS: &[&str] = &[
+    "个月", "小时", "分钟", "秒钟", "星期", "个", "次", "人", "位", "名", "件", "本", "张", "条",
+    "家", "台", "辆", "页", "行", "层", "楼", "天", "周", "岁", "年", "月", "日", "号", "倍", "项",
+    "篇", "首", "道", "场", "届", "期", "户", "座", "所", "只", "头", "口", "套", "份", "批", "组",
+    "棵", "片", "句", "字", "步", "秒", "块", "度", "多",
+];
+
+/// 小数点后紧跟时按时间（「三点五分」「三点五十」）处理的字符
+const TIME_FOLLOWERS: &[char] = &['十', '百', '千', '万', '分', '秒', '刻'];
+
+fn digit_value(c: char) -> Option<u64> {
+    DIGITS.iter().find(|(d, _)| *d == c).map(|(_, v)| *v)
+}
+
+fn unit_value(c: char) -> Option<u64> {
+    UNITS.iter().find(|(u, _)| *u == c).map(|(_, v)| *v)
+}
+
+fn is_number_char(c: char) -> bool {
+    digit_value(c).is_some() || unit_value(c).is_some()
+}
+
+/// 把读法改为书面写法
+pub fn normalize(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some((written, len)) = convert_at(&chars, i, &out) {
+            out.push_str(&written);
+            i += len;
+        } else {
+            out.push(chars[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// 尝试改写 `chars[i..]` 开头的数字，返回改写结果和消耗的字符数
+fn convert_at(chars: &[char], i: usize, out: &str) -> Option<(String, usize)> {
+    let rest = &chars[i..];
+
+    // 百分数
+    if starts_with(rest, "百分之") {
+        if starts_with(&rest[3..], "百") && !rest.get(4).copied().is_some_and(is_number_char) {
+            return Some(("100%".to_string(), 4));
+        }
+        let (number, len) = read_number(&rest[3..])?;
+        return Some((format!("{}%", number.written()), 3 + len));
+    }
+
+    // 数字只从数字或「十」开始，避免从「万一」「千万」这样的词中间开始
+    let first = rest[0];
+    if digit_value(first).is_none() && first != '十' {
+        return None;
+    }
+    // 前面紧跟数字字符的位置已经判断过
+    if out.chars().last().is_some_and(is_number_char) {
+        return None;
+    }
+
+    // 逐位读出的年份
+    let digit_run = rest
+        .iter()
+        .take_while(|c| digit_value(**c).is_some() && **c != '两')
+        .count();
+    if (2..=4).contains(&digit_run) && rest.get(digit_run) == Some(&'年') {
+        let year: String = rest[..digit_run]
+            .iter()
+            .map(|c| char::from(b'0' + digit_value(*c).unwrap_or(0) as u8))
+            .collect();
+        return Some((year, digit_run));
+    }
+
+    let (number, len) = read_number(rest)?;
+    let after = &rest[len..];
+    if number.fraction.is_some() {
+        return Some((number.written(), len));
+    }
+
+    let single = len == 1;
+    let prev_is_date = |suffix: char| {
+        let mut tail = out.chars().rev();
+        tail.next() == Some(suffix) && tail.next().is_some_and(|c| c.is_ascii_digit())
+    };
+
+    // 月日
+    if after.first() == Some(&'月') && (1..=12).contains(&number.integer) {
+        let day_follows = read_number(&after[1..]).is_some_and(|(day, day_len)| {
+            matches!(after.get(1 + day_len), Some('日' | '号')) && (1..=31).contains(&day.integer)
+        });
+        if !single || prev_is_date('年') || day_follows {
+            return Some((number.written(), len));
+        }
+        return None;
+    }
+    if matches!(after.first(), Some('日' | '号')) && (1..=31).contains(&number.integer) {
+        return (!single || prev_is_date('月')).then(|| (number.written(), len));
+    }
+
+    if STRONG_UNITS.iter().any(|unit| starts_with(after, unit)) {
+        return Some((number.written(), len));
+    }
+    if !single && MEASURE_WORDS.iter().any(|word| starts_with(after, word)) {
+        return Some((number.written(), len));
+    }
+    None
+}
+
+/// `chars` 是否以 `prefix` 开头
+fn starts_with(chars: &[char], prefix: &str) -> bool {
+    prefix
+        .chars()
+        .enumerate()
+        .all(|(index, p)| chars.get(index) == Some(&p))
+}
+
+/// 读出的数
+struct Number {
+    /// 整数部分
+    integer: u64,
+    /// 小数部分的各位数字
+    fraction: Option<String>,
+    /// 读法以「万」或「亿」结尾时保留该单位（「三千万」写作 `3000万`）
+    suffix: Option<(char, u64)>,
+}
+
+impl Number {
+    fn written(&self) -> String {
+        let mut written = match self.suffix {
+            Some((unit, value)) => format!("{}{}", self.integer / value, unit),
+            None => self.integer.to_string(),
+        };
+        if let Some(fraction) = &self.fraction {
+            written.push('.');
+            written.push_str(fraction);
+        }
+        written
+    }
+}
+
+/// 从开头读一个数（整数部分可带「十百千万亿」，可带「点」和小数），返回数和消耗的字符数
+fn read_number(chars: &[char]) -> Option<(Number, usize)> {
+    let len = chars.iter().take_while(|c| is_number_char(**c)).count();
+    let integer = parse_integer(&chars[..len])?;
+    let mut number = Number {
+        integer,
+        fraction: None,
+        suffix: None,
+    };
+    if let Some(last) = chars[..len].last().and_then(|c| match c {
+        '万' | '亿' => unit_value(*c).map(|v| (*c, v)),
+        _ => None,
+    }) {
+        // 「一万」「两亿」这样只有一个单位的保留为 `1万`；「一万二千」不以单位结尾
+        if chars[..len]
+            .iter()
+            .filter(|c| matches!(c, '万' | '亿'))
+            .count()
+            == 1
+        {
+            number.suffix = Some(last);
+        }
+    }
+
+    // 小数
+    if chars.get(len) == Some(&'点') {
+        let fraction: String = chars[len + 1..]
+            .iter()
+            .take_while(|c| digit_value(**c).is_some() && **c != '两')
+            .map(|c| char::from(b'0' + digit_value(*c).unwrap_or(0) as u8))
+            .collect();
+        let end = len + 1 + fraction.chars().count();
+        let time = chars.get(end).is_some_and(|c| TIME_FOLLOWERS.contains(c));
+        if !fraction.is_empty() && !time && number.suffix.is_none() {
+            number.fraction = Some(fraction);
+            return Some((number, end));
+        }
+    }
+    Some((number, len))
+}
+
+/// 解析整数读法，连续的非零数字（「九五」）等不合法的读法返回 `None`
+fn parse_integer(chars: &[char]) -> Option<u64> {
+    if chars.is_empty() {
+        return None;
+    }
+    let mut total: u64 = 0; // 亿以上
+    let mut section: u64 = 0; // 万到亿之间
+    let mut current: u64 = 0; // 万以下
+    let mut digit: Option<u64> = None;
+    let mut last_unit: u64 = 1;
+    let mut after_zero = false;
+
+    for &c in chars {
+        if let Some(d) = digit_value(c) {
+            if d == 0 {
+                if digit.is_some() {
+                    return None;
+                }
+                after_zero = true;
+                continue;
+            }
+            if digit.is_some() {
+                return None;
+            }
+            digit = Some(d);
+            continue;
+        }
+        let unit = unit_value(c)?;
+        match unit {
+            10 | 100 | 1_000 => {
+                let n = match digit.take() {
+                    Some(n) => n,
+                    // 「十二」「二百一十」省略的「一」
+                    None if unit == 10 => 1,
+                    None => return None,
+                };
+                current = current.checked_add(n * unit)?;
+            }
+            10_000 => {
+                let small = current + digit.take().unwrap_or(0);
+                if small == 0 {
+                    return None;
+                }
+                section = section.checked_add(small * unit)?;
+                current = 0;
+            }
+            _ => {
+                let small = section + current + digit.take().unwrap_or(0);
+                if small == 0 {
+                    return None;
+                }
+                total = total.checked_add(small.checked_mul(unit)?)?;
+                section = 0;
+                current = 0;
+            }
+        }
+        last_unit = unit;
+        after_zero = false;
+    }
+
+    if let Some(d) = digit {
+        // 「三千五」「一万二」省略了末位单位；「一千零五」「十五」是个位
+        current += if last_unit >= 100 && !after_zero {
+            d * (last_unit / 10)
+        } else {
+            d
+        };
+    }
+    Some(total + section + current)
+}
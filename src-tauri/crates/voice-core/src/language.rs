@@ -0,0 +1,160 @@
+//! 语言代码
+//!
+//! 各 ASR 服务返回的语言标识格式不一（"zh"、"zh_cn"、"cn"、"chinese" 等），
+//! 这里统一转换为 ISO 639-1 代码；服务没有返回语言时按文本字符粗略判断。
+//!
+//! 中英混合模式（[`MIXED_LANGUAGE`]）下识别结果经 [`format_mixed_text`] 整理
+//! 中英文交界处的空格和大小写。
+
+/// 中英混合识别的语言代码
+pub const MIXED_LANGUAGE: &str = "mixed";
+
+/// 统一为 ISO 639-1 语言代码（如 "zh"、"en"）
+///
+/// 无法识别的值原样返回（转为小写）
+pub fn normalize_language(language: &str) -> String {
+    let lower = language.trim().to_lowercase();
+    let primary = lower.split(['_', '-']).next().unwrap_or_default();
+    let code = match primary {
+        "cn" | "chinese" | "mandarin" => "zh",
+        "english" => "en",
+        "japanese" => "ja",
+        "korean" => "ko",
+        "french" => "fr",
+        "german" => "de",
+        "spanish" => "es",
+        "russian" => "ru",
+        other => other,
+    };
+    code.to_string()
+}
+
+/// 按文本字符粗略判断语言
+///
+/// 只区分中文、日文、韩文和英文：含假名视为日文，其余比较汉字、韩文字数
+/// 与拉丁字母单词数。文本中没有这些字符时返回 `None`
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let mut han = 0usize;
+    let mut kana = 0usize;
+    let mut hangul = 0usize;
+    let mut latin_words = 0usize;
+    let mut in_word = false;
+
+    for c in text.chars() {
+        let is_latin = c.is_ascii_alphabetic();
+        if is_latin && !in_word {
+            latin_words += 1;
+        }
+        in_word = is_latin;
+
+        match c {
+            '\u{3040}'..='\u{30FF}' => kana += 1,
+            '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' => hangul += 1,
+            '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' => han += 1,
+            _ => {}
+        }
+    }
+
+    if kana > 0 {
+        Some("ja")
+    } else if hangul > 0 && hangul >= han && hangul >= latin_words {
+        Some("ko")
+    } else if han > 0 && han >= latin_words {
+        Some("zh")
+    } else if latin_words > 0 {
+        Some("en")
+    } else {
+        None
+    }
+}
+
+/// 中英混合文本排版
+///
+/// - 中文与英文、数字之间补一个空格，中文字符、中文标点两侧的多余空格去掉
+/// - 句首的全小写英文单词首字母大写，单独的 "i" 改为 "I"
+pub fn format_mixed_text(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len() + 8);
+    let mut sentence_start = true;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            let end = chars[i..]
+                .iter()
+                .position(|c| !c.is_whitespace())
+                .map_or(chars.len(), |n| i + n);
+            let run = &chars[i..end];
+            match (out.chars().last(), chars.get(end)) {
+                // 换行保持原样
+                _ if run.contains(&'\n') => out.extend(run),
+                (Some(prev), Some(&next)) if !is_cjk_side(prev) && !is_cjk_side(next) => {
+                    out.push(' ')
+                }
+                // 与中文相邻的空格由下面按需补充，首尾空格直接去掉
+                _ => {}
+            }
+            i = end;
+            continue;
+        }
+
+        if let Some(prev) = out.chars().last() {
+            let boundary = (is_cjk(prev) && c.is_ascii_alphanumeric())
+                || (prev.is_ascii_alphanumeric() && is_cjk(c));
+            if boundary {
+                out.push(' ');
+            }
+        }
+
+        let word_start = c.is_ascii_alphabetic()
+            && !out
+                .chars()
+                .last()
+                .is_some_and(|p| p.is_ascii_alphanumeric());
+        if word_start {
+            let word_len = chars[i..]
+                .iter()
+                .take_while(|c| c.is_ascii_alphabetic())
+                .count();
+            let word = &chars[i..i + word_len];
+            let all_lower = word.iter().all(|c| c.is_ascii_lowercase());
+            if all_lower && (sentence_start || word == ['i']) {
+                out.push(c.to_ascii_uppercase());
+                out.extend(&word[1..]);
+                sentence_start = false;
+                i += word_len;
+                continue;
+            }
+        }
+
+        out.push(c);
+        if matches!(c, '。' | '！' | '？') {
+            sentence_start = true;
+        } else if matches!(c, '.' | '!' | '?') {
+            // 英文标点后需有空白才算句末，避免 "node.js" 之类被改写
+            sentence_start = chars.get(i + 1).is_none_or(|n| n.is_whitespace());
+        } else if c.is_alphanumeric() {
+            sentence_start = false;
+        }
+        i += 1;
+    }
+
+    out
+}
+
+/// 中日韩文字
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7AF}'
+    )
+}
+
+/// 中日韩文字或全角标点
+fn is_cjk_side(c: char) -> bool {
+    is_cjk(c) || matches!(c, '\u{3000}'..='\u{303F}' | '\u{FF00}'..='\u{FFEF}')
+}
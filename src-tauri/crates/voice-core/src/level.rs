@@ -0,0 +1,172 @@
+//! 音量电平测量
+//!
+//! 按块计算 RMS / 峰值 dBFS 并检测削波，供麦克风电平校准和界面音量显示使用。
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+
+/// 电平下限（16-bit PCM 的理论动态范围）
+pub const MIN_DBFS: f32 = -96.0;
+/// 削波判定阈值（满幅的 99.9%）
+pub const CLIP_THRESHOLD: f32 = 0.999;
+/// 音量百分比对应的 dBFS 范围下限，低于此值显示为 0%
+const PERCENT_FLOOR_DBFS: f32 = -60.0;
+
+/// 线性幅度转换为 dBFS
+pub fn amplitude_to_dbfs(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        return MIN_DBFS;
+    }
+    (20.0 * amplitude.log10()).max(MIN_DBFS)
+}
+
+/// 一块音频的电平
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AudioLevel {
+    /// 均方根电平（dBFS）
+    pub rms_dbfs: f32,
+    /// 峰值电平（dBFS）
+    pub peak_dbfs: f32,
+    /// 是否出现削波
+    pub clipping: bool,
+}
+
+impl AudioLevel {
+    /// 静音
+    pub const SILENCE: Self = Self {
+        rms_dbfs: MIN_DBFS,
+        peak_dbfs: MIN_DBFS,
+        clipping: false,
+    };
+
+    /// 测量一块 f32 采样（-1.0 ~ 1.0）的电平
+    pub fn measure(samples: &[f32]) -> Self {
+        Self::measure_iter(samples.iter().copied())
+    }
+
+    /// 测量一块 i16 采样的电平
+    pub fn measure_i16(samples: &[i16]) -> Self {
+        Self::measure_iter(samples.iter().map(|&s| s as f32 / i16::MAX as f32))
+    }
+
+    fn measure_iter(samples: impl Iterator<Item = f32>) -> Self {
+        let mut count = 0usize;
+        let mut sum_sq = 0.0f32;
+        let mut peak = 0.0f32;
+        for sample in samples {
+            count += 1;
+            sum_sq += sample * sample;
+            peak = peak.max(sample.abs());
+        }
+        if count == 0 {
+            return Self::SILENCE;
+        }
+        let rms = (sum_sq / count as f32).sqrt();
+        Self {
+            rms_dbfs: amplitude_to_dbfs(rms),
+            peak_dbfs: amplitude_to_dbfs(peak),
+            clipping: peak >= CLIP_THRESHOLD,
+        }
+    }
+
+    /// 映射为 0-100 的音量百分比（-60 dBFS 到 0 dBFS 线性映射）
+    pub fn percent(&self) -> u32 {
+        let ratio = (self.rms_dbfs - PERCENT_FLOOR_DBFS) / -PERCENT_FLOOR_DBFS;
+        (ratio.clamp(0.0, 1.0) * 100.0).round() as u32
+    }
+}
+
+impl Default for AudioLevel {
+    fn default() -> Self {
+        Self::SILENCE
+    }
+}
+
+/// 跨线程共享的电平表
+///
+/// 录音处理线程写入，界面轮询读取；浮点数以位模式存入原子变量，无需加锁
+#[derive(Debug)]
+pub struct LevelMeter {
+    rms_bits: AtomicU32,
+    peak_bits: AtomicU32,
+    clipping: AtomicBool,
+    /// 本次录音累计削波的采样数
+    clipped_samples: AtomicU64,
+    /// 本次录音中最响一块音频的 RMS
+    max_rms_bits: AtomicU32,
+}
+
+impl LevelMeter {
+    pub fn new() -> Self {
+        Self {
+            rms_bits: AtomicU32::new(MIN_DBFS.to_bits()),
+            peak_bits: AtomicU32::new(MIN_DBFS.to_bits()),
+            clipping: AtomicBool::new(false),
+            clipped_samples: AtomicU64::new(0),
+            max_rms_bits: AtomicU32::new(MIN_DBFS.to_bits()),
+        }
+    }
+
+    /// 更新最新一块音频的电平
+    pub fn update(&self, level: &AudioLevel, clipped_samples: u64) {
+        self.rms_bits
+            .store(level.rms_dbfs.to_bits(), Ordering::Relaxed);
+        self.peak_bits
+            .store(level.peak_dbfs.to_bits(), Ordering::Relaxed);
+        self.clipping.store(level.clipping, Ordering::Relaxed);
+        if clipped_samples > 0 {
+            self.clipped_samples
+                .fetch_add(clipped_samples, Ordering::Relaxed);
+        }
+        let _ = self
+            .max_rms_bits
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                (level.rms_dbfs > f32::from_bits(bits)).then(|| level.rms_dbfs.to_bits())
+            });
+    }
+
+    /// 测量一块 f32 采样并更新
+    pub fn record(&self, samples: &[f32]) -> AudioLevel {
+        let level = AudioLevel::measure(samples);
+        let clipped = if level.clipping {
+            samples.iter().filter(|s| s.abs() >= CLIP_THRESHOLD).count() as u64
+        } else {
+            0
+        };
+        self.update(&level, clipped);
+        level
+    }
+
+    /// 最新电平
+    pub fn level(&self) -> AudioLevel {
+        AudioLevel {
+            rms_dbfs: f32::from_bits(self.rms_bits.load(Ordering::Relaxed)),
+            peak_dbfs: f32::from_bits(self.peak_bits.load(Ordering::Relaxed)),
+            clipping: self.clipping.load(Ordering::Relaxed),
+        }
+    }
+
+    /// 本次录音累计削波的采样数
+    pub fn clipped_samples(&self) -> u64 {
+        self.clipped_samples.load(Ordering::Relaxed)
+    }
+
+    /// 本次录音中最响一块音频的 RMS（dBFS）
+    pub fn max_rms_dbfs(&self) -> f32 {
+        f32::from_bits(self.max_rms_bits.load(Ordering::Relaxed))
+    }
+
+    /// 重置为静音（开始新录音或停止时调用）
+    pub fn reset(&self) {
+        self.update(&AudioLevel::SILENCE, 0);
+        self.clipped_samples.store(0, Ordering::Relaxed);
+        self.max_rms_bits
+            .store(MIN_DBFS.to_bits(), Ordering::Relaxed);
+    }
+}
+
+impl Default for LevelMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,15 +3,45 @@
 //! 提供音频录制、语音识别、文字输出等功能。
 //! 不依赖 Tauri，可被任何 Rust 项目使用。
 
+pub mod action;
 pub mod asr_client;
+pub mod calibration;
+pub mod compute;
+pub mod decode;
+pub mod diarization;
+pub mod encode;
 pub mod error;
+pub mod fbank;
+pub mod flac;
+pub mod itn;
+pub mod language;
+pub mod level;
 pub mod output;
+pub mod privacy;
+pub mod punctuation;
 pub mod recorder;
+pub mod ring_buffer;
+#[cfg(feature = "sensevoice")]
+pub mod sensevoice;
+pub mod silence;
+pub mod source;
 pub mod transcriber;
 pub mod types;
 
+pub use action::OutputAction;
+pub use calibration::{CalibrationResult, CalibrationSample};
+pub use compute::{BenchmarkResult, ComputeInfo, GpuInfo};
+pub use diarization::DiarizationOptions;
+pub use encode::EncodedAudio;
 pub use error::{Result, VoiceError};
+pub use language::{detect_language, format_mixed_text, normalize_language, MIXED_LANGUAGE};
+pub use level::{AudioLevel, LevelMeter};
 pub use output::OutputHandler;
+pub use privacy::AudioPrivacy;
 pub use recorder::AudioRecorder;
+#[cfg(feature = "sensevoice")]
+pub use sensevoice::SenseVoiceTranscriber;
+pub use silence::SilenceTrimOptions;
+pub use source::{AudioSource, CpalSource, WavFileSource};
 pub use transcriber::WhisperTranscriber;
 pub use types::*;
@@ -1,10 +1,11 @@
 //! 文字输出模块
 //!
-//! 支持模拟键盘输入和剪贴板两种输出方式。
+//! 支持模拟键盘输入和剪贴板两种输出方式，模拟输入时可执行包含按键的输出动作（见 [`crate::action`]）。
 
 use arboard::Clipboard;
-use enigo::{Enigo, Keyboard, Settings};
+use enigo::{Direction, Enigo, Key, Keyboard, Settings};
 
+use crate::action::{KeyCode, Modifier, OutputAction};
 use crate::error::{Result, VoiceError};
 use crate::types::OutputMode;
 
@@ -45,6 +46,42 @@ impl OutputHandler {
         Ok(())
     }
 
+    /// 按顺序执行输出动作
+    pub fn perform(&mut self, actions: &[OutputAction]) -> Result<()> {
+        for action in actions {
+            match action {
+                OutputAction::Text(text) => self.type_text(text)?,
+                OutputAction::Key { modifiers, key } => self.press_key(modifiers, *key)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// 按下组合键：依次按下修饰键，按一次主键，再反序松开修饰键
+    pub fn press_key(&mut self, modifiers: &[Modifier], key: KeyCode) -> Result<()> {
+        let mut pressed = Vec::with_capacity(modifiers.len());
+        let mut result = Ok(());
+        for modifier in modifiers {
+            let modifier = modifier_key(*modifier);
+            result = self.enigo.key(modifier, Direction::Press);
+            if result.is_err() {
+                break;
+            }
+            pressed.push(modifier);
+        }
+        if result.is_ok() {
+            result = self.enigo.key(key_code(key), Direction::Click);
+        }
+        // 出错时也要松开已按下的修饰键
+        for modifier in pressed.into_iter().rev() {
+            let _ = self.enigo.key(modifier, Direction::Release);
+        }
+        result.map_err(|e| VoiceError::KeyboardError(e.to_string()))?;
+
+        tracing::info!("模拟按键完成: {:?} {:?}", modifiers, key);
+        Ok(())
+    }
+
     /// 复制到剪贴板
     pub fn copy_to_clipboard(&self, text: &str) -> Result<()> {
         let mut clipboard =
@@ -59,6 +96,35 @@ impl OutputHandler {
     }
 }
 
+fn modifier_key(modifier: Modifier) -> Key {
+    match modifier {
+        Modifier::Ctrl => Key::Control,
+        Modifier::Shift => Key::Shift,
+        Modifier::Alt => Key::Alt,
+        Modifier::Meta => Key::Meta,
+    }
+}
+
+fn key_code(key: KeyCode) -> Key {
+    match key {
+        KeyCode::Enter => Key::Return,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::Escape => Key::Escape,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::Space => Key::Space,
+        KeyCode::Up => Key::UpArrow,
+        KeyCode::Down => Key::DownArrow,
+        KeyCode::Left => Key::LeftArrow,
+        KeyCode::Right => Key::RightArrow,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::Char(c) => Key::Unicode(c),
+    }
+}
+
 impl Default for OutputHandler {
     fn default() -> Self {
         Self::new().expect("创建输出处理器失败")
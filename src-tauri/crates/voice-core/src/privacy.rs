@@ -0,0 +1,124 @@
+//! 音频隐私策略
+//!
+//! 全局生效的隐私设置，在 voice-core 内部统一执行，不依赖调用方逐处检查：
+//! - 禁止上传时，[`create_client_with_hotwords`](crate::asr_client::create_client_with_hotwords)
+//!   拒绝构建会把音频发到本机以外的客户端（只允许自定义命令和回环地址上的服务）
+//! - 禁止写盘时，需要临时音频文件的识别（如自定义命令的 `{file}` 占位符）直接失败
+//! - [`purge_temp_audio`] 删除超过保留时长的临时音频文件，防止异常退出后残留
+//!
+//! 默认允许上传和写盘、不自动清理，与未设置隐私策略时的行为一致。
+
+use std::path::Path;
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
+
+use crate::error::{Result, VoiceError};
+
+/// voice-core 写入的临时音频文件名前缀
+pub const TEMP_AUDIO_PREFIX: &str = "voice-asr-";
+
+/// 音频隐私策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioPrivacy {
+    /// 是否允许原始音频离开本机
+    pub allow_upload: bool,
+    /// 是否允许把音频写入磁盘
+    pub allow_disk: bool,
+    /// 临时音频文件的保留时长，`None` 表示不自动清理
+    pub temp_retention: Option<Duration>,
+}
+
+impl AudioPrivacy {
+    /// 允许上传和写盘、不自动清理
+    pub const PERMISSIVE: Self = Self {
+        allow_upload: true,
+        allow_disk: true,
+        temp_retention: None,
+    };
+}
+
+impl Default for AudioPrivacy {
+    fn default() -> Self {
+        Self::PERMISSIVE
+    }
+}
+
+static POLICY: RwLock<AudioPrivacy> = RwLock::new(AudioPrivacy::PERMISSIVE);
+
+/// 设置全局隐私策略
+pub fn set_policy(policy: AudioPrivacy) {
+    *POLICY.write().unwrap_or_else(|e| e.into_inner()) = policy;
+}
+
+/// 当前的全局隐私策略
+pub fn policy() -> AudioPrivacy {
+    *POLICY.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 检查是否允许把音频发送给 `service`
+pub fn check_upload(service: &str) -> Result<()> {
+    if policy().allow_upload {
+        return Ok(());
+    }
+    Err(VoiceError::PrivacyError(format!(
+        "隐私设置禁止音频离开本机，无法使用{}",
+        service
+    )))
+}
+
+/// 检查是否允许把音频写入磁盘
+pub fn check_disk() -> Result<()> {
+    if policy().allow_disk {
+        return Ok(());
+    }
+    Err(VoiceError::PrivacyError(
+        "隐私设置禁止把音频写入磁盘".to_string(),
+    ))
+}
+
+/// 是否为 voice-core 写入的临时音频文件
+fn is_temp_audio(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with(TEMP_AUDIO_PREFIX))
+}
+
+/// 删除 `dir` 中修改时间早于 `max_age` 的临时音频文件，返回删除的文件数
+///
+/// 只处理 [`TEMP_AUDIO_PREFIX`] 开头的文件，目录不存在或无法读取时返回 0
+pub fn purge_temp_audio(dir: &Path, max_age: Duration) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let now = SystemTime::now();
+    let mut purged = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !is_temp_audio(&path) {
+            continue;
+        }
+        let expired = entry
+            .metadata()
+            .ok()
+            .filter(|meta| meta.is_file())
+            .and_then(|meta| meta.modified().ok())
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age >= max_age);
+        if !expired {
+            continue;
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => purged += 1,
+            Err(e) => tracing::warn!("[隐私] 删除临时音频 {} 失败: {}", path.display(), e),
+        }
+    }
+    purged
+}
+
+/// 按当前策略清理系统临时目录中的过期临时音频，未设置保留时长时不清理
+pub fn purge_expired_temp_audio() -> usize {
+    match policy().temp_retention {
+        Some(max_age) => purge_temp_audio(&std::env::temp_dir(), max_age),
+        None => 0,
+    }
+}
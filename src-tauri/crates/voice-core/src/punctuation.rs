@@ -0,0 +1,144 @@
+//! 规则标点恢复
+//!
+//! 部分服务（如百度普通话）返回的识别结果没有标点。这里按简单规则补全：
+//! 中文在停顿（识别结果中的空格）和连接词前加逗号，句末按疑问词选择问号或句号；
+//! 英文句首大写、句末补句号或问号。规则无法区分多个句子，只保证基本可读。
+
+use crate::language::detect_language;
+
+/// 视为已有标点的字符
+const PUNCTUATION: &[char] = &[
+    '，', '。', '！', '？', '、', '；', '：', ',', '.', '!', '?', ';', ':',
+];
+
+/// 前面加逗号的中文连接词
+const ZH_CONNECTIVES: &[&str] = &[
+    "但是", "可是", "不过", "所以", "因此", "而且", "并且", "然后", "因为", "如果", "虽然",
+];
+
+/// 句末表示疑问的中文语气词
+const ZH_QUESTION_PARTICLES: &[char] = &['吗', '呢', '么'];
+
+/// 表示疑问的中文词
+const ZH_QUESTION_WORDS: &[&str] = &[
+    "什么",
+    "为什么",
+    "怎么",
+    "哪里",
+    "哪个",
+    "谁",
+    "多少",
+    "几点",
+    "是不是",
+    "能不能",
+    "有没有",
+    "要不要",
+    "可不可以",
+];
+
+/// 句首表示疑问的英文单词
+const EN_QUESTION_WORDS: &[&str] = &[
+    "what", "why", "how", "who", "where", "when", "which", "is", "are", "do", "does", "did", "can",
+    "could", "would", "will", "should",
+];
+
+/// 连接词前至少有这么多字才加逗号，避免把短句切得太碎
+const MIN_CLAUSE_CHARS: usize = 4;
+
+/// 文本中是否已有标点
+pub fn has_punctuation(text: &str) -> bool {
+    text.contains(PUNCTUATION)
+}
+
+/// 按规则为没有标点的文本补全标点
+///
+/// 已有标点或为空时原样返回
+pub fn restore_punctuation(text: &str) -> String {
+    let text = text.trim();
+    if text.is_empty() || has_punctuation(text) {
+        return text.to_string();
+    }
+    match detect_language(text) {
+        Some("en") => restore_english(text),
+        Some("zh") => restore_chinese(text),
+        _ => text.to_string(),
+    }
+}
+
+fn restore_chinese(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 16);
+    // 当前小句的字数
+    let mut clause_chars = 0;
+
+    for (i, token) in text.split_whitespace().enumerate() {
+        if i > 0 {
+            // 中文之间的空格是识别服务标出的停顿；与英文、数字相邻的空格保留
+            let prev_latin = out
+                .chars()
+                .last()
+                .is_some_and(|c| c.is_ascii_alphanumeric());
+            let next_latin = token.starts_with(|c: char| c.is_ascii_alphanumeric());
+            if prev_latin || next_latin {
+                out.push(' ');
+            } else {
+                out.push('，');
+                clause_chars = 0;
+            }
+        }
+
+        let mut rest = token;
+        while !rest.is_empty() {
+            let connective = ZH_CONNECTIVES.iter().find(|w| rest.starts_with(*w));
+            if let Some(word) = connective {
+                if clause_chars >= MIN_CLAUSE_CHARS {
+                    out.push('，');
+                    clause_chars = 0;
+                }
+                out.push_str(word);
+                clause_chars += word.chars().count();
+                rest = &rest[word.len()..];
+                continue;
+            }
+            let c = rest.chars().next().unwrap_or_default();
+            out.push(c);
+            clause_chars += 1;
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    let last_clause = out.rsplit('，').next().unwrap_or_default();
+    let question = out.ends_with(ZH_QUESTION_PARTICLES)
+        || ZH_QUESTION_WORDS.iter().any(|w| last_clause.contains(w));
+    out.push(if question { '？' } else { '。' });
+    out
+}
+
+fn restore_english(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut out = String::with_capacity(text.len() + 4);
+
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            // "but" 前有足够长的小句时加逗号
+            if word.eq_ignore_ascii_case("but") && i >= MIN_CLAUSE_CHARS {
+                out.push(',');
+            }
+            out.push(' ');
+        }
+        if i == 0 || *word == "i" {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                out.extend(first.to_uppercase());
+                out.push_str(chars.as_str());
+            }
+        } else {
+            out.push_str(word);
+        }
+    }
+
+    let question = words
+        .first()
+        .is_some_and(|w| EN_QUESTION_WORDS.contains(&w.to_ascii_lowercase().as_str()));
+    out.push(if question { '?' } else { '.' });
+    out
+}
@@ -1,13 +1,21 @@
 //! 音频录制模块
 //!
-//! 使用 cpal 进行跨平台音频采集。
+//! 默认使用 cpal 进行跨平台音频采集，也可以通过 [`AudioRecorder::with_source`]
+//! 换用其他 [`AudioSource`]（如回放 WAV 文件）。
+//!
+//! 录音回调运行在音频驱动的实时线程上，只把原始 f32 采样写入无锁环形缓冲区；
+//! 声道混合、i16 转换和音量计算都在 [`CaptureWorker`] 处理线程中完成。
+//! 流式识别时，处理线程还会把每块单声道采样转发到 [`AudioTap`]。
 
-use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 use crate::error::{Result, VoiceError};
+use crate::level::{AudioLevel, LevelMeter};
+use crate::ring_buffer::{ring_buffer, Consumer, Producer};
+use crate::source::{AudioSource, CpalSource};
 use crate::types::AudioData;
 
 /// 默认采样率（ASR 标准）
@@ -17,104 +25,182 @@ pub const DEFAULT_CHANNELS: u16 = 1;
 /// 最大录音时长（秒）
 pub const MAX_RECORDING_DURATION: f32 = 60.0;
 
+/// 环形缓冲区可容纳的音频时长（秒），处理线程短暂卡顿时不丢数据
+const RING_BUFFER_SECS: usize = 2;
+/// 处理线程空闲时的轮询间隔
+const DRAIN_INTERVAL: Duration = Duration::from_millis(10);
+/// 处理线程每次取出的最大帧数
+const DRAIN_BLOCK_FRAMES: usize = 1024;
+
+/// 录音过程中转发单声道 i16 采样的通道（流式识别使用）
+///
+/// 录音结束时发送端随处理线程一起释放，接收端据此得知音频流结束
+pub type AudioTap = tokio::sync::mpsc::UnboundedSender<Vec<i16>>;
+
+/// 创建录音用的环形缓冲区
+pub fn capture_buffer(sample_rate: u32, channels: u16) -> (Producer<f32>, Consumer<f32>) {
+    ring_buffer(sample_rate as usize * channels.max(1) as usize * RING_BUFFER_SECS)
+}
+
+/// 录音处理线程
+///
+/// 从环形缓冲区取出交错的 f32 采样，混合为单声道 i16 并更新电平表
+pub struct CaptureWorker {
+    /// 通知处理线程音频流已释放
+    finished: Arc<AtomicBool>,
+    handle: Option<JoinHandle<Vec<i16>>>,
+}
+
+impl CaptureWorker {
+    /// 启动处理线程
+    pub fn spawn(consumer: Consumer<f32>, channels: u16, meter: Arc<LevelMeter>) -> Result<Self> {
+        Self::spawn_with_tap(consumer, channels, meter, None)
+    }
+
+    /// 启动处理线程，并把每块单声道采样转发到 `tap`
+    pub fn spawn_with_tap(
+        mut consumer: Consumer<f32>,
+        channels: u16,
+        meter: Arc<LevelMeter>,
+        mut tap: Option<AudioTap>,
+    ) -> Result<Self> {
+        let finished = Arc::new(AtomicBool::new(false));
+        let finished_flag = Arc::clone(&finished);
+        let channels = channels.max(1) as usize;
+
+        let handle = std::thread::Builder::new()
+            .name("voice-capture".to_string())
+            .spawn(move || {
+                let mut samples: Vec<i16> = Vec::new();
+                let mut block = vec![0.0f32; DRAIN_BLOCK_FRAMES * channels];
+
+                loop {
+                    // 先读标记再取数据：标记置位时流已释放，之后不会再有写入
+                    let done = finished_flag.load(Ordering::Acquire);
+                    let count = consumer.pop_slice(&mut block);
+                    if count == 0 {
+                        if done {
+                            break;
+                        }
+                        std::thread::sleep(DRAIN_INTERVAL);
+                        continue;
+                    }
+
+                    if samples.is_empty() {
+                        tracing::info!("[录音] 首次收到音频数据，数据长度: {}", count);
+                    }
+
+                    let data = &block[..count];
+                    meter.record(data);
+
+                    let start = samples.len();
+                    samples.extend(data.chunks(channels).map(|frame| {
+                        let mixed = frame.iter().sum::<f32>() / frame.len() as f32;
+                        (mixed.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+                    }));
+
+                    // 接收端已关闭（流式识别提前结束）时不再转发
+                    if let Some(sender) = &tap {
+                        if sender.send(samples[start..].to_vec()).is_err() {
+                            tap = None;
+                        }
+                    }
+                }
+
+                let dropped = consumer.dropped();
+                if dropped > 0 {
+                    tracing::warn!("[录音] 处理线程跟不上，丢弃了 {} 个采样", dropped);
+                }
+                samples
+            })
+            .map_err(|e| VoiceError::RecorderError(e.to_string()))?;
+
+        Ok(Self {
+            finished,
+            handle: Some(handle),
+        })
+    }
+
+    /// 结束处理，返回全部单声道采样
+    ///
+    /// 必须在音频流释放后调用，否则可能丢失最后几块数据
+    pub fn finish(mut self) -> Vec<i16> {
+        self.finished.store(true, Ordering::Release);
+        self.handle
+            .take()
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for CaptureWorker {
+    fn drop(&mut self) {
+        self.finished.store(true, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
 /// 音频录制器
 pub struct AudioRecorder {
-    /// 录音数据缓冲区
-    samples: Arc<Mutex<Vec<i16>>>,
-    /// 当前音量级别（0-100）
-    volume_level: Arc<AtomicU32>,
+    /// 录音处理线程（录音时持有）
+    worker: Option<CaptureWorker>,
+    /// 电平表
+    meter: Arc<LevelMeter>,
     /// 是否正在录音
     is_recording: Arc<AtomicBool>,
     /// 录音开始时间
     start_time: Option<Instant>,
-    /// 音频流（录音时持有）
-    stream: Option<cpal::Stream>,
-    /// 采样率
-    sample_rate: u32,
+    /// 音频源
+    source: Box<dyn AudioSource>,
 }
 
 impl AudioRecorder {
-    /// 创建新的录音器
+    /// 创建使用系统默认麦克风的录音器
     pub fn new() -> Result<Self> {
-        Ok(Self {
-            samples: Arc::new(Mutex::new(Vec::new())),
-            volume_level: Arc::new(AtomicU32::new(0)),
+        Ok(Self::with_source(Box::new(CpalSource::new())))
+    }
+
+    /// 创建使用指定音频源的录音器
+    pub fn with_source(source: Box<dyn AudioSource>) -> Self {
+        Self {
+            worker: None,
+            meter: Arc::new(LevelMeter::new()),
             is_recording: Arc::new(AtomicBool::new(false)),
             start_time: None,
-            stream: None,
-            sample_rate: DEFAULT_SAMPLE_RATE,
-        })
+            source,
+        }
+    }
+
+    /// 使用外部共享的电平表，录音期间调用方可以直接读取音量
+    pub fn with_meter(mut self, meter: Arc<LevelMeter>) -> Self {
+        self.meter = meter;
+        self
     }
 
     /// 开始录音
     pub fn start(&mut self) -> Result<()> {
+        self.start_with_tap(None)
+    }
+
+    /// 开始录音，并把每块单声道采样转发到 `tap`（流式识别使用）
+    pub fn start_with_tap(&mut self, tap: Option<AudioTap>) -> Result<()> {
         if self.is_recording.load(Ordering::SeqCst) {
             return Ok(());
         }
 
-        // 清空缓冲区
-        if let Ok(mut samples) = self.samples.lock() {
-            samples.clear();
-        }
-
-        // 获取默认输入设备
-        let host = cpal::default_host();
-        let device = host
-            .default_input_device()
-            .ok_or(VoiceError::NoMicrophoneFound)?;
-
-        tracing::info!("使用麦克风: {:?}", device.name());
-
-        // 配置音频格式
-        let config = cpal::StreamConfig {
-            channels: DEFAULT_CHANNELS,
-            sample_rate: cpal::SampleRate(DEFAULT_SAMPLE_RATE),
-            buffer_size: cpal::BufferSize::Default,
-        };
-
-        self.sample_rate = DEFAULT_SAMPLE_RATE;
-
-        // 创建共享状态
-        let samples = Arc::clone(&self.samples);
-        let volume_level = Arc::clone(&self.volume_level);
-        let is_recording = Arc::clone(&self.is_recording);
-
-        // 创建输入流
-        let stream = device
-            .build_input_stream(
-                &config,
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    if !is_recording.load(Ordering::SeqCst) {
-                        return;
-                    }
-
-                    // 计算音量级别
-                    let sum: f32 = data.iter().map(|s| s.abs()).sum();
-                    let avg = sum / data.len() as f32;
-                    let level = (avg * 100.0).min(100.0) as u32;
-                    volume_level.store(level, Ordering::SeqCst);
-
-                    // 转换为 i16 并存储
-                    let i16_samples: Vec<i16> =
-                        data.iter().map(|&s| (s * i16::MAX as f32) as i16).collect();
+        let format = self.source.format();
+        let (producer, consumer) = capture_buffer(format.sample_rate, format.channels);
 
-                    if let Ok(mut buffer) = samples.lock() {
-                        buffer.extend(i16_samples);
-                    }
-                },
-                |err| {
-                    tracing::error!("录音流错误: {}", err);
-                },
-                None,
-            )
-            .map_err(|e| VoiceError::RecorderError(e.to_string()))?;
+        self.meter.reset();
+        let worker =
+            CaptureWorker::spawn_with_tap(consumer, format.channels, Arc::clone(&self.meter), tap)?;
 
         // 开始录音
-        stream
-            .play()
-            .map_err(|e| VoiceError::RecorderError(e.to_string()))?;
+        self.source.start(producer)?;
 
-        self.stream = Some(stream);
+        self.worker = Some(worker);
         self.is_recording.store(true, Ordering::SeqCst);
         self.start_time = Some(Instant::now());
 
@@ -131,19 +217,24 @@ impl AudioRecorder {
         // 停止录音
         self.is_recording.store(false, Ordering::SeqCst);
 
-        // 停止流
-        if let Some(stream) = self.stream.take() {
-            drop(stream);
-        }
+        // 停止音频源，之后处理线程取完剩余数据即可结束
+        self.source.stop();
 
         // 获取录音数据
         let samples = self
-            .samples
-            .lock()
-            .map_err(|e| VoiceError::RecorderError(e.to_string()))?
-            .clone();
+            .worker
+            .take()
+            .map(CaptureWorker::finish)
+            .unwrap_or_default();
+        if self.meter.clipped_samples() > 0 {
+            tracing::warn!(
+                "录音出现削波，共 {} 个采样，建议调低麦克风增益",
+                self.meter.clipped_samples()
+            );
+        }
 
-        let audio = AudioData::new(samples, self.sample_rate, DEFAULT_CHANNELS);
+        // 处理线程已混合为单声道
+        let audio = AudioData::new(samples, self.source.format().sample_rate, 1);
 
         tracing::info!("停止录音，时长: {:.2}s", audio.duration_secs);
 
@@ -155,9 +246,24 @@ impl AudioRecorder {
         Ok(audio)
     }
 
+    /// 音频源的采样率
+    pub fn sample_rate(&self) -> u32 {
+        self.source.format().sample_rate
+    }
+
     /// 获取当前音量级别（0-100）
     pub fn get_volume(&self) -> u32 {
-        self.volume_level.load(Ordering::SeqCst)
+        self.meter.level().percent()
+    }
+
+    /// 获取当前电平（dBFS）
+    pub fn get_level(&self) -> AudioLevel {
+        self.meter.level()
+    }
+
+    /// 获取本次录音累计削波的采样数
+    pub fn get_clipped_samples(&self) -> u64 {
+        self.meter.clipped_samples()
     }
 
     /// 获取录音时长（秒）
@@ -175,12 +281,10 @@ impl AudioRecorder {
     /// 取消录音
     pub fn cancel(&mut self) {
         self.is_recording.store(false, Ordering::SeqCst);
-        if let Some(stream) = self.stream.take() {
-            drop(stream);
-        }
-        if let Ok(mut samples) = self.samples.lock() {
-            samples.clear();
-        }
+        self.source.stop();
+        // 丢弃处理线程中的数据
+        self.worker.take();
+        self.meter.reset();
         tracing::info!("取消录音");
     }
 }
@@ -193,6 +297,8 @@ impl Default for AudioRecorder {
 
 impl Drop for AudioRecorder {
     fn drop(&mut self) {
-        self.cancel();
+        if self.is_recording() {
+            self.cancel();
+        }
     }
 }
@@ -0,0 +1,133 @@
+//! 单生产者单消费者无锁环形缓冲区
+//!
+//! 用于录音回调（实时线程）向处理线程传递采样：写入端不分配内存、不加锁，
+//! 缓冲区满时丢弃整块采样并计数，避免阻塞音频驱动导致爆音。
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct Shared<T> {
+    buffer: Box<[UnsafeCell<T>]>,
+    /// 容量减一（容量为 2 的幂）
+    mask: usize,
+    /// 写入位置（只由生产者递增）
+    head: AtomicUsize,
+    /// 读取位置（只由消费者递增）
+    tail: AtomicUsize,
+    /// 因缓冲区已满被丢弃的元素数
+    dropped: AtomicUsize,
+}
+
+// 生产者只写 [tail, head + 空闲) 区间，消费者只读 [tail, head) 区间，
+// 两者通过 head/tail 的 Release/Acquire 同步，不会同时访问同一槽位
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+        head.wrapping_sub(tail)
+    }
+
+    fn capacity(&self) -> usize {
+        self.mask + 1
+    }
+}
+
+/// 创建环形缓冲区，容量向上取整为 2 的幂
+pub fn ring_buffer<T: Copy + Default + Send>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    let capacity = capacity.max(2).next_power_of_two();
+    let buffer = (0..capacity)
+        .map(|_| UnsafeCell::new(T::default()))
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+    let shared = Arc::new(Shared {
+        buffer,
+        mask: capacity - 1,
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        dropped: AtomicUsize::new(0),
+    });
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+        },
+        Consumer { shared },
+    )
+}
+
+/// 写入端（实时线程持有）
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Copy> Producer<T> {
+    /// 写入全部元素；剩余空间不足时整块丢弃并返回 false
+    ///
+    /// 整块写入保证多声道交错数据不会在帧中间被截断
+    pub fn push(&mut self, items: &[T]) -> bool {
+        let shared = &*self.shared;
+        let head = shared.head.load(Ordering::Relaxed);
+        let tail = shared.tail.load(Ordering::Acquire);
+        let free = shared.capacity() - head.wrapping_sub(tail);
+        if items.len() > free {
+            shared.dropped.fetch_add(items.len(), Ordering::Relaxed);
+            return false;
+        }
+
+        for (i, item) in items.iter().enumerate() {
+            let slot = &shared.buffer[head.wrapping_add(i) & shared.mask];
+            // SAFETY: 该槽位位于空闲区间，消费者在 head 更新前不会读取
+            unsafe { *slot.get() = *item };
+        }
+        shared
+            .head
+            .store(head.wrapping_add(items.len()), Ordering::Release);
+        true
+    }
+
+    /// 当前可写入的元素数
+    pub fn free_len(&self) -> usize {
+        self.shared.capacity() - self.shared.len()
+    }
+}
+
+/// 读取端（处理线程持有）
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Copy> Consumer<T> {
+    /// 读取到 `out`，返回实际读取数量
+    pub fn pop_slice(&mut self, out: &mut [T]) -> usize {
+        let shared = &*self.shared;
+        let tail = shared.tail.load(Ordering::Relaxed);
+        let head = shared.head.load(Ordering::Acquire);
+        let count = out.len().min(head.wrapping_sub(tail));
+
+        for (i, item) in out[..count].iter_mut().enumerate() {
+            let slot = &shared.buffer[tail.wrapping_add(i) & shared.mask];
+            // SAFETY: 该槽位位于已写入区间，生产者在 tail 更新前不会覆盖
+            *item = unsafe { *slot.get() };
+        }
+        shared
+            .tail
+            .store(tail.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    /// 当前可读元素数
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 因缓冲区已满被丢弃的元素总数
+    pub fn dropped(&self) -> usize {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+}
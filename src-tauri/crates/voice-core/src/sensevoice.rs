@@ -0,0 +1,292 @@
+//! SenseVoice 本地语音识别模块
+//!
+//! 使用 ONNX Runtime 运行 FunASR 的 SenseVoice Small 模型（sherpa-onnx 导出格式），
+//! 中文、粤语识别明显好于同体积的 Whisper，并自带标点和逆文本正则化（ITN）。
+//!
+//! 模型目录包含：
+//! - `model.int8.onnx` 或 `model.onnx`：模型，元数据中带有 CMVN 均值方差、
+//!   LFR 参数和语言 ID
+//! - `tokens.txt`：词表，每行为 `符号 ID`
+//!
+//! 识别流程：FBank 特征 → LFR 拼接 → CMVN 归一化 → 模型输出 CTC logits → 贪心解码。
+//! 解码结果开头的 `<|zh|><|NEUTRAL|><|Speech|><|withitn|>` 等标签用于取出识别语言，
+//! 不计入文本。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::Tensor;
+
+use crate::asr_client::{AsrCapabilities, AudioFormat};
+use crate::error::{Result, VoiceError};
+use crate::fbank::{apply_lfr, Fbank, NUM_MEL_BINS, SAMPLE_RATE};
+use crate::language::{normalize_language, MIXED_LANGUAGE};
+use crate::types::{AudioData, SenseVoiceModel, TranscribeResult};
+
+/// CTC 空白符 ID
+const BLANK_ID: usize = 0;
+
+/// 推理线程数上限
+const MAX_THREADS: usize = 4;
+
+/// SenseVoice 识别器
+pub struct SenseVoiceTranscriber {
+    /// ONNX Runtime 会话
+    session: Session,
+    /// 特征提取器
+    fbank: Fbank,
+    /// 词表，下标为 ID
+    tokens: Vec<String>,
+    /// CMVN 均值的相反数
+    neg_mean: Vec<f32>,
+    /// CMVN 标准差的倒数
+    inv_stddev: Vec<f32>,
+    /// LFR 拼接帧数
+    lfr_window: usize,
+    /// LFR 帧移
+    lfr_shift: usize,
+    /// 识别语言对应的模型语言 ID
+    language_id: i32,
+    /// 启用逆文本正则化（输出标点和阿拉伯数字）的 ID
+    with_itn_id: i32,
+    /// 模型
+    model: SenseVoiceModel,
+    /// 语言（如 "zh", "en", "auto"）
+    language: String,
+}
+
+impl SenseVoiceTranscriber {
+    /// 本地 SenseVoice 的能力
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: false,
+        timestamps: false,
+        hotwords: false,
+        punctuation: true,
+        code_switching: true,
+        diarization: false,
+        max_duration_secs: None,
+        formats: &[AudioFormat::Pcm],
+        sample_rates: &[16000],
+    };
+
+    /// 创建新的 SenseVoice 识别器
+    ///
+    /// # 参数
+    /// - `model_dir`: 模型目录，包含模型文件和 `tokens.txt`
+    /// - `model`: 模型精度
+    /// - `language`: 语言代码（"zh", "en", "yue", "ja", "ko", "auto"，
+    ///   中英混合 [`MIXED_LANGUAGE`] 按自动检测处理）
+    pub fn new(model_dir: &Path, model: SenseVoiceModel, language: &str) -> Result<Self> {
+        let model_error = |e: String| VoiceError::SenseVoiceModelError(e);
+
+        let threads = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(MAX_THREADS);
+        let session = Session::builder()
+            .and_then(|b| b.with_optimization_level(GraphOptimizationLevel::Level3))
+            .and_then(|b| b.with_intra_threads(threads))
+            .and_then(|b| b.commit_from_file(model_dir.join(model.filename())))
+            .map_err(|e| model_error(e.to_string()))?;
+
+        let metadata = session.metadata().map_err(|e| model_error(e.to_string()))?;
+        let custom = |key: &str| -> Result<String> {
+            metadata
+                .custom(key)
+                .map_err(|e| model_error(e.to_string()))?
+                .ok_or_else(|| model_error(format!("模型元数据缺少 {}", key)))
+        };
+        let custom_int = |key: &str| -> Result<i32> {
+            custom(key)?
+                .trim()
+                .parse()
+                .map_err(|_| model_error(format!("模型元数据 {} 不是整数", key)))
+        };
+        let custom_floats = |key: &str| -> Result<Vec<f32>> {
+            custom(key)?
+                .split(',')
+                .map(|v| v.trim().parse())
+                .collect::<std::result::Result<_, _>>()
+                .map_err(|_| model_error(format!("模型元数据 {} 格式错误", key)))
+        };
+
+        let lfr_window = custom_int("lfr_window_size")? as usize;
+        let lfr_shift = custom_int("lfr_window_shift")? as usize;
+        let neg_mean = custom_floats("neg_mean")?;
+        let inv_stddev = custom_floats("inv_stddev")?;
+        let feature_dim = lfr_window * NUM_MEL_BINS;
+        if neg_mean.len() != feature_dim || inv_stddev.len() != feature_dim {
+            return Err(model_error(format!(
+                "CMVN 维数 {} 与特征维数 {} 不一致",
+                neg_mean.len(),
+                feature_dim
+            )));
+        }
+
+        // 未知语言按自动检测处理
+        let code = match normalize_language(language).as_str() {
+            MIXED_LANGUAGE => "auto".to_string(),
+            code => code.to_string(),
+        };
+        let language_id = match custom_int(&format!("lang_{}", code)) {
+            Ok(id) => id,
+            Err(_) => custom_int("lang_auto")?,
+        };
+        let with_itn_id = custom_int("with_itn")?;
+        drop(metadata);
+
+        let tokens = load_tokens(&model_dir.join(SenseVoiceModel::TOKENS_FILENAME))?;
+
+        Ok(Self {
+            session,
+            fbank: Fbank::new(),
+            tokens,
+            neg_mean,
+            inv_stddev,
+            lfr_window,
+            lfr_shift,
+            language_id,
+            with_itn_id,
+            model,
+            language: language.to_string(),
+        })
+    }
+
+    /// 识别音频
+    pub fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        if audio.sample_rate != SAMPLE_RATE || audio.channels != 1 {
+            return Err(VoiceError::AudioFormatError(format!(
+                "SenseVoice 需要 {}Hz 单声道音频，实际为 {}Hz {} 声道",
+                SAMPLE_RATE, audio.sample_rate, audio.channels
+            )));
+        }
+
+        let samples: Vec<f32> = audio.samples.iter().map(|&s| s as f32).collect();
+        let mut features = apply_lfr(
+            &self.fbank.compute(&samples),
+            self.lfr_window,
+            self.lfr_shift,
+        );
+        if features.is_empty() {
+            return Err(VoiceError::RecordingTooShort);
+        }
+        for frame in &mut features {
+            let cmvn = self.neg_mean.iter().zip(&self.inv_stddev);
+            for (value, (mean, scale)) in frame.iter_mut().zip(cmvn) {
+                *value = (*value + mean) * scale;
+            }
+        }
+
+        let num_frames = features.len();
+        let (vocab_size, logits) = self
+            .run(features.concat(), num_frames)
+            .map_err(|e| VoiceError::TranscriberError(e.to_string()))?;
+        if vocab_size == 0 {
+            return Err(VoiceError::TranscriberError(
+                "模型输出的 logits 为空".to_string(),
+            ));
+        }
+
+        let (text, detected) = self.decode(&logits, vocab_size);
+
+        // 指定了语言时直接使用，自动检测时取模型输出的语言标签
+        let language = match self.language.as_str() {
+            MIXED_LANGUAGE => None,
+            "auto" => detected,
+            language => Some(language.to_string()),
+        };
+
+        Ok(TranscribeResult {
+            text,
+            language,
+            confidence: None,
+            segments: Vec::new(),
+            backend: None,
+        })
+    }
+
+    /// 运行模型，返回词表大小和 CTC logits
+    fn run(&self, features: Vec<f32>, num_frames: usize) -> ort::Result<(usize, Vec<f32>)> {
+        let feature_dim = self.neg_mean.len();
+        let inputs = ort::inputs![
+            "x" => Tensor::from_array(([1, num_frames, feature_dim], features))?,
+            "x_length" => Tensor::from_array(([1], vec![num_frames as i32]))?,
+            "language" => Tensor::from_array(([1], vec![self.language_id]))?,
+            "text_norm" => Tensor::from_array(([1], vec![self.with_itn_id]))?,
+        ]?;
+        let outputs = self.session.run(inputs)?;
+        let (shape, logits) = outputs["logits"].try_extract_raw_tensor::<f32>()?;
+        Ok((shape.last().copied().unwrap_or(0) as usize, logits.to_vec()))
+    }
+
+    /// CTC 贪心解码，返回文本和模型输出的语言
+    fn decode(&self, logits: &[f32], vocab_size: usize) -> (String, Option<String>) {
+        let mut text = String::new();
+        let mut language = None;
+        let mut previous = BLANK_ID;
+
+        for frame in logits.chunks_exact(vocab_size) {
+            let id = frame
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .map_or(BLANK_ID, |(id, _)| id);
+            if id != BLANK_ID && id != previous {
+                let token = self.tokens.get(id).map(String::as_str).unwrap_or_default();
+                match token.strip_prefix("<|").and_then(|t| t.strip_suffix("|>")) {
+                    // 第一个标签是语言，其余为情感、事件等
+                    Some(tag) => {
+                        if language.is_none() {
+                            language = Some(tag.to_string()).filter(|t| t != "nospeech");
+                        }
+                    }
+                    None => text.push_str(token),
+                }
+            }
+            previous = id;
+        }
+
+        (text.replace('\u{2581}', " ").trim().to_string(), language)
+    }
+
+    /// 获取模型
+    pub fn model(&self) -> SenseVoiceModel {
+        self.model
+    }
+
+    /// 获取语言设置
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+}
+
+/// 读取词表（每行为 `符号 ID`）
+fn load_tokens(path: &Path) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        VoiceError::SenseVoiceModelError(format!("读取词表 {} 失败: {}", path.display(), e))
+    })?;
+
+    let mut entries = HashMap::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let parsed = line
+            .rsplit_once(char::is_whitespace)
+            .and_then(|(token, id)| Some((id.trim().parse::<usize>().ok()?, token.to_string())));
+        match parsed {
+            Some((id, token)) => {
+                entries.insert(id, token);
+            }
+            None => {
+                return Err(VoiceError::SenseVoiceModelError(format!(
+                    "词表格式错误: {}",
+                    line
+                )))
+            }
+        }
+    }
+
+    let size = entries.keys().max().map_or(0, |max| max + 1);
+    Ok((0..size)
+        .map(|id| entries.remove(&id).unwrap_or_default())
+        .collect())
+}
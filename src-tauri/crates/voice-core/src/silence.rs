@@ -0,0 +1,119 @@
+//! 首尾静音裁剪
+//!
+//! 识别前去掉录音开头和结尾的静音，减少云端 ASR 计费时长并加快本地 Whisper 识别。
+//! 按 10ms 分帧计算 RMS，首个和最后一个高于阈值的帧之外的部分被裁掉，
+//! 两端各保留一段余量，避免切掉轻声的起音和尾音。
+//!
+//! 同时提供按停顿切分长音频的工具，供单次时长受限的实时接口分段识别。
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::level::AudioLevel;
+use crate::types::AudioData;
+
+/// 分帧时长（毫秒）
+const FRAME_MS: u32 = 10;
+/// 切分长音频时，在每段末尾的这段时间内寻找最安静的位置（秒）
+const SPLIT_SEARCH_SECS: f32 = 5.0;
+
+/// 静音裁剪参数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SilenceTrimOptions {
+    /// 静音阈值（dBFS），帧 RMS 低于此值视为静音
+    pub threshold_dbfs: f32,
+    /// 两端保留的余量（毫秒）
+    pub padding_ms: u32,
+}
+
+impl SilenceTrimOptions {
+    /// 默认静音阈值（dBFS）
+    pub const DEFAULT_THRESHOLD_DBFS: f32 = -50.0;
+    /// 默认余量（毫秒）
+    pub const DEFAULT_PADDING_MS: u32 = 200;
+}
+
+impl Default for SilenceTrimOptions {
+    fn default() -> Self {
+        Self {
+            threshold_dbfs: Self::DEFAULT_THRESHOLD_DBFS,
+            padding_ms: Self::DEFAULT_PADDING_MS,
+        }
+    }
+}
+
+/// 计算需要保留的采样区间
+///
+/// 全部为静音时返回 `None`，由调用方决定是否仍然送去识别
+pub fn speech_range(
+    samples: &[i16],
+    sample_rate: u32,
+    options: &SilenceTrimOptions,
+) -> Option<Range<usize>> {
+    let frame_len = (sample_rate * FRAME_MS / 1000).max(1) as usize;
+    let is_voiced =
+        |frame: &[i16]| AudioLevel::measure_i16(frame).rms_dbfs >= options.threshold_dbfs;
+
+    let first = samples.chunks(frame_len).position(is_voiced)?;
+    let last = samples.chunks(frame_len).rposition(is_voiced)?;
+
+    let padding = (sample_rate as u64 * options.padding_ms as u64 / 1000) as usize;
+    let start = (first * frame_len).saturating_sub(padding);
+    let end = ((last + 1) * frame_len)
+        .saturating_add(padding)
+        .min(samples.len());
+    Some(start..end)
+}
+
+impl AudioData {
+    /// 裁剪首尾静音，返回裁剪掉的时长（秒）
+    ///
+    /// 仅支持单声道；全部为静音时保持原样
+    pub fn trim_silence(&mut self, options: &SilenceTrimOptions) -> f32 {
+        if self.channels != 1 {
+            return 0.0;
+        }
+        let Some(range) = speech_range(&self.samples, self.sample_rate, options) else {
+            return 0.0;
+        };
+        if range.start == 0 && range.end == self.samples.len() {
+            return 0.0;
+        }
+
+        let before = self.duration_secs;
+        self.samples.truncate(range.end);
+        self.samples.drain(..range.start);
+        self.duration_secs = self.samples.len() as f32 / self.sample_rate as f32;
+        before - self.duration_secs
+    }
+}
+
+/// 按停顿把长音频切分为不超过 `max_secs` 的若干段
+///
+/// 每段在末尾 5 秒内选择 RMS 最低的帧作为切分点，尽量不把一句话切成两半
+pub fn split_at_pauses(samples: &[i16], sample_rate: u32, max_secs: f32) -> Vec<Range<usize>> {
+    let frame_len = (sample_rate * FRAME_MS / 1000).max(1) as usize;
+    let max_len = ((max_secs * sample_rate as f32) as usize).max(frame_len);
+    let search_len = ((SPLIT_SEARCH_SECS * sample_rate as f32) as usize).min(max_len / 2);
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while samples.len() - start > max_len {
+        let limit = start + max_len;
+        let search_start = limit - search_len;
+        let split = samples[search_start..limit]
+            .chunks_exact(frame_len)
+            .map(|frame| AudioLevel::measure_i16(frame).rms_dbfs)
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| search_start + i * frame_len + frame_len / 2)
+            .unwrap_or(limit);
+        ranges.push(start..split);
+        start = split;
+    }
+    if start < samples.len() {
+        ranges.push(start..samples.len());
+    }
+    ranges
+}
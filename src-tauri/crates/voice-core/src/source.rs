@@ -0,0 +1,273 @@
+//! 音频源
+//!
+//! [`AudioRecorder`](crate::recorder::AudioRecorder) 通过 [`AudioSource`] 获取采样：
+//! 实际使用时是麦克风（[`CpalSource`]），测试和插件开发时可以用 [`WavFileSource`]
+//! 回放 WAV 文件，无需音频硬件即可走通录音 → 识别 → 润色 → 输出的完整流程。
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+
+use crate::error::{Result, VoiceError};
+use crate::recorder::{DEFAULT_CHANNELS, DEFAULT_SAMPLE_RATE};
+use crate::ring_buffer::Producer;
+use crate::types::AudioData;
+
+/// 回放时每块的时长（毫秒）
+const PLAYBACK_BLOCK_MS: u32 = 10;
+/// 环形缓冲区已满时等待处理线程取走数据的间隔
+const BACKPRESSURE_INTERVAL: Duration = Duration::from_millis(1);
+
+/// 音频源输出的采样格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceFormat {
+    /// 采样率
+    pub sample_rate: u32,
+    /// 声道数
+    pub channels: u16,
+}
+
+/// 音频源
+///
+/// 向录音环形缓冲区写入交错的 f32 采样（-1.0 ~ 1.0）
+pub trait AudioSource {
+    /// 采样格式，录音器据此创建环形缓冲区和计算时长
+    fn format(&self) -> SourceFormat;
+
+    /// 开始采集，把采样写入 `producer`
+    fn start(&mut self, producer: Producer<f32>) -> Result<()>;
+
+    /// 停止采集并释放 `producer`，返回后不会再写入
+    fn stop(&mut self);
+}
+
+/// 麦克风（cpal）
+pub struct CpalSource {
+    /// 输入设备，为空时开始采集时使用系统默认设备
+    device: Option<cpal::Device>,
+    /// 采样格式
+    format: SourceFormat,
+    /// 音频流（采集时持有）
+    stream: Option<cpal::Stream>,
+}
+
+impl Default for CpalSource {
+    fn default() -> Self {
+        Self {
+            device: None,
+            format: SourceFormat {
+                sample_rate: DEFAULT_SAMPLE_RATE,
+                channels: DEFAULT_CHANNELS,
+            },
+            stream: None,
+        }
+    }
+}
+
+impl CpalSource {
+    /// 系统默认麦克风，按 ASR 标准格式（16kHz 单声道）采集
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按设备 ID（设备名称）打开麦克风，使用设备默认的采样格式
+    ///
+    /// 找不到指定设备时回退到系统默认设备
+    pub fn open(device_id: Option<&str>) -> Result<Self> {
+        let host = cpal::default_host();
+        let device = match device_id {
+            Some(id) => host
+                .input_devices()
+                .ok()
+                .and_then(|mut devices| devices.find(|d| d.name().ok().as_deref() == Some(id)))
+                .or_else(|| {
+                    tracing::warn!("未找到指定设备 {}，使用默认设备", id);
+                    host.default_input_device()
+                }),
+            None => host.default_input_device(),
+        }
+        .ok_or(VoiceError::NoMicrophoneFound)?;
+
+        let config = device
+            .default_input_config()
+            .map_err(|e| VoiceError::RecorderError(format!("获取音频配置失败: {}", e)))?;
+        tracing::info!(
+            "设备支持配置: 采样率={}, 声道={}",
+            config.sample_rate().0,
+            config.channels()
+        );
+
+        Ok(Self {
+            device: Some(device),
+            format: SourceFormat {
+                sample_rate: config.sample_rate().0,
+                channels: config.channels(),
+            },
+            stream: None,
+        })
+    }
+}
+
+impl AudioSource for CpalSource {
+    fn format(&self) -> SourceFormat {
+        self.format
+    }
+
+    fn start(&mut self, mut producer: Producer<f32>) -> Result<()> {
+        let default_device;
+        let device = match &self.device {
+            Some(device) => device,
+            None => {
+                default_device = cpal::default_host()
+                    .default_input_device()
+                    .ok_or(VoiceError::NoMicrophoneFound)?;
+                &default_device
+            }
+        };
+
+        tracing::info!("使用麦克风: {:?}", device.name());
+
+        // 配置音频格式
+        let config = cpal::StreamConfig {
+            channels: self.format.channels,
+            sample_rate: cpal::SampleRate(self.format.sample_rate),
+            buffer_size: cpal::BufferSize::Default,
+        };
+
+        // 创建输入流（实时线程只写入环形缓冲区）
+        let stream = device
+            .build_input_stream(
+                &config,
+                move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                    producer.push(data);
+                },
+                |err| {
+                    tracing::error!("录音流错误: {}", err);
+                },
+                None,
+            )
+            .map_err(|e| VoiceError::RecorderError(format!("创建音频流失败: {}", e)))?;
+
+        stream
+            .play()
+            .map_err(|e| VoiceError::RecorderError(format!("启动录音失败: {}", e)))?;
+
+        self.stream = Some(stream);
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        self.stream.take();
+    }
+}
+
+/// WAV 文件回放
+///
+/// 多声道会混合为单声道。默认尽快写入（缓冲区满时等待处理线程），
+/// [`realtime`](Self::realtime) 时按音频实际时长回放，用于测试录音时长、
+/// 中途停止和流式识别等与时间相关的逻辑
+pub struct WavFileSource {
+    audio: Arc<AudioData>,
+    realtime: bool,
+    stop_flag: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl WavFileSource {
+    /// 读取 WAV 文件
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = std::fs::read(path.as_ref())?;
+        Ok(Self::from_audio(AudioData::from_wav_bytes(&bytes)?))
+    }
+
+    /// 回放内存中的音频
+    pub fn from_audio(audio: AudioData) -> Self {
+        Self {
+            audio: Arc::new(audio),
+            realtime: false,
+            stop_flag: Arc::new(AtomicBool::new(false)),
+            handle: None,
+        }
+    }
+
+    /// 是否按实际时长回放
+    ///
+    /// 非实时回放时 [`stop`](AudioSource::stop) 会等待整个文件写入完毕；
+    /// 实时回放时立即停止，录音只包含已回放的部分
+    pub fn realtime(mut self, realtime: bool) -> Self {
+        self.realtime = realtime;
+        self
+    }
+}
+
+impl AudioSource for WavFileSource {
+    fn format(&self) -> SourceFormat {
+        SourceFormat {
+            sample_rate: self.audio.sample_rate,
+            channels: self.audio.channels,
+        }
+    }
+
+    fn start(&mut self, mut producer: Producer<f32>) -> Result<()> {
+        self.stop();
+        self.stop_flag.store(false, Ordering::Release);
+
+        let audio = Arc::clone(&self.audio);
+        let realtime = self.realtime;
+        let stop_flag = Arc::clone(&self.stop_flag);
+        let block_len = (audio.sample_rate * PLAYBACK_BLOCK_MS / 1000) as usize
+            * audio.channels.max(1) as usize;
+
+        let handle = std::thread::Builder::new()
+            .name("voice-wav-source".to_string())
+            .spawn(move || {
+                let start = Instant::now();
+                let mut block = Vec::with_capacity(block_len);
+
+                for (i, chunk) in audio.samples.chunks(block_len.max(1)).enumerate() {
+                    if realtime {
+                        if stop_flag.load(Ordering::Acquire) {
+                            break;
+                        }
+                        // 按块对齐到实际时间
+                        let due = Duration::from_millis(i as u64 * PLAYBACK_BLOCK_MS as u64);
+                        if let Some(wait) = due.checked_sub(start.elapsed()) {
+                            std::thread::sleep(wait);
+                        }
+                    }
+
+                    block.clear();
+                    block.extend(chunk.iter().map(|&s| s as f32 / i16::MAX as f32));
+                    // 非实时回放时等待处理线程取走数据，避免缓冲区满被丢弃
+                    while producer.free_len() < block.len() {
+                        if realtime && stop_flag.load(Ordering::Acquire) {
+                            return;
+                        }
+                        std::thread::sleep(BACKPRESSURE_INTERVAL);
+                    }
+                    producer.push(&block);
+                }
+            })
+            .map_err(|e| VoiceError::RecorderError(e.to_string()))?;
+
+        self.handle = Some(handle);
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        self.stop_flag.store(true, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for WavFileSource {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
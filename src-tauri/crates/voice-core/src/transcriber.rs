@@ -1,13 +1,18 @@
 //! Whisper 本地语音识别模块
 //!
-//! 使用 whisper-rs 进行本地语音识别。
+//! 使用 whisper-rs 进行本地语音识别。支持翻译模式：任意语言的语音直接输出英文文本。
 
 use std::path::PathBuf;
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
+use crate::asr_client::{AsrCapabilities, AudioFormat};
 use crate::error::{Result, VoiceError};
+use crate::language::MIXED_LANGUAGE;
 use crate::types::{AudioData, Segment, TranscribeResult, WhisperModel};
 
+/// 中英混合识别的提示词
+const MIXED_PROMPT: &str = "我们用 Python 写了一个 API，明天 review 一下 PR。";
+
 /// Whisper 识别器
 pub struct WhisperTranscriber {
     /// Whisper 上下文
@@ -19,12 +24,25 @@ pub struct WhisperTranscriber {
 }
 
 impl WhisperTranscriber {
+    /// 本地 Whisper 的能力
+    pub const CAPABILITIES: AsrCapabilities = AsrCapabilities {
+        streaming: false,
+        timestamps: true,
+        hotwords: false,
+        punctuation: true,
+        code_switching: true,
+        diarization: false,
+        max_duration_secs: None,
+        formats: &[AudioFormat::Pcm],
+        sample_rates: &[16000],
+    };
+
     /// 创建新的 Whisper 识别器
     ///
     /// # 参数
     /// - `model_path`: 模型文件路径
     /// - `model`: 模型大小
-    /// - `language`: 语言代码（"zh", "en", "auto"）
+    /// - `language`: 语言代码（"zh", "en", "auto"，中英混合为 [`MIXED_LANGUAGE`]）
     pub fn new(model_path: PathBuf, model: WhisperModel, language: &str) -> Result<Self> {
         let ctx = WhisperContext::new_with_params(
             model_path.to_str().unwrap_or_default(),
@@ -41,6 +59,14 @@ impl WhisperTranscriber {
 
     /// 识别音频
     pub fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        self.transcribe_with(audio, false)
+    }
+
+    /// 识别音频，`translate` 为 `true` 时直接输出英文译文
+    ///
+    /// 翻译模式下结果语言为 `en`；中英混合设置按中文识别，不使用混合提示词，
+    /// 避免提示词把输出引向中文
+    pub fn transcribe_with(&self, audio: &AudioData, translate: bool) -> Result<TranscribeResult> {
         // 转换为 f32 采样
         let samples: Vec<f32> = audio
             .samples
@@ -52,7 +78,13 @@ impl WhisperTranscriber {
         let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
 
         // 设置语言
-        if self.language != "auto" {
+        if self.language == MIXED_LANGUAGE {
+            // 按中文识别，并用中英混杂的提示词引导模型保留英文单词原文
+            params.set_language(Some("zh"));
+            if !translate {
+                params.set_initial_prompt(MIXED_PROMPT);
+            }
+        } else if self.language != "auto" {
             params.set_language(Some(&self.language));
         }
 
@@ -61,7 +93,7 @@ impl WhisperTranscriber {
         params.set_print_progress(false);
         params.set_print_realtime(false);
         params.set_print_timestamps(false);
-        params.set_translate(false);
+        params.set_translate(translate);
         params.set_no_context(true);
         params.set_single_segment(false);
 
@@ -90,12 +122,18 @@ impl WhisperTranscriber {
                     start,
                     end,
                     text: segment_text,
+                    speaker: None,
                 });
             }
         }
 
         // 检测语言
-        let detected_language = if self.language == "auto" {
+        // 翻译模式输出英文；中英混合时不确定主要语言，由调用方按文本判断
+        let detected_language = if translate {
+            Some("en".to_string())
+        } else if self.language == MIXED_LANGUAGE {
+            None
+        } else if self.language == "auto" {
             state
                 .full_lang_id_from_state()
                 .ok()
@@ -109,6 +147,7 @@ impl WhisperTranscriber {
             language: detected_language,
             confidence: None,
             segments,
+            backend: None,
         })
     }
 
@@ -53,6 +53,41 @@ impl AudioData {
 
         cursor.into_inner()
     }
+
+    /// 从 WAV 字节解析音频，多声道会混合为单声道
+    ///
+    /// 支持 8/16/24/32-bit 整数和 32-bit 浮点 PCM
+    pub fn from_wav_bytes(bytes: &[u8]) -> crate::error::Result<Self> {
+        let reader = hound::WavReader::new(std::io::Cursor::new(bytes))
+            .map_err(|e| crate::error::VoiceError::AudioFormatError(e.to_string()))?;
+        let spec = reader.spec();
+        let channels = spec.channels.max(1) as usize;
+
+        let interleaved: Vec<f32> = match spec.sample_format {
+            hound::SampleFormat::Float => reader
+                .into_samples::<f32>()
+                .collect::<std::result::Result<_, _>>()
+                .map_err(|e| crate::error::VoiceError::AudioFormatError(e.to_string()))?,
+            hound::SampleFormat::Int => {
+                let scale = (1i64 << (spec.bits_per_sample.clamp(1, 32) - 1)) as f32;
+                reader
+                    .into_samples::<i32>()
+                    .map(|s| s.map(|v| v as f32 / scale))
+                    .collect::<std::result::Result<_, _>>()
+                    .map_err(|e| crate::error::VoiceError::AudioFormatError(e.to_string()))?
+            }
+        };
+
+        let samples = interleaved
+            .chunks(channels)
+            .map(|frame| {
+                let mixed = frame.iter().sum::<f32>() / frame.len() as f32;
+                (mixed.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+            })
+            .collect();
+
+        Ok(Self::new(samples, spec.sample_rate, 1))
+    }
 }
 
 /// 识别结果
@@ -66,6 +101,53 @@ pub struct TranscribeResult {
     pub confidence: Option<f32>,
     /// 分段信息
     pub segments: Vec<Segment>,
+    /// 产出结果的识别服务（经过 [`AsrFallbackChain`] 时记录，直接调用客户端时为空）
+    ///
+    /// [`AsrFallbackChain`]: crate::asr_client::AsrFallbackChain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+}
+
+impl TranscribeResult {
+    /// 分段中出现的说话人数，未区分说话人时为 0
+    pub fn speaker_count(&self) -> usize {
+        let mut speakers: Vec<u32> = self.segments.iter().filter_map(|s| s.speaker).collect();
+        speakers.sort_unstable();
+        speakers.dedup();
+        speakers.len()
+    }
+}
+
+/// 流式识别中间结果
+#[derive(Debug, Clone, Serialize)]
+pub struct PartialTranscript {
+    /// 当前完整的识别文本（动态修正可能改写之前的内容）
+    pub text: String,
+    /// 是否为最终结果
+    pub is_final: bool,
+    /// 最终识别结果，仅最后一项有值
+    #[serde(skip)]
+    pub result: Option<TranscribeResult>,
+}
+
+impl PartialTranscript {
+    /// 创建中间结果
+    pub fn partial(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            is_final: false,
+            result: None,
+        }
+    }
+
+    /// 由最终识别结果创建
+    pub fn finished(result: TranscribeResult) -> Self {
+        Self {
+            text: result.text.clone(),
+            is_final: true,
+            result: Some(result),
+        }
+    }
 }
 
 /// 识别分段
@@ -77,6 +159,9 @@ pub struct Segment {
     pub end: f32,
     /// 文本内容
     pub text: String,
+    /// 说话人编号（从 0 开始），服务未区分说话人时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<u32>,
 }
 
 /// ASR 引擎类型
@@ -122,6 +207,29 @@ impl WhisperModel {
     }
 }
 
+/// SenseVoice 模型精度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SenseVoiceModel {
+    /// int8 量化 - 体积小，速度快
+    Int8,
+    /// float32 - 原始精度
+    Float32,
+}
+
+impl SenseVoiceModel {
+    /// 词表文件名（两种精度共用）
+    pub const TOKENS_FILENAME: &'static str = "tokens.txt";
+
+    /// 获取模型文件名
+    pub fn filename(&self) -> &'static str {
+        match self {
+            Self::Int8 => "model.int8.onnx",
+            Self::Float32 => "model.onnx",
+        }
+    }
+}
+
 /// 输出模式
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
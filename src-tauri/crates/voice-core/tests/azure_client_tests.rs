@@ -0,0 +1,154 @@
+//! Azure 语音服务客户端集成测试
+//!
+//! 在本地端口启动一个只响应一次的 HTTP 服务模拟短音频识别和快速转写接口，不需要网络。
+//!
+//! ```bash
+//! cargo test --package voice-core --test azure_client_tests
+//! ```
+
+mod common;
+
+use voice_core::asr_client::factory::azure_locale;
+use voice_core::asr_client::{
+    create_client, AsrClient, AsrClientConfig, AzureProfanity, AzureSpeechClient,
+};
+use voice_core::VoiceError;
+
+use common::{audio, serve_once};
+
+const KEY: &str = "azure-key";
+
+#[tokio::test]
+async fn test_short_audio_request() {
+    let (url, server) = serve_once(
+        "200 OK",
+        r#"{"RecognitionStatus": "Success", "Offset": 0, "Duration": 10000000,
+            "DisplayText": "Hello world.",
+            "NBest": [{"Confidence": 0.92, "Lexical": "hello world", "Display": "Hello, world."}]}"#,
+    )
+    .await;
+
+    let client = AzureSpeechClient::new(KEY.to_string(), "eastasia".to_string())
+        .with_endpoint(url)
+        .with_language("en-US".to_string())
+        .with_profanity(AzureProfanity::Removed);
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "Hello, world.");
+    assert_eq!(result.language.as_deref(), Some("en"));
+    assert_eq!(result.confidence, Some(0.92));
+
+    let request = server.await.unwrap();
+    assert_eq!(
+        request.request_line(),
+        "POST /speech/recognition/conversation/cognitiveservices/v1?language=en-US&format=detailed&profanity=removed HTTP/1.1"
+    );
+    assert_eq!(request.header("ocp-apim-subscription-key"), Some(KEY));
+    assert_eq!(
+        request.header("content-type"),
+        Some("audio/wav; codecs=audio/pcm; samplerate=16000")
+    );
+    assert_eq!(&request.body[..4], b"RIFF");
+}
+
+#[tokio::test]
+async fn test_no_match_and_errors() {
+    let (url, _server) = serve_once(
+        "200 OK",
+        r#"{"RecognitionStatus": "InitialSilenceTimeout", "Offset": 0, "Duration": 0}"#,
+    )
+    .await;
+    let client = AzureSpeechClient::new(KEY.to_string(), "eastasia".to_string()).with_endpoint(url);
+    assert_eq!(client.transcribe(&audio()).await.unwrap().text, "");
+
+    let (url, _server) = serve_once("401 Unauthorized", r#"{"error": "invalid key"}"#).await;
+    let client = AzureSpeechClient::new(KEY.to_string(), "eastasia".to_string()).with_endpoint(url);
+    assert!(matches!(
+        client.transcribe(&audio()).await,
+        Err(VoiceError::AsrAuthError(_))
+    ));
+
+    let (url, _server) = serve_once("200 OK", r#"{"RecognitionStatus": "Error"}"#).await;
+    let client = AzureSpeechClient::new(KEY.to_string(), "eastasia".to_string()).with_endpoint(url);
+    assert!(matches!(
+        client.transcribe(&audio()).await,
+        Err(VoiceError::AsrError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_fast_transcription_with_speakers() {
+    let (url, server) = serve_once(
+        "200 OK",
+        r#"{"durationMilliseconds": 4000,
+            "combinedPhrases": [{"text": "你好。你好，请坐。"}],
+            "phrases": [
+              {"offsetMilliseconds": 0, "durationMilliseconds": 1200, "text": "你好。",
+               "confidence": 0.9, "speaker": 1, "locale": "zh-CN"},
+              {"offsetMilliseconds": 1500, "durationMilliseconds": 2000, "text": "你好，请坐。",
+               "confidence": 0.7, "speaker": 2, "locale": "zh-CN"}
+            ]}"#,
+    )
+    .await;
+
+    let client = AzureSpeechClient::new(KEY.to_string(), "eastasia".to_string())
+        .with_endpoint(url)
+        .with_language("zh-CN".to_string())
+        .with_diarization(true);
+    assert!(client.capabilities().diarization);
+    assert!(client.capabilities().timestamps);
+
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "你好。你好，请坐。");
+    assert_eq!(result.language.as_deref(), Some("zh"));
+    assert!((result.confidence.unwrap() - 0.8).abs() < 1e-4);
+    assert_eq!(result.speaker_count(), 2);
+
+    // 说话人编号从 0 开始
+    let second = &result.segments[1];
+    assert_eq!(second.speaker, Some(1));
+    assert!((second.start - 1.5).abs() < 1e-4);
+    assert!((second.end - 3.5).abs() < 1e-4);
+
+    let request = server.await.unwrap();
+    assert_eq!(
+        request.request_line(),
+        "POST /speechtotext/transcriptions:transcribe?api-version=2024-11-15 HTTP/1.1"
+    );
+    assert_eq!(request.header("ocp-apim-subscription-key"), Some(KEY));
+    assert!(request
+        .header("content-type")
+        .unwrap()
+        .starts_with("multipart/form-data"));
+    let body = String::from_utf8_lossy(&request.body);
+    assert!(body.contains(r#""diarization":{"enabled":true"#));
+    assert!(body.contains(r#""locales":["zh-CN"]"#));
+    assert!(body.contains(r#""profanityFilterMode":"Masked""#));
+}
+
+#[test]
+fn test_config_and_locale() {
+    let config = |region: &str, diarize: bool| AsrClientConfig::Azure {
+        subscription_key: KEY.to_string(),
+        region: region.to_string(),
+        locale: None,
+        profanity: AzureProfanity::Masked,
+        diarize,
+        language: "zh".to_string(),
+    };
+    assert!(matches!(
+        create_client(&config("", false)),
+        Err(VoiceError::ConfigError(_))
+    ));
+    assert_eq!(
+        create_client(&config("eastasia", false)).unwrap().name(),
+        "Azure 语音"
+    );
+    assert!(!config("eastasia", false).capabilities().diarization);
+    assert!(config("eastasia", true).capabilities().diarization);
+
+    assert_eq!(azure_locale("zh"), "zh-CN");
+    assert_eq!(azure_locale("en"), "en-US");
+    assert_eq!(azure_locale("mixed"), "zh-CN");
+    assert_eq!(azure_locale("auto"), "zh-CN");
+    assert_eq!(azure_locale("en-GB"), "en-GB");
+}
@@ -0,0 +1,166 @@
+//! 百度语音识别客户端集成测试
+//!
+//! 在本地端口启动 HTTP 服务模拟百度的 Token 接口和短语音识别接口，不需要网络。
+//! 验证 Access Token 在客户端之间共用、到期前刷新、鉴权失败后重新获取。
+//!
+//! ```bash
+//! cargo test --package voice-core --test baidu_client_tests
+//! ```
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use voice_core::asr_client::{AsrClient, BaiduClient};
+use voice_core::types::AudioData;
+use voice_core::VoiceError;
+
+fn audio() -> AudioData {
+    AudioData::new(vec![0; 16000], 16000, 1)
+}
+
+/// 模拟的百度接口
+struct MockBaidu {
+    url: String,
+    /// 已签发的 Token 数
+    issued: Arc<AtomicUsize>,
+    /// 识别请求携带的 Token
+    used: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockBaidu {
+    /// 启动服务：Token 有效期为 `expires_in` 秒，携带 `rejected` 的识别请求返回 Token 无效
+    async fn start(expires_in: u64, rejected: Option<&'static str>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let issued = Arc::new(AtomicUsize::new(0));
+        let used = Arc::new(Mutex::new(Vec::new()));
+
+        let (issued_clone, used_clone) = (issued.clone(), used.clone());
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let (head, body) = read_request(&mut stream).await;
+                let response = if head.starts_with("POST /oauth/2.0/token") {
+                    let n = issued_clone.fetch_add(1, Ordering::SeqCst) + 1;
+                    format!(
+                        r#"{{"access_token": "token-{}", "expires_in": {}}}"#,
+                        n, expires_in
+                    )
+                } else {
+                    let request: serde_json::Value = serde_json::from_str(&body).unwrap();
+                    let token = request["token"].as_str().unwrap().to_string();
+                    used_clone.lock().unwrap().push(token.clone());
+                    if Some(token.as_str()) == rejected {
+                        r#"{"err_no": 3302, "err_msg": "Authentication failed.", "result": []}"#
+                            .to_string()
+                    } else {
+                        r#"{"err_no": 0, "err_msg": "success.", "result": ["你好"]}"#.to_string()
+                    }
+                };
+                let reply = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                );
+                stream.write_all(reply.as_bytes()).await.unwrap();
+            }
+        });
+
+        Self { url, issued, used }
+    }
+
+    fn client(&self, api_key: &str) -> BaiduClient {
+        BaiduClient::new(api_key.to_string(), "secret".to_string()).with_endpoints(
+            format!("{}/oauth/2.0/token", self.url),
+            format!("{}/server_api", self.url),
+        )
+    }
+
+    fn issued(&self) -> usize {
+        self.issued.load(Ordering::SeqCst)
+    }
+
+    fn used(&self) -> Vec<String> {
+        self.used.lock().unwrap().clone()
+    }
+}
+
+/// 读取请求头和按 Content-Length 读取请求体
+async fn read_request(stream: &mut tokio::net::TcpStream) -> (String, String) {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 8192];
+    let (head, body_start, length) = loop {
+        let n = stream.read(&mut buf).await.unwrap();
+        data.extend_from_slice(&buf[..n]);
+        if let Some(pos) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            let head = String::from_utf8_lossy(&data[..pos]).to_string();
+            let length = head
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or(0);
+            break (head, pos + 4, length);
+        }
+    };
+    while data.len() < body_start + length {
+        let n = stream.read(&mut buf).await.unwrap();
+        data.extend_from_slice(&buf[..n]);
+    }
+    (
+        head,
+        String::from_utf8_lossy(&data[body_start..]).to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_token_shared_across_clients() {
+    let server = MockBaidu::start(2_592_000, None).await;
+
+    // 每次听写都新建客户端，同一凭证共用缓存的 Token
+    for _ in 0..3 {
+        let result = server.client("shared").transcribe(&audio()).await.unwrap();
+        assert_eq!(result.text, "你好");
+    }
+    assert_eq!(server.issued(), 1);
+    assert_eq!(server.used(), vec!["token-1"; 3]);
+
+    // 其他凭证使用自己的 Token
+    server.client("another").transcribe(&audio()).await.unwrap();
+    assert_eq!(server.issued(), 2);
+}
+
+#[tokio::test]
+async fn test_token_refreshed_before_expiry() {
+    // 有效期 1 秒，剩余不足十分之一时刷新
+    let server = MockBaidu::start(1, None).await;
+    let client = server.client("refresh");
+
+    client.transcribe(&audio()).await.unwrap();
+    client.transcribe(&audio()).await.unwrap();
+    assert_eq!(server.issued(), 1);
+
+    tokio::time::sleep(Duration::from_millis(950)).await;
+    client.transcribe(&audio()).await.unwrap();
+    assert_eq!(server.issued(), 2);
+    assert_eq!(server.used(), vec!["token-1", "token-1", "token-2"]);
+}
+
+#[tokio::test]
+async fn test_rejected_token_is_cleared() {
+    let server = MockBaidu::start(2_592_000, Some("token-1")).await;
+    let client = server.client("revoked");
+
+    let err = client.transcribe(&audio()).await.unwrap_err();
+    assert!(matches!(err, VoiceError::AsrAuthError(_)));
+
+    // 下次识别重新获取 Token
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "你好");
+    assert_eq!(server.used(), vec!["token-1", "token-2"]);
+}
@@ -0,0 +1,130 @@
+//! 麦克风电平校准测试
+
+use voice_core::calibration::{apply_gain, CalibrationResult, CalibrationSample};
+use voice_core::error::VoiceError;
+
+fn sample(median_dbfs: f32, loud_dbfs: f32, peak_dbfs: f32, clipping: bool) -> CalibrationSample {
+    CalibrationSample {
+        median_dbfs,
+        loud_dbfs,
+        peak_dbfs,
+        clipping,
+        duration_secs: 2.0,
+    }
+}
+
+#[test]
+fn test_analyze_too_short() {
+    // 16kHz 下一帧 320 个采样，不足一帧时无法分析
+    let result = CalibrationSample::analyze(&[1000; 319], 16000);
+    assert!(matches!(result, Err(VoiceError::RecordingTooShort)));
+    assert!(CalibrationSample::analyze(&[], 16000).is_err());
+}
+
+#[test]
+fn test_analyze_percentiles() {
+    // 前 80 帧静音，后 20 帧半幅方波
+    let mut samples = vec![0i16; 320 * 80];
+    samples.extend((0..320 * 20).map(|i| if i % 2 == 0 { 16384 } else { -16384 }));
+
+    let result = CalibrationSample::analyze(&samples, 16000).unwrap();
+    assert_eq!(result.median_dbfs, -96.0);
+    assert!((result.loud_dbfs - -6.0).abs() < 0.1);
+    assert!((result.peak_dbfs - -6.0).abs() < 0.1);
+    assert!(!result.clipping);
+    assert!((result.duration_secs - 2.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_analyze_ignores_partial_frame() {
+    // 末尾不足一帧的采样不参与分帧统计
+    let mut samples = vec![0i16; 320];
+    samples.extend([i16::MAX; 100]);
+    let result = CalibrationSample::analyze(&samples, 16000).unwrap();
+    assert_eq!(result.loud_dbfs, -96.0);
+    assert!(result.clipping);
+}
+
+#[test]
+fn test_compute_rejects_no_speech() {
+    let ambient = sample(-50.0, -45.0, -40.0, false);
+    // 说话电平只比噪声底高 5dB，低于 6dB 的判定余量
+    let speech = sample(-48.0, -45.0, -40.0, false);
+    let result = CalibrationResult::compute(&ambient, &speech);
+    assert!(matches!(result, Err(VoiceError::CalibrationError(_))));
+}
+
+#[test]
+fn test_compute_gain_and_threshold() {
+    let ambient = sample(-60.0, -58.0, -50.0, false);
+    let speech = sample(-45.0, -40.0, -30.0, false);
+    let result = CalibrationResult::compute(&ambient, &speech).unwrap();
+
+    assert_eq!(result.noise_floor_dbfs, -60.0);
+    assert_eq!(result.speech_level_dbfs, -40.0);
+    assert_eq!(result.snr_db, 20.0);
+    // 目标 -20dBFS，需要放大 20dB
+    assert_eq!(result.recommended_gain_db, 20.0);
+    // 阈值取中点 -50dBFS，再计入增益
+    assert_eq!(result.silence_threshold_dbfs, -30.0);
+    assert!(!result.is_noisy());
+}
+
+#[test]
+fn test_compute_gain_limits() {
+    let ambient = sample(-80.0, -78.0, -70.0, false);
+
+    // 放大不超过 24dB
+    let quiet = sample(-60.0, -55.0, -50.0, false);
+    let result = CalibrationResult::compute(&ambient, &quiet).unwrap();
+    assert_eq!(result.recommended_gain_db, 24.0);
+
+    // 峰值接近满幅时，增益使峰值保持在 -3dBFS 以下
+    let peaky = sample(-40.0, -30.0, -2.0, false);
+    let result = CalibrationResult::compute(&ambient, &peaky).unwrap();
+    assert_eq!(result.recommended_gain_db, -1.0);
+
+    // 衰减不超过 12dB
+    let loud = sample(-10.0, -2.0, -0.5, false);
+    let result = CalibrationResult::compute(&ambient, &loud).unwrap();
+    assert_eq!(result.recommended_gain_db, -12.0);
+
+    // 削波时至少衰减 3dB
+    let clipped = sample(-40.0, -30.0, 0.0, true);
+    let result = CalibrationResult::compute(&ambient, &clipped).unwrap();
+    assert_eq!(result.recommended_gain_db, -3.0);
+    assert!(result.clipping);
+}
+
+#[test]
+fn test_compute_noisy_threshold_margin() {
+    let ambient = sample(-50.0, -48.0, -40.0, false);
+    let speech = sample(-45.0, -42.0, -30.0, false);
+    let result = CalibrationResult::compute(&ambient, &speech).unwrap();
+
+    // 中点 -46dBFS 低于噪声底 + 6dB，取 -44dBFS
+    assert_eq!(
+        result.silence_threshold_dbfs,
+        -44.0 + result.recommended_gain_db
+    );
+    assert_eq!(result.snr_db, 8.0);
+    assert!(result.is_noisy());
+}
+
+#[test]
+fn test_apply_gain() {
+    let mut samples = [1000i16, -1000, 0];
+    apply_gain(&mut samples, 0.0);
+    assert_eq!(samples, [1000, -1000, 0]);
+
+    apply_gain(&mut samples, 20.0);
+    assert_eq!(samples, [10000, -10000, 0]);
+
+    apply_gain(&mut samples, -20.0);
+    assert_eq!(samples, [1000, -1000, 0]);
+
+    // 放大时饱和截断
+    let mut loud = [20000i16, -20000];
+    apply_gain(&mut loud, 12.0);
+    assert_eq!(loud, [i16::MAX, i16::MIN]);
+}
@@ -0,0 +1,120 @@
+//! 识别取消测试
+//!
+//! 验证 `transcribe_cancellable` 在令牌触发后立即返回，并丢弃进行中的请求。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use voice_core::asr_client::{AsrCapabilities, AsrClient, CancellationToken};
+use voice_core::error::{Result, VoiceError};
+use voice_core::types::{AudioData, TranscribeResult};
+
+/// 请求被丢弃时设置标记
+struct DropFlag(Arc<AtomicBool>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// 识别需要较长时间的测试客户端
+struct SlowClient {
+    delay: Duration,
+    dropped: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl AsrClient for SlowClient {
+    async fn transcribe(&self, _audio: &AudioData) -> Result<TranscribeResult> {
+        let _guard = DropFlag(self.dropped.clone());
+        tokio::time::sleep(self.delay).await;
+        Ok(TranscribeResult {
+            text: "你好".to_string(),
+            language: Some("zh".to_string()),
+            confidence: None,
+            segments: Vec::new(),
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "slow"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        AsrCapabilities {
+            streaming: false,
+            timestamps: false,
+            hotwords: false,
+            punctuation: true,
+            code_switching: false,
+            diarization: false,
+            max_duration_secs: None,
+            formats: &[],
+            sample_rates: &[],
+        }
+    }
+}
+
+fn slow_client(delay: Duration) -> (SlowClient, Arc<AtomicBool>) {
+    let dropped = Arc::new(AtomicBool::new(false));
+    let client = SlowClient {
+        delay,
+        dropped: dropped.clone(),
+    };
+    (client, dropped)
+}
+
+fn audio() -> AudioData {
+    AudioData::new(vec![0; 16000], 16000, 1)
+}
+
+#[tokio::test]
+async fn test_cancel_aborts_in_flight_request() {
+    let (client, dropped) = slow_client(Duration::from_secs(30));
+    let cancel = CancellationToken::new();
+    let trigger = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        trigger.cancel();
+    });
+
+    let started = std::time::Instant::now();
+    let err = client
+        .transcribe_cancellable(&audio(), &cancel)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, VoiceError::Cancelled));
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(dropped.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_already_cancelled_token_skips_request() {
+    let (client, dropped) = slow_client(Duration::from_secs(30));
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+
+    let err = client
+        .transcribe_cancellable(&audio(), &cancel)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, VoiceError::Cancelled));
+    // 请求从未开始
+    assert!(!dropped.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_completes_when_not_cancelled() {
+    let (client, _) = slow_client(Duration::from_millis(1));
+    let cancel = CancellationToken::new();
+
+    let result = client
+        .transcribe_cancellable(&audio(), &cancel)
+        .await
+        .unwrap();
+    assert_eq!(result.text, "你好");
+}
@@ -0,0 +1,91 @@
+//! 自定义命令 ASR 客户端集成测试
+//!
+//! 用 `sh` 脚本模拟外部识别引擎，仅在类 Unix 系统上运行。
+//!
+//! ```bash
+//! cargo test --package voice-core --test command_client_tests
+//! ```
+
+#![cfg(unix)]
+
+use std::time::{Duration, Instant};
+
+use voice_core::asr_client::{create_client, AsrClient, AsrClientConfig, CommandClient};
+use voice_core::types::AudioData;
+use voice_core::VoiceError;
+
+fn audio() -> AudioData {
+    AudioData::new(vec![0; 16000], 16000, 1)
+}
+
+/// 以 `sh -c <script>` 运行的客户端，`args` 作为脚本的位置参数
+fn sh(script: &str, args: &[&str]) -> CommandClient {
+    let mut all = vec!["-c".to_string(), script.to_string(), "sh".to_string()];
+    all.extend(args.iter().map(|a| a.to_string()));
+    CommandClient::new("sh".to_string(), all)
+}
+
+#[tokio::test]
+async fn test_wav_piped_to_stdin() {
+    // 标准输入以 WAV 文件头开头时输出结果
+    let client = sh(
+        r#"[ "$(head -c 4)" = RIFF ] && cat > /dev/null && echo '{"text": " 你好世界 ", "language": "zh-CN"}'"#,
+        &[],
+    );
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "你好世界");
+    assert_eq!(result.language.as_deref(), Some("zh"));
+}
+
+#[tokio::test]
+async fn test_file_and_language_placeholders() {
+    // 先打印日志，最后一行输出结果
+    let client = sh(
+        r#"echo "loading model"; [ -s "$1" ] && echo "{\"text\": \"ok $2\"}""#,
+        &["{file}", "{language}"],
+    )
+    .with_language("en".to_string());
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "ok en");
+    assert_eq!(result.language.as_deref(), Some("en"));
+}
+
+#[tokio::test]
+async fn test_failed_command_reports_stderr() {
+    let client = sh("echo 'model not found' >&2; exit 3", &[]);
+    let err = client.transcribe(&audio()).await.unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains('3'), "{}", message);
+    assert!(message.contains("model not found"), "{}", message);
+}
+
+#[tokio::test]
+async fn test_invalid_output_is_error() {
+    let client = sh("cat > /dev/null; echo 'not json'", &[]);
+    assert!(matches!(
+        client.transcribe(&audio()).await,
+        Err(VoiceError::AsrError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_timeout_kills_command() {
+    let client = sh("sleep 10", &[]).with_timeout(Duration::from_millis(200));
+    let start = Instant::now();
+    assert!(client.transcribe(&audio()).await.is_err());
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_factory_requires_program() {
+    let config = AsrClientConfig::Command {
+        program: " ".to_string(),
+        args: Vec::new(),
+        timeout_secs: 60,
+        language: "zh".to_string(),
+    };
+    assert!(matches!(
+        create_client(&config),
+        Err(VoiceError::ConfigError(_))
+    ));
+}
@@ -0,0 +1,96 @@
+//! 集成测试共用的模拟服务
+//!
+//! 各 ASR 客户端测试在本地端口启动只处理一次请求（或连接）的服务，不需要网络。
+
+// 每个测试文件只用到其中一部分
+#![allow(dead_code)]
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use voice_core::types::AudioData;
+
+/// 1 秒 16kHz 单声道音频
+pub fn audio() -> AudioData {
+    AudioData::new(vec![100; 16000], 16000, 1)
+}
+
+/// 绑定本地随机端口，返回监听器和以 `scheme` 开头、`path` 结尾的地址
+pub async fn bind(scheme: &str, path: &str) -> (TcpListener, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("{}://{}{}", scheme, listener.local_addr().unwrap(), path);
+    (listener, url)
+}
+
+/// 收到的请求
+pub struct Request {
+    pub head: String,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn request_line(&self) -> &str {
+        self.head.lines().next().unwrap_or_default()
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.head.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    }
+
+    pub fn body_text(&self) -> String {
+        String::from_utf8_lossy(&self.body).to_string()
+    }
+}
+
+/// 启动只处理一个 HTTP 请求的服务，返回地址和收到的请求
+pub async fn serve_once(
+    status: &'static str,
+    response: &'static str,
+) -> (String, JoinHandle<Request>) {
+    let (listener, url) = bind("http", "").await;
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut data = Vec::new();
+        let mut buf = [0u8; 8192];
+
+        // 读取请求头和按 Content-Length 读取请求体
+        let (head, body_start, length) = loop {
+            let n = stream.read(&mut buf).await.unwrap();
+            data.extend_from_slice(&buf[..n]);
+            if let Some(pos) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+                let head = String::from_utf8_lossy(&data[..pos]).to_string();
+                let length = head
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                break (head, pos + 4, length);
+            }
+        };
+        while data.len() < body_start + length {
+            let n = stream.read(&mut buf).await.unwrap();
+            data.extend_from_slice(&buf[..n]);
+        }
+
+        let reply = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            status,
+            response.len(),
+            response
+        );
+        stream.write_all(reply.as_bytes()).await.unwrap();
+        Request {
+            head,
+            body: data[body_start..].to_vec(),
+        }
+    });
+    (url, handle)
+}
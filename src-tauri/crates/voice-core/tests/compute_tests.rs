@@ -0,0 +1,56 @@
+//! 本地推理算力诊断测试
+
+use voice_core::compute::{benchmark, benchmark_sample, compute_info, parse_nvidia_smi};
+use voice_core::error::VoiceError;
+use voice_core::types::AudioData;
+
+#[test]
+fn test_benchmark_sample_is_ten_seconds_of_signal() {
+    let audio = benchmark_sample();
+    assert_eq!(audio.sample_rate, 16000);
+    assert_eq!(audio.channels, 1);
+    assert!((audio.duration_secs - 10.0).abs() < 1e-3);
+
+    // 每秒都应有音节，不能是静音
+    for second in audio.samples.chunks(16000) {
+        let peak = second.iter().map(|s| s.unsigned_abs()).max().unwrap();
+        assert!(peak > 1000, "测试音频出现静音段");
+    }
+}
+
+#[test]
+fn test_parse_nvidia_smi() {
+    let gpus =
+        parse_nvidia_smi("NVIDIA GeForce RTX 4090, 24564, 23012\nTesla T4, [N/A], 15000\n, 1, 2\n");
+    assert_eq!(gpus.len(), 2);
+    assert_eq!(gpus[0].name, "NVIDIA GeForce RTX 4090");
+    assert_eq!(gpus[0].total_memory_mb, Some(24564));
+    assert_eq!(gpus[0].free_memory_mb, Some(23012));
+    assert!(!gpus[0].unified_memory);
+    assert_eq!(gpus[1].total_memory_mb, None);
+    assert_eq!(gpus[1].free_memory_mb, Some(15000));
+
+    assert!(parse_nvidia_smi("").is_empty());
+}
+
+#[test]
+fn test_compute_info_reports_threads() {
+    let info = compute_info();
+    assert!(info.cpu_threads >= 1);
+    assert_eq!(info.gpu_enabled(), info.metal || info.cuda);
+}
+
+#[test]
+fn test_benchmark_rejects_empty_audio() {
+    let empty = AudioData::new(Vec::new(), 16000, 1);
+    let result = benchmark(Some(&empty), || unreachable!("空音频不应加载模型"));
+    assert!(matches!(result, Err(VoiceError::AudioFormatError(_))));
+}
+
+#[test]
+fn test_benchmark_propagates_load_error() {
+    let result = benchmark(None, || {
+        Err(VoiceError::WhisperModelError("模型不存在".to_string()))
+    });
+    assert!(matches!(result, Err(VoiceError::WhisperModelError(_))));
+}
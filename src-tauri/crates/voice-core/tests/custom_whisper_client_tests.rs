@@ -0,0 +1,162 @@
+//! 自建 Whisper 服务客户端集成测试
+//!
+//! 在本地端口启动一个只响应一次的 HTTP 服务模拟兼容 OpenAI 的转写接口，不需要网络。
+//!
+//! ```bash
+//! cargo test --package voice-core --test custom_whisper_client_tests
+//! ```
+
+mod common;
+
+use std::time::Duration;
+
+use voice_core::asr_client::{create_client, AsrClient, AsrClientConfig, CustomWhisperClient};
+use voice_core::VoiceError;
+
+use common::{audio, bind, serve_once};
+
+/// multipart 表单中指定文本字段的值
+fn form_field(body: &[u8], name: &str) -> Option<String> {
+    let body = String::from_utf8_lossy(body);
+    let marker = format!("name=\"{}\"\r\n\r\n", name);
+    let start = body.find(&marker)? + marker.len();
+    let end = body[start..].find("\r\n")?;
+    Some(body[start..start + end].to_string())
+}
+
+#[tokio::test]
+async fn test_verbose_json_with_segments() {
+    let (url, server) = serve_once(
+        "200 OK",
+        r#"{"task": "transcribe", "language": "english", "duration": 1.0,
+            "text": " Hello world. How are you?",
+            "segments": [
+                {"id": 0, "start": 0.0, "end": 0.6, "text": " Hello world."},
+                {"id": 1, "start": 0.6, "end": 1.0, "text": " How are you?"}
+            ]}"#,
+    )
+    .await;
+
+    let client = CustomWhisperClient::new(format!("{}/", url))
+        .with_api_key("lan-key".to_string())
+        .with_model("Systran/faster-whisper-small".to_string())
+        .with_language("en".to_string());
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "Hello world. How are you?");
+    assert_eq!(result.language.as_deref(), Some("en"));
+    let segments: Vec<(f32, f32, &str)> = result
+        .segments
+        .iter()
+        .map(|s| (s.start, s.end, s.text.as_str()))
+        .collect();
+    assert_eq!(
+        segments,
+        [(0.0, 0.6, "Hello world."), (0.6, 1.0, "How are you?")]
+    );
+
+    let request = server.await.unwrap();
+    assert_eq!(
+        request.request_line(),
+        "POST /v1/audio/transcriptions HTTP/1.1"
+    );
+    assert_eq!(request.header("authorization"), Some("Bearer lan-key"));
+    assert!(request
+        .header("content-type")
+        .unwrap()
+        .starts_with("multipart/form-data"));
+    assert_eq!(
+        form_field(&request.body, "model").as_deref(),
+        Some("Systran/faster-whisper-small")
+    );
+    assert_eq!(
+        form_field(&request.body, "response_format").as_deref(),
+        Some("verbose_json")
+    );
+    assert_eq!(form_field(&request.body, "language").as_deref(), Some("en"));
+    let body = String::from_utf8_lossy(&request.body);
+    assert!(body.contains(r#"name="file"; filename="audio.wav""#));
+    assert!(body.contains("RIFF"));
+}
+
+#[tokio::test]
+async fn test_plain_json_without_auth() {
+    // whisper.cpp 等服务可能只返回文本
+    let (url, server) = serve_once("200 OK", r#"{"text": "你好世界"}"#).await;
+    let client = CustomWhisperClient::new(format!("{}/v1", url));
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "你好世界");
+    assert_eq!(result.language, None);
+    assert!(result.segments.is_empty());
+
+    let request = server.await.unwrap();
+    assert_eq!(
+        request.request_line(),
+        "POST /v1/audio/transcriptions HTTP/1.1"
+    );
+    assert_eq!(request.header("authorization"), None);
+    assert_eq!(
+        form_field(&request.body, "model").as_deref(),
+        Some("whisper-1")
+    );
+    assert_eq!(form_field(&request.body, "language"), None);
+}
+
+#[tokio::test]
+async fn test_errors() {
+    let (url, _server) = serve_once("401 Unauthorized", r#"{"detail": "bad key"}"#).await;
+    assert!(matches!(
+        CustomWhisperClient::new(url).transcribe(&audio()).await,
+        Err(VoiceError::AsrAuthError(_))
+    ));
+
+    let (url, _server) =
+        serve_once("500 Internal Server Error", r#"{"error": "model busy"}"#).await;
+    let message = CustomWhisperClient::new(url)
+        .transcribe(&audio())
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(message.contains("model busy"), "{}", message);
+
+    // 服务未启动
+    let (listener, url) = bind("http", "").await;
+    drop(listener);
+    let client = CustomWhisperClient::new(url).with_timeout(Duration::from_secs(5));
+    assert!(matches!(
+        client.transcribe(&audio()).await,
+        Err(VoiceError::NetworkError(_))
+    ));
+}
+
+#[test]
+fn test_config() {
+    let config = |base_url: &str, language: &str| AsrClientConfig::CustomWhisper {
+        base_url: base_url.to_string(),
+        api_key: None,
+        model: Some(" ".to_string()),
+        timeout_secs: 60,
+        language: language.to_string(),
+    };
+    assert!(matches!(
+        create_client(&config("", "zh")),
+        Err(VoiceError::ConfigError(_))
+    ));
+    assert!(matches!(
+        create_client(&config("192.168.1.10:8000", "zh")),
+        Err(VoiceError::ConfigError(_))
+    ));
+    let client = create_client(&config("http://192.168.1.10:8000", "mixed")).unwrap();
+    assert_eq!(client.name(), "自建 Whisper");
+    assert!(client.capabilities().timestamps);
+    assert!(client.capabilities().code_switching);
+    assert_eq!(client.capabilities().max_duration_secs, None);
+
+    assert_eq!(
+        CustomWhisperClient::new("http://whisper.lan:9000/".to_string()).url(),
+        "http://whisper.lan:9000/v1/audio/transcriptions"
+    );
+    assert_eq!(
+        CustomWhisperClient::new("http://whisper.lan/api/v1".to_string()).url(),
+        "http://whisper.lan/api/v1/audio/transcriptions"
+    );
+}
@@ -0,0 +1,86 @@
+//! 音频文件解码测试
+//!
+//! 用 WAV 和内置 FLAC 编码器生成测试文件，验证解码为 16kHz 单声道。
+//! mp3 / m4a / ogg 与 FLAC 共用同一解码流程，没有编码器生成样本，不单独测试。
+//!
+//! ```bash
+//! cargo test --package voice-core --test decode_tests
+//! ```
+
+use std::f32::consts::TAU;
+use std::path::Path;
+
+use voice_core::decode::{self, TARGET_SAMPLE_RATE};
+use voice_core::{flac, AudioData, VoiceError};
+
+/// 生成交错的正弦波，各声道相同
+fn sine(sample_rate: u32, channels: u16, secs: f32) -> Vec<i16> {
+    let frames = (sample_rate as f32 * secs) as usize;
+    (0..frames)
+        .flat_map(|i| {
+            let s = ((TAU * 440.0 * i as f32 / sample_rate as f32).sin() * 12000.0) as i16;
+            std::iter::repeat_n(s, channels as usize)
+        })
+        .collect()
+}
+
+/// 峰值幅度
+fn peak(samples: &[i16]) -> i16 {
+    samples
+        .iter()
+        .map(|s| s.saturating_abs())
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_decode_stereo_wav_to_16k_mono() {
+    let wav = AudioData::new(sine(44100, 2, 1.0), 44100, 2).to_wav_bytes();
+    let audio = decode::decode_bytes(wav, Some("wav")).unwrap();
+
+    assert_eq!(audio.sample_rate, TARGET_SAMPLE_RATE);
+    assert_eq!(audio.channels, 1);
+    assert!((audio.duration_secs - 1.0).abs() < 0.01);
+    // 两个声道相同，混合后幅度不变
+    assert!((11500..=12000).contains(&peak(&audio.samples)));
+}
+
+#[test]
+fn test_decode_flac() {
+    let bytes = flac::encode(&sine(48000, 1, 0.5), 48000, 1);
+    let audio = decode::decode_bytes(bytes, None).unwrap();
+
+    assert_eq!(audio.sample_rate, TARGET_SAMPLE_RATE);
+    assert_eq!(audio.samples.len(), 8000);
+    assert!((11500..=12000).contains(&peak(&audio.samples)));
+}
+
+#[test]
+fn test_decode_file() {
+    let path = std::env::temp_dir().join(format!("voice-core-decode-{}.flac", std::process::id()));
+    std::fs::write(&path, flac::encode(&sine(16000, 2, 1.0), 16000, 2)).unwrap();
+    let result = decode::decode_file(&path);
+    let _ = std::fs::remove_file(&path);
+
+    let audio = result.unwrap();
+    assert_eq!(audio.samples.len(), 16000);
+    assert_eq!(audio.channels, 1);
+}
+
+#[test]
+fn test_invalid_audio() {
+    let err = decode::decode_bytes(b"definitely not audio".to_vec(), Some("mp3")).unwrap_err();
+    assert!(matches!(err, VoiceError::AudioFormatError(_)));
+
+    let missing = decode::decode_file(Path::new("/nonexistent/voice-core/a.mp3"));
+    assert!(matches!(missing, Err(VoiceError::IoError(_))));
+}
+
+#[test]
+fn test_is_supported() {
+    for name in ["a.mp3", "b.M4A", "c.ogg", "d.flac", "e.wav"] {
+        assert!(decode::is_supported(Path::new(name)), "{}", name);
+    }
+    assert!(!decode::is_supported(Path::new("notes.txt")));
+    assert!(!decode::is_supported(Path::new("noext")));
+}
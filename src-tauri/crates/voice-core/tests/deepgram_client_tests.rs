@@ -0,0 +1,162 @@
+//! Deepgram 客户端集成测试
+//!
+//! 在本地端口启动一个只响应一次的 HTTP 服务模拟预录音频接口，不需要网络。
+//!
+//! ```bash
+//! cargo test --package voice-core --test deepgram_client_tests
+//! ```
+
+mod common;
+
+use voice_core::asr_client::factory::deepgram_language;
+use voice_core::asr_client::{create_client, AsrClient, AsrClientConfig, DeepgramClient};
+use voice_core::VoiceError;
+
+use common::{audio, serve_once};
+
+const KEY: &str = "dg-key";
+
+#[tokio::test]
+async fn test_prerecorded_request_with_word_timestamps() {
+    let (url, server) = serve_once(
+        "200 OK",
+        r#"{"metadata": {"request_id": "req-1", "duration": 1.0},
+            "results": {"channels": [{"alternatives": [{
+                "transcript": "Hello world.",
+                "confidence": 0.98,
+                "words": [
+                    {"word": "hello", "start": 0.08, "end": 0.4, "confidence": 0.99, "punctuated_word": "Hello"},
+                    {"word": "world", "start": 0.48, "end": 0.9, "confidence": 0.97, "punctuated_word": "world."}
+                ]}]}]}}"#,
+    )
+    .await;
+
+    let client = DeepgramClient::new(KEY.to_string())
+        .with_endpoint(url)
+        .with_model("nova-2-meeting".to_string())
+        .with_language("en-US".to_string());
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "Hello world.");
+    assert_eq!(result.language.as_deref(), Some("en"));
+    assert_eq!(result.confidence, Some(0.98));
+
+    let words: Vec<(f32, f32, &str)> = result
+        .segments
+        .iter()
+        .map(|s| (s.start, s.end, s.text.as_str()))
+        .collect();
+    assert_eq!(words, [(0.08, 0.4, "Hello"), (0.48, 0.9, "world.")]);
+
+    let request = server.await.unwrap();
+    assert_eq!(
+        request.request_line(),
+        "POST /v1/listen?model=nova-2-meeting&language=en-US&punctuate=true&smart_format=true HTTP/1.1"
+    );
+    assert_eq!(request.header("authorization"), Some("Token dg-key"));
+    assert_eq!(request.header("content-type"), Some("audio/flac"));
+    assert_eq!(&request.body[..4], b"fLaC");
+}
+
+#[tokio::test]
+async fn test_diarization_groups_speaker_turns() {
+    let (url, server) = serve_once(
+        "200 OK",
+        r#"{"results": {"channels": [{"alternatives": [{
+                "transcript": "Hi there. Hello.",
+                "words": [
+                    {"word": "hi", "start": 0.1, "end": 0.3, "speaker": 0, "punctuated_word": "Hi"},
+                    {"word": "there", "start": 0.3, "end": 0.6, "speaker": 0, "punctuated_word": "there."},
+                    {"word": "hello", "start": 0.8, "end": 1.2, "speaker": 1, "punctuated_word": "Hello."}
+                ]}]}]}}"#,
+    )
+    .await;
+
+    let client = DeepgramClient::new(KEY.to_string())
+        .with_endpoint(url)
+        .with_language("en".to_string())
+        .with_diarization(true);
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.speaker_count(), 2);
+
+    let turns: Vec<(f32, f32, &str, Option<u32>)> = result
+        .segments
+        .iter()
+        .map(|s| (s.start, s.end, s.text.as_str(), s.speaker))
+        .collect();
+    assert_eq!(
+        turns,
+        [
+            (0.1, 0.6, "Hi there.", Some(0)),
+            (0.8, 1.2, "Hello.", Some(1))
+        ]
+    );
+
+    let request = server.await.unwrap();
+    assert!(request.request_line().contains("&diarize=true "));
+}
+
+#[tokio::test]
+async fn test_detect_language_and_errors() {
+    let (url, server) = serve_once(
+        "200 OK",
+        r#"{"results": {"channels": [{"detected_language": "zh",
+            "alternatives": [{"transcript": "你好", "confidence": 0.9,
+                "words": [{"word": "你好", "start": 0.1, "end": 0.6}]}]}]}}"#,
+    )
+    .await;
+    let client = DeepgramClient::new(KEY.to_string()).with_endpoint(url);
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "你好");
+    assert_eq!(result.language.as_deref(), Some("zh"));
+    assert_eq!(result.segments[0].text, "你好");
+    let request = server.await.unwrap();
+    assert!(request
+        .request_line()
+        .starts_with("POST /v1/listen?model=nova-2&detect_language=true&"));
+
+    let (url, _server) = serve_once(
+        "401 Unauthorized",
+        r#"{"err_code": "INVALID_AUTH", "err_msg": "Invalid credentials."}"#,
+    )
+    .await;
+    let client = DeepgramClient::new(KEY.to_string()).with_endpoint(url);
+    match client.transcribe(&audio()).await {
+        Err(VoiceError::AsrAuthError(message)) => assert!(message.contains("INVALID_AUTH")),
+        other => panic!("期望鉴权错误: {:?}", other.map(|r| r.text)),
+    }
+
+    let (url, _server) = serve_once(
+        "400 Bad Request",
+        r#"{"err_code": "Bad Request", "err_msg": "No such model."}"#,
+    )
+    .await;
+    let client = DeepgramClient::new(KEY.to_string()).with_endpoint(url);
+    assert!(matches!(
+        client.transcribe(&audio()).await,
+        Err(VoiceError::AsrError(_))
+    ));
+}
+
+#[test]
+fn test_config_and_language() {
+    let config = |api_key: &str| AsrClientConfig::Deepgram {
+        api_key: api_key.to_string(),
+        model: Some("nova-2".to_string()),
+        diarize: true,
+        language: "zh".to_string(),
+    };
+    assert!(matches!(
+        create_client(&config(" ")),
+        Err(VoiceError::ConfigError(_))
+    ));
+    let client = create_client(&config(KEY)).unwrap();
+    assert_eq!(client.name(), "Deepgram");
+    assert!(client.capabilities().timestamps);
+    assert_eq!(client.capabilities().max_duration_secs, None);
+    assert!(client.capabilities().diarization);
+
+    assert_eq!(deepgram_language("auto"), None);
+    assert_eq!(deepgram_language("mixed").as_deref(), Some("zh"));
+    assert_eq!(deepgram_language("en").as_deref(), Some("en"));
+    assert_eq!(deepgram_language("zh-TW").as_deref(), Some("zh-TW"));
+}
@@ -0,0 +1,113 @@
+//! 本地说话人区分集成测试
+//!
+//! 用基频和泛音不同的合成音模拟不同的说话人。
+//!
+//! ```bash
+//! cargo test --package voice-core --test diarization_tests
+//! ```
+
+use std::f32::consts::TAU;
+
+use voice_core::diarization::{diarize, DiarizationOptions};
+use voice_core::{AudioData, Segment};
+
+const SAMPLE_RATE: u32 = 16000;
+
+/// 一个"说话人"的合成音：基频和各次泛音的幅度
+struct Voice {
+    pitch: f32,
+    harmonics: &'static [f32],
+}
+
+const LOW: Voice = Voice {
+    pitch: 120.0,
+    harmonics: &[1.0, 0.8, 0.6, 0.4, 0.2],
+};
+
+const HIGH: Voice = Voice {
+    pitch: 900.0,
+    harmonics: &[1.0, 0.1, 0.5],
+};
+
+fn tone(voice: &Voice, secs: f32) -> Vec<i16> {
+    let count = (secs * SAMPLE_RATE as f32) as usize;
+    (0..count)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let value: f32 = voice
+                .harmonics
+                .iter()
+                .enumerate()
+                .map(|(h, amp)| amp * (TAU * voice.pitch * (h + 1) as f32 * t).sin())
+                .sum();
+            (value * 4000.0) as i16
+        })
+        .collect()
+}
+
+/// 依次拼接各段合成音，返回音频和对应的分段
+fn conversation(turns: &[(&Voice, f32)]) -> (AudioData, Vec<Segment>) {
+    let mut samples = Vec::new();
+    let mut segments = Vec::new();
+    let mut start = 0.0;
+    for (voice, secs) in turns {
+        samples.extend(tone(voice, *secs));
+        segments.push(Segment {
+            start,
+            end: start + secs,
+            text: format!("第 {} 段", segments.len() + 1),
+            speaker: None,
+        });
+        start += secs;
+    }
+    (AudioData::new(samples, SAMPLE_RATE, 1), segments)
+}
+
+fn speakers(segments: &[Segment]) -> Vec<Option<u32>> {
+    segments.iter().map(|s| s.speaker).collect()
+}
+
+#[test]
+fn test_alternating_speakers() {
+    let (audio, mut segments) =
+        conversation(&[(&HIGH, 1.0), (&LOW, 1.5), (&HIGH, 1.2), (&LOW, 1.0)]);
+
+    let count = diarize(&audio, &mut segments, DiarizationOptions::default());
+    assert_eq!(count, 2);
+    assert_eq!(
+        speakers(&segments),
+        vec![Some(0), Some(1), Some(0), Some(1)]
+    );
+}
+
+#[test]
+fn test_short_segment_follows_previous_speaker() {
+    let (audio, mut segments) = conversation(&[(&LOW, 1.0), (&HIGH, 0.2), (&HIGH, 1.0)]);
+
+    let count = diarize(&audio, &mut segments, DiarizationOptions::default());
+    assert_eq!(count, 2);
+    assert_eq!(speakers(&segments), vec![Some(0), Some(0), Some(1)]);
+}
+
+#[test]
+fn test_max_speakers_merges_clusters() {
+    let (audio, mut segments) = conversation(&[(&LOW, 1.0), (&HIGH, 1.0), (&LOW, 1.0)]);
+    let options = DiarizationOptions {
+        max_speakers: 1,
+        ..Default::default()
+    };
+
+    assert_eq!(diarize(&audio, &mut segments, options), 1);
+    assert_eq!(speakers(&segments), vec![Some(0); 3]);
+}
+
+#[test]
+fn test_no_usable_segments() {
+    let (audio, mut segments) = conversation(&[(&LOW, 0.3), (&HIGH, 0.3)]);
+
+    assert_eq!(
+        diarize(&audio, &mut segments, DiarizationOptions::default()),
+        0
+    );
+    assert_eq!(speakers(&segments), vec![None, None]);
+}
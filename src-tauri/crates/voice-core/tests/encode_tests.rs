@@ -0,0 +1,160 @@
+//! 音频编码集成测试
+//!
+//! ```bash
+//! cargo test --package voice-core --test encode_tests
+//! ```
+
+use std::f32::consts::TAU;
+use std::io::Cursor;
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use voice_core::asr_client::{AsrCapabilities, AudioFormat};
+use voice_core::encode::{encode, resample, to_mono};
+use voice_core::{flac, AudioData};
+
+/// 生成正弦波
+fn sine(freq: f32, sample_rate: u32, secs: f32) -> Vec<i16> {
+    let len = (sample_rate as f32 * secs) as usize;
+    (0..len)
+        .map(|i| ((TAU * freq * i as f32 / sample_rate as f32).sin() * 12000.0) as i16)
+        .collect()
+}
+
+/// 用 symphonia 解码 FLAC，返回交错采样、采样率和声道数
+fn decode_flac(bytes: Vec<u8>) -> (Vec<i16>, u32, usize) {
+    let source = MediaSourceStream::new(Box::new(Cursor::new(bytes)), Default::default());
+    let mut hint = Hint::new();
+    hint.with_extension("flac");
+    let mut format = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .expect("probe flac")
+        .format;
+    let track = format.default_track().expect("flac track").clone();
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions { verify: true })
+        .expect("flac decoder");
+
+    let sample_rate = track.codec_params.sample_rate.unwrap();
+    let channels = track.codec_params.channels.unwrap().count();
+    let mut samples = Vec::new();
+    while let Ok(packet) = format.next_packet() {
+        let decoded = decoder.decode(&packet).expect("decode frame");
+        let mut buffer = SampleBuffer::<i16>::new(decoded.capacity() as u64, *decoded.spec());
+        buffer.copy_interleaved_ref(decoded);
+        samples.extend_from_slice(buffer.samples());
+    }
+    (samples, sample_rate, channels)
+}
+
+#[test]
+fn test_flac_round_trip_is_lossless() {
+    // 非整块长度，覆盖最后一个短块
+    let mut samples = sine(440.0, 16000, 1.3);
+    // 混入噪声和极值，覆盖原样子帧
+    let mut seed = 1u32;
+    for s in samples.iter_mut().skip(8000).take(4096) {
+        seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        *s = (seed >> 16) as i16;
+    }
+    samples[100] = i16::MIN;
+    samples[101] = i16::MAX;
+
+    let (decoded, sample_rate, channels) = decode_flac(flac::encode(&samples, 16000, 1));
+    assert_eq!(sample_rate, 16000);
+    assert_eq!(channels, 1);
+    assert_eq!(decoded, samples);
+}
+
+#[test]
+fn test_flac_stereo_and_silence() {
+    let left = sine(440.0, 44100, 0.5);
+    let interleaved: Vec<i16> = left.iter().flat_map(|&s| [s, 0]).collect();
+
+    let (decoded, sample_rate, channels) = decode_flac(flac::encode(&interleaved, 44100, 2));
+    assert_eq!(sample_rate, 44100);
+    assert_eq!(channels, 2);
+    assert_eq!(decoded, interleaved);
+}
+
+#[test]
+fn test_flac_compresses_speech_like_audio() {
+    let samples = sine(300.0, 16000, 5.0);
+    let wav = AudioData::new(samples.clone(), 16000, 1).to_wav_bytes();
+    let flac = flac::encode(&samples, 16000, 1);
+    assert!(
+        flac.len() < wav.len() / 2,
+        "{} vs {}",
+        flac.len(),
+        wav.len()
+    );
+}
+
+#[test]
+fn test_encode_converts_to_mono_and_target_rate() {
+    let stereo: Vec<i16> = sine(440.0, 48000, 1.0)
+        .iter()
+        .flat_map(|&s| [s, s])
+        .collect();
+    let audio = AudioData::new(stereo, 48000, 2);
+
+    let pcm = encode(&audio, AudioFormat::Pcm, 16000).unwrap();
+    assert_eq!(pcm.sample_rate, 16000);
+    assert_eq!(pcm.channels, 1);
+    assert_eq!(pcm.bytes.len(), 16000 * 2);
+
+    let wav = encode(&audio, AudioFormat::Wav, 16000).unwrap();
+    let parsed = AudioData::from_wav_bytes(&wav.bytes).unwrap();
+    assert_eq!(parsed.sample_rate, 16000);
+    assert_eq!(parsed.samples.len(), 16000);
+
+    assert_eq!(wav.file_name("audio"), "audio.wav");
+    assert!(encode(&audio, AudioFormat::M4a, 16000).is_err());
+}
+
+#[test]
+fn test_mono_and_resample_helpers() {
+    assert_eq!(to_mono(&[100, 300, -50, 50], 2), vec![200, 0]);
+    assert_eq!(resample(&[0, 10, 20, 30], 16000, 8000), vec![0, 20]);
+    assert!(resample(&[], 48000, 16000).is_empty());
+}
+
+#[test]
+fn test_negotiation_follows_preference() {
+    let capabilities = AsrCapabilities {
+        streaming: false,
+        timestamps: false,
+        hotwords: false,
+        punctuation: true,
+        code_switching: false,
+        diarization: false,
+        max_duration_secs: None,
+        formats: &[AudioFormat::M4a, AudioFormat::Flac, AudioFormat::Wav],
+        sample_rates: &[16000, 8000],
+    };
+
+    // 无法编码的 m4a 被跳过
+    assert_eq!(capabilities.negotiate(), Some(AudioFormat::Flac));
+    assert_eq!(capabilities.negotiate_sample_rate(8000), 8000);
+    assert_eq!(capabilities.negotiate_sample_rate(44100), 16000);
+
+    let any_rate = AsrCapabilities {
+        sample_rates: &[],
+        formats: &[AudioFormat::Amr],
+        ..capabilities
+    };
+    assert_eq!(any_rate.negotiate(), None);
+    assert_eq!(any_rate.negotiate_sample_rate(44100), 44100);
+    assert!(any_rate
+        .encode(&AudioData::new(vec![0; 16000], 16000, 1))
+        .is_err());
+}
@@ -0,0 +1,160 @@
+//! ASR 回退链测试
+//!
+//! 验证 `AsrFallbackChain` 只在网络和鉴权错误时改用下一个服务，并记录产出结果的服务。
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use voice_core::asr_client::{AsrCapabilities, AsrClient, AsrFallbackChain};
+use voice_core::error::{Result, VoiceError};
+use voice_core::types::{AudioData, TranscribeResult};
+
+/// 按预设结果返回并记录调用次数的测试客户端
+struct ScriptedClient {
+    name: &'static str,
+    outcome: fn() -> Result<&'static str>,
+    max_duration_secs: Option<f32>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl ScriptedClient {
+    fn new(name: &'static str, outcome: fn() -> Result<&'static str>) -> (Self, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let client = Self {
+            name,
+            outcome,
+            max_duration_secs: None,
+            calls: calls.clone(),
+        };
+        (client, calls)
+    }
+}
+
+#[async_trait]
+impl AsrClient for ScriptedClient {
+    async fn transcribe(&self, _audio: &AudioData) -> Result<TranscribeResult> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let text = (self.outcome)()?;
+        Ok(TranscribeResult {
+            text: text.to_string(),
+            language: Some("zh".to_string()),
+            confidence: None,
+            segments: Vec::new(),
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        AsrCapabilities {
+            streaming: false,
+            timestamps: false,
+            hotwords: false,
+            punctuation: true,
+            code_switching: false,
+            diarization: false,
+            max_duration_secs: self.max_duration_secs,
+            formats: &[],
+            sample_rates: &[],
+        }
+    }
+}
+
+fn audio() -> AudioData {
+    AudioData::new(vec![0; 16000], 16000, 1)
+}
+
+#[tokio::test]
+async fn test_falls_back_on_network_and_auth_errors() {
+    let (offline, offline_calls) = ScriptedClient::new("离线", || {
+        Err(VoiceError::NetworkError("连接超时".to_string()))
+    });
+    let (unauthorized, unauthorized_calls) = ScriptedClient::new("过期", || {
+        Err(VoiceError::AsrAuthError("密钥无效".to_string()))
+    });
+    let (healthy, healthy_calls) = ScriptedClient::new("可用", || Ok("你好"));
+    let chain = AsrFallbackChain::new(vec![
+        Box::new(offline),
+        Box::new(unauthorized),
+        Box::new(healthy),
+    ])
+    .unwrap();
+    assert_eq!(chain.backends(), vec!["离线", "过期", "可用"]);
+
+    let result = chain.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "你好");
+    assert_eq!(result.backend.as_deref(), Some("可用"));
+    assert_eq!(offline_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(unauthorized_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(healthy_calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_primary_success_skips_remaining_backends() {
+    let (primary, _) = ScriptedClient::new("首选", || Ok("首选结果"));
+    let (secondary, secondary_calls) = ScriptedClient::new("备用", || Ok("备用结果"));
+    let chain = AsrFallbackChain::new(vec![Box::new(primary), Box::new(secondary)]).unwrap();
+
+    let result = chain.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.backend.as_deref(), Some("首选"));
+    assert_eq!(secondary_calls.load(Ordering::SeqCst), 0);
+    assert_eq!(chain.name(), "首选");
+}
+
+#[tokio::test]
+async fn test_other_errors_do_not_fall_back() {
+    let (primary, _) = ScriptedClient::new("首选", || {
+        Err(VoiceError::AsrError("音频无法识别".to_string()))
+    });
+    let (secondary, secondary_calls) = ScriptedClient::new("备用", || Ok("备用结果"));
+    let chain = AsrFallbackChain::new(vec![Box::new(primary), Box::new(secondary)]).unwrap();
+
+    let err = chain.transcribe(&audio()).await.unwrap_err();
+    assert!(matches!(err, VoiceError::AsrError(_)));
+    assert_eq!(secondary_calls.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_last_error_returned_when_all_backends_fail() {
+    let (first, _) = ScriptedClient::new("甲", || {
+        Err(VoiceError::NetworkError("甲不可达".to_string()))
+    });
+    let (second, _) = ScriptedClient::new("乙", || {
+        Err(VoiceError::AsrAuthError("乙鉴权失败".to_string()))
+    });
+    let chain = AsrFallbackChain::new(vec![Box::new(first), Box::new(second)]).unwrap();
+
+    let err = chain.transcribe(&audio()).await.unwrap_err();
+    assert!(matches!(err, VoiceError::AsrAuthError(ref m) if m == "乙鉴权失败"));
+}
+
+#[tokio::test]
+async fn test_skips_fallback_that_cannot_handle_duration() {
+    let (primary, _) = ScriptedClient::new("首选", || {
+        Err(VoiceError::NetworkError("连接超时".to_string()))
+    });
+    let (mut short, short_calls) = ScriptedClient::new("短音频", || Ok("短音频结果"));
+    short.max_duration_secs = Some(0.5);
+    let (long, _) = ScriptedClient::new("长音频", || Ok("长音频结果"));
+    let chain =
+        AsrFallbackChain::new(vec![Box::new(primary), Box::new(short), Box::new(long)]).unwrap();
+
+    // 1 秒录音超过“短音频”的上限
+    let result = chain.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.backend.as_deref(), Some("长音频"));
+    assert_eq!(short_calls.load(Ordering::SeqCst), 0);
+    // 能力按首选服务声明
+    assert_eq!(chain.capabilities().max_duration_secs, None);
+}
+
+#[test]
+fn test_empty_chain_rejected() {
+    assert!(matches!(
+        AsrFallbackChain::new(Vec::new()),
+        Err(VoiceError::ConfigError(_))
+    ));
+}
@@ -0,0 +1,70 @@
+//! FBank 特征提取集成测试
+//!
+//! ```bash
+//! cargo test --package voice-core --test fbank_tests
+//! ```
+
+use std::f32::consts::TAU;
+
+use voice_core::fbank::{apply_lfr, Fbank, NUM_MEL_BINS, SAMPLE_RATE};
+
+/// 生成 16 位整数幅度的正弦波
+fn sine(freq: f32, samples: usize) -> Vec<f32> {
+    (0..samples)
+        .map(|i| (TAU * freq * i as f32 / SAMPLE_RATE as f32).sin() * 10000.0)
+        .collect()
+}
+
+#[test]
+fn test_frame_count() {
+    let fbank = Fbank::new();
+
+    // 1 秒：1 + (16000 - 400) / 160 = 98 帧
+    let frames = fbank.compute(&sine(440.0, 16000));
+    assert_eq!(frames.len(), 98);
+    assert!(frames.iter().all(|f| f.len() == NUM_MEL_BINS));
+
+    // 不足一帧
+    assert!(fbank.compute(&sine(440.0, 399)).is_empty());
+    assert_eq!(fbank.compute(&sine(440.0, 400)).len(), 1);
+}
+
+#[test]
+fn test_tone_energy_in_matching_band() {
+    let frames = Fbank::new().compute(&sine(1000.0, 16000));
+    let frame = &frames[frames.len() / 2];
+
+    // 1000Hz 约为梅尔刻度 1000，落在第 26、27 个滤波器的中心附近
+    let peak = frame
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(i, _)| i)
+        .unwrap();
+    assert!((26..=27).contains(&peak), "peak at bin {}", peak);
+
+    // 远离音调的高频段能量低得多
+    assert!(frame[peak] - frame[NUM_MEL_BINS - 1] > 10.0);
+}
+
+#[test]
+fn test_silence_is_floored() {
+    let frames = Fbank::new().compute(&[0.0; 1600]);
+    let floor = f32::EPSILON.ln();
+    assert!(frames.iter().flatten().all(|&v| v == floor));
+}
+
+#[test]
+fn test_lfr_stacks_frames() {
+    let frames: Vec<Vec<f32>> = (0..13).map(|i| vec![i as f32; 2]).collect();
+
+    // (13 - 7) / 6 + 1 = 2 帧，每帧拼接 7 帧
+    let lfr = apply_lfr(&frames, 7, 6);
+    assert_eq!(lfr.len(), 2);
+    assert_eq!(lfr[0].len(), 14);
+    assert_eq!(lfr[0][..2], [0.0, 0.0]);
+    assert_eq!(lfr[1][..2], [6.0, 6.0]);
+    assert_eq!(lfr[1][12..], [12.0, 12.0]);
+
+    assert!(apply_lfr(&frames[..6], 7, 6).is_empty());
+}
@@ -0,0 +1,124 @@
+//! 通用 HTTP ASR 客户端集成测试
+//!
+//! 在本地端口启动一个只响应一次的 HTTP 服务模拟自建识别服务，不需要网络。
+//!
+//! ```bash
+//! cargo test --package voice-core --test http_client_tests
+//! ```
+
+mod common;
+
+use std::collections::HashMap;
+
+use voice_core::asr_client::{
+    create_client, AsrClient, AsrClientConfig, HttpAsrClient, HttpUpload,
+};
+use voice_core::VoiceError;
+
+use common::{audio, serve_once};
+
+#[tokio::test]
+async fn test_raw_upload_with_headers_and_json_path() {
+    let (url, server) = serve_once(
+        "200 OK",
+        r#"{"code": 0, "result": [{"key": "audio", "text": "今天天气不错"}]}"#,
+    )
+    .await;
+
+    let headers = HashMap::from([("Authorization".to_string(), "Bearer secret".to_string())]);
+    let client = HttpAsrClient::new(format!("{}/asr?lang={{language}}", url), "$.result[0].text")
+        .unwrap()
+        .with_headers(headers)
+        .with_language("zh".to_string());
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "今天天气不错");
+    assert_eq!(result.language.as_deref(), Some("zh"));
+
+    let request = server.await.unwrap();
+    assert!(request.head.starts_with("POST /asr?lang=zh "));
+    assert!(request.head.contains("authorization: Bearer secret"));
+    assert!(request.head.contains("content-type: audio/wav"));
+    assert!(request.body.starts_with(b"RIFF"));
+}
+
+#[tokio::test]
+async fn test_multipart_upload() {
+    let (url, server) = serve_once("200 OK", r#"{"data": {"text": "hello"}}"#).await;
+
+    let client = HttpAsrClient::new(url, "data['text']")
+        .unwrap()
+        .with_upload(HttpUpload::Multipart {
+            field: "audio_file".to_string(),
+        })
+        .with_hotwords(vec!["ProxyCast".to_string(), "钉钉".to_string()]);
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "hello");
+
+    let request = server.await.unwrap();
+    assert!(request.head.contains("multipart/form-data"));
+    let body = String::from_utf8_lossy(&request.body);
+    assert!(body.contains(r#"name="audio_file"; filename="audio.wav""#));
+    assert!(body.contains("name=\"hotwords\"\r\n\r\nProxyCast,钉钉\r\n"));
+}
+
+#[tokio::test]
+async fn test_hotwords_placeholder() {
+    let (url, server) = serve_once("200 OK", r#"{"text": "打开钉钉"}"#).await;
+
+    let client = HttpAsrClient::new(format!("{}/asr?hotwords={{hotwords}}", url), "text")
+        .unwrap()
+        .with_hotwords(vec!["钉钉".to_string(), "Git Hub".to_string()]);
+    client.transcribe(&audio()).await.unwrap();
+
+    let request = server.await.unwrap();
+    assert!(request
+        .head
+        .starts_with("POST /asr?hotwords=%E9%92%89%E9%92%89%2CGit%20Hub "));
+}
+
+#[tokio::test]
+async fn test_missing_field_and_error_status() {
+    let (url, _server) = serve_once("200 OK", r#"{"result": []}"#).await;
+    let client = HttpAsrClient::new(url, "$.result[0].text").unwrap();
+    assert!(matches!(
+        client.transcribe(&audio()).await,
+        Err(VoiceError::AsrError(_))
+    ));
+
+    let (url, _server) =
+        serve_once("500 Internal Server Error", r#"{"error": "model busy"}"#).await;
+    let client = HttpAsrClient::new(url, "text").unwrap();
+    let message = client.transcribe(&audio()).await.unwrap_err().to_string();
+    assert!(message.contains("500"), "{}", message);
+    assert!(message.contains("model busy"), "{}", message);
+}
+
+#[test]
+fn test_invalid_config_rejected() {
+    let config = |url: &str, text_path: &str| AsrClientConfig::Http {
+        url: url.to_string(),
+        headers: HashMap::new(),
+        text_path: text_path.to_string(),
+        upload: HttpUpload::Raw,
+        timeout_secs: 60,
+        language: "zh".to_string(),
+    };
+
+    for (url, path) in [
+        ("localhost:10095/asr", "text"),
+        ("http://localhost:10095/asr", "$.result[0"),
+        ("http://localhost:10095/asr", "$.result[x]"),
+        ("http://localhost:10095/asr", "$"),
+    ] {
+        assert!(
+            matches!(
+                create_client(&config(url, path)),
+                Err(VoiceError::ConfigError(_))
+            ),
+            "{} {}",
+            url,
+            path
+        );
+    }
+    assert!(create_client(&config("http://localhost:10095/asr", "$.result[0].text")).is_ok());
+}
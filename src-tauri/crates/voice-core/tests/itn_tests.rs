@@ -0,0 +1,55 @@
+//! 逆文本标准化测试
+//!
+//! ```bash
+//! cargo test --package voice-core --test itn_tests
+//! ```
+
+use voice_core::itn::normalize;
+
+#[test]
+fn test_dates() {
+    assert_eq!(normalize("二零二五年三月十五日"), "2025年3月15日");
+    assert_eq!(normalize("会议定在十二月三号下午"), "会议定在12月3号下午");
+    assert_eq!(normalize("九八年出生"), "98年出生");
+    assert_eq!(normalize("过了二十年"), "过了20年");
+    // 单独的月份和星期保留
+    assert_eq!(normalize("三月份开始，周三开会"), "三月份开始，周三开会");
+}
+
+#[test]
+fn test_percent_and_decimal() {
+    assert_eq!(normalize("增长了三点五个百分点"), "增长了3.5个百分点");
+    assert_eq!(normalize("占比百分之三十五"), "占比35%");
+    assert_eq!(normalize("利率百分之二点七五"), "利率2.75%");
+    assert_eq!(normalize("百分之百确定"), "100%确定");
+    assert_eq!(normalize("下降了五个百分点"), "下降了5个百分点");
+    assert_eq!(normalize("版本零点九"), "版本0.9");
+}
+
+#[test]
+fn test_amounts() {
+    assert_eq!(normalize("一共三千五百元"), "一共3500元");
+    assert_eq!(normalize("预算一万二千美元"), "预算12000美元");
+    assert_eq!(normalize("月薪一万二块钱"), "月薪12000块钱");
+    assert_eq!(normalize("融资三千万人民币"), "融资3000万人民币");
+    assert_eq!(normalize("花了五元"), "花了5元");
+    assert_eq!(normalize("一千零五个人"), "1005个人");
+    assert_eq!(normalize("二十多个"), "20多个");
+}
+
+#[test]
+fn test_words_kept() {
+    for text in [
+        "两个人一起去",
+        "统一规划，一些问题",
+        "万一下雨，千万别去",
+        "十分重要",
+        "三四个",
+        "下午三点到",
+        "三点五十分出发",
+        "一块儿走",
+        "一千零一夜",
+    ] {
+        assert_eq!(normalize(text), text);
+    }
+}
@@ -0,0 +1,63 @@
+//! 中英混合文本排版测试
+
+use voice_core::language::format_mixed_text;
+
+#[test]
+fn test_empty_and_whitespace() {
+    assert_eq!(format_mixed_text(""), "");
+    assert_eq!(format_mixed_text("   "), "");
+    assert_eq!(format_mixed_text("  你好  "), "你好");
+}
+
+#[test]
+fn test_space_between_cjk_and_latin() {
+    assert_eq!(format_mixed_text("我用rust写代码"), "我用 rust 写代码");
+    assert_eq!(format_mixed_text("一共3个"), "一共 3 个");
+    // 已有的单个空格保留，多余空格合并
+    assert_eq!(format_mixed_text("我用 rust  写代码"), "我用 rust 写代码");
+}
+
+#[test]
+fn test_no_space_around_cjk_punctuation() {
+    assert_eq!(format_mixed_text("你好 ， 世界 。"), "你好，世界。");
+    assert_eq!(format_mixed_text("打开 （GitHub）"), "打开（GitHub）");
+    // 中文之间的空格去掉
+    assert_eq!(format_mixed_text("你 好"), "你好");
+}
+
+#[test]
+fn test_sentence_start_capitalized() {
+    assert_eq!(format_mixed_text("hello world"), "Hello world");
+    assert_eq!(
+        format_mixed_text("好的。thanks a lot"),
+        "好的。Thanks a lot"
+    );
+    assert_eq!(format_mixed_text("done. next step"), "Done. Next step");
+    // 中文开头的句子里英文不算句首
+    assert_eq!(format_mixed_text("用react吧"), "用 react 吧");
+}
+
+#[test]
+fn test_mixed_case_words_untouched() {
+    assert_eq!(format_mixed_text("iPhone 很好用"), "iPhone 很好用");
+    assert_eq!(format_mixed_text("GitHub 上的项目"), "GitHub 上的项目");
+}
+
+#[test]
+fn test_standalone_i() {
+    assert_eq!(format_mixed_text("ok i think so"), "Ok I think so");
+    // 单词内部的 i 不变
+    assert_eq!(format_mixed_text("我觉得 it is fine"), "我觉得 it is fine");
+}
+
+#[test]
+fn test_dot_inside_word_is_not_sentence_end() {
+    assert_eq!(format_mixed_text("用node.js开发"), "用 node.js 开发");
+    assert_eq!(format_mixed_text("版本是v1.2"), "版本是 v1.2");
+}
+
+#[test]
+fn test_newlines_preserved() {
+    assert_eq!(format_mixed_text("第一行\n第二行"), "第一行\n第二行");
+    assert_eq!(format_mixed_text("first\nsecond"), "First\nsecond");
+}
@@ -0,0 +1,86 @@
+//! 音量电平测量测试
+
+use voice_core::level::{amplitude_to_dbfs, AudioLevel, LevelMeter, CLIP_THRESHOLD, MIN_DBFS};
+
+fn approx(a: f32, b: f32) -> bool {
+    (a - b).abs() < 0.05
+}
+
+#[test]
+fn test_amplitude_to_dbfs() {
+    assert!(approx(amplitude_to_dbfs(1.0), 0.0));
+    assert!(approx(amplitude_to_dbfs(0.5), -6.02));
+    assert!(approx(amplitude_to_dbfs(0.1), -20.0));
+    // 零、负数和极小值都钳制到下限
+    assert_eq!(amplitude_to_dbfs(0.0), MIN_DBFS);
+    assert_eq!(amplitude_to_dbfs(-1.0), MIN_DBFS);
+    assert_eq!(amplitude_to_dbfs(1e-9), MIN_DBFS);
+}
+
+#[test]
+fn test_measure_empty_is_silence() {
+    assert_eq!(AudioLevel::measure(&[]), AudioLevel::SILENCE);
+    assert_eq!(AudioLevel::measure_i16(&[]), AudioLevel::SILENCE);
+    assert_eq!(AudioLevel::default(), AudioLevel::SILENCE);
+}
+
+#[test]
+fn test_measure_square_wave() {
+    // 方波的 RMS 等于峰值
+    let level = AudioLevel::measure(&[0.5, -0.5, 0.5, -0.5]);
+    assert!(approx(level.rms_dbfs, -6.02));
+    assert!(approx(level.peak_dbfs, -6.02));
+    assert!(!level.clipping);
+}
+
+#[test]
+fn test_clipping_threshold() {
+    let below = AudioLevel::measure(&[CLIP_THRESHOLD - 0.001]);
+    assert!(!below.clipping);
+    let at = AudioLevel::measure(&[-CLIP_THRESHOLD]);
+    assert!(at.clipping);
+
+    assert!(AudioLevel::measure_i16(&[i16::MAX]).clipping);
+    assert!(AudioLevel::measure_i16(&[i16::MIN]).clipping);
+    assert!(!AudioLevel::measure_i16(&[i16::MAX / 2]).clipping);
+}
+
+#[test]
+fn test_percent_mapping() {
+    let at = |rms_dbfs| AudioLevel {
+        rms_dbfs,
+        ..AudioLevel::SILENCE
+    };
+    assert_eq!(at(0.0).percent(), 100);
+    assert_eq!(at(-30.0).percent(), 50);
+    assert_eq!(at(-60.0).percent(), 0);
+    // 超出范围时钳制
+    assert_eq!(at(-80.0).percent(), 0);
+    assert_eq!(at(3.0).percent(), 100);
+    assert_eq!(AudioLevel::SILENCE.percent(), 0);
+}
+
+#[test]
+fn test_level_meter_tracks_max_and_clipping() {
+    let meter = LevelMeter::new();
+    assert_eq!(meter.level(), AudioLevel::SILENCE);
+    assert_eq!(meter.max_rms_dbfs(), MIN_DBFS);
+
+    let loud = meter.record(&[1.0, -1.0, 0.2, 0.0]);
+    assert!(loud.clipping);
+    assert_eq!(meter.clipped_samples(), 2);
+
+    // 较小的一块更新当前电平，但不降低最大值
+    let quiet = meter.record(&[0.01, -0.01]);
+    assert_eq!(meter.level(), quiet);
+    assert_eq!(meter.max_rms_dbfs(), loud.rms_dbfs);
+    assert_eq!(meter.clipped_samples(), 2);
+
+    meter.record(&[1.0]);
+    assert_eq!(meter.clipped_samples(), 3);
+
+    meter.reset();
+    assert_eq!(meter.level(), AudioLevel::SILENCE);
+    assert_eq!(meter.clipped_samples(), 0);
+    assert_eq!(meter.max_rms_dbfs(), MIN_DBFS);
+}
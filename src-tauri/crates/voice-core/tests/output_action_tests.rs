@@ -0,0 +1,114 @@
+//! 输出动作脚本测试
+//!
+//! ```bash
+//! cargo test --package voice-core --test output_action_tests
+//! ```
+
+use voice_core::action::{self, KeyCode, Modifier, OutputAction};
+
+fn text(s: &str) -> OutputAction {
+    OutputAction::Text(s.to_string())
+}
+
+fn key(modifiers: &[Modifier], key: KeyCode) -> OutputAction {
+    OutputAction::Key {
+        modifiers: modifiers.to_vec(),
+        key,
+    }
+}
+
+#[test]
+fn test_parse_text_and_keys() {
+    let actions = action::parse("好的，明天见{Enter}").unwrap();
+    assert_eq!(
+        actions,
+        vec![text("好的，明天见"), key(&[], KeyCode::Enter)]
+    );
+
+    let actions = action::parse("第一项{tab}第二项{Ctrl+Shift+Enter}").unwrap();
+    assert_eq!(
+        actions,
+        vec![
+            text("第一项"),
+            key(&[], KeyCode::Tab),
+            text("第二项"),
+            key(&[Modifier::Ctrl, Modifier::Shift], KeyCode::Enter),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_aliases_and_char_chords() {
+    let actions = action::parse("{Return}{Escape}{Command+K}{ctrl + a}").unwrap();
+    assert_eq!(
+        actions,
+        vec![
+            key(&[], KeyCode::Enter),
+            key(&[], KeyCode::Escape),
+            key(&[Modifier::Meta], KeyCode::Char('k')),
+            key(&[Modifier::Ctrl], KeyCode::Char('a')),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_escaped_braces() {
+    let actions = action::parse("fn main() {{ }}{Enter}").unwrap();
+    assert_eq!(
+        actions,
+        vec![text("fn main() { }"), key(&[], KeyCode::Enter)]
+    );
+
+    // 单独的右花括号按文字处理
+    assert_eq!(action::parse("a}b").unwrap(), vec![text("a}b")]);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(action::parse("未闭合{Enter").is_err());
+    assert!(action::parse("{Hyper+Enter}").is_err());
+    assert!(action::parse("{Launch}").is_err());
+    // 单个字符必须与修饰键组合
+    assert!(action::parse("{K}").is_err());
+    assert!(action::parse("{}").is_err());
+}
+
+#[test]
+fn test_script_round_trip() {
+    let script = "回复 {{ok}}{Tab}发送{Cmd+Shift+K}{Enter}";
+    let actions = action::parse(script).unwrap();
+    assert_eq!(action::to_script(&actions), script);
+    assert_eq!(
+        action::parse(&action::to_script(&actions)).unwrap(),
+        actions
+    );
+}
+
+#[test]
+fn test_plain_text() {
+    let actions = action::parse("你好{Enter}世界 {{x}}{Tab}").unwrap();
+    assert_eq!(action::plain_text(&actions), "你好世界 {x}");
+    assert_eq!(action::escape("{x}"), "{{x}}");
+}
+
+#[test]
+fn test_retain_allowed_keys() {
+    let allowed = vec!["enter".to_string(), "Shift+Ctrl+Enter".to_string()];
+    let actions = action::parse("好的{Ctrl+W}明天见{Enter}{Ctrl+Shift+Enter}{Cmd+Q}").unwrap();
+    let (kept, dropped) = action::retain_keys(actions, &allowed);
+    assert_eq!(
+        kept,
+        vec![
+            text("好的明天见"),
+            key(&[], KeyCode::Enter),
+            key(&[Modifier::Ctrl, Modifier::Shift], KeyCode::Enter),
+        ]
+    );
+    assert_eq!(dropped, vec!["{Ctrl+W}", "{Cmd+Q}"]);
+
+    // 空白名单或无效条目不允许任何按键
+    let actions = action::parse("a{Tab}b").unwrap();
+    let (kept, dropped) = action::retain_keys(actions, &["Hyper+Tab".to_string()]);
+    assert_eq!(kept, vec![text("ab")]);
+    assert_eq!(dropped, vec!["{Tab}"]);
+}
@@ -0,0 +1,147 @@
+//! 语音输入流程集成测试
+//!
+//! 使用 [`WavFileSource`] 回放生成的 WAV 文件代替麦克风，用假的 ASR 客户端代替云端服务，
+//! 不需要音频硬件和 API 密钥，可以在 CI 中运行。
+//!
+//! ```bash
+//! cargo test --package voice-core --test pipeline_tests
+//! ```
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use voice_core::asr_client::{AsrCapabilities, AsrClient, AudioFormat};
+use voice_core::punctuation::restore_punctuation;
+use voice_core::types::{AudioData, TranscribeResult};
+use voice_core::{AudioRecorder, VoiceError, WavFileSource};
+
+/// 生成正弦波音频
+fn sine_wave(duration_secs: f32, sample_rate: u32, channels: u16) -> AudioData {
+    let frames = (sample_rate as f32 * duration_secs) as usize;
+    let samples = (0..frames)
+        .flat_map(|i| {
+            let t = i as f32 / sample_rate as f32;
+            let sample = (0.3 * i16::MAX as f32 * (std::f32::consts::TAU * 440.0 * t).sin()) as i16;
+            std::iter::repeat_n(sample, channels as usize)
+        })
+        .collect();
+    AudioData::new(samples, sample_rate, channels)
+}
+
+/// 把音频写入临时 WAV 文件
+fn write_wav(name: &str, audio: &AudioData) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("voice-core-{}-{}.wav", name, std::process::id()));
+    std::fs::write(&path, audio.to_wav_bytes()).unwrap();
+    path
+}
+
+/// 假的 ASR 客户端：记录收到的音频时长，返回固定的无标点文本
+struct FakeAsrClient {
+    text: &'static str,
+    received_secs: Mutex<Vec<f32>>,
+}
+
+impl FakeAsrClient {
+    fn new(text: &'static str) -> Self {
+        Self {
+            text,
+            received_secs: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl AsrClient for FakeAsrClient {
+    async fn transcribe(&self, audio: &AudioData) -> voice_core::Result<TranscribeResult> {
+        self.received_secs.lock().unwrap().push(audio.duration_secs);
+        Ok(TranscribeResult {
+            text: self.text.to_string(),
+            language: Some("zh".to_string()),
+            confidence: None,
+            segments: Vec::new(),
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        AsrCapabilities {
+            streaming: false,
+            timestamps: false,
+            hotwords: false,
+            punctuation: false,
+            code_switching: false,
+            diarization: false,
+            max_duration_secs: None,
+            formats: &[AudioFormat::Pcm],
+            sample_rates: &[],
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_record_transcribe_polish_from_wav() {
+    let path = write_wav("pipeline", &sine_wave(2.0, 16000, 1));
+    let source = WavFileSource::open(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    // 录音：非实时回放，停止时等待整个文件写入
+    let mut recorder = AudioRecorder::with_source(Box::new(source));
+    recorder.start().unwrap();
+    assert!(recorder.is_recording());
+    let audio = recorder.stop().unwrap();
+    assert_eq!(audio.sample_rate, 16000);
+    assert_eq!(audio.channels, 1);
+    assert!((audio.duration_secs - 2.0).abs() < 0.01);
+    assert!(recorder.get_level().peak_dbfs > -20.0);
+
+    // 识别
+    let client = FakeAsrClient::new("今天天气不错 我们出去走走吧");
+    let result = client.transcribe(&audio).await.unwrap();
+    assert_eq!(client.received_secs.lock().unwrap().len(), 1);
+
+    // 后处理：无标点的结果按规则补全
+    let text = restore_punctuation(&result.text);
+    assert_eq!(text, "今天天气不错，我们出去走走吧。");
+}
+
+#[test]
+fn test_stereo_source_is_mixed_to_mono() {
+    let source = WavFileSource::from_audio(sine_wave(1.0, 48000, 2));
+    let mut recorder = AudioRecorder::with_source(Box::new(source));
+    recorder.start().unwrap();
+    let audio = recorder.stop().unwrap();
+
+    assert_eq!(audio.sample_rate, 48000);
+    assert_eq!(audio.channels, 1);
+    assert_eq!(audio.samples.len(), 48000);
+}
+
+#[test]
+fn test_realtime_source_stops_early() {
+    let source = WavFileSource::from_audio(sine_wave(5.0, 16000, 1)).realtime(true);
+    let mut recorder = AudioRecorder::with_source(Box::new(source));
+    recorder.start().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(200));
+
+    // 实时回放只写入了约 0.2 秒，视为录音过短
+    assert!(matches!(
+        recorder.stop(),
+        Err(VoiceError::RecordingTooShort)
+    ));
+}
+
+#[test]
+fn test_cancel_discards_recording() {
+    let source = WavFileSource::from_audio(sine_wave(1.0, 16000, 1)).realtime(true);
+    let mut recorder = AudioRecorder::with_source(Box::new(source));
+    recorder.start().unwrap();
+    recorder.cancel();
+
+    assert!(!recorder.is_recording());
+    assert!(recorder.stop().is_err());
+}
@@ -0,0 +1,122 @@
+//! 音频隐私策略测试
+//!
+//! 隐私策略是全局状态，修改策略的断言集中在一个测试中，避免并行测试互相干扰。
+//!
+//! ```bash
+//! cargo test --package voice-core --test privacy_tests
+//! ```
+
+use std::time::Duration;
+
+use voice_core::asr_client::{create_client, AsrClientConfig};
+use voice_core::privacy::{self, AudioPrivacy, TEMP_AUDIO_PREFIX};
+use voice_core::VoiceError;
+
+fn custom_whisper(base_url: &str) -> AsrClientConfig {
+    AsrClientConfig::CustomWhisper {
+        base_url: base_url.to_string(),
+        api_key: None,
+        model: None,
+        timeout_secs: 30,
+        language: "zh".to_string(),
+    }
+}
+
+fn deepgram() -> AsrClientConfig {
+    AsrClientConfig::Deepgram {
+        api_key: "key".to_string(),
+        model: None,
+        diarize: false,
+        language: "zh".to_string(),
+    }
+}
+
+#[test]
+fn test_is_local() {
+    assert!(custom_whisper("http://localhost:8000").is_local());
+    assert!(custom_whisper("http://127.0.0.1:8000/v1").is_local());
+    assert!(custom_whisper("http://[::1]:8000").is_local());
+    assert!(!custom_whisper("http://192.168.1.10:8000").is_local());
+    assert!(!custom_whisper("https://localhost.example.com").is_local());
+    assert!(!deepgram().is_local());
+    assert!(AsrClientConfig::Command {
+        program: "whisper".to_string(),
+        args: Vec::new(),
+        timeout_secs: 60,
+        language: "zh".to_string(),
+    }
+    .is_local());
+}
+
+#[tokio::test]
+async fn test_policy_enforced() {
+    assert_eq!(privacy::policy(), AudioPrivacy::default());
+    assert!(create_client(&deepgram()).is_ok());
+
+    privacy::set_policy(AudioPrivacy {
+        allow_upload: false,
+        allow_disk: false,
+        temp_retention: None,
+    });
+
+    // 禁止上传：云端服务无法构建，本机服务不受影响
+    assert!(matches!(
+        create_client(&deepgram()),
+        Err(VoiceError::PrivacyError(_))
+    ));
+    assert!(matches!(
+        create_client(&custom_whisper("http://192.168.1.10:8000")),
+        Err(VoiceError::PrivacyError(_))
+    ));
+    assert!(create_client(&custom_whisper("http://127.0.0.1:8000")).is_ok());
+
+    // 禁止写盘：需要临时文件的命令识别失败
+    #[cfg(unix)]
+    {
+        use voice_core::asr_client::{AsrClient, CommandClient};
+        use voice_core::AudioData;
+
+        let client = CommandClient::new(
+            "sh".to_string(),
+            vec![
+                "-c".to_string(),
+                r#"echo '{"text": "ok"}'"#.to_string(),
+                "sh".to_string(),
+                "{file}".to_string(),
+            ],
+        );
+        let audio = AudioData::new(vec![0; 16000], 16000, 1);
+        assert!(matches!(
+            client.transcribe(&audio).await,
+            Err(VoiceError::PrivacyError(_))
+        ));
+    }
+
+    privacy::set_policy(AudioPrivacy::default());
+    assert!(create_client(&deepgram()).is_ok());
+}
+
+#[test]
+fn test_purge_temp_audio() {
+    let dir = std::env::temp_dir().join(format!("voice-core-privacy-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let temp_audio = dir.join(format!("{}1-0.wav", TEMP_AUDIO_PREFIX));
+    let other = dir.join("recording.wav");
+    std::fs::write(&temp_audio, b"RIFF").unwrap();
+    std::fs::write(&other, b"RIFF").unwrap();
+
+    // 未过期的文件保留
+    assert_eq!(
+        privacy::purge_temp_audio(&dir, Duration::from_secs(3600)),
+        0
+    );
+    assert!(temp_audio.exists());
+
+    // 过期的临时音频删除，其他文件不动
+    assert_eq!(privacy::purge_temp_audio(&dir, Duration::ZERO), 1);
+    assert!(!temp_audio.exists());
+    assert!(other.exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(privacy::purge_temp_audio(&dir, Duration::ZERO), 0);
+}
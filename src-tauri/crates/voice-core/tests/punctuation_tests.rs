@@ -0,0 +1,101 @@
+//! 规则标点恢复测试
+
+use voice_core::punctuation::{has_punctuation, restore_punctuation};
+
+#[test]
+fn test_has_punctuation() {
+    assert!(has_punctuation("你好，世界"));
+    assert!(has_punctuation("hello."));
+    assert!(!has_punctuation("你好 世界"));
+    assert!(!has_punctuation(""));
+}
+
+#[test]
+fn test_unchanged_inputs() {
+    assert_eq!(restore_punctuation(""), "");
+    assert_eq!(restore_punctuation("   "), "");
+    // 已有标点时只去掉首尾空白
+    assert_eq!(restore_punctuation(" 你好，世界 "), "你好，世界");
+    // 无法判断语言时原样返回
+    assert_eq!(restore_punctuation("123 456"), "123 456");
+}
+
+#[test]
+fn test_chinese_statement() {
+    assert_eq!(restore_punctuation("今天天气很好"), "今天天气很好。");
+    // 停顿处加逗号
+    assert_eq!(
+        restore_punctuation("今天天气很好 我们出去走走"),
+        "今天天气很好，我们出去走走。"
+    );
+}
+
+#[test]
+fn test_chinese_connective_min_clause() {
+    // 连接词前的小句达到 4 个字才加逗号
+    assert_eq!(
+        restore_punctuation("我想去公园但是下雨了"),
+        "我想去公园，但是下雨了。"
+    );
+    assert_eq!(restore_punctuation("我去但是下雨了"), "我去但是下雨了。");
+    // 恰好 4 个字
+    assert_eq!(
+        restore_punctuation("我很想去所以去了"),
+        "我很想去，所以去了。"
+    );
+    // 句首连接词不加逗号
+    assert_eq!(restore_punctuation("但是我不想去"), "但是我不想去。");
+}
+
+#[test]
+fn test_chinese_question() {
+    assert_eq!(restore_punctuation("你吃饭了吗"), "你吃饭了吗？");
+    assert_eq!(restore_punctuation("你在干什么"), "你在干什么？");
+    // 只看最后一个小句
+    assert_eq!(
+        restore_punctuation("什么都别说 我们走吧"),
+        "什么都别说，我们走吧。"
+    );
+    assert_eq!(
+        restore_punctuation("我们走吧 你去不去哪里"),
+        "我们走吧，你去不去哪里？"
+    );
+}
+
+#[test]
+fn test_chinese_keeps_latin_spacing() {
+    assert_eq!(
+        restore_punctuation("我在用 GitHub 写代码"),
+        "我在用 GitHub 写代码。"
+    );
+}
+
+#[test]
+fn test_english_statement() {
+    assert_eq!(restore_punctuation("hello world"), "Hello world.");
+    assert_eq!(restore_punctuation("yes i agree"), "Yes I agree.");
+}
+
+#[test]
+fn test_english_question() {
+    assert_eq!(
+        restore_punctuation("how are you doing"),
+        "How are you doing?"
+    );
+    assert_eq!(restore_punctuation("Can you help"), "Can you help?");
+    // 疑问词不在句首时按陈述句处理
+    assert_eq!(restore_punctuation("tell me how"), "Tell me how.");
+}
+
+#[test]
+fn test_english_but_comma() {
+    // "but" 前至少 4 个单词才加逗号
+    assert_eq!(
+        restore_punctuation("i wanted to go but it rained"),
+        "I wanted to go, but it rained."
+    );
+    assert_eq!(
+        restore_punctuation("i tried but failed"),
+        "I tried but failed."
+    );
+}
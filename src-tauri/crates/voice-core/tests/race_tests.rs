@@ -0,0 +1,143 @@
+//! ASR 竞速识别测试
+//!
+//! 验证 `AsrRace` 采用最先成功的结果、取消其余请求，以及全部失败时返回的错误。
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use voice_core::asr_client::{AsrCapabilities, AsrClient, AsrRace};
+use voice_core::error::{Result, VoiceError};
+use voice_core::types::{AudioData, TranscribeResult};
+
+/// 延迟一段时间后按预设结果返回的测试客户端
+struct DelayedClient {
+    name: &'static str,
+    delay: Duration,
+    outcome: fn() -> Result<&'static str>,
+    max_duration_secs: Option<f32>,
+    /// 识别完成（未被取消）的次数
+    finished: Arc<AtomicUsize>,
+}
+
+impl DelayedClient {
+    fn new(
+        name: &'static str,
+        delay_ms: u64,
+        outcome: fn() -> Result<&'static str>,
+    ) -> (Self, Arc<AtomicUsize>) {
+        let finished = Arc::new(AtomicUsize::new(0));
+        let client = Self {
+            name,
+            delay: Duration::from_millis(delay_ms),
+            outcome,
+            max_duration_secs: None,
+            finished: finished.clone(),
+        };
+        (client, finished)
+    }
+}
+
+#[async_trait]
+impl AsrClient for DelayedClient {
+    async fn transcribe(&self, _audio: &AudioData) -> Result<TranscribeResult> {
+        tokio::time::sleep(self.delay).await;
+        self.finished.fetch_add(1, Ordering::SeqCst);
+        let text = (self.outcome)()?;
+        Ok(TranscribeResult {
+            text: text.to_string(),
+            language: Some("zh".to_string()),
+            confidence: None,
+            segments: Vec::new(),
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        AsrCapabilities {
+            streaming: false,
+            timestamps: false,
+            hotwords: false,
+            punctuation: true,
+            code_switching: false,
+            diarization: false,
+            max_duration_secs: self.max_duration_secs,
+            formats: &[],
+            sample_rates: &[],
+        }
+    }
+}
+
+fn audio() -> AudioData {
+    AudioData::new(vec![0; 16000], 16000, 1)
+}
+
+#[tokio::test]
+async fn test_fastest_success_wins_and_others_cancelled() {
+    let (slow, slow_finished) = DelayedClient::new("慢", 500, || Ok("慢结果"));
+    let (fast, _) = DelayedClient::new("快", 10, || Ok("快结果"));
+    let race = AsrRace::new(vec![Box::new(slow), Box::new(fast)]).unwrap();
+    assert_eq!(race.backends(), vec!["慢", "快"]);
+
+    let result = race.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "快结果");
+    assert_eq!(result.backend.as_deref(), Some("快"));
+
+    // 落后的请求已被取消，不会再完成
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert_eq!(slow_finished.load(Ordering::SeqCst), 0);
+    // 名称和能力按首选服务
+    assert_eq!(race.name(), "慢");
+}
+
+#[tokio::test]
+async fn test_failure_does_not_end_race() {
+    let (failing, _) = DelayedClient::new("失败", 10, || {
+        Err(VoiceError::AsrError("音频无法识别".to_string()))
+    });
+    let (healthy, _) = DelayedClient::new("可用", 50, || Ok("你好"));
+    let race = AsrRace::new(vec![Box::new(failing), Box::new(healthy)]).unwrap();
+
+    let result = race.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.backend.as_deref(), Some("可用"));
+}
+
+#[tokio::test]
+async fn test_primary_error_returned_when_all_fail() {
+    let (primary, _) = DelayedClient::new("首选", 50, || {
+        Err(VoiceError::AsrAuthError("首选鉴权失败".to_string()))
+    });
+    let (secondary, _) = DelayedClient::new("备用", 10, || {
+        Err(VoiceError::NetworkError("备用不可达".to_string()))
+    });
+    let race = AsrRace::new(vec![Box::new(primary), Box::new(secondary)]).unwrap();
+
+    let err = race.transcribe(&audio()).await.unwrap_err();
+    assert!(matches!(err, VoiceError::AsrAuthError(ref m) if m == "首选鉴权失败"));
+}
+
+#[tokio::test]
+async fn test_skips_contender_that_cannot_handle_duration() {
+    let (primary, _) = DelayedClient::new("首选", 100, || Ok("首选结果"));
+    let (mut short, short_finished) = DelayedClient::new("短音频", 10, || Ok("短音频结果"));
+    short.max_duration_secs = Some(0.5);
+    let race = AsrRace::new(vec![Box::new(primary), Box::new(short)]).unwrap();
+
+    // 1 秒录音超过“短音频”的上限
+    let result = race.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.backend.as_deref(), Some("首选"));
+    assert_eq!(short_finished.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_empty_race_rejected() {
+    assert!(matches!(
+        AsrRace::new(Vec::new()),
+        Err(VoiceError::ConfigError(_))
+    ));
+}
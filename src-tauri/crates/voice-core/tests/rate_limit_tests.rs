@@ -0,0 +1,138 @@
+//! ASR 请求限流测试
+//!
+//! 验证令牌桶的突发容量、超出后排队而不是拒绝，以及按凭证 ID 共用令牌桶。
+//!
+//! ```bash
+//! cargo test --package voice-core --test rate_limit_tests
+//! ```
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use voice_core::asr_client::{
+    AsrCapabilities, AsrClient, AsrClientConfig, AsrRateLimit, RateLimit, RateLimiter, TokenBucket,
+};
+use voice_core::error::Result;
+use voice_core::types::{AudioData, TranscribeResult};
+
+/// 记录请求次数的测试客户端
+struct CountingClient {
+    requests: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl AsrClient for CountingClient {
+    async fn transcribe(&self, _audio: &AudioData) -> Result<TranscribeResult> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        Ok(TranscribeResult {
+            text: "你好".to_string(),
+            language: Some("zh".to_string()),
+            confidence: None,
+            segments: Vec::new(),
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "counting"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        AsrCapabilities {
+            streaming: false,
+            timestamps: false,
+            hotwords: false,
+            punctuation: true,
+            code_switching: false,
+            diarization: false,
+            max_duration_secs: None,
+            formats: &[],
+            sample_rates: &[],
+        }
+    }
+}
+
+fn audio() -> AudioData {
+    AudioData::new(vec![0; 1600], 16000, 1)
+}
+
+#[tokio::test]
+async fn test_burst_then_queue() {
+    // 每秒 20 个令牌：容量 2 用完后每 50ms 放行一个请求
+    let bucket = TokenBucket::new(RateLimit::new(20.0, 2));
+    let start = Instant::now();
+    assert!(bucket.acquire().await < Duration::from_millis(10));
+    assert!(bucket.acquire().await < Duration::from_millis(10));
+
+    bucket.acquire().await;
+    bucket.acquire().await;
+    assert!(start.elapsed() >= Duration::from_millis(90));
+}
+
+#[tokio::test]
+async fn test_unlimited() {
+    let bucket = TokenBucket::new(RateLimit::new(0.0, 1));
+    let start = Instant::now();
+    for _ in 0..100 {
+        bucket.acquire().await;
+    }
+    assert!(start.elapsed() < Duration::from_millis(50));
+}
+
+#[tokio::test]
+async fn test_client_queues_excess_requests() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let bucket = Arc::new(TokenBucket::new(RateLimit::new(20.0, 1)));
+    let client = Arc::new(AsrRateLimit::new(
+        Box::new(CountingClient {
+            requests: requests.clone(),
+        }),
+        bucket,
+    ));
+
+    let start = Instant::now();
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.transcribe(&audio()).await })
+        })
+        .collect();
+    for task in tasks {
+        // 超出的请求排队后成功，而不是被拒绝
+        assert_eq!(task.await.unwrap().unwrap().text, "你好");
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 4);
+    assert!(start.elapsed() >= Duration::from_millis(140));
+}
+
+#[test]
+fn test_buckets_shared_by_key() {
+    let limiter = RateLimiter::new();
+    let limit = RateLimit::new(2.0, 1);
+    let a = limiter.bucket("baidu-1", limit);
+    assert!(Arc::ptr_eq(&a, &limiter.bucket("baidu-1", limit)));
+    assert!(!Arc::ptr_eq(&a, &limiter.bucket("baidu-2", limit)));
+
+    // 限流参数变化后换成新的令牌桶
+    let changed = limiter.bucket("baidu-1", RateLimit::new(5.0, 1));
+    assert!(!Arc::ptr_eq(&a, &changed));
+    assert_eq!(changed.limit(), RateLimit::new(5.0, 1));
+}
+
+#[test]
+fn test_default_rate_limit() {
+    let baidu = AsrClientConfig::Baidu {
+        api_key: "key".to_string(),
+        secret_key: "secret".to_string(),
+    };
+    assert!(baidu.default_rate_limit().is_some());
+
+    let openai = AsrClientConfig::OpenAI {
+        api_key: "key".to_string(),
+        base_url: None,
+        language: "zh".to_string(),
+    };
+    assert_eq!(openai.default_rate_limit(), None);
+}
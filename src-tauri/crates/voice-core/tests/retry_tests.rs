@@ -0,0 +1,127 @@
+//! ASR 请求重试测试
+//!
+//! 验证 `AsrRetry` 只重试网络错误、遵守最多尝试次数，以及退避时间的计算。
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use voice_core::asr_client::{AsrCapabilities, AsrClient, AsrRetry, RetryPolicy};
+use voice_core::error::{Result, VoiceError};
+use voice_core::types::{AudioData, TranscribeResult};
+
+/// 前几次请求按预设错误失败、之后成功的测试客户端
+struct FlakyClient {
+    failures: usize,
+    error: fn() -> VoiceError,
+    attempts: Arc<AtomicUsize>,
+}
+
+impl FlakyClient {
+    fn new(failures: usize, error: fn() -> VoiceError) -> (Self, Arc<AtomicUsize>) {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let client = Self {
+            failures,
+            error,
+            attempts: attempts.clone(),
+        };
+        (client, attempts)
+    }
+}
+
+#[async_trait]
+impl AsrClient for FlakyClient {
+    async fn transcribe(&self, _audio: &AudioData) -> Result<TranscribeResult> {
+        if self.attempts.fetch_add(1, Ordering::SeqCst) < self.failures {
+            return Err((self.error)());
+        }
+        Ok(TranscribeResult {
+            text: "你好".to_string(),
+            language: Some("zh".to_string()),
+            confidence: None,
+            segments: Vec::new(),
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "flaky"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        AsrCapabilities {
+            streaming: false,
+            timestamps: false,
+            hotwords: false,
+            punctuation: true,
+            code_switching: false,
+            diarization: false,
+            max_duration_secs: None,
+            formats: &[],
+            sample_rates: &[],
+        }
+    }
+}
+
+fn audio() -> AudioData {
+    AudioData::new(vec![0; 16000], 16000, 1)
+}
+
+fn network_error() -> VoiceError {
+    VoiceError::NetworkError("WebSocket 连接失败".to_string())
+}
+
+fn fast_policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_retries_network_errors_until_success() {
+    let (client, attempts) = FlakyClient::new(2, network_error);
+    let retry = AsrRetry::new(Box::new(client), fast_policy(3));
+
+    let result = retry.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "你好");
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_attempts() {
+    let (client, attempts) = FlakyClient::new(5, network_error);
+    let retry = AsrRetry::new(Box::new(client), fast_policy(3));
+
+    let err = retry.transcribe(&audio()).await.unwrap_err();
+    assert!(matches!(err, VoiceError::NetworkError(_)));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_does_not_retry_other_errors() {
+    let (client, attempts) =
+        FlakyClient::new(1, || VoiceError::AsrAuthError("密钥无效".to_string()));
+    let retry = AsrRetry::new(Box::new(client), fast_policy(3));
+
+    let err = retry.transcribe(&audio()).await.unwrap_err();
+    assert!(matches!(err, VoiceError::AsrAuthError(_)));
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_exponential_backoff_is_capped() {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(500),
+        max_backoff: Duration::from_secs(3),
+        multiplier: 2.0,
+        ..Default::default()
+    };
+    assert_eq!(policy.backoff(1), Duration::from_millis(500));
+    assert_eq!(policy.backoff(2), Duration::from_millis(1000));
+    assert_eq!(policy.backoff(3), Duration::from_millis(2000));
+    assert_eq!(policy.backoff(4), Duration::from_secs(3));
+}
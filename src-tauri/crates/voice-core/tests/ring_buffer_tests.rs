@@ -0,0 +1,108 @@
+//! 无锁环形缓冲区测试
+
+use voice_core::ring_buffer::ring_buffer;
+
+#[test]
+fn test_capacity_rounds_up_to_power_of_two() {
+    let (producer, _consumer) = ring_buffer::<i16>(100);
+    assert_eq!(producer.free_len(), 128);
+
+    let (producer, _consumer) = ring_buffer::<i16>(0);
+    assert_eq!(producer.free_len(), 2);
+}
+
+#[test]
+fn test_empty_buffer() {
+    let (_producer, mut consumer) = ring_buffer::<i16>(8);
+    assert!(consumer.is_empty());
+    assert_eq!(consumer.len(), 0);
+
+    let mut out = [0i16; 4];
+    assert_eq!(consumer.pop_slice(&mut out), 0);
+    assert_eq!(consumer.dropped(), 0);
+}
+
+#[test]
+fn test_full_buffer_drops_whole_block() {
+    let (mut producer, mut consumer) = ring_buffer::<i16>(8);
+    assert!(producer.push(&[1, 2, 3, 4, 5, 6]));
+    assert_eq!(producer.free_len(), 2);
+
+    // 剩余空间不足时整块丢弃，不写入部分数据
+    assert!(!producer.push(&[7, 8, 9]));
+    assert_eq!(consumer.dropped(), 3);
+    assert_eq!(consumer.len(), 6);
+
+    // 恰好填满
+    assert!(producer.push(&[7, 8]));
+    assert_eq!(producer.free_len(), 0);
+    assert!(!producer.push(&[9]));
+    assert_eq!(consumer.dropped(), 4);
+
+    let mut out = [0i16; 8];
+    assert_eq!(consumer.pop_slice(&mut out), 8);
+    assert_eq!(out, [1, 2, 3, 4, 5, 6, 7, 8]);
+    assert!(consumer.is_empty());
+    assert_eq!(producer.free_len(), 8);
+}
+
+#[test]
+fn test_wraparound_preserves_order() {
+    let (mut producer, mut consumer) = ring_buffer::<i16>(8);
+    let mut out = [0i16; 8];
+
+    // 反复写读 5 个元素，使读写位置多次越过缓冲区末尾
+    let mut next = 0i16;
+    for _ in 0..20 {
+        let block: Vec<i16> = (next..next + 5).collect();
+        assert!(producer.push(&block));
+        assert_eq!(consumer.pop_slice(&mut out[..5]), 5);
+        assert_eq!(&out[..5], block.as_slice());
+        next += 5;
+    }
+
+    // 跨越末尾的部分读取
+    assert!(producer.push(&[100, 101, 102, 103, 104, 105]));
+    assert_eq!(consumer.pop_slice(&mut out[..4]), 4);
+    assert_eq!(&out[..4], &[100, 101, 102, 103]);
+    assert!(producer.push(&[106, 107, 108, 109, 110, 111]));
+    assert_eq!(consumer.pop_slice(&mut out), 8);
+    assert_eq!(out, [104, 105, 106, 107, 108, 109, 110, 111]);
+    assert_eq!(consumer.dropped(), 0);
+}
+
+#[test]
+fn test_producer_consumer_threads() {
+    const TOTAL: u32 = 200_000;
+    const BLOCK: u32 = 50;
+
+    let (mut producer, mut consumer) = ring_buffer::<u32>(256);
+
+    let writer = std::thread::spawn(move || {
+        let mut next = 0;
+        while next < TOTAL {
+            let block: Vec<u32> = (next..next + BLOCK).collect();
+            // 缓冲区满时等待消费者，避免丢弃
+            while producer.free_len() < block.len() {
+                std::thread::yield_now();
+            }
+            assert!(producer.push(&block));
+            next += BLOCK;
+        }
+    });
+
+    let mut received = Vec::with_capacity(TOTAL as usize);
+    let mut out = [0u32; 64];
+    while received.len() < TOTAL as usize {
+        let count = consumer.pop_slice(&mut out);
+        if count == 0 {
+            std::thread::yield_now();
+        }
+        received.extend_from_slice(&out[..count]);
+    }
+    writer.join().unwrap();
+
+    assert_eq!(consumer.dropped(), 0);
+    assert!(consumer.is_empty());
+    assert!(received.iter().copied().eq(0..TOTAL));
+}
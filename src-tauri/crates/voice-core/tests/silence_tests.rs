@@ -0,0 +1,157 @@
+//! 首尾静音裁剪与按停顿切分测试
+
+use voice_core::silence::{speech_range, split_at_pauses, SilenceTrimOptions};
+use voice_core::types::AudioData;
+
+const RATE: u32 = 16000;
+/// 10ms 一帧
+const FRAME: usize = 160;
+
+/// 约 -30dBFS 的方波
+fn voiced(len: usize) -> Vec<i16> {
+    (0..len)
+        .map(|i| if i % 2 == 0 { 1000 } else { -1000 })
+        .collect()
+}
+
+/// 静音 + 说话 + 静音
+fn padded(lead: usize, speech: usize, tail: usize) -> Vec<i16> {
+    let mut samples = vec![0i16; lead];
+    samples.extend(voiced(speech));
+    samples.extend(vec![0i16; tail]);
+    samples
+}
+
+fn options(padding_ms: u32) -> SilenceTrimOptions {
+    SilenceTrimOptions {
+        padding_ms,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_speech_range_silent_or_empty() {
+    assert_eq!(speech_range(&[], RATE, &options(0)), None);
+    assert_eq!(speech_range(&[0; 16000], RATE, &options(0)), None);
+    // 低于阈值的底噪视为静音（约 -70dBFS）
+    assert_eq!(speech_range(&[10; 16000], RATE, &options(0)), None);
+}
+
+#[test]
+fn test_speech_range_frame_aligned() {
+    let samples = padded(FRAME * 50, FRAME * 30, FRAME * 20);
+    assert_eq!(
+        speech_range(&samples, RATE, &options(0)),
+        Some(FRAME * 50..FRAME * 80)
+    );
+    // 余量 100ms = 10 帧
+    assert_eq!(
+        speech_range(&samples, RATE, &options(100)),
+        Some(FRAME * 40..FRAME * 90)
+    );
+}
+
+#[test]
+fn test_speech_range_padding_clamped() {
+    let samples = padded(FRAME * 2, FRAME * 10, FRAME * 3);
+    assert_eq!(
+        speech_range(&samples, RATE, &options(500)),
+        Some(0..samples.len())
+    );
+}
+
+#[test]
+fn test_speech_range_threshold() {
+    let samples = padded(FRAME * 10, FRAME * 10, FRAME * 10);
+    // 说话约 -30dBFS，阈值高于说话电平时全部视为静音
+    let strict = SilenceTrimOptions {
+        threshold_dbfs: -20.0,
+        padding_ms: 0,
+    };
+    assert_eq!(speech_range(&samples, RATE, &strict), None);
+    let loose = SilenceTrimOptions {
+        threshold_dbfs: -40.0,
+        padding_ms: 0,
+    };
+    assert_eq!(
+        speech_range(&samples, RATE, &loose),
+        Some(FRAME * 10..FRAME * 20)
+    );
+}
+
+#[test]
+fn test_trim_silence() {
+    let mut audio = AudioData::new(padded(RATE as usize, RATE as usize, RATE as usize), RATE, 1);
+    let trimmed = audio.trim_silence(&options(200));
+    assert!((trimmed - 1.6).abs() < 1e-3);
+    assert!((audio.duration_secs - 1.4).abs() < 1e-3);
+    assert_eq!(audio.samples.len(), 22400);
+    assert_eq!(audio.samples[3200], 1000);
+}
+
+#[test]
+fn test_trim_silence_keeps_unsupported_audio() {
+    // 全部静音时保持原样
+    let mut silent = AudioData::new(vec![0; 16000], RATE, 1);
+    assert_eq!(silent.trim_silence(&options(0)), 0.0);
+    assert_eq!(silent.samples.len(), 16000);
+
+    // 多声道不裁剪
+    let mut stereo = AudioData::new(padded(16000, 16000, 16000), RATE, 2);
+    assert_eq!(stereo.trim_silence(&options(0)), 0.0);
+    assert_eq!(stereo.samples.len(), 48000);
+
+    // 无静音可裁时返回 0
+    let mut full = AudioData::new(voiced(16000), RATE, 1);
+    assert_eq!(full.trim_silence(&options(0)), 0.0);
+}
+
+#[test]
+fn test_split_short_audio() {
+    assert!(split_at_pauses(&[], RATE, 10.0).is_empty());
+    let samples = voiced(RATE as usize * 5);
+    assert_eq!(
+        split_at_pauses(&samples, RATE, 10.0),
+        vec![0..samples.len()]
+    );
+    // 恰好等于上限时不切分
+    let samples = voiced(RATE as usize * 10);
+    assert_eq!(
+        split_at_pauses(&samples, RATE, 10.0),
+        vec![0..samples.len()]
+    );
+}
+
+#[test]
+fn test_split_at_pause() {
+    // 8 秒说话后停顿 0.5 秒，再说 6 秒
+    let pause_start = RATE as usize * 8;
+    let mut samples = voiced(pause_start);
+    samples.extend(vec![0i16; RATE as usize / 2]);
+    samples.extend(voiced(RATE as usize * 6));
+
+    let ranges = split_at_pauses(&samples, RATE, 10.0);
+    assert_eq!(ranges.len(), 2);
+    assert_eq!(ranges[0].start, 0);
+    assert_eq!(ranges[0].end, ranges[1].start);
+    assert_eq!(ranges[1].end, samples.len());
+    // 切分点落在停顿内
+    assert!(ranges[0].end > pause_start && ranges[0].end < pause_start + RATE as usize / 2);
+}
+
+#[test]
+fn test_split_covers_long_audio() {
+    let samples = voiced(RATE as usize * 95);
+    let max_len = RATE as usize * 10;
+    let ranges = split_at_pauses(&samples, RATE, 10.0);
+
+    assert!(ranges.len() >= 10);
+    let mut expected_start = 0;
+    for range in &ranges {
+        assert_eq!(range.start, expected_start);
+        assert!(range.len() <= max_len);
+        assert!(!range.is_empty());
+        expected_start = range.end;
+    }
+    assert_eq!(expected_start, samples.len());
+}
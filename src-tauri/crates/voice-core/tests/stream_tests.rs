@@ -0,0 +1,76 @@
+//! 流式识别测试
+//!
+//! 验证 `AsrClient::transcribe_stream` 默认实现收齐音频后整段识别。
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use voice_core::asr_client::{AsrCapabilities, AsrClient};
+use voice_core::error::{Result, VoiceError};
+use voice_core::types::{AudioData, TranscribeResult};
+
+/// 返回收到的采样数和采样率的测试客户端
+struct CountingClient {
+    fail: bool,
+}
+
+#[async_trait]
+impl AsrClient for CountingClient {
+    async fn transcribe(&self, audio: &AudioData) -> Result<TranscribeResult> {
+        if self.fail {
+            return Err(VoiceError::AsrError("识别失败".to_string()));
+        }
+        Ok(TranscribeResult {
+            text: format!("{}@{}", audio.samples.len(), audio.sample_rate),
+            language: Some("zh".to_string()),
+            confidence: None,
+            segments: Vec::new(),
+            backend: None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "测试"
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        AsrCapabilities {
+            streaming: false,
+            timestamps: false,
+            hotwords: false,
+            punctuation: false,
+            code_switching: false,
+            diarization: false,
+            max_duration_secs: None,
+            formats: &[],
+            sample_rates: &[],
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_default_stream_yields_single_final_result() {
+    let client = CountingClient { fail: false };
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    tx.send(vec![1; 160]).unwrap();
+    tx.send(vec![2; 320]).unwrap();
+    drop(tx);
+
+    let items: Vec<_> = client.transcribe_stream(rx, 48000).collect().await;
+    assert_eq!(items.len(), 1);
+
+    let last = items.into_iter().next().unwrap().unwrap();
+    assert!(last.is_final);
+    assert_eq!(last.text, "480@48000");
+    assert_eq!(last.result.unwrap().language.as_deref(), Some("zh"));
+}
+
+#[tokio::test]
+async fn test_default_stream_ends_with_error() {
+    let client = CountingClient { fail: true };
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    drop(tx);
+
+    let items: Vec<_> = client.transcribe_stream(rx, 16000).collect().await;
+    assert_eq!(items.len(), 1);
+    assert!(items[0].is_err());
+}
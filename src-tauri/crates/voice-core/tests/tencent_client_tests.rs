@@ -0,0 +1,144 @@
+//! 腾讯云一句话识别客户端集成测试
+//!
+//! 在本地端口启动一个只响应一次的 HTTP 服务模拟腾讯云接口，不需要网络。
+//! 按签名方法 v3 用实际收到的请求重新计算签名，校验 `Authorization` 请求头。
+//!
+//! ```bash
+//! cargo test --package voice-core --test tencent_client_tests
+//! ```
+
+mod common;
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use voice_core::asr_client::factory::tencent_engine;
+use voice_core::asr_client::{create_client, AsrClient, AsrClientConfig, TencentClient};
+use voice_core::VoiceError;
+
+use common::{audio, serve_once, Request};
+
+const SECRET_ID: &str = "AKIDtest";
+const SECRET_KEY: &str = "secret";
+
+fn hmac_sha256(key: &[u8], message: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+    mac.update(message.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 按签名方法 v3 计算期望的签名
+fn expected_signature(request: &Request) -> String {
+    let timestamp: i64 = request.header("x-tc-timestamp").unwrap().parse().unwrap();
+    let date = chrono::DateTime::from_timestamp(timestamp, 0)
+        .unwrap()
+        .format("%Y-%m-%d")
+        .to_string();
+    let canonical_request = format!(
+        "POST\n/\n\ncontent-type:{}\nhost:{}\nx-tc-action:sentencerecognition\n\ncontent-type;host;x-tc-action\n{}",
+        request.header("content-type").unwrap(),
+        request.header("host").unwrap(),
+        hex(&Sha256::digest(&request.body))
+    );
+    let string_to_sign = format!(
+        "TC3-HMAC-SHA256\n{}\n{}/asr/tc3_request\n{}",
+        timestamp,
+        date,
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+    let secret_date = hmac_sha256(format!("TC3{}", SECRET_KEY).as_bytes(), &date);
+    let secret_service = hmac_sha256(&secret_date, "asr");
+    let secret_signing = hmac_sha256(&secret_service, "tc3_request");
+    hex(&hmac_sha256(&secret_signing, &string_to_sign))
+}
+
+#[tokio::test]
+async fn test_sentence_recognition_signed_request() {
+    let (url, server) = serve_once(
+        "200 OK",
+        r#"{"Response": {"Result": "今天天气不错", "AudioDuration": 1000, "RequestId": "req-1"}}"#,
+    )
+    .await;
+
+    let client = TencentClient::new(SECRET_ID.to_string(), SECRET_KEY.to_string())
+        .with_endpoint(url)
+        .with_region("ap-shanghai".to_string());
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "今天天气不错");
+    assert_eq!(result.language.as_deref(), Some("zh"));
+
+    let request = server.await.unwrap();
+    assert_eq!(request.header("x-tc-action"), Some("SentenceRecognition"));
+    assert_eq!(request.header("x-tc-version"), Some("2019-06-14"));
+    assert_eq!(request.header("x-tc-region"), Some("ap-shanghai"));
+
+    let authorization = request.header("authorization").unwrap();
+    let prefix = format!("TC3-HMAC-SHA256 Credential={}/", SECRET_ID);
+    assert!(authorization.starts_with(&prefix), "{}", authorization);
+    assert!(
+        authorization.contains("/asr/tc3_request, SignedHeaders=content-type;host;x-tc-action, ")
+    );
+    assert!(
+        authorization.ends_with(&format!("Signature={}", expected_signature(&request))),
+        "{}",
+        authorization
+    );
+
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["EngSerViceType"], "16k_zh");
+    assert_eq!(body["SourceType"], 1);
+    assert_eq!(body["VoiceFormat"], "pcm");
+    assert_eq!(body["DataLen"], 32000);
+}
+
+#[tokio::test]
+async fn test_error_response() {
+    let (url, _server) = serve_once(
+        "200 OK",
+        r#"{"Response": {"Error": {"Code": "AuthFailure.SignatureFailure", "Message": "签名错误"}, "RequestId": "req-2"}}"#,
+    )
+    .await;
+    let client =
+        TencentClient::new(SECRET_ID.to_string(), SECRET_KEY.to_string()).with_endpoint(url);
+    let error = client.transcribe(&audio()).await.unwrap_err();
+    assert!(matches!(error, VoiceError::AsrAuthError(_)), "{}", error);
+    assert!(error.to_string().contains("req-2"));
+
+    let (url, _server) = serve_once(
+        "200 OK",
+        r#"{"Response": {"Error": {"Code": "FailedOperation.ErrorRecognize", "Message": "识别失败"}}}"#,
+    )
+    .await;
+    let client =
+        TencentClient::new(SECRET_ID.to_string(), SECRET_KEY.to_string()).with_endpoint(url);
+    assert!(matches!(
+        client.transcribe(&audio()).await,
+        Err(VoiceError::AsrError(_))
+    ));
+}
+
+#[test]
+fn test_config_and_engine() {
+    let config = |secret_key: &str| AsrClientConfig::Tencent {
+        secret_id: SECRET_ID.to_string(),
+        secret_key: secret_key.to_string(),
+        region: None,
+        language: "en".to_string(),
+    };
+    assert!(matches!(
+        create_client(&config(" ")),
+        Err(VoiceError::ConfigError(_))
+    ));
+    assert_eq!(
+        create_client(&config(SECRET_KEY)).unwrap().name(),
+        "腾讯云语音"
+    );
+
+    assert_eq!(tencent_engine("zh"), "16k_zh");
+    assert_eq!(tencent_engine("en"), "16k_en");
+    assert_eq!(tencent_engine("mixed"), "16k_zh");
+    assert_eq!(tencent_engine("auto"), "16k_zh");
+}
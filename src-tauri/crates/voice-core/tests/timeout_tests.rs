@@ -0,0 +1,119 @@
+//! ASR 请求超时测试
+//!
+//! 在本地端口启动接受连接但不返回识别结果的服务，验证客户端按 [`AsrTimeouts`] 及时放弃，不需要网络。
+//!
+//! ```bash
+//! cargo test --package voice-core --test timeout_tests
+//! ```
+
+use std::time::{Duration, Instant};
+
+use futures_util::StreamExt;
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpListener;
+use voice_core::asr_client::{
+    create_client_with_timeouts, AsrClient, AsrClientConfig, AsrTimeouts, DeepgramClient,
+    VolcengineClient,
+};
+use voice_core::types::AudioData;
+use voice_core::VoiceError;
+
+fn audio() -> AudioData {
+    AudioData::new(vec![0; 16000], 16000, 1)
+}
+
+fn short_timeouts() -> AsrTimeouts {
+    AsrTimeouts {
+        connect: Some(Duration::from_millis(500)),
+        send: Some(Duration::from_millis(500)),
+        receive: Some(Duration::from_millis(200)),
+    }
+}
+
+/// 启动读取请求但从不响应的 HTTP 服务
+async fn serve_silent_http() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 8192];
+        while stream.read(&mut buf).await.is_ok_and(|n| n > 0) {}
+    });
+    url
+}
+
+/// 启动完成握手、接收音频但从不返回识别结果的 WebSocket 服务
+async fn serve_silent_websocket() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}/api/v2/asr", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        while let Some(Ok(_)) = ws.next().await {}
+    });
+    url
+}
+
+#[test]
+fn test_unset_timeouts_use_defaults() {
+    let timeouts = AsrTimeouts::default();
+    assert_eq!(timeouts.connect(), Duration::from_secs(10));
+    assert_eq!(timeouts.send(), Duration::from_secs(30));
+    assert_eq!(
+        timeouts.receive_or(Duration::from_secs(120)),
+        Duration::from_secs(120)
+    );
+
+    let timeouts = short_timeouts();
+    assert_eq!(
+        timeouts.receive_or(Duration::from_secs(120)),
+        Duration::from_millis(200)
+    );
+}
+
+#[tokio::test]
+async fn test_http_request_times_out() {
+    let url = serve_silent_http().await;
+    let client = DeepgramClient::new("dg-key".to_string())
+        .with_endpoint(url)
+        .with_timeouts(short_timeouts());
+
+    let started = Instant::now();
+    let err = client.transcribe(&audio()).await.unwrap_err();
+    assert!(matches!(err, VoiceError::NetworkError(_)), "{}", err);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_websocket_receive_times_out() {
+    let url = serve_silent_websocket().await;
+    let client = VolcengineClient::new("app-1".to_string(), "token-1".to_string())
+        .with_endpoint(url)
+        .with_timeouts(short_timeouts());
+
+    let started = Instant::now();
+    let err = client.transcribe(&audio()).await.unwrap_err();
+    assert!(matches!(err, VoiceError::NetworkError(_)), "{}", err);
+    assert!(err.to_string().contains("等待识别结果超时"), "{}", err);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_receive_timeout_overrides_config_timeout() {
+    let url = serve_silent_http().await;
+    let config = AsrClientConfig::CustomWhisper {
+        base_url: url,
+        api_key: None,
+        model: None,
+        timeout_secs: 120,
+        language: "zh".to_string(),
+    };
+    let client = create_client_with_timeouts(&config, short_timeouts()).unwrap();
+
+    let started = Instant::now();
+    assert!(matches!(
+        client.transcribe(&audio()).await,
+        Err(VoiceError::NetworkError(_))
+    ));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
@@ -0,0 +1,269 @@
+//! 火山引擎客户端集成测试
+//!
+//! 在本地端口启动一个只处理一次连接的 WebSocket 服务模拟流式识别接口，不需要网络。
+//! 服务端用同一套帧编解码解析客户端发来的二进制帧，校验请求参数和分包。
+//!
+//! ```bash
+//! cargo test --package voice-core --test volcengine_client_tests
+//! ```
+
+mod common;
+
+use std::io::Read;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+use tokio_tungstenite::tungstenite::Message;
+use voice_core::asr_client::factory::volcengine_language;
+use voice_core::asr_client::volcengine::{Frame, MessageType};
+use voice_core::asr_client::{create_client, AsrClient, AsrClientConfig, VolcengineClient};
+use voice_core::VoiceError;
+
+use common::{audio, bind};
+
+const APP_ID: &str = "app-1";
+const TOKEN: &str = "token-1";
+
+/// 服务端收到的内容
+struct Received {
+    authorization: Option<String>,
+    /// 完整请求帧的参数
+    request: serde_json::Value,
+    /// 音频帧（不含完整请求帧）
+    audio_frames: Vec<Frame>,
+}
+
+fn response(sequence: i64, text: &str) -> Frame {
+    let payload = serde_json::json!({
+        "code": 1000,
+        "message": "Success",
+        "sequence": sequence,
+        "result": [{
+            "text": text,
+            "confidence": 0,
+            "utterances": [{"text": text, "start_time": 120, "end_time": 1480, "definite": sequence < 0}]
+        }]
+    });
+    Frame::new(
+        MessageType::FullServerResponse,
+        serde_json::to_vec(&payload).unwrap(),
+    )
+}
+
+/// 启动只处理一次连接的服务：每包音频返回当前包数作为中间结果，最后一包返回 `last_reply`
+// 握手回调的错误类型由 tungstenite 决定
+#[allow(clippy::result_large_err)]
+async fn serve_once(last_reply: Frame) -> (String, JoinHandle<Received>) {
+    let (listener, url) = bind("ws", "/api/v2/asr").await;
+
+    let handle = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut authorization = None;
+        let mut ws = tokio_tungstenite::accept_hdr_async(stream, |req: &Request, res: Response| {
+            authorization = req
+                .headers()
+                .get("authorization")
+                .map(|v| v.to_str().unwrap().to_string());
+            Ok(res)
+        })
+        .await
+        .unwrap();
+
+        let mut request = serde_json::Value::Null;
+        let mut audio_frames = Vec::new();
+        while let Some(Ok(message)) = ws.next().await {
+            let Message::Binary(data) = message else {
+                continue;
+            };
+            let frame = Frame::decode(&data).unwrap();
+            let reply = match frame.message_type {
+                MessageType::FullClientRequest => {
+                    request = serde_json::from_slice(&frame.payload).unwrap();
+                    response(1, "")
+                }
+                _ if frame.last => last_reply.clone(),
+                _ => response(
+                    audio_frames.len() as i64 + 2,
+                    &audio_frames.len().to_string(),
+                ),
+            };
+            let last = frame.last;
+            if frame.message_type == MessageType::AudioOnlyRequest {
+                audio_frames.push(frame);
+            }
+            ws.send(Message::Binary(reply.encode().unwrap()))
+                .await
+                .unwrap();
+            if last {
+                break;
+            }
+        }
+        let _ = ws.close(None).await;
+
+        Received {
+            authorization,
+            request,
+            audio_frames,
+        }
+    });
+    (url, handle)
+}
+
+fn client(url: String) -> VolcengineClient {
+    VolcengineClient::new(APP_ID.to_string(), TOKEN.to_string())
+        .with_cluster("volcengine_input_common".to_string())
+        .with_endpoint(url)
+}
+
+#[test]
+fn test_frame_encoding() {
+    let frame = Frame::new(MessageType::FullClientRequest, br#"{"a":1}"#.to_vec());
+    let data = frame.encode().unwrap();
+    // 版本 1、帧头 4 字节；完整请求；JSON + gzip
+    assert_eq!(&data[..4], &[0x11, 0x10, 0x11, 0x00]);
+    let size = u32::from_be_bytes(data[4..8].try_into().unwrap()) as usize;
+    assert_eq!(data.len(), 8 + size);
+    // 负载是标准 gzip 数据
+    assert_eq!(&data[8..10], &[0x1f, 0x8b]);
+    let mut payload = String::new();
+    flate2::read::GzDecoder::new(&data[8..])
+        .read_to_string(&mut payload)
+        .unwrap();
+    assert_eq!(payload, r#"{"a":1}"#);
+    assert_eq!(Frame::decode(&data).unwrap(), frame);
+
+    // 最后一包音频：不序列化，带结束标志
+    let audio = Frame::new(MessageType::AudioOnlyRequest, vec![1, 2, 3]).with_last(true);
+    let data = audio.encode().unwrap();
+    assert_eq!(&data[..3], &[0x11, 0x22, 0x01]);
+    assert_eq!(Frame::decode(&data).unwrap(), audio);
+
+    // 错误响应在负载长度前带错误码
+    let error = Frame::error(1002, "access denied");
+    let data = error.encode().unwrap();
+    assert_eq!(data[1] >> 4, 0b1111);
+    assert_eq!(u32::from_be_bytes(data[4..8].try_into().unwrap()), 1002);
+    assert_eq!(Frame::decode(&data).unwrap(), error);
+
+    // 服务端也可能返回未压缩的负载
+    let mut plain = vec![0x11, 0x90, 0x10, 0x00];
+    plain.extend_from_slice(&2u32.to_be_bytes());
+    plain.extend_from_slice(b"{}");
+    let frame = Frame::decode(&plain).unwrap();
+    assert_eq!(frame.message_type, MessageType::FullServerResponse);
+    assert_eq!(frame.payload, b"{}");
+
+    assert!(Frame::decode(&plain[..6]).is_err());
+    assert!(Frame::decode(&[0x11, 0x50, 0x11, 0x00]).is_err());
+}
+
+#[tokio::test]
+async fn test_streaming_session() {
+    let (url, server) = serve_once(response(-5, "今天天气不错。")).await;
+    let client = client(url);
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    // 48kHz 采样，重采样后共 0.5 秒：两整包加剩余的最后一包
+    for _ in 0..5 {
+        tx.send(vec![100i16; 4800]).unwrap();
+    }
+    drop(tx);
+
+    let items: Vec<_> = client.transcribe_stream(rx, 48000).collect().await;
+    let (last, partials) = items.split_last().unwrap();
+    let partials: Vec<&str> = partials
+        .iter()
+        .map(|item| item.as_ref().unwrap().text.as_str())
+        .collect();
+    assert_eq!(partials, ["0", "1"]);
+
+    let last = last.as_ref().unwrap();
+    assert!(last.is_final);
+    let result = last.result.as_ref().unwrap();
+    assert_eq!(result.text, "今天天气不错。");
+    assert_eq!(result.language.as_deref(), Some("zh"));
+    assert_eq!(result.confidence, None);
+    assert_eq!(result.segments.len(), 1);
+    assert_eq!(result.segments[0].start, 0.12);
+    assert_eq!(result.segments[0].end, 1.48);
+
+    let received = server.await.unwrap();
+    assert_eq!(received.authorization.as_deref(), Some("Bearer; token-1"));
+    assert_eq!(received.request["app"]["appid"], APP_ID);
+    assert_eq!(received.request["app"]["token"], TOKEN);
+    assert_eq!(
+        received.request["app"]["cluster"],
+        "volcengine_input_common"
+    );
+    assert_eq!(received.request["audio"]["format"], "raw");
+    assert_eq!(received.request["audio"]["rate"], 16000);
+    assert_eq!(received.request["audio"]["language"], "zh-CN");
+    assert_eq!(received.request["request"]["result_type"], "full");
+
+    let sizes: Vec<usize> = received
+        .audio_frames
+        .iter()
+        .map(|f| f.payload.len())
+        .collect();
+    assert_eq!(sizes, [6400, 6400, 3200]);
+    let lasts: Vec<bool> = received.audio_frames.iter().map(|f| f.last).collect();
+    assert_eq!(lasts, [false, false, true]);
+}
+
+#[tokio::test]
+async fn test_transcribe_and_errors() {
+    let audio = audio();
+
+    let (url, server) = serve_once(response(-6, "你好")).await;
+    let result = client(url)
+        .with_language("en-US".to_string())
+        .transcribe(&audio)
+        .await
+        .unwrap();
+    assert_eq!(result.text, "你好");
+    assert_eq!(result.language.as_deref(), Some("en"));
+    let received = server.await.unwrap();
+    // 1 秒音频：5 包，最后一包带结束标志
+    assert_eq!(received.audio_frames.len(), 5);
+    assert!(received.audio_frames[4].last);
+    assert_eq!(received.request["audio"]["language"], "en-US");
+
+    let (url, _server) = serve_once(Frame::error(1002, "invalid token")).await;
+    assert!(matches!(
+        client(url).transcribe(&audio).await,
+        Err(VoiceError::AsrAuthError(_))
+    ));
+
+    let (url, _server) = serve_once(Frame::error(1013, "silence audio")).await;
+    assert_eq!(client(url).transcribe(&audio).await.unwrap().text, "");
+
+    let (url, _server) = serve_once(Frame::error(1022, "recognition error")).await;
+    assert!(matches!(
+        client(url).transcribe(&audio).await,
+        Err(VoiceError::AsrError(_))
+    ));
+}
+
+#[test]
+fn test_config_and_language() {
+    let config = |token: &str| AsrClientConfig::Volcengine {
+        app_id: APP_ID.to_string(),
+        access_token: token.to_string(),
+        cluster: None,
+        language: "zh".to_string(),
+    };
+    assert!(matches!(
+        create_client(&config("")),
+        Err(VoiceError::ConfigError(_))
+    ));
+    let client = create_client(&config(TOKEN)).unwrap();
+    assert_eq!(client.name(), "火山引擎");
+    assert!(client.capabilities().streaming);
+
+    assert_eq!(volcengine_language("zh"), "zh-CN");
+    assert_eq!(volcengine_language("mixed"), "zh-CN");
+    assert_eq!(volcengine_language("auto"), "zh-CN");
+    assert_eq!(volcengine_language("en"), "en-US");
+    assert_eq!(volcengine_language("ja-JP"), "ja-JP");
+}
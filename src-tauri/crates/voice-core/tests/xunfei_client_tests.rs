@@ -0,0 +1,177 @@
+//! 讯飞客户端集成测试
+//!
+//! 在本地端口启动一个只处理一次连接的 WebSocket 服务模拟听写接口，不需要网络。
+//! 服务端收到尾帧后依次返回预设的识别结果帧，校验动态修正、分段时间戳和置信度的解析。
+//!
+//! ```bash
+//! cargo test --package voice-core --test xunfei_client_tests
+//! ```
+
+mod common;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use voice_core::asr_client::{AsrClient, XunfeiClient};
+
+use common::{audio, bind};
+
+fn client(url: String) -> XunfeiClient {
+    XunfeiClient::new(
+        "app-1".to_string(),
+        "key-1".to_string(),
+        "secret-1".to_string(),
+    )
+    .with_endpoint(url)
+}
+
+/// 一帧识别结果，`words` 为（起始帧，词，分数）
+fn response(
+    status: u8,
+    sn: i32,
+    pgs: &str,
+    rg: Option<[i32; 2]>,
+    words: &[(u32, &str, f32)],
+) -> String {
+    let ws: Vec<_> = words
+        .iter()
+        .map(|(bg, w, sc)| serde_json::json!({"bg": bg, "cw": [{"w": w, "sc": sc}]}))
+        .collect();
+    let mut result = serde_json::json!({
+        "sn": sn,
+        "ls": status == 2,
+        "pgs": pgs,
+        "ws": ws,
+    });
+    if let Some(rg) = rg {
+        result["rg"] = serde_json::json!(rg);
+    }
+    serde_json::json!({
+        "code": 0,
+        "message": "success",
+        "sid": "iat-1",
+        "data": {"status": status, "result": result},
+    })
+    .to_string()
+}
+
+/// 启动只处理一次连接的服务：收到尾帧后依次返回 `replies`，返回收到的帧数和首帧的业务参数
+async fn serve_once(replies: Vec<String>) -> (String, JoinHandle<(usize, serde_json::Value)>) {
+    let (listener, url) = bind("ws", "/v2/iat").await;
+
+    let handle = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+        let mut frames = 0;
+        let mut business = serde_json::Value::Null;
+        while let Some(Ok(message)) = ws.next().await {
+            let Message::Text(text) = message else {
+                continue;
+            };
+            frames += 1;
+            let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+            if frames == 1 {
+                business = request["business"].clone();
+            }
+            if request["data"]["status"] == 2 {
+                break;
+            }
+        }
+        for reply in replies {
+            ws.send(Message::Text(reply)).await.unwrap();
+        }
+        (frames, business)
+    });
+    (url, handle)
+}
+
+#[tokio::test]
+async fn test_dynamic_correction_and_segments() {
+    let replies = vec![
+        response(0, 1, "apd", None, &[(5, "今天", 0.0), (25, "天汽", 0.0)]),
+        // 动态修正替换第 1 帧的结果
+        response(
+            1,
+            2,
+            "rpl",
+            Some([1, 1]),
+            &[
+                (5, "今天", 0.0),
+                (25, "天气", 0.0),
+                (40, "很好", 0.0),
+                (55, "。", 0.0),
+            ],
+        ),
+        response(2, 3, "apd", None, &[(60, "出去", 0.0), (75, "走走吧", 0.0)]),
+    ];
+    let (url, server) = serve_once(replies).await;
+
+    let result = client(url).transcribe(&audio()).await.unwrap();
+    assert!(server.await.unwrap().0 > 1);
+
+    assert_eq!(result.text, "今天天气很好。出去走走吧");
+    assert_eq!(result.segments.len(), 2);
+
+    let first = &result.segments[0];
+    assert_eq!(first.text, "今天天气很好。");
+    assert!((first.start - 0.05).abs() < 1e-4);
+    assert!((first.end - 0.60).abs() < 1e-4);
+
+    // 最后一个分段到音频结束
+    let last = &result.segments[1];
+    assert_eq!(last.text, "出去走走吧");
+    assert!((last.start - 0.60).abs() < 1e-4);
+    assert!((last.end - 1.0).abs() < 1e-4);
+
+    // 服务端未返回分数
+    assert_eq!(result.confidence, None);
+}
+
+#[tokio::test]
+async fn test_confidence_from_word_scores() {
+    let replies = vec![response(
+        2,
+        1,
+        "apd",
+        None,
+        &[(10, "你好", 0.9), (30, "世界", 0.7), (50, "。", 0.0)],
+    )];
+    let (url, _server) = serve_once(replies).await;
+
+    let result = client(url).transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "你好世界。");
+    assert_eq!(result.segments.len(), 1);
+    let confidence = result.confidence.unwrap();
+    assert!((confidence - 0.8).abs() < 1e-4, "{}", confidence);
+}
+
+#[tokio::test]
+async fn test_hotwords_sent_in_first_frame() {
+    let replies = vec![response(2, 1, "apd", None, &[(10, "打开钉钉", 0.0)])];
+    let (url, server) = serve_once(replies).await;
+
+    let client = client(url).with_hotwords(vec![
+        "钉钉".to_string(),
+        "长".to_string(),
+        "a|b".to_string(),
+        " ProxyCast ".to_string(),
+    ]);
+    let result = client.transcribe(&audio()).await.unwrap();
+    assert_eq!(result.text, "打开钉钉");
+
+    // 跳过过短和含分隔符的热词
+    let (_, business) = server.await.unwrap();
+    assert_eq!(business["dhw"], "utf-8;钉钉|ProxyCast");
+    assert_eq!(business["dwa"], "wpgs");
+}
+
+#[tokio::test]
+async fn test_no_hotwords_omits_dhw() {
+    let replies = vec![response(2, 1, "apd", None, &[(10, "你好", 0.0)])];
+    let (url, server) = serve_once(replies).await;
+
+    client(url).transcribe(&audio()).await.unwrap();
+    let (_, business) = server.await.unwrap();
+    assert!(business.get("dhw").is_none());
+}
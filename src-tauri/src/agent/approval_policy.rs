@@ -0,0 +1,274 @@
+//! 工具调用审批策略
+//!
+//! 根据工具名称和参数识别危险操作（工作区外写文件、执行 Shell 命令、网络访问），
+//! 结合工作区的允许/拒绝列表，决定工具调用是自动放行、自动拒绝还是需要用户确认。
+//!
+//! 策略在工具分发处生效：Aster 以审批模式运行（见 [`enforce_approval_mode`]），
+//! 分发每个工具调用前都暂停等待确认，由对话流按本策略答复。这样允许/拒绝列表
+//! 对所有调用生效，不会因为 Aster 自行放行而被绕过。
+
+use crate::config::ToolApprovalConfig;
+use crate::workspace::ToolPolicyLists;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+/// Aster 读取运行模式的环境变量
+const ASTER_MODE_ENV: &str = "ASTER_MODE";
+
+/// 审批模式：分发任何工具调用前都请求确认
+const ASTER_APPROVE_MODE: &str = "approve";
+
+/// 让 Aster 在分发每个工具调用前请求确认，使审批策略覆盖所有调用
+pub fn enforce_approval_mode() {
+    std::env::set_var(ASTER_MODE_ENV, ASTER_APPROVE_MODE);
+}
+
+/// 工具调用风险类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolRiskCategory {
+    /// 写入工作区之外的文件
+    FileWriteOutsideWorkspace,
+    /// 执行 Shell 命令
+    ShellCommand,
+    /// 访问网络
+    NetworkAccess,
+}
+
+/// 策略决定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyDecision {
+    Allow,
+    Deny,
+    Ask,
+}
+
+/// 策略评估结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyEvaluation {
+    pub decision: PolicyDecision,
+    /// 识别到的风险类别
+    pub categories: Vec<ToolRiskCategory>,
+    /// 命中的允许/拒绝列表规则
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_rule: Option<String>,
+}
+
+const SHELL_TOOL_KEYWORDS: &[&str] = &["shell", "bash", "exec", "command", "terminal", "run"];
+const NETWORK_TOOL_KEYWORDS: &[&str] = &["fetch", "http", "web", "browser", "download", "curl"];
+const WRITE_TOOL_KEYWORDS: &[&str] = &[
+    "write", "edit", "create", "delete", "remove", "move", "rename", "patch",
+];
+const PATH_ARGUMENT_KEYS: &[&str] = &["path", "file_path", "file", "target", "destination"];
+
+/// 评估一次工具调用
+pub fn evaluate(
+    config: &ToolApprovalConfig,
+    lists: &ToolPolicyLists,
+    tool_name: &str,
+    arguments: &serde_json::Value,
+    workspace_root: Option<&Path>,
+) -> PolicyEvaluation {
+    let categories = classify(tool_name, arguments, workspace_root);
+
+    // 拒绝列表优先于允许列表
+    if let Some(rule) = lists.deny.iter().find(|p| pattern_matches(p, tool_name)) {
+        return PolicyEvaluation {
+            decision: PolicyDecision::Deny,
+            categories,
+            matched_rule: Some(rule.clone()),
+        };
+    }
+    if let Some(rule) = lists.allow.iter().find(|p| pattern_matches(p, tool_name)) {
+        return PolicyEvaluation {
+            decision: PolicyDecision::Allow,
+            categories,
+            matched_rule: Some(rule.clone()),
+        };
+    }
+
+    let needs_approval = config.enabled
+        && categories.iter().any(|c| match c {
+            ToolRiskCategory::FileWriteOutsideWorkspace => config.file_writes_outside_workspace,
+            ToolRiskCategory::ShellCommand => config.shell_commands,
+            ToolRiskCategory::NetworkAccess => config.network_access,
+        });
+
+    PolicyEvaluation {
+        decision: if needs_approval {
+            PolicyDecision::Ask
+        } else {
+            PolicyDecision::Allow
+        },
+        categories,
+        matched_rule: None,
+    }
+}
+
+/// 识别工具调用的风险类别
+pub fn classify(
+    tool_name: &str,
+    arguments: &serde_json::Value,
+    workspace_root: Option<&Path>,
+) -> Vec<ToolRiskCategory> {
+    let name = tool_name.to_lowercase();
+    // 扩展工具名为 `{extension}__{tool}`，只匹配工具部分
+    let tool = name.rsplit("__").next().unwrap_or(&name);
+    let mut categories = Vec::new();
+
+    if contains_keyword(tool, SHELL_TOOL_KEYWORDS) || arguments.get("command").is_some() {
+        categories.push(ToolRiskCategory::ShellCommand);
+    }
+
+    if contains_keyword(tool, NETWORK_TOOL_KEYWORDS) || has_url_argument(arguments) {
+        categories.push(ToolRiskCategory::NetworkAccess);
+    }
+
+    if contains_keyword(tool, WRITE_TOOL_KEYWORDS) {
+        let outside = path_arguments(arguments)
+            .iter()
+            .any(|p| !is_within_workspace(p, workspace_root));
+        if outside {
+            categories.push(ToolRiskCategory::FileWriteOutsideWorkspace);
+        }
+    }
+
+    categories
+}
+
+/// 匹配工具名规则，支持结尾 `*` 通配
+pub fn pattern_matches(pattern: &str, tool_name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => tool_name.starts_with(prefix),
+        None => pattern == tool_name,
+    }
+}
+
+fn contains_keyword(tool: &str, keywords: &[&str]) -> bool {
+    tool.split(|c: char| !c.is_ascii_alphanumeric())
+        .any(|part| keywords.contains(&part))
+}
+
+fn has_url_argument(arguments: &serde_json::Value) -> bool {
+    arguments
+        .as_object()
+        .map(|obj| {
+            obj.values().any(|v| {
+                v.as_str()
+                    .map(|s| s.starts_with("http://") || s.starts_with("https://"))
+                    .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn path_arguments(arguments: &serde_json::Value) -> Vec<PathBuf> {
+    PATH_ARGUMENT_KEYS
+        .iter()
+        .filter_map(|key| arguments.get(*key).and_then(|v| v.as_str()))
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// 判断路径是否在工作区内（未指定工作区时一律视为工作区外）
+fn is_within_workspace(path: &Path, workspace_root: Option<&Path>) -> bool {
+    let Some(root) = workspace_root else {
+        return false;
+    };
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        root.join(path)
+    };
+    normalize(&absolute).starts_with(normalize(root))
+}
+
+/// 词法规范化路径（处理 `.` 和 `..`，不访问文件系统）
+fn normalize(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                result.pop();
+            }
+            other => result.push(other),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn root() -> PathBuf {
+        PathBuf::from("/home/user/project")
+    }
+
+    #[test]
+    fn test_classify_shell_command() {
+        let categories = classify("developer__shell", &json!({"command": "ls"}), None);
+        assert_eq!(categories, vec![ToolRiskCategory::ShellCommand]);
+    }
+
+    #[test]
+    fn test_classify_file_write() {
+        let inside = classify(
+            "developer__write_file",
+            &json!({"path": "src/main.rs"}),
+            Some(&root()),
+        );
+        assert!(inside.is_empty());
+
+        let outside = classify(
+            "developer__write_file",
+            &json!({"path": "../other/secret.txt"}),
+            Some(&root()),
+        );
+        assert_eq!(outside, vec![ToolRiskCategory::FileWriteOutsideWorkspace]);
+    }
+
+    #[test]
+    fn test_classify_network() {
+        let categories = classify("fetch__get", &json!({"url": "https://example.com"}), None);
+        assert_eq!(categories, vec![ToolRiskCategory::NetworkAccess]);
+    }
+
+    #[test]
+    fn test_evaluate_lists_take_precedence() {
+        let config = ToolApprovalConfig::default();
+        let lists = ToolPolicyLists {
+            allow: vec!["developer__*".to_string()],
+            deny: vec!["developer__shell".to_string()],
+        };
+
+        let denied = evaluate(&config, &lists, "developer__shell", &json!({}), None);
+        assert_eq!(denied.decision, PolicyDecision::Deny);
+
+        let allowed = evaluate(&config, &lists, "developer__bash", &json!({}), None);
+        assert_eq!(allowed.decision, PolicyDecision::Allow);
+        assert_eq!(allowed.matched_rule.as_deref(), Some("developer__*"));
+    }
+
+    #[test]
+    fn test_evaluate_asks_for_risky_calls() {
+        let config = ToolApprovalConfig::default();
+        let lists = ToolPolicyLists::default();
+
+        let risky = evaluate(&config, &lists, "shell", &json!({"command": "rm"}), None);
+        assert_eq!(risky.decision, PolicyDecision::Ask);
+
+        let safe = evaluate(&config, &lists, "read_file", &json!({"path": "a"}), None);
+        assert_eq!(safe.decision, PolicyDecision::Allow);
+
+        let disabled = ToolApprovalConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        let risky = evaluate(&disabled, &lists, "shell", &json!({}), None);
+        assert_eq!(risky.decision, PolicyDecision::Allow);
+    }
+}
@@ -15,6 +15,27 @@ use crate::agent::credential_bridge::{
 };
 use crate::database::DbConnection;
 
+/// 向 Agent 回复工具调用确认结果
+pub async fn respond_tool_confirmation(agent: &Agent, request_id: &str, allowed: bool) {
+    use aster::permission::permission_confirmation::PrincipalType;
+    use aster::permission::{Permission, PermissionConfirmation};
+
+    let permission = if allowed {
+        Permission::AllowOnce
+    } else {
+        Permission::DenyOnce
+    };
+    agent
+        .handle_confirmation(
+            request_id.to_string(),
+            PermissionConfirmation {
+                principal_type: PrincipalType::Tool,
+                permission,
+            },
+        )
+        .await;
+}
+
 /// Provider 配置信息
 #[derive(Debug, Clone)]
 pub struct ProviderConfig {
@@ -67,6 +88,7 @@ impl AsterAgentState {
     pub async fn init_agent(&self) -> Result<(), String> {
         let mut agent_guard = self.agent.write().await;
         if agent_guard.is_none() {
+            crate::agent::approval_policy::enforce_approval_mode();
             let agent = Agent::new();
             *agent_guard = Some(agent);
             tracing::info!("Aster Agent initialized");
@@ -285,6 +307,14 @@ impl AsterAgentState {
         }
     }
 
+    /// 回复工具调用确认请求
+    pub async fn confirm_tool_call(&self, request_id: &str, allowed: bool) -> Result<(), String> {
+        let guard = self.agent.read().await;
+        let agent = guard.as_ref().ok_or("Agent not initialized")?;
+        respond_tool_confirmation(agent, request_id, allowed).await;
+        Ok(())
+    }
+
     /// 获取 Agent 的 Arc 引用
     ///
     /// 用于需要长期持有 Agent 引用的场景
@@ -0,0 +1,39 @@
+//! Agent 会话检查点
+//!
+//! 在会话的指定时刻保存对话状态和会话文件引用，
+//! 之后可以从检查点恢复原会话，或分叉出新的会话。
+//! 对话取自 aster `SessionManager` 中的会话，恢复和分叉也通过 `SessionManager` 写回。
+
+use serde::{Deserialize, Serialize};
+
+/// 会话检查点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentCheckpoint {
+    /// 检查点 ID
+    pub id: String,
+    /// 所属会话 ID
+    pub session_id: String,
+    /// 关联的工作区 ID
+    pub workspace_id: Option<String>,
+    /// 检查点名称
+    pub name: String,
+    /// 快照时的消息数量
+    pub message_count: usize,
+    /// 会话文件快照引用
+    pub file_snapshots: Vec<FileSnapshotRef>,
+    /// 创建时间（毫秒时间戳）
+    pub created_at: i64,
+}
+
+/// 会话文件快照引用
+///
+/// 只记录文件元数据，文件内容仍由会话文件存储管理
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileSnapshotRef {
+    /// 文件名
+    pub name: String,
+    /// 文件大小（字节）
+    pub size: u64,
+    /// 快照时的文件更新时间
+    pub updated_at: i64,
+}
@@ -3,19 +3,26 @@
 //! 基于 aster-rust 框架实现 Agent 功能
 //!
 //! ## 架构设计
+//! - approval_policy - 危险工具调用审批策略
 //! - aster_state - Aster Agent 状态管理
 //! - aster_agent - Aster Agent 包装器
 //! - event_converter - Aster 事件转换器
+//! - checkpoint - 会话检查点（快照、恢复、分叉）
 //! - credential_bridge - 凭证池桥接（连接 ProxyCast 凭证池与 Aster Provider）
+//! - tool_audit - 工具调用审计
 
+pub mod approval_policy;
 pub mod aster_agent;
 pub mod aster_state;
+pub mod checkpoint;
 pub mod credential_bridge;
 pub mod event_converter;
+pub mod tool_audit;
 pub mod types;
 
 pub use aster_agent::{AsterAgentWrapper, SessionDetail, SessionInfo};
 pub use aster_state::AsterAgentState;
+pub use checkpoint::{AgentCheckpoint, FileSnapshotRef};
 pub use credential_bridge::{
     create_aster_provider, AsterProviderConfig, CredentialBridge, CredentialBridgeError,
 };
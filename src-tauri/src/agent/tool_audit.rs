@@ -0,0 +1,248 @@
+//! 工具调用审计
+//!
+//! 记录 Agent 执行的每一次 MCP 工具调用（服务器、工具、参数摘要、耗时、结果），
+//! 便于用户事后审查 Agent 实际做了什么。
+//!
+//! 参数只保存 SHA-256 摘要，避免把文件内容、密钥等敏感数据写入数据库。
+
+use crate::agent::event_converter::TauriAgentEvent;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// 无扩展前缀的工具归属的服务器名
+const BUILTIN_SERVER: &str = "agent";
+
+/// 工具调用结果状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolAuditStatus {
+    Success,
+    Error,
+    /// 流结束时仍未收到结果（被取消或中断）
+    Incomplete,
+}
+
+impl ToolAuditStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ToolAuditStatus::Success => "success",
+            ToolAuditStatus::Error => "error",
+            ToolAuditStatus::Incomplete => "incomplete",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "success" => ToolAuditStatus::Success,
+            "error" => ToolAuditStatus::Error,
+            _ => ToolAuditStatus::Incomplete,
+        }
+    }
+}
+
+/// 审计记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolAuditEntry {
+    /// 数据库自增 ID（写入前为 None）
+    #[serde(default)]
+    pub id: Option<i64>,
+    pub session_id: String,
+    pub workspace_id: Option<String>,
+    /// MCP 服务器（扩展）名称
+    pub server: String,
+    /// 工具名称
+    pub tool: String,
+    /// 参数 JSON 的 SHA-256 摘要
+    pub arguments_digest: String,
+    pub duration_ms: u64,
+    pub status: ToolAuditStatus,
+    pub error: Option<String>,
+    /// 调用开始时间（毫秒时间戳）
+    pub created_at: i64,
+}
+
+/// 审计记录查询条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolAuditFilter {
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub server: Option<String>,
+    #[serde(default)]
+    pub status: Option<ToolAuditStatus>,
+    /// 起始时间（毫秒时间戳，包含）
+    #[serde(default)]
+    pub since: Option<i64>,
+    /// 最大返回条数，默认 200
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// 拆分工具全名为 (服务器, 工具)
+///
+/// Aster 扩展工具以 `{extension}__{tool}` 命名，无前缀的视为内置工具
+pub fn split_tool_name(full_name: &str) -> (String, String) {
+    match full_name.split_once("__") {
+        Some((server, tool)) if !server.is_empty() && !tool.is_empty() => {
+            (server.to_string(), tool.to_string())
+        }
+        _ => (BUILTIN_SERVER.to_string(), full_name.to_string()),
+    }
+}
+
+/// 计算参数摘要
+pub fn digest_arguments(arguments: Option<&str>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(arguments.unwrap_or("").as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+struct PendingCall {
+    full_name: String,
+    arguments_digest: String,
+    started: Instant,
+    started_at: i64,
+}
+
+/// 从 Agent 事件流中配对工具开始/结束事件，生成审计记录
+pub struct ToolAuditRecorder {
+    session_id: String,
+    workspace_id: Option<String>,
+    pending: HashMap<String, PendingCall>,
+}
+
+impl ToolAuditRecorder {
+    pub fn new(session_id: impl Into<String>, workspace_id: Option<String>) -> Self {
+        Self {
+            session_id: session_id.into(),
+            workspace_id,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// 处理一个事件，工具调用结束时返回审计记录
+    pub fn observe(&mut self, event: &TauriAgentEvent) -> Option<ToolAuditEntry> {
+        match event {
+            TauriAgentEvent::ToolStart {
+                tool_name,
+                tool_id,
+                arguments,
+            } => {
+                self.pending.insert(
+                    tool_id.clone(),
+                    PendingCall {
+                        full_name: tool_name.clone(),
+                        arguments_digest: digest_arguments(arguments.as_deref()),
+                        started: Instant::now(),
+                        started_at: chrono::Utc::now().timestamp_millis(),
+                    },
+                );
+                None
+            }
+            TauriAgentEvent::ToolEnd { tool_id, result } => {
+                let call = self.pending.remove(tool_id)?;
+                let status = if result.success {
+                    ToolAuditStatus::Success
+                } else {
+                    ToolAuditStatus::Error
+                };
+                Some(self.build_entry(call, status, result.error.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// 流结束时，将仍未完成的调用记为未完成
+    pub fn finish(&mut self) -> Vec<ToolAuditEntry> {
+        let pending: Vec<PendingCall> = self.pending.drain().map(|(_, call)| call).collect();
+        pending
+            .into_iter()
+            .map(|call| self.build_entry(call, ToolAuditStatus::Incomplete, None))
+            .collect()
+    }
+
+    fn build_entry(
+        &self,
+        call: PendingCall,
+        status: ToolAuditStatus,
+        error: Option<String>,
+    ) -> ToolAuditEntry {
+        let (server, tool) = split_tool_name(&call.full_name);
+        ToolAuditEntry {
+            id: None,
+            session_id: self.session_id.clone(),
+            workspace_id: self.workspace_id.clone(),
+            server,
+            tool,
+            arguments_digest: call.arguments_digest,
+            duration_ms: call.started.elapsed().as_millis() as u64,
+            status,
+            error,
+            created_at: call.started_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::event_converter::TauriToolResult;
+
+    #[test]
+    fn test_split_tool_name() {
+        assert_eq!(
+            split_tool_name("developer__shell"),
+            ("developer".to_string(), "shell".to_string())
+        );
+        assert_eq!(
+            split_tool_name("read_file"),
+            ("agent".to_string(), "read_file".to_string())
+        );
+    }
+
+    #[test]
+    fn test_recorder_pairs_start_and_end() {
+        let mut recorder = ToolAuditRecorder::new("s1", Some("ws1".to_string()));
+        let start = TauriAgentEvent::ToolStart {
+            tool_name: "fs__write".to_string(),
+            tool_id: "t1".to_string(),
+            arguments: Some("{\"path\":\"a.txt\"}".to_string()),
+        };
+        assert!(recorder.observe(&start).is_none());
+
+        let end = TauriAgentEvent::ToolEnd {
+            tool_id: "t1".to_string(),
+            result: TauriToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("denied".to_string()),
+            },
+        };
+        let entry = recorder.observe(&end).unwrap();
+        assert_eq!(entry.server, "fs");
+        assert_eq!(entry.tool, "write");
+        assert_eq!(entry.status, ToolAuditStatus::Error);
+        assert_eq!(
+            entry.arguments_digest,
+            digest_arguments(Some("{\"path\":\"a.txt\"}"))
+        );
+        assert!(recorder.finish().is_empty());
+    }
+
+    #[test]
+    fn test_recorder_marks_unfinished_calls() {
+        let mut recorder = ToolAuditRecorder::new("s1", None);
+        recorder.observe(&TauriAgentEvent::ToolStart {
+            tool_name: "shell".to_string(),
+            tool_id: "t1".to_string(),
+            arguments: None,
+        });
+        let entries = recorder.finish();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].status, ToolAuditStatus::Incomplete);
+    }
+}
@@ -164,6 +164,7 @@ pub fn init_states(config: &Config) -> Result<AppStates, String> {
     // 核心状态
     let state: AppState = Arc::new(RwLock::new(server::ServerState::new(config.clone())));
     let logs: LogState = Arc::new(RwLock::new(logger::LogStore::with_config(&config.logging)));
+    logger::tracing_bridge::init(&config.logging.level, logs.clone());
 
     // 数据库
     let db = database::init_database().map_err(|e| format!("数据库初始化失败: {}", e))?;
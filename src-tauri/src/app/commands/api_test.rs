@@ -3,7 +3,7 @@
 //! 包含 API 测试、模型列表和兼容性检查命令。
 
 use crate::app::types::{AppState, LogState, ProviderType};
-use crate::commands::model_registry_cmd::ModelRegistryState;
+use crate::commands::model_registry_cmd::{read_registry, ModelRegistryState};
 
 /// 测试结果
 #[derive(serde::Serialize)]
@@ -281,7 +281,7 @@ pub async fn check_api_compatibility(
 pub async fn get_available_models(
     state: tauri::State<'_, ModelRegistryState>,
 ) -> Result<Vec<ModelInfo>, String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
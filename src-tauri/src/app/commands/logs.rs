@@ -1,9 +1,10 @@
 //! 日志命令
 //!
-//! 包含日志查询和清理命令。
+//! 包含日志查询、按请求追踪、清理、运行时级别调整和日志文件导出命令。
 
 use crate::app::types::LogState;
 use crate::logger;
+use crate::logger::tracing_bridge::{self, LogLevels};
 
 /// 获取日志
 #[tauri::command]
@@ -11,9 +12,58 @@ pub async fn get_logs(logs: tauri::State<'_, LogState>) -> Result<Vec<logger::Lo
     Ok(logs.read().await.get_logs())
 }
 
+/// 获取指定请求的全部日志
+///
+/// 请求 ID 见响应头 `X-Proxycast-Request-Id`
+#[tauri::command]
+pub async fn get_request_trace(
+    logs: tauri::State<'_, LogState>,
+    id: String,
+) -> Result<Vec<logger::LogEntry>, String> {
+    Ok(logs.read().await.get_request_trace(&id))
+}
+
 /// 清除日志
 #[tauri::command]
 pub async fn clear_logs(logs: tauri::State<'_, LogState>) -> Result<(), String> {
     logs.write().await.clear();
     Ok(())
 }
+
+/// 获取日志级别设置
+#[tauri::command]
+pub async fn get_log_levels() -> Result<LogLevels, String> {
+    Ok(tracing_bridge::levels())
+}
+
+/// 设置日志级别，立即生效
+///
+/// `module` 为空时设置默认级别；为 `voice`、`pipeline`、`providers` 时只调整该模块，
+/// `level` 为 `default` 时取消该模块的单独设置
+#[tauri::command]
+pub async fn set_log_level(module: Option<String>, level: String) -> Result<LogLevels, String> {
+    tracing_bridge::set_level(module.as_deref(), &level)
+}
+
+/// 用系统默认程序打开当前日志文件
+#[tauri::command]
+pub async fn open_log_file(logs: tauri::State<'_, LogState>) -> Result<(), String> {
+    let path = logs
+        .read()
+        .await
+        .get_log_file_path()
+        .ok_or("日志文件未启用")?;
+    open::that(&path).map_err(|e| format!("打开日志文件失败: {}", e))
+}
+
+/// 导出当前日志文件到指定路径，返回导出的路径
+#[tauri::command]
+pub async fn export_log_file(
+    logs: tauri::State<'_, LogState>,
+    dest: String,
+) -> Result<String, String> {
+    logs.read()
+        .await
+        .export_log_file(std::path::Path::new(&dest))?;
+    Ok(dest)
+}
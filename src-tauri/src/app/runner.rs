@@ -9,6 +9,7 @@ use tauri::Manager;
 use tauri::{Emitter, Listener};
 
 use crate::commands;
+use crate::services::startup_profile_service::{profiler, InitPhase};
 use crate::tray::{TrayIconStatus, TrayManager, TrayStateSnapshot};
 
 use super::bootstrap::{self, AppStates};
@@ -23,10 +24,20 @@ use super::types::{AppState, TrayManagerState};
 /// 3. 配置 Tauri Builder（插件、状态管理、事件处理）
 /// 4. 注册所有 Tauri 命令
 /// 5. 启动应用
+///
+/// 模型注册表、本地 Whisper 模型等耗时子系统延迟到首次使用时初始化，
+/// 各子系统耗时可通过 `get_startup_profile` 命令查看。
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // 以此为启动计时起点
+    let profiler = profiler();
+
     // 加载并验证配置
-    let config = match bootstrap::load_and_validate_config() {
+    let config = match profiler.measure(
+        "config",
+        InitPhase::Eager,
+        bootstrap::load_and_validate_config,
+    ) {
         Ok(cfg) => cfg,
         Err(err) => {
             tracing::error!("{}", err);
@@ -36,7 +47,9 @@ pub fn run() {
     };
 
     // 初始化所有应用状态
-    let states = match bootstrap::init_states(&config) {
+    let states = match profiler.measure("app_states", InitPhase::Eager, || {
+        bootstrap::init_states(&config)
+    }) {
         Ok(s) => s,
         Err(err) => {
             tracing::error!("应用状态初始化失败: {}", err);
@@ -202,7 +215,7 @@ pub fn run() {
 
             // 初始化托盘管理器
             // Requirements 1.4: 应用启动时显示停止状态图标
-            match TrayManager::new(app.handle()) {
+            match profiler.measure("tray", InitPhase::Eager, || TrayManager::new(app.handle())) {
                 Ok(tray_manager) => {
                     tracing::info!("[启动] 托盘管理器初始化成功");
                     // 将托盘管理器存储到应用状态中
@@ -231,7 +244,9 @@ pub fn run() {
             // _Requirements: 7.3_
             {
                 let app_handle = app.handle();
-                match crate::screenshot::init(app_handle) {
+                match profiler.measure("screenshot", InitPhase::Eager, || {
+                    crate::screenshot::init(app_handle)
+                }) {
                     Ok(()) => {
                         tracing::info!("[启动] 截图对话模块初始化成功");
                     }
@@ -245,7 +260,9 @@ pub fn run() {
             // 初始化语音输入模块
             {
                 let app_handle = app.handle();
-                match crate::voice::init(app_handle) {
+                match profiler.measure("voice", InitPhase::Eager, || {
+                    crate::voice::init(app_handle)
+                }) {
                     Ok(()) => {
                         tracing::info!("[启动] 语音输入模块初始化成功");
                     }
@@ -267,7 +284,14 @@ pub fn run() {
                         .join("proxycast");
 
                     // 初始化 Connect 状态
-                    match crate::commands::connect_cmd::init_connect_state(app_data_dir).await {
+                    let result = profiler
+                        .measure_async(
+                            "connect",
+                            InitPhase::Eager,
+                            crate::commands::connect_cmd::init_connect_state(app_data_dir),
+                        )
+                        .await;
+                    match result {
                         Ok(connect_state_inner) => {
                             tracing::info!("[启动] Connect 模块初始化成功");
                             // 更新状态
@@ -285,9 +309,8 @@ pub fn run() {
                 });
             }
 
-            // 初始化 Model Registry 服务
+            // 登记 Model Registry 服务的延迟初始化（首次使用或收到预热提示时加载）
             {
-                let app_handle = app.handle().clone();
                 let db_clone = db_clone.clone();
                 // 获取资源目录路径
                 let mut resource_dir = app.path().resource_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
@@ -320,35 +343,20 @@ pub fn run() {
                     }
                 }
 
-                tauri::async_runtime::spawn(async move {
-                    // 创建 ModelRegistryService
-                    let mut service = crate::services::model_registry_service::ModelRegistryService::new(db_clone);
-                    // 设置资源目录路径
-                    service.set_resource_dir(resource_dir);
-
-                    // 初始化服务
-                    match service.initialize().await {
-                        Ok(()) => {
-                            tracing::info!("[启动] Model Registry 服务初始化成功");
-                            // 更新状态
-                            if let Some(state) = app_handle
-                                .try_state::<crate::commands::model_registry_cmd::ModelRegistryState>()
-                            {
-                                let mut guard = state.write().await;
-                                *guard = Some(service);
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("[启动] Model Registry 服务初始化失败: {}", e);
-                        }
-                    }
-                });
+                crate::commands::model_registry_cmd::defer_init(db_clone, resource_dir);
+                crate::commands::model_registry_cmd::share_state(
+                    app.state::<crate::commands::model_registry_cmd::ModelRegistryState>()
+                        .inner()
+                        .clone(),
+                );
             }
 
             // 初始化终端会话管理器
             {
                 let app_handle = app.handle().clone();
-                let terminal_manager = crate::terminal::TerminalSessionManager::new(app_handle.clone());
+                let terminal_manager = profiler.measure("terminal", InitPhase::Eager, || {
+                    crate::terminal::TerminalSessionManager::new(app_handle.clone())
+                });
                 if let Some(state) = app_handle.try_state::<crate::commands::terminal_cmd::TerminalManagerState>() {
                     let mut guard = state.inner().0.blocking_write();
                     *guard = Some(terminal_manager);
@@ -601,6 +609,9 @@ pub fn run() {
             });
             tracing::info!("[启动] 后台更新检查任务已启动");
 
+            // 启动定时任务调度
+            crate::scheduler::service::start(app.handle().clone(), db_clone.clone());
+
             // 启动会话文件清理任务（清理 30 天前的过期会话）
             tauri::async_runtime::spawn(async move {
                 // 延迟 10 秒执行，避免影响启动性能
@@ -637,6 +648,7 @@ pub fn run() {
                 }
             });
 
+            profiler.mark_ready();
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -682,7 +694,12 @@ pub fn run() {
             app_commands::set_claude_custom_config,
             // Log commands (from app::commands)
             app_commands::get_logs,
+            app_commands::get_request_trace,
             app_commands::clear_logs,
+            app_commands::get_log_levels,
+            app_commands::set_log_level,
+            app_commands::open_log_file,
+            app_commands::export_log_file,
             // API test commands (from app::commands)
             app_commands::test_api,
             app_commands::get_available_models,
@@ -728,6 +745,8 @@ pub fn run() {
             commands::mcp_cmd::toggle_mcp_server,
             commands::mcp_cmd::import_mcp_from_app,
             commands::mcp_cmd::sync_all_mcp_to_live,
+            commands::mcp_cmd::query_mcp_audit_log,
+            commands::mcp_cmd::clear_mcp_audit_log,
             // Prompt commands
             commands::prompt_cmd::get_prompts,
             commands::prompt_cmd::upsert_prompt,
@@ -846,10 +865,36 @@ pub fn run() {
             commands::telemetry_cmd::get_stats_summary,
             commands::telemetry_cmd::get_stats_by_provider,
             commands::telemetry_cmd::get_stats_by_model,
+            commands::telemetry_cmd::get_stats_by_provider_model,
+            commands::telemetry_cmd::get_requests_per_hour,
+            commands::telemetry_cmd::get_error_rate,
+            commands::telemetry_cmd::get_latency_percentiles,
+            commands::telemetry_cmd::get_top_models,
+            commands::telemetry_cmd::get_cost_by_provider,
+            commands::telemetry_cmd::get_usage_by_client,
+            commands::telemetry_cmd::get_provider_anomalies,
+            commands::telemetry_cmd::clear_provider_anomalies,
+            commands::telemetry_cmd::get_connection_stats,
+            commands::telemetry_cmd::clear_connection_stats,
+            commands::telemetry_cmd::get_pipeline_overhead,
+            commands::telemetry_cmd::clear_pipeline_overhead,
             commands::telemetry_cmd::get_token_summary,
             commands::telemetry_cmd::get_token_stats_by_provider,
             commands::telemetry_cmd::get_token_stats_by_model,
             commands::telemetry_cmd::get_token_stats_by_day,
+            // Postmortem commands
+            commands::postmortem_cmd::get_request_postmortem,
+            commands::postmortem_cmd::list_request_postmortems,
+            commands::postmortem_cmd::clear_request_postmortems,
+            // Doctor commands
+            commands::doctor_cmd::doctor,
+            // Startup commands
+            commands::startup_cmd::get_startup_profile,
+            commands::startup_cmd::warm_up_subsystem,
+            commands::compat_cmd::get_data_compatibility,
+            commands::compat_cmd::export_data_snapshot,
+            // Token count commands
+            commands::token_count_cmd::count_prompt_tokens,
             // Injection commands
             commands::injection_cmd::get_injection_config,
             commands::injection_cmd::set_injection_enabled,
@@ -1076,6 +1121,11 @@ pub fn run() {
             commands::aster_agent_cmd::aster_session_create,
             commands::aster_agent_cmd::aster_session_list,
             commands::aster_agent_cmd::aster_session_get,
+            commands::aster_agent_cmd::aster_checkpoint_create,
+            commands::aster_agent_cmd::aster_checkpoint_list,
+            commands::aster_agent_cmd::aster_checkpoint_resume,
+            commands::aster_agent_cmd::aster_checkpoint_branch,
+            commands::aster_agent_cmd::aster_checkpoint_delete,
             commands::aster_agent_cmd::aster_agent_confirm,
             // Models config commands
             commands::models_cmd::get_models_config,
@@ -1217,6 +1267,12 @@ pub fn run() {
             commands::session_files_cmd::session_files_list_files,
             commands::session_files_cmd::session_files_cleanup_expired,
             commands::session_files_cmd::session_files_cleanup_empty,
+            // Scheduler commands
+            commands::scheduler_cmd::scheduler_list_jobs,
+            commands::scheduler_cmd::scheduler_save_job,
+            commands::scheduler_cmd::scheduler_delete_job,
+            commands::scheduler_cmd::scheduler_run_job_now,
+            commands::scheduler_cmd::scheduler_list_runs,
             // General Chat commands
             commands::general_chat_cmd::general_chat_create_session,
             commands::general_chat_cmd::general_chat_list_sessions,
@@ -1284,24 +1340,73 @@ pub fn run() {
             commands::asr_cmd::update_asr_credential,
             commands::asr_cmd::delete_asr_credential,
             commands::asr_cmd::set_default_asr_credential,
-            commands::asr_cmd::test_asr_credential,
             // Voice Input commands
             crate::voice::commands::get_voice_input_config,
             crate::voice::commands::save_voice_input_config,
+            crate::voice::commands::get_quiet_mode_status,
             crate::voice::commands::get_voice_instructions,
             crate::voice::commands::save_voice_instruction,
             crate::voice::commands::delete_voice_instruction,
             crate::voice::commands::open_voice_window,
             crate::voice::commands::close_voice_window,
             crate::voice::commands::transcribe_audio,
+            crate::voice::commands::get_asr_capabilities,
             crate::voice::commands::polish_voice_text,
             crate::voice::commands::output_voice_text,
+            crate::voice::commands::get_voice_output_queue,
+            crate::voice::commands::cancel_voice_output,
+            crate::voice::commands::resume_voice_output,
+            crate::voice::commands::copy_paused_voice_output,
+            crate::voice::commands::retry_voice_draft,
+            crate::voice::commands::accept_voice_draft,
+            crate::voice::commands::discard_voice_draft,
             // 录音命令（使用独立线程 + channel 通信）
             crate::voice::commands::start_recording,
             crate::voice::commands::stop_recording,
             crate::voice::commands::cancel_recording,
             crate::voice::commands::get_recording_status,
+            crate::voice::commands::start_streaming_transcription,
+            crate::voice::commands::stop_streaming_transcription,
+            crate::voice::commands::get_voice_sessions,
+            crate::voice::commands::get_voice_session_timelines,
+            crate::voice::commands::clear_voice_session_timelines,
+            crate::voice::commands::test_microphone,
+            crate::voice::commands::get_compute_diagnostics,
+            crate::voice::commands::list_local_asr_models,
+            crate::voice::commands::download_local_asr_model,
+            crate::voice::commands::delete_local_asr_model,
+            crate::voice::commands::record_calibration_sample,
+            crate::voice::commands::save_microphone_calibration,
+            crate::voice::commands::clear_microphone_calibration,
             crate::voice::commands::list_audio_devices,
+            crate::voice::commands::check_voice_permissions,
+            crate::voice::commands::open_permission_settings,
+            crate::voice::commands::get_watch_folders,
+            crate::voice::commands::save_watch_folder,
+            crate::voice::commands::delete_watch_folder,
+            crate::voice::commands::rescan_watch_folder,
+            crate::voice::commands::transcribe_files,
+            crate::voice::commands::get_clipboard_history,
+            crate::voice::commands::delete_clipboard_history_entry,
+            crate::voice::commands::clear_clipboard_history,
+            crate::voice::commands::get_voice_snippets,
+            crate::voice::commands::save_voice_snippet,
+            crate::voice::commands::delete_voice_snippet,
+            crate::voice::commands::expand_voice_snippets,
+            crate::voice::commands::get_voice_vocabulary,
+            crate::voice::commands::set_voice_vocabulary,
+            crate::voice::commands::get_workspace_voice_defaults,
+            crate::voice::commands::set_workspace_voice_defaults,
+            crate::voice::commands::get_asr_usage_stats,
+            crate::voice::commands::test_asr_credential,
+            crate::voice::commands::get_voice_history,
+            crate::voice::commands::repolish_voice_history,
+            crate::voice::commands::delete_voice_history_entry,
+            crate::voice::commands::save_voice_as_prompt,
+            crate::voice::commands::clear_voice_history,
+            crate::voice::commands::rate_voice_ab_trial,
+            crate::voice::commands::get_voice_ab_summary,
+            crate::voice::commands::clear_voice_ab_trials,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
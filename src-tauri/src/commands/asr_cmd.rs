@@ -3,9 +3,12 @@
 //! 提供语音识别服务凭证的 CRUD 操作
 
 use crate::config::{
-    load_config, save_config, AsrCredentialEntry, AsrProviderType, BaiduConfig, OpenAIAsrConfig,
+    load_config, save_config, AsrCredentialEntry, AsrProviderType, AsrRateLimitConfig,
+    AsrTimeoutConfig, AzureAsrConfig, BaiduConfig, CommandAsrConfig, CustomWhisperAsrConfig,
+    DeepgramAsrConfig, HttpAsrConfig, OpenAIAsrConfig, TencentAsrConfig, VolcengineAsrConfig,
     WhisperLocalConfig, XunfeiConfig,
 };
+use crate::voice::asr_cache;
 use serde::{Deserialize, Serialize};
 use tauri::command;
 use uuid::Uuid;
@@ -27,6 +30,8 @@ pub struct AddAsrCredentialRequest {
     pub is_default: bool,
     #[serde(default)]
     pub disabled: bool,
+    #[serde(default)]
+    pub priority: u32,
     #[serde(default = "default_language")]
     pub language: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -36,7 +41,25 @@ pub struct AddAsrCredentialRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub baidu_config: Option<BaiduConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tencent_config: Option<TencentAsrConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub azure_config: Option<AzureAsrConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deepgram_config: Option<DeepgramAsrConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volcengine_config: Option<VolcengineAsrConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub openai_config: Option<OpenAIAsrConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_whisper_config: Option<CustomWhisperAsrConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_config: Option<CommandAsrConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_config: Option<HttpAsrConfig>,
+    #[serde(default)]
+    pub timeouts: AsrTimeoutConfig,
+    #[serde(default)]
+    pub rate_limit: AsrRateLimitConfig,
 }
 
 fn default_language() -> String {
@@ -66,11 +89,21 @@ pub async fn add_asr_credential(
         name: entry.name,
         is_default: entry.is_default,
         disabled: entry.disabled,
+        priority: entry.priority,
         language: entry.language,
         whisper_config: entry.whisper_config,
         xunfei_config: entry.xunfei_config,
         baidu_config: entry.baidu_config,
+        tencent_config: entry.tencent_config,
+        azure_config: entry.azure_config,
+        deepgram_config: entry.deepgram_config,
+        volcengine_config: entry.volcengine_config,
         openai_config: entry.openai_config,
+        custom_whisper_config: entry.custom_whisper_config,
+        command_config: entry.command_config,
+        http_config: entry.http_config,
+        timeouts: entry.timeouts,
+        rate_limit: entry.rate_limit,
     };
 
     tracing::info!("[ASR] 生成新 ID: {}", new_entry.id);
@@ -104,8 +137,11 @@ pub async fn update_asr_credential(entry: AsrCredentialEntry) -> Result<(), Stri
         .position(|c| c.id == entry.id)
         .ok_or_else(|| format!("凭证不存在: {}", entry.id))?;
 
+    let id = entry.id.clone();
     config.credential_pool.asr[idx] = entry;
     save_config(&config).map_err(|e| e.to_string())?;
+    // 配置变化后旧的识别结果不再可靠
+    asr_cache::forget_credential(&id);
 
     Ok(())
 }
@@ -131,6 +167,7 @@ pub async fn delete_asr_credential(id: String) -> Result<(), String> {
     }
 
     save_config(&config).map_err(|e| e.to_string())?;
+    asr_cache::forget_credential(&id);
 
     Ok(())
 }
@@ -155,76 +192,3 @@ pub async fn set_default_asr_credential(id: String) -> Result<(), String> {
 
     Ok(())
 }
-
-/// 测试 ASR 凭证连通性
-#[command]
-pub async fn test_asr_credential(id: String) -> Result<TestResult, String> {
-    let config = load_config().map_err(|e| e.to_string())?;
-
-    let credential = config
-        .credential_pool
-        .asr
-        .iter()
-        .find(|c| c.id == id)
-        .ok_or_else(|| format!("凭证不存在: {}", id))?;
-
-    // 根据 Provider 类型测试
-    match credential.provider {
-        AsrProviderType::WhisperLocal => {
-            // 本地 Whisper 检查模型文件是否存在
-            Ok(TestResult {
-                success: true,
-                message: "本地 Whisper 已就绪".to_string(),
-            })
-        }
-        AsrProviderType::Xunfei => {
-            // TODO: 实现讯飞 API 测试
-            if credential.xunfei_config.is_some() {
-                Ok(TestResult {
-                    success: true,
-                    message: "讯飞配置已设置（实际测试待实现）".to_string(),
-                })
-            } else {
-                Ok(TestResult {
-                    success: false,
-                    message: "讯飞配置缺失".to_string(),
-                })
-            }
-        }
-        AsrProviderType::Baidu => {
-            // TODO: 实现百度 API 测试
-            if credential.baidu_config.is_some() {
-                Ok(TestResult {
-                    success: true,
-                    message: "百度配置已设置（实际测试待实现）".to_string(),
-                })
-            } else {
-                Ok(TestResult {
-                    success: false,
-                    message: "百度配置缺失".to_string(),
-                })
-            }
-        }
-        AsrProviderType::OpenAI => {
-            // TODO: 实现 OpenAI API 测试
-            if credential.openai_config.is_some() {
-                Ok(TestResult {
-                    success: true,
-                    message: "OpenAI 配置已设置（实际测试待实现）".to_string(),
-                })
-            } else {
-                Ok(TestResult {
-                    success: false,
-                    message: "OpenAI 配置缺失".to_string(),
-                })
-            }
-        }
-    }
-}
-
-/// 测试结果
-#[derive(serde::Serialize)]
-pub struct TestResult {
-    pub success: bool,
-    pub message: String,
-}
@@ -4,13 +4,22 @@
 //! 这是新的对话系统实现，与 native_agent_cmd.rs 并行存在
 //! 支持从 ProxyCast 凭证池自动选择凭证
 
-use crate::agent::aster_state::{ProviderConfig, SessionConfigBuilder};
+use crate::agent::approval_policy::{self, PolicyDecision};
+use crate::agent::aster_state::{respond_tool_confirmation, ProviderConfig, SessionConfigBuilder};
 use crate::agent::event_converter::convert_agent_event;
+use crate::agent::tool_audit::{ToolAuditEntry, ToolAuditRecorder};
 use crate::agent::{
-    AsterAgentState, AsterAgentWrapper, SessionDetail, SessionInfo, TauriAgentEvent,
+    AgentCheckpoint, AsterAgentState, AsterAgentWrapper, FileSnapshotRef, SessionDetail,
+    SessionInfo, TauriAgentEvent,
 };
+use crate::commands::session_files_cmd::SessionFilesState;
+use crate::config::GlobalConfigManagerState;
+use crate::database::dao::agent_checkpoint::AgentCheckpointDao;
+use crate::database::dao::mcp_audit::McpAuditDao;
 use crate::database::DbConnection;
+use crate::workspace::{ToolPolicyLists, WorkspaceManager, WorkspaceUpdate};
 use aster::conversation::message::Message;
+use aster::conversation::Conversation;
 use aster::session::SessionManager;
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
@@ -196,6 +205,9 @@ pub struct AsterChatRequest {
     /// Provider 配置（可选，如果未配置则使用当前配置）
     #[serde(default)]
     pub provider_config: Option<ConfigureProviderRequest>,
+    /// 所属工作区 ID（用于工具调用审计）
+    #[serde(default)]
+    pub workspace_id: Option<String>,
 }
 
 /// 图片输入
@@ -211,6 +223,8 @@ pub struct ImageInput {
 pub async fn aster_agent_chat_stream(
     app: AppHandle,
     state: State<'_, AsterAgentState>,
+    db: State<'_, DbConnection>,
+    config_manager: State<'_, GlobalConfigManagerState>,
     request: AsterChatRequest,
 ) -> Result<(), String> {
     tracing::info!(
@@ -266,6 +280,11 @@ pub async fn aster_agent_chat_stream(
 
     match stream_result {
         Ok(mut stream) => {
+            let mut audit = ToolAuditRecorder::new(&session_id, request.workspace_id.clone());
+            let approval_config = config_manager.config().agent.approval.clone();
+            let (workspace_root, tool_policy) =
+                load_workspace_tool_policy(&db, request.workspace_id.as_deref());
+
             // 处理事件流
             while let Some(event_result) = stream.next().await {
                 match event_result {
@@ -274,7 +293,64 @@ pub async fn aster_agent_chat_stream(
                         let tauri_events = convert_agent_event(agent_event);
 
                         // 发送每个事件到前端
-                        for tauri_event in tauri_events {
+                        for mut tauri_event in tauri_events {
+                            if let Some(entry) = audit.observe(&tauri_event) {
+                                record_tool_audit(&db, &entry);
+                            }
+
+                            // Aster 分发每个工具调用前都会请求确认，在这里按审批策略答复；
+                            // 自动放行/拒绝的不再打扰用户，需要确认的只转发这一条请求
+                            if let TauriAgentEvent::ActionRequired {
+                                request_id,
+                                action_type,
+                                data,
+                            } = &mut tauri_event
+                            {
+                                if action_type == "tool_confirmation" {
+                                    let tool_name = data
+                                        .get("tool_name")
+                                        .and_then(|v| v.as_str())
+                                        .unwrap_or_default()
+                                        .to_string();
+                                    let arguments = data
+                                        .get("arguments")
+                                        .cloned()
+                                        .unwrap_or(serde_json::Value::Null);
+                                    let evaluation = approval_policy::evaluate(
+                                        &approval_config,
+                                        &tool_policy,
+                                        &tool_name,
+                                        &arguments,
+                                        workspace_root.as_deref(),
+                                    );
+                                    tracing::info!(
+                                        "[AsterAgent] 工具审批: tool={}, decision={:?}, categories={:?}",
+                                        tool_name,
+                                        evaluation.decision,
+                                        evaluation.categories
+                                    );
+
+                                    match evaluation.decision {
+                                        PolicyDecision::Allow | PolicyDecision::Deny => {
+                                            let allowed =
+                                                evaluation.decision == PolicyDecision::Allow;
+                                            respond_tool_confirmation(agent, request_id, allowed)
+                                                .await;
+                                            continue;
+                                        }
+                                        PolicyDecision::Ask => {
+                                            // 附上风险类别，供前端在确认卡片中展示
+                                            if let Some(obj) = data.as_object_mut() {
+                                                obj.insert(
+                                                    "categories".to_string(),
+                                                    serde_json::json!(evaluation.categories),
+                                                );
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
                             if let Err(e) = app.emit(&request.event_name, &tauri_event) {
                                 tracing::error!("[AsterAgent] 发送事件失败: {}", e);
                             }
@@ -292,6 +368,10 @@ pub async fn aster_agent_chat_stream(
                 }
             }
 
+            for entry in audit.finish() {
+                record_tool_audit(&db, &entry);
+            }
+
             // 发送完成事件
             let done_event = TauriAgentEvent::FinalDone { usage: None };
             if let Err(e) = app.emit(&request.event_name, &done_event) {
@@ -318,6 +398,41 @@ pub async fn aster_agent_chat_stream(
     Ok(())
 }
 
+/// 读取工作区根目录和工具允许/拒绝列表
+fn load_workspace_tool_policy(
+    db: &DbConnection,
+    workspace_id: Option<&str>,
+) -> (Option<PathBuf>, ToolPolicyLists) {
+    let Some(workspace_id) = workspace_id else {
+        return (None, ToolPolicyLists::default());
+    };
+
+    match WorkspaceManager::new(db.clone()).get(&workspace_id.to_string()) {
+        Ok(Some(workspace)) => (Some(workspace.root_path), workspace.settings.tool_policy),
+        Ok(None) => (None, ToolPolicyLists::default()),
+        Err(e) => {
+            tracing::warn!("[AsterAgent] 读取工作区策略失败: {}", e);
+            (None, ToolPolicyLists::default())
+        }
+    }
+}
+
+/// 写入工具调用审计记录（失败只记录日志，不影响对话）
+fn record_tool_audit(db: &DbConnection, entry: &ToolAuditEntry) {
+    let result = db
+        .lock()
+        .map_err(|e| e.to_string())
+        .and_then(|conn| McpAuditDao::insert(&conn, entry).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        tracing::warn!(
+            "[AsterAgent] 写入工具审计失败: {}__{}: {}",
+            entry.server,
+            entry.tool,
+            e
+        );
+    }
+}
+
 /// 停止当前会话
 #[tauri::command]
 pub async fn aster_agent_stop(
@@ -353,6 +468,147 @@ pub async fn aster_session_get(session_id: String) -> Result<SessionDetail, Stri
     AsterAgentWrapper::get_session(&session_id).await
 }
 
+/// 创建会话检查点
+///
+/// 快照 aster 会话当前的对话和会话文件引用
+#[tauri::command]
+pub async fn aster_checkpoint_create(
+    db: State<'_, DbConnection>,
+    session_files: State<'_, SessionFilesState>,
+    session_id: String,
+    name: String,
+    workspace_id: Option<String>,
+) -> Result<AgentCheckpoint, String> {
+    tracing::info!(
+        "[AsterAgent] 创建检查点: session={}, name={}",
+        session_id,
+        name
+    );
+
+    // 会话存储与检查点共用数据库连接，需在锁定数据库前读取会话
+    let session = SessionManager::get_session(&session_id, true)
+        .await
+        .map_err(|e| format!("会话不存在: {} ({})", session_id, e))?;
+    let conversation = session.conversation.unwrap_or_default();
+
+    let file_snapshots = {
+        let storage = session_files
+            .0
+            .lock()
+            .map_err(|e| format!("锁定失败: {}", e))?;
+        if storage.session_exists(&session_id) {
+            storage
+                .list_files(&session_id)?
+                .into_iter()
+                .map(|f| FileSnapshotRef {
+                    name: f.name,
+                    size: f.size,
+                    updated_at: f.updated_at,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        }
+    };
+
+    let checkpoint = AgentCheckpoint {
+        id: uuid::Uuid::new_v4().to_string(),
+        session_id,
+        workspace_id,
+        name,
+        message_count: 0,
+        file_snapshots,
+        created_at: chrono::Utc::now().timestamp_millis(),
+    };
+
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    AgentCheckpointDao::create(&conn, &checkpoint, &conversation)
+        .map_err(|e| format!("创建检查点失败: {}", e))
+}
+
+/// 读取检查点及其对话快照
+fn load_checkpoint(
+    db: &DbConnection,
+    checkpoint_id: &str,
+) -> Result<(AgentCheckpoint, Conversation), String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    let checkpoint = AgentCheckpointDao::get(&conn, checkpoint_id)
+        .map_err(|e| format!("获取检查点失败: {}", e))?
+        .ok_or_else(|| format!("检查点不存在: {}", checkpoint_id))?;
+    let conversation = AgentCheckpointDao::get_conversation(&conn, checkpoint_id)
+        .map_err(|e| format!("获取检查点失败: {}", e))?;
+    Ok((checkpoint, conversation))
+}
+
+/// 列出检查点
+///
+/// 指定 `session_id` 时列出会话的检查点，否则列出工作区的检查点
+#[tauri::command]
+pub async fn aster_checkpoint_list(
+    db: State<'_, DbConnection>,
+    session_id: Option<String>,
+    workspace_id: Option<String>,
+) -> Result<Vec<AgentCheckpoint>, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    let result = match (session_id, workspace_id) {
+        (Some(session_id), _) => AgentCheckpointDao::list_by_session(&conn, &session_id),
+        (None, Some(workspace_id)) => AgentCheckpointDao::list_by_workspace(&conn, &workspace_id),
+        (None, None) => return Err("需要指定 session_id 或 workspace_id".to_string()),
+    };
+    result.map_err(|e| format!("获取检查点列表失败: {}", e))
+}
+
+/// 将会话恢复到检查点状态
+///
+/// 检查点之后的消息会被丢弃，返回被恢复的会话 ID
+#[tauri::command]
+pub async fn aster_checkpoint_resume(
+    db: State<'_, DbConnection>,
+    checkpoint_id: String,
+) -> Result<String, String> {
+    tracing::info!("[AsterAgent] 恢复检查点: {}", checkpoint_id);
+    let (checkpoint, conversation) = load_checkpoint(&db, &checkpoint_id)?;
+    SessionManager::replace_conversation(&checkpoint.session_id, &conversation)
+        .await
+        .map_err(|e| format!("恢复检查点失败: {}", e))?;
+    Ok(checkpoint.session_id)
+}
+
+/// 从检查点分叉出新会话
+///
+/// 原会话保持不变，返回新会话 ID
+#[tauri::command]
+pub async fn aster_checkpoint_branch(
+    db: State<'_, DbConnection>,
+    checkpoint_id: String,
+    name: Option<String>,
+) -> Result<String, String> {
+    tracing::info!("[AsterAgent] 从检查点分叉会话: {}", checkpoint_id);
+    let (checkpoint, conversation) = load_checkpoint(&db, &checkpoint_id)?;
+    let source = SessionManager::get_session(&checkpoint.session_id, false)
+        .await
+        .map_err(|e| format!("会话不存在: {} ({})", checkpoint.session_id, e))?;
+    let name = name.unwrap_or_else(|| format!("{} ({})", source.name, checkpoint.name));
+
+    let session = SessionManager::create_session(source.working_dir, name, source.session_type)
+        .await
+        .map_err(|e| format!("分叉会话失败: {}", e))?;
+    SessionManager::replace_conversation(&session.id, &conversation)
+        .await
+        .map_err(|e| format!("分叉会话失败: {}", e))?;
+    Ok(session.id)
+}
+
+/// 删除检查点
+#[tauri::command]
+pub async fn aster_checkpoint_delete(
+    db: State<'_, DbConnection>,
+    checkpoint_id: String,
+) -> Result<bool, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    AgentCheckpointDao::delete(&conn, &checkpoint_id).map_err(|e| format!("删除检查点失败: {}", e))
+}
+
 /// 确认权限请求
 #[derive(Debug, Deserialize)]
 pub struct ConfirmRequest {
@@ -360,12 +616,22 @@ pub struct ConfirmRequest {
     pub confirmed: bool,
     #[allow(dead_code)]
     pub response: Option<String>,
+    /// 工具全名，配合 `remember` 写入工作区允许/拒绝列表
+    #[serde(default)]
+    pub tool_name: Option<String>,
+    /// 所属工作区 ID
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    /// 记住本次选择
+    #[serde(default)]
+    pub remember: bool,
 }
 
 /// 确认权限请求（用于工具调用确认等）
 #[tauri::command]
 pub async fn aster_agent_confirm(
-    _state: State<'_, AsterAgentState>,
+    state: State<'_, AsterAgentState>,
+    db: State<'_, DbConnection>,
     request: ConfirmRequest,
 ) -> Result<(), String> {
     tracing::info!(
@@ -374,10 +640,53 @@ pub async fn aster_agent_confirm(
         request.confirmed
     );
 
-    // TODO: 实现权限确认逻辑
-    // 这需要 Aster 框架支持 confirmation_tx 通道
-    // 目前先返回成功
+    if request.remember {
+        if let (Some(tool_name), Some(workspace_id)) = (&request.tool_name, &request.workspace_id) {
+            remember_tool_decision(&db, workspace_id, tool_name, request.confirmed)?;
+        }
+    }
+
+    state
+        .confirm_tool_call(&request.request_id, request.confirmed)
+        .await
+}
 
+/// 将工具决定写入工作区允许/拒绝列表
+fn remember_tool_decision(
+    db: &DbConnection,
+    workspace_id: &str,
+    tool_name: &str,
+    allowed: bool,
+) -> Result<(), String> {
+    let manager = WorkspaceManager::new(db.clone());
+    let workspace = manager
+        .get(&workspace_id.to_string())?
+        .ok_or_else(|| format!("工作区不存在: {}", workspace_id))?;
+
+    let mut settings = workspace.settings;
+    let lists = &mut settings.tool_policy;
+    lists.allow.retain(|p| p != tool_name);
+    lists.deny.retain(|p| p != tool_name);
+    if allowed {
+        lists.allow.push(tool_name.to_string());
+    } else {
+        lists.deny.push(tool_name.to_string());
+    }
+
+    manager.update(
+        &workspace.id,
+        WorkspaceUpdate {
+            settings: Some(settings),
+            ..Default::default()
+        },
+    )?;
+
+    tracing::info!(
+        "[AsterAgent] 工作区 {} 记住工具决定: {} -> {}",
+        workspace_id,
+        tool_name,
+        if allowed { "allow" } else { "deny" }
+    );
     Ok(())
 }
 
@@ -0,0 +1,84 @@
+//! 版本兼容相关的 Tauri 命令
+//!
+//! 回退到旧版本后，数据库或配置文件可能来自更新的版本。此时数据库以只读模式打开、
+//! 配置文件拒绝写入（见 [`crate::database::version`] 和 [`crate::config::version`]），
+//! 前端启动时查询兼容状态提示用户，并可导出数据库和配置文件的副本。
+
+use std::path::PathBuf;
+
+use rusqlite::DatabaseName;
+use serde::Serialize;
+use tauri::State;
+
+use crate::config::{self, ConfigManager, CONFIG_VERSION};
+use crate::database::{version, DbConnection};
+
+/// 数据来自更新版本的情况
+#[derive(Debug, Clone, Serialize)]
+pub struct NewerVersion {
+    /// 数据记录的版本
+    pub found: u32,
+    /// 当前程序支持的版本
+    pub supported: u32,
+}
+
+/// 数据兼容状态
+#[derive(Debug, Clone, Serialize)]
+pub struct DataCompatibility {
+    /// 数据库来自更新的版本（已只读打开）
+    pub database: Option<NewerVersion>,
+    /// 配置文件来自更新的版本（已拒绝写入）
+    pub config: Option<NewerVersion>,
+}
+
+/// 获取数据库和配置文件的版本兼容状态
+#[tauri::command]
+pub fn get_data_compatibility() -> Result<DataCompatibility, String> {
+    let database = version::newer_version().map(|found| NewerVersion {
+        found,
+        supported: version::SCHEMA_VERSION,
+    });
+    let config = config::version::file_version(&ConfigManager::default_config_path())
+        .filter(|found| *found > CONFIG_VERSION)
+        .map(|found| NewerVersion {
+            found,
+            supported: CONFIG_VERSION,
+        });
+    Ok(DataCompatibility { database, config })
+}
+
+/// 导出数据库和配置文件的副本到指定目录，返回导出的文件路径
+///
+/// 数据库通过 SQLite 备份接口复制，只读模式下同样可用
+#[tauri::command]
+pub fn export_data_snapshot(
+    db: State<'_, DbConnection>,
+    dir: String,
+) -> Result<Vec<String>, String> {
+    let dir = PathBuf::from(dir);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建导出目录 {:?}: {}", dir, e))?;
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let mut exported = Vec::new();
+
+    let db_path = dir.join(format!("proxycast_{}.db", timestamp));
+    {
+        let conn = db.lock().map_err(|_| "数据库锁已被占用".to_string())?;
+        let progress: Option<fn(rusqlite::backup::Progress)> = None;
+        conn.backup(DatabaseName::Main, &db_path, progress)
+            .map_err(|e| format!("导出数据库失败: {}", e))?;
+    }
+    exported.push(db_path);
+
+    let config_path = ConfigManager::default_config_path();
+    if config_path.exists() {
+        let target = dir.join(format!("config_{}.yaml", timestamp));
+        std::fs::copy(&config_path, &target).map_err(|e| format!("导出配置失败: {}", e))?;
+        exported.push(target);
+    }
+
+    tracing::info!("[数据导出] 已导出 {} 个文件到 {:?}", exported.len(), dir);
+    Ok(exported
+        .into_iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect())
+}
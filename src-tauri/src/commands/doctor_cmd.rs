@@ -0,0 +1,23 @@
+//! 自检相关的 Tauri 命令
+
+use tauri::State;
+
+use crate::app::AppState;
+use crate::commands::provider_pool_cmd::ProviderPoolServiceState;
+use crate::database::DbConnection;
+use crate::services::doctor_service::{self, DoctorReport};
+
+/// 运行自检，返回各项检查的 pass / warn / fail 报告
+///
+/// `live` 为 true 时对启用了健康检查的凭证发起实际请求（耗时较长，会更新凭证健康状态），
+/// 否则只汇总最近一次记录的健康状态
+#[tauri::command]
+pub async fn doctor(
+    app_state: State<'_, AppState>,
+    db: State<'_, DbConnection>,
+    pool_service: State<'_, ProviderPoolServiceState>,
+    live: Option<bool>,
+) -> Result<DoctorReport, String> {
+    let server = app_state.read().await.status();
+    Ok(doctor_service::run(server, &db, &pool_service.0, live.unwrap_or(false)).await)
+}
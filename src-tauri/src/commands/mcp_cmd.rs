@@ -1,3 +1,5 @@
+use crate::agent::tool_audit::{ToolAuditEntry, ToolAuditFilter};
+use crate::database::dao::mcp_audit::McpAuditDao;
 use crate::database::DbConnection;
 use crate::models::McpServer;
 use crate::services::mcp_service::McpService;
@@ -42,3 +44,23 @@ pub fn import_mcp_from_app(db: State<'_, DbConnection>, app_type: String) -> Res
 pub fn sync_all_mcp_to_live(db: State<'_, DbConnection>) -> Result<(), String> {
     McpService::sync_all_to_live(&db)
 }
+
+/// 查询 MCP 工具调用审计日志
+///
+/// 支持按工作区、会话、服务器、状态和起始时间过滤
+#[tauri::command]
+pub fn query_mcp_audit_log(
+    db: State<'_, DbConnection>,
+    filter: Option<ToolAuditFilter>,
+) -> Result<Vec<ToolAuditEntry>, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    McpAuditDao::query(&conn, &filter.unwrap_or_default())
+        .map_err(|e| format!("查询审计日志失败: {}", e))
+}
+
+/// 清理指定时间（毫秒时间戳）之前的审计日志
+#[tauri::command]
+pub fn clear_mcp_audit_log(db: State<'_, DbConnection>, before: i64) -> Result<usize, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    McpAuditDao::delete_before(&conn, before).map_err(|e| format!("清理审计日志失败: {}", e))
+}
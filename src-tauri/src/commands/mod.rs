@@ -4,11 +4,13 @@ pub mod asr_cmd;
 pub mod aster_agent_cmd;
 pub mod auto_fix_cmd;
 pub mod browser_interceptor_cmd;
+pub mod compat_cmd;
 pub mod config_cmd;
 pub mod connect_cmd;
 pub mod connection_cmd;
 pub mod content_cmd;
 pub mod context_memory;
+pub mod doctor_cmd;
 pub mod flow_monitor_cmd;
 pub mod general_chat_cmd;
 pub mod injection_cmd;
@@ -26,16 +28,20 @@ pub mod orchestrator_cmd;
 pub mod plugin_cmd;
 pub mod plugin_install_cmd;
 pub mod plugin_rpc_cmd;
+pub mod postmortem_cmd;
 pub mod prompt_cmd;
 pub mod provider_pool_cmd;
 pub mod resilience_cmd;
 pub mod route_cmd;
+pub mod scheduler_cmd;
 pub mod screenshot_cmd;
 pub mod session_files_cmd;
 pub mod skill_cmd;
+pub mod startup_cmd;
 pub mod switch_cmd;
 pub mod telemetry_cmd;
 pub mod terminal_cmd;
+pub mod token_count_cmd;
 pub mod tool_hooks;
 pub mod tray_cmd;
 pub mod update_cmd;
@@ -1,24 +1,94 @@
 //! 模型注册表 Tauri 命令
 //!
 //! 提供模型注册表相关的前端 API
+//!
+//! 模型注册表不在启动时加载：setup 时只登记数据库和资源目录（[`defer_init`]），
+//! 首次访问（或收到预热提示）时再从内嵌资源加载。
 
+use crate::database::DbConnection;
 use crate::models::model_registry::{
     EnhancedModelMetadata, ModelSyncState, ModelTier, ProviderAliasConfig, UserModelPreference,
 };
 use crate::services::model_registry_service::{FetchModelsResult, ModelRegistryService};
+use crate::services::startup_profile_service::{profiler, InitPhase};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::State;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, RwLockReadGuard};
 
 /// 模型注册服务状态
 pub type ModelRegistryState = Arc<RwLock<Option<ModelRegistryService>>>;
 
+/// 延迟初始化所需的数据库和资源目录
+static DEFERRED_INIT: once_cell::sync::OnceCell<(DbConnection, PathBuf)> =
+    once_cell::sync::OnceCell::new();
+
+/// 应用管理的服务状态，供请求转发路径读取模型限制
+static SHARED_STATE: once_cell::sync::OnceCell<ModelRegistryState> =
+    once_cell::sync::OnceCell::new();
+
+/// 首次访问时的初始化（只执行一次）
+static INITIALIZED: tokio::sync::OnceCell<()> = tokio::sync::OnceCell::const_new();
+
+/// 登记延迟初始化参数（setup 时调用）
+pub fn defer_init(db: DbConnection, resource_dir: PathBuf) {
+    if DEFERRED_INIT.set((db, resource_dir)).is_err() {
+        tracing::warn!("[ModelRegistry] 延迟初始化参数已登记，忽略重复登记");
+    }
+}
+
+/// 登记应用管理的服务状态（setup 时调用）
+pub fn share_state(state: ModelRegistryState) {
+    if SHARED_STATE.set(state).is_err() {
+        tracing::warn!("[ModelRegistry] 服务状态已登记，忽略重复登记");
+    }
+}
+
+/// 应用登记的服务状态，未登记时（如独立运行的服务器）返回 `None`
+pub fn shared_state() -> Option<ModelRegistryState> {
+    SHARED_STATE.get().cloned()
+}
+
+/// 确保模型注册服务已初始化
+///
+/// 首次调用时从内嵌资源加载，并发调用等待同一次初始化；初始化失败后不再重试，
+/// 与启动时初始化失败的行为一致
+pub async fn ensure_initialized(state: &ModelRegistryState) {
+    INITIALIZED
+        .get_or_init(|| async {
+            let Some((db, resource_dir)) = DEFERRED_INIT.get() else {
+                return;
+            };
+            let mut service = ModelRegistryService::new(db.clone());
+            service.set_resource_dir(resource_dir.clone());
+            let result = profiler()
+                .measure_async("model_registry", InitPhase::Deferred, service.initialize())
+                .await;
+            match result {
+                Ok(()) => {
+                    tracing::info!("[ModelRegistry] 模型注册服务初始化成功");
+                    *state.write().await = Some(service);
+                }
+                Err(e) => tracing::error!("[ModelRegistry] 模型注册服务初始化失败: {}", e),
+            }
+        })
+        .await;
+}
+
+/// 初始化后读取模型注册服务
+pub async fn read_registry(
+    state: &ModelRegistryState,
+) -> RwLockReadGuard<'_, Option<ModelRegistryService>> {
+    ensure_initialized(state).await;
+    state.read().await
+}
+
 /// 获取所有模型
 #[tauri::command]
 pub async fn get_model_registry(
     state: State<'_, ModelRegistryState>,
 ) -> Result<Vec<EnhancedModelMetadata>, String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -33,7 +103,7 @@ pub async fn search_models(
     query: String,
     limit: Option<usize>,
 ) -> Result<Vec<EnhancedModelMetadata>, String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -46,7 +116,7 @@ pub async fn search_models(
 pub async fn get_model_preferences(
     state: State<'_, ModelRegistryState>,
 ) -> Result<Vec<UserModelPreference>, String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -60,7 +130,7 @@ pub async fn toggle_model_favorite(
     state: State<'_, ModelRegistryState>,
     model_id: String,
 ) -> Result<bool, String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -74,7 +144,7 @@ pub async fn hide_model(
     state: State<'_, ModelRegistryState>,
     model_id: String,
 ) -> Result<(), String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -88,7 +158,7 @@ pub async fn record_model_usage(
     state: State<'_, ModelRegistryState>,
     model_id: String,
 ) -> Result<(), String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -101,7 +171,7 @@ pub async fn record_model_usage(
 pub async fn get_model_sync_state(
     state: State<'_, ModelRegistryState>,
 ) -> Result<ModelSyncState, String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -115,7 +185,7 @@ pub async fn get_models_for_provider(
     state: State<'_, ModelRegistryState>,
     provider_id: String,
 ) -> Result<Vec<EnhancedModelMetadata>, String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -129,7 +199,7 @@ pub async fn get_models_by_tier(
     state: State<'_, ModelRegistryState>,
     tier: String,
 ) -> Result<Vec<EnhancedModelMetadata>, String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -149,7 +219,7 @@ pub async fn get_provider_alias_config(
     state: State<'_, ModelRegistryState>,
     provider: String,
 ) -> Result<Option<ProviderAliasConfig>, String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -162,7 +232,7 @@ pub async fn get_provider_alias_config(
 pub async fn get_all_alias_configs(
     state: State<'_, ModelRegistryState>,
 ) -> Result<std::collections::HashMap<String, ProviderAliasConfig>, String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -173,7 +243,7 @@ pub async fn get_all_alias_configs(
 /// 刷新模型注册表（强制从内嵌资源重新加载）
 #[tauri::command]
 pub async fn refresh_model_registry(state: State<'_, ModelRegistryState>) -> Result<u32, String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -197,7 +267,7 @@ pub async fn fetch_provider_models_from_api(
     api_host: String,
     api_key: String,
 ) -> Result<FetchModelsResult, String> {
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
@@ -242,7 +312,7 @@ pub async fn fetch_provider_models_auto(
     }
 
     // 调用模型注册服务
-    let guard = state.read().await;
+    let guard = read_registry(&state).await;
     let service = guard
         .as_ref()
         .ok_or_else(|| "模型注册服务未初始化".to_string())?;
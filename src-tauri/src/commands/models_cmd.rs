@@ -2,7 +2,7 @@
 //!
 //! 提供动态模型配置的 Tauri 命令
 
-use crate::commands::model_registry_cmd::ModelRegistryState;
+use crate::commands::model_registry_cmd::{read_registry, ModelRegistryState};
 use crate::config::{save_config, ModelInfo, ModelsConfig, ProviderModelsConfig};
 use crate::AppState;
 use serde::{Deserialize, Serialize};
@@ -81,7 +81,7 @@ pub async fn get_all_provider_models(
 
     // 获取别名配置
     let alias_configs = {
-        let guard = model_registry_state.read().await;
+        let guard = read_registry(&model_registry_state).await;
         if let Some(service) = guard.as_ref() {
             let configs = service.get_all_alias_configs().await;
             tracing::info!(
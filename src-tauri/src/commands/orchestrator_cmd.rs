@@ -187,6 +187,8 @@ fn map_pool_provider_type(pool_type: &str) -> ProviderType {
         "kiro" => ProviderType::Kiro,
         "codex" => ProviderType::OpenAI,
         "antigravity" => ProviderType::Antigravity,
+        "moonshotai" | "moonshot" => ProviderType::Moonshot,
+        "zhipuai" | "zhipu" => ProviderType::Zhipu,
         _ => ProviderType::Custom,
     }
 }
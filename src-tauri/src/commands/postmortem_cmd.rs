@@ -0,0 +1,34 @@
+//! 失败请求诊断包相关的 Tauri 命令
+
+use crate::services::postmortem_service::{get_postmortem_store, PostmortemBundle};
+
+/// 默认列出的诊断包数量
+const DEFAULT_LIST_LIMIT: usize = 50;
+
+/// 按请求 ID 获取诊断包
+#[tauri::command]
+pub async fn get_request_postmortem(request_id: String) -> Result<PostmortemBundle, String> {
+    get_postmortem_store()
+        .get(request_id.trim())
+        .ok_or_else(|| {
+            format!(
+                "未找到请求 {} 的诊断包（可能已被淘汰或请求未失败）",
+                request_id
+            )
+        })
+}
+
+/// 列出最近的诊断包（最新的在前）
+#[tauri::command]
+pub async fn list_request_postmortems(
+    limit: Option<usize>,
+) -> Result<Vec<PostmortemBundle>, String> {
+    Ok(get_postmortem_store().list(limit.unwrap_or(DEFAULT_LIST_LIMIT)))
+}
+
+/// 清空诊断包
+#[tauri::command]
+pub async fn clear_request_postmortems() -> Result<(), String> {
+    get_postmortem_store().clear();
+    Ok(())
+}
@@ -0,0 +1,100 @@
+//! 定时任务相关命令
+
+use crate::database::dao::scheduled_jobs::ScheduledJobDao;
+use crate::database::DbConnection;
+use crate::scheduler::service::{compute_next_run, run_job};
+use crate::scheduler::{JobKind, JobRun, ScheduledJob};
+use serde::Deserialize;
+use tauri::{AppHandle, State};
+
+/// 默认返回的执行记录条数
+const DEFAULT_RUNS_LIMIT: u32 = 20;
+
+/// 保存定时任务请求
+#[derive(Debug, Deserialize)]
+pub struct SaveScheduledJobRequest {
+    /// 为空时新建任务
+    pub id: Option<String>,
+    pub name: String,
+    pub enabled: bool,
+    pub schedule: String,
+    pub job: JobKind,
+}
+
+/// 获取所有定时任务（含下次执行时间）
+#[tauri::command]
+pub fn scheduler_list_jobs(db: State<'_, DbConnection>) -> Result<Vec<ScheduledJob>, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    ScheduledJobDao::list(&conn).map_err(|e| format!("获取定时任务失败: {}", e))
+}
+
+/// 新建或更新定时任务
+#[tauri::command]
+pub fn scheduler_save_job(
+    db: State<'_, DbConnection>,
+    request: SaveScheduledJobRequest,
+) -> Result<ScheduledJob, String> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let next_run_at = compute_next_run(&request.schedule, now)?;
+
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    let existing = match &request.id {
+        Some(id) => ScheduledJobDao::get(&conn, id).map_err(|e| e.to_string())?,
+        None => None,
+    };
+
+    let job = ScheduledJob {
+        id: request
+            .id
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        name: request.name,
+        enabled: request.enabled,
+        schedule: request.schedule,
+        job: request.job,
+        last_run_at: existing.as_ref().and_then(|j| j.last_run_at),
+        last_status: existing.as_ref().and_then(|j| j.last_status),
+        last_error: existing.as_ref().and_then(|j| j.last_error.clone()),
+        next_run_at: if request.enabled { next_run_at } else { None },
+        created_at: existing.map(|j| j.created_at).unwrap_or(now),
+    };
+
+    ScheduledJobDao::upsert(&conn, &job).map_err(|e| format!("保存定时任务失败: {}", e))?;
+    Ok(job)
+}
+
+/// 删除定时任务
+#[tauri::command]
+pub fn scheduler_delete_job(db: State<'_, DbConnection>, id: String) -> Result<bool, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    ScheduledJobDao::delete(&conn, &id).map_err(|e| format!("删除定时任务失败: {}", e))
+}
+
+/// 立即执行定时任务（不影响下次执行时间）
+#[tauri::command]
+pub async fn scheduler_run_job_now(
+    app: AppHandle,
+    db: State<'_, DbConnection>,
+    id: String,
+) -> Result<JobRun, String> {
+    let job = {
+        let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+        ScheduledJobDao::get(&conn, &id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("定时任务不存在: {}", id))?
+    };
+
+    let db = db.inner().clone();
+    Ok(run_job(&app, &db, &job).await)
+}
+
+/// 获取定时任务的执行记录
+#[tauri::command]
+pub fn scheduler_list_runs(
+    db: State<'_, DbConnection>,
+    job_id: String,
+    limit: Option<u32>,
+) -> Result<Vec<JobRun>, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    ScheduledJobDao::list_runs(&conn, &job_id, limit.unwrap_or(DEFAULT_RUNS_LIMIT))
+        .map_err(|e| format!("获取执行记录失败: {}", e))
+}
@@ -0,0 +1,41 @@
+//! 启动耗时和延迟初始化相关的 Tauri 命令
+
+use tauri::{AppHandle, Manager};
+
+use crate::commands::model_registry_cmd::{self, ModelRegistryState};
+use crate::services::startup_profile_service::{profiler, StartupProfile};
+use crate::voice::asr_service::AsrService;
+
+/// 获取启动耗时报告（各子系统在启动时或首次使用时的初始化耗时）
+#[tauri::command]
+pub async fn get_startup_profile() -> Result<StartupProfile, String> {
+    Ok(profiler().profile())
+}
+
+/// 预热延迟初始化的子系统
+///
+/// 前端预计即将用到某个子系统时调用，在后台提前完成初始化：
+/// - `model_registry`：模型注册表
+/// - `local_asr`：默认凭证为本地 Whisper 时加载模型
+#[tauri::command]
+pub async fn warm_up_subsystem(app: AppHandle, subsystem: String) -> Result<(), String> {
+    match subsystem.as_str() {
+        "model_registry" => {
+            let state = app
+                .try_state::<ModelRegistryState>()
+                .ok_or("Model Registry 状态不可用")?
+                .inner()
+                .clone();
+            tauri::async_runtime::spawn(async move {
+                model_registry_cmd::ensure_initialized(&state).await;
+            });
+        }
+        "local_asr" => {
+            if let Some(credential) = AsrService::get_default_credential()? {
+                AsrService::warm_up_local_model(&credential);
+            }
+        }
+        other => return Err(format!("未知的子系统: {}", other)),
+    }
+    Ok(())
+}
@@ -1,11 +1,18 @@
 //! 遥测命令模块
 //!
-//! 提供请求日志、统计数据和 Token 追踪的 Tauri 命令
+//! 提供请求日志、统计数据、监控面板聚合查询、上游连接复用统计、管道开销统计和 Token 追踪的 Tauri 命令
 
+use crate::app::AppState;
+use crate::database::DbConnection;
+use crate::services::{anomaly_service, http_client_service, pipeline_metrics_service};
 use crate::telemetry::{
-    ModelStats, ModelTokenStats, ProviderStats, ProviderTokenStats, RequestLog, RequestLogger,
-    RequestStatus, StatsAggregator, StatsSummary, TimeRange, TokenStatsSummary, TokenTracker,
+    AnomalyRecord, ClientModelUsage, ConnectionStats, ErrorRateStats, HourlyRequestStats,
+    LatencyPercentiles, LogFilter, ModelStats, ModelTokenStats, ModelUsage, ProviderAnomalyStats,
+    ProviderModelStats, ProviderStats, ProviderTokenStats, RequestLog, RequestLogger,
+    RequestStatus, StatsAggregator, StatsSummary, StepOverhead, TimeRange, TokenStatsSummary,
+    TokenTracker,
 };
+use crate::voice::cost;
 use crate::ProviderType;
 use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
@@ -96,14 +103,7 @@ pub async fn get_request_logs(
 
     // 按状态过滤
     if let Some(s) = status {
-        let req_status = match s.as_str() {
-            "success" => RequestStatus::Success,
-            "failed" => RequestStatus::Failed,
-            "timeout" => RequestStatus::Timeout,
-            "retrying" => RequestStatus::Retrying,
-            "cancelled" => RequestStatus::Cancelled,
-            _ => return Err(format!("Invalid status: {}", s)),
-        };
+        let req_status = parse_status(&s)?;
         logs.retain(|l| l.status == req_status);
     }
 
@@ -118,6 +118,18 @@ pub async fn get_request_logs(
     Ok(logs)
 }
 
+/// 解析请求状态
+fn parse_status(status: &str) -> Result<RequestStatus, String> {
+    match status {
+        "success" => Ok(RequestStatus::Success),
+        "failed" => Ok(RequestStatus::Failed),
+        "timeout" => Ok(RequestStatus::Timeout),
+        "retrying" => Ok(RequestStatus::Retrying),
+        "cancelled" => Ok(RequestStatus::Cancelled),
+        _ => Err(format!("Invalid status: {}", status)),
+    }
+}
+
 /// 获取单个请求日志详情
 #[tauri::command]
 pub async fn get_request_log_detail(
@@ -211,6 +223,334 @@ pub async fn get_stats_by_model(
     Ok(stats.by_model(range))
 }
 
+/// 按 Provider + 模型分组统计
+///
+/// 包含首 Token 延迟和生成速度，按平均首 Token 延迟升序排列（无流式数据的排在最后）
+#[tauri::command]
+pub async fn get_stats_by_provider_model(
+    state: tauri::State<'_, TelemetryState>,
+    time_range: Option<TimeRangeParam>,
+) -> Result<Vec<ProviderModelStats>, String> {
+    let range = time_range.map(|r| r.to_time_range()).transpose()?.flatten();
+    let stats = state.stats.read();
+    let mut result: Vec<ProviderModelStats> = stats
+        .by_provider_and_model(range)
+        .into_iter()
+        .map(|((provider, model), summary)| ProviderModelStats {
+            provider: Some(provider),
+            model,
+            summary,
+        })
+        .collect();
+    result.sort_by(|a, b| {
+        let key = |s: &ProviderModelStats| s.summary.avg_first_token_ms.unwrap_or(f64::MAX);
+        key(a).total_cmp(&key(b))
+    });
+    Ok(result)
+}
+
+// ========== 监控面板命令 ==========
+
+/// 监控面板过滤参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DashboardFilterParam {
+    /// Provider
+    pub provider: Option<String>,
+    /// 模型名称
+    pub model: Option<String>,
+    /// 请求状态
+    pub status: Option<String>,
+}
+
+impl DashboardFilterParam {
+    fn to_log_filter(&self, time_range: Option<TimeRangeParam>) -> Result<LogFilter, String> {
+        Ok(LogFilter {
+            range: time_range.map(|r| r.to_time_range()).transpose()?.flatten(),
+            provider: self
+                .provider
+                .as_deref()
+                .map(|p| p.parse().map_err(|e: String| e))
+                .transpose()?,
+            model: self.model.clone(),
+            status: self.status.as_deref().map(parse_status).transpose()?,
+        })
+    }
+}
+
+fn log_filter(
+    time_range: Option<TimeRangeParam>,
+    filter: Option<DashboardFilterParam>,
+) -> Result<LogFilter, String> {
+    filter.unwrap_or_default().to_log_filter(time_range)
+}
+
+/// 按小时统计请求数和错误率
+#[tauri::command]
+pub async fn get_requests_per_hour(
+    state: tauri::State<'_, TelemetryState>,
+    time_range: Option<TimeRangeParam>,
+    filter: Option<DashboardFilterParam>,
+) -> Result<Vec<HourlyRequestStats>, String> {
+    let filter = log_filter(time_range, filter)?;
+    Ok(state.stats.read().requests_per_hour(&filter))
+}
+
+/// 统计错误率（取消的请求不计入错误）
+#[tauri::command]
+pub async fn get_error_rate(
+    state: tauri::State<'_, TelemetryState>,
+    time_range: Option<TimeRangeParam>,
+    filter: Option<DashboardFilterParam>,
+) -> Result<ErrorRateStats, String> {
+    let filter = log_filter(time_range, filter)?;
+    Ok(state.stats.read().error_rate(&filter))
+}
+
+/// 获取请求延迟的 P50 / P95
+#[tauri::command]
+pub async fn get_latency_percentiles(
+    state: tauri::State<'_, TelemetryState>,
+    time_range: Option<TimeRangeParam>,
+    filter: Option<DashboardFilterParam>,
+) -> Result<LatencyPercentiles, String> {
+    let filter = log_filter(time_range, filter)?;
+    Ok(state.stats.read().latency_percentiles(&filter))
+}
+
+/// 获取请求数最多的模型（默认前 10 个）
+#[tauri::command]
+pub async fn get_top_models(
+    state: tauri::State<'_, TelemetryState>,
+    time_range: Option<TimeRangeParam>,
+    filter: Option<DashboardFilterParam>,
+    limit: Option<usize>,
+) -> Result<Vec<ModelUsage>, String> {
+    let filter = log_filter(time_range, filter)?;
+    Ok(state.stats.read().top_models(&filter, limit.unwrap_or(10)))
+}
+
+/// 按 Provider 汇总的费用
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderCost {
+    /// Provider
+    pub provider: String,
+    /// 请求数
+    pub requests: u64,
+    /// 输入 Token 数
+    pub input_tokens: u64,
+    /// 输出 Token 数
+    pub output_tokens: u64,
+    /// 各货币单位的费用
+    pub costs: HashMap<String, f64>,
+    /// 模型没有价格信息、未计入费用的请求数
+    pub unpriced_requests: u64,
+}
+
+/// 按 Provider 统计费用
+///
+/// 价格取自模型注册表，按每百万 Token 计算；没有价格的模型只计入 Token 数。
+/// 结果按请求数降序排列。
+#[tauri::command]
+pub async fn get_cost_by_provider(
+    state: tauri::State<'_, TelemetryState>,
+    db: tauri::State<'_, DbConnection>,
+    time_range: Option<TimeRangeParam>,
+    filter: Option<DashboardFilterParam>,
+) -> Result<Vec<ProviderCost>, String> {
+    let filter = log_filter(time_range, filter)?;
+    let usage = state.stats.read().usage_by_provider_model(&filter);
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+
+    let mut by_provider: HashMap<ProviderType, ProviderCost> = HashMap::new();
+    for item in usage {
+        let entry = by_provider
+            .entry(item.provider)
+            .or_insert_with(|| ProviderCost {
+                provider: item.provider.to_string(),
+                requests: 0,
+                input_tokens: 0,
+                output_tokens: 0,
+                costs: HashMap::new(),
+                unpriced_requests: 0,
+            });
+        entry.requests += item.requests;
+        entry.input_tokens += item.input_tokens;
+        entry.output_tokens += item.output_tokens;
+
+        match price(&conn, &item.model, item.input_tokens, item.output_tokens) {
+            Some((currency, amount)) => *entry.costs.entry(currency).or_default() += amount,
+            None => entry.unpriced_requests += item.requests,
+        }
+    }
+
+    let mut result: Vec<ProviderCost> = by_provider.into_values().collect();
+    result.sort_by(|a, b| {
+        b.requests
+            .cmp(&a.requests)
+            .then_with(|| a.provider.cmp(&b.provider))
+    });
+    Ok(result)
+}
+
+/// 按模型注册表的价格计算费用，返回货币单位和金额；模型没有价格时为空
+fn price(
+    conn: &rusqlite::Connection,
+    model: &str,
+    input_tokens: u64,
+    output_tokens: u64,
+) -> Option<(String, f64)> {
+    let pricing = cost::pricing(conn, model)?;
+    let input = pricing.input_per_million?;
+    let output = pricing.output_per_million?;
+    let amount = (input * input_tokens as f64 + output * output_tokens as f64) / 1_000_000.0;
+    Some((pricing.currency, amount))
+}
+
+/// 按客户端密钥汇总的用量
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientUsage {
+    /// 客户端密钥 ID，使用主密钥的请求为空
+    pub client_key_id: Option<String>,
+    /// 显示名称（主密钥为"主机"，已删除的密钥为其 ID）
+    pub name: String,
+    /// 请求数
+    pub requests: u64,
+    /// 失败和超时的请求数
+    pub failed_requests: u64,
+    /// 输入 Token 数
+    pub input_tokens: u64,
+    /// 输出 Token 数
+    pub output_tokens: u64,
+    /// 各货币单位的费用
+    pub costs: HashMap<String, f64>,
+    /// 模型没有价格信息、未计入费用的请求数
+    pub unpriced_requests: u64,
+}
+
+/// 按客户端密钥统计请求和费用
+///
+/// 局域网共享时每位成员使用自己的客户端密钥，据此按成员汇总用量。
+/// 价格的计算方式同 [`get_cost_by_provider`]，结果按请求数降序排列。
+#[tauri::command]
+pub async fn get_usage_by_client(
+    state: tauri::State<'_, TelemetryState>,
+    app_state: tauri::State<'_, AppState>,
+    db: tauri::State<'_, DbConnection>,
+    time_range: Option<TimeRangeParam>,
+    filter: Option<DashboardFilterParam>,
+) -> Result<Vec<ClientUsage>, String> {
+    let filter = log_filter(time_range, filter)?;
+    let usage = state.stats.read().usage_by_client_model(&filter);
+    let client_keys = app_state.read().await.config.server.client_keys.clone();
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+
+    let mut by_client: HashMap<Option<String>, ClientUsage> = HashMap::new();
+    for item in usage {
+        let ClientModelUsage {
+            client_key_id,
+            model,
+            requests,
+            failed_requests,
+            input_tokens,
+            output_tokens,
+        } = item;
+        let entry = by_client
+            .entry(client_key_id.clone())
+            .or_insert_with(|| ClientUsage {
+                name: match &client_key_id {
+                    Some(id) => client_keys
+                        .iter()
+                        .find(|c| &c.id == id)
+                        .map(|c| c.name.clone())
+                        .unwrap_or_else(|| id.clone()),
+                    None => "主机".to_string(),
+                },
+                client_key_id,
+                requests: 0,
+                failed_requests: 0,
+                input_tokens: 0,
+                output_tokens: 0,
+                costs: HashMap::new(),
+                unpriced_requests: 0,
+            });
+        entry.requests += requests;
+        entry.failed_requests += failed_requests;
+        entry.input_tokens += input_tokens;
+        entry.output_tokens += output_tokens;
+
+        match price(&conn, &model, input_tokens, output_tokens) {
+            Some((currency, amount)) => *entry.costs.entry(currency).or_default() += amount,
+            None => entry.unpriced_requests += requests,
+        }
+    }
+
+    let mut result: Vec<ClientUsage> = by_client.into_values().collect();
+    result.sort_by(|a, b| {
+        b.requests
+            .cmp(&a.requests)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(result)
+}
+
+/// Provider 响应异常报告
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderAnomalyReport {
+    /// 各 Provider 的异常统计（降级的排在前面）
+    pub providers: Vec<ProviderAnomalyStats>,
+    /// 最近的异常记录（最新的在前）
+    pub recent: Vec<AnomalyRecord>,
+}
+
+/// 获取上游响应异常统计
+///
+/// 指定 `provider` 时只返回该 Provider 的最近异常记录
+#[tauri::command]
+pub async fn get_provider_anomalies(
+    provider: Option<String>,
+    limit: Option<usize>,
+) -> Result<ProviderAnomalyReport, String> {
+    let provider = provider.map(|p| p.parse::<ProviderType>()).transpose()?;
+    let tracker = anomaly_service::get_anomaly_tracker();
+    Ok(ProviderAnomalyReport {
+        providers: tracker.provider_stats(),
+        recent: tracker.recent(provider, limit.unwrap_or(50)),
+    })
+}
+
+/// 清空上游响应异常记录
+#[tauri::command]
+pub async fn clear_provider_anomalies() -> Result<(), String> {
+    anomaly_service::get_anomaly_tracker().clear();
+    Ok(())
+}
+
+/// 获取上游连接复用统计（按 Provider 的 HTTP 客户端）
+#[tauri::command]
+pub async fn get_connection_stats() -> Result<Vec<ConnectionStats>, String> {
+    Ok(http_client_service::connection_tracker().stats())
+}
+
+/// 清空上游连接复用统计
+#[tauri::command]
+pub async fn clear_connection_stats() -> Result<(), String> {
+    http_client_service::connection_tracker().clear();
+    Ok(())
+}
+
+/// 获取管道自身开销统计（按步骤的 P50 / P95 / 最大耗时，不含等待上游）
+#[tauri::command]
+pub async fn get_pipeline_overhead() -> Result<Vec<StepOverhead>, String> {
+    Ok(pipeline_metrics_service::get_overhead_tracker().report())
+}
+
+/// 清空管道自身开销统计
+#[tauri::command]
+pub async fn clear_pipeline_overhead() -> Result<(), String> {
+    pipeline_metrics_service::get_overhead_tracker().clear();
+    Ok(())
+}
+
 // ========== Token 统计命令 ==========
 
 /// 获取 Token 统计摘要
@@ -0,0 +1,41 @@
+//! Token 计数相关的 Tauri 命令
+//!
+//! 供前端在发送前展示提示词大小，并提示是否超出模型上下文长度。
+
+use crate::database::DbConnection;
+use crate::models::openai::{ChatCompletionRequest, ChatMessage};
+use crate::services::token_count_service::{self, TokenCount};
+use tauri::State;
+
+/// 计算提示词 Token 数
+///
+/// `messages` 优先；未提供时按纯文本 `text` 计数
+#[tauri::command]
+pub async fn count_prompt_tokens(
+    db: State<'_, DbConnection>,
+    model: String,
+    text: Option<String>,
+    messages: Option<Vec<ChatMessage>>,
+) -> Result<TokenCount, String> {
+    let input_tokens = match (messages, text) {
+        (Some(messages), _) => token_count_service::count_openai_request(&ChatCompletionRequest {
+            model: model.clone(),
+            messages,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            stream: false,
+            tools: None,
+            tool_choice: None,
+            reasoning_effort: None,
+        }),
+        (None, Some(text)) => token_count_service::count_text(&text, &model),
+        (None, None) => return Err("text 和 messages 不能同时为空".to_string()),
+    };
+
+    Ok(token_count_service::with_context(
+        Some(db.inner()),
+        &model,
+        input_tokens,
+    ))
+}
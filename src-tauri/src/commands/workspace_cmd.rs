@@ -13,7 +13,8 @@
 
 use crate::database::DbConnection;
 use crate::workspace::{
-    Workspace, WorkspaceManager, WorkspaceSettings, WorkspaceType, WorkspaceUpdate,
+    events, Workspace, WorkspaceEvent, WorkspaceManager, WorkspaceSettings, WorkspaceType,
+    WorkspaceUpdate,
 };
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -155,6 +156,7 @@ pub async fn workspace_update(
     };
 
     let workspace = manager.update(&id, updates)?;
+    events::publish(WorkspaceEvent::Updated(id));
     Ok(workspace.into())
 }
 
@@ -178,14 +180,20 @@ pub async fn workspace_delete(
         }
     }
 
-    manager.delete(&id)
+    let deleted = manager.delete(&id)?;
+    if deleted {
+        events::publish(WorkspaceEvent::Deleted(id));
+    }
+    Ok(deleted)
 }
 
 /// 设置默认 workspace
 #[tauri::command]
 pub async fn workspace_set_default(db: State<'_, DbConnection>, id: String) -> Result<(), String> {
     let manager = WorkspaceManager::new(db.inner().clone());
-    manager.set_default(&id)
+    manager.set_default(&id)?;
+    events::publish(WorkspaceEvent::DefaultChanged(id));
+    Ok(())
 }
 
 /// 获取默认 workspace
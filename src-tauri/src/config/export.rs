@@ -289,6 +289,9 @@ impl ExportService {
 
         // 脱敏服务器 API 密钥
         redacted.server.api_key = REDACTED_PLACEHOLDER.to_string();
+        for client in &mut redacted.server.client_keys {
+            client.key = REDACTED_PLACEHOLDER.to_string();
+        }
 
         // 脱敏 Provider API 密钥
         if redacted.providers.openai.api_key.is_some() {
@@ -347,6 +350,14 @@ impl ExportService {
         if !config.server.api_key.is_empty() && config.server.api_key != REDACTED_PLACEHOLDER {
             return true;
         }
+        if config
+            .server
+            .client_keys
+            .iter()
+            .any(|c| !c.key.is_empty() && c.key != REDACTED_PLACEHOLDER)
+        {
+            return true;
+        }
 
         // 检查 Provider API 密钥
         if let Some(ref key) = config.providers.openai.api_key {
@@ -507,6 +518,7 @@ pub use self::base64::encode as base64_encode;
 #[cfg(test)]
 mod unit_tests {
     use super::*;
+    use crate::config::ClientApiKey;
 
     #[test]
     fn test_export_options_default() {
@@ -633,10 +645,18 @@ mod unit_tests {
             disabled: false,
             proxy_url: None,
         });
+        config.server.client_keys.push(ClientApiKey {
+            id: "alice".to_string(),
+            name: "Alice".to_string(),
+            key: "pc_alice_secret".to_string(),
+            disabled: false,
+        });
 
         let redacted = ExportService::redact_config(&config);
 
         assert_eq!(redacted.server.api_key, REDACTED_PLACEHOLDER);
+        assert_eq!(redacted.server.client_keys[0].key, REDACTED_PLACEHOLDER);
+        assert_eq!(redacted.server.client_keys[0].name, "Alice");
         assert_eq!(
             redacted.providers.openai.api_key,
             Some(REDACTED_PLACEHOLDER.to_string())
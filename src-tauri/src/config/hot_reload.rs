@@ -374,63 +374,7 @@ impl HotReloadManager {
 
     /// 验证配置
     fn validate_config(&self, config: &Config) -> Result<(), HotReloadError> {
-        let _is_localhost = is_localhost_host(&config.server.host);
-        let is_valid_host = is_valid_bind_host(&config.server.host);
-        let _is_non_local = is_non_local_bind(&config.server.host);
-
-        // 验证端口范围
-        if config.server.port == 0 {
-            return Err(HotReloadError::ValidationError(
-                "端口号不能为 0".to_string(),
-            ));
-        }
-
-        // 验证绑定地址
-        if !is_valid_host {
-            return Err(HotReloadError::ValidationError(
-                "无效的监听地址。允许的地址：127.0.0.1、localhost、::1、0.0.0.0、::".to_string(),
-            ));
-        }
-
-        // 验证重试配置
-        if config.retry.max_retries > 100 {
-            return Err(HotReloadError::ValidationError(
-                "最大重试次数不能超过 100".to_string(),
-            ));
-        }
-
-        if config.retry.base_delay_ms == 0 {
-            return Err(HotReloadError::ValidationError(
-                "基础延迟不能为 0".to_string(),
-            ));
-        }
-
-        // 验证日志保留天数
-        if config.logging.retention_days == 0 {
-            return Err(HotReloadError::ValidationError(
-                "日志保留天数不能为 0".to_string(),
-            ));
-        }
-
-        if config.server.api_key.trim().is_empty() {
-            return Err(HotReloadError::ValidationError(
-                "API Key 不能为空".to_string(),
-            ));
-        }
-
-        if config.server.tls.enable {
-            return Err(HotReloadError::ValidationError(
-                "当前版本暂不支持 TLS，请关闭 TLS 配置".to_string(),
-            ));
-        }
-
-        if config.remote_management.allow_remote {
-            return Err(HotReloadError::ValidationError(
-                "当前版本未启用 TLS，禁止开启远程管理".to_string(),
-            ));
-        }
-
-        Ok(())
+        validate_config(config)
     }
 
     /// 手动回滚到备份配置
@@ -470,6 +414,86 @@ impl HotReloadManager {
     }
 }
 
+/// 验证配置，热重载和自检共用
+pub fn validate_config(config: &Config) -> Result<(), HotReloadError> {
+    let _is_localhost = is_localhost_host(&config.server.host);
+    let is_valid_host = is_valid_bind_host(&config.server.host);
+    let _is_non_local = is_non_local_bind(&config.server.host);
+
+    // 验证端口范围
+    if config.server.port == 0 {
+        return Err(HotReloadError::ValidationError(
+            "端口号不能为 0".to_string(),
+        ));
+    }
+
+    // 验证绑定地址
+    if !is_valid_host {
+        return Err(HotReloadError::ValidationError(
+            "无效的监听地址。允许的地址：127.0.0.1、localhost、::1、0.0.0.0、::".to_string(),
+        ));
+    }
+
+    // 验证重试配置
+    if config.retry.max_retries > 100 {
+        return Err(HotReloadError::ValidationError(
+            "最大重试次数不能超过 100".to_string(),
+        ));
+    }
+
+    if config.retry.base_delay_ms == 0 {
+        return Err(HotReloadError::ValidationError(
+            "基础延迟不能为 0".to_string(),
+        ));
+    }
+
+    // 验证日志保留天数
+    if config.logging.retention_days == 0 {
+        return Err(HotReloadError::ValidationError(
+            "日志保留天数不能为 0".to_string(),
+        ));
+    }
+
+    if config.server.api_key.trim().is_empty() {
+        return Err(HotReloadError::ValidationError(
+            "API Key 不能为空".to_string(),
+        ));
+    }
+
+    for (i, client) in config.server.client_keys.iter().enumerate() {
+        if client.key.trim().is_empty() {
+            return Err(HotReloadError::ValidationError(format!(
+                "客户端密钥 {} 不能为空",
+                client.name
+            )));
+        }
+        if client.key == config.server.api_key
+            || config.server.client_keys[..i]
+                .iter()
+                .any(|c| c.key == client.key || c.id == client.id)
+        {
+            return Err(HotReloadError::ValidationError(format!(
+                "客户端密钥 {} 与其他密钥重复",
+                client.name
+            )));
+        }
+    }
+
+    if config.server.tls.enable {
+        return Err(HotReloadError::ValidationError(
+            "当前版本暂不支持 TLS，请关闭 TLS 配置".to_string(),
+        ));
+    }
+
+    if config.remote_management.allow_remote {
+        return Err(HotReloadError::ValidationError(
+            "当前版本未启用 TLS，禁止开启远程管理".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
 fn is_localhost_host(host: &str) -> bool {
     if host == "localhost" {
         return true;
@@ -503,6 +503,12 @@ impl ImportService {
             server_key_cleared = true;
         }
 
+        // 脱敏的客户端密钥无法使用，直接移除
+        config
+            .server
+            .client_keys
+            .retain(|c| c.key != REDACTED_PLACEHOLDER);
+
         server_key_cleared
     }
 
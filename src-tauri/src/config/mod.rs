@@ -11,60 +11,112 @@ mod import;
 pub mod observer;
 mod path_utils;
 mod types;
+pub mod version;
 mod yaml;
 
 pub use export::{ExportBundle, ExportOptions, ExportService, REDACTED_PLACEHOLDER};
 pub use hot_reload::{
-    ConfigChangeEvent as FileChangeEvent, ConfigChangeKind, FileWatcher, HotReloadManager,
-    ReloadResult,
+    validate_config, ConfigChangeEvent as FileChangeEvent, ConfigChangeKind, FileWatcher,
+    HotReloadManager, ReloadResult,
 };
 pub use import::{ImportOptions, ImportService, ValidationResult};
 pub use path_utils::{collapse_tilde, contains_tilde, expand_tilde};
 pub use types::{
     generate_secure_api_key,
+    AdmissionControlConfig,
     AmpConfig,
     AmpModelMapping,
+    AnomalyDetectionConfig,
     ApiKeyEntry,
     AsrCredentialEntry,
     // ASR 和语音输入相关类型
     AsrProviderType,
+    AsrRateLimitConfig,
+    AsrRetryConfig,
+    AsrTimeoutConfig,
+    AudioPrivacyConfig,
+    AzureAsrConfig,
+    AzureProfanityFilter,
     BaiduConfig,
+    ClientApiKey,
+    ClipboardHistoryConfig,
+    CommandAsrConfig,
     Config,
     CredentialEntry,
     CredentialPoolConfig,
     CustomProviderConfig,
+    DashScopeSettings,
+    CustomWhisperAsrConfig,
+    DeepgramAsrConfig,
+    DeviceCalibration,
+    DiarizationConfig,
     EndpointProvidersConfig,
     ExperimentalFeatures,
     GeminiApiKeyEntry,
+    HomophoneCorrection,
+    HttpAsrConfig,
+    HttpAsrUpload,
     InjectionRuleConfig,
     InjectionSettings,
+    InstructionAbTest,
+    LocalAsrEngine,
     LoggingConfig,
+    MockProviderSettings,
     ModelInfo,
     ModelsConfig,
     NativeAgentConfig,
+    NoSpeechConfig,
     OpenAIAsrConfig,
+    PipelineMetricsConfig,
+    PolishCostAction,
+    PolishCostLimit,
     ProviderConfig,
     ProviderModelsConfig,
     ProvidersConfig,
+    PunctuationMode,
+    QuietHoursConfig,
+    QuietHoursSchedule,
     QuotaExceededConfig,
+    ReasoningSettings,
+    RecognitionStrategy,
     RemoteManagementConfig,
+    ResponseBufferConfig,
     RetrySettings,
     RoutingConfig,
     ScreenshotChatConfig,
+    SenseVoicePrecision,
     ServerConfig,
+    SilenceTrimConfig,
+    SpokenPunctuationConfig,
+    SpokenPunctuationMapping,
+    StreamAggregationConfig,
+    StreamAggregationRoute,
+    TencentAsrConfig,
     TlsConfig,
+    ToolApprovalConfig,
+    TranscriptFormat,
+    UpstreamHttpConfig,
     VertexApiKeyEntry,
     VertexModelAlias,
+    VoiceHistoryConfig,
+    VoiceHistorySyncConfig,
     VoiceInputConfig,
     VoiceInstruction,
     VoiceOutputConfig,
     VoiceOutputMode,
     VoiceProcessorConfig,
+    VoiceWindowConfig,
+    VoiceWindowPosition,
+    VolcengineAsrConfig,
+    WatchFolderConfig,
     WhisperLocalConfig,
     WhisperModelSize,
+    WhisperTask,
     XunfeiConfig,
     DEFAULT_API_KEY,
+    DEFAULT_CALIBRATION_DEVICE,
 };
+pub use version::CONFIG_VERSION;
 pub use yaml::{load_config, save_config, ConfigError, ConfigManager, YamlService};
 
 // 重新导出观察者模块的核心类型
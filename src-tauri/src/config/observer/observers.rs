@@ -207,6 +207,10 @@ impl ConfigObserver for LoggingObserver {
             config.logging.level
         );
 
+        if let Err(e) = crate::logger::tracing_bridge::set_level(None, &config.logging.level) {
+            tracing::warn!("[LoggingObserver] 应用日志级别失败: {}", e);
+        }
+
         Ok(())
     }
 }
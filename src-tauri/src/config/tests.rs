@@ -38,6 +38,7 @@ fn arb_server_config() -> impl Strategy<Value = ServerConfig> {
         port,
         api_key,
         tls: crate::config::TlsConfig::default(),
+        client_keys: Vec::new(),
     })
 }
 
@@ -96,6 +97,7 @@ fn arb_providers_config() -> impl Strategy<Value = ProvidersConfig> {
             qwen,
             openai,
             claude,
+            dashscope: Default::default(),
         })
 }
 
@@ -156,6 +158,7 @@ fn arb_logging_config() -> impl Strategy<Value = LoggingConfig> {
                 level,
                 retention_days,
                 include_request_body,
+                max_file_size_mb: 10,
             },
         )
 }
@@ -170,6 +173,7 @@ fn arb_config() -> impl Strategy<Value = Config> {
         arb_logging_config(),
     )
         .prop_map(|(server, providers, routing, retry, logging)| Config {
+            config_version: crate::config::CONFIG_VERSION,
             server,
             providers,
             default_provider: routing.default_provider.clone(),
@@ -177,10 +181,18 @@ fn arb_config() -> impl Strategy<Value = Config> {
             retry,
             logging,
             injection: InjectionSettings::default(),
+            reasoning: crate::config::ReasoningSettings::default(),
+            mock: crate::config::MockProviderSettings::default(),
             auth_dir: "~/.proxycast/auth".to_string(),
             credential_pool: crate::config::CredentialPoolConfig::default(),
             remote_management: crate::config::RemoteManagementConfig::default(),
             quota_exceeded: crate::config::QuotaExceededConfig::default(),
+            anomaly_detection: crate::config::AnomalyDetectionConfig::default(),
+            admission_control: crate::config::AdmissionControlConfig::default(),
+            response_buffer: crate::config::ResponseBufferConfig::default(),
+            upstream_http: crate::config::UpstreamHttpConfig::default(),
+            pipeline_metrics: crate::config::PipelineMetricsConfig::default(),
+            stream_aggregation: crate::config::StreamAggregationConfig::default(),
             proxy_url: None,
             ampcode: crate::config::AmpConfig::default(),
             endpoint_providers: crate::config::EndpointProvidersConfig::default(),
@@ -354,6 +366,7 @@ fn arb_valid_server_config() -> impl Strategy<Value = ServerConfig> {
         port,
         api_key,
         tls: crate::config::TlsConfig::default(),
+        client_keys: Vec::new(),
     })
 }
 
@@ -394,6 +407,7 @@ fn arb_valid_logging_config() -> impl Strategy<Value = LoggingConfig> {
                 level,
                 retention_days,
                 include_request_body,
+                max_file_size_mb: 10,
             },
         )
 }
@@ -408,6 +422,7 @@ fn arb_valid_config() -> impl Strategy<Value = Config> {
         arb_valid_logging_config(),
     )
         .prop_map(|(server, providers, routing, retry, logging)| Config {
+            config_version: crate::config::CONFIG_VERSION,
             server,
             providers,
             default_provider: routing.default_provider.clone(),
@@ -415,10 +430,18 @@ fn arb_valid_config() -> impl Strategy<Value = Config> {
             retry,
             logging,
             injection: InjectionSettings::default(),
+            reasoning: crate::config::ReasoningSettings::default(),
+            mock: crate::config::MockProviderSettings::default(),
             auth_dir: "~/.proxycast/auth".to_string(),
             credential_pool: crate::config::CredentialPoolConfig::default(),
             remote_management: crate::config::RemoteManagementConfig::default(),
             quota_exceeded: crate::config::QuotaExceededConfig::default(),
+            anomaly_detection: crate::config::AnomalyDetectionConfig::default(),
+            admission_control: crate::config::AdmissionControlConfig::default(),
+            response_buffer: crate::config::ResponseBufferConfig::default(),
+            upstream_http: crate::config::UpstreamHttpConfig::default(),
+            pipeline_metrics: crate::config::PipelineMetricsConfig::default(),
+            stream_aggregation: crate::config::StreamAggregationConfig::default(),
             proxy_url: None,
             ampcode: crate::config::AmpConfig::default(),
             endpoint_providers: crate::config::EndpointProvidersConfig::default(),
@@ -457,6 +480,7 @@ fn arb_invalid_config() -> impl Strategy<Value = Config> {
         .prop_map(
             |(server, providers, routing, retry, logging, invalid_type)| {
                 let mut config = Config {
+                    config_version: crate::config::CONFIG_VERSION,
                     server,
                     providers,
                     default_provider: routing.default_provider.clone(),
@@ -464,10 +488,18 @@ fn arb_invalid_config() -> impl Strategy<Value = Config> {
                     retry,
                     logging,
                     injection: InjectionSettings::default(),
+                    reasoning: crate::config::ReasoningSettings::default(),
+                    mock: crate::config::MockProviderSettings::default(),
                     auth_dir: "~/.proxycast/auth".to_string(),
                     credential_pool: crate::config::CredentialPoolConfig::default(),
                     remote_management: crate::config::RemoteManagementConfig::default(),
                     quota_exceeded: crate::config::QuotaExceededConfig::default(),
+                    anomaly_detection: crate::config::AnomalyDetectionConfig::default(),
+                    admission_control: crate::config::AdmissionControlConfig::default(),
+                    response_buffer: crate::config::ResponseBufferConfig::default(),
+                    upstream_http: crate::config::UpstreamHttpConfig::default(),
+                    pipeline_metrics: crate::config::PipelineMetricsConfig::default(),
+                    stream_aggregation: crate::config::StreamAggregationConfig::default(),
                     proxy_url: None,
                     ampcode: crate::config::AmpConfig::default(),
                     endpoint_providers: crate::config::EndpointProvidersConfig::default(),
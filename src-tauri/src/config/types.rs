@@ -4,6 +4,7 @@
 //! 保持与旧版 JSON 配置的向后兼容性
 
 use crate::injection::{InjectionMode, InjectionRule};
+use proxycast_providers::{DashScopeMode, ReasoningPolicy};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -53,10 +54,24 @@ pub enum AsrProviderType {
     WhisperLocal,
     /// 讯飞语音识别
     Xunfei,
+    /// 火山引擎（豆包）流式语音识别
+    Volcengine,
     /// 百度语音识别
     Baidu,
+    /// 腾讯云一句话识别
+    Tencent,
+    /// Azure 语音服务
+    Azure,
+    /// Deepgram 预录音频识别
+    Deepgram,
     /// OpenAI Whisper API
     OpenAI,
+    /// 自建 Whisper 服务（兼容 OpenAI 转写接口）
+    CustomWhisper,
+    /// 自定义命令（外部识别引擎）
+    Command,
+    /// 通用 HTTP 接口（自建识别服务）
+    Http,
 }
 
 impl Default for AsrProviderType {
@@ -77,6 +92,8 @@ pub enum WhisperModelSize {
     Small,
     /// medium - 中型（~1.5GB）
     Medium,
+    /// large - 大型，最准确，中英混说效果最好（~3.1GB）
+    Large,
 }
 
 impl Default for WhisperModelSize {
@@ -85,6 +102,32 @@ impl Default for WhisperModelSize {
     }
 }
 
+/// 本地识别引擎
+///
+/// SenseVoice 需要 `sensevoice` 特性；未启用时配置中的其他引擎按 Whisper 处理
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalAsrEngine {
+    /// Whisper（whisper.cpp）
+    #[default]
+    #[cfg_attr(not(feature = "sensevoice"), serde(other))]
+    Whisper,
+    /// SenseVoice（FunASR，ONNX Runtime），中文识别效果更好
+    #[cfg(feature = "sensevoice")]
+    SenseVoice,
+}
+
+/// SenseVoice 模型精度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SenseVoicePrecision {
+    /// int8 量化（~230MB）
+    #[default]
+    Int8,
+    /// float32 原始精度（~900MB）
+    Float32,
+}
+
 /// ASR 凭证条目
 ///
 /// 用于语音识别服务的凭证管理
@@ -103,10 +146,15 @@ pub struct AsrCredentialEntry {
     /// 是否禁用
     #[serde(default)]
     pub disabled: bool,
+    /// 回退优先级（数字越小越先尝试，相同时按凭证池顺序）
+    ///
+    /// 所选凭证的服务连不上或鉴权失败时，按此顺序改用其他云端凭证
+    #[serde(default)]
+    pub priority: u32,
     /// 识别语言（如 "zh", "en", "auto"）
     #[serde(default = "default_asr_language")]
     pub language: String,
-    /// Whisper 本地配置（仅 WhisperLocal）
+    /// 本地识别配置（仅 WhisperLocal）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub whisper_config: Option<WhisperLocalConfig>,
     /// 讯飞配置（仅 Xunfei）
@@ -115,31 +163,98 @@ pub struct AsrCredentialEntry {
     /// 百度配置（仅 Baidu）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub baidu_config: Option<BaiduConfig>,
+    /// 腾讯云配置（仅 Tencent）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tencent_config: Option<TencentAsrConfig>,
+    /// Azure 配置（仅 Azure）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub azure_config: Option<AzureAsrConfig>,
+    /// Deepgram 配置（仅 Deepgram）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deepgram_config: Option<DeepgramAsrConfig>,
+    /// 火山引擎配置（仅 Volcengine）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volcengine_config: Option<VolcengineAsrConfig>,
     /// OpenAI 配置（仅 OpenAI）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub openai_config: Option<OpenAIAsrConfig>,
+    /// 自建 Whisper 配置（仅 CustomWhisper）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_whisper_config: Option<CustomWhisperAsrConfig>,
+    /// 自定义命令配置（仅 Command）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_config: Option<CommandAsrConfig>,
+    /// HTTP 接口配置（仅 Http）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_config: Option<HttpAsrConfig>,
+    /// 请求超时（本地 Whisper 不使用）
+    #[serde(default)]
+    pub timeouts: AsrTimeoutConfig,
+    /// 请求限流（本地 Whisper 不使用）
+    #[serde(default)]
+    pub rate_limit: AsrRateLimitConfig,
 }
 
 fn default_asr_language() -> String {
     "zh".to_string()
 }
 
-/// Whisper 本地配置
+/// ASR 请求超时（秒）
+///
+/// 未设置的项使用服务的默认值：连接 10 秒、发送 30 秒、接收 30 秒；
+/// 自建 Whisper、自定义命令和 HTTP 接口的接收超时默认取各自配置中的 `timeout_secs`，
+/// 讯飞录音文件转写的接收超时为等待转写完成的时间（默认 600 秒）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AsrTimeoutConfig {
+    /// 建立连接（TCP、TLS 和 WebSocket 握手）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_secs: Option<u64>,
+    /// 发送音频（WebSocket 服务按帧计时）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub send_secs: Option<u64>,
+    /// 音频发送完后等待识别结果
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receive_secs: Option<u64>,
+}
+
+/// ASR 请求限流
+///
+/// 同一凭证的请求超过每秒次数时排队等待，避免超出服务的 QPS 配额。
+/// 未设置时使用服务的默认值：百度每秒 2 次，其他服务不限流
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AsrRateLimitConfig {
+    /// 每秒最多请求次数（0 表示不限流）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_sec: Option<f64>,
+    /// 空闲后允许连续发出的请求数（默认 1）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
+}
+
+/// 本地识别配置
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct WhisperLocalConfig {
-    /// 模型大小
+    /// 本地识别引擎
+    #[serde(default)]
+    pub engine: LocalAsrEngine,
+    /// Whisper 模型大小
     #[serde(default)]
     pub model: WhisperModelSize,
     /// 模型文件路径（可选，默认自动下载）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model_path: Option<String>,
+    /// SenseVoice 模型精度（仅 SenseVoice 引擎）
+    #[serde(default)]
+    pub sensevoice_model: SenseVoicePrecision,
 }
 
 impl Default for WhisperLocalConfig {
     fn default() -> Self {
         Self {
+            engine: LocalAsrEngine::default(),
             model: WhisperModelSize::default(),
             model_path: None,
+            sensevoice_model: SenseVoicePrecision::default(),
         }
     }
 }
@@ -153,6 +268,9 @@ pub struct XunfeiConfig {
     pub api_key: String,
     /// API Secret
     pub api_secret: String,
+    /// 录音文件转写 SecretKey（可选，配置后超过 60 秒的录音使用录音文件转写）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lfasr_secret_key: Option<String>,
 }
 
 /// 百度语音配置
@@ -164,6 +282,74 @@ pub struct BaiduConfig {
     pub secret_key: String,
 }
 
+/// 腾讯云语音配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TencentAsrConfig {
+    /// SecretId
+    pub secret_id: String,
+    /// SecretKey
+    pub secret_key: String,
+    /// 地域（可选，如 ap-shanghai）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+}
+
+/// Azure 语音识别的脏话过滤方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AzureProfanityFilter {
+    /// 用星号替换
+    #[default]
+    Masked,
+    /// 从结果中删除
+    Removed,
+    /// 保留原文
+    Raw,
+}
+
+/// Azure 语音服务配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AzureAsrConfig {
+    /// 订阅密钥
+    pub subscription_key: String,
+    /// 资源所在地域（如 eastasia）
+    pub region: String,
+    /// 识别语言（如 zh-CN、en-US），为空时按语音输入的语言设置推断
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// 脏话过滤方式
+    #[serde(default)]
+    pub profanity: AzureProfanityFilter,
+    /// 区分说话人（改用快速转写接口）
+    #[serde(default)]
+    pub diarize: bool,
+}
+
+/// Deepgram 配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeepgramAsrConfig {
+    /// API Key
+    pub api_key: String,
+    /// 模型（可选，如 nova-2、nova-2-meeting，默认 nova-2）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// 区分说话人
+    #[serde(default)]
+    pub diarize: bool,
+}
+
+/// 火山引擎语音识别配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VolcengineAsrConfig {
+    /// 应用 APPID
+    pub app_id: String,
+    /// Access Token
+    pub access_token: String,
+    /// 集群（可选，默认 volcengine_streaming_common）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cluster: Option<String>,
+}
+
 /// OpenAI ASR 配置
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct OpenAIAsrConfig {
@@ -177,6 +363,94 @@ pub struct OpenAIAsrConfig {
     pub proxy_url: Option<String>,
 }
 
+/// 自建 Whisper 服务配置
+///
+/// 录音上传到 `{base_url}/v1/audio/transcriptions`（兼容 OpenAI 的转写接口）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomWhisperAsrConfig {
+    /// 服务地址（如 http://192.168.1.10:8000）
+    pub base_url: String,
+    /// API Key（可选，服务开启鉴权时填写）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// 模型名（可选，如 Systran/faster-whisper-large-v3）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// 超时时间（秒）
+    #[serde(default = "default_custom_whisper_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_custom_whisper_timeout_secs() -> u64 {
+    120
+}
+
+/// 自定义命令 ASR 配置
+///
+/// 录音以 WAV 格式写入命令的标准输入，命令从标准输出返回 `{"text": ...}` 格式的 JSON
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommandAsrConfig {
+    /// 可执行文件路径
+    pub program: String,
+    /// 参数，支持 `{language}`（识别语言）和 `{file}`（临时 WAV 文件路径）占位符
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// 超时时间（秒）
+    #[serde(default = "default_command_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_command_timeout_secs() -> u64 {
+    60
+}
+
+/// HTTP 接口上传录音的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HttpAsrUpload {
+    /// 请求体直接为 WAV
+    #[default]
+    Raw,
+    /// multipart 表单的文件字段
+    Multipart,
+}
+
+/// 通用 HTTP ASR 配置
+///
+/// 录音以 WAV 格式 POST 到 `url`，按 `text_path` 从响应 JSON 中取出识别文本
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HttpAsrConfig {
+    /// 接口地址，支持 `{language}` 占位符
+    pub url: String,
+    /// 额外的请求头（如 `Authorization`）
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    /// 识别文本在响应中的 JSONPath，如 `$.result[0].text`
+    #[serde(default = "default_http_text_path")]
+    pub text_path: String,
+    /// 上传方式
+    #[serde(default)]
+    pub upload: HttpAsrUpload,
+    /// multipart 上传时的文件字段名
+    #[serde(default = "default_http_file_field")]
+    pub file_field: String,
+    /// 超时时间（秒）
+    #[serde(default = "default_http_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_http_text_path() -> String {
+    "$.text".to_string()
+}
+
+fn default_http_file_field() -> String {
+    "file".to_string()
+}
+
+fn default_http_timeout_secs() -> u64 {
+    60
+}
+
 /// Gemini API Key 凭证条目
 ///
 /// 用于 Gemini API Key 多账号负载均衡
@@ -376,6 +650,9 @@ impl EndpointProvidersConfig {
 /// - 新版 YAML 格式：`default_provider` 在 `routing` 中
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Config {
+    /// 配置版本（写入该配置的程序支持的版本，见 [`super::version`]）
+    #[serde(default = "default_config_version")]
+    pub config_version: u32,
     /// 服务器配置
     #[serde(default)]
     pub server: ServerConfig,
@@ -391,6 +668,12 @@ pub struct Config {
     /// 重试配置
     #[serde(default)]
     pub retry: RetrySettings,
+    /// 推理内容配置
+    #[serde(default)]
+    pub reasoning: ReasoningSettings,
+    /// 本地 Mock Provider 配置
+    #[serde(default)]
+    pub mock: MockProviderSettings,
     /// 日志配置
     #[serde(default)]
     pub logging: LoggingConfig,
@@ -409,6 +692,24 @@ pub struct Config {
     /// 配额超限配置
     #[serde(default)]
     pub quota_exceeded: QuotaExceededConfig,
+    /// 上游响应异常检测配置
+    #[serde(default)]
+    pub anomaly_detection: AnomalyDetectionConfig,
+    /// 上游并发准入控制配置
+    #[serde(default)]
+    pub admission_control: AdmissionControlConfig,
+    /// 响应缓冲内存上限配置
+    #[serde(default)]
+    pub response_buffer: ResponseBufferConfig,
+    /// 上游 HTTP 客户端配置（连接池、keepalive、域名解析覆盖）
+    #[serde(default)]
+    pub upstream_http: UpstreamHttpConfig,
+    /// 管道开销统计与昂贵遥测的采样配置
+    #[serde(default)]
+    pub pipeline_metrics: PipelineMetricsConfig,
+    /// 流式响应合并配置（按路由把细碎的 SSE chunk 攒成批再转发）
+    #[serde(default)]
+    pub stream_aggregation: StreamAggregationConfig,
     /// 全局代理 URL
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub proxy_url: Option<String>,
@@ -465,6 +766,9 @@ pub struct NativeAgentConfig {
     /// 默认最大 token 数
     #[serde(default = "default_max_tokens")]
     pub max_tokens: u32,
+    /// 危险工具调用审批策略
+    #[serde(default)]
+    pub approval: ToolApprovalConfig,
 }
 
 fn default_use_default_prompt() -> bool {
@@ -492,6 +796,41 @@ impl Default for NativeAgentConfig {
             default_model: default_agent_model(),
             temperature: default_temperature(),
             max_tokens: default_max_tokens(),
+            approval: ToolApprovalConfig::default(),
+        }
+    }
+}
+
+/// 工具调用审批策略配置
+///
+/// 命中以下类别的工具调用需要用户确认（工作区允许/拒绝列表优先）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ToolApprovalConfig {
+    /// 是否启用审批策略，关闭时所有工具调用自动放行
+    #[serde(default = "default_approval_enabled")]
+    pub enabled: bool,
+    /// 执行 Shell 命令需要确认
+    #[serde(default = "default_approval_enabled")]
+    pub shell_commands: bool,
+    /// 写入工作区之外的文件需要确认
+    #[serde(default = "default_approval_enabled")]
+    pub file_writes_outside_workspace: bool,
+    /// 网络访问需要确认
+    #[serde(default = "default_approval_enabled")]
+    pub network_access: bool,
+}
+
+fn default_approval_enabled() -> bool {
+    true
+}
+
+impl Default for ToolApprovalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            shell_commands: true,
+            file_writes_outside_workspace: true,
+            network_access: true,
         }
     }
 }
@@ -618,6 +957,274 @@ pub struct VoiceInputConfig {
     /// 翻译模式使用的指令 ID
     #[serde(default = "default_translate_instruction_id")]
     pub translate_instruction_id: String,
+    /// 悬浮窗口位置配置
+    #[serde(default)]
+    pub window: VoiceWindowConfig,
+    /// 自动转写的监听文件夹
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watch_folders: Vec<WatchFolderConfig>,
+    /// 剪贴板历史配置
+    #[serde(default)]
+    pub clipboard_history: ClipboardHistoryConfig,
+    /// 识别前的首尾静音裁剪配置
+    #[serde(default)]
+    pub silence_trim: SilenceTrimConfig,
+    /// 各麦克风设备的电平校准结果（键为设备 ID，系统默认设备为 `default`）
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub calibrations: HashMap<String, DeviceCalibration>,
+    /// 免打扰时段配置
+    #[serde(default)]
+    pub quiet_hours: QuietHoursConfig,
+    /// 开头持续静音时自动取消录音的配置
+    #[serde(default)]
+    pub no_speech: NoSpeechConfig,
+    /// 听写历史配置
+    #[serde(default)]
+    pub history: VoiceHistoryConfig,
+    /// 多个云端识别服务的使用方式
+    #[serde(default)]
+    pub recognition_strategy: RecognitionStrategy,
+    /// 云端识别遇到网络错误时的重试配置
+    #[serde(default)]
+    pub asr_retry: AsrRetryConfig,
+    /// 本地说话人区分（转写录音文件时使用）
+    #[serde(default)]
+    pub diarization: DiarizationConfig,
+    /// 音频隐私设置
+    #[serde(default)]
+    pub privacy: AudioPrivacyConfig,
+    /// 本地 Whisper 的识别任务（转写原文或直接译为英文）
+    #[serde(default)]
+    pub whisper_task: WhisperTask,
+    /// 口述标点（把识别结果中的「逗号」「换行」等口令替换为对应字符）
+    #[serde(default)]
+    pub spoken_punctuation: SpokenPunctuationConfig,
+}
+
+/// 本地 Whisper 的识别任务
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WhisperTask {
+    /// 按原语言转写
+    #[default]
+    Transcribe,
+    /// 把语音直接译为英文文本
+    Translate,
+}
+
+/// 口述标点配置
+///
+/// 部分识别服务（以及本地 Whisper）加标点不可靠，用户可以直接说出标点。开启后识别结果中的
+/// 口令（如「逗号」「new line」）替换为对应字符，口令表可编辑
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpokenPunctuationConfig {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 口令和替换成的字符
+    #[serde(default = "default_spoken_punctuation_mappings")]
+    pub mappings: Vec<SpokenPunctuationMapping>,
+}
+
+/// 一条口述标点口令
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpokenPunctuationMapping {
+    /// 说出的口令（英文不区分大小写）
+    pub spoken: String,
+    /// 替换成的字符，可以是换行
+    pub symbol: String,
+}
+
+fn default_spoken_punctuation_mappings() -> Vec<SpokenPunctuationMapping> {
+    [
+        ("逗号", "，"),
+        ("句号", "。"),
+        ("问号", "？"),
+        ("感叹号", "！"),
+        ("冒号", "："),
+        ("分号", "；"),
+        ("顿号", "、"),
+        ("省略号", "……"),
+        ("左引号", "“"),
+        ("右引号", "”"),
+        ("左括号", "（"),
+        ("右括号", "）"),
+        ("换行", "\n"),
+        ("另起一段", "\n\n"),
+        ("comma", ","),
+        ("period", "."),
+        ("full stop", "."),
+        ("question mark", "?"),
+        ("exclamation mark", "!"),
+        ("colon", ":"),
+        ("semicolon", ";"),
+        ("new line", "\n"),
+        ("new paragraph", "\n\n"),
+    ]
+    .into_iter()
+    .map(|(spoken, symbol)| SpokenPunctuationMapping {
+        spoken: spoken.to_string(),
+        symbol: symbol.to_string(),
+    })
+    .collect()
+}
+
+impl Default for SpokenPunctuationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mappings: default_spoken_punctuation_mappings(),
+        }
+    }
+}
+
+/// 多个云端识别服务的使用方式
+///
+/// 参与的服务为所选凭证和其他启用的云端凭证（按回退优先级排列）
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RecognitionStrategy {
+    /// 依次尝试：所选服务连不上或鉴权失败时改用下一个
+    #[default]
+    Fallback,
+    /// 竞速：同时发给所有服务，采用最先成功返回的结果，其余请求取消
+    Race,
+}
+
+/// 云端识别重试配置
+///
+/// 连不上服务、WebSocket 握手中断等网络错误时，用同一段录音按指数退避重试，
+/// 仍然失败再按识别策略回退到其他服务；鉴权失败等其他错误不重试
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AsrRetryConfig {
+    /// 每个服务最多尝试次数（含首次请求），1 表示不重试
+    #[serde(default = "default_asr_retry_max_attempts")]
+    pub max_attempts: u32,
+    /// 首次重试前的等待时间（毫秒），之后每次翻倍
+    #[serde(default = "default_asr_retry_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    /// 单次等待的上限（毫秒）
+    #[serde(default = "default_asr_retry_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// 等待时间的随机抖动比例（0-1）
+    #[serde(default = "default_asr_retry_jitter")]
+    pub jitter: f64,
+}
+
+fn default_asr_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_asr_retry_initial_backoff_ms() -> u64 {
+    500
+}
+
+fn default_asr_retry_max_backoff_ms() -> u64 {
+    5000
+}
+
+fn default_asr_retry_jitter() -> f64 {
+    0.2
+}
+
+impl Default for AsrRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_asr_retry_max_attempts(),
+            initial_backoff_ms: default_asr_retry_initial_backoff_ms(),
+            max_backoff_ms: default_asr_retry_max_backoff_ms(),
+            jitter: default_asr_retry_jitter(),
+        }
+    }
+}
+
+/// 本地说话人区分配置（实验性）
+///
+/// 转写会议录音等文件时，识别服务未返回说话人的分段按声纹特征聚类标注说话人
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DiarizationConfig {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 最多区分的说话人数
+    #[serde(default = "default_diarization_max_speakers")]
+    pub max_speakers: usize,
+    /// 视为同一说话人的相似度阈值（0-1），越高越容易区分出更多说话人
+    #[serde(default = "default_diarization_threshold")]
+    pub threshold: f32,
+}
+
+fn default_diarization_max_speakers() -> usize {
+    4
+}
+
+fn default_diarization_threshold() -> f32 {
+    0.7
+}
+
+impl Default for DiarizationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_speakers: default_diarization_max_speakers(),
+            threshold: default_diarization_threshold(),
+        }
+    }
+}
+
+/// 音频隐私配置
+///
+/// 由 voice-core 统一执行：禁止上传时只能使用本地识别，禁止写盘时不保存录音
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AudioPrivacyConfig {
+    /// 是否允许原始音频发送到云端识别服务，关闭后强制使用本地 Whisper
+    #[serde(default = "default_privacy_allow_cloud")]
+    pub allow_cloud: bool,
+    /// 是否允许把音频写入磁盘（听写历史同步录音、命令识别的临时文件）
+    #[serde(default = "default_privacy_allow_disk")]
+    pub allow_disk: bool,
+    /// 临时音频文件保留时长（小时），超过后自动删除，0 表示不自动清理
+    #[serde(default)]
+    pub temp_audio_retention_hours: u32,
+}
+
+fn default_privacy_allow_cloud() -> bool {
+    true
+}
+
+fn default_privacy_allow_disk() -> bool {
+    true
+}
+
+impl Default for AudioPrivacyConfig {
+    fn default() -> Self {
+        Self {
+            allow_cloud: default_privacy_allow_cloud(),
+            allow_disk: default_privacy_allow_disk(),
+            temp_audio_retention_hours: 0,
+        }
+    }
+}
+
+/// 未选择设备时的校准键
+pub const DEFAULT_CALIBRATION_DEVICE: &str = "default";
+
+impl VoiceInputConfig {
+    /// 获取设备的校准结果（设备 ID 为空时使用系统默认设备）
+    pub fn calibration_for(&self, device_id: Option<&str>) -> Option<&DeviceCalibration> {
+        self.calibrations
+            .get(device_id.unwrap_or(DEFAULT_CALIBRATION_DEVICE))
+    }
+
+    /// 设备的静音阈值（dBFS）
+    ///
+    /// 优先使用静音裁剪配置的阈值，其次是设备的校准阈值，都没有时为 `None`
+    pub fn silence_threshold_dbfs(&self, device_id: Option<&str>) -> Option<f32> {
+        self.silence_trim.threshold_dbfs.or_else(|| {
+            self.calibration_for(device_id)
+                .map(|c| c.silence_threshold_dbfs)
+        })
+    }
 }
 
 fn default_voice_shortcut() -> String {
@@ -628,26 +1235,365 @@ fn default_sound_enabled() -> bool {
     true
 }
 
-fn default_translate_instruction_id() -> String {
-    "translate_en".to_string()
+fn default_translate_instruction_id() -> String {
+    "translate_en".to_string()
+}
+
+impl Default for VoiceInputConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            shortcut: default_voice_shortcut(),
+            processor: VoiceProcessorConfig::default(),
+            output: VoiceOutputConfig::default(),
+            instructions: default_instructions(),
+            selected_device_id: None,
+            sound_enabled: default_sound_enabled(),
+            translate_shortcut: None,
+            translate_instruction_id: default_translate_instruction_id(),
+            window: VoiceWindowConfig::default(),
+            watch_folders: Vec::new(),
+            clipboard_history: ClipboardHistoryConfig::default(),
+            silence_trim: SilenceTrimConfig::default(),
+            calibrations: HashMap::new(),
+            quiet_hours: QuietHoursConfig::default(),
+            no_speech: NoSpeechConfig::default(),
+            history: VoiceHistoryConfig::default(),
+            recognition_strategy: RecognitionStrategy::default(),
+            asr_retry: AsrRetryConfig::default(),
+            diarization: DiarizationConfig::default(),
+            privacy: AudioPrivacyConfig::default(),
+            whisper_task: WhisperTask::default(),
+            spoken_punctuation: SpokenPunctuationConfig::default(),
+        }
+    }
+}
+
+/// 首尾静音裁剪配置
+///
+/// 识别前去掉录音开头和结尾的静音，减少云端计费时长并加快本地识别
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SilenceTrimConfig {
+    /// 是否启用（默认启用）
+    #[serde(default = "default_silence_trim_enabled")]
+    pub enabled: bool,
+    /// 静音阈值（dBFS），为空时使用当前设备的校准阈值，未校准则为 -50
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold_dbfs: Option<f32>,
+    /// 两端保留的余量（毫秒）
+    #[serde(default = "default_silence_trim_padding_ms")]
+    pub padding_ms: u32,
+}
+
+fn default_silence_trim_enabled() -> bool {
+    true
+}
+
+fn default_silence_trim_padding_ms() -> u32 {
+    200
+}
+
+impl Default for SilenceTrimConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_silence_trim_enabled(),
+            threshold_dbfs: None,
+            padding_ms: default_silence_trim_padding_ms(),
+        }
+    }
+}
+
+/// 开头静音自动取消配置
+///
+/// 打开录音后一直没有说话（例如误触后离开），持续静音超过设定时长即自动取消，
+/// 避免录满时长后白白调用一次识别
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NoSpeechConfig {
+    /// 是否启用（默认启用）
+    #[serde(default = "default_no_speech_enabled")]
+    pub enabled: bool,
+    /// 开始录音后持续静音多少秒自动取消
+    #[serde(default = "default_no_speech_timeout_secs")]
+    pub timeout_secs: f32,
+}
+
+fn default_no_speech_enabled() -> bool {
+    true
+}
+
+fn default_no_speech_timeout_secs() -> f32 {
+    8.0
+}
+
+impl Default for NoSpeechConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_no_speech_enabled(),
+            timeout_secs: default_no_speech_timeout_secs(),
+        }
+    }
+}
+
+/// 免打扰时段配置
+///
+/// 处于任一时段内时，按开关关闭音效、改为复制到剪贴板输出、不弹出通知
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuietHoursConfig {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 免打扰时段列表
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedules: Vec<QuietHoursSchedule>,
+    /// 免打扰时关闭交互音效
+    #[serde(default = "default_quiet_hours_mute_sounds")]
+    pub mute_sounds: bool,
+    /// 免打扰时只复制到剪贴板，不模拟键盘输入
+    #[serde(default = "default_quiet_hours_clipboard_output")]
+    pub clipboard_output: bool,
+}
+
+fn default_quiet_hours_mute_sounds() -> bool {
+    true
+}
+
+fn default_quiet_hours_clipboard_output() -> bool {
+    true
+}
+
+impl Default for QuietHoursConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            schedules: Vec::new(),
+            mute_sounds: default_quiet_hours_mute_sounds(),
+            clipboard_output: default_quiet_hours_clipboard_output(),
+        }
+    }
+}
+
+/// 免打扰时段
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuietHoursSchedule {
+    /// 开始时间（本地时间 HH:MM）
+    pub start: String,
+    /// 结束时间（本地时间 HH:MM），早于开始时间表示跨越午夜
+    pub end: String,
+    /// 生效的星期（0 为周日，6 为周六），为空表示每天；跨越午夜时按开始当天计算
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub weekdays: Vec<u8>,
+}
+
+/// 麦克风电平校准结果
+///
+/// 由校准向导录制环境噪声和说话声音后计算得出
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeviceCalibration {
+    /// 建议增益（dB），录音结束后应用
+    pub gain_db: f32,
+    /// 静音阈值（dBFS，已计入增益），低于此值视为静音
+    pub silence_threshold_dbfs: f32,
+    /// 环境噪声底（dBFS）
+    pub noise_floor_dbfs: f32,
+    /// 说话电平（dBFS）
+    pub speech_level_dbfs: f32,
+    /// 校准时间（毫秒时间戳）
+    pub calibrated_at: i64,
+}
+
+/// 语音悬浮窗口配置
+///
+/// 记录用户手动拖动后的窗口位置，按显示器布局分别保存；
+/// 另可在每个显示器上显示独立于悬浮窗的录音指示
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VoiceWindowConfig {
+    /// 是否记住手动拖动后的窗口位置（关闭时每次居中）
+    #[serde(default = "default_remember_window_position")]
+    pub remember_position: bool,
+    /// 是否跟随焦点应用所在的显示器
+    #[serde(default)]
+    pub follow_active_monitor: bool,
+    /// 已保存的窗口位置（key 为显示器布局签名）
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub saved_positions: HashMap<String, VoiceWindowPosition>,
+    /// 麦克风录音时是否在每个显示器右上角显示红点指示（默认关闭）
+    #[serde(default)]
+    pub recording_indicator: bool,
+}
+
+fn default_remember_window_position() -> bool {
+    true
+}
+
+impl Default for VoiceWindowConfig {
+    fn default() -> Self {
+        Self {
+            remember_position: default_remember_window_position(),
+            follow_active_monitor: false,
+            saved_positions: HashMap::new(),
+            recording_indicator: false,
+        }
+    }
+}
+
+/// 语音悬浮窗口位置
+///
+/// 使用相对显示器左上角的偏移（物理像素），以便在同尺寸显示器间迁移
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VoiceWindowPosition {
+    /// 窗口所在显示器标识
+    pub monitor: String,
+    /// 相对显示器左上角的 X 偏移
+    pub offset_x: i32,
+    /// 相对显示器左上角的 Y 偏移
+    pub offset_y: i32,
+}
+
+/// 监听文件夹配置
+///
+/// 文件夹中出现新的音频文件时（如手机录音经 iCloud/坚果云同步），自动转写并导出
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WatchFolderConfig {
+    /// 唯一标识
+    pub id: String,
+    /// 监听的文件夹路径
+    pub path: String,
+    /// 是否启用
+    #[serde(default = "default_watch_folder_enabled")]
+    pub enabled: bool,
+    /// 是否包含子文件夹
+    #[serde(default)]
+    pub recursive: bool,
+    /// 使用的 ASR 凭证 ID（为空使用默认凭证）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_id: Option<String>,
+    /// 转写后应用的指令 ID（为空不润色）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instruction_id: Option<String>,
+    /// 转写结果导出目录（为空保存在音频旁边）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_dir: Option<String>,
+    /// 转写结果格式
+    #[serde(default)]
+    pub output_format: TranscriptFormat,
+}
+
+fn default_watch_folder_enabled() -> bool {
+    true
+}
+
+/// 剪贴板历史配置
+///
+/// 开启后记录最近复制的文本（加密保存），指令模板可通过 `{{clipboard_history[n]}}` 引用
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClipboardHistoryConfig {
+    /// 是否启用（默认关闭）
+    #[serde(default)]
+    pub enabled: bool,
+    /// 最多保留的条数
+    #[serde(default = "default_clipboard_history_max_entries")]
+    pub max_entries: usize,
+    /// 单条记录的最大字符数，超出部分截断
+    #[serde(default = "default_clipboard_history_max_chars")]
+    pub max_entry_chars: usize,
+}
+
+fn default_clipboard_history_max_entries() -> usize {
+    20
+}
+
+fn default_clipboard_history_max_chars() -> usize {
+    4000
+}
+
+impl Default for ClipboardHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: default_clipboard_history_max_entries(),
+            max_entry_chars: default_clipboard_history_max_chars(),
+        }
+    }
+}
+
+/// 听写历史配置
+///
+/// 保存每次听写的原始识别文本和润色结果，可换用其他指令或模型重新润色
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VoiceHistoryConfig {
+    /// 是否启用（默认启用）
+    #[serde(default = "default_voice_history_enabled")]
+    pub enabled: bool,
+    /// 最多保留的听写条数（重新润色生成的版本随原记录一起清理）
+    #[serde(default = "default_voice_history_max_entries")]
+    pub max_entries: usize,
+    /// 同步到工作区文件夹的配置
+    #[serde(default)]
+    pub workspace_sync: VoiceHistorySyncConfig,
+}
+
+fn default_voice_history_enabled() -> bool {
+    true
+}
+
+fn default_voice_history_max_entries() -> usize {
+    200
+}
+
+impl Default for VoiceHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_voice_history_enabled(),
+            max_entries: default_voice_history_max_entries(),
+            workspace_sync: VoiceHistorySyncConfig::default(),
+        }
+    }
+}
+
+/// 听写历史同步到工作区的配置
+///
+/// 每次听写按日期追加到默认工作区内文件夹的 Markdown 文件（如 `dictations/2026-01-01.md`），
+/// 听写内容成为项目的一部分，可被工作区搜索到
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VoiceHistorySyncConfig {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 工作区内的文件夹（相对工作区根目录）
+    #[serde(default = "default_voice_history_sync_folder")]
+    pub folder: String,
+    /// 是否同时保存录音（WAV，保存在文件夹的 `audio/` 子目录）
+    #[serde(default)]
+    pub include_audio: bool,
+}
+
+fn default_voice_history_sync_folder() -> String {
+    "dictations".to_string()
 }
 
-impl Default for VoiceInputConfig {
+impl Default for VoiceHistorySyncConfig {
     fn default() -> Self {
         Self {
             enabled: false,
-            shortcut: default_voice_shortcut(),
-            processor: VoiceProcessorConfig::default(),
-            output: VoiceOutputConfig::default(),
-            instructions: default_instructions(),
-            selected_device_id: None,
-            sound_enabled: default_sound_enabled(),
-            translate_shortcut: None,
-            translate_instruction_id: default_translate_instruction_id(),
+            folder: default_voice_history_sync_folder(),
+            include_audio: false,
         }
     }
 }
 
+/// 转写结果文件格式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    /// 纯文本
+    #[default]
+    Txt,
+    /// Markdown（含来源和时间）
+    Markdown,
+    /// JSON（含来源、时间和原始文本）
+    Json,
+}
+
 /// 语音处理配置
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct VoiceProcessorConfig {
@@ -663,6 +1609,123 @@ pub struct VoiceProcessorConfig {
     /// 默认指令 ID
     #[serde(default = "default_instruction_id")]
     pub default_instruction_id: String,
+    /// 按识别出的语言选择指令（键为语言代码如 `en`，值为指令 ID）
+    ///
+    /// 未配置的语言使用默认指令
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub language_instructions: HashMap<String, String>,
+    /// 识别结果没有标点时的补全方式
+    #[serde(default)]
+    pub punctuation: PunctuationMode,
+    /// 单次润色的费用上限
+    #[serde(default)]
+    pub cost_limit: PolishCostLimit,
+    /// 润色指令 A/B 测试
+    #[serde(default)]
+    pub ab_test: InstructionAbTest,
+    /// 同音词纠正
+    #[serde(default)]
+    pub homophone: HomophoneCorrection,
+}
+
+/// 同音词纠正
+///
+/// 识别结果中与用户词条拼音相同（不计声调）、字不同的片段替换为词条，在补全标点和润色之前进行
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct HomophoneCorrection {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 用户词条（如 `账号`、人名、产品名），至少两个汉字
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub terms: Vec<String>,
+    /// 模糊音：不区分平翘舌、前后鼻音和 n/l
+    #[serde(default)]
+    pub fuzzy: bool,
+}
+
+/// 润色指令 A/B 测试
+///
+/// 启用后按识别语言自动选择指令的润色在两个变体指令之间轮换，
+/// 用户在悬浮窗中评价润色结果，按变体汇总好评率
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct InstructionAbTest {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 变体 A 的指令 ID
+    #[serde(default)]
+    pub variant_a: String,
+    /// 变体 B 的指令 ID
+    #[serde(default)]
+    pub variant_b: String,
+}
+
+impl InstructionAbTest {
+    /// 已启用且配置了两个不同的变体
+    pub fn is_active(&self) -> bool {
+        self.enabled
+            && !self.variant_a.is_empty()
+            && !self.variant_b.is_empty()
+            && self.variant_a != self.variant_b
+    }
+}
+
+/// 标点恢复方式
+///
+/// 只在识别结果完全没有标点时生效
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PunctuationMode {
+    /// 不处理
+    #[default]
+    Off,
+    /// 按规则补全（停顿、连接词、疑问词）
+    Rules,
+    /// 调用一次润色模型只添加标点，失败时按规则补全
+    Llm,
+}
+
+/// 润色费用上限
+///
+/// 润色前按 Token 数和模型注册表中的价格预估费用，超出上限时按 `action` 处理。
+/// 模型没有价格信息时不做限制
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PolishCostLimit {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 单次润色的费用上限（模型定价的货币单位，通常为美元）
+    #[serde(default = "default_polish_max_cost")]
+    pub max_cost: f64,
+    /// 超出上限时的处理方式
+    #[serde(default)]
+    pub action: PolishCostAction,
+}
+
+fn default_polish_max_cost() -> f64 {
+    0.01
+}
+
+impl Default for PolishCostLimit {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_cost: default_polish_max_cost(),
+            action: PolishCostAction::default(),
+        }
+    }
+}
+
+/// 润色费用超出上限时的处理方式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PolishCostAction {
+    /// 不润色，直接输出原始文本
+    #[default]
+    Raw,
+    /// 显示预估费用，由用户确认后再润色
+    Confirm,
 }
 
 fn default_polish_enabled() -> bool {
@@ -680,30 +1743,56 @@ impl Default for VoiceProcessorConfig {
             polish_provider: None,
             polish_model: None,
             default_instruction_id: default_instruction_id(),
+            language_instructions: HashMap::new(),
+            punctuation: PunctuationMode::default(),
+            cost_limit: PolishCostLimit::default(),
+            ab_test: InstructionAbTest::default(),
+            homophone: HomophoneCorrection::default(),
         }
     }
 }
 
+impl VoiceProcessorConfig {
+    /// 识别出的语言对应的指令 ID，未配置该语言时使用默认指令
+    pub fn instruction_for_language(&self, language: Option<&str>) -> &str {
+        language
+            .and_then(|l| self.language_instructions.get(l))
+            .unwrap_or(&self.default_instruction_id)
+    }
+}
+
 /// 语音输出配置
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct VoiceOutputConfig {
     /// 输出模式
     #[serde(default)]
     pub mode: VoiceOutputMode,
-    /// 输入延迟（毫秒），用于模拟键盘输入
+    /// 输入延迟（毫秒），模拟键盘输入时每段文字之间的间隔
     #[serde(default = "default_type_delay_ms")]
     pub type_delay_ms: u32,
+    /// 输出前先在悬浮窗中确认（可编辑、重试）
+    #[serde(default)]
+    pub review_before_output: bool,
+    /// 模拟键盘输入时焦点离开听写开始时的应用则暂停
+    #[serde(default = "default_pause_on_focus_change")]
+    pub pause_on_focus_change: bool,
 }
 
 fn default_type_delay_ms() -> u32 {
     10
 }
 
+fn default_pause_on_focus_change() -> bool {
+    true
+}
+
 impl Default for VoiceOutputConfig {
     fn default() -> Self {
         Self {
             mode: VoiceOutputMode::default(),
             type_delay_ms: default_type_delay_ms(),
+            review_before_output: false,
+            pause_on_focus_change: default_pause_on_focus_change(),
         }
     }
 }
@@ -749,6 +1838,19 @@ pub struct VoiceInstruction {
     /// 图标（可选，用于 UI 显示）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
+    /// 润色结果中的按键标记（如 `{Enter}`）是否作为输出动作执行
+    #[serde(default)]
+    pub actions: bool,
+    /// 允许执行的按键（如 `Enter`、`Ctrl+Enter`），润色结果中的其他按键不执行
+    #[serde(default = "default_instruction_allowed_keys")]
+    pub allowed_keys: Vec<String>,
+    /// 输出前是否把数字、日期、金额的读法改为书面写法（「三点五个百分点」改为「3.5个百分点」）
+    #[serde(default)]
+    pub itn: bool,
+}
+
+fn default_instruction_allowed_keys() -> Vec<String> {
+    vec!["Enter".to_string(), "Tab".to_string()]
 }
 
 /// 默认指令列表
@@ -762,6 +1864,9 @@ fn default_instructions() -> Vec<VoiceInstruction> {
             shortcut: None,
             is_preset: true,
             icon: Some("sparkles".to_string()),
+            actions: false,
+            allowed_keys: default_instruction_allowed_keys(),
+            itn: false,
         },
         VoiceInstruction {
             id: "translate_en".to_string(),
@@ -771,6 +1876,9 @@ fn default_instructions() -> Vec<VoiceInstruction> {
             shortcut: None,
             is_preset: true,
             icon: Some("globe".to_string()),
+            actions: false,
+            allowed_keys: default_instruction_allowed_keys(),
+            itn: false,
         },
         VoiceInstruction {
             id: "email".to_string(),
@@ -780,6 +1888,9 @@ fn default_instructions() -> Vec<VoiceInstruction> {
             shortcut: None,
             is_preset: true,
             icon: Some("mail".to_string()),
+            actions: false,
+            allowed_keys: default_instruction_allowed_keys(),
+            itn: false,
         },
         VoiceInstruction {
             id: "summary".to_string(),
@@ -789,6 +1900,21 @@ fn default_instructions() -> Vec<VoiceInstruction> {
             shortcut: None,
             is_preset: true,
             icon: Some("list".to_string()),
+            actions: false,
+            allowed_keys: default_instruction_allowed_keys(),
+            itn: false,
+        },
+        VoiceInstruction {
+            id: "send_message".to_string(),
+            name: "发送消息".to_string(),
+            description: Some("润色后输入，说「发送」时自动按 Enter".to_string()),
+            prompt: "请对以下语音转文字内容进行润色，去除语气词，添加合适的标点符号，但保持原意不变。如果内容以「发送」「发出去」「发了」等表示发送的词结尾，去掉这个词并在末尾加上 {Enter}。文本中原有的花括号写成 {{ 或 }}。只输出润色后的文本，不要添加任何解释：\n\n{{text}}".to_string(),
+            shortcut: None,
+            is_preset: true,
+            icon: Some("send".to_string()),
+            actions: true,
+            allowed_keys: vec!["Enter".to_string()],
+            itn: false,
         },
         VoiceInstruction {
             id: "raw".to_string(),
@@ -798,6 +1924,9 @@ fn default_instructions() -> Vec<VoiceInstruction> {
             shortcut: None,
             is_preset: true,
             icon: Some("type".to_string()),
+            actions: false,
+            allowed_keys: default_instruction_allowed_keys(),
+            itn: false,
         },
     ]
 }
@@ -835,6 +1964,10 @@ impl NativeAgentConfig {
     }
 }
 
+fn default_config_version() -> u32 {
+    super::version::CONFIG_VERSION
+}
+
 fn default_minimize_to_tray() -> bool {
     true
 }
@@ -858,6 +1991,25 @@ pub struct ServerConfig {
     /// TLS 配置
     #[serde(default)]
     pub tls: TlsConfig,
+    /// 客户端密钥（局域网共享时分发给团队成员，用量按密钥归属）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub client_keys: Vec<ClientApiKey>,
+}
+
+/// 客户端密钥
+///
+/// 与主密钥一样可以调用代理 API，请求的统计和费用记在该密钥名下
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClientApiKey {
+    /// 唯一标识（统计中引用，改名不影响历史记录）
+    pub id: String,
+    /// 显示名称（如成员姓名）
+    pub name: String,
+    /// 密钥
+    pub key: String,
+    /// 是否禁用
+    #[serde(default)]
+    pub disabled: bool,
 }
 
 /// TLS 配置
@@ -930,6 +2082,284 @@ impl Default for QuotaExceededConfig {
     }
 }
 
+/// 上游响应异常检测配置
+///
+/// 检查上游返回的成功响应是否退化（空响应、重复 Token、JSON 截断、延迟突增），
+/// 按 Provider 记录并在时间窗口内异常过多时标记为降级
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnomalyDetectionConfig {
+    /// 是否启用
+    #[serde(default = "default_anomaly_enabled")]
+    pub enabled: bool,
+    /// 检测到异常时把凭证记为一次失败，错误次数达到上限后自动切换到其他凭证
+    #[serde(default = "default_anomaly_failover")]
+    pub failover: bool,
+    /// 同一片段连续重复多少次视为异常
+    #[serde(default = "default_repeat_threshold")]
+    pub repeat_threshold: usize,
+    /// 延迟超过近期中位数的倍数视为突增
+    #[serde(default = "default_latency_spike_factor")]
+    pub latency_spike_factor: f64,
+    /// 时间窗口内异常次数达到该值时标记为降级
+    #[serde(default = "default_degraded_threshold")]
+    pub degraded_threshold: usize,
+    /// 降级判定的时间窗口（分钟）
+    #[serde(default = "default_anomaly_window_minutes")]
+    pub window_minutes: u64,
+}
+
+fn default_anomaly_enabled() -> bool {
+    true
+}
+
+fn default_anomaly_failover() -> bool {
+    false
+}
+
+fn default_repeat_threshold() -> usize {
+    20
+}
+
+fn default_latency_spike_factor() -> f64 {
+    3.0
+}
+
+fn default_degraded_threshold() -> usize {
+    3
+}
+
+fn default_anomaly_window_minutes() -> u64 {
+    10
+}
+
+impl Default for AnomalyDetectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_anomaly_enabled(),
+            failover: default_anomaly_failover(),
+            repeat_threshold: default_repeat_threshold(),
+            latency_spike_factor: default_latency_spike_factor(),
+            degraded_threshold: default_degraded_threshold(),
+            window_minutes: default_anomaly_window_minutes(),
+        }
+    }
+}
+
+/// 上游并发准入控制配置
+///
+/// 限制同时转发到上游的对话请求数，超出时按 `X-Proxycast-Priority` 优先级排队：
+/// 高优先级（语音润色）先于普通请求（交互对话），低优先级（定时任务等后台作业）最后
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdmissionControlConfig {
+    /// 是否启用
+    #[serde(default = "default_admission_enabled")]
+    pub enabled: bool,
+    /// 同时转发到上游的最大请求数
+    #[serde(default = "default_admission_max_concurrent")]
+    pub max_concurrent: usize,
+    /// 只留给高优先级请求的并发数
+    #[serde(default = "default_admission_reserved_high")]
+    pub reserved_high: usize,
+    /// 低优先级请求最多占用的并发数
+    #[serde(default = "default_admission_max_low")]
+    pub max_low: usize,
+    /// 排队超时（秒），超时返回 503
+    #[serde(default = "default_admission_queue_timeout_secs")]
+    pub queue_timeout_secs: u64,
+}
+
+fn default_admission_enabled() -> bool {
+    true
+}
+
+fn default_admission_max_concurrent() -> usize {
+    8
+}
+
+fn default_admission_reserved_high() -> usize {
+    2
+}
+
+fn default_admission_max_low() -> usize {
+    2
+}
+
+fn default_admission_queue_timeout_secs() -> u64 {
+    120
+}
+
+impl Default for AdmissionControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_admission_enabled(),
+            max_concurrent: default_admission_max_concurrent(),
+            reserved_high: default_admission_reserved_high(),
+            max_low: default_admission_max_low(),
+            queue_timeout_secs: default_admission_queue_timeout_secs(),
+        }
+    }
+}
+
+/// 响应缓冲内存上限配置
+///
+/// 后置步骤（异常检测、诊断包、Flow 捕获）读取完整的非流式响应时，
+/// 超出上限的响应写入临时文件，不再占用内存
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResponseBufferConfig {
+    /// 单个响应在内存中缓冲的上限（MB）
+    #[serde(default = "default_buffer_per_request_mb")]
+    pub per_request_mb: usize,
+    /// 所有请求在内存中缓冲的总上限（MB）
+    #[serde(default = "default_buffer_global_mb")]
+    pub global_mb: usize,
+}
+
+fn default_buffer_per_request_mb() -> usize {
+    16
+}
+
+fn default_buffer_global_mb() -> usize {
+    256
+}
+
+impl Default for ResponseBufferConfig {
+    fn default() -> Self {
+        Self {
+            per_request_mb: default_buffer_per_request_mb(),
+            global_mb: default_buffer_global_mb(),
+        }
+    }
+}
+
+/// 管道开销统计与昂贵遥测的采样配置
+///
+/// 请求量大时可降低采样率，减少请求体捕获和 Token 重新计数的开销。
+/// 采样按请求 ID 决定，同一请求在各采样项中的结果一致
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PipelineMetricsConfig {
+    /// 捕获请求和响应正文（Flow 监控）的请求比例（0-1），未采样的请求仍创建 Flow 并可拦截，
+    /// 只是不保存正文（Flow 带 `body-not-captured` 标签）
+    #[serde(default = "default_body_capture_rate")]
+    pub body_capture_rate: f64,
+    /// 上游未返回用量时用分词器重新计数输出 Token 的请求比例（0-1），未采样时按字符数粗略估算
+    #[serde(default = "default_token_recount_rate")]
+    pub token_recount_rate: f64,
+    /// 每个步骤保留的耗时样本数，用于计算 P95
+    #[serde(default = "default_overhead_window")]
+    pub overhead_window: usize,
+}
+
+fn default_body_capture_rate() -> f64 {
+    1.0
+}
+
+fn default_token_recount_rate() -> f64 {
+    1.0
+}
+
+fn default_overhead_window() -> usize {
+    1000
+}
+
+impl Default for PipelineMetricsConfig {
+    fn default() -> Self {
+        Self {
+            body_capture_rate: default_body_capture_rate(),
+            token_recount_rate: default_token_recount_rate(),
+            overhead_window: default_overhead_window(),
+        }
+    }
+}
+
+/// 流式响应合并配置
+///
+/// 部分客户端 UI 处理大量细碎的 SSE chunk 时会卡顿，可以为它们使用的路由开启合并：
+/// 按时间窗口或累积字符数把事件攒成一批再转发，批内相邻的文本增量合并为一个事件
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct StreamAggregationConfig {
+    /// 按路由的合并规则，按顺序使用第一条匹配的规则，没有匹配的路由原样转发
+    #[serde(default)]
+    pub routes: Vec<StreamAggregationRoute>,
+}
+
+/// 单条路由的流式响应合并规则
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StreamAggregationRoute {
+    /// 请求路径，`*` 匹配任意一段（如 `/v1/messages`、`/*/v1/chat/completions`）
+    pub path: String,
+    /// 时间窗口（毫秒），从批内第一个事件到达时开始计时，0 表示不按时间发送
+    #[serde(default = "default_aggregation_interval_ms")]
+    pub interval_ms: u64,
+    /// 批内累积的字符数达到该值时立即发送，0 表示不按字符数发送
+    #[serde(default)]
+    pub max_chars: usize,
+}
+
+fn default_aggregation_interval_ms() -> u64 {
+    100
+}
+
+/// 上游 HTTP 客户端配置
+///
+/// 每个 Provider 复用一个客户端及其连接池。`hosts` 把域名固定解析到指定 IP，
+/// 用于公司内网 DNS 与公网解析结果不同（split-horizon）的环境
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UpstreamHttpConfig {
+    /// 每个主机保留的最大空闲连接数
+    #[serde(default = "default_upstream_pool_max_idle_per_host")]
+    pub pool_max_idle_per_host: usize,
+    /// 空闲连接的保留时间（秒）
+    #[serde(default = "default_upstream_pool_idle_timeout_secs")]
+    pub pool_idle_timeout_secs: u64,
+    /// TCP keepalive 间隔（秒），0 表示不启用
+    #[serde(default = "default_upstream_tcp_keepalive_secs")]
+    pub tcp_keepalive_secs: u64,
+    /// 连接超时（秒）
+    #[serde(default = "default_upstream_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    /// 是否允许 HTTP/2（通过 TLS ALPN 协商，上游不支持时自动使用 HTTP/1.1）
+    ///
+    /// 部分代理或网关的 HTTP/2 实现有问题时可关闭
+    #[serde(default = "default_upstream_http2")]
+    pub http2: bool,
+    /// 域名解析覆盖（域名 -> IP 列表），端口仍使用 URL 中的端口
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub hosts: HashMap<String, Vec<String>>,
+}
+
+fn default_upstream_pool_max_idle_per_host() -> usize {
+    32
+}
+
+fn default_upstream_pool_idle_timeout_secs() -> u64 {
+    90
+}
+
+fn default_upstream_tcp_keepalive_secs() -> u64 {
+    60
+}
+
+fn default_upstream_connect_timeout_secs() -> u64 {
+    30
+}
+
+fn default_upstream_http2() -> bool {
+    true
+}
+
+impl Default for UpstreamHttpConfig {
+    fn default() -> Self {
+        Self {
+            pool_max_idle_per_host: default_upstream_pool_max_idle_per_host(),
+            pool_idle_timeout_secs: default_upstream_pool_idle_timeout_secs(),
+            tcp_keepalive_secs: default_upstream_tcp_keepalive_secs(),
+            connect_timeout_secs: default_upstream_connect_timeout_secs(),
+            http2: default_upstream_http2(),
+            hosts: HashMap::new(),
+        }
+    }
+}
+
 /// Amp CLI 模型映射
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AmpModelMapping {
@@ -994,6 +2424,7 @@ impl Default for ServerConfig {
             port: default_port(),
             api_key: default_api_key(),
             tls: TlsConfig::default(),
+            client_keys: Vec::new(),
         }
     }
 }
@@ -1016,6 +2447,9 @@ pub struct ProvidersConfig {
     /// Claude 自定义 Provider 配置
     #[serde(default)]
     pub claude: CustomProviderConfig,
+    /// 阿里云百炼 DashScope 配置
+    #[serde(default)]
+    pub dashscope: DashScopeSettings,
 }
 
 impl Default for ProvidersConfig {
@@ -1049,10 +2483,21 @@ impl Default for ProvidersConfig {
                 api_key: None,
                 base_url: Some("https://api.anthropic.com".to_string()),
             },
+            dashscope: DashScopeSettings::default(),
         }
     }
 }
 
+/// 阿里云百炼 DashScope 配置
+///
+/// 作用于基础地址指向 DashScope 的 OpenAI 兼容凭证
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct DashScopeSettings {
+    /// 接入方式：`compatible` 走兼容模式接口，`native` 走原生 `/api/v1/services/aigc/*/generation` 接口
+    #[serde(default)]
+    pub mode: DashScopeMode,
+}
+
 /// OAuth Provider 配置
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct ProviderConfig {
@@ -1152,6 +2597,67 @@ impl Default for RetrySettings {
     }
 }
 
+/// 推理内容配置
+///
+/// 控制推理模型（如 deepseek-reasoner）返回的 `reasoning_content` 如何转发给客户端
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ReasoningSettings {
+    /// 处理策略
+    #[serde(default)]
+    pub policy: ReasoningPolicy,
+}
+
+/// 本地 Mock Provider 配置
+///
+/// 启用后，指定 Provider 为 `mock` 或模型名以 `mock` 开头的请求由本地生成模拟回复，
+/// 不调用上游、不消耗 Token，便于前端和插件开发
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MockProviderSettings {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 回复模板，支持 `{{model}}`、`{{prompt}}`、`{{message_count}}` 占位符
+    #[serde(default = "default_mock_response_template")]
+    pub response_template: String,
+    /// 直接回显最后一条用户消息（忽略模板）
+    #[serde(default)]
+    pub echo_prompt: bool,
+    /// 首个响应前的模拟延迟（毫秒）
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// 流式响应中每个 chunk 之间的间隔（毫秒）
+    #[serde(default = "default_mock_stream_chunk_delay_ms")]
+    pub stream_chunk_delay_ms: u64,
+    /// 流式响应中每个 chunk 的字符数
+    #[serde(default = "default_mock_stream_chunk_chars")]
+    pub stream_chunk_chars: usize,
+}
+
+fn default_mock_response_template() -> String {
+    "这是来自 ProxyCast Mock Provider 的模拟回复（模型: {{model}}）。".to_string()
+}
+
+fn default_mock_stream_chunk_delay_ms() -> u64 {
+    30
+}
+
+fn default_mock_stream_chunk_chars() -> usize {
+    4
+}
+
+impl Default for MockProviderSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            response_template: default_mock_response_template(),
+            echo_prompt: false,
+            latency_ms: 0,
+            stream_chunk_delay_ms: default_mock_stream_chunk_delay_ms(),
+            stream_chunk_chars: default_mock_stream_chunk_chars(),
+        }
+    }
+}
+
 /// 日志配置
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LoggingConfig {
@@ -1167,12 +2673,19 @@ pub struct LoggingConfig {
     /// 是否包含请求体
     #[serde(default)]
     pub include_request_body: bool,
+    /// 单个日志文件的最大大小（MB），超出后轮转
+    #[serde(default = "default_max_log_file_size_mb")]
+    pub max_file_size_mb: u64,
 }
 
 fn default_logging_enabled() -> bool {
     true
 }
 
+fn default_max_log_file_size_mb() -> u64 {
+    10
+}
+
 fn default_log_level() -> String {
     "info".to_string()
 }
@@ -1188,6 +2701,7 @@ impl Default for LoggingConfig {
             level: default_log_level(),
             retention_days: default_retention_days(),
             include_request_body: false,
+            max_file_size_mb: default_max_log_file_size_mb(),
         }
     }
 }
@@ -1643,17 +3157,26 @@ impl From<&InjectionRule> for InjectionRuleConfig {
 impl Default for Config {
     fn default() -> Self {
         Self {
+            config_version: default_config_version(),
             server: ServerConfig::default(),
             providers: ProvidersConfig::default(),
             default_provider: default_provider(),
             routing: RoutingConfig::default(),
             retry: RetrySettings::default(),
+            reasoning: ReasoningSettings::default(),
+            mock: MockProviderSettings::default(),
             logging: LoggingConfig::default(),
             injection: InjectionSettings::default(),
             auth_dir: default_auth_dir(),
             credential_pool: CredentialPoolConfig::default(),
             remote_management: RemoteManagementConfig::default(),
             quota_exceeded: QuotaExceededConfig::default(),
+            anomaly_detection: AnomalyDetectionConfig::default(),
+            admission_control: AdmissionControlConfig::default(),
+            response_buffer: ResponseBufferConfig::default(),
+            upstream_http: UpstreamHttpConfig::default(),
+            pipeline_metrics: PipelineMetricsConfig::default(),
+            stream_aggregation: StreamAggregationConfig::default(),
             proxy_url: None,
             ampcode: AmpConfig::default(),
             endpoint_providers: EndpointProvidersConfig::default(),
@@ -1998,14 +3521,30 @@ mod unit_tests {
             name: Some("本地 Whisper".to_string()),
             is_default: true,
             disabled: false,
+            priority: 0,
             language: "zh".to_string(),
             whisper_config: Some(WhisperLocalConfig {
                 model: WhisperModelSize::Base,
-                model_path: None,
+                ..Default::default()
             }),
             xunfei_config: None,
             baidu_config: None,
+            tencent_config: None,
+            azure_config: None,
+            deepgram_config: None,
+            volcengine_config: None,
             openai_config: None,
+            custom_whisper_config: None,
+            command_config: None,
+            http_config: None,
+            timeouts: AsrTimeoutConfig {
+                receive_secs: Some(5),
+                ..Default::default()
+            },
+            rate_limit: AsrRateLimitConfig {
+                requests_per_sec: Some(2.0),
+                burst: None,
+            },
         };
         let yaml = serde_yaml::to_string(&entry).unwrap();
         assert!(yaml.contains("provider: whisper_local"));
@@ -2013,6 +3552,34 @@ mod unit_tests {
 
         let parsed: AsrCredentialEntry = serde_yaml::from_str(&yaml).unwrap();
         assert_eq!(parsed, entry);
+
+        // 旧配置没有 timeouts 字段
+        let legacy = yaml.replace("timeouts:\n  receive_secs: 5\n", "");
+        assert_ne!(legacy, yaml);
+        let parsed: AsrCredentialEntry = serde_yaml::from_str(&legacy).unwrap();
+        assert_eq!(parsed.timeouts, AsrTimeoutConfig::default());
+
+        // 旧配置没有 rate_limit 字段
+        let legacy = yaml.replace("rate_limit:\n  requests_per_sec: 2.0\n", "");
+        assert_ne!(legacy, yaml);
+        let parsed: AsrCredentialEntry = serde_yaml::from_str(&legacy).unwrap();
+        assert_eq!(parsed.rate_limit, AsrRateLimitConfig::default());
+    }
+
+    #[test]
+    fn test_local_asr_engine_defaults_to_whisper() {
+        // 旧配置没有 engine 字段
+        let config: WhisperLocalConfig = serde_yaml::from_str("model: small").unwrap();
+        assert_eq!(config.engine, LocalAsrEngine::Whisper);
+        assert_eq!(config.model, WhisperModelSize::Small);
+
+        let config: WhisperLocalConfig =
+            serde_yaml::from_str("engine: sense_voice\nsensevoice_model: float32").unwrap();
+        #[cfg(feature = "sensevoice")]
+        assert_eq!(config.engine, LocalAsrEngine::SenseVoice);
+        #[cfg(not(feature = "sensevoice"))]
+        assert_eq!(config.engine, LocalAsrEngine::Whisper);
+        assert_eq!(config.sensevoice_model, SenseVoicePrecision::Float32);
     }
 
     #[test]
@@ -2022,8 +3589,51 @@ mod unit_tests {
         assert_eq!(config.shortcut, "CommandOrControl+Shift+V");
         assert!(config.processor.polish_enabled);
         assert_eq!(config.processor.default_instruction_id, "default");
+        assert_eq!(config.processor.punctuation, PunctuationMode::Off);
+        assert!(!config.processor.cost_limit.enabled);
         assert_eq!(config.output.mode, VoiceOutputMode::Type);
         assert!(!config.instructions.is_empty());
+        assert_eq!(config.whisper_task, WhisperTask::Transcribe);
+
+        let config: VoiceInputConfig = serde_yaml::from_str("whisper_task: translate").unwrap();
+        assert_eq!(config.whisper_task, WhisperTask::Translate);
+    }
+
+    #[test]
+    fn test_spoken_punctuation_defaults() {
+        // 只写了 enabled 时使用默认口令表
+        let config: VoiceInputConfig =
+            serde_yaml::from_str("spoken_punctuation:\n  enabled: true").unwrap();
+        assert!(config.spoken_punctuation.enabled);
+        assert!(config
+            .spoken_punctuation
+            .mappings
+            .iter()
+            .any(|m| m.spoken == "换行" && m.symbol == "\n"));
+    }
+
+    #[test]
+    fn test_recording_indicator_defaults_off() {
+        // 旧配置没有 recording_indicator 字段
+        let config: VoiceWindowConfig = serde_yaml::from_str("remember_position: false").unwrap();
+        assert!(!config.remember_position);
+        assert!(!config.recording_indicator);
+        assert!(!VoiceWindowConfig::default().recording_indicator);
+    }
+
+    #[test]
+    fn test_instruction_for_language() {
+        let mut processor = VoiceProcessorConfig::default();
+        processor
+            .language_instructions
+            .insert("en".to_string(), "translate_zh".to_string());
+
+        assert_eq!(
+            processor.instruction_for_language(Some("en")),
+            "translate_zh"
+        );
+        assert_eq!(processor.instruction_for_language(Some("zh")), "default");
+        assert_eq!(processor.instruction_for_language(None), "default");
     }
 
     #[test]
@@ -2036,6 +3646,9 @@ mod unit_tests {
             shortcut: Some("CommandOrControl+1".to_string()),
             is_preset: false,
             icon: None,
+            actions: true,
+            allowed_keys: vec!["Ctrl+Enter".to_string()],
+            itn: true,
         };
         let yaml = serde_yaml::to_string(&instruction).unwrap();
         assert!(yaml.contains("id: custom"));
@@ -2062,15 +3675,26 @@ mod unit_tests {
                 name: Some("讯飞语音".to_string()),
                 is_default: false,
                 disabled: false,
+                priority: 0,
                 language: "zh".to_string(),
                 whisper_config: None,
                 xunfei_config: Some(XunfeiConfig {
                     app_id: "test_app_id".to_string(),
                     api_key: "test_api_key".to_string(),
                     api_secret: "test_api_secret".to_string(),
+                    lfasr_secret_key: None,
                 }),
                 baidu_config: None,
+                tencent_config: None,
+                azure_config: None,
+                deepgram_config: None,
+                volcengine_config: None,
                 openai_config: None,
+                custom_whisper_config: None,
+                command_config: None,
+                http_config: None,
+                timeouts: AsrTimeoutConfig::default(),
+                rate_limit: AsrRateLimitConfig::default(),
             }],
         };
 
@@ -0,0 +1,80 @@
+//! 配置文件版本
+//!
+//! 配置文件顶层的 `config_version` 记录写入它的程序支持的配置版本 [`CONFIG_VERSION`]，
+//! 调整配置结构且旧版本无法正确读写时需要提升该版本。
+//!
+//! 回退到旧版本时，配置文件版本高于当前支持的版本：旧程序读取时会忽略不认识的字段，
+//! 写回会丢失这些设置，因此拒绝写入配置文件，直到升级 ProxyCast 或导出配置副本后手动处理。
+
+use std::path::Path;
+
+use super::types::Config;
+use super::yaml::ConfigError;
+
+/// 当前程序支持的配置版本
+pub const CONFIG_VERSION: u32 = 1;
+
+/// 配置文件记录的版本（文件不存在、无法解析或未记录时为空）
+pub fn file_version(path: &Path) -> Option<u32> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let value: serde_yaml::Value = serde_yaml::from_str(&content).ok()?;
+    value
+        .get("config_version")?
+        .as_u64()
+        .and_then(|v| u32::try_from(v).ok())
+}
+
+/// 写入前检查：要写入的配置或磁盘上的配置文件来自更新的版本时拒绝写入
+pub fn ensure_writable(path: &Path, config: &Config) -> Result<(), ConfigError> {
+    let found = file_version(path)
+        .unwrap_or_default()
+        .max(config.config_version);
+    if found > CONFIG_VERSION {
+        return Err(ConfigError::WriteError(format!(
+            "配置文件版本 {} 高于当前支持的版本 {}，为避免丢失设置已拒绝写入，请升级 ProxyCast",
+            found, CONFIG_VERSION
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        assert_eq!(file_version(&path), None);
+
+        std::fs::write(&path, "server:\n  port: 8999\n").unwrap();
+        assert_eq!(file_version(&path), None);
+
+        std::fs::write(&path, "config_version: 3\nserver:\n  port: 8999\n").unwrap();
+        assert_eq!(file_version(&path), Some(3));
+    }
+
+    #[test]
+    fn test_ensure_writable() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        let config = Config::default();
+        assert!(ensure_writable(&path, &config).is_ok());
+
+        std::fs::write(&path, format!("config_version: {}\n", CONFIG_VERSION)).unwrap();
+        assert!(ensure_writable(&path, &config).is_ok());
+
+        // 磁盘上的配置来自更新的版本
+        std::fs::write(&path, format!("config_version: {}\n", CONFIG_VERSION + 1)).unwrap();
+        assert!(ensure_writable(&path, &config).is_err());
+
+        // 要写入的配置来自更新的版本（如导入新版本导出的配置）
+        std::fs::remove_file(&path).unwrap();
+        let newer = Config {
+            config_version: CONFIG_VERSION + 1,
+            ..Config::default()
+        };
+        assert!(ensure_writable(&path, &newer).is_err());
+    }
+}
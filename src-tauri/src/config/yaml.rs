@@ -101,6 +101,8 @@ impl ConfigManager {
 
     /// 保存配置到指定路径
     pub fn save_to(&self, path: &Path) -> Result<(), ConfigError> {
+        super::version::ensure_writable(path, &self.config)?;
+
         // 确保父目录存在
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| ConfigError::WriteError(e.to_string()))?;
@@ -274,6 +276,8 @@ impl YamlService {
     /// * `Ok(())` - 保存成功
     /// * `Err(ConfigError)` - 保存失败
     pub fn save_preserve_comments(path: &Path, config: &Config) -> Result<(), ConfigError> {
+        super::version::ensure_writable(path, config)?;
+
         // 读取原文件内容（如果存在）
         let original_content = if path.exists() {
             std::fs::read_to_string(path).ok()
@@ -724,6 +728,7 @@ pub fn save_config(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
 /// 保存配置为 YAML 格式
 pub fn save_config_yaml(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
     let path = ConfigManager::default_config_path();
+    super::version::ensure_writable(&path, config)?;
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
@@ -91,6 +91,7 @@ fn convert_anthropic_message(msg: &AnthropicMessage) -> Vec<ChatMessage> {
             let mut text_parts: Vec<String> = Vec::new();
             let mut tool_calls: Vec<ToolCall> = Vec::new();
             let mut tool_results: Vec<(String, String)> = Vec::new(); // (tool_use_id, content)
+            let mut image_urls: Vec<String> = Vec::new();
 
             for part in parts {
                 let part_type = part.get("type").and_then(|t| t.as_str()).unwrap_or("");
@@ -127,6 +128,13 @@ fn convert_anthropic_message(msg: &AnthropicMessage) -> Vec<ChatMessage> {
                         let content = extract_tool_result_content(part.get("content"));
                         tool_results.push((tool_use_id.to_string(), content));
                     }
+                    "image" => {
+                        if let Some(url) =
+                            crate::converter::image::anthropic_block_to_image_url(part)
+                        {
+                            image_urls.push(url);
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -165,8 +173,27 @@ fn convert_anthropic_message(msg: &AnthropicMessage) -> Vec<ChatMessage> {
                     });
                 }
 
+                // 含图片时使用多模态内容
+                if !image_urls.is_empty() {
+                    let mut content_parts: Vec<ContentPart> = Vec::new();
+                    if !text_parts.is_empty() {
+                        content_parts.push(ContentPart::Text {
+                            text: text_parts.join(""),
+                        });
+                    }
+                    content_parts.extend(image_urls.into_iter().map(|url| ContentPart::ImageUrl {
+                        image_url: ImageUrl { url, detail: None },
+                    }));
+                    result.push(ChatMessage {
+                        role: "user".to_string(),
+                        content: Some(MessageContent::Parts(content_parts)),
+                        tool_calls: None,
+                        tool_call_id: None,
+                        reasoning_content: None,
+                    });
+                }
                 // 添加文本内容
-                if !text_parts.is_empty() {
+                else if !text_parts.is_empty() {
                     result.push(ChatMessage {
                         role: "user".to_string(),
                         content: Some(MessageContent::Text(text_parts.join(""))),
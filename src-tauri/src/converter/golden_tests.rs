@@ -0,0 +1,141 @@
+//! 转换层 golden 测试
+//!
+//! 覆盖请求处理链路实际使用的转换器：Anthropic → OpenAI 请求（OpenAI 兼容 Provider、
+//! Antigravity 的 `/v1/messages`），以及 OpenAI ↔ Antigravity 的请求与非流式响应。
+//!
+//! 每个用例由 `tests/fixtures/golden/<转换方向>/<用例>.input.json` 和
+//! 同名的 `.golden.json` 组成，转换结果与 golden 文件逐字段比较。
+//!
+//! 新增用例时只需放入 `.input.json`，然后执行
+//! `UPDATE_GOLDEN=1 cargo test converter::golden_tests` 生成 golden 文件，
+//! 人工检查后一并提交。转换器行为变更导致的 golden 差异同样用此方式更新。
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::anthropic_to_openai::convert_anthropic_to_openai;
+use super::openai_to_antigravity::{
+    convert_antigravity_to_openai_response, convert_openai_to_antigravity_with_context,
+};
+use crate::models::anthropic::AnthropicMessagesRequest;
+use crate::models::openai::ChatCompletionRequest;
+
+/// 每次运行都会变化的顶层字段，比较前替换为固定值
+const VOLATILE_FIELDS: &[&str] = &["id", "created", "requestId"];
+
+/// 任意层级都需要替换的字段：`thoughtSignature` 取自全局缓存，其他测试可能同时写入
+const VOLATILE_NESTED_FIELDS: &[&str] = &["thoughtSignature"];
+
+/// Antigravity 用例使用的项目 ID
+const ANTIGRAVITY_PROJECT: &str = "golden-project";
+
+fn golden_dir(direction: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/golden")
+        .join(direction)
+}
+
+fn normalize(mut value: Value) -> Value {
+    if let Some(obj) = value.as_object_mut() {
+        for field in VOLATILE_FIELDS {
+            if obj.contains_key(*field) {
+                obj.insert(field.to_string(), Value::String(format!("<{field}>")));
+            }
+        }
+    }
+    normalize_nested(&mut value);
+    value
+}
+
+fn normalize_nested(value: &mut Value) {
+    match value {
+        Value::Object(obj) => {
+            for (key, field) in obj.iter_mut() {
+                if VOLATILE_NESTED_FIELDS.contains(&key.as_str()) {
+                    *field = Value::String(format!("<{key}>"));
+                } else {
+                    normalize_nested(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(normalize_nested),
+        _ => {}
+    }
+}
+
+/// 对目录下的所有用例执行转换并与 golden 文件比较
+fn run_golden(direction: &str, convert: impl Fn(Value) -> Value) {
+    let dir = golden_dir(direction);
+    let mut inputs: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("读取 golden 目录失败 {}: {e}", dir.display()))
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.to_string_lossy().ends_with(".input.json"))
+        .collect();
+    inputs.sort();
+    assert!(!inputs.is_empty(), "{} 下没有用例", dir.display());
+
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let mut failures = Vec::new();
+
+    for input_path in inputs {
+        let case = input_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_suffix(".input.json"))
+            .unwrap_or_default()
+            .to_string();
+        let golden_path = dir.join(format!("{case}.golden.json"));
+
+        let input: Value = serde_json::from_str(&std::fs::read_to_string(&input_path).unwrap())
+            .unwrap_or_else(|e| panic!("{case}: 输入不是合法 JSON: {e}"));
+        let actual = normalize(convert(input));
+        let actual_text = serde_json::to_string_pretty(&actual).unwrap() + "\n";
+
+        if update {
+            std::fs::write(&golden_path, &actual_text).unwrap();
+            continue;
+        }
+
+        let Ok(golden_text) = std::fs::read_to_string(&golden_path) else {
+            failures.push(format!(
+                "{direction}/{case}: 缺少 golden 文件，使用 UPDATE_GOLDEN=1 生成"
+            ));
+            continue;
+        };
+        let expected: Value = serde_json::from_str(&golden_text)
+            .unwrap_or_else(|e| panic!("{case}: golden 文件不是合法 JSON: {e}"));
+        if expected != actual {
+            failures.push(format!(
+                "{direction}/{case}: 转换结果与 golden 不一致\n--- 期望\n{}\n--- 实际\n{actual_text}",
+                serde_json::to_string_pretty(&expected).unwrap()
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
+
+#[test]
+fn golden_anthropic_to_openai_request() {
+    run_golden("anthropic_to_openai", |input| {
+        let request: AnthropicMessagesRequest = serde_json::from_value(input).unwrap();
+        serde_json::to_value(convert_anthropic_to_openai(&request)).unwrap()
+    });
+}
+
+#[test]
+fn golden_openai_to_antigravity_request() {
+    run_golden("openai_to_antigravity", |input| {
+        let request: ChatCompletionRequest = serde_json::from_value(input).unwrap();
+        convert_openai_to_antigravity_with_context(&request, ANTIGRAVITY_PROJECT)
+    });
+}
+
+#[test]
+fn golden_antigravity_to_openai_response() {
+    run_golden("antigravity_to_openai_response", |input| {
+        let model = input["model"].as_str().unwrap_or_default().to_string();
+        convert_antigravity_to_openai_response(&input["response"], &model)
+    });
+}
@@ -0,0 +1,407 @@
+//! 多模态图片处理
+//!
+//! 在 OpenAI / Anthropic / Gemini 格式之间转换图片内容时共用的解析逻辑：
+//! - `data:` URL 解析为 (media_type, base64)
+//! - `file://` URL 或本地绝对路径读取为 base64（仅限内置 Agent 的请求，供其直接引用截图等文件；
+//!   API 客户端的请求引用本地文件会被拒绝）
+//! - 按目标协议校验图片大小
+//! - 统计请求中的图片数量和大小，用于遥测
+
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+use crate::models::anthropic::AnthropicMessagesRequest;
+use crate::models::openai::{ChatCompletionRequest, ContentPart, MessageContent};
+
+/// OpenAI 单张图片上限
+pub const OPENAI_MAX_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+
+/// Anthropic 单张图片上限
+pub const ANTHROPIC_MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+
+/// Gemini 内联图片上限
+pub const GEMINI_MAX_INLINE_BYTES: usize = 20 * 1024 * 1024;
+
+/// 请求上下文中记录图片数量的元数据键
+pub const IMAGE_COUNT_METADATA: &str = "image_count";
+
+/// 请求上下文中记录图片总字节数的元数据键
+pub const IMAGE_BYTES_METADATA: &str = "image_bytes";
+
+/// 解析后的图片引用
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageRef {
+    /// 内联 base64 数据
+    Base64 { media_type: String, data: String },
+    /// 远程 URL
+    Url(String),
+}
+
+impl ImageRef {
+    /// 解码后的字节数（远程 URL 返回 None）
+    pub fn size_bytes(&self) -> Option<usize> {
+        match self {
+            ImageRef::Base64 { data, .. } => Some(decoded_len(data)),
+            ImageRef::Url(_) => None,
+        }
+    }
+
+    /// 转为 OpenAI 使用的 URL 形式
+    pub fn to_url(&self) -> String {
+        match self {
+            ImageRef::Base64 { media_type, data } => format!("data:{};base64,{}", media_type, data),
+            ImageRef::Url(url) => url.clone(),
+        }
+    }
+
+    /// 校验图片大小
+    pub fn check_size(&self, max_bytes: usize) -> Result<(), String> {
+        match self.size_bytes() {
+            Some(size) if size > max_bytes => {
+                Err(format!("图片过大: {} 字节，上限 {} 字节", size, max_bytes))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 解析 OpenAI `image_url.url`
+///
+/// 支持 `data:` 和 `http(s)://`。本地文件引用只由 [`normalize_openai_images`] 为内置 Agent 读取
+pub fn parse_image_url(url: &str) -> Result<ImageRef, String> {
+    let url = url.trim();
+    if url.starts_with("data:") {
+        return parse_data_url(url)
+            .map(|(media_type, data)| ImageRef::Base64 { media_type, data })
+            .ok_or_else(|| "无效的 data URL".to_string());
+    }
+    if url.starts_with("http://") || url.starts_with("https://") {
+        return Ok(ImageRef::Url(url.to_string()));
+    }
+    Err(format!("不支持的图片地址: {}", truncate(url, 64)))
+}
+
+/// 本地文件引用（`file://` URL 或绝对路径）对应的路径
+fn local_image_path(url: &str) -> Option<&Path> {
+    let url = url.trim();
+    if let Some(path) = url.strip_prefix("file://") {
+        return Some(Path::new(path));
+    }
+    let path = Path::new(url);
+    path.is_absolute().then_some(path)
+}
+
+/// 解析 data URL: `data:image/jpeg;base64,xxxxx` -> (image/jpeg, xxxxx)
+pub fn parse_data_url(url: &str) -> Option<(String, String)> {
+    let (meta, data) = url.strip_prefix("data:")?.split_once(',')?;
+    let media_type = meta.split(';').next().filter(|m| !m.is_empty());
+    Some((
+        media_type.unwrap_or("image/jpeg").to_string(),
+        data.to_string(),
+    ))
+}
+
+/// 根据 base64 字符串长度计算解码后的字节数
+pub fn decoded_len(data: &str) -> usize {
+    let trimmed = data.trim_end_matches('=');
+    trimmed.len() * 3 / 4
+}
+
+/// 读取本地图片文件，超过 `max_bytes` 时不读取内容
+async fn read_local_image(path: &Path, max_bytes: usize) -> Result<ImageRef, String> {
+    let media_type =
+        media_type_for_path(path).ok_or_else(|| format!("不支持的图片格式: {}", path.display()))?;
+    let size = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| format!("读取图片失败: {} - {}", path.display(), e))?
+        .len() as usize;
+    if size > max_bytes {
+        return Err(format!("图片过大: {} 字节，上限 {} 字节", size, max_bytes));
+    }
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("读取图片失败: {} - {}", path.display(), e))?;
+    Ok(ImageRef::Base64 {
+        media_type: media_type.to_string(),
+        data: BASE64.encode(bytes),
+    })
+}
+
+/// 根据扩展名判断图片 MIME 类型，只允许常见图片格式
+fn media_type_for_path(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+fn truncate(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        s.to_string()
+    } else {
+        format!("{}...", s.chars().take(max_chars).collect::<String>())
+    }
+}
+
+/// 规范化 OpenAI 请求中的图片
+///
+/// 校验每张图片不超过 `max_bytes`。`allow_local_files` 为 true（内置 Agent 的请求）时
+/// 将本地文件引用内联为 data URL，否则引用本地文件的请求返回错误
+pub async fn normalize_openai_images(
+    request: &mut ChatCompletionRequest,
+    max_bytes: usize,
+    allow_local_files: bool,
+) -> Result<(), String> {
+    for msg in &mut request.messages {
+        let Some(MessageContent::Parts(parts)) = &mut msg.content else {
+            continue;
+        };
+        for part in parts {
+            let ContentPart::ImageUrl { image_url } = part else {
+                continue;
+            };
+            let image = match local_image_path(&image_url.url) {
+                Some(path) if allow_local_files => read_local_image(path, max_bytes).await?,
+                Some(_) => return Err("不允许引用本地图片文件".to_string()),
+                None => parse_image_url(&image_url.url)?,
+            };
+            image.check_size(max_bytes)?;
+            if !image_url.url.starts_with("data:") && matches!(image, ImageRef::Base64 { .. }) {
+                image_url.url = image.to_url();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 请求中的图片统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImagePayloadStats {
+    /// 图片数量
+    pub count: u32,
+    /// 内联图片解码后的总字节数（远程 URL 不计入）
+    pub bytes: u64,
+}
+
+impl ImagePayloadStats {
+    fn add(&mut self, size: Option<usize>) {
+        self.count += 1;
+        self.bytes += size.unwrap_or(0) as u64;
+    }
+}
+
+/// 统计 OpenAI 请求中的图片
+pub fn openai_image_stats(request: &ChatCompletionRequest) -> ImagePayloadStats {
+    let mut stats = ImagePayloadStats::default();
+    for msg in &request.messages {
+        if let Some(MessageContent::Parts(parts)) = &msg.content {
+            for part in parts {
+                if let ContentPart::ImageUrl { image_url } = part {
+                    let size = parse_data_url(&image_url.url).map(|(_, data)| decoded_len(&data));
+                    stats.add(size);
+                }
+            }
+        }
+    }
+    stats
+}
+
+/// 统计 Anthropic 请求中的图片
+pub fn anthropic_image_stats(request: &AnthropicMessagesRequest) -> ImagePayloadStats {
+    let mut stats = ImagePayloadStats::default();
+    for msg in &request.messages {
+        let Some(blocks) = msg.content.as_array() else {
+            continue;
+        };
+        for block in blocks {
+            if block.get("type").and_then(|t| t.as_str()) == Some("image") {
+                let size = block
+                    .get("source")
+                    .and_then(|s| s.get("data"))
+                    .and_then(|d| d.as_str())
+                    .map(decoded_len);
+                stats.add(size);
+            }
+        }
+    }
+    stats
+}
+
+/// 校验 Anthropic 请求中每张内联图片不超过 `max_bytes`
+pub fn check_anthropic_images(
+    request: &AnthropicMessagesRequest,
+    max_bytes: usize,
+) -> Result<(), String> {
+    for msg in &request.messages {
+        let Some(blocks) = msg.content.as_array() else {
+            continue;
+        };
+        for block in blocks {
+            if block.get("type").and_then(|t| t.as_str()) != Some("image") {
+                continue;
+            }
+            let size = block
+                .get("source")
+                .and_then(|s| s.get("data"))
+                .and_then(|d| d.as_str())
+                .map(decoded_len);
+            if let Some(size) = size.filter(|size| *size > max_bytes) {
+                return Err(format!("图片过大: {} 字节，上限 {} 字节", size, max_bytes));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 将 Anthropic image block 转为 OpenAI image_url
+pub fn anthropic_block_to_image_url(block: &serde_json::Value) -> Option<String> {
+    let source = block.get("source")?;
+    match source.get("type").and_then(|t| t.as_str())? {
+        "base64" => {
+            let media_type = source
+                .get("media_type")
+                .and_then(|m| m.as_str())
+                .unwrap_or("image/jpeg");
+            let data = source.get("data").and_then(|d| d.as_str())?;
+            Some(format!("data:{};base64,{}", media_type, data))
+        }
+        "url" => source
+            .get("url")
+            .and_then(|u| u.as_str())
+            .map(|u| u.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_data_url() {
+        let image = parse_image_url("data:image/png;base64,aGVsbG8=").unwrap();
+        assert_eq!(
+            image,
+            ImageRef::Base64 {
+                media_type: "image/png".to_string(),
+                data: "aGVsbG8=".to_string()
+            }
+        );
+        assert_eq!(image.size_bytes(), Some(5));
+        assert!(image.check_size(4).is_err());
+        assert!(image.check_size(5).is_ok());
+    }
+
+    #[test]
+    fn test_parse_remote_and_invalid_urls() {
+        assert_eq!(
+            parse_image_url("https://example.com/a.png").unwrap(),
+            ImageRef::Url("https://example.com/a.png".to_string())
+        );
+        assert!(parse_image_url("relative/a.png").is_err());
+        assert!(parse_image_url("data:image/png;base64").is_err());
+    }
+
+    fn image_request(url: &str) -> ChatCompletionRequest {
+        serde_json::from_value(serde_json::json!({
+            "model": "gpt-4o",
+            "messages": [{
+                "role": "user",
+                "content": [{"type": "image_url", "image_url": {"url": url}}]
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn first_image_url(request: &ChatCompletionRequest) -> &str {
+        let Some(MessageContent::Parts(parts)) = &request.messages[0].content else {
+            panic!("expected parts");
+        };
+        let ContentPart::ImageUrl { image_url } = &parts[0] else {
+            panic!("expected image_url");
+        };
+        &image_url.url
+    }
+
+    #[tokio::test]
+    async fn test_local_images_only_for_agent() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shot.PNG");
+        std::fs::write(&path, b"hello").unwrap();
+        let url = format!("file://{}", path.display());
+
+        // API 客户端不能读取本地文件
+        assert!(parse_image_url(&url).is_err());
+        let mut request = image_request(&url);
+        assert!(
+            normalize_openai_images(&mut request, OPENAI_MAX_IMAGE_BYTES, false)
+                .await
+                .is_err()
+        );
+        assert_eq!(first_image_url(&request), url);
+
+        let mut request = image_request(&url);
+        normalize_openai_images(&mut request, OPENAI_MAX_IMAGE_BYTES, true)
+            .await
+            .unwrap();
+        assert_eq!(first_image_url(&request), "data:image/png;base64,aGVsbG8=");
+
+        // 超过上限的文件不读取
+        let mut request = image_request(&path.display().to_string());
+        assert!(normalize_openai_images(&mut request, 4, true)
+            .await
+            .is_err());
+
+        let text = dir.path().join("secret.txt");
+        std::fs::write(&text, b"nope").unwrap();
+        let mut request = image_request(&text.display().to_string());
+        assert!(
+            normalize_openai_images(&mut request, OPENAI_MAX_IMAGE_BYTES, true)
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_check_anthropic_images() {
+        let request: AnthropicMessagesRequest = serde_json::from_value(serde_json::json!({
+            "model": "claude-sonnet-4",
+            "max_tokens": 16,
+            "messages": [{
+                "role": "user",
+                "content": [{
+                    "type": "image",
+                    "source": {"type": "base64", "media_type": "image/png", "data": "aGVsbG8="}
+                }]
+            }]
+        }))
+        .unwrap();
+        assert!(check_anthropic_images(&request, 5).is_ok());
+        assert!(check_anthropic_images(&request, 4).is_err());
+    }
+
+    #[test]
+    fn test_anthropic_block_to_image_url() {
+        let block = serde_json::json!({
+            "type": "image",
+            "source": {"type": "base64", "media_type": "image/webp", "data": "AAAA"}
+        });
+        assert_eq!(
+            anthropic_block_to_image_url(&block).as_deref(),
+            Some("data:image/webp;base64,AAAA")
+        );
+        let block = serde_json::json!({
+            "type": "image",
+            "source": {"type": "url", "url": "https://example.com/a.png"}
+        });
+        assert_eq!(
+            anthropic_block_to_image_url(&block).as_deref(),
+            Some("https://example.com/a.png")
+        );
+    }
+}
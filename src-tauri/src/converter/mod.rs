@@ -1,5 +1,6 @@
 pub mod anthropic_to_openai;
 pub mod cw_to_openai;
+pub mod image;
 pub mod openai_to_antigravity;
 pub mod openai_to_cw;
 pub mod protocol_selector;
@@ -17,3 +18,6 @@ pub use openai_to_cw::*;
 pub use protocol_selector::*;
 #[allow(unused_imports)]
 pub use reasoning_handler::*;
+
+#[cfg(test)]
+mod golden_tests;
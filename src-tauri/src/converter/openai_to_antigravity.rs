@@ -389,7 +389,7 @@ pub fn convert_openai_to_antigravity_with_context(
                 if let Some(MessageContent::Parts(content_parts)) = &msg.content {
                     for part in content_parts {
                         if let ContentPart::ImageUrl { image_url } = part {
-                            if let Some((mime, data)) = parse_data_url(&image_url.url) {
+                            if let Some((mime, data)) = inline_image(&image_url.url) {
                                 parts.push(GeminiPart {
                                     text: None,
                                     inline_data: Some(InlineData {
@@ -800,8 +800,8 @@ fn convert_user_content(msg: &ChatMessage) -> Vec<GeminiPart> {
                         });
                     }
                     ContentPart::ImageUrl { image_url } => {
-                        // 处理 base64 图片
-                        if let Some((mime, data)) = parse_data_url(&image_url.url) {
+                        // 处理 base64 / 本地文件图片
+                        if let Some((mime, data)) = inline_image(&image_url.url) {
                             parts.push(GeminiPart {
                                 text: None,
                                 inline_data: Some(InlineData {
@@ -823,18 +823,32 @@ fn convert_user_content(msg: &ChatMessage) -> Vec<GeminiPart> {
     parts
 }
 
-/// 解析 data URL
-fn parse_data_url(url: &str) -> Option<(String, String)> {
-    if url.starts_with("data:") {
-        let parts: Vec<&str> = url.splitn(2, ',').collect();
-        if parts.len() == 2 {
-            let meta = parts[0].strip_prefix("data:")?;
-            let mime = meta.split(';').next()?.to_string();
-            let data = parts[1].to_string();
-            return Some((mime, data));
+/// 将图片 URL 解析为 Gemini inlineData 所需的 (mime, base64)
+///
+/// Gemini 不支持任意远程 URL，远程图片和超出大小限制的图片会被跳过
+fn inline_image(url: &str) -> Option<(String, String)> {
+    match super::image::parse_image_url(url) {
+        Ok(image) => {
+            if let Err(e) = image.check_size(super::image::GEMINI_MAX_INLINE_BYTES) {
+                tracing::warn!("[ANTIGRAVITY_IMAGE] 跳过图片: {}", e);
+                return None;
+            }
+            match image {
+                super::image::ImageRef::Base64 { media_type, data } => Some((media_type, data)),
+                super::image::ImageRef::Url(url) => {
+                    tracing::warn!(
+                        "[ANTIGRAVITY_IMAGE] Gemini 不支持远程图片 URL，已跳过: {}",
+                        url
+                    );
+                    None
+                }
+            }
+        }
+        Err(e) => {
+            tracing::warn!("[ANTIGRAVITY_IMAGE] 无法解析图片: {}", e);
+            None
         }
     }
-    None
 }
 
 // ============================================================================
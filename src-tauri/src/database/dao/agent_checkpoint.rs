@@ -0,0 +1,298 @@
+//! Agent 会话检查点的数据访问层
+//!
+//! 检查点保存 aster 会话对话的完整快照（序列化的 `Message` 列表），
+//! 恢复和分叉由调用方通过 `SessionManager` 写回会话。
+
+use crate::agent::checkpoint::{AgentCheckpoint, FileSnapshotRef};
+use aster::conversation::message::Message;
+use aster::conversation::Conversation;
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub struct AgentCheckpointDao;
+
+impl AgentCheckpointDao {
+    /// 创建检查点，快照会话当前的对话
+    pub fn create(
+        conn: &Connection,
+        checkpoint: &AgentCheckpoint,
+        conversation: &Conversation,
+    ) -> Result<AgentCheckpoint, rusqlite::Error> {
+        let messages = conversation.messages();
+        let messages_json = serde_json::to_string(messages)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let files_json = serde_json::to_string(&checkpoint.file_snapshots)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        conn.execute(
+            "INSERT INTO agent_checkpoints (id, session_id, workspace_id, name, messages_json, file_snapshots_json, message_count, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                checkpoint.id,
+                checkpoint.session_id,
+                checkpoint.workspace_id,
+                checkpoint.name,
+                messages_json,
+                files_json,
+                messages.len() as i64,
+                checkpoint.created_at,
+            ],
+        )?;
+
+        Ok(AgentCheckpoint {
+            message_count: messages.len(),
+            ..checkpoint.clone()
+        })
+    }
+
+    /// 获取检查点（不包含消息快照）
+    pub fn get(
+        conn: &Connection,
+        checkpoint_id: &str,
+    ) -> Result<Option<AgentCheckpoint>, rusqlite::Error> {
+        conn.query_row(
+            "SELECT id, session_id, workspace_id, name, message_count, file_snapshots_json, created_at
+             FROM agent_checkpoints WHERE id = ?",
+            [checkpoint_id],
+            Self::row_to_checkpoint,
+        )
+        .optional()
+    }
+
+    /// 列出会话的所有检查点（按创建时间倒序）
+    pub fn list_by_session(
+        conn: &Connection,
+        session_id: &str,
+    ) -> Result<Vec<AgentCheckpoint>, rusqlite::Error> {
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, workspace_id, name, message_count, file_snapshots_json, created_at
+             FROM agent_checkpoints WHERE session_id = ? ORDER BY created_at DESC",
+        )?;
+
+        let checkpoints = stmt.query_map([session_id], Self::row_to_checkpoint)?;
+        checkpoints.collect()
+    }
+
+    /// 列出工作区的所有检查点（按创建时间倒序）
+    pub fn list_by_workspace(
+        conn: &Connection,
+        workspace_id: &str,
+    ) -> Result<Vec<AgentCheckpoint>, rusqlite::Error> {
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, workspace_id, name, message_count, file_snapshots_json, created_at
+             FROM agent_checkpoints WHERE workspace_id = ? ORDER BY created_at DESC",
+        )?;
+
+        let checkpoints = stmt.query_map([workspace_id], Self::row_to_checkpoint)?;
+        checkpoints.collect()
+    }
+
+    /// 删除检查点
+    pub fn delete(conn: &Connection, checkpoint_id: &str) -> Result<bool, rusqlite::Error> {
+        let rows = conn.execute(
+            "DELETE FROM agent_checkpoints WHERE id = ?",
+            [checkpoint_id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// 获取检查点的对话快照
+    pub fn get_conversation(
+        conn: &Connection,
+        checkpoint_id: &str,
+    ) -> Result<Conversation, rusqlite::Error> {
+        let messages_json: String = conn.query_row(
+            "SELECT messages_json FROM agent_checkpoints WHERE id = ?",
+            [checkpoint_id],
+            |row| row.get(0),
+        )?;
+
+        let messages: Vec<Message> = serde_json::from_str(&messages_json).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+        Ok(Conversation::new_unvalidated(messages))
+    }
+
+    fn row_to_checkpoint(row: &rusqlite::Row) -> Result<AgentCheckpoint, rusqlite::Error> {
+        let files_json: String = row.get(5)?;
+        let file_snapshots: Vec<FileSnapshotRef> =
+            serde_json::from_str(&files_json).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    5,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?;
+        let message_count: i64 = row.get(4)?;
+
+        Ok(AgentCheckpoint {
+            id: row.get(0)?,
+            session_id: row.get(1)?,
+            workspace_id: row.get(2)?,
+            name: row.get(3)?,
+            message_count: message_count as usize,
+            file_snapshots,
+            created_at: row.get(6)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::aster_session_store::ProxyCastSessionStore;
+    use aster::conversation::message::MessageContent;
+    use aster::session::{SessionStore, SessionType};
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    fn setup_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::create_tables(&conn).unwrap();
+        conn
+    }
+
+    fn conversation(texts: &[&str]) -> Conversation {
+        let messages = texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let message = if i % 2 == 0 {
+                    Message::user()
+                } else {
+                    Message::assistant()
+                };
+                message.with_text(*text)
+            })
+            .collect();
+        Conversation::new_unvalidated(messages)
+    }
+
+    fn texts(conversation: &Conversation) -> Vec<String> {
+        conversation
+            .messages()
+            .iter()
+            .flat_map(|m| &m.content)
+            .filter_map(|c| match c {
+                MessageContent::Text(text) => Some(text.text.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn checkpoint(id: &str, session_id: &str) -> AgentCheckpoint {
+        AgentCheckpoint {
+            id: id.to_string(),
+            session_id: session_id.to_string(),
+            workspace_id: Some("ws1".to_string()),
+            name: "before-refactor".to_string(),
+            message_count: 0,
+            file_snapshots: vec![FileSnapshotRef {
+                name: "plan.md".to_string(),
+                size: 42,
+                updated_at: 1,
+            }],
+            created_at: 1000,
+        }
+    }
+
+    #[test]
+    fn test_create_and_list_checkpoints() {
+        let conn = setup_test_db();
+        let created = AgentCheckpointDao::create(
+            &conn,
+            &checkpoint("cp1", "s1"),
+            &conversation(&["hello", "hi"]),
+        )
+        .unwrap();
+        assert_eq!(created.message_count, 2);
+
+        let list = AgentCheckpointDao::list_by_session(&conn, "s1").unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].file_snapshots[0].name, "plan.md");
+        assert_eq!(
+            AgentCheckpointDao::list_by_workspace(&conn, "ws1")
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let snapshot = AgentCheckpointDao::get_conversation(&conn, "cp1").unwrap();
+        assert_eq!(texts(&snapshot), vec!["hello", "hi"]);
+    }
+
+    #[tokio::test]
+    async fn test_restore_and_branch_aster_session() {
+        let db = Arc::new(Mutex::new(setup_test_db()));
+        let store = ProxyCastSessionStore::new(db.clone());
+
+        // 按 aster 的方式创建会话并写入消息
+        let session = store
+            .create_session(
+                PathBuf::from("."),
+                "测试会话".to_string(),
+                SessionType::User,
+            )
+            .await
+            .unwrap();
+        for message in conversation(&["hello", "hi"]).messages() {
+            store.add_message(&session.id, message).await.unwrap();
+        }
+
+        let snapshot = store.get_session(&session.id, true).await.unwrap();
+        let created = AgentCheckpointDao::create(
+            &db.lock().unwrap(),
+            &checkpoint("cp1", &session.id),
+            snapshot.conversation.as_ref().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(created.message_count, 2);
+
+        store
+            .add_message(&session.id, &Message::user().with_text("later"))
+            .await
+            .unwrap();
+        assert_eq!(
+            store
+                .get_session(&session.id, false)
+                .await
+                .unwrap()
+                .message_count,
+            3
+        );
+
+        // 恢复：用快照替换会话对话
+        let saved = AgentCheckpointDao::get_conversation(&db.lock().unwrap(), "cp1").unwrap();
+        store
+            .replace_conversation(&session.id, &saved)
+            .await
+            .unwrap();
+        let restored = store.get_session(&session.id, true).await.unwrap();
+        assert_eq!(
+            texts(restored.conversation.as_ref().unwrap()),
+            vec!["hello", "hi"]
+        );
+
+        // 分叉：以快照内容创建新会话，原会话不变
+        let branch = store
+            .create_session(PathBuf::from("."), "分支".to_string(), SessionType::User)
+            .await
+            .unwrap();
+        store
+            .replace_conversation(&branch.id, &saved)
+            .await
+            .unwrap();
+        let branched = store.get_session(&branch.id, true).await.unwrap();
+        assert_eq!(
+            texts(branched.conversation.as_ref().unwrap()),
+            vec!["hello", "hi"]
+        );
+        assert_eq!(
+            store
+                .get_session(&session.id, false)
+                .await
+                .unwrap()
+                .message_count,
+            2
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! ASR 用量记录的数据访问层
+
+use crate::voice::usage::{AsrUsageRecord, AsrUsageStats};
+use rusqlite::{params, Connection};
+
+pub struct AsrUsageDao;
+
+impl AsrUsageDao {
+    /// 新增记录
+    pub fn insert(conn: &Connection, record: &AsrUsageRecord) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "INSERT INTO asr_usage (id, credential_id, provider, audio_secs, latency_ms, status, error, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                record.id,
+                record.credential_id,
+                record.provider,
+                record.audio_secs,
+                record.latency_ms as i64,
+                record.status.as_str(),
+                record.error,
+                record.created_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 按凭证和服务汇总 `since`（毫秒时间戳）之后的用量，调用次数多的在前
+    pub fn stats_since(
+        conn: &Connection,
+        since: i64,
+    ) -> Result<Vec<AsrUsageStats>, rusqlite::Error> {
+        let mut stmt = conn.prepare(
+            "SELECT credential_id, provider, COUNT(*),
+                    COALESCE(SUM(status = 'success'), 0),
+                    COALESCE(SUM(status = 'error'), 0),
+                    COALESCE(SUM(CASE WHEN status = 'success' THEN audio_secs ELSE 0 END), 0),
+                    COALESCE(AVG(CASE WHEN status = 'success' THEN latency_ms END), 0)
+             FROM asr_usage WHERE created_at >= ?1
+             GROUP BY credential_id, provider
+             ORDER BY COUNT(*) DESC",
+        )?;
+        let rows = stmt.query_map([since], |row| {
+            Ok(AsrUsageStats {
+                credential_id: row.get(0)?,
+                provider: row.get(1)?,
+                calls: row.get(2)?,
+                succeeded: row.get(3)?,
+                failed: row.get(4)?,
+                audio_secs: row.get(5)?,
+                avg_latency_ms: row.get(6)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// 删除 `before`（毫秒时间戳）之前的记录，返回删除条数
+    pub fn delete_before(conn: &Connection, before: i64) -> Result<usize, rusqlite::Error> {
+        conn.execute("DELETE FROM asr_usage WHERE created_at < ?1", [before])
+    }
+}
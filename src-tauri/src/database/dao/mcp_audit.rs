@@ -0,0 +1,194 @@
+//! MCP 工具调用审计日志的数据访问层
+
+use crate::agent::tool_audit::{ToolAuditEntry, ToolAuditFilter, ToolAuditStatus};
+use rusqlite::{params, Connection};
+
+/// 默认查询条数
+const DEFAULT_QUERY_LIMIT: u32 = 200;
+
+pub struct McpAuditDao;
+
+impl McpAuditDao {
+    /// 写入一条审计记录
+    pub fn insert(conn: &Connection, entry: &ToolAuditEntry) -> Result<i64, rusqlite::Error> {
+        conn.execute(
+            "INSERT INTO mcp_tool_audit (session_id, workspace_id, server, tool, arguments_digest,
+                                         duration_ms, status, error, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                entry.session_id,
+                entry.workspace_id,
+                entry.server,
+                entry.tool,
+                entry.arguments_digest,
+                entry.duration_ms as i64,
+                entry.status.as_str(),
+                entry.error,
+                entry.created_at,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// 按条件查询审计记录（按时间倒序）
+    pub fn query(
+        conn: &Connection,
+        filter: &ToolAuditFilter,
+    ) -> Result<Vec<ToolAuditEntry>, rusqlite::Error> {
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, workspace_id, server, tool, arguments_digest,
+                    duration_ms, status, error, created_at
+             FROM mcp_tool_audit
+             WHERE (?1 IS NULL OR workspace_id = ?1)
+               AND (?2 IS NULL OR session_id = ?2)
+               AND (?3 IS NULL OR server = ?3)
+               AND (?4 IS NULL OR status = ?4)
+               AND (?5 IS NULL OR created_at >= ?5)
+             ORDER BY created_at DESC, id DESC
+             LIMIT ?6",
+        )?;
+
+        let entries = stmt.query_map(
+            params![
+                filter.workspace_id,
+                filter.session_id,
+                filter.server,
+                filter.status.map(|s| s.as_str()),
+                filter.since,
+                filter.limit.unwrap_or(DEFAULT_QUERY_LIMIT),
+            ],
+            |row| {
+                let duration_ms: i64 = row.get(6)?;
+                let status: String = row.get(7)?;
+                Ok(ToolAuditEntry {
+                    id: Some(row.get(0)?),
+                    session_id: row.get(1)?,
+                    workspace_id: row.get(2)?,
+                    server: row.get(3)?,
+                    tool: row.get(4)?,
+                    arguments_digest: row.get(5)?,
+                    duration_ms: duration_ms.max(0) as u64,
+                    status: ToolAuditStatus::parse(&status),
+                    error: row.get(8)?,
+                    created_at: row.get(9)?,
+                })
+            },
+        )?;
+
+        entries.collect()
+    }
+
+    /// 删除指定时间之前的审计记录
+    pub fn delete_before(conn: &Connection, before: i64) -> Result<usize, rusqlite::Error> {
+        conn.execute("DELETE FROM mcp_tool_audit WHERE created_at < ?", [before])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::create_tables(&conn).unwrap();
+        conn
+    }
+
+    fn entry(
+        session_id: &str,
+        server: &str,
+        status: ToolAuditStatus,
+        created_at: i64,
+    ) -> ToolAuditEntry {
+        ToolAuditEntry {
+            id: None,
+            session_id: session_id.to_string(),
+            workspace_id: Some("ws-1".to_string()),
+            server: server.to_string(),
+            tool: "read_file".to_string(),
+            arguments_digest: "abc123".to_string(),
+            duration_ms: 42,
+            status,
+            error: None,
+            created_at,
+        }
+    }
+
+    #[test]
+    fn test_insert_and_query() {
+        let conn = setup_test_db();
+        let mut failed = entry("s1", "fs", ToolAuditStatus::Error, 200);
+        failed.error = Some("文件不存在".to_string());
+        let id = McpAuditDao::insert(&conn, &failed).unwrap();
+        McpAuditDao::insert(&conn, &entry("s1", "fs", ToolAuditStatus::Success, 100)).unwrap();
+
+        let entries = McpAuditDao::query(&conn, &ToolAuditFilter::default()).unwrap();
+        assert_eq!(entries.len(), 2);
+        // 按时间倒序
+        assert_eq!(entries[0].id, Some(id));
+        assert_eq!(entries[0].status, ToolAuditStatus::Error);
+        assert_eq!(entries[0].error.as_deref(), Some("文件不存在"));
+        assert_eq!(entries[0].duration_ms, 42);
+        assert_eq!(entries[1].created_at, 100);
+    }
+
+    #[test]
+    fn test_query_filters() {
+        let conn = setup_test_db();
+        McpAuditDao::insert(&conn, &entry("s1", "fs", ToolAuditStatus::Success, 100)).unwrap();
+        McpAuditDao::insert(&conn, &entry("s1", "git", ToolAuditStatus::Error, 200)).unwrap();
+        McpAuditDao::insert(&conn, &entry("s2", "fs", ToolAuditStatus::Success, 300)).unwrap();
+
+        let query = |filter: ToolAuditFilter| McpAuditDao::query(&conn, &filter).unwrap();
+
+        let by_session = query(ToolAuditFilter {
+            session_id: Some("s1".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(by_session.len(), 2);
+
+        let by_server = query(ToolAuditFilter {
+            server: Some("fs".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(by_server.len(), 2);
+
+        let by_status = query(ToolAuditFilter {
+            status: Some(ToolAuditStatus::Error),
+            ..Default::default()
+        });
+        assert_eq!(by_status.len(), 1);
+        assert_eq!(by_status[0].server, "git");
+
+        let since = query(ToolAuditFilter {
+            since: Some(200),
+            ..Default::default()
+        });
+        assert_eq!(since.len(), 2);
+
+        let limited = query(ToolAuditFilter {
+            limit: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].created_at, 300);
+
+        let other_workspace = query(ToolAuditFilter {
+            workspace_id: Some("ws-2".to_string()),
+            ..Default::default()
+        });
+        assert!(other_workspace.is_empty());
+    }
+
+    #[test]
+    fn test_delete_before() {
+        let conn = setup_test_db();
+        McpAuditDao::insert(&conn, &entry("s1", "fs", ToolAuditStatus::Success, 100)).unwrap();
+        McpAuditDao::insert(&conn, &entry("s1", "fs", ToolAuditStatus::Success, 200)).unwrap();
+
+        assert_eq!(McpAuditDao::delete_before(&conn, 150).unwrap(), 1);
+        let entries = McpAuditDao::query(&conn, &ToolAuditFilter::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].created_at, 200);
+    }
+}
@@ -1,10 +1,17 @@
 pub mod agent;
+pub mod agent_checkpoint;
 pub mod api_key_provider;
+pub mod asr_usage;
 pub mod general_chat;
 pub mod installed_plugins;
 pub mod mcp;
+pub mod mcp_audit;
 pub mod orchestrator;
 pub mod prompts;
 pub mod provider_pool;
 pub mod providers;
+pub mod scheduled_jobs;
 pub mod skills;
+pub mod voice_ab_trials;
+pub mod voice_history;
+pub mod voice_snippets;
@@ -0,0 +1,244 @@
+//! 定时任务的数据访问层
+
+use crate::scheduler::types::{JobKind, JobRun, JobRunStatus, ScheduledJob};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// 每个任务保留的执行记录条数
+const MAX_RUNS_PER_JOB: i64 = 50;
+
+pub struct ScheduledJobDao;
+
+impl ScheduledJobDao {
+    /// 新增或更新任务
+    pub fn upsert(conn: &Connection, job: &ScheduledJob) -> Result<(), rusqlite::Error> {
+        let job_json = serde_json::to_string(&job.job)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        conn.execute(
+            "INSERT INTO scheduled_jobs (id, name, enabled, schedule, job_type, job_json,
+                                         last_run_at, last_status, last_error, next_run_at, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+             ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                enabled = excluded.enabled,
+                schedule = excluded.schedule,
+                job_type = excluded.job_type,
+                job_json = excluded.job_json,
+                next_run_at = excluded.next_run_at",
+            params![
+                job.id,
+                job.name,
+                job.enabled,
+                job.schedule,
+                job.job.type_name(),
+                job_json,
+                job.last_run_at,
+                job.last_status.map(|s| s.as_str()),
+                job.last_error,
+                job.next_run_at,
+                job.created_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 获取任务
+    pub fn get(conn: &Connection, id: &str) -> Result<Option<ScheduledJob>, rusqlite::Error> {
+        conn.query_row(
+            "SELECT id, name, enabled, schedule, job_json, last_run_at, last_status, last_error,
+                    next_run_at, created_at
+             FROM scheduled_jobs WHERE id = ?",
+            [id],
+            Self::row_to_job,
+        )
+        .optional()
+    }
+
+    /// 获取所有任务
+    pub fn list(conn: &Connection) -> Result<Vec<ScheduledJob>, rusqlite::Error> {
+        let mut stmt = conn.prepare(
+            "SELECT id, name, enabled, schedule, job_json, last_run_at, last_status, last_error,
+                    next_run_at, created_at
+             FROM scheduled_jobs ORDER BY created_at",
+        )?;
+
+        let jobs = stmt.query_map([], Self::row_to_job)?;
+        jobs.collect()
+    }
+
+    /// 获取已到期的启用任务
+    pub fn list_due(conn: &Connection, now: i64) -> Result<Vec<ScheduledJob>, rusqlite::Error> {
+        let mut stmt = conn.prepare(
+            "SELECT id, name, enabled, schedule, job_json, last_run_at, last_status, last_error,
+                    next_run_at, created_at
+             FROM scheduled_jobs
+             WHERE enabled = 1 AND next_run_at IS NOT NULL AND next_run_at <= ?
+             ORDER BY next_run_at",
+        )?;
+
+        let jobs = stmt.query_map([now], Self::row_to_job)?;
+        jobs.collect()
+    }
+
+    /// 删除任务及其执行记录
+    pub fn delete(conn: &Connection, id: &str) -> Result<bool, rusqlite::Error> {
+        conn.execute("DELETE FROM scheduled_job_runs WHERE job_id = ?", [id])?;
+        let rows = conn.execute("DELETE FROM scheduled_jobs WHERE id = ?", [id])?;
+        Ok(rows > 0)
+    }
+
+    /// 更新下次执行时间
+    pub fn set_next_run(
+        conn: &Connection,
+        id: &str,
+        next_run_at: Option<i64>,
+    ) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "UPDATE scheduled_jobs SET next_run_at = ? WHERE id = ?",
+            params![next_run_at, id],
+        )?;
+        Ok(())
+    }
+
+    /// 记录一次执行结果，并更新任务的上次执行信息
+    pub fn record_run(conn: &Connection, run: &JobRun) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "INSERT INTO scheduled_job_runs (job_id, started_at, finished_at, status, output, error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                run.job_id,
+                run.started_at,
+                run.finished_at,
+                run.status.as_str(),
+                run.output,
+                run.error,
+            ],
+        )?;
+
+        conn.execute(
+            "UPDATE scheduled_jobs SET last_run_at = ?, last_status = ?, last_error = ? WHERE id = ?",
+            params![run.started_at, run.status.as_str(), run.error, run.job_id],
+        )?;
+
+        // 只保留最近的执行记录
+        conn.execute(
+            "DELETE FROM scheduled_job_runs WHERE job_id = ?1 AND id NOT IN (
+                SELECT id FROM scheduled_job_runs WHERE job_id = ?1 ORDER BY id DESC LIMIT ?2
+             )",
+            params![run.job_id, MAX_RUNS_PER_JOB],
+        )?;
+
+        Ok(())
+    }
+
+    /// 获取任务的执行记录（最新在前）
+    pub fn list_runs(
+        conn: &Connection,
+        job_id: &str,
+        limit: u32,
+    ) -> Result<Vec<JobRun>, rusqlite::Error> {
+        let mut stmt = conn.prepare(
+            "SELECT id, job_id, started_at, finished_at, status, output, error
+             FROM scheduled_job_runs WHERE job_id = ? ORDER BY id DESC LIMIT ?",
+        )?;
+
+        let runs = stmt.query_map(params![job_id, limit], |row| {
+            let status: String = row.get(4)?;
+            Ok(JobRun {
+                id: row.get(0)?,
+                job_id: row.get(1)?,
+                started_at: row.get(2)?,
+                finished_at: row.get(3)?,
+                status: JobRunStatus::parse(&status),
+                output: row.get(5)?,
+                error: row.get(6)?,
+            })
+        })?;
+
+        runs.collect()
+    }
+
+    fn row_to_job(row: &rusqlite::Row) -> Result<ScheduledJob, rusqlite::Error> {
+        let job_json: String = row.get(4)?;
+        let job: JobKind = serde_json::from_str(&job_json).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+        let last_status: Option<String> = row.get(6)?;
+
+        Ok(ScheduledJob {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            enabled: row.get(2)?,
+            schedule: row.get(3)?,
+            job,
+            last_run_at: row.get(5)?,
+            last_status: last_status.as_deref().map(JobRunStatus::parse),
+            last_error: row.get(7)?,
+            next_run_at: row.get(8)?,
+            created_at: row.get(9)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::create_tables(&conn).unwrap();
+        conn
+    }
+
+    fn job(id: &str, next_run_at: Option<i64>) -> ScheduledJob {
+        ScheduledJob {
+            id: id.to_string(),
+            name: "清理日志".to_string(),
+            enabled: true,
+            schedule: "@daily".to_string(),
+            job: JobKind::PruneLogs { retention_days: 7 },
+            last_run_at: None,
+            last_status: None,
+            last_error: None,
+            next_run_at,
+            created_at: 1,
+        }
+    }
+
+    #[test]
+    fn test_upsert_and_list_due() {
+        let conn = setup_test_db();
+        ScheduledJobDao::upsert(&conn, &job("a", Some(100))).unwrap();
+        ScheduledJobDao::upsert(&conn, &job("b", Some(300))).unwrap();
+
+        let due = ScheduledJobDao::list_due(&conn, 200).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, "a");
+        assert_eq!(due[0].job, JobKind::PruneLogs { retention_days: 7 });
+    }
+
+    #[test]
+    fn test_record_run_updates_job() {
+        let conn = setup_test_db();
+        ScheduledJobDao::upsert(&conn, &job("a", Some(100))).unwrap();
+
+        ScheduledJobDao::record_run(
+            &conn,
+            &JobRun {
+                id: 0,
+                job_id: "a".to_string(),
+                started_at: 100,
+                finished_at: 150,
+                status: JobRunStatus::Failed,
+                output: None,
+                error: Some("boom".to_string()),
+            },
+        )
+        .unwrap();
+
+        let stored = ScheduledJobDao::get(&conn, "a").unwrap().unwrap();
+        assert_eq!(stored.last_status, Some(JobRunStatus::Failed));
+        assert_eq!(stored.last_error.as_deref(), Some("boom"));
+        assert_eq!(ScheduledJobDao::list_runs(&conn, "a", 10).unwrap().len(), 1);
+    }
+}
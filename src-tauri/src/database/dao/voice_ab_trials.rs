@@ -0,0 +1,83 @@
+//! 润色指令 A/B 测试记录的数据访问层
+
+use crate::voice::ab_test::{AbRating, AbVariantStats, VoiceAbTrial};
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub struct VoiceAbTrialDao;
+
+impl VoiceAbTrialDao {
+    /// 新增记录
+    pub fn insert(conn: &Connection, trial: &VoiceAbTrial) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "INSERT INTO voice_ab_trials (id, instruction_id, history_id, rating, created_at, rated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                trial.id,
+                trial.instruction_id,
+                trial.history_id,
+                trial.rating.map(AbRating::as_str),
+                trial.created_at,
+                trial.rated_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 两个变体中最近一次使用的指令 ID
+    pub fn last_instruction(
+        conn: &Connection,
+        variant_a: &str,
+        variant_b: &str,
+    ) -> Result<Option<String>, rusqlite::Error> {
+        conn.query_row(
+            "SELECT instruction_id FROM voice_ab_trials
+             WHERE instruction_id IN (?1, ?2)
+             ORDER BY created_at DESC LIMIT 1",
+            params![variant_a, variant_b],
+            |row| row.get(0),
+        )
+        .optional()
+    }
+
+    /// 记录评价（重复评价时覆盖）
+    pub fn rate(
+        conn: &Connection,
+        id: &str,
+        rating: AbRating,
+        rated_at: i64,
+    ) -> Result<bool, rusqlite::Error> {
+        let rows = conn.execute(
+            "UPDATE voice_ab_trials SET rating = ?2, rated_at = ?3 WHERE id = ?1",
+            params![id, rating.as_str(), rated_at],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// 按指令汇总润色次数和评价
+    pub fn stats(
+        conn: &Connection,
+        instruction_id: &str,
+    ) -> Result<AbVariantStats, rusqlite::Error> {
+        conn.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(rating = 'good'), 0),
+                    COALESCE(SUM(rating = 'bad'), 0)
+             FROM voice_ab_trials WHERE instruction_id = ?1",
+            [instruction_id],
+            |row| {
+                Ok(AbVariantStats::new(
+                    instruction_id.to_string(),
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                ))
+            },
+        )
+    }
+
+    /// 清空记录
+    pub fn clear(conn: &Connection) -> Result<(), rusqlite::Error> {
+        conn.execute("DELETE FROM voice_ab_trials", [])?;
+        Ok(())
+    }
+}
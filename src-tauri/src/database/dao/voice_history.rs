@@ -0,0 +1,272 @@
+//! 语音听写历史的数据访问层
+
+use crate::voice::history::VoiceHistoryEntry;
+use crate::voice::timeline::SessionTimeline;
+use rusqlite::{params, Connection, OptionalExtension};
+
+const COLUMNS: &str = "id, parent_id, raw_text, text, language, provider, instruction_id, model, \
+     created_at, timeline";
+
+pub struct VoiceHistoryDao;
+
+impl VoiceHistoryDao {
+    /// 获取最近的听写（最近在前），包含这些听写重新润色生成的版本
+    pub fn list_recent(
+        conn: &Connection,
+        limit: usize,
+    ) -> Result<Vec<VoiceHistoryEntry>, rusqlite::Error> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM voice_history
+             WHERE parent_id IS NULL
+                OR parent_id IN (
+                    SELECT id FROM voice_history WHERE parent_id IS NULL
+                    ORDER BY created_at DESC LIMIT ?1
+                )
+             ORDER BY created_at DESC"
+        ))?;
+
+        let entries = stmt.query_map([limit as i64], Self::row_to_entry)?;
+        let entries: Vec<VoiceHistoryEntry> = entries.collect::<Result<_, _>>()?;
+
+        // 外层条件会带出全部原始听写，这里只保留最近的 limit 条
+        let mut roots = 0;
+        Ok(entries
+            .into_iter()
+            .filter(|e| {
+                if e.parent_id.is_some() {
+                    return true;
+                }
+                roots += 1;
+                roots <= limit
+            })
+            .collect())
+    }
+
+    /// 获取记录
+    pub fn get(conn: &Connection, id: &str) -> Result<Option<VoiceHistoryEntry>, rusqlite::Error> {
+        conn.query_row(
+            &format!("SELECT {COLUMNS} FROM voice_history WHERE id = ?"),
+            [id],
+            Self::row_to_entry,
+        )
+        .optional()
+    }
+
+    /// 新增记录
+    pub fn insert(conn: &Connection, entry: &VoiceHistoryEntry) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            &format!(
+                "INSERT INTO voice_history ({COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+            ),
+            params![
+                entry.id,
+                entry.parent_id,
+                entry.raw_text,
+                entry.text,
+                entry.language,
+                entry.provider,
+                entry.instruction_id,
+                entry.model,
+                entry.created_at,
+                entry
+                    .timeline
+                    .as_ref()
+                    .and_then(|t| serde_json::to_string(t).ok()),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 记录润色结果
+    pub fn update_polish(
+        conn: &Connection,
+        id: &str,
+        text: &str,
+        instruction_id: &str,
+        model: Option<&str>,
+    ) -> Result<bool, rusqlite::Error> {
+        let rows = conn.execute(
+            "UPDATE voice_history SET text = ?2, instruction_id = ?3, model = ?4 WHERE id = ?1",
+            params![id, text, instruction_id, model],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// 保存听写的阶段时间线
+    pub fn update_timeline(
+        conn: &Connection,
+        id: &str,
+        timeline: &SessionTimeline,
+    ) -> Result<bool, rusqlite::Error> {
+        let json = serde_json::to_string(timeline).unwrap_or_default();
+        let rows = conn.execute(
+            "UPDATE voice_history SET timeline = ?2 WHERE id = ?1",
+            params![id, json],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// 删除记录，删除原始听写时连同其版本一起删除
+    pub fn delete(conn: &Connection, id: &str) -> Result<bool, rusqlite::Error> {
+        let rows = conn.execute(
+            "DELETE FROM voice_history WHERE id = ?1 OR parent_id = ?1",
+            [id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// 清空历史
+    pub fn clear(conn: &Connection) -> Result<(), rusqlite::Error> {
+        conn.execute("DELETE FROM voice_history", [])?;
+        Ok(())
+    }
+
+    /// 只保留最近的 `max_entries` 条听写及其版本
+    pub fn prune(conn: &Connection, max_entries: usize) -> Result<usize, rusqlite::Error> {
+        conn.execute(
+            "DELETE FROM voice_history WHERE COALESCE(parent_id, id) NOT IN (
+                SELECT id FROM voice_history WHERE parent_id IS NULL
+                ORDER BY created_at DESC LIMIT ?1
+            )",
+            [max_entries as i64],
+        )
+    }
+
+    fn row_to_entry(row: &rusqlite::Row) -> Result<VoiceHistoryEntry, rusqlite::Error> {
+        Ok(VoiceHistoryEntry {
+            id: row.get(0)?,
+            parent_id: row.get(1)?,
+            raw_text: row.get(2)?,
+            text: row.get(3)?,
+            language: row.get(4)?,
+            provider: row.get(5)?,
+            instruction_id: row.get(6)?,
+            model: row.get(7)?,
+            created_at: row.get(8)?,
+            timeline: row
+                .get::<_, Option<String>>(9)?
+                .and_then(|json| serde_json::from_str(&json).ok()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::create_tables(&conn).unwrap();
+        conn
+    }
+
+    fn entry(id: &str, created_at: i64) -> VoiceHistoryEntry {
+        VoiceHistoryEntry {
+            id: id.to_string(),
+            created_at,
+            ..VoiceHistoryEntry::new("原始文本".to_string(), None, None)
+        }
+    }
+
+    fn variant(parent: &VoiceHistoryEntry, id: &str, created_at: i64) -> VoiceHistoryEntry {
+        VoiceHistoryEntry {
+            id: id.to_string(),
+            created_at,
+            ..parent.variant("润色文本".to_string(), "polish".to_string(), None)
+        }
+    }
+
+    fn ids(entries: &[VoiceHistoryEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_list_recent_limits_roots_and_keeps_variants() {
+        let conn = setup_test_db();
+        let a = entry("a", 100);
+        let b = entry("b", 200);
+        let c = entry("c", 300);
+        for e in [&a, &b, &c] {
+            VoiceHistoryDao::insert(&conn, e).unwrap();
+        }
+        // 旧听写 a 的新版本不应把 a 带进最近列表
+        VoiceHistoryDao::insert(&conn, &variant(&a, "a1", 400)).unwrap();
+        VoiceHistoryDao::insert(&conn, &variant(&b, "b1", 250)).unwrap();
+
+        let recent = VoiceHistoryDao::list_recent(&conn, 2).unwrap();
+        assert_eq!(ids(&recent), ["c", "b1", "b"]);
+
+        let all = VoiceHistoryDao::list_recent(&conn, 10).unwrap();
+        assert_eq!(ids(&all), ["a1", "c", "b1", "b", "a"]);
+
+        assert!(VoiceHistoryDao::list_recent(&conn, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_prune_removes_old_entries_with_variants() {
+        let conn = setup_test_db();
+        let a = entry("a", 100);
+        let b = entry("b", 200);
+        let c = entry("c", 300);
+        for e in [&a, &b, &c] {
+            VoiceHistoryDao::insert(&conn, e).unwrap();
+        }
+        VoiceHistoryDao::insert(&conn, &variant(&a, "a1", 400)).unwrap();
+        VoiceHistoryDao::insert(&conn, &variant(&c, "c1", 500)).unwrap();
+
+        assert_eq!(VoiceHistoryDao::prune(&conn, 2).unwrap(), 2);
+        assert!(VoiceHistoryDao::get(&conn, "a").unwrap().is_none());
+        assert!(VoiceHistoryDao::get(&conn, "a1").unwrap().is_none());
+
+        let rest = VoiceHistoryDao::list_recent(&conn, 10).unwrap();
+        assert_eq!(ids(&rest), ["c1", "c", "b"]);
+
+        // 未超出上限时不删除
+        assert_eq!(VoiceHistoryDao::prune(&conn, 2).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_update_polish_records_model() {
+        let conn = setup_test_db();
+        VoiceHistoryDao::insert(&conn, &entry("a", 100)).unwrap();
+
+        let updated =
+            VoiceHistoryDao::update_polish(&conn, "a", "润色后", "polish", Some("gpt-4o-mini"))
+                .unwrap();
+        assert!(updated);
+        let saved = VoiceHistoryDao::get(&conn, "a").unwrap().unwrap();
+        assert_eq!(saved.text, "润色后");
+        assert_eq!(saved.raw_text, "原始文本");
+        assert_eq!(saved.instruction_id.as_deref(), Some("polish"));
+        assert_eq!(saved.model.as_deref(), Some("gpt-4o-mini"));
+
+        assert!(!VoiceHistoryDao::update_polish(&conn, "missing", "x", "polish", None).unwrap());
+    }
+
+    #[test]
+    fn test_old_table_gains_timeline_column() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE voice_history (
+                id TEXT PRIMARY KEY,
+                parent_id TEXT,
+                raw_text TEXT NOT NULL,
+                text TEXT NOT NULL,
+                language TEXT,
+                provider TEXT,
+                instruction_id TEXT,
+                model TEXT,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .unwrap();
+        conn.pragma_update(None, "user_version", 1).unwrap();
+
+        crate::database::schema::create_tables(&conn).unwrap();
+        VoiceHistoryDao::insert(&conn, &entry("a", 100)).unwrap();
+        let saved = VoiceHistoryDao::get(&conn, "a").unwrap().unwrap();
+        assert!(saved.timeline.is_none());
+    }
+}
@@ -0,0 +1,69 @@
+//! 语音片段的数据访问层
+
+use crate::voice::snippets::VoiceSnippet;
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub struct VoiceSnippetDao;
+
+impl VoiceSnippetDao {
+    /// 获取所有片段
+    pub fn list(conn: &Connection) -> Result<Vec<VoiceSnippet>, rusqlite::Error> {
+        let mut stmt = conn.prepare(
+            "SELECT id, trigger_phrase, content, enabled, created_at, updated_at
+             FROM voice_snippets ORDER BY created_at",
+        )?;
+
+        let snippets = stmt.query_map([], Self::row_to_snippet)?;
+        snippets.collect()
+    }
+
+    /// 获取片段
+    pub fn get(conn: &Connection, id: &str) -> Result<Option<VoiceSnippet>, rusqlite::Error> {
+        conn.query_row(
+            "SELECT id, trigger_phrase, content, enabled, created_at, updated_at
+             FROM voice_snippets WHERE id = ?",
+            [id],
+            Self::row_to_snippet,
+        )
+        .optional()
+    }
+
+    /// 新增或更新片段
+    pub fn upsert(conn: &Connection, snippet: &VoiceSnippet) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "INSERT INTO voice_snippets (id, trigger_phrase, content, enabled, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(id) DO UPDATE SET
+                trigger_phrase = excluded.trigger_phrase,
+                content = excluded.content,
+                enabled = excluded.enabled,
+                updated_at = excluded.updated_at",
+            params![
+                snippet.id,
+                snippet.trigger,
+                snippet.content,
+                snippet.enabled,
+                snippet.created_at,
+                snippet.updated_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 删除片段
+    pub fn delete(conn: &Connection, id: &str) -> Result<bool, rusqlite::Error> {
+        let rows = conn.execute("DELETE FROM voice_snippets WHERE id = ?", [id])?;
+        Ok(rows > 0)
+    }
+
+    fn row_to_snippet(row: &rusqlite::Row) -> Result<VoiceSnippet, rusqlite::Error> {
+        Ok(VoiceSnippet {
+            id: row.get(0)?,
+            trigger: row.get(1)?,
+            content: row.get(2)?,
+            enabled: row.get(3)?,
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+        })
+    }
+}
@@ -2,6 +2,7 @@ pub mod dao;
 pub mod migration;
 pub mod schema;
 pub mod system_providers;
+pub mod version;
 
 use rusqlite::Connection;
 use std::path::PathBuf;
@@ -27,6 +28,16 @@ pub fn init_database() -> Result<DbConnection, String> {
     conn.busy_timeout(std::time::Duration::from_secs(5))
         .map_err(|e| format!("设置 busy_timeout 失败: {}", e))?;
 
+    // 数据库来自更新的版本时以只读模式打开，跳过建表和迁移
+    if let Some(found) = version::guard_newer(&conn)? {
+        tracing::error!(
+            "[数据库] 数据库版本 {} 高于当前支持的版本 {}，已以只读模式打开，请升级 ProxyCast 或导出数据后处理",
+            found,
+            version::SCHEMA_VERSION
+        );
+        return Ok(Arc::new(Mutex::new(conn)));
+    }
+
     // 创建表结构
     schema::create_tables(&conn).map_err(|e| e.to_string())?;
     migration::migrate_from_json(&conn)?;
@@ -72,5 +83,7 @@ pub fn init_database() -> Result<DbConnection, String> {
         }
     }
 
+    version::stamp(&conn)?;
+
     Ok(Arc::new(Mutex::new(conn)))
 }
@@ -105,6 +105,150 @@ pub fn create_tables(conn: &Connection) -> Result<(), rusqlite::Error> {
         [],
     )?;
 
+    // MCP 工具调用审计表
+    // 记录 Agent 的每次工具调用，参数只保存摘要
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS mcp_tool_audit (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT NOT NULL,
+            workspace_id TEXT,
+            server TEXT NOT NULL,
+            tool TEXT NOT NULL,
+            arguments_digest TEXT NOT NULL,
+            duration_ms INTEGER NOT NULL DEFAULT 0,
+            status TEXT NOT NULL,
+            error TEXT,
+            created_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_mcp_tool_audit_workspace ON mcp_tool_audit(workspace_id, created_at)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_mcp_tool_audit_session ON mcp_tool_audit(session_id)",
+        [],
+    )?;
+
+    // 定时任务表
+    // job_json 保存任务内容（JobKind），时间均为毫秒时间戳
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scheduled_jobs (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            schedule TEXT NOT NULL,
+            job_type TEXT NOT NULL,
+            job_json TEXT NOT NULL,
+            last_run_at INTEGER,
+            last_status TEXT,
+            last_error TEXT,
+            next_run_at INTEGER,
+            created_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    // 定时任务执行记录表
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scheduled_job_runs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            job_id TEXT NOT NULL,
+            started_at INTEGER NOT NULL,
+            finished_at INTEGER NOT NULL,
+            status TEXT NOT NULL,
+            output TEXT,
+            error TEXT,
+            FOREIGN KEY (job_id) REFERENCES scheduled_jobs(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_scheduled_job_runs_job ON scheduled_job_runs(job_id, id)",
+        [],
+    )?;
+
+    // 语音片段表
+    // 口述触发词展开为保存的文本块
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS voice_snippets (
+            id TEXT PRIMARY KEY,
+            trigger_phrase TEXT NOT NULL,
+            content TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    // 语音听写历史表
+    // 重新润色生成的版本通过 parent_id 指向原始听写
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS voice_history (
+            id TEXT PRIMARY KEY,
+            parent_id TEXT,
+            raw_text TEXT NOT NULL,
+            text TEXT NOT NULL,
+            language TEXT,
+            provider TEXT,
+            instruction_id TEXT,
+            model TEXT,
+            created_at INTEGER NOT NULL,
+            timeline TEXT
+        )",
+        [],
+    )?;
+    migrate_voice_history_timeline(conn)?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_voice_history_parent ON voice_history(parent_id)",
+        [],
+    )?;
+
+    // 润色指令 A/B 测试记录
+    // 每次润色记录使用的变体指令，用户评价后写入 rating
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS voice_ab_trials (
+            id TEXT PRIMARY KEY,
+            instruction_id TEXT NOT NULL,
+            history_id TEXT,
+            rating TEXT,
+            created_at INTEGER NOT NULL,
+            rated_at INTEGER
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_voice_ab_trials_instruction ON voice_ab_trials(instruction_id, created_at)",
+        [],
+    )?;
+
+    // ASR 用量记录
+    // 每次调用识别服务记录一条，按凭证汇总用量
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS asr_usage (
+            id TEXT PRIMARY KEY,
+            credential_id TEXT NOT NULL,
+            provider TEXT NOT NULL,
+            audio_secs REAL NOT NULL,
+            latency_ms INTEGER NOT NULL,
+            status TEXT NOT NULL,
+            error TEXT,
+            created_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_asr_usage_created ON asr_usage(created_at)",
+        [],
+    )?;
+
     // Prompts 表
     conn.execute(
         "CREATE TABLE IF NOT EXISTS prompts (
@@ -482,6 +626,32 @@ pub fn create_tables(conn: &Connection) -> Result<(), rusqlite::Error> {
         [],
     )?;
 
+    // Agent 会话检查点表
+    // 保存会话在指定时刻的消息快照和会话文件引用，用于恢复和分叉会话
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS agent_checkpoints (
+            id TEXT PRIMARY KEY,
+            session_id TEXT NOT NULL,
+            workspace_id TEXT,
+            name TEXT NOT NULL,
+            messages_json TEXT NOT NULL,
+            file_snapshots_json TEXT NOT NULL DEFAULT '[]',
+            message_count INTEGER NOT NULL DEFAULT 0,
+            created_at INTEGER NOT NULL,
+            FOREIGN KEY (session_id) REFERENCES agent_sessions(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_agent_checkpoints_session ON agent_checkpoints(session_id)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_agent_checkpoints_workspace ON agent_checkpoints(workspace_id)",
+        [],
+    )?;
+
     // ============================================================================
     // General Chat 相关表
     // ============================================================================
@@ -732,6 +902,25 @@ pub fn create_tables(conn: &Connection) -> Result<(), rusqlite::Error> {
     Ok(())
 }
 
+/// 迁移（结构版本 2）：语音听写历史增加时间线列
+///
+/// 结构版本低于 2 的数据库可能是没有该列的旧表，新建的表已包含该列
+fn migrate_voice_history_timeline(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let user_version: u32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if user_version >= 2 {
+        return Ok(());
+    }
+
+    let mut stmt = conn.prepare("PRAGMA table_info(voice_history)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get(1))?
+        .collect::<Result<Vec<_>, _>>()?;
+    if !columns.iter().any(|c| c == "timeline") {
+        conn.execute("ALTER TABLE voice_history ADD COLUMN timeline TEXT", [])?;
+    }
+    Ok(())
+}
+
 /// 迁移：添加proxy_url列到provider_pool_credentials表
 /// 使用重建表结构的方式确保数据完整性
 fn migrate_add_proxy_url_column(conn: &Connection) -> Result<(), rusqlite::Error> {
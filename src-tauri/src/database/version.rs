@@ -0,0 +1,75 @@
+//! 数据库结构版本
+//!
+//! 结构版本记录在 SQLite 的 `user_version` 中，每次初始化完成后写入当前版本 [`SCHEMA_VERSION`]。
+//! 修改表结构的迁移需要同时提升该版本，旧版本程序据此识别由新版本创建或升级过的数据库。
+//!
+//! 回退到旧版本时，数据库版本高于当前支持的版本：跳过建表和迁移，以只读模式（`query_only`）
+//! 打开，避免旧程序按旧结构写入破坏数据，用户可以导出数据库副本后再决定如何处理。
+
+use once_cell::sync::OnceCell;
+use rusqlite::Connection;
+
+/// 当前程序支持的数据库结构版本
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// 启动时发现的更高数据库版本
+static NEWER_VERSION: OnceCell<u32> = OnceCell::new();
+
+/// 读取数据库记录的结构版本（新建或旧版本数据库为 0）
+pub fn read(conn: &Connection) -> Result<u32, String> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(|e| format!("读取数据库版本失败: {}", e))
+}
+
+/// 写入当前结构版本
+pub fn stamp(conn: &Connection) -> Result<(), String> {
+    conn.pragma_update(None, "user_version", SCHEMA_VERSION)
+        .map_err(|e| format!("写入数据库版本失败: {}", e))
+}
+
+/// 数据库版本高于当前支持的版本时，改为只读并返回数据库版本
+pub fn guard_newer(conn: &Connection) -> Result<Option<u32>, String> {
+    let found = read(conn)?;
+    if found <= SCHEMA_VERSION {
+        return Ok(None);
+    }
+    conn.pragma_update(None, "query_only", true)
+        .map_err(|e| format!("切换只读模式失败: {}", e))?;
+    let _ = NEWER_VERSION.set(found);
+    Ok(Some(found))
+}
+
+/// 启动时发现的更高数据库版本，数据库版本兼容时为空
+pub fn newer_version() -> Option<u32> {
+    NEWER_VERSION.get().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stamp_and_read() {
+        let conn = Connection::open_in_memory().unwrap();
+        assert_eq!(read(&conn).unwrap(), 0);
+        stamp(&conn).unwrap();
+        assert_eq!(read(&conn).unwrap(), SCHEMA_VERSION);
+        assert_eq!(guard_newer(&conn).unwrap(), None);
+        conn.execute("CREATE TABLE t (id INTEGER)", []).unwrap();
+    }
+
+    #[test]
+    fn test_newer_database_is_read_only() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE t (id INTEGER)", []).unwrap();
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION + 1)
+            .unwrap();
+
+        assert_eq!(guard_newer(&conn).unwrap(), Some(SCHEMA_VERSION + 1));
+        assert!(conn.execute("INSERT INTO t (id) VALUES (1)", []).is_err());
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM t", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+}
@@ -67,10 +67,36 @@ pub async fn handle_command(
                 "timestamp": e.timestamp,
                 "level": e.level,
                 "message": e.message,
+                "request_id": e.request_id,
             })).collect();
             Ok(serde_json::to_value(recent)?)
         }
 
+        "get_request_trace" => {
+            let id = args
+                .as_ref()
+                .and_then(|a| a.get("id"))
+                .and_then(|v| v.as_str())
+                .ok_or("缺少 id 参数")?;
+            let trace = state.logs.read().await.get_request_trace(id);
+            Ok(serde_json::to_value(trace)?)
+        }
+
+        "get_log_levels" => Ok(serde_json::to_value(
+            crate::logger::tracing_bridge::levels(),
+        )?),
+
+        "set_log_level" => {
+            let args = args.as_ref();
+            let level = args
+                .and_then(|a| a.get("level"))
+                .and_then(|v| v.as_str())
+                .ok_or("缺少 level 参数")?;
+            let module = args.and_then(|a| a.get("module")).and_then(|v| v.as_str());
+            let levels = crate::logger::tracing_bridge::set_level(module, level)?;
+            Ok(serde_json::to_value(levels)?)
+        }
+
         "clear_logs" => {
             state.logs.write().await.clear();
             Ok(serde_json::json!({ "success": true }))
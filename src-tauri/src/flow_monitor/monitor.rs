@@ -728,6 +728,28 @@ pub enum FlowEvent {
 // 活跃 Flow 状态
 // ============================================================================
 
+/// 未保存正文的 Flow 带有的标签
+pub const BODY_NOT_CAPTURED_TAG: &str = "body-not-captured";
+
+/// 清除请求正文，保留模型、参数和大小等元数据
+fn strip_request_body(request: &mut LLMRequest) {
+    request.body = serde_json::Value::Null;
+    request.messages.clear();
+    request.system_prompt = None;
+    request.tools = None;
+}
+
+/// 清除响应正文，保留状态、用量和流式统计等元数据
+fn strip_response_body(response: &mut LLMResponse) {
+    response.body = serde_json::Value::Null;
+    response.content.clear();
+    response.thinking = None;
+    response.tool_calls.clear();
+    if let Some(stream_info) = response.stream_info.as_mut() {
+        stream_info.raw_chunks = None;
+    }
+}
+
 /// 活跃 Flow 状态
 ///
 /// 用于跟踪正在进行中的 Flow，包括流式响应重建器。
@@ -738,6 +760,8 @@ struct ActiveFlow {
     stream_rebuilder: Option<StreamRebuilder>,
     /// 请求开始时间
     request_start: DateTime<Utc>,
+    /// 是否保存请求和响应正文
+    capture_body: bool,
 }
 
 // ============================================================================
@@ -1048,6 +1072,27 @@ impl FlowMonitor {
     /// - `Some(flow_id)`: 成功创建 Flow，返回 Flow ID
     /// - `None`: 根据配置跳过监控
     pub async fn start_flow(&self, request: LLMRequest, metadata: FlowMetadata) -> Option<String> {
+        self.start_flow_with_capture(request, metadata, true).await
+    }
+
+    /// 开始捕获一个新的 Flow，但不保存请求和响应正文
+    ///
+    /// 只记录模型、用量、耗时和状态等元数据，Flow 带有 [`BODY_NOT_CAPTURED_TAG`] 标签。
+    /// 拦截检查由调用方基于完整请求进行，不受影响
+    pub async fn start_flow_without_body(
+        &self,
+        request: LLMRequest,
+        metadata: FlowMetadata,
+    ) -> Option<String> {
+        self.start_flow_with_capture(request, metadata, false).await
+    }
+
+    async fn start_flow_with_capture(
+        &self,
+        mut request: LLMRequest,
+        metadata: FlowMetadata,
+        capture_body: bool,
+    ) -> Option<String> {
         let config = self.config.read().await;
 
         // 检查是否应该监控
@@ -1076,14 +1121,24 @@ impl FlowMonitor {
         // 确定 Flow 类型
         let flow_type = Self::determine_flow_type(&request.path);
 
+        if !capture_body {
+            strip_request_body(&mut request);
+        }
+
         // 创建 Flow
-        let flow = LLMFlow::new(flow_id.clone(), flow_type, request.clone(), metadata);
+        let mut flow = LLMFlow::new(flow_id.clone(), flow_type, request, metadata);
+        if !capture_body {
+            flow.annotations
+                .tags
+                .push(BODY_NOT_CAPTURED_TAG.to_string());
+        }
 
         // 创建活跃 Flow 状态
         let active_flow = ActiveFlow {
             flow: flow.clone(),
             stream_rebuilder: None,
             request_start: Utc::now(),
+            capture_body,
         };
 
         // 添加到活跃 Flow
@@ -1138,8 +1193,10 @@ impl FlowMonitor {
         let mut active = self.active_flows.write().await;
         if let Some(active_flow) = active.get_mut(flow_id) {
             active_flow.flow.state = FlowState::Streaming;
-            active_flow.stream_rebuilder =
-                Some(StreamRebuilder::new(format).with_save_raw_chunks(save_chunks));
+            active_flow.stream_rebuilder = Some(
+                StreamRebuilder::new(format)
+                    .with_save_raw_chunks(save_chunks && active_flow.capture_body),
+            );
 
             // 发送更新事件
             let _ = self.event_sender.send(FlowEvent::FlowUpdated {
@@ -1194,11 +1251,16 @@ impl FlowMonitor {
             let now = Utc::now();
 
             // 如果有流式重建器，使用重建的响应
-            let final_response = if let Some(rebuilder) = active_flow.stream_rebuilder.take() {
+            let mut final_response = if let Some(rebuilder) = active_flow.stream_rebuilder.take() {
                 Some(rebuilder.finish())
             } else {
                 response
             };
+            if !active_flow.capture_body {
+                if let Some(response) = final_response.as_mut() {
+                    strip_response_body(response);
+                }
+            }
 
             // 更新 Flow
             active_flow.flow.response = final_response.clone();
@@ -1382,7 +1444,19 @@ impl FlowMonitor {
     }
 
     /// 添加标签
+    ///
+    /// 进行中的 Flow 直接更新，完成后随 Flow 一起保存
     pub async fn add_tag(&self, flow_id: &str, tag: String) -> bool {
+        {
+            let mut active = self.active_flows.write().await;
+            if let Some(active_flow) = active.get_mut(flow_id) {
+                if !active_flow.flow.annotations.tags.contains(&tag) {
+                    active_flow.flow.annotations.tags.push(tag);
+                }
+                return true;
+            }
+        }
+
         let store = self.memory_store.read().await;
         store.update(flow_id, |flow| {
             if !flow.annotations.tags.contains(&tag) {
@@ -1619,6 +1693,40 @@ mod tests {
         assert_eq!(monitor.active_flow_count().await, 1);
     }
 
+    #[tokio::test]
+    async fn test_flow_without_body_keeps_metadata() {
+        let config = FlowMonitorConfig::default();
+        let monitor = FlowMonitor::new(config, None);
+
+        let request = create_test_request("gpt-4", "/v1/chat/completions");
+        let metadata = create_test_metadata(ProviderType::OpenAI);
+        let flow_id = monitor
+            .start_flow_without_body(request, metadata)
+            .await
+            .unwrap();
+
+        let mut response = LLMResponse {
+            content: "secret answer".to_string(),
+            body: serde_json::json!({"choices": []}),
+            ..Default::default()
+        };
+        response.usage.input_tokens = 12;
+        response.usage.output_tokens = 34;
+        monitor.complete_flow(&flow_id, Some(response)).await;
+
+        let store = monitor.memory_store.read().await;
+        let binding = store.get(&flow_id).unwrap();
+        let flow = binding.read().unwrap();
+        assert!(flow.request.messages.is_empty());
+        assert_eq!(flow.request.model, "gpt-4");
+        let response = flow.response.as_ref().unwrap();
+        assert!(response.content.is_empty());
+        assert!(response.body.is_null());
+        assert_eq!(response.usage.input_tokens, 12);
+        assert_eq!(response.usage.output_tokens, 34);
+        assert_eq!(flow.annotations.tags, vec![BODY_NOT_CAPTURED_TAG]);
+    }
+
     #[tokio::test]
     async fn test_complete_flow() {
         let config = FlowMonitorConfig::default();
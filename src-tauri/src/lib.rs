@@ -39,6 +39,7 @@ pub mod flow_monitor;
 pub mod memory;
 pub mod orchestrator;
 pub mod plugin;
+pub mod scheduler;
 pub mod screenshot;
 pub mod services;
 pub mod session;
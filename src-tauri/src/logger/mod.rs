@@ -1,4 +1,10 @@
 //! 日志管理模块
+//!
+//! [`LogStore`] 保存最近的日志并写入日志文件（按大小轮转、按天数清理），
+//! `tracing` 日志由 [`tracing_bridge`] 转发到这里，级别可在运行时按模块调整。
+
+pub mod tracing_bridge;
+
 use chrono::{Duration, Local, Utc};
 use flate2::write::GzEncoder;
 use flate2::Compression;
@@ -35,6 +41,9 @@ pub struct LogEntry {
     pub timestamp: String,
     pub level: String,
     pub message: String,
+    /// 关联的请求 ID（不属于任何 HTTP 请求时为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 pub struct LogStore {
@@ -77,17 +86,32 @@ impl LogStore {
         let mut store = Self::default();
         store.config.retention_days = logging.retention_days;
         store.config.enable_file_logging = logging.enabled;
+        store.config.max_file_size = logging.max_file_size_mb.max(1) * 1024 * 1024;
         store.max_logs = store.config.max_logs;
         store
     }
 
+    /// 添加日志，低于默认日志级别的忽略
+    ///
+    /// 在请求作用域内（见 [`crate::middleware::request_id`]）记录的日志自动关联当前请求 ID，
+    /// 作用域外的日志按消息中的 `request_id=<id>` 关联
     pub fn add(&mut self, level: &str, message: &str) {
+        if !tracing_bridge::is_enabled(level) {
+            return;
+        }
+        self.record(level, message, crate::middleware::current_request_id());
+    }
+
+    /// 记录日志（不检查级别）
+    fn record(&mut self, level: &str, message: &str, request_id: Option<String>) {
         let sanitized = sanitize_log_message(message);
         let now = Utc::now();
+        let request_id = request_id.or_else(|| extract_request_id(&sanitized));
         let entry = LogEntry {
             timestamp: now.to_rfc3339(),
             level: level.to_string(),
             message: sanitized.clone(),
+            request_id: request_id.clone(),
         };
 
         self.logs.push_back(entry.clone());
@@ -97,7 +121,16 @@ impl LogStore {
             if let Some(ref path) = self.log_file_path {
                 self.rotate_log_file_if_needed(path);
                 let local_time = Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
-                let log_line = format!("{} [{}] {}\n", local_time, level.to_uppercase(), sanitized);
+                let log_line = match &request_id {
+                    Some(id) => format!(
+                        "{} [{}] [req:{}] {}\n",
+                        local_time,
+                        level.to_uppercase(),
+                        id,
+                        sanitized
+                    ),
+                    None => format!("{} [{}] {}\n", local_time, level.to_uppercase(), sanitized),
+                };
 
                 if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
                     let _ = file.write_all(log_line.as_bytes());
@@ -134,10 +167,28 @@ impl LogStore {
         self.logs.iter().cloned().collect()
     }
 
+    /// 获取指定请求的全部日志（按时间顺序）
+    pub fn get_request_trace(&self, request_id: &str) -> Vec<LogEntry> {
+        self.logs
+            .iter()
+            .filter(|e| e.request_id.as_deref() == Some(request_id))
+            .cloned()
+            .collect()
+    }
+
     pub fn clear(&mut self) {
         self.logs.clear();
     }
 
+    /// 导出当前日志文件到指定路径，返回复制的字节数
+    pub fn export_log_file(&self, dest: &std::path::Path) -> Result<u64, String> {
+        let path = self.log_file_path.as_ref().ok_or("日志文件未启用")?;
+        if !path.exists() {
+            return Err("日志文件不存在".to_string());
+        }
+        fs::copy(path, dest).map_err(|e| format!("导出日志文件失败: {}", e))
+    }
+
     pub fn get_log_file_path(&self) -> Option<String> {
         self.log_file_path
             .as_ref()
@@ -263,6 +314,13 @@ impl LogStore {
 #[allow(dead_code)]
 pub type SharedLogStore = Arc<RwLock<LogStore>>;
 
+/// 从消息中提取 `request_id=<id>`
+fn extract_request_id(message: &str) -> Option<String> {
+    static RE: once_cell::sync::Lazy<Regex> =
+        once_cell::sync::Lazy::new(|| Regex::new(r"request_id=([A-Za-z0-9_.:-]+)").unwrap());
+    RE.captures(message).map(|c| c[1].to_string())
+}
+
 /// P2 安全修复：扩展日志脱敏规则，覆盖更多敏感字段
 pub fn sanitize_log_message(message: &str) -> String {
     let patterns = [
@@ -315,7 +373,47 @@ pub fn sanitize_log_message(message: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::sanitize_log_message;
+    use super::{extract_request_id, sanitize_log_message, LogStore};
+
+    fn memory_store() -> LogStore {
+        let mut store = LogStore::new();
+        store.config.enable_file_logging = false;
+        store
+    }
+
+    #[test]
+    fn test_extract_request_id() {
+        assert_eq!(
+            extract_request_id("[MOCK] request_id=abc-123 model=gpt-4o").as_deref(),
+            Some("abc-123")
+        );
+        assert_eq!(extract_request_id("plain message"), None);
+    }
+
+    #[tokio::test]
+    async fn test_request_trace_collects_scoped_logs() {
+        let mut store = memory_store();
+        store.add("info", "startup");
+        crate::middleware::request_id::scope("req-1".to_string(), async {
+            store.add("info", "first");
+            store.add("warn", "second");
+        })
+        .await;
+        store.add("info", "[STREAM_TEE] request_id=req-1 completed=true");
+        store.add("info", "[STREAM_TEE] request_id=req-2 completed=true");
+
+        let trace = store.get_request_trace("req-1");
+        let messages: Vec<&str> = trace.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "first",
+                "second",
+                "[STREAM_TEE] request_id=req-1 completed=true"
+            ]
+        );
+        assert!(store.get_logs()[0].request_id.is_none());
+    }
 
     #[test]
     fn test_sanitize_bearer_token() {
@@ -0,0 +1,288 @@
+//! tracing 日志桥接与运行时级别控制
+//!
+//! 语音输入、请求处理管道、Provider 等模块通过 `tracing` 输出日志。启动时安装全局订阅者，
+//! 把事件转发到 [`LogStore`]，与其他日志共用同一个日志文件（按大小轮转、按天数清理）
+//! 和前端日志面板。
+//!
+//! 日志级别可在运行时按模块调整（[`set_level`]），无需重启。未单独设置的模块使用默认级别
+//! （`logging.level`），直接写入 [`LogStore`] 的日志同样按默认级别过滤。
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, Layer, Registry};
+
+use super::LogStore;
+
+/// 可单独调整级别的模块及其 tracing target 前缀
+pub const LOG_MODULES: &[(&str, &[&str])] = &[
+    ("voice", &["proxycast_lib::voice", "voice_core"]),
+    (
+        "pipeline",
+        &[
+            "proxycast_lib::server",
+            "proxycast_lib::processor",
+            "proxycast_lib::router",
+            "proxycast_lib::streaming",
+        ],
+    ),
+    (
+        "providers",
+        &["proxycast_lib::providers", "proxycast_providers"],
+    ),
+];
+
+/// 日志级别设置
+#[derive(Debug, Clone, Serialize)]
+pub struct LogLevels {
+    /// 默认级别
+    pub default: String,
+    /// 各模块的级别（未单独设置时为默认级别）
+    pub modules: BTreeMap<String, String>,
+    /// 单独设置过级别的模块
+    pub overridden: Vec<String>,
+}
+
+struct LevelSettings {
+    default: LevelFilter,
+    modules: BTreeMap<&'static str, LevelFilter>,
+}
+
+static LEVELS: RwLock<LevelSettings> = RwLock::new(LevelSettings {
+    default: LevelFilter::INFO,
+    modules: BTreeMap::new(),
+});
+
+static RELOAD_HANDLE: OnceLock<reload::Handle<Targets, Registry>> = OnceLock::new();
+
+/// 转发给日志存储的事件
+struct ForwardedEvent {
+    level: String,
+    message: String,
+    request_id: Option<String>,
+}
+
+/// 把 tracing 事件转发到日志存储的层
+struct ForwardLayer {
+    sender: mpsc::UnboundedSender<ForwardedEvent>,
+}
+
+impl<S: Subscriber> Layer<S> for ForwardLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let _ = self.sender.send(ForwardedEvent {
+            level: event.metadata().level().as_str().to_ascii_lowercase(),
+            message: visitor.finish(),
+            request_id: crate::middleware::current_request_id(),
+        });
+    }
+}
+
+/// 收集事件消息和附加字段（`message key=value ...`）
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        if self.fields.is_empty() {
+            self.message
+        } else if self.message.is_empty() {
+            self.fields.trim_start().to_string()
+        } else {
+            format!("{}{}", self.message, self.fields)
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// 安装全局 tracing 订阅者，把日志转发到日志存储
+///
+/// 只能安装一次，重复调用或已有其他订阅者时忽略
+pub fn init(default_level: &str, logs: Arc<tokio::sync::RwLock<LogStore>>) {
+    if let Ok(level) = parse_level(default_level) {
+        LEVELS.write().default = level;
+    }
+
+    let (filter, handle) = reload::Layer::new(build_targets(&LEVELS.read()));
+    let (sender, mut receiver) = mpsc::unbounded_channel::<ForwardedEvent>();
+    let layer = ForwardLayer { sender }.with_filter(filter);
+    if tracing_subscriber::registry()
+        .with(layer)
+        .try_init()
+        .is_err()
+    {
+        // 日志交给已安装的订阅者处理
+        tracing::debug!("[日志] tracing 订阅者已存在，跳过安装");
+        return;
+    }
+    let _ = RELOAD_HANDLE.set(handle);
+
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = receiver.recv().await {
+            logs.write()
+                .await
+                .record(&event.level, &event.message, event.request_id);
+        }
+    });
+}
+
+/// 当前日志级别设置
+pub fn levels() -> LogLevels {
+    let settings = LEVELS.read();
+    LogLevels {
+        default: level_name(settings.default),
+        modules: LOG_MODULES
+            .iter()
+            .map(|(module, _)| {
+                let level = settings
+                    .modules
+                    .get(module)
+                    .copied()
+                    .unwrap_or(settings.default);
+                (module.to_string(), level_name(level))
+            })
+            .collect(),
+        overridden: settings.modules.keys().map(|m| m.to_string()).collect(),
+    }
+}
+
+/// 设置日志级别
+///
+/// `module` 为空时设置默认级别；`level` 为 `default` 时取消模块的单独设置
+pub fn set_level(module: Option<&str>, level: &str) -> Result<LogLevels, String> {
+    {
+        let mut settings = LEVELS.write();
+        match module {
+            None => settings.default = parse_level(level)?,
+            Some(module) => {
+                let (name, _) = LOG_MODULES
+                    .iter()
+                    .find(|(name, _)| *name == module)
+                    .ok_or_else(|| format!("未知的日志模块: {}", module))?;
+                if level == "default" {
+                    settings.modules.remove(name);
+                } else {
+                    settings.modules.insert(name, parse_level(level)?);
+                }
+            }
+        }
+    }
+
+    if let Some(handle) = RELOAD_HANDLE.get() {
+        handle
+            .reload(build_targets(&LEVELS.read()))
+            .map_err(|e| format!("更新日志级别失败: {}", e))?;
+    }
+    Ok(levels())
+}
+
+/// 直接写入日志存储的日志是否达到默认级别
+pub fn is_enabled(level: &str) -> bool {
+    let level = match level.to_ascii_lowercase().as_str() {
+        "error" => Level::ERROR,
+        "warn" | "warning" => Level::WARN,
+        "debug" => Level::DEBUG,
+        "trace" => Level::TRACE,
+        _ => Level::INFO,
+    };
+    level <= LEVELS.read().default
+}
+
+fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    level
+        .trim()
+        .parse()
+        .map_err(|_| format!("无效的日志级别: {}", level))
+}
+
+fn level_name(level: LevelFilter) -> String {
+    level
+        .into_level()
+        .map(|l| l.as_str().to_ascii_lowercase())
+        .unwrap_or_else(|| "off".to_string())
+}
+
+fn build_targets(settings: &LevelSettings) -> Targets {
+    settings
+        .modules
+        .iter()
+        .flat_map(|(module, level)| {
+            LOG_MODULES
+                .iter()
+                .filter(move |(name, _)| name == module)
+                .flat_map(|(_, targets)| targets.iter())
+                .map(move |target| (*target, *level))
+        })
+        .fold(
+            Targets::new().with_default(settings.default),
+            |t, (target, level)| t.with_target(target, level),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_targets_applies_module_overrides() {
+        let mut modules = BTreeMap::new();
+        modules.insert("voice", LevelFilter::DEBUG);
+        let targets = build_targets(&LevelSettings {
+            default: LevelFilter::WARN,
+            modules,
+        });
+
+        assert!(targets.would_enable("voice_core::recorder", &Level::DEBUG));
+        assert!(targets.would_enable("proxycast_lib::voice::commands", &Level::DEBUG));
+        assert!(!targets.would_enable("proxycast_lib::providers::kiro", &Level::INFO));
+        assert!(targets.would_enable("proxycast_lib::providers::kiro", &Level::WARN));
+    }
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(parse_level("debug"), Ok(LevelFilter::DEBUG));
+        assert_eq!(parse_level("OFF"), Ok(LevelFilter::OFF));
+        assert!(parse_level("verbose").is_err());
+        assert_eq!(level_name(LevelFilter::WARN), "warn");
+        assert_eq!(level_name(LevelFilter::OFF), "off");
+    }
+
+    #[test]
+    fn test_message_visitor_appends_fields() {
+        let visitor = MessageVisitor {
+            message: "已连接".to_string(),
+            fields: " provider=\"kiro\"".to_string(),
+        };
+        assert_eq!(visitor.finish(), "已连接 provider=\"kiro\"");
+    }
+}
@@ -0,0 +1,125 @@
+//! 上游并发准入中间件
+//!
+//! 对话请求（`/v1/chat/completions`、`/v1/messages`）进入处理器前按 `X-Proxycast-Priority`
+//! 向 [`AdmissionGate`] 申请上游并发：
+//! - 未带请求头按 `normal` 处理；取值非法时直接放行，由处理器返回 400
+//! - 排队超时返回 503
+//! - 非流式响应在处理器返回时归还并发，流式响应在正文结束（或客户端断开）时归还
+
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::{
+    body::{Body, HttpBody},
+    http::{header, HeaderValue, Request, Response},
+    response::IntoResponse,
+};
+use futures::future::BoxFuture;
+use futures::StreamExt;
+use tower::{Layer, Service};
+
+use crate::server::api_error::{ApiError, ApiErrorCode};
+use crate::server::overrides::{RequestPriority, HEADER_PRIORITY};
+use crate::services::admission_service::{AdmissionError, AdmissionGate};
+
+/// 排队超时时建议客户端的重试间隔（秒）
+const RETRY_AFTER_SECS: &str = "5";
+
+/// 准入层
+#[derive(Clone)]
+pub struct AdmissionLayer {
+    gate: Arc<AdmissionGate>,
+}
+
+impl AdmissionLayer {
+    /// 创建使用指定闸门的准入层
+    pub fn new(gate: Arc<AdmissionGate>) -> Self {
+        Self { gate }
+    }
+}
+
+impl<S> Layer<S> for AdmissionLayer {
+    type Service = AdmissionService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AdmissionService {
+            inner,
+            gate: self.gate.clone(),
+        }
+    }
+}
+
+/// 准入服务
+#[derive(Clone)]
+pub struct AdmissionService<S> {
+    inner: S,
+    gate: Arc<AdmissionGate>,
+}
+
+impl<S> Service<Request<Body>> for AdmissionService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let priority = match req.headers().get(HEADER_PRIORITY) {
+            None => RequestPriority::default(),
+            Some(value) => match value.to_str().ok().and_then(|v| v.parse().ok()) {
+                Some(priority) => priority,
+                None => return Box::pin(self.inner.call(req)),
+            },
+        };
+
+        // 排队期间不占用服务，按 tower 约定换出已就绪的实例
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let gate = self.gate.clone();
+
+        Box::pin(async move {
+            let permit = match gate.acquire(priority).await {
+                Ok(permit) => permit,
+                Err(e) => {
+                    tracing::warn!("[ADMISSION] {}", e);
+                    return Ok(overloaded_response(&e));
+                }
+            };
+            if !permit.waited().is_zero() {
+                tracing::info!(
+                    "[ADMISSION] {} 优先级请求排队 {}ms 后放行",
+                    priority.as_str(),
+                    permit.waited().as_millis()
+                );
+            }
+
+            let response = inner.call(req).await?;
+            // 正文已在内存中，处理器返回时上游请求已完成
+            if response.body().size_hint().exact().is_some() {
+                return Ok(response);
+            }
+            let (parts, body) = response.into_parts();
+            let body = Body::from_stream(body.into_data_stream().map(move |chunk| {
+                let _ = &permit;
+                chunk
+            }));
+            Ok(Response::from_parts(parts, body))
+        })
+    }
+}
+
+/// 排队超时的响应
+fn overloaded_response(error: &AdmissionError) -> Response<Body> {
+    let mut response = ApiError::new(ApiErrorCode::Overloaded, error.to_string()).into_response();
+    response.headers_mut().insert(
+        header::RETRY_AFTER,
+        HeaderValue::from_static(RETRY_AFTER_SECS),
+    );
+    response
+}
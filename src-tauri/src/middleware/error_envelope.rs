@@ -0,0 +1,107 @@
+//! 错误响应统一中间件
+//!
+//! 状态码不小于 400 且正文已完整生成的响应，按 [`normalize_error_body`] 整理为统一的错误结构，
+//! 补全错误码、Provider、是否可重试和请求 ID。处理器在上游失败时通过响应扩展
+//! [`ErrorProvider`] 标明 Provider；流式正文和超过 [`MAX_ERROR_BODY_BYTES`] 的正文原样返回。
+
+use std::task::{Context, Poll};
+
+use axum::{
+    body::{Body, HttpBody},
+    http::{header, HeaderValue, Request, Response},
+};
+use futures::future::BoxFuture;
+use tower::{Layer, Service};
+
+use crate::middleware::current_request_id;
+use crate::server::api_error::{normalize_error_body, ErrorProvider};
+
+/// 整理的错误正文上限
+const MAX_ERROR_BODY_BYTES: u64 = 256 * 1024;
+
+/// 错误响应统一层
+#[derive(Clone, Default)]
+pub struct ErrorEnvelopeLayer;
+
+impl ErrorEnvelopeLayer {
+    /// 创建新的错误响应统一层
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for ErrorEnvelopeLayer {
+    type Service = ErrorEnvelopeService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ErrorEnvelopeService { inner }
+    }
+}
+
+/// 错误响应统一服务
+#[derive(Clone)]
+pub struct ErrorEnvelopeService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for ErrorEnvelopeService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let future = self.inner.call(req);
+        Box::pin(async move {
+            let response = future.await?;
+            let is_error =
+                response.status().is_client_error() || response.status().is_server_error();
+            let complete = response
+                .body()
+                .size_hint()
+                .exact()
+                .is_some_and(|len| len <= MAX_ERROR_BODY_BYTES);
+            if !is_error || !complete {
+                return Ok(response);
+            }
+            Ok(normalize(response).await)
+        })
+    }
+}
+
+/// 整理错误响应，正文读取失败时返回空正文的统一错误
+async fn normalize(response: Response<Body>) -> Response<Body> {
+    let (mut parts, body) = response.into_parts();
+    let bytes = axum::body::to_bytes(body, MAX_ERROR_BODY_BYTES as usize)
+        .await
+        .unwrap_or_default();
+    let text = String::from_utf8_lossy(&bytes);
+    let body = if text.trim().is_empty() {
+        serde_json::json!({})
+    } else {
+        serde_json::from_str(&text)
+            .unwrap_or_else(|_| serde_json::Value::String(text.trim().to_string()))
+    };
+
+    let provider = parts.extensions.get::<ErrorProvider>().map(|p| p.0.clone());
+    let normalized = normalize_error_body(
+        parts.status,
+        body,
+        provider.as_deref(),
+        current_request_id().as_deref(),
+    );
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    Response::from_parts(parts, Body::from(normalized.to_string()))
+}
@@ -1,10 +1,18 @@
 //! Middleware 模块
 //!
-//! 提供 HTTP 请求处理的中间件组件
+//! 提供 HTTP 请求处理的中间件组件（管理 API 认证、请求 ID 关联、上游并发准入、错误响应统一、流式响应合并）
 
+pub mod admission;
+pub mod error_envelope;
 pub mod management_auth;
+pub mod request_id;
+pub mod stream_aggregation;
 
 #[cfg(test)]
 mod tests;
 
+pub use admission::AdmissionLayer;
+pub use error_envelope::ErrorEnvelopeLayer;
 pub use management_auth::ManagementAuthLayer;
+pub use request_id::{current_client_request_id, current_request_id, RequestIdLayer};
+pub use stream_aggregation::StreamAggregationLayer;
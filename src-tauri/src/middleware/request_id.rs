@@ -0,0 +1,140 @@
+//! 请求 ID 中间件
+//!
+//! 在入口为每个 HTTP 请求分配关联 ID：
+//! - 请求 ID 总是由服务端生成（UUID），诊断包保留和遥测采样都以它为键，不受客户端控制
+//! - 客户端通过 `X-Proxycast-Request-Id` / `X-Request-Id` 传入的合法 ID 另行记为客户端请求 ID，
+//!   只用于和客户端日志关联（`request` span 的 `client_request_id` 字段、
+//!   [`crate::processor::RequestContext::client_request_id`]）
+//! - 把服务端 ID 写回请求头，供处理器和 [`crate::processor::RequestContext`] 使用
+//! - 在 `request` tracing span 和任务局部变量中携带，日志存储据此为每行日志标注请求 ID
+//! - 写入响应头 `X-Proxycast-Request-Id`
+//!
+//! 流式响应的正文在处理器返回后才被读取，此时已离开任务局部作用域，
+//! 这部分日志需要在消息中带上 `request_id=<id>`（见 [`crate::logger`]）。
+
+use crate::services::postmortem_service::REQUEST_ID_HEADER;
+use axum::{
+    body::Body,
+    http::{HeaderMap, HeaderValue, Request, Response},
+};
+use futures::future::BoxFuture;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::Instrument;
+
+/// 通用的请求 ID 请求头
+const GENERIC_REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// 客户端传入的请求 ID 最大长度
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+    static CURRENT_CLIENT_REQUEST_ID: Option<String>;
+}
+
+/// 当前任务所处理请求的 ID（不在请求作用域内时为空）
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// 当前任务所处理请求的客户端请求 ID（客户端未传入或不在请求作用域内时为空）
+pub fn current_client_request_id() -> Option<String> {
+    CURRENT_CLIENT_REQUEST_ID
+        .try_with(|id| id.clone())
+        .ok()
+        .flatten()
+}
+
+/// 在指定请求 ID 的作用域内执行
+pub async fn scope<F: std::future::Future>(request_id: String, future: F) -> F::Output {
+    CURRENT_REQUEST_ID.scope(request_id, future).await
+}
+
+/// 从请求头读取客户端传入的请求 ID
+///
+/// 只接受不超过 128 个字符的字母、数字和 `-_.:`，避免日志注入
+pub fn request_id_from_headers(headers: &HeaderMap) -> Option<String> {
+    [REQUEST_ID_HEADER, GENERIC_REQUEST_ID_HEADER]
+        .iter()
+        .filter_map(|name| headers.get(*name)?.to_str().ok())
+        .map(str::trim)
+        .find(|id| {
+            !id.is_empty()
+                && id.len() <= MAX_REQUEST_ID_LEN
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+        })
+        .map(str::to_string)
+}
+
+/// 请求 ID 层
+#[derive(Clone, Default)]
+pub struct RequestIdLayer;
+
+impl RequestIdLayer {
+    /// 创建新的请求 ID 层
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService { inner }
+    }
+}
+
+/// 请求 ID 服务
+#[derive(Clone)]
+pub struct RequestIdService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for RequestIdService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        let client_request_id = request_id_from_headers(req.headers());
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let header_value = HeaderValue::from_str(&request_id).ok();
+        if let Some(value) = &header_value {
+            req.headers_mut().insert(REQUEST_ID_HEADER, value.clone());
+        }
+
+        let span = tracing::info_span!(
+            "request",
+            request_id = %request_id,
+            client_request_id = tracing::field::Empty,
+            method = %req.method(),
+            path = %req.uri().path(),
+        );
+        if let Some(client_request_id) = &client_request_id {
+            span.record("client_request_id", client_request_id.as_str());
+        }
+        let future = CURRENT_CLIENT_REQUEST_ID
+            .scope(client_request_id, scope(request_id, self.inner.call(req)))
+            .instrument(span);
+
+        Box::pin(async move {
+            let mut response = future.await?;
+            if let Some(value) = header_value {
+                response.headers_mut().insert(REQUEST_ID_HEADER, value);
+            }
+            Ok(response)
+        })
+    }
+}
@@ -0,0 +1,84 @@
+//! 流式响应合并中间件
+//!
+//! 请求路径匹配 `stream_aggregation` 中的规则时，把 `text/event-stream` 响应交给
+//! [`aggregate_stream`] 按时间窗口或累积字符数合并后再转发；其他响应原样返回。
+//! 合并发生在处理器之外，Flow 监控和 Token 统计看到的仍是上游的原始 chunk。
+
+use std::task::{Context, Poll};
+
+use axum::{
+    body::Body,
+    http::{header, Request, Response},
+};
+use futures::future::BoxFuture;
+use tower::{Layer, Service};
+
+use crate::services::stream_aggregation_service;
+use crate::streaming::aggregate_stream;
+
+/// 流式响应合并层
+#[derive(Clone, Default)]
+pub struct StreamAggregationLayer;
+
+impl StreamAggregationLayer {
+    /// 创建新的流式响应合并层
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for StreamAggregationLayer {
+    type Service = StreamAggregationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        StreamAggregationService { inner }
+    }
+}
+
+/// 流式响应合并服务
+#[derive(Clone)]
+pub struct StreamAggregationService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for StreamAggregationService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let settings = stream_aggregation_service::settings_for(req.uri().path());
+        let future = self.inner.call(req);
+        Box::pin(async move {
+            let response = future.await?;
+            let Some(settings) = settings else {
+                return Ok(response);
+            };
+            let is_sse = response
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| v.starts_with("text/event-stream"));
+            if !is_sse {
+                return Ok(response);
+            }
+
+            let (mut parts, body) = response.into_parts();
+            parts.headers.remove(header::CONTENT_LENGTH);
+            let stream = aggregate_stream(
+                body.into_data_stream(),
+                settings.interval,
+                settings.max_chars,
+            );
+            Ok(Response::from_parts(parts, Body::from_stream(stream)))
+        })
+    }
+}
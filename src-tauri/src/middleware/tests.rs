@@ -419,3 +419,294 @@ mod unit_tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 }
+
+#[cfg(test)]
+mod request_id_tests {
+    use super::*;
+    use crate::middleware::request_id::request_id_from_headers;
+    use crate::middleware::{current_client_request_id, current_request_id, RequestIdLayer};
+    use crate::services::postmortem_service::REQUEST_ID_HEADER;
+
+    /// 把处理器看到的请求头、任务局部请求 ID 和客户端请求 ID 写入响应正文
+    #[derive(Clone)]
+    struct EchoRequestIdService;
+
+    impl Service<Request<Body>> for EchoRequestIdService {
+        type Response = Response<Body>;
+        type Error = std::convert::Infallible;
+        type Future = std::pin::Pin<
+            Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+        >;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: Request<Body>) -> Self::Future {
+            let header = req
+                .headers()
+                .get(REQUEST_ID_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            Box::pin(async move {
+                let current = current_request_id().unwrap_or_default();
+                let client = current_client_request_id().unwrap_or_default();
+                Ok(Response::new(Body::from(format!(
+                    "{header}|{current}|{client}"
+                ))))
+            })
+        }
+    }
+
+    async fn call_with_header(header: Option<(&str, &str)>) -> (String, String) {
+        let mut service = RequestIdLayer::new().layer(EchoRequestIdService);
+        let mut builder = Request::builder().uri("/v1/chat/completions");
+        if let Some((name, value)) = header {
+            builder = builder.header(name, value);
+        }
+        let response = service
+            .call(builder.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let response_id = response.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (response_id, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_request_id_generated_when_missing() {
+        let (response_id, body) = call_with_header(None).await;
+        assert!(uuid::Uuid::parse_str(&response_id).is_ok());
+        assert_eq!(body, format!("{response_id}|{response_id}|"));
+        assert!(current_request_id().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_client_request_id_kept_separately() {
+        let (response_id, body) = call_with_header(Some(("x-request-id", "client-42"))).await;
+        // 客户端 ID 不会成为请求 ID，避免碰撞诊断包和采样
+        assert!(uuid::Uuid::parse_str(&response_id).is_ok());
+        assert_eq!(body, format!("{response_id}|{response_id}|client-42"));
+        assert!(current_client_request_id().is_none());
+    }
+
+    #[test]
+    fn test_request_id_rejects_unsafe_values() {
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert("x-request-id", "bad id\twith spaces".parse().unwrap());
+        assert_eq!(request_id_from_headers(&headers), None);
+
+        headers.insert("x-request-id", "a".repeat(129).parse().unwrap());
+        assert_eq!(request_id_from_headers(&headers), None);
+
+        headers.insert(REQUEST_ID_HEADER, "ok-1".parse().unwrap());
+        assert_eq!(request_id_from_headers(&headers).as_deref(), Some("ok-1"));
+    }
+}
+
+#[cfg(test)]
+mod admission_tests {
+    use super::*;
+    use crate::config::AdmissionControlConfig;
+    use crate::middleware::AdmissionLayer;
+    use crate::services::admission_service::{AdmissionGate, LaneCounts};
+    use std::sync::Arc;
+
+    /// 路径为 `/stream` 时返回流式正文，否则返回固定正文
+    #[derive(Clone)]
+    struct StubChatService;
+
+    impl Service<Request<Body>> for StubChatService {
+        type Response = Response<Body>;
+        type Error = std::convert::Infallible;
+        type Future = std::pin::Pin<
+            Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+        >;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: Request<Body>) -> Self::Future {
+            let body = if req.uri().path() == "/stream" {
+                Body::from_stream(futures::stream::iter(vec![
+                    Ok::<_, std::convert::Infallible>("data: 1\n\n"),
+                    Ok("data: [DONE]\n\n"),
+                ]))
+            } else {
+                Body::from("{}")
+            };
+            Box::pin(async move { Ok(Response::new(body)) })
+        }
+    }
+
+    fn gate() -> Arc<AdmissionGate> {
+        Arc::new(AdmissionGate::new(AdmissionControlConfig {
+            max_concurrent: 1,
+            reserved_high: 0,
+            queue_timeout_secs: 1,
+            ..AdmissionControlConfig::default()
+        }))
+    }
+
+    fn request(path: &str, priority: Option<&str>) -> Request<Body> {
+        let mut builder = Request::builder().uri(path);
+        if let Some(priority) = priority {
+            builder = builder.header("x-proxycast-priority", priority);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_admission_holds_permit_until_stream_ends() {
+        let gate = gate();
+        let mut service = AdmissionLayer::new(gate.clone()).layer(StubChatService);
+
+        // 非流式响应返回时已归还并发
+        let response = service.call(request("/json", None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(gate.status().in_flight, LaneCounts::default());
+
+        // 流式响应在正文读完后归还
+        let response = service
+            .call(request("/stream", Some("high")))
+            .await
+            .unwrap();
+        assert_eq!(gate.status().in_flight.high, 1);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"data: 1\n\ndata: [DONE]\n\n");
+        assert_eq!(gate.status().in_flight, LaneCounts::default());
+    }
+
+    #[tokio::test]
+    async fn test_admission_queue_timeout_returns_503() {
+        let gate = gate();
+        let mut service = AdmissionLayer::new(gate.clone()).layer(StubChatService);
+
+        let streaming = service.call(request("/stream", Some("low"))).await.unwrap();
+        let response = service.call(request("/json", Some("low"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()["retry-after"], "5");
+
+        // 非法优先级不排队，交给处理器返回 400
+        let response = service
+            .call(request("/json", Some("urgent")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 客户端断开时归还并发
+        drop(streaming);
+        assert_eq!(gate.status().in_flight, LaneCounts::default());
+    }
+}
+
+#[cfg(test)]
+mod error_envelope_tests {
+    use super::*;
+    use crate::middleware::{ErrorEnvelopeLayer, RequestIdLayer};
+    use crate::server::api_error::{ApiError, ApiErrorCode, ErrorProvider};
+    use crate::services::postmortem_service::REQUEST_ID_HEADER;
+    use axum::{response::IntoResponse, routing::get, Json, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/ok", get(|| async { "ok" }))
+            .route(
+                "/upstream",
+                get(|| async {
+                    let mut response = (
+                        StatusCode::TOO_MANY_REQUESTS,
+                        Json(serde_json::json!({"error": {"message": "slow down"}})),
+                    )
+                        .into_response();
+                    response
+                        .extensions_mut()
+                        .insert(ErrorProvider("deepseek".to_string()));
+                    response
+                }),
+            )
+            .route(
+                "/text",
+                get(|| async { (StatusCode::BAD_REQUEST, "bad input").into_response() }),
+            )
+            .route(
+                "/typed",
+                get(|| async {
+                    ApiError::new(ApiErrorCode::MissingApiKey, "No API key").anthropic()
+                }),
+            )
+            .layer(ErrorEnvelopeLayer::new())
+            .layer(RequestIdLayer::new())
+    }
+
+    async fn call(path: &str) -> (StatusCode, serde_json::Value, String) {
+        let request = Request::builder()
+            .uri(path)
+            .header("x-request-id", "req-7")
+            .body(Body::empty())
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        let status = response.status();
+        let request_id = response.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&body).unwrap_or_default(),
+            request_id,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_error_responses_normalized() {
+        let (status, body, request_id) = call("/upstream").await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body["error"]["message"], "slow down");
+        assert_eq!(body["error"]["code"], "upstream_rate_limited");
+        assert_eq!(body["error"]["provider"], "deepseek");
+        assert_eq!(body["error"]["retryable"], true);
+        assert_eq!(body["error"]["request_id"], request_id.as_str());
+        assert_ne!(request_id, "req-7");
+
+        let (status, body, _) = call("/text").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["message"], "bad input");
+        assert_eq!(body["error"]["code"], "invalid_request");
+
+        let (status, body, request_id) = call("/typed").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["type"], "error");
+        assert_eq!(body["error"]["code"], "missing_api_key");
+        assert_eq!(body["error"]["request_id"], request_id.as_str());
+
+        let (status, body, _) = call("/missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["code"], "no_route");
+    }
+
+    #[tokio::test]
+    async fn test_success_untouched() {
+        let response = app()
+            .oneshot(Request::builder().uri("/ok").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"ok");
+    }
+}
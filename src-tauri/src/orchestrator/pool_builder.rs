@@ -17,6 +17,10 @@ pub enum ProviderType {
     Azure,
     Bedrock,
     Antigravity,
+    /// Moonshot（Kimi）
+    Moonshot,
+    /// 智谱 BigModel（GLM）
+    Zhipu,
     Custom,
 }
 
@@ -31,6 +35,8 @@ impl ProviderType {
             "azure" => Some(ProviderType::Azure),
             "bedrock" => Some(ProviderType::Bedrock),
             "antigravity" => Some(ProviderType::Antigravity),
+            "moonshot" | "moonshotai" | "kimi" => Some(ProviderType::Moonshot),
+            "zhipu" | "zhipuai" | "glm" => Some(ProviderType::Zhipu),
             _ => Some(ProviderType::Custom),
         }
     }
@@ -45,6 +51,8 @@ impl ProviderType {
             ProviderType::Azure => "Azure",
             ProviderType::Bedrock => "Bedrock",
             ProviderType::Antigravity => "Antigravity",
+            ProviderType::Moonshot => "Moonshot",
+            ProviderType::Zhipu => "Zhipu",
             ProviderType::Custom => "Custom",
         }
     }
@@ -243,6 +251,58 @@ pub fn builtin_provider_definitions() -> Vec<ProviderDefinition> {
             ],
             default_base_url: None,
         },
+        // Moonshot (Kimi)
+        ProviderDefinition {
+            provider_type: ProviderType::Moonshot,
+            display_name: "Moonshot".to_string(),
+            families: vec![
+                ModelFamily {
+                    name: "kimi-k2-thinking".to_string(),
+                    pattern: "kimi-k2-thinking*".to_string(),
+                    tier: 3,
+                    description: Some("Kimi K2 Thinking - 深度推理".to_string()),
+                },
+                ModelFamily {
+                    name: "kimi-k2".to_string(),
+                    pattern: "kimi-k2*".to_string(),
+                    tier: 2,
+                    description: Some("Kimi K2 - 均衡选择".to_string()),
+                },
+                ModelFamily {
+                    name: "moonshot-v1".to_string(),
+                    pattern: "moonshot-v1*".to_string(),
+                    tier: 1,
+                    description: Some("Moonshot V1 - 快速响应".to_string()),
+                },
+            ],
+            default_base_url: Some("https://api.moonshot.cn/v1".to_string()),
+        },
+        // Zhipu (GLM)
+        ProviderDefinition {
+            provider_type: ProviderType::Zhipu,
+            display_name: "Zhipu".to_string(),
+            families: vec![
+                ModelFamily {
+                    name: "glm-flash".to_string(),
+                    pattern: "glm-*flash*".to_string(),
+                    tier: 1,
+                    description: Some("GLM Flash - 免费快速".to_string()),
+                },
+                ModelFamily {
+                    name: "glm-air".to_string(),
+                    pattern: "glm-*air*".to_string(),
+                    tier: 2,
+                    description: Some("GLM Air - 均衡选择".to_string()),
+                },
+                ModelFamily {
+                    name: "glm".to_string(),
+                    pattern: "glm-4.*".to_string(),
+                    tier: 3,
+                    description: Some("GLM 旗舰 - 最强能力".to_string()),
+                },
+            ],
+            default_base_url: Some("https://open.bigmodel.cn/api/paas/v4".to_string()),
+        },
         // Antigravity (Google Cloud Code Assist)
         ProviderDefinition {
             provider_type: ProviderType::Antigravity,
@@ -450,6 +510,86 @@ pub fn builtin_model_metadata() -> Vec<ModelMetadata> {
             release_date: Some("2024-05-14".to_string()),
             is_latest: true,
         },
+        // Moonshot Models
+        ModelMetadata {
+            id: "kimi-k2-thinking".to_string(),
+            display_name: "Kimi K2 Thinking".to_string(),
+            provider_type: ProviderType::Moonshot,
+            family: Some("kimi-k2-thinking".to_string()),
+            context_length: Some(262144),
+            supports_vision: false,
+            supports_tools: true,
+            input_cost_per_million: Some(0.6),
+            output_cost_per_million: Some(2.5),
+            release_date: Some("2025-11-06".to_string()),
+            is_latest: true,
+        },
+        ModelMetadata {
+            id: "kimi-k2-0905-preview".to_string(),
+            display_name: "Kimi K2 0905".to_string(),
+            provider_type: ProviderType::Moonshot,
+            family: Some("kimi-k2".to_string()),
+            context_length: Some(262144),
+            supports_vision: false,
+            supports_tools: true,
+            input_cost_per_million: Some(0.6),
+            output_cost_per_million: Some(2.5),
+            release_date: Some("2025-09-05".to_string()),
+            is_latest: true,
+        },
+        ModelMetadata {
+            id: "kimi-k2-turbo-preview".to_string(),
+            display_name: "Kimi K2 Turbo".to_string(),
+            provider_type: ProviderType::Moonshot,
+            family: Some("kimi-k2".to_string()),
+            context_length: Some(262144),
+            supports_vision: false,
+            supports_tools: true,
+            input_cost_per_million: Some(2.4),
+            output_cost_per_million: Some(10.0),
+            release_date: Some("2025-09-05".to_string()),
+            is_latest: true,
+        },
+        // Zhipu Models
+        ModelMetadata {
+            id: "glm-4.6".to_string(),
+            display_name: "GLM-4.6".to_string(),
+            provider_type: ProviderType::Zhipu,
+            family: Some("glm".to_string()),
+            context_length: Some(204800),
+            supports_vision: false,
+            supports_tools: true,
+            input_cost_per_million: Some(0.6),
+            output_cost_per_million: Some(2.2),
+            release_date: Some("2025-09-30".to_string()),
+            is_latest: true,
+        },
+        ModelMetadata {
+            id: "glm-4.5-air".to_string(),
+            display_name: "GLM-4.5 Air".to_string(),
+            provider_type: ProviderType::Zhipu,
+            family: Some("glm-air".to_string()),
+            context_length: Some(131072),
+            supports_vision: false,
+            supports_tools: true,
+            input_cost_per_million: Some(0.2),
+            output_cost_per_million: Some(1.1),
+            release_date: Some("2025-07-28".to_string()),
+            is_latest: true,
+        },
+        ModelMetadata {
+            id: "glm-4.5-flash".to_string(),
+            display_name: "GLM-4.5 Flash".to_string(),
+            provider_type: ProviderType::Zhipu,
+            family: Some("glm-flash".to_string()),
+            context_length: Some(131072),
+            supports_vision: false,
+            supports_tools: true,
+            input_cost_per_million: Some(0.0),
+            output_cost_per_million: Some(0.0),
+            release_date: Some("2025-07-28".to_string()),
+            is_latest: true,
+        },
     ]
 }
 
@@ -653,6 +793,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_zhipu_and_moonshot_tiers() {
+        let definitions = builtin_provider_definitions();
+        let find = |provider_type: ProviderType| {
+            definitions
+                .iter()
+                .find(|d| d.provider_type == provider_type)
+                .unwrap()
+        };
+
+        let zhipu = find(ProviderType::Zhipu);
+        assert_eq!(zhipu.get_tier("glm-4.5-flash"), Some(ServiceTier::Mini));
+        assert_eq!(zhipu.get_tier("glm-4.5-air"), Some(ServiceTier::Pro));
+        assert_eq!(zhipu.get_tier("glm-4.6"), Some(ServiceTier::Max));
+
+        let moonshot = find(ProviderType::Moonshot);
+        assert_eq!(
+            moonshot.get_tier("kimi-k2-thinking-turbo"),
+            Some(ServiceTier::Max)
+        );
+        assert_eq!(
+            moonshot.get_tier("kimi-k2-0905-preview"),
+            Some(ServiceTier::Pro)
+        );
+        assert_eq!(ProviderType::from_str("zhipuai"), Some(ProviderType::Zhipu));
+    }
+
     #[test]
     fn test_dynamic_pool_builder() {
         let builder = DynamicPoolBuilder::new();
@@ -26,6 +26,10 @@ pub struct RequestContext {
     pub provider: Option<ProviderType>,
     /// 使用的凭证 ID
     pub credential_id: Option<String>,
+    /// 客户端传入的请求 ID（`X-Request-Id`），仅用于和客户端日志关联
+    pub client_request_id: Option<String>,
+    /// 发起请求的客户端密钥 ID（使用主密钥时为空）
+    pub client_key_id: Option<String>,
     /// 重试次数
     pub retry_count: u32,
     /// 是否为流式请求
@@ -38,8 +42,11 @@ pub struct RequestContext {
 
 impl RequestContext {
     /// 创建新的请求上下文
+    ///
+    /// 在 HTTP 请求作用域内创建时沿用入口生成的请求 ID 和客户端请求 ID，否则生成新的 ID
     pub fn new(model: String) -> Self {
-        let request_id = uuid::Uuid::new_v4().to_string();
+        let request_id = crate::middleware::current_request_id()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
         Self {
             request_id: request_id.clone(),
             start_time: Instant::now(),
@@ -48,6 +55,8 @@ impl RequestContext {
             resolved_model: model,
             provider: None,
             credential_id: None,
+            client_request_id: crate::middleware::current_client_request_id(),
+            client_key_id: None,
             retry_count: 0,
             is_stream: false,
             plugin_ctx: None,
@@ -71,6 +80,11 @@ impl RequestContext {
         self.credential_id = Some(credential_id);
     }
 
+    /// 设置发起请求的客户端密钥 ID
+    pub fn set_client_key_id(&mut self, client_key_id: String) {
+        self.client_key_id = Some(client_key_id);
+    }
+
     /// 设置解析后的模型名称
     pub fn set_resolved_model(&mut self, model: String) {
         self.resolved_model = model;
@@ -130,6 +144,7 @@ mod tests {
         assert_eq!(ctx.resolved_model, "claude-sonnet-4-5");
         assert!(ctx.provider.is_none());
         assert!(ctx.credential_id.is_none());
+        assert!(ctx.client_request_id.is_none());
         assert_eq!(ctx.retry_count, 0);
         assert!(!ctx.is_stream);
     }
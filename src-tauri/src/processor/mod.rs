@@ -9,15 +9,23 @@
 //! 2. 参数注入 (InjectionStep)
 //! 3. 路由解析 (RoutingStep)
 //! 4. 插件前置钩子 (PluginPreStep)
-//! 5. Provider 调用 (ProviderStep) - 包含重试和故障转移
-//! 6. 插件后置钩子 (PluginPostStep)
-//! 7. 统计记录 (TelemetryStep)
+//! 5. 参数整形 (ShapingStep) - 按上游和模型限制截断或移除参数
+//! 6. Provider 调用 (ProviderStep) - 包含重试和故障转移
+//! 7. 插件后置钩子 (PluginPostStep)
+//! 8. 响应异常检测 (anomaly_service::inspect) - 可选触发故障转移
+//! 9. 统计记录 (TelemetryStep)
+//!
+//! API 处理器中的 Provider 调用由看门狗 (StepWatchdog) 按请求超时限时执行，
+//! 卡住的调用被取消并返回上游超时错误。
 
 mod context;
 mod error;
 mod steps;
 
 pub use context::RequestContext;
+pub use steps::ShapingStep;
+#[allow(unused_imports)]
+pub use steps::{StepTimeoutStats, StepWatchdog};
 
 use crate::injection::Injector;
 use crate::plugin::PluginManager;
@@ -55,6 +63,10 @@ pub struct RequestProcessor {
     pub pool_service: Arc<ProviderPoolService>,
     /// 热重载协调锁（避免配置更新期间请求读取不一致的配置）
     pub reload_lock: Arc<RwLock<()>>,
+    /// 管道步骤看门狗
+    pub watchdog: Arc<StepWatchdog>,
+    /// 请求参数整形（转发前按上游和模型限制调整参数）
+    pub shaping: Arc<ShapingStep>,
 }
 
 impl RequestProcessor {
@@ -83,6 +95,8 @@ impl RequestProcessor {
             tokens,
             pool_service,
             reload_lock: Arc::new(RwLock::new(())),
+            watchdog: Self::default_watchdog(),
+            shaping: Arc::new(ShapingStep::with_defaults()),
         }
     }
 
@@ -100,9 +114,18 @@ impl RequestProcessor {
             tokens: Arc::new(ParkingLotRwLock::new(TokenTracker::with_defaults())),
             pool_service,
             reload_lock: Arc::new(RwLock::new(())),
+            watchdog: Self::default_watchdog(),
+            shaping: Arc::new(ShapingStep::with_defaults()),
         }
     }
 
+    /// 使用各步骤自身时限的看门狗，步骤耗时记入全局管道开销统计
+    fn default_watchdog() -> Arc<StepWatchdog> {
+        Arc::new(StepWatchdog::with_defaults().with_overhead_tracker(
+            crate::services::pipeline_metrics_service::get_overhead_tracker(),
+        ))
+    }
+
     /// 创建带默认路由规则的路由器
     ///
     /// 注意：不再添加硬编码的路由规则，让用户设置的默认 Provider 生效
@@ -138,6 +161,8 @@ impl RequestProcessor {
             tokens,
             pool_service,
             reload_lock: Arc::new(RwLock::new(())),
+            watchdog: Self::default_watchdog(),
+            shaping: Arc::new(ShapingStep::with_defaults()),
         }
     }
 
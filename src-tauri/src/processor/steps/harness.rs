@@ -0,0 +1,300 @@
+//! 管道步骤测试工具
+//!
+//! 用录制的请求/响应 fixture 驱动单个 [`PipelineStep`]，校验步骤执行后的
+//! 负载、上下文和错误状态码，新增 Provider 或修改步骤时无需启动完整服务。
+//!
+//! fixture 为 JSON 文件，格式如下（除 `name`、`model`、`payload` 外均可省略）：
+//!
+//! ```json
+//! {
+//!   "name": "alias_is_resolved",
+//!   "model": "gpt-4",
+//!   "stream": false,
+//!   "provider": "kiro",
+//!   "metadata": {"api_key": "test-key"},
+//!   "payload": {"model": "gpt-4", "messages": []},
+//!   "expect": {
+//!     "payload": {"model": "claude-sonnet-4-5", "messages": []},
+//!     "resolved_model": "claude-sonnet-4-5",
+//!     "provider": "kiro",
+//!     "metadata": {"injection_result": {"applied_rules": ["r1"]}},
+//!     "error_status": 401
+//!   }
+//! }
+//! ```
+//!
+//! 前置步骤的 `payload` 为请求体，后置步骤（插件、统计）为上游响应体。
+//! `expect.metadata` 只校验列出的键，其余元数据不参与比较。
+//!
+//! 本模块只在测试时编译，fixture 位于 `tests/fixtures/steps/<步骤>/`。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::traits::{PipelineStep, StepError};
+use crate::processor::RequestContext;
+use crate::ProviderType;
+
+/// 步骤测试 fixture
+#[derive(Debug, Clone, Deserialize)]
+pub struct StepFixture {
+    /// 用例名称，断言失败时用于定位
+    pub name: String,
+    /// 请求模型
+    pub model: String,
+    /// 是否为流式请求
+    #[serde(default)]
+    pub stream: bool,
+    /// 执行前已选定的 Provider（模拟前序路由步骤的结果）
+    #[serde(default)]
+    pub provider: Option<ProviderType>,
+    /// 执行前写入上下文的元数据
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
+    /// 录制的请求体或响应体
+    pub payload: serde_json::Value,
+    /// 期望结果
+    #[serde(default)]
+    pub expect: StepExpectation,
+}
+
+/// 步骤执行后的期望结果
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StepExpectation {
+    /// 执行后的完整负载
+    #[serde(default)]
+    pub payload: Option<serde_json::Value>,
+    /// 解析后的模型
+    #[serde(default)]
+    pub resolved_model: Option<String>,
+    /// 选定的 Provider
+    #[serde(default)]
+    pub provider: Option<ProviderType>,
+    /// 需要存在的元数据键及其值
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
+    /// 期望步骤失败时返回的 HTTP 状态码，未设置时期望执行成功
+    #[serde(default)]
+    pub error_status: Option<u16>,
+}
+
+impl StepFixture {
+    /// 从 JSON 文件加载 fixture
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("读取 fixture 失败 {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("解析 fixture 失败 {}: {}", path.display(), e))
+    }
+
+    /// 加载目录下所有 `.json` fixture（按文件名排序）
+    pub fn load_dir(dir: &Path) -> Result<Vec<Self>, String> {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| format!("读取 fixture 目录失败 {}: {}", dir.display(), e))?;
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+        paths.iter().map(|path| Self::load(path)).collect()
+    }
+
+    /// 根据 fixture 构建请求上下文
+    pub fn context(&self) -> RequestContext {
+        let mut ctx = RequestContext::new(self.model.clone()).with_stream(self.stream);
+        if let Some(provider) = self.provider {
+            ctx.set_provider(provider);
+        }
+        for (key, value) in &self.metadata {
+            ctx.set_metadata(key, value.clone());
+        }
+        ctx
+    }
+}
+
+/// 步骤执行结果
+#[derive(Debug)]
+pub struct StepOutcome {
+    pub ctx: RequestContext,
+    pub payload: serde_json::Value,
+    pub result: Result<(), StepError>,
+}
+
+/// 管道步骤测试工具
+pub struct StepHarness<S: PipelineStep> {
+    step: S,
+}
+
+impl<S: PipelineStep> StepHarness<S> {
+    pub fn new(step: S) -> Self {
+        Self { step }
+    }
+
+    /// 用 fixture 执行步骤，不做校验
+    pub async fn run(&self, fixture: &StepFixture) -> StepOutcome {
+        let mut ctx = fixture.context();
+        let mut payload = fixture.payload.clone();
+        let result = self.step.execute(&mut ctx, &mut payload).await;
+        StepOutcome {
+            ctx,
+            payload,
+            result,
+        }
+    }
+
+    /// 用 fixture 执行步骤并按期望校验，失败时返回差异描述
+    pub async fn check(&self, fixture: &StepFixture) -> Result<StepOutcome, String> {
+        let outcome = self.run(fixture).await;
+        let expect = &fixture.expect;
+        let mut problems = Vec::new();
+
+        match (&outcome.result, expect.error_status) {
+            (Ok(()), Some(status)) => {
+                problems.push(format!("期望失败（状态码 {}），实际执行成功", status))
+            }
+            (Err(e), None) => problems.push(format!("期望成功，实际失败: {}", e)),
+            (Err(e), Some(status)) if e.status_code() != status => problems.push(format!(
+                "期望状态码 {}，实际 {}（{}）",
+                status,
+                e.status_code(),
+                e
+            )),
+            _ => {}
+        }
+
+        if let Some(expected) = &expect.payload {
+            if expected != &outcome.payload {
+                problems.push(format!(
+                    "负载不一致\n--- 期望\n{}\n--- 实际\n{}",
+                    pretty(expected),
+                    pretty(&outcome.payload)
+                ));
+            }
+        }
+
+        if let Some(expected) = &expect.resolved_model {
+            if expected != &outcome.ctx.resolved_model {
+                problems.push(format!(
+                    "resolved_model 期望 {}，实际 {}",
+                    expected, outcome.ctx.resolved_model
+                ));
+            }
+        }
+
+        if expect.provider.is_some() && expect.provider != outcome.ctx.provider {
+            problems.push(format!(
+                "provider 期望 {:?}，实际 {:?}",
+                expect.provider, outcome.ctx.provider
+            ));
+        }
+
+        for (key, expected) in &expect.metadata {
+            match outcome.ctx.get_metadata(key) {
+                Some(actual) if actual == expected => {}
+                Some(actual) => problems.push(format!(
+                    "元数据 {} 期望 {}，实际 {}",
+                    key,
+                    pretty(expected),
+                    pretty(actual)
+                )),
+                None => problems.push(format!("缺少元数据 {}", key)),
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(outcome)
+        } else {
+            Err(format!(
+                "[{}] {} 步骤校验失败:\n{}",
+                fixture.name,
+                self.step.name(),
+                problems.join("\n")
+            ))
+        }
+    }
+
+    /// 依次校验多个 fixture，汇总所有失败
+    pub async fn check_all(&self, fixtures: &[StepFixture]) -> Result<(), String> {
+        let mut failures = Vec::new();
+        for fixture in fixtures {
+            if let Err(e) = self.check(fixture).await {
+                failures.push(e);
+            }
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures.join("\n\n"))
+        }
+    }
+}
+
+fn pretty(value: &serde_json::Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::injection::{InjectionRule, Injector};
+    use crate::processor::steps::{AuthStep, InjectionStep, RoutingStep};
+    use crate::router::{ModelMapper, Router};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    fn fixtures(step: &str) -> Vec<StepFixture> {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/steps")
+            .join(step);
+        StepFixture::load_dir(&dir).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_auth_step_fixtures() {
+        let harness = StepHarness::new(AuthStep::new("test-key".to_string()));
+        harness.check_all(&fixtures("auth")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_routing_step_fixtures() {
+        let mut mapper = ModelMapper::new();
+        mapper.add_alias("gpt-4", "claude-sonnet-4-5");
+        let harness = StepHarness::new(RoutingStep::new(
+            Arc::new(RwLock::new(Router::new(ProviderType::Kiro))),
+            Arc::new(RwLock::new(mapper)),
+            Arc::new(RwLock::new("kiro".to_string())),
+        ));
+        harness.check_all(&fixtures("routing")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_injection_step_fixtures() {
+        let mut injector = Injector::new();
+        injector.add_rule(InjectionRule::new(
+            "claude-defaults",
+            "claude-*",
+            serde_json::json!({"temperature": 0.5, "max_tokens": 4096}),
+        ));
+        let harness = StepHarness::new(InjectionStep::new(Arc::new(RwLock::new(injector))));
+        harness.check_all(&fixtures("injection")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_reports_mismatch() {
+        let harness = StepHarness::new(AuthStep::new("test-key".to_string()));
+        let fixture: StepFixture = serde_json::from_value(serde_json::json!({
+            "name": "wrong_expectation",
+            "model": "gpt-4",
+            "metadata": {"api_key": "test-key"},
+            "payload": {},
+            "expect": {"error_status": 401}
+        }))
+        .unwrap();
+
+        let err = harness.check(&fixture).await.unwrap_err();
+        assert!(err.contains("wrong_expectation"));
+        assert!(err.contains("401"));
+    }
+}
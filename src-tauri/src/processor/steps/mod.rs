@@ -3,12 +3,16 @@
 //! 定义请求处理管道中的各个步骤
 
 mod auth;
+#[cfg(test)]
+mod harness;
 mod injection;
 mod plugin;
 mod provider;
 mod routing;
+mod shaping;
 mod telemetry;
 mod traits;
+mod watchdog;
 
 // 这些类型目前未在外部使用，但保留以供将来扩展
 #[allow(unused_imports)]
@@ -22,6 +26,10 @@ pub use provider::ProviderStep;
 #[allow(unused_imports)]
 pub use routing::RoutingStep;
 #[allow(unused_imports)]
+pub use shaping::{ShapingStep, SHAPING_METADATA};
+#[allow(unused_imports)]
 pub use telemetry::TelemetryStep;
 #[allow(unused_imports)]
-pub use traits::PipelineStep;
+pub use traits::{PipelineStep, DEFAULT_STEP_BUDGET};
+#[allow(unused_imports)]
+pub use watchdog::{StepTimeoutStats, StepWatchdog, STEP_TIMEOUT_METADATA};
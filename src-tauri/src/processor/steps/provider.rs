@@ -1,6 +1,7 @@
 //! Provider 调用步骤
 //!
-//! 集成重试、故障转移和超时控制
+//! 集成重试、故障转移和超时控制。
+//! 实际的上游请求由 `server::handlers::provider_calls` 按凭证类型发出，不经过此步骤。
 
 #![allow(dead_code)]
 
@@ -15,6 +16,7 @@ use crate::ProviderType;
 use async_trait::async_trait;
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Provider 调用结果
 #[derive(Debug, Clone)]
@@ -495,6 +497,20 @@ impl PipelineStep for ProviderStep {
     fn name(&self) -> &str {
         "provider"
     }
+
+    /// 每次尝试受请求超时约束，步骤时限为所有尝试及其间隔之和，未设置请求超时时不限时
+    fn budget(&self) -> Option<Duration> {
+        let request_timeout = self.timeout.config().request_timeout()?;
+        let retry = self.retrier.config();
+        Some(
+            request_timeout * (retry.max_retries + 1)
+                + Duration::from_millis(retry.max_delay_ms) * retry.max_retries,
+        )
+    }
+
+    fn calls_upstream(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,212 @@
+//! 请求参数整形步骤
+//!
+//! 发往上游前按上游和模型的限制调整请求参数，避免上游以难懂的 400 拒绝请求：
+//! 上游的参数范围和不支持的参数来自 `proxycast_providers` 中的 `Provider::param_limits`，
+//! 模型的最大输出 Token 数来自模型注册表。
+//!
+//! 请求转发路径（`provider_calls`）在发出转换后的请求前调用 [`ShapingStep::shape`]；
+//! 作为管道步骤执行时调整内容写入请求元数据。
+
+use super::traits::{PipelineStep, StepError};
+use crate::commands::model_registry_cmd;
+use crate::models::model_registry::EnhancedModelMetadata;
+use crate::processor::RequestContext;
+use crate::ProviderType;
+use async_trait::async_trait;
+use proxycast_providers::{shaping, ParamAdjustment, ParamLimits, ProviderRegistry};
+use std::sync::Arc;
+
+/// 记录参数调整的元数据键
+pub const SHAPING_METADATA: &str = "shaping_adjustments";
+
+/// 请求参数整形步骤
+pub struct ShapingStep {
+    /// 上游 Provider 注册表
+    providers: Arc<ProviderRegistry>,
+}
+
+impl ShapingStep {
+    /// 使用内置 Provider 注册表创建
+    ///
+    /// 模型信息来自应用登记的模型注册服务（见 [`model_registry_cmd::shared_state`]），
+    /// 未登记或未加载时只按上游限制整形
+    pub fn with_defaults() -> Self {
+        Self {
+            providers: Arc::new(ProviderRegistry::builtin()),
+        }
+    }
+
+    /// 请求的参数限制
+    ///
+    /// 上游优先取模型注册表中该模型所属的 Provider，其次取 `provider_id`；
+    /// 两者都不在 Provider 注册表中时只限制最大输出 Token 数
+    pub fn limits_for(
+        &self,
+        provider_id: Option<&str>,
+        model: &str,
+        metadata: Option<&EnhancedModelMetadata>,
+    ) -> Option<ParamLimits> {
+        let provider = metadata
+            .and_then(|m| self.providers.get(&m.provider_id).ok())
+            .or_else(|| provider_id.and_then(|id| self.providers.get(id).ok()));
+        let max_output_tokens = metadata.and_then(|m| m.limits.max_output_tokens);
+
+        let limits = match provider {
+            Some(provider) => provider.param_limits(model),
+            None if max_output_tokens.is_some() => ParamLimits::default(),
+            None => return None,
+        };
+        Some(limits.with_max_output_tokens(max_output_tokens))
+    }
+
+    /// 按上游和模型的限制整形请求体，返回所做的调整
+    ///
+    /// `provider_id` 为 Provider 注册表标识，如 "openai"、"anthropic"
+    pub async fn shape(
+        &self,
+        provider_id: Option<&str>,
+        model: &str,
+        payload: &mut serde_json::Value,
+    ) -> Vec<ParamAdjustment> {
+        let metadata = match model_registry_cmd::shared_state() {
+            Some(models) => match model_registry_cmd::read_registry(&models).await.as_ref() {
+                Some(service) => service.get_model(model).await,
+                None => None,
+            },
+            None => None,
+        };
+        let Some(limits) = self.limits_for(provider_id, model, metadata.as_ref()) else {
+            return Vec::new();
+        };
+
+        let adjustments = shaping::apply(payload, &limits);
+        if !adjustments.is_empty() {
+            tracing::info!(
+                "[SHAPING] model={} provider={:?} adjustments=[{}]",
+                model,
+                provider_id,
+                adjustments
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        adjustments
+    }
+}
+
+/// 路由 Provider 类型对应的 Provider 注册表标识
+fn registry_id(provider: ProviderType) -> Option<&'static str> {
+    match provider {
+        ProviderType::OpenAI | ProviderType::Codex | ProviderType::AzureOpenai => Some("openai"),
+        ProviderType::Claude
+        | ProviderType::ClaudeOAuth
+        | ProviderType::Anthropic
+        | ProviderType::AnthropicCompatible => Some("anthropic"),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl PipelineStep for ShapingStep {
+    async fn execute(
+        &self,
+        ctx: &mut RequestContext,
+        payload: &mut serde_json::Value,
+    ) -> Result<(), StepError> {
+        let provider_id = ctx.provider.and_then(registry_id);
+        let adjustments = self
+            .shape(provider_id, &ctx.resolved_model, payload)
+            .await;
+        if !adjustments.is_empty() {
+            ctx.set_metadata(SHAPING_METADATA, serde_json::json!(adjustments));
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "shaping"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model_registry::ModelLimits;
+
+    fn step() -> ShapingStep {
+        ShapingStep::with_defaults()
+    }
+
+    fn model(id: &str, provider_id: &str, max_output_tokens: u32) -> EnhancedModelMetadata {
+        EnhancedModelMetadata::new(
+            id.to_string(),
+            id.to_string(),
+            provider_id.to_string(),
+            provider_id.to_string(),
+        )
+        .with_limits(ModelLimits {
+            max_output_tokens: Some(max_output_tokens),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_limits_from_model_registry() {
+        let step = step();
+        let name = "deepseek-reasoner";
+
+        // 模型所属 Provider 的限制 + 模型的输出上限
+        let limits = step
+            .limits_for(None, name, Some(&model(name, "deepseek", 32768)))
+            .unwrap();
+        assert_eq!(limits.max_output_tokens, Some(32768));
+        assert!(limits.unsupported.contains(&"temperature"));
+
+        // 未接入的 Provider 只限制输出 Token 数
+        let limits = step
+            .limits_for(None, name, Some(&model(name, "siliconflow", 8192)))
+            .unwrap();
+        assert_eq!(limits.max_output_tokens, Some(8192));
+        assert!(limits.temperature.is_none());
+
+        assert!(step.limits_for(None, name, None).is_none());
+
+        // 没有模型信息时按调用方给出的上游限制
+        let limits = step.limits_for(Some("deepseek"), name, None).unwrap();
+        assert!(limits.unsupported.contains(&"temperature"));
+    }
+
+    #[tokio::test]
+    async fn test_shaping_step_uses_routed_provider() {
+        let step = step();
+        let mut ctx = RequestContext::new("claude-sonnet-4-5".to_string());
+        ctx.set_provider(ProviderType::Claude);
+        let mut payload = serde_json::json!({
+            "model": "claude-sonnet-4-5",
+            "temperature": 1.5,
+            "frequency_penalty": 0.5,
+        });
+
+        step.execute(&mut ctx, &mut payload).await.unwrap();
+        assert_eq!(payload["temperature"], 1.0);
+        assert!(payload.get("frequency_penalty").is_none());
+
+        let adjustments = ctx.get_metadata(SHAPING_METADATA).unwrap();
+        assert_eq!(adjustments[0]["action"], "removed");
+        assert_eq!(adjustments[1]["param"], "temperature");
+    }
+
+    #[tokio::test]
+    async fn test_shaping_step_without_limits() {
+        let step = step();
+        let mut ctx = RequestContext::new("gemini-2.5-pro".to_string());
+        ctx.set_provider(ProviderType::Gemini);
+        let mut payload = serde_json::json!({"temperature": 5});
+
+        step.execute(&mut ctx, &mut payload).await.unwrap();
+        assert_eq!(payload["temperature"], 5);
+        assert!(ctx.get_metadata(SHAPING_METADATA).is_none());
+    }
+}
@@ -66,6 +66,11 @@ impl TelemetryStep {
             log.set_credential_id(cred_id.clone());
         }
 
+        // 设置客户端密钥 ID
+        if let Some(client_key_id) = &ctx.client_key_id {
+            log.set_client_key_id(client_key_id.clone());
+        }
+
         // 设置重试次数
         log.retry_count = ctx.retry_count;
 
@@ -6,8 +6,12 @@
 
 use crate::processor::RequestContext;
 use async_trait::async_trait;
+use std::time::Duration;
 use thiserror::Error;
 
+/// 步骤默认的执行时限
+pub const DEFAULT_STEP_BUDGET: Duration = Duration::from_secs(30);
+
 /// 步骤错误
 #[derive(Error, Debug, Clone)]
 pub enum StepError {
@@ -89,4 +93,16 @@ pub trait PipelineStep: Send + Sync {
     fn is_enabled(&self) -> bool {
         true
     }
+
+    /// 步骤的执行时限，超过时由 [`super::StepWatchdog`] 取消
+    ///
+    /// 默认为 [`DEFAULT_STEP_BUDGET`]，返回 `None` 表示不限时
+    fn budget(&self) -> Option<Duration> {
+        Some(DEFAULT_STEP_BUDGET)
+    }
+
+    /// 步骤是否等待上游，等待上游的耗时不计入管道自身开销
+    fn calls_upstream(&self) -> bool {
+        false
+    }
 }
@@ -0,0 +1,399 @@
+//! 管道步骤看门狗
+//!
+//! 每个步骤在执行时限内完成，超时（例如插件卡住、上游无响应）时取消该步骤，
+//! 记录日志并返回 [`StepError::Timeout`]，避免单个步骤拖住整个请求。
+//!
+//! 执行时限优先取看门狗中按步骤名称配置的时限，其次取步骤自身的
+//! [`PipelineStep::budget`]。时限为 0 或步骤返回 `None` 表示不限时。
+//! 超时的步骤按名称累计次数，可通过 [`StepWatchdog::report`] 查看。
+//! 设置了开销统计器时，不等待上游的步骤的耗时记入管道自身开销。
+//!
+//! 处理器中直接调用的阶段（如 API 处理器中的 Provider 调用）通过
+//! [`StepWatchdog::run_stage`] 以同样的规则限时执行。
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use super::traits::{PipelineStep, StepError};
+use crate::processor::RequestContext;
+use crate::telemetry::PipelineOverheadTracker;
+
+/// 记录最近一次超时步骤的元数据键
+pub const STEP_TIMEOUT_METADATA: &str = "step_timeout";
+
+/// 步骤超时统计
+#[derive(Debug, Clone, Serialize)]
+pub struct StepTimeoutStats {
+    /// 步骤名称
+    pub step: String,
+    /// 累计超时次数
+    pub count: u64,
+    /// 最近一次超时的请求 ID
+    pub last_request_id: String,
+    /// 最近一次的执行时限（毫秒）
+    pub last_budget_ms: u64,
+    /// 最近一次超时的时间
+    pub last_at: DateTime<Utc>,
+}
+
+/// 管道步骤看门狗
+#[derive(Debug, Default)]
+pub struct StepWatchdog {
+    /// 按步骤名称配置的执行时限，优先于步骤自身的时限
+    budgets: HashMap<String, Duration>,
+    /// 按步骤名称累计的超时统计
+    timeouts: Mutex<HashMap<String, StepTimeoutStats>>,
+    /// 管道自身开销统计
+    overhead: Option<Arc<PipelineOverheadTracker>>,
+}
+
+impl StepWatchdog {
+    /// 创建看门狗，按步骤名称覆盖执行时限
+    pub fn new(budgets: HashMap<String, Duration>) -> Self {
+        Self {
+            budgets,
+            timeouts: Mutex::new(HashMap::new()),
+            overhead: None,
+        }
+    }
+
+    /// 使用各步骤自身的执行时限
+    pub fn with_defaults() -> Self {
+        Self::default()
+    }
+
+    /// 设置指定步骤的执行时限（0 表示不限时）
+    pub fn with_budget(mut self, step: &str, budget: Duration) -> Self {
+        self.budgets.insert(step.to_string(), budget);
+        self
+    }
+
+    /// 记录不等待上游的步骤的耗时
+    pub fn with_overhead_tracker(mut self, tracker: Arc<PipelineOverheadTracker>) -> Self {
+        self.overhead = Some(tracker);
+        self
+    }
+
+    /// 步骤的执行时限，`None` 表示不限时
+    pub fn budget_for(&self, step: &dyn PipelineStep) -> Option<Duration> {
+        self.stage_budget(step.name(), step.budget())
+    }
+
+    /// 阶段的执行时限：优先取按名称配置的时限，其次取 `default`
+    fn stage_budget(&self, stage: &str, default: Option<Duration>) -> Option<Duration> {
+        self.budgets
+            .get(stage)
+            .copied()
+            .or(default)
+            .filter(|budget| !budget.is_zero())
+    }
+
+    /// 在执行时限内执行步骤，超时时取消并返回 [`StepError::Timeout`]
+    ///
+    /// 超时时步骤可能已修改了部分上下文或负载，调用方应结束本次请求
+    pub async fn run(
+        &self,
+        step: &dyn PipelineStep,
+        ctx: &mut RequestContext,
+        payload: &mut serde_json::Value,
+    ) -> Result<(), StepError> {
+        let started = Instant::now();
+        let result = self.run_within_budget(step, ctx, payload).await;
+        if let Some(overhead) = self.overhead.as_ref().filter(|_| !step.calls_upstream()) {
+            overhead.record(step.name(), started.elapsed());
+        }
+        result
+    }
+
+    async fn run_within_budget(
+        &self,
+        step: &dyn PipelineStep,
+        ctx: &mut RequestContext,
+        payload: &mut serde_json::Value,
+    ) -> Result<(), StepError> {
+        let Some(budget) = self.budget_for(step) else {
+            return step.execute(ctx, payload).await;
+        };
+
+        let result = tokio::time::timeout(budget, step.execute(ctx, payload)).await;
+        match result {
+            Ok(result) => result,
+            Err(_) => Err(self.timed_out(step.name(), ctx, budget)),
+        }
+    }
+
+    /// 在执行时限内执行处理器直接调用的阶段，超时时取消并返回 [`StepError::Timeout`]
+    ///
+    /// `budget` 为阶段自身的时限，按名称配置的时限优先；`calls_upstream` 为
+    /// `true` 的阶段不计入管道自身开销
+    pub async fn run_stage<T>(
+        &self,
+        stage: &str,
+        budget: Option<Duration>,
+        calls_upstream: bool,
+        ctx: &mut RequestContext,
+        future: impl Future<Output = T>,
+    ) -> Result<T, StepError> {
+        let started = Instant::now();
+        let result = match self.stage_budget(stage, budget) {
+            Some(budget) => tokio::time::timeout(budget, future)
+                .await
+                .map_err(|_| self.timed_out(stage, ctx, budget)),
+            None => Ok(future.await),
+        };
+        if let Some(overhead) = self.overhead.as_ref().filter(|_| !calls_upstream) {
+            overhead.record(stage, started.elapsed());
+        }
+        result
+    }
+
+    /// 记录超时并在上下文中标明超时的步骤
+    fn timed_out(&self, step: &str, ctx: &mut RequestContext, budget: Duration) -> StepError {
+        let timeout_ms = budget.as_millis() as u64;
+        tracing::error!(
+            "[WATCHDOG] request_id={} step={} 执行超过 {}ms，已取消",
+            ctx.request_id,
+            step,
+            timeout_ms
+        );
+        self.record_timeout(step, &ctx.request_id, timeout_ms);
+        ctx.set_metadata(
+            STEP_TIMEOUT_METADATA,
+            serde_json::json!({ "step": step, "budget_ms": timeout_ms }),
+        );
+        StepError::Timeout { timeout_ms }
+    }
+
+    /// 依次执行启用的步骤，任一步骤失败或超时即停止
+    pub async fn run_all(
+        &self,
+        steps: &[Arc<dyn PipelineStep>],
+        ctx: &mut RequestContext,
+        payload: &mut serde_json::Value,
+    ) -> Result<(), StepError> {
+        for step in steps.iter().filter(|step| step.is_enabled()) {
+            self.run(step.as_ref(), ctx, payload).await?;
+        }
+        Ok(())
+    }
+
+    /// 各步骤的超时统计，按超时次数从多到少排列
+    pub fn report(&self) -> Vec<StepTimeoutStats> {
+        let mut stats: Vec<_> = self.timeouts.lock().values().cloned().collect();
+        stats.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.step.cmp(&b.step)));
+        stats
+    }
+
+    /// 清空超时统计
+    pub fn reset(&self) {
+        self.timeouts.lock().clear();
+    }
+
+    fn record_timeout(&self, step: &str, request_id: &str, budget_ms: u64) {
+        let mut timeouts = self.timeouts.lock();
+        let stats = timeouts
+            .entry(step.to_string())
+            .or_insert_with(|| StepTimeoutStats {
+                step: step.to_string(),
+                count: 0,
+                last_request_id: String::new(),
+                last_budget_ms: 0,
+                last_at: Utc::now(),
+            });
+        stats.count += 1;
+        stats.last_request_id = request_id.to_string();
+        stats.last_budget_ms = budget_ms;
+        stats.last_at = Utc::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    /// 执行指定时长后写入负载的测试步骤
+    struct SleepStep {
+        name: &'static str,
+        delay: Duration,
+        budget: Option<Duration>,
+        upstream: bool,
+    }
+
+    #[async_trait]
+    impl PipelineStep for SleepStep {
+        async fn execute(
+            &self,
+            _ctx: &mut RequestContext,
+            payload: &mut serde_json::Value,
+        ) -> Result<(), StepError> {
+            tokio::time::sleep(self.delay).await;
+            payload[self.name] = serde_json::json!(true);
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn budget(&self) -> Option<Duration> {
+            self.budget
+        }
+
+        fn calls_upstream(&self) -> bool {
+            self.upstream
+        }
+    }
+
+    fn step(name: &'static str, delay_ms: u64, budget_ms: Option<u64>) -> Arc<dyn PipelineStep> {
+        Arc::new(SleepStep {
+            name,
+            delay: Duration::from_millis(delay_ms),
+            budget: budget_ms.map(Duration::from_millis),
+            upstream: false,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_step_within_budget() {
+        let watchdog = StepWatchdog::with_defaults();
+        let mut ctx = RequestContext::new("model".to_string());
+        let mut payload = serde_json::json!({});
+
+        let fast = step("fast", 1, Some(1000));
+        watchdog
+            .run(fast.as_ref(), &mut ctx, &mut payload)
+            .await
+            .unwrap();
+        assert_eq!(payload["fast"], true);
+        assert!(watchdog.report().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_hung_step_is_cancelled() {
+        let watchdog = StepWatchdog::with_defaults();
+        let mut ctx = RequestContext::new("model".to_string());
+        let mut payload = serde_json::json!({});
+
+        let hung = step("plugin_pre", 10_000, Some(20));
+        let err = watchdog
+            .run(hung.as_ref(), &mut ctx, &mut payload)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StepError::Timeout { timeout_ms: 20 }));
+        assert_eq!(err.status_code(), 408);
+        assert!(payload.get("plugin_pre").is_none());
+        assert_eq!(
+            ctx.get_metadata(STEP_TIMEOUT_METADATA).unwrap()["step"],
+            "plugin_pre"
+        );
+
+        let report = watchdog.report();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].step, "plugin_pre");
+        assert_eq!(report[0].count, 1);
+        assert_eq!(report[0].last_request_id, ctx.request_id);
+    }
+
+    #[test]
+    fn test_budget_overrides() {
+        // 配置的时限优先于步骤自身的时限
+        let watchdog = StepWatchdog::with_defaults().with_budget("slow", Duration::from_millis(10));
+        let slow = step("slow", 200, Some(1000));
+        assert_eq!(
+            watchdog.budget_for(slow.as_ref()),
+            Some(Duration::from_millis(10))
+        );
+
+        // 0 表示不限时
+        let watchdog = StepWatchdog::with_defaults().with_budget("slow", Duration::ZERO);
+        assert_eq!(watchdog.budget_for(slow.as_ref()), None);
+        assert_eq!(watchdog.budget_for(step("other", 0, None).as_ref()), None);
+    }
+
+    #[tokio::test]
+    async fn test_overhead_excludes_upstream_steps() {
+        let tracker = Arc::new(PipelineOverheadTracker::new(10));
+        let watchdog = StepWatchdog::with_defaults().with_overhead_tracker(tracker.clone());
+        let mut ctx = RequestContext::new("model".to_string());
+        let mut payload = serde_json::json!({});
+
+        let provider: Arc<dyn PipelineStep> = Arc::new(SleepStep {
+            name: "provider",
+            delay: Duration::from_millis(1),
+            budget: None,
+            upstream: true,
+        });
+        let steps = vec![
+            step("routing", 1, Some(1000)),
+            provider,
+            step("hung", 10_000, Some(20)),
+        ];
+        let _ = watchdog.run_all(&steps, &mut ctx, &mut payload).await;
+
+        // 等待上游的步骤不计入，超时的步骤同样计入
+        let report = tracker.report();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].step, "hung");
+        assert!(report[0].p95_us >= 20_000);
+        assert_eq!(report[1].step, "routing");
+    }
+
+    #[tokio::test]
+    async fn test_run_all_stops_at_timeout() {
+        let watchdog = StepWatchdog::with_defaults();
+        let mut ctx = RequestContext::new("model".to_string());
+        let mut payload = serde_json::json!({});
+        let steps = vec![
+            step("first", 1, Some(1000)),
+            step("hung", 10_000, Some(20)),
+            step("last", 1, Some(1000)),
+        ];
+
+        let result = watchdog.run_all(&steps, &mut ctx, &mut payload).await;
+        assert!(matches!(result, Err(StepError::Timeout { .. })));
+        assert_eq!(payload["first"], true);
+        assert!(payload.get("last").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_hung_stage_is_cancelled() {
+        let watchdog = StepWatchdog::with_defaults();
+        let mut ctx = RequestContext::new("model".to_string());
+
+        let value = watchdog
+            .run_stage(
+                "provider",
+                Some(Duration::from_secs(1)),
+                true,
+                &mut ctx,
+                async { 42 },
+            )
+            .await
+            .unwrap();
+        assert_eq!(value, 42);
+
+        // 按名称配置的时限优先于阶段自身的时限
+        let watchdog = watchdog.with_budget("provider", Duration::from_millis(20));
+        let err = watchdog
+            .run_stage("provider", None, true, &mut ctx, async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StepError::Timeout { timeout_ms: 20 }));
+        assert_eq!(
+            ctx.get_metadata(STEP_TIMEOUT_METADATA).unwrap()["step"],
+            "provider"
+        );
+        assert_eq!(watchdog.report()[0].step, "provider");
+    }
+}
@@ -20,6 +20,7 @@ fn test_request_processor_new() {
     assert!(Arc::strong_count(&processor.stats) >= 1);
     assert!(Arc::strong_count(&processor.tokens) >= 1);
     assert!(Arc::strong_count(&processor.pool_service) >= 1);
+    assert!(processor.watchdog.report().is_empty());
 }
 
 #[tokio::test]
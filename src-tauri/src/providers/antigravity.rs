@@ -5,6 +5,7 @@
 #![allow(dead_code)]
 
 use super::traits::{CredentialProvider, ProviderResult};
+use crate::services::http_client_service::TrackedSend;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -372,10 +373,10 @@ impl Default for AntigravityProvider {
         Self {
             credentials: AntigravityCredentials::default(),
             project_id: None,
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: crate::services::http_client_service::client(
+                "antigravity",
+                std::time::Duration::from_secs(120),
+            ),
             // 只使用生产环境和 daily 环境（参考 Antigravity-Manager）
             // 沙盒环境（autopush）需要特殊许可证，不适合普通用户
             base_urls: vec![
@@ -682,7 +683,7 @@ impl AntigravityProvider {
                 .client
                 .post("https://oauth2.googleapis.com/token")
                 .form(&params)
-                .send()
+                .send_tracked("antigravity")
                 .await;
 
             match result {
@@ -790,7 +791,7 @@ impl AntigravityProvider {
             .client
             .post("https://oauth2.googleapis.com/token")
             .form(&params)
-            .send()
+            .send_tracked("antigravity")
             .await?;
 
         if !resp.status().is_success() {
@@ -872,7 +873,7 @@ impl AntigravityProvider {
             .header("Content-Type", "application/json")
             .header("User-Agent", "antigravity/1.11.9 windows/amd64")
             .json(body)
-            .send()
+            .send_tracked("antigravity")
             .await
             .map_err(|e| {
                 eprintln!("[ANTIGRAVITY_API] 网络错误: {}", e);
@@ -2186,7 +2187,7 @@ impl StreamingProvider for AntigravityProvider {
                 .header("Accept", "text/event-stream")
                 .header("User-Agent", "antigravity/1.11.9 windows/amd64")
                 .json(&payload)
-                .send()
+                .send_tracked("antigravity")
                 .await;
 
             match result {
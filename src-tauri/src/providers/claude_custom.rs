@@ -1,6 +1,7 @@
 //! Claude Custom Provider (自定义 Claude API)
 use crate::models::anthropic::AnthropicMessagesRequest;
 use crate::models::openai::{ChatCompletionRequest, ContentPart, MessageContent};
+use crate::services::http_client_service::{self, TrackedSend};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
@@ -18,22 +19,11 @@ pub struct ClaudeCustomProvider {
     pub client: Client,
 }
 
-/// 创建配置好的 HTTP 客户端
+/// 获取共享的 HTTP 客户端
 ///
-/// 配置说明：
-/// - connect_timeout: 连接超时 30 秒
-/// - timeout: 总超时 10 分钟（流式响应可能很长）
-/// - 不设置 pool_idle_timeout 以保持连接活跃
+/// 总超时 10 分钟（流式响应可能很长），连接池和 keepalive 见 [`http_client_service`]
 fn create_http_client() -> Client {
-    Client::builder()
-        .connect_timeout(Duration::from_secs(30))
-        .timeout(Duration::from_secs(600)) // 10 分钟总超时，支持长时间流式响应
-        .tcp_keepalive(Duration::from_secs(60)) // TCP keepalive 保持连接活跃
-        .gzip(true) // 自动解压 gzip 响应
-        .brotli(true) // 自动解压 brotli 响应
-        .deflate(true) // 自动解压 deflate 响应
-        .build()
-        .unwrap_or_else(|_| Client::new())
+    http_client_service::client("claude_custom", Duration::from_secs(600))
 }
 
 impl Default for ClaudeCustomProvider {
@@ -76,59 +66,56 @@ impl ClaudeCustomProvider {
     /// 构建完整的 API URL
     /// 智能处理用户输入的 base_url，无论是否带 /v1 都能正确工作
     fn build_url(&self, endpoint: &str) -> String {
-        let base = self.get_base_url();
-        let base = base.trim_end_matches('/');
-
-        // 如果用户输入了带 /v1 的 URL，直接拼接 endpoint
-        // 否则拼接 /v1/endpoint
-        if base.ends_with("/v1") {
-            format!("{}/{}", base, endpoint)
-        } else {
-            format!("{}/v1/{}", base, endpoint)
-        }
+        proxycast_providers::anthropic::build_url(&self.get_base_url(), endpoint)
     }
 
     /// 将 OpenAI 图片 URL 格式转换为 Claude 图片格式
     ///
-    /// 支持两种格式：
+    /// 支持以下格式：
     /// 1. data URL: `data:image/jpeg;base64,xxxxx` -> Claude base64 格式
-    /// 2. HTTP URL: `https://...` -> 作为文本提示（Claude 不直接支持 URL）
+    /// 2. 本地文件: `file:///path/to/a.png` -> 读取后转为 Claude base64 格式
+    /// 3. HTTP URL: `https://...` -> Claude url 格式
+    ///
+    /// 超过 Claude 单图大小限制的图片转为文本提示
     fn convert_image_url_to_claude(url: &str) -> Option<serde_json::Value> {
-        if url.starts_with("data:") {
-            // 解析 data URL: data:image/jpeg;base64,xxxxx
-            let parts: Vec<&str> = url.splitn(2, ',').collect();
-            if parts.len() == 2 {
-                let header = parts[0]; // data:image/jpeg;base64
-                let data = parts[1]; // base64 数据
-
-                // 提取 media_type: image/jpeg, image/png, image/gif, image/webp
-                let media_type = header
-                    .strip_prefix("data:")
-                    .and_then(|s| s.split(';').next())
-                    .unwrap_or("image/jpeg");
+        use crate::converter::image::{parse_image_url, ImageRef, ANTHROPIC_MAX_IMAGE_BYTES};
 
-                tracing::debug!("[CLAUDE_IMAGE] 转换 base64 图片: media_type={}", media_type);
+        let image = match parse_image_url(url) {
+            Ok(image) => image,
+            Err(e) => {
+                tracing::warn!("[CLAUDE_IMAGE] 无法解析图片 URL: {}", e);
+                return None;
+            }
+        };
+
+        if let Err(e) = image.check_size(ANTHROPIC_MAX_IMAGE_BYTES) {
+            tracing::warn!("[CLAUDE_IMAGE] {}", e);
+            return Some(serde_json::json!({
+                "type": "text",
+                "text": format!("[Image omitted: {}]", e)
+            }));
+        }
 
-                return Some(serde_json::json!({
+        match image {
+            ImageRef::Base64 { media_type, data } => {
+                tracing::debug!("[CLAUDE_IMAGE] 转换 base64 图片: media_type={}", media_type);
+                Some(serde_json::json!({
                     "type": "image",
                     "source": {
                         "type": "base64",
                         "media_type": media_type,
                         "data": data
                     }
-                }));
+                }))
             }
-        } else if url.starts_with("http://") || url.starts_with("https://") {
-            // Claude 不直接支持 URL 图片，转为文本提示
-            tracing::warn!("[CLAUDE_IMAGE] Claude 不支持 URL 图片，转为文本: {}", url);
-            return Some(serde_json::json!({
-                "type": "text",
-                "text": format!("[Image: {}]", url)
-            }));
+            ImageRef::Url(url) => Some(serde_json::json!({
+                "type": "image",
+                "source": {
+                    "type": "url",
+                    "url": url
+                }
+            })),
         }
-
-        tracing::warn!("[CLAUDE_IMAGE] 无法解析图片 URL: {}", url);
-        None
     }
 
     /// 调用 Anthropic API（原生格式）
@@ -159,7 +146,7 @@ impl ClaudeCustomProvider {
             .header("anthropic-version", "2023-06-01")
             .header("Content-Type", "application/json")
             .json(request)
-            .send()
+            .send_tracked("claude_custom")
             .await?;
 
         // 打印响应状态
@@ -268,7 +255,7 @@ impl ClaudeCustomProvider {
             .header("anthropic-version", "2023-06-01")
             .header("Content-Type", "application/json")
             .json(&anthropic_body)
-            .send()
+            .send_tracked("claude_custom")
             .await?;
 
         // 打印响应状态
@@ -363,7 +350,7 @@ impl ClaudeCustomProvider {
             .header("anthropic-version", "2023-06-01")
             .header("Content-Type", "application/json")
             .json(request)
-            .send()
+            .send_tracked("claude_custom")
             .await?;
 
         // 打印响应状态
@@ -395,7 +382,7 @@ impl ClaudeCustomProvider {
             .header("anthropic-version", "2023-06-01")
             .header("Content-Type", "application/json")
             .json(request)
-            .send()
+            .send_tracked("claude_custom")
             .await?;
 
         if !resp.status().is_success() {
@@ -640,7 +627,7 @@ impl StreamingProvider for ClaudeCustomProvider {
             .header("Content-Type", "application/json")
             .header("Accept", "text/event-stream")
             .json(&anthropic_body)
-            .send()
+            .send_tracked("claude_custom")
             .await
             .map_err(|e| ProviderError::from_reqwest_error(&e))?;
 
@@ -19,6 +19,7 @@
 use super::error::{
     create_auth_error, create_config_error, create_token_refresh_error, ProviderError,
 };
+use crate::services::http_client_service::TrackedSend;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
@@ -125,7 +126,10 @@ impl Default for ClaudeOAuthProvider {
     fn default() -> Self {
         Self {
             credentials: ClaudeOAuthCredentials::default(),
-            client: Client::new(),
+            client: crate::services::http_client_service::client(
+                "claude_oauth",
+                std::time::Duration::from_secs(600),
+            ),
             creds_path: None,
         }
     }
@@ -245,7 +249,7 @@ impl ClaudeOAuthProvider {
             .header("Content-Type", "application/json")
             .header("Accept", "application/json")
             .json(&body)
-            .send()
+            .send_tracked("claude_oauth")
             .await
             .map_err(|e| Box::new(ProviderError::from(e)) as Box<dyn Error + Send + Sync>)?;
 
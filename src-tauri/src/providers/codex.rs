@@ -8,6 +8,7 @@
 use super::error::{
     create_auth_error, create_config_error, create_token_refresh_error, ProviderError,
 };
+use crate::services::http_client_service::TrackedSend;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
@@ -394,7 +395,10 @@ impl Default for CodexProvider {
     fn default() -> Self {
         Self {
             credentials: CodexCredentials::default(),
-            client: Client::new(),
+            client: crate::services::http_client_service::client(
+                "codex",
+                std::time::Duration::from_secs(600),
+            ),
             creds_path: None,
             callback_port: DEFAULT_CALLBACK_PORT,
         }
@@ -642,7 +646,7 @@ impl CodexProvider {
             .header("Content-Type", "application/x-www-form-urlencoded")
             .header("Accept", "application/json")
             .form(&params)
-            .send()
+            .send_tracked("codex")
             .await?;
 
         if !resp.status().is_success() {
@@ -762,7 +766,7 @@ impl CodexProvider {
             .header("Content-Type", "application/x-www-form-urlencoded")
             .header("Accept", "application/json")
             .form(&params)
-            .send()
+            .send_tracked("codex")
             .await
             .map_err(|e| Box::new(ProviderError::from(e)) as Box<dyn Error + Send + Sync>)?;
 
@@ -1141,7 +1145,7 @@ impl CodexProvider {
             }
         }
 
-        let resp = req.send().await?;
+        let resp = req.send_tracked("codex").await?;
 
         Ok(resp)
     }
@@ -9,6 +9,7 @@ use super::error::{
     create_auth_error, create_config_error, create_token_refresh_error, ProviderError,
 };
 use super::traits::{CredentialProvider, ProviderResult};
+use crate::services::http_client_service::TrackedSend;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -191,7 +192,10 @@ impl Default for GeminiProvider {
         Self {
             credentials: GeminiCredentials::default(),
             project_id: None,
-            client: Client::new(),
+            client: crate::services::http_client_service::client(
+                "gemini",
+                std::time::Duration::from_secs(600),
+            ),
         }
     }
 }
@@ -290,7 +294,7 @@ impl GeminiProvider {
             .header("Content-Type", "application/x-www-form-urlencoded")
             .header("Accept", "application/json")
             .form(&params)
-            .send()
+            .send_tracked("gemini")
             .await
             .map_err(|e| Box::new(ProviderError::from(e)) as Box<dyn Error + Send + Sync>)?;
 
@@ -395,7 +399,7 @@ impl GeminiProvider {
             .header("Authorization", format!("Bearer {token}"))
             .header("Content-Type", "application/json")
             .json(body)
-            .send()
+            .send_tracked("gemini")
             .await?;
 
         if !resp.status().is_success() {
@@ -584,7 +588,10 @@ impl GeminiApiKeyProvider {
     /// Create a new Gemini API Key provider
     pub fn new() -> Self {
         Self {
-            client: Client::new(),
+            client: crate::services::http_client_service::client(
+                "gemini_api_key",
+                std::time::Duration::from_secs(600),
+            ),
         }
     }
 
@@ -608,7 +615,7 @@ impl GeminiApiKeyProvider {
             .header("x-goog-api-key", &credential.api_key)
             .header("Content-Type", "application/json")
             .json(body)
-            .send()
+            .send_tracked("gemini_api_key")
             .await?;
 
         if !resp.status().is_success() {
@@ -639,7 +646,7 @@ impl GeminiApiKeyProvider {
             .header("x-goog-api-key", &credential.api_key)
             .header("Content-Type", "application/json")
             .json(body)
-            .send()
+            .send_tracked("gemini_api_key")
             .await?;
 
         if !resp.status().is_success() {
@@ -662,7 +669,7 @@ impl GeminiApiKeyProvider {
             .client
             .get(&url)
             .header("x-goog-api-key", &credential.api_key)
-            .send()
+            .send_tracked("gemini_api_key")
             .await?;
 
         if !resp.status().is_success() {
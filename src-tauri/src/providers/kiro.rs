@@ -6,6 +6,7 @@
 use crate::models::anthropic::AnthropicMessagesRequest;
 use crate::models::openai::*;
 use crate::providers::traits::{CredentialProvider, ProviderResult};
+use crate::services::http_client_service::TrackedSend;
 use crate::translator::kiro::anthropic::request::convert_anthropic_to_codewhisperer;
 use crate::translator::kiro::openai::request::convert_openai_to_codewhisperer;
 use async_trait::async_trait;
@@ -253,13 +254,12 @@ pub struct KiroProvider {
 
 impl Default for KiroProvider {
     fn default() -> Self {
-        // 创建带超时配置的 HTTP 客户端
+        // 共享的 HTTP 客户端，总超时 5 分钟
         // 参考 AIClient-2-API: AXIOS_TIMEOUT: 300000 (5分钟)
-        let client = Client::builder()
-            .connect_timeout(std::time::Duration::from_secs(30)) // 连接超时 30 秒
-            .timeout(std::time::Duration::from_secs(300)) // 总超时 5 分钟
-            .build()
-            .unwrap_or_else(|_| Client::new());
+        let client = crate::services::http_client_service::client(
+            "kiro",
+            std::time::Duration::from_secs(300),
+        );
 
         Self {
             credentials: KiroCredentials::default(),
@@ -770,7 +770,7 @@ impl KiroProvider {
                 .header("Accept", "*/*")
                 .header("Connection", "close")
                 .json(&body)
-                .send()
+                .send_tracked("kiro")
                 .await?
         } else {
             // Social 认证使用简单的 JSON 格式（参考 Kir-Manager）
@@ -790,7 +790,7 @@ impl KiroProvider {
                 .header("Sec-Fetch-Mode", "cors")
                 .header("Connection", "close")
                 .json(&body)
-                .send()
+                .send_tracked("kiro")
                 .await?
         };
 
@@ -1040,7 +1040,7 @@ impl KiroProvider {
             // 添加 Connection: close 避免连接复用被检测
             .header("Connection", "close")
             .json(&cw_request)
-            .send()
+            .send_tracked("kiro")
             .await?;
 
         Ok(resp)
@@ -1192,7 +1192,7 @@ impl StreamingProvider for KiroProvider {
             )
             // 注意：不要设置 Connection: close，否则会导致流式响应无法工作
             .json(&cw_request)
-            .send()
+            .send_tracked("kiro")
             .await
             .map_err(|e| {
                 tracing::error!("[KIRO_STREAM] 请求发送失败: {}", e);
@@ -1291,7 +1291,7 @@ impl KiroProvider {
                 ),
             )
             .json(&cw_request)
-            .send()
+            .send_tracked("kiro")
             .await
             .map_err(|e| {
                 tracing::error!("[KIRO_STREAM_ANTHROPIC] 请求发送失败: {}", e);
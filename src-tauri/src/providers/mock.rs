@@ -0,0 +1,395 @@
+//! 本地 Mock Provider
+//!
+//! 不调用任何上游，按模板生成 OpenAI / Anthropic 格式的模拟回复，
+//! 支持回显提示词、模拟延迟和逐块流式输出，便于前端和插件开发时离线调试。
+//!
+//! 启用后，以下请求会被 Mock Provider 处理：
+//! - `X-Proxycast-Provider: mock`
+//! - 默认 Provider 设置为 `mock`
+//! - 模型名以 `mock` 开头（如 `mock`、`mock-fast`）
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+
+use crate::config::MockProviderSettings;
+use crate::models::anthropic::AnthropicMessagesRequest;
+use crate::models::openai::{ChatCompletionRequest, ContentPart, MessageContent};
+use crate::services::token_count_service;
+
+/// Mock Provider ID
+pub const MOCK_PROVIDER_ID: &str = "mock";
+
+/// Mock Provider
+#[derive(Debug, Clone)]
+pub struct MockProvider {
+    settings: MockProviderSettings,
+}
+
+impl MockProvider {
+    pub fn new(settings: MockProviderSettings) -> Self {
+        Self { settings }
+    }
+
+    /// 请求是否应由 Mock Provider 处理
+    pub fn matches(&self, provider: Option<&str>, model: &str) -> bool {
+        self.settings.enabled
+            && (provider.is_some_and(|p| p.eq_ignore_ascii_case(MOCK_PROVIDER_ID))
+                || model.to_ascii_lowercase().starts_with(MOCK_PROVIDER_ID))
+    }
+
+    /// 首个响应前的模拟延迟
+    pub fn latency(&self) -> Duration {
+        Duration::from_millis(self.settings.latency_ms)
+    }
+
+    /// 生成回复文本
+    pub fn render(&self, model: &str, prompt: &str, message_count: usize) -> String {
+        if self.settings.echo_prompt {
+            return prompt.to_string();
+        }
+        self.settings
+            .response_template
+            .replace("{{model}}", model)
+            .replace("{{prompt}}", prompt)
+            .replace("{{message_count}}", &message_count.to_string())
+    }
+
+    /// 生成 OpenAI Chat Completions 响应
+    pub fn openai_completion(&self, request: &ChatCompletionRequest) -> serde_json::Value {
+        let content = self.openai_reply(request);
+        let (prompt_tokens, completion_tokens) = openai_usage(request, &content);
+        serde_json::json!({
+            "id": format!("chatcmpl-mock-{}", uuid::Uuid::new_v4()),
+            "object": "chat.completion",
+            "created": chrono::Utc::now().timestamp(),
+            "model": request.model,
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": content},
+                "finish_reason": "stop"
+            }],
+            "usage": {
+                "prompt_tokens": prompt_tokens,
+                "completion_tokens": completion_tokens,
+                "total_tokens": prompt_tokens + completion_tokens
+            }
+        })
+    }
+
+    /// 生成 OpenAI 流式响应的 SSE 事件
+    pub fn openai_stream_events(&self, request: &ChatCompletionRequest) -> Vec<String> {
+        let content = self.openai_reply(request);
+        let (prompt_tokens, completion_tokens) = openai_usage(request, &content);
+        let id = format!("chatcmpl-mock-{}", uuid::Uuid::new_v4());
+        let created = chrono::Utc::now().timestamp();
+        let chunk = |delta: serde_json::Value, finish_reason: Option<&str>| {
+            sse_data(&serde_json::json!({
+                "id": id,
+                "object": "chat.completion.chunk",
+                "created": created,
+                "model": request.model,
+                "choices": [{"index": 0, "delta": delta, "finish_reason": finish_reason}]
+            }))
+        };
+
+        let mut events = vec![chunk(serde_json::json!({"role": "assistant"}), None)];
+        for piece in split_chunks(&content, self.settings.stream_chunk_chars) {
+            events.push(chunk(serde_json::json!({"content": piece}), None));
+        }
+        events.push(chunk(serde_json::json!({}), Some("stop")));
+        events.push(sse_data(&serde_json::json!({
+            "id": id,
+            "object": "chat.completion.chunk",
+            "created": created,
+            "model": request.model,
+            "choices": [],
+            "usage": {
+                "prompt_tokens": prompt_tokens,
+                "completion_tokens": completion_tokens,
+                "total_tokens": prompt_tokens + completion_tokens
+            }
+        })));
+        events.push("data: [DONE]\n\n".to_string());
+        events
+    }
+
+    /// 生成 Anthropic Messages 响应
+    pub fn anthropic_message(&self, request: &AnthropicMessagesRequest) -> serde_json::Value {
+        let content = self.anthropic_reply(request);
+        let (input_tokens, output_tokens) = anthropic_usage(request, &content);
+        serde_json::json!({
+            "id": format!("msg_mock_{}", uuid::Uuid::new_v4().simple()),
+            "type": "message",
+            "role": "assistant",
+            "model": request.model,
+            "content": [{"type": "text", "text": content}],
+            "stop_reason": "end_turn",
+            "stop_sequence": null,
+            "usage": {"input_tokens": input_tokens, "output_tokens": output_tokens}
+        })
+    }
+
+    /// 生成 Anthropic 流式响应的 SSE 事件
+    pub fn anthropic_stream_events(&self, request: &AnthropicMessagesRequest) -> Vec<String> {
+        let content = self.anthropic_reply(request);
+        let (input_tokens, output_tokens) = anthropic_usage(request, &content);
+
+        let mut events = vec![
+            sse_event(
+                "message_start",
+                &serde_json::json!({
+                    "type": "message_start",
+                    "message": {
+                        "id": format!("msg_mock_{}", uuid::Uuid::new_v4().simple()),
+                        "type": "message",
+                        "role": "assistant",
+                        "model": request.model,
+                        "content": [],
+                        "stop_reason": null,
+                        "stop_sequence": null,
+                        "usage": {"input_tokens": input_tokens, "output_tokens": 0}
+                    }
+                }),
+            ),
+            sse_event(
+                "content_block_start",
+                &serde_json::json!({
+                    "type": "content_block_start",
+                    "index": 0,
+                    "content_block": {"type": "text", "text": ""}
+                }),
+            ),
+        ];
+        for piece in split_chunks(&content, self.settings.stream_chunk_chars) {
+            events.push(sse_event(
+                "content_block_delta",
+                &serde_json::json!({
+                    "type": "content_block_delta",
+                    "index": 0,
+                    "delta": {"type": "text_delta", "text": piece}
+                }),
+            ));
+        }
+        events.push(sse_event(
+            "content_block_stop",
+            &serde_json::json!({"type": "content_block_stop", "index": 0}),
+        ));
+        events.push(sse_event(
+            "message_delta",
+            &serde_json::json!({
+                "type": "message_delta",
+                "delta": {"stop_reason": "end_turn", "stop_sequence": null},
+                "usage": {"output_tokens": output_tokens}
+            }),
+        ));
+        events.push(sse_event(
+            "message_stop",
+            &serde_json::json!({"type": "message_stop"}),
+        ));
+        events
+    }
+
+    /// 将 SSE 事件按配置的间隔逐个输出
+    pub fn into_stream(
+        &self,
+        events: Vec<String>,
+    ) -> impl Stream<Item = Result<Bytes, Infallible>> + Send + 'static {
+        let delay = Duration::from_millis(self.settings.stream_chunk_delay_ms);
+        futures::stream::iter(events.into_iter().enumerate()).then(move |(i, event)| async move {
+            if i > 0 && !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            Ok(Bytes::from(event))
+        })
+    }
+
+    fn openai_reply(&self, request: &ChatCompletionRequest) -> String {
+        let prompt = request
+            .messages
+            .iter()
+            .rev()
+            .find(|m| m.role == "user")
+            .and_then(|m| m.content.as_ref())
+            .map(openai_content_text)
+            .unwrap_or_default();
+        self.render(&request.model, &prompt, request.messages.len())
+    }
+
+    fn anthropic_reply(&self, request: &AnthropicMessagesRequest) -> String {
+        let prompt = request
+            .messages
+            .iter()
+            .rev()
+            .find(|m| m.role == "user")
+            .map(|m| anthropic_content_text(&m.content))
+            .unwrap_or_default();
+        self.render(&request.model, &prompt, request.messages.len())
+    }
+}
+
+fn openai_content_text(content: &MessageContent) -> String {
+    match content {
+        MessageContent::Text(text) => text.clone(),
+        MessageContent::Parts(parts) => parts
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::Text { text } => Some(text.as_str()),
+                ContentPart::ImageUrl { .. } => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+fn anthropic_content_text(content: &serde_json::Value) -> String {
+    match content {
+        serde_json::Value::String(text) => text.clone(),
+        serde_json::Value::Array(blocks) => blocks
+            .iter()
+            .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("text"))
+            .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn openai_usage(request: &ChatCompletionRequest, content: &str) -> (u32, u32) {
+    (
+        token_count_service::count_openai_request(request),
+        token_count_service::count_text(content, &request.model),
+    )
+}
+
+fn anthropic_usage(request: &AnthropicMessagesRequest, content: &str) -> (u32, u32) {
+    (
+        token_count_service::count_anthropic_request(request),
+        token_count_service::count_text(content, &request.model),
+    )
+}
+
+/// 按字符数切分文本，保证不会切断多字节字符
+fn split_chunks(text: &str, chunk_chars: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    chars
+        .chunks(chunk_chars.max(1))
+        .map(|c| c.iter().collect())
+        .collect()
+}
+
+fn sse_data(value: &serde_json::Value) -> String {
+    format!("data: {}\n\n", value)
+}
+
+fn sse_event(event: &str, value: &serde_json::Value) -> String {
+    format!("event: {}\ndata: {}\n\n", event, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::openai::ChatMessage;
+
+    fn settings() -> MockProviderSettings {
+        MockProviderSettings {
+            enabled: true,
+            response_template: "[{{model}}] {{prompt}} ({{message_count}})".to_string(),
+            stream_chunk_delay_ms: 0,
+            stream_chunk_chars: 3,
+            ..Default::default()
+        }
+    }
+
+    fn openai_request(prompt: &str) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: "mock-fast".to_string(),
+            messages: vec![ChatMessage {
+                role: "user".to_string(),
+                content: Some(MessageContent::Text(prompt.to_string())),
+                tool_calls: None,
+                tool_call_id: None,
+                reasoning_content: None,
+            }],
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            stream: true,
+            tools: None,
+            tool_choice: None,
+            reasoning_effort: None,
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let mock = MockProvider::new(settings());
+        assert!(mock.matches(None, "mock-fast"));
+        assert!(mock.matches(Some("MOCK"), "gpt-4o"));
+        assert!(!mock.matches(Some("openai"), "gpt-4o"));
+
+        let disabled = MockProvider::new(MockProviderSettings::default());
+        assert!(!disabled.matches(Some("mock"), "mock"));
+    }
+
+    #[test]
+    fn test_render_template_and_echo() {
+        let mock = MockProvider::new(settings());
+        let response = mock.openai_completion(&openai_request("你好"));
+        assert_eq!(
+            response["choices"][0]["message"]["content"],
+            "[mock-fast] 你好 (1)"
+        );
+        assert!(response["usage"]["prompt_tokens"].as_u64().unwrap() > 0);
+
+        let echo = MockProvider::new(MockProviderSettings {
+            echo_prompt: true,
+            ..settings()
+        });
+        assert_eq!(echo.render("m", "原样返回", 3), "原样返回");
+    }
+
+    #[test]
+    fn test_openai_stream_events() {
+        let mock = MockProvider::new(settings());
+        let events = mock.openai_stream_events(&openai_request("abcdef"));
+        let content: String = events
+            .iter()
+            .filter_map(|e| e.strip_prefix("data: "))
+            .filter_map(|d| serde_json::from_str::<serde_json::Value>(d.trim()).ok())
+            .filter_map(|v| {
+                v["choices"][0]["delta"]["content"]
+                    .as_str()
+                    .map(String::from)
+            })
+            .collect();
+        assert_eq!(content, "[mock-fast] abcdef (1)");
+        assert_eq!(events.last().unwrap(), "data: [DONE]\n\n");
+    }
+
+    #[test]
+    fn test_anthropic_stream_events() {
+        let mock = MockProvider::new(settings());
+        let request: AnthropicMessagesRequest = serde_json::from_value(serde_json::json!({
+            "model": "mock",
+            "max_tokens": 100,
+            "messages": [{"role": "user", "content": [{"type": "text", "text": "hi"}]}]
+        }))
+        .unwrap();
+        let events = mock.anthropic_stream_events(&request);
+        assert!(events[0].starts_with("event: message_start\n"));
+        assert!(events.last().unwrap().starts_with("event: message_stop\n"));
+        assert_eq!(
+            mock.anthropic_message(&request)["content"][0]["text"],
+            "[mock] hi (1)"
+        );
+    }
+
+    #[test]
+    fn test_split_chunks_multibyte() {
+        assert_eq!(split_chunks("你好世界！", 2), vec!["你好", "世界", "！"]);
+        assert!(split_chunks("", 4).is_empty());
+    }
+}
@@ -5,6 +5,7 @@ pub mod codex;
 pub mod error;
 pub mod gemini;
 pub mod kiro;
+pub mod mock;
 pub mod openai_custom;
 pub mod traits;
 pub mod vertex;
@@ -35,6 +36,8 @@ pub use gemini::{GeminiApiKeyCredential, GeminiApiKeyProvider, GeminiProvider};
 #[allow(unused_imports)]
 pub use kiro::KiroProvider;
 #[allow(unused_imports)]
+pub use mock::MockProvider;
+#[allow(unused_imports)]
 pub use openai_custom::OpenAICustomProvider;
 #[allow(unused_imports)]
 pub use vertex::VertexProvider;
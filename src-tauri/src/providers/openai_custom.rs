@@ -1,5 +1,8 @@
 //! OpenAI Custom Provider (自定义 OpenAI 兼容 API)
 use crate::models::openai::ChatCompletionRequest;
+use crate::services::http_client_service::{self, TrackedSend};
+use proxycast_providers::dashscope::{self, DashScopeMode, DashScopeProvider};
+use proxycast_providers::{Provider, ProviderEndpoint};
 use reqwest::Client;
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
@@ -17,19 +20,15 @@ pub struct OpenAICustomConfig {
 pub struct OpenAICustomProvider {
     pub config: OpenAICustomConfig,
     pub client: Client,
+    /// 基础地址指向 DashScope 时的接入方式
+    pub dashscope_mode: DashScopeMode,
 }
 
-/// 创建配置好的 HTTP 客户端
+/// 获取共享的 HTTP 客户端
+///
+/// 总超时 10 分钟（流式响应可能很长），连接池和 keepalive 见 [`http_client_service`]
 fn create_http_client() -> Client {
-    Client::builder()
-        .connect_timeout(Duration::from_secs(30))
-        .timeout(Duration::from_secs(600)) // 10 分钟总超时
-        .tcp_keepalive(Duration::from_secs(60))
-        .gzip(true) // 自动解压 gzip 响应
-        .brotli(true) // 自动解压 brotli 响应
-        .deflate(true) // 自动解压 deflate 响应
-        .build()
-        .unwrap_or_else(|_| Client::new())
+    http_client_service::client("openai_custom", Duration::from_secs(600))
 }
 
 impl Default for OpenAICustomProvider {
@@ -37,6 +36,7 @@ impl Default for OpenAICustomProvider {
         Self {
             config: OpenAICustomConfig::default(),
             client: create_http_client(),
+            dashscope_mode: DashScopeMode::default(),
         }
     }
 }
@@ -55,9 +55,16 @@ impl OpenAICustomProvider {
                 enabled: true,
             },
             client: create_http_client(),
+            dashscope_mode: DashScopeMode::default(),
         }
     }
 
+    /// 设置 DashScope 接入方式（仅对 DashScope 地址生效）
+    pub fn with_dashscope_mode(mut self, mode: DashScopeMode) -> Self {
+        self.dashscope_mode = mode;
+        self
+    }
+
     pub fn get_base_url(&self) -> String {
         self.config
             .base_url
@@ -69,6 +76,71 @@ impl OpenAICustomProvider {
         self.config.api_key.is_some() && self.config.enabled
     }
 
+    /// 生成 Authorization 头
+    ///
+    /// 智谱 BigModel 的 `{id}.{secret}` 格式 Key 需签发 JWT
+    fn authorization(&self, api_key: &str) -> String {
+        if self.get_base_url().contains("bigmodel.cn") {
+            let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+            if let Ok(token) = proxycast_providers::zhipu::auth_token(api_key, now_ms) {
+                return format!("Bearer {}", token);
+            }
+        }
+        format!("Bearer {}", api_key)
+    }
+
+    /// 需要走 DashScope 原生接口时返回对应的 Provider
+    fn dashscope_native(&self) -> Option<DashScopeProvider> {
+        (self.dashscope_mode == DashScopeMode::Native
+            && dashscope::is_dashscope_url(&self.get_base_url()))
+        .then(DashScopeProvider::native)
+    }
+
+    /// 发送 DashScope 原生请求，请求体为 OpenAI 格式，由 [`DashScopeProvider`] 转换
+    async fn send_dashscope_native(
+        &self,
+        provider: &DashScopeProvider,
+        api_key: &str,
+        request: &ChatCompletionRequest,
+    ) -> Result<reqwest::Response, Box<dyn Error + Send + Sync>> {
+        let endpoint = ProviderEndpoint::new(api_key).with_base_url(self.get_base_url());
+        let upstream = provider.build_request(&endpoint, &serde_json::to_value(request)?)?;
+
+        eprintln!("[OPENAI_CUSTOM] DashScope native URL: {}", upstream.url);
+        let mut builder = self.client.post(&upstream.url);
+        for (name, value) in &upstream.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        Ok(builder
+            .json(&upstream.body)
+            .send_tracked("openai_custom")
+            .await?)
+    }
+
+    /// 调用 DashScope 原生接口，成功响应转换为 OpenAI 格式
+    async fn call_dashscope_native(
+        &self,
+        provider: &DashScopeProvider,
+        api_key: &str,
+        request: &ChatCompletionRequest,
+    ) -> Result<reqwest::Response, Box<dyn Error + Send + Sync>> {
+        let resp = self
+            .send_dashscope_native(provider, api_key, request)
+            .await?;
+        let status = resp.status();
+        if !status.is_success() {
+            return Ok(resp);
+        }
+
+        let native: serde_json::Value = resp.json().await?;
+        let body = dashscope::native_to_openai_response(&native, &request.model);
+        let converted = axum::http::Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(serde_json::to_vec(&body)?)?;
+        Ok(reqwest::Response::from(converted))
+    }
+
     /// 构建完整的 API URL
     /// 智能处理用户输入的 base_url，支持多种 API 版本格式
     ///
@@ -78,28 +150,7 @@ impl OpenAICustomProvider {
     /// - `https://open.bigmodel.cn/api/paas/v4` -> `https://open.bigmodel.cn/api/paas/v4/chat/completions`
     /// - `https://api.deepseek.com/v1` -> `https://api.deepseek.com/v1/chat/completions`
     fn build_url(&self, endpoint: &str) -> String {
-        let base = self.get_base_url();
-        let base = base.trim_end_matches('/');
-
-        // 检查是否已经包含版本号路径（/v1, /v2, /v3, /v4 等）
-        // 使用正则匹配 /v 后跟数字的模式
-        let has_version = base
-            .rsplit('/')
-            .next()
-            .map(|last_segment| {
-                last_segment.starts_with('v')
-                    && last_segment.len() >= 2
-                    && last_segment[1..].chars().all(|c| c.is_ascii_digit())
-            })
-            .unwrap_or(false);
-
-        if has_version {
-            // 已有版本号，直接拼接 endpoint
-            format!("{}/{}", base, endpoint)
-        } else {
-            // 没有版本号，添加 /v1
-            format!("{}/v1/{}", base, endpoint)
-        }
+        proxycast_providers::openai::build_url(&self.get_base_url(), endpoint)
     }
 
     fn build_url_fallback_without_v1(&self, endpoint: &str) -> Option<String> {
@@ -112,23 +163,7 @@ impl OpenAICustomProvider {
     }
 
     fn build_url_from_base(base_url: &str, endpoint: &str) -> String {
-        let base = base_url.trim_end_matches('/');
-
-        let has_version = base
-            .rsplit('/')
-            .next()
-            .map(|last_segment| {
-                last_segment.starts_with('v')
-                    && last_segment.len() >= 2
-                    && last_segment[1..].chars().all(|c| c.is_ascii_digit())
-            })
-            .unwrap_or(false);
-
-        if has_version {
-            format!("{}/{}", base, endpoint)
-        } else {
-            format!("{}/v1/{}", base, endpoint)
-        }
+        proxycast_providers::openai::build_url(base_url, endpoint)
     }
 
     fn base_url_parent(&self) -> Option<String> {
@@ -203,6 +238,12 @@ impl OpenAICustomProvider {
             .as_ref()
             .ok_or("OpenAI API key not configured")?;
 
+        if let Some(provider) = self.dashscope_native() {
+            return self
+                .call_dashscope_native(&provider, api_key, request)
+                .await;
+        }
+
         let urls = self.build_urls_with_fallbacks("chat/completions");
         let mut last_resp: Option<reqwest::Response> = None;
 
@@ -216,10 +257,10 @@ impl OpenAICustomProvider {
             let resp = self
                 .client
                 .post(url)
-                .header("Authorization", format!("Bearer {api_key}"))
+                .header("Authorization", self.authorization(api_key))
                 .header("Content-Type", "application/json")
                 .json(request)
-                .send()
+                .send_tracked("openai_custom")
                 .await?;
 
             if resp.status() != StatusCode::NOT_FOUND {
@@ -252,10 +293,10 @@ impl OpenAICustomProvider {
         let resp = self
             .client
             .post(&url)
-            .header("Authorization", format!("Bearer {api_key}"))
+            .header("Authorization", self.authorization(api_key))
             .header("Content-Type", "application/json")
             .json(request)
-            .send()
+            .send_tracked("openai_custom")
             .await?;
 
         if resp.status() == StatusCode::NOT_FOUND {
@@ -264,10 +305,10 @@ impl OpenAICustomProvider {
                     let resp2 = self
                         .client
                         .post(&fallback_url)
-                        .header("Authorization", format!("Bearer {api_key}"))
+                        .header("Authorization", self.authorization(api_key))
                         .header("Content-Type", "application/json")
                         .json(request)
-                        .send()
+                        .send_tracked("openai_custom")
                         .await?;
                     return Ok(resp2);
                 }
@@ -294,8 +335,8 @@ impl OpenAICustomProvider {
             let r = self
                 .client
                 .get(&url)
-                .header("Authorization", format!("Bearer {api_key}"))
-                .send()
+                .header("Authorization", self.authorization(api_key))
+                .send_tracked("openai_custom")
                 .await?;
             if r.status() != StatusCode::NOT_FOUND {
                 resp = Some(r);
@@ -322,6 +363,58 @@ impl OpenAICustomProvider {
     }
 }
 
+impl OpenAICustomProvider {
+    /// 调用 DashScope 原生流式接口，SSE 改写为 OpenAI 格式后返回
+    async fn call_dashscope_native_stream(
+        &self,
+        provider: &DashScopeProvider,
+        api_key: &str,
+        request: &ChatCompletionRequest,
+    ) -> Result<StreamResponse, ProviderError> {
+        let resp = self
+            .send_dashscope_native(provider, api_key, request)
+            .await
+            .map_err(|e| ProviderError::NetworkError(e.to_string()))?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            tracing::error!(
+                "[OPENAI_STREAM] DashScope 原生请求失败: {} - {}",
+                status,
+                body
+            );
+            return Err(ProviderError::from_http_status(status.as_u16(), &body));
+        }
+
+        tracing::info!(
+            "[OPENAI_STREAM] DashScope 原生流式响应开始: status={}",
+            status
+        );
+
+        let mut translator = dashscope::NativeStreamTranslator::new(request.model.clone());
+        let mut upstream = reqwest_stream_to_stream_response(resp);
+        Ok(Box::pin(async_stream::stream! {
+            use futures::StreamExt;
+            while let Some(result) = upstream.next().await {
+                match result {
+                    Ok(bytes) => {
+                        let translated = translator.translate(&bytes);
+                        if !translated.is_empty() {
+                            yield Ok(bytes::Bytes::from(translated));
+                        }
+                    }
+                    Err(e) => {
+                        yield Err(e);
+                        return;
+                    }
+                }
+            }
+            yield Ok(bytes::Bytes::from(translator.finish()));
+        }))
+    }
+}
+
 // ============================================================================
 // StreamingProvider Trait 实现
 // ============================================================================
@@ -353,6 +446,12 @@ impl StreamingProvider for OpenAICustomProvider {
         let mut stream_request = request.clone();
         stream_request.stream = true;
 
+        if let Some(provider) = self.dashscope_native() {
+            return self
+                .call_dashscope_native_stream(&provider, api_key, &stream_request)
+                .await;
+        }
+
         let url = self.build_url("chat/completions");
 
         tracing::info!(
@@ -364,11 +463,11 @@ impl StreamingProvider for OpenAICustomProvider {
         let resp = self
             .client
             .post(&url)
-            .header("Authorization", format!("Bearer {api_key}"))
+            .header("Authorization", self.authorization(api_key))
             .header("Content-Type", "application/json")
             .header("Accept", "text/event-stream")
             .json(&stream_request)
-            .send()
+            .send_tracked("openai_custom")
             .await
             .map_err(|e| ProviderError::from_reqwest_error(&e))?;
 
@@ -377,11 +476,11 @@ impl StreamingProvider for OpenAICustomProvider {
                 if fallback_url != url {
                     self.client
                         .post(&fallback_url)
-                        .header("Authorization", format!("Bearer {api_key}"))
+                        .header("Authorization", self.authorization(api_key))
                         .header("Content-Type", "application/json")
                         .header("Accept", "text/event-stream")
                         .json(&stream_request)
-                        .send()
+                        .send_tracked("openai_custom")
                         .await
                         .map_err(|e| ProviderError::from_reqwest_error(&e))?
                 } else {
@@ -6,6 +6,7 @@
 #![allow(dead_code)]
 
 use crate::config::VertexApiKeyEntry;
+use crate::services::http_client_service::TrackedSend;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -53,7 +54,10 @@ impl Default for VertexProvider {
     fn default() -> Self {
         Self {
             config: VertexConfig::default(),
-            client: Client::new(),
+            client: crate::services::http_client_service::client(
+                "vertex",
+                std::time::Duration::from_secs(600),
+            ),
         }
     }
 }
@@ -74,7 +78,10 @@ impl VertexProvider {
                 model_aliases: HashMap::new(),
                 proxy_url: None,
             },
-            client: Client::new(),
+            client: crate::services::http_client_service::client(
+                "vertex",
+                std::time::Duration::from_secs(600),
+            ),
         }
     }
 
@@ -93,7 +100,10 @@ impl VertexProvider {
                 model_aliases,
                 proxy_url: entry.proxy_url.clone(),
             },
-            client: Client::new(),
+            client: crate::services::http_client_service::client(
+                "vertex",
+                std::time::Duration::from_secs(600),
+            ),
         }
     }
 
@@ -192,7 +202,7 @@ impl VertexProvider {
             .header("x-goog-api-key", api_key)
             .header("Content-Type", "application/json")
             .json(&request)
-            .send()
+            .send_tracked("vertex")
             .await?;
 
         Ok(resp)
@@ -231,7 +241,7 @@ impl VertexProvider {
             .header("x-goog-api-key", api_key)
             .header("Content-Type", "application/json")
             .json(&request)
-            .send()
+            .send_tracked("vertex")
             .await?;
 
         Ok(resp)
@@ -252,7 +262,7 @@ impl VertexProvider {
             .client
             .get(&url)
             .header("x-goog-api-key", api_key)
-            .send()
+            .send_tracked("vertex")
             .await?;
 
         if !resp.status().is_success() {
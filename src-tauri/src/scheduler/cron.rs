@@ -0,0 +1,204 @@
+//! Cron 表达式解析
+//!
+//! 支持标准 5 段格式：`分 时 日 月 周`，每段支持 `*`、`*/n`、`a-b`、`a-b/n` 和逗号列表，
+//! 以及 `@hourly`、`@daily`、`@weekly`、`@monthly` 别名。
+//! 日和周同时受限时按 cron 惯例取并集。
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike};
+
+/// 查找下次执行时间的最大范围（天）
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 4;
+
+/// 解析后的 Cron 表达式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u32,
+    days_of_month: u32,
+    months: u16,
+    days_of_week: u8,
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+impl CronSchedule {
+    /// 解析 Cron 表达式
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let expr = match expr.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "Cron 表达式需要 5 段（分 时 日 月 周），实际 {} 段",
+                fields.len()
+            ));
+        }
+
+        // 周字段允许 7 表示周日
+        let mut days_of_week = parse_field(fields[4], 0, 7)? as u8;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & 0x7f) | 1;
+        }
+
+        Ok(Self {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)? as u32,
+            days_of_month: parse_field(fields[2], 1, 31)? as u32,
+            months: parse_field(fields[3], 1, 12)? as u16,
+            days_of_week,
+            dom_restricted: fields[2] != "*",
+            dow_restricted: fields[4] != "*",
+        })
+    }
+
+    /// 计算严格晚于 `after` 的下一次执行时间（精确到分钟）
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let mut t = after.clone().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let deadline = after.clone() + Duration::days(MAX_LOOKAHEAD_DAYS);
+
+        while t <= deadline {
+            if !self.matches_day(&t) {
+                // 跳到次日零点
+                let skip = (24 * 60 - (t.hour() * 60 + t.minute())) as i64;
+                t += Duration::minutes(skip);
+                continue;
+            }
+            if self.hours & (1 << t.hour()) == 0 {
+                let skip = (60 - t.minute()) as i64;
+                t += Duration::minutes(skip);
+                continue;
+            }
+            if self.minutes & (1 << t.minute()) == 0 {
+                t += Duration::minutes(1);
+                continue;
+            }
+            return Some(t);
+        }
+
+        None
+    }
+
+    fn matches_day<Tz: TimeZone>(&self, t: &DateTime<Tz>) -> bool {
+        if self.months & (1 << t.month()) == 0 {
+            return false;
+        }
+        let dom = self.days_of_month & (1 << t.day()) != 0;
+        let dow = self.days_of_week & (1 << t.weekday().num_days_from_sunday()) != 0;
+        match (self.dom_restricted, self.dow_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+}
+
+/// 解析单个字段为位掩码
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut mask = 0u64;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| format!("无效的步长: {}", part))?;
+                if step == 0 {
+                    return Err(format!("步长不能为 0: {}", part));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (parse_value(a, min, max)?, parse_value(b, min, max)?)
+        } else {
+            let v = parse_value(range, min, max)?;
+            // `5/15` 表示从 5 开始每 15 个单位
+            (v, if step > 1 { max } else { v })
+        };
+
+        if start > end {
+            return Err(format!("无效的范围: {}", part));
+        }
+
+        let mut v = start;
+        while v <= end {
+            mask |= 1 << v;
+            v += step;
+        }
+    }
+
+    Ok(mask)
+}
+
+fn parse_value(s: &str, min: u32, max: u32) -> Result<u32, String> {
+    let v: u32 = s.parse().map_err(|_| format!("无效的数值: {}", s))?;
+    if v < min || v > max {
+        return Err(format!("数值 {} 超出范围 {}-{}", v, min, max));
+    }
+    Ok(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_every_fifteen_minutes() {
+        let cron = CronSchedule::parse("*/15 * * * *").unwrap();
+        assert_eq!(
+            cron.next_after(&at("2026-03-01T10:07:30Z")),
+            Some(at("2026-03-01T10:15:00Z"))
+        );
+        assert_eq!(
+            cron.next_after(&at("2026-03-01T10:45:00Z")),
+            Some(at("2026-03-01T11:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_daily_alias() {
+        let cron = CronSchedule::parse("@daily").unwrap();
+        assert_eq!(
+            cron.next_after(&at("2026-03-01T10:07:00Z")),
+            Some(at("2026-03-02T00:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_weekday_range() {
+        // 工作日 9:30；2026-03-06 是周五
+        let cron = CronSchedule::parse("30 9 * * 1-5").unwrap();
+        assert_eq!(
+            cron.next_after(&at("2026-03-06T10:00:00Z")),
+            Some(at("2026-03-09T09:30:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_sunday_as_seven() {
+        let cron = CronSchedule::parse("0 0 * * 7").unwrap();
+        assert_eq!(cron, CronSchedule::parse("0 0 * * 0").unwrap());
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert!(CronSchedule::parse("* * * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("5-1 * * * *").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! 定时任务执行器
+//!
+//! 执行单个任务并返回输出文本，由调度服务负责记录执行结果。
+
+use std::path::Path;
+
+use chrono::{Duration, Utc};
+use tauri::{AppHandle, Manager};
+
+use super::types::JobKind;
+use crate::commands::model_registry_cmd::{read_registry, ModelRegistryState};
+use crate::config::load_config;
+use crate::server::overrides::{RequestPriority, HEADER_PRIORITY};
+
+/// 当前写入中的日志文件，清理时跳过
+const ACTIVE_LOG_FILE: &str = "proxycast.log";
+
+/// 执行任务，返回输出文本
+pub async fn execute(app: &AppHandle, job: &JobKind) -> Result<String, String> {
+    match job {
+        JobKind::PruneLogs { retention_days } => {
+            let dir = dirs::home_dir()
+                .ok_or_else(|| "无法获取用户主目录".to_string())?
+                .join(".proxycast")
+                .join("logs");
+            let removed = prune_logs(&dir, *retention_days)?;
+            Ok(format!("已清理 {} 个过期日志文件", removed))
+        }
+        JobKind::RefreshModels => {
+            let state = app
+                .try_state::<ModelRegistryState>()
+                .ok_or_else(|| "Model Registry 状态不可用".to_string())?;
+            let guard = read_registry(&state).await;
+            let service = guard
+                .as_ref()
+                .ok_or_else(|| "Model Registry 服务尚未初始化".to_string())?;
+            let count = service.force_reload().await?;
+            Ok(format!("已刷新 {} 个模型", count))
+        }
+        JobKind::RunPrompt {
+            model,
+            prompt,
+            system_prompt,
+        } => run_prompt(model, prompt, system_prompt.as_deref()).await,
+        JobKind::TranscribeFolder {
+            folder,
+            credential_id,
+        } => {
+            let credential = crate::voice::batch::resolve_credential(credential_id.as_deref())?;
+            let summary = crate::voice::batch::transcribe_folder(
+                Path::new(folder),
+                &credential,
+                &Default::default(),
+            )
+            .await?;
+            if !summary.failed.is_empty() && summary.transcribed.is_empty() {
+                return Err(format!(
+                    "{} 个文件转写失败: {}",
+                    summary.failed.len(),
+                    summary.failed[0].1
+                ));
+            }
+            Ok(format!(
+                "转写成功 {} 个，失败 {} 个",
+                summary.transcribed.len(),
+                summary.failed.len()
+            ))
+        }
+    }
+}
+
+/// 删除日志目录中超过保留天数的文件，返回删除数量
+fn prune_logs(dir: &Path, retention_days: u32) -> Result<usize, String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(format!("读取日志目录失败: {}", e)),
+    };
+    let cutoff = Utc::now() - Duration::days(retention_days as i64);
+    let mut removed = 0;
+
+    for entry in entries.flatten() {
+        if entry.file_name() == ACTIVE_LOG_FILE {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        let Ok(modified) = metadata.modified() else {
+            continue;
+        };
+        if chrono::DateTime::<Utc>::from(modified) < cutoff
+            && std::fs::remove_file(entry.path()).is_ok()
+        {
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
+/// 通过本地 API 服务器执行提示词
+async fn run_prompt(
+    model: &str,
+    prompt: &str,
+    system_prompt: Option<&str>,
+) -> Result<String, String> {
+    let config = load_config().map_err(|e| e.to_string())?;
+    let base_url = format!("http://{}:{}", config.server.host, config.server.port);
+
+    let mut messages = Vec::new();
+    if let Some(system) = system_prompt.filter(|s| !s.is_empty()) {
+        messages.push(serde_json::json!({ "role": "system", "content": system }));
+    }
+    messages.push(serde_json::json!({ "role": "user", "content": prompt }));
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/v1/chat/completions", base_url))
+        .header("Authorization", format!("Bearer {}", config.server.api_key))
+        // 后台作业，不与交互请求争抢上游并发
+        .header(HEADER_PRIORITY, RequestPriority::Low.as_str())
+        .json(&serde_json::json!({
+            "model": model,
+            "messages": messages,
+            "stream": false,
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("请求失败: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("LLM API 错误: {} - {}", status, body));
+    }
+
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("解析响应失败: {}", e))?;
+
+    body["choices"][0]["message"]["content"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "LLM 返回空内容".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_logs_keeps_active_and_recent() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(ACTIVE_LOG_FILE), b"").unwrap();
+        std::fs::write(dir.path().join("proxycast.log.20260101"), b"").unwrap();
+
+        // 保留 0 天：除当前日志外全部视为过期
+        assert_eq!(prune_logs(dir.path(), 0).unwrap(), 1);
+        assert!(dir.path().join(ACTIVE_LOG_FILE).exists());
+
+        assert_eq!(prune_logs(&dir.path().join("missing"), 7).unwrap(), 0);
+    }
+}
@@ -0,0 +1,15 @@
+//! 定时任务模块
+//!
+//! 按 Cron 表达式定期执行后台任务：
+//! - 清理过期日志
+//! - 刷新模型注册表
+//! - 执行保存的提示词并保存结果
+//! - 批量转写文件夹中的音频
+
+pub mod cron;
+pub mod executor;
+pub mod service;
+pub mod types;
+
+pub use cron::CronSchedule;
+pub use types::{JobKind, JobRun, JobRunStatus, ScheduledJob};
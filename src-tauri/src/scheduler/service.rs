@@ -0,0 +1,134 @@
+//! 定时任务调度服务
+//!
+//! 后台循环定期检查到期任务。任务开始执行前先推进下次执行时间，
+//! 避免长耗时任务在下一轮检查时被重复触发；应用关闭期间错过的执行只补跑一次。
+
+use chrono::{Local, TimeZone, Utc};
+use tauri::AppHandle;
+
+use super::cron::CronSchedule;
+use super::executor;
+use super::types::{JobRun, JobRunStatus, ScheduledJob};
+use crate::database::dao::scheduled_jobs::ScheduledJobDao;
+use crate::database::DbConnection;
+
+/// 检查到期任务的间隔（秒）
+const TICK_INTERVAL_SECS: u64 = 30;
+
+/// 计算 `after_ms` 之后的下次执行时间（毫秒时间戳，本地时区）
+pub fn compute_next_run(schedule: &str, after_ms: i64) -> Result<Option<i64>, String> {
+    let cron = CronSchedule::parse(schedule)?;
+    let after = Local
+        .timestamp_millis_opt(after_ms)
+        .single()
+        .ok_or_else(|| format!("无效的时间戳: {}", after_ms))?;
+    Ok(cron.next_after(&after).map(|t| t.timestamp_millis()))
+}
+
+/// 启动调度循环
+pub fn start(app: AppHandle, db: DbConnection) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = refresh_next_runs(&db) {
+            tracing::warn!("[定时任务] 初始化下次执行时间失败: {}", e);
+        }
+
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(TICK_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if let Err(e) = tick(&app, &db) {
+                tracing::warn!("[定时任务] 检查到期任务失败: {}", e);
+            }
+        }
+    });
+    tracing::info!("[启动] 定时任务调度已启动");
+}
+
+/// 为缺少下次执行时间的启用任务补充计算
+fn refresh_next_runs(db: &DbConnection) -> Result<(), String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    let now = Utc::now().timestamp_millis();
+    for job in ScheduledJobDao::list(&conn).map_err(|e| e.to_string())? {
+        if job.enabled && job.next_run_at.is_none() {
+            let next = compute_next_run(&job.schedule, now).unwrap_or(None);
+            ScheduledJobDao::set_next_run(&conn, &job.id, next).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// 检查并触发到期任务
+fn tick(app: &AppHandle, db: &DbConnection) -> Result<(), String> {
+    let now = Utc::now().timestamp_millis();
+    let due = {
+        let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+        let due = ScheduledJobDao::list_due(&conn, now).map_err(|e| e.to_string())?;
+        for job in &due {
+            let next = compute_next_run(&job.schedule, now).unwrap_or_else(|e| {
+                tracing::warn!("[定时任务] 任务 {} 的 Cron 表达式无效: {}", job.name, e);
+                None
+            });
+            ScheduledJobDao::set_next_run(&conn, &job.id, next).map_err(|e| e.to_string())?;
+        }
+        due
+    };
+
+    for job in due {
+        let app = app.clone();
+        let db = db.clone();
+        tauri::async_runtime::spawn(async move {
+            run_job(&app, &db, &job).await;
+        });
+    }
+    Ok(())
+}
+
+/// 执行任务并记录结果
+pub async fn run_job(app: &AppHandle, db: &DbConnection, job: &ScheduledJob) -> JobRun {
+    tracing::info!(
+        "[定时任务] 开始执行: {} ({})",
+        job.name,
+        job.job.type_name()
+    );
+    let started_at = Utc::now().timestamp_millis();
+    let result = executor::execute(app, &job.job).await;
+    let finished_at = Utc::now().timestamp_millis();
+
+    let run = match result {
+        Ok(output) => {
+            tracing::info!("[定时任务] 执行成功: {}", job.name);
+            JobRun {
+                id: 0,
+                job_id: job.id.clone(),
+                started_at,
+                finished_at,
+                status: JobRunStatus::Success,
+                output: Some(output),
+                error: None,
+            }
+        }
+        Err(e) => {
+            tracing::warn!("[定时任务] 执行失败: {} - {}", job.name, e);
+            JobRun {
+                id: 0,
+                job_id: job.id.clone(),
+                started_at,
+                finished_at,
+                status: JobRunStatus::Failed,
+                output: None,
+                error: Some(e),
+            }
+        }
+    };
+
+    match db.lock() {
+        Ok(conn) => {
+            if let Err(e) = ScheduledJobDao::record_run(&conn, &run) {
+                tracing::warn!("[定时任务] 保存执行记录失败: {}", e);
+            }
+        }
+        Err(e) => tracing::warn!("[定时任务] 数据库锁定失败: {}", e),
+    }
+
+    run
+}
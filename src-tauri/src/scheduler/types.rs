@@ -0,0 +1,114 @@
+//! 定时任务类型定义
+
+use serde::{Deserialize, Serialize};
+
+/// 定时任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    /// 任务 ID
+    pub id: String,
+    /// 任务名称
+    pub name: String,
+    /// 是否启用
+    pub enabled: bool,
+    /// Cron 表达式（本地时区）
+    pub schedule: String,
+    /// 任务内容
+    pub job: JobKind,
+    /// 上次执行时间（毫秒时间戳）
+    #[serde(default)]
+    pub last_run_at: Option<i64>,
+    /// 上次执行状态
+    #[serde(default)]
+    pub last_status: Option<JobRunStatus>,
+    /// 上次执行错误
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// 下次执行时间（毫秒时间戳）
+    #[serde(default)]
+    pub next_run_at: Option<i64>,
+    /// 创建时间（毫秒时间戳）
+    pub created_at: i64,
+}
+
+/// 任务内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobKind {
+    /// 清理过期日志
+    PruneLogs {
+        /// 保留天数
+        retention_days: u32,
+    },
+    /// 刷新模型注册表
+    RefreshModels,
+    /// 使用指定模型执行保存的提示词，并保存结果
+    RunPrompt {
+        /// 模型名称（经本地服务器路由到对应 Provider）
+        model: String,
+        /// 提示词
+        prompt: String,
+        /// 系统提示词
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        system_prompt: Option<String>,
+    },
+    /// 批量转写文件夹中尚未转写的音频
+    TranscribeFolder {
+        /// 文件夹路径
+        folder: String,
+        /// ASR 凭证 ID（为空使用默认凭证）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        credential_id: Option<String>,
+    },
+}
+
+impl JobKind {
+    /// 任务类型名称
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            JobKind::PruneLogs { .. } => "prune_logs",
+            JobKind::RefreshModels => "refresh_models",
+            JobKind::RunPrompt { .. } => "run_prompt",
+            JobKind::TranscribeFolder { .. } => "transcribe_folder",
+        }
+    }
+}
+
+/// 任务执行状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobRunStatus {
+    Success,
+    Failed,
+}
+
+impl JobRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobRunStatus::Success => "success",
+            JobRunStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "success" => JobRunStatus::Success,
+            _ => JobRunStatus::Failed,
+        }
+    }
+}
+
+/// 任务执行记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRun {
+    pub id: i64,
+    pub job_id: String,
+    /// 开始时间（毫秒时间戳）
+    pub started_at: i64,
+    /// 结束时间（毫秒时间戳）
+    pub finished_at: i64,
+    pub status: JobRunStatus,
+    /// 执行输出（如提示词结果、清理数量）
+    pub output: Option<String>,
+    pub error: Option<String>,
+}
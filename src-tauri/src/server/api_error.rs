@@ -0,0 +1,482 @@
+//! 代理 API 错误分类
+//!
+//! 本地服务返回的所有错误响应使用统一的结构，客户端和界面可以按错误码处理：
+//!
+//! ```json
+//! {
+//!   "error": {
+//!     "message": "No API key provided",
+//!     "type": "authentication_error",
+//!     "code": "missing_api_key",
+//!     "provider": null,
+//!     "retryable": false,
+//!     "request_id": "0b6c…"
+//!   }
+//! }
+//! ```
+//!
+//! Anthropic 端点在外层额外带 `"type": "error"`。处理器可以直接返回 [`ApiError`]；
+//! 其余错误响应由 [`crate::middleware::ErrorEnvelopeLayer`] 按状态码和 [`ErrorProvider`]
+//! 补全缺失的字段（[`normalize_error_body`]）。
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Map, Value};
+
+/// 错误码
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorCode {
+    /// 请求未携带 API Key
+    MissingApiKey,
+    /// API Key 无效或已禁用
+    InvalidApiKey,
+    /// 请求格式或参数错误
+    InvalidRequest,
+    /// 输入超出模型上下文窗口
+    ContextLengthExceeded,
+    /// 没有匹配请求的路由或端点
+    NoRoute,
+    /// 所选 Provider 没有可用凭证
+    NoCredentials,
+    /// 指定的凭证不存在或已禁用
+    CredentialUnavailable,
+    /// 上游并发已满，排队超时
+    Overloaded,
+    /// 请求被用户取消
+    RequestCancelled,
+    /// 上游拒绝了凭证
+    UpstreamAuthFailed,
+    /// 上游限流或配额用尽
+    UpstreamRateLimited,
+    /// 上游响应超时
+    UpstreamTimeout,
+    /// 上游认为请求无效
+    UpstreamInvalidRequest,
+    /// 上游的其他错误
+    UpstreamError,
+    /// 本地服务内部错误
+    InternalError,
+}
+
+impl ApiErrorCode {
+    /// 全部错误码
+    pub const ALL: [Self; 15] = [
+        Self::MissingApiKey,
+        Self::InvalidApiKey,
+        Self::InvalidRequest,
+        Self::ContextLengthExceeded,
+        Self::NoRoute,
+        Self::NoCredentials,
+        Self::CredentialUnavailable,
+        Self::Overloaded,
+        Self::RequestCancelled,
+        Self::UpstreamAuthFailed,
+        Self::UpstreamRateLimited,
+        Self::UpstreamTimeout,
+        Self::UpstreamInvalidRequest,
+        Self::UpstreamError,
+        Self::InternalError,
+    ];
+
+    /// 错误码字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MissingApiKey => "missing_api_key",
+            Self::InvalidApiKey => "invalid_api_key",
+            Self::InvalidRequest => "invalid_request",
+            Self::ContextLengthExceeded => "context_length_exceeded",
+            Self::NoRoute => "no_route",
+            Self::NoCredentials => "no_credentials",
+            Self::CredentialUnavailable => "credential_unavailable",
+            Self::Overloaded => "overloaded",
+            Self::RequestCancelled => "request_cancelled",
+            Self::UpstreamAuthFailed => "upstream_auth_failed",
+            Self::UpstreamRateLimited => "upstream_rate_limited",
+            Self::UpstreamTimeout => "upstream_timeout",
+            Self::UpstreamInvalidRequest => "upstream_invalid_request",
+            Self::UpstreamError => "upstream_error",
+            Self::InternalError => "internal_error",
+        }
+    }
+
+    /// 解析错误码字符串，兼容旧响应中的写法
+    pub fn parse(code: &str) -> Option<Self> {
+        match code {
+            "no_credential" => Some(Self::NoCredentials),
+            _ => Self::ALL.into_iter().find(|c| c.as_str() == code),
+        }
+    }
+
+    /// 默认 HTTP 状态码
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::MissingApiKey | Self::InvalidApiKey => StatusCode::UNAUTHORIZED,
+            Self::InvalidRequest | Self::ContextLengthExceeded | Self::RequestCancelled => {
+                StatusCode::BAD_REQUEST
+            }
+            Self::NoRoute => StatusCode::NOT_FOUND,
+            Self::NoCredentials | Self::CredentialUnavailable | Self::Overloaded => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            Self::UpstreamAuthFailed | Self::UpstreamError => StatusCode::BAD_GATEWAY,
+            Self::UpstreamRateLimited => StatusCode::TOO_MANY_REQUESTS,
+            Self::UpstreamTimeout => StatusCode::GATEWAY_TIMEOUT,
+            Self::UpstreamInvalidRequest => StatusCode::BAD_REQUEST,
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// 原样重试（或稍后重试）是否可能成功
+    ///
+    /// 排队超时和上游的临时故障可以重试；鉴权、参数、路由和凭证错误需要先修改请求或配置
+    pub fn retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::Overloaded
+                | Self::UpstreamRateLimited
+                | Self::UpstreamTimeout
+                | Self::UpstreamError
+        )
+    }
+
+    /// OpenAI / Anthropic 风格的错误类型
+    pub fn error_type(&self) -> &'static str {
+        match self {
+            Self::MissingApiKey | Self::InvalidApiKey => "authentication_error",
+            Self::InvalidRequest
+            | Self::ContextLengthExceeded
+            | Self::RequestCancelled
+            | Self::UpstreamInvalidRequest => "invalid_request_error",
+            Self::NoRoute => "not_found_error",
+            Self::NoCredentials | Self::CredentialUnavailable => "provider_unavailable",
+            Self::Overloaded => "overloaded_error",
+            Self::UpstreamRateLimited => "rate_limit_error",
+            Self::UpstreamAuthFailed | Self::UpstreamTimeout | Self::UpstreamError => {
+                "upstream_error"
+            }
+            Self::InternalError => "api_error",
+        }
+    }
+
+    /// 按状态码推断错误码，`from_provider` 表示错误来自上游
+    pub fn classify(status: StatusCode, from_provider: bool) -> Self {
+        if from_provider {
+            return match status.as_u16() {
+                401 | 403 => Self::UpstreamAuthFailed,
+                429 => Self::UpstreamRateLimited,
+                408 | 504 => Self::UpstreamTimeout,
+                400 | 404 | 413 | 422 => Self::UpstreamInvalidRequest,
+                _ => Self::UpstreamError,
+            };
+        }
+        match status.as_u16() {
+            401 | 403 => Self::InvalidApiKey,
+            404 | 405 => Self::NoRoute,
+            499 => Self::RequestCancelled,
+            429 | 503 => Self::Overloaded,
+            502 => Self::UpstreamError,
+            408 | 504 => Self::UpstreamTimeout,
+            400..=499 => Self::InvalidRequest,
+            _ => Self::InternalError,
+        }
+    }
+
+    /// 按旧响应的错误类型推断错误码
+    fn from_error_type(error_type: &str) -> Option<Self> {
+        match error_type {
+            "overloaded_error" => Some(Self::Overloaded),
+            "request_cancelled" => Some(Self::RequestCancelled),
+            "provider_unavailable" | "no_credential_error" => Some(Self::NoCredentials),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 错误响应的外层格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorFormat {
+    /// `{"error": {...}}`
+    #[default]
+    OpenAI,
+    /// `{"type": "error", "error": {...}}`
+    Anthropic,
+}
+
+/// 产生错误的上游 Provider，作为响应扩展供 [`crate::middleware::ErrorEnvelopeLayer`] 读取
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorProvider(pub String);
+
+/// 代理 API 错误
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    /// 错误码
+    pub code: ApiErrorCode,
+    /// 错误描述
+    pub message: String,
+    /// 产生错误的上游 Provider
+    pub provider: Option<String>,
+    /// 外层格式
+    pub format: ErrorFormat,
+}
+
+impl ApiError {
+    /// 创建 OpenAI 格式的错误
+    pub fn new(code: ApiErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            provider: None,
+            format: ErrorFormat::OpenAI,
+        }
+    }
+
+    /// 指定产生错误的 Provider
+    pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
+    /// 使用 Anthropic 格式
+    pub fn anthropic(mut self) -> Self {
+        self.format = ErrorFormat::Anthropic;
+        self
+    }
+
+    /// 响应正文，请求 ID 取当前请求作用域内的 ID
+    pub fn to_json(&self) -> Value {
+        let error = json!({
+            "message": self.message,
+            "type": self.code.error_type(),
+            "code": self.code.as_str(),
+            "provider": self.provider,
+            "retryable": self.code.retryable(),
+            "request_id": crate::middleware::current_request_id(),
+        });
+        match self.format {
+            ErrorFormat::OpenAI => json!({ "error": error }),
+            ErrorFormat::Anthropic => json!({ "type": "error", "error": error }),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.code.status(), Json(self.to_json())).into_response()
+    }
+}
+
+/// 把错误响应正文整理为统一结构
+///
+/// 正文不是 JSON 对象时整体作为错误描述；没有 `error` 字段的对象保留原有字段，补充 `error`
+/// 对象。已有的 `type`、`message` 和 `code`（包括上游的数字错误码）保留，缺失的字段按状态码
+/// 和 Provider 补全
+pub fn normalize_error_body(
+    status: StatusCode,
+    body: Value,
+    provider: Option<&str>,
+    request_id: Option<&str>,
+) -> Value {
+    let mut body = match body {
+        Value::Object(map) => map,
+        Value::String(message) => Map::from_iter([("error".to_string(), json!(message))]),
+        other => Map::from_iter([("error".to_string(), json!(other.to_string()))]),
+    };
+    let mut error = match body.remove("error") {
+        Some(Value::Object(error)) => error,
+        Some(Value::String(message)) => Map::from_iter([("message".to_string(), json!(message))]),
+        Some(other) => Map::from_iter([("message".to_string(), other)]),
+        None if body.is_empty() => Map::new(),
+        // 没有 error 字段（上游原样透传的错误）：原有字段保留，常见的描述字段作为 message
+        None => {
+            let message = ["message", "detail", "msg"]
+                .iter()
+                .find_map(|key| body.get(*key).and_then(Value::as_str))
+                .map(str::to_string)
+                .unwrap_or_else(|| Value::Object(body.clone()).to_string());
+            Map::from_iter([("message".to_string(), json!(message))])
+        }
+    };
+
+    let code = error
+        .get("code")
+        .and_then(Value::as_str)
+        .and_then(ApiErrorCode::parse)
+        .or_else(|| {
+            error
+                .get("type")
+                .and_then(Value::as_str)
+                .and_then(ApiErrorCode::from_error_type)
+        })
+        .unwrap_or_else(|| ApiErrorCode::classify(status, provider.is_some()));
+
+    // 上游自带的错误码（包括数字）原样保留
+    error.entry("code").or_insert_with(|| json!(code.as_str()));
+    error
+        .entry("type")
+        .or_insert_with(|| json!(code.error_type()));
+    error
+        .entry("message")
+        .or_insert_with(|| json!(status.canonical_reason().unwrap_or("error")));
+    if !error.get("provider").is_some_and(Value::is_string) {
+        error.insert("provider".to_string(), json!(provider));
+    }
+    error.insert("retryable".to_string(), json!(code.retryable()));
+    if !error.get("request_id").is_some_and(Value::is_string) {
+        error.insert("request_id".to_string(), json!(request_id));
+    }
+
+    body.insert("error".to_string(), Value::Object(error));
+    Value::Object(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_round_trip() {
+        for code in ApiErrorCode::ALL {
+            assert_eq!(ApiErrorCode::parse(code.as_str()), Some(code));
+        }
+        assert_eq!(
+            ApiErrorCode::parse("no_credential"),
+            Some(ApiErrorCode::NoCredentials)
+        );
+        assert_eq!(ApiErrorCode::parse("unknown"), None);
+    }
+
+    #[test]
+    fn test_api_error_json() {
+        let json = ApiError::new(ApiErrorCode::UpstreamRateLimited, "slow down")
+            .with_provider("deepseek")
+            .anthropic()
+            .to_json();
+        assert_eq!(json["type"], "error");
+        assert_eq!(json["error"]["code"], "upstream_rate_limited");
+        assert_eq!(json["error"]["type"], "rate_limit_error");
+        assert_eq!(json["error"]["provider"], "deepseek");
+        assert_eq!(json["error"]["retryable"], true);
+        assert!(json["error"]["request_id"].is_null());
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            ApiErrorCode::classify(StatusCode::UNAUTHORIZED, false),
+            ApiErrorCode::InvalidApiKey
+        );
+        assert_eq!(
+            ApiErrorCode::classify(StatusCode::UNAUTHORIZED, true),
+            ApiErrorCode::UpstreamAuthFailed
+        );
+        assert_eq!(
+            ApiErrorCode::classify(StatusCode::TOO_MANY_REQUESTS, true),
+            ApiErrorCode::UpstreamRateLimited
+        );
+        assert_eq!(
+            ApiErrorCode::classify(StatusCode::UNPROCESSABLE_ENTITY, false),
+            ApiErrorCode::InvalidRequest
+        );
+        assert_eq!(
+            ApiErrorCode::classify(StatusCode::INTERNAL_SERVER_ERROR, true),
+            ApiErrorCode::UpstreamError
+        );
+    }
+
+    #[test]
+    fn test_normalize_legacy_body() {
+        // 旧格式：只有 message
+        let body = normalize_error_body(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            json!({"error": {"message": "Upstream error: boom"}}),
+            Some("openai"),
+            Some("req-1"),
+        );
+        assert_eq!(body["error"]["message"], "Upstream error: boom");
+        assert_eq!(body["error"]["code"], "upstream_error");
+        assert_eq!(body["error"]["type"], "upstream_error");
+        assert_eq!(body["error"]["provider"], "openai");
+        assert_eq!(body["error"]["retryable"], true);
+        assert_eq!(body["error"]["request_id"], "req-1");
+
+        // 已有的错误类型和可识别的错误码保留
+        let body = normalize_error_body(
+            StatusCode::SERVICE_UNAVAILABLE,
+            json!({"type": "error", "error": {"type": "overloaded_error", "message": "busy"}}),
+            None,
+            None,
+        );
+        assert_eq!(body["type"], "error");
+        assert_eq!(body["error"]["type"], "overloaded_error");
+        assert_eq!(body["error"]["code"], "overloaded");
+        assert_eq!(body["error"]["retryable"], true);
+
+        // 数字错误码原样保留，是否可重试按状态码分类
+        let body = normalize_error_body(
+            StatusCode::UNAUTHORIZED,
+            json!({"error": {"message": "bad key", "code": 401}}),
+            None,
+            None,
+        );
+        assert_eq!(body["error"]["code"], 401);
+        assert_eq!(body["error"]["type"], "authentication_error");
+        assert_eq!(body["error"]["retryable"], false);
+
+        // 上游的字符串错误码原样保留
+        let body = normalize_error_body(
+            StatusCode::TOO_MANY_REQUESTS,
+            json!({"error": {"message": "quota", "code": "insufficient_quota"}}),
+            Some("openai"),
+            None,
+        );
+        assert_eq!(body["error"]["code"], "insufficient_quota");
+        assert_eq!(body["error"]["retryable"], true);
+    }
+
+    #[test]
+    fn test_normalize_plain_text() {
+        let body = normalize_error_body(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            json!("missing field `model`"),
+            None,
+            None,
+        );
+        assert_eq!(body["error"]["message"], "missing field `model`");
+        assert_eq!(body["error"]["code"], "invalid_request");
+
+        // 上游透传、没有 error 字段的正文
+        let body = normalize_error_body(
+            StatusCode::BAD_GATEWAY,
+            json!({"detail": "gateway down"}),
+            Some("qwen"),
+            None,
+        );
+        assert_eq!(body["error"]["message"], "gateway down");
+        assert_eq!(body["error"]["provider"], "qwen");
+        assert_eq!(body["detail"], "gateway down");
+
+        let body = normalize_error_body(
+            StatusCode::BAD_GATEWAY,
+            json!({"status": 502, "trace": "abc"}),
+            None,
+            None,
+        );
+        assert_eq!(body["status"], 502);
+        assert_eq!(body["trace"], "abc");
+        assert!(body["error"]["message"].as_str().unwrap().contains("abc"));
+
+        // 空正文使用状态码的描述
+        let body = normalize_error_body(StatusCode::NOT_FOUND, json!({}), None, None);
+        assert_eq!(body["error"]["message"], "Not Found");
+        assert_eq!(body["error"]["code"], "no_route");
+    }
+}
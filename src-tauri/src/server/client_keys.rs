@@ -0,0 +1,116 @@
+//! 客户端密钥
+//!
+//! 局域网共享时，主机所有者可以在 `server.client_keys` 中为每位成员分发单独的密钥。
+//! 请求通过主密钥或任一启用的客户端密钥鉴权，使用客户端密钥的请求在统计中记到该密钥名下，
+//! 用于按成员汇总请求数、Token 和费用。修改客户端密钥后随配置热重载生效，无需重启服务。
+//!
+//! 语音识别只在主机本地调用、不经过代理 API，其用量（`asr_usage`）按 ASR 凭证统计，
+//! 不区分客户端密钥。
+//!
+//! 内置 Agent 经代理 API 调用时使用 [`agent_api_key`]。该密钥每次启动随机生成、不写入配置，
+//! 只有进程内调用方能拿到；需要读取本机文件的能力（如图片中的 `file://` 引用）只对它开放。
+
+use once_cell::sync::Lazy;
+
+use crate::config::ClientApiKey;
+
+/// 内置 Agent 的密钥
+static AGENT_API_KEY: Lazy<String> =
+    Lazy::new(|| format!("pc_agent_{}", uuid::Uuid::new_v4().simple()));
+
+/// 内置 Agent 调用代理 API 使用的密钥
+pub fn agent_api_key() -> &'static str {
+    &AGENT_API_KEY
+}
+
+/// 请求方
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiCaller {
+    /// 使用主密钥（主机所有者）
+    Owner,
+    /// 使用客户端密钥，值为密钥 ID
+    Client(String),
+    /// 内置 Agent
+    Agent,
+}
+
+impl ApiCaller {
+    /// 按请求携带的密钥识别请求方，密钥无效或已禁用时返回 `None`
+    pub fn identify(key: &str, api_key: &str, client_keys: &[ClientApiKey]) -> Option<Self> {
+        if key == api_key {
+            return Some(Self::Owner);
+        }
+        if key == agent_api_key() {
+            return Some(Self::Agent);
+        }
+        client_keys
+            .iter()
+            .find(|c| !c.disabled && !c.key.is_empty() && c.key == key)
+            .map(|c| Self::Client(c.id.clone()))
+    }
+
+    /// 客户端密钥 ID，主密钥和内置 Agent 为空
+    pub fn client_key_id(&self) -> Option<&str> {
+        match self {
+            Self::Owner | Self::Agent => None,
+            Self::Client(id) => Some(id),
+        }
+    }
+
+    /// 是否允许读取本机文件（只有内置 Agent 可以）
+    pub fn can_read_local_files(&self) -> bool {
+        matches!(self, Self::Agent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(id: &str, key: &str, disabled: bool) -> ClientApiKey {
+        ClientApiKey {
+            id: id.to_string(),
+            name: id.to_string(),
+            key: key.to_string(),
+            disabled,
+        }
+    }
+
+    #[test]
+    fn test_identify() {
+        let clients = [
+            client("alice", "pc_alice", false),
+            client("bob", "pc_bob", true),
+            client("empty", "", false),
+        ];
+
+        assert_eq!(
+            ApiCaller::identify("pc_owner", "pc_owner", &clients),
+            Some(ApiCaller::Owner)
+        );
+        assert_eq!(
+            ApiCaller::identify("pc_alice", "pc_owner", &clients),
+            Some(ApiCaller::Client("alice".to_string()))
+        );
+        // 禁用的密钥和空密钥不能通过
+        assert_eq!(ApiCaller::identify("pc_bob", "pc_owner", &clients), None);
+        assert_eq!(ApiCaller::identify("", "pc_owner", &clients), None);
+        assert_eq!(ApiCaller::identify("pc_other", "pc_owner", &clients), None);
+
+        assert_eq!(
+            ApiCaller::identify(agent_api_key(), "pc_owner", &clients),
+            Some(ApiCaller::Agent)
+        );
+        assert!(ApiCaller::Agent.can_read_local_files());
+        assert!(!ApiCaller::Owner.can_read_local_files());
+    }
+
+    #[test]
+    fn test_client_key_id() {
+        assert_eq!(ApiCaller::Owner.client_key_id(), None);
+        assert_eq!(
+            ApiCaller::Client("alice".to_string()).client_key_id(),
+            Some("alice")
+        );
+    }
+}
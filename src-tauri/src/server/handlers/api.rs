@@ -23,24 +23,30 @@ use axum::{
     Json,
 };
 use chrono::Utc;
-use serde_json::json;
 use std::collections::HashMap;
+use std::time::Instant;
 
 use crate::converter::anthropic_to_openai::convert_anthropic_to_openai;
 use crate::flow_monitor::{
     ClientInfo, FlowError, FlowErrorType, FlowMetadata, FlowType, InterceptAction, InterceptType,
     LLMFlow, LLMRequest, LLMResponse, Message, MessageContent, MessageRole, RequestParameters,
-    RoutingInfo, TokenUsage,
+    RoutingInfo, StreamInfo, ThinkingContent, TokenUsage, ToolCall,
 };
 use crate::models::anthropic::AnthropicMessagesRequest;
 use crate::models::openai::ChatCompletionRequest;
 use crate::processor::RequestContext;
+use crate::server::api_error::{ApiError, ApiErrorCode, ErrorProvider};
 use crate::server::client_detector::ClientType;
-use crate::server::{record_request_telemetry, record_token_usage, AppState};
+use crate::server::client_keys::ApiCaller;
+use crate::server::overrides::RequestOverrides;
+use crate::server::{record_request_telemetry, record_stream_timing, record_token_usage, AppState};
 use crate::server_utils::{
     build_anthropic_response, build_anthropic_stream_response, message_content_len,
     parse_cw_response, safe_truncate,
 };
+use crate::services::{
+    anomaly_service, pipeline_metrics_service, postmortem_service, response_buffer_service,
+};
 use crate::streaming::StreamFormat as StreamingFormat;
 use crate::ProviderType;
 
@@ -547,10 +553,9 @@ async fn check_response_intercept(
 // ============================================================================
 
 /// OpenAI 格式的 API key 验证
-pub async fn verify_api_key(
-    headers: &HeaderMap,
-    expected_key: &str,
-) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+///
+/// 接受主密钥和启用的客户端密钥，返回请求方
+pub async fn verify_api_key(headers: &HeaderMap, state: &AppState) -> Result<ApiCaller, ApiError> {
     let auth = headers
         .get("authorization")
         .or_else(|| headers.get("x-api-key"))
@@ -560,28 +565,25 @@ pub async fn verify_api_key(
         Some(s) if s.starts_with("Bearer ") => &s[7..],
         Some(s) => s,
         None => {
-            return Err((
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({"error": {"message": "No API key provided"}})),
+            return Err(ApiError::new(
+                ApiErrorCode::MissingApiKey,
+                "No API key provided",
             ))
         }
     };
 
-    if key != expected_key {
-        return Err((
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({"error": {"message": "Invalid API key"}})),
-        ));
-    }
-
-    Ok(())
+    let client_keys = state.client_keys.read().await;
+    ApiCaller::identify(key, &state.api_key, &client_keys)
+        .ok_or_else(|| ApiError::new(ApiErrorCode::InvalidApiKey, "Invalid API key"))
 }
 
 /// Anthropic 格式的 API key 验证
+///
+/// 接受主密钥和启用的客户端密钥，返回请求方
 pub async fn verify_api_key_anthropic(
     headers: &HeaderMap,
-    expected_key: &str,
-) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    state: &AppState,
+) -> Result<ApiCaller, ApiError> {
     let auth = headers
         .get("x-api-key")
         .or_else(|| headers.get("authorization"))
@@ -591,33 +593,433 @@ pub async fn verify_api_key_anthropic(
         Some(s) if s.starts_with("Bearer ") => &s[7..],
         Some(s) => s,
         None => {
-            return Err((
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({
-                    "type": "error",
-                    "error": {
-                        "type": "authentication_error",
-                        "message": "No API key provided. Please set the x-api-key header."
-                    }
-                })),
-            ))
+            return Err(ApiError::new(
+                ApiErrorCode::MissingApiKey,
+                "No API key provided. Please set the x-api-key header.",
+            )
+            .anthropic())
         }
     };
 
-    if key != expected_key {
-        return Err((
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({
-                "type": "error",
-                "error": {
-                    "type": "authentication_error",
-                    "message": "Invalid API key"
-                }
-            })),
-        ));
+    let client_keys = state.client_keys.read().await;
+    ApiCaller::identify(key, &state.api_key, &client_keys)
+        .ok_or_else(|| ApiError::new(ApiErrorCode::InvalidApiKey, "Invalid API key").anthropic())
+}
+
+/// 解析 X-Proxycast-* 覆盖请求头并写入请求上下文，非法取值返回 400
+fn parse_request_overrides(
+    headers: &HeaderMap,
+    ctx: &mut RequestContext,
+) -> Result<RequestOverrides, Response> {
+    let overrides = RequestOverrides::from_headers(headers)
+        .map_err(|e| ApiError::new(ApiErrorCode::InvalidRequest, e).into_response())?;
+    if !overrides.is_empty() {
+        tracing::info!(
+            "[OVERRIDE] request_id={} overrides={:?}",
+            ctx.request_id,
+            overrides
+        );
+        postmortem_service::trace(ctx, format!("overrides {:?}", overrides));
+    }
+    overrides.apply_to_context(ctx);
+    Ok(overrides)
+}
+
+/// 获取 X-Proxycast-Credential 指定的凭证，不存在或已禁用时不降级
+async fn select_forced_credential(
+    state: &AppState,
+    ctx: &RequestContext,
+    uuid: &str,
+) -> Result<crate::models::provider_pool_model::ProviderCredential, Response> {
+    let found = match &state.db {
+        Some(db) => state.pool_service.get_by_uuid(db, uuid).ok().flatten(),
+        None => None,
+    };
+    match found {
+        Some(cred) if !cred.is_disabled => {
+            state.logs.write().await.add(
+                "info",
+                &format!(
+                    "[OVERRIDE] request_id={} 使用指定凭证 {}",
+                    ctx.request_id, uuid
+                ),
+            );
+            Ok(cred)
+        }
+        _ => {
+            state.logs.write().await.add(
+                "error",
+                &format!(
+                    "[OVERRIDE] request_id={} 指定凭证 {} 不存在或已禁用，拒绝降级",
+                    ctx.request_id, uuid
+                ),
+            );
+            Err(ApiError::new(
+                ApiErrorCode::CredentialUnavailable,
+                format!("Credential '{}' not found or disabled", uuid),
+            )
+            .into_response())
+        }
     }
+}
 
-    Ok(())
+/// 为 Flow 添加 X-Proxycast-Tag 指定的标签
+async fn tag_flow(state: &AppState, flow_id: Option<&str>, tags: &[String]) {
+    let Some(flow_id) = flow_id else {
+        return;
+    };
+    for tag in tags {
+        state.flow_monitor.add_tag(flow_id, tag.clone()).await;
+    }
+}
+
+/// 将图片负载统计写入请求上下文，供遥测记录
+fn record_image_stats(ctx: &mut RequestContext, stats: crate::converter::image::ImagePayloadStats) {
+    if stats.count == 0 {
+        return;
+    }
+    ctx.set_metadata(
+        crate::converter::image::IMAGE_COUNT_METADATA,
+        serde_json::json!(stats.count),
+    );
+    ctx.set_metadata(
+        crate::converter::image::IMAGE_BYTES_METADATA,
+        serde_json::json!(stats.bytes),
+    );
+}
+
+/// 使用本地 Mock Provider 生成回复
+///
+/// Mock 回复不计入遥测和 Token 统计，避免污染真实 Provider 的数据
+async fn mock_response(
+    state: &AppState,
+    ctx: &mut RequestContext,
+    stream: bool,
+    body: impl FnOnce(&crate::providers::MockProvider) -> serde_json::Value,
+    events: impl FnOnce(&crate::providers::MockProvider) -> Vec<String>,
+) -> Response {
+    postmortem_service::trace(ctx, "provider=mock");
+    state.logs.write().await.add(
+        "info",
+        &format!(
+            "[MOCK] request_id={} model={} stream={}",
+            ctx.request_id, ctx.resolved_model, stream
+        ),
+    );
+
+    let mock = &state.mock_provider;
+    tokio::time::sleep(mock.latency()).await;
+    if !stream {
+        return Json(body(mock)).into_response();
+    }
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/event-stream")
+        .header(header::CACHE_CONTROL, "no-cache")
+        .header(header::CONNECTION, "keep-alive")
+        .body(Body::from_stream(mock.into_stream(events(mock))))
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+/// 在看门狗时限内调用 Provider，超时时取消调用并返回上游超时错误
+///
+/// 时限取请求超时配置；流式请求只限制到收到响应头为止
+async fn call_provider_within_budget(
+    state: &AppState,
+    ctx: &mut RequestContext,
+    call: impl std::future::Future<Output = Response>,
+) -> Result<Response, ApiError> {
+    let budget = state.processor.timeout.config().request_timeout();
+    state
+        .processor
+        .watchdog
+        .run_stage("provider", budget, true, ctx, call)
+        .await
+        .map_err(|e| {
+            ApiError::new(
+                ApiErrorCode::UpstreamTimeout,
+                format!("Upstream did not respond in time ({})", e),
+            )
+        })
+}
+
+/// 上游调用失败时保存诊断包，并在响应头中返回请求 ID、在响应扩展中标明 Provider
+///
+/// 上下文中没有记录实际发往上游的请求体时，诊断包保存 `client_request`。
+/// 读取上游响应体失败时诊断包不含响应体，并返回错误
+async fn capture_postmortem(
+    state: &AppState,
+    ctx: &RequestContext,
+    endpoint: &str,
+    provider: String,
+    client_request: serde_json::Value,
+    response: Response,
+) -> Result<Response, ApiError> {
+    let (mut parts, body) = response.into_parts();
+    let buffered = response_buffer_service::buffer(body).await;
+
+    parts.extensions.insert(ErrorProvider(provider.clone()));
+    // 落盘的超大响应只取开头，诊断包本身也会截断
+    let bundle = postmortem_service::build_bundle(
+        ctx,
+        endpoint,
+        Some(provider.clone()),
+        client_request,
+        parts.status.as_u16(),
+        &parts.headers,
+        buffered.as_ref().map(|b| b.head()).unwrap_or_default(),
+    );
+    postmortem_service::get_postmortem_store().record(bundle);
+    state.logs.write().await.add(
+        "warn",
+        &format!(
+            "[POSTMORTEM] request_id={} status={} 已保存诊断包",
+            ctx.request_id,
+            parts.status.as_u16()
+        ),
+    );
+
+    let buffered = buffered.map_err(|e| {
+        ApiError::new(
+            ApiErrorCode::UpstreamError,
+            format!("Failed to read response body: {}", e),
+        )
+        .with_provider(provider)
+    })?;
+
+    if let Ok(value) = axum::http::HeaderValue::from_str(&ctx.request_id) {
+        parts
+            .headers
+            .insert(postmortem_service::REQUEST_ID_HEADER, value);
+    }
+    Ok(Response::from_parts(parts, buffered.into_body()))
+}
+
+/// 检查成功的非流式响应是否退化
+///
+/// 异常按 Provider 记录到监控面板；开启故障转移时把当前凭证记为一次失败，
+/// 错误次数达到上限后后续请求会切换到其他凭证。响应本身照常返回给客户端，
+/// 读取响应体失败时返回错误
+async fn inspect_anomalies(
+    state: &AppState,
+    ctx: &mut RequestContext,
+    response: Response,
+) -> Result<Response, ApiError> {
+    if !anomaly_service::is_enabled() {
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let buffered = response_buffer_service::buffer(body).await.map_err(|e| {
+        ApiError::new(
+            ApiErrorCode::UpstreamError,
+            format!("Failed to read response body: {}", e),
+        )
+    })?;
+    // 落盘的超大响应不做检测
+    let Some(body_bytes) = buffered.bytes() else {
+        return Ok(Response::from_parts(parts, buffered.into_body()));
+    };
+
+    let records = anomaly_service::inspect(ctx, body_bytes);
+    if !records.is_empty() {
+        let description = anomaly_service::describe(&records);
+        state.logs.write().await.add(
+            "warn",
+            &format!(
+                "[ANOMALY] request_id={} provider={:?} {}",
+                ctx.request_id, ctx.provider, description
+            ),
+        );
+        postmortem_service::trace(ctx, format!("anomaly {}", description));
+
+        if let Some(db) = &state.db {
+            anomaly_service::trigger_failover(
+                &state.pool_service,
+                db,
+                ctx.credential_id.as_deref(),
+                &records,
+            );
+        }
+    }
+
+    Ok(Response::from_parts(parts, buffered.into_body()))
+}
+
+/// 包装成功的流式响应：边转发边捕获，流结束后用完整内容记录 Token 用量并完成 Flow
+///
+/// 上游未返回用量时，输入使用预估值，输出按捕获到的正文计数
+fn tee_streaming_response(
+    state: &AppState,
+    ctx: &RequestContext,
+    flow_id: Option<String>,
+    response: Response,
+    provider: std::sync::Arc<dyn proxycast_providers::Provider>,
+    estimated_input_tokens: u32,
+) -> Response {
+    let (parts, body) = response.into_parts();
+    let state = state.clone();
+    let ctx = ctx.clone();
+    let started_at = ctx.start_time;
+
+    let stream = crate::streaming::tee_stream(
+        body.into_data_stream(),
+        provider,
+        started_at,
+        move |capture| async move {
+            let (input_tokens, output_tokens) = match &capture.usage {
+                Some(usage) => (usage.input_tokens, usage.output_tokens),
+                None => (
+                    estimated_input_tokens,
+                    recount_output_tokens(&ctx, &capture.text),
+                ),
+            };
+            record_token_usage(&state, &ctx, Some(input_tokens), Some(output_tokens));
+            if let (Some(first), Some(last)) = (capture.first_token_ms, capture.last_token_ms) {
+                record_stream_timing(&state, &ctx, first, last, output_tokens);
+            }
+
+            tracing::info!(
+                "[STREAM_TEE] request_id={} completed={} chunks={} bytes={} first_token_ms={:?} last_token_ms={:?}",
+                ctx.request_id,
+                capture.completed,
+                capture.chunk_count,
+                capture.total_bytes,
+                capture.first_token_ms,
+                capture.last_token_ms
+            );
+
+            let Some(fid) = flow_id else {
+                return;
+            };
+            if !capture.completed && capture.text.is_empty() && capture.tool_calls.is_empty() {
+                let error = FlowError::new(FlowErrorType::Cancelled, "客户端在流结束前断开");
+                state.flow_monitor.fail_flow(&fid, error).await;
+                return;
+            }
+
+            let mut llm_response =
+                build_llm_response(200, &capture.text, Some((input_tokens, output_tokens)));
+            if !capture.reasoning.is_empty() {
+                llm_response.thinking = Some(ThinkingContent {
+                    text: capture.reasoning.clone(),
+                    tokens: None,
+                    signature: None,
+                });
+            }
+            llm_response.tool_calls = capture
+                .tool_calls
+                .iter()
+                .map(|call| ToolCall {
+                    id: call.id.clone().unwrap_or_default(),
+                    tool_type: "function".to_string(),
+                    function: crate::flow_monitor::models::FunctionCall {
+                        name: call.name.clone().unwrap_or_default(),
+                        arguments: call.arguments.clone(),
+                    },
+                })
+                .collect();
+            llm_response.size_bytes = capture.total_bytes;
+            llm_response.stream_info = Some(StreamInfo {
+                chunk_count: capture.chunk_count,
+                first_chunk_latency_ms: capture.first_byte_ms.unwrap_or(0),
+                avg_chunk_interval_ms: avg_interval_ms(&capture.delta_offsets_ms),
+                raw_chunks: None,
+            });
+            state
+                .flow_monitor
+                .complete_flow(&fid, Some(llm_response))
+                .await;
+        },
+    );
+
+    Response::from_parts(parts, Body::from_stream(stream))
+}
+
+/// 上游未返回用量时计数输出 Token
+///
+/// 采样到的请求用分词器计数，其余按约 4 字符 1 Token 粗略估算
+fn recount_output_tokens(ctx: &RequestContext, text: &str) -> u32 {
+    if !pipeline_metrics_service::sample_token_recount(&ctx.request_id) {
+        return (text.len() / 4) as u32;
+    }
+    pipeline_metrics_service::measure(pipeline_metrics_service::STEP_TOKEN_RECOUNT, || {
+        crate::services::token_count_service::count_text(text, &ctx.resolved_model)
+    })
+}
+
+/// 创建 Flow（请求和响应拦截依赖 Flow ID）
+///
+/// 请求体捕获采样只决定是否保存正文：未采样的请求仍创建 Flow 并照常拦截，
+/// 只记录元数据
+async fn start_flow(
+    state: &AppState,
+    ctx: &RequestContext,
+    llm_request: &LLMRequest,
+    flow_metadata: &FlowMetadata,
+) -> Option<String> {
+    let started = Instant::now();
+    let flow_id = if pipeline_metrics_service::sample_body_capture(&ctx.request_id) {
+        state
+            .flow_monitor
+            .start_flow(llm_request.clone(), flow_metadata.clone())
+            .await
+    } else {
+        state
+            .flow_monitor
+            .start_flow_without_body(llm_request.clone(), flow_metadata.clone())
+            .await
+    };
+    pipeline_metrics_service::record_step(
+        pipeline_metrics_service::STEP_FLOW_CAPTURE,
+        started.elapsed(),
+    );
+    flow_id
+}
+
+/// 相邻增量之间的平均间隔
+fn avg_interval_ms(offsets: &[u64]) -> f64 {
+    match (offsets.first(), offsets.last()) {
+        (Some(first), Some(last)) if offsets.len() > 1 => {
+            (last - first) as f64 / (offsets.len() - 1) as f64
+        }
+        _ => 0.0,
+    }
+}
+
+/// 记录预估输入 Token，明显超出模型上下文长度时返回错误响应
+///
+/// 略微超出时只记录警告、照常转发，由上游判断
+async fn check_token_count(
+    state: &AppState,
+    ctx: &mut RequestContext,
+    count: &crate::services::token_count_service::TokenCount,
+) -> Option<Response> {
+    ctx.set_metadata(
+        crate::services::token_count_service::ESTIMATED_INPUT_TOKENS_METADATA,
+        serde_json::json!(count.input_tokens),
+    );
+    let Err(e) = crate::services::token_count_service::check_context(count) else {
+        if count.exceeds_context {
+            state.logs.write().await.add(
+                "warn",
+                &format!(
+                    "[TOKEN_COUNT] request_id={} 输入约 {} tokens，可能超出模型 {} 的上下文长度 {}，照常转发",
+                    ctx.request_id,
+                    count.input_tokens,
+                    count.model,
+                    count.context_length.unwrap_or_default()
+                ),
+            );
+        }
+        return None;
+    };
+    state.logs.write().await.add(
+        "warn",
+        &format!("[TOKEN_COUNT] request_id={} {}", ctx.request_id, e),
+    );
+    Some(ApiError::new(ApiErrorCode::ContextLengthExceeded, e).into_response())
 }
 
 pub async fn chat_completions(
@@ -632,21 +1034,52 @@ pub async fn chat_completions(
     eprintln!("[CHAT_COMPLETIONS] 流式: {}", request.stream);
     eprintln!("[CHAT_COMPLETIONS] 消息数量: {}", request.messages.len());
 
-    if let Err(e) = verify_api_key(&headers, &state.api_key).await {
-        eprintln!("[CHAT_COMPLETIONS] 认证失败!");
-        state
-            .logs
-            .write()
-            .await
-            .add("warn", "Unauthorized request to /v1/chat/completions");
-        return e.into_response();
-    }
+    let caller = match verify_api_key(&headers, &state).await {
+        Ok(caller) => caller,
+        Err(e) => {
+            eprintln!("[CHAT_COMPLETIONS] 认证失败!");
+            state
+                .logs
+                .write()
+                .await
+                .add("warn", "Unauthorized request to /v1/chat/completions");
+            return e.into_response();
+        }
+    };
     eprintln!("[CHAT_COMPLETIONS] 认证成功");
 
     // 创建请求上下文
     let mut ctx = RequestContext::new(request.model.clone()).with_stream(request.stream);
+    if let Some(client_key_id) = caller.client_key_id() {
+        ctx.set_client_key_id(client_key_id.to_string());
+    }
     eprintln!("[CHAT_COMPLETIONS] 请求ID: {}", ctx.request_id);
 
+    // 解析 X-Proxycast-* 覆盖请求头
+    let overrides = match parse_request_overrides(&headers, &mut ctx) {
+        Ok(overrides) => overrides,
+        Err(response) => return response,
+    };
+
+    // 校验图片大小，内置 Agent 的请求内联本地图片
+    if let Err(e) = crate::converter::image::normalize_openai_images(
+        &mut request,
+        crate::converter::image::OPENAI_MAX_IMAGE_BYTES,
+        caller.can_read_local_files(),
+    )
+    .await
+    {
+        state.logs.write().await.add(
+            "warn",
+            &format!("[IMAGE] request_id={} 图片无效: {}", ctx.request_id, e),
+        );
+        return ApiError::new(ApiErrorCode::InvalidRequest, e).into_response();
+    }
+    record_image_stats(
+        &mut ctx,
+        crate::converter::image::openai_image_stats(&request),
+    );
+
     state.logs.write().await.add(
         "info",
         &format!(
@@ -674,11 +1107,27 @@ pub async fn chat_completions(
                 ctx.request_id, ctx.original_model, resolved_model
             ),
         );
+        let step = format!("alias {} -> {}", ctx.original_model, resolved_model);
+        postmortem_service::trace(&mut ctx, step);
+    }
+
+    // 预估输入 Token 并检测上下文溢出
+    let token_count =
+        pipeline_metrics_service::measure(pipeline_metrics_service::STEP_TOKEN_COUNT, || {
+            crate::services::token_count_service::with_context(
+                state.db.as_ref(),
+                &request.model,
+                crate::services::token_count_service::count_openai_request(&request),
+            )
+        });
+    if let Some(response) = check_token_count(&state, &mut ctx, &token_count).await {
+        return response;
     }
 
     // 应用参数注入
     let injection_enabled = *state.injection_enabled.read().await;
     if injection_enabled {
+        let started = Instant::now();
         let injector = state.processor.injector.read().await;
         let mut payload = serde_json::to_value(&request).unwrap_or_default();
         let result = injector.inject(&request.model, &mut payload);
@@ -690,11 +1139,16 @@ pub async fn chat_completions(
                     ctx.request_id, result.applied_rules, result.injected_params
                 ),
             );
+            postmortem_service::trace(&mut ctx, format!("inject rules={:?}", result.applied_rules));
             // 更新请求
             if let Ok(updated) = serde_json::from_value(payload) {
                 request = updated;
             }
         }
+        pipeline_metrics_service::record_step(
+            pipeline_metrics_service::STEP_INJECTION,
+            started.elapsed(),
+        );
     }
 
     // 根据客户端类型选择 Provider
@@ -713,6 +1167,13 @@ pub async fn chat_completions(
             ctx.request_id, client_type, selected_provider
         ),
     );
+    postmortem_service::trace(
+        &mut ctx,
+        format!(
+            "client_type={} selected_provider={}",
+            client_type, selected_provider
+        ),
+    );
 
     // 记录路由结果（使用 selected_provider）
     state.logs.write().await.add(
@@ -723,96 +1184,119 @@ pub async fn chat_completions(
         ),
     );
 
-    // 从请求头提取 X-Provider-Id（用于精确路由）
-    let provider_id_header = headers
-        .get("x-provider-id")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_lowercase());
+    // X-Proxycast-Provider / X-Provider-Id（用于精确路由）
+    let provider_id_header = overrides.provider.clone();
+
+    // Mock Provider：本地生成模拟回复，不调用上游
+    let mock_provider_id = provider_id_header.as_deref().unwrap_or(&selected_provider);
+    if state
+        .mock_provider
+        .matches(Some(mock_provider_id), &request.model)
+    {
+        return mock_response(
+            &state,
+            &mut ctx,
+            request.stream,
+            |mock| mock.openai_completion(&request),
+            |mock| mock.openai_stream_events(&request),
+        )
+        .await;
+    }
+
+    // X-Proxycast-Credential 指定的凭证优先于凭证池选择
+    let forced_credential = match overrides.credential.as_deref() {
+        Some(uuid) => match select_forced_credential(&state, &ctx, uuid).await {
+            Ok(cred) => Some(cred),
+            Err(response) => return response,
+        },
+        None => None,
+    };
 
     // 尝试从凭证池中选择凭证（带客户端兼容性检查）
     // 如果指定了 X-Provider-Id，优先使用它（不降级）
     // 否则使用 selected_provider
     eprintln!("[CHAT_COMPLETIONS] 开始选择凭证...");
-    let credential = match &state.db {
-        Some(db) => {
-            // 如果指定了 X-Provider-Id，优先使用它（不降级）
-            if let Some(ref explicit_provider_id) = provider_id_header {
-                eprintln!(
-                    "[CHAT_COMPLETIONS] 使用 X-Provider-Id 指定的 provider: {}",
-                    explicit_provider_id
-                );
-                let cred = state
-                    .pool_service
-                    .select_credential_with_client_check(
-                        db,
-                        explicit_provider_id,
-                        Some(&request.model),
-                        Some(&client_type),
-                    )
-                    .ok()
-                    .flatten();
-
-                if cred.is_none() {
+    let credential = if forced_credential.is_some() {
+        forced_credential
+    } else {
+        match &state.db {
+            Some(db) => {
+                // 如果指定了 X-Provider-Id，优先使用它（不降级）
+                if let Some(ref explicit_provider_id) = provider_id_header {
                     eprintln!(
-                        "[CHAT_COMPLETIONS] X-Provider-Id '{}' 没有可用凭证，不进行降级",
+                        "[CHAT_COMPLETIONS] 使用 X-Provider-Id 指定的 provider: {}",
                         explicit_provider_id
                     );
-                    state.logs.write().await.add(
-                        "error",
-                        &format!(
-                            "[ROUTE] No available credentials for explicitly specified provider '{}', refusing to fallback",
+                    let cred = state
+                        .pool_service
+                        .select_credential_with_client_check(
+                            db,
+                            explicit_provider_id,
+                            Some(&request.model),
+                            Some(&client_type),
+                        )
+                        .ok()
+                        .flatten();
+
+                    if cred.is_none() {
+                        eprintln!(
+                            "[CHAT_COMPLETIONS] X-Provider-Id '{}' 没有可用凭证，不进行降级",
                             explicit_provider_id
-                        ),
-                    );
-                    // 返回错误，不降级
-                    return (
-                        StatusCode::SERVICE_UNAVAILABLE,
-                        Json(json!({
-                            "error": {
-                                "message": format!("No available credentials for provider '{}'", explicit_provider_id),
-                                "type": "provider_unavailable",
-                                "code": "no_credentials"
-                            }
-                        })),
-                    )
+                        );
+                        state.logs.write().await.add(
+                            "error",
+                            &format!(
+                                "[ROUTE] No available credentials for explicitly specified provider '{}', refusing to fallback",
+                                explicit_provider_id
+                            ),
+                        );
+                        // 返回错误，不降级
+                        return ApiError::new(
+                            ApiErrorCode::NoCredentials,
+                            format!(
+                                "No available credentials for provider '{}'",
+                                explicit_provider_id
+                            ),
+                        )
                         .into_response();
-                }
-                cred
-            } else {
-                // 使用 selected_provider（从 API Server 配置中获取）
-                eprintln!(
-                    "[CHAT_COMPLETIONS] 尝试从凭证池选择: provider={}, model={}",
-                    selected_provider, request.model
-                );
-                let cred = state
-                    .pool_service
-                    .select_credential_with_client_check(
-                        db,
-                        &selected_provider,
-                        Some(&request.model),
-                        Some(&client_type),
-                    )
-                    .ok()
-                    .flatten();
-
-                if cred.is_some() {
-                    eprintln!(
-                        "[CHAT_COMPLETIONS] 找到凭证: provider={}",
-                        selected_provider
-                    );
+                    }
+                    cred
                 } else {
+                    // 使用 selected_provider（从 API Server 配置中获取）
                     eprintln!(
-                        "[CHAT_COMPLETIONS] 未找到凭证: provider={}",
-                        selected_provider
+                        "[CHAT_COMPLETIONS] 尝试从凭证池选择: provider={}, model={}",
+                        selected_provider, request.model
                     );
-                }
+                    let cred = state
+                        .pool_service
+                        .select_credential_with_client_check(
+                            db,
+                            &selected_provider,
+                            Some(&request.model),
+                            Some(&client_type),
+                        )
+                        .ok()
+                        .flatten();
+
+                    if cred.is_some() {
+                        eprintln!(
+                            "[CHAT_COMPLETIONS] 找到凭证: provider={}",
+                            selected_provider
+                        );
+                    } else {
+                        eprintln!(
+                            "[CHAT_COMPLETIONS] 未找到凭证: provider={}",
+                            selected_provider
+                        );
+                    }
 
-                cred
+                    cred
+                }
+            }
+            None => {
+                eprintln!("[CHAT_COMPLETIONS] 数据库未初始化!");
+                None
             }
-        }
-        None => {
-            eprintln!("[CHAT_COMPLETIONS] 数据库未初始化!");
-            None
         }
     };
 
@@ -984,6 +1468,15 @@ pub async fn chat_completions(
                 &cred.uuid[..8]
             ),
         );
+        postmortem_service::trace(
+            &mut ctx,
+            format!(
+                "credential type={} name={:?} uuid={}",
+                cred.provider_type,
+                cred.name,
+                &cred.uuid[..8.min(cred.uuid.len())]
+            ),
+        );
 
         // 启动 Flow 捕获
         let llm_request = build_llm_request_from_openai(&request, "/v1/chat/completions", &headers);
@@ -1013,10 +1506,10 @@ pub async fn chat_completions(
             &headers,
             &ctx.request_id,
         );
-        let flow_id = state
-            .flow_monitor
-            .start_flow(llm_request.clone(), flow_metadata.clone())
-            .await;
+        ctx.set_provider(provider_type);
+        ctx.set_credential_id(cred.uuid.clone());
+        let flow_id = start_flow(&state, &ctx, &llm_request, &flow_metadata).await;
+        tag_flow(&state, flow_id.as_deref(), &overrides.tags).await;
 
         // 检查是否需要拦截请求
         // **Validates: Requirements 2.1, 2.3, 2.5**
@@ -1035,23 +1528,50 @@ pub async fn chat_completions(
                     // 请求被取消，标记 Flow 失败并返回错误
                     let error = FlowError::new(FlowErrorType::Cancelled, "请求被用户取消");
                     state.flow_monitor.fail_flow(fid, error).await;
-                    return (
-                        StatusCode::BAD_REQUEST,
-                        Json(
-                            serde_json::json!({"error": {"message": "Request cancelled by user"}}),
-                        ),
+                    return ApiError::new(
+                        ApiErrorCode::RequestCancelled,
+                        "Request cancelled by user",
                     )
-                        .into_response();
+                    .into_response();
                 }
             }
         }
 
         eprintln!("[CHAT_COMPLETIONS] 调用 Provider: {}", cred.provider_type);
-        let response = call_provider_openai(&state, &cred, &request, flow_id.as_deref()).await;
+        let upstream = postmortem_service::UpstreamRequest::default();
+        let response = match call_provider_within_budget(
+            &state,
+            &mut ctx,
+            call_provider_openai(&state, &cred, &request, flow_id.as_deref(), &upstream),
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(error) => error.into_response(),
+        };
         eprintln!(
             "[CHAT_COMPLETIONS] Provider 响应状态: {}",
             response.status()
         );
+        let response = if !response.status().is_success() {
+            upstream.store(&mut ctx);
+            capture_postmortem(
+                &state,
+                &ctx,
+                "/v1/chat/completions",
+                cred.provider_type.to_string(),
+                serde_json::to_value(&request).unwrap_or_default(),
+                response,
+            )
+            .await
+            .unwrap_or_else(|error| error.into_response())
+        } else if !request.stream {
+            inspect_anomalies(&state, &mut ctx, response)
+                .await
+                .unwrap_or_else(|error| error.into_response())
+        } else {
+            response
+        };
 
         // 记录请求统计
         let is_success = response.status().is_success();
@@ -1063,9 +1583,9 @@ pub async fn chat_completions(
         };
         record_request_telemetry(&state, &ctx, status, None);
 
-        // 如果成功且需要 Flow 捕获，提取响应体内容和响应头
+        // 成功的非流式响应：读取上游返回的用量，并提取响应体内容和响应头用于 Flow 捕获
         // 注意：非流式响应需要读取 body，所以必须在这里处理
-        if is_success && flow_id.is_some() && !request.stream {
+        if is_success && !request.stream {
             // 将 Response 转换为 bytes
             let (parts, body) = response.into_parts();
 
@@ -1077,13 +1597,13 @@ pub async fn chat_completions(
                 }
             }
 
-            let body_bytes = match axum::body::to_bytes(body, usize::MAX).await {
-                Ok(bytes) => bytes,
+            let buffered = match response_buffer_service::buffer(body).await {
+                Ok(buffered) => buffered,
                 Err(e) => {
-                    eprintln!("[CHAT_COMPLETIONS] 读取响应体失败: {}", e);
+                    eprintln!("[CHAT_COMPLETIONS] {}", e);
                     // 如果读取失败，返回错误
                     if let Some(fid) = flow_id {
-                        let error = FlowError::new(FlowErrorType::Network, &e.to_string());
+                        let error = FlowError::new(FlowErrorType::Network, &e);
                         state.flow_monitor.fail_flow(&fid, error).await;
                     }
                     return (
@@ -1094,6 +1614,17 @@ pub async fn chat_completions(
                 }
             };
 
+            // 落盘的超大响应不解析内容，Flow 只记录完成状态
+            let Some(body_bytes) = buffered.bytes().cloned() else {
+                if let Some(fid) = flow_id {
+                    state
+                        .flow_monitor
+                        .complete_flow(&fid, Some(build_llm_response(200, "", None)))
+                        .await;
+                }
+                return Response::from_parts(parts, buffered.into_body());
+            };
+
             // 解析响应体
             let response_json: serde_json::Value = match serde_json::from_slice(&body_bytes) {
                 Ok(json) => json,
@@ -1108,7 +1639,7 @@ pub async fn chat_completions(
                         state.flow_monitor.fail_flow(&fid, error).await;
                     }
                     // 重新构建响应
-                    let response = Response::from_parts(parts, Body::from(body_bytes));
+                    let response = Response::from_parts(parts, buffered.into_body());
                     return response;
                 }
             };
@@ -1221,7 +1752,7 @@ pub async fn chat_completions(
             }
 
             // 重新构建响应返回给客户端
-            let response = Response::from_parts(parts, Body::from(body_bytes));
+            let response = Response::from_parts(parts, buffered.into_body());
             return response;
         } else {
             // 流式响应或没有 Flow 捕获，直接返回
@@ -1239,6 +1770,18 @@ pub async fn chat_completions(
                 .sum::<usize>() as u32;
             let estimated_output_tokens = if is_success { 100u32 } else { 0u32 };
 
+            // 流式成功响应：旁路捕获完整内容，流结束后记录实际用量并完成 Flow
+            if is_success && request.stream {
+                return tee_streaming_response(
+                    &state,
+                    &ctx,
+                    flow_id,
+                    response,
+                    std::sync::Arc::new(proxycast_providers::OpenAiCompatibleProvider::openai()),
+                    estimated_input_tokens,
+                );
+            }
+
             if is_success {
                 record_token_usage(
                     &state,
@@ -1275,17 +1818,14 @@ pub async fn chat_completions(
                 selected_provider, client_type
             ),
         );
-        return (
-            StatusCode::SERVICE_UNAVAILABLE,
-            Json(serde_json::json!({
-                "error": {
-                    "message": format!("没有找到可用的 '{}' 凭证。请在凭证池中添加对应的凭证。", selected_provider),
-                    "type": "no_credential_error",
-                    "code": "no_credential"
-                }
-            })),
+        return ApiError::new(
+            ApiErrorCode::NoCredentials,
+            format!(
+                "没有找到可用的 '{}' 凭证。请在凭证池中添加对应的凭证。",
+                selected_provider
+            ),
         )
-            .into_response();
+        .into_response();
     }
 
     state.logs.write().await.add(
@@ -1312,10 +1852,8 @@ pub async fn chat_completions(
         &headers,
         &ctx.request_id,
     );
-    let flow_id = state
-        .flow_monitor
-        .start_flow(llm_request.clone(), flow_metadata.clone())
-        .await;
+    let flow_id = start_flow(&state, &ctx, &llm_request, &flow_metadata).await;
+    tag_flow(&state, flow_id.as_deref(), &overrides.tags).await;
 
     // 检查是否需要拦截请求（legacy mode）
     // **Validates: Requirements 2.1, 2.3, 2.5**
@@ -1333,10 +1871,7 @@ pub async fn chat_completions(
                 // 请求被取消，标记 Flow 失败并返回错误
                 let error = FlowError::new(FlowErrorType::Cancelled, "请求被用户取消");
                 state.flow_monitor.fail_flow(fid, error).await;
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(serde_json::json!({"error": {"message": "Request cancelled by user"}})),
-                )
+                return ApiError::new(ApiErrorCode::RequestCancelled, "Request cancelled by user")
                     .into_response();
             }
         }
@@ -1818,17 +2353,47 @@ pub async fn anthropic_messages(
     Json(mut request): Json<AnthropicMessagesRequest>,
 ) -> Response {
     // 使用 Anthropic 格式的认证验证（优先检查 x-api-key）
-    if let Err(e) = verify_api_key_anthropic(&headers, &state.api_key).await {
-        state
-            .logs
-            .write()
-            .await
-            .add("warn", "Unauthorized request to /v1/messages");
-        return e.into_response();
-    }
+    let caller = match verify_api_key_anthropic(&headers, &state).await {
+        Ok(caller) => caller,
+        Err(e) => {
+            state
+                .logs
+                .write()
+                .await
+                .add("warn", "Unauthorized request to /v1/messages");
+            return e.into_response();
+        }
+    };
 
     // 创建请求上下文
     let mut ctx = RequestContext::new(request.model.clone()).with_stream(request.stream);
+    if let Some(client_key_id) = caller.client_key_id() {
+        ctx.set_client_key_id(client_key_id.to_string());
+    }
+
+    // 解析 X-Proxycast-* 覆盖请求头
+    let overrides = match parse_request_overrides(&headers, &mut ctx) {
+        Ok(overrides) => overrides,
+        Err(response) => return response,
+    };
+
+    // 校验图片大小
+    if let Err(e) = crate::converter::image::check_anthropic_images(
+        &request,
+        crate::converter::image::ANTHROPIC_MAX_IMAGE_BYTES,
+    ) {
+        state.logs.write().await.add(
+            "warn",
+            &format!("[IMAGE] request_id={} 图片无效: {}", ctx.request_id, e),
+        );
+        return ApiError::new(ApiErrorCode::InvalidRequest, e)
+            .anthropic()
+            .into_response();
+    }
+    record_image_stats(
+        &mut ctx,
+        crate::converter::image::anthropic_image_stats(&request),
+    );
 
     // 详细记录请求信息
     let msg_count = request.messages.len();
@@ -1856,6 +2421,21 @@ pub async fn anthropic_messages(
                 ctx.request_id, ctx.original_model, resolved_model
             ),
         );
+        let step = format!("alias {} -> {}", ctx.original_model, resolved_model);
+        postmortem_service::trace(&mut ctx, step);
+    }
+
+    // 预估输入 Token 并检测上下文溢出
+    let token_count =
+        pipeline_metrics_service::measure(pipeline_metrics_service::STEP_TOKEN_COUNT, || {
+            crate::services::token_count_service::with_context(
+                state.db.as_ref(),
+                &request.model,
+                crate::services::token_count_service::count_anthropic_request(&request),
+            )
+        });
+    if let Some(response) = check_token_count(&state, &mut ctx, &token_count).await {
+        return response;
     }
 
     // 记录最后一条消息的角色和内容预览
@@ -1887,6 +2467,7 @@ pub async fn anthropic_messages(
     // 应用参数注入
     let injection_enabled = *state.injection_enabled.read().await;
     if injection_enabled {
+        let started = Instant::now();
         let injector = state.processor.injector.read().await;
         let mut payload = serde_json::to_value(&request).unwrap_or_default();
         let result = injector.inject(&request.model, &mut payload);
@@ -1898,11 +2479,16 @@ pub async fn anthropic_messages(
                     ctx.request_id, result.applied_rules, result.injected_params
                 ),
             );
+            postmortem_service::trace(&mut ctx, format!("inject rules={:?}", result.applied_rules));
             // 更新请求
             if let Ok(updated) = serde_json::from_value(payload) {
                 request = updated;
             }
         }
+        pipeline_metrics_service::record_step(
+            pipeline_metrics_service::STEP_INJECTION,
+            started.elapsed(),
+        );
     }
 
     // 根据客户端类型选择 Provider
@@ -1917,6 +2503,13 @@ pub async fn anthropic_messages(
             ctx.request_id, client_type, selected_provider
         ),
     );
+    postmortem_service::trace(
+        &mut ctx,
+        format!(
+            "client_type={} selected_provider={}",
+            client_type, selected_provider
+        ),
+    );
 
     // 记录路由结果（使用 selected_provider）
     state.logs.write().await.add(
@@ -1927,94 +2520,119 @@ pub async fn anthropic_messages(
         ),
     );
 
-    // 从请求头提取 X-Provider-Id（用于精确路由）
-    let provider_id_header = headers
-        .get("x-provider-id")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_lowercase());
+    // X-Proxycast-Provider / X-Provider-Id（用于精确路由）
+    let provider_id_header = overrides.provider.clone();
+
+    // Mock Provider：本地生成模拟回复，不调用上游
+    let mock_provider_id = provider_id_header.as_deref().unwrap_or(&selected_provider);
+    if state
+        .mock_provider
+        .matches(Some(mock_provider_id), &request.model)
+    {
+        return mock_response(
+            &state,
+            &mut ctx,
+            request.stream,
+            |mock| mock.anthropic_message(&request),
+            |mock| mock.anthropic_stream_events(&request),
+        )
+        .await;
+    }
+
+    // X-Proxycast-Credential 指定的凭证优先于凭证池选择
+    let forced_credential = match overrides.credential.as_deref() {
+        Some(uuid) => match select_forced_credential(&state, &ctx, uuid).await {
+            Ok(cred) => Some(cred),
+            Err(response) => return response,
+        },
+        None => None,
+    };
 
     // 尝试从凭证池中选择凭证（带客户端兼容性检查）
     // 如果指定了 X-Provider-Id，优先使用它（不降级）
     // 否则使用 selected_provider
-    let credential = match &state.db {
-        Some(db) => {
-            // 如果指定了 X-Provider-Id，优先使用它（不降级）
-            if let Some(ref explicit_provider_id) = provider_id_header {
-                eprintln!(
-                    "[ANTHROPIC_MESSAGES] 使用 X-Provider-Id 指定的 provider: {}",
-                    explicit_provider_id
-                );
-                let cred = state
-                    .pool_service
-                    .select_credential_with_client_check(
-                        db,
-                        explicit_provider_id,
-                        Some(&request.model),
-                        Some(&client_type),
-                    )
-                    .ok()
-                    .flatten();
-
-                if cred.is_none() {
+    let credential = if forced_credential.is_some() {
+        forced_credential
+    } else {
+        match &state.db {
+            Some(db) => {
+                // 如果指定了 X-Provider-Id，优先使用它（不降级）
+                if let Some(ref explicit_provider_id) = provider_id_header {
                     eprintln!(
-                        "[AMP] X-Provider-Id '{}' 没有可用凭证，不进行降级",
+                        "[ANTHROPIC_MESSAGES] 使用 X-Provider-Id 指定的 provider: {}",
                         explicit_provider_id
                     );
-                    state.logs.write().await.add(
-                        "error",
-                        &format!(
-                            "[ROUTE] No available credentials for explicitly specified provider '{}', refusing to fallback",
+                    let cred = state
+                        .pool_service
+                        .select_credential_with_client_check(
+                            db,
+                            explicit_provider_id,
+                            Some(&request.model),
+                            Some(&client_type),
+                        )
+                        .ok()
+                        .flatten();
+
+                    if cred.is_none() {
+                        eprintln!(
+                            "[AMP] X-Provider-Id '{}' 没有可用凭证，不进行降级",
                             explicit_provider_id
-                        ),
-                    );
-                    // 返回错误，不降级
-                    return (
-                        StatusCode::SERVICE_UNAVAILABLE,
-                        Json(json!({
-                            "error": {
-                                "type": "provider_unavailable",
-                                "message": format!("No available credentials for provider '{}'", explicit_provider_id)
-                            }
-                        })),
-                    )
+                        );
+                        state.logs.write().await.add(
+                            "error",
+                            &format!(
+                                "[ROUTE] No available credentials for explicitly specified provider '{}', refusing to fallback",
+                                explicit_provider_id
+                            ),
+                        );
+                        // 返回错误，不降级
+                        return ApiError::new(
+                            ApiErrorCode::NoCredentials,
+                            format!(
+                                "No available credentials for provider '{}'",
+                                explicit_provider_id
+                            ),
+                        )
+                        .anthropic()
                         .into_response();
-                }
-                cred
-            } else {
-                // 使用 selected_provider（从 API Server 配置中获取）
-                eprintln!(
-                    "[ANTHROPIC_MESSAGES] 尝试从凭证池选择: provider={}, model={}",
-                    selected_provider, request.model
-                );
-                let cred = state
-                    .pool_service
-                    .select_credential_with_client_check(
-                        db,
-                        &selected_provider,
-                        Some(&request.model),
-                        Some(&client_type),
-                    )
-                    .ok()
-                    .flatten();
-
-                if cred.is_some() {
-                    eprintln!(
-                        "[ANTHROPIC_MESSAGES] 找到凭证: provider={}",
-                        selected_provider
-                    );
+                    }
+                    cred
                 } else {
+                    // 使用 selected_provider（从 API Server 配置中获取）
                     eprintln!(
-                        "[ANTHROPIC_MESSAGES] 未找到凭证: provider={}",
-                        selected_provider
+                        "[ANTHROPIC_MESSAGES] 尝试从凭证池选择: provider={}, model={}",
+                        selected_provider, request.model
                     );
-                }
+                    let cred = state
+                        .pool_service
+                        .select_credential_with_client_check(
+                            db,
+                            &selected_provider,
+                            Some(&request.model),
+                            Some(&client_type),
+                        )
+                        .ok()
+                        .flatten();
 
-                cred
+                    if cred.is_some() {
+                        eprintln!(
+                            "[ANTHROPIC_MESSAGES] 找到凭证: provider={}",
+                            selected_provider
+                        );
+                    } else {
+                        eprintln!(
+                            "[ANTHROPIC_MESSAGES] 未找到凭证: provider={}",
+                            selected_provider
+                        );
+                    }
+
+                    cred
+                }
+            }
+            None => {
+                eprintln!("[ANTHROPIC_MESSAGES] 数据库未初始化!");
+                None
             }
-        }
-        None => {
-            eprintln!("[ANTHROPIC_MESSAGES] 数据库未初始化!");
-            None
         }
     };
 
@@ -2077,6 +2695,15 @@ pub async fn anthropic_messages(
                 &cred.uuid[..8]
             ),
         );
+        postmortem_service::trace(
+            &mut ctx,
+            format!(
+                "credential type={} name={:?} uuid={}",
+                cred.provider_type,
+                cred.name,
+                &cred.uuid[..8.min(cred.uuid.len())]
+            ),
+        );
 
         // 启动 Flow 捕获
         let llm_request = build_llm_request_from_anthropic(&request, "/v1/messages", &headers);
@@ -2104,10 +2731,10 @@ pub async fn anthropic_messages(
             &headers,
             &ctx.request_id,
         );
-        let flow_id = state
-            .flow_monitor
-            .start_flow(llm_request.clone(), flow_metadata.clone())
-            .await;
+        ctx.set_provider(provider_type);
+        ctx.set_credential_id(cred.uuid.clone());
+        let flow_id = start_flow(&state, &ctx, &llm_request, &flow_metadata).await;
+        tag_flow(&state, flow_id.as_deref(), &overrides.tags).await;
 
         // 检查是否需要拦截请求
         // **Validates: Requirements 2.1, 2.3, 2.5**
@@ -2126,22 +2753,46 @@ pub async fn anthropic_messages(
                     // 请求被取消，标记 Flow 失败并返回错误
                     let error = FlowError::new(FlowErrorType::Cancelled, "请求被用户取消");
                     state.flow_monitor.fail_flow(fid, error).await;
-                    return (
-                        StatusCode::BAD_REQUEST,
-                        Json(serde_json::json!({
-                            "type": "error",
-                            "error": {
-                                "type": "request_cancelled",
-                                "message": "Request cancelled by user"
-                            }
-                        })),
+                    return ApiError::new(
+                        ApiErrorCode::RequestCancelled,
+                        "Request cancelled by user",
                     )
-                        .into_response();
+                    .anthropic()
+                    .into_response();
                 }
             }
         }
 
-        let response = call_provider_anthropic(&state, &cred, &request, flow_id.as_deref()).await;
+        let upstream = postmortem_service::UpstreamRequest::default();
+        let response = match call_provider_within_budget(
+            &state,
+            &mut ctx,
+            call_provider_anthropic(&state, &cred, &request, flow_id.as_deref(), &upstream),
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(error) => error.anthropic().into_response(),
+        };
+        let response = if !response.status().is_success() {
+            upstream.store(&mut ctx);
+            capture_postmortem(
+                &state,
+                &ctx,
+                "/v1/messages",
+                cred.provider_type.to_string(),
+                serde_json::to_value(&request).unwrap_or_default(),
+                response,
+            )
+            .await
+            .unwrap_or_else(|error| error.anthropic().into_response())
+        } else if !request.stream {
+            inspect_anomalies(&state, &mut ctx, response)
+                .await
+                .unwrap_or_else(|error| error.anthropic().into_response())
+        } else {
+            response
+        };
 
         // 记录请求统计
         let is_success = response.status().is_success();
@@ -2171,24 +2822,81 @@ pub async fn anthropic_messages(
             .sum::<usize>() as u32;
         let estimated_output_tokens = if is_success { 100u32 } else { 0u32 };
 
-        if is_success {
-            record_token_usage(
+        // 流式成功响应：旁路捕获完整内容，流结束后记录实际用量并完成 Flow
+        if is_success && request.stream {
+            return tee_streaming_response(
                 &state,
                 &ctx,
-                Some(estimated_input_tokens),
-                Some(estimated_output_tokens),
+                flow_id,
+                response,
+                std::sync::Arc::new(proxycast_providers::AnthropicProvider::new()),
+                estimated_input_tokens,
             );
         }
 
-        // 完成 Flow 捕获并检查响应拦截
-        // **Validates: Requirements 2.1, 2.5**
-        if let Some(fid) = flow_id {
-            if is_success {
-                let llm_response = build_llm_response(
-                    200,
-                    "",
-                    Some((estimated_input_tokens, estimated_output_tokens)),
-                );
+        // 成功的非流式响应：读取上游返回的用量和内容，用于统计和 Flow 捕获
+        if is_success {
+            let (parts, body) = response.into_parts();
+
+            let mut response_headers = HashMap::new();
+            for (name, value) in parts.headers.iter() {
+                if let Ok(v) = value.to_str() {
+                    response_headers.insert(name.as_str().to_string(), v.to_string());
+                }
+            }
+
+            let buffered = match response_buffer_service::buffer(body).await {
+                Ok(buffered) => buffered,
+                Err(e) => {
+                    if let Some(fid) = flow_id {
+                        let error = FlowError::new(FlowErrorType::Network, &e);
+                        state.flow_monitor.fail_flow(&fid, error).await;
+                    }
+                    return ApiError::new(
+                        ApiErrorCode::UpstreamError,
+                        format!("Failed to read response body: {}", e),
+                    )
+                    .anthropic()
+                    .into_response();
+                }
+            };
+
+            // 落盘的超大响应或无法解析的响应体不提取内容，用量回退到估算值
+            let response_json: Option<serde_json::Value> = buffered
+                .bytes()
+                .and_then(|bytes| serde_json::from_slice(bytes).ok());
+            let content = response_json
+                .as_ref()
+                .and_then(|json| json["content"].as_array())
+                .map(|blocks| {
+                    blocks
+                        .iter()
+                        .filter(|b| b["type"] == "text")
+                        .filter_map(|b| b["text"].as_str())
+                        .collect::<String>()
+                })
+                .unwrap_or_default();
+            let usage = response_json.as_ref().map(|json| &json["usage"]);
+            let input_tokens = usage
+                .and_then(|u| u["input_tokens"].as_u64())
+                .map(|v| v as u32)
+                .unwrap_or(estimated_input_tokens);
+            let output_tokens = usage
+                .and_then(|u| u["output_tokens"].as_u64())
+                .map(|v| v as u32)
+                .unwrap_or(estimated_output_tokens);
+
+            record_token_usage(&state, &ctx, Some(input_tokens), Some(output_tokens));
+
+            // 完成 Flow 捕获并检查响应拦截
+            // **Validates: Requirements 2.1, 2.5**
+            if let Some(fid) = flow_id {
+                let mut llm_response =
+                    build_llm_response(200, &content, Some((input_tokens, output_tokens)));
+                if let Some(json) = response_json {
+                    llm_response.body = json;
+                }
+                llm_response.headers = response_headers;
 
                 // 检查是否需要拦截响应
                 if let Some(modified_response) = check_response_intercept(
@@ -2240,14 +2948,19 @@ pub async fn anthropic_messages(
                     .flow_monitor
                     .complete_flow(&fid, Some(llm_response))
                     .await;
-            } else {
-                let error = FlowError::new(
-                    FlowErrorType::from_status_code(response.status().as_u16()),
-                    "Request failed",
-                )
-                .with_status_code(response.status().as_u16());
-                state.flow_monitor.fail_flow(&fid, error).await;
             }
+
+            return Response::from_parts(parts, buffered.into_body());
+        }
+
+        // 失败响应：标记 Flow 失败
+        if let Some(fid) = flow_id {
+            let error = FlowError::new(
+                FlowErrorType::from_status_code(response.status().as_u16()),
+                "Request failed",
+            )
+            .with_status_code(response.status().as_u16());
+            state.flow_monitor.fail_flow(&fid, error).await;
         }
 
         return response;
@@ -2264,17 +2977,15 @@ pub async fn anthropic_messages(
                 selected_provider, client_type
             ),
         );
-        return (
-            StatusCode::SERVICE_UNAVAILABLE,
-            Json(serde_json::json!({
-                "type": "error",
-                "error": {
-                    "type": "no_credential_error",
-                    "message": format!("没有找到可用的 '{}' 凭证。请在凭证池中添加对应的凭证。", selected_provider)
-                }
-            })),
+        return ApiError::new(
+            ApiErrorCode::NoCredentials,
+            format!(
+                "没有找到可用的 '{}' 凭证。请在凭证池中添加对应的凭证。",
+                selected_provider
+            ),
         )
-            .into_response();
+        .anthropic()
+        .into_response();
     }
 
     state.logs.write().await.add(
@@ -2301,10 +3012,8 @@ pub async fn anthropic_messages(
         &headers,
         &ctx.request_id,
     );
-    let flow_id = state
-        .flow_monitor
-        .start_flow(llm_request.clone(), flow_metadata.clone())
-        .await;
+    let flow_id = start_flow(&state, &ctx, &llm_request, &flow_metadata).await;
+    tag_flow(&state, flow_id.as_deref(), &overrides.tags).await;
 
     // 检查是否需要拦截请求（legacy mode）
     // **Validates: Requirements 2.1, 2.3, 2.5**
@@ -2322,16 +3031,8 @@ pub async fn anthropic_messages(
                 // 请求被取消，标记 Flow 失败并返回错误
                 let error = FlowError::new(FlowErrorType::Cancelled, "请求被用户取消");
                 state.flow_monitor.fail_flow(fid, error).await;
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(serde_json::json!({
-                        "type": "error",
-                        "error": {
-                            "type": "request_cancelled",
-                            "message": "Request cancelled by user"
-                        }
-                    })),
-                )
+                return ApiError::new(ApiErrorCode::RequestCancelled, "Request cancelled by user")
+                    .anthropic()
                     .into_response();
             }
         }
@@ -66,7 +66,7 @@ pub async fn handle_image_generation(
     Json(request): Json<ImageGenerationRequest>,
 ) -> Response {
     // 验证 API Key
-    if let Err(e) = verify_api_key(&headers, &state.api_key).await {
+    if let Err(e) = verify_api_key(&headers, &state).await {
         return e.into_response();
     }
 
@@ -67,6 +67,7 @@ use crate::server_utils::{
     build_anthropic_response, build_anthropic_stream_response, build_error_response,
     build_error_response_with_status, parse_cw_response, safe_truncate, CWParsedResponse,
 };
+use crate::services::postmortem_service::UpstreamRequest;
 use crate::session::store_thought_signature;
 use crate::stream::{PipelineConfig, StreamPipeline};
 use crate::streaming::traits::StreamingProvider;
@@ -75,6 +76,42 @@ use crate::streaming::{
     StreamResponse,
 };
 
+/// 发往上游前按上游和模型的限制整形请求参数（见 [`crate::processor::ShapingStep`]），
+/// 并记录整形后发往上游的请求体
+///
+/// 没有调整时返回 `None`，调用方继续使用原请求
+async fn shape_request<T>(
+    state: &AppState,
+    upstream: &UpstreamRequest,
+    provider_id: &str,
+    model: &str,
+    request: &T,
+) -> Option<T>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let mut payload = serde_json::to_value(request).ok()?;
+    let adjustments = state
+        .processor
+        .shaping
+        .shape(Some(provider_id), model, &mut payload)
+        .await;
+    let shaped = if adjustments.is_empty() {
+        None
+    } else {
+        serde_json::from_value(payload.clone()).ok()
+    };
+    upstream.record(payload);
+    shaped
+}
+
+/// OpenAI 兼容凭证对应的 Provider 注册表标识，无法按地址识别时按 OpenAI 通用限制
+fn openai_compatible_id(base_url: Option<&str>) -> &'static str {
+    base_url
+        .and_then(proxycast_providers::registry::id_for_base_url)
+        .unwrap_or("openai")
+}
+
 /// 根据凭证调用 Provider (Anthropic 格式)
 ///
 /// # 参数
@@ -82,11 +119,13 @@ use crate::streaming::{
 /// - `credential`: 凭证信息
 /// - `request`: Anthropic 格式请求
 /// - `flow_id`: Flow ID（可选，用于流式响应处理）
+/// - `upstream`: 记录格式转换、参数整形后实际发往上游的请求体
 pub async fn call_provider_anthropic(
     state: &AppState,
     credential: &ProviderCredential,
     request: &AnthropicMessagesRequest,
     flow_id: Option<&str>,
+    upstream: &UpstreamRequest,
 ) -> Response {
     // 如果是流式请求且有 flow_id，设置流式状态
     if request.stream {
@@ -408,6 +447,7 @@ pub async fn call_provider_anthropic(
             // 先转换为 OpenAI 格式，再转换为 Antigravity 格式
             let openai_request = convert_anthropic_to_openai(request);
             let antigravity_request = convert_openai_to_antigravity_with_context(&openai_request, &proj_id);
+            upstream.record_serialized(&antigravity_request);
             match antigravity
                 .generate_content(&request.model, &antigravity_request)
                 .await
@@ -454,8 +494,13 @@ pub async fn call_provider_anthropic(
             }
         }
         CredentialData::OpenAIKey { api_key, base_url } => {
-            let openai = OpenAICustomProvider::with_config(api_key.clone(), base_url.clone());
+            let openai = OpenAICustomProvider::with_config(api_key.clone(), base_url.clone())
+                .with_dashscope_mode(state.dashscope_mode);
             let openai_request = convert_anthropic_to_openai(request);
+            let provider_id = openai_compatible_id(base_url.as_deref());
+            let openai_request = shape_request(state, upstream, provider_id, &request.model, &openai_request)
+                .await
+                .unwrap_or(openai_request);
             match openai.call_api(&openai_request).await {
                 Ok(resp) => {
                     let status = resp.status();
@@ -567,6 +612,8 @@ pub async fn call_provider_anthropic(
             let actual_base_url = base_url.as_deref().unwrap_or("https://api.anthropic.com");
             let claude = ClaudeCustomProvider::with_config(api_key.clone(), base_url.clone());
             let request_url = claude.get_base_url();
+            let shaped = shape_request(state, upstream, "anthropic", &request.model, request).await;
+            let request = shaped.as_ref().unwrap_or(request);
             state.logs.write().await.add(
                 "info",
                 &format!(
@@ -730,7 +777,9 @@ pub async fn call_provider_anthropic(
             // Vertex AI uses Gemini-compatible API, convert Anthropic to OpenAI format first
             let openai_request = convert_anthropic_to_openai(request);
             let vertex = VertexProvider::with_config(api_key.clone(), base_url.clone());
-            match vertex.chat_completions(&serde_json::to_value(&openai_request).unwrap_or_default()).await {
+            let vertex_request = serde_json::to_value(&openai_request).unwrap_or_default();
+            upstream.record(vertex_request.clone());
+            match vertex.chat_completions(&vertex_request).await {
                 Ok(resp) => {
                     let status = resp.status();
                     match resp.text().await {
@@ -792,6 +841,8 @@ pub async fn call_provider_anthropic(
             // 使用 Anthropic 原生格式调用（无论是否有自定义 base_url）
             let claude = ClaudeCustomProvider::with_config(api_key.clone(), base_url.clone());
             let request_url = claude.get_base_url();
+            let shaped = shape_request(state, upstream, "anthropic", &request.model, request).await;
+            let request = shaped.as_ref().unwrap_or(request);
             state.logs.write().await.add(
                 "info",
                 &format!(
@@ -926,11 +977,13 @@ pub async fn call_provider_anthropic(
 /// - `credential`: 凭证信息
 /// - `request`: OpenAI 格式请求
 /// - `flow_id`: Flow ID（可选，用于流式响应处理）
+/// - `upstream`: 记录格式转换、参数整形后实际发往上游的请求体
 pub async fn call_provider_openai(
     state: &AppState,
     credential: &ProviderCredential,
     request: &ChatCompletionRequest,
     _flow_id: Option<&str>,
+    upstream: &UpstreamRequest,
 ) -> Response {
     let _start_time = std::time::Instant::now();
 
@@ -1340,6 +1393,7 @@ pub async fn call_provider_openai(
                     let proj_id = antigravity.project_id.clone().unwrap_or_default();
                     // 转换请求格式 - 这已经是完整的 Antigravity 请求格式
                     let antigravity_request = convert_openai_to_antigravity_with_context(request, &proj_id);
+                    upstream.record_serialized(&antigravity_request);
 
                     // 直接调用 call_api，因为 antigravity_request 已经是完整格式
                     match antigravity.call_api("generateContent", &antigravity_request).await {
@@ -1549,6 +1603,7 @@ pub async fn call_provider_openai(
             // 转换请求格式
             eprintln!("[ANTIGRAVITY_OPENAI] 开始转换请求格式...");
             let antigravity_request = convert_openai_to_antigravity_with_context(request, &proj_id);
+            upstream.record_serialized(&antigravity_request);
             eprintln!("[ANTIGRAVITY_OPENAI] 请求格式转换完成");
 
             eprintln!("[ANTIGRAVITY_OPENAI] 调用 generate_content...");
@@ -1569,7 +1624,11 @@ pub async fn call_provider_openai(
             }
         }
         CredentialData::OpenAIKey { api_key, base_url } => {
-            let openai = OpenAICustomProvider::with_config(api_key.clone(), base_url.clone());
+            let openai = OpenAICustomProvider::with_config(api_key.clone(), base_url.clone())
+                .with_dashscope_mode(state.dashscope_mode);
+            let provider_id = openai_compatible_id(base_url.as_deref());
+            let shaped = shape_request(state, upstream, provider_id, &request.model, request).await;
+            let request = shaped.as_ref().unwrap_or(request);
 
             tracing::info!("[OPENAI_KEY] request.stream = {}, model = {}", request.stream, request.model);
 
@@ -1580,13 +1639,27 @@ pub async fn call_provider_openai(
                     Ok(stream_response) => {
                         tracing::info!("[OPENAI_KEY_STREAM] 开始直接转发 OpenAI SSE 流");
 
-                        // OpenAI 提供商已经返回 OpenAI SSE 格式，直接转发
-                        let body_stream = stream_response.map(|result| -> Result<axum::body::Bytes, std::io::Error> {
-                            match result {
-                                Ok(bytes) => Ok(bytes),
-                                Err(e) => Ok(axum::body::Bytes::from(e.to_sse_error())),
+                        // OpenAI 提供商已经返回 OpenAI SSE 格式，按推理内容策略改写后转发
+                        let mut reasoning_filter =
+                            proxycast_providers::ReasoningFilter::new(state.reasoning_policy);
+                        let body_stream = async_stream::stream! {
+                            let mut stream_response = stream_response;
+                            while let Some(result) = stream_response.next().await {
+                                match result {
+                                    Ok(bytes) => {
+                                        let rewritten = reasoning_filter.rewrite_sse(&bytes);
+                                        if !rewritten.is_empty() {
+                                            yield Ok::<_, std::io::Error>(axum::body::Bytes::from(rewritten));
+                                        }
+                                    }
+                                    Err(e) => yield Ok(axum::body::Bytes::from(e.to_sse_error())),
+                                }
                             }
-                        });
+                            let rest = reasoning_filter.finish();
+                            if !rest.is_empty() {
+                                yield Ok(axum::body::Bytes::from(rest));
+                            }
+                        };
 
                         return Response::builder()
                             .status(StatusCode::OK)
@@ -1622,7 +1695,9 @@ pub async fn call_provider_openai(
                     if resp.status().is_success() {
                         match resp.text().await {
                             Ok(body) => {
-                                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&body) {
+                                if let Ok(mut json) = serde_json::from_str::<serde_json::Value>(&body) {
+                                    proxycast_providers::ReasoningFilter::new(state.reasoning_policy)
+                                        .apply_response(&mut json);
                                     Json(json).into_response()
                                 } else {
                                     (
@@ -1664,6 +1739,8 @@ pub async fn call_provider_openai(
                 request.stream
             );
             let claude = ClaudeCustomProvider::with_config(api_key.clone(), base_url.clone());
+            let shaped = shape_request(state, upstream, "anthropic", &request.model, request).await;
+            let request = shaped.as_ref().unwrap_or(request);
 
             // 检查是否为流式请求
             if request.stream {
@@ -1771,7 +1848,9 @@ pub async fn call_provider_openai(
             let mut modified_request = request.clone();
             modified_request.model = resolved_model;
             let vertex = VertexProvider::with_config(api_key.clone(), base_url.clone());
-            match vertex.chat_completions(&serde_json::to_value(&modified_request).unwrap_or_default()).await {
+            let vertex_request = serde_json::to_value(&modified_request).unwrap_or_default();
+            upstream.record(vertex_request.clone());
+            match vertex.chat_completions(&vertex_request).await {
                 Ok(resp) => {
                     if resp.status().is_success() {
                         match resp.text().await {
@@ -1805,6 +1884,9 @@ pub async fn call_provider_openai(
             // 如果有自定义 base_url，假设是 OpenAI 兼容的代理服务器
             if let Some(custom_url) = base_url {
                 let openai = OpenAICustomProvider::with_config(api_key.clone(), Some(custom_url.clone()));
+                let provider_id = openai_compatible_id(Some(custom_url.as_str()));
+                let shaped = shape_request(state, upstream, provider_id, &request.model, request).await;
+                let request = shaped.as_ref().unwrap_or(request);
                 state.logs.write().await.add(
                     "info",
                     &format!(
@@ -1964,6 +2046,8 @@ pub async fn call_provider_openai(
                 }
             };
 
+            upstream.record(request_json.clone());
+
             // 调用 Codex API
             match codex.call_api(&request_json).await {
                 Ok(response) => {
@@ -27,6 +27,7 @@ use crate::processor::RequestContext;
 use crate::providers::{
     AntigravityProvider, ClaudeCustomProvider, KiroProvider, OpenAICustomProvider,
 };
+use crate::server::client_keys::ApiCaller;
 use crate::server::AppState;
 use crate::server_utils::parse_cw_response;
 use crate::websocket::{
@@ -66,8 +67,15 @@ pub async fn ws_upgrade_handler(
 
     // 如果没有提供任何认证信息，允许连接（用于内部 Flow Monitor）
     // 但会在日志中记录
+    let key_valid = match key {
+        Some(k) => {
+            let client_keys = state.client_keys.read().await;
+            ApiCaller::identify(k, &state.api_key, &client_keys).is_some()
+        }
+        None => false,
+    };
     let authenticated = match key {
-        Some(k) if k == state.api_key => true,
+        Some(_) if key_valid => true,
         Some(_) => {
             return axum::http::Response::builder()
                 .status(401)
@@ -667,7 +675,8 @@ pub async fn call_provider_openai_for_ws(
             }
         }
         CredentialData::OpenAIKey { api_key, base_url } => {
-            let provider = OpenAICustomProvider::with_config(api_key.clone(), base_url.clone());
+            let provider = OpenAICustomProvider::with_config(api_key.clone(), base_url.clone())
+                .with_dashscope_mode(state.dashscope_mode);
             let resp = match provider.call_api(request).await {
                 Ok(r) => r,
                 Err(e) => {
@@ -1,6 +1,7 @@
 //! HTTP API 服务器
 
 pub mod client_detector;
+pub mod client_keys;
 
 use crate::config::{
     Config, ConfigChangeKind, ConfigManager, EndpointProvidersConfig, FileChangeEvent, FileWatcher,
@@ -23,6 +24,7 @@ use crate::providers::claude_custom::ClaudeCustomProvider;
 use crate::providers::gemini::GeminiProvider;
 use crate::providers::kiro::KiroProvider;
 use crate::providers::openai_custom::OpenAICustomProvider;
+use crate::server::api_error::{ApiError, ApiErrorCode, ErrorProvider};
 use crate::server_utils::{
     build_anthropic_response, build_anthropic_stream_response, build_error_response,
     build_error_response_with_status, build_gemini_cli_request, build_gemini_native_request,
@@ -81,9 +83,26 @@ pub fn record_request_telemetry(
         log.set_credential_id(cred_id.clone());
     }
 
+    // 设置客户端密钥 ID
+    if let Some(client_key_id) = &ctx.client_key_id {
+        log.set_client_key_id(client_key_id.clone());
+    }
+
     // 设置重试次数
     log.retry_count = ctx.retry_count;
 
+    // 设置图片负载统计
+    let image_count = ctx
+        .get_metadata(crate::converter::image::IMAGE_COUNT_METADATA)
+        .and_then(|v| v.as_u64());
+    if let Some(count) = image_count {
+        let bytes = ctx
+            .get_metadata(crate::converter::image::IMAGE_BYTES_METADATA)
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        log.set_image_payload(count as u32, bytes);
+    }
+
     // 记录到统计聚合器
     {
         let stats = state.processor.stats.write();
@@ -105,6 +124,44 @@ pub fn record_request_telemetry(
     );
 }
 
+/// 补充流式请求的首 Token 延迟和生成速度
+///
+/// 流式请求在响应开始时已记录遥测，这里在流结束后更新同一条记录
+pub fn record_stream_timing(
+    state: &AppState,
+    ctx: &RequestContext,
+    first_token_ms: u64,
+    last_token_ms: u64,
+    output_tokens: u32,
+) {
+    {
+        let stats = state.processor.stats.write();
+        stats.update_stream_timing(
+            &ctx.request_id,
+            first_token_ms,
+            last_token_ms,
+            output_tokens,
+        );
+    }
+
+    if let Some(logger) = &state.request_logger {
+        logger.update_stream_timing(
+            &ctx.request_id,
+            first_token_ms,
+            last_token_ms,
+            output_tokens,
+        );
+    }
+
+    tracing::debug!(
+        "[TELEMETRY] request_id={} first_token_ms={} last_token_ms={} output_tokens={}",
+        ctx.request_id,
+        first_token_ms,
+        last_token_ms,
+        output_tokens
+    );
+}
+
 /// 记录 Token 使用量到遥测系统
 pub fn record_token_usage(
     state: &AppState,
@@ -429,18 +486,22 @@ impl Clone for KiroProvider {
     fn clone(&self) -> Self {
         Self {
             credentials: self.credentials.clone(),
-            client: reqwest::Client::new(),
+            client: self.client.clone(),
             creds_path: self.creds_path.clone(),
         }
     }
 }
 
+pub mod api_error;
 pub mod handlers;
+pub mod overrides;
 
 #[derive(Clone)]
 #[allow(dead_code)]
 pub struct AppState {
     pub api_key: String,
+    /// 客户端密钥（局域网共享时分发给团队成员），配置热重载时更新
+    pub client_keys: Arc<RwLock<Vec<crate::config::ClientApiKey>>>,
     pub base_url: String,
     pub default_provider: Arc<RwLock<String>>,
     pub kiro: Arc<RwLock<KiroProvider>>,
@@ -476,6 +537,12 @@ pub struct AppState {
     pub kiro_event_service: Arc<KiroEventService>,
     /// API Key Provider 服务（用于智能降级）
     pub api_key_service: Arc<crate::services::api_key_provider_service::ApiKeyProviderService>,
+    /// 推理内容处理策略
+    pub reasoning_policy: proxycast_providers::ReasoningPolicy,
+    /// DashScope 接入方式
+    pub dashscope_mode: proxycast_providers::DashScopeMode,
+    /// 本地 Mock Provider
+    pub mock_provider: crate::providers::MockProvider,
 }
 
 /// 启动配置文件监控
@@ -493,6 +560,7 @@ async fn start_config_watcher(
     config_path: PathBuf,
     hot_reload_manager: Option<Arc<HotReloadManager>>,
     processor: Arc<RequestProcessor>,
+    client_keys: Arc<RwLock<Vec<crate::config::ClientApiKey>>>,
     logs: Arc<RwLock<LogStore>>,
     db: Option<DbConnection>,
     config_manager: Option<Arc<std::sync::RwLock<ConfigManager>>>,
@@ -551,6 +619,13 @@ async fn start_config_watcher(
                         let new_config = manager.config();
                         update_processor_config(&processor_clone, &new_config).await;
 
+                        // 更新客户端密钥，新增、禁用或删除的密钥立即生效
+                        *client_keys.write().await = new_config.server.client_keys.clone();
+                        tracing::debug!(
+                            "[HOT_RELOAD] 客户端密钥已更新: {} 个",
+                            new_config.server.client_keys.len()
+                        );
+
                         // 同步凭证池
                         if let (Some(ref db), Some(ref cfg_manager)) =
                             (&db_clone, &config_manager_clone)
@@ -680,6 +755,24 @@ async fn update_processor_config(processor: &RequestProcessor, config: &Config)
         );
     }
 
+    // 更新响应异常检测配置
+    crate::services::anomaly_service::configure(&config.anomaly_detection);
+
+    // 更新上游并发准入配置
+    crate::services::admission_service::configure(&config.admission_control);
+
+    // 更新响应缓冲内存上限
+    crate::services::response_buffer_service::configure(&config.response_buffer);
+
+    // 更新上游 HTTP 客户端配置
+    crate::services::http_client_service::configure(&config.upstream_http);
+
+    // 更新管道开销统计与遥测采样配置
+    crate::services::pipeline_metrics_service::configure(&config.pipeline_metrics);
+
+    // 更新流式响应合并规则
+    crate::services::stream_aggregation_service::configure(&config.stream_aggregation);
+
     // 注意：重试配置目前不支持热更新，因为 Retrier 是不可变的
     // 如果需要更新重试配置，需要重启服务器
     tracing::debug!(
@@ -817,6 +910,16 @@ async fn run_server(
         }
     }
 
+    // 从配置初始化响应异常检测、上游并发准入、响应缓冲上限、上游 HTTP 客户端、遥测采样和流式响应合并
+    if let Some(cfg) = &config {
+        crate::services::anomaly_service::configure(&cfg.anomaly_detection);
+        crate::services::admission_service::configure(&cfg.admission_control);
+        crate::services::response_buffer_service::configure(&cfg.response_buffer);
+        crate::services::http_client_service::configure(&cfg.upstream_http);
+        crate::services::pipeline_metrics_service::configure(&cfg.pipeline_metrics);
+        crate::services::stream_aggregation_service::configure(&cfg.stream_aggregation);
+    }
+
     // 初始化 WebSocket 管理器
     let ws_manager = Arc::new(WsConnectionManager::new(WsConfig::default()));
     let ws_stats = ws_manager.stats().clone();
@@ -870,8 +973,30 @@ async fn run_server(
     let api_key_service =
         Arc::new(crate::services::api_key_provider_service::ApiKeyProviderService::new());
 
+    let reasoning_policy = config
+        .as_ref()
+        .map(|c| c.reasoning.policy)
+        .unwrap_or_default();
+
+    let dashscope_mode = config
+        .as_ref()
+        .map(|c| c.providers.dashscope.mode)
+        .unwrap_or_default();
+
+    let mock_provider = crate::providers::MockProvider::new(
+        config.as_ref().map(|c| c.mock.clone()).unwrap_or_default(),
+    );
+
+    let client_keys = Arc::new(RwLock::new(
+        config
+            .as_ref()
+            .map(|c| c.server.client_keys.clone())
+            .unwrap_or_default(),
+    ));
+
     let state = AppState {
         api_key: api_key.to_string(),
+        client_keys: client_keys.clone(),
         base_url,
         default_provider,
         kiro: Arc::new(RwLock::new(kiro)),
@@ -894,6 +1019,9 @@ async fn run_server(
         endpoint_providers,
         kiro_event_service,
         api_key_service,
+        reasoning_policy,
+        dashscope_mode,
+        mock_provider,
     };
 
     // ========== 开发模式：启动独立的 HTTP 桥接服务器 ==========
@@ -924,6 +1052,7 @@ async fn run_server(
             path,
             hot_reload_manager,
             processor,
+            client_keys,
             logs_clone,
             db_clone,
             config_manager,
@@ -991,10 +1120,8 @@ async fn run_server(
             get(handlers::credentials_get_token),
         );
 
-    let app = Router::new()
-        .route("/health", get(health))
-        .route("/v1/models", get(models))
-        .route("/v1/routes", get(list_routes))
+    // 对话 API 路由（按请求优先级调度上游并发，按路由合并流式响应）
+    let chat_routes = Router::new()
         .route("/v1/chat/completions", post(
             |State(state): State<AppState>,
              headers: HeaderMap,
@@ -1009,24 +1136,39 @@ async fn run_server(
                 handlers::anthropic_messages(State(state), headers, Json(request)).await
             }
         ))
+        .route(
+            "/{selector}/v1/messages",
+            post(anthropic_messages_with_selector),
+        )
+        .route(
+            "/{selector}/v1/chat/completions",
+            post(chat_completions_with_selector),
+        )
+        .layer(crate::middleware::AdmissionLayer::new(
+            crate::services::admission_service::get_admission_gate(),
+        ))
+        .layer(crate::middleware::StreamAggregationLayer::new());
+
+    // 代理 API 路由（统一错误响应结构：错误码、Provider、是否可重试、请求 ID）
+    let proxy_routes = Router::new()
+        .route("/v1/models", get(models))
         .route("/v1/messages/count_tokens", post(count_tokens))
         // 图像生成 API 路由
         .route(
             "/v1/images/generations",
             post(handlers::handle_image_generation),
         )
+        // 对话 API 路由（含多供应商路由）
+        .merge(chat_routes)
+        .layer(crate::middleware::ErrorEnvelopeLayer::new());
+
+    let app = Router::new()
+        .route("/health", get(health))
+        .route("/v1/routes", get(list_routes))
         // WebSocket 路由
         .route("/v1/ws", get(handlers::ws_upgrade_handler))
         .route("/ws", get(handlers::ws_upgrade_handler))
-        // 多供应商路由
-        .route(
-            "/{selector}/v1/messages",
-            post(anthropic_messages_with_selector),
-        )
-        .route(
-            "/{selector}/v1/chat/completions",
-            post(chat_completions_with_selector),
-        )
+        .merge(proxy_routes)
         // 管理 API 路由
         .merge(management_routes)
         // Kiro凭证管理API路由
@@ -1034,6 +1176,8 @@ async fn run_server(
         // 凭证 API 路由（用于 aster Agent 集成）
         .merge(credentials_api_routes)
         .layer(DefaultBodyLimit::max(body_limit))
+        // 为每个请求分配关联 ID（日志、tracing span 和响应头）
+        .layer(crate::middleware::RequestIdLayer::new())
         .with_state(state);
 
     let addr: std::net::SocketAddr = format!("{host}:{port}")
@@ -1061,15 +1205,16 @@ async fn run_server(
 async fn count_tokens(
     State(state): State<AppState>,
     headers: HeaderMap,
-    Json(_request): Json<serde_json::Value>,
+    Json(request): Json<crate::models::anthropic::AnthropicMessagesRequest>,
 ) -> Response {
-    if let Err(e) = handlers::verify_api_key(&headers, &state.api_key).await {
+    if let Err(e) = handlers::verify_api_key(&headers, &state).await {
         return e.into_response();
     }
 
-    // Claude Code 需要这个端点，返回估算值
+    // Claude Code 需要这个端点，返回本地估算值
+    let input_tokens = crate::services::token_count_service::count_anthropic_request(&request);
     Json(serde_json::json!({
-        "input_tokens": 100
+        "input_tokens": input_tokens
     }))
     .into_response()
 }
@@ -1084,7 +1229,7 @@ async fn gemini_generate_content(
     Path(path): Path<String>,
     Json(request): Json<serde_json::Value>,
 ) -> Response {
-    if let Err(e) = handlers::verify_api_key(&headers, &state.api_key).await {
+    if let Err(e) = handlers::verify_api_key(&headers, &state).await {
         return e.into_response();
     }
 
@@ -1145,15 +1290,11 @@ async fn gemini_generate_content(
     let cred = match credential {
         Some(c) => c,
         None => {
-            return (
-                StatusCode::SERVICE_UNAVAILABLE,
-                Json(serde_json::json!({
-                    "error": {
-                        "message": format!("No available credentials for provider '{}'. Please add credentials in the Provider Pool.", default_provider)
-                    }
-                })),
+            return ApiError::new(
+                ApiErrorCode::NoCredentials,
+                format!("No available credentials for provider '{}'. Please add credentials in the Provider Pool.", default_provider),
             )
-                .into_response();
+            .into_response();
         }
     };
 
@@ -1549,6 +1690,16 @@ async fn list_routes(State(state): State<AppState>) -> impl IntoResponse {
     Json(response)
 }
 
+/// 按响应状态记录选择器路由的请求统计
+fn record_selector_telemetry(state: &AppState, ctx: &RequestContext, response: &Response) {
+    let status = if response.status().is_success() {
+        crate::telemetry::RequestStatus::Success
+    } else {
+        crate::telemetry::RequestStatus::Failed
+    };
+    record_request_telemetry(state, ctx, status, None);
+}
+
 /// 带选择器的 Anthropic messages 处理
 async fn anthropic_messages_with_selector(
     State(state): State<AppState>,
@@ -1557,12 +1708,21 @@ async fn anthropic_messages_with_selector(
     Json(request): Json<AnthropicMessagesRequest>,
 ) -> Response {
     // 使用 Anthropic 格式的认证验证
-    if let Err(e) = handlers::verify_api_key_anthropic(&headers, &state.api_key).await {
-        state.logs.write().await.add(
-            "warn",
-            &format!("Unauthorized request to /{}/v1/messages", selector),
-        );
-        return e.into_response();
+    let caller = match handlers::verify_api_key_anthropic(&headers, &state).await {
+        Ok(caller) => caller,
+        Err(e) => {
+            state.logs.write().await.add(
+                "warn",
+                &format!("Unauthorized request to /{}/v1/messages", selector),
+            );
+            return e.into_response();
+        }
+    };
+
+    // 创建请求上下文，记录发起请求的客户端密钥
+    let mut ctx = RequestContext::new(request.model.clone()).with_stream(request.stream);
+    if let Some(client_key_id) = caller.client_key_id() {
+        ctx.set_client_key_id(client_key_id.to_string());
     }
 
     state.logs.write().await.add(
@@ -1612,7 +1772,27 @@ async fn anthropic_messages_with_selector(
 
             // 根据凭证类型调用相应的 Provider
             // 注意：这里没有 Flow 捕获，因为是通过 selector 路由的请求
-            handlers::call_provider_anthropic(&state, &cred, &request, None).await
+            if let Ok(provider_type) = cred
+                .provider_type
+                .to_string()
+                .parse::<crate::ProviderType>()
+            {
+                ctx.set_provider(provider_type);
+            }
+            ctx.set_credential_id(cred.uuid.clone());
+            let mut response = handlers::call_provider_anthropic(
+                &state,
+                &cred,
+                &request,
+                None,
+                &Default::default(),
+            )
+            .await;
+            record_selector_telemetry(&state, &ctx, &response);
+            response
+                .extensions_mut()
+                .insert(ErrorProvider(cred.provider_type.to_string()));
+            response
         }
         None => {
             // 不再回退到默认 provider，直接返回错误
@@ -1623,16 +1803,12 @@ async fn anthropic_messages_with_selector(
                     selector
                 ),
             );
-            (
-                StatusCode::SERVICE_UNAVAILABLE,
-                Json(serde_json::json!({
-                    "error": {
-                        "type": "provider_unavailable",
-                        "message": format!("No available credentials for selector '{}'", selector)
-                    }
-                })),
+            ApiError::new(
+                ApiErrorCode::NoCredentials,
+                format!("No available credentials for selector '{}'", selector),
             )
-                .into_response()
+            .anthropic()
+            .into_response()
         }
     }
 }
@@ -1644,12 +1820,21 @@ async fn chat_completions_with_selector(
     headers: HeaderMap,
     Json(request): Json<ChatCompletionRequest>,
 ) -> Response {
-    if let Err(e) = handlers::verify_api_key(&headers, &state.api_key).await {
-        state.logs.write().await.add(
-            "warn",
-            &format!("Unauthorized request to /{}/v1/chat/completions", selector),
-        );
-        return e.into_response();
+    let caller = match handlers::verify_api_key(&headers, &state).await {
+        Ok(caller) => caller,
+        Err(e) => {
+            state.logs.write().await.add(
+                "warn",
+                &format!("Unauthorized request to /{}/v1/chat/completions", selector),
+            );
+            return e.into_response();
+        }
+    };
+
+    // 创建请求上下文，记录发起请求的客户端密钥
+    let mut ctx = RequestContext::new(request.model.clone()).with_stream(request.stream);
+    if let Some(client_key_id) = caller.client_key_id() {
+        ctx.set_client_key_id(client_key_id.to_string());
     }
 
     state.logs.write().await.add(
@@ -1693,7 +1878,22 @@ async fn chat_completions_with_selector(
             );
 
             // 注意：这里没有 Flow 捕获，因为是通过 selector 路由的请求
-            handlers::call_provider_openai(&state, &cred, &request, None).await
+            if let Ok(provider_type) = cred
+                .provider_type
+                .to_string()
+                .parse::<crate::ProviderType>()
+            {
+                ctx.set_provider(provider_type);
+            }
+            ctx.set_credential_id(cred.uuid.clone());
+            let mut response =
+                handlers::call_provider_openai(&state, &cred, &request, None, &Default::default())
+                    .await;
+            record_selector_telemetry(&state, &ctx, &response);
+            response
+                .extensions_mut()
+                .insert(ErrorProvider(cred.provider_type.to_string()));
+            response
         }
         None => {
             // 不再回退到默认 provider，直接返回错误
@@ -1704,17 +1904,11 @@ async fn chat_completions_with_selector(
                     selector
                 ),
             );
-            (
-                StatusCode::SERVICE_UNAVAILABLE,
-                Json(serde_json::json!({
-                    "error": {
-                        "message": format!("No available credentials for selector '{}'", selector),
-                        "type": "provider_unavailable",
-                        "code": "no_credentials"
-                    }
-                })),
+            ApiError::new(
+                ApiErrorCode::NoCredentials,
+                format!("No available credentials for selector '{}'", selector),
             )
-                .into_response()
+            .into_response()
         }
     }
 }
@@ -0,0 +1,232 @@
+//! 请求级覆盖请求头
+//!
+//! 高级用户可以通过 `X-Proxycast-*` 请求头在单次调用中控制路由和行为，无需修改全局配置：
+//!
+//! | 请求头 | 说明 |
+//! |--------|------|
+//! | `X-Proxycast-Provider` | 强制使用指定 Provider（同 `X-Provider-Id`，无可用凭证时不降级） |
+//! | `X-Proxycast-Credential` | 强制使用指定凭证 UUID |
+//! | `X-Proxycast-Tag` | 为请求打标签，逗号分隔，记录到 Flow Monitor |
+//! | `X-Proxycast-Priority` | 请求优先级：`low` / `normal` / `high`，上游并发已满时按优先级排队 |
+//!
+//! 请求头在管道最前面解析，非法取值直接返回 400。
+
+use axum::http::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+use crate::processor::RequestContext;
+
+pub const HEADER_PROVIDER: &str = "x-proxycast-provider";
+pub const HEADER_CREDENTIAL: &str = "x-proxycast-credential";
+pub const HEADER_TAG: &str = "x-proxycast-tag";
+pub const HEADER_PRIORITY: &str = "x-proxycast-priority";
+
+/// 兼容旧版的 Provider 指定请求头
+const LEGACY_HEADER_PROVIDER: &str = "x-provider-id";
+
+/// 请求上下文中记录覆盖项的元数据键
+pub const TAGS_METADATA: &str = "tags";
+pub const PRIORITY_METADATA: &str = "priority";
+
+/// 单个请求最多携带的标签数
+const MAX_TAGS: usize = 8;
+
+/// 单个标签最大长度
+const MAX_TAG_LEN: usize = 64;
+
+/// 请求优先级
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl RequestPriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RequestPriority::Low => "low",
+            RequestPriority::Normal => "normal",
+            RequestPriority::High => "high",
+        }
+    }
+}
+
+impl std::str::FromStr for RequestPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "low" => Ok(RequestPriority::Low),
+            "normal" => Ok(RequestPriority::Normal),
+            "high" => Ok(RequestPriority::High),
+            other => Err(format!(
+                "无效的 {}: {}（可选 low / normal / high）",
+                HEADER_PRIORITY, other
+            )),
+        }
+    }
+}
+
+/// 解析后的覆盖项
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestOverrides {
+    /// 强制 Provider（小写）
+    pub provider: Option<String>,
+    /// 强制凭证 UUID
+    pub credential: Option<String>,
+    /// 请求标签
+    pub tags: Vec<String>,
+    /// 请求优先级
+    pub priority: Option<RequestPriority>,
+}
+
+impl RequestOverrides {
+    /// 从请求头解析覆盖项
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, String> {
+        let provider = header_str(headers, HEADER_PROVIDER)?
+            .or(header_str(headers, LEGACY_HEADER_PROVIDER)?)
+            .map(|s| s.to_lowercase());
+
+        let credential = header_str(headers, HEADER_CREDENTIAL)?.map(|s| s.to_string());
+
+        let mut tags: Vec<String> = Vec::new();
+        for value in headers.get_all(HEADER_TAG) {
+            let value = value
+                .to_str()
+                .map_err(|_| format!("{} 包含非法字符", HEADER_TAG))?;
+            for tag in value.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+                if tag.chars().count() > MAX_TAG_LEN {
+                    return Err(format!(
+                        "{} 单个标签不能超过 {} 个字符",
+                        HEADER_TAG, MAX_TAG_LEN
+                    ));
+                }
+                if !tags.iter().any(|t| t == tag) {
+                    tags.push(tag.to_string());
+                }
+            }
+        }
+        if tags.len() > MAX_TAGS {
+            return Err(format!("{} 最多 {} 个标签", HEADER_TAG, MAX_TAGS));
+        }
+
+        let priority = header_str(headers, HEADER_PRIORITY)?
+            .map(str::parse)
+            .transpose()?;
+
+        Ok(Self {
+            provider,
+            credential,
+            tags,
+            priority,
+        })
+    }
+
+    /// 是否没有任何覆盖项
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 将不影响路由的覆盖项写入请求上下文，供后续步骤读取
+    pub fn apply_to_context(&self, ctx: &mut RequestContext) {
+        if !self.tags.is_empty() {
+            ctx.set_metadata(TAGS_METADATA, serde_json::json!(self.tags));
+        }
+        if let Some(priority) = self.priority {
+            ctx.set_metadata(PRIORITY_METADATA, serde_json::json!(priority.as_str()));
+        }
+    }
+}
+
+/// 读取请求头，空值视为未设置
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Result<Option<&'a str>, String> {
+    match headers.get(name) {
+        None => Ok(None),
+        Some(value) => {
+            let value = value
+                .to_str()
+                .map_err(|_| format!("{} 包含非法字符", name))?
+                .trim();
+            Ok((!value.is_empty()).then_some(value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(*name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    #[test]
+    fn test_parse_all_overrides() {
+        let overrides = RequestOverrides::from_headers(&headers(&[
+            ("x-proxycast-provider", "DeepSeek"),
+            ("x-proxycast-credential", "cred-uuid"),
+            ("x-proxycast-tag", "eval, nightly"),
+            ("x-proxycast-tag", "eval"),
+            ("x-proxycast-priority", "HIGH"),
+        ]))
+        .unwrap();
+
+        assert_eq!(overrides.provider.as_deref(), Some("deepseek"));
+        assert_eq!(overrides.credential.as_deref(), Some("cred-uuid"));
+        assert_eq!(overrides.tags, vec!["eval", "nightly"]);
+        assert_eq!(overrides.priority, Some(RequestPriority::High));
+    }
+
+    #[test]
+    fn test_legacy_provider_header() {
+        let overrides =
+            RequestOverrides::from_headers(&headers(&[("x-provider-id", "Moonshot")])).unwrap();
+        assert_eq!(overrides.provider.as_deref(), Some("moonshot"));
+
+        let overrides = RequestOverrides::from_headers(&headers(&[
+            ("x-provider-id", "moonshot"),
+            ("x-proxycast-provider", "zhipuai"),
+        ]))
+        .unwrap();
+        assert_eq!(overrides.provider.as_deref(), Some("zhipuai"));
+    }
+
+    #[test]
+    fn test_invalid_values_rejected() {
+        assert!(
+            RequestOverrides::from_headers(&headers(&[("x-proxycast-priority", "urgent")]))
+                .is_err()
+        );
+        assert!(RequestOverrides::from_headers(&headers(&[(
+            "x-proxycast-tag",
+            "a,b,c,d,e,f,g,h,i"
+        )]))
+        .is_err());
+    }
+
+    #[test]
+    fn test_empty_headers() {
+        let overrides = RequestOverrides::from_headers(&HeaderMap::new()).unwrap();
+        assert!(overrides.is_empty());
+
+        let mut ctx = RequestContext::new("gpt-4o".to_string());
+        RequestOverrides {
+            priority: Some(RequestPriority::Low),
+            ..Default::default()
+        }
+        .apply_to_context(&mut ctx);
+        assert_eq!(
+            ctx.get_metadata(PRIORITY_METADATA),
+            Some(&serde_json::json!("low"))
+        );
+        assert!(ctx.get_metadata(TAGS_METADATA).is_none());
+    }
+}
@@ -0,0 +1,469 @@
+//! 上游并发准入控制服务
+//!
+//! 限制同时转发到上游的对话请求数，超出时按请求优先级（`X-Proxycast-Priority`）排队：
+//! - 空出的并发总是先分给高优先级请求，其次普通，最后低优先级，同一优先级先到先得
+//! - `reserved_high` 个并发只留给高优先级请求，后台作业占满时语音润色仍能立即发出
+//! - 低优先级请求最多同时占用 `max_low` 个并发
+//!
+//! 排队超过 `queue_timeout_secs` 返回错误；未启用时只统计并发，不限制。
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::sync::oneshot;
+
+use crate::config::AdmissionControlConfig;
+use crate::server::overrides::RequestPriority;
+
+/// 优先级从高到低
+const PRIORITIES: [RequestPriority; 3] = [
+    RequestPriority::High,
+    RequestPriority::Normal,
+    RequestPriority::Low,
+];
+
+/// 全局准入闸门
+static ADMISSION_GATE: once_cell::sync::Lazy<Arc<AdmissionGate>> =
+    once_cell::sync::Lazy::new(|| Arc::new(AdmissionGate::new(AdmissionControlConfig::default())));
+
+/// 获取全局准入闸门
+pub fn get_admission_gate() -> Arc<AdmissionGate> {
+    ADMISSION_GATE.clone()
+}
+
+/// 应用配置（服务启动和配置热重载时调用）
+pub fn configure(config: &AdmissionControlConfig) {
+    ADMISSION_GATE.set_config(config.clone());
+}
+
+fn lane(priority: RequestPriority) -> usize {
+    match priority {
+        RequestPriority::High => 0,
+        RequestPriority::Normal => 1,
+        RequestPriority::Low => 2,
+    }
+}
+
+/// 准入错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdmissionError {
+    /// 排队超时
+    QueueTimeout {
+        priority: RequestPriority,
+        waited: Duration,
+    },
+}
+
+impl std::fmt::Display for AdmissionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdmissionError::QueueTimeout { priority, waited } => write!(
+                f,
+                "上游并发已满，{} 优先级请求排队 {} 秒后超时",
+                priority.as_str(),
+                waited.as_secs()
+            ),
+        }
+    }
+}
+
+/// 各优先级的请求数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct LaneCounts {
+    pub high: usize,
+    pub normal: usize,
+    pub low: usize,
+}
+
+impl From<[usize; 3]> for LaneCounts {
+    fn from(counts: [usize; 3]) -> Self {
+        Self {
+            high: counts[0],
+            normal: counts[1],
+            low: counts[2],
+        }
+    }
+}
+
+/// 准入状态快照
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AdmissionStatus {
+    pub enabled: bool,
+    pub max_concurrent: usize,
+    /// 正在转发的请求数
+    pub in_flight: LaneCounts,
+    /// 排队中的请求数
+    pub queued: LaneCounts,
+}
+
+struct Waiter {
+    id: u64,
+    grant: oneshot::Sender<()>,
+}
+
+struct GateState {
+    config: AdmissionControlConfig,
+    in_flight: [usize; 3],
+    queues: [VecDeque<Waiter>; 3],
+    next_waiter_id: u64,
+}
+
+impl GateState {
+    /// 指定优先级的请求现在能否发出
+    fn can_admit(&self, lane: usize) -> bool {
+        if !self.config.enabled {
+            return true;
+        }
+        let max = self.config.max_concurrent.max(1);
+        if self.in_flight.iter().sum::<usize>() >= max {
+            return false;
+        }
+        // 高优先级之外的请求共用扣除预留后的并发，至少保留一个
+        let shared = max.saturating_sub(self.config.reserved_high).max(1);
+        match lane {
+            0 => true,
+            1 => self.in_flight[1] + self.in_flight[2] < shared,
+            _ => {
+                self.in_flight[1] + self.in_flight[2] < shared
+                    && self.in_flight[2] < self.config.max_low.max(1)
+            }
+        }
+    }
+
+    /// 按优先级把空出的并发分给排队的请求
+    fn dispatch(&mut self) {
+        for lane in 0..PRIORITIES.len() {
+            while self.can_admit(lane) {
+                let Some(waiter) = self.queues[lane].pop_front() else {
+                    break;
+                };
+                self.in_flight[lane] += 1;
+                // 等待方已取消（客户端断开），归还并发
+                if waiter.grant.send(()).is_err() {
+                    self.in_flight[lane] -= 1;
+                }
+            }
+        }
+    }
+
+    /// 清理已取消的等待方
+    fn prune_cancelled(&mut self) {
+        for queue in &mut self.queues {
+            queue.retain(|waiter| !waiter.grant.is_closed());
+        }
+    }
+}
+
+/// 按优先级调度上游并发的闸门
+pub struct AdmissionGate {
+    state: Mutex<GateState>,
+}
+
+impl AdmissionGate {
+    pub fn new(config: AdmissionControlConfig) -> Self {
+        Self {
+            state: Mutex::new(GateState {
+                config,
+                in_flight: [0; 3],
+                queues: Default::default(),
+                next_waiter_id: 0,
+            }),
+        }
+    }
+
+    /// 更新配置，放宽限制时立即放行排队的请求
+    pub fn set_config(&self, config: AdmissionControlConfig) {
+        let mut state = self.state.lock();
+        state.config = config;
+        state.dispatch();
+    }
+
+    /// 当前状态
+    pub fn status(&self) -> AdmissionStatus {
+        let state = self.state.lock();
+        let mut queued = [0; 3];
+        for (count, queue) in queued.iter_mut().zip(&state.queues) {
+            *count = queue
+                .iter()
+                .filter(|waiter| !waiter.grant.is_closed())
+                .count();
+        }
+        AdmissionStatus {
+            enabled: state.config.enabled,
+            max_concurrent: state.config.max_concurrent,
+            in_flight: state.in_flight.into(),
+            queued: queued.into(),
+        }
+    }
+
+    /// 申请一个上游并发，并发已满时排队等待
+    ///
+    /// 返回的许可在丢弃时归还并发
+    pub async fn acquire(
+        self: &Arc<Self>,
+        priority: RequestPriority,
+    ) -> Result<AdmissionPermit, AdmissionError> {
+        let lane = lane(priority);
+
+        let (id, mut granted, timeout) = {
+            let mut state = self.state.lock();
+            state.prune_cancelled();
+            // 同一优先级已有人排队时不插队
+            if state.queues[lane].is_empty() && state.can_admit(lane) {
+                state.in_flight[lane] += 1;
+                return Ok(self.permit(lane, Duration::ZERO));
+            }
+            let id = state.next_waiter_id;
+            state.next_waiter_id += 1;
+            let (grant, granted) = oneshot::channel();
+            state.queues[lane].push_back(Waiter { id, grant });
+            (
+                id,
+                granted,
+                Duration::from_secs(state.config.queue_timeout_secs),
+            )
+        };
+
+        let started_at = Instant::now();
+        if tokio::time::timeout(timeout, &mut granted).await.is_ok() {
+            return Ok(self.permit(lane, started_at.elapsed()));
+        }
+
+        let mut state = self.state.lock();
+        match state.queues[lane].iter().position(|waiter| waiter.id == id) {
+            Some(index) => {
+                state.queues[lane].remove(index);
+                Err(AdmissionError::QueueTimeout {
+                    priority,
+                    waited: started_at.elapsed(),
+                })
+            }
+            // 超时的同时已被放行，并发已计入
+            None => Ok(self.permit(lane, started_at.elapsed())),
+        }
+    }
+
+    fn permit(self: &Arc<Self>, lane: usize, waited: Duration) -> AdmissionPermit {
+        AdmissionPermit {
+            gate: self.clone(),
+            lane,
+            waited,
+        }
+    }
+
+    fn release(&self, lane: usize) {
+        let mut state = self.state.lock();
+        state.in_flight[lane] = state.in_flight[lane].saturating_sub(1);
+        state.dispatch();
+    }
+}
+
+/// 上游并发许可，丢弃时归还
+pub struct AdmissionPermit {
+    gate: Arc<AdmissionGate>,
+    lane: usize,
+    waited: Duration,
+}
+
+impl AdmissionPermit {
+    /// 请求的优先级
+    pub fn priority(&self) -> RequestPriority {
+        PRIORITIES[self.lane]
+    }
+
+    /// 排队等待的时间
+    pub fn waited(&self) -> Duration {
+        self.waited
+    }
+}
+
+impl Drop for AdmissionPermit {
+    fn drop(&mut self) {
+        self.gate.release(self.lane);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gate(max_concurrent: usize, reserved_high: usize, max_low: usize) -> Arc<AdmissionGate> {
+        Arc::new(AdmissionGate::new(AdmissionControlConfig {
+            enabled: true,
+            max_concurrent,
+            reserved_high,
+            max_low,
+            queue_timeout_secs: 60,
+        }))
+    }
+
+    /// 让排队的任务跑到等待点
+    async fn settle() {
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config: AdmissionControlConfig = serde_yaml::from_str("max_concurrent: 4").unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.max_concurrent, 4);
+        assert_eq!(config.reserved_high, 2);
+        assert_eq!(config.max_low, 2);
+        assert_eq!(config.queue_timeout_secs, 120);
+    }
+
+    #[tokio::test]
+    async fn test_reserved_and_low_limits() {
+        let gate = gate(4, 1, 1);
+
+        let low = gate.acquire(RequestPriority::Low).await.unwrap();
+        assert_eq!(low.priority(), RequestPriority::Low);
+        let normal: Vec<_> =
+            futures::future::join_all((0..2).map(|_| gate.acquire(RequestPriority::Normal)))
+                .await
+                .into_iter()
+                .map(Result::unwrap)
+                .collect();
+
+        // 第二个低优先级请求超过 max_low，普通请求用完了非预留并发
+        let gate_clone = gate.clone();
+        let queued_low =
+            tokio::spawn(async move { gate_clone.acquire(RequestPriority::Low).await });
+        let gate_clone = gate.clone();
+        let queued_normal =
+            tokio::spawn(async move { gate_clone.acquire(RequestPriority::Normal).await });
+        settle().await;
+
+        // 预留的并发仍能给高优先级
+        let high = gate.acquire(RequestPriority::High).await.unwrap();
+        assert_eq!(high.waited(), Duration::ZERO);
+        let status = gate.status();
+        assert_eq!(
+            status.in_flight,
+            LaneCounts {
+                high: 1,
+                normal: 2,
+                low: 1
+            }
+        );
+        assert_eq!(
+            status.queued,
+            LaneCounts {
+                high: 0,
+                normal: 1,
+                low: 1
+            }
+        );
+
+        // 归还的高优先级并发不分给其他请求
+        drop(high);
+        settle().await;
+        assert_eq!(gate.status().queued.normal, 1);
+
+        // 普通请求归还后先放行排队的普通请求，低优先级仍受 max_low 限制
+        drop(normal);
+        let _normal = queued_normal.await.unwrap().unwrap();
+        settle().await;
+        assert_eq!(gate.status().queued.low, 1);
+
+        drop(low);
+        let _low = queued_low.await.unwrap().unwrap();
+        assert_eq!(gate.status().queued, LaneCounts::default());
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_jumps_queue() {
+        let gate = gate(1, 0, 1);
+        let running = gate.acquire(RequestPriority::Low).await.unwrap();
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut tasks = Vec::new();
+        for priority in [
+            RequestPriority::Low,
+            RequestPriority::Normal,
+            RequestPriority::High,
+        ] {
+            let gate = gate.clone();
+            let order = order.clone();
+            tasks.push(tokio::spawn(async move {
+                let permit = gate.acquire(priority).await.unwrap();
+                order.lock().push(permit.priority());
+                settle().await;
+            }));
+            settle().await;
+        }
+
+        drop(running);
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(
+            *order.lock(),
+            [
+                RequestPriority::High,
+                RequestPriority::Normal,
+                RequestPriority::Low
+            ]
+        );
+        assert_eq!(gate.status().in_flight, LaneCounts::default());
+    }
+
+    #[tokio::test]
+    async fn test_queue_timeout_and_cancel() {
+        let gate = Arc::new(AdmissionGate::new(AdmissionControlConfig {
+            max_concurrent: 1,
+            reserved_high: 0,
+            queue_timeout_secs: 1,
+            ..AdmissionControlConfig::default()
+        }));
+        let running = gate.acquire(RequestPriority::Normal).await.unwrap();
+
+        let error = gate.acquire(RequestPriority::Normal).await.err().unwrap();
+        assert!(matches!(
+            error,
+            AdmissionError::QueueTimeout {
+                priority: RequestPriority::Normal,
+                waited,
+            } if waited >= Duration::from_secs(1)
+        ));
+
+        // 取消的等待方不占并发
+        let gate_clone = gate.clone();
+        let cancelled =
+            tokio::spawn(async move { gate_clone.acquire(RequestPriority::High).await });
+        settle().await;
+        assert_eq!(gate.status().queued.high, 1);
+        cancelled.abort();
+        let _ = cancelled.await;
+        assert_eq!(gate.status().queued.high, 0);
+
+        drop(running);
+        assert_eq!(gate.status().in_flight, LaneCounts::default());
+        let _permit = gate.acquire(RequestPriority::Low).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_disabled_and_reconfigure() {
+        let gate = gate(1, 0, 1);
+        let first = gate.acquire(RequestPriority::Normal).await.unwrap();
+        let gate_clone = gate.clone();
+        let queued = tokio::spawn(async move { gate_clone.acquire(RequestPriority::Normal).await });
+        settle().await;
+
+        // 关闭限制后立即放行排队的请求，仍统计并发
+        gate.set_config(AdmissionControlConfig {
+            enabled: false,
+            ..AdmissionControlConfig::default()
+        });
+        let second = queued.await.unwrap().unwrap();
+        let third = gate.acquire(RequestPriority::Normal).await.unwrap();
+        assert_eq!(gate.status().in_flight.normal, 3);
+        drop((first, second, third));
+        assert_eq!(gate.status().in_flight.normal, 0);
+    }
+}
@@ -0,0 +1,169 @@
+//! 上游响应异常检测服务
+//!
+//! 在后置步骤检查上游成功返回的非流式响应（空响应、重复 Token、JSON 截断、延迟突增），
+//! 按 Provider 记录到全局的 [`AnomalyTracker`]，供监控面板显示"Provider 降级"。
+//!
+//! 配置 `anomaly_detection.failover` 开启时，检测到异常的凭证记为一次失败，
+//! 错误次数达到凭证池上限后自动切换到其他凭证。
+
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::config::AnomalyDetectionConfig;
+use crate::database::DbConnection;
+use crate::processor::RequestContext;
+use crate::services::provider_pool_service::ProviderPoolService;
+use crate::telemetry::{AnomalyConfig, AnomalyRecord, AnomalyTracker};
+use crate::ProviderType;
+
+/// 请求上下文中记录异常的元数据键
+pub const ANOMALIES_METADATA: &str = "anomalies";
+
+/// 全局异常追踪器
+static ANOMALY_TRACKER: once_cell::sync::Lazy<Arc<AnomalyTracker>> =
+    once_cell::sync::Lazy::new(|| {
+        Arc::new(AnomalyTracker::new(tracker_config(
+            &AnomalyDetectionConfig::default(),
+        )))
+    });
+
+/// 当前的检测配置
+static SETTINGS: once_cell::sync::Lazy<RwLock<AnomalyDetectionConfig>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(AnomalyDetectionConfig::default()));
+
+/// 获取全局异常追踪器
+pub fn get_anomaly_tracker() -> Arc<AnomalyTracker> {
+    ANOMALY_TRACKER.clone()
+}
+
+/// 应用配置（服务启动和配置热重载时调用）
+pub fn configure(config: &AnomalyDetectionConfig) {
+    ANOMALY_TRACKER.set_config(tracker_config(config));
+    *SETTINGS.write() = config.clone();
+}
+
+/// 是否启用异常检测
+pub fn is_enabled() -> bool {
+    SETTINGS.read().enabled
+}
+
+/// 检测到异常时是否触发故障转移
+pub fn failover_enabled() -> bool {
+    SETTINGS.read().failover
+}
+
+fn tracker_config(config: &AnomalyDetectionConfig) -> AnomalyConfig {
+    AnomalyConfig {
+        repeat_threshold: config.repeat_threshold,
+        latency_spike_factor: config.latency_spike_factor,
+        degraded_threshold: config.degraded_threshold,
+        window: chrono::Duration::minutes(config.window_minutes.max(1) as i64),
+        ..AnomalyConfig::default()
+    }
+}
+
+/// 用指定的追踪器检查一次成功的非流式响应
+///
+/// 检测到的异常写入请求上下文的 [`ANOMALIES_METADATA`] 元数据
+pub fn inspect_with(
+    tracker: &AnomalyTracker,
+    ctx: &mut RequestContext,
+    body: &[u8],
+) -> Vec<AnomalyRecord> {
+    let records = tracker.inspect(
+        &ctx.request_id,
+        ctx.provider.unwrap_or(ProviderType::Kiro),
+        &ctx.resolved_model,
+        ctx.credential_id.as_deref(),
+        body,
+        Some(ctx.elapsed_ms()),
+    );
+    if !records.is_empty() {
+        ctx.set_metadata(ANOMALIES_METADATA, serde_json::json!(records));
+    }
+    records
+}
+
+/// 检查一次成功的非流式响应，记录到全局追踪器
+pub fn inspect(ctx: &mut RequestContext, body: &[u8]) -> Vec<AnomalyRecord> {
+    inspect_with(&ANOMALY_TRACKER, ctx, body)
+}
+
+/// 异常说明，用于日志和凭证错误信息
+pub fn describe(records: &[AnomalyRecord]) -> String {
+    records
+        .iter()
+        .map(|r| format!("{}: {}", r.kind, r.detail))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// 开启故障转移时把出现异常的凭证记为一次失败
+///
+/// 返回是否已标记
+pub fn trigger_failover(
+    pool_service: &ProviderPoolService,
+    db: &DbConnection,
+    credential_id: Option<&str>,
+    records: &[AnomalyRecord],
+) -> bool {
+    if records.is_empty() || !failover_enabled() {
+        return false;
+    }
+    let Some(uuid) = credential_id else {
+        return false;
+    };
+
+    let message = format!("上游响应异常: {}", describe(records));
+    match pool_service.mark_unhealthy(db, uuid, Some(&message)) {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("[ANOMALY] 标记凭证 {} 失败: {}", uuid, e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::AnomalyKind;
+
+    #[test]
+    fn test_inspect_with_sets_metadata() {
+        let tracker = AnomalyTracker::with_defaults();
+        let mut ctx = RequestContext::new("gpt-4o".to_string());
+        ctx.set_provider(ProviderType::OpenAI);
+
+        let ok = br#"{"choices": [{"message": {"content": "hi"}}]}"#;
+        assert!(inspect_with(&tracker, &mut ctx, ok).is_empty());
+        assert!(ctx.get_metadata(ANOMALIES_METADATA).is_none());
+
+        let empty = br#"{"choices": [{"message": {"content": ""}}]}"#;
+        let records = inspect_with(&tracker, &mut ctx, empty);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].kind, AnomalyKind::EmptyResponse);
+        assert_eq!(records[0].provider, ProviderType::OpenAI);
+        assert_eq!(records[0].model, "gpt-4o");
+        assert_eq!(
+            ctx.get_metadata(ANOMALIES_METADATA).unwrap()[0]["kind"],
+            "empty_response"
+        );
+        assert!(describe(&records).starts_with("empty_response: "));
+    }
+
+    #[test]
+    fn test_tracker_config_mapping() {
+        let config = AnomalyDetectionConfig {
+            repeat_threshold: 5,
+            degraded_threshold: 7,
+            window_minutes: 0,
+            ..Default::default()
+        };
+        let mapped = tracker_config(&config);
+        assert_eq!(mapped.repeat_threshold, 5);
+        assert_eq!(mapped.degraded_threshold, 7);
+        assert_eq!(mapped.window, chrono::Duration::minutes(1));
+    }
+}
@@ -87,7 +87,7 @@ impl EncryptionService {
     /// 创建新的加密服务
     fn new() -> Self {
         // 使用机器特定信息生成密钥
-        let machine_id = Self::get_machine_id();
+        let machine_id = machine_id();
         let mut hasher = Sha256::new();
         hasher.update(machine_id.as_bytes());
         hasher.update(b"proxycast-api-key-encryption-salt");
@@ -96,36 +96,6 @@ impl EncryptionService {
         Self { key }
     }
 
-    /// 获取机器 ID
-    fn get_machine_id() -> String {
-        // 尝试获取机器 ID，失败则使用默认值
-        if let Ok(id) = std::fs::read_to_string("/etc/machine-id") {
-            return id.trim().to_string();
-        }
-        if let Ok(id) = std::fs::read_to_string("/var/lib/dbus/machine-id") {
-            return id.trim().to_string();
-        }
-        // macOS: 使用 IOPlatformUUID
-        #[cfg(target_os = "macos")]
-        {
-            if let Ok(output) = std::process::Command::new("ioreg")
-                .args(["-rd1", "-c", "IOPlatformExpertDevice"])
-                .output()
-            {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                for line in stdout.lines() {
-                    if line.contains("IOPlatformUUID") {
-                        if let Some(uuid) = line.split('"').nth(3) {
-                            return uuid.to_string();
-                        }
-                    }
-                }
-            }
-        }
-        // 默认值
-        "proxycast-default-machine-id".to_string()
-    }
-
     /// 加密 API Key
     fn encrypt(&self, plaintext: &str) -> String {
         let encrypted: Vec<u8> = plaintext
@@ -160,6 +130,36 @@ impl EncryptionService {
     }
 }
 
+/// 获取机器 ID（用于派生本地加密密钥）
+pub(crate) fn machine_id() -> String {
+    // 尝试获取机器 ID，失败则使用默认值
+    if let Ok(id) = std::fs::read_to_string("/etc/machine-id") {
+        return id.trim().to_string();
+    }
+    if let Ok(id) = std::fs::read_to_string("/var/lib/dbus/machine-id") {
+        return id.trim().to_string();
+    }
+    // macOS: 使用 IOPlatformUUID
+    #[cfg(target_os = "macos")]
+    {
+        if let Ok(output) = std::process::Command::new("ioreg")
+            .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+            .output()
+        {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            for line in stdout.lines() {
+                if line.contains("IOPlatformUUID") {
+                    if let Some(uuid) = line.split('"').nth(3) {
+                        return uuid.to_string();
+                    }
+                }
+            }
+        }
+    }
+    // 默认值
+    "proxycast-default-machine-id".to_string()
+}
+
 // ============================================================================
 // API Key Provider 服务
 // ============================================================================
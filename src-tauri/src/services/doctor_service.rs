@@ -0,0 +1,496 @@
+//! 自检服务
+//!
+//! `doctor` 命令依次执行端到端检查，返回 pass / warn / fail 结构化报告：
+//! - 端口：API 服务端口是否可监听
+//! - 配置：配置文件能否解析、是否通过校验
+//! - 数据库：SQLite 完整性检查
+//! - 凭证：凭证池中启用的凭证是否可用（可选发起实际健康检查请求）
+//! - 麦克风、辅助功能权限
+//! - 本地识别模型文件
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::config::{validate_config, ConfigManager};
+use crate::database::dao::provider_pool::ProviderPoolDao;
+use crate::database::DbConnection;
+use crate::models::provider_pool_model::ProviderCredential;
+use crate::server::ServerStatus;
+use crate::services::provider_pool_service::ProviderPoolService;
+use crate::voice::asr_service::AsrService;
+use crate::voice::permissions::{self, PermissionKind, PermissionStatus};
+
+/// 完整性检查最多展示的问题条数
+const MAX_INTEGRITY_ERRORS: usize = 5;
+
+/// 检查结果状态（按严重程度排序）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// 单项检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorCheck {
+    /// 检查项 ID
+    pub id: &'static str,
+    /// 检查项名称
+    pub name: &'static str,
+    pub status: CheckStatus,
+    /// 检查结论
+    pub message: String,
+    /// 修复建议
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    /// 详细信息（如不可用的凭证）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+}
+
+impl DoctorCheck {
+    fn new(
+        id: &'static str,
+        name: &'static str,
+        status: CheckStatus,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            status,
+            message: message.into(),
+            hint: None,
+            details: Vec::new(),
+        }
+    }
+
+    fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+/// 自检报告
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    /// 总体状态（最严重的检查结果）
+    pub status: CheckStatus,
+    pub passed: usize,
+    pub warned: usize,
+    pub failed: usize,
+    pub checks: Vec<DoctorCheck>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl DoctorReport {
+    fn new(checks: Vec<DoctorCheck>) -> Self {
+        let count = |status| checks.iter().filter(|c| c.status == status).count();
+        Self {
+            status: checks
+                .iter()
+                .map(|c| c.status)
+                .max()
+                .unwrap_or(CheckStatus::Pass),
+            passed: count(CheckStatus::Pass),
+            warned: count(CheckStatus::Warn),
+            failed: count(CheckStatus::Fail),
+            checks,
+            checked_at: Utc::now(),
+        }
+    }
+}
+
+/// 执行全部检查
+///
+/// `live` 为 true 时对启用了健康检查的凭证发起实际请求，否则只读取最近一次记录的健康状态
+pub async fn run(
+    server: ServerStatus,
+    db: &DbConnection,
+    pool_service: &ProviderPoolService,
+    live: bool,
+) -> DoctorReport {
+    let mut checks = vec![
+        check_port(&server),
+        check_config(&ConfigManager::default_config_path()),
+    ];
+
+    let db_clone = db.clone();
+    checks.push(
+        tokio::task::spawn_blocking(move || match db_clone.lock() {
+            Ok(conn) => check_database(&conn),
+            Err(e) => DoctorCheck::new("database", "数据库", CheckStatus::Fail, e.to_string()),
+        })
+        .await
+        .unwrap_or_else(|e| {
+            DoctorCheck::new("database", "数据库", CheckStatus::Fail, e.to_string())
+        }),
+    );
+
+    checks.push(check_credentials(db, pool_service, live).await);
+    checks.push(check_permission(
+        PermissionKind::Microphone,
+        permissions::check(PermissionKind::Microphone),
+    ));
+    checks.push(check_permission(
+        PermissionKind::Accessibility,
+        permissions::check(PermissionKind::Accessibility),
+    ));
+    checks.push(check_local_model(AsrService::check_local_model()));
+
+    let report = DoctorReport::new(checks);
+    tracing::info!(
+        "[自检] 通过 {}，警告 {}，失败 {}",
+        report.passed,
+        report.warned,
+        report.failed
+    );
+    report
+}
+
+/// 检查 API 服务端口
+fn check_port(server: &ServerStatus) -> DoctorCheck {
+    let addr = format!("{}:{}", server.host, server.port);
+    if server.running {
+        return DoctorCheck::new(
+            "port",
+            "服务端口",
+            CheckStatus::Pass,
+            format!("API 服务正在监听 {}", addr),
+        );
+    }
+
+    match std::net::TcpListener::bind((server.host.as_str(), server.port)) {
+        Ok(_) => DoctorCheck::new(
+            "port",
+            "服务端口",
+            CheckStatus::Pass,
+            format!("端口 {} 可用", addr),
+        ),
+        Err(e) => DoctorCheck::new(
+            "port",
+            "服务端口",
+            CheckStatus::Fail,
+            format!("无法监听 {}: {}", addr, e),
+        )
+        .with_hint("关闭占用该端口的程序，或在设置中修改服务端口"),
+    }
+}
+
+/// 检查配置文件能否解析并通过校验
+fn check_config(path: &Path) -> DoctorCheck {
+    let fail = |message: String| {
+        DoctorCheck::new("config", "配置文件", CheckStatus::Fail, message)
+            .with_hint(format!("检查配置文件: {}", path.display()))
+    };
+
+    if !path.exists() {
+        return DoctorCheck::new(
+            "config",
+            "配置文件",
+            CheckStatus::Warn,
+            "配置文件不存在，正在使用默认配置",
+        )
+        .with_hint("在设置中保存一次配置即可生成配置文件");
+    }
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return fail(format!("读取配置文件失败: {}", e)),
+    };
+    let config = match ConfigManager::parse_yaml(&content) {
+        Ok(config) => config,
+        Err(e) => return fail(format!("解析配置文件失败: {}", e)),
+    };
+    match validate_config(&config) {
+        Ok(()) => DoctorCheck::new(
+            "config",
+            "配置文件",
+            CheckStatus::Pass,
+            format!("配置有效: {}", path.display()),
+        ),
+        Err(e) => fail(e.to_string()),
+    }
+}
+
+/// SQLite 完整性检查
+fn check_database(conn: &Connection) -> DoctorCheck {
+    let result = conn.prepare("PRAGMA integrity_check").and_then(|mut stmt| {
+        stmt.query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()
+    });
+
+    match result {
+        Ok(rows) if rows.len() == 1 && rows[0] == "ok" => DoctorCheck::new(
+            "database",
+            "数据库",
+            CheckStatus::Pass,
+            "数据库完整性检查通过",
+        ),
+        Ok(rows) => DoctorCheck::new(
+            "database",
+            "数据库",
+            CheckStatus::Fail,
+            format!("数据库完整性检查发现 {} 个问题", rows.len()),
+        )
+        .with_hint("数据库可能已损坏，请从备份恢复")
+        .with_details(rows.into_iter().take(MAX_INTEGRITY_ERRORS).collect()),
+        Err(e) => DoctorCheck::new(
+            "database",
+            "数据库",
+            CheckStatus::Fail,
+            format!("数据库完整性检查失败: {}", e),
+        ),
+    }
+}
+
+/// 检查凭证池中启用的凭证
+async fn check_credentials(
+    db: &DbConnection,
+    pool_service: &ProviderPoolService,
+    live: bool,
+) -> DoctorCheck {
+    let load = || -> Result<Vec<ProviderCredential>, String> {
+        let conn = db.lock().map_err(|e| e.to_string())?;
+        ProviderPoolDao::get_all(&conn).map_err(|e| e.to_string())
+    };
+
+    if live {
+        let targets = match load() {
+            Ok(credentials) => credentials
+                .into_iter()
+                .filter(|c| !c.is_disabled && c.check_health)
+                .map(|c| c.uuid),
+            Err(e) => return credentials_error(e),
+        };
+        for uuid in targets {
+            // 检查结果会写回凭证的健康状态，下面统一汇总
+            if let Err(e) = pool_service.check_credential_health(db, &uuid).await {
+                tracing::warn!("[自检] 凭证 {} 健康检查失败: {}", uuid, e);
+            }
+        }
+    }
+
+    match load() {
+        Ok(credentials) => summarize_credentials(&credentials),
+        Err(e) => credentials_error(e),
+    }
+}
+
+fn credentials_error(error: String) -> DoctorCheck {
+    DoctorCheck::new(
+        "credentials",
+        "凭证",
+        CheckStatus::Fail,
+        format!("读取凭证池失败: {}", error),
+    )
+}
+
+/// 按凭证健康状态汇总：全部可用为通过，部分不可用为警告，全部不可用为失败
+fn summarize_credentials(credentials: &[ProviderCredential]) -> DoctorCheck {
+    let enabled: Vec<_> = credentials.iter().filter(|c| !c.is_disabled).collect();
+    if enabled.is_empty() {
+        return DoctorCheck::new(
+            "credentials",
+            "凭证",
+            CheckStatus::Warn,
+            "凭证池中没有启用的凭证",
+        )
+        .with_hint("在凭证池中添加凭证后才能转发请求");
+    }
+
+    let unhealthy: Vec<String> = enabled
+        .iter()
+        .filter(|c| !c.is_healthy)
+        .map(|c| {
+            let name = c.name.clone().unwrap_or_else(|| c.uuid.clone());
+            format!(
+                "{} ({}): {}",
+                name,
+                c.provider_type,
+                c.last_error_message.as_deref().unwrap_or("不可用")
+            )
+        })
+        .collect();
+
+    let (status, message) = if unhealthy.is_empty() {
+        (
+            CheckStatus::Pass,
+            format!("{} 个启用的凭证均可用", enabled.len()),
+        )
+    } else if unhealthy.len() == enabled.len() {
+        (
+            CheckStatus::Fail,
+            format!("{} 个启用的凭证均不可用", enabled.len()),
+        )
+    } else {
+        (
+            CheckStatus::Warn,
+            format!("{}/{} 个启用的凭证不可用", unhealthy.len(), enabled.len()),
+        )
+    };
+
+    let check = DoctorCheck::new("credentials", "凭证", status, message);
+    if unhealthy.is_empty() {
+        check
+    } else {
+        check
+            .with_hint("在凭证池中刷新 Token 或重新授权不可用的凭证")
+            .with_details(unhealthy)
+    }
+}
+
+/// 系统权限检查
+fn check_permission(kind: PermissionKind, status: PermissionStatus) -> DoctorCheck {
+    let (id, name, usage) = match kind {
+        PermissionKind::Accessibility => ("accessibility", "辅助功能权限", "语音输入结果上屏"),
+        _ => ("microphone", "麦克风权限", "语音输入录音"),
+    };
+
+    let (status, message) = match status {
+        PermissionStatus::Granted => (CheckStatus::Pass, "已授权".to_string()),
+        PermissionStatus::NotRequired => (CheckStatus::Pass, "当前平台无需授权".to_string()),
+        PermissionStatus::NotDetermined => (
+            CheckStatus::Warn,
+            format!("尚未授权，首次{}时系统会请求授权", usage),
+        ),
+        PermissionStatus::Denied => (CheckStatus::Fail, format!("已拒绝，无法{}", usage)),
+        PermissionStatus::Unknown => (CheckStatus::Warn, "无法检测授权状态".to_string()),
+    };
+
+    let check = DoctorCheck::new(id, name, status, message);
+    if status == CheckStatus::Pass {
+        check
+    } else {
+        check.with_hint("在语音设置的权限检查中打开系统设置授权")
+    }
+}
+
+/// 本地识别模型检查
+fn check_local_model(result: Result<Option<PathBuf>, String>) -> DoctorCheck {
+    match result {
+        Ok(Some(path)) => DoctorCheck::new(
+            "local_model",
+            "本地识别模型",
+            CheckStatus::Pass,
+            format!("模型已就绪: {}", path.display()),
+        ),
+        Ok(None) => DoctorCheck::new(
+            "local_model",
+            "本地识别模型",
+            CheckStatus::Pass,
+            "未启用本地识别，无需模型",
+        ),
+        Err(e) => DoctorCheck::new("local_model", "本地识别模型", CheckStatus::Fail, e)
+            .with_hint("在语音设置中下载模型"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::provider_pool_model::{CredentialData, PoolProviderType};
+
+    fn credential(name: &str, healthy: bool, disabled: bool) -> ProviderCredential {
+        let mut credential = ProviderCredential::new(
+            PoolProviderType::OpenAI,
+            CredentialData::OpenAIKey {
+                api_key: "sk-test".to_string(),
+                base_url: None,
+            },
+        );
+        credential.name = Some(name.to_string());
+        credential.is_healthy = healthy;
+        credential.is_disabled = disabled;
+        credential
+    }
+
+    #[test]
+    fn test_report_status_is_most_severe() {
+        let report = DoctorReport::new(vec![
+            DoctorCheck::new("a", "A", CheckStatus::Pass, ""),
+            DoctorCheck::new("b", "B", CheckStatus::Warn, ""),
+            DoctorCheck::new("c", "C", CheckStatus::Pass, ""),
+        ]);
+        assert_eq!(report.status, CheckStatus::Warn);
+        assert_eq!((report.passed, report.warned, report.failed), (2, 1, 0));
+        assert_eq!(DoctorReport::new(Vec::new()).status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_check_port_in_use() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let mut server = ServerStatus {
+            running: false,
+            host: "127.0.0.1".to_string(),
+            port,
+            requests: 0,
+            uptime_secs: 0,
+        };
+        assert_eq!(check_port(&server).status, CheckStatus::Fail);
+
+        server.running = true;
+        assert_eq!(check_port(&server).status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_check_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        assert_eq!(check_config(&path).status, CheckStatus::Warn);
+
+        std::fs::write(&path, "server: [").unwrap();
+        assert_eq!(check_config(&path).status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_check_database() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t (id INTEGER PRIMARY KEY);")
+            .unwrap();
+        assert_eq!(check_database(&conn).status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_summarize_credentials() {
+        assert_eq!(summarize_credentials(&[]).status, CheckStatus::Warn);
+
+        let all_healthy = [credential("a", true, false), credential("b", false, true)];
+        assert_eq!(
+            summarize_credentials(&all_healthy).status,
+            CheckStatus::Pass
+        );
+
+        let partial = [credential("a", true, false), credential("b", false, false)];
+        let check = summarize_credentials(&partial);
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert_eq!(check.details.len(), 1);
+        assert!(check.details[0].starts_with("b ("));
+
+        let none = [credential("a", false, false)];
+        assert_eq!(summarize_credentials(&none).status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_check_permission() {
+        let denied = check_permission(PermissionKind::Microphone, PermissionStatus::Denied);
+        assert_eq!(denied.id, "microphone");
+        assert_eq!(denied.status, CheckStatus::Fail);
+        assert!(denied.hint.is_some());
+
+        let granted = check_permission(PermissionKind::Accessibility, PermissionStatus::Granted);
+        assert_eq!(granted.id, "accessibility");
+        assert_eq!(granted.status, CheckStatus::Pass);
+    }
+}
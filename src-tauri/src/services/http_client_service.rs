@@ -0,0 +1,254 @@
+//! 上游 HTTP 客户端服务
+//!
+//! 每个 Provider 复用一个 reqwest 客户端，请求之间共享连接池和 TLS 会话，
+//! 而不是每次构造 Provider 时新建客户端、重新握手。
+//!
+//! 连接池大小、空闲超时、keepalive 和域名解析覆盖（`hosts`）来自 [`UpstreamHttpConfig`]，
+//! 配置变化时清空缓存，之后的请求按新配置创建客户端；已发出的请求不受影响。
+//!
+//! 默认允许 HTTP/2（TLS ALPN 协商），同一主机的并发请求复用一个连接。
+//! 每个客户端新建的连接和通过 [`TrackedSend::send_tracked`] 发出的请求
+//! 记录到全局的 [`ConnectionTracker`]，用来确认连接复用率和实际使用的协议版本。
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use parking_lot::RwLock;
+use reqwest::{Client, RequestBuilder, Response};
+use tower::{Layer, Service};
+
+use crate::config::UpstreamHttpConfig;
+use crate::telemetry::ConnectionTracker;
+
+/// 当前的客户端配置
+static SETTINGS: once_cell::sync::Lazy<RwLock<UpstreamHttpConfig>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(UpstreamHttpConfig::default()));
+
+/// 按 Provider 缓存的客户端
+static CLIENTS: once_cell::sync::Lazy<RwLock<HashMap<String, Client>>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 全局连接复用统计
+static CONNECTION_TRACKER: once_cell::sync::Lazy<Arc<ConnectionTracker>> =
+    once_cell::sync::Lazy::new(|| Arc::new(ConnectionTracker::new()));
+
+/// 获取全局连接复用统计
+pub fn connection_tracker() -> Arc<ConnectionTracker> {
+    CONNECTION_TRACKER.clone()
+}
+
+/// 应用配置（服务启动和配置热重载时调用）
+pub fn configure(config: &UpstreamHttpConfig) {
+    let mut settings = SETTINGS.write();
+    if *settings == *config {
+        return;
+    }
+    *settings = config.clone();
+    CLIENTS.write().clear();
+    tracing::info!(
+        "[HTTP] 上游客户端配置已更新，连接池上限 {}/主机，HTTP/2 {}，域名覆盖 {} 条",
+        config.pool_max_idle_per_host,
+        if config.http2 { "开启" } else { "关闭" },
+        config.hosts.len()
+    );
+}
+
+/// 获取 Provider 共享的客户端
+///
+/// 同一 Provider 的超时固定，首次调用时按当前配置创建；
+/// 配置无效（如 `hosts` 中的 IP 格式错误）时记录警告并使用默认客户端
+pub fn client(provider: &str, timeout: Duration) -> Client {
+    if let Some(client) = CLIENTS.read().get(provider) {
+        return client.clone();
+    }
+
+    let settings = SETTINGS.read().clone();
+    let client = build_client(provider, &settings, timeout).unwrap_or_else(|e| {
+        tracing::warn!("[HTTP] 创建 {} 客户端失败，使用默认配置: {}", provider, e);
+        Client::new()
+    });
+    CLIENTS
+        .write()
+        .entry(provider.to_string())
+        .or_insert(client)
+        .clone()
+}
+
+/// 按配置创建客户端，新建的连接计入 `provider` 的连接复用统计
+pub fn build_client(
+    provider: &str,
+    config: &UpstreamHttpConfig,
+    timeout: Duration,
+) -> Result<Client, String> {
+    let mut builder = Client::builder()
+        .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+        .timeout(timeout)
+        .pool_max_idle_per_host(config.pool_max_idle_per_host)
+        .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_secs))
+        .gzip(true)
+        .brotli(true)
+        .deflate(true)
+        .connector_layer(CountConnectionsLayer::new(provider));
+    builder = if config.http2 {
+        builder.http2_adaptive_window(true)
+    } else {
+        builder.http1_only()
+    };
+    if config.tcp_keepalive_secs > 0 {
+        builder = builder.tcp_keepalive(Duration::from_secs(config.tcp_keepalive_secs));
+    }
+    for (host, addrs) in parse_hosts(&config.hosts)? {
+        builder = builder.resolve_to_addrs(&host, &addrs);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// 记录请求和响应协议版本的发送方法
+pub trait TrackedSend {
+    /// 发送请求，并把结果计入 `client` 的连接复用统计
+    fn send_tracked(self, client: &str) -> impl Future<Output = reqwest::Result<Response>> + Send;
+}
+
+impl TrackedSend for RequestBuilder {
+    fn send_tracked(self, client: &str) -> impl Future<Output = reqwest::Result<Response>> + Send {
+        let client = client.to_string();
+        async move {
+            let result = self.send().await;
+            connection_tracker()
+                .record_request(&client, result.as_ref().ok().map(Response::version));
+            result
+        }
+    }
+}
+
+/// 统计新建连接的连接器层
+///
+/// 只有连接池中没有可用连接时才会调用连接器，因此每次调用都是一次完整的 TCP + TLS 握手
+#[derive(Clone)]
+struct CountConnectionsLayer {
+    client: Arc<str>,
+}
+
+impl CountConnectionsLayer {
+    fn new(client: &str) -> Self {
+        Self {
+            client: Arc::from(client),
+        }
+    }
+}
+
+impl<S> Layer<S> for CountConnectionsLayer {
+    type Service = CountConnections<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CountConnections {
+            inner,
+            client: self.client.clone(),
+        }
+    }
+}
+
+/// 统计新建连接的连接器
+#[derive(Clone)]
+struct CountConnections<S> {
+    inner: S,
+    client: Arc<str>,
+}
+
+impl<S, R> Service<R> for CountConnections<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<S::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let future = self.inner.call(request);
+        let client = self.client.clone();
+        Box::pin(async move {
+            let result = future.await;
+            connection_tracker().record_connection(&client, result.is_ok());
+            result
+        })
+    }
+}
+
+/// 解析域名覆盖
+///
+/// reqwest 忽略覆盖地址中的端口，端口统一填 0
+fn parse_hosts(
+    hosts: &HashMap<String, Vec<String>>,
+) -> Result<Vec<(String, Vec<SocketAddr>)>, String> {
+    hosts
+        .iter()
+        .map(|(host, ips)| {
+            if ips.is_empty() {
+                return Err(format!("域名 {} 未配置 IP", host));
+            }
+            let addrs = ips
+                .iter()
+                .map(|ip| {
+                    ip.trim()
+                        .parse::<IpAddr>()
+                        .map(|ip| SocketAddr::new(ip, 0))
+                        .map_err(|_| format!("域名 {} 的 IP 无效: {}", host, ip))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((host.trim().to_lowercase(), addrs))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hosts() {
+        let hosts = HashMap::from([(
+            "API.Internal.example.com".to_string(),
+            vec!["10.0.0.5".to_string(), " fd00::1 ".to_string()],
+        )]);
+        let parsed = parse_hosts(&hosts).unwrap();
+        assert_eq!(parsed[0].0, "api.internal.example.com");
+        assert_eq!(
+            parsed[0].1,
+            vec![
+                "10.0.0.5:0".parse::<SocketAddr>().unwrap(),
+                "[fd00::1]:0".parse::<SocketAddr>().unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_hosts_rejects_invalid_ip() {
+        let hosts = HashMap::from([(
+            "api.example.com".to_string(),
+            vec!["10.0.0.5:443".to_string()],
+        )]);
+        assert!(parse_hosts(&hosts).unwrap_err().contains("10.0.0.5:443"));
+
+        let empty = HashMap::from([("api.example.com".to_string(), Vec::new())]);
+        assert!(parse_hosts(&empty).is_err());
+        assert!(build_client(
+            "test",
+            &UpstreamHttpConfig {
+                hosts: empty,
+                ..Default::default()
+            },
+            Duration::from_secs(10)
+        )
+        .is_err());
+    }
+}
@@ -1,9 +1,13 @@
+pub mod admission_service;
+pub mod anomaly_service;
 pub mod api_key_provider_service;
 pub mod aster_session_store;
 pub mod backup_service;
 pub mod context_memory_service;
+pub mod doctor_service;
 pub mod file_browser_service;
 pub mod general_chat;
+pub mod http_client_service;
 pub mod kiro_event_service;
 pub mod live_sync;
 pub mod machine_id_service;
@@ -11,14 +15,20 @@ pub mod mcp_service;
 pub mod mcp_sync;
 pub mod model_registry_service;
 pub mod model_service;
+pub mod pipeline_metrics_service;
+pub mod postmortem_service;
 pub mod prompt_service;
 pub mod prompt_sync;
 pub mod provider_pool_service;
+pub mod response_buffer_service;
 pub mod session_context_service;
 pub mod skill_service;
+pub mod startup_profile_service;
+pub mod stream_aggregation_service;
 pub mod switch;
 pub mod sysinfo_service;
 pub mod token_cache_service;
+pub mod token_count_service;
 pub mod tool_hooks_service;
 pub mod update_check_service;
 pub mod update_window;
@@ -8,6 +8,7 @@ use crate::models::model_registry::{
     EnhancedModelMetadata, ModelCapabilities, ModelLimits, ModelPricing, ModelSource, ModelStatus,
     ModelSyncState, ModelTier, ProviderAliasConfig, UserModelPreference,
 };
+use proxycast_providers::{ProviderEndpoint, ProviderRegistry};
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -519,6 +520,7 @@ impl ModelRegistryService {
         tx.commit().map_err(|e| e.to_string())?;
 
         tracing::info!("[ModelRegistry] 保存了 {} 个模型到数据库", models.len());
+        crate::services::token_count_service::invalidate_context_lengths();
 
         Ok(())
     }
@@ -574,6 +576,16 @@ impl ModelRegistryService {
         Ok(model_count)
     }
 
+    /// 按模型 ID 获取模型
+    pub async fn get_model(&self, model_id: &str) -> Option<EnhancedModelMetadata> {
+        self.models_cache
+            .read()
+            .await
+            .iter()
+            .find(|m| m.id == model_id)
+            .cloned()
+    }
+
     /// 按 Provider 获取模型
     pub async fn get_models_by_provider(&self, provider_id: &str) -> Vec<EnhancedModelMetadata> {
         self.models_cache
@@ -842,12 +854,23 @@ impl ModelRegistryService {
             api_host
         );
 
-        // 构建 API URL
-        let api_url = Self::build_models_api_url(api_host);
+        // 已接入 proxycast_providers 的上游由其构建请求（URL 和认证方式可能不同）
+        let provider = ProviderRegistry::builtin().get(provider_id).ok();
+        let request = provider.as_ref().and_then(|p| {
+            p.build_models_request(&ProviderEndpoint::new(api_key).with_base_url(api_host))
+                .ok()
+        });
+        let (api_url, headers) = match request {
+            Some(request) => (request.url, request.headers),
+            None => (
+                Self::build_models_api_url(api_host),
+                vec![("Authorization".to_string(), format!("Bearer {}", api_key))],
+            ),
+        };
         tracing::info!("[ModelRegistry] API URL: {}", api_url);
 
         // 尝试从 API 获取
-        match self.call_models_api(&api_url, api_key).await {
+        match self.call_models_api(&api_url, &headers).await {
             Ok(api_models) => {
                 tracing::info!("[ModelRegistry] 从 API 获取到 {} 个模型", api_models.len());
 
@@ -870,8 +893,25 @@ impl ModelRegistryService {
                     api_error
                 );
 
-                // 回退到本地 JSON 文件
-                let local_models = self.get_models_by_provider(provider_id).await;
+                // 回退到本地 JSON 文件，仍为空时使用 Provider 内置模型列表
+                let mut local_models = self.get_models_by_provider(provider_id).await;
+                if local_models.is_empty() {
+                    if let Some(provider) = &provider {
+                        let now = chrono::Utc::now().timestamp();
+                        local_models = provider
+                            .builtin_models()
+                            .iter()
+                            .map(|id| {
+                                let model = ApiModelResponse {
+                                    id: id.to_string(),
+                                    owned_by: None,
+                                    context_length: None,
+                                };
+                                self.convert_api_model(model, provider_id, now)
+                            })
+                            .collect();
+                    }
+                }
 
                 if local_models.is_empty() {
                     Ok(FetchModelsResult {
@@ -909,17 +949,19 @@ impl ModelRegistryService {
     async fn call_models_api(
         &self,
         url: &str,
-        api_key: &str,
+        headers: &[(String, String)],
     ) -> Result<Vec<ApiModelResponse>, String> {
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(30))
             .build()
             .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
 
-        let response = client
-            .get(url)
-            .header("Authorization", format!("Bearer {}", api_key))
-            .header("Content-Type", "application/json")
+        let mut request = client.get(url).header("Content-Type", "application/json");
+        for (name, value) in headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+
+        let response = request
             .send()
             .await
             .map_err(|e| format!("请求失败: {}", e))?;
@@ -0,0 +1,85 @@
+//! 管道开销统计服务
+//!
+//! 按步骤记录 ProxyCast 在请求路径上自身的耗时（不含等待上游），记录到全局的
+//! [`PipelineOverheadTracker`]，供监控面板确认代理只增加了可忽略的延迟。
+//!
+//! 同时按 `pipeline_metrics` 配置对昂贵的遥测采样：请求体捕获（Flow 监控）和
+//! 上游未返回用量时的输出 Token 重新计数。
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::RwLock;
+
+use crate::config::PipelineMetricsConfig;
+use crate::telemetry::{sampled, PipelineOverheadTracker};
+
+/// 预估输入 Token 并检测上下文溢出
+pub const STEP_TOKEN_COUNT: &str = "token_count";
+/// 参数注入
+pub const STEP_INJECTION: &str = "injection";
+/// 创建 Flow 并捕获请求体
+pub const STEP_FLOW_CAPTURE: &str = "flow_capture";
+/// 流结束后重新计数输出 Token
+pub const STEP_TOKEN_RECOUNT: &str = "token_recount";
+
+/// 全局开销统计器
+static OVERHEAD_TRACKER: once_cell::sync::Lazy<Arc<PipelineOverheadTracker>> =
+    once_cell::sync::Lazy::new(|| {
+        Arc::new(PipelineOverheadTracker::new(
+            PipelineMetricsConfig::default().overhead_window,
+        ))
+    });
+
+/// 当前的采样配置
+static SETTINGS: once_cell::sync::Lazy<RwLock<PipelineMetricsConfig>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(PipelineMetricsConfig::default()));
+
+/// 获取全局开销统计器
+pub fn get_overhead_tracker() -> Arc<PipelineOverheadTracker> {
+    OVERHEAD_TRACKER.clone()
+}
+
+/// 应用配置（服务启动和配置热重载时调用）
+pub fn configure(config: &PipelineMetricsConfig) {
+    OVERHEAD_TRACKER.set_window(config.overhead_window);
+    *SETTINGS.write() = config.clone();
+}
+
+/// 该请求是否捕获请求和响应正文
+pub fn sample_body_capture(request_id: &str) -> bool {
+    sampled(SETTINGS.read().body_capture_rate, request_id)
+}
+
+/// 该请求是否用分词器重新计数输出 Token
+pub fn sample_token_recount(request_id: &str) -> bool {
+    sampled(SETTINGS.read().token_recount_rate, request_id)
+}
+
+/// 记录一次步骤耗时
+pub fn record_step(step: &str, elapsed: Duration) {
+    OVERHEAD_TRACKER.record(step, elapsed);
+}
+
+/// 执行 `f` 并记录其耗时
+pub fn measure<T>(step: &str, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = f();
+    record_step(step, started.elapsed());
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_records_step() {
+        let value = measure("test_measure_step", || 42);
+        assert_eq!(value, 42);
+        let report = get_overhead_tracker().report();
+        assert!(report
+            .iter()
+            .any(|s| s.step == "test_measure_step" && s.count == 1));
+    }
+}
@@ -0,0 +1,577 @@
+//! 失败请求诊断包服务
+//!
+//! 上游调用失败时保存一份脱敏后的诊断包，按请求 ID 查询，
+//! 无需开启全局调试日志即可排查"为什么工具收到 400"之类的问题。
+//!
+//! 诊断包包含：
+//! - 格式转换、参数整形后实际发往上游的请求体（Provider 调用未记录时为客户端请求体）
+//! - 上游状态码、响应头和响应体
+//! - 路由决策轨迹（别名映射、客户端识别、覆盖请求头、凭证选择）
+//!
+//! 认证相关的请求头和 JSON 字段在保存前替换为 `[REDACTED]`，
+//! 请求体中过长的字符串（如 base64 图片）和过大的响应体会被截断，
+//! 诊断包只保存在内存中，按数量上限滚动淘汰。
+
+use std::collections::{HashMap, VecDeque};
+
+use axum::http::HeaderMap;
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+
+use crate::processor::RequestContext;
+
+/// 失败响应中返回请求 ID 的响应头，客户端可凭此查询诊断包
+pub const REQUEST_ID_HEADER: &str = "x-proxycast-request-id";
+
+/// 请求上下文中记录路由轨迹的元数据键
+pub const ROUTE_TRACE_METADATA: &str = "route_trace";
+
+/// 请求上下文中记录实际发往上游的请求体的元数据键
+pub const UPSTREAM_REQUEST_METADATA: &str = "upstream_request";
+
+/// 最多保留的诊断包数量
+const MAX_BUNDLES: usize = 200;
+
+/// 请求体 / 响应体最大保存字节数
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// 请求体中单个字符串字段的最大保存字节数
+const MAX_FIELD_BYTES: usize = 4 * 1024;
+
+/// 脱敏替换值
+const REDACTED: &str = "[REDACTED]";
+
+/// 需要脱敏的请求头 / 响应头（小写）
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "x-api-key",
+    "api-key",
+    "x-goog-api-key",
+    "cookie",
+    "set-cookie",
+];
+
+/// 需要脱敏的 JSON 字段名（小写）
+const SENSITIVE_KEYS: &[&str] = &[
+    "api_key",
+    "apikey",
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "client_secret",
+    "password",
+    "authorization",
+];
+
+/// 失败请求诊断包
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostmortemBundle {
+    pub request_id: String,
+    pub created_at: DateTime<Utc>,
+    /// 入口端点，如 `/v1/chat/completions`
+    pub endpoint: String,
+    /// 客户端请求的模型
+    pub original_model: String,
+    /// 别名解析后的模型
+    pub resolved_model: String,
+    /// 使用的凭证类型
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// 使用的凭证 UUID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_id: Option<String>,
+    /// 请求体（已脱敏；超过上限时截断过长的字符串字段，
+    /// 仍然过大时保存为截断后的 JSON 文本）
+    pub request: serde_json::Value,
+    /// `request` 是否为实际发往上游的请求体；为 `false` 时是客户端发来的请求体
+    #[serde(default)]
+    pub request_is_upstream: bool,
+    /// 请求体是否被截断
+    #[serde(default)]
+    pub request_truncated: bool,
+    /// 上游状态码
+    pub upstream_status: u16,
+    /// 上游响应头（已脱敏）
+    pub response_headers: HashMap<String, String>,
+    /// 上游响应体（超过上限时截断）
+    pub response_body: String,
+    /// 响应体是否被截断
+    pub response_truncated: bool,
+    /// 路由决策轨迹
+    pub route_trace: Vec<String>,
+    pub retry_count: u32,
+    pub duration_ms: u64,
+}
+
+/// 诊断包存储
+pub struct PostmortemStore {
+    bundles: RwLock<VecDeque<PostmortemBundle>>,
+    capacity: usize,
+}
+
+impl PostmortemStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            bundles: RwLock::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    /// 保存诊断包，超出上限时淘汰最旧的
+    pub fn record(&self, bundle: PostmortemBundle) {
+        let mut bundles = self.bundles.write();
+        bundles.retain(|b| b.request_id != bundle.request_id);
+        bundles.push_back(bundle);
+        while bundles.len() > self.capacity {
+            bundles.pop_front();
+        }
+    }
+
+    /// 按请求 ID 查询
+    pub fn get(&self, request_id: &str) -> Option<PostmortemBundle> {
+        self.bundles
+            .read()
+            .iter()
+            .find(|b| b.request_id == request_id)
+            .cloned()
+    }
+
+    /// 列出最近的诊断包（最新的在前）
+    pub fn list(&self, limit: usize) -> Vec<PostmortemBundle> {
+        self.bundles
+            .read()
+            .iter()
+            .rev()
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    pub fn clear(&self) {
+        self.bundles.write().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.bundles.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bundles.read().is_empty()
+    }
+}
+
+/// 全局诊断包存储
+static POSTMORTEM_STORE: once_cell::sync::Lazy<PostmortemStore> =
+    once_cell::sync::Lazy::new(|| PostmortemStore::new(MAX_BUNDLES));
+
+/// 获取全局诊断包存储
+pub fn get_postmortem_store() -> &'static PostmortemStore {
+    &POSTMORTEM_STORE
+}
+
+/// Provider 调用中实际发往上游的请求体（格式转换和参数整形之后）
+///
+/// 由 Provider 调用记录，调用失败时通过 [`UpstreamRequest::store`] 写入请求上下文
+#[derive(Debug, Default)]
+pub struct UpstreamRequest(Mutex<Option<serde_json::Value>>);
+
+impl UpstreamRequest {
+    /// 记录发往上游的请求体，多次记录时保留最后一次
+    pub fn record(&self, request: serde_json::Value) {
+        *self.0.lock() = Some(request);
+    }
+
+    /// 序列化并记录发往上游的请求体
+    pub fn record_serialized(&self, request: &impl Serialize) {
+        if let Ok(value) = serde_json::to_value(request) {
+            self.record(value);
+        }
+    }
+
+    /// 把记录的请求体写入请求上下文
+    pub fn store(&self, ctx: &mut RequestContext) {
+        if let Some(request) = self.0.lock().take() {
+            ctx.set_metadata(UPSTREAM_REQUEST_METADATA, request);
+        }
+    }
+}
+
+/// 在请求上下文中追加一条路由轨迹
+pub fn trace(ctx: &mut RequestContext, step: impl Into<String>) {
+    let mut steps = route_trace(ctx);
+    steps.push(step.into());
+    ctx.set_metadata(ROUTE_TRACE_METADATA, serde_json::json!(steps));
+}
+
+/// 读取请求上下文中的路由轨迹
+pub fn route_trace(ctx: &RequestContext) -> Vec<String> {
+    ctx.get_metadata(ROUTE_TRACE_METADATA)
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// 构建诊断包
+///
+/// 请求上下文中记录了实际发往上游的请求体时保存该请求体，否则保存 `client_request`
+pub fn build_bundle(
+    ctx: &RequestContext,
+    endpoint: &str,
+    provider: Option<String>,
+    client_request: serde_json::Value,
+    upstream_status: u16,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> PostmortemBundle {
+    let upstream_request = ctx.get_metadata(UPSTREAM_REQUEST_METADATA).cloned();
+    let request_is_upstream = upstream_request.is_some();
+    let mut request = upstream_request.unwrap_or(client_request);
+    sanitize_json(&mut request);
+    let request_truncated = truncate_request(&mut request);
+    // 先脱敏再截断，避免截断处残留半截密钥
+    let (response_body, response_truncated) =
+        truncate_text(redact_text(&String::from_utf8_lossy(body)), MAX_BODY_BYTES);
+    PostmortemBundle {
+        request_id: ctx.request_id.clone(),
+        created_at: Utc::now(),
+        endpoint: endpoint.to_string(),
+        original_model: ctx.original_model.clone(),
+        resolved_model: ctx.resolved_model.clone(),
+        provider,
+        credential_id: ctx.credential_id.clone(),
+        request,
+        request_is_upstream,
+        request_truncated,
+        upstream_status,
+        response_headers: sanitize_headers(headers),
+        response_body,
+        response_truncated,
+        route_trace: route_trace(ctx),
+        retry_count: ctx.retry_count,
+        duration_ms: ctx.elapsed_ms(),
+    }
+}
+
+/// 脱敏 JSON 中的认证字段
+pub fn sanitize_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if is_sensitive_key(key) && !v.is_null() {
+                    *v = serde_json::json!(REDACTED);
+                } else {
+                    sanitize_json(v);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(sanitize_json),
+        _ => {}
+    }
+}
+
+/// 脱敏请求头 / 响应头
+pub fn sanitize_headers(headers: &HeaderMap) -> HashMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let name = name.as_str().to_string();
+            let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                value.to_str().unwrap_or("<binary>").to_string()
+            };
+            (name, value)
+        })
+        .collect()
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SENSITIVE_KEYS.contains(&key.as_str())
+}
+
+/// 上游错误信息中偶尔会回显 API Key，按常见前缀遮盖
+///
+/// 前缀必须位于单词开头，避免 `task-...`、`disk-...` 之类的普通标识符被误遮盖
+fn redact_text(text: &str) -> String {
+    const KEY_PREFIXES: &[&str] = &["sk-ant-", "sk-", "AIza"];
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((pos, prefix)) = KEY_PREFIXES
+        .iter()
+        .filter_map(|p| rest.find(p).map(|pos| (pos, *p)))
+        .min_by_key(|(pos, p)| (*pos, std::cmp::Reverse(p.len())))
+    {
+        let after = &rest[pos + prefix.len()..];
+        let word_start = !rest[..pos]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !word_start {
+            result.push_str(&rest[..pos + prefix.len()]);
+            rest = after;
+            continue;
+        }
+        let key_len = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(after.len());
+        result.push_str(&rest[..pos]);
+        if key_len >= 16 {
+            result.push_str(prefix);
+            result.push_str(REDACTED);
+        } else {
+            result.push_str(&rest[pos..pos + prefix.len() + key_len]);
+        }
+        rest = &after[key_len..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// 按字节上限截断文本（不切断多字节字符），返回是否截断
+fn truncate_text(mut text: String, max_bytes: usize) -> (String, bool) {
+    if text.len() <= max_bytes {
+        return (text, false);
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    (text, true)
+}
+
+/// 截断请求体，返回是否截断
+///
+/// 先截断过长的字符串字段以保留 JSON 结构；整体仍超过上限时改存截断后的 JSON 文本
+fn truncate_request(request: &mut serde_json::Value) -> bool {
+    let mut truncated = truncate_strings(request);
+    let json = serde_json::to_string(request).unwrap_or_default();
+    if json.len() > MAX_BODY_BYTES {
+        let (text, _) = truncate_text(json, MAX_BODY_BYTES);
+        *request = serde_json::Value::String(text);
+        truncated = true;
+    }
+    truncated
+}
+
+fn truncate_strings(value: &mut serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::String(text) if text.len() > MAX_FIELD_BYTES => {
+            let original = text.len();
+            let (mut kept, _) = truncate_text(std::mem::take(text), MAX_FIELD_BYTES);
+            kept.push_str(&format!("...[truncated {} bytes]", original - kept.len()));
+            *text = kept;
+            true
+        }
+        serde_json::Value::Object(map) => map
+            .values_mut()
+            .fold(false, |acc, v| truncate_strings(v) | acc),
+        serde_json::Value::Array(items) => items
+            .iter_mut()
+            .fold(false, |acc, v| truncate_strings(v) | acc),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn bundle(request_id: &str) -> PostmortemBundle {
+        let ctx = RequestContext::new("gpt-4o".to_string());
+        let mut bundle = build_bundle(
+            &ctx,
+            "/v1/chat/completions",
+            None,
+            serde_json::json!({}),
+            400,
+            &HeaderMap::new(),
+            b"{}",
+        );
+        bundle.request_id = request_id.to_string();
+        bundle
+    }
+
+    #[test]
+    fn test_sanitize_json_and_headers() {
+        let mut value = serde_json::json!({
+            "model": "gpt-4o",
+            "api_key": "secret",
+            "metadata": {"Access_Token": "abc", "user": "u1"},
+            "messages": [{"role": "user", "content": "hi"}]
+        });
+        sanitize_json(&mut value);
+        assert_eq!(value["api_key"], REDACTED);
+        assert_eq!(value["metadata"]["Access_Token"], REDACTED);
+        assert_eq!(value["metadata"]["user"], "u1");
+        assert_eq!(value["messages"][0]["content"], "hi");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", HeaderValue::from_static("sk-1"));
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+        let headers = sanitize_headers(&headers);
+        assert_eq!(headers["x-api-key"], REDACTED);
+        assert_eq!(headers["content-type"], "application/json");
+    }
+
+    #[test]
+    fn test_redact_text() {
+        let text = "Incorrect API key provided: sk-abcdefghijklmnopqrstuvwxyz. See docs.";
+        assert_eq!(
+            redact_text(text),
+            "Incorrect API key provided: sk-[REDACTED]. See docs."
+        );
+        assert_eq!(redact_text("task-id sk-short"), "task-id sk-short");
+        // 前缀不在单词开头时不遮盖
+        assert_eq!(
+            redact_text("id=task-abcdefghijklmnopqrstuvwxyz disk-0123456789abcdefghij"),
+            "id=task-abcdefghijklmnopqrstuvwxyz disk-0123456789abcdefghij"
+        );
+        assert_eq!(
+            redact_text("\"key\":\"sk-ant-REDACTED\""),
+            "\"key\":\"sk-ant-[REDACTED]\""
+        );
+    }
+
+    #[test]
+    fn test_request_truncation() {
+        let ctx = RequestContext::new("gpt-4o".to_string());
+        let image = "A".repeat(MAX_FIELD_BYTES * 4);
+        let request = serde_json::json!({
+            "model": "gpt-4o",
+            "messages": [{"role": "user", "content": [{"type": "image_url", "image_url": {"url": image}}]}]
+        });
+        let bundle = build_bundle(
+            &ctx,
+            "/v1/chat/completions",
+            None,
+            request,
+            400,
+            &HeaderMap::new(),
+            b"{}",
+        );
+        assert!(bundle.request_truncated);
+        assert_eq!(bundle.request["model"], "gpt-4o");
+        let url = bundle.request["messages"][0]["content"][0]["image_url"]["url"]
+            .as_str()
+            .unwrap();
+        assert!(url.starts_with(&image[..MAX_FIELD_BYTES]));
+        assert!(url.ends_with(&format!("...[truncated {} bytes]", MAX_FIELD_BYTES * 3)));
+
+        // 字段都不长但整体过大时保存为截断后的 JSON 文本
+        let messages: Vec<_> = (0..MAX_BODY_BYTES / 16)
+            .map(|_| serde_json::json!({"role": "user", "content": "hello"}))
+            .collect();
+        let request = serde_json::json!({"model": "gpt-4o", "messages": messages});
+        let bundle = build_bundle(
+            &ctx,
+            "/v1/chat/completions",
+            None,
+            request,
+            400,
+            &HeaderMap::new(),
+            b"{}",
+        );
+        assert!(bundle.request_truncated);
+        assert_eq!(bundle.request.as_str().unwrap().len(), MAX_BODY_BYTES);
+
+        let small = build_bundle(
+            &ctx,
+            "/v1/chat/completions",
+            None,
+            serde_json::json!({"model": "gpt-4o"}),
+            400,
+            &HeaderMap::new(),
+            b"{}",
+        );
+        assert!(!small.request_truncated);
+    }
+
+    #[test]
+    fn test_truncate_text_keeps_char_boundary() {
+        let (text, truncated) = truncate_text("你好世界".to_string(), 7);
+        assert!(truncated);
+        assert_eq!(text, "你好");
+        let (text, truncated) = truncate_text("abc".to_string(), 3);
+        assert!(!truncated);
+        assert_eq!(text, "abc");
+    }
+
+    #[test]
+    fn test_route_trace_and_truncation() {
+        let mut ctx = RequestContext::new("fast".to_string());
+        trace(&mut ctx, "alias fast -> gpt-4o-mini");
+        trace(&mut ctx, "credential openai/abcd1234");
+
+        let body = vec![b'x'; MAX_BODY_BYTES + 10];
+        let bundle = build_bundle(
+            &ctx,
+            "/v1/messages",
+            Some("openai".to_string()),
+            serde_json::json!({"model": "gpt-4o-mini"}),
+            400,
+            &HeaderMap::new(),
+            &body,
+        );
+        assert_eq!(bundle.route_trace.len(), 2);
+        assert!(bundle.response_truncated);
+        assert_eq!(bundle.response_body.len(), MAX_BODY_BYTES);
+    }
+
+    #[test]
+    fn test_store_capacity_and_lookup() {
+        let store = PostmortemStore::new(2);
+        store.record(bundle("a"));
+        store.record(bundle("b"));
+        store.record(bundle("c"));
+
+        assert_eq!(store.len(), 2);
+        assert!(store.get("a").is_none());
+        assert_eq!(store.get("c").unwrap().upstream_status, 400);
+        let ids: Vec<String> = store.list(10).into_iter().map(|b| b.request_id).collect();
+        assert_eq!(ids, vec!["c", "b"]);
+    }
+
+    #[test]
+    fn test_bundle_prefers_upstream_request() {
+        let mut ctx = RequestContext::new("claude-sonnet-4".to_string());
+        let client = serde_json::json!({"model": "claude-sonnet-4", "max_tokens": 64000});
+
+        let bundle = build_bundle(
+            &ctx,
+            "/v1/messages",
+            Some("openai".to_string()),
+            client.clone(),
+            400,
+            &HeaderMap::new(),
+            b"{}",
+        );
+        assert!(!bundle.request_is_upstream);
+        assert_eq!(bundle.request["max_tokens"], 64000);
+
+        // 记录了转换、整形后的请求体时保存该请求体
+        let upstream = UpstreamRequest::default();
+        upstream.record(serde_json::json!({
+            "model": "deepseek-chat",
+            "max_tokens": 8192,
+            "api_key": "sk-secret"
+        }));
+        upstream.store(&mut ctx);
+        let bundle = build_bundle(
+            &ctx,
+            "/v1/messages",
+            Some("openai".to_string()),
+            client,
+            400,
+            &HeaderMap::new(),
+            b"{}",
+        );
+        assert!(bundle.request_is_upstream);
+        assert_eq!(bundle.request["model"], "deepseek-chat");
+        assert_eq!(bundle.request["max_tokens"], 8192);
+        assert_eq!(bundle.request["api_key"], REDACTED);
+    }
+}
@@ -0,0 +1,368 @@
+//! 响应缓冲内存上限服务
+//!
+//! 后置步骤（异常检测、诊断包、Flow 捕获）需要读取完整的非流式响应。
+//! 单个响应超过 `per_request_mb`，或所有请求缓冲在内存中的总量达到 `global_mb` 时，
+//! 正文改为写入临时文件（落盘），转发给客户端时再从文件读出，文件随响应正文一起删除。
+//!
+//! 落盘的响应只在内存中保留开头一段（[`HEAD_BYTES`]），需要解析完整内容的后置步骤跳过它们。
+
+use std::io::SeekFrom;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use axum::body::Body;
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use parking_lot::RwLock;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+use crate::config::ResponseBufferConfig;
+
+/// 落盘的响应在内存中保留的开头字节数（用于诊断包等预览）
+pub const HEAD_BYTES: usize = 64 * 1024;
+
+const MB: usize = 1024 * 1024;
+
+/// 从临时文件读出时每块的字节数
+const READ_CHUNK_BYTES: usize = 64 * 1024;
+
+/// 全局内存预算
+static BUDGET: once_cell::sync::Lazy<Arc<BufferBudget>> = once_cell::sync::Lazy::new(|| {
+    Arc::new(BufferBudget::new(
+        ResponseBufferConfig::default().global_mb * MB,
+    ))
+});
+
+/// 当前的缓冲配置
+static SETTINGS: once_cell::sync::Lazy<RwLock<ResponseBufferConfig>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(ResponseBufferConfig::default()));
+
+/// 应用配置（服务启动和配置热重载时调用）
+pub fn configure(config: &ResponseBufferConfig) {
+    BUDGET.set_limit(config.global_mb * MB);
+    *SETTINGS.write() = config.clone();
+}
+
+/// 按当前配置缓冲响应正文
+pub async fn buffer(body: Body) -> Result<BufferedBody, String> {
+    let per_request_limit = SETTINGS.read().per_request_mb * MB;
+    buffer_with(body, per_request_limit, &BUDGET).await
+}
+
+/// 所有请求共享的缓冲内存预算
+pub struct BufferBudget {
+    limit: AtomicUsize,
+    used: AtomicUsize,
+}
+
+impl BufferBudget {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit: AtomicUsize::new(limit),
+            used: AtomicUsize::new(0),
+        }
+    }
+
+    /// 更新上限，已占用的内存不受影响
+    pub fn set_limit(&self, limit: usize) {
+        self.limit.store(limit, Ordering::Relaxed);
+    }
+
+    /// 当前缓冲在内存中的字节数
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    fn try_reserve(&self, bytes: usize) -> bool {
+        let limit = self.limit.load(Ordering::Relaxed);
+        self.used
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |used| {
+                used.checked_add(bytes).filter(|total| *total <= limit)
+            })
+            .is_ok()
+    }
+
+    fn release(&self, bytes: usize) {
+        self.used.fetch_sub(bytes, Ordering::AcqRel);
+    }
+}
+
+/// 占用的预算，丢弃时归还
+#[derive(Default)]
+struct Reservation {
+    budget: Option<Arc<BufferBudget>>,
+    bytes: usize,
+}
+
+impl Reservation {
+    fn new(budget: &Arc<BufferBudget>) -> Self {
+        Self {
+            budget: Some(budget.clone()),
+            bytes: 0,
+        }
+    }
+
+    fn grow(&mut self, bytes: usize) -> bool {
+        let reserved = self
+            .budget
+            .as_ref()
+            .is_none_or(|budget| budget.try_reserve(bytes));
+        if reserved {
+            self.bytes += bytes;
+        }
+        reserved
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if let Some(budget) = &self.budget {
+            budget.release(self.bytes);
+        }
+    }
+}
+
+enum Storage {
+    /// 完整保存在内存中
+    Memory {
+        bytes: Bytes,
+        _reservation: Reservation,
+    },
+    /// 已写入临时文件
+    Spilled {
+        file: tempfile::NamedTempFile,
+        reader: tokio::fs::File,
+        head: Bytes,
+        len: u64,
+    },
+}
+
+/// 缓冲后的响应正文
+pub struct BufferedBody {
+    storage: Storage,
+}
+
+impl Default for BufferedBody {
+    fn default() -> Self {
+        Self {
+            storage: Storage::Memory {
+                bytes: Bytes::new(),
+                _reservation: Reservation::default(),
+            },
+        }
+    }
+}
+
+impl BufferedBody {
+    /// 完整的正文，已落盘时为 None
+    pub fn bytes(&self) -> Option<&Bytes> {
+        match &self.storage {
+            Storage::Memory { bytes, .. } => Some(bytes),
+            Storage::Spilled { .. } => None,
+        }
+    }
+
+    /// 正文开头，未落盘时为完整正文
+    pub fn head(&self) -> &[u8] {
+        match &self.storage {
+            Storage::Memory { bytes, .. } => bytes,
+            Storage::Spilled { head, .. } => head,
+        }
+    }
+
+    /// 正文总长度
+    pub fn len(&self) -> u64 {
+        match &self.storage {
+            Storage::Memory { bytes, .. } => bytes.len() as u64,
+            Storage::Spilled { len, .. } => *len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 是否已落盘
+    pub fn is_spilled(&self) -> bool {
+        matches!(self.storage, Storage::Spilled { .. })
+    }
+
+    /// 转换为响应正文
+    ///
+    /// 内存中的正文交给响应后不再计入预算；落盘的正文从文件读出，读完或客户端断开时删除文件
+    pub fn into_body(self) -> Body {
+        match self.storage {
+            Storage::Memory { bytes, .. } => Body::from(bytes),
+            Storage::Spilled { file, reader, .. } => {
+                let stream = futures::stream::unfold(Some((reader, file)), |state| async move {
+                    let (mut reader, file) = state?;
+                    let mut chunk = BytesMut::zeroed(READ_CHUNK_BYTES);
+                    match reader.read(&mut chunk).await {
+                        Ok(0) => None,
+                        Ok(n) => {
+                            chunk.truncate(n);
+                            Some((Ok(chunk.freeze()), Some((reader, file))))
+                        }
+                        Err(e) => Some((Err(e), None)),
+                    }
+                });
+                Body::from_stream(stream)
+            }
+        }
+    }
+}
+
+/// 缓冲响应正文，超过单个响应上限或全局预算不足时落盘
+pub async fn buffer_with(
+    body: Body,
+    per_request_limit: usize,
+    budget: &Arc<BufferBudget>,
+) -> Result<BufferedBody, String> {
+    let mut stream = body.into_data_stream();
+    let mut buffered = BytesMut::new();
+    let mut reservation = Reservation::new(budget);
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("读取响应体失败: {}", e))?;
+        if buffered.len() + chunk.len() <= per_request_limit && reservation.grow(chunk.len()) {
+            buffered.extend_from_slice(&chunk);
+            continue;
+        }
+
+        // 落盘：已缓冲的部分和剩余的正文依次写入临时文件
+        let file = tempfile::Builder::new()
+            .prefix("proxycast-response-")
+            .tempfile()
+            .map_err(|e| format!("创建临时文件失败: {}", e))?;
+        let mut writer = tokio::fs::File::from_std(
+            file.reopen()
+                .map_err(|e| format!("打开临时文件失败: {}", e))?,
+        );
+        let mut head = BytesMut::new();
+        let mut len = 0u64;
+        let mut pending = Some(buffered.split().freeze());
+        let mut next = Some(chunk);
+        drop(reservation);
+
+        loop {
+            let data = match pending.take().or_else(|| next.take()) {
+                Some(data) => data,
+                None => match stream.next().await {
+                    Some(chunk) => chunk.map_err(|e| format!("读取响应体失败: {}", e))?,
+                    None => break,
+                },
+            };
+            let remaining = HEAD_BYTES.saturating_sub(head.len());
+            head.extend_from_slice(&data[..remaining.min(data.len())]);
+            len += data.len() as u64;
+            writer
+                .write_all(&data)
+                .await
+                .map_err(|e| format!("写入临时文件失败: {}", e))?;
+        }
+        writer
+            .flush()
+            .await
+            .map_err(|e| format!("写入临时文件失败: {}", e))?;
+        writer
+            .seek(SeekFrom::Start(0))
+            .await
+            .map_err(|e| format!("读取临时文件失败: {}", e))?;
+
+        tracing::info!(
+            "[BUFFER] 响应体 {} 字节超过内存缓冲上限，已写入临时文件",
+            len
+        );
+        return Ok(BufferedBody {
+            storage: Storage::Spilled {
+                file,
+                reader: writer,
+                head: head.freeze(),
+                len,
+            },
+        });
+    }
+
+    Ok(BufferedBody {
+        storage: Storage::Memory {
+            bytes: buffered.freeze(),
+            _reservation: reservation,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunked(chunks: &[&'static str]) -> Body {
+        let chunks: Vec<Result<&'static str, std::convert::Infallible>> =
+            chunks.iter().map(|c| Ok(*c)).collect();
+        Body::from_stream(futures::stream::iter(chunks))
+    }
+
+    async fn read(body: Body) -> String {
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_small_body_stays_in_memory() {
+        let budget = Arc::new(BufferBudget::new(1024));
+        let buffered = buffer_with(chunked(&["{\"a\":", "1}"]), 1024, &budget)
+            .await
+            .unwrap();
+        assert!(!buffered.is_spilled());
+        assert_eq!(buffered.bytes().unwrap().as_ref(), b"{\"a\":1}");
+        assert_eq!(budget.used(), 7);
+
+        assert_eq!(read(buffered.into_body()).await, "{\"a\":1}");
+        assert_eq!(budget.used(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_spills_to_file() {
+        let budget = Arc::new(BufferBudget::new(1024));
+        let buffered = buffer_with(chunked(&["abcd", "efgh", "ij"]), 6, &budget)
+            .await
+            .unwrap();
+        assert!(buffered.is_spilled());
+        assert_eq!(buffered.bytes(), None);
+        assert_eq!(buffered.head(), b"abcdefghij");
+        assert_eq!(buffered.len(), 10);
+        // 落盘后不再占用内存预算
+        assert_eq!(budget.used(), 0);
+
+        let path = match &buffered.storage {
+            Storage::Spilled { file, .. } => file.path().to_path_buf(),
+            Storage::Memory { .. } => unreachable!(),
+        };
+        assert!(path.exists());
+        assert_eq!(read(buffered.into_body()).await, "abcdefghij");
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_global_budget_shared_between_requests() {
+        let budget = Arc::new(BufferBudget::new(8));
+        let first = buffer_with(chunked(&["12345"]), 1024, &budget)
+            .await
+            .unwrap();
+        assert!(!first.is_spilled());
+
+        // 预算剩余 3 字节，第二个响应落盘
+        let second = buffer_with(chunked(&["67890"]), 1024, &budget)
+            .await
+            .unwrap();
+        assert!(second.is_spilled());
+        assert_eq!(second.head(), b"67890");
+
+        // 第一个响应归还预算后可以再缓冲在内存中
+        drop(first);
+        let third = buffer_with(chunked(&["abcde"]), 1024, &budget)
+            .await
+            .unwrap();
+        assert!(!third.is_spilled());
+        assert_eq!(budget.used(), 5);
+    }
+}
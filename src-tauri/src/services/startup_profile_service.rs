@@ -0,0 +1,200 @@
+//! 启动耗时分析服务
+//!
+//! 记录各子系统的初始化耗时，供 `get_startup_profile` 命令查看：
+//! - `eager`：启动时初始化，计入窗口可用前的耗时
+//! - `deferred`：首次使用（或收到预热提示）时才初始化，如模型注册表、本地 Whisper 模型
+//!
+//! 时间均相对进程启动（首次访问分析器）计算。
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// 全局启动分析器
+static PROFILER: once_cell::sync::Lazy<StartupProfiler> =
+    once_cell::sync::Lazy::new(StartupProfiler::new);
+
+/// 获取全局启动分析器（应用入口处首先调用，以确定计时起点）
+pub fn profiler() -> &'static StartupProfiler {
+    &PROFILER
+}
+
+/// 初始化时机
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InitPhase {
+    /// 启动时初始化
+    Eager,
+    /// 首次使用时初始化
+    Deferred,
+}
+
+/// 单个子系统的初始化耗时
+#[derive(Debug, Clone, Serialize)]
+pub struct SubsystemTiming {
+    /// 子系统名称
+    pub name: String,
+    pub phase: InitPhase,
+    /// 开始初始化的时间（距进程启动，毫秒）
+    pub started_ms: u64,
+    /// 初始化耗时（毫秒）
+    pub duration_ms: u64,
+}
+
+/// 启动耗时报告
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupProfile {
+    /// 启动流程完成（setup 结束）的时间（距进程启动，毫秒），尚未完成时为空
+    pub ready_ms: Option<u64>,
+    /// 启动时初始化的总耗时（毫秒）
+    pub eager_total_ms: u64,
+    /// 按开始时间排列的子系统耗时
+    pub subsystems: Vec<SubsystemTiming>,
+}
+
+/// 启动分析器
+pub struct StartupProfiler {
+    origin: Instant,
+    ready: Mutex<Option<Duration>>,
+    timings: Mutex<Vec<SubsystemTiming>>,
+}
+
+impl Default for StartupProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StartupProfiler {
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            ready: Mutex::new(None),
+            timings: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 记录一次初始化
+    pub fn record(&self, name: &str, phase: InitPhase, started: Instant, elapsed: Duration) {
+        let timing = SubsystemTiming {
+            name: name.to_string(),
+            phase,
+            started_ms: started.saturating_duration_since(self.origin).as_millis() as u64,
+            duration_ms: elapsed.as_millis() as u64,
+        };
+        match phase {
+            InitPhase::Eager => {
+                tracing::debug!("[启动] {} 初始化耗时 {}ms", name, timing.duration_ms)
+            }
+            InitPhase::Deferred => {
+                tracing::info!(
+                    "[启动] {} 首次使用时初始化，耗时 {}ms",
+                    name,
+                    timing.duration_ms
+                )
+            }
+        }
+        self.timings.lock().push(timing);
+    }
+
+    /// 执行并记录同步初始化
+    pub fn measure<T>(&self, name: &str, phase: InitPhase, init: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let value = init();
+        self.record(name, phase, started, started.elapsed());
+        value
+    }
+
+    /// 执行并记录异步初始化
+    pub async fn measure_async<T>(
+        &self,
+        name: &str,
+        phase: InitPhase,
+        init: impl Future<Output = T>,
+    ) -> T {
+        let started = Instant::now();
+        let value = init.await;
+        self.record(name, phase, started, started.elapsed());
+        value
+    }
+
+    /// 标记启动流程完成
+    pub fn mark_ready(&self) {
+        let elapsed = self.origin.elapsed();
+        *self.ready.lock() = Some(elapsed);
+        tracing::info!("[启动] 启动流程完成，耗时 {}ms", elapsed.as_millis());
+    }
+
+    /// 当前的启动耗时报告
+    pub fn profile(&self) -> StartupProfile {
+        let mut subsystems = self.timings.lock().clone();
+        subsystems.sort_by_key(|t| t.started_ms);
+        StartupProfile {
+            ready_ms: self.ready.lock().map(|d| d.as_millis() as u64),
+            eager_total_ms: subsystems
+                .iter()
+                .filter(|t| t.phase == InitPhase::Eager)
+                .map(|t| t.duration_ms)
+                .sum(),
+            subsystems,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_records_timings_in_start_order() {
+        let profiler = StartupProfiler::new();
+        let value = profiler.measure("config", InitPhase::Eager, || {
+            std::thread::sleep(Duration::from_millis(5));
+            42
+        });
+        assert_eq!(value, 42);
+        let origin = profiler.origin;
+        profiler.record(
+            "whisper_model",
+            InitPhase::Deferred,
+            origin + Duration::from_millis(50),
+            Duration::from_millis(300),
+        );
+        profiler.record(
+            "database",
+            InitPhase::Eager,
+            origin + Duration::from_millis(10),
+            Duration::from_millis(2),
+        );
+
+        let profile = profiler.profile();
+        let names: Vec<_> = profile.subsystems.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["config", "database", "whisper_model"]);
+        assert_eq!(profile.subsystems[1].started_ms, 10);
+        assert!(profile.subsystems[0].duration_ms >= 5);
+        // 延迟初始化不计入启动耗时
+        assert_eq!(
+            profile.eager_total_ms,
+            profile.subsystems[0].duration_ms + 2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_measure_async_and_ready() {
+        let profiler = StartupProfiler::new();
+        assert!(profiler.profile().ready_ms.is_none());
+
+        let value = profiler
+            .measure_async("model_registry", InitPhase::Deferred, async { "ok" })
+            .await;
+        assert_eq!(value, "ok");
+        profiler.mark_ready();
+
+        let profile = profiler.profile();
+        assert!(profile.ready_ms.is_some());
+        assert_eq!(profile.subsystems[0].phase, InitPhase::Deferred);
+        assert_eq!(profile.eager_total_ms, 0);
+    }
+}
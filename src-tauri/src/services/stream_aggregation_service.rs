@@ -0,0 +1,66 @@
+//! 流式响应合并服务
+//!
+//! 保存 `stream_aggregation` 配置中按路由的合并规则，供
+//! [`StreamAggregationLayer`](crate::middleware::StreamAggregationLayer) 按请求路径查找。
+
+use std::time::Duration;
+
+use parking_lot::RwLock;
+
+use crate::config::{StreamAggregationConfig, StreamAggregationRoute};
+
+/// 当前的合并规则
+static ROUTES: once_cell::sync::Lazy<RwLock<Vec<StreamAggregationRoute>>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(Vec::new()));
+
+/// 单个请求使用的合并参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AggregationSettings {
+    /// 时间窗口
+    pub interval: Option<Duration>,
+    /// 触发发送的累积字符数
+    pub max_chars: Option<usize>,
+}
+
+/// 应用配置（服务启动和配置热重载时调用）
+pub fn configure(config: &StreamAggregationConfig) {
+    *ROUTES.write() = config.routes.clone();
+}
+
+/// 查找请求路径的合并参数，没有匹配的规则或规则未开启任何条件时返回 `None`
+pub fn settings_for(path: &str) -> Option<AggregationSettings> {
+    let routes = ROUTES.read();
+    let route = routes.iter().find(|r| path_matches(&r.path, path))?;
+    let settings = AggregationSettings {
+        interval: (route.interval_ms > 0).then(|| Duration::from_millis(route.interval_ms)),
+        max_chars: (route.max_chars > 0).then_some(route.max_chars),
+    };
+    (settings.interval.is_some() || settings.max_chars.is_some()).then_some(settings)
+}
+
+/// 路径是否匹配规则，`*` 匹配任意一段
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.trim_end_matches('/').split('/').collect();
+    let path: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    pattern.len() == path.len() && pattern.iter().zip(&path).all(|(p, s)| *p == "*" || p == s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_matches() {
+        assert!(path_matches("/v1/messages", "/v1/messages"));
+        assert!(path_matches("/v1/messages/", "/v1/messages"));
+        assert!(path_matches(
+            "/*/v1/chat/completions",
+            "/kiro-main/v1/chat/completions"
+        ));
+        assert!(!path_matches(
+            "/*/v1/chat/completions",
+            "/v1/chat/completions"
+        ));
+        assert!(!path_matches("/v1/messages", "/v1/chat/completions"));
+    }
+}
@@ -0,0 +1,415 @@
+//! Token 计数服务
+//!
+//! 在进程内按模型族选择分词器计数，用于：
+//! - 请求转发前的上下文溢出检测
+//! - 预算预检查（记录到请求上下文）
+//! - 前端发送前展示提示词大小
+//!
+//! OpenAI 模型使用对应的 tiktoken 编码；Claude 没有公开的本地分词器，
+//! 使用 cl100k_base 计数后乘以经验系数；其他模型按 cl100k_base 近似。
+//!
+//! 计数只是估算，略超上下文长度时只记录警告、照常转发，明显超出时才拒绝。
+//! 模型上下文长度按模型缓存，请求路径上不必每次占用数据库锁。
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::database::DbConnection;
+use crate::models::anthropic::AnthropicMessagesRequest;
+use crate::models::openai::{ChatCompletionRequest, ContentPart, MessageContent};
+use crate::telemetry::TokenEstimator;
+
+/// 请求上下文中记录预估输入 Token 数的元数据键
+pub const ESTIMATED_INPUT_TOKENS_METADATA: &str = "estimated_input_tokens";
+
+/// 每条消息的格式化开销
+const TOKENS_PER_MESSAGE: u32 = 4;
+
+/// 回复前缀开销
+const TOKENS_PER_REPLY: u32 = 3;
+
+/// 单张图片的估算 Token 数（无法得知尺寸时按 1024x1024 高清图估算）
+const TOKENS_PER_IMAGE: u32 = 765;
+
+/// Claude 相对 cl100k_base 的经验系数（百分比）
+const CLAUDE_TOKEN_RATIO_PERCENT: u32 = 115;
+
+/// 精确计数超出上下文长度超过此比例时才拒绝
+const EXACT_REJECT_MARGIN_PERCENT: u64 = 10;
+
+/// 估算计数（Claude 等）超出上下文长度超过此比例时才拒绝
+const APPROX_REJECT_MARGIN_PERCENT: u64 = 25;
+
+/// 上下文长度缓存的有效期
+const CONTEXT_LENGTH_TTL: Duration = Duration::from_secs(300);
+
+/// 最多缓存的模型数，超出时清空（请求中的模型名由客户端决定）
+const MAX_CACHED_MODELS: usize = 1024;
+
+/// 缓存的上下文长度和读取时间
+type CachedContextLength = (Option<u32>, Instant);
+
+/// 按模型缓存的上下文长度
+static CONTEXT_LENGTHS: Lazy<Mutex<HashMap<String, CachedContextLength>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static ESTIMATOR: Lazy<Option<TokenEstimator>> = Lazy::new(|| match TokenEstimator::new() {
+    Ok(estimator) => Some(estimator),
+    Err(e) => {
+        tracing::warn!("[TOKEN_COUNT] {}", e);
+        None
+    }
+});
+
+/// 分词器类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenizerKind {
+    /// GPT-4o / GPT-4.1 / GPT-5 / o 系列
+    O200kBase,
+    /// GPT-4 / GPT-3.5，以及其他模型的近似
+    Cl100kBase,
+    /// Claude（cl100k_base 乘以经验系数）
+    ClaudeApprox,
+}
+
+impl TokenizerKind {
+    /// 根据模型名称选择分词器
+    pub fn for_model(model: &str) -> Self {
+        if model.to_ascii_lowercase().contains("claude") {
+            TokenizerKind::ClaudeApprox
+        } else if TokenEstimator::encoding_name(Some(model)) == "o200k_base" {
+            TokenizerKind::O200kBase
+        } else {
+            TokenizerKind::Cl100kBase
+        }
+    }
+
+    /// 计数结果是否为精确值
+    pub fn is_exact(&self) -> bool {
+        !matches!(self, TokenizerKind::ClaudeApprox)
+    }
+}
+
+/// Token 计数结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenCount {
+    pub model: String,
+    pub tokenizer: TokenizerKind,
+    pub input_tokens: u32,
+    /// 是否为精确值（Claude 等模型为估算）
+    pub exact: bool,
+    /// 模型上下文长度（模型注册表中有记录时）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_length: Option<u32>,
+    /// 输入是否超出上下文长度
+    pub exceeds_context: bool,
+}
+
+impl TokenCount {
+    pub fn new(model: &str, input_tokens: u32, context_length: Option<u32>) -> Self {
+        let tokenizer = TokenizerKind::for_model(model);
+        Self {
+            model: model.to_string(),
+            tokenizer,
+            input_tokens,
+            exact: tokenizer.is_exact(),
+            context_length,
+            exceeds_context: context_length.is_some_and(|limit| input_tokens > limit),
+        }
+    }
+}
+
+/// 计算文本的 Token 数
+pub fn count_text(text: &str, model: &str) -> u32 {
+    if text.is_empty() {
+        return 0;
+    }
+    let raw = match ESTIMATOR.as_ref() {
+        Some(estimator) => estimator.estimate(text, Some(model)),
+        // 分词器不可用时按 4 字符 1 Token 粗略估算
+        None => (text.chars().count() as u32).div_ceil(4),
+    };
+    match TokenizerKind::for_model(model) {
+        TokenizerKind::ClaudeApprox => raw * CLAUDE_TOKEN_RATIO_PERCENT / 100,
+        _ => raw,
+    }
+}
+
+/// 计算 OpenAI Chat Completions 请求的输入 Token 数
+pub fn count_openai_request(request: &ChatCompletionRequest) -> u32 {
+    let model = request.model.as_str();
+    let mut total = TOKENS_PER_REPLY;
+
+    for msg in &request.messages {
+        total += TOKENS_PER_MESSAGE + count_text(&msg.role, model);
+        match &msg.content {
+            Some(MessageContent::Text(text)) => total += count_text(text, model),
+            Some(MessageContent::Parts(parts)) => {
+                for part in parts {
+                    total += match part {
+                        ContentPart::Text { text } => count_text(text, model),
+                        ContentPart::ImageUrl { .. } => TOKENS_PER_IMAGE,
+                    };
+                }
+            }
+            None => {}
+        }
+        if let Some(tool_calls) = &msg.tool_calls {
+            for call in tool_calls {
+                total += count_text(&call.function.name, model);
+                total += count_text(&call.function.arguments, model);
+            }
+        }
+        if let Some(reasoning) = &msg.reasoning_content {
+            total += count_text(reasoning, model);
+        }
+    }
+
+    if let Some(tools) = &request.tools {
+        total += count_json(&serde_json::to_value(tools).unwrap_or_default(), model);
+    }
+
+    total
+}
+
+/// 计算 Anthropic Messages 请求的输入 Token 数
+pub fn count_anthropic_request(request: &AnthropicMessagesRequest) -> u32 {
+    let model = request.model.as_str();
+    let mut total = TOKENS_PER_REPLY;
+
+    if let Some(system) = &request.system {
+        total += count_anthropic_content(system, model);
+    }
+    for msg in &request.messages {
+        total += TOKENS_PER_MESSAGE + count_anthropic_content(&msg.content, model);
+    }
+    if let Some(tools) = &request.tools {
+        total += count_json(&serde_json::to_value(tools).unwrap_or_default(), model);
+    }
+
+    total
+}
+
+/// 计算 Anthropic content（字符串或内容块数组）的 Token 数
+fn count_anthropic_content(content: &serde_json::Value, model: &str) -> u32 {
+    match content {
+        serde_json::Value::String(text) => count_text(text, model),
+        serde_json::Value::Array(blocks) => blocks
+            .iter()
+            .map(|block| match block.get("type").and_then(|t| t.as_str()) {
+                Some("text") => count_text(
+                    block.get("text").and_then(|t| t.as_str()).unwrap_or(""),
+                    model,
+                ),
+                Some("thinking") => count_text(
+                    block.get("thinking").and_then(|t| t.as_str()).unwrap_or(""),
+                    model,
+                ),
+                Some("image") => TOKENS_PER_IMAGE,
+                Some("tool_use") => count_json(
+                    block.get("input").unwrap_or(&serde_json::Value::Null),
+                    model,
+                ),
+                Some("tool_result") => count_anthropic_content(
+                    block.get("content").unwrap_or(&serde_json::Value::Null),
+                    model,
+                ),
+                _ => 0,
+            })
+            .sum(),
+        serde_json::Value::Null => 0,
+        other => count_json(other, model),
+    }
+}
+
+fn count_json(value: &serde_json::Value, model: &str) -> u32 {
+    if value.is_null() {
+        return 0;
+    }
+    count_text(&value.to_string(), model)
+}
+
+/// 从模型注册表读取模型上下文长度
+pub fn context_length(conn: &Connection, model: &str) -> Option<u32> {
+    let limits: Option<String> = conn
+        .query_row(
+            "SELECT limits FROM model_registry WHERE id = ?1 LIMIT 1",
+            [model],
+            |row| row.get(0),
+        )
+        .optional()
+        .ok()
+        .flatten();
+    let limits: serde_json::Value = serde_json::from_str(&limits?).ok()?;
+    limits
+        .get("context_length")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u32)
+}
+
+/// 按模型计数并附带上下文长度（上下文长度优先从缓存读取）
+pub fn with_context(db: Option<&DbConnection>, model: &str, input_tokens: u32) -> TokenCount {
+    TokenCount::new(model, input_tokens, cached_context_length(db, model))
+}
+
+fn cached_context_length(db: Option<&DbConnection>, model: &str) -> Option<u32> {
+    let now = Instant::now();
+    if let Some((length, at)) = CONTEXT_LENGTHS.lock().get(model) {
+        if now.duration_since(*at) < CONTEXT_LENGTH_TTL {
+            return *length;
+        }
+    }
+
+    let length = db
+        .and_then(|db| db.lock().ok())
+        .and_then(|conn| context_length(&conn, model));
+    let mut cache = CONTEXT_LENGTHS.lock();
+    if cache.len() >= MAX_CACHED_MODELS {
+        cache.clear();
+    }
+    cache.insert(model.to_string(), (length, now));
+    length
+}
+
+/// 清空上下文长度缓存（模型注册表更新后调用）
+pub fn invalidate_context_lengths() {
+    CONTEXT_LENGTHS.lock().clear();
+}
+
+/// 上下文溢出检测，明显超出时返回错误信息
+///
+/// 计数是估算值，超出上下文长度不到容差（精确计数 10%，估算计数 25%）时不拒绝，
+/// 由调用方根据 [`TokenCount::exceeds_context`] 记录警告
+pub fn check_context(count: &TokenCount) -> Result<(), String> {
+    let Some(limit) = count.context_length else {
+        return Ok(());
+    };
+    let margin = if count.exact {
+        EXACT_REJECT_MARGIN_PERCENT
+    } else {
+        APPROX_REJECT_MARGIN_PERCENT
+    };
+    if u64::from(count.input_tokens) * 100 > u64::from(limit) * (100 + margin) {
+        return Err(format!(
+            "输入约 {} tokens，超出模型 {} 的上下文长度 {}",
+            count.input_tokens, count.model, limit
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::openai::ChatMessage;
+
+    fn message(role: &str, text: &str) -> ChatMessage {
+        ChatMessage {
+            role: role.to_string(),
+            content: Some(MessageContent::Text(text.to_string())),
+            tool_calls: None,
+            tool_call_id: None,
+            reasoning_content: None,
+        }
+    }
+
+    #[test]
+    fn test_tokenizer_for_model() {
+        assert_eq!(TokenizerKind::for_model("gpt-4o"), TokenizerKind::O200kBase);
+        assert_eq!(
+            TokenizerKind::for_model("claude-sonnet-4-5"),
+            TokenizerKind::ClaudeApprox
+        );
+        assert_eq!(
+            TokenizerKind::for_model("deepseek-chat"),
+            TokenizerKind::Cl100kBase
+        );
+    }
+
+    #[test]
+    fn test_claude_count_is_scaled() {
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(20);
+        let gpt = count_text(&text, "gpt-4");
+        let claude = count_text(&text, "claude-sonnet-4-5");
+        assert!(claude > gpt);
+        assert_eq!(claude, gpt * CLAUDE_TOKEN_RATIO_PERCENT / 100);
+    }
+
+    #[test]
+    fn test_count_openai_request() {
+        let request = ChatCompletionRequest {
+            model: "gpt-4o".to_string(),
+            messages: vec![message("system", "You are helpful."), message("user", "Hi")],
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            stream: false,
+            tools: None,
+            tool_choice: None,
+            reasoning_effort: None,
+        };
+        let total = count_openai_request(&request);
+        assert!(total > TOKENS_PER_REPLY + 2 * TOKENS_PER_MESSAGE);
+        assert!(total < 30);
+    }
+
+    #[test]
+    fn test_check_context() {
+        assert!(check_context(&TokenCount::new("gpt-4o", 100, Some(50))).is_err());
+        assert!(check_context(&TokenCount::new("gpt-4o", 100, Some(200))).is_ok());
+        assert!(check_context(&TokenCount::new("gpt-4o", 100, None)).is_ok());
+    }
+
+    #[test]
+    fn test_check_context_margin() {
+        // 精确计数：超出 10% 以内只标记超出，不拒绝
+        let slightly_over = TokenCount::new("gpt-4o", 1050, Some(1000));
+        assert!(slightly_over.exceeds_context);
+        assert!(check_context(&slightly_over).is_ok());
+        assert!(check_context(&TokenCount::new("gpt-4o", 1100, Some(1000))).is_ok());
+        assert!(check_context(&TokenCount::new("gpt-4o", 1101, Some(1000))).is_err());
+
+        // 估算计数的容差更大
+        assert!(check_context(&TokenCount::new("claude-sonnet-4-5", 1200, Some(1000))).is_ok());
+        assert!(check_context(&TokenCount::new("claude-sonnet-4-5", 1300, Some(1000))).is_err());
+    }
+
+    #[test]
+    fn test_context_length_is_cached() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE model_registry (id TEXT PRIMARY KEY, limits TEXT NOT NULL)",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO model_registry (id, limits) VALUES ('cache-test-model', '{\"context_length\": 1000}')",
+            [],
+        )
+        .unwrap();
+        let db: DbConnection = std::sync::Arc::new(std::sync::Mutex::new(conn));
+
+        let count = with_context(Some(&db), "cache-test-model", 10);
+        assert_eq!(count.context_length, Some(1000));
+
+        // 注册表更新后缓存仍有效，清空缓存后读到新值
+        db.lock()
+            .unwrap()
+            .execute(
+                "UPDATE model_registry SET limits = '{\"context_length\": 2000}'",
+                [],
+            )
+            .unwrap();
+        let count = with_context(Some(&db), "cache-test-model", 10);
+        assert_eq!(count.context_length, Some(1000));
+        invalidate_context_lengths();
+        let count = with_context(Some(&db), "cache-test-model", 10);
+        assert_eq!(count.context_length, Some(2000));
+    }
+}
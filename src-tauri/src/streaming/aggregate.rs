@@ -0,0 +1,410 @@
+//! 流式响应合并（Aggregate）
+//!
+//! 部分客户端 UI 处理大量细碎的 SSE chunk 时会卡顿。合并模式按时间窗口或累积字符数
+//! 把 SSE 事件攒成一批再转发，批内相邻的文本增量合并为一个事件：
+//! - OpenAI：只含 `content`（和 `role`）的 `chat.completion.chunk`，按 choice 序号合并
+//! - Anthropic：`content_block_delta` 的 `text_delta` / `thinking_delta` / `input_json_delta`，
+//!   按内容块序号和增量类型合并
+//!
+//! 其他事件（开始、结束、工具调用、用量、`[DONE]`）原样转发，并且不会越过它们合并。
+//! 流结束或出错时立即发送已攒的内容。
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use serde_json::Value;
+use tokio::time::Instant;
+
+/// 合并流式响应中的 SSE 事件
+///
+/// - `interval`: 批的时间窗口，从批内第一个事件到达时开始计时；`None` 表示不按时间发送
+/// - `max_chars`: 批内累积的字符数达到该值时立即发送；`None` 表示不按字符数发送
+pub fn aggregate_stream<S, E>(
+    stream: S,
+    interval: Option<Duration>,
+    max_chars: Option<usize>,
+) -> impl Stream<Item = Result<Bytes, E>> + Send
+where
+    S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+    E: Send + 'static,
+{
+    async_stream::stream! {
+        let mut stream = Box::pin(stream);
+        let mut batch = SseBatch::default();
+        let mut deadline: Option<Instant> = None;
+
+        loop {
+            let next = match deadline {
+                Some(at) => match tokio::time::timeout_at(at, stream.next()).await {
+                    Ok(next) => next,
+                    Err(_) => {
+                        deadline = None;
+                        if let Some(bytes) = batch.flush() {
+                            yield Ok(bytes);
+                        }
+                        continue;
+                    }
+                },
+                None => stream.next().await,
+            };
+
+            match next {
+                Some(Ok(chunk)) => {
+                    batch.push(&chunk);
+                    if max_chars.is_some_and(|max| batch.chars >= max) {
+                        deadline = None;
+                        if let Some(bytes) = batch.flush() {
+                            yield Ok(bytes);
+                        }
+                    } else if deadline.is_none() && !batch.events.is_empty() {
+                        deadline = interval.map(|interval| Instant::now() + interval);
+                    }
+                }
+                Some(Err(e)) => {
+                    if let Some(bytes) = batch.finish() {
+                        yield Ok(bytes);
+                    }
+                    yield Err(e);
+                    return;
+                }
+                None => {
+                    if let Some(bytes) = batch.finish() {
+                        yield Ok(bytes);
+                    }
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// 待发送的一批事件
+#[derive(Default)]
+struct SseBatch {
+    /// 还没收完的事件
+    partial: Vec<u8>,
+    /// 已收完的事件
+    events: Vec<SseEvent>,
+    /// 批内累积的字符数（文本增量按文本计，其他事件按原始长度计）
+    chars: usize,
+}
+
+impl SseBatch {
+    /// 追加收到的 chunk，拆出其中完整的事件
+    fn push(&mut self, chunk: &[u8]) {
+        self.partial.extend_from_slice(chunk);
+        while let Some((end, next)) = event_end(&self.partial) {
+            let raw: Vec<u8> = self.partial.drain(..next).collect();
+            let event = SseEvent::parse(&raw[..end]).unwrap_or(SseEvent::Raw(raw));
+            self.add(event);
+        }
+    }
+
+    fn add(&mut self, event: SseEvent) {
+        match event {
+            SseEvent::Delta(delta) => {
+                self.chars += delta.text.chars().count();
+                if let Some(SseEvent::Delta(last)) = self.events.last_mut() {
+                    if last.kind == delta.kind {
+                        last.text.push_str(&delta.text);
+                        return;
+                    }
+                }
+                self.events.push(SseEvent::Delta(delta));
+            }
+            SseEvent::Raw(raw) => {
+                self.chars += raw.len();
+                self.events.push(SseEvent::Raw(raw));
+            }
+        }
+    }
+
+    /// 取出已收完的事件
+    fn flush(&mut self) -> Option<Bytes> {
+        self.chars = 0;
+        if self.events.is_empty() {
+            return None;
+        }
+        let mut out = Vec::new();
+        for event in self.events.drain(..) {
+            event.write(&mut out);
+        }
+        Some(Bytes::from(out))
+    }
+
+    /// 流结束时取出全部内容，包括没有以空行结尾的部分
+    fn finish(&mut self) -> Option<Bytes> {
+        let mut out = self.flush().map(|b| b.to_vec()).unwrap_or_default();
+        out.append(&mut self.partial);
+        (!out.is_empty()).then(|| Bytes::from(out))
+    }
+}
+
+/// 查找第一个事件的结束位置，返回（事件正文结束位置，下一个事件开始位置）
+fn event_end(buf: &[u8]) -> Option<(usize, usize)> {
+    let lf = buf.windows(2).position(|w| w == b"\n\n");
+    let crlf = buf.windows(4).position(|w| w == b"\r\n\r\n");
+    match (lf, crlf) {
+        (Some(lf), Some(crlf)) if crlf < lf => Some((crlf, crlf + 4)),
+        (Some(lf), _) => Some((lf, lf + 2)),
+        (None, Some(crlf)) => Some((crlf, crlf + 4)),
+        (None, None) => None,
+    }
+}
+
+/// 一个 SSE 事件
+enum SseEvent {
+    /// 可合并的文本增量
+    Delta(DeltaEvent),
+    /// 其他事件，按原始字节转发（含结尾空行）
+    Raw(Vec<u8>),
+}
+
+/// 文本增量事件
+struct DeltaEvent {
+    /// `event:` 行的事件名
+    name: Option<String>,
+    /// 第一个事件的 JSON，发送时写回合并后的文本
+    data: Value,
+    kind: DeltaKind,
+    text: String,
+}
+
+/// 增量的归属，相同归属的相邻增量可以合并
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DeltaKind {
+    OpenAi { index: u64, role: Option<String> },
+    Anthropic { index: u64, field: &'static str },
+}
+
+impl SseEvent {
+    /// 解析文本增量事件，其他事件返回 `None`
+    fn parse(raw: &[u8]) -> Option<Self> {
+        let text = std::str::from_utf8(raw).ok()?;
+        let mut name = None;
+        let mut data = None;
+        for line in text.lines() {
+            if let Some(value) = line.strip_prefix("event:") {
+                name = Some(value.trim().to_string());
+            } else if let Some(value) = line.strip_prefix("data:") {
+                // 多行 data 需要拼接，不合并
+                if data.replace(value.trim()).is_some() {
+                    return None;
+                }
+            } else {
+                return None;
+            }
+        }
+        let data: Value = serde_json::from_str(data?).ok()?;
+        let (kind, text) = match name.as_deref() {
+            None => openai_delta(&data)?,
+            Some("content_block_delta") => anthropic_delta(&data)?,
+            Some(_) => return None,
+        };
+        Some(SseEvent::Delta(DeltaEvent {
+            name,
+            data,
+            kind,
+            text,
+        }))
+    }
+
+    fn write(self, out: &mut Vec<u8>) {
+        let mut delta = match self {
+            SseEvent::Raw(raw) => {
+                out.extend_from_slice(&raw);
+                return;
+            }
+            SseEvent::Delta(delta) => delta,
+        };
+        let text = Value::String(delta.text);
+        match &delta.kind {
+            DeltaKind::OpenAi { .. } => delta.data["choices"][0]["delta"]["content"] = text,
+            DeltaKind::Anthropic { field, .. } => delta.data["delta"][*field] = text,
+        }
+        if let Some(name) = &delta.name {
+            out.extend_from_slice(format!("event: {}\n", name).as_bytes());
+        }
+        out.extend_from_slice(format!("data: {}\n\n", delta.data).as_bytes());
+    }
+}
+
+/// 只含文本的 OpenAI 增量
+fn openai_delta(data: &Value) -> Option<(DeltaKind, String)> {
+    if data.get("object")?.as_str()? != "chat.completion.chunk"
+        || !data.get("usage").is_none_or(Value::is_null)
+    {
+        return None;
+    }
+    let choices = data.get("choices")?.as_array()?;
+    let [choice] = choices.as_slice() else {
+        return None;
+    };
+    let choice = choice.as_object()?;
+    let only_content = choice.iter().all(|(key, value)| match key.as_str() {
+        "index" | "delta" => true,
+        "finish_reason" | "logprobs" => value.is_null(),
+        _ => false,
+    });
+    let delta = choice.get("delta")?.as_object()?;
+    if !only_content || !delta.keys().all(|k| k == "content" || k == "role") {
+        return None;
+    }
+    let kind = DeltaKind::OpenAi {
+        index: choice.get("index").and_then(Value::as_u64).unwrap_or(0),
+        role: delta.get("role").and_then(Value::as_str).map(String::from),
+    };
+    Some((kind, delta.get("content")?.as_str()?.to_string()))
+}
+
+/// Anthropic 的文本、思考和工具参数增量
+fn anthropic_delta(data: &Value) -> Option<(DeltaKind, String)> {
+    if data.get("type")?.as_str()? != "content_block_delta" {
+        return None;
+    }
+    let delta = data.get("delta")?;
+    let field = match delta.get("type")?.as_str()? {
+        "text_delta" => "text",
+        "thinking_delta" => "thinking",
+        "input_json_delta" => "partial_json",
+        _ => return None,
+    };
+    let kind = DeltaKind::Anthropic {
+        index: data.get("index")?.as_u64()?,
+        field,
+    };
+    Some((kind, delta.get(field)?.as_str()?.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn openai_chunk(content: &str) -> String {
+        format!(
+            "data: {{\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"choices\":[{{\"index\":0,\"delta\":{{\"content\":\"{}\"}},\"finish_reason\":null}}]}}\n\n",
+            content
+        )
+    }
+
+    fn anthropic_chunk(index: u64, text: &str) -> String {
+        format!(
+            "event: content_block_delta\ndata: {{\"type\":\"content_block_delta\",\"index\":{},\"delta\":{{\"type\":\"text_delta\",\"text\":\"{}\"}}}}\n\n",
+            index, text
+        )
+    }
+
+    async fn collect(chunks: Vec<String>, max_chars: Option<usize>) -> Vec<String> {
+        let stream = futures::stream::iter(
+            chunks
+                .into_iter()
+                .map(|c| Ok::<_, std::io::Error>(Bytes::from(c))),
+        );
+        aggregate_stream(stream, None, max_chars)
+            .map(|item| String::from_utf8(item.unwrap().to_vec()).unwrap())
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_merges_openai_deltas() {
+        let mut chunks: Vec<String> = ["He", "llo", " wor", "ld"]
+            .iter()
+            .map(|c| openai_chunk(c))
+            .collect();
+        chunks.push("data: [DONE]\n\n".to_string());
+
+        let out = collect(chunks, None).await;
+        assert_eq!(out.len(), 1);
+        let events: Vec<&str> = out[0].split("\n\n").filter(|e| !e.is_empty()).collect();
+        assert_eq!(events.len(), 2);
+        let data: Value = serde_json::from_str(events[0].trim_start_matches("data: ")).unwrap();
+        assert_eq!(data["choices"][0]["delta"]["content"], "Hello world");
+        assert_eq!(data["id"], "c1");
+        assert_eq!(events[1], "data: [DONE]");
+    }
+
+    #[tokio::test]
+    async fn test_does_not_merge_across_other_events() {
+        let chunks = vec![
+            anthropic_chunk(0, "a"),
+            anthropic_chunk(0, "b"),
+            "event: content_block_stop\ndata: {\"type\":\"content_block_stop\",\"index\":0}\n\n"
+                .to_string(),
+            anthropic_chunk(1, "c"),
+            anthropic_chunk(1, "d"),
+        ];
+        let out = collect(chunks, None).await.concat();
+        let texts: Vec<String> = out
+            .split("\n\n")
+            .filter_map(|e| e.lines().find_map(|l| l.strip_prefix("data: ")))
+            .filter_map(|d| serde_json::from_str::<Value>(d).ok())
+            .map(|v| v["delta"]["text"].as_str().unwrap_or("-").to_string())
+            .collect();
+        assert_eq!(texts, vec!["ab", "-", "cd"]);
+        assert!(out.starts_with("event: content_block_delta\n"));
+    }
+
+    #[tokio::test]
+    async fn test_flushes_by_chars_and_keeps_split_events() {
+        // 一个事件跨两个 chunk
+        let first = openai_chunk("abc");
+        let (head, tail) = first.split_at(20);
+        let chunks = vec![
+            head.to_string(),
+            tail.to_string(),
+            openai_chunk("def"),
+            openai_chunk("g"),
+        ];
+        let out = collect(chunks, Some(6)).await;
+        assert_eq!(out.len(), 2);
+        assert!(out[0].contains("\"content\":\"abcdef\""));
+        assert!(out[1].contains("\"content\":\"g\""));
+    }
+
+    #[tokio::test]
+    async fn test_flushes_by_interval() {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<Result<Bytes, std::io::Error>>();
+        let stream = tokio_stream_from(rx);
+        let mut aggregated = Box::pin(aggregate_stream(
+            stream,
+            Some(Duration::from_millis(20)),
+            None,
+        ));
+
+        tx.send(Ok(Bytes::from(openai_chunk("a")))).unwrap();
+        tx.send(Ok(Bytes::from(openai_chunk("b")))).unwrap();
+        // 窗口到期时即使流未结束也发送
+        let first = aggregated.next().await.unwrap().unwrap();
+        assert!(String::from_utf8_lossy(&first).contains("\"content\":\"ab\""));
+
+        tx.send(Ok(Bytes::from_static(b"data: [DONE]\n\n")))
+            .unwrap();
+        drop(tx);
+        let rest = aggregated.next().await.unwrap().unwrap();
+        assert_eq!(&rest[..], b"data: [DONE]\n\n");
+        assert!(aggregated.next().await.is_none());
+    }
+
+    fn tokio_stream_from<T: Send + 'static>(
+        mut rx: tokio::sync::mpsc::UnboundedReceiver<T>,
+    ) -> impl Stream<Item = T> + Send {
+        async_stream::stream! {
+            while let Some(item) = rx.recv().await {
+                yield item;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_passes_through_unrecognized_events() {
+        let chunks = vec![
+            ": keep-alive\n\n".to_string(),
+            "data: {\"object\":\"chat.completion.chunk\",\"choices\":[{\"index\":0,\"delta\":{\"tool_calls\":[]},\"finish_reason\":null}]}\n\n".to_string(),
+            "data: partial".to_string(),
+        ];
+        let out = collect(chunks.clone(), None).await.concat();
+        assert_eq!(out, chunks.concat());
+    }
+}
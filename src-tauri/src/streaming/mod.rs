@@ -12,18 +12,24 @@
 //! - `converter`: 流式格式转换器
 //! - `traits`: StreamingProvider trait 定义
 //! - `manager`: 流式管理器
+//! - `tee`: 流式响应旁路捕获（边转发边累积完整响应）
+//! - `aggregate`: 流式响应合并（按时间窗口或字符数合并细碎的 SSE 事件）
 
+pub mod aggregate;
 pub mod anthropic_sse;
 pub mod aws_parser;
 pub mod converter;
 pub mod error;
 pub mod manager;
 pub mod metrics;
+pub mod tee;
 pub mod traits;
 
 // 重新导出核心类型
+pub use aggregate::aggregate_stream;
 pub use converter::StreamFormat;
 pub use error::StreamError;
 pub use manager::{with_timeout, StreamConfig, StreamContext, StreamManager};
 pub use metrics::StreamMetrics;
+pub use tee::tee_stream;
 pub use traits::{reqwest_stream_to_stream_response, StreamResponse};
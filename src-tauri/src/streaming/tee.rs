@@ -0,0 +1,154 @@
+//! 流式响应旁路捕获（Tee）
+//!
+//! 在不缓冲、不增加客户端延迟的前提下，把转发给客户端的每个 chunk
+//! 复制一份发送给后台任务，由 [`StreamAccumulator`] 解析累积完整响应，
+//! 流结束（或客户端断开）后回调 `on_complete`，用于写入历史记录和遥测。
+//!
+//! 复制的是 `Bytes`（引用计数），转发路径上只多一次无界通道发送。
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use proxycast_providers::{Provider, StreamAccumulator, StreamCapture};
+use tokio::sync::mpsc;
+
+/// 包装流式响应，旁路捕获完整内容
+///
+/// - `started_at`: 请求开始时间，用于计算首字节 / 首 Token 耗时
+/// - `on_complete`: 流结束后在后台任务中调用
+pub fn tee_stream<S, E, F, Fut>(
+    stream: S,
+    provider: Arc<dyn Provider>,
+    started_at: Instant,
+    on_complete: F,
+) -> impl Stream<Item = Result<Bytes, E>> + Send
+where
+    S: Stream<Item = Result<Bytes, E>> + Send,
+    E: Send,
+    F: FnOnce(StreamCapture) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let (tx, mut rx) = mpsc::unbounded_channel::<(Bytes, u64)>();
+
+    tokio::spawn(async move {
+        let mut accumulator = StreamAccumulator::new(provider);
+        while let Some((chunk, elapsed_ms)) = rx.recv().await {
+            accumulator.feed(&chunk, elapsed_ms);
+        }
+        // 发送端随流一起释放：流正常结束、出错或客户端断开都会走到这里
+        let capture = accumulator.finish(elapsed_ms(started_at));
+        on_complete(capture).await;
+    });
+
+    stream.map(move |item| {
+        if let Ok(chunk) = &item {
+            // 后台任务不会先于发送端退出，发送失败可忽略
+            let _ = tx.send((chunk.clone(), elapsed_ms(started_at)));
+        }
+        item
+    })
+}
+
+fn elapsed_ms(started_at: Instant) -> u64 {
+    started_at.elapsed().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proxycast_providers::{AnthropicProvider, OpenAiCompatibleProvider};
+
+    #[tokio::test]
+    async fn test_tee_forwards_and_captures() {
+        let chunks: Vec<Result<Bytes, std::io::Error>> = vec![
+            Ok(Bytes::from_static(
+                b"data: {\"choices\":[{\"delta\":{\"content\":\"Hi \"}}]}\n\n",
+            )),
+            Ok(Bytes::from_static(
+                b"data: {\"choices\":[{\"delta\":{\"content\":\"there\"}}]}\n\ndata: [DONE]\n\n",
+            )),
+        ];
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+
+        let teed = tee_stream(
+            futures::stream::iter(chunks),
+            Arc::new(OpenAiCompatibleProvider::openai()),
+            Instant::now(),
+            move |capture| async move {
+                let _ = done_tx.send(capture);
+            },
+        );
+        let forwarded: Vec<Bytes> = teed.map(|c| c.unwrap()).collect().await;
+        assert_eq!(forwarded.len(), 2);
+
+        let capture = done_rx.await.unwrap();
+        assert_eq!(capture.text, "Hi there");
+        assert!(capture.completed);
+        assert_eq!(capture.chunk_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_tee_reports_partial_on_drop() {
+        let chunks: Vec<Result<Bytes, std::io::Error>> = vec![
+            Ok(Bytes::from_static(
+                b"data: {\"choices\":[{\"delta\":{\"content\":\"partial\"}}]}\n\n",
+            )),
+            Ok(Bytes::from_static(b"data: [DONE]\n\n")),
+        ];
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+
+        let mut teed = Box::pin(tee_stream(
+            futures::stream::iter(chunks),
+            Arc::new(OpenAiCompatibleProvider::openai()),
+            Instant::now(),
+            move |capture| async move {
+                let _ = done_tx.send(capture);
+            },
+        ));
+        // 客户端只读了一个 chunk 就断开
+        teed.next().await;
+        drop(teed);
+
+        let capture = done_rx.await.unwrap();
+        assert_eq!(capture.text, "partial");
+        assert!(!capture.completed);
+    }
+
+    #[tokio::test]
+    async fn test_tee_captures_anthropic_stream() {
+        let chunks: Vec<Result<Bytes, std::io::Error>> = vec![
+            Ok(Bytes::from_static(
+                b"event: message_start\ndata: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":12,\"output_tokens\":1}}}\n\n",
+            )),
+            Ok(Bytes::from_static(
+                b"event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}\n\n",
+            )),
+            Ok(Bytes::from_static(
+                b"event: message_delta\ndata: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"end_turn\"},\"usage\":{\"output_tokens\":7}}\n\nevent: message_stop\ndata: {\"type\":\"message_stop\"}\n\n",
+            )),
+        ];
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+
+        let teed = tee_stream(
+            futures::stream::iter(chunks),
+            Arc::new(AnthropicProvider::new()),
+            Instant::now(),
+            move |capture| async move {
+                let _ = done_tx.send(capture);
+            },
+        );
+        let forwarded: Vec<Bytes> = teed.map(|c| c.unwrap()).collect().await;
+        assert_eq!(forwarded.len(), 3);
+
+        let capture = done_rx.await.unwrap();
+        assert_eq!(capture.text, "Hi");
+        assert!(capture.completed);
+        assert!(capture.first_token_ms.is_some());
+        let usage = capture.usage.unwrap();
+        assert_eq!(usage.input_tokens, 12);
+        assert_eq!(usage.output_tokens, 7);
+    }
+}
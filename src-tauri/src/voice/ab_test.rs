@@ -0,0 +1,232 @@
+//! 润色指令 A/B 测试
+//!
+//! `voice_input.processor.ab_test` 指定两个变体指令。启用后按识别语言自动选择指令的润色
+//! 改为在两个变体之间轮换（以最近一次记录为准，重启后继续交替），每次润色记录一条测试记录，
+//! 用户在悬浮窗中评价润色结果（好 / 差）。
+//!
+//! 汇总时按变体统计润色次数和好评率；两个变体都有至少 [`MIN_RATED_TRIALS`] 条评价，
+//! 且好评率差异在双比例 z 检验下显著（95% 置信度）时给出胜出的变体。
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::InstructionAbTest;
+use crate::database::dao::voice_ab_trials::VoiceAbTrialDao;
+use crate::database::DbConnection;
+
+/// 判断胜出前每个变体至少需要的评价数
+pub const MIN_RATED_TRIALS: u32 = 10;
+
+/// 95% 置信度对应的 z 值
+const Z_CRITICAL: f64 = 1.96;
+
+/// 润色结果评价
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AbRating {
+    Good,
+    Bad,
+}
+
+impl AbRating {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Good => "good",
+            Self::Bad => "bad",
+        }
+    }
+}
+
+/// 一次 A/B 测试润色
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoiceAbTrial {
+    pub id: String,
+    /// 使用的变体指令 ID
+    pub instruction_id: String,
+    /// 对应的听写历史 ID（未开启历史时为空）
+    pub history_id: Option<String>,
+    /// 用户评价（未评价时为空）
+    pub rating: Option<AbRating>,
+    pub created_at: i64,
+    pub rated_at: Option<i64>,
+}
+
+impl VoiceAbTrial {
+    pub fn new(instruction_id: String, history_id: Option<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            instruction_id,
+            history_id,
+            rating: None,
+            created_at: chrono::Utc::now().timestamp_millis(),
+            rated_at: None,
+        }
+    }
+}
+
+/// 单个变体的统计
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AbVariantStats {
+    pub instruction_id: String,
+    /// 润色次数
+    pub trials: u32,
+    /// 好评数
+    pub good: u32,
+    /// 差评数
+    pub bad: u32,
+    /// 好评率（没有评价时为空）
+    pub good_rate: Option<f64>,
+}
+
+impl AbVariantStats {
+    pub fn new(instruction_id: String, trials: u32, good: u32, bad: u32) -> Self {
+        let rated = good + bad;
+        Self {
+            instruction_id,
+            trials,
+            good,
+            bad,
+            good_rate: (rated > 0).then(|| good as f64 / rated as f64),
+        }
+    }
+
+    /// 已评价的次数
+    pub fn rated(&self) -> u32 {
+        self.good + self.bad
+    }
+}
+
+/// A/B 测试汇总
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AbTestSummary {
+    pub variant_a: AbVariantStats,
+    pub variant_b: AbVariantStats,
+    /// 好评率显著更高的变体指令 ID（评价不足或差异不显著时为空）
+    pub winner: Option<String>,
+}
+
+impl AbTestSummary {
+    pub fn new(variant_a: AbVariantStats, variant_b: AbVariantStats) -> Self {
+        let winner = winner(&variant_a, &variant_b).map(|s| s.instruction_id.clone());
+        Self {
+            variant_a,
+            variant_b,
+            winner,
+        }
+    }
+}
+
+/// 下一次润色使用的变体：与上一次相反，没有记录时从变体 A 开始
+pub fn next_variant<'a>(config: &'a InstructionAbTest, last: Option<&str>) -> &'a str {
+    if last == Some(config.variant_a.as_str()) {
+        &config.variant_b
+    } else {
+        &config.variant_a
+    }
+}
+
+/// 双比例 z 检验，好评率差异显著时返回好评率更高的变体
+fn winner<'a>(a: &'a AbVariantStats, b: &'a AbVariantStats) -> Option<&'a AbVariantStats> {
+    if a.rated() < MIN_RATED_TRIALS || b.rated() < MIN_RATED_TRIALS {
+        return None;
+    }
+    let (n1, n2) = (a.rated() as f64, b.rated() as f64);
+    let (p1, p2) = (a.good as f64 / n1, b.good as f64 / n2);
+    let pooled = (a.good + b.good) as f64 / (n1 + n2);
+    let se = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+    if se == 0.0 {
+        return None;
+    }
+    let z = (p1 - p2) / se;
+    if z.abs() < Z_CRITICAL {
+        return None;
+    }
+    Some(if z > 0.0 { a } else { b })
+}
+
+/// 选择本次润色的变体指令，A/B 测试未启用时返回空
+pub fn choose(db: &DbConnection, config: &InstructionAbTest) -> Option<String> {
+    if !config.is_active() {
+        return None;
+    }
+    let last = {
+        let conn = db.lock().ok()?;
+        VoiceAbTrialDao::last_instruction(&conn, &config.variant_a, &config.variant_b)
+    };
+    match last {
+        Ok(last) => Some(next_variant(config, last.as_deref()).to_string()),
+        Err(e) => {
+            tracing::warn!("[指令 A/B 测试] 读取测试记录失败: {}", e);
+            None
+        }
+    }
+}
+
+/// 记录一次变体润色，返回测试记录 ID（写入失败时返回空，不影响润色）
+pub fn record(
+    db: &DbConnection,
+    instruction_id: &str,
+    history_id: Option<String>,
+) -> Option<String> {
+    let trial = VoiceAbTrial::new(instruction_id.to_string(), history_id);
+    let conn = db.lock().ok()?;
+    match VoiceAbTrialDao::insert(&conn, &trial) {
+        Ok(()) => Some(trial.id),
+        Err(e) => {
+            tracing::warn!("[指令 A/B 测试] 保存测试记录失败: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> InstructionAbTest {
+        InstructionAbTest {
+            enabled: true,
+            variant_a: "default".to_string(),
+            variant_b: "concise".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_variants_alternate() {
+        let config = config();
+        assert_eq!(next_variant(&config, None), "default");
+        assert_eq!(next_variant(&config, Some("default")), "concise");
+        assert_eq!(next_variant(&config, Some("concise")), "default");
+    }
+
+    #[test]
+    fn test_inactive_without_two_variants() {
+        let mut config = config();
+        assert!(config.is_active());
+        config.variant_b = "default".to_string();
+        assert!(!config.is_active());
+        config.variant_b = String::new();
+        assert!(!config.is_active());
+        config.variant_b = "concise".to_string();
+        config.enabled = false;
+        assert!(!config.is_active());
+    }
+
+    #[test]
+    fn test_winner_requires_enough_ratings() {
+        let a = AbVariantStats::new("a".to_string(), 12, 9, 0);
+        let b = AbVariantStats::new("b".to_string(), 12, 0, 9);
+        assert_eq!(a.good_rate, Some(1.0));
+        assert_eq!(AbTestSummary::new(a, b).winner, None);
+    }
+
+    #[test]
+    fn test_winner_requires_significant_difference() {
+        let a = AbVariantStats::new("a".to_string(), 40, 18, 12);
+        let b = AbVariantStats::new("b".to_string(), 40, 16, 14);
+        assert_eq!(AbTestSummary::new(a, b).winner, None);
+
+        let a = AbVariantStats::new("a".to_string(), 40, 12, 18);
+        let b = AbVariantStats::new("b".to_string(), 40, 26, 4);
+        assert_eq!(AbTestSummary::new(a, b).winner.as_deref(), Some("b"));
+    }
+}
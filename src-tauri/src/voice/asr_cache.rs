@@ -0,0 +1,259 @@
+//! 识别结果缓存
+//!
+//! 界面异常后用户点重试、同一段录音被重复提交，或换一个润色指令重新处理同一段录音时，
+//! 音频与上次完全相同，没必要再付费调用一次云端识别。按音频指纹（裁剪首尾静音后的
+//! PCM 采样和采样率的 SHA-256）、识别语言、凭证 ID 和热词表缓存云端识别成功的结果，
+//! 容量满时淘汰最久未使用的结果，超过有效期的结果不再使用。
+//!
+//! 只缓存所选云端服务（含回退、竞速的其他云端凭证）的结果，回退到本地 Whisper 的
+//! 结果不缓存，以便云端恢复后重试仍走云端。修改或删除凭证时清除该凭证的缓存。
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use voice_core::types::TranscribeResult;
+
+use crate::config::AsrCredentialEntry;
+
+/// 缓存有效期
+const TTL: Duration = Duration::from_secs(300);
+
+/// 最多缓存的结果数
+const CAPACITY: usize = 32;
+
+/// 全局识别结果缓存
+static CACHE: once_cell::sync::Lazy<Mutex<AsrCache>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(AsrCache::new(TTL, CAPACITY)));
+
+/// 音频指纹：PCM 采样和采样率的 SHA-256
+pub type Fingerprint = [u8; 32];
+
+/// 缓存键
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    /// 音频指纹
+    pub fingerprint: Fingerprint,
+    /// 识别语言（凭证的语言设置）
+    pub language: String,
+    /// 凭证 ID
+    pub credential_id: String,
+    /// 热词表的 SHA-256，热词表变化后同一段录音的识别结果可能不同
+    pub hotwords: [u8; 32],
+}
+
+/// 计算音频指纹
+///
+/// `audio_data` 应为裁剪首尾静音后的 PCM，使前后静音长短不同的同一段录音也能命中
+pub fn fingerprint(audio_data: &[u8], sample_rate: u32) -> Fingerprint {
+    let mut hasher = Sha256::new();
+    hasher.update(sample_rate.to_le_bytes());
+    hasher.update(audio_data);
+    hasher.finalize().into()
+}
+
+/// 计算热词表的 SHA-256，每个词前写入长度以区分拼接结果相同的列表
+fn hotwords_digest(hotwords: &[String]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for word in hotwords {
+        hasher.update((word.len() as u64).to_le_bytes());
+        hasher.update(word.as_bytes());
+    }
+    hasher.finalize().into()
+}
+
+/// 计算缓存键，`hotwords` 为构建客户端时使用的热词表
+pub fn key(
+    credential: &AsrCredentialEntry,
+    hotwords: &[String],
+    audio_data: &[u8],
+    sample_rate: u32,
+) -> CacheKey {
+    CacheKey {
+        fingerprint: fingerprint(audio_data, sample_rate),
+        language: credential.language.clone(),
+        credential_id: credential.id.clone(),
+        hotwords: hotwords_digest(hotwords),
+    }
+}
+
+/// 查找未过期的缓存结果
+pub fn get(key: &CacheKey) -> Option<TranscribeResult> {
+    CACHE.lock().get(key, Instant::now())
+}
+
+/// 缓存识别结果
+pub fn insert(key: CacheKey, result: &TranscribeResult) {
+    CACHE.lock().insert(key, result.clone(), Instant::now());
+}
+
+/// 清除凭证的缓存结果（凭证修改或删除后调用）
+pub fn forget_credential(credential_id: &str) {
+    CACHE.lock().remove_credential(credential_id);
+}
+
+/// 缓存的识别结果
+struct Entry {
+    key: CacheKey,
+    stored_at: Instant,
+    result: TranscribeResult,
+}
+
+/// 按有效期过期、按最近使用淘汰的识别结果缓存
+struct AsrCache {
+    ttl: Duration,
+    capacity: usize,
+    /// 按最近使用排列，最久未使用的在前
+    entries: VecDeque<Entry>,
+}
+
+impl AsrCache {
+    fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// 命中时移到末尾，标记为最近使用
+    fn get(&mut self, key: &CacheKey, now: Instant) -> Option<TranscribeResult> {
+        self.evict_expired(now);
+        let index = self.entries.iter().position(|entry| entry.key == *key)?;
+        let entry = self.entries.remove(index)?;
+        let result = entry.result.clone();
+        self.entries.push_back(entry);
+        Some(result)
+    }
+
+    fn insert(&mut self, key: CacheKey, result: TranscribeResult, now: Instant) {
+        self.evict_expired(now);
+        self.entries.retain(|entry| entry.key != key);
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Entry {
+            key,
+            stored_at: now,
+            result,
+        });
+    }
+
+    fn remove_credential(&mut self, credential_id: &str) {
+        self.entries
+            .retain(|entry| entry.key.credential_id != credential_id);
+    }
+
+    /// 有效期从写入时算起，命中不会延长
+    fn evict_expired(&mut self, now: Instant) {
+        let ttl = self.ttl;
+        self.entries
+            .retain(|entry| now.duration_since(entry.stored_at) < ttl);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(text: &str) -> TranscribeResult {
+        TranscribeResult {
+            text: text.to_string(),
+            language: None,
+            confidence: None,
+            segments: Vec::new(),
+            backend: Some("xunfei".to_string()),
+        }
+    }
+
+    fn credential(id: &str) -> AsrCredentialEntry {
+        serde_json::from_value(serde_json::json!({ "id": id, "provider": "xunfei" })).unwrap()
+    }
+
+    fn cache_key(n: u8) -> CacheKey {
+        CacheKey {
+            fingerprint: [n; 32],
+            language: "zh".to_string(),
+            credential_id: "a".to_string(),
+            hotwords: hotwords_digest(&[]),
+        }
+    }
+
+    #[test]
+    fn test_key() {
+        let audio = [1u8, 0, 2, 0, 3, 0];
+        let base = key(&credential("a"), &[], &audio, 16000);
+        assert_eq!(base, key(&credential("a"), &[], &audio, 16000));
+        assert_ne!(base, key(&credential("b"), &[], &audio, 16000));
+        assert_ne!(base, key(&credential("a"), &[], &audio, 8000));
+        assert_ne!(base, key(&credential("a"), &[], &audio[..4], 16000));
+
+        let mut english = credential("a");
+        english.language = "en".to_string();
+        assert_ne!(base, key(&english, &[], &audio, 16000));
+
+        let words = |list: &[&str]| list.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        let with_hotwords = key(&credential("a"), &words(&["ProxyCast"]), &audio, 16000);
+        assert_ne!(base, with_hotwords);
+        assert_eq!(
+            with_hotwords,
+            key(&credential("a"), &words(&["ProxyCast"]), &audio, 16000)
+        );
+        assert_ne!(
+            key(&credential("a"), &words(&["ab", "c"]), &audio, 16000),
+            key(&credential("a"), &words(&["a", "bc"]), &audio, 16000)
+        );
+    }
+
+    #[test]
+    fn test_expiry() {
+        let mut cache = AsrCache::new(Duration::from_secs(60), 4);
+        let now = Instant::now();
+        cache.insert(cache_key(1), result("你好"), now);
+
+        let hit = cache
+            .get(&cache_key(1), now + Duration::from_secs(59))
+            .unwrap();
+        assert_eq!(hit.text, "你好");
+        assert!(cache.get(&cache_key(2), now).is_none());
+        assert!(cache
+            .get(&cache_key(1), now + Duration::from_secs(60))
+            .is_none());
+        assert!(cache.entries.is_empty());
+    }
+
+    #[test]
+    fn test_least_recently_used_evicted() {
+        let mut cache = AsrCache::new(Duration::from_secs(60), 2);
+        let now = Instant::now();
+        cache.insert(cache_key(1), result("一"), now);
+        cache.insert(cache_key(2), result("二"), now);
+        cache.insert(cache_key(1), result("新的一"), now);
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.get(&cache_key(1), now).unwrap().text, "新的一");
+
+        // 读取 2 后 1 成为最久未使用，超出容量时被淘汰
+        assert!(cache.get(&cache_key(2), now).is_some());
+        cache.insert(cache_key(3), result("三"), now);
+        assert!(cache.get(&cache_key(1), now).is_none());
+        assert!(cache.get(&cache_key(2), now).is_some());
+        assert!(cache.get(&cache_key(3), now).is_some());
+    }
+
+    #[test]
+    fn test_remove_credential() {
+        let mut cache = AsrCache::new(Duration::from_secs(60), 4);
+        let now = Instant::now();
+        cache.insert(cache_key(1), result("一"), now);
+        let other = CacheKey {
+            credential_id: "b".to_string(),
+            ..cache_key(1)
+        };
+        cache.insert(other.clone(), result("一"), now);
+
+        cache.remove_credential("a");
+        assert!(cache.get(&cache_key(1), now).is_none());
+        assert!(cache.get(&other, now).is_some());
+    }
+}
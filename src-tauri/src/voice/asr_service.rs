@@ -4,9 +4,44 @@
 //!
 //! ## 功能
 //! - 本地 Whisper 识别（离线、隐私）
+//! - 本地 SenseVoice 识别（FunASR ONNX 模型，中文效果更好，由本地凭证的 `engine` 选择）
 //! - OpenAI Whisper API
+//! - 自建 Whisper 服务（兼容 OpenAI 转写接口的 faster-whisper、whisper.cpp 等）
 //! - 百度语音识别
+//! - 腾讯云一句话识别（TC3-HMAC-SHA256 签名）
+//! - Azure 语音服务短音频识别
+//! - Deepgram 预录音频识别（逐词时间戳，可选模型）
 //! - 讯飞语音识别（WebSocket 流式）
+//! - 火山引擎流式语音识别（WebSocket 二进制协议，gzip 压缩负载）
+//! - 自定义命令（把 WAV 写入外部命令，读取其输出的 JSON 结果）
+//! - 通用 HTTP 接口（自建的 FunASR、SenseVoice 等服务）
+//!
+//! 识别前会按 `voice_input.silence_trim` 配置裁剪首尾静音。
+//!
+//! 讯飞听写、火山引擎、百度短语音、腾讯云一句话识别和 Azure 短音频识别单次最长 60 秒。更长的录音在讯飞配置了录音文件转写
+//! SecretKey 时整段上传转写，否则按停顿分段调用实时接口。百度的长语音接口只接受
+//! 公网可访问的音频 URL，因此始终分段识别。单次时长上限等差异由各客户端的
+//! `AsrCapabilities` 声明。
+//!
+//! 云端客户端统一由 `voice_core::asr_client::create_client_with_hotwords` 根据凭证及其请求超时构建，
+//! 同时带上默认工作区的热词表（见 [`super::vocabulary`]）。
+//! 所选云端服务连不上或鉴权失败时，按凭证的回退优先级（`priority`）依次改用其他启用的
+//! 云端凭证，都失败时再回退到本地 Whisper；识别结果的 `backend` 记录实际使用的服务。
+//! 识别策略设为竞速（`recognition_strategy = "race"`）时，同一段音频同时发给这些云端凭证，
+//! 采用最先成功返回的结果。每个云端服务遇到网络错误时先按 `voice_input.asr_retry`
+//! 指数退避重试，仍然失败才回退。每次请求（含重试）按凭证的 `rate_limit` 限流，
+//! 超出每秒次数时排队等待，百度默认每秒 2 次。
+//!
+//! 同一段音频（裁剪静音后）以相同语言和凭证短时间内重复识别时直接使用缓存的云端结果，
+//! 见 [`super::asr_cache`]；要求重新识别时用 [`AsrService::retranscribe`] 跳过缓存。
+//!
+//! 取消听写时通过 [`AsrService::transcribe_cancellable`] 中止进行中的识别请求。
+//!
+//! 识别结果带上语言代码：凭证指定了语言时直接使用，自动检测时取服务返回的语言，
+//! 服务没有返回时按文本字符判断，用于按语言选择润色指令。
+//!
+//! 凭证语言设为中英混合（`mixed`）时，各服务使用支持中英混说的引擎，识别结果
+//! 再整理中英文交界处的空格和大小写。
 //!
 //! ## 模型文件路径
 //! Whisper 模型文件存储在：`~/Library/Application Support/proxycast/models/whisper/`
@@ -16,16 +51,60 @@
 //! - `ggml-base.bin` (~142MB)
 //! - `ggml-small.bin` (~466MB)
 //! - `ggml-medium.bin` (~1.5GB)
+//! - `ggml-large-v3.bin` (~3.1GB，中英混说效果最好)
+//!
+//! SenseVoice 模型存储在同级的 `sensevoice/` 目录，可在语音设置中下载，见 [`super::models`]。
+//!
+//! Whisper 模型不在启动时加载：首次识别时加载并缓存，开始听写会话时在后台预热
+//! （[`AsrService::warm_up_local_model`]），模型或语言变化时重新加载。
 //!
 //! ## 使用示例
 //! ```rust,ignore
 //! let credential = AsrService::get_default_credential()?.unwrap();
-//! let text = AsrService::transcribe(&credential, &audio_data, 16000).await?;
+//! let result = AsrService::transcribe(&credential, &audio_data, 16000).await?;
 //! ```
 
 use std::path::PathBuf;
-
-use crate::config::{load_config, AsrCredentialEntry, AsrProviderType, WhisperModelSize};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use voice_core::asr_client::{
+    create_client_with_hotwords, AsrCapabilities, AsrClient, AsrClientConfig, AsrFallbackChain,
+    AsrRace, AsrRateLimit, AsrRetry, AsrTimeouts, AzureProfanity, AzureSpeechClient, BaiduClient,
+    CancellationToken, CommandClient, CustomWhisperClient, DeepgramClient, HttpAsrClient,
+    HttpUpload, OpenAIWhisperClient, RateLimit, RateLimiter, RetryPolicy, TencentClient,
+    VolcengineClient, XunfeiClient,
+};
+use voice_core::error::VoiceError;
+use voice_core::silence::split_at_pauses;
+use voice_core::types::{Segment, TranscribeResult};
+#[cfg(feature = "sensevoice")]
+use voice_core::SenseVoiceTranscriber;
+use voice_core::{
+    detect_language, format_mixed_text, normalize_language, BenchmarkResult, SilenceTrimOptions,
+    WhisperTranscriber, MIXED_LANGUAGE,
+};
+
+use super::asr_cache;
+use super::models;
+use super::usage::{self, MeteredClient};
+use crate::config::{
+    load_config, AsrCredentialEntry, AsrProviderType, AsrRetryConfig, AzureProfanityFilter,
+    HttpAsrUpload, LocalAsrEngine, RecognitionStrategy, WhisperModelSize, WhisperTask,
+};
+use crate::services::startup_profile_service::{profiler, InitPhase};
+
+/// 已加载的本地 Whisper 模型
+static WHISPER_CACHE: once_cell::sync::Lazy<Mutex<Option<CachedWhisper>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(None));
+
+/// 缓存的 Whisper 识别器及其模型文件和语言
+struct CachedWhisper {
+    model_path: PathBuf,
+    language: String,
+    transcriber: Arc<WhisperTranscriber>,
+}
 
 /// ASR 服务
 pub struct AsrService;
@@ -47,36 +126,150 @@ impl AsrService {
         Ok(config.credential_pool.asr.into_iter().find(|c| c.id == id))
     }
 
+    /// 凭证对应服务的能力
+    ///
+    /// 讯飞配置了录音文件转写时，超过听写上限的录音改用录音文件转写，
+    /// 因此单次时长上限取录音文件转写的上限
+    pub fn capabilities(credential: &AsrCredentialEntry) -> AsrCapabilities {
+        match credential.provider {
+            AsrProviderType::WhisperLocal => match Self::local_engine(credential) {
+                LocalAsrEngine::Whisper => WhisperTranscriber::CAPABILITIES,
+                #[cfg(feature = "sensevoice")]
+                LocalAsrEngine::SenseVoice => SenseVoiceTranscriber::CAPABILITIES,
+            },
+            AsrProviderType::OpenAI => OpenAIWhisperClient::CAPABILITIES,
+            AsrProviderType::CustomWhisper => CustomWhisperClient::CAPABILITIES,
+            AsrProviderType::Baidu => BaiduClient::CAPABILITIES,
+            AsrProviderType::Tencent => TencentClient::CAPABILITIES,
+            AsrProviderType::Azure => match credential.azure_config.as_ref() {
+                Some(config) if config.diarize => AzureSpeechClient::DIARIZATION_CAPABILITIES,
+                _ => AzureSpeechClient::CAPABILITIES,
+            },
+            AsrProviderType::Deepgram => DeepgramClient::CAPABILITIES,
+            AsrProviderType::Volcengine => VolcengineClient::CAPABILITIES,
+            AsrProviderType::Command => CommandClient::CAPABILITIES,
+            AsrProviderType::Http => HttpAsrClient::CAPABILITIES,
+            AsrProviderType::Xunfei => match Self::lfasr_config(credential) {
+                Some(lfasr) => AsrCapabilities {
+                    max_duration_secs: lfasr.capabilities().max_duration_secs,
+                    ..XunfeiClient::CAPABILITIES
+                },
+                None => XunfeiClient::CAPABILITIES,
+            },
+        }
+    }
+
     /// 使用指定凭证进行语音识别
     ///
-    /// 当云端服务失败时，自动回退到本地 Whisper（需求 3.4）
+    /// 当云端服务失败时，自动回退到本地 Whisper（需求 3.4）。
+    /// 识别结果按配置纠正同音词、替换口述标点，没有标点时按配置补全标点
     pub async fn transcribe(
         credential: &AsrCredentialEntry,
         audio_data: &[u8],
         sample_rate: u32,
-    ) -> Result<String, String> {
+    ) -> Result<TranscribeResult, String> {
+        let result =
+            Self::transcribe_with_fallback(credential, audio_data, sample_rate, true).await?;
+        let result = super::homophone::correct(result);
+        let result = super::processor::apply_spoken_punctuation(result);
+        Ok(super::punctuation::restore(result).await)
+    }
+
+    /// 重新识别，不使用缓存的结果
+    ///
+    /// 用户对结果不满意、要求重新识别时使用，新的云端结果会替换缓存
+    pub async fn retranscribe(
+        credential: &AsrCredentialEntry,
+        audio_data: &[u8],
+        sample_rate: u32,
+    ) -> Result<TranscribeResult, String> {
+        let result =
+            Self::transcribe_with_fallback(credential, audio_data, sample_rate, false).await?;
+        let result = super::homophone::correct(result);
+        let result = super::processor::apply_spoken_punctuation(result);
+        Ok(super::punctuation::restore(result).await)
+    }
+
+    /// 可取消的语音识别
+    ///
+    /// `cancel` 触发时立即返回，进行中的云端请求（含讯飞、火山引擎的 WebSocket 会话）随之中止；
+    /// 本地识别在后台线程执行，无法中途停止，结果被丢弃
+    pub async fn transcribe_cancellable(
+        credential: &AsrCredentialEntry,
+        audio_data: &[u8],
+        sample_rate: u32,
+        cancel: &CancellationToken,
+    ) -> Result<TranscribeResult, String> {
+        tokio::select! {
+            biased;
+            _ = cancel.cancelled() => {
+                tracing::info!("[语音识别] 识别已取消");
+                Err(VoiceError::Cancelled.to_string())
+            }
+            result = Self::transcribe(credential, audio_data, sample_rate) => result,
+        }
+    }
+
+    /// 识别音频，云端服务失败时回退到本地 Whisper
+    ///
+    /// `use_cache` 为 false 时不读取缓存，但仍缓存新的云端结果
+    async fn transcribe_with_fallback(
+        credential: &AsrCredentialEntry,
+        audio_data: &[u8],
+        sample_rate: u32,
+        use_cache: bool,
+    ) -> Result<TranscribeResult, String> {
+        let trimmed = Self::trim_silence(audio_data, sample_rate);
+        let audio_data = trimmed.as_deref().unwrap_or(audio_data);
+
+        if credential.language == MIXED_LANGUAGE && !Self::capabilities(credential).code_switching {
+            tracing::warn!(
+                "[语音识别] {:?} 对中英混说支持较差，建议改用讯飞或本地 Whisper",
+                credential.provider
+            );
+        }
+
         // 如果是本地 Whisper，直接调用
         if matches!(credential.provider, AsrProviderType::WhisperLocal) {
-            return Self::transcribe_whisper_local(credential, audio_data, sample_rate).await;
+            return Self::transcribe_local_metered(credential, audio_data, sample_rate)
+                .await
+                .map(|result| Self::finalize(credential, result));
+        }
+
+        // 隐私设置禁止音频离开本机时，云端凭证强制改用本地 Whisper
+        if Self::upload_blocked(credential) {
+            let whisper_credential = Self::get_whisper_local_credential()?
+                .ok_or_else(|| "隐私设置禁止音频离开本机，请先配置本地 Whisper".to_string())?;
+            tracing::info!(
+                "[语音识别] 隐私设置禁止上传音频，{:?} 改用本地识别",
+                credential.provider
+            );
+            let mut result =
+                Self::transcribe_local_metered(&whisper_credential, audio_data, sample_rate)
+                    .await?;
+            result.backend = Some(Self::local_backend(&whisper_credential).to_string());
+            return Ok(Self::finalize(&whisper_credential, result));
+        }
+
+        // 同一段音频短时间内重复提交、换指令重新处理时直接使用上次的云端结果
+        let cache_key = asr_cache::key(
+            credential,
+            &super::vocabulary::current(),
+            audio_data,
+            sample_rate,
+        );
+        if let Some(result) = use_cache.then(|| asr_cache::get(&cache_key)).flatten() {
+            tracing::info!("[语音识别] 音频与近期识别过的相同，使用缓存结果");
+            return Ok(Self::finalize(credential, result));
         }
 
         // 云端服务：先尝试云端，失败则回退到本地 Whisper
-        let cloud_result = match credential.provider {
-            AsrProviderType::OpenAI => {
-                Self::transcribe_openai(credential, audio_data, sample_rate).await
-            }
-            AsrProviderType::Baidu => {
-                Self::transcribe_baidu(credential, audio_data, sample_rate).await
-            }
-            AsrProviderType::Xunfei => {
-                Self::transcribe_xunfei(credential, audio_data, sample_rate).await
-            }
-            AsrProviderType::WhisperLocal => unreachable!(), // 已在上面处理
-        };
+        let cloud_result = Self::transcribe_cloud(credential, audio_data, sample_rate).await;
 
-        // 云端成功，直接返回
-        if cloud_result.is_ok() {
-            return cloud_result;
+        // 云端成功，缓存后返回
+        if let Ok(result) = &cloud_result {
+            asr_cache::insert(cache_key, result);
+            return cloud_result.map(|result| Self::finalize(credential, result));
         }
 
         // 云端失败，尝试回退到本地 Whisper
@@ -91,12 +284,13 @@ impl AsrService {
         match Self::get_whisper_local_credential() {
             Ok(Some(whisper_credential)) => {
                 tracing::info!("正在使用本地 Whisper 进行回退识别...");
-                match Self::transcribe_whisper_local(&whisper_credential, audio_data, sample_rate)
+                match Self::transcribe_local_metered(&whisper_credential, audio_data, sample_rate)
                     .await
                 {
-                    Ok(text) => {
+                    Ok(mut result) => {
                         tracing::info!("本地 Whisper 回退识别成功");
-                        Ok(text)
+                        result.backend = Some(Self::local_backend(&whisper_credential).to_string());
+                        Ok(Self::finalize(&whisper_credential, result))
                     }
                     Err(whisper_error) => {
                         tracing::error!("本地 Whisper 回退也失败: {}", whisper_error);
@@ -125,6 +319,82 @@ impl AsrService {
         }
     }
 
+    /// 识别结果后处理
+    ///
+    /// 确定识别结果的语言，中英混合模式下整理中英文交界处的空格和大小写
+    pub fn finalize(
+        credential: &AsrCredentialEntry,
+        mut result: TranscribeResult,
+    ) -> TranscribeResult {
+        if credential.language == MIXED_LANGUAGE {
+            result.text = format_mixed_text(&result.text);
+        }
+        result.language =
+            Self::resolve_language(credential, result.language.as_deref(), &result.text);
+        result
+    }
+
+    /// 确定识别结果的语言
+    ///
+    /// 凭证指定了语言时直接使用；自动检测时优先使用服务返回的语言，
+    /// 否则按文本字符判断；中英混合时按文本中占多数的语言判断
+    fn resolve_language(
+        credential: &AsrCredentialEntry,
+        reported: Option<&str>,
+        text: &str,
+    ) -> Option<String> {
+        let detected = || detect_language(text).map(str::to_string);
+        match credential.language.as_str() {
+            "auto" => reported.map(normalize_language).or_else(detected),
+            MIXED_LANGUAGE => detected().or_else(|| reported.map(normalize_language)),
+            language => Some(normalize_language(language)),
+        }
+    }
+
+    /// 按语音输入配置裁剪首尾静音
+    ///
+    /// 未启用、全部为静音或无需裁剪时返回 `None`，使用原始音频
+    fn trim_silence(audio_data: &[u8], sample_rate: u32) -> Option<Vec<u8>> {
+        let voice_config = load_config().ok()?.experimental.voice_input;
+        let trim = &voice_config.silence_trim;
+        if !trim.enabled {
+            return None;
+        }
+
+        let threshold_dbfs = voice_config
+            .silence_threshold_dbfs(voice_config.selected_device_id.as_deref())
+            .unwrap_or(SilenceTrimOptions::DEFAULT_THRESHOLD_DBFS);
+        let options = SilenceTrimOptions {
+            threshold_dbfs,
+            padding_ms: trim.padding_ms,
+        };
+
+        let samples: Vec<i16> = audio_data
+            .chunks_exact(2)
+            .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]))
+            .collect();
+        let mut audio = voice_core::types::AudioData::new(samples, sample_rate, 1);
+        let trimmed_secs = audio.trim_silence(&options);
+        // 裁剪后过短时保留原始音频，避免被判定为无效录音
+        if trimmed_secs <= 0.0 || !audio.is_valid() {
+            return None;
+        }
+
+        tracing::info!(
+            "[语音识别] 裁剪首尾静音 {:.2}s（阈值 {:.1}dBFS），剩余 {:.2}s",
+            trimmed_secs,
+            threshold_dbfs,
+            audio.duration_secs
+        );
+        Some(
+            audio
+                .samples
+                .iter()
+                .flat_map(|&s| s.to_le_bytes())
+                .collect(),
+        )
+    }
+
     /// 获取本地 Whisper 凭证（用于回退）
     fn get_whisper_local_credential() -> Result<Option<AsrCredentialEntry>, String> {
         let config = load_config().map_err(|e| e.to_string())?;
@@ -135,21 +405,52 @@ impl AsrService {
             .find(|c| matches!(c.provider, AsrProviderType::WhisperLocal) && !c.disabled))
     }
 
-    /// 本地 Whisper 识别
-    async fn transcribe_whisper_local(
+    /// 本地凭证选择的识别引擎
+    fn local_engine(credential: &AsrCredentialEntry) -> LocalAsrEngine {
+        credential
+            .whisper_config
+            .as_ref()
+            .map(|c| c.engine)
+            .unwrap_or_default()
+    }
+
+    /// 本地识别引擎的名称
+    fn local_backend(credential: &AsrCredentialEntry) -> &'static str {
+        match Self::local_engine(credential) {
+            LocalAsrEngine::Whisper => "本地 Whisper",
+            #[cfg(feature = "sensevoice")]
+            LocalAsrEngine::SenseVoice => "本地 SenseVoice",
+        }
+    }
+
+    /// 本地识别并记录用量
+    async fn transcribe_local_metered(
+        credential: &AsrCredentialEntry,
+        audio_data: &[u8],
+        sample_rate: u32,
+    ) -> Result<TranscribeResult, String> {
+        let audio_secs = audio_data.len() as f32 / 2.0 / sample_rate.max(1) as f32;
+        usage::metered(
+            &credential.id,
+            Self::local_backend(credential),
+            audio_secs,
+            Self::transcribe_local(credential, audio_data, sample_rate),
+        )
+        .await
+    }
+
+    /// 本地识别，按凭证选择的引擎调用 Whisper 或 SenseVoice
+    async fn transcribe_local(
         credential: &AsrCredentialEntry,
         audio_data: &[u8],
         sample_rate: u32,
-    ) -> Result<String, String> {
-        // 获取 Whisper 配置
-        let whisper_config = credential
+    ) -> Result<TranscribeResult, String> {
+        // 获取本地配置
+        let local_config = credential
             .whisper_config
             .as_ref()
             .ok_or("Whisper 本地配置缺失")?;
 
-        // 获取模型文件路径
-        let model_path = Self::get_whisper_model_path(&whisper_config.model)?;
-
         // 将 PCM 字节转换为 i16 采样
         let samples: Vec<i16> = audio_data
             .chunks_exact(2)
@@ -169,20 +470,160 @@ impl AsrService {
             return Err("录音时间过短（需要至少 0.5 秒）".to_string());
         }
 
+        #[cfg(feature = "sensevoice")]
+        if local_config.engine == LocalAsrEngine::SenseVoice {
+            let model_dir = models::sensevoice_model_dir(local_config.sensevoice_model)?;
+            let model = models::sensevoice_model(local_config.sensevoice_model);
+            let language = credential.language.clone();
+            return tokio::task::spawn_blocking(move || {
+                SenseVoiceTranscriber::new(&model_dir, model, &language)
+                    .map_err(|e| e.to_string())?
+                    .transcribe(&audio)
+                    .map_err(|e| format!("SenseVoice 识别失败: {}", e))
+            })
+            .await
+            .map_err(|e| format!("识别任务异常: {}", e))?;
+        }
+
+        // 获取模型文件路径
+        let model_path = Self::get_whisper_model_path(&local_config.model)?;
+
         // 转换模型大小枚举
-        let model = Self::convert_model_size(&whisper_config.model);
+        let model = Self::convert_model_size(&local_config.model);
+
+        let language = credential.language.clone();
+        let translate = Self::whisper_translate();
+        tokio::task::spawn_blocking(move || {
+            // 获取 Whisper 识别器（首次使用时加载模型）
+            let transcriber = Self::cached_whisper(model_path, model, &language)?;
+
+            // 执行识别，翻译模式直接输出英文
+            transcriber
+                .transcribe_with(&audio, translate)
+                .map_err(|e| format!("Whisper 识别失败: {}", e))
+        })
+        .await
+        .map_err(|e| format!("识别任务异常: {}", e))?
+    }
 
-        // 创建 Whisper 识别器
-        let transcriber =
-            voice_core::WhisperTranscriber::new(model_path, model, &credential.language)
-                .map_err(|e| format!("Whisper 模型加载失败: {}", e))?;
+    /// 语音输入配置是否让本地 Whisper 直接译为英文
+    fn whisper_translate() -> bool {
+        load_config()
+            .map(|config| config.experimental.voice_input.whisper_task == WhisperTask::Translate)
+            .unwrap_or(false)
+    }
 
-        // 执行识别
-        let result = transcriber
-            .transcribe(&audio)
-            .map_err(|e| format!("Whisper 识别失败: {}", e))?;
+    /// 获取缓存的 Whisper 识别器，模型文件或语言与缓存不同时重新加载
+    ///
+    /// 加载期间持有缓存锁，预热和首次识别同时发生时只加载一次
+    fn cached_whisper(
+        model_path: PathBuf,
+        model: voice_core::types::WhisperModel,
+        language: &str,
+    ) -> Result<Arc<WhisperTranscriber>, String> {
+        let mut cache = WHISPER_CACHE.lock();
+        if let Some(cached) = cache
+            .as_ref()
+            .filter(|c| c.model_path == model_path && c.language == language)
+        {
+            return Ok(cached.transcriber.clone());
+        }
+
+        // 先释放旧模型，避免同时占用两份内存
+        *cache = None;
+        let transcriber = profiler()
+            .measure("whisper_model", InitPhase::Deferred, || {
+                WhisperTranscriber::new(model_path.clone(), model, language)
+            })
+            .map_err(|e| format!("Whisper 模型加载失败: {}", e))?;
+        let transcriber = Arc::new(transcriber);
+        *cache = Some(CachedWhisper {
+            model_path,
+            language: language.to_string(),
+            transcriber: transcriber.clone(),
+        });
+        Ok(transcriber)
+    }
+
+    /// 预热本地 Whisper 模型
+    ///
+    /// 凭证为本地 Whisper 时在后台加载模型，录音结束时即可直接识别；其他凭证不做处理
+    pub fn warm_up_local_model(credential: &AsrCredentialEntry) {
+        if !matches!(credential.provider, AsrProviderType::WhisperLocal)
+            || Self::local_engine(credential) != LocalAsrEngine::Whisper
+        {
+            return;
+        }
+        let Some(model_size) = credential.whisper_config.as_ref().map(|c| c.model) else {
+            return;
+        };
+        let language = credential.language.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let result = Self::get_whisper_model_path(&model_size).and_then(|model_path| {
+                Self::cached_whisper(model_path, Self::convert_model_size(&model_size), &language)
+            });
+            if let Err(e) = result {
+                tracing::warn!("[语音识别] 预热本地 Whisper 模型失败: {}", e);
+            }
+        });
+    }
+
+    /// 检查本地识别凭证所需的模型文件
+    ///
+    /// 未配置本地凭证时返回 `None`，模型文件缺失时返回错误
+    pub fn check_local_model() -> Result<Option<PathBuf>, String> {
+        let Some(credential) = Self::get_whisper_local_credential()? else {
+            return Ok(None);
+        };
+        let local_config = credential
+            .whisper_config
+            .as_ref()
+            .ok_or("Whisper 本地配置缺失")?;
+        let path = match Self::local_engine(&credential) {
+            #[cfg(feature = "sensevoice")]
+            LocalAsrEngine::SenseVoice => {
+                models::sensevoice_model_dir(local_config.sensevoice_model)?
+            }
+            LocalAsrEngine::Whisper => Self::get_whisper_model_path(&local_config.model)?,
+        };
+        Ok(Some(path))
+    }
 
-        Ok(result.text)
+    /// 测量本地 Whisper 的识别速度
+    ///
+    /// 使用凭证池中启用的本地 Whisper 凭证配置的模型，返回模型大小和测速结果。
+    /// `audio` 为空时使用内置合成音频
+    pub async fn benchmark_whisper_local(
+        audio: Option<voice_core::types::AudioData>,
+    ) -> Result<(WhisperModelSize, BenchmarkResult), String> {
+        let credential = Self::get_whisper_local_credential()?.ok_or("未配置本地 Whisper 凭证")?;
+        let model_size = credential
+            .whisper_config
+            .as_ref()
+            .ok_or("Whisper 本地配置缺失")?
+            .model;
+        let model_path = Self::get_whisper_model_path(&model_size)?;
+        let model = Self::convert_model_size(&model_size);
+        let language = credential.language;
+
+        let result = tokio::task::spawn_blocking(move || {
+            voice_core::compute::benchmark(audio.as_ref(), || {
+                WhisperTranscriber::new(model_path, model, &language)
+            })
+        })
+        .await
+        .map_err(|e| format!("测速任务异常: {}", e))?
+        .map_err(|e| format!("Whisper 测速失败: {}", e))?;
+
+        tracing::info!(
+            "[语音识别] Whisper {:?} 测速：加载 {:.2}s，识别 {:.1}s 音频耗时 {:.2}s，RTF {:.2}",
+            model_size,
+            result.load_secs,
+            result.audio_secs,
+            result.elapsed_secs,
+            result.rtf
+        );
+        Ok((model_size, result))
     }
 
     /// 获取 Whisper 模型文件路径
@@ -193,14 +634,11 @@ impl AsrService {
             WhisperModelSize::Base => "ggml-base.bin",
             WhisperModelSize::Small => "ggml-small.bin",
             WhisperModelSize::Medium => "ggml-medium.bin",
+            WhisperModelSize::Large => "ggml-large-v3.bin",
         };
 
         // 模型存储目录：~/Library/Application Support/proxycast/models/whisper/
-        let models_dir = dirs::data_dir()
-            .ok_or("无法获取数据目录")?
-            .join("proxycast")
-            .join("models")
-            .join("whisper");
+        let models_dir = models::models_dir(LocalAsrEngine::Whisper)?;
 
         let model_path = models_dir.join(filename);
 
@@ -223,248 +661,364 @@ impl AsrService {
             WhisperModelSize::Base => voice_core::types::WhisperModel::Base,
             WhisperModelSize::Small => voice_core::types::WhisperModel::Small,
             WhisperModelSize::Medium => voice_core::types::WhisperModel::Medium,
+            WhisperModelSize::Large => voice_core::types::WhisperModel::Large,
         }
     }
 
-    /// OpenAI Whisper API 识别
+    /// 由凭证生成云端客户端配置
     ///
-    /// 使用手动构建 multipart/form-data 请求
-    async fn transcribe_openai(
-        credential: &AsrCredentialEntry,
-        audio_data: &[u8],
-        sample_rate: u32,
-    ) -> Result<String, String> {
-        let config = credential.openai_config.as_ref().ok_or("OpenAI 配置缺失")?;
-
-        // 构建 WAV 文件
-        let wav_data = Self::build_wav(audio_data, sample_rate, 1)?;
-
-        // 构建 multipart/form-data 请求体
-        let boundary = format!("----WebKitFormBoundary{}", uuid::Uuid::new_v4().simple());
-        let mut body = Vec::new();
-
-        // 添加 file 字段
-        body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
-        body.extend_from_slice(
-            b"Content-Disposition: form-data; name=\"file\"; filename=\"audio.wav\"\r\n",
-        );
-        body.extend_from_slice(b"Content-Type: audio/wav\r\n\r\n");
-        body.extend_from_slice(&wav_data);
-        body.extend_from_slice(b"\r\n");
-
-        // 添加 model 字段
-        body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
-        body.extend_from_slice(b"Content-Disposition: form-data; name=\"model\"\r\n\r\n");
-        body.extend_from_slice(b"whisper-1\r\n");
-
-        // 添加 language 字段
-        body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
-        body.extend_from_slice(b"Content-Disposition: form-data; name=\"language\"\r\n\r\n");
-        body.extend_from_slice(credential.language.as_bytes());
-        body.extend_from_slice(b"\r\n");
-
-        // 结束边界
-        body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
-
-        // 构建请求
-        let base_url = config
-            .base_url
-            .as_deref()
-            .unwrap_or("https://api.openai.com");
-        let url = format!("{}/v1/audio/transcriptions", base_url);
-
-        let client = reqwest::Client::new();
-        let response = client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", config.api_key))
-            .header(
-                "Content-Type",
-                format!("multipart/form-data; boundary={}", boundary),
-            )
-            .body(body)
-            .send()
-            .await
-            .map_err(|e| format!("请求失败: {}", e))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(format!("OpenAI API 错误: {} - {}", status, body));
+    /// 本地 Whisper 不通过云端客户端识别，返回错误
+    pub fn client_config(credential: &AsrCredentialEntry) -> Result<AsrClientConfig, String> {
+        let language = credential.language.clone();
+        match credential.provider {
+            AsrProviderType::OpenAI => {
+                let config = credential.openai_config.as_ref().ok_or("OpenAI 配置缺失")?;
+                Ok(AsrClientConfig::OpenAI {
+                    api_key: config.api_key.clone(),
+                    base_url: config.base_url.clone(),
+                    language,
+                })
+            }
+            AsrProviderType::CustomWhisper => {
+                let config = credential
+                    .custom_whisper_config
+                    .as_ref()
+                    .ok_or("自建 Whisper 配置缺失")?;
+                Ok(AsrClientConfig::CustomWhisper {
+                    base_url: config.base_url.clone(),
+                    api_key: config.api_key.clone(),
+                    model: config.model.clone(),
+                    timeout_secs: config.timeout_secs,
+                    language,
+                })
+            }
+            AsrProviderType::Baidu => {
+                let config = credential.baidu_config.as_ref().ok_or("百度配置缺失")?;
+                Ok(AsrClientConfig::Baidu {
+                    api_key: config.api_key.clone(),
+                    secret_key: config.secret_key.clone(),
+                })
+            }
+            AsrProviderType::Tencent => {
+                let config = credential.tencent_config.as_ref().ok_or("腾讯云配置缺失")?;
+                Ok(AsrClientConfig::Tencent {
+                    secret_id: config.secret_id.clone(),
+                    secret_key: config.secret_key.clone(),
+                    region: config.region.clone(),
+                    language,
+                })
+            }
+            AsrProviderType::Azure => {
+                let config = credential.azure_config.as_ref().ok_or("Azure 配置缺失")?;
+                Ok(AsrClientConfig::Azure {
+                    subscription_key: config.subscription_key.clone(),
+                    region: config.region.clone(),
+                    locale: config.language.clone(),
+                    profanity: match config.profanity {
+                        AzureProfanityFilter::Masked => AzureProfanity::Masked,
+                        AzureProfanityFilter::Removed => AzureProfanity::Removed,
+                        AzureProfanityFilter::Raw => AzureProfanity::Raw,
+                    },
+                    diarize: config.diarize,
+                    language,
+                })
+            }
+            AsrProviderType::Xunfei => {
+                let config = credential.xunfei_config.as_ref().ok_or("讯飞配置缺失")?;
+                Ok(AsrClientConfig::Xunfei {
+                    app_id: config.app_id.clone(),
+                    api_key: config.api_key.clone(),
+                    api_secret: config.api_secret.clone(),
+                    language,
+                })
+            }
+            AsrProviderType::Deepgram => {
+                let config = credential
+                    .deepgram_config
+                    .as_ref()
+                    .ok_or("Deepgram 配置缺失")?;
+                Ok(AsrClientConfig::Deepgram {
+                    api_key: config.api_key.clone(),
+                    model: config.model.clone(),
+                    diarize: config.diarize,
+                    language,
+                })
+            }
+            AsrProviderType::Volcengine => {
+                let config = credential
+                    .volcengine_config
+                    .as_ref()
+                    .ok_or("火山引擎配置缺失")?;
+                Ok(AsrClientConfig::Volcengine {
+                    app_id: config.app_id.clone(),
+                    access_token: config.access_token.clone(),
+                    cluster: config.cluster.clone(),
+                    language,
+                })
+            }
+            AsrProviderType::Command => {
+                let config = credential
+                    .command_config
+                    .as_ref()
+                    .ok_or("自定义命令配置缺失")?;
+                Ok(AsrClientConfig::Command {
+                    program: config.program.clone(),
+                    args: config.args.clone(),
+                    timeout_secs: config.timeout_secs,
+                    language,
+                })
+            }
+            AsrProviderType::Http => {
+                let config = credential.http_config.as_ref().ok_or("HTTP 接口配置缺失")?;
+                Ok(AsrClientConfig::Http {
+                    url: config.url.clone(),
+                    headers: config.headers.clone(),
+                    text_path: config.text_path.clone(),
+                    upload: match config.upload {
+                        HttpAsrUpload::Raw => HttpUpload::Raw,
+                        HttpAsrUpload::Multipart => HttpUpload::Multipart {
+                            field: config.file_field.clone(),
+                        },
+                    },
+                    timeout_secs: config.timeout_secs,
+                    language,
+                })
+            }
+            AsrProviderType::WhisperLocal => Err("本地 Whisper 不使用云端客户端".to_string()),
         }
+    }
 
-        #[derive(serde::Deserialize)]
-        struct WhisperResponse {
-            text: String,
+    /// 凭证的请求超时，未设置（或为 0）的项使用服务的默认值
+    fn client_timeouts(credential: &AsrCredentialEntry) -> AsrTimeouts {
+        let secs = |value: Option<u64>| value.filter(|s| *s > 0).map(Duration::from_secs);
+        AsrTimeouts {
+            connect: secs(credential.timeouts.connect_secs),
+            send: secs(credential.timeouts.send_secs),
+            receive: secs(credential.timeouts.receive_secs),
         }
+    }
 
-        let result: WhisperResponse = response
-            .json()
-            .await
-            .map_err(|e| format!("解析响应失败: {}", e))?;
-
-        Ok(result.text)
+    /// 凭证的请求限流，未设置时使用服务的默认值（见 [`AsrClientConfig::default_rate_limit`]）
+    fn rate_limit(credential: &AsrCredentialEntry, config: &AsrClientConfig) -> Option<RateLimit> {
+        match credential.rate_limit.requests_per_sec {
+            Some(per_second) if per_second > 0.0 => Some(RateLimit::new(
+                per_second,
+                credential.rate_limit.burst.unwrap_or(1),
+            )),
+            Some(_) => None,
+            None => config.default_rate_limit(),
+        }
     }
 
-    /// 百度语音识别
-    async fn transcribe_baidu(
+    /// 按凭证的请求超时和当前热词表构建客户端，每次调用记录用量
+    ///
+    /// 凭证需要限流时，请求先在该凭证共用的令牌桶排队，排队时间不计入用量耗时
+    fn build_client(
         credential: &AsrCredentialEntry,
-        audio_data: &[u8],
-        sample_rate: u32,
-    ) -> Result<String, String> {
-        let config = credential.baidu_config.as_ref().ok_or("百度配置缺失")?;
+        config: &AsrClientConfig,
+    ) -> Result<Box<dyn AsrClient>, String> {
+        let client = create_client_with_hotwords(
+            config,
+            Self::client_timeouts(credential),
+            super::vocabulary::current(),
+        )
+        .map_err(|e| e.to_string())?;
+        let client: Box<dyn AsrClient> =
+            Box::new(MeteredClient::new(client, credential.id.clone()));
+        Ok(match Self::rate_limit(credential, config) {
+            Some(limit) => {
+                let bucket = RateLimiter::shared().bucket(&credential.id, limit);
+                Box::new(AsrRateLimit::new(client, bucket))
+            }
+            None => client,
+        })
+    }
 
-        // 获取 Access Token
-        let token_url = format!(
-            "https://aip.baidubce.com/oauth/2.0/token?grant_type=client_credentials&client_id={}&client_secret={}",
-            config.api_key, config.secret_key
-        );
+    /// 隐私设置是否禁止使用该凭证（禁止音频离开本机且服务不在本机）
+    pub fn upload_blocked(credential: &AsrCredentialEntry) -> bool {
+        !super::privacy::cloud_allowed()
+            && !Self::client_config(credential).is_ok_and(|config| config.is_local())
+    }
 
-        let client = reqwest::Client::new();
-        let token_resp = client
-            .post(&token_url)
-            .send()
-            .await
-            .map_err(|e| format!("获取 Token 失败: {}", e))?;
+    /// 由凭证构建云端客户端（本地 Whisper 返回错误）
+    pub fn create_client(credential: &AsrCredentialEntry) -> Result<Box<dyn AsrClient>, String> {
+        Self::build_client(credential, &Self::client_config(credential)?)
+    }
 
-        #[derive(serde::Deserialize)]
-        struct TokenResponse {
-            access_token: String,
-        }
+    /// 讯飞录音文件转写配置（未配置 SecretKey 时为 `None`）
+    fn lfasr_config(credential: &AsrCredentialEntry) -> Option<AsrClientConfig> {
+        let config = credential.xunfei_config.as_ref()?;
+        let secret_key = config.lfasr_secret_key.as_ref().filter(|k| !k.is_empty())?;
+        Some(AsrClientConfig::XunfeiLfasr {
+            app_id: config.app_id.clone(),
+            secret_key: secret_key.clone(),
+            language: credential.language.clone(),
+        })
+    }
 
-        let token: TokenResponse = token_resp
-            .json()
-            .await
-            .map_err(|e| format!("解析 Token 失败: {}", e))?;
-
-        // 构建 WAV 并 Base64 编码
-        let wav_data = Self::build_wav(audio_data, sample_rate, 1)?;
-        let speech = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &wav_data);
-
-        #[derive(serde::Serialize)]
-        struct AsrRequest {
-            format: String,
-            rate: u32,
-            channel: u16,
-            cuid: String,
-            token: String,
-            speech: String,
-            len: usize,
+    /// 按录音时长生成云端客户端配置
+    ///
+    /// 超过服务单次时长上限且讯飞配置了录音文件转写时，改用录音文件转写
+    fn cloud_client_config(
+        credential: &AsrCredentialEntry,
+        duration_secs: f32,
+    ) -> Result<AsrClientConfig, String> {
+        let config = Self::client_config(credential)?;
+        if config.capabilities().exceeds_max_duration(duration_secs) {
+            if let Some(lfasr) = Self::lfasr_config(credential) {
+                tracing::info!(
+                    "[语音识别] 录音 {:.1}s 超过讯飞实时听写上限，使用录音文件转写",
+                    duration_secs
+                );
+                return Ok(lfasr);
+            }
         }
+        Ok(config)
+    }
 
-        let request = AsrRequest {
-            format: "wav".to_string(),
-            rate: sample_rate,
-            channel: 1,
-            cuid: "proxycast".to_string(),
-            token: token.access_token,
-            speech,
-            len: wav_data.len(),
+    /// 回退凭证：除所选凭证外启用的云端凭证，按回退优先级排列
+    fn fallback_credentials(credential: &AsrCredentialEntry) -> Vec<AsrCredentialEntry> {
+        let Ok(config) = load_config() else {
+            return Vec::new();
         };
+        let mut credentials: Vec<_> = config
+            .credential_pool
+            .asr
+            .into_iter()
+            .filter(|c| {
+                c.id != credential.id
+                    && !c.disabled
+                    && !matches!(c.provider, AsrProviderType::WhisperLocal)
+            })
+            .collect();
+        credentials.sort_by_key(|c| c.priority);
+        credentials
+    }
 
-        let response = client
-            .post("https://vop.baidu.com/server_api")
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| format!("请求失败: {}", e))?;
-
-        #[derive(serde::Deserialize)]
-        struct AsrResponse {
-            err_no: i32,
-            err_msg: String,
-            #[serde(default)]
-            result: Vec<String>,
+    /// 网络错误的重试策略
+    fn retry_policy(config: &AsrRetryConfig) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: config.max_attempts.max(1),
+            initial_backoff: Duration::from_millis(config.initial_backoff_ms),
+            max_backoff: Duration::from_millis(config.max_backoff_ms),
+            jitter: config.jitter,
+            ..Default::default()
         }
+    }
 
-        let result: AsrResponse = response
-            .json()
-            .await
-            .map_err(|e| format!("解析响应失败: {}", e))?;
+    /// 按识别策略组合云端客户端：所选凭证在前，其他云端凭证按回退优先级排在后面
+    ///
+    /// 每个客户端遇到网络错误时先单独重试。
+    /// 其他凭证配置无效时跳过，所选凭证配置无效时返回错误
+    fn cloud_client(
+        credential: &AsrCredentialEntry,
+        duration_secs: f32,
+    ) -> Result<Box<dyn AsrClient>, String> {
+        let voice_config = load_config()
+            .map(|config| config.experimental.voice_input)
+            .unwrap_or_default();
+        let policy = Self::retry_policy(&voice_config.asr_retry);
+        let with_retry = |credential: &AsrCredentialEntry| -> Result<Box<dyn AsrClient>, String> {
+            let config = Self::cloud_client_config(credential, duration_secs)?;
+            let client = Self::build_client(credential, &config)?;
+            Ok(Box::new(AsrRetry::new(client, policy)))
+        };
 
-        if result.err_no != 0 {
-            return Err(format!(
-                "百度 ASR 错误: {} - {}",
-                result.err_no, result.err_msg
-            ));
+        let mut clients = vec![with_retry(credential)?];
+        for fallback in Self::fallback_credentials(credential) {
+            let client = with_retry(&fallback);
+            match client {
+                Ok(client) => clients.push(client),
+                Err(e) => tracing::warn!(
+                    "[语音识别] 跳过凭证 {}（{:?}）: {}",
+                    fallback.id,
+                    fallback.provider,
+                    e
+                ),
+            }
         }
 
-        Ok(result.result.join(""))
+        let strategy = voice_config.recognition_strategy;
+        let backends: Vec<_> = clients.iter().map(|client| client.name()).collect();
+        tracing::debug!(
+            "[语音识别] 识别策略 {:?}，识别服务: {:?}",
+            strategy,
+            backends
+        );
+        let client: Box<dyn AsrClient> = match strategy {
+            RecognitionStrategy::Fallback => {
+                Box::new(AsrFallbackChain::new(clients).map_err(|e| e.to_string())?)
+            }
+            RecognitionStrategy::Race => {
+                Box::new(AsrRace::new(clients).map_err(|e| e.to_string())?)
+            }
+        };
+        Ok(client)
     }
 
-    /// 讯飞语音识别
+    /// 云端识别
     ///
-    /// 使用 WebSocket 流式识别，支持实时语音转文字
-    async fn transcribe_xunfei(
+    /// 超过服务单次时长上限时，讯飞配置了录音文件转写则整段上传转写，
+    /// 否则按停顿分段调用实时接口后拼接。
+    /// 回退策略下所选服务连不上或鉴权失败时，按回退优先级改用其他云端凭证；
+    /// 竞速策略下同时请求所有云端凭证，采用最先成功的结果
+    async fn transcribe_cloud(
         credential: &AsrCredentialEntry,
         audio_data: &[u8],
         sample_rate: u32,
-    ) -> Result<String, String> {
-        let config = credential.xunfei_config.as_ref().ok_or("讯飞配置缺失")?;
-
+    ) -> Result<TranscribeResult, String> {
         // 将 PCM 字节转换为 i16 采样
         let samples: Vec<i16> = audio_data
             .chunks_exact(2)
             .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]))
             .collect();
-
-        // 创建 AudioData
         let audio = voice_core::types::AudioData::new(samples, sample_rate, 1);
 
-        // 创建讯飞客户端
-        // 讯飞语言代码转换：zh -> zh_cn, en -> en_us
-        let xunfei_language = match credential.language.as_str() {
-            "zh" => "zh_cn".to_string(),
-            "en" => "en_us".to_string(),
-            other => other.to_string(),
+        let client = Self::cloud_client(credential, audio.duration_secs)?;
+        let ranges = match client.capabilities().max_duration_secs {
+            Some(max_secs) if audio.duration_secs > max_secs => {
+                split_at_pauses(&audio.samples, sample_rate, max_secs)
+            }
+            _ => vec![0..audio.samples.len()],
         };
+        if ranges.len() > 1 {
+            tracing::info!(
+                "[语音识别] 录音 {:.1}s 超过{}单次上限，分 {} 段识别",
+                audio.duration_secs,
+                client.name(),
+                ranges.len()
+            );
+        }
 
-        let client = voice_core::asr_client::XunfeiClient::new(
-            config.app_id.clone(),
-            config.api_key.clone(),
-            config.api_secret.clone(),
-        )
-        .with_language(xunfei_language);
+        let mut merged = TranscribeResult {
+            text: String::new(),
+            language: None,
+            confidence: None,
+            segments: Vec::new(),
+            backend: None,
+        };
+        for range in ranges {
+            // 分段时间戳换算为整段录音中的时间
+            let offset = range.start as f32 / sample_rate as f32;
+            let chunk =
+                voice_core::types::AudioData::new(audio.samples[range].to_vec(), sample_rate, 1);
+            let result = client
+                .transcribe(&chunk)
+                .await
+                .map_err(|e| format!("{}识别失败: {}", client.name(), e))?;
+            merged.text.push_str(&result.text);
+            merged.language = merged.language.or(result.language);
+            merged.backend = merged.backend.or(result.backend);
+            merged
+                .segments
+                .extend(result.segments.into_iter().map(|segment| Segment {
+                    start: segment.start + offset,
+                    end: segment.end + offset,
+                    text: segment.text,
+                    speaker: segment.speaker,
+                }));
+        }
 
-        // 调用识别
-        use voice_core::asr_client::AsrClient;
-        let result = client
-            .transcribe(&audio)
-            .await
-            .map_err(|e| format!("讯飞识别失败: {}", e))?;
-
-        Ok(result.text)
-    }
-
-    /// 构建 WAV 文件
-    fn build_wav(pcm_data: &[u8], sample_rate: u32, channels: u16) -> Result<Vec<u8>, String> {
-        let bits_per_sample: u16 = 16;
-        let byte_rate = sample_rate * u32::from(channels) * u32::from(bits_per_sample) / 8;
-        let block_align = channels * bits_per_sample / 8;
-        let data_size = pcm_data.len() as u32;
-        let file_size = 36 + data_size;
-
-        let mut wav = Vec::with_capacity(44 + pcm_data.len());
-
-        // RIFF header
-        wav.extend_from_slice(b"RIFF");
-        wav.extend_from_slice(&file_size.to_le_bytes());
-        wav.extend_from_slice(b"WAVE");
-
-        // fmt chunk
-        wav.extend_from_slice(b"fmt ");
-        wav.extend_from_slice(&16u32.to_le_bytes()); // chunk size
-        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM format
-        wav.extend_from_slice(&channels.to_le_bytes());
-        wav.extend_from_slice(&sample_rate.to_le_bytes());
-        wav.extend_from_slice(&byte_rate.to_le_bytes());
-        wav.extend_from_slice(&block_align.to_le_bytes());
-        wav.extend_from_slice(&bits_per_sample.to_le_bytes());
-
-        // data chunk
-        wav.extend_from_slice(b"data");
-        wav.extend_from_slice(&data_size.to_le_bytes());
-        wav.extend_from_slice(pcm_data);
-
-        Ok(wav)
+        Ok(merged)
     }
 }
@@ -0,0 +1,489 @@
+//! 批量转写
+//!
+//! 转写文件夹中尚未转写的音频（wav / mp3 / m4a / ogg / flac），结果默认以同名 `.txt` 文件保存在音频旁边，
+//! 也可指定导出目录和格式。已存在转写结果的音频视为已转写，重复执行不会重复识别。
+//!
+//! 也可直接转写指定的一组音频文件（[`transcribe_files`]），逐个返回识别结果而不写入文件，
+//! 适合一次转写多段会议录音；进度通过 [`BATCH_PROGRESS_EVENT`] 事件发送给前端。
+//!
+//! 识别结果带说话人时（服务支持区分说话人，或启用了本地说话人区分），
+//! 转写结果按说话人分段输出。
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use voice_core::decode;
+use voice_core::diarization::{self, DiarizationOptions};
+use voice_core::types::TranscribeResult;
+
+use super::asr_service::AsrService;
+use super::config::load_voice_config;
+use crate::config::{AsrCredentialEntry, TranscriptFormat};
+
+/// 批量转写进度事件
+pub const BATCH_PROGRESS_EVENT: &str = "voice-batch-transcribe-progress";
+
+/// 转写结果的保存位置和格式
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptTarget {
+    /// 导出目录（为空保存在音频旁边）
+    pub output_dir: Option<PathBuf>,
+    /// 文件格式
+    pub format: TranscriptFormat,
+}
+
+/// 批量转写结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchTranscribeSummary {
+    /// 成功转写的文件
+    pub transcribed: Vec<String>,
+    /// 转写失败的文件及原因
+    pub failed: Vec<(String, String)>,
+}
+
+/// 单个文件的转写状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStatus {
+    /// 正在识别
+    Transcribing,
+    /// 识别完成
+    Done,
+    /// 识别失败
+    Failed,
+}
+
+/// 批量转写进度事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchProgress {
+    /// 文件序号（从 0 开始）
+    pub index: usize,
+    /// 文件总数
+    pub total: usize,
+    /// 音频路径
+    pub path: String,
+    /// 转写状态
+    pub status: FileStatus,
+    /// 失败原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 单个文件的转写结果
+#[derive(Debug, Clone, Serialize)]
+pub struct FileTranscription {
+    /// 音频路径
+    pub path: String,
+    /// 识别结果（失败时为空）
+    pub result: Option<TranscribeResult>,
+    /// 失败原因
+    pub error: Option<String>,
+}
+
+/// 解析 ASR 凭证（未指定时使用默认凭证）
+pub fn resolve_credential(credential_id: Option<&str>) -> Result<AsrCredentialEntry, String> {
+    match credential_id {
+        Some(id) => {
+            AsrService::get_credential(id)?.ok_or_else(|| format!("ASR 凭证不存在: {}", id))
+        }
+        None => {
+            AsrService::get_default_credential()?.ok_or_else(|| "未配置默认 ASR 凭证".to_string())
+        }
+    }
+}
+
+/// 是否为支持的音频文件
+pub fn is_audio_file(path: &Path) -> bool {
+    decode::is_supported(path)
+}
+
+/// 音频对应的转写结果路径
+pub fn transcript_path(audio: &Path, target: &TranscriptTarget) -> PathBuf {
+    let extension = match target.format {
+        TranscriptFormat::Txt => "txt",
+        TranscriptFormat::Markdown => "md",
+        TranscriptFormat::Json => "json",
+    };
+    match &target.output_dir {
+        Some(dir) => dir
+            .join(audio.file_name().unwrap_or_default())
+            .with_extension(extension),
+        None => audio.with_extension(extension),
+    }
+}
+
+/// 列出文件夹中尚未转写的音频文件（按路径排序）
+pub fn pending_audio_files(
+    folder: &Path,
+    recursive: bool,
+    target: &TranscriptTarget,
+) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    collect_audio_files(folder, recursive, &mut files)?;
+    files.retain(|path| !transcript_path(path, target).exists());
+    files.sort();
+    Ok(files)
+}
+
+fn collect_audio_files(
+    folder: &Path,
+    recursive: bool,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), String> {
+    let entries = std::fs::read_dir(folder)
+        .map_err(|e| format!("读取文件夹失败 {}: {}", folder.display(), e))?;
+
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        if path.is_dir() && recursive {
+            collect_audio_files(&path, recursive, files)?;
+        } else if path.is_file() && is_audio_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 按说话人合并相邻分段，每个说话人轮次一段：`说话人 1：……`
+///
+/// 分段都没有说话人时返回 `None`
+fn speaker_turns(result: &TranscribeResult) -> Option<String> {
+    if result.speaker_count() == 0 {
+        return None;
+    }
+
+    let mut turns: Vec<(Option<u32>, String)> = Vec::new();
+    for segment in &result.segments {
+        match turns.last_mut() {
+            Some((speaker, text)) if *speaker == segment.speaker => text.push_str(&segment.text),
+            _ => turns.push((segment.speaker, segment.text.clone())),
+        }
+    }
+    let lines: Vec<String> = turns
+        .into_iter()
+        .map(|(speaker, text)| match speaker {
+            Some(speaker) => format!("说话人 {}：{}", speaker + 1, text.trim()),
+            None => text.trim().to_string(),
+        })
+        .collect();
+    Some(lines.join("\n\n"))
+}
+
+/// 按格式生成转写结果文件内容
+pub fn render_transcript(
+    audio: &Path,
+    result: &TranscribeResult,
+    format: TranscriptFormat,
+) -> String {
+    let source = audio
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let transcribed_at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let text = speaker_turns(result).unwrap_or_else(|| result.text.clone());
+
+    match format {
+        TranscriptFormat::Txt => text,
+        TranscriptFormat::Markdown => format!(
+            "# {}\n\n> 转写时间：{}\n\n{}\n",
+            source, transcribed_at, text
+        ),
+        TranscriptFormat::Json => {
+            let mut json = serde_json::json!({
+                "source": audio.display().to_string(),
+                "transcribed_at": transcribed_at,
+                "text": result.text,
+            });
+            if !result.segments.is_empty() {
+                json["segments"] = serde_json::json!(result.segments);
+            }
+            json.to_string()
+        }
+    }
+}
+
+/// 写入转写结果，返回结果文件路径
+pub async fn write_transcript(
+    audio: &Path,
+    result: &TranscribeResult,
+    target: &TranscriptTarget,
+) -> Result<PathBuf, String> {
+    let path = transcript_path(audio, target);
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("创建导出目录失败: {}", e))?;
+    }
+    tokio::fs::write(&path, render_transcript(audio, result, target.format))
+        .await
+        .map_err(|e| format!("写入转写结果失败: {}", e))?;
+    Ok(path)
+}
+
+/// 转写单个音频文件
+///
+/// 启用了本地说话人区分且识别服务未返回说话人时，按声纹给分段标注说话人
+pub async fn transcribe_file(
+    path: &Path,
+    credential: &AsrCredentialEntry,
+) -> Result<TranscribeResult, String> {
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("读取音频失败 {}: {}", path.display(), e))?;
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_string);
+    // 解码压缩格式较耗时，放到阻塞线程
+    let audio =
+        tokio::task::spawn_blocking(move || decode::decode_bytes(bytes, extension.as_deref()))
+            .await
+            .map_err(|e| format!("解码音频失败: {}", e))?
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    let pcm: Vec<u8> = audio
+        .samples
+        .iter()
+        .flat_map(|&s| s.to_le_bytes())
+        .collect();
+
+    let mut result = AsrService::transcribe(credential, &pcm, audio.sample_rate).await?;
+
+    let config = load_voice_config()?.diarization;
+    if config.enabled && !result.segments.is_empty() && result.speaker_count() == 0 {
+        let options = DiarizationOptions {
+            max_speakers: config.max_speakers,
+            threshold: config.threshold,
+        };
+        let speakers = diarization::diarize(&audio, &mut result.segments, options);
+        tracing::info!(
+            "[批量转写] 本地区分出 {} 个说话人: {}",
+            speakers,
+            path.display()
+        );
+    }
+    Ok(result)
+}
+
+/// 转写文件夹中所有尚未转写的音频，并写入转写结果
+pub async fn transcribe_folder(
+    folder: &Path,
+    credential: &AsrCredentialEntry,
+    target: &TranscriptTarget,
+) -> Result<BatchTranscribeSummary, String> {
+    let mut summary = BatchTranscribeSummary::default();
+
+    for path in pending_audio_files(folder, false, target)? {
+        let name = path.display().to_string();
+        let result = match transcribe_file(&path, credential).await {
+            Ok(transcript) => write_transcript(&path, &transcript, target).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(_) => {
+                tracing::info!("[批量转写] 完成: {}", name);
+                summary.transcribed.push(name);
+            }
+            Err(e) => {
+                tracing::warn!("[批量转写] 失败: {} - {}", name, e);
+                summary.failed.push((name, e));
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// 依次转写音频文件，每个文件开始和结束时回调进度
+///
+/// 单个文件失败不影响其他文件，返回的结果与 `paths` 顺序一一对应
+pub async fn transcribe_files(
+    paths: &[PathBuf],
+    credential: &AsrCredentialEntry,
+    mut on_progress: impl FnMut(BatchProgress),
+) -> Vec<FileTranscription> {
+    let total = paths.len();
+    let mut transcriptions = Vec::with_capacity(total);
+
+    for (index, path) in paths.iter().enumerate() {
+        let name = path.display().to_string();
+        let progress = |status, error| BatchProgress {
+            index,
+            total,
+            path: name.clone(),
+            status,
+            error,
+        };
+        on_progress(progress(FileStatus::Transcribing, None));
+
+        let result = if is_audio_file(path) {
+            transcribe_file(path, credential).await
+        } else {
+            Err(format!("不支持的音频格式: {}", name))
+        };
+
+        match result {
+            Ok(result) => {
+                tracing::info!("[批量转写] 完成 ({}/{}): {}", index + 1, total, name);
+                on_progress(progress(FileStatus::Done, None));
+                transcriptions.push(FileTranscription {
+                    path: name,
+                    result: Some(result),
+                    error: None,
+                });
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "[批量转写] 失败 ({}/{}): {} - {}",
+                    index + 1,
+                    total,
+                    name,
+                    e
+                );
+                on_progress(progress(FileStatus::Failed, Some(e.clone())));
+                transcriptions.push(FileTranscription {
+                    path: name,
+                    result: None,
+                    error: Some(e),
+                });
+            }
+        }
+    }
+
+    transcriptions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use voice_core::types::Segment;
+
+    fn segment(text: &str, speaker: Option<u32>) -> Segment {
+        Segment {
+            start: 0.0,
+            end: 1.0,
+            text: text.to_string(),
+            speaker,
+        }
+    }
+
+    fn result(segments: Vec<Segment>) -> TranscribeResult {
+        TranscribeResult {
+            text: segments.iter().map(|s| s.text.as_str()).collect(),
+            language: None,
+            confidence: None,
+            segments,
+            backend: None,
+        }
+    }
+
+    #[test]
+    fn test_render_speaker_turns() {
+        let result = result(vec![
+            segment("大家好，", Some(0)),
+            segment("我们开始吧。", Some(0)),
+            segment("好的。", Some(1)),
+            segment("第一项议程。", Some(0)),
+        ]);
+        let text = render_transcript(Path::new("a.wav"), &result, TranscriptFormat::Txt);
+        assert_eq!(
+            text,
+            "说话人 1：大家好，我们开始吧。\n\n说话人 2：好的。\n\n说话人 1：第一项议程。"
+        );
+
+        let json = render_transcript(Path::new("a.wav"), &result, TranscriptFormat::Json);
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["text"], "大家好，我们开始吧。好的。第一项议程。");
+        assert_eq!(json["segments"][2]["speaker"], 1);
+    }
+
+    #[test]
+    fn test_render_without_speakers() {
+        let result = result(vec![segment("你好。", None)]);
+        let text = render_transcript(Path::new("a.wav"), &result, TranscriptFormat::Txt);
+        assert_eq!(text, "你好。");
+    }
+
+    #[test]
+    fn test_pending_audio_files_skips_transcribed() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.wav"), b"").unwrap();
+        std::fs::write(dir.path().join("b.WAV"), b"").unwrap();
+        std::fs::write(dir.path().join("b.txt"), b"done").unwrap();
+        std::fs::write(dir.path().join("c.mp3"), b"").unwrap();
+        std::fs::write(dir.path().join("notes.md"), b"").unwrap();
+
+        let pending = pending_audio_files(dir.path(), false, &TranscriptTarget::default()).unwrap();
+        assert_eq!(
+            pending,
+            vec![dir.path().join("a.wav"), dir.path().join("c.mp3")]
+        );
+    }
+
+    #[test]
+    fn test_pending_audio_files_with_output_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        std::fs::create_dir_all(dir.path().join("sub")).unwrap();
+        std::fs::create_dir_all(&out).unwrap();
+        std::fs::write(dir.path().join("a.wav"), b"").unwrap();
+        std::fs::write(dir.path().join("sub").join("c.wav"), b"").unwrap();
+        std::fs::write(out.join("a.md"), b"done").unwrap();
+
+        let target = TranscriptTarget {
+            output_dir: Some(out),
+            format: TranscriptFormat::Markdown,
+        };
+        assert!(pending_audio_files(dir.path(), false, &target)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            pending_audio_files(dir.path(), true, &target).unwrap(),
+            vec![dir.path().join("sub").join("c.wav")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_files_reports_each_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let notes = dir.path().join("notes.txt");
+        let broken = dir.path().join("broken.wav");
+        std::fs::write(&notes, b"").unwrap();
+        std::fs::write(&broken, b"not a wav").unwrap();
+        let missing = dir.path().join("missing.wav");
+        let credential: AsrCredentialEntry =
+            serde_json::from_value(serde_json::json!({ "id": "a", "provider": "xunfei" })).unwrap();
+
+        let mut events = Vec::new();
+        let paths = vec![notes, broken, missing];
+        let results = transcribe_files(&paths, &credential, |p| events.push(p)).await;
+
+        // 失败的文件不会中断后续文件，结果与输入顺序一致
+        assert_eq!(results.len(), 3);
+        for (result, path) in results.iter().zip(&paths) {
+            assert_eq!(result.path, path.display().to_string());
+            assert!(result.result.is_none());
+            assert!(result.error.is_some());
+        }
+        assert!(results[0]
+            .error
+            .as_ref()
+            .unwrap()
+            .starts_with("不支持的音频格式"));
+
+        let statuses: Vec<_> = events.iter().map(|p| (p.index, p.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (0, FileStatus::Transcribing),
+                (0, FileStatus::Failed),
+                (1, FileStatus::Transcribing),
+                (1, FileStatus::Failed),
+                (2, FileStatus::Transcribing),
+                (2, FileStatus::Failed),
+            ]
+        );
+        assert!(events.iter().all(|p| p.total == 3));
+    }
+}
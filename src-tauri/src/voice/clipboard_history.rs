@@ -0,0 +1,316 @@
+//! 剪贴板历史
+//!
+//! 开启后定期读取剪贴板，记录最近复制的文本，供指令模板通过
+//! `{{clipboard_history[n]}}` 引用（n 从 0 开始，0 为最近一条），
+//! 也可在语音悬浮窗中快速插入。
+//!
+//! 历史记录使用机器 ID 派生的密钥以 ChaCha20-Poly1305 加密后保存在
+//! `~/.proxycast/clipboard_history.dat`，每次写入使用新的随机 nonce。
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use parking_lot::Mutex;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::ClipboardHistoryConfig;
+
+/// 剪贴板轮询间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// 指令模板中引用剪贴板历史的占位符前缀
+const PLACEHOLDER_PREFIX: &str = "{{clipboard_history[";
+
+/// ChaCha20-Poly1305 的 nonce 长度
+const NONCE_LEN: usize = 12;
+
+/// 剪贴板历史记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClipboardEntry {
+    pub text: String,
+    /// 复制时间（毫秒时间戳）
+    pub captured_at: i64,
+}
+
+/// 内存中的历史记录（None 表示尚未从磁盘加载）
+static HISTORY: Mutex<Option<Vec<ClipboardEntry>>> = Mutex::new(None);
+
+/// 监听线程代次，用于停止旧线程
+static WATCH_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 按配置启动或停止剪贴板监听
+pub fn apply_config(config: &ClipboardHistoryConfig) {
+    if config.enabled {
+        start(config.clone());
+    } else {
+        stop();
+    }
+}
+
+/// 启动剪贴板监听（会替换正在运行的监听线程）
+pub fn start(config: ClipboardHistoryConfig) {
+    let generation = WATCH_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+
+    std::thread::spawn(move || {
+        let mut clipboard = match arboard::Clipboard::new() {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::warn!("[剪贴板历史] 无法访问剪贴板: {}", e);
+                return;
+            }
+        };
+        // 启动时已在剪贴板中的内容不记录
+        let mut last_seen = clipboard.get_text().ok();
+        tracing::info!("[剪贴板历史] 开始监听");
+
+        while WATCH_GENERATION.load(Ordering::SeqCst) == generation {
+            std::thread::sleep(POLL_INTERVAL);
+
+            let Ok(text) = clipboard.get_text() else {
+                continue;
+            };
+            if last_seen.as_deref() == Some(text.as_str()) {
+                continue;
+            }
+            last_seen = Some(text.clone());
+            record(&text, &config);
+        }
+
+        tracing::info!("[剪贴板历史] 停止监听");
+    });
+}
+
+/// 停止剪贴板监听
+pub fn stop() {
+    WATCH_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// 获取历史记录（最近在前）
+pub fn entries() -> Vec<ClipboardEntry> {
+    with_history(|history| history.clone())
+}
+
+/// 删除指定位置的记录
+pub fn remove(index: usize) -> Result<(), String> {
+    with_history(|history| {
+        if index >= history.len() {
+            return Err(format!("剪贴板历史不存在第 {} 条", index));
+        }
+        history.remove(index);
+        save(history)
+    })
+}
+
+/// 清空历史记录
+pub fn clear() -> Result<(), String> {
+    with_history(|history| {
+        history.clear();
+        save(history)
+    })
+}
+
+/// 替换模板中的 `{{clipboard_history[n]}}` 占位符
+pub fn expand_placeholders(template: &str) -> String {
+    if !template.contains(PLACEHOLDER_PREFIX) {
+        return template.to_string();
+    }
+    let texts: Vec<String> = entries().into_iter().map(|e| e.text).collect();
+    render_placeholders(template, &texts)
+}
+
+/// 用给定历史替换占位符，越界的引用替换为空字符串
+fn render_placeholders(template: &str, texts: &[String]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find(PLACEHOLDER_PREFIX) {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + PLACEHOLDER_PREFIX.len()..];
+        let parsed = after
+            .find("]}}")
+            .and_then(|end| after[..end].trim().parse::<usize>().ok().map(|n| (n, end)));
+
+        match parsed {
+            Some((n, end)) => {
+                result.push_str(texts.get(n).map(|s| s.as_str()).unwrap_or(""));
+                rest = &after[end + 3..];
+            }
+            None => {
+                // 不是合法占位符，原样保留
+                result.push_str(PLACEHOLDER_PREFIX);
+                rest = after;
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+fn record(text: &str, config: &ClipboardHistoryConfig) {
+    if text.trim().is_empty() {
+        return;
+    }
+    let text: String = text.chars().take(config.max_entry_chars).collect();
+
+    let result = with_history(|history| {
+        push_entry(
+            history,
+            ClipboardEntry {
+                text,
+                captured_at: chrono::Utc::now().timestamp_millis(),
+            },
+            config.max_entries,
+        );
+        save(history)
+    });
+    if let Err(e) = result {
+        tracing::warn!("[剪贴板历史] 保存失败: {}", e);
+    }
+}
+
+/// 插入新记录：重复内容移到最前，超出上限时丢弃最旧的记录
+fn push_entry(history: &mut Vec<ClipboardEntry>, entry: ClipboardEntry, max_entries: usize) {
+    history.retain(|e| e.text != entry.text);
+    history.insert(0, entry);
+    history.truncate(max_entries);
+}
+
+fn with_history<T>(f: impl FnOnce(&mut Vec<ClipboardEntry>) -> T) -> T {
+    let mut guard = HISTORY.lock();
+    let history = guard.get_or_insert_with(|| match load() {
+        Ok(history) => history,
+        Err(e) => {
+            tracing::warn!("[剪贴板历史] 读取失败，将重新记录: {}", e);
+            Vec::new()
+        }
+    });
+    f(history)
+}
+
+fn storage_path() -> Result<PathBuf, String> {
+    Ok(dirs::home_dir()
+        .ok_or_else(|| "无法获取用户主目录".to_string())?
+        .join(".proxycast")
+        .join("clipboard_history.dat"))
+}
+
+fn load() -> Result<Vec<ClipboardEntry>, String> {
+    let path = storage_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let plaintext = decrypt(&encryption_key(), content.trim())?;
+    serde_json::from_slice(&plaintext).map_err(|e| e.to_string())
+}
+
+fn save(history: &[ClipboardEntry]) -> Result<(), String> {
+    let path = storage_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let plaintext = serde_json::to_vec(history).map_err(|e| e.to_string())?;
+    let encoded = encrypt(&encryption_key(), &plaintext)?;
+    std::fs::write(&path, encoded).map_err(|e| e.to_string())
+}
+
+/// 从机器 ID 派生加密密钥
+fn encryption_key() -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(crate::services::api_key_provider_service::machine_id().as_bytes());
+    hasher.update(b"proxycast-clipboard-history-salt");
+    hasher.finalize().into()
+}
+
+/// 加密：ChaCha20-Poly1305，每次使用新的随机 nonce
+///
+/// 输出格式：Base64(nonce || ciphertext || tag)
+fn encrypt(key: &[u8; 32], plaintext: &[u8]) -> Result<String, String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|e| format!("加密失败: {}", e))?;
+
+    let mut output = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    output.extend_from_slice(&nonce);
+    output.extend_from_slice(&ciphertext);
+    Ok(BASE64.encode(output))
+}
+
+fn decrypt(key: &[u8; 32], encoded: &str) -> Result<Vec<u8>, String> {
+    let data = BASE64
+        .decode(encoded)
+        .map_err(|e| format!("Base64 解码失败: {}", e))?;
+    if data.len() < NONCE_LEN {
+        return Err("数据长度无效".to_string());
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "解密失败（密钥不匹配或数据已损坏）".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let key = [7u8; 32];
+        let plaintext = "剪贴板内容 with more than thirty-two bytes of text".as_bytes();
+        let encoded = encrypt(&key, plaintext).unwrap();
+        assert!(!encoded.contains("剪贴板"));
+        assert_eq!(decrypt(&key, &encoded).unwrap(), plaintext);
+        assert!(decrypt(&[8u8; 32], &encoded).is_err());
+
+        // 每次写入使用新的 nonce
+        assert_ne!(encrypt(&key, plaintext).unwrap(), encoded);
+
+        // 篡改密文后无法解密
+        let mut data = BASE64.decode(&encoded).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 1;
+        assert!(decrypt(&key, &BASE64.encode(data)).is_err());
+    }
+
+    #[test]
+    fn test_push_entry_dedup_and_bound() {
+        let entry = |text: &str| ClipboardEntry {
+            text: text.to_string(),
+            captured_at: 0,
+        };
+        let mut history = Vec::new();
+        push_entry(&mut history, entry("a"), 2);
+        push_entry(&mut history, entry("b"), 2);
+        push_entry(&mut history, entry("a"), 2);
+        push_entry(&mut history, entry("c"), 2);
+
+        let texts: Vec<_> = history.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, vec!["c", "a"]);
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let texts = vec!["最近".to_string(), "更早".to_string()];
+        assert_eq!(
+            render_placeholders(
+                "参考：{{clipboard_history[0]}} / {{clipboard_history[ 1 ]}} / {{clipboard_history[5]}}",
+                &texts
+            ),
+            "参考：最近 / 更早 / "
+        );
+        assert_eq!(
+            render_placeholders("{{clipboard_history[x]}}", &texts),
+            "{{clipboard_history[x]}}"
+        );
+    }
+}
@@ -2,11 +2,29 @@
 //!
 //! 提供前端调用的语音输入相关命令
 
-use crate::config::{VoiceInputConfig, VoiceInstruction};
-use tauri::{command, AppHandle};
+use crate::config::{
+    DeviceCalibration, VoiceInputConfig, VoiceInstruction, WatchFolderConfig, WhisperModelSize,
+    DEFAULT_CALIBRATION_DEVICE,
+};
+use crate::database::dao::voice_ab_trials::VoiceAbTrialDao;
+use crate::database::dao::voice_history::VoiceHistoryDao;
+use crate::database::dao::voice_snippets::VoiceSnippetDao;
+use crate::database::DbConnection;
+use crate::models::Prompt;
+use crate::services::prompt_service::PromptService;
+use tauri::{command, AppHandle, Emitter};
+use voice_core::calibration::{CalibrationResult, CalibrationSample};
+use voice_core::types::AudioData;
 
+use super::ab_test::{AbRating, AbTestSummary};
+use super::clipboard_history::ClipboardEntry;
 use super::config;
+use super::health_check::{self, AsrHealthCheck};
+use super::history::VoiceHistoryEntry;
 use super::recording_service::AudioDeviceInfo;
+use super::snippets::VoiceSnippet;
+use super::timeline::{self, SessionTimeline, VoiceStage};
+use super::usage::{self, AsrUsageStats};
 
 /// 获取所有可用的麦克风设备
 #[command]
@@ -42,7 +60,51 @@ pub async fn save_voice_input_config(
         }
     }
 
-    config::save_voice_config(voice_config)
+    // 剪贴板历史随语音输入功能一起启停
+    let clipboard_changed = old_config.enabled != voice_config.enabled
+        || old_config.clipboard_history != voice_config.clipboard_history;
+    if clipboard_changed {
+        if voice_config.enabled {
+            super::clipboard_history::apply_config(&voice_config.clipboard_history);
+        } else {
+            super::clipboard_history::stop();
+        }
+    }
+
+    // 免打扰调度同样随语音输入功能一起启停
+    let quiet_hours_changed = old_config.enabled != voice_config.enabled
+        || old_config.quiet_hours != voice_config.quiet_hours;
+    if quiet_hours_changed {
+        if voice_config.enabled {
+            super::quiet_hours::apply_config(&app, &voice_config.quiet_hours);
+        } else {
+            super::quiet_hours::stop();
+        }
+    }
+
+    if old_config.privacy != voice_config.privacy {
+        super::privacy::apply_config(&voice_config.privacy);
+    }
+
+    let watch_folders_changed = old_config.watch_folders != voice_config.watch_folders;
+    let indicator_changed =
+        old_config.window.recording_indicator != voice_config.window.recording_indicator;
+    config::save_voice_config(voice_config)?;
+
+    if watch_folders_changed {
+        super::watch_folder::reload(&app)?;
+    }
+    // 录音中切换录音指示时立即生效
+    if indicator_changed {
+        super::window::refresh_recording_indicators(&app)?;
+    }
+    Ok(())
+}
+
+/// 获取当前的免打扰状态
+#[command]
+pub async fn get_quiet_mode_status() -> Result<super::quiet_hours::QuietModeStatus, String> {
+    super::quiet_hours::current_status()
 }
 
 /// 获取指令列表
@@ -105,11 +167,21 @@ pub struct TranscribeResult {
     pub text: String,
     /// 使用的 ASR 服务
     pub provider: String,
+    /// 识别出的语言（ISO 639-1 代码，无法判断时为空）
+    pub language: Option<String>,
+    /// 听写历史记录 ID（未开启历史时为空），润色时传回以记录润色结果
+    pub history_id: Option<String>,
+    /// 输出队列占位 ID（只有听写会占位，识别结果为空时为空），输出或放弃时必须传回
+    pub output_id: Option<u64>,
+    /// 听写会话 ID（不经过听写会话的识别为空），润色、输出时传回以记录到对应的时间线
+    pub session_id: Option<String>,
 }
 
 /// 执行语音识别
 #[command]
 pub async fn transcribe_audio(
+    db: State<'_, DbConnection>,
+    recording_service: State<'_, RecordingServiceState>,
     audio_data: Vec<u8>,
     sample_rate: u32,
     credential_id: Option<String>,
@@ -183,44 +255,123 @@ pub async fn transcribe_audio(
         }
     };
 
-    let provider_name = match credential.provider {
-        crate::config::AsrProviderType::WhisperLocal => "本地 Whisper",
-        crate::config::AsrProviderType::OpenAI => "OpenAI Whisper",
-        crate::config::AsrProviderType::Baidu => "百度语音",
-        crate::config::AsrProviderType::Xunfei => "讯飞语音",
-    };
+    let provider_name = provider_display_name(&credential.provider);
     tracing::info!("[语音识别] 使用服务: {}", provider_name);
 
     // 执行识别
-    let text = AsrService::transcribe(&credential, &audio_data, sample_rate).await?;
-    tracing::info!("[语音识别] 识别完成，文本长度: {} 字符", text.len());
+    // 识别中取消录音（Esc）时中止请求
+    let cancel = recording_service.0.lock().recognition_token();
+    timeline::mark(None, VoiceStage::AsrSubmit);
+    let result =
+        AsrService::transcribe_cancellable(&credential, &audio_data, sample_rate, &cancel).await?;
+    // 所选服务不可用时由回退链中的其他服务产出结果
+    let provider_name = result.backend.as_deref().unwrap_or(provider_name);
+    timeline::mark_asr_done(None, provider_name);
+    tracing::info!(
+        "[语音识别] 识别完成，文本长度: {} 字符，语言: {:?}",
+        result.text.len(),
+        result.language
+    );
 
+    let history_id = record_history(
+        &db,
+        None,
+        &result.text,
+        result.language.clone(),
+        provider_name,
+        || pcm_audio(&audio_data, sample_rate),
+    );
+    // 不经过听写会话的识别（引导页演示等）不一定输出，不在输出队列中占位
     Ok(TranscribeResult {
-        text,
+        text: result.text,
         provider: provider_name.to_string(),
+        language: result.language,
+        history_id,
+        output_id: None,
+        session_id: None,
     })
 }
 
+/// ASR 服务的显示名称
+fn provider_display_name(provider: &crate::config::AsrProviderType) -> &'static str {
+    match provider {
+        crate::config::AsrProviderType::WhisperLocal => "本地 Whisper",
+        crate::config::AsrProviderType::OpenAI => "OpenAI Whisper",
+        crate::config::AsrProviderType::CustomWhisper => "自建 Whisper",
+        crate::config::AsrProviderType::Baidu => "百度语音",
+        crate::config::AsrProviderType::Tencent => "腾讯云语音",
+        crate::config::AsrProviderType::Azure => "Azure 语音",
+        crate::config::AsrProviderType::Deepgram => "Deepgram",
+        crate::config::AsrProviderType::Xunfei => "讯飞语音",
+        crate::config::AsrProviderType::Volcengine => "火山引擎",
+        crate::config::AsrProviderType::Command => "自定义命令",
+        crate::config::AsrProviderType::Http => "HTTP 接口",
+    }
+}
+
+/// 获取 ASR 服务能力（未指定凭证时使用默认凭证）
+#[command]
+pub async fn get_asr_capabilities(
+    credential_id: Option<String>,
+) -> Result<voice_core::asr_client::AsrCapabilities, String> {
+    let credential = super::batch::resolve_credential(credential_id.as_deref())?;
+    Ok(super::asr_service::AsrService::capabilities(&credential))
+}
+
 /// 润色文本结果
 #[derive(serde::Serialize)]
 pub struct PolishResult {
     /// 润色后的文本
     pub text: String,
+    /// 指令开启了输出动作且结果包含按键时为动作脚本（`text` 为其中的文字），输出时传回
+    pub script: Option<String>,
     /// 使用的指令
     pub instruction_name: String,
+    /// 预估费用超出上限时未润色，返回原文和本次预估
+    pub over_budget: Option<super::cost::PolishEstimate>,
+    /// 指令 A/B 测试记录 ID（本次润色使用了测试变体时才有），评价时传回
+    pub ab_trial_id: Option<String>,
 }
 
 /// 润色文本
+///
+/// 未指定指令时按识别出的语言选择指令，该语言未配置时使用默认指令；
+/// 启用了指令 A/B 测试时改为在两个变体之间轮换，并返回测试记录 ID 供用户评价。
+/// 传入 `history_id` 时把润色结果记录到对应的听写历史，传入 `session_id` 时记录到对应会话的时间线。
+///
+/// 启用费用上限时先预估费用，超出上限且未经用户确认（`confirmed`）时不润色，
+/// 返回原文并在 `over_budget` 中附带预估
 #[command]
 pub async fn polish_voice_text(
+    app: AppHandle,
+    db: State<'_, DbConnection>,
     text: String,
     instruction_id: Option<String>,
+    language: Option<String>,
+    history_id: Option<String>,
+    confirmed: Option<bool>,
+    session_id: Option<String>,
 ) -> Result<PolishResult, String> {
     let voice_config = config::load_voice_config()?;
 
-    // 获取指令
-    let instruction_id =
-        instruction_id.unwrap_or_else(|| voice_config.processor.default_instruction_id.clone());
+    // 获取指令：ProxyCast 在前台时优先使用工作区指定的指令
+    let instruction_id = instruction_id.or_else(|| {
+        super::workspace_defaults::active(&app)
+            .and_then(|d| d.instruction_id)
+            .filter(|id| voice_config.instructions.iter().any(|i| &i.id == id))
+    });
+    let ab_variant = match instruction_id {
+        Some(_) => None,
+        // 变体指令已被删除时按原规则选择
+        None => super::ab_test::choose(&db, &voice_config.processor.ab_test)
+            .filter(|id| voice_config.instructions.iter().any(|i| &i.id == id)),
+    };
+    let instruction_id = instruction_id.or(ab_variant.clone()).unwrap_or_else(|| {
+        voice_config
+            .processor
+            .instruction_for_language(language.as_deref())
+            .to_string()
+    });
 
     let instruction = voice_config
         .instructions
@@ -228,15 +379,57 @@ pub async fn polish_voice_text(
         .find(|i| i.id == instruction_id)
         .ok_or_else(|| format!("指令不存在: {}", instruction_id))?;
 
+    // 读法改写不依赖 LLM，原文输出时同样生效
+    let normalize = |text: String| {
+        if instruction.itn {
+            voice_core::itn::normalize(&text)
+        } else {
+            text
+        }
+    };
+
     // 如果是原始输出，直接返回
     if instruction_id == "raw" {
         return Ok(PolishResult {
-            text,
+            text: normalize(text),
+            script: None,
             instruction_name: instruction.name.clone(),
+            over_budget: None,
+            ab_trial_id: None,
         });
     }
 
+    // 预估费用，超出上限时输出原文
+    let cost_limit = &voice_config.processor.cost_limit;
+    if cost_limit.enabled && !confirmed.unwrap_or(false) {
+        let estimate = super::cost::estimate(
+            &db,
+            &text,
+            instruction,
+            voice_config.processor.polish_model.as_deref(),
+            cost_limit,
+        );
+        if estimate.over_limit {
+            tracing::info!(
+                "[语音润色] 预估费用 {:?} {:?} 超出上限 {}，跳过润色（输入 {} / 输出 {} tokens）",
+                estimate.cost,
+                estimate.currency,
+                cost_limit.max_cost,
+                estimate.input_tokens,
+                estimate.output_tokens
+            );
+            return Ok(PolishResult {
+                text: normalize(text),
+                script: None,
+                instruction_name: instruction.name.clone(),
+                over_budget: Some(estimate),
+                ab_trial_id: None,
+            });
+        }
+    }
+
     // 调用 LLM 润色
+    timeline::mark(session_id.as_deref(), VoiceStage::PolishStart);
     let polished = super::processor::polish_text(
         &text,
         instruction,
@@ -244,37 +437,251 @@ pub async fn polish_voice_text(
         voice_config.processor.polish_model.as_deref(),
     )
     .await?;
+    let polished_model = polished.model;
+    let polished = normalize(polished.text);
+    timeline::mark(session_id.as_deref(), VoiceStage::PolishDone);
+
+    // 指令输出按键时，按键留在脚本中输出时执行，历史和返回的文本只含文字
+    let (polished, script) = if instruction.actions {
+        super::output_service::split_actions(polished, &instruction.allowed_keys)
+    } else {
+        (polished, None)
+    };
+
+    let ab_trial_id =
+        ab_variant.and_then(|variant| super::ab_test::record(&db, &variant, history_id.clone()));
+
+    if let Some(id) = history_id {
+        let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+        if let Err(e) = VoiceHistoryDao::update_polish(
+            &conn,
+            &id,
+            &polished,
+            &instruction_id,
+            polished_model.as_deref(),
+        ) {
+            tracing::warn!("[听写历史] 记录润色结果失败: {}", e);
+        }
+    }
 
     Ok(PolishResult {
         text: polished,
+        script,
         instruction_name: instruction.name.clone(),
+        over_budget: None,
+        ab_trial_id,
     })
 }
 
+/// 在输出队列中为听写结果占位，保证先说完的先输出
+///
+/// 前端必须用返回的 ID 输出或放弃，否则同一应用后面的输出要等到占位超时
+fn reserve_output(text: &str) -> Option<u64> {
+    (!text.trim().is_empty()).then(|| super::output_service::reserve(super::window::target_app()))
+}
+
 /// 输出文本到系统
 ///
-/// 根据配置的输出模式，将文字输出到当前焦点应用
+/// 根据配置的输出模式，将文字输出到悬浮窗打开前的焦点应用。
+/// 传入识别时返回的 `output_id` 时按听写顺序输出，前面的听写未完成时等待。
+/// `actions` 为 true 时文本是润色返回的动作脚本，模拟输入时执行其中的按键
+#[command]
+pub async fn output_voice_text(
+    app: AppHandle,
+    db: State<'_, DbConnection>,
+    text: String,
+    mode: Option<String>,
+    output_id: Option<u64>,
+    session_id: Option<String>,
+    actions: Option<bool>,
+) -> Result<(), String> {
+    output(
+        &app,
+        &db,
+        &text,
+        mode.as_deref(),
+        output_id,
+        session_id.as_deref(),
+        actions.unwrap_or(false),
+    )
+    .await
+}
+
+/// 当前输出队列
 #[command]
-pub async fn output_voice_text(text: String, mode: Option<String>) -> Result<(), String> {
+pub async fn get_voice_output_queue() -> Result<Vec<super::output_service::QueuedOutputInfo>, String>
+{
+    Ok(super::output_service::snapshot())
+}
+
+/// 取消尚未输出的听写，排在其后的听写继续输出
+#[command]
+pub async fn cancel_voice_output(app: AppHandle, id: u64) -> Result<bool, String> {
+    super::output_service::cancel(&app, id).await
+}
+
+/// 焦点切换后暂停的听写：焦点回到暂停时的应用后在该应用中继续输入
+#[command]
+pub async fn resume_voice_output(app: AppHandle, id: u64) -> Result<bool, String> {
+    super::output_service::resume(&app, id).await
+}
+
+/// 焦点切换后暂停的听写：未输入的部分改为复制到剪贴板
+#[command]
+pub async fn copy_paused_voice_output(app: AppHandle, id: u64) -> Result<bool, String> {
+    super::output_service::copy_paused(&app, id).await
+}
+
+/// 按输出模式（为空时使用配置）输出文字，并结束本次听写的时间线
+///
+/// 输出前出错时释放 `output_id` 的占位，不阻塞后面的听写
+async fn output(
+    app: &AppHandle,
+    db: &DbConnection,
+    text: &str,
+    mode: Option<&str>,
+    output_id: Option<u64>,
+    session_id: Option<&str>,
+    actions: bool,
+) -> Result<(), String> {
+    let (text, output_mode) = match prepare_output(app, db, text, mode) {
+        Ok(prepared) => prepared,
+        Err(e) => {
+            if let Some(id) = output_id {
+                super::output_service::cancel(app, id).await?;
+            }
+            return Err(e);
+        }
+    };
+
+    // 按听写顺序输出
+    match output_id {
+        Some(id) => {
+            super::output_service::submit(app, id, &text, output_mode, actions).await?;
+        }
+        None => super::output_service::enqueue(app, &text, output_mode, actions).await?,
+    }
+    timeline::mark(session_id, VoiceStage::OutputDone);
+    if let Some((history_id, session)) = timeline::finish(session_id) {
+        let saved = db.lock().map_err(|e| e.to_string()).and_then(|conn| {
+            VoiceHistoryDao::update_timeline(&conn, &history_id, &session)
+                .map_err(|e| e.to_string())
+        });
+        if let Err(e) = saved {
+            tracing::warn!("[听写历史] 保存时间线失败: {}", e);
+        }
+    }
+
+    tracing::info!("[语音输出] 文本已输出: {} 字符", text.chars().count());
+    Ok(())
+}
+
+/// 解析输出模式并展开语音片段，返回要输出的文本和模式
+fn prepare_output(
+    app: &AppHandle,
+    db: &DbConnection,
+    text: &str,
+    mode: Option<&str>,
+) -> Result<(String, crate::config::VoiceOutputMode), String> {
     use crate::config::VoiceOutputMode;
 
     // 解析输出模式
-    let output_mode = match mode.as_deref() {
+    let output_mode = match mode {
         Some("type") => VoiceOutputMode::Type,
         Some("clipboard") => VoiceOutputMode::Clipboard,
         Some("both") => VoiceOutputMode::Both,
-        None => {
+        None => match super::workspace_defaults::active(app).and_then(|d| d.output_mode) {
+            // ProxyCast 在前台时使用工作区指定的模式
+            Some(mode) => mode,
             // 使用配置的默认模式
-            let config = config::load_voice_config()?;
-            config.output.mode
-        }
+            None => config::load_voice_config()?.output.mode,
+        },
         Some(other) => return Err(format!("未知的输出模式: {}", other)),
     };
 
-    // 执行输出
-    super::output_service::output_text(&text, output_mode)?;
+    // 免打扰时段内只复制到剪贴板
+    let quiet = super::quiet_hours::current_status()?;
+    let output_mode = quiet.output_mode(output_mode);
 
-    tracing::info!("[语音输出] 文本已输出: {} 字符", text.chars().count());
+    // 展开语音片段触发词
+    let text = expand_snippets(db, text)?;
+    Ok((text, output_mode))
+}
+
+// ============ 听写草稿确认命令 ============
+
+/// 关闭悬浮窗后等待焦点回到原应用的时间（毫秒）
+const DRAFT_FOCUS_DELAY_MS: u64 = 150;
+
+/// 用最近一次听写的录音重新识别
+///
+/// 仅在开启输出前确认时可用，润色由前端按原流程重新执行
+#[command]
+pub async fn retry_voice_draft() -> Result<TranscribeResult, String> {
+    let (session_id, result, credential) = super::draft::retry().await?;
+    let provider = result
+        .backend
+        .as_deref()
+        .unwrap_or(provider_display_name(&credential.provider))
+        .to_string();
+    Ok(TranscribeResult {
+        text: result.text,
+        provider,
+        language: result.language,
+        history_id: None,
+        output_id: None,
+        session_id: Some(session_id),
+    })
+}
+
+/// 确认草稿并输出
+///
+/// 先关闭悬浮窗让焦点回到原来的应用，再按输出模式输出（用户可能已编辑过文本）。
+/// `actions` 为 true 时草稿是动作脚本
+#[command]
+pub async fn accept_voice_draft(
+    app: AppHandle,
+    db: State<'_, DbConnection>,
+    text: String,
+    mode: Option<String>,
+    output_id: Option<u64>,
+    session_id: Option<String>,
+    actions: Option<bool>,
+) -> Result<(), String> {
+    super::draft::discard();
+    if let Err(e) = super::window::close_voice_window(&app) {
+        if let Some(id) = output_id {
+            super::output_service::cancel(&app, id).await?;
+        }
+        return Err(e);
+    }
+    tokio::time::sleep(std::time::Duration::from_millis(DRAFT_FOCUS_DELAY_MS)).await;
+    output(
+        &app,
+        &db,
+        &text,
+        mode.as_deref(),
+        output_id,
+        session_id.as_deref(),
+        actions.unwrap_or(false),
+    )
+    .await
+}
+
+/// 放弃草稿，不输出任何内容，并释放输出队列中的占位
+#[command]
+pub async fn discard_voice_draft(
+    app: AppHandle,
+    output_id: Option<u64>,
+    session_id: Option<String>,
+) -> Result<(), String> {
+    super::draft::discard();
+    if let Some(id) = session_id.as_deref() {
+        timeline::discard(Some(id));
+    }
+    if let Some(id) = output_id {
+        super::output_service::cancel(&app, id).await?;
+    }
     Ok(())
 }
 
@@ -282,18 +689,28 @@ pub async fn output_voice_text(text: String, mode: Option<String>) -> Result<(),
 // 使用独立线程 + channel 通信解决 cpal::Stream 不是 Send 的问题
 
 use super::recording_service::RecordingServiceState;
+use super::session::{Dictation, SessionInfo};
 use tauri::State;
 
 /// 开始录音
+///
+/// 不经过听写会话的普通录音（引导页演示等），听写正在录音时拒绝
 #[command]
 pub async fn start_recording(
+    app: AppHandle,
     recording_service: State<'_, RecordingServiceState>,
     device_id: Option<String>,
 ) -> Result<(), String> {
     tracing::info!("[录音命令] 收到开始录音请求，设备ID: {:?}", device_id);
+    if super::session::is_recording() {
+        return Err("听写正在录音，请结束后再试".to_string());
+    }
     let mut service = recording_service.0.lock();
     let result = service.start(device_id);
     tracing::info!("[录音命令] 开始录音结果: {:?}", result.is_ok());
+    if result.is_ok() {
+        timeline::begin(&app);
+    }
     result
 }
 
@@ -307,8 +724,12 @@ pub async fn start_recording(
 pub async fn stop_recording(
     recording_service: State<'_, RecordingServiceState>,
 ) -> Result<StopRecordingResult, String> {
+    if super::session::is_recording() {
+        return Err("听写正在录音，请使用 stop_streaming_transcription 结束".to_string());
+    }
     let mut service = recording_service.0.lock();
-    let audio = service.stop()?;
+    let audio = service.stop_calibrated()?;
+    timeline::mark(None, VoiceStage::RecordStop);
 
     tracing::info!(
         "[录音命令] 停止录音，样本数: {}, 采样率: {}, 时长: {:.2}s",
@@ -353,13 +774,25 @@ pub struct StopRecordingResult {
 }
 
 /// 取消录音
+///
+/// 有听写正在录音时取消该听写会话（指定 `session_id` 时取消对应会话，识别中的也会放弃），
+/// 否则取消普通录音
 #[command]
 pub async fn cancel_recording(
     recording_service: State<'_, RecordingServiceState>,
+    session_id: Option<String>,
 ) -> Result<(), String> {
+    if super::session::cancel(session_id.as_deref()).await {
+        tracing::info!("[录音命令] 已取消听写会话");
+        return Ok(());
+    }
+
     // 使用 try_lock 避免阻塞，如果锁被占用则跳过
     match recording_service.0.try_lock() {
         Some(mut service) => {
+            if service.is_recording() {
+                timeline::discard(None);
+            }
             service.cancel();
             tracing::info!("[录音命令] 取消录音成功");
         }
@@ -371,6 +804,105 @@ pub async fn cancel_recording(
     Ok(())
 }
 
+/// 开始边录边识别
+///
+/// 为本次听写创建会话：服务支持流式识别时，录音过程中即开始识别并通过
+/// `voice-transcript-partial` 事件推送中间结果；否则只录音，停止后整段识别。
+/// 返回会话信息（含是否启用了流式识别）。
+///
+/// 已有听写正在录音时拒绝；上一次听写仍在识别时可以开始新的听写。
+/// 开始后持续静音超过配置时长会自动取消，并发送 `voice-no-speech` 事件
+#[command]
+pub async fn start_streaming_transcription(
+    app: AppHandle,
+    recording_service: State<'_, RecordingServiceState>,
+    device_id: Option<String>,
+    credential_id: Option<String>,
+) -> Result<SessionInfo, String> {
+    let credential = super::batch::resolve_credential(credential_id.as_deref())?;
+    let voice_config = config::load_voice_config()?;
+    let info = super::session::start(
+        &app,
+        recording_service.0.clone(),
+        device_id.clone(),
+        credential,
+    )
+    .await?;
+    super::no_speech::watch(
+        &app,
+        recording_service.0.clone(),
+        &voice_config,
+        device_id.as_deref(),
+        info.session_id.clone(),
+    );
+    Ok(info)
+}
+
+/// 停止边录边识别并返回最终识别结果
+///
+/// 未指定 `session_id` 时停止正在录音的听写。录音不足 0.5 秒时视为误触，
+/// 取消录音并返回空文本
+#[command]
+pub async fn stop_streaming_transcription(
+    db: State<'_, DbConnection>,
+    session_id: Option<String>,
+) -> Result<TranscribeResult, String> {
+    let Some(dictation) = super::session::stop(session_id.as_deref()).await? else {
+        return Ok(TranscribeResult {
+            text: String::new(),
+            provider: String::new(),
+            language: None,
+            history_id: None,
+            output_id: None,
+            session_id,
+        });
+    };
+
+    let Dictation {
+        session_id,
+        audio,
+        result,
+        credential,
+    } = dictation;
+    let provider = provider_display_name(&credential.provider);
+    timeline::mark_asr_done(Some(&session_id), provider);
+    tracing::info!(
+        "[边录边识别] 识别完成，录音 {:.2}s，文本长度: {} 字符，语言: {:?}",
+        audio.duration_secs,
+        result.text.chars().count(),
+        result.language
+    );
+
+    let history_id = record_history(
+        &db,
+        Some(&session_id),
+        &result.text,
+        result.language.clone(),
+        provider,
+        || audio.clone(),
+    );
+
+    // 输出前需要确认时保留录音，供重试使用
+    if config::load_voice_config()?.output.review_before_output {
+        super::draft::keep(session_id.clone(), audio, credential);
+    }
+    let output_id = reserve_output(&result.text);
+    Ok(TranscribeResult {
+        text: result.text,
+        provider: provider.to_string(),
+        language: result.language,
+        history_id,
+        output_id,
+        session_id: Some(session_id),
+    })
+}
+
+/// 获取进行中的听写会话
+#[command]
+pub async fn get_voice_sessions() -> Result<Vec<SessionInfo>, String> {
+    Ok(super::session::sessions())
+}
+
 /// 录音状态
 #[derive(serde::Serialize)]
 pub struct RecordingStatus {
@@ -378,6 +910,14 @@ pub struct RecordingStatus {
     pub is_recording: bool,
     /// 当前音量级别（0-100）
     pub volume: u32,
+    /// 均方根电平（dBFS）
+    pub rms_dbfs: f32,
+    /// 峰值电平（dBFS）
+    pub peak_dbfs: f32,
+    /// 当前是否削波
+    pub clipping: bool,
+    /// 本次录音累计削波的采样数
+    pub clipped_samples: u64,
     /// 录音时长（秒）
     pub duration: f32,
 }
@@ -388,16 +928,702 @@ pub async fn get_recording_status(
     recording_service: State<'_, RecordingServiceState>,
 ) -> Result<RecordingStatus, String> {
     let service = recording_service.0.lock();
+    let level = service.get_level();
     let status = RecordingStatus {
         is_recording: service.is_recording(),
-        volume: service.get_volume(),
+        volume: level.percent(),
+        rms_dbfs: level.rms_dbfs,
+        peak_dbfs: level.peak_dbfs,
+        clipping: level.clipping,
+        clipped_samples: service.get_clipped_samples(),
         duration: service.get_duration(),
     };
     tracing::debug!(
-        "[录音命令] 获取状态: is_recording={}, volume={}, duration={:.2}",
+        "[录音命令] 获取状态: is_recording={}, volume={}, rms={:.1}dBFS, peak={:.1}dBFS, duration={:.2}",
         status.is_recording,
         status.volume,
+        status.rms_dbfs,
+        status.peak_dbfs,
         status.duration
     );
     Ok(status)
 }
+
+// ============ 会话时间线命令 ============
+
+/// 获取最近听写的各阶段时间线（最近在前）
+#[command]
+pub async fn get_voice_session_timelines() -> Result<Vec<SessionTimeline>, String> {
+    Ok(timeline::sessions())
+}
+
+/// 清空听写时间线历史
+#[command]
+pub async fn clear_voice_session_timelines() -> Result<(), String> {
+    timeline::clear();
+    Ok(())
+}
+
+// ============ 麦克风测试命令 ============
+
+/// 麦克风测试默认录音时长（秒）
+const MIC_TEST_DEFAULT_SECS: f32 = 3.0;
+/// 麦克风测试录音时长范围（秒）
+const MIC_TEST_MIN_SECS: f32 = 1.0;
+const MIC_TEST_MAX_SECS: f32 = 10.0;
+/// 峰值低于此值视为没有录到声音（dBFS）
+const MIC_TEST_SILENT_PEAK_DBFS: f32 = -50.0;
+
+/// 麦克风测试结果
+#[derive(serde::Serialize)]
+pub struct MicrophoneTestResult {
+    /// 录音（WAV 格式，Base64 编码），前端可直接回放
+    pub audio_base64: String,
+    /// 采样率
+    pub sample_rate: u32,
+    /// 整段录音的均方根电平（dBFS）
+    pub rms_dbfs: f32,
+    /// 电平统计（中位数约为环境噪声，90 分位约为说话电平）
+    pub stats: CalibrationSample,
+    /// 削波的采样数
+    pub clipped_samples: u64,
+    /// 是否几乎没有录到声音（麦克风静音或没有权限）
+    pub silent: bool,
+}
+
+/// 录制一段测试音频，返回录音和电平统计
+///
+/// 录音已应用设备的校准增益，回放听到的就是识别时使用的声音
+#[command]
+pub async fn test_microphone(
+    recording_service: State<'_, RecordingServiceState>,
+    seconds: Option<f32>,
+    device_id: Option<String>,
+) -> Result<MicrophoneTestResult, String> {
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+    let seconds = seconds
+        .unwrap_or(MIC_TEST_DEFAULT_SECS)
+        .clamp(MIC_TEST_MIN_SECS, MIC_TEST_MAX_SECS);
+    {
+        let mut service = recording_service.0.lock();
+        if service.is_recording() {
+            return Err("正在录音，请结束后再测试麦克风".to_string());
+        }
+        service.start(device_id.clone())?;
+    }
+    tracing::info!(
+        "[麦克风测试] 开始录音，设备: {:?}，时长: {:.1}s",
+        device_id,
+        seconds
+    );
+
+    tokio::time::sleep(std::time::Duration::from_secs_f32(seconds)).await;
+    let (audio, clipped_samples) = {
+        let mut service = recording_service.0.lock();
+        let clipped_samples = service.get_clipped_samples();
+        (service.stop_calibrated()?, clipped_samples)
+    };
+
+    let stats =
+        CalibrationSample::analyze(&audio.samples, audio.sample_rate).map_err(|e| e.to_string())?;
+    let level = voice_core::AudioLevel::measure_i16(&audio.samples);
+    let silent = stats.peak_dbfs < MIC_TEST_SILENT_PEAK_DBFS;
+    tracing::info!(
+        "[麦克风测试] 完成，RMS: {:.1}dBFS，峰值: {:.1}dBFS，削波: {}",
+        level.rms_dbfs,
+        stats.peak_dbfs,
+        clipped_samples
+    );
+
+    Ok(MicrophoneTestResult {
+        audio_base64: BASE64.encode(audio.to_wav_bytes()),
+        sample_rate: audio.sample_rate,
+        rms_dbfs: level.rms_dbfs,
+        stats,
+        clipped_samples,
+        silent,
+    })
+}
+
+// ============ 本地推理诊断命令 ============
+
+/// 本地推理算力诊断结果
+#[derive(serde::Serialize)]
+pub struct ComputeDiagnostics {
+    /// 加速后端、GPU 和 CPU 信息
+    #[serde(flatten)]
+    pub info: voice_core::ComputeInfo,
+    /// 测速使用的 Whisper 模型
+    pub model: Option<WhisperModelSize>,
+    /// 识别速度测试结果（未测速时为空）
+    pub benchmark: Option<voice_core::BenchmarkResult>,
+}
+
+/// 获取本地推理算力诊断信息
+///
+/// `benchmark` 为 true 时用本地 Whisper 凭证的模型测量识别速度，需要加载模型，
+/// 耗时数秒到数十秒。`record_ms` 不为空时先录制用户朗读的一段话用于测速，
+/// 否则使用内置合成音频（实时率偏乐观）
+#[command]
+pub async fn get_compute_diagnostics(
+    recording_service: State<'_, RecordingServiceState>,
+    benchmark: Option<bool>,
+    record_ms: Option<u64>,
+    device_id: Option<String>,
+) -> Result<ComputeDiagnostics, String> {
+    // nvidia-smi 等系统命令可能耗时较长，不阻塞异步运行时
+    let info = tokio::task::spawn_blocking(voice_core::compute::compute_info)
+        .await
+        .map_err(|e| format!("算力检测任务异常: {}", e))?;
+    let (model, benchmark) = if benchmark.unwrap_or(false) {
+        let audio = match record_ms {
+            Some(ms) => {
+                tracing::info!("[算力诊断] 录制 {}ms 朗读音频用于测速", ms);
+                recording_service.0.lock().start(device_id)?;
+                tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
+                Some(recording_service.0.lock().stop()?)
+            }
+            None => None,
+        };
+        let (model, result) =
+            super::asr_service::AsrService::benchmark_whisper_local(audio).await?;
+        (Some(model), Some(result))
+    } else {
+        (None, None)
+    };
+
+    Ok(ComputeDiagnostics {
+        info,
+        model,
+        benchmark,
+    })
+}
+
+// ============ 本地模型命令 ============
+
+/// 列出可下载的本地识别模型
+#[command]
+pub async fn list_local_asr_models() -> Result<Vec<super::models::LocalModelInfo>, String> {
+    super::models::list()
+}
+
+/// 下载本地识别模型，进度通过 `voice-model-download-progress` 事件发送
+#[command]
+pub async fn download_local_asr_model(app: AppHandle, id: String) -> Result<(), String> {
+    super::models::download(&app, &id).await
+}
+
+/// 删除已下载的本地识别模型
+#[command]
+pub async fn delete_local_asr_model(id: String) -> Result<(), String> {
+    super::models::delete(&id)
+}
+
+// ============ 电平校准命令 ============
+
+/// 录制一段校准音频并返回统计结果
+///
+/// 校准向导先以默认时长录制环境噪声，再录制用户说话
+#[command]
+pub async fn record_calibration_sample(
+    recording_service: State<'_, RecordingServiceState>,
+    device_id: Option<String>,
+    duration_ms: Option<u64>,
+) -> Result<CalibrationSample, String> {
+    let duration_ms = duration_ms.unwrap_or(voice_core::calibration::AMBIENT_DURATION_MS);
+    tracing::info!(
+        "[电平校准] 开始录制校准音频，设备: {:?}，时长: {}ms",
+        device_id,
+        duration_ms
+    );
+
+    recording_service.0.lock().start(device_id)?;
+    tokio::time::sleep(std::time::Duration::from_millis(duration_ms)).await;
+    let audio = recording_service.0.lock().stop()?;
+
+    CalibrationSample::analyze(&audio.samples, audio.sample_rate).map_err(|e| e.to_string())
+}
+
+/// 根据环境噪声和说话两段统计计算校准结果，并保存到对应设备
+#[command]
+pub async fn save_microphone_calibration(
+    device_id: Option<String>,
+    ambient: CalibrationSample,
+    speech: CalibrationSample,
+) -> Result<DeviceCalibration, String> {
+    let result = CalibrationResult::compute(&ambient, &speech).map_err(|e| e.to_string())?;
+    if result.is_noisy() {
+        tracing::warn!(
+            "[电平校准] 信噪比偏低: {:.1}dB，建议在安静环境中使用",
+            result.snr_db
+        );
+    }
+
+    let calibration = DeviceCalibration {
+        gain_db: result.recommended_gain_db,
+        silence_threshold_dbfs: result.silence_threshold_dbfs,
+        noise_floor_dbfs: result.noise_floor_dbfs,
+        speech_level_dbfs: result.speech_level_dbfs,
+        calibrated_at: chrono::Utc::now().timestamp_millis(),
+    };
+
+    let key = device_id.unwrap_or_else(|| DEFAULT_CALIBRATION_DEVICE.to_string());
+    let mut voice_config = config::load_voice_config()?;
+    voice_config
+        .calibrations
+        .insert(key.clone(), calibration.clone());
+    config::save_voice_config(voice_config)?;
+
+    tracing::info!(
+        "[电平校准] 设备 {} 校准完成: 增益 {:.1}dB，静音阈值 {:.1}dBFS",
+        key,
+        calibration.gain_db,
+        calibration.silence_threshold_dbfs
+    );
+    Ok(calibration)
+}
+
+/// 清除设备的校准结果
+#[command]
+pub async fn clear_microphone_calibration(device_id: Option<String>) -> Result<(), String> {
+    let key = device_id.unwrap_or_else(|| DEFAULT_CALIBRATION_DEVICE.to_string());
+    let mut voice_config = config::load_voice_config()?;
+    if voice_config.calibrations.remove(&key).is_some() {
+        config::save_voice_config(voice_config)?;
+    }
+    Ok(())
+}
+
+/// 检查语音输入相关的系统权限
+#[command]
+pub async fn check_voice_permissions() -> Result<super::permissions::PermissionReport, String> {
+    let report = super::permissions::check_all();
+    tracing::info!(
+        "[权限检查] 平台: {}，必需权限就绪: {}",
+        report.platform,
+        report.all_required_granted
+    );
+    Ok(report)
+}
+
+/// 打开指定权限的系统设置页面
+#[command]
+pub async fn open_permission_settings(
+    kind: super::permissions::PermissionKind,
+) -> Result<(), String> {
+    super::permissions::open_settings(kind)
+}
+
+/// 获取监听文件夹列表
+#[command]
+pub async fn get_watch_folders() -> Result<Vec<WatchFolderConfig>, String> {
+    Ok(config::load_voice_config()?.watch_folders)
+}
+
+/// 新增或更新监听文件夹
+#[command]
+pub async fn save_watch_folder(app: AppHandle, folder: WatchFolderConfig) -> Result<(), String> {
+    if !std::path::Path::new(&folder.path).is_dir() {
+        return Err(format!("文件夹不存在: {}", folder.path));
+    }
+
+    let mut voice_config = config::load_voice_config()?;
+    match voice_config
+        .watch_folders
+        .iter_mut()
+        .find(|f| f.id == folder.id)
+    {
+        Some(existing) => *existing = folder,
+        None => voice_config.watch_folders.push(folder),
+    }
+    config::save_voice_config(voice_config)?;
+
+    super::watch_folder::reload(&app)
+}
+
+/// 删除监听文件夹
+#[command]
+pub async fn delete_watch_folder(app: AppHandle, id: String) -> Result<(), String> {
+    let mut voice_config = config::load_voice_config()?;
+    voice_config.watch_folders.retain(|f| f.id != id);
+    config::save_voice_config(voice_config)?;
+
+    super::watch_folder::reload(&app)
+}
+
+/// 立即扫描监听文件夹，返回加入转写队列的文件数
+#[command]
+pub async fn rescan_watch_folder(app: AppHandle, id: String) -> Result<usize, String> {
+    let folder = config::load_voice_config()?
+        .watch_folders
+        .into_iter()
+        .find(|f| f.id == id)
+        .ok_or_else(|| format!("监听文件夹不存在: {}", id))?;
+
+    super::watch_folder::rescan(&app, &folder)
+}
+
+/// 批量转写音频文件（wav / mp3 / m4a / ogg / flac），返回每个文件的识别结果
+///
+/// 依次识别，每个文件开始和结束时发送 `voice-batch-transcribe-progress` 事件；
+/// 单个文件失败不影响其他文件。未指定凭证时使用默认凭证
+#[command]
+pub async fn transcribe_files(
+    app: AppHandle,
+    paths: Vec<std::path::PathBuf>,
+    credential_id: Option<String>,
+) -> Result<Vec<super::batch::FileTranscription>, String> {
+    let credential = super::batch::resolve_credential(credential_id.as_deref())?;
+    Ok(
+        super::batch::transcribe_files(&paths, &credential, |progress| {
+            let _ = app.emit(super::batch::BATCH_PROGRESS_EVENT, &progress);
+        })
+        .await,
+    )
+}
+
+/// 获取剪贴板历史（最近在前）
+#[command]
+pub async fn get_clipboard_history() -> Result<Vec<ClipboardEntry>, String> {
+    Ok(super::clipboard_history::entries())
+}
+
+/// 删除一条剪贴板历史
+#[command]
+pub async fn delete_clipboard_history_entry(index: usize) -> Result<(), String> {
+    super::clipboard_history::remove(index)
+}
+
+/// 清空剪贴板历史
+#[command]
+pub async fn clear_clipboard_history() -> Result<(), String> {
+    super::clipboard_history::clear()
+}
+
+// ============ 语音片段命令 ============
+
+/// 获取所有语音片段
+#[command]
+pub async fn get_voice_snippets(db: State<'_, DbConnection>) -> Result<Vec<VoiceSnippet>, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    VoiceSnippetDao::list(&conn).map_err(|e| format!("获取语音片段失败: {}", e))
+}
+
+/// 新增或更新语音片段（id 为空时新建）
+#[command]
+pub async fn save_voice_snippet(
+    db: State<'_, DbConnection>,
+    mut snippet: VoiceSnippet,
+) -> Result<VoiceSnippet, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    let existing = VoiceSnippetDao::list(&conn).map_err(|e| e.to_string())?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    if snippet.id.is_empty() {
+        snippet.id = uuid::Uuid::new_v4().to_string();
+    }
+    snippet.trigger = snippet.trigger.trim().to_string();
+    snippet.created_at = existing
+        .iter()
+        .find(|s| s.id == snippet.id)
+        .map(|s| s.created_at)
+        .unwrap_or(now);
+    snippet.updated_at = now;
+    super::snippets::validate(&snippet, &existing)?;
+
+    VoiceSnippetDao::upsert(&conn, &snippet).map_err(|e| format!("保存语音片段失败: {}", e))?;
+    Ok(snippet)
+}
+
+/// 删除语音片段
+#[command]
+pub async fn delete_voice_snippet(db: State<'_, DbConnection>, id: String) -> Result<bool, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    VoiceSnippetDao::delete(&conn, &id).map_err(|e| format!("删除语音片段失败: {}", e))
+}
+
+/// 预览文本展开语音片段后的结果
+#[command]
+pub async fn expand_voice_snippets(
+    db: State<'_, DbConnection>,
+    text: String,
+) -> Result<String, String> {
+    expand_snippets(&db, &text)
+}
+
+fn expand_snippets(db: &DbConnection, text: &str) -> Result<String, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    let snippets = VoiceSnippetDao::list(&conn).map_err(|e| e.to_string())?;
+    Ok(super::snippets::expand(text, &snippets))
+}
+
+// ============ 识别热词命令 ============
+
+/// 获取工作区的识别热词，未指定工作区时为默认工作区
+#[command]
+pub async fn get_voice_vocabulary(
+    db: State<'_, DbConnection>,
+    workspace_id: Option<String>,
+) -> Result<Vec<String>, String> {
+    super::vocabulary::get(&db, workspace_id.as_deref())
+}
+
+/// 保存工作区的识别热词，返回去重整理后的热词
+#[command]
+pub async fn set_voice_vocabulary(
+    db: State<'_, DbConnection>,
+    workspace_id: Option<String>,
+    words: Vec<String>,
+) -> Result<Vec<String>, String> {
+    super::vocabulary::set(&db, workspace_id.as_deref(), words)
+}
+
+/// 获取工作区的听写默认值（输出方式和润色指令），未指定工作区时为默认工作区
+#[command]
+pub async fn get_workspace_voice_defaults(
+    db: State<'_, DbConnection>,
+    workspace_id: Option<String>,
+) -> Result<super::workspace_defaults::WorkspaceVoiceDefaults, String> {
+    super::workspace_defaults::get(&db, workspace_id.as_deref())
+}
+
+/// 保存工作区的听写默认值
+#[command]
+pub async fn set_workspace_voice_defaults(
+    db: State<'_, DbConnection>,
+    workspace_id: Option<String>,
+    defaults: super::workspace_defaults::WorkspaceVoiceDefaults,
+) -> Result<(), String> {
+    super::workspace_defaults::set(&db, workspace_id.as_deref(), defaults)
+}
+
+// ============ 听写历史命令 ============
+
+/// 把识别结果记录到听写历史，返回记录 ID
+///
+/// 开启了工作区同步时，同时在后台写入默认工作区（需要保存录音时才调用 `audio`）。
+/// 记录关联到 `session_id` 的时间线，输出完成时保存时间线。
+/// 历史关闭、文本为空或写入失败时返回空，不影响听写流程
+fn record_history(
+    db: &DbConnection,
+    session_id: Option<&str>,
+    text: &str,
+    language: Option<String>,
+    provider: &str,
+    audio: impl FnOnce() -> AudioData,
+) -> Option<String> {
+    let history = config::load_voice_config().ok()?.history;
+    if !history.enabled || text.trim().is_empty() {
+        return None;
+    }
+
+    let entry = VoiceHistoryEntry::new(text.to_string(), language, Some(provider.to_string()));
+    let saved = {
+        let conn = db.lock().ok()?;
+        VoiceHistoryDao::insert(&conn, &entry)
+            .and_then(|_| VoiceHistoryDao::prune(&conn, history.max_entries))
+    };
+    match saved {
+        Ok(_) => {
+            super::history_sync::spawn_sync(db, &entry, audio, &history.workspace_sync);
+            timeline::attach_history(session_id, &entry.id);
+            Some(entry.id)
+        }
+        Err(e) => {
+            tracing::warn!("[听写历史] 保存失败: {}", e);
+            None
+        }
+    }
+}
+
+/// 16bit 小端 PCM 字节转为单声道音频
+fn pcm_audio(bytes: &[u8], sample_rate: u32) -> AudioData {
+    let samples = bytes
+        .chunks_exact(2)
+        .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]))
+        .collect();
+    AudioData::new(samples, sample_rate, 1)
+}
+
+/// 获取最近的听写历史（最近在前），包含重新润色生成的版本
+#[command]
+pub async fn get_voice_history(
+    db: State<'_, DbConnection>,
+    limit: Option<usize>,
+) -> Result<Vec<VoiceHistoryEntry>, String> {
+    let limit = match limit {
+        Some(limit) => limit,
+        None => config::load_voice_config()?.history.max_entries,
+    };
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    VoiceHistoryDao::list_recent(&conn, limit).map_err(|e| format!("获取听写历史失败: {}", e))
+}
+
+/// 用其他指令（和模型）重新润色历史记录的原始文本
+///
+/// 结果作为新版本保存，原记录不变。未指定模型时使用配置的润色模型
+#[command]
+pub async fn repolish_voice_history(
+    db: State<'_, DbConnection>,
+    id: String,
+    instruction_id: String,
+    model: Option<String>,
+) -> Result<VoiceHistoryEntry, String> {
+    let voice_config = config::load_voice_config()?;
+    let entry = {
+        let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+        VoiceHistoryDao::get(&conn, &id)
+            .map_err(|e| format!("获取听写历史失败: {}", e))?
+            .ok_or_else(|| format!("听写历史不存在: {}", id))?
+    };
+
+    let instruction = voice_config
+        .instructions
+        .iter()
+        .find(|i| i.id == instruction_id)
+        .ok_or_else(|| format!("指令不存在: {}", instruction_id))?;
+
+    let model = model.or(voice_config.processor.polish_model.clone());
+    // 记录实际响应请求的模型，而非请求时指定的模型
+    let (text, model) = if instruction_id == "raw" {
+        (entry.raw_text.clone(), None)
+    } else {
+        let polished = super::processor::polish_text(
+            &entry.raw_text,
+            instruction,
+            voice_config.processor.polish_provider.as_deref(),
+            model.as_deref(),
+        )
+        .await?;
+        (polished.text, polished.model)
+    };
+    tracing::info!(
+        "[听写历史] 重新润色 {}，指令: {}，模型: {:?}",
+        entry.root_id(),
+        instruction.name,
+        model
+    );
+
+    let variant = entry.variant(text, instruction_id, model);
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    VoiceHistoryDao::insert(&conn, &variant).map_err(|e| format!("保存听写历史失败: {}", e))?;
+    Ok(variant)
+}
+
+/// 删除听写历史记录（删除原始听写时连同其版本一起删除）
+#[command]
+pub async fn delete_voice_history_entry(
+    db: State<'_, DbConnection>,
+    id: String,
+) -> Result<bool, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    VoiceHistoryDao::delete(&conn, &id).map_err(|e| format!("删除听写历史失败: {}", e))
+}
+
+/// 清空听写历史
+#[command]
+pub async fn clear_voice_history(db: State<'_, DbConnection>) -> Result<(), String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    VoiceHistoryDao::clear(&conn).map_err(|e| format!("清空听写历史失败: {}", e))
+}
+
+/// 把听写存为提示词库中 `app` 的提示词（不启用）
+///
+/// 传入 `text` 时保存该文本（如编辑过的听写），否则保存听写历史 `history_id` 的润色结果
+#[command]
+pub async fn save_voice_as_prompt(
+    db: State<'_, DbConnection>,
+    app: String,
+    text: Option<String>,
+    history_id: Option<String>,
+    name: Option<String>,
+) -> Result<Prompt, String> {
+    let text = match (text, history_id) {
+        (Some(text), _) => text,
+        (None, Some(id)) => {
+            let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+            VoiceHistoryDao::get(&conn, &id)
+                .map_err(|e| format!("获取听写历史失败: {}", e))?
+                .ok_or_else(|| format!("听写历史不存在: {}", id))?
+                .text
+        }
+        (None, None) => return Err("未指定要保存的听写".to_string()),
+    };
+
+    let prompt = super::prompt_export::to_prompt(&text, &app, name.as_deref())?;
+    PromptService::add(&db, prompt.clone())?;
+    tracing::info!("[听写历史] 已存为 {} 的提示词: {}", app, prompt.name);
+    Ok(prompt)
+}
+
+// ============ 指令 A/B 测试命令 ============
+
+/// 评价一次 A/B 测试润色的结果（重复评价时覆盖）
+#[command]
+pub async fn rate_voice_ab_trial(
+    db: State<'_, DbConnection>,
+    trial_id: String,
+    rating: AbRating,
+) -> Result<bool, String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    VoiceAbTrialDao::rate(
+        &conn,
+        &trial_id,
+        rating,
+        chrono::Utc::now().timestamp_millis(),
+    )
+    .map_err(|e| format!("保存评价失败: {}", e))
+}
+
+/// 获取当前两个变体的 A/B 测试汇总（未配置两个不同的变体时为空）
+#[command]
+pub async fn get_voice_ab_summary(
+    db: State<'_, DbConnection>,
+) -> Result<Option<AbTestSummary>, String> {
+    let ab_test = config::load_voice_config()?.processor.ab_test;
+    if ab_test.variant_a.is_empty()
+        || ab_test.variant_b.is_empty()
+        || ab_test.variant_a == ab_test.variant_b
+    {
+        return Ok(None);
+    }
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    let stats = |id: &str| {
+        VoiceAbTrialDao::stats(&conn, id).map_err(|e| format!("获取 A/B 测试结果失败: {}", e))
+    };
+    Ok(Some(AbTestSummary::new(
+        stats(&ab_test.variant_a)?,
+        stats(&ab_test.variant_b)?,
+    )))
+}
+
+/// 清空 A/B 测试记录（修改变体指令后重新开始统计）
+#[command]
+pub async fn clear_voice_ab_trials(db: State<'_, DbConnection>) -> Result<(), String> {
+    let conn = db.lock().map_err(|e| format!("数据库锁定失败: {}", e))?;
+    VoiceAbTrialDao::clear(&conn).map_err(|e| format!("清空 A/B 测试记录失败: {}", e))
+}
+
+// ============ ASR 用量命令 ============
+
+/// 按凭证汇总 ASR 用量，`since` 为毫秒时间戳，默认从本月第一天起
+#[command]
+pub async fn get_asr_usage_stats(
+    db: State<'_, DbConnection>,
+    since: Option<i64>,
+) -> Result<Vec<AsrUsageStats>, String> {
+    usage::stats(&db, since.unwrap_or_else(usage::month_start))
+}
+
+// ============ ASR 凭证测试命令 ============
+
+/// 用内置测试音频调用凭证对应的识别服务，返回耗时和识别文本
+#[command]
+pub async fn test_asr_credential(id: String) -> Result<AsrHealthCheck, String> {
+    use super::asr_service::AsrService;
+
+    let credential =
+        AsrService::get_credential(&id)?.ok_or_else(|| format!("凭证不存在: {}", id))?;
+    Ok(health_check::check(&credential).await)
+}
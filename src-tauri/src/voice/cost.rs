@@ -0,0 +1,141 @@
+//! 润色费用预估
+//!
+//! 调用 LLM 润色前，用 Token 计数服务估算输入输出 Token 数，
+//! 再按模型注册表中的价格估算费用。超出 `voice_input.processor.cost_limit`
+//! 时由调用方改为输出原文或请用户确认。
+//!
+//! 润色结果与原文长度相近，输出 Token 数按原文的 Token 数估算。
+
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
+
+use super::processor;
+use crate::config::{PolishCostLimit, VoiceInstruction};
+use crate::database::DbConnection;
+use crate::models::model_registry::ModelPricing;
+use crate::models::openai::{ChatCompletionRequest, ChatMessage, MessageContent};
+use crate::services::token_count_service;
+
+/// 润色费用预估
+#[derive(Debug, Clone, Serialize)]
+pub struct PolishEstimate {
+    /// 润色使用的模型
+    pub model: String,
+    /// 预估输入 Token 数
+    pub input_tokens: u32,
+    /// 预估输出 Token 数
+    pub output_tokens: u32,
+    /// 预估费用（模型没有价格信息时为空）
+    pub cost: Option<f64>,
+    /// 价格的货币单位
+    pub currency: Option<String>,
+    /// 是否超出费用上限
+    pub over_limit: bool,
+}
+
+/// 预估一次润色的 Token 数和费用
+pub fn estimate(
+    db: &DbConnection,
+    text: &str,
+    instruction: &VoiceInstruction,
+    model: Option<&str>,
+    limit: &PolishCostLimit,
+) -> PolishEstimate {
+    let model = processor::model_name(model);
+    let prompt = processor::process_text(text, instruction);
+    let input_tokens = count_input(&prompt, &instruction.id, model);
+    let output_tokens =
+        token_count_service::count_text(text, model).min(processor::MAX_POLISH_TOKENS);
+
+    let model_pricing = db.lock().ok().and_then(|conn| pricing(&conn, model));
+    let cost = model_pricing
+        .as_ref()
+        .and_then(|p| calculate_cost(p, input_tokens, output_tokens));
+    PolishEstimate {
+        model: model.to_string(),
+        input_tokens,
+        output_tokens,
+        cost,
+        currency: model_pricing.map(|p| p.currency),
+        over_limit: limit.enabled && cost.is_some_and(|c| c > limit.max_cost),
+    }
+}
+
+/// 按润色请求的消息结构计算输入 Token 数
+fn count_input(prompt: &str, instruction_id: &str, model: &str) -> u32 {
+    let message = |role: &str, content: &str| ChatMessage {
+        role: role.to_string(),
+        content: Some(MessageContent::Text(content.to_string())),
+        tool_calls: None,
+        tool_call_id: None,
+        reasoning_content: None,
+    };
+
+    let mut messages = Vec::with_capacity(2);
+    if let Some(system) = processor::system_prompt(instruction_id) {
+        messages.push(message("system", system));
+    }
+    messages.push(message("user", prompt));
+
+    token_count_service::count_openai_request(&ChatCompletionRequest {
+        model: model.to_string(),
+        messages,
+        temperature: None,
+        max_tokens: Some(processor::MAX_POLISH_TOKENS),
+        top_p: None,
+        stream: false,
+        tools: None,
+        tool_choice: None,
+        reasoning_effort: None,
+    })
+}
+
+/// 从模型注册表读取模型价格
+pub fn pricing(conn: &Connection, model: &str) -> Option<ModelPricing> {
+    let pricing: Option<String> = conn
+        .query_row(
+            "SELECT pricing FROM model_registry WHERE id = ?1 LIMIT 1",
+            [model],
+            |row| row.get(0),
+        )
+        .optional()
+        .ok()
+        .flatten();
+    serde_json::from_str(&pricing?).ok()
+}
+
+/// 按每百万 Token 价格计算费用，缺少输入或输出价格时返回空
+fn calculate_cost(pricing: &ModelPricing, input_tokens: u32, output_tokens: u32) -> Option<f64> {
+    let input = pricing.input_per_million?;
+    let output = pricing.output_per_million?;
+    Some((input * input_tokens as f64 + output * output_tokens as f64) / 1_000_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_cost() {
+        let pricing = ModelPricing {
+            input_per_million: Some(2.0),
+            output_per_million: Some(8.0),
+            ..Default::default()
+        };
+        let cost = calculate_cost(&pricing, 500_000, 250_000).unwrap();
+        assert!((cost - 3.0).abs() < 1e-9);
+
+        let missing = ModelPricing {
+            output_per_million: None,
+            ..pricing
+        };
+        assert!(calculate_cost(&missing, 100, 100).is_none());
+    }
+
+    #[test]
+    fn test_system_prompt_counted() {
+        let with_system = count_input("你好", "default", "gpt-4o");
+        let without = count_input("你好", "translate_en", "gpt-4o");
+        assert!(with_system > without);
+    }
+}
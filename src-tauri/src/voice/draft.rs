@@ -0,0 +1,61 @@
+//! 听写草稿确认
+//!
+//! 开启 `voice_input.output.review_before_output` 后，识别（和润色）完成的文本不直接输出，
+//! 而是作为草稿显示在悬浮窗中，由用户确认、编辑或重试后再输出。
+//! 这里保存最近一次听写的录音和使用的凭证，供重试时重新识别。
+
+use parking_lot::Mutex;
+use voice_core::types::{AudioData, TranscribeResult};
+
+use super::asr_service::AsrService;
+use crate::config::AsrCredentialEntry;
+
+/// 等待确认的听写
+struct PendingDictation {
+    session_id: String,
+    audio: AudioData,
+    credential: AsrCredentialEntry,
+}
+
+static PENDING: Mutex<Option<PendingDictation>> = Mutex::new(None);
+
+/// 保存本次听写的录音，等待用户确认
+pub fn keep(session_id: String, audio: AudioData, credential: AsrCredentialEntry) {
+    *PENDING.lock() = Some(PendingDictation {
+        session_id,
+        audio,
+        credential,
+    });
+}
+
+/// 丢弃等待确认的听写
+pub fn discard() {
+    PENDING.lock().take();
+}
+
+/// 用保存的录音重新识别
+///
+/// 返回听写会话 ID、识别结果和使用的凭证
+pub async fn retry() -> Result<(String, TranscribeResult, AsrCredentialEntry), String> {
+    let (session_id, pcm, sample_rate, credential) = {
+        let pending = PENDING.lock();
+        let pending = pending.as_ref().ok_or("没有等待确认的听写")?;
+        let pcm: Vec<u8> = pending
+            .audio
+            .samples
+            .iter()
+            .flat_map(|&s| s.to_le_bytes())
+            .collect();
+        (
+            pending.session_id.clone(),
+            pcm,
+            pending.audio.sample_rate,
+            pending.credential.clone(),
+        )
+    };
+
+    tracing::info!("[听写草稿] 重新识别，服务: {:?}", credential.provider);
+    // 用户对结果不满意才会重试，不使用缓存的结果
+    let result = AsrService::retranscribe(&credential, &pcm, sample_rate).await?;
+    Ok((session_id, result, credential))
+}
@@ -0,0 +1,104 @@
+//! ASR 凭证健康检查
+//!
+//! 用内置的 1 秒测试音频调用凭证对应的识别服务，返回耗时和识别文本，
+//! 供用户在依赖某个凭证前确认密钥可用：
+//!
+//! - 云端凭证直接调用该凭证的客户端，不重试、不回退到其他服务，失败原因即服务返回的错误
+//! - 本地凭证加载模型并识别一次，模型未下载时返回失败
+//!
+//! 测试音频是合成的元音"啊"，识别文本可能为空或只有一个字，以服务是否正常返回为准。
+
+use std::time::Instant;
+
+use serde::Serialize;
+use voice_core::asr_client::AsrClient;
+use voice_core::types::AudioData;
+
+use super::asr_service::AsrService;
+use crate::config::{AsrCredentialEntry, AsrProviderType};
+
+/// 内置测试音频（16kHz 单声道 16-bit WAV，1 秒）
+const SAMPLE_CLIP: &[u8] = include_bytes!("../../resources/voice/asr_health_check.wav");
+
+/// 健康检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct AsrHealthCheck {
+    pub success: bool,
+    /// 结果说明（失败时为错误信息）
+    pub message: String,
+    /// 识别耗时（毫秒），配置无效时为空
+    pub latency_ms: Option<u64>,
+    /// 识别文本
+    pub text: Option<String>,
+}
+
+impl AsrHealthCheck {
+    fn failed(message: String, latency_ms: Option<u64>) -> Self {
+        Self {
+            success: false,
+            message,
+            latency_ms,
+            text: None,
+        }
+    }
+}
+
+/// 内置测试音频
+fn sample_clip() -> Result<AudioData, String> {
+    AudioData::from_wav_bytes(SAMPLE_CLIP).map_err(|e| format!("测试音频解析失败: {}", e))
+}
+
+/// 用测试音频调用凭证对应的识别服务
+pub async fn check(credential: &AsrCredentialEntry) -> AsrHealthCheck {
+    let audio = match sample_clip() {
+        Ok(audio) => audio,
+        Err(e) => return AsrHealthCheck::failed(e, None),
+    };
+
+    let started = Instant::now();
+    let result = if matches!(credential.provider, AsrProviderType::WhisperLocal) {
+        let bytes: Vec<u8> = audio.samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        AsrService::transcribe(credential, &bytes, audio.sample_rate).await
+    } else {
+        let client = match AsrService::create_client(credential) {
+            Ok(client) => client,
+            Err(e) => return AsrHealthCheck::failed(e, None),
+        };
+        client.transcribe(&audio).await.map_err(|e| e.to_string())
+    };
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(result) => {
+            tracing::info!(
+                "[ASR 健康检查] {} 识别成功，耗时 {}ms",
+                credential.id,
+                latency_ms
+            );
+            AsrHealthCheck {
+                success: true,
+                message: format!("识别成功，耗时 {} ms", latency_ms),
+                latency_ms: Some(latency_ms),
+                text: Some(result.text),
+            }
+        }
+        Err(e) => {
+            tracing::warn!("[ASR 健康检查] {} 识别失败: {}", credential.id, e);
+            AsrHealthCheck::failed(e, Some(latency_ms))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_clip() {
+        let audio = sample_clip().unwrap();
+        assert_eq!(audio.sample_rate, 16000);
+        assert_eq!(audio.channels, 1);
+        assert!((audio.duration_secs - 1.0).abs() < 0.01);
+        assert!(audio.samples.iter().any(|&s| s != 0));
+    }
+}
@@ -0,0 +1,100 @@
+//! 听写历史
+//!
+//! 每次听写保存原始识别文本和润色结果（`voice_input.history` 可关闭）。
+//! 之后可换用其他指令或模型对原始文本重新润色，结果作为新版本保存，
+//! 通过 `parent_id` 指向原始听写，无需重新录音。原始听写同时保存该次听写的
+//! 阶段时间线，便于事后排查延迟。
+
+use serde::{Deserialize, Serialize};
+
+use super::timeline::SessionTimeline;
+
+/// 听写历史记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoiceHistoryEntry {
+    pub id: String,
+    /// 原始听写的 ID（重新润色生成的版本才有）
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    /// 原始识别文本
+    pub raw_text: String,
+    /// 润色后的文本（未润色时与原始文本相同）
+    pub text: String,
+    /// 识别出的语言
+    #[serde(default)]
+    pub language: Option<String>,
+    /// 使用的 ASR 服务
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// 润色使用的指令 ID（未润色时为空）
+    #[serde(default)]
+    pub instruction_id: Option<String>,
+    /// 润色使用的模型
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub created_at: i64,
+    /// 听写各阶段的时间线（输出完成后写入，重新润色的版本没有）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeline: Option<SessionTimeline>,
+}
+
+impl VoiceHistoryEntry {
+    /// 新建一条听写记录，润色前文本与原始文本相同
+    pub fn new(raw_text: String, language: Option<String>, provider: Option<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            parent_id: None,
+            text: raw_text.clone(),
+            raw_text,
+            language,
+            provider,
+            instruction_id: None,
+            model: None,
+            created_at: chrono::Utc::now().timestamp_millis(),
+            timeline: None,
+        }
+    }
+
+    /// 原始听写的 ID（本身就是原始听写时为自身 ID）
+    pub fn root_id(&self) -> &str {
+        self.parent_id.as_deref().unwrap_or(&self.id)
+    }
+
+    /// 基于本条记录生成重新润色的版本
+    pub fn variant(&self, text: String, instruction_id: String, model: Option<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            parent_id: Some(self.root_id().to_string()),
+            raw_text: self.raw_text.clone(),
+            text,
+            language: self.language.clone(),
+            provider: self.provider.clone(),
+            instruction_id: Some(instruction_id),
+            model,
+            created_at: chrono::Utc::now().timestamp_millis(),
+            timeline: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_points_to_root() {
+        let entry = VoiceHistoryEntry::new("你好世界".to_string(), Some("zh".to_string()), None);
+        assert_eq!(entry.text, entry.raw_text);
+        assert_eq!(entry.root_id(), entry.id);
+
+        let first = entry.variant("你好，世界。".to_string(), "default".to_string(), None);
+        assert_eq!(first.parent_id.as_deref(), Some(entry.id.as_str()));
+        assert_eq!(first.raw_text, "你好世界");
+
+        // 基于版本再次润色时仍指向原始听写
+        let second = first.variant("Hello, world.".to_string(), "translate".to_string(), None);
+        assert_eq!(second.root_id(), entry.id);
+        assert_eq!(second.language.as_deref(), Some("zh"));
+    }
+}
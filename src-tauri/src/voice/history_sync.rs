@@ -0,0 +1,191 @@
+//! 听写历史同步到工作区
+//!
+//! 开启 `voice_input.history.workspace_sync` 后，每条听写追加到默认工作区内文件夹中
+//! 当天的 Markdown 文件（`<folder>/YYYY-MM-DD.md`），听写内容随项目一起被工作区搜索索引。
+//! 开启 `include_audio` 时录音另存为 `<folder>/audio/` 下的 WAV，并在 Markdown 中链接；
+//! 隐私设置禁止写盘时不保存录音。
+//!
+//! 同步在后台执行，没有默认工作区或写入失败时只记录警告，不影响听写流程。
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Local, TimeZone};
+use voice_core::asr_client::AudioFormat;
+use voice_core::types::AudioData;
+
+use super::history::VoiceHistoryEntry;
+use crate::config::VoiceHistorySyncConfig;
+use crate::database::DbConnection;
+use crate::workspace::WorkspaceManager;
+
+/// 在后台把听写同步到默认工作区
+pub fn spawn_sync(
+    db: &DbConnection,
+    entry: &VoiceHistoryEntry,
+    audio: impl FnOnce() -> AudioData,
+    config: &VoiceHistorySyncConfig,
+) {
+    if !config.enabled {
+        return;
+    }
+    let db = db.clone();
+    let entry = entry.clone();
+    // 隐私设置禁止写盘时只同步文字
+    let audio = (config.include_audio && super::privacy::disk_allowed()).then(audio);
+    let config = config.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = sync(&db, &entry, audio.as_ref(), &config) {
+            tracing::warn!("[听写历史] 同步到工作区失败: {}", e);
+        }
+    });
+}
+
+/// 把听写追加到默认工作区的当天文件，返回写入的文件路径
+fn sync(
+    db: &DbConnection,
+    entry: &VoiceHistoryEntry,
+    audio: Option<&AudioData>,
+    config: &VoiceHistorySyncConfig,
+) -> Result<PathBuf, String> {
+    let workspace = WorkspaceManager::new(db.clone())
+        .get_default()?
+        .ok_or_else(|| "没有默认工作区".to_string())?;
+    let dir = sync_dir(&workspace.root_path, &config.folder)?;
+    let created_at = Local
+        .timestamp_millis_opt(entry.created_at)
+        .single()
+        .unwrap_or_else(Local::now);
+
+    let audio_link = match audio {
+        Some(audio) => Some(write_audio(&dir, entry, audio, &created_at)?),
+        None => None,
+    };
+
+    std::fs::create_dir_all(&dir).map_err(|e| format!("创建同步目录失败: {}", e))?;
+    let path = dir.join(format!("{}.md", created_at.format("%Y-%m-%d")));
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("打开 {} 失败: {}", path.display(), e))?;
+    let is_new = file.metadata().map(|m| m.len() == 0).unwrap_or(false);
+    let mut content = String::new();
+    if is_new {
+        content.push_str(&format!("# 听写记录 {}\n", created_at.format("%Y-%m-%d")));
+    }
+    content.push_str(&render_entry(entry, &created_at, audio_link.as_deref()));
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("写入 {} 失败: {}", path.display(), e))?;
+
+    tracing::debug!("[听写历史] 已同步到 {}", path.display());
+    Ok(path)
+}
+
+/// 同步目录：工作区根目录下的相对路径，不允许指向工作区之外
+fn sync_dir(root: &Path, folder: &str) -> Result<PathBuf, String> {
+    let folder = Path::new(folder.trim());
+    if folder.as_os_str().is_empty()
+        || !folder
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(format!(
+            "同步文件夹必须是工作区内的相对路径: {}",
+            folder.display()
+        ));
+    }
+    Ok(root.join(folder))
+}
+
+/// 保存录音，返回相对同步目录的链接
+fn write_audio(
+    dir: &Path,
+    entry: &VoiceHistoryEntry,
+    audio: &AudioData,
+    created_at: &DateTime<Local>,
+) -> Result<String, String> {
+    let encoded = voice_core::encode::encode(audio, AudioFormat::Wav, audio.sample_rate)
+        .map_err(|e| e.to_string())?;
+    let file_name = format!(
+        "{}-{}.wav",
+        created_at.format("%Y%m%d-%H%M%S"),
+        entry.id.chars().take(8).collect::<String>()
+    );
+    let audio_dir = dir.join("audio");
+    std::fs::create_dir_all(&audio_dir).map_err(|e| format!("创建录音目录失败: {}", e))?;
+    std::fs::write(audio_dir.join(&file_name), encoded.bytes)
+        .map_err(|e| format!("保存录音失败: {}", e))?;
+    Ok(format!("audio/{}", file_name))
+}
+
+/// 一条听写的 Markdown 段落
+fn render_entry(
+    entry: &VoiceHistoryEntry,
+    created_at: &DateTime<Local>,
+    audio_link: Option<&str>,
+) -> String {
+    let mut meta = Vec::new();
+    if let Some(provider) = &entry.provider {
+        meta.push(format!("服务：{}", provider));
+    }
+    if let Some(language) = &entry.language {
+        meta.push(format!("语言：{}", language));
+    }
+
+    let mut section = format!(
+        "\n## {}\n\n{}\n",
+        created_at.format("%H:%M:%S"),
+        entry.text.trim()
+    );
+    if !meta.is_empty() || audio_link.is_some() {
+        section.push('\n');
+    }
+    if !meta.is_empty() {
+        section.push_str(&format!("- {}\n", meta.join(" · ")));
+    }
+    if let Some(link) = audio_link {
+        section.push_str(&format!("- 录音：[{}]({})\n", link, link));
+    }
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_dir_stays_inside_workspace() {
+        let root = Path::new("/projects/demo");
+        assert_eq!(
+            sync_dir(root, "notes/dictations").unwrap(),
+            PathBuf::from("/projects/demo/notes/dictations")
+        );
+        assert!(sync_dir(root, "../outside").is_err());
+        assert!(sync_dir(root, "/tmp/dictations").is_err());
+        assert!(sync_dir(root, "  ").is_err());
+    }
+
+    #[test]
+    fn test_render_entry() {
+        let mut entry = VoiceHistoryEntry::new(
+            "今天先把接口文档补完。".to_string(),
+            Some("zh".to_string()),
+            Some("讯飞".to_string()),
+        );
+        let created_at = Local.with_ymd_and_hms(2026, 1, 2, 9, 30, 5).unwrap();
+        assert_eq!(
+            render_entry(&entry, &created_at, Some("audio/20260102-093005-abcd.wav")),
+            "\n## 09:30:05\n\n今天先把接口文档补完。\n\n- 服务：讯飞 · 语言：zh\n\
+             - 录音：[audio/20260102-093005-abcd.wav](audio/20260102-093005-abcd.wav)\n"
+        );
+
+        entry.provider = None;
+        entry.language = None;
+        assert_eq!(
+            render_entry(&entry, &created_at, None),
+            "\n## 09:30:05\n\n今天先把接口文档补完。\n"
+        );
+    }
+}
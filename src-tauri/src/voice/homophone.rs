@@ -0,0 +1,187 @@
+//! 同音词纠正
+//!
+//! 中文识别常把词写成同音的别字（如 `帐号` / `账号`），人名、产品名更是容易认错。开启
+//! `voice_input.processor.homophone` 后，识别结果中与用户词条拼音相同（不计声调）但字不同的
+//! 片段替换为词条。在补全标点和润色之前进行，减少送给润色模型的错误。
+
+use pinyin::ToPinyin;
+use voice_core::types::TranscribeResult;
+
+use super::config::load_voice_config;
+
+/// 词条最少字数，单字的同音字太多，容易误改
+const MIN_TERM_CHARS: usize = 2;
+
+/// 按配置纠正识别结果中的同音词
+pub fn correct(mut result: TranscribeResult) -> TranscribeResult {
+    let Ok(config) = load_voice_config() else {
+        return result;
+    };
+    let homophone = &config.processor.homophone;
+    if !homophone.enabled || homophone.terms.is_empty() || result.text.trim().is_empty() {
+        return result;
+    }
+
+    let dictionary = Dictionary::new(&homophone.terms, homophone.fuzzy);
+    let (text, replaced) = dictionary.correct(&result.text);
+    if replaced > 0 {
+        tracing::info!("[同音词纠正] 替换了 {} 处", replaced);
+        result.text = text;
+    }
+    result
+}
+
+/// 带读音的用户词条
+struct Dictionary {
+    /// 词条和每个字的读音，长词条在前
+    terms: Vec<(Vec<char>, Vec<String>)>,
+    fuzzy: bool,
+}
+
+impl Dictionary {
+    /// 忽略不足两个字或含有非汉字的词条
+    fn new(terms: &[String], fuzzy: bool) -> Self {
+        let mut terms: Vec<_> = terms
+            .iter()
+            .filter_map(|term| {
+                let chars: Vec<char> = term.trim().chars().collect();
+                if chars.len() < MIN_TERM_CHARS {
+                    return None;
+                }
+                let reading = chars
+                    .iter()
+                    .map(|c| syllable(*c, fuzzy))
+                    .collect::<Option<Vec<_>>>()?;
+                Some((chars, reading))
+            })
+            .collect();
+        terms.sort_by_key(|(chars, _)| std::cmp::Reverse(chars.len()));
+        Self { terms, fuzzy }
+    }
+
+    /// 从左到右优先匹配最长的词条，返回纠正后的文本和替换的处数
+    fn correct(&self, text: &str) -> (String, usize) {
+        let chars: Vec<char> = text.chars().collect();
+        let readings: Vec<Option<String>> =
+            chars.iter().map(|c| syllable(*c, self.fuzzy)).collect();
+
+        let mut corrected = String::with_capacity(text.len());
+        let mut replaced = 0;
+        let mut i = 0;
+        while i < chars.len() {
+            let matched = self.terms.iter().find(|(term, reading)| {
+                readings.get(i..i + term.len()).is_some_and(|window| {
+                    window
+                        .iter()
+                        .zip(reading)
+                        .all(|(r, t)| r.as_deref() == Some(t.as_str()))
+                })
+            });
+            match matched {
+                Some((term, _)) => {
+                    if chars[i..i + term.len()] != term[..] {
+                        replaced += 1;
+                    }
+                    corrected.extend(term);
+                    i += term.len();
+                }
+                None => {
+                    corrected.push(chars[i]);
+                    i += 1;
+                }
+            }
+        }
+        (corrected, replaced)
+    }
+}
+
+/// 汉字不计声调的拼音，不是汉字时为空
+fn syllable(c: char, fuzzy: bool) -> Option<String> {
+    let plain = c.to_pinyin()?.plain();
+    Some(if fuzzy {
+        fuzzy_syllable(plain)
+    } else {
+        plain.to_string()
+    })
+}
+
+/// 归并易混的读音：zh/ch/sh 归为 z/c/s，l 归为 n，ang/eng/ing 归为 an/en/in
+fn fuzzy_syllable(plain: &str) -> String {
+    let mut syllable = ["zh", "ch", "sh"]
+        .iter()
+        .find_map(|retroflex| {
+            plain
+                .strip_prefix(retroflex)
+                .map(|rest| format!("{}{}", &retroflex[..1], rest))
+        })
+        .unwrap_or_else(|| plain.to_string());
+    if let Some(rest) = syllable.strip_prefix('l') {
+        syllable = format!("n{}", rest);
+    }
+    if ["ang", "eng", "ing"]
+        .iter()
+        .any(|nasal| syllable.ends_with(nasal))
+    {
+        syllable.pop();
+    }
+    syllable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn correct_with(terms: &[&str], fuzzy: bool, text: &str) -> (String, usize) {
+        let terms: Vec<String> = terms.iter().map(|t| t.to_string()).collect();
+        Dictionary::new(&terms, fuzzy).correct(text)
+    }
+
+    #[test]
+    fn test_replaces_homophone_with_term() {
+        let (text, replaced) = correct_with(&["账号"], false, "请输入你的帐号和密码");
+        assert_eq!(text, "请输入你的账号和密码");
+        assert_eq!(replaced, 1);
+    }
+
+    #[test]
+    fn test_keeps_correct_text_and_other_words() {
+        let (text, replaced) = correct_with(&["账号"], false, "账号已登录，Hello 世界");
+        assert_eq!(text, "账号已登录，Hello 世界");
+        assert_eq!(replaced, 0);
+    }
+
+    #[test]
+    fn test_prefers_longer_term() {
+        let (text, replaced) = correct_with(&["钉钉", "钉钉文档"], false, "打开叮叮文档");
+        assert_eq!(text, "打开钉钉文档");
+        assert_eq!(replaced, 1);
+    }
+
+    #[test]
+    fn test_ignores_short_and_non_chinese_terms() {
+        let dictionary = Dictionary::new(
+            &["号".to_string(), "Git库".to_string(), " 账号 ".to_string()],
+            false,
+        );
+        assert_eq!(dictionary.terms.len(), 1);
+        assert_eq!(dictionary.terms[0].0, vec!['账', '号']);
+    }
+
+    #[test]
+    fn test_fuzzy_syllable() {
+        assert_eq!(fuzzy_syllable("zhang"), "zan");
+        assert_eq!(fuzzy_syllable("shi"), "si");
+        assert_eq!(fuzzy_syllable("liu"), "niu");
+        assert_eq!(fuzzy_syllable("xing"), "xin");
+        assert_eq!(fuzzy_syllable("hong"), "hong");
+    }
+
+    #[test]
+    fn test_fuzzy_matches_confusable_initials() {
+        // 平翘舌不分时 "四十" 常被识别成 "是时"
+        assert_eq!(correct_with(&["四十"], false, "是时").1, 0);
+        let (text, replaced) = correct_with(&["四十"], true, "是时");
+        assert_eq!(text, "四十");
+        assert_eq!(replaced, 1);
+    }
+}
@@ -2,32 +2,95 @@
 //!
 //! 提供系统级语音输入功能，包括：
 //! - 全局快捷键触发
-//! - 悬浮窗口管理
-//! - 语音识别处理
+//! - 悬浮窗口管理（含各显示器上的录音指示）
+//! - 语音识别处理（支持边录边识别，每次听写由独立的会话负责）
 //! - 文本输出
+//! - 免打扰时段调度
 
+pub mod ab_test;
+pub mod asr_cache;
 pub mod asr_service;
+pub mod batch;
+pub mod clipboard_history;
 pub mod commands;
 pub mod config;
+pub mod cost;
+pub mod draft;
+pub mod health_check;
+pub mod history;
+pub mod history_sync;
+pub mod homophone;
+pub mod models;
+pub mod no_speech;
 pub mod output_service;
+pub mod permissions;
+pub mod privacy;
 pub mod processor;
+pub mod prompt_export;
+pub mod punctuation;
+pub mod quiet_hours;
 pub mod recording_service;
+pub mod secure_input;
+pub mod session;
 pub mod shortcut;
+pub mod snippets;
+pub mod streaming;
+pub mod timeline;
+pub mod usage;
+pub mod vocabulary;
+pub mod watch_folder;
 pub mod window;
+pub mod workspace_defaults;
 
-use tauri::AppHandle;
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager};
+
+use recording_service::RecordingServiceState;
 
 /// 初始化语音输入模块
 pub fn init(app: &AppHandle) -> Result<(), String> {
+    // 录音指示跟随麦克风状态，麦克风测试等录音不依赖语音输入是否启用
+    let handle = app.clone();
+    app.state::<RecordingServiceState>()
+        .0
+        .lock()
+        .set_state_listener(Arc::new(move |hot| window::set_mic_hot(&handle, hot)));
+
     // 加载配置
     let config = config::load_voice_config()?;
 
+    // 隐私设置对所有识别入口生效，不依赖语音输入是否启用
+    privacy::apply_config(&config.privacy);
+
+    // 加载默认工作区的热词表和听写默认值，工作区变更后重新加载
+    let db = app.state::<crate::database::DbConnection>();
+    vocabulary::reload(&db);
+    workspace_defaults::reload(&db);
+    workspace_defaults::listen(db.inner().clone());
+
+    // 记录每次 ASR 调用的用量
+    usage::init(db.inner().clone());
+
+    // 监听文件夹自动转写不依赖语音输入快捷键是否启用
+    if !config.watch_folders.is_empty() {
+        if let Err(e) = watch_folder::reload(app) {
+            tracing::warn!("[语音输入] 监听文件夹启动失败: {}", e);
+        }
+    }
+
     // 如果功能未启用，直接返回
     if !config.enabled {
         tracing::info!("[语音输入] 功能未启用");
         return Ok(());
     }
 
+    // 启动剪贴板历史（需用户开启）
+    clipboard_history::apply_config(&config.clipboard_history);
+
+    // 启动免打扰时段调度
+    quiet_hours::apply_config(app, &config.quiet_hours);
+
     // 注册全局快捷键
     shortcut::register(app, &config.shortcut)?;
 
@@ -62,6 +125,18 @@ pub fn cleanup(app: &AppHandle) -> Result<(), String> {
     // 注销翻译快捷键
     let _ = shortcut::unregister_translate(app);
 
+    // 取消进行中的听写
+    session::cancel_all();
+
+    // 停止监听文件夹
+    watch_folder::stop();
+
+    // 停止剪贴板历史
+    clipboard_history::stop();
+
+    // 停止免打扰调度
+    quiet_hours::stop();
+
     // 关闭悬浮窗口
     window::close_voice_window(app)?;
 
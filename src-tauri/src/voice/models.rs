@@ -0,0 +1,91 @@
+//! 本地识别模型下载
+//!
+//! 本地模型存放在 `<数据目录>/proxycast/models/` 下，按引擎分目录：
+//! - `whisper/`：Whisper ggml 模型
+//! - `sensevoice/`：SenseVoice ONNX 模型和词表，从 Hugging Face 下载 sherpa-onnx 导出的版本
+//!
+//! 下载时先写入 `.part` 临时文件，完成后再重命名，中断的下载不会被当作可用模型。
+//! 下载进度通过 [`MODEL_DOWNLOAD_EVENT`] 事件发送给前端。
+//!
+//! SenseVoice 引擎需要 `sensevoice` 特性，未启用时没有可下载的模型。
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+#[cfg(not(feature = "sensevoice"))]
+use tauri::AppHandle;
+
+use crate::config::LocalAsrEngine;
+
+#[cfg(feature = "sensevoice")]
+mod sensevoice;
+
+#[cfg(feature = "sensevoice")]
+pub use sensevoice::{delete, download, list, sensevoice_model, sensevoice_model_dir};
+
+/// 模型下载进度事件
+pub const MODEL_DOWNLOAD_EVENT: &str = "voice-model-download-progress";
+
+/// 可下载的本地模型
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalModelInfo {
+    /// 模型 ID
+    pub id: String,
+    /// 所属引擎
+    pub engine: LocalAsrEngine,
+    /// 显示名称
+    pub name: String,
+    /// 大致体积（MB）
+    pub size_mb: u64,
+    /// 是否已下载
+    pub downloaded: bool,
+    /// 是否正在下载
+    pub downloading: bool,
+}
+
+/// 下载进度事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelDownloadProgress {
+    /// 模型 ID
+    pub id: String,
+    /// 当前下载的文件
+    pub file: String,
+    /// 当前文件已下载字节数
+    pub downloaded: u64,
+    /// 当前文件总字节数（服务器未返回时为 `None`）
+    pub total: Option<u64>,
+    /// 是否全部下载完成
+    pub done: bool,
+}
+
+/// 模型目录：`<数据目录>/proxycast/models/<engine>`
+pub fn models_dir(engine: LocalAsrEngine) -> Result<PathBuf, String> {
+    let dir = match engine {
+        LocalAsrEngine::Whisper => "whisper",
+        #[cfg(feature = "sensevoice")]
+        LocalAsrEngine::SenseVoice => "sensevoice",
+    };
+    Ok(dirs::data_dir()
+        .ok_or("无法获取数据目录")?
+        .join("proxycast")
+        .join("models")
+        .join(dir))
+}
+
+/// 列出可下载的本地模型
+#[cfg(not(feature = "sensevoice"))]
+pub fn list() -> Result<Vec<LocalModelInfo>, String> {
+    Ok(Vec::new())
+}
+
+/// 下载模型
+#[cfg(not(feature = "sensevoice"))]
+pub async fn download(_app: &AppHandle, id: &str) -> Result<(), String> {
+    Err(format!("未知的模型: {}（当前版本未启用 SenseVoice）", id))
+}
+
+/// 删除已下载的模型
+#[cfg(not(feature = "sensevoice"))]
+pub fn delete(id: &str) -> Result<(), String> {
+    Err(format!("未知的模型: {}（当前版本未启用 SenseVoice）", id))
+}
@@ -0,0 +1,257 @@
+//! SenseVoice 模型下载（`sensevoice` 特性）
+
+use std::path::PathBuf;
+
+use parking_lot::Mutex;
+use tauri::{AppHandle, Emitter};
+use tokio::io::AsyncWriteExt;
+use voice_core::types::SenseVoiceModel;
+
+use super::{models_dir, LocalModelInfo, ModelDownloadProgress, MODEL_DOWNLOAD_EVENT};
+use crate::config::{LocalAsrEngine, SenseVoicePrecision};
+
+/// SenseVoice 模型的下载地址前缀
+const SENSEVOICE_BASE_URL: &str = "https://huggingface.co/csukuangfj/sherpa-onnx-sense-voice-zh-en-ja-ko-yue-2024-07-17/resolve/main";
+
+/// 两次进度事件之间至少间隔的字节数
+const PROGRESS_STEP_BYTES: u64 = 1024 * 1024;
+
+/// 正在下载的模型 ID
+static DOWNLOADING: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// 转换 SenseVoice 模型精度
+pub fn sensevoice_model(precision: SenseVoicePrecision) -> SenseVoiceModel {
+    match precision {
+        SenseVoicePrecision::Int8 => SenseVoiceModel::Int8,
+        SenseVoicePrecision::Float32 => SenseVoiceModel::Float32,
+    }
+}
+
+/// SenseVoice 模型的 ID
+fn sensevoice_id(precision: SenseVoicePrecision) -> &'static str {
+    match precision {
+        SenseVoicePrecision::Int8 => "sensevoice-int8",
+        SenseVoicePrecision::Float32 => "sensevoice-float32",
+    }
+}
+
+/// 模型 ID 对应的 SenseVoice 精度
+fn sensevoice_precision(id: &str) -> Result<SenseVoicePrecision, String> {
+    [SenseVoicePrecision::Int8, SenseVoicePrecision::Float32]
+        .into_iter()
+        .find(|p| sensevoice_id(*p) == id)
+        .ok_or_else(|| format!("未知的模型: {}", id))
+}
+
+/// SenseVoice 模型需要的文件
+fn sensevoice_files(precision: SenseVoicePrecision) -> [&'static str; 2] {
+    [
+        sensevoice_model(precision).filename(),
+        SenseVoiceModel::TOKENS_FILENAME,
+    ]
+}
+
+/// 获取 SenseVoice 模型目录，模型文件不完整时返回错误
+pub fn sensevoice_model_dir(precision: SenseVoicePrecision) -> Result<PathBuf, String> {
+    let dir = models_dir(LocalAsrEngine::SenseVoice)?;
+    if let Some(missing) = sensevoice_files(precision)
+        .into_iter()
+        .find(|file| !dir.join(file).exists())
+    {
+        return Err(format!(
+            "SenseVoice 模型文件不存在: {}\n请在语音设置中下载模型，或手动放到: {}",
+            missing,
+            dir.display()
+        ));
+    }
+    Ok(dir)
+}
+
+/// 列出可下载的本地模型
+pub fn list() -> Result<Vec<LocalModelInfo>, String> {
+    let dir = models_dir(LocalAsrEngine::SenseVoice)?;
+    let downloading = DOWNLOADING.lock();
+
+    Ok([
+        (
+            SenseVoicePrecision::Int8,
+            "SenseVoice Small（int8 量化）",
+            230,
+        ),
+        (
+            SenseVoicePrecision::Float32,
+            "SenseVoice Small（float32）",
+            900,
+        ),
+    ]
+    .into_iter()
+    .map(|(precision, name, size_mb)| {
+        let id = sensevoice_id(precision);
+        LocalModelInfo {
+            id: id.to_string(),
+            engine: LocalAsrEngine::SenseVoice,
+            name: name.to_string(),
+            size_mb,
+            downloaded: sensevoice_files(precision)
+                .iter()
+                .all(|file| dir.join(file).exists()),
+            downloading: downloading.iter().any(|d| d == id),
+        }
+    })
+    .collect())
+}
+
+/// 下载模型，已存在的文件跳过
+pub async fn download(app: &AppHandle, id: &str) -> Result<(), String> {
+    let precision = sensevoice_precision(id)?;
+    {
+        let mut downloading = DOWNLOADING.lock();
+        if downloading.iter().any(|d| d == id) {
+            return Err("该模型正在下载中".to_string());
+        }
+        downloading.push(id.to_string());
+    }
+
+    let result = download_files(app, id, precision).await;
+    DOWNLOADING.lock().retain(|d| d != id);
+
+    match &result {
+        Ok(()) => tracing::info!("[本地模型] 下载完成: {}", id),
+        Err(e) => tracing::error!("[本地模型] 下载失败: {}: {}", id, e),
+    }
+    result
+}
+
+/// 依次下载模型需要的文件
+async fn download_files(
+    app: &AppHandle,
+    id: &str,
+    precision: SenseVoicePrecision,
+) -> Result<(), String> {
+    let dir = models_dir(LocalAsrEngine::SenseVoice)?;
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| format!("创建模型目录失败: {}", e))?;
+
+    let client = reqwest::Client::new();
+    for file in sensevoice_files(precision) {
+        let path = dir.join(file);
+        if path.exists() {
+            continue;
+        }
+
+        let url = format!("{}/{}", SENSEVOICE_BASE_URL, file);
+        tracing::info!("[本地模型] 开始下载: {}", url);
+        let mut response = client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("下载 {} 失败: {}", file, e))?;
+        let total = response.content_length();
+
+        let part = dir.join(format!("{}.part", file));
+        let mut out = tokio::fs::File::create(&part)
+            .await
+            .map_err(|e| format!("创建文件失败: {}", e))?;
+        let mut downloaded = 0u64;
+        let mut reported = 0u64;
+
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| format!("下载 {} 中断: {}", file, e))?
+        {
+            out.write_all(&chunk)
+                .await
+                .map_err(|e| format!("写入文件失败: {}", e))?;
+            downloaded += chunk.len() as u64;
+            if downloaded - reported >= PROGRESS_STEP_BYTES {
+                reported = downloaded;
+                emit_progress(app, id, file, downloaded, total, false);
+            }
+        }
+        out.flush()
+            .await
+            .map_err(|e| format!("写入文件失败: {}", e))?;
+        drop(out);
+
+        tokio::fs::rename(&part, &path)
+            .await
+            .map_err(|e| format!("保存模型文件失败: {}", e))?;
+        emit_progress(app, id, file, downloaded, total, false);
+    }
+
+    emit_progress(app, id, "", 0, None, true);
+    Ok(())
+}
+
+/// 删除已下载的模型（共用的词表在另一精度仍需要时保留）
+pub fn delete(id: &str) -> Result<(), String> {
+    let precision = sensevoice_precision(id)?;
+    if DOWNLOADING.lock().iter().any(|d| d == id) {
+        return Err("该模型正在下载中".to_string());
+    }
+
+    let dir = models_dir(LocalAsrEngine::SenseVoice)?;
+    let other = match precision {
+        SenseVoicePrecision::Int8 => SenseVoicePrecision::Float32,
+        SenseVoicePrecision::Float32 => SenseVoicePrecision::Int8,
+    };
+    let keep_tokens = dir.join(sensevoice_model(other).filename()).exists();
+
+    for file in sensevoice_files(precision) {
+        if keep_tokens && file == SenseVoiceModel::TOKENS_FILENAME {
+            continue;
+        }
+        let path = dir.join(file);
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(|e| format!("删除 {} 失败: {}", file, e))?;
+        }
+    }
+    Ok(())
+}
+
+fn emit_progress(
+    app: &AppHandle,
+    id: &str,
+    file: &str,
+    downloaded: u64,
+    total: Option<u64>,
+    done: bool,
+) {
+    let _ = app.emit(
+        MODEL_DOWNLOAD_EVENT,
+        ModelDownloadProgress {
+            id: id.to_string(),
+            file: file.to_string(),
+            downloaded,
+            total,
+            done,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_ids_round_trip() {
+        for precision in [SenseVoicePrecision::Int8, SenseVoicePrecision::Float32] {
+            assert_eq!(
+                sensevoice_precision(sensevoice_id(precision)).unwrap(),
+                precision
+            );
+        }
+        assert!(sensevoice_precision("whisper-base").is_err());
+    }
+
+    #[test]
+    fn test_precisions_share_tokens() {
+        let int8 = sensevoice_files(SenseVoicePrecision::Int8);
+        let float32 = sensevoice_files(SenseVoicePrecision::Float32);
+        assert_ne!(int8[0], float32[0]);
+        assert_eq!(int8[1], float32[1]);
+    }
+}
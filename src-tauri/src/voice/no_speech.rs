@@ -0,0 +1,86 @@
+//! 开头静音自动取消
+//!
+//! 打开听写后一直没有说话（例如误触快捷键后离开），录音会一直持续并在结束后
+//! 白白调用一次识别。开始录音后启动监视任务：若持续静音超过
+//! `voice_input.no_speech.timeout_secs` 仍未检测到说话，自动取消听写会话并发送
+//! [`NO_SPEECH_EVENT`] 事件；一旦检测到说话，监视任务即结束。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use voice_core::SilenceTrimOptions;
+
+use super::recording_service::RecordingService;
+use crate::config::VoiceInputConfig;
+
+/// 未检测到说话、录音已自动取消事件
+pub const NO_SPEECH_EVENT: &str = "voice-no-speech";
+
+/// 检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 监视任务代次，开始新的录音时让旧任务退出
+static WATCH_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 未检测到说话事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct NoSpeechPayload {
+    /// 持续静音的时长（秒）
+    pub silence_secs: f32,
+}
+
+/// 开始监视听写会话的录音（未启用时只让旧任务退出）
+pub fn watch(
+    app: &AppHandle,
+    service: Arc<Mutex<RecordingService>>,
+    config: &VoiceInputConfig,
+    device_id: Option<&str>,
+    session_id: String,
+) {
+    let generation = WATCH_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if !config.no_speech.enabled || config.no_speech.timeout_secs <= 0.0 {
+        return;
+    }
+
+    let timeout_secs = config.no_speech.timeout_secs;
+    let threshold_dbfs = config
+        .silence_threshold_dbfs(device_id)
+        .unwrap_or(SilenceTrimOptions::DEFAULT_THRESHOLD_DBFS);
+    let app = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            if WATCH_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+
+            let silence_secs = {
+                let service = service.lock();
+                if !service.is_recording() || service.get_max_rms_dbfs() >= threshold_dbfs {
+                    return;
+                }
+                service.get_duration()
+            };
+            if silence_secs < timeout_secs {
+                continue;
+            }
+
+            // 会话已停止或取消时不再处理
+            if !super::session::cancel_recording(&session_id).await {
+                return;
+            }
+            tracing::info!(
+                "[语音输入] 开始录音后 {:.1}s 未检测到说话（阈值 {:.1}dBFS），已自动取消",
+                silence_secs,
+                threshold_dbfs
+            );
+            let _ = app.emit(NO_SPEECH_EVENT, NoSpeechPayload { silence_secs });
+            return;
+        }
+    });
+}
@@ -1,37 +1,534 @@
 //! 文字输出服务
 //!
 //! 提供模拟键盘输入和剪贴板输出功能
+//!
+//! 多次听写的润色耗时不同，先说完的可能后完成。听写结束时通过 [`reserve`] 按顺序
+//! 在输出队列中占位（只有听写流程占位，占位方必须 [`submit`] 或 [`cancel`]，
+//! 否则同一应用后面的输出要等到占位超时），结果就绪后 [`submit`]；同一目标应用的输出严格按占位顺序进行，
+//! 前面的听写未就绪时后面的等待，避免文字交错。尚未输出的项可以 [`cancel`]。
+//!
+//! 模拟输入和剪贴板操作会阻塞，在 `spawn_blocking` 线程中进行，不占用异步运行时。
+//!
+//! 每项输出前检测安全输入（见 [`super::secure_input`]），焦点在密码框时只复制到剪贴板。
+//!
+//! 模拟键盘输入按段进行，段间间隔 `type_delay_ms`。开启 `pause_on_focus_change` 时每段输入前
+//! 确认焦点仍在目标应用，用户中途切换了窗口则暂停，未输入的部分留在队列中，并发送
+//! [`OUTPUT_PAUSED_EVENT`] 事件，由用户选择在新应用中 [`resume`] 或改为 [`copy_paused`]。
+//!
+//! 指令开启了输出动作时，文本是动作脚本（见 [`voice_core::action`]），模拟输入时按顺序输入文字、
+//! 执行按键（如末尾的 `{Enter}`），每次按键前同样确认焦点；只复制到剪贴板时只复制其中的文字。
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
-use crate::config::VoiceOutputMode;
+use crate::config::{VoiceOutputConfig, VoiceOutputMode};
 use arboard::Clipboard;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use voice_core::action::{self, OutputAction};
+use voice_core::OutputHandler;
+
+/// 占位后超过此时间仍未就绪的项视为放弃，不再阻塞后面的输出
+const PENDING_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 暂停超过此时间仍未处理的项放弃模拟输入，未输入的部分复制到剪贴板
+const PAUSED_TIMEOUT: Duration = Duration::from_secs(300);
 
-/// 输出文字到系统
+/// 无法获取目标应用时使用的队列
+const DEFAULT_TARGET: &str = "default";
+
+/// 模拟键盘输入时每段的字数，每段输入前检查焦点
+const TYPE_CHUNK_CHARS: usize = 8;
+
+/// 继续输入前等待焦点回到新应用的最长时间
+const RESUME_FOCUS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 等待焦点时的检查间隔
+const RESUME_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 焦点离开目标应用、输入暂停事件
+pub const OUTPUT_PAUSED_EVENT: &str = "voice-output-paused";
+
+/// 输入暂停事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct OutputPausedPayload {
+    pub id: u64,
+    /// 听写开始时的目标应用
+    pub target: String,
+    /// 暂停时的焦点应用
+    pub focused: Option<String>,
+    /// 已输入的字数
+    pub typed: usize,
+    /// 尚未输入的字数
+    pub remaining: usize,
+}
+
+/// 焦点离开目标应用而暂停的状态
+struct Paused {
+    at: Instant,
+    /// 暂停时的焦点应用
+    focused: Option<String>,
+}
+
+/// 输出队列中的一项
+struct QueuedOutput {
+    id: u64,
+    target: String,
+    /// 就绪的文本和输出模式（未就绪时为空，暂停时为尚未输入的部分）
+    ready: Option<(String, VoiceOutputMode)>,
+    /// 文本是否为输出动作脚本
+    actions: bool,
+    queued_at: Instant,
+    /// 已输入的字数
+    typed: usize,
+    /// 焦点离开目标应用时暂停，等待用户处理
+    paused: Option<Paused>,
+}
+
+/// 输出队列项的状态（供前端展示）
+#[derive(Debug, Clone, Serialize)]
+pub struct QueuedOutputInfo {
+    pub id: u64,
+    /// 目标应用
+    pub target: String,
+    /// 是否已就绪，等待前面的项输出
+    pub ready: bool,
+    /// 是否因焦点离开目标应用而暂停
+    pub paused: bool,
+    /// 尚未输出的字数（未就绪时为 0）
+    pub chars: usize,
+    /// 已等待的毫秒数
+    pub waited_ms: u64,
+}
+
+static QUEUE: Mutex<VecDeque<QueuedOutput>> = Mutex::new(VecDeque::new());
+
+/// 输出过程持有，保证同一时间只有一处在模拟输入
+static OUTPUT_LOCK: Mutex<()> = Mutex::new(());
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 为一次听写在目标应用的输出队列中占位，返回占位 ID
+pub fn reserve(target: Option<String>) -> u64 {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    QUEUE.lock().push_back(QueuedOutput {
+        id,
+        target: target.unwrap_or_else(|| DEFAULT_TARGET.to_string()),
+        ready: None,
+        actions: false,
+        queued_at: Instant::now(),
+        typed: 0,
+        paused: None,
+    });
+    id
+}
+
+/// 提交占位项的文本，并按顺序输出已就绪的项
 ///
-/// 根据配置的输出模式，将文字输出到当前焦点应用
-pub fn output_text(text: &str, mode: VoiceOutputMode) -> Result<(), String> {
-    match mode {
-        VoiceOutputMode::Type => type_text(text),
-        VoiceOutputMode::Clipboard => copy_to_clipboard(text),
-        VoiceOutputMode::Both => {
-            copy_to_clipboard(text)?;
-            type_text(text)
+/// `actions` 为 true 时文本是输出动作脚本，脚本无效时返回错误。
+/// 占位已取消或超时时返回 `Ok(false)`，不输出
+pub async fn submit(
+    app: &AppHandle,
+    id: u64,
+    text: &str,
+    mode: VoiceOutputMode,
+    actions: bool,
+) -> Result<bool, String> {
+    if actions {
+        action::parse(text).map_err(|e| e.to_string())?;
+    }
+    let found = {
+        let mut queue = QUEUE.lock();
+        match queue.iter_mut().find(|item| item.id == id) {
+            Some(item) => {
+                item.ready = Some((text.to_string(), mode));
+                item.actions = actions;
+                true
+            }
+            None => false,
         }
+    };
+    if !found {
+        tracing::warn!("[语音输出] 输出 #{} 已取消或超时，不再输出", id);
+        return Ok(false);
     }
+    flush(app).await?;
+    Ok(true)
+}
+
+/// 输出到悬浮窗打开前的焦点应用，排在该应用已有的听写之后
+pub async fn enqueue(
+    app: &AppHandle,
+    text: &str,
+    mode: VoiceOutputMode,
+    actions: bool,
+) -> Result<(), String> {
+    let id = reserve(super::window::target_app());
+    let result = submit(app, id, text, mode, actions).await;
+    if result.is_err() {
+        // 脚本无效时占位不会被提交，立即释放
+        QUEUE.lock().retain(|item| item.id != id);
+    }
+    result.map(|_| ())
+}
+
+/// 取消尚未输出的项，返回是否找到
+///
+/// 取消后排在其后、已就绪的项会继续输出
+pub async fn cancel(app: &AppHandle, id: u64) -> Result<bool, String> {
+    let removed = {
+        let mut queue = QUEUE.lock();
+        let len = queue.len();
+        queue.retain(|item| item.id != id);
+        queue.len() < len
+    };
+    if removed {
+        tracing::info!("[语音输出] 已取消输出 #{}", id);
+        flush(app).await?;
+    }
+    Ok(removed)
+}
+
+/// 在暂停时的焦点应用中继续输入暂停的项，返回是否找到
+///
+/// 用户在本应用中点击继续后焦点不在新应用，先等待焦点回到新应用（最多
+/// [`RESUME_FOCUS_TIMEOUT`]），超时仍不在时输入会再次暂停
+pub async fn resume(app: &AppHandle, id: u64) -> Result<bool, String> {
+    let focused = {
+        let queue = QUEUE.lock();
+        match queue.iter().find(|item| item.id == id) {
+            Some(QueuedOutput {
+                paused: Some(paused),
+                ..
+            }) => paused.focused.clone(),
+            _ => return Ok(false),
+        }
+    };
+    let Some(focused) = focused else {
+        return Ok(false);
+    };
+
+    let deadline = Instant::now() + RESUME_FOCUS_TIMEOUT;
+    while !focus_on(&focused) && Instant::now() < deadline {
+        tokio::time::sleep(RESUME_POLL_INTERVAL).await;
+    }
+
+    let resumed = {
+        let mut queue = QUEUE.lock();
+        match queue
+            .iter_mut()
+            .find(|item| item.id == id && item.paused.is_some())
+        {
+            Some(item) => {
+                item.target = focused.clone();
+                item.paused = None;
+                true
+            }
+            None => false,
+        }
+    };
+    if resumed {
+        tracing::info!("[语音输出] 输出 #{} 在 {} 中继续输入", id, focused);
+        flush(app).await?;
+    }
+    Ok(resumed)
+}
+
+/// 暂停的项改为把未输入的部分复制到剪贴板，返回是否找到
+pub async fn copy_paused(app: &AppHandle, id: u64) -> Result<bool, String> {
+    let text = {
+        let mut queue = QUEUE.lock();
+        queue
+            .iter()
+            .position(|item| item.id == id && item.paused.is_some())
+            .and_then(|index| queue.remove(index))
+            .and_then(|item| item.ready.map(|(text, _)| plain_text(&text, item.actions)))
+    };
+    let Some(text) = text else {
+        return Ok(false);
+    };
+    let app_handle = app.clone();
+    tokio::task::spawn_blocking(move || {
+        copy_to_clipboard(&text)?;
+        flush_blocking(&app_handle)
+    })
+    .await
+    .map_err(|e| format!("输出任务失败: {}", e))??;
+    Ok(true)
+}
+
+/// 当前输出队列
+pub fn snapshot() -> Vec<QueuedOutputInfo> {
+    QUEUE
+        .lock()
+        .iter()
+        .map(|item| QueuedOutputInfo {
+            id: item.id,
+            target: item.target.clone(),
+            ready: item.ready.is_some(),
+            paused: item.paused.is_some(),
+            chars: item
+                .ready
+                .as_ref()
+                .map(|(text, _)| plain_text(text, item.actions).chars().count())
+                .unwrap_or(0),
+            waited_ms: item.queued_at.elapsed().as_millis() as u64,
+        })
+        .collect()
+}
+
+/// 在阻塞线程中依次输出可以输出的项
+async fn flush(app: &AppHandle) -> Result<(), String> {
+    let app = app.clone();
+    tokio::task::spawn_blocking(move || flush_blocking(&app))
+        .await
+        .map_err(|e| format!("输出任务失败: {}", e))?
+}
+
+/// 依次输出可以输出的项（会阻塞，持有输出锁直到队列中没有可输出的项）
+fn flush_blocking(app: &AppHandle) -> Result<(), String> {
+    let _output = OUTPUT_LOCK.lock();
+    let config = super::config::load_voice_config()
+        .map(|c| c.output)
+        .unwrap_or_default();
+    loop {
+        let (next, abandoned) = {
+            let mut queue = QUEUE.lock();
+            let abandoned = drop_expired(&mut queue, Instant::now());
+            (take_next(&mut queue), abandoned)
+        };
+        for text in abandoned {
+            copy_to_clipboard(&text)?;
+        }
+        match next {
+            Some(item) => output_item(app, item, &config)?,
+            None => return Ok(()),
+        }
+    }
+}
+
+/// 输出一项，焦点中途离开目标应用时暂停并放回队列
+fn output_item(
+    app: &AppHandle,
+    mut item: QueuedOutput,
+    config: &VoiceOutputConfig,
+) -> Result<(), String> {
+    let Some((text, mode)) = item.ready.take() else {
+        return Ok(());
+    };
+    let plain = plain_text(&text, item.actions);
+    let mode = super::secure_input::output_mode(app, mode, &plain);
+    // 继续输入时剪贴板里已经是完整的文本
+    if matches!(mode, VoiceOutputMode::Clipboard)
+        || (matches!(mode, VoiceOutputMode::Both) && item.typed == 0)
+    {
+        copy_to_clipboard(&plain)?;
+    }
+    if matches!(mode, VoiceOutputMode::Clipboard) {
+        return Ok(());
+    }
+
+    let target = (config.pause_on_focus_change && item.target != DEFAULT_TARGET)
+        .then_some(item.target.as_str());
+    let delay = Duration::from_millis(config.type_delay_ms as u64);
+    let interrupted = if item.actions {
+        let actions = action::parse(&text).map_err(|e| e.to_string())?;
+        run_actions(&actions, target, delay)?
+    } else {
+        type_text(&text, target, delay)?.map(|typed| (typed, text.chars().skip(typed).collect()))
+    };
+    let Some((typed, remaining)) = interrupted else {
+        return Ok(());
+    };
+
+    let focused = super::window::frontmost_app();
+    item.typed += typed;
+    let payload = OutputPausedPayload {
+        id: item.id,
+        target: item.target.clone(),
+        focused: focused.clone(),
+        typed: item.typed,
+        remaining: plain_text(&remaining, item.actions).chars().count(),
+    };
+    tracing::warn!(
+        "[语音输出] 焦点已离开 {}（当前 {:?}），输出 #{} 暂停，剩余 {} 字符",
+        payload.target,
+        focused,
+        item.id,
+        payload.remaining
+    );
+    item.ready = Some((remaining, mode));
+    item.paused = Some(Paused {
+        at: Instant::now(),
+        focused,
+    });
+    // 同一目标应用前面没有排队的项，放回队首保持顺序
+    QUEUE.lock().push_front(item);
+    let _ = app.emit(OUTPUT_PAUSED_EVENT, payload);
+    Ok(())
 }
 
-/// 模拟键盘输入文字
-fn type_text(text: &str) -> Result<(), String> {
+/// 移除超时仍未就绪的占位和暂停过久的项，返回暂停项尚未输入的文本
+fn drop_expired(queue: &mut VecDeque<QueuedOutput>, now: Instant) -> Vec<String> {
+    let mut abandoned = Vec::new();
+    queue.retain_mut(|item| {
+        if item.ready.is_none() && now.duration_since(item.queued_at) > PENDING_TIMEOUT {
+            tracing::warn!("[语音输出] 输出 #{} 等待超时，已放弃", item.id);
+            return false;
+        }
+        let paused_expired = item
+            .paused
+            .as_ref()
+            .is_some_and(|paused| now.duration_since(paused.at) > PAUSED_TIMEOUT);
+        if paused_expired {
+            tracing::warn!(
+                "[语音输出] 输出 #{} 暂停超时，未输入的部分改为复制到剪贴板",
+                item.id
+            );
+            abandoned.extend(
+                item.ready
+                    .take()
+                    .map(|(text, _)| plain_text(&text, item.actions)),
+            );
+            return false;
+        }
+        true
+    });
+    abandoned
+}
+
+/// 取出下一个可以输出的项：已就绪、未暂停，且同一目标应用前面没有排队的项
+fn take_next(queue: &mut VecDeque<QueuedOutput>) -> Option<QueuedOutput> {
+    let index = queue.iter().enumerate().position(|(i, item)| {
+        item.ready.is_some()
+            && item.paused.is_none()
+            && !queue.iter().take(i).any(|prev| prev.target == item.target)
+    })?;
+    queue.remove(index)
+}
+
+/// 焦点是否在 `target` 应用中（无法获取焦点应用时视为是）
+fn focus_on(target: &str) -> bool {
+    super::window::frontmost_app().is_none_or(|app| app == target)
+}
+
+/// 按字数切分文本
+fn split_chunks(text: &str, chars: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let end = rest
+            .char_indices()
+            .nth(chars)
+            .map(|(i, _)| i)
+            .unwrap_or(rest.len());
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks
+}
+
+/// 分段模拟键盘输入文字，段间间隔 `delay`
+///
+/// 传入 `target` 时每段输入前确认焦点仍在该应用，不在则停止并返回已输入的字数
+fn type_text(text: &str, target: Option<&str>, delay: Duration) -> Result<Option<usize>, String> {
     use enigo::{Enigo, Keyboard, Settings};
 
     let mut enigo =
         Enigo::new(&Settings::default()).map_err(|e| format!("初始化键盘模拟器失败: {}", e))?;
 
-    enigo
-        .text(text)
-        .map_err(|e| format!("键盘输入失败: {}", e))?;
+    let mut typed = 0;
+    for (i, chunk) in split_chunks(text, TYPE_CHUNK_CHARS).into_iter().enumerate() {
+        if i > 0 && !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+        if target.is_some_and(|target| !focus_on(target)) {
+            return Ok(Some(typed));
+        }
+        enigo
+            .text(chunk)
+            .map_err(|e| format!("键盘输入失败: {}", e))?;
+        typed += chunk.chars().count();
+    }
+
+    tracing::info!("[语音输出] 键盘输入完成: {} 字符", typed);
+    Ok(None)
+}
 
-    tracing::info!("[语音输出] 键盘输入完成: {} 字符", text.chars().count());
-    Ok(())
+/// 按顺序执行输出动作，文字分段输入，按键前间隔 `delay`
+///
+/// 传入 `target` 时每段文字和每次按键前确认焦点仍在该应用，不在则停止，
+/// 返回已输入的字数和尚未执行部分的脚本
+fn run_actions(
+    actions: &[OutputAction],
+    target: Option<&str>,
+    delay: Duration,
+) -> Result<Option<(usize, String)>, String> {
+    let mut handler = OutputHandler::new().map_err(|e| e.to_string())?;
+    let mut typed = 0;
+    for (i, current) in actions.iter().enumerate() {
+        let rest = || action::to_script(&actions[i + 1..]);
+        match current {
+            OutputAction::Text(text) => {
+                if let Some(n) = type_text(text, target, delay)? {
+                    let remaining: String = text.chars().skip(n).collect();
+                    return Ok(Some((typed + n, action::escape(&remaining) + &rest())));
+                }
+                typed += text.chars().count();
+            }
+            OutputAction::Key { modifiers, key } => {
+                if i > 0 && !delay.is_zero() {
+                    std::thread::sleep(delay);
+                }
+                if target.is_some_and(|target| !focus_on(target)) {
+                    return Ok(Some((typed, action::to_script(&actions[i..]))));
+                }
+                handler
+                    .press_key(modifiers, *key)
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// 输出文本中的文字：动作脚本去掉按键，无效的脚本按原文处理
+fn plain_text(text: &str, actions: bool) -> String {
+    if !actions {
+        return text.to_string();
+    }
+    action::parse(text)
+        .map(|actions| action::plain_text(&actions))
+        .unwrap_or_else(|_| text.to_string())
+}
+
+/// 拆分开启了输出动作的指令的润色结果，返回其中的文字和动作脚本（不含按键时为空）
+///
+/// 不在 `allowed_keys` 中的按键不执行，脚本无效时按普通文本处理
+pub fn split_actions(polished: String, allowed_keys: &[String]) -> (String, Option<String>) {
+    match action::parse(&polished) {
+        Ok(actions) => {
+            let (actions, dropped) = action::retain_keys(actions, allowed_keys);
+            if !dropped.is_empty() {
+                tracing::warn!(
+                    "[语音输出] 润色结果中的按键不在指令允许的范围内，已忽略: {}",
+                    dropped.join(" ")
+                );
+            }
+            let text = action::plain_text(&actions);
+            let has_keys = actions
+                .iter()
+                .any(|a| matches!(a, OutputAction::Key { .. }));
+            (text, has_keys.then(|| action::to_script(&actions)))
+        }
+        Err(e) => {
+            tracing::warn!("[语音输出] 润色结果中的输出动作无效，按普通文本处理: {}", e);
+            (polished, None)
+        }
+    }
 }
 
 /// 复制到剪贴板
@@ -45,3 +542,146 @@ fn copy_to_clipboard(text: &str) -> Result<(), String> {
     tracing::info!("[语音输出] 已复制到剪贴板: {} 字符", text.chars().count());
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: u64, target: &str, text: Option<&str>) -> QueuedOutput {
+        QueuedOutput {
+            id,
+            target: target.to_string(),
+            ready: text.map(|t| (t.to_string(), VoiceOutputMode::Type)),
+            actions: false,
+            queued_at: Instant::now(),
+            typed: 0,
+            paused: None,
+        }
+    }
+
+    fn paused(mut item: QueuedOutput, at: Instant) -> QueuedOutput {
+        item.paused = Some(Paused {
+            at,
+            focused: Some("chat".to_string()),
+        });
+        item
+    }
+
+    fn next_text(queue: &mut VecDeque<QueuedOutput>) -> Option<String> {
+        take_next(queue)
+            .and_then(|item| item.ready)
+            .map(|(text, _)| text)
+    }
+
+    #[test]
+    fn test_same_target_waits_for_earlier_item() {
+        // 第一条还在润色，第二条已就绪
+        let mut queue = VecDeque::from([item(1, "app", None), item(2, "app", Some("second"))]);
+        assert_eq!(next_text(&mut queue), None);
+
+        queue[0].ready = Some(("first".to_string(), VoiceOutputMode::Type));
+        assert_eq!(next_text(&mut queue).as_deref(), Some("first"));
+        assert_eq!(next_text(&mut queue).as_deref(), Some("second"));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_other_target_is_not_blocked() {
+        let mut queue = VecDeque::from([
+            item(1, "editor", None),
+            item(2, "chat", Some("hello")),
+            item(3, "editor", Some("later")),
+        ]);
+        assert_eq!(next_text(&mut queue).as_deref(), Some("hello"));
+        assert_eq!(next_text(&mut queue), None);
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn test_expired_pending_item_is_dropped() {
+        let mut queue = VecDeque::from([item(1, "app", None), item(2, "app", Some("second"))]);
+        drop_expired(&mut queue, Instant::now());
+        assert_eq!(queue.len(), 2);
+
+        drop_expired(&mut queue, Instant::now() + PENDING_TIMEOUT * 2);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(next_text(&mut queue).as_deref(), Some("second"));
+    }
+
+    #[test]
+    fn test_paused_item_blocks_same_target() {
+        let now = Instant::now();
+        let mut queue = VecDeque::from([
+            paused(item(1, "editor", Some("rest")), now),
+            item(2, "editor", Some("later")),
+            item(3, "chat", Some("hello")),
+        ]);
+        assert_eq!(next_text(&mut queue).as_deref(), Some("hello"));
+        assert_eq!(next_text(&mut queue), None);
+
+        queue[0].paused = None;
+        assert_eq!(next_text(&mut queue).as_deref(), Some("rest"));
+        assert_eq!(next_text(&mut queue).as_deref(), Some("later"));
+    }
+
+    #[test]
+    fn test_expired_paused_item_returns_remaining_text() {
+        let now = Instant::now();
+        let mut queue = VecDeque::from([
+            paused(item(1, "editor", Some("rest")), now),
+            item(2, "editor", Some("later")),
+        ]);
+        assert!(drop_expired(&mut queue, now).is_empty());
+        assert_eq!(queue.len(), 2);
+
+        let abandoned = drop_expired(&mut queue, now + PAUSED_TIMEOUT * 2);
+        assert_eq!(abandoned, vec!["rest".to_string()]);
+        assert_eq!(next_text(&mut queue).as_deref(), Some("later"));
+    }
+
+    #[test]
+    fn test_split_actions() {
+        let allowed = vec!["Enter".to_string()];
+        assert_eq!(
+            split_actions("好的{Enter}".to_string(), &allowed),
+            ("好的".to_string(), Some("好的{Enter}".to_string()))
+        );
+        // 白名单外的按键不执行
+        assert_eq!(
+            split_actions("好的{Ctrl+W}{Enter}".to_string(), &allowed),
+            ("好的".to_string(), Some("好的{Enter}".to_string()))
+        );
+        assert_eq!(
+            split_actions("关闭{Cmd+Q}".to_string(), &allowed),
+            ("关闭".to_string(), None)
+        );
+        // 不含按键时只保留文字，无效的脚本按普通文本处理
+        assert_eq!(
+            split_actions("只有文字 {{x}}".to_string(), &allowed),
+            ("只有文字 {x}".to_string(), None)
+        );
+        assert_eq!(
+            split_actions("未闭合{Enter".to_string(), &allowed),
+            ("未闭合{Enter".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_expired_action_item_returns_plain_text() {
+        let now = Instant::now();
+        let mut action_item = paused(item(1, "chat", Some("剩余{Enter}")), now);
+        action_item.actions = true;
+        let mut queue = VecDeque::from([action_item]);
+
+        let abandoned = drop_expired(&mut queue, now + PAUSED_TIMEOUT * 2);
+        assert_eq!(abandoned, vec!["剩余".to_string()]);
+        assert_eq!(plain_text("a{Tab}b", false), "a{Tab}b");
+    }
+
+    #[test]
+    fn test_split_chunks_keeps_char_boundaries() {
+        assert_eq!(split_chunks("你好世界abc", 3), vec!["你好世", "界ab", "c"]);
+        assert_eq!(split_chunks("ab", 8), vec!["ab"]);
+        assert!(split_chunks("", 8).is_empty());
+    }
+}
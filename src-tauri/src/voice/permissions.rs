@@ -0,0 +1,300 @@
+//! 系统权限检查
+//!
+//! 检查语音输入依赖的系统权限，并提供跳转到对应系统设置页面的能力：
+//! - 麦克风：录音
+//! - 辅助功能：enigo 模拟键盘输入
+//! - 屏幕录制：预留给系统音频采集
+//! - 通知：识别结果提示
+
+use serde::{Deserialize, Serialize};
+
+/// 权限类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionKind {
+    Microphone,
+    Accessibility,
+    ScreenRecording,
+    Notifications,
+}
+
+impl PermissionKind {
+    /// 所有需要检查的权限
+    pub const ALL: [PermissionKind; 4] = [
+        PermissionKind::Microphone,
+        PermissionKind::Accessibility,
+        PermissionKind::ScreenRecording,
+        PermissionKind::Notifications,
+    ];
+
+    /// 是否为语音输入必需的权限
+    pub fn is_required(&self) -> bool {
+        matches!(
+            self,
+            PermissionKind::Microphone | PermissionKind::Accessibility
+        )
+    }
+}
+
+/// 权限状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionStatus {
+    /// 已授权
+    Granted,
+    /// 已拒绝
+    Denied,
+    /// 尚未询问用户
+    NotDetermined,
+    /// 当前平台不需要此权限
+    NotRequired,
+    /// 无法检测
+    Unknown,
+}
+
+/// 单项权限检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionCheck {
+    pub kind: PermissionKind,
+    pub status: PermissionStatus,
+    /// 是否为必需权限
+    pub required: bool,
+    /// 是否可以跳转到系统设置
+    pub can_open_settings: bool,
+}
+
+/// 权限汇总报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionReport {
+    pub platform: String,
+    pub checks: Vec<PermissionCheck>,
+    /// 所有必需权限是否已就绪
+    pub all_required_granted: bool,
+}
+
+/// 检查所有权限并生成汇总报告
+pub fn check_all() -> PermissionReport {
+    let checks: Vec<PermissionCheck> = PermissionKind::ALL
+        .iter()
+        .map(|kind| PermissionCheck {
+            kind: *kind,
+            status: check(*kind),
+            required: kind.is_required(),
+            can_open_settings: settings_url(*kind).is_some(),
+        })
+        .collect();
+
+    let all_required_granted = checks.iter().filter(|c| c.required).all(|c| {
+        matches!(
+            c.status,
+            PermissionStatus::Granted | PermissionStatus::NotRequired
+        )
+    });
+
+    PermissionReport {
+        platform: std::env::consts::OS.to_string(),
+        checks,
+        all_required_granted,
+    }
+}
+
+/// 检查单项权限
+pub fn check(kind: PermissionKind) -> PermissionStatus {
+    platform::check(kind)
+}
+
+/// 打开权限对应的系统设置页面
+pub fn open_settings(kind: PermissionKind) -> Result<(), String> {
+    let url = settings_url(kind).ok_or_else(|| format!("当前平台不支持打开 {:?} 设置", kind))?;
+    tracing::info!("[权限检查] 打开系统设置: {}", url);
+    open::that(url).map_err(|e| format!("打开系统设置失败: {}", e))
+}
+
+/// 获取权限对应的系统设置深链接
+fn settings_url(kind: PermissionKind) -> Option<&'static str> {
+    if cfg!(target_os = "macos") {
+        Some(match kind {
+            PermissionKind::Microphone => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone"
+            }
+            PermissionKind::Accessibility => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility"
+            }
+            PermissionKind::ScreenRecording => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture"
+            }
+            PermissionKind::Notifications => {
+                "x-apple.systempreferences:com.apple.preference.notifications"
+            }
+        })
+    } else if cfg!(target_os = "windows") {
+        match kind {
+            PermissionKind::Microphone => Some("ms-settings:privacy-microphone"),
+            PermissionKind::ScreenRecording => {
+                Some("ms-settings:privacy-graphicsCaptureProgrammatic")
+            }
+            PermissionKind::Notifications => Some("ms-settings:notifications"),
+            PermissionKind::Accessibility => None,
+        }
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::{PermissionKind, PermissionStatus};
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> bool;
+    }
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGPreflightScreenCaptureAccess() -> bool;
+    }
+
+    pub fn check(kind: PermissionKind) -> PermissionStatus {
+        match kind {
+            PermissionKind::Microphone => microphone_status(),
+            PermissionKind::Accessibility => {
+                if unsafe { AXIsProcessTrusted() } {
+                    PermissionStatus::Granted
+                } else {
+                    PermissionStatus::Denied
+                }
+            }
+            PermissionKind::ScreenRecording => {
+                if unsafe { CGPreflightScreenCaptureAccess() } {
+                    PermissionStatus::Granted
+                } else {
+                    PermissionStatus::Denied
+                }
+            }
+            // UNUserNotificationCenter 只提供异步回调接口，无法同步检测
+            PermissionKind::Notifications => PermissionStatus::Unknown,
+        }
+    }
+
+    /// 通过 AVCaptureDevice 查询麦克风授权状态
+    #[allow(deprecated, unexpected_cfgs)]
+    fn microphone_status() -> PermissionStatus {
+        use cocoa::base::{id, nil};
+        use cocoa::foundation::NSString;
+        use objc::runtime::Class;
+        use objc::{msg_send, sel, sel_impl};
+
+        let Some(class) = Class::get("AVCaptureDevice") else {
+            return PermissionStatus::Unknown;
+        };
+
+        // AVAuthorizationStatus: 0 未询问, 1 受限, 2 拒绝, 3 已授权
+        let status: i64 = unsafe {
+            // AVMediaTypeAudio 的值为 "soun"
+            let media_type: id = NSString::alloc(nil).init_str("soun");
+            msg_send![class, authorizationStatusForMediaType: media_type]
+        };
+
+        match status {
+            0 => PermissionStatus::NotDetermined,
+            1 | 2 => PermissionStatus::Denied,
+            3 => PermissionStatus::Granted,
+            _ => PermissionStatus::Unknown,
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{PermissionKind, PermissionStatus};
+    use winreg::{enums::*, RegKey};
+
+    const CONSENT_STORE: &str =
+        "Software\\Microsoft\\Windows\\CurrentVersion\\CapabilityAccessManager\\ConsentStore";
+
+    pub fn check(kind: PermissionKind) -> PermissionStatus {
+        match kind {
+            PermissionKind::Microphone => consent_status("microphone"),
+            PermissionKind::ScreenRecording => consent_status("graphicsCaptureProgrammatic"),
+            PermissionKind::Notifications => notifications_status(),
+            // Windows 模拟键盘输入不需要额外授权
+            PermissionKind::Accessibility => PermissionStatus::NotRequired,
+        }
+    }
+
+    /// 读取隐私设置中的能力授权状态（"Allow" / "Deny"）
+    fn consent_status(capability: &str) -> PermissionStatus {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let path = format!("{}\\{}", CONSENT_STORE, capability);
+        let Ok(key) = hkcu.open_subkey_with_flags(&path, KEY_READ) else {
+            return PermissionStatus::Unknown;
+        };
+
+        match key.get_value::<String, _>("Value").as_deref() {
+            Ok("Allow") => PermissionStatus::Granted,
+            Ok("Deny") => PermissionStatus::Denied,
+            Ok(_) => PermissionStatus::NotDetermined,
+            Err(_) => PermissionStatus::Unknown,
+        }
+    }
+
+    /// 读取系统通知总开关
+    fn notifications_status() -> PermissionStatus {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let Ok(key) = hkcu.open_subkey_with_flags(
+            "Software\\Microsoft\\Windows\\CurrentVersion\\PushNotifications",
+            KEY_READ,
+        ) else {
+            // 未设置过时默认开启
+            return PermissionStatus::Granted;
+        };
+
+        match key.get_value::<u32, _>("ToastEnabled") {
+            Ok(0) => PermissionStatus::Denied,
+            _ => PermissionStatus::Granted,
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    use super::{PermissionKind, PermissionStatus};
+
+    pub fn check(kind: PermissionKind) -> PermissionStatus {
+        match kind {
+            // Linux 桌面没有统一的权限模型，麦克风由音频服务直接开放给应用
+            PermissionKind::Notifications => PermissionStatus::Unknown,
+            PermissionKind::Microphone
+            | PermissionKind::Accessibility
+            | PermissionKind::ScreenRecording => PermissionStatus::NotRequired,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_covers_all_permissions() {
+        let report = check_all();
+        assert_eq!(report.checks.len(), PermissionKind::ALL.len());
+        assert_eq!(report.platform, std::env::consts::OS);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_linux_microphone_not_required() {
+        assert_eq!(
+            check(PermissionKind::Microphone),
+            PermissionStatus::NotRequired
+        );
+    }
+
+    #[test]
+    fn test_permission_kind_serialization() {
+        let json = serde_json::to_string(&PermissionKind::ScreenRecording).unwrap();
+        assert_eq!(json, "\"screen_recording\"");
+    }
+}
@@ -0,0 +1,86 @@
+//! 音频隐私设置
+//!
+//! 把配置中的 [`AudioPrivacyConfig`] 同步为 voice-core 的全局隐私策略，
+//! 上传和写盘的限制由 voice-core 统一执行。设置了临时音频保留时长时，
+//! 后台线程启动时和之后每小时清理一次过期的临时音频。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use voice_core::privacy::{self, AudioPrivacy};
+
+use crate::config::AudioPrivacyConfig;
+
+/// 清理间隔
+const PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// 清理线程代次，用于停止旧线程
+static PURGE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 配置对应的 voice-core 隐私策略
+fn to_policy(config: &AudioPrivacyConfig) -> AudioPrivacy {
+    AudioPrivacy {
+        allow_upload: config.allow_cloud,
+        allow_disk: config.allow_disk,
+        temp_retention: (config.temp_audio_retention_hours > 0)
+            .then(|| Duration::from_secs(config.temp_audio_retention_hours as u64 * 3600)),
+    }
+}
+
+/// 应用隐私配置，按需启动或停止临时音频清理
+pub fn apply_config(config: &AudioPrivacyConfig) {
+    let policy = to_policy(config);
+    privacy::set_policy(policy);
+    tracing::info!(
+        "[隐私] 允许上传: {}，允许写盘: {}，临时音频保留: {} 小时",
+        policy.allow_upload,
+        policy.allow_disk,
+        config.temp_audio_retention_hours
+    );
+
+    let generation = PURGE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if policy.temp_retention.is_none() {
+        return;
+    }
+    std::thread::spawn(move || {
+        while PURGE_GENERATION.load(Ordering::SeqCst) == generation {
+            let purged = privacy::purge_expired_temp_audio();
+            if purged > 0 {
+                tracing::info!("[隐私] 已删除 {} 个过期的临时音频", purged);
+            }
+            std::thread::sleep(PURGE_INTERVAL);
+        }
+    });
+}
+
+/// 是否允许音频发送到云端识别服务
+pub fn cloud_allowed() -> bool {
+    privacy::policy().allow_upload
+}
+
+/// 是否允许把音频写入磁盘
+pub fn disk_allowed() -> bool {
+    privacy::policy().allow_disk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_policy() {
+        assert_eq!(
+            to_policy(&AudioPrivacyConfig::default()),
+            AudioPrivacy::default()
+        );
+
+        let policy = to_policy(&AudioPrivacyConfig {
+            allow_cloud: false,
+            allow_disk: false,
+            temp_audio_retention_hours: 24,
+        });
+        assert!(!policy.allow_upload);
+        assert!(!policy.allow_disk);
+        assert_eq!(policy.temp_retention, Some(Duration::from_secs(86400)));
+    }
+}
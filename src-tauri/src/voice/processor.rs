@@ -1,8 +1,12 @@
 //! 语音处理器
 //!
-//! 处理语音识别结果的 LLM 润色
+//! 处理语音识别结果的 LLM 润色，以及润色前的口述标点替换
 
-use crate::config::VoiceInstruction;
+use voice_core::types::TranscribeResult;
+
+use super::config::load_voice_config;
+use crate::config::{SpokenPunctuationMapping, VoiceInstruction};
+use crate::server::overrides::{RequestPriority, HEADER_PRIORITY};
 
 /// 语音润色的 System Prompt
 const VOICE_POLISH_SYSTEM_PROMPT: &str = r#"你是一个语音转文字的后处理助手。
@@ -29,10 +33,130 @@ const VOICE_POLISH_SYSTEM_PROMPT: &str = r#"你是一个语音转文字的后处
 ## 输出要求
 只输出处理后的文本，不要添加任何解释、说明或前缀。"#;
 
+/// 未配置润色模型时使用的模型
+const DEFAULT_POLISH_MODEL: &str = "deepseek-chat";
+
+/// 润色回复的最大 Token 数
+pub const MAX_POLISH_TOKENS: u32 = 2048;
+
+/// 标点恢复的 Prompt
+const PUNCTUATION_PROMPT: &str = r#"下面是一段没有标点的语音识别文本。请只为它添加合适的标点符号并按语义断句，不要增删或修改任何字词，只输出处理后的文本。
+
+{{text}}"#;
+
+/// 识别服务在口令前后自动加的标点，替换口令时一并去掉
+const AUTO_PUNCTUATION: &[char] = &['，', '。', ',', '.'];
+
 /// 处理文本（应用指令模板）
+///
+/// 支持 `{{text}}` 和 `{{clipboard_history[n]}}` 占位符
 pub fn process_text(text: &str, instruction: &VoiceInstruction) -> String {
-    // 替换模板中的占位符
-    instruction.prompt.replace("{{text}}", text)
+    // 先替换剪贴板历史，避免识别文本中恰好包含占位符时被二次替换
+    let prompt = super::clipboard_history::expand_placeholders(&instruction.prompt);
+    prompt.replace("{{text}}", text)
+}
+
+/// 实际使用的润色模型（未配置时使用 deepseek-chat）
+pub fn model_name(model: Option<&str>) -> &str {
+    model
+        .filter(|m| !m.is_empty())
+        .unwrap_or(DEFAULT_POLISH_MODEL)
+}
+
+/// 指令使用的 system prompt（只有默认润色使用专门的 system prompt）
+pub fn system_prompt(instruction_id: &str) -> Option<&'static str> {
+    (instruction_id == "default").then_some(VOICE_POLISH_SYSTEM_PROMPT)
+}
+
+/// 按 `voice_input.spoken_punctuation` 配置把识别结果中的口述标点口令替换为对应字符
+///
+/// 在补全标点之前进行：说了标点的结果不再自动补全
+pub fn apply_spoken_punctuation(mut result: TranscribeResult) -> TranscribeResult {
+    let Ok(config) = load_voice_config() else {
+        return result;
+    };
+    let spoken = &config.spoken_punctuation;
+    if !spoken.enabled || spoken.mappings.is_empty() || result.text.trim().is_empty() {
+        return result;
+    }
+    result.text = replace_spoken_punctuation(&result.text, &spoken.mappings);
+    result
+}
+
+/// 把口令替换为对应字符
+///
+/// 长口令优先；英文口令不区分大小写，前后紧挨字母或数字时不替换（如 `commas`）。
+/// 口令两侧的空白和识别服务自动加的逗号、句号一并去掉，英文标点后的单词前保留一个空格
+pub fn replace_spoken_punctuation(text: &str, mappings: &[SpokenPunctuationMapping]) -> String {
+    let mut mappings: Vec<(Vec<char>, &str)> = mappings
+        .iter()
+        .filter(|m| !m.spoken.trim().is_empty())
+        .map(|m| {
+            (
+                m.spoken.trim().to_lowercase().chars().collect(),
+                m.symbol.as_str(),
+            )
+        })
+        .collect();
+    mappings.sort_by_key(|(spoken, _)| std::cmp::Reverse(spoken.len()));
+
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars.iter().map(|c| c.to_ascii_lowercase()).collect();
+    let is_word = |i: usize| chars.get(i).is_some_and(|c| c.is_ascii_alphanumeric());
+
+    let mut out = String::with_capacity(text.len());
+    // 最后一次替换结束的位置，之前的内容不再改动
+    let mut replaced_end = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let matched = mappings.iter().find(|(spoken, _)| {
+            let end = i + spoken.len();
+            lower[i..].starts_with(spoken)
+                && !(spoken[0].is_ascii_alphanumeric() && i > 0 && is_word(i - 1))
+                && !(spoken[spoken.len() - 1].is_ascii_alphanumeric() && is_word(end))
+        });
+        let Some((spoken, symbol)) = matched else {
+            out.push(chars[i]);
+            i += 1;
+            continue;
+        };
+
+        let trim_end = |out: &mut String| {
+            while out.len() > replaced_end && out.ends_with(char::is_whitespace) {
+                out.pop();
+            }
+        };
+        trim_end(&mut out);
+        // 换行前保留用户说的句号，标点替换识别服务自动加的标点
+        if !symbol.trim().is_empty() && out.len() > replaced_end && out.ends_with(AUTO_PUNCTUATION)
+        {
+            out.pop();
+            trim_end(&mut out);
+        }
+        out.push_str(symbol);
+        replaced_end = out.len();
+
+        i += spoken.len();
+        if chars.get(i).is_some_and(|c| AUTO_PUNCTUATION.contains(c)) {
+            i += 1;
+        }
+        while chars.get(i).is_some_and(|c| c.is_whitespace()) {
+            i += 1;
+        }
+        if symbol.ends_with(|c: char| c.is_ascii_punctuation()) && is_word(i) {
+            out.push(' ');
+            replaced_end = out.len();
+        }
+    }
+    out
+}
+
+/// 润色结果
+#[derive(Debug, Clone)]
+pub struct Polished {
+    pub text: String,
+    /// 实际响应请求的模型（原始输出指令未调用 LLM，为空）
+    pub model: Option<String>,
 }
 
 /// 使用 LLM 润色文本
@@ -43,26 +167,44 @@ pub async fn polish_text(
     instruction: &VoiceInstruction,
     _provider: Option<&str>,
     model: Option<&str>,
-) -> Result<String, String> {
+) -> Result<Polished, String> {
     // 如果是原始输出指令，直接返回
     if instruction.id == "raw" {
-        return Ok(text.to_string());
+        return Ok(Polished {
+            text: text.to_string(),
+            model: None,
+        });
     }
 
     // 构建 prompt
     let prompt = process_text(text, instruction);
 
     // 调用本地 API 服务器
-    let result = call_local_llm(&prompt, model, &instruction.id).await?;
-    Ok(result)
+    let (text, model) = call_local_llm(&prompt, model, &instruction.id).await?;
+    Ok(Polished {
+        text,
+        model: Some(model),
+    })
+}
+
+/// 使用 LLM 为没有标点的文本添加标点
+///
+/// 只添加标点，不改动字词
+pub async fn restore_punctuation(text: &str, model: Option<&str>) -> Result<String, String> {
+    let prompt = PUNCTUATION_PROMPT.replace("{{text}}", text);
+    let (result, _) = call_local_llm(&prompt, model, "punctuation").await?;
+    Ok(result.trim().to_string())
 }
 
 /// 调用本地 API 服务器进行 LLM 推理
+///
+/// 返回回复内容和实际响应的模型（模型别名、路由规则可能改写请求的模型，
+/// 响应未带模型名时取请求的模型）
 async fn call_local_llm(
     prompt: &str,
     model: Option<&str>,
     instruction_id: &str,
-) -> Result<String, String> {
+) -> Result<(String, String), String> {
     use crate::config::load_config;
 
     // 加载配置获取 API 地址和密钥
@@ -70,8 +212,7 @@ async fn call_local_llm(
     let base_url = format!("http://{}:{}", config.server.host, config.server.port);
     let api_key = &config.server.api_key;
 
-    // 使用配置的模型，如果没有配置则使用 deepseek-chat
-    let model_name = model.filter(|m| !m.is_empty()).unwrap_or("deepseek-chat");
+    let model_name = model_name(model);
 
     tracing::info!(
         "[语音润色] 使用模型: {}, 指令: {}",
@@ -94,31 +235,23 @@ async fn call_local_llm(
         temperature: f32,
     }
 
-    // 根据指令类型决定是否使用 system prompt
-    let messages = if instruction_id == "default" {
-        // 默认润色使用专门的 system prompt
-        vec![
-            Message {
-                role: "system".to_string(),
-                content: VOICE_POLISH_SYSTEM_PROMPT.to_string(),
-            },
-            Message {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            },
-        ]
-    } else {
-        // 其他指令（翻译、邮件等）直接使用 user message
-        vec![Message {
-            role: "user".to_string(),
-            content: prompt.to_string(),
-        }]
-    };
+    // 默认润色使用专门的 system prompt，其他指令（翻译、邮件等）直接使用 user message
+    let mut messages = Vec::with_capacity(2);
+    if let Some(system) = system_prompt(instruction_id) {
+        messages.push(Message {
+            role: "system".to_string(),
+            content: system.to_string(),
+        });
+    }
+    messages.push(Message {
+        role: "user".to_string(),
+        content: prompt.to_string(),
+    });
 
     let request = ChatRequest {
         model: model_name.to_string(),
         messages,
-        max_tokens: 2048,
+        max_tokens: MAX_POLISH_TOKENS,
         temperature: 0.3,
     };
 
@@ -128,6 +261,8 @@ async fn call_local_llm(
         .post(format!("{}/v1/chat/completions", base_url))
         .header("Authorization", format!("Bearer {}", api_key))
         .header("Content-Type", "application/json")
+        // 听写等待润色结果，优先于其他请求占用上游并发
+        .header(HEADER_PRIORITY, RequestPriority::High.as_str())
         .json(&request)
         .send()
         .await
@@ -152,6 +287,8 @@ async fn call_local_llm(
 
     #[derive(serde::Deserialize)]
     struct ChatResponse {
+        #[serde(default)]
+        model: Option<String>,
         choices: Vec<Choice>,
     }
 
@@ -160,9 +297,63 @@ async fn call_local_llm(
         .await
         .map_err(|e| format!("解析响应失败: {}", e))?;
 
-    result
+    let content = result
         .choices
         .first()
         .and_then(|c| c.message.content.clone())
-        .ok_or_else(|| "LLM 返回空内容".to_string())
+        .ok_or_else(|| "LLM 返回空内容".to_string())?;
+    let served_model = result
+        .model
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| model_name.to_string());
+    Ok((content, served_model))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mappings() -> Vec<SpokenPunctuationMapping> {
+        crate::config::SpokenPunctuationConfig::default().mappings
+    }
+
+    #[test]
+    fn test_replace_chinese_spoken_punctuation() {
+        let mappings = mappings();
+        assert_eq!(
+            replace_spoken_punctuation("你好逗号明天见句号", &mappings),
+            "你好，明天见。"
+        );
+        assert_eq!(
+            replace_spoken_punctuation("第一行 换行 第二行", &mappings),
+            "第一行\n第二行"
+        );
+        // 识别服务自动加的标点不重复
+        assert_eq!(
+            replace_spoken_punctuation("真的吗，问号。", &mappings),
+            "真的吗？"
+        );
+    }
+
+    #[test]
+    fn test_replace_english_spoken_punctuation() {
+        let mappings = mappings();
+        assert_eq!(
+            replace_spoken_punctuation("Hello comma world period", &mappings),
+            "Hello, world."
+        );
+        assert_eq!(
+            replace_spoken_punctuation("Done. New line. Next item", &mappings),
+            "Done.\nNext item"
+        );
+        assert_eq!(
+            replace_spoken_punctuation("is it ready Question Mark", &mappings),
+            "is it ready?"
+        );
+        // 单词中间的口令不替换
+        assert_eq!(
+            replace_spoken_punctuation("separate commas and colons", &mappings),
+            "separate commas and colons"
+        );
+    }
 }
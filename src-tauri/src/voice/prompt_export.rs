@@ -0,0 +1,75 @@
+//! 听写存为提示词
+//!
+//! 把润色后的听写（或听写历史中的某个版本）保存到提示词库，经常口述的提示词
+//! 之后可以在提示词库中一键启用，不必每次重新听写。
+
+use crate::models::{AppType, Prompt};
+
+/// 自动生成的名称最多保留的字符数
+const NAME_MAX_CHARS: usize = 20;
+
+/// 由听写文本生成提示词，未指定名称时取第一行的开头
+pub fn to_prompt(text: &str, app_type: &str, name: Option<&str>) -> Result<Prompt, String> {
+    let app = app_type.parse::<AppType>()?;
+    let content = text.trim();
+    if content.is_empty() {
+        return Err("听写内容为空，无法存为提示词".to_string());
+    }
+
+    let name = name
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| default_name(content));
+
+    let mut prompt = Prompt::new(
+        uuid::Uuid::new_v4().to_string(),
+        app.as_str().to_string(),
+        name,
+        content.to_string(),
+    );
+    prompt.description = Some("来自语音听写".to_string());
+    Ok(prompt)
+}
+
+/// 第一行超过 [`NAME_MAX_CHARS`] 个字符时截断并加省略号
+fn default_name(content: &str) -> String {
+    let first_line = content.lines().next().unwrap_or_default().trim();
+    if first_line.chars().count() <= NAME_MAX_CHARS {
+        return first_line.to_string();
+    }
+    let truncated: String = first_line.chars().take(NAME_MAX_CHARS).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_from_first_line() {
+        let prompt = to_prompt("  帮我审查这段代码\n重点关注并发问题  ", "claude", None).unwrap();
+        assert_eq!(prompt.name, "帮我审查这段代码");
+        assert_eq!(prompt.content, "帮我审查这段代码\n重点关注并发问题");
+        assert_eq!(prompt.app_type, "claude");
+        assert!(!prompt.enabled);
+    }
+
+    #[test]
+    fn test_long_first_line_truncated() {
+        let text = "请把下面的会议纪要整理成要点列表并标出每一项的负责人和截止时间";
+        let prompt = to_prompt(text, "Codex", None).unwrap();
+        assert_eq!(prompt.name.chars().count(), NAME_MAX_CHARS + 1);
+        assert!(prompt.name.ends_with('…'));
+        assert_eq!(prompt.app_type, "codex");
+    }
+
+    #[test]
+    fn test_explicit_name_and_invalid_input() {
+        let prompt = to_prompt("总结这篇文章", "gemini", Some(" 文章总结 ")).unwrap();
+        assert_eq!(prompt.name, "文章总结");
+
+        assert!(to_prompt("   ", "claude", None).is_err());
+        assert!(to_prompt("总结这篇文章", "unknown", None).is_err());
+    }
+}
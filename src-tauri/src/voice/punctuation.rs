@@ -0,0 +1,70 @@
+//! 标点恢复
+//!
+//! 百度普通话等服务的识别结果常常没有标点。按 `voice_input.processor.punctuation`
+//! 配置，识别结果完全没有标点时补全：`rules` 使用 `voice_core::punctuation` 的规则，
+//! `llm` 调用一次润色模型只添加标点。模型改动了字词或调用失败时改用规则补全。
+
+use voice_core::punctuation::{has_punctuation, restore_punctuation};
+use voice_core::types::TranscribeResult;
+
+use super::config::load_voice_config;
+use super::processor;
+use crate::config::PunctuationMode;
+
+/// 识别结果没有标点时按配置补全
+pub async fn restore(mut result: TranscribeResult) -> TranscribeResult {
+    let Ok(config) = load_voice_config() else {
+        return result;
+    };
+    let mode = config.processor.punctuation;
+    if mode == PunctuationMode::Off
+        || result.text.trim().is_empty()
+        || has_punctuation(&result.text)
+    {
+        return result;
+    }
+
+    let text = match mode {
+        PunctuationMode::Llm => {
+            let model = config.processor.polish_model.as_deref();
+            match processor::restore_punctuation(&result.text, model).await {
+                Ok(text) if same_words(&result.text, &text) => text,
+                Ok(_) => {
+                    tracing::warn!("[标点恢复] 模型改动了识别文本，改用规则补全");
+                    restore_punctuation(&result.text)
+                }
+                Err(e) => {
+                    tracing::warn!("[标点恢复] 调用模型失败: {}，改用规则补全", e);
+                    restore_punctuation(&result.text)
+                }
+            }
+        }
+        _ => restore_punctuation(&result.text),
+    };
+
+    result.text = text;
+    result
+}
+
+/// 去掉标点和空白后两段文本是否相同（忽略英文大小写）
+fn same_words(original: &str, punctuated: &str) -> bool {
+    let words = |text: &str| -> String {
+        text.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    words(original) == words(punctuated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_words() {
+        assert!(same_words("你明天有空吗", "你明天有空吗？"));
+        assert!(same_words("what time is it", "What time is it?"));
+        assert!(!same_words("我本来想去的", "我本来想去。"));
+    }
+}
@@ -0,0 +1,227 @@
+//! 免打扰时段
+//!
+//! 在配置的时段内（如会议、夜间）自动进入免打扰模式：关闭交互音效、
+//! 识别结果只复制到剪贴板。后台线程每 30 秒检查一次，
+//! 状态变化时发送 [`QUIET_MODE_EVENT`] 事件通知前端。
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+use chrono::{Datelike, Local, NaiveDateTime, NaiveTime};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::config::{QuietHoursConfig, QuietHoursSchedule, VoiceOutputMode};
+
+/// 免打扰状态变化事件
+pub const QUIET_MODE_EVENT: &str = "voice-quiet-mode-changed";
+
+/// 检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 调度线程代次，用于停止旧线程
+static SCHEDULER_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 调度线程最近一次检查的结果
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// 免打扰状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct QuietModeStatus {
+    /// 当前是否处于免打扰时段
+    pub active: bool,
+    /// 是否关闭交互音效
+    pub mute_sounds: bool,
+    /// 是否只复制到剪贴板
+    pub clipboard_output: bool,
+}
+
+impl QuietModeStatus {
+    /// 根据配置计算指定时刻的状态
+    pub fn at(config: &QuietHoursConfig, now: NaiveDateTime) -> Self {
+        let active = is_quiet_at(config, now);
+        Self {
+            active,
+            mute_sounds: active && config.mute_sounds,
+            clipboard_output: active && config.clipboard_output,
+        }
+    }
+
+    /// 免打扰时覆盖输出模式
+    pub fn output_mode(&self, mode: VoiceOutputMode) -> VoiceOutputMode {
+        if self.clipboard_output {
+            VoiceOutputMode::Clipboard
+        } else {
+            mode
+        }
+    }
+}
+
+/// 当前的免打扰状态
+pub fn current_status() -> Result<QuietModeStatus, String> {
+    let config = super::config::load_voice_config()?;
+    Ok(QuietModeStatus::at(
+        &config.quiet_hours,
+        Local::now().naive_local(),
+    ))
+}
+
+/// 指定时刻是否处于任一免打扰时段
+pub fn is_quiet_at(config: &QuietHoursConfig, now: NaiveDateTime) -> bool {
+    config.enabled
+        && config
+            .schedules
+            .iter()
+            .any(|schedule| schedule_contains(schedule, now))
+}
+
+/// 解析 HH:MM 格式的时间
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+/// 时段是否包含指定时刻
+///
+/// 跨越午夜的时段，午夜之后的部分按前一天的星期判断
+fn schedule_contains(schedule: &QuietHoursSchedule, now: NaiveDateTime) -> bool {
+    let (Some(start), Some(end)) = (parse_time(&schedule.start), parse_time(&schedule.end)) else {
+        tracing::warn!(
+            "[免打扰] 时段格式无效: {} - {}",
+            schedule.start,
+            schedule.end
+        );
+        return false;
+    };
+    let applies_on = |weekday: u32| {
+        schedule.weekdays.is_empty() || schedule.weekdays.iter().any(|&d| u32::from(d) == weekday)
+    };
+
+    let time = now.time();
+    let today = now.weekday().num_days_from_sunday();
+    if start <= end {
+        start <= time && time < end && applies_on(today)
+    } else if time >= start {
+        applies_on(today)
+    } else if time < end {
+        applies_on((today + 6) % 7)
+    } else {
+        false
+    }
+}
+
+/// 按配置启动或停止调度线程
+pub fn apply_config(app: &AppHandle, config: &QuietHoursConfig) {
+    if config.enabled && !config.schedules.is_empty() {
+        start(app.clone());
+    } else {
+        stop();
+        if ACTIVE.swap(false, Ordering::SeqCst) {
+            emit_status(app, QuietModeStatus::at(config, Local::now().naive_local()));
+        }
+    }
+}
+
+/// 启动调度线程（会替换正在运行的线程）
+fn start(app: AppHandle) {
+    let generation = SCHEDULER_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+
+    std::thread::spawn(move || {
+        tracing::info!("[免打扰] 开始调度");
+        while SCHEDULER_GENERATION.load(Ordering::SeqCst) == generation {
+            match current_status() {
+                Ok(status) => {
+                    if ACTIVE.swap(status.active, Ordering::SeqCst) != status.active {
+                        tracing::info!(
+                            "[免打扰] {}免打扰时段",
+                            if status.active { "进入" } else { "离开" }
+                        );
+                        emit_status(&app, status);
+                    }
+                }
+                Err(e) => tracing::warn!("[免打扰] 读取配置失败: {}", e),
+            }
+            std::thread::sleep(CHECK_INTERVAL);
+        }
+        tracing::info!("[免打扰] 停止调度");
+    });
+}
+
+/// 停止调度线程
+pub fn stop() {
+    SCHEDULER_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+fn emit_status(app: &AppHandle, status: QuietModeStatus) {
+    let _ = app.emit(QUIET_MODE_EVENT, status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn schedule(start: &str, end: &str, weekdays: Vec<u8>) -> QuietHoursSchedule {
+        QuietHoursSchedule {
+            start: start.to_string(),
+            end: end.to_string(),
+            weekdays,
+        }
+    }
+
+    /// 2024-01-01 是周一
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_same_day_schedule() {
+        let s = schedule("09:00", "10:30", vec![]);
+        assert!(!schedule_contains(&s, at(1, 8, 59)));
+        assert!(schedule_contains(&s, at(1, 9, 0)));
+        assert!(schedule_contains(&s, at(1, 10, 29)));
+        assert!(!schedule_contains(&s, at(1, 10, 30)));
+    }
+
+    #[test]
+    fn test_overnight_schedule_uses_start_weekday() {
+        // 仅周五晚上开始：周五 23:00 和周六 06:00 生效，周日 06:00 不生效
+        let s = schedule("22:00", "07:00", vec![5]);
+        assert!(schedule_contains(&s, at(5, 23, 0)));
+        assert!(schedule_contains(&s, at(6, 6, 0)));
+        assert!(!schedule_contains(&s, at(7, 6, 0)));
+        assert!(!schedule_contains(&s, at(6, 12, 0)));
+    }
+
+    #[test]
+    fn test_status_respects_switches() {
+        let config = QuietHoursConfig {
+            enabled: true,
+            schedules: vec![schedule("00:00", "23:59", vec![])],
+            mute_sounds: true,
+            clipboard_output: false,
+        };
+        let status = QuietModeStatus::at(&config, at(1, 12, 0));
+        assert!(status.active && status.mute_sounds && !status.clipboard_output);
+        assert_eq!(
+            status.output_mode(VoiceOutputMode::Type),
+            VoiceOutputMode::Type
+        );
+
+        let disabled = QuietHoursConfig {
+            enabled: false,
+            ..config
+        };
+        assert!(!QuietModeStatus::at(&disabled, at(1, 12, 0)).active);
+    }
+
+    #[test]
+    fn test_invalid_time_is_ignored() {
+        assert!(!schedule_contains(
+            &schedule("25:00", "07:00", vec![]),
+            at(1, 1, 0)
+        ));
+    }
+}
@@ -15,9 +15,14 @@
 //! └─────────────────┘     Response     └─────────────────┘
 //! ```
 //!
-//! - 录音线程拥有 `cpal::Stream`，在独立线程中运行
+//! - 录音线程拥有 `voice_core::recorder::AudioRecorder`（内含 `cpal::Stream`），在独立线程中运行
 //! - Tauri 命令通过 channel 发送控制指令
 //! - 录音线程通过 channel 返回结果
+//! - 音频回调只把原始采样写入无锁环形缓冲区，格式转换和音量计算由
+//!   `voice_core::recorder::CaptureWorker` 在处理线程中完成
+//! - 音频源由 [`AudioSourceFactory`] 按设备 ID 创建，默认是麦克风（`CpalSource`），
+//!   测试时可以换成 `WavFileSource` 回放音频，走与实际录音相同的路径
+//! - 开始、停止和取消录音时调用 [`RecordingStateListener`]，录音指示窗口据此显示或关闭
 
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
@@ -26,8 +31,19 @@ use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 use std::time::Instant;
+use voice_core::asr_client::CancellationToken;
+use voice_core::level::{AudioLevel, LevelMeter};
+use voice_core::recorder::{AudioRecorder, AudioTap};
+use voice_core::source::{AudioSource, CpalSource};
 use voice_core::types::AudioData;
 
+/// 录音状态变化回调，参数为麦克风是否正在录音
+pub type RecordingStateListener = Arc<dyn Fn(bool) + Send + Sync>;
+
+/// 音频源工厂，参数为设备 ID（为空表示系统默认设备），在录音线程中调用
+pub type AudioSourceFactory =
+    Arc<dyn Fn(Option<&str>) -> voice_core::error::Result<Box<dyn AudioSource>> + Send + Sync>;
+
 /// 麦克风设备信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioDeviceInfo {
@@ -64,11 +80,18 @@ pub fn list_audio_devices() -> Result<Vec<AudioDeviceInfo>, String> {
     Ok(devices)
 }
 
+/// 校准向导为设备给出的增益（dB），未校准时为空
+pub fn calibration_gain_db(device_id: Option<&str>) -> Option<f32> {
+    super::config::load_voice_config()
+        .ok()
+        .and_then(|c| c.calibration_for(device_id).map(|c| c.gain_db))
+}
+
 /// 录音控制命令
 #[derive(Debug)]
 pub enum RecordingCommand {
-    /// 开始录音（可选指定设备 ID）
-    Start(Option<String>),
+    /// 开始录音（可选指定设备 ID 和流式识别的采样转发通道）
+    Start(Option<String>, Option<AudioTap>),
     /// 停止录音
     Stop,
     /// 取消录音
@@ -90,7 +113,7 @@ pub enum RecordingResponse {
 
 /// 录音服务
 ///
-/// 使用独立线程管理 cpal::Stream，通过 channel 与 Tauri 命令通信
+/// 使用独立线程管理录音器，通过 channel 与 Tauri 命令通信
 pub struct RecordingService {
     /// 命令发送端
     command_tx: Option<Sender<RecordingCommand>>,
@@ -100,22 +123,56 @@ pub struct RecordingService {
     thread_handle: Option<JoinHandle<()>>,
     /// 是否正在录音（共享状态，用于快速查询）
     is_recording: Arc<AtomicBool>,
-    /// 当前音量级别（共享状态，用于快速查询）
-    volume_level: Arc<AtomicU32>,
+    /// 电平表（共享状态，用于快速查询）
+    meter: Arc<LevelMeter>,
     /// 录音开始时间（共享状态）
     start_time: Arc<Mutex<Option<Instant>>>,
+    /// 当前录音的实际采样率（共享状态）
+    sample_rate: Arc<AtomicU32>,
+    /// 当前录音使用的设备 ID（用于查找校准结果）
+    device_id: Option<String>,
+    /// 录音状态变化回调
+    state_listener: Option<RecordingStateListener>,
+    /// 进行中的整段识别的取消令牌，取消录音时触发
+    recognition_cancel: CancellationToken,
+    /// 音频源工厂
+    source_factory: AudioSourceFactory,
 }
 
 impl RecordingService {
-    /// 创建新的录音服务
+    /// 创建使用麦克风录音的服务
     pub fn new() -> Self {
+        Self::with_source_factory(Arc::new(|device_id| {
+            Ok(Box::new(CpalSource::open(device_id)?) as Box<dyn AudioSource>)
+        }))
+    }
+
+    /// 创建使用指定音频源的录音服务
+    pub fn with_source_factory(source_factory: AudioSourceFactory) -> Self {
         Self {
             command_tx: None,
             response_rx: None,
             thread_handle: None,
             is_recording: Arc::new(AtomicBool::new(false)),
-            volume_level: Arc::new(AtomicU32::new(0)),
+            meter: Arc::new(LevelMeter::new()),
             start_time: Arc::new(Mutex::new(None)),
+            sample_rate: Arc::new(AtomicU32::new(16000)),
+            device_id: None,
+            state_listener: None,
+            recognition_cancel: CancellationToken::new(),
+            source_factory,
+        }
+    }
+
+    /// 设置录音状态变化回调
+    pub fn set_state_listener(&mut self, listener: RecordingStateListener) {
+        self.state_listener = Some(listener);
+    }
+
+    /// 通知录音状态变化
+    fn notify_state(&self, recording: bool) {
+        if let Some(listener) = &self.state_listener {
+            listener(recording);
         }
     }
 
@@ -129,11 +186,21 @@ impl RecordingService {
         let (resp_tx, resp_rx) = mpsc::channel::<RecordingResponse>();
 
         let is_recording = Arc::clone(&self.is_recording);
-        let volume_level = Arc::clone(&self.volume_level);
+        let meter = Arc::clone(&self.meter);
         let start_time = Arc::clone(&self.start_time);
+        let sample_rate = Arc::clone(&self.sample_rate);
+        let source_factory = Arc::clone(&self.source_factory);
 
         let handle = thread::spawn(move || {
-            recording_thread_main(cmd_rx, resp_tx, is_recording, volume_level, start_time);
+            recording_thread_main(
+                cmd_rx,
+                resp_tx,
+                is_recording,
+                meter,
+                start_time,
+                sample_rate,
+                source_factory,
+            );
         });
 
         self.command_tx = Some(cmd_tx);
@@ -145,17 +212,36 @@ impl RecordingService {
 
     /// 开始录音（可选指定设备 ID）
     pub fn start(&mut self, device_id: Option<String>) -> Result<(), String> {
+        self.start_with_tap(device_id, None)
+    }
+
+    /// 开始录音，并把单声道采样实时转发到 `tap`（流式识别使用）
+    pub fn start_streaming(
+        &mut self,
+        device_id: Option<String>,
+        tap: AudioTap,
+    ) -> Result<(), String> {
+        self.start_with_tap(device_id, Some(tap))
+    }
+
+    fn start_with_tap(
+        &mut self,
+        device_id: Option<String>,
+        tap: Option<AudioTap>,
+    ) -> Result<(), String> {
         self.ensure_thread_started();
 
         let tx = self.command_tx.as_ref().ok_or("录音线程未启动")?;
         let rx = self.response_rx.as_ref().ok_or("录音线程未启动")?;
 
-        tx.send(RecordingCommand::Start(device_id))
+        tx.send(RecordingCommand::Start(device_id.clone(), tap))
             .map_err(|e| format!("发送命令失败: {}", e))?;
 
         match rx.recv() {
             Ok(RecordingResponse::Ok) => {
                 tracing::info!("[录音服务] 开始录音");
+                self.device_id = device_id;
+                self.notify_state(true);
                 Ok(())
             }
             Ok(RecordingResponse::Error(e)) => Err(e),
@@ -172,7 +258,9 @@ impl RecordingService {
         tx.send(RecordingCommand::Stop)
             .map_err(|e| format!("发送命令失败: {}", e))?;
 
-        match rx.recv() {
+        let response = rx.recv();
+        self.notify_state(false);
+        match response {
             Ok(RecordingResponse::AudioData(audio)) => {
                 tracing::info!("[录音服务] 停止录音，时长: {:.2}s", audio.duration_secs);
                 Ok(audio)
@@ -183,6 +271,15 @@ impl RecordingService {
         }
     }
 
+    /// 停止录音，并应用校准向导给出的当前设备增益
+    pub fn stop_calibrated(&mut self) -> Result<AudioData, String> {
+        let mut audio = self.stop()?;
+        if let Some(gain_db) = calibration_gain_db(self.device_id()) {
+            voice_core::calibration::apply_gain(&mut audio.samples, gain_db);
+        }
+        Ok(audio)
+    }
+
     /// 取消录音
     pub fn cancel(&mut self) {
         if let Some(tx) = &self.command_tx {
@@ -202,15 +299,38 @@ impl RecordingService {
                 }
             }
         }
+        // 中止已提交的识别，之后的识别使用新的令牌
+        std::mem::take(&mut self.recognition_cancel).cancel();
         // 无论如何都重置状态
         self.is_recording.store(false, Ordering::SeqCst);
-        self.volume_level.store(0, Ordering::SeqCst);
+        self.meter.reset();
         *self.start_time.lock() = None;
+        self.notify_state(false);
+    }
+
+    /// 识别取消令牌：[`RecordingService::cancel`] 时触发，用于中止录音停止后提交的识别
+    pub fn recognition_token(&self) -> CancellationToken {
+        self.recognition_cancel.clone()
     }
 
     /// 获取当前音量级别（0-100）
     pub fn get_volume(&self) -> u32 {
-        self.volume_level.load(Ordering::SeqCst)
+        self.meter.level().percent()
+    }
+
+    /// 获取当前电平（dBFS）
+    pub fn get_level(&self) -> AudioLevel {
+        self.meter.level()
+    }
+
+    /// 获取本次录音累计削波的采样数
+    pub fn get_clipped_samples(&self) -> u64 {
+        self.meter.clipped_samples()
+    }
+
+    /// 本次录音中最响的电平（dBFS），用于判断是否说过话
+    pub fn get_max_rms_dbfs(&self) -> f32 {
+        self.meter.max_rms_dbfs()
     }
 
     /// 获取录音时长（秒）
@@ -226,6 +346,16 @@ impl RecordingService {
         self.is_recording.load(Ordering::SeqCst)
     }
 
+    /// 当前录音的实际采样率（由设备默认配置决定）
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate.load(Ordering::SeqCst)
+    }
+
+    /// 最近一次录音使用的设备 ID（为空表示系统默认设备）
+    pub fn device_id(&self) -> Option<&str> {
+        self.device_id.as_deref()
+    }
+
     /// 关闭录音服务
     pub fn shutdown(&mut self) {
         if let Some(tx) = self.command_tx.take() {
@@ -253,234 +383,96 @@ impl Drop for RecordingService {
 
 /// 录音线程主函数
 ///
-/// 在独立线程中运行，拥有 cpal::Stream
+/// 在独立线程中运行，拥有当前录音的 [`AudioRecorder`]（其中的 `cpal::Stream` 不能跨线程）
 fn recording_thread_main(
     cmd_rx: Receiver<RecordingCommand>,
     resp_tx: Sender<RecordingResponse>,
     is_recording: Arc<AtomicBool>,
-    volume_level: Arc<AtomicU32>,
+    meter: Arc<LevelMeter>,
     start_time: Arc<Mutex<Option<Instant>>>,
+    sample_rate: Arc<AtomicU32>,
+    source_factory: AudioSourceFactory,
 ) {
-    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-
-    // 录音数据缓冲区
-    let samples: Arc<Mutex<Vec<i16>>> = Arc::new(Mutex::new(Vec::new()));
-    // 当前活跃的音频流
-    let mut active_stream: Option<cpal::Stream> = None;
-    // 实际使用的采样率和声道数
-    let mut actual_sample_rate: u32 = 16000;
-    #[allow(unused_assignments)]
-    let mut actual_channels: u16 = 1;
+    // 当前录音使用的录音器
+    let mut active_recorder: Option<AudioRecorder> = None;
 
     tracing::debug!("[录音线程] 开始运行");
 
     loop {
         match cmd_rx.recv() {
-            Ok(RecordingCommand::Start(device_id)) => {
+            Ok(RecordingCommand::Start(device_id, tap)) => {
                 // 如果已在录音，返回错误
                 if is_recording.load(Ordering::SeqCst) {
                     let _ = resp_tx.send(RecordingResponse::Error("已在录音中".to_string()));
                     continue;
                 }
 
-                // 清空缓冲区
-                samples.lock().clear();
-
-                // 获取输入设备
-                let host = cpal::default_host();
-                let device = if let Some(ref id) = device_id {
-                    // 查找指定设备
-                    host.input_devices()
-                        .ok()
-                        .and_then(|mut devices| {
-                            devices.find(|d| d.name().ok().as_ref() == Some(id))
-                        })
-                        .or_else(|| {
-                            tracing::warn!("[录音线程] 未找到指定设备 {}，使用默认设备", id);
-                            host.default_input_device()
-                        })
-                } else {
-                    host.default_input_device()
-                };
-
-                let device = match device {
-                    Some(d) => d,
-                    None => {
-                        let _ =
-                            resp_tx.send(RecordingResponse::Error("未找到麦克风设备".to_string()));
-                        continue;
-                    }
-                };
-
-                tracing::info!("[录音线程] 使用麦克风: {:?}", device.name());
-
-                // 获取设备支持的配置
-                let supported_config = match device.default_input_config() {
-                    Ok(c) => c,
+                let source = match source_factory(device_id.as_deref()) {
+                    Ok(source) => source,
                     Err(e) => {
-                        let _ = resp_tx
-                            .send(RecordingResponse::Error(format!("获取音频配置失败: {}", e)));
+                        let _ = resp_tx.send(RecordingResponse::Error(e.to_string()));
                         continue;
                     }
                 };
+                let format = source.format();
 
-                tracing::info!(
-                    "[录音线程] 设备支持配置: 采样率={}, 声道={}",
-                    supported_config.sample_rate().0,
-                    supported_config.channels()
-                );
-
-                // 使用设备默认配置
-                actual_sample_rate = supported_config.sample_rate().0;
-                actual_channels = supported_config.channels();
-
-                let config = cpal::StreamConfig {
-                    channels: actual_channels,
-                    sample_rate: supported_config.sample_rate(),
-                    buffer_size: cpal::BufferSize::Default,
-                };
-
-                // 创建共享状态的克隆
-                let samples_clone = Arc::clone(&samples);
-                let volume_clone = Arc::clone(&volume_level);
-                let is_rec_clone = Arc::clone(&is_recording);
-                let channels = actual_channels;
-
-                // 回调计数器（用于调试）
-                let callback_count = Arc::new(AtomicU32::new(0));
-                let callback_count_clone = Arc::clone(&callback_count);
-
-                // 创建输入流
-                let stream = match device.build_input_stream(
-                    &config,
-                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                        if !is_rec_clone.load(Ordering::SeqCst) {
-                            return;
-                        }
-
-                        // 增加回调计数
-                        let count = callback_count_clone.fetch_add(1, Ordering::SeqCst);
-                        if count == 0 {
-                            tracing::info!("[录音线程] 首次收到音频数据，数据长度: {}", data.len());
-                        } else if count % 100 == 0 {
-                            tracing::debug!("[录音线程] 已收到 {} 次音频回调", count);
-                        }
-
-                        // 计算音量级别（使用 RMS 均方根，更准确反映音量）
-                        let sum_sq: f32 = data.iter().map(|s| s * s).sum();
-                        let rms = (sum_sq / data.len() as f32).sqrt();
-                        // 将 RMS 值映射到 0-100 范围
-                        // 静音时 RMS 约 0.001-0.01，说话时约 0.02-0.1
-                        // 使用更高的系数来提高灵敏度
-                        let level = ((rms * 1500.0).min(100.0)) as u32;
-
-                        // 每 50 次回调打印一次音量（用于调试）
-                        if count % 50 == 0 {
-                            tracing::debug!("[录音线程] RMS: {:.6}, 音量: {}%", rms, level);
-                        }
-
-                        volume_clone.store(level, Ordering::SeqCst);
-
-                        // 如果是多声道，转换为单声道
-                        let mono_data: Vec<f32> = if channels > 1 {
-                            data.chunks(channels as usize)
-                                .map(|chunk| chunk.iter().sum::<f32>() / channels as f32)
-                                .collect()
-                        } else {
-                            data.to_vec()
-                        };
-
-                        // 转换为 i16 并存储
-                        let i16_samples: Vec<i16> = mono_data
-                            .iter()
-                            .map(|&s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
-                            .collect();
-
-                        samples_clone.lock().extend(i16_samples);
-                    },
-                    |err| {
-                        tracing::error!("[录音线程] 录音流错误: {}", err);
-                    },
-                    None,
-                ) {
-                    Ok(s) => s,
-                    Err(e) => {
-                        let _ = resp_tx
-                            .send(RecordingResponse::Error(format!("创建音频流失败: {}", e)));
-                        continue;
-                    }
-                };
-
-                // 开始播放（录音）
-                if let Err(e) = stream.play() {
-                    let _ = resp_tx.send(RecordingResponse::Error(format!("启动录音失败: {}", e)));
+                let mut recorder =
+                    AudioRecorder::with_source(source).with_meter(Arc::clone(&meter));
+                if let Err(e) = recorder.start_with_tap(tap) {
+                    let _ = resp_tx.send(RecordingResponse::Error(e.to_string()));
                     continue;
                 }
 
-                tracing::info!("[录音线程] stream.play() 成功，等待音频数据...");
-
-                // 保存流和状态
-                active_stream = Some(stream);
+                // 保存录音器和状态
+                active_recorder = Some(recorder);
                 is_recording.store(true, Ordering::SeqCst);
+                sample_rate.store(format.sample_rate, Ordering::SeqCst);
                 *start_time.lock() = Some(Instant::now());
 
                 let _ = resp_tx.send(RecordingResponse::Ok);
                 tracing::info!(
                     "[录音线程] 开始录音，采样率: {}, 声道: {}",
-                    actual_sample_rate,
-                    actual_channels
+                    format.sample_rate,
+                    format.channels
                 );
             }
 
             Ok(RecordingCommand::Stop) => {
-                if !is_recording.load(Ordering::SeqCst) {
+                let Some(mut recorder) = active_recorder.take() else {
                     let _ = resp_tx.send(RecordingResponse::Error("未在录音中".to_string()));
                     continue;
-                }
+                };
 
-                // 停止录音
+                // 停止录音，获取录音数据（已转换为单声道）
                 is_recording.store(false, Ordering::SeqCst);
-
-                // 停止并释放流
-                if let Some(stream) = active_stream.take() {
-                    drop(stream);
-                }
-
-                // 获取录音数据（已转换为单声道）
-                let audio_samples = samples.lock().clone();
-                let audio = AudioData::new(audio_samples, actual_sample_rate, 1);
+                let result = recorder.stop();
+                drop(recorder);
 
                 // 重置开始时间
                 *start_time.lock() = None;
-                volume_level.store(0, Ordering::SeqCst);
+                meter.reset();
 
-                // 检查录音时长
-                if !audio.is_valid() {
-                    let _ = resp_tx.send(RecordingResponse::Error(
-                        "录音时间过短（需要至少 0.5 秒）".to_string(),
-                    ));
-                    continue;
+                match result {
+                    Ok(audio) => {
+                        let _ = resp_tx.send(RecordingResponse::AudioData(audio));
+                        tracing::info!("[录音线程] 停止录音");
+                    }
+                    Err(e) => {
+                        let _ = resp_tx.send(RecordingResponse::Error(e.to_string()));
+                    }
                 }
-
-                let _ = resp_tx.send(RecordingResponse::AudioData(audio));
-                tracing::info!("[录音线程] 停止录音");
             }
 
             Ok(RecordingCommand::Cancel) => {
-                // 停止录音
+                // 停止录音并丢弃已录制的数据
                 is_recording.store(false, Ordering::SeqCst);
-
-                // 停止并释放流
-                if let Some(stream) = active_stream.take() {
-                    drop(stream);
+                if let Some(mut recorder) = active_recorder.take() {
+                    recorder.cancel();
                 }
 
-                // 清空缓冲区
-                samples.lock().clear();
-
                 // 重置状态
                 *start_time.lock() = None;
-                volume_level.store(0, Ordering::SeqCst);
+                meter.reset();
 
                 let _ = resp_tx.send(RecordingResponse::Ok);
                 tracing::info!("[录音线程] 取消录音");
@@ -489,9 +481,7 @@ fn recording_thread_main(
             Ok(RecordingCommand::Shutdown) => {
                 // 清理资源
                 is_recording.store(false, Ordering::SeqCst);
-                if let Some(stream) = active_stream.take() {
-                    drop(stream);
-                }
+                active_recorder.take();
                 tracing::info!("[录音线程] 收到关闭命令，退出");
                 break;
             }
@@ -525,3 +515,84 @@ impl Default for RecordingServiceState {
 pub fn create_recording_service_state() -> RecordingServiceState {
     RecordingServiceState::new()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use voice_core::source::WavFileSource;
+
+    /// 回放指定时长的立体声正弦波，记录每次请求的设备 ID
+    fn wav_service(secs: f32, devices: Arc<Mutex<Vec<Option<String>>>>) -> RecordingService {
+        let frames = (16000.0 * secs) as usize;
+        let samples: Vec<i16> = (0..frames)
+            .flat_map(|i| {
+                let s = ((i as f32 * 0.05).sin() * 8000.0) as i16;
+                [s, s]
+            })
+            .collect();
+        RecordingService::with_source_factory(Arc::new(move |device_id| {
+            devices.lock().push(device_id.map(str::to_string));
+            let audio = AudioData::new(samples.clone(), 16000, 2);
+            Ok(Box::new(WavFileSource::from_audio(audio)) as Box<dyn AudioSource>)
+        }))
+    }
+
+    #[test]
+    fn test_start_stop_records_source_audio() {
+        let devices = Arc::new(Mutex::new(Vec::new()));
+        let mut service = wav_service(1.0, Arc::clone(&devices));
+
+        service.start(Some("USB Mic".to_string())).unwrap();
+        assert!(service.is_recording());
+        assert_eq!(service.sample_rate(), 16000);
+
+        let audio = service.stop().unwrap();
+        assert!(!service.is_recording());
+        // 立体声已混合为单声道
+        assert_eq!(audio.channels, 1);
+        assert_eq!(audio.samples.len(), 16000);
+        assert_eq!(service.device_id(), Some("USB Mic"));
+        assert_eq!(*devices.lock(), vec![Some("USB Mic".to_string())]);
+    }
+
+    #[test]
+    fn test_streaming_tap_receives_all_samples() {
+        let mut service = wav_service(1.0, Arc::new(Mutex::new(Vec::new())));
+        let (tap, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        service.start_streaming(None, tap).unwrap();
+        let audio = service.stop().unwrap();
+
+        let mut streamed = Vec::new();
+        while let Ok(block) = rx.try_recv() {
+            streamed.extend(block);
+        }
+        assert_eq!(streamed, audio.samples);
+    }
+
+    #[test]
+    fn test_short_recording_is_rejected() {
+        let mut service = wav_service(0.2, Arc::new(Mutex::new(Vec::new())));
+
+        service.start(None).unwrap();
+        let err = service.stop().unwrap_err();
+        assert!(err.contains("录音时间过短"));
+        assert!(!service.is_recording());
+
+        // 失败后可以再次开始录音
+        service.start(None).unwrap();
+        assert!(service.is_recording());
+        service.cancel();
+        assert!(!service.is_recording());
+        assert!(service.stop().is_err());
+    }
+
+    #[test]
+    fn test_start_twice_is_rejected() {
+        let mut service = wav_service(1.0, Arc::new(Mutex::new(Vec::new())));
+
+        service.start(None).unwrap();
+        assert_eq!(service.start(None).unwrap_err(), "已在录音中");
+        assert!(service.stop().is_ok());
+    }
+}
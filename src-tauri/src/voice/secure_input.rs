@@ -0,0 +1,98 @@
+//! 安全输入检测
+//!
+//! 焦点在密码框等安全输入字段时，模拟键盘输入要么被系统拦截（macOS 安全输入模式），
+//! 要么把听写内容直接写进密码框。输出前检测到安全输入时不再模拟键盘输入，
+//! 改为只复制到剪贴板，并发送 [`SECURE_INPUT_EVENT`] 事件提醒用户。
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::config::VoiceOutputMode;
+
+/// 因安全输入改为复制到剪贴板事件
+pub const SECURE_INPUT_EVENT: &str = "voice-secure-input";
+
+/// 安全输入事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct SecureInputPayload {
+    /// 已复制到剪贴板的字数
+    pub chars: usize,
+}
+
+/// 焦点是否在安全输入字段中
+pub fn is_active() -> bool {
+    platform::is_active()
+}
+
+/// 安全输入时把需要模拟键盘输入的模式改为只复制到剪贴板
+pub fn output_mode(app: &AppHandle, mode: VoiceOutputMode, text: &str) -> VoiceOutputMode {
+    if matches!(mode, VoiceOutputMode::Clipboard) || !is_active() {
+        return mode;
+    }
+
+    let chars = text.chars().count();
+    tracing::warn!(
+        "[语音输出] 焦点在安全输入字段中，改为复制到剪贴板: {} 字符",
+        chars
+    );
+    let _ = app.emit(SECURE_INPUT_EVENT, SecureInputPayload { chars });
+    VoiceOutputMode::Clipboard
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    #[link(name = "Carbon", kind = "framework")]
+    extern "C" {
+        fn IsSecureEventInputEnabled() -> bool;
+    }
+
+    /// 任一应用开启安全输入模式（如密码框获得焦点）时，其他进程无法模拟键盘输入
+    pub fn is_active() -> bool {
+        unsafe { IsSecureEventInputEnabled() }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use winapi::um::winuser::{
+        GetClassNameW, GetForegroundWindow, GetGUIThreadInfo, GetWindowLongW,
+        GetWindowThreadProcessId, ES_PASSWORD, GUITHREADINFO, GWL_STYLE,
+    };
+
+    /// 焦点控件是带 `ES_PASSWORD` 样式的 Edit 控件
+    pub fn is_active() -> bool {
+        unsafe {
+            let foreground = GetForegroundWindow();
+            if foreground.is_null() {
+                return false;
+            }
+            let thread_id = GetWindowThreadProcessId(foreground, std::ptr::null_mut());
+
+            let mut info: GUITHREADINFO = std::mem::zeroed();
+            info.cbSize = std::mem::size_of::<GUITHREADINFO>() as u32;
+            if GetGUIThreadInfo(thread_id, &mut info) == 0 || info.hwndFocus.is_null() {
+                return false;
+            }
+
+            let mut class = [0u16; 64];
+            let len = GetClassNameW(info.hwndFocus, class.as_mut_ptr(), class.len() as i32);
+            if len <= 0 {
+                return false;
+            }
+            let class = String::from_utf16_lossy(&class[..len as usize]);
+            if !class.eq_ignore_ascii_case("Edit") {
+                return false;
+            }
+
+            GetWindowLongW(info.hwndFocus, GWL_STYLE) as u32 & ES_PASSWORD != 0
+        }
+    }
+}
+
+/// 其他平台无法检测，按非安全输入处理
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    pub fn is_active() -> bool {
+        false
+    }
+}
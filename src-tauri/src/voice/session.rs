@@ -0,0 +1,634 @@
+//! 听写会话
+//!
+//! 每次听写由一个会话 actor 负责：开始录音时创建，独占本次听写的凭证、流式识别任务
+//! 和时间线，按 录音中 → 识别中 → 已完成 / 已取消 / 失败 的顺序推进，不会回退。
+//! 停止、取消通过 channel 投递给会话，由会话自己按当前阶段处理，连续按快捷键时
+//! 重复的停止或迟到的取消不会影响其他会话。
+//!
+//! 所有会话登记在监督者中，并发规则是确定的：
+//! - 麦克风同一时间只属于一个会话，录音中再次开始听写会被拒绝；
+//!   正在停止或取消的会话释放麦克风后，新的听写排在其后开始
+//! - 上一次听写仍在识别时可以开始新的录音，识别中的会话最多 [`MAX_TRANSCRIBING`] 个，超出时拒绝
+//! - 会话任务异常退出时由监督者取消录音、丢弃时间线并移除登记
+//!
+//! 识别中取消会话会中止进行中的识别请求（流式会话或整段识别）。
+//!
+//! 会话阶段变化时发送 [`SESSION_STATE_EVENT`] 事件。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, oneshot, watch};
+use voice_core::types::{AudioData, TranscribeResult};
+
+use super::asr_service::AsrService;
+use super::recording_service::RecordingService;
+use super::streaming::StreamingRecognition;
+use super::timeline::{self, VoiceStage};
+use crate::config::AsrCredentialEntry;
+
+/// 会话阶段变化事件，负载为 [`SessionInfo`]
+pub const SESSION_STATE_EVENT: &str = "voice-session-state";
+
+/// 同时识别中的会话上限
+pub const MAX_TRANSCRIBING: usize = 3;
+
+/// 录音不足该时长视为误触
+pub const MIN_RECORDING_SECS: f32 = 0.5;
+
+/// 等待上一个会话释放麦克风的时间
+const RELEASE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 听写会话阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionPhase {
+    /// 录音中
+    Recording,
+    /// 识别中
+    Transcribing,
+    /// 已完成
+    Completed,
+    /// 已取消（含录音过短和开头静音）
+    Cancelled,
+    /// 失败
+    Failed,
+}
+
+impl SessionPhase {
+    /// 是否已结束
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Completed | Self::Cancelled | Self::Failed)
+    }
+
+    /// 是否允许进入下一阶段
+    pub fn can_transition(self, next: SessionPhase) -> bool {
+        match self {
+            Self::Recording => next != Self::Recording && next != Self::Completed,
+            Self::Transcribing => next.is_terminal(),
+            _ => false,
+        }
+    }
+}
+
+/// 听写会话信息
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionInfo {
+    /// 会话 ID（同时也是时间线 ID）
+    pub session_id: String,
+    pub phase: SessionPhase,
+    /// 是否启用了边录边识别
+    pub streaming: bool,
+    /// 开始时间（毫秒时间戳）
+    pub started_at: i64,
+    /// 失败原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 完成识别的听写
+pub struct Dictation {
+    pub session_id: String,
+    /// 已应用校准增益的录音
+    pub audio: AudioData,
+    pub result: TranscribeResult,
+    /// 实际使用的凭证
+    pub credential: AsrCredentialEntry,
+}
+
+/// 投递给会话的命令
+enum SessionCommand {
+    /// 停止录音并识别；录音过短时返回空
+    Stop(oneshot::Sender<Result<Option<Dictation>, String>>),
+    /// 取消会话，处理完成后应答
+    Cancel(oneshot::Sender<()>),
+}
+
+/// 监督者登记的会话
+struct SessionHandle {
+    info: SessionInfo,
+    /// 会话当前阶段，由会话任务更新
+    phase: watch::Receiver<SessionPhase>,
+    commands: mpsc::UnboundedSender<SessionCommand>,
+    /// 已投递停止或取消，正在释放麦克风
+    closing: bool,
+}
+
+impl SessionHandle {
+    fn phase(&self) -> SessionPhase {
+        *self.phase.borrow()
+    }
+}
+
+static SESSIONS: Mutex<Vec<SessionHandle>> = Mutex::new(Vec::new());
+
+/// 开始新会话时对已有会话的处理
+#[derive(Debug, PartialEq, Eq)]
+enum Admission {
+    /// 可以开始
+    Admit,
+    /// 等待该会话释放麦克风后重试
+    Wait(String),
+    /// 拒绝
+    Reject(String),
+}
+
+/// 按已有会话的阶段决定能否开始新会话
+///
+/// 参数为各会话的（ID，阶段，是否正在停止或取消）
+fn admission<'a>(sessions: impl Iterator<Item = (&'a str, SessionPhase, bool)>) -> Admission {
+    let mut transcribing = 0;
+    for (id, phase, closing) in sessions {
+        match phase {
+            SessionPhase::Recording if closing => return Admission::Wait(id.to_string()),
+            SessionPhase::Recording => {
+                return Admission::Reject("已有听写正在录音，请先结束当前听写".to_string())
+            }
+            SessionPhase::Transcribing => transcribing += 1,
+            _ => {}
+        }
+    }
+    if transcribing >= MAX_TRANSCRIBING {
+        return Admission::Reject(format!("已有 {} 次听写正在识别，请稍后再试", transcribing));
+    }
+    Admission::Admit
+}
+
+/// 开始新的听写会话
+///
+/// 开始录音（服务支持时同时启动流式识别），返回会话信息。
+/// 使用本地 Whisper 时同时在后台预热模型
+pub async fn start(
+    app: &AppHandle,
+    recording: Arc<Mutex<RecordingService>>,
+    device_id: Option<String>,
+    credential: AsrCredentialEntry,
+) -> Result<SessionInfo, String> {
+    AsrService::warm_up_local_model(&credential);
+    loop {
+        let (id, mut phase) = match try_start(app, &recording, &device_id, &credential)? {
+            StartAttempt::Started(info) => return Ok(info),
+            StartAttempt::Wait(id, phase) => (id, phase),
+        };
+        tracing::info!("[听写会话] 等待会话 {} 释放麦克风", id);
+        let released = tokio::time::timeout(
+            RELEASE_TIMEOUT,
+            phase.wait_for(|p| *p != SessionPhase::Recording),
+        )
+        .await;
+        if released.is_err() {
+            return Err("上一次听写仍在停止，请稍后再试".to_string());
+        }
+    }
+}
+
+/// 一次开始尝试的结果
+enum StartAttempt {
+    Started(SessionInfo),
+    /// 等待该会话（ID 及其阶段）释放麦克风
+    Wait(String, watch::Receiver<SessionPhase>),
+}
+
+/// 按准入规则尝试开始新会话
+fn try_start(
+    app: &AppHandle,
+    recording: &Arc<Mutex<RecordingService>>,
+    device_id: &Option<String>,
+    credential: &AsrCredentialEntry,
+) -> Result<StartAttempt, String> {
+    let mut sessions = SESSIONS.lock();
+    let decision = admission(
+        sessions
+            .iter()
+            .map(|h| (h.info.session_id.as_str(), h.phase(), h.closing)),
+    );
+    match decision {
+        Admission::Admit => spawn(
+            app,
+            &mut sessions,
+            recording.clone(),
+            device_id.clone(),
+            credential.clone(),
+        )
+        .map(StartAttempt::Started),
+        Admission::Reject(reason) => {
+            tracing::info!("[听写会话] 拒绝开始新的听写: {}", reason);
+            Err(reason)
+        }
+        Admission::Wait(id) => {
+            let phase = sessions
+                .iter()
+                .find(|h| h.info.session_id == id)
+                .map(|h| h.phase.clone())
+                .ok_or("听写会话不存在")?;
+            Ok(StartAttempt::Wait(id, phase))
+        }
+    }
+}
+
+/// 启动会话任务并登记（调用方持有会话登记表的锁，保证准入判断和登记之间没有其他会话插入）
+fn spawn(
+    app: &AppHandle,
+    sessions: &mut Vec<SessionHandle>,
+    recording: Arc<Mutex<RecordingService>>,
+    device_id: Option<String>,
+    credential: AsrCredentialEntry,
+) -> Result<SessionInfo, String> {
+    let session_id = timeline::begin(app);
+    let recognition = {
+        let mut service = recording.lock();
+        StreamingRecognition::start(app, &mut service, device_id, credential, &session_id)
+    };
+    let recognition = match recognition {
+        Ok(recognition) => recognition,
+        Err(e) => {
+            timeline::discard(Some(&session_id));
+            return Err(e);
+        }
+    };
+
+    let info = SessionInfo {
+        session_id: session_id.clone(),
+        phase: SessionPhase::Recording,
+        streaming: recognition.is_streaming(),
+        started_at: chrono::Utc::now().timestamp_millis(),
+        error: None,
+    };
+    let (phase_tx, phase_rx) = watch::channel(SessionPhase::Recording);
+    let (commands_tx, commands_rx) = mpsc::unbounded_channel();
+    let actor = SessionActor {
+        app: app.clone(),
+        info: info.clone(),
+        recording: recording.clone(),
+        recognition: Some(recognition),
+        phase: phase_tx,
+    };
+    let task = tauri::async_runtime::spawn(actor.run(commands_rx));
+    tauri::async_runtime::spawn(supervise(
+        app.clone(),
+        session_id.clone(),
+        task,
+        phase_rx.clone(),
+        recording,
+    ));
+
+    sessions.push(SessionHandle {
+        info: info.clone(),
+        phase: phase_rx,
+        commands: commands_tx,
+        closing: false,
+    });
+    tracing::info!(
+        "[听写会话] {} 开始录音，边录边识别: {}",
+        session_id,
+        info.streaming
+    );
+    let _ = app.emit(SESSION_STATE_EVENT, &info);
+    Ok(info)
+}
+
+/// 监督会话任务：任务异常退出时清理录音和时间线，结束后移除登记
+async fn supervise(
+    app: AppHandle,
+    session_id: String,
+    task: JoinHandle<()>,
+    phase: watch::Receiver<SessionPhase>,
+    recording: Arc<Mutex<RecordingService>>,
+) {
+    if let Err(e) = task.await {
+        let last_phase = *phase.borrow();
+        tracing::error!(
+            "[听写会话] {} 异常退出（阶段 {:?}）: {}",
+            session_id,
+            last_phase,
+            e
+        );
+        if last_phase == SessionPhase::Recording {
+            recording.lock().cancel();
+        }
+        timeline::discard(Some(&session_id));
+        if let Some(handle) = SESSIONS
+            .lock()
+            .iter()
+            .find(|h| h.info.session_id == session_id)
+        {
+            let info = SessionInfo {
+                phase: SessionPhase::Failed,
+                error: Some(format!("听写会话异常退出: {}", e)),
+                ..handle.info.clone()
+            };
+            let _ = app.emit(SESSION_STATE_EVENT, &info);
+        }
+    }
+    SESSIONS.lock().retain(|h| h.info.session_id != session_id);
+}
+
+/// 查找投递目标：指定 ID 时按 ID 查找，否则取正在录音（未在停止）的会话
+fn target<'a>(
+    sessions: &'a mut [SessionHandle],
+    session_id: Option<&str>,
+) -> Option<&'a mut SessionHandle> {
+    sessions.iter_mut().find(|h| match session_id {
+        Some(id) => h.info.session_id == id,
+        None => h.phase() == SessionPhase::Recording && !h.closing,
+    })
+}
+
+/// 停止会话的录音并等待识别结果
+///
+/// 未指定会话时停止正在录音的会话。录音不足 [`MIN_RECORDING_SECS`] 时视为误触，
+/// 取消录音并返回空
+pub async fn stop(session_id: Option<&str>) -> Result<Option<Dictation>, String> {
+    let (reply_tx, reply_rx) = oneshot::channel();
+    {
+        let mut sessions = SESSIONS.lock();
+        let handle = target(&mut sessions, session_id).ok_or("没有正在录音的听写")?;
+        if handle.phase() != SessionPhase::Recording || handle.closing {
+            return Err(format!("听写 {} 已停止", handle.info.session_id));
+        }
+        handle
+            .commands
+            .send(SessionCommand::Stop(reply_tx))
+            .map_err(|_| "听写会话已结束".to_string())?;
+        handle.closing = true;
+    }
+    reply_rx.await.map_err(|_| "听写会话异常结束".to_string())?
+}
+
+/// 取消会话，等待会话处理完成
+///
+/// 未指定会话时取消正在录音的会话；识别中的会话只有指定 ID 时才会取消。
+/// 返回是否找到了要取消的会话
+pub async fn cancel(session_id: Option<&str>) -> bool {
+    wait_cancelled(request_cancel(session_id, false)).await
+}
+
+/// 只在会话仍在录音、且没有在停止时取消（开头静音自动取消使用）
+pub async fn cancel_recording(session_id: &str) -> bool {
+    wait_cancelled(request_cancel(Some(session_id), true)).await
+}
+
+async fn wait_cancelled(ack: Option<oneshot::Receiver<()>>) -> bool {
+    let Some(ack) = ack else {
+        return false;
+    };
+    if tokio::time::timeout(RELEASE_TIMEOUT, ack).await.is_err() {
+        tracing::warn!("[听写会话] 等待取消完成超时");
+    }
+    true
+}
+
+fn request_cancel(session_id: Option<&str>, recording_only: bool) -> Option<oneshot::Receiver<()>> {
+    let mut sessions = SESSIONS.lock();
+    let handle = target(&mut sessions, session_id)?;
+    let phase = handle.phase();
+    if phase.is_terminal()
+        || (recording_only && (phase != SessionPhase::Recording || handle.closing))
+    {
+        return None;
+    }
+    let (ack_tx, ack_rx) = oneshot::channel();
+    handle.commands.send(SessionCommand::Cancel(ack_tx)).ok()?;
+    handle.closing = true;
+    Some(ack_rx)
+}
+
+/// 取消所有会话（语音输入关闭时），不等待完成
+pub fn cancel_all() {
+    for handle in SESSIONS.lock().iter_mut() {
+        let (ack, _) = oneshot::channel();
+        if handle.commands.send(SessionCommand::Cancel(ack)).is_ok() {
+            handle.closing = true;
+        }
+    }
+}
+
+/// 当前的会话（按开始顺序）
+pub fn sessions() -> Vec<SessionInfo> {
+    SESSIONS
+        .lock()
+        .iter()
+        .map(|h| SessionInfo {
+            phase: h.phase(),
+            ..h.info.clone()
+        })
+        .collect()
+}
+
+/// 是否有会话正在录音
+pub fn is_recording() -> bool {
+    SESSIONS
+        .lock()
+        .iter()
+        .any(|h| h.phase() == SessionPhase::Recording)
+}
+
+/// 单次听写的会话任务
+struct SessionActor {
+    app: AppHandle,
+    info: SessionInfo,
+    recording: Arc<Mutex<RecordingService>>,
+    /// 识别开始后移出
+    recognition: Option<StreamingRecognition>,
+    phase: watch::Sender<SessionPhase>,
+}
+
+impl SessionActor {
+    async fn run(mut self, mut commands: mpsc::UnboundedReceiver<SessionCommand>) {
+        // 录音阶段：等待停止或取消
+        let reply = match commands.recv().await {
+            Some(SessionCommand::Stop(reply)) => reply,
+            Some(SessionCommand::Cancel(ack)) => {
+                self.cancel_recording();
+                self.set_phase(SessionPhase::Cancelled, None);
+                let _ = ack.send(());
+                return;
+            }
+            // 登记已移除，不会再收到命令
+            None => {
+                self.cancel_recording();
+                self.set_phase(SessionPhase::Cancelled, None);
+                return;
+            }
+        };
+
+        let audio = match self.stop_recording() {
+            Ok(Some(audio)) => audio,
+            Ok(None) => {
+                tracing::info!("[听写会话] {} 录音时间过短，已取消", self.info.session_id);
+                self.set_phase(SessionPhase::Cancelled, None);
+                let _ = reply.send(Ok(None));
+                return;
+            }
+            Err(e) => {
+                self.set_phase(SessionPhase::Failed, Some(e.clone()));
+                let _ = reply.send(Err(e));
+                return;
+            }
+        };
+
+        // 识别阶段：识别的同时继续处理命令，取消时放弃识别结果
+        self.set_phase(SessionPhase::Transcribing, None);
+        timeline::mark(Some(&self.info.session_id), VoiceStage::AsrSubmit);
+        let Some(recognition) = self.recognition.take() else {
+            return;
+        };
+        let cancel = recognition.cancellation_token();
+        let transcription = recognition.finish(&audio);
+        tokio::pin!(transcription);
+        let mut open = true;
+        loop {
+            tokio::select! {
+                result = &mut transcription => {
+                    let result = match result {
+                        Ok((result, credential)) => {
+                            self.set_phase(SessionPhase::Completed, None);
+                            Ok(Some(Dictation {
+                                session_id: self.info.session_id.clone(),
+                                audio: audio.clone(),
+                                result,
+                                credential,
+                            }))
+                        }
+                        Err(e) => {
+                            self.set_phase(SessionPhase::Failed, Some(e.clone()));
+                            Err(e)
+                        }
+                    };
+                    let _ = reply.send(result);
+                    return;
+                }
+                command = commands.recv(), if open => match command {
+                    Some(SessionCommand::Stop(other)) => {
+                        let _ = other.send(Err(format!("听写 {} 已停止", self.info.session_id)));
+                    }
+                    Some(SessionCommand::Cancel(ack)) => {
+                        // 中止进行中的识别请求，不在后台等到超时
+                        cancel.cancel();
+                        timeline::discard(Some(&self.info.session_id));
+                        self.set_phase(SessionPhase::Cancelled, None);
+                        let _ = reply.send(Err("听写已取消".to_string()));
+                        let _ = ack.send(());
+                        return;
+                    }
+                    None => open = false,
+                },
+            }
+        }
+    }
+
+    /// 停止录音，录音过短时取消并返回空
+    fn stop_recording(&mut self) -> Result<Option<AudioData>, String> {
+        let mut service = self.recording.lock();
+        if service.get_duration() < MIN_RECORDING_SECS {
+            service.cancel();
+            drop(service);
+            self.discard_recognition();
+            return Ok(None);
+        }
+        match service.stop_calibrated() {
+            Ok(audio) => {
+                timeline::mark(Some(&self.info.session_id), VoiceStage::RecordStop);
+                Ok(Some(audio))
+            }
+            Err(e) => {
+                drop(service);
+                self.discard_recognition();
+                Err(e)
+            }
+        }
+    }
+
+    /// 取消录音和流式识别
+    fn cancel_recording(&mut self) {
+        self.recording.lock().cancel();
+        self.discard_recognition();
+        tracing::info!("[听写会话] {} 已取消", self.info.session_id);
+    }
+
+    /// 放弃识别并丢弃时间线
+    fn discard_recognition(&mut self) {
+        if let Some(recognition) = self.recognition.take() {
+            recognition.cancel();
+        }
+        timeline::discard(Some(&self.info.session_id));
+    }
+
+    /// 进入下一阶段并通知前端（不允许的阶段变化会被忽略）
+    fn set_phase(&mut self, next: SessionPhase, error: Option<String>) {
+        let current = *self.phase.borrow();
+        if !current.can_transition(next) {
+            tracing::warn!(
+                "[听写会话] {} 忽略阶段变化 {:?} -> {:?}",
+                self.info.session_id,
+                current,
+                next
+            );
+            return;
+        }
+        self.phase.send_replace(next);
+        self.info.phase = next;
+        self.info.error = error;
+        tracing::debug!("[听写会话] {} -> {:?}", self.info.session_id, next);
+        let _ = self.app.emit(SESSION_STATE_EVENT, &self.info);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_transitions() {
+        use SessionPhase::*;
+        assert!(Recording.can_transition(Transcribing));
+        assert!(Recording.can_transition(Cancelled));
+        assert!(!Recording.can_transition(Completed));
+        assert!(Transcribing.can_transition(Completed));
+        assert!(Transcribing.can_transition(Failed));
+        assert!(!Transcribing.can_transition(Recording));
+        for terminal in [Completed, Cancelled, Failed] {
+            assert!(terminal.is_terminal());
+            assert!(!terminal.can_transition(Recording));
+            assert!(!terminal.can_transition(Transcribing));
+        }
+    }
+
+    #[test]
+    fn test_admission_is_deterministic() {
+        use SessionPhase::*;
+        assert_eq!(admission(std::iter::empty()), Admission::Admit);
+
+        // 录音中再次开始被拒绝，正在停止的会话释放麦克风后开始
+        assert!(matches!(
+            admission([("a", Recording, false)].into_iter()),
+            Admission::Reject(_)
+        ));
+        assert_eq!(
+            admission([("a", Transcribing, false), ("b", Recording, true)].into_iter()),
+            Admission::Wait("b".to_string())
+        );
+
+        // 识别中的会话不阻塞新的录音，直到达到上限
+        let ids = ["a", "b", "c"];
+        let busy: Vec<_> = ids.iter().map(|id| (*id, Transcribing, false)).collect();
+        assert_eq!(
+            admission(busy[..MAX_TRANSCRIBING - 1].iter().copied()),
+            Admission::Admit
+        );
+        assert!(matches!(
+            admission(busy[..MAX_TRANSCRIBING].iter().copied()),
+            Admission::Reject(_)
+        ));
+
+        // 已结束的会话不计入
+        assert_eq!(
+            admission([("a", Completed, false), ("b", Cancelled, true)].into_iter()),
+            Admission::Admit
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! 语音片段库
+//!
+//! 用户保存常用文本块（如邮件签名、地址），并为其设置口述触发词。
+//! 识别结果输出前，触发词会被展开为对应的文本块：
+//! - 整句只有触发词时（忽略句末标点），整句替换为文本块
+//! - 句中出现触发词时，就地替换
+//!
+//! 触发词匹配忽略 ASCII 大小写，较长的触发词优先匹配。以英文字母或数字开头（结尾）的
+//! 触发词要求前（后）面不紧挨字母或数字，避免 `sig` 匹配到 `design` 之类的单词内部；
+//! 中文没有词边界，按原样匹配。
+
+use serde::{Deserialize, Serialize};
+
+/// 句末可忽略的标点
+const TRAILING_PUNCTUATION: &[char] = &['。', '.', '！', '!', '？', '?', '，', ','];
+
+/// 语音片段
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoiceSnippet {
+    pub id: String,
+    /// 口述触发词，如"插入邮件签名"
+    pub trigger: String,
+    /// 展开后的文本
+    pub content: String,
+    #[serde(default = "default_snippet_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+fn default_snippet_enabled() -> bool {
+    true
+}
+
+/// 展开文本中的触发词
+pub fn expand(text: &str, snippets: &[VoiceSnippet]) -> String {
+    let mut active: Vec<&VoiceSnippet> = snippets
+        .iter()
+        .filter(|s| s.enabled && !s.trigger.trim().is_empty())
+        .collect();
+    if active.is_empty() {
+        return text.to_string();
+    }
+    active.sort_by_key(|s| std::cmp::Reverse(s.trigger.trim().len()));
+
+    // 整句匹配
+    let sentence = text.trim().trim_end_matches(TRAILING_PUNCTUATION).trim();
+    if let Some(snippet) = active
+        .iter()
+        .find(|s| sentence.eq_ignore_ascii_case(s.trigger.trim()))
+    {
+        return snippet.content.clone();
+    }
+
+    // 句中匹配
+    let mut result = String::with_capacity(text.len());
+    let mut i = 0;
+    'outer: while i < text.len() {
+        for snippet in &active {
+            let trigger = snippet.trigger.trim();
+            if starts_with_ignore_ascii_case(&text[i..], trigger)
+                && at_word_boundary(text, i, i + trigger.len(), trigger)
+            {
+                result.push_str(&snippet.content);
+                i += trigger.len();
+                continue 'outer;
+            }
+        }
+        let ch = text[i..].chars().next().unwrap_or_default();
+        result.push(ch);
+        i += ch.len_utf8().max(1);
+    }
+    result
+}
+
+/// 仅 ASCII 字母忽略大小写，非 ASCII 字节必须完全相同，因此匹配结束位置一定是字符边界
+fn starts_with_ignore_ascii_case(text: &str, prefix: &str) -> bool {
+    text.len() >= prefix.len()
+        && text.as_bytes()[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
+}
+
+/// `text[start..end]` 处的触发词两端是否位于词边界
+fn at_word_boundary(text: &str, start: usize, end: usize, trigger: &str) -> bool {
+    let is_word = |c: char| c.is_ascii_alphanumeric();
+    let starts_word = trigger.starts_with(is_word);
+    let ends_word = trigger.ends_with(is_word);
+    !(starts_word && text[..start].ends_with(is_word))
+        && !(ends_word && text[end..].starts_with(is_word))
+}
+
+/// 校验片段
+pub fn validate(snippet: &VoiceSnippet, existing: &[VoiceSnippet]) -> Result<(), String> {
+    let trigger = snippet.trigger.trim();
+    if trigger.is_empty() {
+        return Err("触发词不能为空".to_string());
+    }
+    if snippet.content.is_empty() {
+        return Err("片段内容不能为空".to_string());
+    }
+    if existing
+        .iter()
+        .any(|s| s.id != snippet.id && s.trigger.trim().eq_ignore_ascii_case(trigger))
+    {
+        return Err(format!("触发词已存在: {}", trigger));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet(trigger: &str, content: &str) -> VoiceSnippet {
+        VoiceSnippet {
+            id: trigger.to_string(),
+            trigger: trigger.to_string(),
+            content: content.to_string(),
+            enabled: true,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_expand_whole_sentence() {
+        let snippets = vec![snippet("Insert email signature", "-- \nZhang San")];
+        assert_eq!(
+            expand("insert email signature.", &snippets),
+            "-- \nZhang San"
+        );
+    }
+
+    #[test]
+    fn test_expand_inline_prefers_longest() {
+        let snippets = vec![snippet("地址", "短"), snippet("公司地址", "上海市浦东新区")];
+        assert_eq!(
+            expand("请寄到公司地址，谢谢", &snippets),
+            "请寄到上海市浦东新区，谢谢"
+        );
+    }
+
+    #[test]
+    fn test_expand_requires_word_boundary() {
+        let snippets = vec![snippet("sig", "-- Zhang San")];
+        assert_eq!(
+            expand("my design is a signal", &snippets),
+            "my design is a signal"
+        );
+        assert_eq!(
+            expand("thanks, sig. bye", &snippets),
+            "thanks, -- Zhang San. bye"
+        );
+        // 与中文相邻不算单词内部
+        assert_eq!(expand("结尾加sig吧", &snippets), "结尾加-- Zhang San吧");
+        assert_eq!(expand("SIG", &snippets), "-- Zhang San");
+    }
+
+    #[test]
+    fn test_expand_skips_disabled() {
+        let mut s = snippet("签名", "张三");
+        s.enabled = false;
+        assert_eq!(expand("签名", &[s]), "签名");
+    }
+
+    #[test]
+    fn test_validate_duplicate_trigger() {
+        let existing = vec![snippet("签名", "张三")];
+        let mut new = snippet("签名 ", "李四");
+        new.id = "other".to_string();
+        assert!(validate(&new, &existing).is_err());
+        assert!(validate(&existing[0], &existing).is_ok());
+    }
+}
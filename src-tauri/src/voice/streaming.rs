@@ -0,0 +1,200 @@
+//! 边录边识别
+//!
+//! 开始录音时即建立流式识别连接，录音处理线程把单声道采样实时转发给支持流式的
+//! ASR 客户端（[`AsrClient::transcribe_stream`]），停止录音后只需等待最后几帧的
+//! 识别结果，端到端延迟接近实时。每次听写的识别由听写会话（[`super::session`]）持有。
+//!
+//! 目前讯飞听写和火山引擎声明支持流式。其他服务（以及流式识别失败、录音超过单次会话上限时）
+//! 在停止录音后回退到整段识别。识别过程中的中间结果通过
+//! [`PARTIAL_TRANSCRIPT_EVENT`] 事件推送给前端。
+//!
+//! 取消听写时触发 [`StreamingRecognition::cancellation_token`]，流式会话和整段识别
+//! 都立即中止，不会在后台继续等待服务端结果。
+
+use futures::StreamExt;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc::UnboundedReceiver;
+use voice_core::asr_client::{AsrClient, CancellationToken};
+use voice_core::error::VoiceError;
+use voice_core::types::{AudioData, PartialTranscript, TranscribeResult};
+
+use super::asr_service::AsrService;
+use super::recording_service::{calibration_gain_db, RecordingService};
+use super::timeline::{self, VoiceStage};
+use crate::config::AsrCredentialEntry;
+
+/// 流式识别中间结果事件，负载为 [`PartialTranscript`]
+pub const PARTIAL_TRANSCRIPT_EVENT: &str = "voice-transcript-partial";
+
+/// 对录音帧应用校准增益后转发，录音结束（发送端关闭）时输出端随之关闭
+fn calibrated_frames(
+    mut frames: UnboundedReceiver<Vec<i16>>,
+    gain_db: f32,
+) -> UnboundedReceiver<Vec<i16>> {
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    tauri::async_runtime::spawn(async move {
+        while let Some(mut frame) = frames.recv().await {
+            voice_core::calibration::apply_gain(&mut frame, gain_db);
+            if sender.send(frame).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+/// 一次听写的边录边识别，由听写会话持有
+pub struct StreamingRecognition {
+    credential: AsrCredentialEntry,
+    /// 流式识别任务（服务不支持流式时为空）
+    task: Option<JoinHandle<Result<TranscribeResult, String>>>,
+    /// 取消令牌，触发时中止流式识别和整段识别
+    cancel: CancellationToken,
+}
+
+/// 凭证对应的服务是否支持流式识别
+///
+/// 隐私设置禁止音频离开本机时，云端服务不做流式识别，录音结束后改用本地识别
+pub fn supports_streaming(credential: &AsrCredentialEntry) -> bool {
+    AsrService::capabilities(credential).streaming
+        && AsrService::client_config(credential).is_ok()
+        && !AsrService::upload_blocked(credential)
+}
+
+impl StreamingRecognition {
+    /// 开始录音，服务支持时同时启动流式识别
+    ///
+    /// 首个中间结果记录到 `session_id` 对应的会话时间线
+    pub fn start(
+        app: &AppHandle,
+        service: &mut RecordingService,
+        device_id: Option<String>,
+        credential: AsrCredentialEntry,
+        session_id: &str,
+    ) -> Result<Self, String> {
+        let cancel = CancellationToken::new();
+        let task = if supports_streaming(&credential) {
+            let client = AsrService::create_client(&credential)?;
+
+            let (tap, frames) = tokio::sync::mpsc::unbounded_channel();
+            // 与整段识别（`stop_calibrated`）一致，送给流式识别的音频也应用设备校准增益
+            let frames = match calibration_gain_db(device_id.as_deref()) {
+                Some(gain_db) => calibrated_frames(frames, gain_db),
+                None => frames,
+            };
+            service.start_streaming(device_id, tap)?;
+            let sample_rate = service.sample_rate();
+
+            let app = app.clone();
+            let session_id = session_id.to_string();
+            let cancel = cancel.clone();
+            Some(tauri::async_runtime::spawn(async move {
+                let mut stream = client.transcribe_stream(frames, sample_rate);
+                loop {
+                    let item = tokio::select! {
+                        biased;
+                        _ = cancel.cancelled() => return Err("流式识别已取消".to_string()),
+                        item = stream.next() => item,
+                    };
+                    let Some(item) = item else {
+                        return Err(format!("{}流式识别未返回最终结果", client.name()));
+                    };
+                    let partial =
+                        item.map_err(|e| format!("{}流式识别失败: {}", client.name(), e))?;
+                    match partial.result {
+                        Some(result) => return Ok(result),
+                        None => {
+                            timeline::mark_once(
+                                Some(session_id.as_str()),
+                                VoiceStage::FirstPartial,
+                            );
+                            let _ = app.emit(PARTIAL_TRANSCRIPT_EVENT, &partial);
+                        }
+                    }
+                }
+            }))
+        } else {
+            service.start(device_id)?;
+            None
+        };
+
+        tracing::info!(
+            "[边录边识别] 开始录音，服务: {:?}，流式: {}",
+            credential.provider,
+            task.is_some()
+        );
+        Ok(Self {
+            credential,
+            task,
+            cancel,
+        })
+    }
+
+    /// 是否启用了流式识别
+    pub fn is_streaming(&self) -> bool {
+        self.task.is_some()
+    }
+
+    /// 取消令牌，识别进行中触发即中止 [`StreamingRecognition::finish`]
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancel.clone()
+    }
+
+    /// 录音停止后获取识别结果
+    ///
+    /// 流式识别成功时直接返回其结果，否则用录音整段识别。同时返回使用的凭证
+    pub async fn finish(
+        self,
+        audio: &AudioData,
+    ) -> Result<(TranscribeResult, AsrCredentialEntry), String> {
+        if let Some(task) = self.task {
+            if AsrService::capabilities(&self.credential).exceeds_max_duration(audio.duration_secs)
+            {
+                // 流式会话有时长上限（60 秒），超出部分需要整段重新识别
+                task.abort();
+                tracing::info!(
+                    "[边录边识别] 录音 {:.1}s 超过流式会话上限，改为整段识别",
+                    audio.duration_secs
+                );
+            } else {
+                match task.await {
+                    Ok(Err(_)) | Err(_) if self.cancel.is_cancelled() => {
+                        return Err(VoiceError::Cancelled.to_string());
+                    }
+                    Ok(Ok(result)) => {
+                        let result = AsrService::finalize(&self.credential, result);
+                        let result = super::homophone::correct(result);
+                        let result = super::processor::apply_spoken_punctuation(result);
+                        let result = super::punctuation::restore(result).await;
+                        return Ok((result, self.credential));
+                    }
+                    Ok(Err(e)) => tracing::warn!("[边录边识别] {}，改为整段识别", e),
+                    Err(e) => tracing::warn!("[边录边识别] 流式识别任务异常: {}，改为整段识别", e),
+                }
+            }
+        }
+
+        let pcm: Vec<u8> = audio
+            .samples
+            .iter()
+            .flat_map(|&s| s.to_le_bytes())
+            .collect();
+        let result = AsrService::transcribe_cancellable(
+            &self.credential,
+            &pcm,
+            audio.sample_rate,
+            &self.cancel,
+        )
+        .await?;
+        Ok((result, self.credential))
+    }
+
+    /// 取消流式识别（录音由调用方取消）
+    pub fn cancel(self) {
+        self.cancel.cancel();
+        if let Some(task) = self.task {
+            task.abort();
+        }
+    }
+}
@@ -0,0 +1,390 @@
+//! 语音会话时间线
+//!
+//! 记录每次听写各阶段（录音开始/结束、提交识别、首个中间结果、识别完成、
+//! 润色开始/完成、输出完成）的时间点，用于排查从说完话到文字上屏的延迟。
+//!
+//! 每个阶段发生时发送 [`SESSION_STAGE_EVENT`] 事件。各阶段按会话 ID 记录，连续听写时
+//! 前一次的识别、润色不会记到后一次的会话上。最近的会话保存在内存（最多
+//! [`MAX_SESSIONS`] 条），不记录识别文本内容；保存了听写历史的会话结束时，
+//! 时间线随历史记录一起写入数据库。
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+/// 会话阶段事件
+pub const SESSION_STAGE_EVENT: &str = "voice-session-stage";
+
+/// 保留的最近会话数
+pub const MAX_SESSIONS: usize = 50;
+
+/// 听写阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceStage {
+    /// 开始录音
+    RecordStart,
+    /// 停止录音
+    RecordStop,
+    /// 提交识别
+    AsrSubmit,
+    /// 收到首个中间结果（仅边录边识别）
+    FirstPartial,
+    /// 识别完成
+    AsrDone,
+    /// 开始润色
+    PolishStart,
+    /// 润色完成
+    PolishDone,
+    /// 输出完成
+    OutputDone,
+}
+
+/// 阶段时间点
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageEvent {
+    pub stage: VoiceStage,
+    /// 发生时间（毫秒时间戳）
+    pub at: i64,
+    /// 距录音开始的毫秒数
+    pub elapsed_ms: u64,
+}
+
+/// 一次听写的时间线
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionTimeline {
+    pub id: String,
+    /// 开始时间（毫秒时间戳）
+    pub started_at: i64,
+    /// 使用的 ASR 服务
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub events: Vec<StageEvent>,
+}
+
+/// 阶段事件负载
+#[derive(Debug, Clone, Serialize)]
+struct StagePayload {
+    session_id: String,
+    #[serde(flatten)]
+    event: StageEvent,
+}
+
+/// 记录中的会话
+struct TrackedSession {
+    started: Instant,
+    timeline: SessionTimeline,
+    /// 对应的听写历史记录 ID
+    history_id: Option<String>,
+    /// 输出完成后不再记录新的阶段
+    finished: bool,
+}
+
+/// 最近的会话（最近在前），连续听写时可能同时有多个会话在识别或润色
+#[derive(Default)]
+struct Timelines {
+    app: Option<AppHandle>,
+    sessions: VecDeque<TrackedSession>,
+}
+
+impl Timelines {
+    const fn new() -> Self {
+        Self {
+            app: None,
+            sessions: VecDeque::new(),
+        }
+    }
+
+    fn begin(&mut self, id: String) {
+        self.sessions.push_front(TrackedSession {
+            started: Instant::now(),
+            timeline: SessionTimeline {
+                id,
+                started_at: chrono::Utc::now().timestamp_millis(),
+                provider: None,
+                events: Vec::new(),
+            },
+            history_id: None,
+            finished: false,
+        });
+        self.sessions.truncate(MAX_SESSIONS);
+    }
+
+    /// 查找会话：指定 ID 时按 ID 查找，否则取最近一个未结束的会话
+    fn find(&mut self, session_id: Option<&str>) -> Option<&mut TrackedSession> {
+        self.sessions
+            .iter_mut()
+            .find(|s| session_id.map_or(!s.finished, |id| s.timeline.id == id))
+    }
+
+    /// 记录阶段时间点，返回要发送的事件（会话不存在或已结束时忽略）
+    fn record(&mut self, session_id: Option<&str>, stage: VoiceStage) -> Option<StagePayload> {
+        let session = self.find(session_id).filter(|s| !s.finished)?;
+        let event = StageEvent {
+            stage,
+            at: chrono::Utc::now().timestamp_millis(),
+            elapsed_ms: session.started.elapsed().as_millis() as u64,
+        };
+        session.timeline.events.push(event.clone());
+        Some(StagePayload {
+            session_id: session.timeline.id.clone(),
+            event,
+        })
+    }
+
+    /// 阶段尚未记录时记录
+    fn record_once(&mut self, session_id: Option<&str>, stage: VoiceStage) -> Option<StagePayload> {
+        let recorded = self
+            .find(session_id)
+            .is_some_and(|s| s.timeline.events.iter().any(|e| e.stage == stage));
+        if recorded {
+            return None;
+        }
+        self.record(session_id, stage)
+    }
+
+    /// 结束会话，返回关联了听写历史的时间线
+    fn finish(&mut self, session_id: Option<&str>) -> Option<(String, SessionTimeline)> {
+        let session = self.find(session_id)?;
+        session.finished = true;
+        let history_id = session.history_id.clone()?;
+        Some((history_id, session.timeline.clone()))
+    }
+
+    fn discard(&mut self, session_id: Option<&str>) {
+        let index = self
+            .sessions
+            .iter()
+            .position(|s| session_id.map_or(!s.finished, |id| s.timeline.id == id));
+        if let Some(index) = index {
+            self.sessions.remove(index);
+        }
+    }
+}
+
+static SESSIONS: Mutex<Timelines> = Mutex::new(Timelines::new());
+
+/// 在锁外发送阶段事件
+fn emit(app: Option<AppHandle>, payload: Option<StagePayload>) {
+    let Some(payload) = payload else {
+        return;
+    };
+    tracing::debug!(
+        "[会话时间线] {} {:?} +{}ms",
+        payload.session_id,
+        payload.event.stage,
+        payload.event.elapsed_ms
+    );
+    if let Some(app) = app {
+        let _ = app.emit(SESSION_STAGE_EVENT, payload);
+    }
+}
+
+/// 开始新的会话并记录录音开始，返回会话 ID
+///
+/// 之前的会话可能仍在识别或润色，按各自的会话 ID 继续记录
+pub fn begin(app: &AppHandle) -> String {
+    let id = uuid::Uuid::new_v4().to_string();
+    let payload = {
+        let mut timelines = SESSIONS.lock();
+        timelines.app = Some(app.clone());
+        timelines.begin(id.clone());
+        timelines.record(Some(&id), VoiceStage::RecordStart)
+    };
+    emit(Some(app.clone()), payload);
+    id
+}
+
+/// 记录阶段时间点（会话不存在或已结束时忽略）
+pub fn mark(session_id: Option<&str>, stage: VoiceStage) {
+    let (app, payload) = {
+        let mut timelines = SESSIONS.lock();
+        let payload = timelines.record(session_id, stage);
+        (timelines.app.clone(), payload)
+    };
+    emit(app, payload);
+}
+
+/// 阶段尚未记录时记录（用于首个中间结果）
+pub fn mark_once(session_id: Option<&str>, stage: VoiceStage) {
+    let (app, payload) = {
+        let mut timelines = SESSIONS.lock();
+        let payload = timelines.record_once(session_id, stage);
+        (timelines.app.clone(), payload)
+    };
+    emit(app, payload);
+}
+
+/// 记录识别完成及使用的服务
+pub fn mark_asr_done(session_id: Option<&str>, provider: &str) {
+    if let Some(session) = SESSIONS.lock().find(session_id) {
+        session.timeline.provider = Some(provider.to_string());
+    }
+    mark(session_id, VoiceStage::AsrDone);
+}
+
+/// 关联听写历史记录，会话结束时时间线随该记录保存
+pub fn attach_history(session_id: Option<&str>, history_id: &str) {
+    if let Some(session) = SESSIONS.lock().find(session_id) {
+        session.history_id = Some(history_id.to_string());
+    }
+}
+
+/// 结束会话，之后不再记录新的阶段
+///
+/// 返回关联的听写历史 ID 和完整时间线，由调用方写入数据库
+pub fn finish(session_id: Option<&str>) -> Option<(String, SessionTimeline)> {
+    SESSIONS.lock().finish(session_id)
+}
+
+/// 丢弃会话（取消录音时）
+pub fn discard(session_id: Option<&str>) {
+    SESSIONS.lock().discard(session_id);
+}
+
+/// 最近的会话（最近在前，包含进行中的会话）
+pub fn sessions() -> Vec<SessionTimeline> {
+    SESSIONS
+        .lock()
+        .sessions
+        .iter()
+        .map(|s| s.timeline.clone())
+        .collect()
+}
+
+/// 清空已结束的会话
+pub fn clear() {
+    SESSIONS.lock().sessions.retain(|s| !s.finished);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stages(timelines: &Timelines, index: usize) -> Vec<VoiceStage> {
+        timelines.sessions[index]
+            .timeline
+            .events
+            .iter()
+            .map(|e| e.stage)
+            .collect()
+    }
+
+    #[test]
+    fn test_stages_recorded_in_order() {
+        let mut timelines = Timelines::default();
+        timelines.begin("a".to_string());
+        for stage in [
+            VoiceStage::RecordStart,
+            VoiceStage::RecordStop,
+            VoiceStage::AsrSubmit,
+            VoiceStage::AsrDone,
+            VoiceStage::PolishStart,
+            VoiceStage::PolishDone,
+            VoiceStage::OutputDone,
+        ] {
+            assert!(timelines.record(None, stage).is_some());
+        }
+        assert_eq!(
+            stages(&timelines, 0),
+            vec![
+                VoiceStage::RecordStart,
+                VoiceStage::RecordStop,
+                VoiceStage::AsrSubmit,
+                VoiceStage::AsrDone,
+                VoiceStage::PolishStart,
+                VoiceStage::PolishDone,
+                VoiceStage::OutputDone,
+            ]
+        );
+        let events = &timelines.sessions[0].timeline.events;
+        assert!(events
+            .windows(2)
+            .all(|w| w[0].elapsed_ms <= w[1].elapsed_ms));
+    }
+
+    #[test]
+    fn test_overlapping_sessions_keep_their_own_stages() {
+        let mut timelines = Timelines::default();
+        timelines.begin("first".to_string());
+        timelines.record(Some("first"), VoiceStage::RecordStart);
+        timelines.record(Some("first"), VoiceStage::RecordStop);
+
+        // 第一次还在识别时开始第二次听写
+        timelines.begin("second".to_string());
+        timelines.record(Some("second"), VoiceStage::RecordStart);
+        timelines.record(Some("first"), VoiceStage::AsrDone);
+        timelines.record(None, VoiceStage::RecordStop);
+
+        assert_eq!(timelines.sessions[0].timeline.id, "second");
+        assert_eq!(
+            stages(&timelines, 0),
+            vec![VoiceStage::RecordStart, VoiceStage::RecordStop]
+        );
+        assert_eq!(
+            stages(&timelines, 1),
+            vec![
+                VoiceStage::RecordStart,
+                VoiceStage::RecordStop,
+                VoiceStage::AsrDone
+            ]
+        );
+    }
+
+    #[test]
+    fn test_record_once_and_finished_sessions() {
+        let mut timelines = Timelines::default();
+        timelines.begin("a".to_string());
+        assert!(timelines
+            .record_once(None, VoiceStage::FirstPartial)
+            .is_some());
+        assert!(timelines
+            .record_once(None, VoiceStage::FirstPartial)
+            .is_none());
+
+        // 未关联听写历史时结束不返回时间线
+        assert!(timelines.finish(Some("a")).is_none());
+        assert!(timelines
+            .record(Some("a"), VoiceStage::OutputDone)
+            .is_none());
+        assert!(timelines.record(None, VoiceStage::AsrDone).is_none());
+        assert_eq!(stages(&timelines, 0), vec![VoiceStage::FirstPartial]);
+    }
+
+    #[test]
+    fn test_finish_returns_timeline_for_history() {
+        let mut timelines = Timelines::default();
+        timelines.begin("a".to_string());
+        timelines.record(None, VoiceStage::RecordStart);
+        timelines.find(Some("a")).unwrap().history_id = Some("h1".to_string());
+        timelines.record(None, VoiceStage::OutputDone);
+
+        let (history_id, timeline) = timelines.finish(Some("a")).unwrap();
+        assert_eq!(history_id, "h1");
+        assert_eq!(timeline.id, "a");
+        assert_eq!(timeline.events.len(), 2);
+    }
+
+    #[test]
+    fn test_discard_and_capacity() {
+        let mut timelines = Timelines::default();
+        for i in 0..MAX_SESSIONS + 5 {
+            timelines.begin(i.to_string());
+        }
+        assert_eq!(timelines.sessions.len(), MAX_SESSIONS);
+        assert_eq!(
+            timelines.sessions[0].timeline.id,
+            (MAX_SESSIONS + 4).to_string()
+        );
+
+        timelines.discard(None);
+        assert_eq!(timelines.sessions.len(), MAX_SESSIONS - 1);
+        assert!(timelines
+            .find(Some(&(MAX_SESSIONS + 4).to_string()))
+            .is_none());
+    }
+}
@@ -0,0 +1,332 @@
+//! ASR 用量统计
+//!
+//! 每次调用识别服务记录一条用量（凭证、服务、音频时长、耗时、结果），写入 `asr_usage` 表：
+//!
+//! - 云端客户端由 [`MeteredClient`] 包装，重试的每次请求、回退链和竞速中的每个服务各记一条；
+//!   竞速落败被取消的请求记为已取消
+//! - 流式识别的音频时长按实际推送的采样计算，耗时从录音结束算起
+//! - 本地识别同样记录，便于对比云端和本地的耗时
+//!
+//! `get_asr_usage_stats` 按凭证汇总本月用量，用于估算各凭证消耗的云端配额（按成功识别的音频时长计）。
+//! 只保留最近 [`RETENTION_DAYS`] 天的记录。
+
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::{Datelike, Local, TimeZone};
+use futures::stream::{BoxStream, StreamExt};
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use tokio::sync::mpsc::UnboundedReceiver;
+use voice_core::asr_client::{AsrCapabilities, AsrClient};
+use voice_core::types::{AudioData, PartialTranscript, TranscribeResult};
+use voice_core::VoiceError;
+
+use crate::database::dao::asr_usage::AsrUsageDao;
+use crate::database::DbConnection;
+
+/// 用量记录的保留天数
+pub const RETENTION_DAYS: i64 = 400;
+
+/// 用量记录写入的数据库（语音输入模块初始化时设置）
+static DB: OnceCell<DbConnection> = OnceCell::new();
+
+/// 一次调用的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AsrUsageStatus {
+    Success,
+    Error,
+    /// 被取消（用户取消或竞速落败）
+    Cancelled,
+}
+
+impl AsrUsageStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Error => "error",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// 一次识别调用
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AsrUsageRecord {
+    pub id: String,
+    pub credential_id: String,
+    /// 识别服务名称（如 讯飞语音、讯飞录音文件转写）
+    pub provider: String,
+    /// 音频时长（秒）
+    pub audio_secs: f32,
+    /// 耗时（毫秒）
+    pub latency_ms: u64,
+    pub status: AsrUsageStatus,
+    /// 失败原因
+    pub error: Option<String>,
+    pub created_at: i64,
+}
+
+/// 凭证在一个服务上的用量汇总
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AsrUsageStats {
+    pub credential_id: String,
+    pub provider: String,
+    /// 调用次数
+    pub calls: u32,
+    /// 成功次数
+    pub succeeded: u32,
+    /// 失败次数（其余为取消）
+    pub failed: u32,
+    /// 成功识别的音频时长（秒）
+    pub audio_secs: f64,
+    /// 成功调用的平均耗时（毫秒）
+    pub avg_latency_ms: f64,
+}
+
+/// 设置用量记录的数据库，并清理过期记录
+pub fn init(db: DbConnection) {
+    let before = chrono::Utc::now().timestamp_millis() - RETENTION_DAYS * 24 * 3600 * 1000;
+    match db.lock() {
+        Ok(conn) => match AsrUsageDao::delete_before(&conn, before) {
+            Ok(0) => {}
+            Ok(deleted) => tracing::info!("[ASR 用量] 清理了 {} 条过期记录", deleted),
+            Err(e) => tracing::warn!("[ASR 用量] 清理过期记录失败: {}", e),
+        },
+        Err(e) => tracing::warn!("[ASR 用量] 数据库锁定失败: {}", e),
+    }
+    let _ = DB.set(db);
+}
+
+/// 写入一条记录，未初始化或写入失败时只记录日志
+fn record(record: &AsrUsageRecord) {
+    let Some(db) = DB.get() else {
+        return;
+    };
+    let result = db
+        .lock()
+        .map_err(|e| e.to_string())
+        .and_then(|conn| AsrUsageDao::insert(&conn, record).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        tracing::warn!("[ASR 用量] 写入记录失败: {}", e);
+    }
+}
+
+/// 本月第一天零点（本地时间）的毫秒时间戳
+pub fn month_start() -> i64 {
+    let today = Local::now().date_naive();
+    let first = today.with_day(1).unwrap_or(today);
+    first
+        .and_hms_opt(0, 0, 0)
+        .and_then(|t| Local.from_local_datetime(&t).earliest())
+        .map(|t| t.timestamp_millis())
+        .unwrap_or_default()
+}
+
+/// 按凭证汇总 `since`（毫秒时间戳）之后的用量
+pub fn stats(db: &DbConnection, since: i64) -> Result<Vec<AsrUsageStats>, String> {
+    let conn = db.lock().map_err(|e| e.to_string())?;
+    AsrUsageDao::stats_since(&conn, since).map_err(|e| e.to_string())
+}
+
+/// 进行中的一次调用，结束前被丢弃时记为已取消
+struct Call {
+    credential_id: String,
+    provider: String,
+    audio_secs: f32,
+    started: Instant,
+    finished: bool,
+}
+
+impl Call {
+    fn start(credential_id: &str, provider: &str, audio_secs: f32) -> Self {
+        Self {
+            credential_id: credential_id.to_string(),
+            provider: provider.to_string(),
+            audio_secs,
+            started: Instant::now(),
+            finished: false,
+        }
+    }
+
+    fn finish(&mut self, status: AsrUsageStatus, error: Option<String>) {
+        self.finished = true;
+        record(&AsrUsageRecord {
+            id: uuid::Uuid::new_v4().to_string(),
+            credential_id: self.credential_id.clone(),
+            provider: self.provider.clone(),
+            audio_secs: self.audio_secs,
+            latency_ms: self.started.elapsed().as_millis() as u64,
+            status,
+            error,
+            created_at: chrono::Utc::now().timestamp_millis(),
+        });
+    }
+
+    fn finish_with<T>(&mut self, result: &Result<T, VoiceError>) {
+        match result {
+            Ok(_) => self.finish(AsrUsageStatus::Success, None),
+            Err(VoiceError::Cancelled) => self.finish(AsrUsageStatus::Cancelled, None),
+            Err(e) => self.finish(AsrUsageStatus::Error, Some(e.to_string())),
+        }
+    }
+}
+
+impl Drop for Call {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.finish(AsrUsageStatus::Cancelled, None);
+        }
+    }
+}
+
+/// 记录本地识别等不经过客户端的调用
+pub async fn metered<T>(
+    credential_id: &str,
+    provider: &str,
+    audio_secs: f32,
+    call: impl Future<Output = Result<T, String>>,
+) -> Result<T, String> {
+    let mut usage = Call::start(credential_id, provider, audio_secs);
+    let result = call.await;
+    match &result {
+        Ok(_) => usage.finish(AsrUsageStatus::Success, None),
+        Err(e) => usage.finish(AsrUsageStatus::Error, Some(e.clone())),
+    }
+    result
+}
+
+/// 记录每次调用用量的客户端包装
+pub struct MeteredClient {
+    client: Box<dyn AsrClient>,
+    credential_id: String,
+}
+
+impl MeteredClient {
+    pub fn new(client: Box<dyn AsrClient>, credential_id: String) -> Self {
+        Self {
+            client,
+            credential_id,
+        }
+    }
+}
+
+#[async_trait]
+impl AsrClient for MeteredClient {
+    async fn transcribe(&self, audio: &AudioData) -> voice_core::Result<TranscribeResult> {
+        let mut usage = Call::start(&self.credential_id, self.client.name(), audio.duration_secs);
+        let result = self.client.transcribe(audio).await;
+        usage.finish_with(&result);
+        result
+    }
+
+    fn transcribe_stream<'a>(
+        &'a self,
+        mut frames: UnboundedReceiver<Vec<i16>>,
+        sample_rate: u32,
+    ) -> BoxStream<'a, voice_core::Result<PartialTranscript>> {
+        // 转发音频时累计采样数，录音结束时开始计时
+        let samples = Arc::new(AtomicUsize::new(0));
+        let ended = Arc::new(parking_lot::Mutex::new(None));
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let (counted, ended_at) = (samples.clone(), ended.clone());
+        tokio::spawn(async move {
+            while let Some(chunk) = frames.recv().await {
+                counted.fetch_add(chunk.len(), Ordering::Relaxed);
+                if tx.send(chunk).is_err() {
+                    break;
+                }
+            }
+            *ended_at.lock() = Some(Instant::now());
+        });
+
+        let mut usage = Call::start(&self.credential_id, self.client.name(), 0.0);
+        self.client
+            .transcribe_stream(rx, sample_rate)
+            .map(move |item| {
+                let done = match &item {
+                    Ok(partial) => partial.result.is_some(),
+                    Err(_) => true,
+                };
+                if done && !usage.finished {
+                    usage.audio_secs =
+                        samples.load(Ordering::Relaxed) as f32 / sample_rate.max(1) as f32;
+                    if let Some(ended) = *ended.lock() {
+                        usage.started = ended;
+                    }
+                    usage.finish_with(&item);
+                }
+                item
+            })
+            .boxed()
+    }
+
+    fn name(&self) -> &'static str {
+        self.client.name()
+    }
+
+    fn capabilities(&self) -> AsrCapabilities {
+        self.client.capabilities()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_start() {
+        let start = Local.timestamp_millis_opt(month_start()).unwrap();
+        let now = Local::now();
+        assert_eq!(start.day(), 1);
+        assert_eq!(start.month(), now.month());
+        assert_eq!(start.year(), now.year());
+        assert!(start <= now);
+    }
+
+    #[test]
+    fn test_stats_by_credential() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::database::schema::create_tables(&conn).unwrap();
+
+        let usage =
+            |credential: &str, secs: f32, status: AsrUsageStatus, created_at: i64| AsrUsageRecord {
+                id: uuid::Uuid::new_v4().to_string(),
+                credential_id: credential.to_string(),
+                provider: "讯飞语音".to_string(),
+                audio_secs: secs,
+                latency_ms: 400,
+                status,
+                error: None,
+                created_at,
+            };
+        for record in [
+            usage("a", 10.0, AsrUsageStatus::Success, 2000),
+            usage("a", 5.0, AsrUsageStatus::Success, 3000),
+            usage("a", 8.0, AsrUsageStatus::Error, 3000),
+            usage("b", 3.0, AsrUsageStatus::Cancelled, 3000),
+            // 统计区间之前
+            usage("b", 60.0, AsrUsageStatus::Success, 500),
+        ] {
+            AsrUsageDao::insert(&conn, &record).unwrap();
+        }
+
+        let stats = AsrUsageDao::stats_since(&conn, 1000).unwrap();
+        assert_eq!(stats.len(), 2);
+        let a = &stats[0];
+        assert_eq!(a.credential_id, "a");
+        assert_eq!((a.calls, a.succeeded, a.failed), (3, 2, 1));
+        assert!((a.audio_secs - 15.0).abs() < 1e-6);
+        assert!((a.avg_latency_ms - 400.0).abs() < 1e-6);
+
+        let b = &stats[1];
+        assert_eq!((b.calls, b.succeeded, b.failed), (1, 0, 0));
+        assert_eq!(b.audio_secs, 0.0);
+
+        assert_eq!(AsrUsageDao::delete_before(&conn, 1000).unwrap(), 1);
+    }
+}
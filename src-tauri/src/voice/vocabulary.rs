@@ -0,0 +1,114 @@
+//! 识别热词
+//!
+//! 每个工作区可维护一份热词表（专业术语、产品名、人名等），保存在工作区设置的
+//! `voice_vocabulary` 中。听写使用默认工作区的热词表，构建云端客户端时通过
+//! [`voice_core::asr_client::AsrClient::with_hotwords`] 传给支持热词的服务，提高这些词的识别率。
+//!
+//! 热词表缓存在内存中，启动时和工作区变更后重新加载，听写时不必读取数据库。
+
+use parking_lot::RwLock;
+
+use crate::database::DbConnection;
+use crate::workspace::{WorkspaceManager, WorkspaceUpdate};
+
+/// 热词个数上限，过长的列表会被部分服务拒绝
+const MAX_WORDS: usize = 100;
+
+/// 默认工作区的热词表
+static VOCABULARY: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// 当前使用的热词表
+pub fn current() -> Vec<String> {
+    VOCABULARY.read().clone()
+}
+
+/// 重新加载默认工作区的热词表（没有默认工作区时清空）
+pub fn reload(db: &DbConnection) {
+    let words = match WorkspaceManager::new(db.clone()).get_default() {
+        Ok(workspace) => workspace
+            .map(|w| w.settings.voice_vocabulary)
+            .unwrap_or_default(),
+        Err(e) => {
+            tracing::warn!("[语音输入] 读取热词表失败: {}", e);
+            return;
+        }
+    };
+    tracing::debug!("[语音输入] 已加载 {} 个热词", words.len());
+    *VOCABULARY.write() = words;
+}
+
+/// 读取工作区的热词表，未指定工作区时读取默认工作区
+pub fn get(db: &DbConnection, workspace_id: Option<&str>) -> Result<Vec<String>, String> {
+    let manager = WorkspaceManager::new(db.clone());
+    let workspace = match workspace_id {
+        Some(id) => manager.get(&id.to_string())?,
+        None => manager.get_default()?,
+    };
+    Ok(workspace
+        .map(|w| w.settings.voice_vocabulary)
+        .unwrap_or_default())
+}
+
+/// 保存工作区的热词表，未指定工作区时保存到默认工作区，返回整理后的热词表
+pub fn set(
+    db: &DbConnection,
+    workspace_id: Option<&str>,
+    words: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let manager = WorkspaceManager::new(db.clone());
+    let workspace = match workspace_id {
+        Some(id) => manager.get(&id.to_string())?,
+        None => manager.get_default()?,
+    }
+    .ok_or_else(|| "工作区不存在".to_string())?;
+
+    let words = normalize(words);
+    let mut settings = workspace.settings;
+    settings.voice_vocabulary = words.clone();
+    manager.update(
+        &workspace.id,
+        WorkspaceUpdate {
+            settings: Some(settings),
+            ..Default::default()
+        },
+    )?;
+
+    reload(db);
+    Ok(words)
+}
+
+/// 去掉首尾空白、空词和重复词，最多保留 [`MAX_WORDS`] 个
+fn normalize(words: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for word in words {
+        let word = word.trim();
+        if !word.is_empty() && !normalized.iter().any(|w| w == word) {
+            normalized.push(word.to_string());
+        }
+    }
+    normalized.truncate(MAX_WORDS);
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let words = vec![
+            " ProxyCast ".to_string(),
+            String::new(),
+            "钉钉".to_string(),
+            "ProxyCast".to_string(),
+            "  ".to_string(),
+        ];
+        assert_eq!(normalize(words), vec!["ProxyCast", "钉钉"]);
+    }
+
+    #[test]
+    fn test_normalize_caps_count() {
+        let words = (0..MAX_WORDS + 10).map(|i| format!("词{}", i)).collect();
+        assert_eq!(normalize(words).len(), MAX_WORDS);
+    }
+}
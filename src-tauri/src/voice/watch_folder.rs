@@ -0,0 +1,296 @@
+//! 监听文件夹自动转写
+//!
+//! 监听用户配置的文件夹，出现新的音频文件时（如手机录音经 iCloud/坚果云同步），
+//! 等待文件写入完成后自动转写，并按文件夹配置导出转写结果。
+//!
+//! 每个文件在独立任务中等待写入完成，互不阻塞；转写时所有文件夹共用一个许可，
+//! 逐个转写，避免同时占用 ASR 服务。
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, Semaphore};
+
+use super::batch::{self, TranscriptTarget};
+use super::config;
+use crate::config::WatchFolderConfig;
+
+/// 转写完成（或失败）时发送给前端的事件名
+pub const WATCH_FOLDER_EVENT: &str = "voice-watch-folder-transcribed";
+
+/// 检查文件是否写入完成的间隔
+const STABLE_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 等待文件写入完成的最长时间
+const STABLE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// 当前生效的文件监听器
+static WATCHERS: Mutex<Vec<RecommendedWatcher>> = Mutex::new(Vec::new());
+
+/// 处理队列（文件夹 ID，音频路径）
+static QUEUE: OnceCell<mpsc::UnboundedSender<(String, PathBuf)>> = OnceCell::new();
+
+/// 转写结果事件
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchFolderTranscribed {
+    pub folder_id: String,
+    pub audio_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcript_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 按当前配置重新建立所有文件夹监听，并补转写已存在的未转写音频
+pub fn reload(app: &AppHandle) -> Result<(), String> {
+    let folders = config::load_voice_config()?.watch_folders;
+    let queue = queue(app);
+
+    let mut watchers = WATCHERS.lock();
+    watchers.clear();
+
+    for folder in folders.iter().filter(|f| f.enabled) {
+        match watch(folder, queue.clone()) {
+            Ok(watcher) => {
+                watchers.push(watcher);
+                tracing::info!("[监听文件夹] 开始监听: {}", folder.path);
+            }
+            Err(e) => {
+                tracing::warn!("[监听文件夹] 监听失败: {} - {}", folder.path, e);
+                continue;
+            }
+        }
+
+        if let Err(e) = enqueue_pending(folder, &queue) {
+            tracing::warn!("[监听文件夹] 扫描失败: {} - {}", folder.path, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// 停止所有文件夹监听
+pub fn stop() {
+    WATCHERS.lock().clear();
+}
+
+/// 手动扫描文件夹，将未转写的音频加入队列，返回加入数量
+pub fn rescan(app: &AppHandle, folder: &WatchFolderConfig) -> Result<usize, String> {
+    enqueue_pending(folder, &queue(app))
+}
+
+/// 获取处理队列，首次调用时启动处理任务
+fn queue(app: &AppHandle) -> mpsc::UnboundedSender<(String, PathBuf)> {
+    QUEUE
+        .get_or_init(|| {
+            let (tx, rx) = mpsc::unbounded_channel();
+            let app = app.clone();
+            tauri::async_runtime::spawn(process_queue(app, rx));
+            tx
+        })
+        .clone()
+}
+
+fn watch(
+    folder: &WatchFolderConfig,
+    queue: mpsc::UnboundedSender<(String, PathBuf)>,
+) -> Result<RecommendedWatcher, String> {
+    let folder_id = folder.id.clone();
+    let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
+        match res {
+            Ok(event) => {
+                // 同步工具通常先写临时文件再重命名，因此同时关注创建和重命名
+                if !matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(notify::event::ModifyKind::Name(_))
+                ) {
+                    return;
+                }
+                for path in event.paths {
+                    if batch::is_audio_file(&path) {
+                        let _ = queue.send((folder_id.clone(), path));
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!("[监听文件夹] 文件监控错误: {:?}", e);
+            }
+        }
+    })
+    .map_err(|e| e.to_string())?;
+
+    let mode = if folder.recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    watcher
+        .watch(Path::new(&folder.path), mode)
+        .map_err(|e| e.to_string())?;
+    Ok(watcher)
+}
+
+fn enqueue_pending(
+    folder: &WatchFolderConfig,
+    queue: &mpsc::UnboundedSender<(String, PathBuf)>,
+) -> Result<usize, String> {
+    let pending =
+        batch::pending_audio_files(Path::new(&folder.path), folder.recursive, &target(folder))?;
+    let count = pending.len();
+    for path in pending {
+        let _ = queue.send((folder.id.clone(), path));
+    }
+    Ok(count)
+}
+
+fn target(folder: &WatchFolderConfig) -> TranscriptTarget {
+    TranscriptTarget {
+        output_dir: folder.output_dir.as_ref().map(PathBuf::from),
+        format: folder.output_format,
+    }
+}
+
+async fn process_queue(app: AppHandle, mut rx: mpsc::UnboundedReceiver<(String, PathBuf)>) {
+    // 同一文件可能触发多个事件，已处理过的路径在本次运行中不再处理
+    let seen = Arc::new(Mutex::new(HashSet::new()));
+    // 同一时间只转写一个文件
+    let permit = Arc::new(Semaphore::new(1));
+
+    while let Some((folder_id, path)) = rx.recv().await {
+        if !seen.lock().insert(path.clone()) {
+            continue;
+        }
+        // 等待写入完成可能长达数分钟，每个文件单独处理，不阻塞队列中的其他文件
+        tauri::async_runtime::spawn(process_file(
+            app.clone(),
+            folder_id,
+            path,
+            seen.clone(),
+            permit.clone(),
+        ));
+    }
+}
+
+async fn process_file(
+    app: AppHandle,
+    folder_id: String,
+    path: PathBuf,
+    seen: Arc<Mutex<HashSet<PathBuf>>>,
+    permit: Arc<Semaphore>,
+) {
+    // 每次处理时重新读取配置，文件夹可能已被删除或修改
+    let folder = match config::load_voice_config() {
+        Ok(c) => c
+            .watch_folders
+            .into_iter()
+            .find(|f| f.id == folder_id && f.enabled),
+        Err(e) => {
+            tracing::warn!("[监听文件夹] 读取配置失败: {}", e);
+            None
+        }
+    };
+    let Some(folder) = folder else {
+        seen.lock().remove(&path);
+        return;
+    };
+
+    let target = target(&folder);
+    if batch::transcript_path(&path, &target).exists() {
+        return;
+    }
+
+    let result = async {
+        wait_until_stable(&path).await?;
+        let _permit = permit
+            .acquire()
+            .await
+            .map_err(|e| format!("转写队列已关闭: {}", e))?;
+        transcribe(&folder, &path, &target).await
+    }
+    .await;
+
+    let event = match result {
+        Ok((transcript, text)) => {
+            tracing::info!("[监听文件夹] 转写完成: {}", path.display());
+            WatchFolderTranscribed {
+                folder_id,
+                audio_path: path.display().to_string(),
+                transcript_path: Some(transcript.display().to_string()),
+                text: Some(text),
+                error: None,
+            }
+        }
+        Err(e) => {
+            tracing::warn!("[监听文件夹] 转写失败: {} - {}", path.display(), e);
+            // 失败的文件允许在下次扫描时重试
+            seen.lock().remove(&path);
+            WatchFolderTranscribed {
+                folder_id,
+                audio_path: path.display().to_string(),
+                transcript_path: None,
+                text: None,
+                error: Some(e),
+            }
+        }
+    };
+
+    let _ = app.emit(WATCH_FOLDER_EVENT, &event);
+}
+
+async fn transcribe(
+    folder: &WatchFolderConfig,
+    path: &Path,
+    target: &TranscriptTarget,
+) -> Result<(PathBuf, String), String> {
+    let credential = batch::resolve_credential(folder.credential_id.as_deref())?;
+    let mut result = batch::transcribe_file(path, &credential).await?;
+
+    if let Some(instruction_id) = &folder.instruction_id {
+        let instruction = config::get_instruction(instruction_id)?
+            .ok_or_else(|| format!("指令不存在: {}", instruction_id))?;
+        let voice_config = config::load_voice_config()?;
+        // 润色后的文本与分段不再对应，不再按说话人分段输出
+        result.text = super::processor::polish_text(
+            &result.text,
+            &instruction,
+            voice_config.processor.polish_provider.as_deref(),
+            voice_config.processor.polish_model.as_deref(),
+        )
+        .await?
+        .text;
+        result.segments.clear();
+    }
+
+    let transcript = batch::write_transcript(path, &result, target).await?;
+    Ok((transcript, result.text))
+}
+
+/// 等待文件大小稳定（同步工具写入完成）
+async fn wait_until_stable(path: &Path) -> Result<(), String> {
+    let started = std::time::Instant::now();
+    let mut last_size = None;
+
+    loop {
+        let size = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| format!("读取文件信息失败: {}", e))?
+            .len();
+        if size > 0 && last_size == Some(size) {
+            return Ok(());
+        }
+        if started.elapsed() > STABLE_TIMEOUT {
+            return Err("等待文件写入完成超时".to_string());
+        }
+        last_size = Some(size);
+        tokio::time::sleep(STABLE_CHECK_INTERVAL).await;
+    }
+}
@@ -1,15 +1,53 @@
 //! 语音输入悬浮窗口管理
 //!
-//! 创建和管理语音输入的悬浮窗口
+//! 创建和管理语音输入的悬浮窗口，以及录音指示窗口：
+//! 开启 `window.recording_indicator` 后，麦克风录音期间在每个显示器右上角显示一个
+//! 置顶的小红点，与悬浮窗是否打开无关（麦克风测试、校准等录音同样显示）。
 
-use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+use tauri::{
+    AppHandle, Emitter, Manager, Monitor, PhysicalPosition, WebviewUrl, WebviewWindowBuilder,
+    WindowEvent,
+};
+
+use super::config;
+use crate::config::VoiceWindowPosition;
 
 const VOICE_WINDOW_LABEL: &str = "voice-input";
 const VOICE_WINDOW_WIDTH: f64 = 500.0;
 const VOICE_WINDOW_HEIGHT: f64 = 80.0;
+/// 窗口拖动结束后保存位置的防抖时间（毫秒）
+const SAVE_DEBOUNCE_MS: u64 = 500;
+
+/// 录音指示窗口标签前缀，后接显示器序号
+const INDICATOR_LABEL_PREFIX: &str = "voice-recording-indicator-";
+/// 录音指示窗口边长（逻辑像素）
+const INDICATOR_SIZE: f64 = 14.0;
+/// 录音指示窗口与显示器右上角的距离（逻辑像素）
+const INDICATOR_MARGIN: f64 = 6.0;
+
+/// 程序主动设置的窗口位置，用于区分用户手动拖动
+static PROGRAMMATIC_POSITION: Mutex<Option<(i32, i32)>> = Mutex::new(None);
+
+/// 窗口移动事件代次，用于防抖
+static MOVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 打开悬浮窗前的焦点应用，听写结果输出到该应用
+static TARGET_APP: Mutex<Option<String>> = Mutex::new(None);
+
+/// 麦克风是否正在录音（由录音服务的状态回调更新）
+static MIC_HOT: AtomicBool = AtomicBool::new(false);
+
+/// 串行化录音指示窗口的创建和关闭
+static INDICATOR_LOCK: Mutex<()> = Mutex::new(());
 
 /// 打开语音输入窗口
 pub fn open_voice_window(app: &AppHandle) -> Result<(), String> {
+    // 悬浮窗获得焦点前记录目标应用
+    *TARGET_APP.lock() = frontmost_app();
+
     // 检查窗口是否已存在
     if let Some(window) = app.get_webview_window(VOICE_WINDOW_LABEL) {
         // 发送重置事件，让前端重新开始录音
@@ -17,8 +55,8 @@ pub fn open_voice_window(app: &AppHandle) -> Result<(), String> {
             .emit("voice-reset", ())
             .map_err(|e| format!("发送重置事件失败: {}", e))?;
 
-        // 移动到鼠标所在屏幕
-        position_window_on_cursor_screen(&window)?;
+        // 恢复记住的位置或移动到目标屏幕
+        position_voice_window(&window)?;
 
         window.show().map_err(|e| e.to_string())?;
         window.set_focus().map_err(|e| e.to_string())?;
@@ -42,8 +80,9 @@ pub fn open_voice_window(app: &AppHandle) -> Result<(), String> {
     .build()
     .map_err(|e| format!("创建窗口失败: {}", e))?;
 
-    // 移动到鼠标所在屏幕
-    position_window_on_cursor_screen(&window)?;
+    // 恢复记住的位置或移动到目标屏幕，并监听用户拖动
+    position_voice_window(&window)?;
+    watch_window_moves(&window);
 
     window.show().map_err(|e| e.to_string())?;
     window.set_focus().map_err(|e| e.to_string())?;
@@ -52,65 +91,335 @@ pub fn open_voice_window(app: &AppHandle) -> Result<(), String> {
     Ok(())
 }
 
-/// 将窗口定位到鼠标所在屏幕的中央
-fn position_window_on_cursor_screen(window: &tauri::WebviewWindow) -> Result<(), String> {
-    use tauri::PhysicalPosition;
+/// 定位语音窗口
+///
+/// 优先恢复当前显示器布局下用户手动拖动过的位置，否则在目标显示器居中。
+/// 目标显示器：开启 `follow_active_monitor` 时为焦点应用所在显示器，否则为鼠标所在显示器。
+fn position_voice_window(window: &tauri::WebviewWindow) -> Result<(), String> {
+    let window_config = config::load_voice_config()
+        .map(|c| c.window)
+        .unwrap_or_default();
 
-    // 获取鼠标位置
-    let cursor_pos = match window.cursor_position() {
-        Ok(pos) => pos,
-        Err(e) => {
-            tracing::warn!("[语音输入] 获取鼠标位置失败: {}，使用默认居中", e);
-            window.center().map_err(|e| e.to_string())?;
-            return Ok(());
-        }
+    let monitors = window.available_monitors().map_err(|e| e.to_string())?;
+    if monitors.is_empty() {
+        tracing::warn!("[语音输入] 未获取到显示器列表，使用默认居中");
+        return window.center().map_err(|e| e.to_string());
+    }
+
+    let layout = monitor_layout_signature(&monitors);
+    let saved = if window_config.remember_position {
+        window_config.saved_positions.get(&layout).cloned()
+    } else {
+        None
     };
 
-    // 获取所有显示器
-    let monitors = window.available_monitors().map_err(|e| e.to_string())?;
+    let cursor_monitor = window
+        .cursor_position()
+        .ok()
+        .and_then(|pos| monitor_containing(&monitors, pos.x, pos.y));
+
+    let target_monitor = if window_config.follow_active_monitor {
+        focused_app_monitor(&monitors).or(cursor_monitor)
+    } else {
+        // 未跟随焦点显示器时，优先使用保存位置所在的显示器
+        saved
+            .as_ref()
+            .and_then(|p| {
+                monitors
+                    .iter()
+                    .find(|m| monitor_id(m) == p.monitor)
+                    .cloned()
+            })
+            .or(cursor_monitor)
+    };
+
+    let Some(monitor) = target_monitor else {
+        tracing::warn!("[语音输入] 未找到目标显示器，使用默认居中");
+        return window.center().map_err(|e| e.to_string());
+    };
+
+    let scale_factor = monitor.scale_factor();
+    let window_width = (VOICE_WINDOW_WIDTH * scale_factor) as i32;
+    let window_height = (VOICE_WINDOW_HEIGHT * scale_factor) as i32;
+
+    let (x, y) = match saved {
+        Some(p) => clamp_to_monitor(
+            &monitor,
+            monitor.position().x + p.offset_x,
+            monitor.position().y + p.offset_y,
+            window_width,
+            window_height,
+        ),
+        None => centered_on_monitor(&monitor, window_width, window_height),
+    };
+
+    *PROGRAMMATIC_POSITION.lock() = Some((x, y));
+    window
+        .set_position(PhysicalPosition::new(x, y))
+        .map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        "[语音输入] 窗口定位到显示器 {}，窗口位置 ({}, {})",
+        monitor_id(&monitor),
+        x,
+        y
+    );
+
+    Ok(())
+}
 
-    // 找到鼠标所在的显示器
-    let target_monitor = monitors.iter().find(|m| {
-        let pos = m.position();
-        let size = m.size();
-        cursor_pos.x >= pos.x as f64
-            && cursor_pos.x < (pos.x + size.width as i32) as f64
-            && cursor_pos.y >= pos.y as f64
-            && cursor_pos.y < (pos.y + size.height as i32) as f64
+/// 监听窗口移动，防抖后保存用户手动拖动的位置
+fn watch_window_moves(window: &tauri::WebviewWindow) {
+    let window_clone = window.clone();
+    window.on_window_event(move |event| {
+        let WindowEvent::Moved(pos) = event else {
+            return;
+        };
+
+        // 忽略程序主动设置位置触发的移动事件
+        {
+            let mut programmatic = PROGRAMMATIC_POSITION.lock();
+            if *programmatic == Some((pos.x, pos.y)) {
+                *programmatic = None;
+                return;
+            }
+        }
+
+        let generation = MOVE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+        let window = window_clone.clone();
+        let pos = *pos;
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(SAVE_DEBOUNCE_MS));
+            if MOVE_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            if let Err(e) = save_window_position(&window, pos) {
+                tracing::warn!("[语音输入] 保存窗口位置失败: {}", e);
+            }
+        });
     });
+}
 
-    if let Some(monitor) = target_monitor {
-        let monitor_pos = monitor.position();
-        let monitor_size = monitor.size();
-        let scale_factor = monitor.scale_factor();
+/// 保存窗口位置到当前显示器布局
+fn save_window_position(
+    window: &tauri::WebviewWindow,
+    pos: PhysicalPosition<i32>,
+) -> Result<(), String> {
+    let mut voice_config = config::load_voice_config()?;
+    if !voice_config.window.remember_position {
+        return Ok(());
+    }
 
-        // 计算窗口在该显示器上的居中位置
-        let window_width = (VOICE_WINDOW_WIDTH * scale_factor) as i32;
-        let window_height = (VOICE_WINDOW_HEIGHT * scale_factor) as i32;
+    let monitors = window.available_monitors().map_err(|e| e.to_string())?;
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let center_x = pos.x as f64 + size.width as f64 / 2.0;
+    let center_y = pos.y as f64 + size.height as f64 / 2.0;
+    let monitor =
+        monitor_containing(&monitors, center_x, center_y).ok_or("窗口不在任何显示器内")?;
 
-        let x = monitor_pos.x + (monitor_size.width as i32 - window_width) / 2;
-        let y = monitor_pos.y + (monitor_size.height as i32 - window_height) / 2;
+    let position = VoiceWindowPosition {
+        monitor: monitor_id(&monitor),
+        offset_x: pos.x - monitor.position().x,
+        offset_y: pos.y - monitor.position().y,
+    };
 
-        window
-            .set_position(PhysicalPosition::new(x, y))
-            .map_err(|e| e.to_string())?;
+    let layout = monitor_layout_signature(&monitors);
+    if voice_config.window.saved_positions.get(&layout) == Some(&position) {
+        return Ok(());
+    }
 
-        tracing::info!(
-            "[语音输入] 窗口定位到显示器 ({}, {}) 尺寸 {}x{}, 窗口位置 ({}, {})",
-            monitor_pos.x,
-            monitor_pos.y,
-            monitor_size.width,
-            monitor_size.height,
-            x,
-            y
-        );
-    } else {
-        // 没找到对应显示器，使用默认居中
-        tracing::warn!("[语音输入] 未找到鼠标所在显示器，使用默认居中");
-        window.center().map_err(|e| e.to_string())?;
+    tracing::info!(
+        "[语音输入] 记住窗口位置: 布局 {}，显示器 {}，偏移 ({}, {})",
+        layout,
+        position.monitor,
+        position.offset_x,
+        position.offset_y
+    );
+    voice_config.window.saved_positions.insert(layout, position);
+    config::save_voice_config(voice_config)
+}
+
+/// 显示器标识（名称 + 左上角坐标）
+fn monitor_id(monitor: &Monitor) -> String {
+    let pos = monitor.position();
+    format!(
+        "{}@{},{}",
+        monitor.name().map(String::as_str).unwrap_or("unknown"),
+        pos.x,
+        pos.y
+    )
+}
+
+/// 显示器布局签名
+///
+/// 由所有显示器的标识和尺寸排序后拼接，插拔显示器或调整排列时会变化
+fn monitor_layout_signature(monitors: &[Monitor]) -> String {
+    let mut parts: Vec<String> = monitors
+        .iter()
+        .map(|m| format!("{}:{}x{}", monitor_id(m), m.size().width, m.size().height))
+        .collect();
+    parts.sort();
+    parts.join("|")
+}
+
+/// 查找包含指定物理坐标的显示器
+fn monitor_containing(monitors: &[Monitor], x: f64, y: f64) -> Option<Monitor> {
+    monitors
+        .iter()
+        .find(|m| {
+            let pos = m.position();
+            let size = m.size();
+            x >= pos.x as f64
+                && x < (pos.x + size.width as i32) as f64
+                && y >= pos.y as f64
+                && y < (pos.y + size.height as i32) as f64
+        })
+        .cloned()
+}
+
+/// 计算窗口在显示器上的居中位置
+fn centered_on_monitor(monitor: &Monitor, width: i32, height: i32) -> (i32, i32) {
+    let pos = monitor.position();
+    let size = monitor.size();
+    (
+        pos.x + (size.width as i32 - width) / 2,
+        pos.y + (size.height as i32 - height) / 2,
+    )
+}
+
+/// 将窗口位置限制在显示器范围内
+fn clamp_to_monitor(monitor: &Monitor, x: i32, y: i32, width: i32, height: i32) -> (i32, i32) {
+    let pos = monitor.position();
+    let size = monitor.size();
+    let max_x = (pos.x + size.width as i32 - width).max(pos.x);
+    let max_y = (pos.y + size.height as i32 - height).max(pos.y);
+    (x.clamp(pos.x, max_x), y.clamp(pos.y, max_y))
+}
+
+/// 获取焦点应用所在的显示器（macOS）
+///
+/// `NSScreen::mainScreen` 返回包含键盘焦点窗口的屏幕，坐标为左下角原点的逻辑坐标
+#[cfg(target_os = "macos")]
+#[allow(deprecated)]
+fn focused_app_monitor(monitors: &[Monitor]) -> Option<Monitor> {
+    use cocoa::appkit::NSScreen;
+    use cocoa::base::nil;
+    use cocoa::foundation::NSArray;
+
+    let (center_x, center_y) = unsafe {
+        let main_screen = NSScreen::mainScreen(nil);
+        let screens = NSScreen::screens(nil);
+        if main_screen == nil || screens == nil || screens.count() == 0 {
+            return None;
+        }
+        let primary = NSScreen::frame(screens.objectAtIndex(0));
+        let frame = NSScreen::frame(main_screen);
+        // 转换为左上角原点
+        let top = primary.size.height - (frame.origin.y + frame.size.height);
+        (
+            frame.origin.x + frame.size.width / 2.0,
+            top + frame.size.height / 2.0,
+        )
+    };
+
+    monitors
+        .iter()
+        .find(|m| {
+            let scale = m.scale_factor();
+            let left = m.position().x as f64 / scale;
+            let top = m.position().y as f64 / scale;
+            let right = left + m.size().width as f64 / scale;
+            let bottom = top + m.size().height as f64 / scale;
+            center_x >= left && center_x < right && center_y >= top && center_y < bottom
+        })
+        .cloned()
+}
+
+/// 获取焦点应用所在的显示器（Windows）
+#[cfg(target_os = "windows")]
+fn focused_app_monitor(monitors: &[Monitor]) -> Option<Monitor> {
+    use winapi::shared::windef::RECT;
+    use winapi::um::winuser::{GetForegroundWindow, GetWindowRect};
+
+    let (center_x, center_y) = unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_null() {
+            return None;
+        }
+        let mut rect: RECT = std::mem::zeroed();
+        if GetWindowRect(hwnd, &mut rect) == 0 {
+            return None;
+        }
+        (
+            (rect.left + rect.right) as f64 / 2.0,
+            (rect.top + rect.bottom) as f64 / 2.0,
+        )
+    };
+
+    monitor_containing(monitors, center_x, center_y)
+}
+
+/// 其他平台无法获取焦点应用窗口，回退到鼠标所在显示器
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn focused_app_monitor(_monitors: &[Monitor]) -> Option<Monitor> {
+    None
+}
+
+/// 最近一次打开悬浮窗前的焦点应用（无法获取时为空）
+pub fn target_app() -> Option<String> {
+    TARGET_APP.lock().clone()
+}
+
+/// 当前焦点应用的 Bundle ID（macOS）
+#[cfg(target_os = "macos")]
+#[allow(deprecated, unexpected_cfgs)]
+pub fn frontmost_app() -> Option<String> {
+    use cocoa::base::{id, nil};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    unsafe {
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let app: id = msg_send![workspace, frontmostApplication];
+        if app == nil {
+            return None;
+        }
+        let bundle_id: id = msg_send![app, bundleIdentifier];
+        if bundle_id == nil {
+            return None;
+        }
+        let utf8: *const std::os::raw::c_char = msg_send![bundle_id, UTF8String];
+        if utf8.is_null() {
+            return None;
+        }
+        Some(
+            std::ffi::CStr::from_ptr(utf8)
+                .to_string_lossy()
+                .into_owned(),
+        )
     }
+}
 
-    Ok(())
+/// 当前焦点窗口所属的进程（Windows）
+#[cfg(target_os = "windows")]
+pub fn frontmost_app() -> Option<String> {
+    use winapi::um::winuser::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    let pid = unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_null() {
+            return None;
+        }
+        let mut pid = 0;
+        GetWindowThreadProcessId(hwnd, &mut pid);
+        pid
+    };
+    (pid != 0).then(|| format!("pid:{}", pid))
+}
+
+/// 其他平台无法获取焦点应用
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn frontmost_app() -> Option<String> {
+    None
 }
 
 /// 关闭语音输入窗口
@@ -142,3 +451,89 @@ pub fn send_stop_recording_event(app: &AppHandle) -> Result<(), String> {
     }
     Ok(())
 }
+
+/// 录音状态变化时调用，在后台显示或关闭录音指示窗口
+///
+/// 回调在录音服务持有锁时触发，窗口操作放到异步任务中执行以免阻塞录音命令
+pub fn set_mic_hot(app: &AppHandle, hot: bool) {
+    MIC_HOT.store(hot, Ordering::SeqCst);
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = refresh_recording_indicators(&app) {
+            tracing::warn!("[录音指示] 更新失败: {}", e);
+        }
+    });
+}
+
+/// 按录音状态和配置显示或关闭录音指示窗口
+///
+/// 每次都读取最新状态，多次调用的先后顺序不影响结果
+pub fn refresh_recording_indicators(app: &AppHandle) -> Result<(), String> {
+    let _guard = INDICATOR_LOCK.lock();
+    let enabled = config::load_voice_config()
+        .map(|c| c.window.recording_indicator)
+        .unwrap_or(false);
+    if !enabled || !MIC_HOT.load(Ordering::SeqCst) {
+        close_recording_indicators(app);
+        return Ok(());
+    }
+
+    let monitors = app.available_monitors().map_err(|e| e.to_string())?;
+    let labels: Vec<String> = (0..monitors.len())
+        .map(|i| format!("{}{}", INDICATOR_LABEL_PREFIX, i))
+        .collect();
+
+    // 关闭已断开的显示器上的指示窗口
+    for (label, window) in app.webview_windows() {
+        if label.starts_with(INDICATOR_LABEL_PREFIX) && !labels.contains(&label) {
+            let _ = window.close();
+        }
+    }
+
+    for (monitor, label) in monitors.iter().zip(&labels) {
+        let window = match app.get_webview_window(label) {
+            Some(window) => window,
+            None => WebviewWindowBuilder::new(
+                app,
+                label,
+                WebviewUrl::App("/recording-indicator".into()),
+            )
+            .title("正在录音")
+            .inner_size(INDICATOR_SIZE, INDICATOR_SIZE)
+            .resizable(false)
+            .decorations(false)
+            .always_on_top(true)
+            .visible_on_all_workspaces(true)
+            .transparent(true)
+            .shadow(false)
+            .skip_taskbar(true)
+            .focused(false)
+            .visible(false)
+            .build()
+            .map_err(|e| format!("创建录音指示窗口失败: {}", e))?,
+        };
+
+        // 不拦截鼠标点击，也不抢占焦点
+        let _ = window.set_ignore_cursor_events(true);
+        let scale_factor = monitor.scale_factor();
+        let size = ((INDICATOR_SIZE + INDICATOR_MARGIN) * scale_factor) as i32;
+        let x = monitor.position().x + monitor.size().width as i32 - size;
+        let y = monitor.position().y + (INDICATOR_MARGIN * scale_factor) as i32;
+        window
+            .set_position(PhysicalPosition::new(x, y))
+            .map_err(|e| e.to_string())?;
+        window.show().map_err(|e| e.to_string())?;
+    }
+
+    tracing::info!("[录音指示] 已在 {} 个显示器上显示", monitors.len());
+    Ok(())
+}
+
+/// 关闭所有录音指示窗口
+fn close_recording_indicators(app: &AppHandle) {
+    for (label, window) in app.webview_windows() {
+        if label.starts_with(INDICATOR_LABEL_PREFIX) {
+            let _ = window.close();
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! 工作区听写默认值
+//!
+//! 工作区设置可指定听写的输出方式（`voice_output_mode`）和润色指令（`voice_instruction_id`），
+//! 例如在"生产运维"工作区里听写总是只复制到剪贴板。打开悬浮窗前的焦点应用是 ProxyCast 本身时，
+//! 听写使用默认工作区（即当前打开的工作区）的设置；其他应用在前台时不受影响。
+//!
+//! 设置缓存在内存中，启动时加载，之后订阅 [`crate::workspace::events`] 在工作区变更后重新加载，
+//! 热词表（[`super::vocabulary`]）随之刷新。
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::config::VoiceOutputMode;
+use crate::database::DbConnection;
+use crate::workspace::{events, WorkspaceManager, WorkspaceUpdate};
+
+/// 工作区的听写默认值
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceVoiceDefaults {
+    /// 输出方式（为空时使用语音输入配置）
+    #[serde(default)]
+    pub output_mode: Option<VoiceOutputMode>,
+    /// 润色指令（为空时按语音输入配置选择）
+    #[serde(default)]
+    pub instruction_id: Option<String>,
+}
+
+/// 默认工作区的听写默认值
+static DEFAULTS: RwLock<WorkspaceVoiceDefaults> = RwLock::new(WorkspaceVoiceDefaults {
+    output_mode: None,
+    instruction_id: None,
+});
+
+/// ProxyCast 在前台时使用的听写默认值，其他应用在前台时为空
+pub fn active(app: &AppHandle) -> Option<WorkspaceVoiceDefaults> {
+    let target = super::window::target_app()?;
+    is_own_app(&target, &app.config().identifier).then(|| DEFAULTS.read().clone())
+}
+
+/// 焦点应用是否为 ProxyCast 本身（macOS 比较 Bundle ID，Windows 比较进程 ID）
+fn is_own_app(target: &str, identifier: &str) -> bool {
+    target == identifier || target == format!("pid:{}", std::process::id())
+}
+
+/// 重新加载默认工作区的听写默认值（没有默认工作区时清空）
+pub fn reload(db: &DbConnection) {
+    let defaults = match WorkspaceManager::new(db.clone()).get_default() {
+        Ok(workspace) => workspace
+            .map(|w| WorkspaceVoiceDefaults {
+                output_mode: w.settings.voice_output_mode,
+                instruction_id: w.settings.voice_instruction_id,
+            })
+            .unwrap_or_default(),
+        Err(e) => {
+            tracing::warn!("[语音输入] 读取工作区听写设置失败: {}", e);
+            return;
+        }
+    };
+    *DEFAULTS.write() = defaults;
+}
+
+/// 订阅工作区事件，变更后重新加载听写默认值和热词表
+pub fn listen(db: DbConnection) {
+    let mut receiver = events::subscribe();
+    tauri::async_runtime::spawn(async move {
+        use tokio::sync::broadcast::error::RecvError;
+
+        loop {
+            match receiver.recv().await {
+                // 错过的事件无法补齐，直接按最新状态重新加载
+                Ok(_) | Err(RecvError::Lagged(_)) => {
+                    reload(&db);
+                    super::vocabulary::reload(&db);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+/// 读取工作区的听写默认值，未指定工作区时读取默认工作区
+pub fn get(
+    db: &DbConnection,
+    workspace_id: Option<&str>,
+) -> Result<WorkspaceVoiceDefaults, String> {
+    let manager = WorkspaceManager::new(db.clone());
+    let workspace = match workspace_id {
+        Some(id) => manager.get(&id.to_string())?,
+        None => manager.get_default()?,
+    };
+    Ok(workspace
+        .map(|w| WorkspaceVoiceDefaults {
+            output_mode: w.settings.voice_output_mode,
+            instruction_id: w.settings.voice_instruction_id,
+        })
+        .unwrap_or_default())
+}
+
+/// 保存工作区的听写默认值，未指定工作区时保存到默认工作区
+///
+/// 指令必须是语音输入配置中已有的指令
+pub fn set(
+    db: &DbConnection,
+    workspace_id: Option<&str>,
+    defaults: WorkspaceVoiceDefaults,
+) -> Result<(), String> {
+    if let Some(instruction_id) = &defaults.instruction_id {
+        let config = super::config::load_voice_config()?;
+        if !config.instructions.iter().any(|i| &i.id == instruction_id) {
+            return Err(format!("指令不存在: {}", instruction_id));
+        }
+    }
+
+    let manager = WorkspaceManager::new(db.clone());
+    let workspace = match workspace_id {
+        Some(id) => manager.get(&id.to_string())?,
+        None => manager.get_default()?,
+    }
+    .ok_or_else(|| "工作区不存在".to_string())?;
+
+    let mut settings = workspace.settings;
+    settings.voice_output_mode = defaults.output_mode;
+    settings.voice_instruction_id = defaults.instruction_id;
+    manager.update(
+        &workspace.id,
+        WorkspaceUpdate {
+            settings: Some(settings),
+            ..Default::default()
+        },
+    )?;
+
+    events::publish(events::WorkspaceEvent::Updated(workspace.id));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_own_app() {
+        let identifier = "com.proxycast.app";
+        assert!(is_own_app(identifier, identifier));
+        assert!(is_own_app(
+            &format!("pid:{}", std::process::id()),
+            identifier
+        ));
+        assert!(!is_own_app("com.apple.Terminal", identifier));
+        assert!(!is_own_app("pid:0", identifier));
+    }
+}
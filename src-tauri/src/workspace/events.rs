@@ -0,0 +1,55 @@
+//! Workspace 事件总线
+//!
+//! Workspace 更新、删除或切换默认 workspace 后发布事件，依赖 workspace 设置的模块
+//! （如语音输入的热词表和默认输出方式）订阅后刷新各自的缓存，不必由命令层逐个通知。
+
+use once_cell::sync::Lazy;
+use tokio::sync::broadcast;
+
+use super::WorkspaceId;
+
+/// Workspace 变更事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkspaceEvent {
+    /// 名称、设置等被更新
+    Updated(WorkspaceId),
+    /// 被删除
+    Deleted(WorkspaceId),
+    /// 默认 workspace 切换
+    DefaultChanged(WorkspaceId),
+}
+
+/// 事件广播通道，没有订阅者时事件被丢弃
+static EVENTS: Lazy<broadcast::Sender<WorkspaceEvent>> = Lazy::new(|| broadcast::channel(64).0);
+
+/// 发布事件
+pub fn publish(event: WorkspaceEvent) {
+    tracing::debug!("[Workspace] 发布事件: {:?}", event);
+    let _ = EVENTS.send(event);
+}
+
+/// 订阅事件
+pub fn subscribe() -> broadcast::Receiver<WorkspaceEvent> {
+    EVENTS.subscribe()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscribers_receive_events() {
+        let mut receiver = subscribe();
+        publish(WorkspaceEvent::DefaultChanged("ws-1".to_string()));
+        publish(WorkspaceEvent::Deleted("ws-2".to_string()));
+
+        assert_eq!(
+            receiver.recv().await.unwrap(),
+            WorkspaceEvent::DefaultChanged("ws-1".to_string())
+        );
+        assert_eq!(
+            receiver.recv().await.unwrap(),
+            WorkspaceEvent::Deleted("ws-2".to_string())
+        );
+    }
+}
@@ -7,14 +7,19 @@
 //! - Workspace CRUD 操作
 //! - 与 Aster Session 通过 working_dir 关联
 //! - Workspace 级别的配置管理
+//! - 变更事件总线（[`events`]）
 //!
 //! ## 设计原则
 //! - 读共享，写隔离
 //! - 最小有效 context
 //! - Workspace = 边界（文件系统 + context + 配置）
 
+pub mod events;
 mod manager;
 mod types;
 
+pub use events::WorkspaceEvent;
 pub use manager::WorkspaceManager;
-pub use types::{Workspace, WorkspaceId, WorkspaceSettings, WorkspaceType, WorkspaceUpdate};
+pub use types::{
+    ToolPolicyLists, Workspace, WorkspaceId, WorkspaceSettings, WorkspaceType, WorkspaceUpdate,
+};
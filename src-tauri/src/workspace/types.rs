@@ -6,6 +6,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use crate::config::VoiceOutputMode;
+
 /// Workspace 唯一标识
 pub type WorkspaceId = String;
 
@@ -103,6 +105,37 @@ pub struct WorkspaceSettings {
     /// 自动压缩 context
     #[serde(default)]
     pub auto_compact: bool,
+    /// 工具调用允许/拒绝列表
+    #[serde(default, skip_serializing_if = "ToolPolicyLists::is_empty")]
+    pub tool_policy: ToolPolicyLists,
+    /// 语音识别热词（专业术语、产品名等）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub voice_vocabulary: Vec<String>,
+    /// 本 workspace 在前台时的听写输出方式（为空时使用语音输入配置）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice_output_mode: Option<VoiceOutputMode>,
+    /// 本 workspace 在前台时的润色指令（为空时按语音输入配置选择）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice_instruction_id: Option<String>,
+}
+
+/// 工具调用允许/拒绝列表
+///
+/// 规则为工具全名（如 `developer__shell`），支持结尾 `*` 通配；拒绝列表优先
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ToolPolicyLists {
+    /// 无需确认直接放行
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// 直接拒绝
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl ToolPolicyLists {
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
 }
 
 /// 项目统计信息
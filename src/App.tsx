@@ -42,7 +42,18 @@ import { ComponentDebugOverlay } from "./components/dev";
 import { Page, PageParams, AgentPageParams } from "./types/page";
 import { open } from "@tauri-apps/plugin-dialog";
 import { createProject, createContent } from "./lib/api/project";
+import { warmUpSubsystem } from "./lib/api/startup";
+import { getDataCompatibility, exportDataSnapshot } from "./lib/api/compat";
 import { toast } from "sonner";
+import { safeListen } from "./lib/dev-bridge";
+import {
+  OUTPUT_PAUSED_EVENT,
+  SECURE_INPUT_EVENT,
+  copyPausedVoiceOutput,
+  resumeVoiceOutput,
+  type OutputPausedPayload,
+  type SecureInputPayload,
+} from "./lib/api/asrProvider";
 
 const AppContainer = styled.div`
   display: flex;
@@ -216,6 +227,102 @@ function AppContent() {
     flowEventManager.subscribe();
   }, []);
 
+  // 模型注册表延迟到首次使用时加载，启动完成后空闲时提前预热
+  useEffect(() => {
+    const timer = setTimeout(() => {
+      warmUpSubsystem("model_registry").catch((error) => {
+        console.warn("[App] 模型注册表预热失败:", error);
+      });
+    }, 3000);
+    return () => clearTimeout(timer);
+  }, []);
+
+  // 数据来自更新版本的 ProxyCast（回退版本后），提醒用户并提供导出
+  useEffect(() => {
+    const exportSnapshot = async () => {
+      const dir = await open({ directory: true, title: "选择导出目录" });
+      if (!dir || Array.isArray(dir)) return;
+      try {
+        const files = await exportDataSnapshot(dir);
+        toast.success(`已导出 ${files.length} 个文件到 ${dir}`);
+      } catch (error) {
+        toast.error(`导出失败: ${error}`);
+      }
+    };
+
+    getDataCompatibility()
+      .then(({ database, config }) => {
+        if (!database && !config) return;
+        const parts = [
+          database && "数据库已以只读模式打开，修改不会保存",
+          config && "配置修改不会保存",
+        ].filter(Boolean);
+        toast.warning("数据来自更新版本的 ProxyCast", {
+          id: "newer-data-version",
+          duration: Infinity,
+          description: `${parts.join("；")}。请升级到最新版本，或先导出数据副本。`,
+          action: { label: "导出数据", onClick: exportSnapshot },
+        });
+      })
+      .catch((error) => {
+        console.warn("[App] 检查数据版本失败:", error);
+      });
+  }, []);
+
+  // 听写输出时焦点在密码框中，提醒用户内容只复制到了剪贴板
+  useEffect(() => {
+    const unlistenPromise = safeListen<SecureInputPayload>(
+      SECURE_INPUT_EVENT,
+      (event) => {
+        toast.warning(
+          `当前焦点在密码输入框中，已改为复制到剪贴板（${event.payload.chars} 字）`,
+        );
+      },
+    );
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
+  // 听写输入过程中切换了窗口，暂停输入并让用户选择继续输入或改为复制
+  useEffect(() => {
+    const unlistenPromise = safeListen<OutputPausedPayload>(
+      OUTPUT_PAUSED_EVENT,
+      (event) => {
+        const { id, remaining } = event.payload;
+        const message = `焦点已切换到其他窗口，听写输入已暂停（剩余 ${remaining} 字）`;
+        toast.warning(message, {
+          id: `voice-output-paused-${id}`,
+          duration: Infinity,
+          description: "继续输入需在 10 秒内切回新窗口",
+          action: {
+            label: "在新窗口继续",
+            onClick: () => {
+              resumeVoiceOutput(id).catch((error) => {
+                toast.error(`继续输入失败: ${error}`);
+              });
+            },
+          },
+          cancel: {
+            label: "改为复制",
+            onClick: () => {
+              copyPausedVoiceOutput(id)
+                .then((copied) => {
+                  if (copied) toast.success("未输入的内容已复制到剪贴板");
+                })
+                .catch((error) => {
+                  toast.error(`复制失败: ${error}`);
+                });
+            },
+          },
+        });
+      },
+    );
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
   // 处理 Registry 加载失败
   // _Requirements: 7.2, 7.3_
   useEffect(() => {
@@ -6,6 +6,7 @@
 import App from "./App";
 import { SmartInputPage } from "./pages/smart-input";
 import { UpdateNotificationPage } from "./pages/update-notification";
+import { RecordingIndicatorPage } from "./pages/recording-indicator";
 import { Toaster } from "./components/ui/sonner";
 
 /**
@@ -13,6 +14,7 @@ import { Toaster } from "./components/ui/sonner";
  *
  * - /smart-input: 截图对话悬浮窗口（独立 Tauri 窗口，支持语音模式）
  * - /update-notification: 更新提醒悬浮窗口（独立 Tauri 窗口）
+ * - /recording-indicator: 录音指示红点（每个显示器一个独立 Tauri 窗口）
  * - 其他: 主应用
  */
 export function RootRouter() {
@@ -28,6 +30,11 @@ export function RootRouter() {
     return <UpdateNotificationPage />;
   }
 
+  // 录音指示窗口路由
+  if (pathname === "/recording-indicator") {
+    return <RecordingIndicatorPage />;
+  }
+
   // 默认渲染主应用
   return (
     <>
@@ -1,17 +1,68 @@
 import { useState, useEffect, useRef } from "react";
-import { Trash2, Download } from "lucide-react";
-import { getLogs, clearLogs, LogEntry } from "@/hooks/useTauri";
+import { Trash2, Download, FolderOpen, FileDown } from "lucide-react";
+import { save } from "@tauri-apps/plugin-dialog";
+import {
+  getLogs,
+  clearLogs,
+  getLogLevels,
+  setLogLevel,
+  openLogFile,
+  exportLogFile,
+  LogEntry,
+  LogLevels,
+} from "@/hooks/useTauri";
+
+const LEVEL_OPTIONS = ["error", "warn", "info", "debug", "trace"];
+
+const MODULE_LABELS: Record<string, string> = {
+  voice: "语音",
+  pipeline: "请求管道",
+  providers: "Provider",
+};
 
 export function LogsTab() {
   const [logs, setLogs] = useState<LogEntry[]>([]);
+  const [levels, setLevels] = useState<LogLevels | null>(null);
   const logsContainerRef = useRef<HTMLDivElement>(null);
 
   useEffect(() => {
     fetchLogs();
+    getLogLevels()
+      .then(setLevels)
+      .catch((e) => console.error(e));
     const interval = setInterval(fetchLogs, 1000);
     return () => clearInterval(interval);
   }, []);
 
+  const handleLevelChange = async (level: string, module?: string) => {
+    try {
+      setLevels(await setLogLevel(level, module));
+    } catch (e) {
+      console.error(e);
+    }
+  };
+
+  const handleOpenFile = async () => {
+    try {
+      await openLogFile();
+    } catch (e) {
+      console.error(e);
+    }
+  };
+
+  const handleExportFile = async () => {
+    try {
+      const dest = await save({
+        defaultPath: `proxycast-${new Date().toISOString().slice(0, 10)}.log`,
+      });
+      if (dest) {
+        await exportLogFile(dest);
+      }
+    } catch (e) {
+      console.error(e);
+    }
+  };
+
   const fetchLogs = async () => {
     try {
       const l = await getLogs();
@@ -88,7 +139,60 @@ export function LogsTab() {
 
   return (
     <div className="space-y-4">
-      <div className="flex items-center justify-end gap-2">
+      <div className="flex flex-wrap items-center justify-end gap-2">
+        {levels && (
+          <div className="mr-auto flex flex-wrap items-center gap-3 text-sm">
+            <label className="flex items-center gap-1">
+              默认级别
+              <select
+                value={levels.default}
+                onChange={(e) => handleLevelChange(e.target.value)}
+                className="rounded border bg-background px-1 py-0.5"
+              >
+                {LEVEL_OPTIONS.map((l) => (
+                  <option key={l} value={l}>
+                    {l}
+                  </option>
+                ))}
+              </select>
+            </label>
+            {Object.keys(levels.modules).map((module) => (
+              <label key={module} className="flex items-center gap-1">
+                {MODULE_LABELS[module] ?? module}
+                <select
+                  value={
+                    levels.overridden.includes(module)
+                      ? levels.modules[module]
+                      : "default"
+                  }
+                  onChange={(e) => handleLevelChange(e.target.value, module)}
+                  className="rounded border bg-background px-1 py-0.5"
+                >
+                  <option value="default">跟随默认</option>
+                  {LEVEL_OPTIONS.map((l) => (
+                    <option key={l} value={l}>
+                      {l}
+                    </option>
+                  ))}
+                </select>
+              </label>
+            ))}
+          </div>
+        )}
+        <button
+          onClick={handleOpenFile}
+          className="flex items-center gap-2 rounded-lg border px-3 py-1.5 text-sm hover:bg-muted"
+        >
+          <FolderOpen className="h-4 w-4" />
+          打开日志文件
+        </button>
+        <button
+          onClick={handleExportFile}
+          className="flex items-center gap-2 rounded-lg border px-3 py-1.5 text-sm hover:bg-muted"
+        >
+          <FileDown className="h-4 w-4" />
+          导出日志文件
+        </button>
         <button
           onClick={handleExport}
           className="flex items-center gap-2 rounded-lg border px-3 py-1.5 text-sm hover:bg-muted"
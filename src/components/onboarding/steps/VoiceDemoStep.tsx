@@ -198,7 +198,11 @@ export function VoiceDemoStep({ onSuccess, onSkip }: VoiceDemoStepProps) {
       if (config.processor.polish_enabled) {
         setDemoState("polishing");
         try {
-          const polished = await polishVoiceText(transcribeResult.text);
+          const polished = await polishVoiceText(
+            transcribeResult.text,
+            undefined,
+            transcribeResult.language,
+          );
           finalText = polished.text;
         } catch (e) {
           console.error("润色失败:", e);
@@ -0,0 +1,263 @@
+import { useState, useEffect, useCallback } from "react";
+import {
+  Users,
+  Plus,
+  Trash2,
+  Copy,
+  Check,
+  AlertTriangle,
+  CheckCircle2,
+} from "lucide-react";
+import { getConfig, saveConfig, Config, ClientApiKey } from "@/hooks/useTauri";
+import { getUsageByClient, ClientUsage } from "@/lib/api/telemetry";
+
+const KEY_CHARS =
+  "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/** 生成与主密钥格式相同的随机密钥 */
+function generateKey(): string {
+  const bytes = crypto.getRandomValues(new Uint8Array(32));
+  const token = Array.from(bytes, (b) => KEY_CHARS[b % KEY_CHARS.length]);
+  return `pc_${token.join("")}`;
+}
+
+/** 格式化各货币单位的费用 */
+function formatCosts(costs: Record<string, number>): string {
+  const entries = Object.entries(costs);
+  if (entries.length === 0) return "-";
+  return entries
+    .map(([currency, amount]) => `${amount.toFixed(4)} ${currency}`)
+    .join(" / ");
+}
+
+export function ClientKeysSettings() {
+  const [config, setConfig] = useState<Config | null>(null);
+  const [usage, setUsage] = useState<ClientUsage[]>([]);
+  const [saving, setSaving] = useState(false);
+  const [copiedId, setCopiedId] = useState<string | null>(null);
+  const [message, setMessage] = useState<{
+    type: "success" | "error";
+    text: string;
+  } | null>(null);
+
+  const loadUsage = useCallback(async () => {
+    try {
+      setUsage(await getUsageByClient({ preset: "30d" }));
+    } catch (e) {
+      console.error(e);
+    }
+  }, []);
+
+  useEffect(() => {
+    getConfig().then(setConfig).catch(console.error);
+    loadUsage();
+  }, [loadUsage]);
+
+  if (!config) {
+    return (
+      <div className="flex items-center justify-center h-32">
+        <div className="animate-spin h-6 w-6 border-2 border-primary border-t-transparent rounded-full" />
+      </div>
+    );
+  }
+
+  const clientKeys = config.server.client_keys ?? [];
+
+  const updateKeys = (keys: ClientApiKey[]) => {
+    setConfig({ ...config, server: { ...config.server, client_keys: keys } });
+  };
+
+  const updateKey = (id: string, updates: Partial<ClientApiKey>) => {
+    updateKeys(
+      clientKeys.map((k) => (k.id === id ? { ...k, ...updates } : k)),
+    );
+  };
+
+  const handleAdd = () => {
+    updateKeys([
+      ...clientKeys,
+      {
+        id: crypto.randomUUID(),
+        name: `成员 ${clientKeys.length + 1}`,
+        key: generateKey(),
+        disabled: false,
+      },
+    ]);
+  };
+
+  const handleCopy = (key: ClientApiKey) => {
+    navigator.clipboard.writeText(key.key);
+    setCopiedId(key.id);
+    setTimeout(() => setCopiedId(null), 2000);
+  };
+
+  const handleSave = async () => {
+    setSaving(true);
+    setMessage(null);
+    try {
+      await saveConfig(config);
+      setMessage({
+        type: "success",
+        text: "客户端密钥已保存，已自动生效",
+      });
+      setTimeout(() => setMessage(null), 5000);
+    } catch (e: unknown) {
+      const errorMessage = e instanceof Error ? e.message : String(e);
+      setMessage({ type: "error", text: `保存失败: ${errorMessage}` });
+    }
+    setSaving(false);
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center gap-2">
+        <Users className="h-5 w-5 text-blue-500" />
+        <div>
+          <h3 className="text-sm font-medium">客户端密钥</h3>
+          <p className="text-xs text-muted-foreground">
+            局域网共享时为每位成员分发单独的密钥，用量按成员统计
+          </p>
+        </div>
+      </div>
+
+      {/* 消息提示 */}
+      {message && (
+        <div
+          className={`rounded-lg border p-3 text-sm flex items-center gap-2 ${
+            message.type === "error"
+              ? "border-destructive bg-destructive/10 text-destructive"
+              : "border-green-500 bg-green-50 text-green-700 dark:bg-green-900/20 dark:text-green-400"
+          }`}
+        >
+          {message.type === "success" ? (
+            <CheckCircle2 className="h-4 w-4" />
+          ) : (
+            <AlertTriangle className="h-4 w-4" />
+          )}
+          {message.text}
+        </div>
+      )}
+
+      <div className="p-4 rounded-lg border space-y-3">
+        {clientKeys.length === 0 && (
+          <p className="text-sm text-muted-foreground">
+            暂无客户端密钥，所有请求都记在主机名下
+          </p>
+        )}
+
+        {clientKeys.map((key) => (
+          <div key={key.id} className="flex items-center gap-2">
+            <input
+              type="text"
+              value={key.name}
+              onChange={(e) => updateKey(key.id, { name: e.target.value })}
+              placeholder="显示名称"
+              className="w-32 px-3 py-2 rounded-lg border bg-background text-sm focus:ring-2 focus:ring-primary/20 focus:border-primary outline-none"
+            />
+            <input
+              type="text"
+              value={key.key}
+              readOnly
+              className="flex-1 px-3 py-2 rounded-lg border bg-muted/50 text-xs font-mono outline-none"
+            />
+            <button
+              type="button"
+              onClick={() => handleCopy(key)}
+              className="p-1.5 rounded hover:bg-muted"
+              title="复制"
+            >
+              {copiedId === key.id ? (
+                <Check className="h-4 w-4 text-green-500" />
+              ) : (
+                <Copy className="h-4 w-4" />
+              )}
+            </button>
+            <label className="flex items-center gap-1 text-xs text-muted-foreground">
+              <input
+                type="checkbox"
+                checked={!key.disabled}
+                onChange={(e) =>
+                  updateKey(key.id, { disabled: !e.target.checked })
+                }
+                className="w-4 h-4 rounded border-gray-300"
+              />
+              启用
+            </label>
+            <button
+              type="button"
+              onClick={() =>
+                updateKeys(clientKeys.filter((k) => k.id !== key.id))
+              }
+              className="p-1.5 rounded text-muted-foreground hover:bg-red-100 hover:text-red-600 dark:hover:bg-red-950"
+              title="删除"
+            >
+              <Trash2 className="h-4 w-4" />
+            </button>
+          </div>
+        ))}
+
+        <div className="flex gap-2">
+          <button
+            type="button"
+            onClick={handleAdd}
+            className="flex items-center gap-1 rounded-lg border px-3 py-2 text-sm hover:bg-muted"
+          >
+            <Plus className="h-4 w-4" />
+            添加成员
+          </button>
+          <button
+            onClick={handleSave}
+            disabled={saving}
+            className="flex-1 px-4 py-2 rounded-lg bg-primary text-primary-foreground text-sm font-medium hover:bg-primary/90 disabled:opacity-50"
+          >
+            {saving ? "保存中..." : "保存设置"}
+          </button>
+        </div>
+      </div>
+
+      {/* 成员用量 */}
+      {usage.length > 0 && (
+        <div className="p-4 rounded-lg border space-y-2">
+          <p className="text-sm font-medium">近 30 天用量</p>
+          <table className="w-full text-xs">
+            <thead className="text-muted-foreground">
+              <tr className="text-left">
+                <th className="py-1 font-normal">成员</th>
+                <th className="py-1 font-normal text-right">请求</th>
+                <th className="py-1 font-normal text-right">失败</th>
+                <th className="py-1 font-normal text-right">输入 Token</th>
+                <th className="py-1 font-normal text-right">输出 Token</th>
+                <th className="py-1 font-normal text-right">费用</th>
+              </tr>
+            </thead>
+            <tbody>
+              {usage.map((u) => (
+                <tr key={u.client_key_id ?? "owner"} className="border-t">
+                  <td className="py-1">{u.name}</td>
+                  <td className="py-1 text-right">{u.requests}</td>
+                  <td className="py-1 text-right">{u.failed_requests}</td>
+                  <td className="py-1 text-right">
+                    {u.input_tokens.toLocaleString()}
+                  </td>
+                  <td className="py-1 text-right">
+                    {u.output_tokens.toLocaleString()}
+                  </td>
+                  <td
+                    className="py-1 text-right"
+                    title={
+                      u.unpriced_requests > 0
+                        ? `${u.unpriced_requests} 个请求的模型没有价格信息`
+                        : undefined
+                    }
+                  >
+                    {formatCosts(u.costs)}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+    </div>
+  );
+}
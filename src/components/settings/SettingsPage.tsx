@@ -4,6 +4,7 @@ import { GeneralSettings } from "./GeneralSettings";
 import { DirectorySettings } from "./DirectorySettings";
 import { AboutSection } from "./AboutSection";
 import { TlsSettings } from "./TlsSettings";
+import { ClientKeysSettings } from "./ClientKeysSettings";
 import { QuotaSettings } from "./QuotaSettings";
 import { RemoteManagementSettings } from "./RemoteManagementSettings";
 import { ExtensionsSettings } from "./ExtensionsSettings";
@@ -73,6 +74,7 @@ export function SettingsPage() {
         {activeTab === "security" && (
           <div className="space-y-6 max-w-2xl">
             <TlsSettings />
+            <ClientKeysSettings />
             <RemoteManagementSettings />
           </div>
         )}
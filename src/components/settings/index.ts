@@ -4,6 +4,7 @@ export { ProxySettings } from "./ProxySettings";
 export { DirectorySettings } from "./DirectorySettings";
 export { AboutSection } from "./AboutSection";
 export { TlsSettings } from "./TlsSettings";
+export { ClientKeysSettings } from "./ClientKeysSettings";
 export { QuotaSettings } from "./QuotaSettings";
 export { RemoteManagementSettings } from "./RemoteManagementSettings";
 export { DeveloperSettings } from "./DeveloperSettings";
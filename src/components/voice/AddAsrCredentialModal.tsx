@@ -4,14 +4,26 @@
  * @module components/voice/AddAsrCredentialModal
  */
 
-import { useState } from "react";
-import { X, Cpu, Cloud, Sparkles } from "lucide-react";
+import { useState, useEffect } from "react";
+import { X, Cpu, Cloud, Sparkles, Terminal, Globe } from "lucide-react";
 import type {
   AsrProviderType,
   WhisperModelSize,
+  LocalAsrEngine,
+  SenseVoicePrecision,
   AsrCredentialEntry,
+  AsrTimeoutConfig,
+  HttpAsrUpload,
+  AzureProfanityFilter,
+} from "./types";
+import {
+  ASR_PROVIDERS,
+  WHISPER_MODELS,
+  LOCAL_ENGINES,
+  SENSEVOICE_MODELS,
+  addAsrCredential,
+  listLocalAsrModels,
 } from "./types";
-import { ASR_PROVIDERS, WHISPER_MODELS, addAsrCredential } from "./types";
 
 interface AddAsrCredentialModalProps {
   isOpen: boolean;
@@ -19,13 +31,40 @@ interface AddAsrCredentialModalProps {
   onSuccess: () => void;
 }
 
+/** 解析每行一个的 `Name: value` 请求头 */
+function parseHeaders(text: string): Record<string, string> {
+  const headers: Record<string, string> = {};
+  for (const line of text.split("\n")) {
+    const index = line.indexOf(":");
+    if (index <= 0) continue;
+    headers[line.slice(0, index).trim()] = line.slice(index + 1).trim();
+  }
+  return headers;
+}
+
+/** 请求超时输入项，占位符为默认值 */
+const TIMEOUT_FIELDS: {
+  key: keyof AsrTimeoutConfig;
+  label: string;
+  defaultSecs: string;
+}[] = [
+  { key: "connect_secs", label: "连接", defaultSecs: "10" },
+  { key: "send_secs", label: "发送", defaultSecs: "30" },
+  { key: "receive_secs", label: "接收", defaultSecs: "30" },
+];
+
 /** Provider 图标 */
 const ProviderIcon = ({ type }: { type: AsrProviderType }) => {
   switch (type) {
     case "whisper_local":
       return <Cpu className="h-5 w-5" />;
     case "openai":
+    case "custom_whisper":
       return <Sparkles className="h-5 w-5" />;
+    case "command":
+      return <Terminal className="h-5 w-5" />;
+    case "http":
+      return <Globe className="h-5 w-5" />;
     default:
       return <Cloud className="h-5 w-5" />;
   }
@@ -40,37 +79,124 @@ export function AddAsrCredentialModal({
     useState<AsrProviderType | null>(null);
   const [name, setName] = useState("");
   const [language, setLanguage] = useState("zh");
+  const [priority, setPriority] = useState(0);
+  const [timeouts, setTimeouts] = useState<AsrTimeoutConfig>({});
+  const [requestsPerSec, setRequestsPerSec] = useState<number | undefined>();
   const [submitting, setSubmitting] = useState(false);
   const [error, setError] = useState<string | null>(null);
 
-  // Whisper 配置
+  // 本地识别配置
+  const [localEngine, setLocalEngine] = useState<LocalAsrEngine>("whisper");
   const [whisperModel, setWhisperModel] = useState<WhisperModelSize>("base");
+  const [senseVoiceModel, setSenseVoiceModel] =
+    useState<SenseVoicePrecision>("int8");
+  // 未启用 sensevoice 特性的构建没有 SenseVoice 模型
+  const [senseVoiceAvailable, setSenseVoiceAvailable] = useState(false);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    listLocalAsrModels()
+      .then((models) =>
+        setSenseVoiceAvailable(models.some((m) => m.engine === "sense_voice")),
+      )
+      .catch(() => setSenseVoiceAvailable(false));
+  }, [isOpen]);
 
   // 讯飞配置
   const [xunfeiAppId, setXunfeiAppId] = useState("");
   const [xunfeiApiKey, setXunfeiApiKey] = useState("");
   const [xunfeiApiSecret, setXunfeiApiSecret] = useState("");
+  const [xunfeiLfasrSecretKey, setXunfeiLfasrSecretKey] = useState("");
+
+  // 火山引擎配置
+  const [volcengineAppId, setVolcengineAppId] = useState("");
+  const [volcengineToken, setVolcengineToken] = useState("");
+  const [volcengineCluster, setVolcengineCluster] = useState("");
 
   // 百度配置
   const [baiduApiKey, setBaiduApiKey] = useState("");
   const [baiduSecretKey, setBaiduSecretKey] = useState("");
 
+  // 腾讯云配置
+  const [tencentSecretId, setTencentSecretId] = useState("");
+  const [tencentSecretKey, setTencentSecretKey] = useState("");
+  const [tencentRegion, setTencentRegion] = useState("");
+
+  // Azure 配置
+  const [azureKey, setAzureKey] = useState("");
+  const [azureRegion, setAzureRegion] = useState("");
+  const [azureLocale, setAzureLocale] = useState("");
+  const [azureProfanity, setAzureProfanity] =
+    useState<AzureProfanityFilter>("masked");
+  const [azureDiarize, setAzureDiarize] = useState(false);
+
+  // Deepgram 配置
+  const [deepgramApiKey, setDeepgramApiKey] = useState("");
+  const [deepgramModel, setDeepgramModel] = useState("");
+  const [deepgramDiarize, setDeepgramDiarize] = useState(false);
+
   // OpenAI 配置
   const [openaiApiKey, setOpenaiApiKey] = useState("");
   const [openaiBaseUrl, setOpenaiBaseUrl] = useState("");
 
+  // 自建 Whisper 配置
+  const [customWhisperBaseUrl, setCustomWhisperBaseUrl] = useState("");
+  const [customWhisperApiKey, setCustomWhisperApiKey] = useState("");
+  const [customWhisperModel, setCustomWhisperModel] = useState("");
+
+  // 自定义命令配置
+  const [commandProgram, setCommandProgram] = useState("");
+  const [commandArgs, setCommandArgs] = useState("");
+  const [commandTimeout, setCommandTimeout] = useState(60);
+
+  // HTTP 接口配置
+  const [httpUrl, setHttpUrl] = useState("");
+  const [httpHeaders, setHttpHeaders] = useState("");
+  const [httpTextPath, setHttpTextPath] = useState("$.text");
+  const [httpUpload, setHttpUpload] = useState<HttpAsrUpload>("raw");
+  const [httpFileField, setHttpFileField] = useState("file");
+
   const resetForm = () => {
     setSelectedProvider(null);
     setName("");
     setLanguage("zh");
+    setPriority(0);
+    setTimeouts({});
+    setRequestsPerSec(undefined);
+    setLocalEngine("whisper");
     setWhisperModel("base");
+    setSenseVoiceModel("int8");
     setXunfeiAppId("");
     setXunfeiApiKey("");
     setXunfeiApiSecret("");
+    setXunfeiLfasrSecretKey("");
+    setVolcengineAppId("");
+    setVolcengineToken("");
+    setVolcengineCluster("");
     setBaiduApiKey("");
     setBaiduSecretKey("");
+    setTencentSecretId("");
+    setTencentSecretKey("");
+    setTencentRegion("");
+    setAzureKey("");
+    setAzureRegion("");
+    setAzureLocale("");
+    setAzureProfanity("masked");
+    setDeepgramApiKey("");
+    setDeepgramModel("");
     setOpenaiApiKey("");
     setOpenaiBaseUrl("");
+    setCustomWhisperBaseUrl("");
+    setCustomWhisperApiKey("");
+    setCustomWhisperModel("");
+    setCommandProgram("");
+    setCommandArgs("");
+    setCommandTimeout(60);
+    setHttpUrl("");
+    setHttpHeaders("");
+    setHttpTextPath("$.text");
+    setHttpUpload("raw");
+    setHttpFileField("file");
     setError(null);
   };
 
@@ -91,10 +217,17 @@ export function AddAsrCredentialModal({
         name: name || undefined,
         is_default: false,
         disabled: false,
+        priority,
         language,
+        timeouts,
+        rate_limit: { requests_per_sec: requestsPerSec },
         whisper_config:
           selectedProvider === "whisper_local"
-            ? { model: whisperModel }
+            ? {
+                engine: localEngine,
+                model: whisperModel,
+                sensevoice_model: senseVoiceModel,
+              }
             : undefined,
         xunfei_config:
           selectedProvider === "xunfei"
@@ -102,12 +235,47 @@ export function AddAsrCredentialModal({
                 app_id: xunfeiAppId,
                 api_key: xunfeiApiKey,
                 api_secret: xunfeiApiSecret,
+                lfasr_secret_key: xunfeiLfasrSecretKey || undefined,
+              }
+            : undefined,
+        volcengine_config:
+          selectedProvider === "volcengine"
+            ? {
+                app_id: volcengineAppId.trim(),
+                access_token: volcengineToken.trim(),
+                cluster: volcengineCluster.trim() || undefined,
               }
             : undefined,
         baidu_config:
           selectedProvider === "baidu"
             ? { api_key: baiduApiKey, secret_key: baiduSecretKey }
             : undefined,
+        tencent_config:
+          selectedProvider === "tencent"
+            ? {
+                secret_id: tencentSecretId.trim(),
+                secret_key: tencentSecretKey.trim(),
+                region: tencentRegion.trim() || undefined,
+              }
+            : undefined,
+        azure_config:
+          selectedProvider === "azure"
+            ? {
+                subscription_key: azureKey.trim(),
+                region: azureRegion.trim(),
+                language: azureLocale.trim() || undefined,
+                profanity: azureProfanity,
+                diarize: azureDiarize,
+              }
+            : undefined,
+        deepgram_config:
+          selectedProvider === "deepgram"
+            ? {
+                api_key: deepgramApiKey.trim(),
+                model: deepgramModel.trim() || undefined,
+                diarize: deepgramDiarize,
+              }
+            : undefined,
         openai_config:
           selectedProvider === "openai"
             ? {
@@ -115,6 +283,36 @@ export function AddAsrCredentialModal({
                 base_url: openaiBaseUrl || undefined,
               }
             : undefined,
+        custom_whisper_config:
+          selectedProvider === "custom_whisper"
+            ? {
+                base_url: customWhisperBaseUrl.trim(),
+                api_key: customWhisperApiKey.trim() || undefined,
+                model: customWhisperModel.trim() || undefined,
+              }
+            : undefined,
+        command_config:
+          selectedProvider === "command"
+            ? {
+                program: commandProgram.trim(),
+                // 每行一个参数，参数中可以包含空格
+                args: commandArgs
+                  .split("\n")
+                  .map((arg) => arg.trim())
+                  .filter(Boolean),
+                timeout_secs: commandTimeout,
+              }
+            : undefined,
+        http_config:
+          selectedProvider === "http"
+            ? {
+                url: httpUrl.trim(),
+                headers: parseHeaders(httpHeaders),
+                text_path: httpTextPath.trim(),
+                upload: httpUpload,
+                file_field: httpFileField.trim() || "file",
+              }
+            : undefined,
       };
 
       console.log("[ASR] 添加凭证:", JSON.stringify(entry, null, 2));
@@ -136,10 +334,24 @@ export function AddAsrCredentialModal({
         return true;
       case "xunfei":
         return xunfeiAppId && xunfeiApiKey && xunfeiApiSecret;
+      case "volcengine":
+        return !!volcengineAppId.trim() && !!volcengineToken.trim();
       case "baidu":
         return baiduApiKey && baiduSecretKey;
+      case "tencent":
+        return !!tencentSecretId.trim() && !!tencentSecretKey.trim();
+      case "azure":
+        return !!azureKey.trim() && !!azureRegion.trim();
+      case "deepgram":
+        return !!deepgramApiKey.trim();
       case "openai":
         return !!openaiApiKey;
+      case "custom_whisper":
+        return !!customWhisperBaseUrl.trim();
+      case "command":
+        return !!commandProgram.trim() && commandTimeout > 0;
+      case "http":
+        return !!httpUrl.trim() && !!httpTextPath.trim();
       default:
         return false;
     }
@@ -224,31 +436,165 @@ export function AddAsrCredentialModal({
                 <option value="zh">中文</option>
                 <option value="en">英文</option>
                 <option value="auto">自动检测</option>
+                <option value="mixed">中英混合</option>
               </select>
+              {language === "mixed" && (
+                <p className="text-xs text-muted-foreground mt-1">
+                  推荐讯飞或本地 Whisper Large 模型
+                </p>
+              )}
             </div>
 
-            {/* Provider 特定字段 */}
-            {selectedProvider === "whisper_local" && (
+            {selectedProvider !== "whisper_local" && (
               <div>
                 <label className="block text-sm font-medium mb-1">
-                  模型大小
+                  回退优先级
                 </label>
-                <select
-                  value={whisperModel}
+                <input
+                  type="number"
+                  min={0}
+                  value={priority}
                   onChange={(e) =>
-                    setWhisperModel(e.target.value as WhisperModelSize)
+                    setPriority(Math.max(0, Number(e.target.value) || 0))
                   }
                   className="w-full rounded-lg border bg-background px-3 py-2"
-                >
-                  {WHISPER_MODELS.map((m) => (
-                    <option key={m.value} value={m.value}>
-                      {m.label} ({m.size}, {m.speed})
-                    </option>
+                />
+                <p className="text-xs text-muted-foreground mt-1">
+                  所选服务连不上或鉴权失败时，按数字从小到大改用其他云端凭证
+                </p>
+              </div>
+            )}
+
+            {selectedProvider !== "whisper_local" && (
+              <div>
+                <label className="block text-sm font-medium mb-1">
+                  请求超时（秒）
+                </label>
+                <div className="grid grid-cols-3 gap-2">
+                  {TIMEOUT_FIELDS.map(({ key, label, defaultSecs }) => (
+                    <input
+                      key={key}
+                      type="number"
+                      min={1}
+                      value={timeouts[key] ?? ""}
+                      onChange={(e) =>
+                        setTimeouts((prev) => ({
+                          ...prev,
+                          [key]: e.target.value
+                            ? Math.max(1, Number(e.target.value))
+                            : undefined,
+                        }))
+                      }
+                      placeholder={`${label} ${defaultSecs}`}
+                      title={`${label}超时`}
+                      className="w-full rounded-lg border bg-background px-3 py-2"
+                    />
                   ))}
-                </select>
+                </div>
+                <p className="text-xs text-muted-foreground mt-1">
+                  留空使用默认值。短句听写可调短以尽快回退，长录音可调长接收超时
+                </p>
+              </div>
+            )}
+
+            {selectedProvider !== "whisper_local" && (
+              <div>
+                <label className="block text-sm font-medium mb-1">
+                  每秒最多请求次数
+                </label>
+                <input
+                  type="number"
+                  min={0}
+                  step={0.5}
+                  value={requestsPerSec ?? ""}
+                  onChange={(e) =>
+                    setRequestsPerSec(
+                      e.target.value
+                        ? Math.max(0, Number(e.target.value))
+                        : undefined,
+                    )
+                  }
+                  placeholder={selectedProvider === "baidu" ? "2" : "不限"}
+                  className="w-full rounded-lg border bg-background px-3 py-2"
+                />
+                <p className="text-xs text-muted-foreground mt-1">
+                  超出服务 QPS 配额的请求会失败，限流后排队等待。留空使用默认值，0
+                  表示不限流
+                </p>
               </div>
             )}
 
+            {/* Provider 特定字段 */}
+            {selectedProvider === "whisper_local" && (
+              <>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    识别引擎
+                  </label>
+                  <select
+                    value={localEngine}
+                    onChange={(e) =>
+                      setLocalEngine(e.target.value as LocalAsrEngine)
+                    }
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  >
+                    {LOCAL_ENGINES.filter(
+                      (engine) =>
+                        engine.value !== "sense_voice" || senseVoiceAvailable,
+                    ).map((engine) => (
+                      <option key={engine.value} value={engine.value}>
+                        {engine.label}（{engine.description}）
+                      </option>
+                    ))}
+                  </select>
+                </div>
+                {localEngine === "whisper" ? (
+                  <div>
+                    <label className="block text-sm font-medium mb-1">
+                      模型大小
+                    </label>
+                    <select
+                      value={whisperModel}
+                      onChange={(e) =>
+                        setWhisperModel(e.target.value as WhisperModelSize)
+                      }
+                      className="w-full rounded-lg border bg-background px-3 py-2"
+                    >
+                      {WHISPER_MODELS.map((m) => (
+                        <option key={m.value} value={m.value}>
+                          {m.label} ({m.size}, {m.speed})
+                        </option>
+                      ))}
+                    </select>
+                  </div>
+                ) : (
+                  <div>
+                    <label className="block text-sm font-medium mb-1">
+                      模型精度
+                    </label>
+                    <select
+                      value={senseVoiceModel}
+                      onChange={(e) =>
+                        setSenseVoiceModel(
+                          e.target.value as SenseVoicePrecision,
+                        )
+                      }
+                      className="w-full rounded-lg border bg-background px-3 py-2"
+                    >
+                      {SENSEVOICE_MODELS.map((m) => (
+                        <option key={m.value} value={m.value}>
+                          {m.label} ({m.size})
+                        </option>
+                      ))}
+                    </select>
+                    <p className="text-xs text-muted-foreground mt-1">
+                      需先在语音设置的「本地模型」中下载对应模型
+                    </p>
+                  </div>
+                )}
+              </>
+            )}
+
             {selectedProvider === "xunfei" && (
               <>
                 <div>
@@ -284,6 +630,62 @@ export function AddAsrCredentialModal({
                     className="w-full rounded-lg border bg-background px-3 py-2"
                   />
                 </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    录音文件转写 SecretKey（可选）
+                  </label>
+                  <input
+                    type="password"
+                    value={xunfeiLfasrSecretKey}
+                    onChange={(e) => setXunfeiLfasrSecretKey(e.target.value)}
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                  <p className="text-xs text-muted-foreground mt-1">
+                    实时听写单次最长 60 秒，填写后更长的录音将使用录音文件转写
+                  </p>
+                </div>
+              </>
+            )}
+
+            {selectedProvider === "volcengine" && (
+              <>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    App ID
+                  </label>
+                  <input
+                    type="text"
+                    value={volcengineAppId}
+                    onChange={(e) => setVolcengineAppId(e.target.value)}
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    Access Token
+                  </label>
+                  <input
+                    type="password"
+                    value={volcengineToken}
+                    onChange={(e) => setVolcengineToken(e.target.value)}
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    集群（可选）
+                  </label>
+                  <input
+                    type="text"
+                    value={volcengineCluster}
+                    onChange={(e) => setVolcengineCluster(e.target.value)}
+                    placeholder="volcengine_streaming_common"
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                  <p className="text-xs text-muted-foreground mt-1">
+                    在火山引擎控制台的语音识别应用中查看，留空使用通用流式识别
+                  </p>
+                </div>
               </>
             )}
 
@@ -314,6 +716,152 @@ export function AddAsrCredentialModal({
               </>
             )}
 
+            {selectedProvider === "tencent" && (
+              <>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    SecretId
+                  </label>
+                  <input
+                    type="text"
+                    value={tencentSecretId}
+                    onChange={(e) => setTencentSecretId(e.target.value)}
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    SecretKey
+                  </label>
+                  <input
+                    type="password"
+                    value={tencentSecretKey}
+                    onChange={(e) => setTencentSecretKey(e.target.value)}
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    地域（可选）
+                  </label>
+                  <input
+                    type="text"
+                    value={tencentRegion}
+                    onChange={(e) => setTencentRegion(e.target.value)}
+                    placeholder="ap-shanghai"
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                  <p className="text-xs text-muted-foreground mt-1">
+                    在腾讯云访问管理控制台创建 API 密钥，单次最长 60 秒
+                  </p>
+                </div>
+              </>
+            )}
+
+            {selectedProvider === "azure" && (
+              <>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    订阅密钥
+                  </label>
+                  <input
+                    type="password"
+                    value={azureKey}
+                    onChange={(e) => setAzureKey(e.target.value)}
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">地域</label>
+                  <input
+                    type="text"
+                    value={azureRegion}
+                    onChange={(e) => setAzureRegion(e.target.value)}
+                    placeholder="eastasia"
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    识别语言（可选）
+                  </label>
+                  <input
+                    type="text"
+                    value={azureLocale}
+                    onChange={(e) => setAzureLocale(e.target.value)}
+                    placeholder="zh-CN"
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                  <p className="text-xs text-muted-foreground mt-1">
+                    留空时按上方语言设置推断，单次最长 60 秒
+                  </p>
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    脏话过滤
+                  </label>
+                  <select
+                    value={azureProfanity}
+                    onChange={(e) =>
+                      setAzureProfanity(e.target.value as AzureProfanityFilter)
+                    }
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  >
+                    <option value="masked">用星号替换</option>
+                    <option value="removed">删除</option>
+                    <option value="raw">保留原文</option>
+                  </select>
+                </div>
+                <label className="flex items-center gap-2 text-sm">
+                  <input
+                    type="checkbox"
+                    checked={azureDiarize}
+                    onChange={() => setAzureDiarize(!azureDiarize)}
+                  />
+                  区分说话人（改用快速转写，单次最长 2 小时）
+                </label>
+              </>
+            )}
+
+            {selectedProvider === "deepgram" && (
+              <>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    API Key
+                  </label>
+                  <input
+                    type="password"
+                    value={deepgramApiKey}
+                    onChange={(e) => setDeepgramApiKey(e.target.value)}
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    模型（可选）
+                  </label>
+                  <input
+                    type="text"
+                    value={deepgramModel}
+                    onChange={(e) => setDeepgramModel(e.target.value)}
+                    placeholder="nova-2"
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                  <p className="text-xs text-muted-foreground mt-1">
+                    如 nova-2、nova-2-meeting、nova-2-phonecall，留空使用 nova-2
+                  </p>
+                </div>
+                <label className="flex items-center gap-2 text-sm">
+                  <input
+                    type="checkbox"
+                    checked={deepgramDiarize}
+                    onChange={() => setDeepgramDiarize(!deepgramDiarize)}
+                  />
+                  区分说话人
+                </label>
+              </>
+            )}
+
             {selectedProvider === "openai" && (
               <>
                 <div>
@@ -342,6 +890,169 @@ export function AddAsrCredentialModal({
               </>
             )}
 
+            {selectedProvider === "custom_whisper" && (
+              <>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    服务地址
+                  </label>
+                  <input
+                    type="text"
+                    value={customWhisperBaseUrl}
+                    onChange={(e) => setCustomWhisperBaseUrl(e.target.value)}
+                    placeholder="http://192.168.1.10:8000"
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                  <p className="text-xs text-muted-foreground mt-1">
+                    兼容 OpenAI /v1/audio/transcriptions 接口的服务，如
+                    faster-whisper-server、whisper.cpp server
+                  </p>
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    API Key（可选）
+                  </label>
+                  <input
+                    type="password"
+                    value={customWhisperApiKey}
+                    onChange={(e) => setCustomWhisperApiKey(e.target.value)}
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    模型（可选）
+                  </label>
+                  <input
+                    type="text"
+                    value={customWhisperModel}
+                    onChange={(e) => setCustomWhisperModel(e.target.value)}
+                    placeholder="whisper-1"
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                </div>
+              </>
+            )}
+
+            {selectedProvider === "command" && (
+              <>
+                <div>
+                  <label className="block text-sm font-medium mb-1">命令</label>
+                  <input
+                    type="text"
+                    value={commandProgram}
+                    onChange={(e) => setCommandProgram(e.target.value)}
+                    placeholder="/usr/local/bin/my-asr.sh"
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    参数（可选，每行一个）
+                  </label>
+                  <textarea
+                    value={commandArgs}
+                    onChange={(e) => setCommandArgs(e.target.value)}
+                    rows={3}
+                    placeholder={"--language\n{language}"}
+                    className="w-full rounded-lg border bg-background px-3 py-2 font-mono text-sm"
+                  />
+                  <p className="text-xs text-muted-foreground mt-1">
+                    录音以 WAV 格式写入标准输入，命令需输出 {'{"text": "..."}'}
+                    。{"{language}"} 替换为识别语言，{"{file}"}
+                    替换为临时 WAV 文件路径（此时不写入标准输入）
+                  </p>
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    超时（秒）
+                  </label>
+                  <input
+                    type="number"
+                    min={1}
+                    value={commandTimeout}
+                    onChange={(e) =>
+                      setCommandTimeout(Number(e.target.value))
+                    }
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                </div>
+              </>
+            )}
+
+            {selectedProvider === "http" && (
+              <>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    接口地址
+                  </label>
+                  <input
+                    type="text"
+                    value={httpUrl}
+                    onChange={(e) => setHttpUrl(e.target.value)}
+                    placeholder="http://localhost:50000/api/v1/asr"
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  />
+                  <p className="text-xs text-muted-foreground mt-1">
+                    录音以 WAV 格式 POST 到该地址，{"{language}"}
+                    替换为识别语言
+                  </p>
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    请求头（可选，每行一个）
+                  </label>
+                  <textarea
+                    value={httpHeaders}
+                    onChange={(e) => setHttpHeaders(e.target.value)}
+                    rows={2}
+                    placeholder="Authorization: Bearer xxx"
+                    className="w-full rounded-lg border bg-background px-3 py-2 font-mono text-sm"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    上传方式
+                  </label>
+                  <select
+                    value={httpUpload}
+                    onChange={(e) =>
+                      setHttpUpload(e.target.value as HttpAsrUpload)
+                    }
+                    className="w-full rounded-lg border bg-background px-3 py-2"
+                  >
+                    <option value="raw">请求体为 WAV</option>
+                    <option value="multipart">multipart 表单</option>
+                  </select>
+                </div>
+                {httpUpload === "multipart" && (
+                  <div>
+                    <label className="block text-sm font-medium mb-1">
+                      文件字段名
+                    </label>
+                    <input
+                      type="text"
+                      value={httpFileField}
+                      onChange={(e) => setHttpFileField(e.target.value)}
+                      className="w-full rounded-lg border bg-background px-3 py-2"
+                    />
+                  </div>
+                )}
+                <div>
+                  <label className="block text-sm font-medium mb-1">
+                    文本字段路径（JSONPath）
+                  </label>
+                  <input
+                    type="text"
+                    value={httpTextPath}
+                    onChange={(e) => setHttpTextPath(e.target.value)}
+                    placeholder="$.result[0].text"
+                    className="w-full rounded-lg border bg-background px-3 py-2 font-mono text-sm"
+                  />
+                </div>
+              </>
+            )}
+
             {/* 提交按钮 */}
             <div className="flex justify-end gap-2 pt-2">
               <button
@@ -14,16 +14,25 @@ import {
   Cpu,
   Cloud,
   Sparkles,
+  Terminal,
+  Globe,
 } from "lucide-react";
-import type { AsrCredentialEntry, AsrProviderType } from "./types";
+import type {
+  AsrCredentialEntry,
+  AsrProviderType,
+  AsrUsageStats,
+  AsrHealthCheck,
+} from "./types";
 import { ASR_PROVIDERS } from "./types";
 
 interface AsrCredentialCardProps {
   credential: AsrCredentialEntry;
+  /** 本月用量（按识别服务分别汇总） */
+  usage?: AsrUsageStats[];
   onSetDefault: () => void;
   onToggle: () => void;
   onDelete: () => void;
-  onTest: () => Promise<{ success: boolean; message: string }>;
+  onTest: () => Promise<AsrHealthCheck>;
 }
 
 /** 获取 Provider 图标 */
@@ -33,6 +42,10 @@ const ProviderIcon = ({ type }: { type: AsrProviderType }) => {
       return <Cpu className="h-5 w-5" />;
     case "openai":
       return <Sparkles className="h-5 w-5" />;
+    case "command":
+      return <Terminal className="h-5 w-5" />;
+    case "http":
+      return <Globe className="h-5 w-5" />;
     default:
       return <Cloud className="h-5 w-5" />;
   }
@@ -43,18 +56,27 @@ const getProviderLabel = (type: AsrProviderType): string => {
   return ASR_PROVIDERS.find((p) => p.type === type)?.label || type;
 };
 
+/** 格式化音频时长 */
+const formatAudioSecs = (secs: number): string => {
+  if (secs < 60) return `${Math.round(secs)} 秒`;
+  if (secs < 3600) return `${(secs / 60).toFixed(1)} 分钟`;
+  return `${(secs / 3600).toFixed(1)} 小时`;
+};
+
 export function AsrCredentialCard({
   credential,
+  usage = [],
   onSetDefault,
   onToggle,
   onDelete,
   onTest,
 }: AsrCredentialCardProps) {
   const [testing, setTesting] = useState(false);
-  const [testResult, setTestResult] = useState<{
-    success: boolean;
-    message: string;
-  } | null>(null);
+  const [testResult, setTestResult] = useState<AsrHealthCheck | null>(null);
+
+  const calls = usage.reduce((sum, u) => sum + u.calls, 0);
+  const failed = usage.reduce((sum, u) => sum + u.failed, 0);
+  const audioSecs = usage.reduce((sum, u) => sum + u.audio_secs, 0);
 
   const handleTest = async () => {
     setTesting(true);
@@ -66,6 +88,8 @@ export function AsrCredentialCard({
       setTestResult({
         success: false,
         message: e instanceof Error ? e.message : "测试失败",
+        latency_ms: null,
+        text: null,
       });
     } finally {
       setTesting(false);
@@ -110,6 +134,22 @@ export function AsrCredentialCard({
               <span className="mx-2">·</span>
               <span>语言: {credential.language}</span>
             </div>
+            {calls > 0 && (
+              <div
+                className="mt-1 text-xs text-muted-foreground"
+                title={usage
+                  .map(
+                    (u) =>
+                      `${u.provider}：${u.calls} 次，平均 ${Math.round(u.avg_latency_ms)} ms`,
+                  )
+                  .join("\n")}
+              >
+                本月 {calls} 次 · 识别 {formatAudioSecs(audioSecs)}
+                {failed > 0 && (
+                  <span className="text-red-500"> · 失败 {failed} 次</span>
+                )}
+              </div>
+            )}
           </div>
         </div>
 
@@ -163,6 +203,11 @@ export function AsrCredentialCard({
           }`}
         >
           {testResult.message}
+          {testResult.success && (
+            <div className="mt-1 text-xs opacity-80">
+              识别文本：{testResult.text || "（未识别出文字）"}
+            </div>
+          )}
         </div>
       )}
     </div>
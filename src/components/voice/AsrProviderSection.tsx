@@ -5,16 +5,29 @@
  */
 
 import { useState, useEffect, useCallback } from "react";
-import { Plus, RefreshCw, Cpu, Cloud, Sparkles } from "lucide-react";
+import {
+  Plus,
+  RefreshCw,
+  Cpu,
+  Cloud,
+  Sparkles,
+  Terminal,
+  Globe,
+} from "lucide-react";
 import { AsrCredentialCard } from "./AsrCredentialCard";
 import { AddAsrCredentialModal } from "./AddAsrCredentialModal";
-import type { AsrCredentialEntry, AsrProviderType } from "./types";
+import type {
+  AsrCredentialEntry,
+  AsrProviderType,
+  AsrUsageStats,
+} from "./types";
 import {
   getAsrCredentials,
   deleteAsrCredential,
   setDefaultAsrCredential,
   testAsrCredential,
   updateAsrCredential,
+  getAsrUsageStats,
   ASR_PROVIDERS,
 } from "./types";
 
@@ -25,6 +38,10 @@ const ProviderIcon = ({ type }: { type: AsrProviderType }) => {
       return <Cpu className="h-5 w-5" />;
     case "openai":
       return <Sparkles className="h-5 w-5" />;
+    case "command":
+      return <Terminal className="h-5 w-5" />;
+    case "http":
+      return <Globe className="h-5 w-5" />;
     default:
       return <Cloud className="h-5 w-5" />;
   }
@@ -32,6 +49,7 @@ const ProviderIcon = ({ type }: { type: AsrProviderType }) => {
 
 export function AsrProviderSection() {
   const [credentials, setCredentials] = useState<AsrCredentialEntry[]>([]);
+  const [usage, setUsage] = useState<AsrUsageStats[]>([]);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
   const [addModalOpen, setAddModalOpen] = useState(false);
@@ -45,6 +63,8 @@ export function AsrProviderSection() {
     try {
       const data = await getAsrCredentials();
       setCredentials(data);
+      // 用量统计失败不影响凭证列表
+      setUsage(await getAsrUsageStats().catch(() => []));
     } catch (e) {
       setError(e instanceof Error ? e.message : "加载失败");
     } finally {
@@ -198,6 +218,7 @@ export function AsrProviderSection() {
             <AsrCredentialCard
               key={credential.id}
               credential={credential}
+              usage={usage.filter((u) => u.credential_id === credential.id)}
               onSetDefault={() => handleSetDefault(credential.id)}
               onToggle={() => handleToggle(credential)}
               onDelete={() => handleDelete(credential.id)}
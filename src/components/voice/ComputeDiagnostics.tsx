@@ -0,0 +1,185 @@
+/**
+ * @file ComputeDiagnostics.tsx
+ * @description 本地推理诊断 - 展示 GPU 加速状态、显存和本地 Whisper 识别速度，帮助选择模型大小
+ * @module components/voice/ComputeDiagnostics
+ */
+
+import { useState, useEffect, useCallback } from "react";
+import { Cpu, Gauge, Loader2, Mic } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import {
+  getComputeDiagnostics,
+  type ComputeDiagnostics as Diagnostics,
+  type GpuInfo,
+} from "@/lib/api/asrProvider";
+import { WHISPER_MODELS } from "./types";
+
+/** 实时率低于此值时可以尝试更大的模型 */
+const RTF_FAST = 0.15;
+/** 实时率高于此值时建议换用更小的模型 */
+const RTF_SLOW = 0.5;
+/** 朗读测速的录音时长（毫秒） */
+const READ_ALOUD_MS = 10000;
+
+type BenchmarkMode = "read" | "synthetic";
+
+function formatMemory(mb: number) {
+  return mb >= 1024 ? `${(mb / 1024).toFixed(1)}GB` : `${mb}MB`;
+}
+
+function describeGpu(gpu: GpuInfo) {
+  const parts = [gpu.name];
+  if (gpu.total_memory_mb !== undefined) {
+    const label = gpu.unified_memory ? "统一内存" : "显存";
+    const free =
+      gpu.free_memory_mb !== undefined
+        ? `，可用 ${formatMemory(gpu.free_memory_mb)}`
+        : "";
+    parts.push(`${label} ${formatMemory(gpu.total_memory_mb)}${free}`);
+  }
+  return parts.join(" · ");
+}
+
+function rtfHint(rtf: number, gpuEnabled: boolean) {
+  if (rtf < RTF_FAST) return "速度充裕，可以尝试更大的模型以提高准确率";
+  if (rtf <= RTF_SLOW) return "速度合适，当前模型适合日常听写";
+  return gpuEnabled
+    ? "识别较慢，建议换用更小的模型"
+    : "识别较慢，建议换用更小的模型或使用启用 GPU 加速的版本";
+}
+
+export function ComputeDiagnostics() {
+  const [diagnostics, setDiagnostics] = useState<Diagnostics | null>(null);
+  const [running, setRunning] = useState<BenchmarkMode | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getComputeDiagnostics()
+      .then(setDiagnostics)
+      .catch((err) => console.error("[本地推理诊断] 加载失败:", err));
+  }, []);
+
+  const handleBenchmark = useCallback(async (mode: BenchmarkMode) => {
+    setRunning(mode);
+    setError(null);
+    try {
+      const recordMs = mode === "read" ? READ_ALOUD_MS : undefined;
+      setDiagnostics(await getComputeDiagnostics(true, recordMs));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setRunning(null);
+    }
+  }, []);
+
+  const backends = [
+    diagnostics?.metal && "Metal",
+    diagnostics?.cuda && "CUDA",
+  ].filter(Boolean);
+  const gpuEnabled = backends.length > 0;
+  const benchmark = diagnostics?.benchmark;
+  const modelLabel = WHISPER_MODELS.find(
+    (m) => m.value === diagnostics?.model,
+  )?.label;
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-2">
+          <Cpu className="h-4 w-4 text-muted-foreground" />
+          <div>
+            <span className="text-sm">本地推理</span>
+            <p className="text-xs text-muted-foreground">
+              本地 Whisper 的加速方式和识别速度
+            </p>
+          </div>
+        </div>
+        <div className="flex gap-2">
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={() => handleBenchmark("read")}
+            disabled={running !== null}
+          >
+            {running === "read" ? (
+              <Loader2 className="h-4 w-4 mr-1 animate-spin" />
+            ) : (
+              <Mic className="h-4 w-4 mr-1" />
+            )}
+            {running === "read" ? "测速中..." : "朗读测速"}
+          </Button>
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={() => handleBenchmark("synthetic")}
+            disabled={running !== null}
+          >
+            {running === "synthetic" ? (
+              <Loader2 className="h-4 w-4 mr-1 animate-spin" />
+            ) : (
+              <Gauge className="h-4 w-4 mr-1" />
+            )}
+            {running === "synthetic" ? "测速中..." : "快速测速"}
+          </Button>
+        </div>
+      </div>
+
+      {running === "read" && (
+        <p className="text-xs text-muted-foreground">
+          请在 10 秒内朗读一段话，录音结束后自动测速
+        </p>
+      )}
+
+      {diagnostics && (
+        <div className="space-y-1 rounded-md border px-3 py-2 text-xs">
+          <div className="flex justify-between">
+            <span className="text-muted-foreground">GPU 加速</span>
+            <span>
+              {gpuEnabled ? backends.join(" / ") : "未启用（仅 CPU）"}
+            </span>
+          </div>
+          {diagnostics.gpus.map((gpu) => (
+            <div key={gpu.name} className="flex justify-between gap-4">
+              <span className="text-muted-foreground">GPU</span>
+              <span className="text-right">{describeGpu(gpu)}</span>
+            </div>
+          ))}
+          <div className="flex justify-between">
+            <span className="text-muted-foreground">CPU 线程</span>
+            <span>{diagnostics.cpu_threads}</span>
+          </div>
+          {benchmark && (
+            <>
+              <div className="flex justify-between">
+                <span className="text-muted-foreground">
+                  识别速度{modelLabel && `（${modelLabel}）`}
+                </span>
+                <span>
+                  RTF {benchmark.rtf.toFixed(2)} ·{" "}
+                  {benchmark.audio_secs.toFixed(0)} 秒音频耗时{" "}
+                  {benchmark.elapsed_secs.toFixed(1)} 秒
+                </span>
+              </div>
+              <div className="flex justify-between">
+                <span className="text-muted-foreground">模型加载</span>
+                <span>{benchmark.load_secs.toFixed(1)} 秒</span>
+              </div>
+              <p className="pt-1 text-muted-foreground">
+                {rtfHint(benchmark.rtf, gpuEnabled)}
+              </p>
+              {benchmark.synthetic && (
+                <p className="text-muted-foreground">
+                  快速测速使用合成音频，结果偏乐观；朗读测速更接近实际听写
+                </p>
+              )}
+            </>
+          )}
+        </div>
+      )}
+
+      {error && <p className="text-xs text-destructive">{error}</p>}
+    </div>
+  );
+}
+
+export default ComputeDiagnostics;
@@ -0,0 +1,109 @@
+/**
+ * @file HomophoneCorrection.tsx
+ * @description 同音词纠正 - 编辑用户词条，润色前把识别结果中拼音相同的别字替换为词条
+ * @module components/voice/HomophoneCorrection
+ */
+
+import { useState, useEffect } from "react";
+import { SpellCheck } from "lucide-react";
+import { cn } from "@/lib/utils";
+import type { HomophoneCorrection as HomophoneCorrectionConfig } from "@/lib/api/asrProvider";
+
+/** 同音词纠正的默认配置 */
+export const DEFAULT_HOMOPHONE: HomophoneCorrectionConfig = {
+  enabled: false,
+  terms: [],
+  fuzzy: false,
+};
+
+interface HomophoneCorrectionProps {
+  config: HomophoneCorrectionConfig;
+  onChange: (patch: Partial<HomophoneCorrectionConfig>) => Promise<void>;
+  disabled?: boolean;
+}
+
+/** 每行一个词条，去掉空行和重复 */
+function parseTerms(text: string): string[] {
+  const terms = text
+    .split("\n")
+    .map((line) => line.trim())
+    .filter(Boolean);
+  return Array.from(new Set(terms));
+}
+
+export function HomophoneCorrection({
+  config,
+  onChange,
+  disabled = false,
+}: HomophoneCorrectionProps) {
+  const [termsText, setTermsText] = useState((config.terms ?? []).join("\n"));
+
+  useEffect(() => {
+    setTermsText((config.terms ?? []).join("\n"));
+  }, [config.terms]);
+
+  const handleTermsBlur = () => {
+    const terms = parseTerms(termsText);
+    if (terms.join("\n") !== (config.terms ?? []).join("\n")) {
+      onChange({ terms });
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-2">
+          <SpellCheck className="h-4 w-4 text-muted-foreground" />
+          <div>
+            <span className="text-sm">同音词纠正</span>
+            <p className="text-xs text-muted-foreground">
+              润色前把拼音相同的别字替换为词条（如帐号 → 账号、人名、产品名）
+            </p>
+          </div>
+        </div>
+        <label className="relative inline-flex items-center cursor-pointer">
+          <input
+            type="checkbox"
+            checked={config.enabled}
+            onChange={() => onChange({ enabled: !config.enabled })}
+            disabled={disabled}
+            className="sr-only peer"
+          />
+          <div
+            className={cn(
+              "w-9 h-5 rounded-full transition-colors",
+              "bg-muted peer-checked:bg-primary",
+              "after:content-[''] after:absolute after:top-0.5 after:left-0.5",
+              "after:bg-white after:rounded-full after:h-4 after:w-4",
+              "after:transition-transform peer-checked:after:translate-x-4",
+              disabled && "opacity-50 cursor-not-allowed",
+            )}
+          />
+        </label>
+      </div>
+
+      {config.enabled && (
+        <div className="space-y-2 pl-6">
+          <textarea
+            value={termsText}
+            onChange={(e) => setTermsText(e.target.value)}
+            onBlur={handleTermsBlur}
+            disabled={disabled}
+            rows={4}
+            placeholder={"每行一个词条，至少两个汉字\n账号\n钉钉"}
+            className="w-full rounded-md border bg-background px-3 py-2 text-sm focus:outline-none focus:ring-1 focus:ring-primary"
+          />
+          <label className="flex items-center gap-2 text-xs text-muted-foreground">
+            <input
+              type="checkbox"
+              checked={config.fuzzy}
+              onChange={() => onChange({ fuzzy: !config.fuzzy })}
+              disabled={disabled}
+            />
+            模糊音：不区分平翘舌（z/zh）、前后鼻音（in/ing）和 n/l
+          </label>
+        </div>
+      )}
+    </div>
+  );
+}
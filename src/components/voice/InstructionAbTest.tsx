@@ -0,0 +1,207 @@
+/**
+ * @file InstructionAbTest.tsx
+ * @description 润色指令 A/B 测试 - 选择两个变体指令轮换润色，汇总悬浮窗中的评价
+ * @module components/voice/InstructionAbTest
+ */
+
+import { useState, useEffect, useCallback } from "react";
+import { FlaskConical, RefreshCw, Trash2, Trophy } from "lucide-react";
+import { cn } from "@/lib/utils";
+import {
+  getVoiceAbSummary,
+  clearVoiceAbTrials,
+  type AbTestSummary,
+  type AbVariantStats,
+  type InstructionAbTest as InstructionAbTestConfig,
+  type VoiceInstruction,
+} from "@/lib/api/asrProvider";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+
+/** A/B 测试的默认配置 */
+export const DEFAULT_AB_TEST: InstructionAbTestConfig = {
+  enabled: false,
+  variant_a: "",
+  variant_b: "",
+};
+
+interface InstructionAbTestProps {
+  config: InstructionAbTestConfig;
+  instructions: VoiceInstruction[];
+  onChange: (patch: Partial<InstructionAbTestConfig>) => Promise<void>;
+  disabled?: boolean;
+}
+
+function StatsRow({
+  label,
+  stats,
+  name,
+  winner,
+}: {
+  label: string;
+  stats: AbVariantStats;
+  name: string;
+  winner: boolean;
+}) {
+  return (
+    <div className="flex items-center gap-2 text-xs">
+      <span className="w-4 shrink-0 text-muted-foreground">{label}</span>
+      <span className="flex-1 truncate">
+        {name}
+        {winner && <Trophy className="ml-1 inline h-3 w-3 text-amber-500" />}
+      </span>
+      <span className="text-muted-foreground">
+        {stats.trials} 次 · 好评 {stats.good} · 差评 {stats.bad}
+      </span>
+      <span className="w-12 text-right">
+        {stats.good_rate == null
+          ? "-"
+          : `${Math.round(stats.good_rate * 100)}%`}
+      </span>
+    </div>
+  );
+}
+
+export function InstructionAbTest({
+  config,
+  instructions,
+  onChange,
+  disabled = false,
+}: InstructionAbTestProps) {
+  const [summary, setSummary] = useState<AbTestSummary | null>(null);
+
+  const loadSummary = useCallback(async () => {
+    try {
+      setSummary(await getVoiceAbSummary());
+    } catch (err) {
+      console.error("[指令 A/B 测试] 获取结果失败:", err);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadSummary();
+  }, [loadSummary, config.variant_a, config.variant_b]);
+
+  const handleClear = async () => {
+    try {
+      await clearVoiceAbTrials();
+      await loadSummary();
+    } catch (err) {
+      console.error("[指令 A/B 测试] 清空记录失败:", err);
+    }
+  };
+
+  const instructionName = (id: string) =>
+    instructions.find((i) => i.id === id)?.name ?? id;
+  const candidates = instructions.filter((i) => i.id !== "raw");
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-2">
+          <FlaskConical className="h-4 w-4 text-muted-foreground" />
+          <div>
+            <span className="text-sm">指令 A/B 测试</span>
+            <p className="text-xs text-muted-foreground">
+              自动选择指令的润色在两个变体间轮换，在悬浮窗中评价效果
+            </p>
+          </div>
+        </div>
+        <label className="relative inline-flex items-center cursor-pointer">
+          <input
+            type="checkbox"
+            checked={config.enabled}
+            onChange={() => onChange({ enabled: !config.enabled })}
+            disabled={disabled}
+            className="sr-only peer"
+          />
+          <div
+            className={cn(
+              "w-9 h-5 rounded-full transition-colors",
+              "bg-muted peer-checked:bg-primary",
+              "after:content-[''] after:absolute after:top-0.5 after:left-0.5",
+              "after:bg-white after:rounded-full after:h-4 after:w-4",
+              "after:transition-transform peer-checked:after:translate-x-4",
+              disabled && "opacity-50 cursor-not-allowed",
+            )}
+          />
+        </label>
+      </div>
+
+      {config.enabled && (
+        <div className="space-y-2 pl-6">
+          {(["variant_a", "variant_b"] as const).map((key, index) => (
+            <div key={key} className="flex items-center gap-2">
+              <span className="w-4 text-xs text-muted-foreground">
+                {index === 0 ? "A" : "B"}
+              </span>
+              <Select
+                value={config[key] || undefined}
+                onValueChange={(value) => onChange({ [key]: value })}
+                disabled={disabled}
+              >
+                <SelectTrigger className="flex-1 h-8 text-sm">
+                  <SelectValue placeholder="选择指令" />
+                </SelectTrigger>
+                <SelectContent>
+                  {candidates.map((instruction) => (
+                    <SelectItem key={instruction.id} value={instruction.id}>
+                      {instruction.name}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+          ))}
+
+          {summary && (
+            <div className="space-y-1 rounded-md border px-3 py-2">
+              <StatsRow
+                label="A"
+                stats={summary.variant_a}
+                name={instructionName(summary.variant_a.instruction_id)}
+                winner={summary.winner === summary.variant_a.instruction_id}
+              />
+              <StatsRow
+                label="B"
+                stats={summary.variant_b}
+                name={instructionName(summary.variant_b.instruction_id)}
+                winner={summary.winner === summary.variant_b.instruction_id}
+              />
+              <div className="flex items-center justify-between pt-1 text-xs text-muted-foreground">
+                <span>
+                  {summary.winner
+                    ? `${instructionName(summary.winner)} 的好评率显著更高`
+                    : "每个变体至少 10 条评价且差异显著时判断胜出"}
+                </span>
+                <div className="flex gap-1">
+                  <button
+                    type="button"
+                    onClick={loadSummary}
+                    className="p-0.5 hover:text-foreground"
+                    title="刷新"
+                  >
+                    <RefreshCw className="h-3 w-3" />
+                  </button>
+                  <button
+                    type="button"
+                    onClick={handleClear}
+                    className="p-0.5 hover:text-destructive"
+                    title="清空记录（修改指令后重新统计）"
+                  >
+                    <Trash2 className="h-3 w-3" />
+                  </button>
+                </div>
+              </div>
+            </div>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
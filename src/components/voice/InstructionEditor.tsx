@@ -54,6 +54,10 @@ interface EditingInstruction {
   prompt: string;
   shortcut: string;
   icon: string;
+  actions: boolean;
+  /** 允许执行的按键，逗号分隔 */
+  allowedKeys: string;
+  itn: boolean;
   isPreset: boolean;
 }
 
@@ -61,6 +65,9 @@ interface EditingInstruction {
 // 辅助函数
 // ============================================================
 
+/** 新指令默认允许的按键 */
+const DEFAULT_ALLOWED_KEYS = "Enter, Tab";
+
 /** 预设指令图标映射 */
 const PRESET_ICONS: Record<string, React.ReactNode> = {
   default: <MessageSquare className="h-4 w-4" />,
@@ -456,6 +463,63 @@ function EditForm({
         </p>
       </div>
 
+      {/* 输出动作 */}
+      <div>
+        <label className="flex items-center gap-2 text-sm font-medium">
+          <input
+            type="checkbox"
+            checked={instruction.actions}
+            onChange={(e) =>
+              onChange({ ...instruction, actions: e.target.checked })
+            }
+            disabled={instruction.isPreset}
+            className="h-4 w-4 rounded border-gray-300"
+          />
+          执行按键
+        </label>
+        <p className="mt-1 text-xs text-muted-foreground">
+          开启后润色结果中的 {"{Enter}"}、{"{Tab}"}、{"{Ctrl+Enter}"}
+          等标记在输入时作为按键执行，文字中的花括号需写成 {"{{"} 和 {"}}"}
+        </p>
+        {instruction.actions && (
+          <>
+            <input
+              type="text"
+              value={instruction.allowedKeys}
+              onChange={(e) =>
+                onChange({ ...instruction, allowedKeys: e.target.value })
+              }
+              disabled={instruction.isPreset}
+              placeholder="Enter, Tab, Ctrl+Enter"
+              className="mt-2 w-full rounded-lg border bg-background px-3 py-2 text-sm disabled:opacity-50"
+            />
+            <p className="mt-1 text-xs text-muted-foreground">
+              只执行以上按键（逗号分隔），润色结果中的其他按键忽略
+            </p>
+          </>
+        )}
+      </div>
+
+      {/* 数字书面化 */}
+      <div>
+        <label className="flex items-center gap-2 text-sm font-medium">
+          <input
+            type="checkbox"
+            checked={instruction.itn}
+            onChange={(e) =>
+              onChange({ ...instruction, itn: e.target.checked })
+            }
+            disabled={instruction.isPreset}
+            className="h-4 w-4 rounded border-gray-300"
+          />
+          数字书面化
+        </label>
+        <p className="mt-1 text-xs text-muted-foreground">
+          开启后输出前把「二零二五年三月十五日」「三点五个百分点」等读法改为
+          「2025年3月15日」「3.5个百分点」
+        </p>
+      </div>
+
       {/* 快捷键 */}
       <div>
         <label className="block text-sm font-medium mb-1">快捷键（可选）</label>
@@ -535,6 +599,9 @@ export function InstructionEditor({
       prompt: "",
       shortcut: "",
       icon: "",
+      actions: false,
+      allowedKeys: DEFAULT_ALLOWED_KEYS,
+      itn: false,
       isPreset: false,
     });
     setSaveError(null);
@@ -549,6 +616,9 @@ export function InstructionEditor({
       prompt: instruction.prompt,
       shortcut: instruction.shortcut || "",
       icon: instruction.icon || "",
+      actions: !!instruction.actions,
+      allowedKeys: instruction.allowed_keys?.join(", ") ?? DEFAULT_ALLOWED_KEYS,
+      itn: !!instruction.itn,
       isPreset: instruction.is_preset,
     });
     setSaveError(null);
@@ -576,6 +646,12 @@ export function InstructionEditor({
         shortcut: editingInstruction.shortcut || undefined,
         is_preset: false,
         icon: editingInstruction.icon || undefined,
+        actions: editingInstruction.actions,
+        allowed_keys: editingInstruction.allowedKeys
+          .split(/[,，]/)
+          .map((key) => key.trim())
+          .filter(Boolean),
+        itn: editingInstruction.itn,
       };
 
       await saveVoiceInstruction(instruction);
@@ -0,0 +1,151 @@
+/**
+ * @file LocalModelManager.tsx
+ * @description 本地识别模型管理 - 下载和删除 SenseVoice 等本地引擎的模型
+ * @module components/voice/LocalModelManager
+ */
+
+import { useState, useEffect, useCallback } from "react";
+import { HardDriveDownload, Loader2, Trash2 } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { safeListen } from "@/lib/dev-bridge";
+import {
+  listLocalAsrModels,
+  downloadLocalAsrModel,
+  deleteLocalAsrModel,
+  MODEL_DOWNLOAD_EVENT,
+  type LocalModelInfo,
+  type ModelDownloadProgress,
+} from "@/lib/api/asrProvider";
+
+function formatProgress(progress: ModelDownloadProgress) {
+  const mb = (progress.downloaded / 1024 / 1024).toFixed(0);
+  if (!progress.total) return `${progress.file} ${mb}MB`;
+  const percent = Math.floor((progress.downloaded / progress.total) * 100);
+  return `${progress.file} ${percent}%`;
+}
+
+export function LocalModelManager() {
+  const [models, setModels] = useState<LocalModelInfo[]>([]);
+  const [progress, setProgress] = useState<
+    Record<string, ModelDownloadProgress>
+  >({});
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(() => {
+    listLocalAsrModels()
+      .then(setModels)
+      .catch((err) => console.error("[本地模型] 加载失败:", err));
+  }, []);
+
+  useEffect(() => {
+    refresh();
+
+    let unlisten: (() => void) | undefined;
+    safeListen<ModelDownloadProgress>(MODEL_DOWNLOAD_EVENT, (event) => {
+      setProgress((prev) => ({ ...prev, [event.payload.id]: event.payload }));
+    }).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, [refresh]);
+
+  const handleDownload = useCallback(
+    async (id: string) => {
+      setError(null);
+      setModels((prev) =>
+        prev.map((m) => (m.id === id ? { ...m, downloading: true } : m)),
+      );
+      try {
+        await downloadLocalAsrModel(id);
+      } catch (err) {
+        setError(String(err));
+      } finally {
+        setProgress((prev) => {
+          const { [id]: _, ...rest } = prev;
+          return rest;
+        });
+        refresh();
+      }
+    },
+    [refresh],
+  );
+
+  const handleDelete = useCallback(
+    async (id: string) => {
+      setError(null);
+      try {
+        await deleteLocalAsrModel(id);
+      } catch (err) {
+        setError(String(err));
+      } finally {
+        refresh();
+      }
+    },
+    [refresh],
+  );
+
+  // 未启用 sensevoice 特性的构建没有可下载的模型
+  if (models.length === 0) return null;
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2">
+        <HardDriveDownload className="h-4 w-4 text-muted-foreground" />
+        <div>
+          <span className="text-sm">本地模型</span>
+          <p className="text-xs text-muted-foreground">
+            SenseVoice 引擎使用的模型，下载后在本地识别凭证中选择
+          </p>
+        </div>
+      </div>
+
+      <div className="space-y-1 rounded-md border px-3 py-2 text-xs">
+        {models.map((model) => (
+          <div
+            key={model.id}
+            className="flex items-center justify-between gap-4"
+          >
+            <div>
+              <span>{model.name}</span>
+              <span className="ml-2 text-muted-foreground">
+                ~{model.size_mb}MB
+              </span>
+            </div>
+            {model.downloading ? (
+              <span className="flex items-center gap-1 text-muted-foreground">
+                <Loader2 className="h-3 w-3 animate-spin" />
+                {progress[model.id]
+                  ? formatProgress(progress[model.id])
+                  : "下载中..."}
+              </span>
+            ) : model.downloaded ? (
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => handleDelete(model.id)}
+              >
+                <Trash2 className="h-4 w-4 mr-1" />
+                删除
+              </Button>
+            ) : (
+              <Button
+                variant="outline"
+                size="sm"
+                onClick={() => handleDownload(model.id)}
+              >
+                下载
+              </Button>
+            )}
+          </div>
+        ))}
+      </div>
+
+      {error && <p className="text-xs text-destructive">{error}</p>}
+    </div>
+  );
+}
+
+export default LocalModelManager;
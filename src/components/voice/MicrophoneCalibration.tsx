@@ -0,0 +1,145 @@
+/**
+ * @file MicrophoneCalibration.tsx
+ * @description 麦克风电平校准向导 - 录制环境噪声和说话声音，计算建议增益与静音阈值
+ * @module components/voice/MicrophoneCalibration
+ */
+
+import { useState, useCallback } from "react";
+import { Gauge, Loader2, AlertCircle, RotateCcw } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import {
+  recordCalibrationSample,
+  saveMicrophoneCalibration,
+  clearMicrophoneCalibration,
+  CALIBRATION_AMBIENT_MS,
+  type DeviceCalibration,
+} from "@/lib/api/asrProvider";
+
+/** 说话录音时长（毫秒） */
+const SPEECH_DURATION_MS = 4000;
+/** 信噪比低于此值时提示环境嘈杂 */
+const MIN_SNR_DB = 10;
+
+type CalibrationStep = "idle" | "ambient" | "speech" | "saving";
+
+interface MicrophoneCalibrationProps {
+  /** 当前选择的设备 ID */
+  deviceId?: string;
+  /** 该设备已保存的校准结果 */
+  calibration?: DeviceCalibration;
+  /** 校准结果变化回调（清除时为 undefined） */
+  onCalibrationChange: (calibration: DeviceCalibration | undefined) => void;
+  /** 是否禁用 */
+  disabled?: boolean;
+}
+
+export function MicrophoneCalibration({
+  deviceId,
+  calibration,
+  onCalibrationChange,
+  disabled = false,
+}: MicrophoneCalibrationProps) {
+  const [step, setStep] = useState<CalibrationStep>("idle");
+  const [error, setError] = useState<string | null>(null);
+  const running = step !== "idle";
+
+  const runCalibration = useCallback(async () => {
+    setError(null);
+    try {
+      setStep("ambient");
+      const ambient = await recordCalibrationSample(
+        deviceId,
+        CALIBRATION_AMBIENT_MS,
+      );
+
+      setStep("speech");
+      const speech = await recordCalibrationSample(deviceId, SPEECH_DURATION_MS);
+
+      setStep("saving");
+      const result = await saveMicrophoneCalibration(deviceId, ambient, speech);
+      onCalibrationChange(result);
+    } catch (err: any) {
+      setError(err?.message || String(err) || "校准失败");
+    } finally {
+      setStep("idle");
+    }
+  }, [deviceId, onCalibrationChange]);
+
+  const resetCalibration = useCallback(async () => {
+    setError(null);
+    try {
+      await clearMicrophoneCalibration(deviceId);
+      onCalibrationChange(undefined);
+    } catch (err: any) {
+      setError(err?.message || String(err) || "清除校准失败");
+    }
+  }, [deviceId, onCalibrationChange]);
+
+  const snr = calibration
+    ? calibration.speech_level_dbfs - calibration.noise_floor_dbfs
+    : 0;
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2">
+        <Button
+          variant="outline"
+          size="sm"
+          onClick={runCalibration}
+          disabled={disabled || running}
+        >
+          {running ? (
+            <Loader2 className="h-4 w-4 mr-2 animate-spin" />
+          ) : (
+            <Gauge className="h-4 w-4 mr-2" />
+          )}
+          {calibration ? "重新校准" : "电平校准"}
+        </Button>
+        {calibration && !running && (
+          <Button
+            variant="ghost"
+            size="sm"
+            onClick={resetCalibration}
+            disabled={disabled}
+            title="清除校准结果"
+          >
+            <RotateCcw className="h-4 w-4" />
+          </Button>
+        )}
+        <span className="text-xs text-muted-foreground">
+          {step === "ambient" && "请保持安静，正在录制环境噪声（2 秒）..."}
+          {step === "speech" && "请用平常的音量说一句话（4 秒）..."}
+          {step === "saving" && "正在计算..."}
+        </span>
+      </div>
+
+      {error && (
+        <div className="flex items-center gap-2 text-sm text-destructive">
+          <AlertCircle className="h-4 w-4" />
+          <span>{error}</span>
+        </div>
+      )}
+
+      {calibration && !running && (
+        <div className="flex flex-wrap items-center gap-x-4 gap-y-1 text-xs text-muted-foreground">
+          <span>
+            增益 {calibration.gain_db > 0 ? "+" : ""}
+            {calibration.gain_db.toFixed(1)} dB
+          </span>
+          <span>
+            静音阈值 {calibration.silence_threshold_dbfs.toFixed(1)} dBFS
+          </span>
+          <span>噪声底 {calibration.noise_floor_dbfs.toFixed(1)} dBFS</span>
+          {snr < MIN_SNR_DB && (
+            <span className="flex items-center gap-1 text-amber-500">
+              <AlertCircle className="h-3 w-3" />
+              环境噪声较大，建议在安静环境中重新校准
+            </span>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
+
+export default MicrophoneCalibration;
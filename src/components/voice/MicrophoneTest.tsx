@@ -1,11 +1,19 @@
 /**
  * @file MicrophoneTest.tsx
- * @description 麦克风测试组件 - 设备选择和音量测试
+ * @description 麦克风测试组件 - 设备选择、音量测试和录音回放
  * @module components/voice/MicrophoneTest
  */
 
 import { useState, useEffect, useCallback, useRef } from "react";
-import { Mic, MicOff, RefreshCw, Check, AlertCircle } from "lucide-react";
+import {
+  Mic,
+  MicOff,
+  RefreshCw,
+  Check,
+  AlertCircle,
+  Play,
+  Loader2,
+} from "lucide-react";
 import { cn } from "@/lib/utils";
 import { Button } from "@/components/ui/button";
 import {
@@ -20,10 +28,23 @@ import {
   startRecording,
   cancelRecording,
   getRecordingStatus,
+  testMicrophone,
+  playMicrophoneTest,
   type AudioDeviceInfo,
+  type MicrophoneTestResult,
+  type RecordingStatus,
 } from "@/lib/api/asrProvider";
 import { VolumeWaveform } from "./VolumeWaveform";
 
+/** 电平详情 */
+/** 录音回放的时长（秒） */
+const PLAYBACK_TEST_SECONDS = 3;
+
+type LevelInfo = Pick<
+  RecordingStatus,
+  "rms_dbfs" | "peak_dbfs" | "clipping" | "clipped_samples"
+>;
+
 interface MicrophoneTestProps {
   /** 当前选择的设备 ID */
   selectedDeviceId?: string;
@@ -42,7 +63,10 @@ export function MicrophoneTest({
   const [loading, setLoading] = useState(false);
   const [testing, setTesting] = useState(false);
   const [volume, setVolume] = useState(0);
+  const [level, setLevel] = useState<LevelInfo | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [recordingClip, setRecordingClip] = useState(false);
+  const [clip, setClip] = useState<MicrophoneTestResult | null>(null);
   const pollIntervalRef = useRef<number | null>(null);
   // 使用 ref 跟踪录音状态，避免闭包捕获过时的 state
   const isTestingRef = useRef(false);
@@ -73,6 +97,7 @@ export function MicrophoneTest({
     isTestingRef.current = true;
     setError(null);
     setVolume(0);
+    setLevel(null);
 
     console.log("[麦克风测试] 开始测试，设备ID:", selectedDeviceId);
 
@@ -86,6 +111,12 @@ export function MicrophoneTest({
           const status = await getRecordingStatus();
           console.log("[麦克风测试] 状态:", status);
           setVolume(status.volume);
+          setLevel({
+            rms_dbfs: status.rms_dbfs,
+            peak_dbfs: status.peak_dbfs,
+            clipping: status.clipping,
+            clipped_samples: status.clipped_samples,
+          });
         } catch (err) {
           console.error("[麦克风测试] 获取状态失败:", err);
         }
@@ -123,8 +154,32 @@ export function MicrophoneTest({
     }
 
     setVolume(0);
+    setLevel(null);
   }, [testing]);
 
+  // 录一段音频并回放
+  const recordAndPlay = useCallback(async () => {
+    if (testing || recordingClip) return;
+    setRecordingClip(true);
+    setError(null);
+    setClip(null);
+    try {
+      const result = await testMicrophone(
+        PLAYBACK_TEST_SECONDS,
+        selectedDeviceId,
+      );
+      setClip(result);
+      if (!result.silent) {
+        playMicrophoneTest(result);
+      }
+    } catch (err: any) {
+      console.error("[麦克风测试] 录音回放失败:", err);
+      setError(typeof err === "string" ? err : err?.message || "录音失败");
+    } finally {
+      setRecordingClip(false);
+    }
+  }, [testing, recordingClip, selectedDeviceId]);
+
   // 组件卸载时清理
   useEffect(() => {
     return () => {
@@ -157,7 +212,7 @@ export function MicrophoneTest({
             onValueChange={(value) =>
               onDeviceChange(value === "__default__" ? undefined : value)
             }
-            disabled={disabled || loading || testing}
+            disabled={disabled || loading || testing || recordingClip}
           >
             <SelectTrigger className="w-full">
               <SelectValue placeholder="选择麦克风设备" />
@@ -211,7 +266,7 @@ export function MicrophoneTest({
           variant={testing ? "destructive" : "default"}
           size="sm"
           onClick={testing ? stopTest : startTest}
-          disabled={disabled || loading}
+          disabled={disabled || loading || recordingClip}
         >
           {testing ? (
             <>
@@ -230,14 +285,87 @@ export function MicrophoneTest({
           <VolumeWaveform volume={volume} isRecording={testing} barCount={12} />
         </div>
 
-        <div className="text-sm text-muted-foreground w-12 text-right">
-          {testing ? `${volume}%` : "--"}
+        <div className="text-sm text-muted-foreground w-20 text-right tabular-nums">
+          {testing && level ? `${level.rms_dbfs.toFixed(0)} dBFS` : "--"}
+        </div>
+      </div>
+
+      {/* 电平详情 */}
+      {testing && level && (
+        <div className="flex items-center gap-4 text-xs text-muted-foreground">
+          <span>峰值 {level.peak_dbfs.toFixed(1)} dBFS</span>
+          {level.clipped_samples > 0 ? (
+            <span
+              className={cn(
+                "flex items-center gap-1",
+                level.clipping ? "text-destructive" : "text-amber-500",
+              )}
+            >
+              <AlertCircle className="h-3 w-3" />
+              出现削波，建议调低麦克风增益
+            </span>
+          ) : (
+            <span>说话时峰值保持在 -12 ~ -3 dBFS 之间为宜</span>
+          )}
         </div>
+      )}
+
+      {/* 录音回放 */}
+      <div className="flex items-center gap-3">
+        <Button
+          variant="outline"
+          size="sm"
+          onClick={recordAndPlay}
+          disabled={disabled || loading || testing || recordingClip}
+        >
+          {recordingClip ? (
+            <Loader2 className="h-4 w-4 mr-2 animate-spin" />
+          ) : (
+            <Play className="h-4 w-4 mr-2" />
+          )}
+          {recordingClip
+            ? "录音中..."
+            : `录音 ${PLAYBACK_TEST_SECONDS} 秒并回放`}
+        </Button>
+        {clip && !recordingClip && (
+          <Button
+            variant="ghost"
+            size="sm"
+            onClick={() => playMicrophoneTest(clip)}
+          >
+            再听一遍
+          </Button>
+        )}
       </div>
 
+      {/* 回放录音的电平统计 */}
+      {clip && !recordingClip && (
+        <div className="flex flex-wrap items-center gap-x-4 gap-y-1 text-xs text-muted-foreground">
+          {clip.silent ? (
+            <span className="flex items-center gap-1 text-destructive">
+              <AlertCircle className="h-3 w-3" />
+              几乎没有录到声音，请检查麦克风是否静音或缺少权限
+            </span>
+          ) : (
+            <>
+              <span>环境噪声 {clip.stats.median_dbfs.toFixed(1)} dBFS</span>
+              <span>说话电平 {clip.stats.loud_dbfs.toFixed(1)} dBFS</span>
+              <span>峰值 {clip.stats.peak_dbfs.toFixed(1)} dBFS</span>
+              {clip.clipped_samples > 0 && (
+                <span className="flex items-center gap-1 text-amber-500">
+                  <AlertCircle className="h-3 w-3" />
+                  出现削波
+                </span>
+              )}
+            </>
+          )}
+        </div>
+      )}
+
       {/* 提示信息 */}
       <p className="text-xs text-muted-foreground">
-        点击"测试麦克风"按钮，对着麦克风说话，观察音量波形是否有变化。
+        点击"测试麦克风"按钮，对着麦克风说话，观察音量波形是否有变化；
+        也可以录一小段回放，确认实际录到的声音。
       </p>
     </div>
   );
@@ -0,0 +1,95 @@
+/**
+ * @file SpeakerDiarization.tsx
+ * @description 本地说话人区分 - 转写录音文件时按声纹给分段标注说话人（实验性）
+ * @module components/voice/SpeakerDiarization
+ */
+
+import { Users } from "lucide-react";
+import { cn } from "@/lib/utils";
+import type { DiarizationConfig } from "@/lib/api/asrProvider";
+
+/** 本地说话人区分的默认配置 */
+export const DEFAULT_DIARIZATION: DiarizationConfig = {
+  enabled: false,
+  max_speakers: 4,
+  threshold: 0.7,
+};
+
+interface SpeakerDiarizationProps {
+  config: DiarizationConfig;
+  onChange: (patch: Partial<DiarizationConfig>) => Promise<void>;
+  disabled?: boolean;
+}
+
+export function SpeakerDiarization({
+  config,
+  onChange,
+  disabled = false,
+}: SpeakerDiarizationProps) {
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-2">
+          <Users className="h-4 w-4 text-muted-foreground" />
+          <div>
+            <span className="text-sm">说话人区分（实验性）</span>
+            <p className="text-xs text-muted-foreground">
+              转写会议录音时，识别服务未区分说话人则在本地按声纹区分
+            </p>
+          </div>
+        </div>
+        <label className="relative inline-flex items-center cursor-pointer">
+          <input
+            type="checkbox"
+            checked={config.enabled}
+            onChange={() => onChange({ enabled: !config.enabled })}
+            disabled={disabled}
+            className="sr-only peer"
+          />
+          <div
+            className={cn(
+              "w-9 h-5 rounded-full transition-colors",
+              "bg-muted peer-checked:bg-primary",
+              "after:content-[''] after:absolute after:top-0.5 after:left-0.5",
+              "after:bg-white after:rounded-full after:h-4 after:w-4",
+              "after:transition-transform peer-checked:after:translate-x-4",
+              disabled && "opacity-50 cursor-not-allowed",
+            )}
+          />
+        </label>
+      </div>
+
+      {config.enabled && (
+        <div className="flex items-center gap-4 pl-6 text-xs text-muted-foreground">
+          <label className="flex items-center gap-2">
+            最多说话人
+            <input
+              type="number"
+              min={1}
+              max={10}
+              value={config.max_speakers}
+              onChange={(e) =>
+                onChange({ max_speakers: Math.max(1, Number(e.target.value)) })
+              }
+              disabled={disabled}
+              className="w-16 rounded-md border bg-background px-2 py-1 text-sm"
+            />
+          </label>
+          <label className="flex items-center gap-2">
+            相似度阈值
+            <input
+              type="number"
+              min={0}
+              max={1}
+              step={0.05}
+              value={config.threshold}
+              onChange={(e) => onChange({ threshold: Number(e.target.value) })}
+              disabled={disabled}
+              className="w-16 rounded-md border bg-background px-2 py-1 text-sm"
+            />
+          </label>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,158 @@
+/**
+ * @file SpokenPunctuation.tsx
+ * @description 口述标点 - 编辑口令表，把识别结果中的「逗号」「换行」等口令替换为对应字符
+ * @module components/voice/SpokenPunctuation
+ */
+
+import { useState, useEffect } from "react";
+import { Pilcrow, Plus, Trash2 } from "lucide-react";
+import { cn } from "@/lib/utils";
+import type {
+  SpokenPunctuationConfig,
+  SpokenPunctuationMapping,
+} from "@/lib/api/asrProvider";
+
+/** 口述标点的默认配置（口令表由后端提供默认值） */
+export const DEFAULT_SPOKEN_PUNCTUATION: SpokenPunctuationConfig = {
+  enabled: false,
+  mappings: [],
+};
+
+interface SpokenPunctuationProps {
+  config: SpokenPunctuationConfig;
+  onChange: (patch: Partial<SpokenPunctuationConfig>) => Promise<void>;
+  disabled?: boolean;
+}
+
+/** 换行在输入框中显示为 \n */
+function displaySymbol(symbol: string): string {
+  return symbol.replace(/\n/g, "\\n");
+}
+
+function parseSymbol(text: string): string {
+  return text.replace(/\\n/g, "\n");
+}
+
+/** 去掉口令或字符为空的行 */
+function cleanMappings(
+  mappings: SpokenPunctuationMapping[],
+): SpokenPunctuationMapping[] {
+  return mappings
+    .map((m) => ({ spoken: m.spoken.trim(), symbol: m.symbol }))
+    .filter((m) => m.spoken && m.symbol);
+}
+
+export function SpokenPunctuation({
+  config,
+  onChange,
+  disabled = false,
+}: SpokenPunctuationProps) {
+  const [rows, setRows] = useState(config.mappings ?? []);
+
+  useEffect(() => {
+    setRows(config.mappings ?? []);
+  }, [config.mappings]);
+
+  const commit = (next: SpokenPunctuationMapping[]) => {
+    const mappings = cleanMappings(next);
+    if (JSON.stringify(mappings) !== JSON.stringify(config.mappings ?? [])) {
+      onChange({ mappings });
+    }
+  };
+
+  const updateRow = (
+    index: number,
+    patch: Partial<SpokenPunctuationMapping>,
+  ) => {
+    setRows(rows.map((row, i) => (i === index ? { ...row, ...patch } : row)));
+  };
+
+  const removeRow = (index: number) => {
+    const next = rows.filter((_, i) => i !== index);
+    setRows(next);
+    commit(next);
+  };
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-2">
+          <Pilcrow className="h-4 w-4 text-muted-foreground" />
+          <div>
+            <span className="text-sm">口述标点</span>
+            <p className="text-xs text-muted-foreground">
+              把识别结果中说出的「逗号」「换行」「new line」等口令替换为对应字符
+            </p>
+          </div>
+        </div>
+        <label className="relative inline-flex items-center cursor-pointer">
+          <input
+            type="checkbox"
+            checked={config.enabled}
+            onChange={() => onChange({ enabled: !config.enabled })}
+            disabled={disabled}
+            className="sr-only peer"
+          />
+          <div
+            className={cn(
+              "w-9 h-5 rounded-full transition-colors",
+              "bg-muted peer-checked:bg-primary",
+              "after:content-[''] after:absolute after:top-0.5 after:left-0.5",
+              "after:bg-white after:rounded-full after:h-4 after:w-4",
+              "after:transition-transform peer-checked:after:translate-x-4",
+              disabled && "opacity-50 cursor-not-allowed",
+            )}
+          />
+        </label>
+      </div>
+
+      {config.enabled && (
+        <div className="space-y-1 pl-6">
+          {rows.map((row, index) => (
+            <div key={index} className="flex items-center gap-2">
+              <input
+                value={row.spoken}
+                onChange={(e) => updateRow(index, { spoken: e.target.value })}
+                onBlur={() => commit(rows)}
+                disabled={disabled}
+                placeholder="口令"
+                className="flex-1 rounded-md border bg-background px-2 py-1 text-sm focus:outline-none focus:ring-1 focus:ring-primary"
+              />
+              <input
+                value={displaySymbol(row.symbol)}
+                onChange={(e) =>
+                  updateRow(index, { symbol: parseSymbol(e.target.value) })
+                }
+                onBlur={() => commit(rows)}
+                disabled={disabled}
+                placeholder="字符"
+                className="w-20 rounded-md border bg-background px-2 py-1 text-sm focus:outline-none focus:ring-1 focus:ring-primary"
+              />
+              <button
+                type="button"
+                onClick={() => removeRow(index)}
+                disabled={disabled}
+                className="p-1 text-muted-foreground hover:text-destructive disabled:opacity-50"
+                title="删除口令"
+              >
+                <Trash2 className="h-3.5 w-3.5" />
+              </button>
+            </div>
+          ))}
+          <button
+            type="button"
+            onClick={() => setRows([...rows, { spoken: "", symbol: "" }])}
+            disabled={disabled}
+            className="flex items-center gap-1 text-xs text-primary hover:underline disabled:opacity-50"
+          >
+            <Plus className="h-3 w-3" />
+            添加口令
+          </button>
+          <p className="text-xs text-muted-foreground">
+            字符中的 \n 表示换行；英文口令不区分大小写
+          </p>
+        </div>
+      )}
+    </div>
+  );
+}
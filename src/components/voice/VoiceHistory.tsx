@@ -0,0 +1,340 @@
+/**
+ * @file VoiceHistory.tsx
+ * @description 听写历史 - 查看最近的听写，换用其他指令重新润色而无需重新录音
+ * @module components/voice/VoiceHistory
+ */
+
+import { useState, useEffect, useCallback, useMemo } from "react";
+import {
+  History,
+  RefreshCw,
+  Trash2,
+  Copy,
+  Sparkles,
+  Loader2,
+  BookmarkPlus,
+} from "lucide-react";
+import { Button } from "@/components/ui/button";
+import {
+  getVoiceHistory,
+  repolishVoiceHistory,
+  deleteVoiceHistoryEntry,
+  clearVoiceHistory,
+  saveVoiceAsPrompt,
+  type VoiceHistoryEntry,
+  type VoiceInstruction,
+} from "@/lib/api/asrProvider";
+import type { AppType } from "@/lib/api/prompts";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+
+/** 最多展示的听写数 */
+const MAX_VISIBLE = 10;
+
+/** 可存入的提示词库 */
+const PROMPT_APPS: [AppType, string][] = [
+  ["claude", "Claude"],
+  ["codex", "Codex"],
+  ["gemini", "Gemini"],
+];
+
+interface VoiceHistoryProps {
+  /** 可用于重新润色的指令 */
+  instructions: VoiceInstruction[];
+}
+
+/** 一条听写及其重新润色生成的版本 */
+interface HistoryGroup {
+  root: VoiceHistoryEntry;
+  variants: VoiceHistoryEntry[];
+}
+
+function groupEntries(entries: VoiceHistoryEntry[]): HistoryGroup[] {
+  const groups = entries
+    .filter((e) => !e.parent_id)
+    .map((root) => ({ root, variants: [] as VoiceHistoryEntry[] }));
+  const byId = new Map(groups.map((g) => [g.root.id, g]));
+  for (const entry of entries) {
+    if (entry.parent_id) byId.get(entry.parent_id)?.variants.push(entry);
+  }
+  // 版本按生成时间先后展示
+  for (const group of groups) {
+    group.variants.sort((a, b) => a.created_at - b.created_at);
+  }
+  return groups;
+}
+
+function VersionRow({
+  entry,
+  label,
+  promptApp,
+  onDelete,
+}: {
+  entry: VoiceHistoryEntry;
+  label: string;
+  promptApp: AppType;
+  onDelete?: () => void;
+}) {
+  const [saved, setSaved] = useState(false);
+
+  const handleSavePrompt = async () => {
+    try {
+      await saveVoiceAsPrompt(promptApp, { text: entry.text });
+      setSaved(true);
+    } catch (err) {
+      console.error("[听写历史] 存为提示词失败:", err);
+    }
+  };
+
+  return (
+    <div className="group flex items-start gap-2 text-xs">
+      <span className="shrink-0 text-muted-foreground">{label}</span>
+      <p className="flex-1 whitespace-pre-wrap break-words">{entry.text}</p>
+      <div className="flex shrink-0 opacity-0 group-hover:opacity-100">
+        <button
+          type="button"
+          onClick={() => navigator.clipboard.writeText(entry.text)}
+          className="p-0.5 text-muted-foreground hover:text-foreground"
+          title="复制"
+        >
+          <Copy className="h-3 w-3" />
+        </button>
+        <button
+          type="button"
+          onClick={handleSavePrompt}
+          disabled={saved}
+          className="p-0.5 text-muted-foreground hover:text-foreground disabled:text-primary"
+          title={saved ? "已存为提示词" : "存为提示词"}
+        >
+          <BookmarkPlus className="h-3 w-3" />
+        </button>
+        {onDelete && (
+          <button
+            type="button"
+            onClick={onDelete}
+            className="p-0.5 text-muted-foreground hover:text-destructive"
+            title="删除此版本"
+          >
+            <Trash2 className="h-3 w-3" />
+          </button>
+        )}
+      </div>
+    </div>
+  );
+}
+
+function HistoryItem({
+  group,
+  instructions,
+  promptApp,
+  onChanged,
+}: {
+  group: HistoryGroup;
+  instructions: VoiceInstruction[];
+  promptApp: AppType;
+  onChanged: () => void;
+}) {
+  const { root, variants } = group;
+  const [instructionId, setInstructionId] = useState(
+    instructions.find((i) => i.id !== root.instruction_id)?.id ?? "",
+  );
+  const [polishing, setPolishing] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const instructionName = (id: string | null) =>
+    instructions.find((i) => i.id === id)?.name ?? id ?? "原始";
+
+  const handleRepolish = async () => {
+    if (!instructionId) return;
+    setPolishing(true);
+    setError(null);
+    try {
+      await repolishVoiceHistory(root.id, instructionId);
+      onChanged();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setPolishing(false);
+    }
+  };
+
+  const handleDelete = async (id: string) => {
+    try {
+      await deleteVoiceHistoryEntry(id);
+      onChanged();
+    } catch (err) {
+      console.error("[听写历史] 删除失败:", err);
+    }
+  };
+
+  return (
+    <div className="space-y-1.5 rounded-md border px-3 py-2">
+      <div className="flex items-center justify-between text-xs">
+        <span>
+          {new Date(root.created_at).toLocaleString()}
+          {root.provider && (
+            <span className="ml-2 text-muted-foreground">{root.provider}</span>
+          )}
+        </span>
+        <button
+          type="button"
+          onClick={() => handleDelete(root.id)}
+          className="p-0.5 text-muted-foreground hover:text-destructive"
+          title="删除此听写"
+        >
+          <Trash2 className="h-3.5 w-3.5" />
+        </button>
+      </div>
+
+      <VersionRow
+        entry={{ ...root, text: root.raw_text }}
+        label="原文"
+        promptApp={promptApp}
+      />
+      {root.instruction_id && root.text !== root.raw_text && (
+        <VersionRow
+          entry={root}
+          label={instructionName(root.instruction_id)}
+          promptApp={promptApp}
+        />
+      )}
+      {variants.map((variant) => (
+        <VersionRow
+          key={variant.id}
+          entry={variant}
+          label={instructionName(variant.instruction_id)}
+          promptApp={promptApp}
+          onDelete={() => handleDelete(variant.id)}
+        />
+      ))}
+
+      <div className="flex items-center gap-2 pt-1">
+        <Select
+          value={instructionId}
+          onValueChange={setInstructionId}
+          disabled={polishing}
+        >
+          <SelectTrigger className="h-7 flex-1 text-xs">
+            <SelectValue placeholder="选择指令" />
+          </SelectTrigger>
+          <SelectContent>
+            {instructions.map((instruction) => (
+              <SelectItem key={instruction.id} value={instruction.id}>
+                {instruction.name}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+        <Button
+          variant="outline"
+          size="sm"
+          className="h-7"
+          onClick={handleRepolish}
+          disabled={polishing || !instructionId}
+        >
+          {polishing ? (
+            <Loader2 className="h-3.5 w-3.5 mr-1 animate-spin" />
+          ) : (
+            <Sparkles className="h-3.5 w-3.5 mr-1" />
+          )}
+          重新润色
+        </Button>
+      </div>
+      {error && <p className="text-xs text-destructive">{error}</p>}
+    </div>
+  );
+}
+
+export function VoiceHistory({ instructions }: VoiceHistoryProps) {
+  const [entries, setEntries] = useState<VoiceHistoryEntry[]>([]);
+  const [promptApp, setPromptApp] = useState<AppType>("claude");
+
+  const refresh = useCallback(async () => {
+    try {
+      setEntries(await getVoiceHistory(MAX_VISIBLE));
+    } catch (err) {
+      console.error("[听写历史] 加载失败:", err);
+    }
+  }, []);
+
+  const handleClear = useCallback(async () => {
+    try {
+      await clearVoiceHistory();
+      await refresh();
+    } catch (err) {
+      console.error("[听写历史] 清空失败:", err);
+    }
+  }, [refresh]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  const groups = useMemo(() => groupEntries(entries), [entries]);
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-2">
+          <History className="h-4 w-4 text-muted-foreground" />
+          <div>
+            <span className="text-sm">听写历史</span>
+            <p className="text-xs text-muted-foreground">
+              换用其他指令重新润色，无需重新录音；常用的听写可存为提示词
+            </p>
+          </div>
+        </div>
+        <div className="flex items-center gap-1">
+          <Select
+            value={promptApp}
+            onValueChange={(value) => setPromptApp(value as AppType)}
+          >
+            <SelectTrigger className="h-7 w-24 text-xs" title="存为提示词的目标">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {PROMPT_APPS.map(([app, label]) => (
+                <SelectItem key={app} value={app}>
+                  {label}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+          <Button variant="ghost" size="sm" onClick={refresh} title="刷新">
+            <RefreshCw className="h-4 w-4" />
+          </Button>
+          <Button
+            variant="ghost"
+            size="sm"
+            onClick={handleClear}
+            disabled={groups.length === 0}
+            title="清空历史"
+          >
+            <Trash2 className="h-4 w-4" />
+          </Button>
+        </div>
+      </div>
+
+      {groups.length === 0 ? (
+        <p className="text-xs text-muted-foreground">暂无记录</p>
+      ) : (
+        groups.map((group) => (
+          <HistoryItem
+            key={group.root.id}
+            group={group}
+            instructions={instructions}
+            promptApp={promptApp}
+            onChanged={refresh}
+          />
+        ))
+      )}
+    </div>
+  );
+}
+
+export default VoiceHistory;
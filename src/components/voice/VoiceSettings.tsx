@@ -12,12 +12,53 @@ import {
   Sparkles,
   Volume2,
   Globe,
+  MicOff,
+  Type,
+  ClipboardCheck,
+  ClipboardList,
+  Pause,
+  History,
+  Coins,
+  CircleDot,
+  Zap,
 } from "lucide-react";
 import { cn } from "@/lib/utils";
 import { ShortcutSettings } from "@/components/smart-input/ShortcutSettings";
-import { VoiceInputConfig } from "@/lib/api/asrProvider";
+import {
+  VoiceInputConfig,
+  NoSpeechConfig,
+  VoiceHistoryConfig,
+  VoiceHistorySyncConfig,
+  VoiceWindowConfig,
+  PunctuationMode,
+  PolishCostLimit,
+  PolishCostAction,
+  InstructionAbTest as InstructionAbTestConfig,
+  HomophoneCorrection as HomophoneCorrectionConfig,
+  SpokenPunctuationConfig,
+  ClipboardHistoryConfig,
+  RecognitionStrategy,
+  WhisperTask,
+  DiarizationConfig,
+  DeviceCalibration,
+  DEFAULT_CALIBRATION_DEVICE,
+} from "@/lib/api/asrProvider";
 import { MicrophoneTest } from "./MicrophoneTest";
+import { MicrophoneCalibration } from "./MicrophoneCalibration";
+import { VoiceTimeline } from "./VoiceTimeline";
+import { ComputeDiagnostics } from "./ComputeDiagnostics";
+import { LocalModelManager } from "./LocalModelManager";
+import { VoiceHistory } from "./VoiceHistory";
 import { PolishModelSelector } from "./PolishModelSelector";
+import { InstructionAbTest, DEFAULT_AB_TEST } from "./InstructionAbTest";
+import { HomophoneCorrection, DEFAULT_HOMOPHONE } from "./HomophoneCorrection";
+import {
+  SpokenPunctuation,
+  DEFAULT_SPOKEN_PUNCTUATION,
+} from "./SpokenPunctuation";
+import { VoiceVocabulary } from "./VoiceVocabulary";
+import { SpeakerDiarization, DEFAULT_DIARIZATION } from "./SpeakerDiarization";
+import { WorkspaceVoiceDefaults } from "./WorkspaceVoiceDefaults";
 import {
   Select,
   SelectContent,
@@ -26,6 +67,80 @@ import {
   SelectValue,
 } from "@/components/ui/select";
 
+/** 可按语言选择指令的语言 */
+const ROUTED_LANGUAGES: [string, string][] = [
+  ["zh", "中文"],
+  ["en", "英文"],
+  ["ja", "日文"],
+  ["ko", "韩文"],
+];
+
+/** 使用默认指令（Select 不支持空字符串作为选项值） */
+const DEFAULT_INSTRUCTION_VALUE = "__default__";
+
+/** 标点补全方式选项 */
+const PUNCTUATION_MODES: [PunctuationMode, string][] = [
+  ["off", "不处理"],
+  ["rules", "按规则补全"],
+  ["llm", "润色模型补全"],
+];
+
+/** 剪贴板历史的默认配置 */
+const DEFAULT_CLIPBOARD_HISTORY: ClipboardHistoryConfig = {
+  enabled: false,
+  max_entries: 20,
+  max_entry_chars: 4000,
+};
+
+/** 润色费用上限的默认配置 */
+const DEFAULT_COST_LIMIT: PolishCostLimit = {
+  enabled: false,
+  max_cost: 0.01,
+  action: "raw",
+};
+
+/** 单次润色费用上限选项（模型定价的货币单位） */
+const MAX_COST_OPTIONS = [0.001, 0.005, 0.01, 0.05, 0.1];
+
+/** 超出费用上限时的处理方式选项 */
+const COST_ACTIONS: [PolishCostAction, string][] = [
+  ["raw", "输出原文"],
+  ["confirm", "确认后润色"],
+];
+
+/** 识别策略选项 */
+const RECOGNITION_STRATEGIES: [RecognitionStrategy, string][] = [
+  ["fallback", "依次回退"],
+  ["race", "同时竞速"],
+];
+
+const WHISPER_TASKS: [WhisperTask, string][] = [
+  ["transcribe", "转写原文"],
+  ["translate", "译为英文"],
+];
+
+/** 开头静音自动取消的默认配置 */
+const DEFAULT_NO_SPEECH: NoSpeechConfig = { enabled: true, timeout_secs: 8 };
+
+/** 听写历史的默认配置 */
+const DEFAULT_HISTORY: VoiceHistoryConfig = { enabled: true, max_entries: 200 };
+
+const DEFAULT_HISTORY_SYNC: VoiceHistorySyncConfig = {
+  enabled: false,
+  folder: "dictations",
+  include_audio: false,
+};
+
+/** 悬浮窗口的默认配置 */
+const DEFAULT_WINDOW: VoiceWindowConfig = {
+  remember_position: true,
+  follow_active_monitor: false,
+  recording_indicator: false,
+};
+
+/** 可选的静音等待时长（秒） */
+const NO_SPEECH_TIMEOUTS = [5, 8, 15, 30];
+
 interface VoiceSettingsProps {
   config: VoiceInputConfig;
   onConfigChange: (config: VoiceInputConfig) => Promise<void>;
@@ -41,6 +156,11 @@ export function VoiceSettings({
 }: VoiceSettingsProps) {
   const [saving, setSaving] = useState(false);
   const isMacOS = navigator.userAgent.includes("Mac");
+  const noSpeech = config.no_speech ?? DEFAULT_NO_SPEECH;
+  const windowConfig = config.window ?? DEFAULT_WINDOW;
+  const costLimit = config.processor.cost_limit ?? DEFAULT_COST_LIMIT;
+  const historySync =
+    (config.history ?? DEFAULT_HISTORY).workspace_sync ?? DEFAULT_HISTORY_SYNC;
 
   // 切换功能开关
   const handleToggle = useCallback(async () => {
@@ -90,6 +210,34 @@ export function VoiceSettings({
     }
   }, [config, onConfigChange, disabled, saving]);
 
+  // 更新某种语言使用的指令
+  const handleLanguageInstructionChange = useCallback(
+    async (language: string, instructionId: string) => {
+      if (disabled || saving) return;
+      setSaving(true);
+      try {
+        const languageInstructions = {
+          ...config.processor.language_instructions,
+        };
+        if (instructionId === DEFAULT_INSTRUCTION_VALUE) {
+          delete languageInstructions[language];
+        } else {
+          languageInstructions[language] = instructionId;
+        }
+        await onConfigChange({
+          ...config,
+          processor: {
+            ...config.processor,
+            language_instructions: languageInstructions,
+          },
+        });
+      } finally {
+        setSaving(false);
+      }
+    },
+    [config, onConfigChange, disabled, saving],
+  );
+
   // 切换音效
   const handleToggleSound = useCallback(async () => {
     if (disabled || saving) return;
@@ -104,6 +252,292 @@ export function VoiceSettings({
     }
   }, [config, onConfigChange, disabled, saving]);
 
+  // 切换剪贴板历史
+  const handleToggleClipboardHistory = useCallback(async () => {
+    if (disabled || saving) return;
+    const current = config.clipboard_history ?? DEFAULT_CLIPBOARD_HISTORY;
+    setSaving(true);
+    try {
+      await onConfigChange({
+        ...config,
+        clipboard_history: { ...current, enabled: !current.enabled },
+      });
+    } finally {
+      setSaving(false);
+    }
+  }, [config, onConfigChange, disabled, saving]);
+
+  // 更新标点补全方式
+  const handlePunctuationChange = useCallback(
+    async (mode: PunctuationMode) => {
+      if (disabled || saving) return;
+      setSaving(true);
+      try {
+        await onConfigChange({
+          ...config,
+          processor: {
+            ...config.processor,
+            punctuation: mode,
+          },
+        });
+      } finally {
+        setSaving(false);
+      }
+    },
+    [config, onConfigChange, disabled, saving],
+  );
+
+  // 更新识别策略
+  const handleStrategyChange = useCallback(
+    async (strategy: RecognitionStrategy) => {
+      if (disabled || saving) return;
+      setSaving(true);
+      try {
+        await onConfigChange({
+          ...config,
+          recognition_strategy: strategy,
+        });
+      } finally {
+        setSaving(false);
+      }
+    },
+    [config, onConfigChange, disabled, saving],
+  );
+
+  // 更新本地 Whisper 识别任务
+  const handleWhisperTaskChange = useCallback(
+    async (task: WhisperTask) => {
+      if (disabled || saving) return;
+      setSaving(true);
+      try {
+        await onConfigChange({
+          ...config,
+          whisper_task: task,
+        });
+      } finally {
+        setSaving(false);
+      }
+    },
+    [config, onConfigChange, disabled, saving],
+  );
+
+  // 更新润色费用上限
+  const handleCostLimitChange = useCallback(
+    async (patch: Partial<PolishCostLimit>) => {
+      if (disabled || saving) return;
+      setSaving(true);
+      try {
+        await onConfigChange({
+          ...config,
+          processor: {
+            ...config.processor,
+            cost_limit: {
+              ...(config.processor.cost_limit ?? DEFAULT_COST_LIMIT),
+              ...patch,
+            },
+          },
+        });
+      } finally {
+        setSaving(false);
+      }
+    },
+    [config, onConfigChange, disabled, saving],
+  );
+
+  // 更新指令 A/B 测试配置
+  const handleAbTestChange = useCallback(
+    async (patch: Partial<InstructionAbTestConfig>) => {
+      if (disabled || saving) return;
+      setSaving(true);
+      try {
+        await onConfigChange({
+          ...config,
+          processor: {
+            ...config.processor,
+            ab_test: {
+              ...(config.processor.ab_test ?? DEFAULT_AB_TEST),
+              ...patch,
+            },
+          },
+        });
+      } finally {
+        setSaving(false);
+      }
+    },
+    [config, onConfigChange, disabled, saving],
+  );
+
+  // 更新同音词纠正配置
+  const handleHomophoneChange = useCallback(
+    async (patch: Partial<HomophoneCorrectionConfig>) => {
+      if (disabled || saving) return;
+      setSaving(true);
+      try {
+        await onConfigChange({
+          ...config,
+          processor: {
+            ...config.processor,
+            homophone: {
+              ...(config.processor.homophone ?? DEFAULT_HOMOPHONE),
+              ...patch,
+            },
+          },
+        });
+      } finally {
+        setSaving(false);
+      }
+    },
+    [config, onConfigChange, disabled, saving],
+  );
+
+  // 更新口述标点配置
+  const handleSpokenPunctuationChange = useCallback(
+    async (patch: Partial<SpokenPunctuationConfig>) => {
+      if (disabled || saving) return;
+      setSaving(true);
+      try {
+        await onConfigChange({
+          ...config,
+          spoken_punctuation: {
+            ...(config.spoken_punctuation ?? DEFAULT_SPOKEN_PUNCTUATION),
+            ...patch,
+          },
+        });
+      } finally {
+        setSaving(false);
+      }
+    },
+    [config, onConfigChange, disabled, saving],
+  );
+
+  // 更新本地说话人区分配置
+  const handleDiarizationChange = useCallback(
+    async (patch: Partial<DiarizationConfig>) => {
+      if (disabled || saving) return;
+      setSaving(true);
+      try {
+        await onConfigChange({
+          ...config,
+          diarization: {
+            ...(config.diarization ?? DEFAULT_DIARIZATION),
+            ...patch,
+          },
+        });
+      } finally {
+        setSaving(false);
+      }
+    },
+    [config, onConfigChange, disabled, saving],
+  );
+
+  // 更新开头静音自动取消配置
+  const handleNoSpeechChange = useCallback(
+    async (patch: Partial<NoSpeechConfig>) => {
+      if (disabled || saving) return;
+      setSaving(true);
+      try {
+        await onConfigChange({
+          ...config,
+          no_speech: {
+            ...(config.no_speech ?? DEFAULT_NO_SPEECH),
+            ...patch,
+          },
+        });
+      } finally {
+        setSaving(false);
+      }
+    },
+    [config, onConfigChange, disabled, saving],
+  );
+
+  // 切换输出前确认
+  const handleToggleReview = useCallback(async () => {
+    if (disabled || saving) return;
+    setSaving(true);
+    try {
+      await onConfigChange({
+        ...config,
+        output: {
+          ...config.output,
+          review_before_output: !config.output.review_before_output,
+        },
+      });
+    } finally {
+      setSaving(false);
+    }
+  }, [config, onConfigChange, disabled, saving]);
+
+  // 切换焦点切换时暂停输入
+  const handleTogglePauseOnFocusChange = useCallback(async () => {
+    if (disabled || saving) return;
+    setSaving(true);
+    try {
+      await onConfigChange({
+        ...config,
+        output: {
+          ...config.output,
+          pause_on_focus_change: config.output.pause_on_focus_change === false,
+        },
+      });
+    } finally {
+      setSaving(false);
+    }
+  }, [config, onConfigChange, disabled, saving]);
+
+  // 切换听写历史
+  const handleToggleHistory = useCallback(async () => {
+    if (disabled || saving) return;
+    setSaving(true);
+    try {
+      const history = config.history ?? DEFAULT_HISTORY;
+      await onConfigChange({
+        ...config,
+        history: { ...history, enabled: !history.enabled },
+      });
+    } finally {
+      setSaving(false);
+    }
+  }, [config, onConfigChange, disabled, saving]);
+
+  // 切换听写历史同步到工作区的开关
+  const handleToggleHistorySync = useCallback(
+    async (field: "enabled" | "include_audio") => {
+      if (disabled || saving) return;
+      setSaving(true);
+      try {
+        const history = config.history ?? DEFAULT_HISTORY;
+        const sync = history.workspace_sync ?? DEFAULT_HISTORY_SYNC;
+        await onConfigChange({
+          ...config,
+          history: {
+            ...history,
+            workspace_sync: { ...sync, [field]: !sync[field] },
+          },
+        });
+      } finally {
+        setSaving(false);
+      }
+    },
+    [config, onConfigChange, disabled, saving],
+  );
+
+  // 切换录音指示
+  const handleToggleRecordingIndicator = useCallback(async () => {
+    if (disabled || saving) return;
+    setSaving(true);
+    try {
+      await onConfigChange({
+        ...config,
+        window: {
+          ...windowConfig,
+          recording_indicator: !windowConfig.recording_indicator,
+        },
+      });
+    } finally {
+      setSaving(false);
+    }
+  }, [config, windowConfig, onConfigChange, disabled, saving]);
+
   // 更新润色模型
   const handlePolishModelChange = useCallback(
     async (modelId: string) => {
@@ -175,6 +609,21 @@ export function VoiceSettings({
     [config, onConfigChange, disabled, saving],
   );
 
+  // 校准结果已由后端保存，这里同步到本地配置，避免后续保存时覆盖
+  const handleCalibrationChange = useCallback(
+    async (calibration: DeviceCalibration | undefined) => {
+      const key = config.selected_device_id || DEFAULT_CALIBRATION_DEVICE;
+      const calibrations = { ...config.calibrations };
+      if (calibration) {
+        calibrations[key] = calibration;
+      } else {
+        delete calibrations[key];
+      }
+      await onConfigChange({ ...config, calibrations });
+    },
+    [config, onConfigChange],
+  );
+
   return (
     <div className="space-y-4">
       {/* 标题和开关 */}
@@ -234,6 +683,18 @@ export function VoiceSettings({
                 onDeviceChange={handleDeviceChange}
                 disabled={disabled || saving}
               />
+              <div className="mt-3">
+                <MicrophoneCalibration
+                  deviceId={config.selected_device_id}
+                  calibration={
+                    config.calibrations?.[
+                      config.selected_device_id || DEFAULT_CALIBRATION_DEVICE
+                    ]
+                  }
+                  onCalibrationChange={handleCalibrationChange}
+                  disabled={disabled || saving}
+                />
+              </div>
             </div>
           </div>
 
@@ -283,21 +744,201 @@ export function VoiceSettings({
                 />
               </div>
             )}
+
+            {/* 按语言选择指令 - 仅在启用润色时显示 */}
+            {config.processor.polish_enabled && (
+              <div className="mt-3">
+                <label className="text-xs text-muted-foreground mb-1.5 block">
+                  按识别出的语言选择指令
+                </label>
+                <div className="space-y-2">
+                  {ROUTED_LANGUAGES.map(([language, label]) => (
+                    <div key={language} className="flex items-center gap-2">
+                      <span className="w-10 text-xs">{label}</span>
+                      <Select
+                        value={
+                          config.processor.language_instructions?.[language] ??
+                          DEFAULT_INSTRUCTION_VALUE
+                        }
+                        onValueChange={(value) =>
+                          handleLanguageInstructionChange(language, value)
+                        }
+                        disabled={disabled || saving}
+                      >
+                        <SelectTrigger className="flex-1 h-8 text-sm">
+                          <SelectValue />
+                        </SelectTrigger>
+                        <SelectContent>
+                          <SelectItem value={DEFAULT_INSTRUCTION_VALUE}>
+                            默认指令
+                          </SelectItem>
+                          {config.instructions.map((instruction) => (
+                            <SelectItem
+                              key={instruction.id}
+                              value={instruction.id}
+                            >
+                              {instruction.name}
+                            </SelectItem>
+                          ))}
+                        </SelectContent>
+                      </Select>
+                    </div>
+                  ))}
+                </div>
+              </div>
+            )}
           </div>
 
-          {/* 交互音效设置 */}
+          {/* 标点补全 */}
           <div className="pt-3 border-t">
             <div className="flex items-center justify-between">
               <div className="flex items-center gap-2">
-                <Volume2 className="h-4 w-4 text-muted-foreground" />
+                <Type className="h-4 w-4 text-muted-foreground" />
                 <div>
-                  <span className="text-sm">交互音效</span>
+                  <span className="text-sm">标点补全</span>
                   <p className="text-xs text-muted-foreground">
-                    录音开始和停止时播放提示音
+                    识别结果完全没有标点时（如百度普通话）自动补全
                   </p>
                 </div>
               </div>
-              <label className="relative inline-flex items-center cursor-pointer">
+              <Select
+                value={config.processor.punctuation ?? "off"}
+                onValueChange={(value) =>
+                  handlePunctuationChange(value as PunctuationMode)
+                }
+                disabled={disabled || saving}
+              >
+                <SelectTrigger className="w-32 h-8 text-sm">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {PUNCTUATION_MODES.map(([mode, label]) => (
+                    <SelectItem key={mode} value={mode}>
+                      {label}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+          </div>
+
+          {/* 同音词纠正 */}
+          <div className="pt-3 border-t">
+            <HomophoneCorrection
+              config={config.processor.homophone ?? DEFAULT_HOMOPHONE}
+              onChange={handleHomophoneChange}
+              disabled={disabled || saving}
+            />
+          </div>
+
+          {/* 口述标点 */}
+          <div className="pt-3 border-t">
+            <SpokenPunctuation
+              config={config.spoken_punctuation ?? DEFAULT_SPOKEN_PUNCTUATION}
+              onChange={handleSpokenPunctuationChange}
+              disabled={disabled || saving}
+            />
+          </div>
+
+          {/* 润色费用上限 */}
+          <div className="pt-3 border-t space-y-2">
+            <div className="flex items-center justify-between">
+              <div className="flex items-center gap-2">
+                <Coins className="h-4 w-4 text-muted-foreground" />
+                <div>
+                  <span className="text-sm">润色费用上限</span>
+                  <p className="text-xs text-muted-foreground">
+                    润色前按模型价格预估费用，超出上限时不直接调用
+                  </p>
+                </div>
+              </div>
+              <label className="relative inline-flex items-center cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={costLimit.enabled}
+                  onChange={() =>
+                    handleCostLimitChange({ enabled: !costLimit.enabled })
+                  }
+                  disabled={disabled || saving}
+                  className="sr-only peer"
+                />
+                <div
+                  className={cn(
+                    "w-9 h-5 rounded-full transition-colors",
+                    "bg-muted peer-checked:bg-primary",
+                    "after:content-[''] after:absolute after:top-0.5 after:left-0.5",
+                    "after:bg-white after:rounded-full after:h-4 after:w-4",
+                    "after:transition-transform peer-checked:after:translate-x-4",
+                    (disabled || saving) && "opacity-50 cursor-not-allowed",
+                  )}
+                />
+              </label>
+            </div>
+            {costLimit.enabled && (
+              <div className="flex items-center gap-2 pl-6">
+                <Select
+                  value={String(costLimit.max_cost)}
+                  onValueChange={(value) =>
+                    handleCostLimitChange({ max_cost: Number(value) })
+                  }
+                  disabled={disabled || saving}
+                >
+                  <SelectTrigger className="w-28 h-8 text-sm">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    {MAX_COST_OPTIONS.map((cost) => (
+                      <SelectItem key={cost} value={String(cost)}>
+                        {cost} / 次
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+                <Select
+                  value={costLimit.action}
+                  onValueChange={(value) =>
+                    handleCostLimitChange({ action: value as PolishCostAction })
+                  }
+                  disabled={disabled || saving}
+                >
+                  <SelectTrigger className="w-32 h-8 text-sm">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    {COST_ACTIONS.map(([action, label]) => (
+                      <SelectItem key={action} value={action}>
+                        {label}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+              </div>
+            )}
+          </div>
+
+          {/* 指令 A/B 测试 */}
+          <div className="pt-3 border-t">
+            <InstructionAbTest
+              config={config.processor.ab_test ?? DEFAULT_AB_TEST}
+              instructions={config.instructions}
+              onChange={handleAbTestChange}
+              disabled={disabled || saving}
+            />
+          </div>
+
+          {/* 交互音效设置 */}
+          <div className="pt-3 border-t">
+            <div className="flex items-center justify-between">
+              <div className="flex items-center gap-2">
+                <Volume2 className="h-4 w-4 text-muted-foreground" />
+                <div>
+                  <span className="text-sm">交互音效</span>
+                  <p className="text-xs text-muted-foreground">
+                    录音开始和停止时播放提示音
+                  </p>
+                </div>
+              </div>
+              <label className="relative inline-flex items-center cursor-pointer">
                 <input
                   type="checkbox"
                   checked={config.sound_enabled}
@@ -319,6 +960,201 @@ export function VoiceSettings({
             </div>
           </div>
 
+          {/* 剪贴板历史 */}
+          <div className="pt-3 border-t">
+            <div className="flex items-center justify-between">
+              <div className="flex items-center gap-2">
+                <ClipboardList className="h-4 w-4 text-muted-foreground" />
+                <div>
+                  <span className="text-sm">剪贴板历史</span>
+                  <p className="text-xs text-muted-foreground">
+                    加密记录最近复制的文本，可在悬浮窗中快速插入，指令中用{" "}
+                    {"{{clipboard_history[0]}}"} 引用
+                  </p>
+                </div>
+              </div>
+              <label className="relative inline-flex items-center cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={!!config.clipboard_history?.enabled}
+                  onChange={handleToggleClipboardHistory}
+                  disabled={disabled || saving}
+                  className="sr-only peer"
+                />
+                <div
+                  className={cn(
+                    "w-9 h-5 rounded-full transition-colors",
+                    "bg-muted peer-checked:bg-primary",
+                    "after:content-[''] after:absolute after:top-0.5 after:left-0.5",
+                    "after:bg-white after:rounded-full after:h-4 after:w-4",
+                    "after:transition-transform peer-checked:after:translate-x-4",
+                    (disabled || saving) && "opacity-50 cursor-not-allowed",
+                  )}
+                />
+              </label>
+            </div>
+          </div>
+
+          {/* 输出前确认 */}
+          <div className="pt-3 border-t">
+            <div className="flex items-center justify-between">
+              <div className="flex items-center gap-2">
+                <ClipboardCheck className="h-4 w-4 text-muted-foreground" />
+                <div>
+                  <span className="text-sm">输出前确认</span>
+                  <p className="text-xs text-muted-foreground">
+                    先在悬浮窗中显示结果，确认、编辑或重试后再输出
+                  </p>
+                </div>
+              </div>
+              <label className="relative inline-flex items-center cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={!!config.output.review_before_output}
+                  onChange={handleToggleReview}
+                  disabled={disabled || saving}
+                  className="sr-only peer"
+                />
+                <div
+                  className={cn(
+                    "w-9 h-5 rounded-full transition-colors",
+                    "bg-muted peer-checked:bg-primary",
+                    "after:content-[''] after:absolute after:top-0.5 after:left-0.5",
+                    "after:bg-white after:rounded-full after:h-4 after:w-4",
+                    "after:transition-transform peer-checked:after:translate-x-4",
+                    (disabled || saving) && "opacity-50 cursor-not-allowed",
+                  )}
+                />
+              </label>
+            </div>
+          </div>
+
+          {/* 焦点切换时暂停输入 */}
+          <div className="pt-3 border-t">
+            <div className="flex items-center justify-between">
+              <div className="flex items-center gap-2">
+                <Pause className="h-4 w-4 text-muted-foreground" />
+                <div>
+                  <span className="text-sm">切换窗口时暂停输入</span>
+                  <p className="text-xs text-muted-foreground">
+                    输入过程中焦点离开原应用则暂停，可选择在新窗口继续或改为复制
+                  </p>
+                </div>
+              </div>
+              <label className="relative inline-flex items-center cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={config.output.pause_on_focus_change !== false}
+                  onChange={handleTogglePauseOnFocusChange}
+                  disabled={disabled || saving}
+                  className="sr-only peer"
+                />
+                <div
+                  className={cn(
+                    "w-9 h-5 rounded-full transition-colors",
+                    "bg-muted peer-checked:bg-primary",
+                    "after:content-[''] after:absolute after:top-0.5 after:left-0.5",
+                    "after:bg-white after:rounded-full after:h-4 after:w-4",
+                    "after:transition-transform peer-checked:after:translate-x-4",
+                    (disabled || saving) && "opacity-50 cursor-not-allowed",
+                  )}
+                />
+              </label>
+            </div>
+          </div>
+
+          {/* 开头静音自动取消 */}
+          <div className="pt-3 border-t">
+            <div className="flex items-center justify-between">
+              <div className="flex items-center gap-2">
+                <MicOff className="h-4 w-4 text-muted-foreground" />
+                <div>
+                  <span className="text-sm">未说话自动取消</span>
+                  <p className="text-xs text-muted-foreground">
+                    开始录音后一直没有说话时自动取消，不调用识别
+                  </p>
+                </div>
+              </div>
+              <div className="flex items-center gap-2">
+                {noSpeech.enabled && (
+                  <Select
+                    value={String(noSpeech.timeout_secs)}
+                    onValueChange={(value) =>
+                      handleNoSpeechChange({ timeout_secs: Number(value) })
+                    }
+                    disabled={disabled || saving}
+                  >
+                    <SelectTrigger className="w-20 h-8 text-sm">
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      {NO_SPEECH_TIMEOUTS.map((secs) => (
+                        <SelectItem key={secs} value={String(secs)}>
+                          {secs} 秒
+                        </SelectItem>
+                      ))}
+                    </SelectContent>
+                  </Select>
+                )}
+                <label className="relative inline-flex items-center cursor-pointer">
+                  <input
+                    type="checkbox"
+                    checked={noSpeech.enabled}
+                    onChange={() =>
+                      handleNoSpeechChange({ enabled: !noSpeech.enabled })
+                    }
+                    disabled={disabled || saving}
+                    className="sr-only peer"
+                  />
+                  <div
+                    className={cn(
+                      "w-9 h-5 rounded-full transition-colors",
+                      "bg-muted peer-checked:bg-primary",
+                      "after:content-[''] after:absolute after:top-0.5 after:left-0.5",
+                      "after:bg-white after:rounded-full after:h-4 after:w-4",
+                      "after:transition-transform peer-checked:after:translate-x-4",
+                      (disabled || saving) && "opacity-50 cursor-not-allowed",
+                    )}
+                  />
+                </label>
+              </div>
+            </div>
+          </div>
+
+          {/* 录音指示 */}
+          <div className="pt-3 border-t">
+            <div className="flex items-center justify-between">
+              <div className="flex items-center gap-2">
+                <CircleDot className="h-4 w-4 text-muted-foreground" />
+                <div>
+                  <span className="text-sm">录音指示</span>
+                  <p className="text-xs text-muted-foreground">
+                    麦克风录音时在每个显示器右上角显示红点
+                  </p>
+                </div>
+              </div>
+              <label className="relative inline-flex items-center cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={windowConfig.recording_indicator}
+                  onChange={handleToggleRecordingIndicator}
+                  disabled={disabled || saving}
+                  className="sr-only peer"
+                />
+                <div
+                  className={cn(
+                    "w-9 h-5 rounded-full transition-colors",
+                    "bg-muted peer-checked:bg-primary",
+                    "after:content-[''] after:absolute after:top-0.5 after:left-0.5",
+                    "after:bg-white after:rounded-full after:h-4 after:w-4",
+                    "after:transition-transform peer-checked:after:translate-x-4",
+                    (disabled || saving) && "opacity-50 cursor-not-allowed",
+                  )}
+                />
+              </label>
+            </div>
+          </div>
+
           {/* 翻译模式快捷键设置 */}
           <div className="pt-3 border-t">
             <div className="flex items-center gap-2 mb-3">
@@ -372,6 +1208,100 @@ export function VoiceSettings({
             </div>
           </div>
 
+          {/* 听写耗时 */}
+          <div className="pt-3 border-t">
+            <VoiceTimeline />
+          </div>
+
+          {/* 听写历史 */}
+          <div className="pt-3 border-t space-y-3">
+            <div className="flex items-center justify-between">
+              <div className="flex items-center gap-2">
+                <History className="h-4 w-4 text-muted-foreground" />
+                <div>
+                  <span className="text-sm">保存听写历史</span>
+                  <p className="text-xs text-muted-foreground">
+                    保存原始识别文本，之后可换用其他指令重新润色
+                  </p>
+                </div>
+              </div>
+              <label className="relative inline-flex items-center cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={(config.history ?? DEFAULT_HISTORY).enabled}
+                  onChange={handleToggleHistory}
+                  disabled={disabled || saving}
+                  className="sr-only peer"
+                />
+                <div
+                  className={cn(
+                    "w-9 h-5 rounded-full transition-colors",
+                    "bg-muted peer-checked:bg-primary",
+                    "after:content-[''] after:absolute after:top-0.5 after:left-0.5",
+                    "after:bg-white after:rounded-full after:h-4 after:w-4",
+                    "after:transition-transform peer-checked:after:translate-x-4",
+                    (disabled || saving) && "opacity-50 cursor-not-allowed",
+                  )}
+                />
+              </label>
+            </div>
+            {(config.history ?? DEFAULT_HISTORY).enabled && (
+              <>
+                <div className="flex items-center justify-between pl-6">
+                  <div>
+                    <span className="text-sm">同步到工作区</span>
+                    <p className="text-xs text-muted-foreground">
+                      按日期写入默认工作区的{" "}
+                      {historySync.folder || DEFAULT_HISTORY_SYNC.folder}{" "}
+                      文件夹（Markdown），可被工作区搜索
+                    </p>
+                  </div>
+                  <label className="relative inline-flex items-center cursor-pointer">
+                    <input
+                      type="checkbox"
+                      checked={historySync.enabled}
+                      onChange={() => handleToggleHistorySync("enabled")}
+                      disabled={disabled || saving}
+                      className="sr-only peer"
+                    />
+                    <div
+                      className={cn(
+                        "w-9 h-5 rounded-full transition-colors",
+                        "bg-muted peer-checked:bg-primary",
+                        "after:content-[''] after:absolute after:top-0.5 after:left-0.5",
+                        "after:bg-white after:rounded-full after:h-4 after:w-4",
+                        "after:transition-transform peer-checked:after:translate-x-4",
+                        (disabled || saving) && "opacity-50 cursor-not-allowed",
+                      )}
+                    />
+                  </label>
+                </div>
+                {historySync.enabled && (
+                  <label className="flex items-center gap-2 pl-6 text-xs text-muted-foreground cursor-pointer">
+                    <input
+                      type="checkbox"
+                      checked={historySync.include_audio}
+                      onChange={() => handleToggleHistorySync("include_audio")}
+                      disabled={disabled || saving}
+                    />
+                    同时保存录音（WAV）
+                  </label>
+                )}
+                <VoiceHistory instructions={config.instructions} />
+              </>
+            )}
+          </div>
+
+          {/* 本地推理诊断 */}
+          <div className="pt-3 border-t">
+            <ComputeDiagnostics />
+          </div>
+
+          {/* 本地模型下载 */}
+          <div className="pt-3 border-t">
+            <LocalModelManager />
+          </div>
+
           {/* ASR 服务管理入口 */}
           <div className="pt-3 border-t">
             <div className="flex items-center justify-between">
@@ -392,6 +1322,94 @@ export function VoiceSettings({
             </div>
           </div>
 
+          {/* 识别策略 */}
+          <div className="pt-3 border-t">
+            <div className="flex items-center justify-between">
+              <div className="flex items-center gap-2">
+                <Zap className="h-4 w-4 text-muted-foreground" />
+                <div>
+                  <span className="text-sm">识别策略</span>
+                  <p className="text-xs text-muted-foreground">
+                    竞速模式同时请求所有启用的云端服务，采用最快的结果（每个服务都会计费）
+                  </p>
+                </div>
+              </div>
+              <Select
+                value={config.recognition_strategy ?? "fallback"}
+                onValueChange={(value) =>
+                  handleStrategyChange(value as RecognitionStrategy)
+                }
+                disabled={disabled || saving}
+              >
+                <SelectTrigger className="w-32 h-8 text-sm">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {RECOGNITION_STRATEGIES.map(([strategy, label]) => (
+                    <SelectItem key={strategy} value={strategy}>
+                      {label}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+          </div>
+
+          {/* 本地 Whisper 识别任务 */}
+          <div className="pt-3 border-t">
+            <div className="flex items-center justify-between">
+              <div className="flex items-center gap-2">
+                <Globe className="h-4 w-4 text-muted-foreground" />
+                <div>
+                  <span className="text-sm">本地 Whisper 任务</span>
+                  <p className="text-xs text-muted-foreground">
+                    译为英文时，任意语言的语音由本地 Whisper 直接输出英文文本
+                  </p>
+                </div>
+              </div>
+              <Select
+                value={config.whisper_task ?? "transcribe"}
+                onValueChange={(value) =>
+                  handleWhisperTaskChange(value as WhisperTask)
+                }
+                disabled={disabled || saving}
+              >
+                <SelectTrigger className="w-32 h-8 text-sm">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {WHISPER_TASKS.map(([task, label]) => (
+                    <SelectItem key={task} value={task}>
+                      {label}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+          </div>
+
+          {/* 识别热词 */}
+          <div className="pt-3 border-t">
+            <VoiceVocabulary disabled={disabled} />
+          </div>
+
+          {/* 工作区听写 */}
+          <div className="pt-3 border-t">
+            <WorkspaceVoiceDefaults
+              instructions={config.instructions}
+              disabled={disabled || saving}
+            />
+          </div>
+
+          {/* 说话人区分 */}
+          <div className="pt-3 border-t">
+            <SpeakerDiarization
+              config={config.diarization ?? DEFAULT_DIARIZATION}
+              onChange={handleDiarizationChange}
+              disabled={disabled || saving}
+            />
+          </div>
+
           {/* macOS 麦克风权限警告 */}
           {isMacOS && (
             <div className="flex items-start gap-2 p-3 rounded-lg bg-amber-50 dark:bg-amber-900/20 border border-amber-200 dark:border-amber-800">
@@ -0,0 +1,143 @@
+/**
+ * @file VoiceTimeline.tsx
+ * @description 听写时间线 - 展示最近几次听写各阶段耗时，帮助定位延迟来源
+ * @module components/voice/VoiceTimeline
+ */
+
+import { useState, useEffect, useCallback } from "react";
+import { Timer, RefreshCw, Trash2 } from "lucide-react";
+import { listen } from "@tauri-apps/api/event";
+import { Button } from "@/components/ui/button";
+import {
+  getVoiceSessionTimelines,
+  clearVoiceSessionTimelines,
+  SESSION_STAGE_EVENT,
+  type SessionTimeline,
+  type VoiceStage,
+} from "@/lib/api/asrProvider";
+
+/** 最多展示的会话数 */
+const MAX_VISIBLE = 5;
+
+/** 阶段区间：[名称, 开始阶段, 结束阶段] */
+const SPANS: [string, VoiceStage, VoiceStage][] = [
+  ["录音", "record_start", "record_stop"],
+  ["首个结果", "record_start", "first_partial"],
+  ["识别", "asr_submit", "asr_done"],
+  ["润色", "polish_start", "polish_done"],
+];
+
+/** 阶段发生时距录音开始的毫秒数 */
+function stageAt(session: SessionTimeline, stage: VoiceStage) {
+  return session.events.find((e) => e.stage === stage)?.elapsed_ms;
+}
+
+function formatMs(ms: number) {
+  return ms >= 1000 ? `${(ms / 1000).toFixed(2)}s` : `${ms}ms`;
+}
+
+function SessionRow({ session }: { session: SessionTimeline }) {
+  const stop = stageAt(session, "record_stop");
+  const last = session.events[session.events.length - 1]?.elapsed_ms;
+
+  return (
+    <div className="space-y-1 rounded-md border px-3 py-2">
+      <div className="flex items-center justify-between text-xs">
+        <span>
+          {new Date(session.started_at).toLocaleTimeString()}
+          {session.provider && (
+            <span className="ml-2 text-muted-foreground">
+              {session.provider}
+            </span>
+          )}
+        </span>
+        {stop !== undefined && last !== undefined && last > stop && (
+          <span className="font-medium">
+            说完到完成 {formatMs(last - stop)}
+          </span>
+        )}
+      </div>
+      <div className="flex flex-wrap gap-x-4 gap-y-1 text-xs text-muted-foreground">
+        {SPANS.map(([label, from, to]) => {
+          const start = stageAt(session, from);
+          const end = stageAt(session, to);
+          if (start === undefined || end === undefined) return null;
+          return (
+            <span key={label}>
+              {label} {formatMs(end - start)}
+            </span>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
+
+export function VoiceTimeline() {
+  const [sessions, setSessions] = useState<SessionTimeline[]>([]);
+
+  const refresh = useCallback(async () => {
+    try {
+      setSessions(await getVoiceSessionTimelines());
+    } catch (err) {
+      console.error("[听写时间线] 加载失败:", err);
+    }
+  }, []);
+
+  const handleClear = useCallback(async () => {
+    try {
+      await clearVoiceSessionTimelines();
+      await refresh();
+    } catch (err) {
+      console.error("[听写时间线] 清空失败:", err);
+    }
+  }, [refresh]);
+
+  useEffect(() => {
+    refresh();
+    const unlistenPromise = listen(SESSION_STAGE_EVENT, () => refresh());
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, [refresh]);
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-2">
+          <Timer className="h-4 w-4 text-muted-foreground" />
+          <div>
+            <span className="text-sm">听写耗时</span>
+            <p className="text-xs text-muted-foreground">
+              最近几次听写各阶段的耗时
+            </p>
+          </div>
+        </div>
+        <div className="flex items-center gap-1">
+          <Button variant="ghost" size="sm" onClick={refresh} title="刷新">
+            <RefreshCw className="h-4 w-4" />
+          </Button>
+          <Button
+            variant="ghost"
+            size="sm"
+            onClick={handleClear}
+            disabled={sessions.length === 0}
+            title="清空记录"
+          >
+            <Trash2 className="h-4 w-4" />
+          </Button>
+        </div>
+      </div>
+
+      {sessions.length === 0 ? (
+        <p className="text-xs text-muted-foreground">暂无记录</p>
+      ) : (
+        sessions
+          .slice(0, MAX_VISIBLE)
+          .map((session) => <SessionRow key={session.id} session={session} />)
+      )}
+    </div>
+  );
+}
+
+export default VoiceTimeline;
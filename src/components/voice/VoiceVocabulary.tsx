@@ -0,0 +1,74 @@
+/**
+ * @file VoiceVocabulary.tsx
+ * @description 识别热词 - 编辑默认工作区的热词表，提高专业术语、产品名的识别率
+ * @module components/voice/VoiceVocabulary
+ */
+
+import { useState, useEffect } from "react";
+import { BookOpen } from "lucide-react";
+import { getVoiceVocabulary, setVoiceVocabulary } from "@/lib/api/asrProvider";
+
+interface VoiceVocabularyProps {
+  disabled?: boolean;
+}
+
+/** 每行一个热词，去掉空行和重复 */
+function parseWords(text: string): string[] {
+  const words = text
+    .split("\n")
+    .map((line) => line.trim())
+    .filter(Boolean);
+  return Array.from(new Set(words));
+}
+
+export function VoiceVocabulary({ disabled = false }: VoiceVocabularyProps) {
+  const [words, setWords] = useState<string[]>([]);
+  const [wordsText, setWordsText] = useState("");
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getVoiceVocabulary()
+      .then((loaded) => {
+        setWords(loaded);
+        setWordsText(loaded.join("\n"));
+      })
+      .catch((err) => console.error("[识别热词] 加载失败:", err));
+  }, []);
+
+  const handleBlur = async () => {
+    const parsed = parseWords(wordsText);
+    if (parsed.join("\n") === words.join("\n")) return;
+    setError(null);
+    try {
+      const saved = await setVoiceVocabulary(parsed);
+      setWords(saved);
+      setWordsText(saved.join("\n"));
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2">
+        <BookOpen className="h-4 w-4 text-muted-foreground" />
+        <div>
+          <span className="text-sm">识别热词</span>
+          <p className="text-xs text-muted-foreground">
+            默认工作区的专业术语、产品名，讯飞、自建 Whisper 和自定义接口识别时优先采用
+          </p>
+        </div>
+      </div>
+      <textarea
+        value={wordsText}
+        onChange={(e) => setWordsText(e.target.value)}
+        onBlur={handleBlur}
+        disabled={disabled}
+        rows={4}
+        placeholder={"每行一个热词\nProxyCast\nKubernetes"}
+        className="w-full rounded-md border bg-background px-3 py-2 text-sm focus:outline-none focus:ring-1 focus:ring-primary"
+      />
+      {error && <p className="text-xs text-destructive">{error}</p>}
+    </div>
+  );
+}
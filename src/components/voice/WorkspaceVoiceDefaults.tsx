@@ -0,0 +1,127 @@
+/**
+ * @file WorkspaceVoiceDefaults.tsx
+ * @description 工作区听写默认值 - ProxyCast 在前台时按当前工作区选择输出方式和润色指令
+ * @module components/voice/WorkspaceVoiceDefaults
+ */
+
+import { useState, useEffect } from "react";
+import { FolderOpen } from "lucide-react";
+import {
+  getWorkspaceVoiceDefaults,
+  setWorkspaceVoiceDefaults,
+  type VoiceInstruction,
+  type VoiceOutputMode,
+  type WorkspaceVoiceDefaults as WorkspaceVoiceDefaultsConfig,
+} from "@/lib/api/asrProvider";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+
+/** 跟随语音输入配置（Select 不支持空值） */
+const FOLLOW_CONFIG = "__config__";
+
+/** 输出方式选项 */
+const OUTPUT_MODES: [VoiceOutputMode, string][] = [
+  ["type", "直接输入"],
+  ["clipboard", "仅复制"],
+  ["both", "输入并复制"],
+];
+
+interface WorkspaceVoiceDefaultsProps {
+  instructions: VoiceInstruction[];
+  disabled?: boolean;
+}
+
+export function WorkspaceVoiceDefaults({
+  instructions,
+  disabled = false,
+}: WorkspaceVoiceDefaultsProps) {
+  const [defaults, setDefaults] = useState<WorkspaceVoiceDefaultsConfig>({
+    output_mode: null,
+    instruction_id: null,
+  });
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getWorkspaceVoiceDefaults()
+      .then(setDefaults)
+      .catch((err) => console.error("[工作区听写] 加载失败:", err));
+  }, []);
+
+  const handleChange = async (
+    patch: Partial<WorkspaceVoiceDefaultsConfig>,
+  ) => {
+    const next = { ...defaults, ...patch };
+    setError(null);
+    try {
+      await setWorkspaceVoiceDefaults(next);
+      setDefaults(next);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2">
+        <FolderOpen className="h-4 w-4 text-muted-foreground" />
+        <div>
+          <span className="text-sm">工作区听写</span>
+          <p className="text-xs text-muted-foreground">
+            在 ProxyCast 中听写时，按当前工作区选择输出方式和润色指令
+          </p>
+        </div>
+      </div>
+      <div className="flex items-center gap-2 pl-6">
+        <Select
+          value={defaults.output_mode ?? FOLLOW_CONFIG}
+          onValueChange={(value) =>
+            handleChange({
+              output_mode:
+                value === FOLLOW_CONFIG ? null : (value as VoiceOutputMode),
+            })
+          }
+          disabled={disabled}
+        >
+          <SelectTrigger className="flex-1 h-8 text-sm">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            <SelectItem value={FOLLOW_CONFIG}>默认输出方式</SelectItem>
+            {OUTPUT_MODES.map(([mode, label]) => (
+              <SelectItem key={mode} value={mode}>
+                {label}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+        <Select
+          value={defaults.instruction_id ?? FOLLOW_CONFIG}
+          onValueChange={(value) =>
+            handleChange({
+              instruction_id: value === FOLLOW_CONFIG ? null : value,
+            })
+          }
+          disabled={disabled}
+        >
+          <SelectTrigger className="flex-1 h-8 text-sm">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            <SelectItem value={FOLLOW_CONFIG}>默认指令</SelectItem>
+            {instructions.map((instruction) => (
+              <SelectItem key={instruction.id} value={instruction.id}>
+                {instruction.name}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      </div>
+      {error && <p className="text-xs text-destructive">{error}</p>}
+    </div>
+  );
+}
@@ -12,3 +12,13 @@ export { VoiceSettings } from "./VoiceSettings";
 export { InstructionEditor } from "./InstructionEditor";
 export { MicrophoneTest } from "./MicrophoneTest";
 export { VolumeWaveform } from "./VolumeWaveform";
+export { VoiceTimeline } from "./VoiceTimeline";
+export { ComputeDiagnostics } from "./ComputeDiagnostics";
+export { LocalModelManager } from "./LocalModelManager";
+export { VoiceHistory } from "./VoiceHistory";
+export { InstructionAbTest } from "./InstructionAbTest";
+export { HomophoneCorrection } from "./HomophoneCorrection";
+export { SpokenPunctuation } from "./SpokenPunctuation";
+export { VoiceVocabulary } from "./VoiceVocabulary";
+export { SpeakerDiarization } from "./SpeakerDiarization";
+export { WorkspaceVoiceDefaults } from "./WorkspaceVoiceDefaults";
@@ -9,15 +9,34 @@ export type {
   AsrProviderType,
   WhisperModelSize,
   WhisperLocalConfig,
+  LocalAsrEngine,
+  SenseVoicePrecision,
+  LocalModelInfo,
+  ModelDownloadProgress,
+  BatchTranscribeProgress,
+  FileTranscription,
   XunfeiConfig,
+  VolcengineAsrConfig,
   BaiduConfig,
+  TencentAsrConfig,
+  AzureAsrConfig,
+  AzureProfanityFilter,
+  DeepgramAsrConfig,
   OpenAIAsrConfig,
+  CustomWhisperAsrConfig,
+  CommandAsrConfig,
+  HttpAsrConfig,
+  HttpAsrUpload,
+  AsrRateLimitConfig,
+  AsrTimeoutConfig,
   AsrCredentialEntry,
   VoiceOutputMode,
   VoiceProcessorConfig,
   VoiceOutputConfig,
   VoiceInstruction,
   VoiceInputConfig,
+  AsrUsageStats,
+  AsrHealthCheck,
 } from "@/lib/api/asrProvider";
 
 // 导出 API 函数
@@ -28,11 +47,18 @@ export {
   deleteAsrCredential,
   setDefaultAsrCredential,
   testAsrCredential,
+  getAsrUsageStats,
   getVoiceInputConfig,
   saveVoiceInputConfig,
   getVoiceInstructions,
   saveVoiceInstruction,
   deleteVoiceInstruction,
+  listLocalAsrModels,
+  downloadLocalAsrModel,
+  deleteLocalAsrModel,
+  MODEL_DOWNLOAD_EVENT,
+  transcribeFiles,
+  BATCH_TRANSCRIBE_EVENT,
 } from "@/lib/api/asrProvider";
 
 /** ASR Provider 显示信息 */
@@ -49,7 +75,7 @@ export const ASR_PROVIDERS: AsrProviderInfo[] = [
   {
     type: "whisper_local",
     label: "本地 Whisper",
-    description: "离线语音识别，无需网络",
+    description: "离线语音识别，可选 Whisper 或 SenseVoice 引擎",
     icon: "cpu",
     requiresCredentials: false,
   },
@@ -60,6 +86,13 @@ export const ASR_PROVIDERS: AsrProviderInfo[] = [
     icon: "cloud",
     requiresCredentials: true,
   },
+  {
+    type: "volcengine",
+    label: "火山引擎",
+    description: "火山引擎（豆包）流式语音识别",
+    icon: "cloud",
+    requiresCredentials: true,
+  },
   {
     type: "baidu",
     label: "百度语音",
@@ -67,6 +100,27 @@ export const ASR_PROVIDERS: AsrProviderInfo[] = [
     icon: "cloud",
     requiresCredentials: true,
   },
+  {
+    type: "tencent",
+    label: "腾讯云语音",
+    description: "腾讯云一句话识别",
+    icon: "cloud",
+    requiresCredentials: true,
+  },
+  {
+    type: "azure",
+    label: "Azure 语音",
+    description: "Azure Speech Services 短音频识别",
+    icon: "cloud",
+    requiresCredentials: true,
+  },
+  {
+    type: "deepgram",
+    label: "Deepgram",
+    description: "Deepgram 预录音频识别，带逐词时间戳",
+    icon: "cloud",
+    requiresCredentials: true,
+  },
   {
     type: "openai",
     label: "OpenAI Whisper",
@@ -74,6 +128,27 @@ export const ASR_PROVIDERS: AsrProviderInfo[] = [
     icon: "sparkles",
     requiresCredentials: true,
   },
+  {
+    type: "custom_whisper",
+    label: "自建 Whisper",
+    description: "局域网内兼容 OpenAI 转写接口的 Whisper 服务",
+    icon: "sparkles",
+    requiresCredentials: true,
+  },
+  {
+    type: "command",
+    label: "自定义命令",
+    description: "通过外部命令接入其他识别引擎",
+    icon: "terminal",
+    requiresCredentials: false,
+  },
+  {
+    type: "http",
+    label: "HTTP 接口",
+    description: "自建识别服务（FunASR、SenseVoice 等）",
+    icon: "globe",
+    requiresCredentials: false,
+  },
 ];
 
 /** Whisper 模型选项 */
@@ -82,4 +157,26 @@ export const WHISPER_MODELS = [
   { value: "base", label: "Base", size: "~142MB", speed: "快" },
   { value: "small", label: "Small", size: "~466MB", speed: "中等" },
   { value: "medium", label: "Medium", size: "~1.5GB", speed: "较慢" },
+  { value: "large", label: "Large", size: "~3.1GB", speed: "最慢" },
+] as const;
+
+/** 本地识别引擎选项 */
+export const LOCAL_ENGINES = [
+  { value: "whisper", label: "Whisper", description: "多语言通用" },
+  {
+    value: "sense_voice",
+    label: "SenseVoice",
+    description: "中文、粤语效果更好，速度更快",
+  },
+] as const;
+
+/** SenseVoice 模型选项（`id` 对应模型下载条目） */
+export const SENSEVOICE_MODELS = [
+  { value: "int8", id: "sensevoice-int8", label: "int8 量化", size: "~230MB" },
+  {
+    value: "float32",
+    id: "sensevoice-float32",
+    label: "float32",
+    size: "~900MB",
+  },
 ] as const;
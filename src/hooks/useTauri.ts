@@ -16,6 +16,15 @@ export interface TlsConfig {
   key_path: string | null;
 }
 
+// Client API Key（局域网共享时分发给团队成员）
+export interface ClientApiKey {
+  id: string;
+  /** 显示名称 */
+  name: string;
+  key: string;
+  disabled: boolean;
+}
+
 // Remote Management Configuration
 export interface RemoteManagementConfig {
   allow_remote: boolean;
@@ -30,6 +39,16 @@ export interface QuotaExceededConfig {
   cooldown_seconds: number;
 }
 
+// Mock Provider Configuration
+export interface MockProviderSettings {
+  enabled: boolean;
+  response_template: string;
+  echo_prompt: boolean;
+  latency_ms: number;
+  stream_chunk_delay_ms: number;
+  stream_chunk_chars: number;
+}
+
 // Amp Model Mapping
 export interface AmpModelMapping {
   from: string;
@@ -135,6 +154,8 @@ export interface Config {
     port: number;
     api_key: string;
     tls: TlsConfig;
+    /** 客户端密钥，用量按密钥归属 */
+    client_keys?: ClientApiKey[];
   };
   providers: {
     kiro: {
@@ -173,12 +194,16 @@ export interface Config {
   language: string;
   /** 实验室功能配置 */
   experimental?: ExperimentalFeatures;
+  /** 本地 Mock Provider 配置 */
+  mock?: MockProviderSettings;
 }
 
 export interface LogEntry {
   timestamp: string;
   level: string;
   message: string;
+  /** 关联的请求 ID（响应头 X-Proxycast-Request-Id） */
+  request_id?: string;
 }
 
 export async function startServer(): Promise<string> {
@@ -247,6 +272,46 @@ export async function getLogs(): Promise<LogEntry[]> {
   }
 }
 
+/** 获取指定请求的全部日志 */
+export async function getRequestTrace(id: string): Promise<LogEntry[]> {
+  return safeInvoke("get_request_trace", { id });
+}
+
+/** 日志级别设置 */
+export interface LogLevels {
+  /** 默认级别 */
+  default: string;
+  /** 各模块（voice / pipeline / providers）的生效级别 */
+  modules: Record<string, string>;
+  /** 单独设置过级别的模块 */
+  overridden: string[];
+}
+
+export async function getLogLevels(): Promise<LogLevels> {
+  return safeInvoke("get_log_levels");
+}
+
+/**
+ * 设置日志级别，立即生效
+ *
+ * 不传 module 时设置默认级别；level 为 "default" 时取消模块的单独设置
+ */
+export async function setLogLevel(
+  level: string,
+  module?: string,
+): Promise<LogLevels> {
+  return safeInvoke("set_log_level", { module: module ?? null, level });
+}
+
+export async function openLogFile(): Promise<void> {
+  return safeInvoke("open_log_file");
+}
+
+/** 导出当前日志文件到指定路径 */
+export async function exportLogFile(dest: string): Promise<string> {
+  return safeInvoke("export_log_file", { dest });
+}
+
 export async function clearLogs(): Promise<void> {
   try {
     await safeInvoke("clear_logs");
@@ -107,6 +107,8 @@ export interface StreamEventActionRequired {
   tool_name?: string;
   /** 工具参数（工具确认时） */
   arguments?: Record<string, unknown>;
+  /** 审批策略识别到的风险类别（工具确认时） */
+  categories?: string[];
   /** 提示信息 */
   prompt?: string;
   /** 问题列表（ask_user 时） */
@@ -220,6 +222,7 @@ export function parseStreamEvent(data: unknown): StreamEvent | null {
             | "elicitation") || "tool_confirmation",
         tool_name: event.tool_name as string | undefined,
         arguments: event.arguments as Record<string, unknown> | undefined,
+        categories: event.categories as string[] | undefined,
         prompt: event.prompt as string | undefined,
         questions: event.questions as
           | Array<{
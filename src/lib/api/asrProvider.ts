@@ -5,19 +5,41 @@
  */
 
 import { invoke } from "@tauri-apps/api/core";
+import type { AppType, Prompt } from "./prompts";
 
 // ============ ASR Provider 类型 ============
 
 /** ASR Provider 类型 */
-export type AsrProviderType = "whisper_local" | "xunfei" | "baidu" | "openai";
+export type AsrProviderType =
+  | "whisper_local"
+  | "xunfei"
+  | "volcengine"
+  | "baidu"
+  | "tencent"
+  | "azure"
+  | "deepgram"
+  | "openai"
+  | "custom_whisper"
+  | "command"
+  | "http";
 
 /** Whisper 模型大小 */
-export type WhisperModelSize = "tiny" | "base" | "small" | "medium";
+export type WhisperModelSize = "tiny" | "base" | "small" | "medium" | "large";
 
-/** Whisper 本地配置 */
+/** 本地识别引擎 */
+export type LocalAsrEngine = "whisper" | "sense_voice";
+
+/** SenseVoice 模型精度 */
+export type SenseVoicePrecision = "int8" | "float32";
+
+/** 本地识别配置 */
 export interface WhisperLocalConfig {
+  /** 本地识别引擎，默认 whisper */
+  engine?: LocalAsrEngine;
   model: WhisperModelSize;
   model_path?: string;
+  /** SenseVoice 模型精度（仅 SenseVoice 引擎） */
+  sensevoice_model?: SenseVoicePrecision;
 }
 
 /** 讯飞配置 */
@@ -25,6 +47,16 @@ export interface XunfeiConfig {
   app_id: string;
   api_key: string;
   api_secret: string;
+  /** 录音文件转写 SecretKey（可选，配置后超过 60 秒的录音使用录音文件转写） */
+  lfasr_secret_key?: string;
+}
+
+/** 火山引擎配置 */
+export interface VolcengineAsrConfig {
+  app_id: string;
+  access_token: string;
+  /** 集群（可选，默认 volcengine_streaming_common） */
+  cluster?: string;
 }
 
 /** 百度配置 */
@@ -33,6 +65,38 @@ export interface BaiduConfig {
   secret_key: string;
 }
 
+/** 腾讯云配置 */
+export interface TencentAsrConfig {
+  secret_id: string;
+  secret_key: string;
+  /** 地域（可选，如 ap-shanghai） */
+  region?: string;
+}
+
+/** Azure 脏话过滤方式 */
+export type AzureProfanityFilter = "masked" | "removed" | "raw";
+
+/** Azure 语音配置 */
+export interface AzureAsrConfig {
+  subscription_key: string;
+  /** 资源所在地域（如 eastasia） */
+  region: string;
+  /** 识别语言（可选，如 zh-CN），为空时按语言设置推断 */
+  language?: string;
+  profanity?: AzureProfanityFilter;
+  /** 区分说话人（改用快速转写接口） */
+  diarize?: boolean;
+}
+
+/** Deepgram 配置 */
+export interface DeepgramAsrConfig {
+  api_key: string;
+  /** 模型（可选，如 nova-2、nova-2-meeting，默认 nova-2） */
+  model?: string;
+  /** 区分说话人 */
+  diarize?: boolean;
+}
+
 /** OpenAI ASR 配置 */
 export interface OpenAIAsrConfig {
   api_key: string;
@@ -40,6 +104,65 @@ export interface OpenAIAsrConfig {
   proxy_url?: string;
 }
 
+/** 自建 Whisper 服务配置（兼容 OpenAI 转写接口） */
+export interface CustomWhisperAsrConfig {
+  /** 服务地址（如 http://192.168.1.10:8000） */
+  base_url: string;
+  /** API Key（可选，服务开启鉴权时填写） */
+  api_key?: string;
+  /** 模型名（可选，如 Systran/faster-whisper-large-v3） */
+  model?: string;
+  /** 超时时间（秒），默认 120 */
+  timeout_secs?: number;
+}
+
+/** 自定义命令 ASR 配置 */
+export interface CommandAsrConfig {
+  /** 可执行文件路径 */
+  program: string;
+  /** 参数，支持 {language}、{file} 占位符 */
+  args?: string[];
+  /** 超时时间（秒） */
+  timeout_secs?: number;
+}
+
+/** HTTP 接口上传录音的方式 */
+export type HttpAsrUpload = "raw" | "multipart";
+
+/** 通用 HTTP ASR 配置 */
+export interface HttpAsrConfig {
+  /** 接口地址，支持 {language} 占位符 */
+  url: string;
+  /** 额外的请求头 */
+  headers?: Record<string, string>;
+  /** 识别文本在响应中的 JSONPath，如 $.result[0].text */
+  text_path?: string;
+  /** 上传方式 */
+  upload?: HttpAsrUpload;
+  /** multipart 上传时的文件字段名 */
+  file_field?: string;
+  /** 超时时间（秒） */
+  timeout_secs?: number;
+}
+
+/** ASR 请求超时（秒），未设置的项使用服务的默认值 */
+export interface AsrTimeoutConfig {
+  /** 建立连接，默认 10 */
+  connect_secs?: number;
+  /** 发送音频，默认 30 */
+  send_secs?: number;
+  /** 音频发送完后等待识别结果，默认 30（自建 Whisper、自定义命令、HTTP 接口默认为各自的超时时间） */
+  receive_secs?: number;
+}
+
+/** ASR 请求限流，超过每秒次数的请求排队等待 */
+export interface AsrRateLimitConfig {
+  /** 每秒最多请求次数，0 表示不限流；未设置时百度为 2，其他服务不限流 */
+  requests_per_sec?: number;
+  /** 空闲后允许连续发出的请求数，默认 1 */
+  burst?: number;
+}
+
 /** ASR 凭证条目 */
 export interface AsrCredentialEntry {
   id: string;
@@ -47,11 +170,24 @@ export interface AsrCredentialEntry {
   name?: string;
   is_default: boolean;
   disabled: boolean;
+  /** 回退优先级（数字越小越先尝试），所选服务不可用时按此顺序改用其他云端凭证 */
+  priority?: number;
   language: string;
   whisper_config?: WhisperLocalConfig;
   xunfei_config?: XunfeiConfig;
+  volcengine_config?: VolcengineAsrConfig;
   baidu_config?: BaiduConfig;
+  tencent_config?: TencentAsrConfig;
+  azure_config?: AzureAsrConfig;
+  deepgram_config?: DeepgramAsrConfig;
   openai_config?: OpenAIAsrConfig;
+  custom_whisper_config?: CustomWhisperAsrConfig;
+  command_config?: CommandAsrConfig;
+  http_config?: HttpAsrConfig;
+  /** 请求超时（本地 Whisper 不使用） */
+  timeouts?: AsrTimeoutConfig;
+  /** 请求限流（本地 Whisper 不使用） */
+  rate_limit?: AsrRateLimitConfig;
 }
 
 // ============ 语音输入配置类型 ============
@@ -65,12 +201,58 @@ export interface VoiceProcessorConfig {
   polish_provider?: string;
   polish_model?: string;
   default_instruction_id: string;
+  /** 按识别出的语言选择指令（键为语言代码，值为指令 ID） */
+  language_instructions?: Record<string, string>;
+  /** 识别结果没有标点时的补全方式 */
+  punctuation?: PunctuationMode;
+  /** 单次润色的费用上限 */
+  cost_limit?: PolishCostLimit;
+  /** 润色指令 A/B 测试 */
+  ab_test?: InstructionAbTest;
+  /** 同音词纠正 */
+  homophone?: HomophoneCorrection;
+}
+
+/** 同音词纠正：识别结果中与词条拼音相同、字不同的片段替换为词条 */
+export interface HomophoneCorrection {
+  enabled: boolean;
+  /** 用户词条（如账号、人名、产品名），至少两个汉字 */
+  terms?: string[];
+  /** 模糊音：不区分平翘舌、前后鼻音和 n/l */
+  fuzzy: boolean;
 }
 
+/** 润色指令 A/B 测试：自动选择指令的润色在两个变体之间轮换 */
+export interface InstructionAbTest {
+  enabled: boolean;
+  /** 变体 A 的指令 ID */
+  variant_a: string;
+  /** 变体 B 的指令 ID */
+  variant_b: string;
+}
+
+/** 润色费用超出上限时的处理方式：输出原文 / 确认后润色 */
+export type PolishCostAction = "raw" | "confirm";
+
+/** 润色费用上限（模型没有价格信息时不限制） */
+export interface PolishCostLimit {
+  enabled: boolean;
+  /** 单次润色的费用上限（模型定价的货币单位，通常为美元） */
+  max_cost: number;
+  action: PolishCostAction;
+}
+
+/** 标点恢复方式：不处理 / 按规则补全 / 调用润色模型只添加标点 */
+export type PunctuationMode = "off" | "rules" | "llm";
+
 /** 语音输出配置 */
 export interface VoiceOutputConfig {
   mode: VoiceOutputMode;
   type_delay_ms: number;
+  /** 输出前先在悬浮窗中确认（可编辑、重试） */
+  review_before_output?: boolean;
+  /** 模拟键盘输入时焦点离开听写开始时的应用则暂停（默认开启） */
+  pause_on_focus_change?: boolean;
 }
 
 /** 语音处理指令 */
@@ -82,6 +264,12 @@ export interface VoiceInstruction {
   shortcut?: string;
   is_preset: boolean;
   icon?: string;
+  /** 润色结果中的按键标记（如 {Enter}）是否作为输出动作执行 */
+  actions?: boolean;
+  /** 允许执行的按键（如 Enter、Ctrl+Enter），其他按键不执行 */
+  allowed_keys?: string[];
+  /** 输出前是否把数字、日期、金额的读法改为书面写法 */
+  itn?: boolean;
 }
 
 /** 语音输入功能配置 */
@@ -99,6 +287,194 @@ export interface VoiceInputConfig {
   translate_shortcut?: string;
   /** 翻译模式使用的指令 ID */
   translate_instruction_id: string;
+  /** 识别前的首尾静音裁剪配置 */
+  silence_trim?: SilenceTrimConfig;
+  /** 各麦克风设备的电平校准结果（键为设备 ID，系统默认设备为 `default`） */
+  calibrations?: Record<string, DeviceCalibration>;
+  /** 免打扰时段配置 */
+  quiet_hours?: QuietHoursConfig;
+  /** 开头静音自动取消配置 */
+  no_speech?: NoSpeechConfig;
+  /** 听写历史配置 */
+  history?: VoiceHistoryConfig;
+  /** 悬浮窗口配置 */
+  window?: VoiceWindowConfig;
+  /** 多个云端识别服务的使用方式 */
+  recognition_strategy?: RecognitionStrategy;
+  /** 云端识别遇到网络错误时的重试配置 */
+  asr_retry?: AsrRetryConfig;
+  /** 本地说话人区分（转写录音文件时使用） */
+  diarization?: DiarizationConfig;
+  /** 音频隐私设置 */
+  privacy?: AudioPrivacyConfig;
+  /** 本地 Whisper 的识别任务 */
+  whisper_task?: WhisperTask;
+  /** 口述标点 */
+  spoken_punctuation?: SpokenPunctuationConfig;
+  /** 剪贴板历史 */
+  clipboard_history?: ClipboardHistoryConfig;
+}
+
+/** 剪贴板历史：记录最近复制的文本（加密保存），供指令模板引用和悬浮窗快速插入 */
+export interface ClipboardHistoryConfig {
+  enabled: boolean;
+  /** 最多保留的条数 */
+  max_entries: number;
+  /** 单条记录的最大字符数 */
+  max_entry_chars: number;
+}
+
+/** 口述标点：识别结果中的口令（如「逗号」「new line」）替换为对应字符 */
+export interface SpokenPunctuationConfig {
+  enabled: boolean;
+  /** 口令表，长口令优先匹配 */
+  mappings?: SpokenPunctuationMapping[];
+}
+
+/** 一条口述标点口令 */
+export interface SpokenPunctuationMapping {
+  /** 说出的口令（英文不区分大小写） */
+  spoken: string;
+  /** 替换成的字符，可以是换行 */
+  symbol: string;
+}
+
+/** 多个云端识别服务的使用方式：依次回退 / 同时请求、最快的结果胜出 */
+export type RecognitionStrategy = "fallback" | "race";
+
+/** 本地 Whisper 的识别任务：按原语言转写 / 直接译为英文 */
+export type WhisperTask = "transcribe" | "translate";
+
+/** 云端识别重试配置 */
+export interface AsrRetryConfig {
+  /** 每个服务最多尝试次数（含首次请求），1 表示不重试 */
+  max_attempts: number;
+  /** 首次重试前的等待时间（毫秒），之后每次翻倍 */
+  initial_backoff_ms: number;
+  /** 单次等待的上限（毫秒） */
+  max_backoff_ms: number;
+  /** 等待时间的随机抖动比例（0-1） */
+  jitter: number;
+}
+
+/** 音频隐私配置 */
+export interface AudioPrivacyConfig {
+  /** 是否允许原始音频发送到云端识别服务，关闭后强制使用本地 Whisper */
+  allow_cloud: boolean;
+  /** 是否允许把音频写入磁盘（听写历史同步录音、命令识别的临时文件） */
+  allow_disk: boolean;
+  /** 临时音频文件保留时长（小时），0 表示不自动清理 */
+  temp_audio_retention_hours: number;
+}
+
+/** 本地说话人区分配置（实验性） */
+export interface DiarizationConfig {
+  /** 是否启用 */
+  enabled: boolean;
+  /** 最多区分的说话人数 */
+  max_speakers: number;
+  /** 视为同一说话人的相似度阈值（0-1） */
+  threshold: number;
+}
+
+/** 语音悬浮窗口配置 */
+export interface VoiceWindowConfig {
+  /** 是否记住手动拖动后的窗口位置 */
+  remember_position: boolean;
+  /** 是否跟随焦点应用所在的显示器 */
+  follow_active_monitor: boolean;
+  /** 麦克风录音时是否在每个显示器右上角显示红点指示 */
+  recording_indicator: boolean;
+}
+
+/** 听写历史配置 */
+export interface VoiceHistoryConfig {
+  /** 是否保存听写历史（默认启用） */
+  enabled: boolean;
+  /** 最多保留的听写条数 */
+  max_entries: number;
+  /** 同步到工作区文件夹的配置 */
+  workspace_sync?: VoiceHistorySyncConfig;
+}
+
+/** 听写历史同步到工作区的配置 */
+export interface VoiceHistorySyncConfig {
+  /** 是否把每次听写追加到默认工作区内按日期命名的 Markdown 文件 */
+  enabled: boolean;
+  /** 工作区内的文件夹（相对工作区根目录） */
+  folder: string;
+  /** 是否同时保存录音（WAV） */
+  include_audio: boolean;
+}
+
+/** 开头静音自动取消配置 */
+export interface NoSpeechConfig {
+  /** 是否启用（默认启用） */
+  enabled: boolean;
+  /** 开始录音后持续静音多久自动取消（秒） */
+  timeout_secs: number;
+}
+
+/** 免打扰时段配置 */
+export interface QuietHoursConfig {
+  /** 是否启用 */
+  enabled: boolean;
+  /** 免打扰时段列表 */
+  schedules?: QuietHoursSchedule[];
+  /** 免打扰时关闭交互音效 */
+  mute_sounds: boolean;
+  /** 免打扰时只复制到剪贴板，不模拟键盘输入 */
+  clipboard_output: boolean;
+}
+
+/** 免打扰时段 */
+export interface QuietHoursSchedule {
+  /** 开始时间（本地时间 HH:MM） */
+  start: string;
+  /** 结束时间（本地时间 HH:MM），早于开始时间表示跨越午夜 */
+  end: string;
+  /** 生效的星期（0 为周日，6 为周六），为空表示每天 */
+  weekdays?: number[];
+}
+
+/** 免打扰状态变化事件 */
+export const QUIET_MODE_EVENT = "voice-quiet-mode-changed";
+
+/** 免打扰状态 */
+export interface QuietModeStatus {
+  /** 当前是否处于免打扰时段 */
+  active: boolean;
+  /** 是否关闭交互音效 */
+  mute_sounds: boolean;
+  /** 是否只复制到剪贴板 */
+  clipboard_output: boolean;
+}
+
+/** 首尾静音裁剪配置 */
+export interface SilenceTrimConfig {
+  /** 是否启用（默认启用） */
+  enabled: boolean;
+  /** 静音阈值（dBFS），为空时使用当前设备的校准阈值，未校准则为 -50 */
+  threshold_dbfs?: number;
+  /** 两端保留的余量（毫秒） */
+  padding_ms: number;
+}
+
+/** 未选择设备时的校准键 */
+export const DEFAULT_CALIBRATION_DEVICE = "default";
+
+/** 麦克风电平校准结果 */
+export interface DeviceCalibration {
+  /** 建议增益（dB），录音结束后应用 */
+  gain_db: number;
+  /** 静音阈值（dBFS，已计入增益） */
+  silence_threshold_dbfs: number;
+  /** 环境噪声底（dBFS） */
+  noise_floor_dbfs: number;
+  /** 说话电平（dBFS） */
+  speech_level_dbfs: number;
+  /** 校准时间（毫秒时间戳） */
+  calibrated_at: number;
 }
 
 // ============ 麦克风设备类型 ============
@@ -149,11 +525,20 @@ export async function setDefaultAsrCredential(id: string): Promise<void> {
   return invoke("set_default_asr_credential", { id });
 }
 
-/** 测试 ASR 凭证连通性 */
-export async function testAsrCredential(
-  id: string,
-): Promise<{ success: boolean; message: string }> {
-  return invoke("test_asr_credential", { id });
+/** ASR 凭证测试结果 */
+export interface AsrHealthCheck {
+  success: boolean;
+  /** 结果说明（失败时为错误信息） */
+  message: string;
+  /** 识别耗时（毫秒），配置无效时为空 */
+  latency_ms: number | null;
+  /** 识别文本 */
+  text: string | null;
+}
+
+/** 用内置测试音频测试 ASR 凭证，返回耗时和识别文本 */
+export async function testAsrCredential(id: string): Promise<AsrHealthCheck> {
+  return invoke<AsrHealthCheck>("test_asr_credential", { id });
 }
 
 // ============ 语音输入配置命令 ============
@@ -170,6 +555,11 @@ export async function saveVoiceInputConfig(
   return invoke("save_voice_input_config", { voiceConfig: config });
 }
 
+/** 获取当前的免打扰状态 */
+export async function getQuietModeStatus(): Promise<QuietModeStatus> {
+  return invoke<QuietModeStatus>("get_quiet_mode_status");
+}
+
 /** 获取指令列表 */
 export async function getVoiceInstructions(): Promise<VoiceInstruction[]> {
   return invoke<VoiceInstruction[]>("get_voice_instructions");
@@ -193,12 +583,69 @@ export async function deleteVoiceInstruction(id: string): Promise<void> {
 export interface TranscribeResult {
   text: string;
   provider: string;
+  /** 识别出的语言（ISO 639-1 代码），无法判断时为 null */
+  language: string | null;
+  /** 听写历史记录 ID（未开启历史时为 null），润色时传回 */
+  history_id: string | null;
+  /** 输出队列占位 ID（只有听写会占位，识别结果为空时为 null），输出或放弃时必须传回 */
+  output_id: number | null;
+  /** 听写会话 ID（不经过听写会话的识别为 null），润色、输出时传回 */
+  session_id: string | null;
+}
+
+/** 音频格式 */
+export type AsrAudioFormat =
+  | "pcm"
+  | "wav"
+  | "mp3"
+  | "flac"
+  | "m4a"
+  | "ogg"
+  | "amr";
+
+/** ASR 服务能力 */
+export interface AsrCapabilities {
+  /** 是否支持边录边识别 */
+  streaming: boolean;
+  /** 识别结果是否带分段时间戳 */
+  timestamps: boolean;
+  /** 是否支持热词 */
+  hotwords: boolean;
+  /** 是否自动添加标点 */
+  punctuation: boolean;
+  /** 是否能较好地识别中英混说 */
+  code_switching: boolean;
+  /** 分段是否带说话人编号 */
+  diarization: boolean;
+  /** 单次请求支持的最长音频（秒），null 表示不限制 */
+  max_duration_secs: number | null;
+  /** 接受的音频格式，按偏好排列 */
+  formats: AsrAudioFormat[];
+  /** 接受的采样率，按偏好排列，为空表示不限制 */
+  sample_rates: number[];
+}
+
+/** 润色费用预估 */
+export interface PolishEstimate {
+  model: string;
+  input_tokens: number;
+  output_tokens: number;
+  /** 预估费用，模型没有价格信息时为 null */
+  cost: number | null;
+  currency: string | null;
+  over_limit: boolean;
 }
 
 /** 润色结果 */
 export interface PolishResult {
   text: string;
+  /** 指令输出按键时的动作脚本（text 为其中的文字），输出时传回 */
+  script: string | null;
   instruction_name: string;
+  /** 预估费用超出上限时未润色，text 为原文 */
+  over_budget: PolishEstimate | null;
+  /** 指令 A/B 测试记录 ID（使用了测试变体时才有），评价时传回 */
+  ab_trial_id: string | null;
 }
 
 /** 执行语音识别 */
@@ -214,14 +661,33 @@ export async function transcribeAudio(
   });
 }
 
-/** 润色文本 */
+/** 获取 ASR 服务能力（未指定凭证时使用默认凭证） */
+export async function getAsrCapabilities(
+  credentialId?: string,
+): Promise<AsrCapabilities> {
+  return invoke<AsrCapabilities>("get_asr_capabilities", { credentialId });
+}
+
+/**
+ * 润色文本（未指定指令时按识别出的语言选择指令，传入历史 ID 时记录润色结果）
+ *
+ * 预估费用超出上限时返回原文，`confirmed` 为 true 时跳过费用检查
+ */
 export async function polishVoiceText(
   text: string,
   instructionId?: string,
+  language?: string | null,
+  historyId?: string | null,
+  confirmed?: boolean,
+  sessionId?: string | null,
 ): Promise<PolishResult> {
   return invoke<PolishResult>("polish_voice_text", {
     text,
     instructionId,
+    language,
+    historyId,
+    confirmed,
+    sessionId,
   });
 }
 
@@ -235,12 +701,286 @@ export async function closeVoiceWindow(): Promise<void> {
   return invoke("close_voice_window");
 }
 
-/** 输出文本到系统 */
+/** 输出文本到系统（`actions` 为 true 时 text 是润色返回的动作脚本） */
 export async function outputVoiceText(
   text: string,
   mode?: "type" | "clipboard" | "both",
+  outputId?: number | null,
+  sessionId?: string | null,
+  actions?: boolean,
+): Promise<void> {
+  return invoke("output_voice_text", {
+    text,
+    mode,
+    outputId,
+    sessionId,
+    actions,
+  });
+}
+
+/** 输出队列中的听写 */
+export interface QueuedOutputInfo {
+  id: number;
+  /** 目标应用 */
+  target: string;
+  /** 是否已就绪，等待前面的听写输出 */
+  ready: boolean;
+  /** 是否因焦点离开目标应用而暂停 */
+  paused: boolean;
+  /** 尚未输出的字数（未就绪时为 0） */
+  chars: number;
+  /** 已等待的毫秒数 */
+  waited_ms: number;
+}
+
+/** 获取输出队列 */
+export async function getVoiceOutputQueue(): Promise<QueuedOutputInfo[]> {
+  return invoke<QueuedOutputInfo[]>("get_voice_output_queue");
+}
+
+/** 一条剪贴板历史 */
+export interface ClipboardEntry {
+  text: string;
+  /** 复制时间（毫秒时间戳） */
+  captured_at: number;
+}
+
+/** 获取剪贴板历史（最近在前） */
+export async function getClipboardHistory(): Promise<ClipboardEntry[]> {
+  return invoke<ClipboardEntry[]>("get_clipboard_history");
+}
+
+/** 删除一条剪贴板历史 */
+export async function deleteClipboardHistoryEntry(
+  index: number,
 ): Promise<void> {
-  return invoke("output_voice_text", { text, mode });
+  return invoke("delete_clipboard_history_entry", { index });
+}
+
+/** 清空剪贴板历史 */
+export async function clearClipboardHistory(): Promise<void> {
+  return invoke("clear_clipboard_history");
+}
+
+/** 取消尚未输出的听写，排在其后的听写继续输出 */
+export async function cancelVoiceOutput(id: number): Promise<boolean> {
+  return invoke<boolean>("cancel_voice_output", { id });
+}
+
+/** 焦点离开听写开始时的应用、输入暂停事件 */
+export const OUTPUT_PAUSED_EVENT = "voice-output-paused";
+
+/** 输入暂停事件负载 */
+export interface OutputPausedPayload {
+  id: number;
+  /** 听写开始时的目标应用 */
+  target: string;
+  /** 暂停时的焦点应用 */
+  focused: string | null;
+  /** 已输入的字数 */
+  typed: number;
+  /** 尚未输入的字数 */
+  remaining: number;
+}
+
+/** 焦点回到暂停时的应用后在该应用中继续输入 */
+export async function resumeVoiceOutput(id: number): Promise<boolean> {
+  return invoke<boolean>("resume_voice_output", { id });
+}
+
+/** 暂停的听写未输入的部分改为复制到剪贴板 */
+export async function copyPausedVoiceOutput(id: number): Promise<boolean> {
+  return invoke<boolean>("copy_paused_voice_output", { id });
+}
+
+/** 焦点在密码框等安全输入字段中、已改为复制到剪贴板事件 */
+export const SECURE_INPUT_EVENT = "voice-secure-input";
+
+/** 安全输入事件负载 */
+export interface SecureInputPayload {
+  /** 已复制到剪贴板的字数 */
+  chars: number;
+}
+
+// ============ 听写草稿确认命令 ============
+
+/** 用最近一次听写的录音重新识别（需开启输出前确认） */
+export async function retryVoiceDraft(): Promise<TranscribeResult> {
+  return invoke<TranscribeResult>("retry_voice_draft");
+}
+
+/** 确认草稿：关闭悬浮窗后输出文本（`actions` 为 true 时草稿是动作脚本） */
+export async function acceptVoiceDraft(
+  text: string,
+  mode?: "type" | "clipboard" | "both",
+  outputId?: number | null,
+  sessionId?: string | null,
+  actions?: boolean,
+): Promise<void> {
+  return invoke("accept_voice_draft", {
+    text,
+    mode,
+    outputId,
+    sessionId,
+    actions,
+  });
+}
+
+/** 放弃草稿，不输出任何内容 */
+export async function discardVoiceDraft(
+  outputId?: number | null,
+  sessionId?: string | null,
+): Promise<void> {
+  return invoke("discard_voice_draft", { outputId, sessionId });
+}
+
+// ============ 听写历史命令 ============
+
+/** 听写历史记录 */
+export interface VoiceHistoryEntry {
+  id: string;
+  /** 原始听写的 ID（重新润色生成的版本才有） */
+  parent_id: string | null;
+  /** 原始识别文本 */
+  raw_text: string;
+  /** 润色后的文本 */
+  text: string;
+  language: string | null;
+  provider: string | null;
+  /** 润色使用的指令 ID（未润色时为 null） */
+  instruction_id: string | null;
+  model: string | null;
+  created_at: number;
+  /** 听写各阶段的时间线（输出完成后写入） */
+  timeline?: SessionTimeline;
+}
+
+/** 获取最近的听写历史（最近在前），包含重新润色生成的版本 */
+export async function getVoiceHistory(
+  limit?: number,
+): Promise<VoiceHistoryEntry[]> {
+  return invoke<VoiceHistoryEntry[]>("get_voice_history", { limit });
+}
+
+/** 用其他指令（和模型）重新润色历史记录，返回新生成的版本 */
+export async function repolishVoiceHistory(
+  id: string,
+  instructionId: string,
+  model?: string,
+): Promise<VoiceHistoryEntry> {
+  return invoke<VoiceHistoryEntry>("repolish_voice_history", {
+    id,
+    instructionId,
+    model,
+  });
+}
+
+/** 删除听写历史记录（删除原始听写时连同其版本一起删除） */
+export async function deleteVoiceHistoryEntry(id: string): Promise<boolean> {
+  return invoke<boolean>("delete_voice_history_entry", { id });
+}
+
+/** 清空听写历史 */
+export async function clearVoiceHistory(): Promise<void> {
+  return invoke("clear_voice_history");
+}
+
+/** 存为提示词的听写：`text` 优先，否则取听写历史的润色结果 */
+export interface VoicePromptSource {
+  text?: string;
+  historyId?: string;
+  /** 提示词名称（未指定时取第一行开头） */
+  name?: string;
+}
+
+/** 把听写存为提示词库中的提示词（不启用） */
+export async function saveVoiceAsPrompt(
+  app: AppType,
+  source: VoicePromptSource,
+): Promise<Prompt> {
+  return invoke("save_voice_as_prompt", { app, ...source });
+}
+
+// ============ 指令 A/B 测试命令 ============
+
+/** 润色结果评价 */
+export type AbRating = "good" | "bad";
+
+/** 单个变体的统计 */
+export interface AbVariantStats {
+  instruction_id: string;
+  /** 润色次数 */
+  trials: number;
+  good: number;
+  bad: number;
+  /** 好评率（没有评价时为空） */
+  good_rate: number | null;
+}
+
+/** A/B 测试汇总 */
+export interface AbTestSummary {
+  variant_a: AbVariantStats;
+  variant_b: AbVariantStats;
+  /** 好评率显著更高的变体指令 ID（评价不足或差异不显著时为空） */
+  winner: string | null;
+}
+
+/** 评价一次 A/B 测试润色的结果 */
+export async function rateVoiceAbTrial(
+  trialId: string,
+  rating: AbRating,
+): Promise<boolean> {
+  return invoke("rate_voice_ab_trial", { trialId, rating });
+}
+
+/** 获取当前两个变体的 A/B 测试汇总（未配置两个不同的变体时为空） */
+export async function getVoiceAbSummary(): Promise<AbTestSummary | null> {
+  return invoke("get_voice_ab_summary");
+}
+
+/** 清空 A/B 测试记录 */
+export async function clearVoiceAbTrials(): Promise<void> {
+  return invoke("clear_voice_ab_trials");
+}
+
+// ============ 识别热词命令 ============
+
+/** 获取工作区的识别热词（未指定工作区时为默认工作区） */
+export async function getVoiceVocabulary(
+  workspaceId?: string,
+): Promise<string[]> {
+  return invoke("get_voice_vocabulary", { workspaceId });
+}
+
+/** 保存工作区的识别热词，返回去重整理后的热词 */
+export async function setVoiceVocabulary(
+  words: string[],
+  workspaceId?: string,
+): Promise<string[]> {
+  return invoke("set_voice_vocabulary", { workspaceId, words });
+}
+
+/** 工作区的听写默认值，ProxyCast 在前台时生效 */
+export interface WorkspaceVoiceDefaults {
+  /** 输出方式（为空时使用语音输入配置） */
+  output_mode: VoiceOutputMode | null;
+  /** 润色指令（为空时按语音输入配置选择） */
+  instruction_id: string | null;
+}
+
+/** 获取工作区的听写默认值（未指定工作区时为默认工作区） */
+export async function getWorkspaceVoiceDefaults(
+  workspaceId?: string,
+): Promise<WorkspaceVoiceDefaults> {
+  return invoke("get_workspace_voice_defaults", { workspaceId });
+}
+
+/** 保存工作区的听写默认值 */
+export async function setWorkspaceVoiceDefaults(
+  defaults: WorkspaceVoiceDefaults,
+  workspaceId?: string,
+): Promise<void> {
+  return invoke("set_workspace_voice_defaults", { workspaceId, defaults });
 }
 
 // ============ 录音控制命令 ============
@@ -249,8 +989,16 @@ export async function outputVoiceText(
 export interface RecordingStatus {
   /** 是否正在录音 */
   is_recording: boolean;
-  /** 当前音量级别（0-100） */
+  /** 当前音量级别（0-100，由 RMS dBFS 映射） */
   volume: number;
+  /** 均方根电平（dBFS） */
+  rms_dbfs: number;
+  /** 峰值电平（dBFS） */
+  peak_dbfs: number;
+  /** 当前是否削波 */
+  clipping: boolean;
+  /** 本次录音累计削波的采样数 */
+  clipped_samples: number;
   /** 录音时长（秒） */
   duration: number;
 }
@@ -276,8 +1024,8 @@ export async function stopRecording(): Promise<StopRecordingResult> {
 }
 
 /** 取消录音 */
-export async function cancelRecording(): Promise<void> {
-  return invoke("cancel_recording");
+export async function cancelRecording(sessionId?: string): Promise<void> {
+  return invoke("cancel_recording", { sessionId });
 }
 
 /** 获取录音状态 */
@@ -285,7 +1033,392 @@ export async function getRecordingStatus(): Promise<RecordingStatus> {
   return invoke<RecordingStatus>("get_recording_status");
 }
 
+// ============ 边录边识别 ============
+
+/** 流式识别中间结果事件 */
+export const PARTIAL_TRANSCRIPT_EVENT = "voice-transcript-partial";
+
+/** 流式识别中间结果 */
+export interface PartialTranscript {
+  /** 当前完整的识别文本（可能改写之前的内容） */
+  text: string;
+  /** 是否为最终结果 */
+  is_final: boolean;
+}
+
+/** 未检测到说话、录音已自动取消事件 */
+export const NO_SPEECH_EVENT = "voice-no-speech";
+
+/** 未检测到说话事件负载 */
+export interface NoSpeechPayload {
+  /** 持续静音的时长（秒） */
+  silence_secs: number;
+}
+
+/**
+ * 开始边录边识别
+ *
+ * 为本次听写创建会话，返回会话信息（含是否启用了流式识别）；不支持流式的服务只录音，
+ * 停止后整段识别。已有听写正在录音时拒绝
+ */
+export async function startStreamingTranscription(
+  deviceId?: string,
+  credentialId?: string,
+): Promise<VoiceSessionInfo> {
+  return invoke<VoiceSessionInfo>("start_streaming_transcription", {
+    deviceId,
+    credentialId,
+  });
+}
+
+/** 停止边录边识别并返回最终结果（未指定会话时停止正在录音的听写） */
+export async function stopStreamingTranscription(
+  sessionId?: string | null,
+): Promise<TranscribeResult> {
+  return invoke<TranscribeResult>("stop_streaming_transcription", {
+    sessionId,
+  });
+}
+
+// ============ 听写会话 ============
+
+/** 听写会话阶段 */
+export type VoiceSessionPhase =
+  | "recording"
+  | "transcribing"
+  | "completed"
+  | "cancelled"
+  | "failed";
+
+/** 听写会话 */
+export interface VoiceSessionInfo {
+  session_id: string;
+  phase: VoiceSessionPhase;
+  /** 是否启用了边录边识别 */
+  streaming: boolean;
+  /** 开始时间（毫秒时间戳） */
+  started_at: number;
+  /** 失败原因 */
+  error?: string;
+}
+
+/** 听写会话阶段变化事件 */
+export const SESSION_STATE_EVENT = "voice-session-state";
+
+/** 获取进行中的听写会话 */
+export async function getVoiceSessions(): Promise<VoiceSessionInfo[]> {
+  return invoke<VoiceSessionInfo[]>("get_voice_sessions");
+}
+
+// ============ 会话时间线 ============
+
+/** 听写阶段 */
+export type VoiceStage =
+  | "record_start"
+  | "record_stop"
+  | "asr_submit"
+  | "first_partial"
+  | "asr_done"
+  | "polish_start"
+  | "polish_done"
+  | "output_done";
+
+/** 阶段时间点 */
+export interface StageEvent {
+  stage: VoiceStage;
+  /** 发生时间（毫秒时间戳） */
+  at: number;
+  /** 距录音开始的毫秒数 */
+  elapsed_ms: number;
+}
+
+/** 一次听写的时间线 */
+export interface SessionTimeline {
+  id: string;
+  /** 开始时间（毫秒时间戳） */
+  started_at: number;
+  /** 使用的 ASR 服务 */
+  provider?: string;
+  events: StageEvent[];
+}
+
+/** 会话阶段事件 */
+export const SESSION_STAGE_EVENT = "voice-session-stage";
+
+/** 会话阶段事件负载 */
+export interface SessionStagePayload extends StageEvent {
+  session_id: string;
+}
+
+/** 获取最近听写的各阶段时间线（最近在前） */
+export async function getVoiceSessionTimelines(): Promise<SessionTimeline[]> {
+  return invoke<SessionTimeline[]>("get_voice_session_timelines");
+}
+
+/** 清空听写时间线历史 */
+export async function clearVoiceSessionTimelines(): Promise<void> {
+  return invoke("clear_voice_session_timelines");
+}
+
+// ============ 麦克风测试命令 ============
+
+/** 麦克风测试结果 */
+export interface MicrophoneTestResult {
+  /** 录音（WAV 格式，Base64 编码） */
+  audio_base64: string;
+  /** 采样率 */
+  sample_rate: number;
+  /** 整段录音的均方根电平（dBFS） */
+  rms_dbfs: number;
+  /** 电平统计（中位数约为环境噪声，90 分位约为说话电平） */
+  stats: CalibrationSample;
+  /** 削波的采样数 */
+  clipped_samples: number;
+  /** 是否几乎没有录到声音 */
+  silent: boolean;
+}
+
+/** 录制一段测试音频（默认 3 秒，最长 10 秒），返回录音和电平统计 */
+export async function testMicrophone(
+  seconds?: number,
+  deviceId?: string,
+): Promise<MicrophoneTestResult> {
+  return invoke<MicrophoneTestResult>("test_microphone", {
+    seconds,
+    deviceId,
+  });
+}
+
+/** 回放麦克风测试录音 */
+export function playMicrophoneTest(
+  result: MicrophoneTestResult,
+): HTMLAudioElement {
+  const audio = new Audio(`data:audio/wav;base64,${result.audio_base64}`);
+  audio.play().catch((err) => {
+    console.error("[麦克风测试] 回放失败:", err);
+  });
+  return audio;
+}
+
+// ============ 本地推理诊断命令 ============
+
+/** GPU 信息 */
+export interface GpuInfo {
+  /** 设备名称 */
+  name: string;
+  /** 显存总量（MB） */
+  total_memory_mb?: number;
+  /** 空闲显存（MB） */
+  free_memory_mb?: number;
+  /** 是否与 CPU 共享内存（Apple Silicon） */
+  unified_memory: boolean;
+}
+
+/** 识别速度测试结果 */
+export interface BenchmarkResult {
+  /** 测试音频时长（秒） */
+  audio_secs: number;
+  /** 模型加载耗时（秒） */
+  load_secs: number;
+  /** 识别耗时（秒） */
+  elapsed_secs: number;
+  /** 实时率：识别耗时 / 音频时长，小于 1 表示比实时更快 */
+  rtf: number;
+  /** 是否使用内置合成音频测得（实时率偏乐观） */
+  synthetic: boolean;
+}
+
+/** 本地推理算力诊断结果 */
+export interface ComputeDiagnostics {
+  /** 是否启用了 Metal 加速 */
+  metal: boolean;
+  /** 是否启用了 CUDA 加速 */
+  cuda: boolean;
+  /** 检测到的 GPU */
+  gpus: GpuInfo[];
+  /** CPU 逻辑核心数 */
+  cpu_threads: number;
+  /** whisper.cpp 报告的系统信息 */
+  system_info: string;
+  /** 测速使用的 Whisper 模型 */
+  model?: WhisperModelSize;
+  /** 识别速度测试结果 */
+  benchmark?: BenchmarkResult;
+}
+
+/**
+ * 获取本地推理算力诊断信息，`benchmark` 为 true 时测量本地 Whisper 识别速度
+ *
+ * `recordMs` 不为空时先录制用户朗读的一段话用于测速，否则使用内置合成音频
+ */
+export async function getComputeDiagnostics(
+  benchmark?: boolean,
+  recordMs?: number,
+  deviceId?: string,
+): Promise<ComputeDiagnostics> {
+  return invoke<ComputeDiagnostics>("get_compute_diagnostics", {
+    benchmark,
+    recordMs,
+    deviceId,
+  });
+}
+
+// ============ 批量转写命令 ============
+
+/** 批量转写进度事件 */
+export const BATCH_TRANSCRIBE_EVENT = "voice-batch-transcribe-progress";
+
+/** 批量转写进度事件负载 */
+export interface BatchTranscribeProgress {
+  /** 文件序号（从 0 开始） */
+  index: number;
+  total: number;
+  path: string;
+  status: "transcribing" | "done" | "failed";
+  /** 失败原因 */
+  error?: string;
+}
+
+/** 单个文件的转写结果 */
+export interface FileTranscription {
+  path: string;
+  /** 识别结果（失败时为 null） */
+  result: TranscribeResult | null;
+  error: string | null;
+}
+
+/** 批量转写音频文件（wav / mp3 / m4a / ogg / flac），结果与 `paths` 顺序一致 */
+export async function transcribeFiles(
+  paths: string[],
+  credentialId?: string,
+): Promise<FileTranscription[]> {
+  return invoke<FileTranscription[]>("transcribe_files", {
+    paths,
+    credentialId,
+  });
+}
+
+// ============ 本地模型命令 ============
+
+/** 可下载的本地识别模型 */
+export interface LocalModelInfo {
+  id: string;
+  engine: LocalAsrEngine;
+  name: string;
+  /** 大致体积（MB） */
+  size_mb: number;
+  downloaded: boolean;
+  downloading: boolean;
+}
+
+/** 模型下载进度事件 */
+export const MODEL_DOWNLOAD_EVENT = "voice-model-download-progress";
+
+/** 模型下载进度事件负载 */
+export interface ModelDownloadProgress {
+  id: string;
+  /** 当前下载的文件 */
+  file: string;
+  /** 当前文件已下载字节数 */
+  downloaded: number;
+  /** 当前文件总字节数 */
+  total?: number;
+  /** 是否全部下载完成 */
+  done: boolean;
+}
+
+/** 列出可下载的本地识别模型 */
+export async function listLocalAsrModels(): Promise<LocalModelInfo[]> {
+  return invoke<LocalModelInfo[]>("list_local_asr_models");
+}
+
+/** 下载本地识别模型，完成后返回 */
+export async function downloadLocalAsrModel(id: string): Promise<void> {
+  return invoke("download_local_asr_model", { id });
+}
+
+/** 删除已下载的本地识别模型 */
+export async function deleteLocalAsrModel(id: string): Promise<void> {
+  return invoke("delete_local_asr_model", { id });
+}
+
+// ============ 电平校准命令 ============
+
+/** 环境噪声录音时长（毫秒） */
+export const CALIBRATION_AMBIENT_MS = 2000;
+
+/** 一段校准录音的统计结果 */
+export interface CalibrationSample {
+  /** 帧 RMS 的中位数（dBFS） */
+  median_dbfs: number;
+  /** 帧 RMS 的 90 分位（dBFS） */
+  loud_dbfs: number;
+  /** 峰值电平（dBFS） */
+  peak_dbfs: number;
+  /** 是否出现削波 */
+  clipping: boolean;
+  /** 录音时长（秒） */
+  duration_secs: number;
+}
+
+/** 录制一段校准音频（默认 2 秒） */
+export async function recordCalibrationSample(
+  deviceId?: string,
+  durationMs?: number,
+): Promise<CalibrationSample> {
+  return invoke<CalibrationSample>("record_calibration_sample", {
+    deviceId,
+    durationMs,
+  });
+}
+
+/** 计算并保存设备校准结果 */
+export async function saveMicrophoneCalibration(
+  deviceId: string | undefined,
+  ambient: CalibrationSample,
+  speech: CalibrationSample,
+): Promise<DeviceCalibration> {
+  return invoke<DeviceCalibration>("save_microphone_calibration", {
+    deviceId,
+    ambient,
+    speech,
+  });
+}
+
+/** 清除设备校准结果 */
+export async function clearMicrophoneCalibration(
+  deviceId?: string,
+): Promise<void> {
+  return invoke("clear_microphone_calibration", { deviceId });
+}
+
 /** 打开带预填文本的输入框 */
 export async function openInputWithText(text: string): Promise<void> {
   return invoke("open_input_with_text", { text });
 }
+
+// ============ ASR 用量命令 ============
+
+/** 凭证在一个识别服务上的用量汇总 */
+export interface AsrUsageStats {
+  credential_id: string;
+  /** 识别服务名称 */
+  provider: string;
+  /** 调用次数 */
+  calls: number;
+  /** 成功次数 */
+  succeeded: number;
+  /** 失败次数（其余为取消） */
+  failed: number;
+  /** 成功识别的音频时长（秒） */
+  audio_secs: number;
+  /** 成功调用的平均耗时（毫秒） */
+  avg_latency_ms: number;
+}
+
+/** 按凭证汇总 ASR 用量，`since` 为毫秒时间戳，默认从本月第一天起 */
+export async function getAsrUsageStats(
+  since?: number,
+): Promise<AsrUsageStats[]> {
+  return invoke<AsrUsageStats[]>("get_asr_usage_stats", { since });
+}
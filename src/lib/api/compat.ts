@@ -0,0 +1,35 @@
+import { safeInvoke } from "@/lib/dev-bridge";
+
+// ========== 类型定义 ==========
+
+/** 数据来自更新版本的情况 */
+export interface NewerVersion {
+  /** 数据记录的版本 */
+  found: number;
+  /** 当前程序支持的版本 */
+  supported: number;
+}
+
+/** 数据兼容状态 */
+export interface DataCompatibility {
+  /** 数据库来自更新的版本（已只读打开） */
+  database: NewerVersion | null;
+  /** 配置文件来自更新的版本（已拒绝写入） */
+  config: NewerVersion | null;
+}
+
+// ========== API 函数 ==========
+
+/**
+ * 获取数据库和配置文件的版本兼容状态
+ */
+export async function getDataCompatibility(): Promise<DataCompatibility> {
+  return safeInvoke("get_data_compatibility");
+}
+
+/**
+ * 导出数据库和配置文件的副本到指定目录，返回导出的文件路径
+ */
+export async function exportDataSnapshot(dir: string): Promise<string[]> {
+  return safeInvoke("export_data_snapshot", { dir });
+}
@@ -0,0 +1,46 @@
+import { safeInvoke } from "@/lib/dev-bridge";
+
+// ========== 类型定义 ==========
+
+export type CheckStatus = "pass" | "warn" | "fail";
+
+export type DoctorCheckId =
+  | "port"
+  | "config"
+  | "database"
+  | "credentials"
+  | "microphone"
+  | "accessibility"
+  | "local_model";
+
+export interface DoctorCheck {
+  id: DoctorCheckId;
+  name: string;
+  status: CheckStatus;
+  message: string;
+  /** 修复建议 */
+  hint?: string;
+  /** 详细信息（如不可用的凭证） */
+  details?: string[];
+}
+
+export interface DoctorReport {
+  /** 总体状态（最严重的检查结果） */
+  status: CheckStatus;
+  passed: number;
+  warned: number;
+  failed: number;
+  checks: DoctorCheck[];
+  checked_at: string;
+}
+
+// ========== API 函数 ==========
+
+/**
+ * 运行自检
+ *
+ * live 为 true 时对启用了健康检查的凭证发起实际请求，否则只汇总最近记录的健康状态
+ */
+export async function runDoctor(live?: boolean): Promise<DoctorReport> {
+  return safeInvoke("doctor", { live });
+}
@@ -0,0 +1,45 @@
+import { safeInvoke } from "@/lib/dev-bridge";
+
+// ========== 类型定义 ==========
+
+/** 初始化时机：启动时 / 首次使用时 */
+export type InitPhase = "eager" | "deferred";
+
+export interface SubsystemTiming {
+  name: string;
+  phase: InitPhase;
+  /** 开始初始化的时间（距进程启动，毫秒） */
+  started_ms: number;
+  duration_ms: number;
+}
+
+export interface StartupProfile {
+  /** 启动流程完成的时间（距进程启动，毫秒） */
+  ready_ms: number | null;
+  /** 启动时初始化的总耗时（毫秒） */
+  eager_total_ms: number;
+  subsystems: SubsystemTiming[];
+}
+
+/** 支持预热的延迟初始化子系统 */
+export type DeferredSubsystem = "model_registry" | "local_asr";
+
+// ========== API 函数 ==========
+
+/**
+ * 获取启动耗时报告
+ */
+export async function getStartupProfile(): Promise<StartupProfile> {
+  return safeInvoke("get_startup_profile");
+}
+
+/**
+ * 预热延迟初始化的子系统
+ *
+ * 在后台提前完成初始化，避免首次使用时等待
+ */
+export async function warmUpSubsystem(
+  subsystem: DeferredSubsystem,
+): Promise<void> {
+  return safeInvoke("warm_up_subsystem", { subsystem });
+}
@@ -24,6 +24,10 @@ export interface RequestLog {
   is_streaming: boolean;
   credential_id?: string;
   retry_count: number;
+  first_token_ms?: number;
+  tokens_per_second?: number;
+  /** 发起请求的客户端密钥 ID（使用主密钥时为空） */
+  client_key_id?: string;
 }
 
 export interface StatsSummary {
@@ -38,6 +42,9 @@ export interface StatsSummary {
   total_input_tokens: number;
   total_output_tokens: number;
   total_tokens: number;
+  avg_first_token_ms?: number;
+  p95_first_token_ms?: number;
+  avg_tokens_per_second?: number;
 }
 
 export interface ProviderStats {
@@ -53,6 +60,9 @@ export interface ProviderStats {
   total_input_tokens: number;
   total_output_tokens: number;
   total_tokens: number;
+  avg_first_token_ms?: number;
+  p95_first_token_ms?: number;
+  avg_tokens_per_second?: number;
 }
 
 export interface ModelStats {
@@ -68,6 +78,14 @@ export interface ModelStats {
   total_input_tokens: number;
   total_output_tokens: number;
   total_tokens: number;
+  avg_first_token_ms?: number;
+  p95_first_token_ms?: number;
+  avg_tokens_per_second?: number;
+}
+
+export interface ProviderModelStats extends StatsSummary {
+  provider?: string;
+  model: string;
 }
 
 export interface TokenStatsSummary {
@@ -118,12 +136,155 @@ export interface PeriodTokenStats {
   avg_output_tokens: number;
 }
 
+export interface PostmortemBundle {
+  request_id: string;
+  created_at: string;
+  endpoint: string;
+  original_model: string;
+  resolved_model: string;
+  provider?: string;
+  credential_id?: string;
+  request: unknown;
+  /** request 是否为实际发往上游的请求体，false 时为客户端请求体 */
+  request_is_upstream: boolean;
+  request_truncated: boolean;
+  upstream_status: number;
+  response_headers: Record<string, string>;
+  response_body: string;
+  response_truncated: boolean;
+  route_trace: string[];
+  retry_count: number;
+  duration_ms: number;
+}
+
 export interface TimeRangeParam {
   start?: string;
   end?: string;
   preset?: "1h" | "24h" | "7d" | "30d";
 }
 
+export interface DashboardFilter {
+  provider?: string;
+  model?: string;
+  status?: RequestStatus;
+}
+
+export interface HourlyRequestStats {
+  hour: string;
+  total_requests: number;
+  failed_requests: number;
+  error_rate: number;
+}
+
+export interface ErrorRateStats {
+  total_requests: number;
+  failed_requests: number;
+  timeout_requests: number;
+  cancelled_requests: number;
+  error_rate: number;
+}
+
+export interface LatencyPercentiles {
+  count: number;
+  p50_ms?: number;
+  p95_ms?: number;
+}
+
+export interface ModelUsage {
+  model: string;
+  requests: number;
+  input_tokens: number;
+  output_tokens: number;
+  error_rate: number;
+}
+
+export interface ProviderCost {
+  provider: string;
+  requests: number;
+  input_tokens: number;
+  output_tokens: number;
+  /** 各货币单位的费用 */
+  costs: Record<string, number>;
+  /** 模型没有价格信息、未计入费用的请求数 */
+  unpriced_requests: number;
+}
+
+/** 按客户端密钥汇总的用量 */
+export interface ClientUsage {
+  /** 客户端密钥 ID，使用主密钥的请求为空 */
+  client_key_id: string | null;
+  /** 显示名称（主密钥为"主机"） */
+  name: string;
+  requests: number;
+  failed_requests: number;
+  input_tokens: number;
+  output_tokens: number;
+  /** 各货币单位的费用 */
+  costs: Record<string, number>;
+  /** 模型没有价格信息、未计入费用的请求数 */
+  unpriced_requests: number;
+}
+
+/** 上游响应异常类型 */
+export type AnomalyKind =
+  | "empty_response"
+  | "repeated_tokens"
+  | "truncated_json"
+  | "latency_spike";
+
+export interface AnomalyRecord {
+  request_id: string;
+  timestamp: string;
+  provider: string;
+  model: string;
+  credential_id?: string | null;
+  kind: AnomalyKind;
+  detail: string;
+}
+
+export interface ProviderAnomalyStats {
+  provider: string;
+  /** 检测窗口内的异常数 */
+  recent_anomalies: number;
+  by_kind: Partial<Record<AnomalyKind, number>>;
+  total_anomalies: number;
+  /** 窗口内异常数达到阈值，视为降级 */
+  degraded: boolean;
+  median_latency_ms?: number | null;
+  last_anomaly?: AnomalyRecord | null;
+}
+
+export interface ProviderAnomalyReport {
+  providers: ProviderAnomalyStats[];
+  recent: AnomalyRecord[];
+}
+
+/** 上游连接复用统计（每个 Provider 的 HTTP 客户端一条） */
+export interface ConnectionStats {
+  client: string;
+  requests: number;
+  /** 未收到响应的请求数 */
+  failed_requests: number;
+  /** 新建的连接数（每个都经过完整的 TCP + TLS 握手） */
+  new_connections: number;
+  failed_connections: number;
+  reused_requests: number;
+  /** 连接复用率（0-1） */
+  reuse_rate: number;
+  http2_responses: number;
+  http1_responses: number;
+}
+
+/** 管道步骤的自身开销（不含等待上游），单位为微秒 */
+export interface StepOverhead {
+  step: string;
+  /** 统计窗口内的样本数 */
+  count: number;
+  p50_us: number;
+  p95_us: number;
+  max_us: number;
+}
+
 // ========== 请求日志 API ==========
 
 export async function getRequestLogs(params?: {
@@ -165,6 +326,84 @@ export async function getStatsByModel(
   return safeInvoke("get_stats_by_model", { time_range: timeRange });
 }
 
+export async function getStatsByProviderModel(
+  timeRange?: TimeRangeParam,
+): Promise<ProviderModelStats[]> {
+  return safeInvoke("get_stats_by_provider_model", { time_range: timeRange });
+}
+
+// ========== 监控面板 API ==========
+
+export async function getRequestsPerHour(
+  timeRange?: TimeRangeParam,
+  filter?: DashboardFilter,
+): Promise<HourlyRequestStats[]> {
+  return safeInvoke("get_requests_per_hour", { timeRange, filter });
+}
+
+export async function getErrorRate(
+  timeRange?: TimeRangeParam,
+  filter?: DashboardFilter,
+): Promise<ErrorRateStats> {
+  return safeInvoke("get_error_rate", { timeRange, filter });
+}
+
+export async function getLatencyPercentiles(
+  timeRange?: TimeRangeParam,
+  filter?: DashboardFilter,
+): Promise<LatencyPercentiles> {
+  return safeInvoke("get_latency_percentiles", { timeRange, filter });
+}
+
+export async function getTopModels(
+  timeRange?: TimeRangeParam,
+  filter?: DashboardFilter,
+  limit?: number,
+): Promise<ModelUsage[]> {
+  return safeInvoke("get_top_models", { timeRange, filter, limit });
+}
+
+export async function getCostByProvider(
+  timeRange?: TimeRangeParam,
+  filter?: DashboardFilter,
+): Promise<ProviderCost[]> {
+  return safeInvoke("get_cost_by_provider", { timeRange, filter });
+}
+
+export async function getUsageByClient(
+  timeRange?: TimeRangeParam,
+  filter?: DashboardFilter,
+): Promise<ClientUsage[]> {
+  return safeInvoke("get_usage_by_client", { timeRange, filter });
+}
+
+export async function getProviderAnomalies(
+  provider?: string,
+  limit?: number,
+): Promise<ProviderAnomalyReport> {
+  return safeInvoke("get_provider_anomalies", { provider, limit });
+}
+
+export async function clearProviderAnomalies(): Promise<void> {
+  return safeInvoke("clear_provider_anomalies");
+}
+
+export async function getConnectionStats(): Promise<ConnectionStats[]> {
+  return safeInvoke("get_connection_stats");
+}
+
+export async function clearConnectionStats(): Promise<void> {
+  return safeInvoke("clear_connection_stats");
+}
+
+export async function getPipelineOverhead(): Promise<StepOverhead[]> {
+  return safeInvoke("get_pipeline_overhead");
+}
+
+export async function clearPipelineOverhead(): Promise<void> {
+  return safeInvoke("clear_pipeline_overhead");
+}
+
 // ========== Token 统计 API ==========
 
 export async function getTokenSummary(
@@ -190,3 +429,21 @@ export async function getTokenStatsByDay(
 ): Promise<PeriodTokenStats[]> {
   return safeInvoke("get_token_stats_by_day", { days });
 }
+
+// ========== 失败请求诊断包 API ==========
+
+export async function getRequestPostmortem(
+  requestId: string,
+): Promise<PostmortemBundle> {
+  return safeInvoke("get_request_postmortem", { requestId });
+}
+
+export async function listRequestPostmortems(
+  limit?: number,
+): Promise<PostmortemBundle[]> {
+  return safeInvoke("list_request_postmortems", { limit });
+}
+
+export async function clearRequestPostmortems(): Promise<void> {
+  return safeInvoke("clear_request_postmortems");
+}
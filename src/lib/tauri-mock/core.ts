@@ -396,10 +396,36 @@ const defaultMocks: Record<string, any> = {
   get_stats_summary: () => ({ summary: {} }),
   get_stats_by_provider: () => ({ stats: [] }),
   get_stats_by_model: () => ({ stats: [] }),
+  get_stats_by_provider_model: () => [],
+  get_requests_per_hour: () => [],
+  get_error_rate: () => ({
+    total_requests: 0,
+    failed_requests: 0,
+    timeout_requests: 0,
+    cancelled_requests: 0,
+    error_rate: 0,
+  }),
+  get_latency_percentiles: () => ({ count: 0 }),
+  get_top_models: () => [],
+  get_cost_by_provider: () => [],
+  get_usage_by_client: () => [],
+  get_provider_anomalies: () => ({ providers: [], recent: [] }),
+  clear_provider_anomalies: () => undefined,
   get_token_summary: () => ({ summary: {} }),
   get_token_stats_by_provider: () => ({ stats: [] }),
   get_token_stats_by_model: () => ({ stats: [] }),
   get_token_stats_by_day: () => ({ stats: [] }),
+  get_request_postmortem: () => null,
+  list_request_postmortems: () => [],
+  clear_request_postmortems: () => undefined,
+  doctor: () => ({
+    status: "pass",
+    passed: 0,
+    warned: 0,
+    failed: 0,
+    checks: [],
+    checked_at: new Date().toISOString(),
+  }),
 
   // Routes 相关
   get_available_routes: () => ({ routes: [] }),
@@ -520,7 +546,20 @@ const defaultMocks: Record<string, any> = {
 
   // Log 相关
   get_logs: () => [],
+  get_request_trace: () => [],
   clear_logs: () => ({}),
+  get_log_levels: () => ({
+    default: "info",
+    modules: { pipeline: "info", providers: "info", voice: "info" },
+    overridden: [],
+  }),
+  set_log_level: () => ({
+    default: "info",
+    modules: { pipeline: "info", providers: "info", voice: "info" },
+    overridden: [],
+  }),
+  open_log_file: () => ({}),
+  export_log_file: (args: any) => args?.dest ?? "",
 
   // Test 相关
   test_api: () => ({ success: true, status: 200, body: "", time_ms: 0 }),
@@ -0,0 +1,22 @@
+/**
+ * @file recording-indicator.tsx
+ * @description 录音指示独立窗口页面
+ *
+ * 麦克风录音期间显示在每个显示器右上角的红点，由后端按录音状态创建和关闭。
+ *
+ * input: 无
+ * output: 红点
+ * pos: pages 层，独立 Tauri 窗口
+ */
+
+import "./recording-indicator.css";
+
+export function RecordingIndicatorPage() {
+  return (
+    <div className="recording-indicator" title="正在录音">
+      <div className="recording-indicator-dot" />
+    </div>
+  );
+}
+
+export default RecordingIndicatorPage;
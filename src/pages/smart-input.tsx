@@ -15,11 +15,30 @@ import {
   Mic,
   Loader2,
   Square,
+  MicOff,
+  Check,
+  RotateCcw,
+  Sparkles,
+  ThumbsUp,
+  ThumbsDown,
+  ClipboardList,
 } from "lucide-react";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { useVoiceSound } from "@/hooks/useVoiceSound";
+import type { TranscribeResult } from "@/lib/api/asrProvider";
+import type { PartialTranscript } from "@/lib/api/asrProvider";
+import type { PolishEstimate } from "@/lib/api/asrProvider";
+import type { AbRating } from "@/lib/api/asrProvider";
+import type { ClipboardEntry } from "@/lib/api/asrProvider";
+import type { QuietModeStatus } from "@/lib/api/asrProvider";
 import "./smart-input.css";
 
+/** 快速插入时显示的剪贴板历史条数 */
+const CLIPBOARD_QUICK_INSERT_COUNT = 3;
+
+/** 剪贴板历史标签上显示的字数 */
+const CLIPBOARD_LABEL_CHARS = 12;
+
 // ProxyCast Logo组件
 function Logo() {
   return (
@@ -78,7 +97,123 @@ function getInstructionIdFromUrl(): string | null {
 }
 
 /** 语音状态 */
-type VoiceState = "idle" | "recording" | "transcribing" | "polishing";
+type VoiceState =
+  | "idle"
+  | "recording"
+  | "transcribing"
+  | "polishing"
+  | "no_speech"
+  | "review";
+
+/** 因费用超出上限而未润色、等待确认的听写 */
+interface PendingPolish {
+  transcribeResult: TranscribeResult;
+  translateInstructionId: string | null;
+}
+
+/** 预估费用的显示文本 */
+function formatCost(estimate: PolishEstimate) {
+  const symbol = estimate.currency === "CNY" ? "¥" : "$";
+  return `${symbol}${(estimate.cost ?? 0).toFixed(4)}`;
+}
+
+/**
+ * 释放听写在输出队列中的占位
+ *
+ * 文字填入悬浮窗输入框、不输出到其他应用时调用，避免阻塞后面的听写
+ */
+async function releaseOutput(outputId: number | null) {
+  if (outputId == null) return;
+  try {
+    const { cancelVoiceOutput } = await import("@/lib/api/asrProvider");
+    await cancelVoiceOutput(outputId);
+  } catch (err) {
+    console.error("[语音输出] 释放输出占位失败:", err);
+  }
+}
+
+/**
+ * 按配置润色识别结果
+ *
+ * 传入翻译指令时使用翻译指令，否则在启用润色时按识别出的语言选择指令。
+ * 润色失败时返回原始文本。同时返回是否需要在输出前确认，费用超出上限
+ * 且配置为确认后润色时的预估（`confirmed` 为 true 时跳过费用检查），
+ * 使用了 A/B 测试变体时的测试记录 ID，以及指令输出按键时的动作脚本
+ */
+async function polishTranscript(
+  transcribeResult: TranscribeResult,
+  translateInstructionId: string | null,
+  onPolishing: () => void,
+  confirmed = false,
+): Promise<{
+  text: string;
+  script: string | null;
+  review: boolean;
+  overBudget: PolishEstimate | null;
+  abTrialId: string | null;
+}> {
+  const { polishVoiceText, getVoiceInputConfig } = await import(
+    "@/lib/api/asrProvider"
+  );
+
+  let text = transcribeResult.text;
+  let script: string | null = null;
+  let review = false;
+  let overBudget: PolishEstimate | null = null;
+  let abTrialId: string | null = null;
+  try {
+    const config = await getVoiceInputConfig();
+    review = !!config.output.review_before_output;
+    const confirmOverBudget = config.processor.cost_limit?.action === "confirm";
+    console.log("[语音输入] 润色配置:", {
+      polish_enabled: config.processor.polish_enabled,
+      polish_model: config.processor.polish_model,
+      default_instruction_id: config.processor.default_instruction_id,
+      translateInstructionId,
+      review,
+    });
+
+    if (translateInstructionId) {
+      // 翻译模式：使用指定的翻译指令
+      console.log("[语音输入] 翻译模式，使用指令:", translateInstructionId);
+      onPolishing();
+      const polished = await polishVoiceText(
+        transcribeResult.text,
+        translateInstructionId,
+        undefined,
+        transcribeResult.history_id,
+        confirmed,
+        transcribeResult.session_id,
+      );
+      console.log("[语音输入] 翻译完成:", polished.text);
+      text = polished.text;
+      script = polished.script;
+      if (confirmOverBudget) overBudget = polished.over_budget;
+    } else if (config.processor.polish_enabled) {
+      // 普通模式：按识别出的语言选择指令，未配置时使用默认润色
+      console.log("[语音输入] 进入润色模式分支");
+      onPolishing();
+      const polished = await polishVoiceText(
+        transcribeResult.text,
+        undefined,
+        transcribeResult.language,
+        transcribeResult.history_id,
+        confirmed,
+        transcribeResult.session_id,
+      );
+      console.log("[语音输入] 润色完成:", polished.text);
+      text = polished.text;
+      script = polished.script;
+      abTrialId = polished.ab_trial_id;
+      if (confirmOverBudget) overBudget = polished.over_budget;
+    } else {
+      console.log("[语音输入] 润色未启用，直接使用原始文本");
+    }
+  } catch (e) {
+    console.error("[语音润色] 失败:", e);
+  }
+  return { text, script, review, overBudget, abTrialId };
+}
 
 export function SmartInputPage() {
   const [imagePath, setImagePath] = useState<string | null>(null);
@@ -86,33 +221,85 @@ export function SmartInputPage() {
   const [isLoading, setIsLoading] = useState(false);
   const [voiceState, setVoiceState] = useState<VoiceState>("idle");
   const [voiceMode, setVoiceMode] = useState(false);
+  // 边录边识别的中间结果
+  const [partialText, setPartialText] = useState("");
+  // 等待确认的听写草稿
+  const [draftText, setDraftText] = useState("");
+  // 润色费用超出上限时的预估，以及等待确认润色的听写
+  const [costEstimate, setCostEstimate] = useState<PolishEstimate | null>(
+    null,
+  );
+  const pendingPolishRef = useRef<PendingPolish | null>(null);
+  // 指令 A/B 测试中等待评价的润色
+  const [abTrialId, setAbTrialId] = useState<string | null>(null);
+  // 草稿在输出队列中的占位，确认或放弃时传回
+  const outputIdRef = useRef<number | null>(null);
+  // 草稿是否为动作脚本（指令输出按键时），确认时传回
+  const draftActionsRef = useRef(false);
+  // 本次听写的会话 ID，停止、确认或放弃时传回
+  const sessionIdRef = useRef<string | null>(null);
   const [soundEnabled, setSoundEnabled] = useState(true);
+  const [quietMuted, setQuietMuted] = useState(false);
   const [translateMode, setTranslateMode] = useState(false);
   const [translateInstructionId, setTranslateInstructionId] = useState<
     string | null
   >(null);
   const inputRef = useRef<HTMLTextAreaElement>(null);
   const [errorMsg, setErrorMsg] = useState<string | null>(null);
+  // 剪贴板历史快速插入
+  const [clipboardEnabled, setClipboardEnabled] = useState(false);
+  const [clipboardEntries, setClipboardEntries] = useState<
+    ClipboardEntry[] | null
+  >(null);
 
   // 追踪是否已经从 URL 初始化过语音模式
   const voiceModeInitializedRef = useRef(false);
 
   // 语音音效
-  const { playStartSound, playStopSound } = useVoiceSound(soundEnabled);
+  const { playStartSound, playStopSound } = useVoiceSound(
+    soundEnabled && !quietMuted,
+  );
 
   // 加载音效配置
   useEffect(() => {
     (async () => {
       try {
-        const { getVoiceInputConfig } = await import("@/lib/api/asrProvider");
+        const { getVoiceInputConfig, getQuietModeStatus } = await import(
+          "@/lib/api/asrProvider"
+        );
         const config = await getVoiceInputConfig();
         setSoundEnabled(config.sound_enabled);
+        setClipboardEnabled(config.clipboard_history?.enabled ?? false);
+        // 免打扰时段内关闭音效
+        const quiet = await getQuietModeStatus();
+        setQuietMuted(quiet.mute_sounds);
       } catch (err) {
         console.error("[语音输入] 加载音效配置失败:", err);
       }
     })();
   }, []);
 
+  // 免打扰状态变化时立即切换音效
+  useEffect(() => {
+    const setupQuietModeListener = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        const { QUIET_MODE_EVENT } = await import("@/lib/api/asrProvider");
+        return await listen<QuietModeStatus>(QUIET_MODE_EVENT, (event) => {
+          setQuietMuted(event.payload.mute_sounds);
+        });
+      } catch (err) {
+        console.error("[语音输入] 监听免打扰状态失败:", err);
+        return () => {};
+      }
+    };
+
+    const unlistenPromise = setupQuietModeListener();
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
   // 显示错误提示
   const showError = useCallback((msg: string) => {
     setErrorMsg(msg);
@@ -129,13 +316,19 @@ export function SmartInputPage() {
     setVoiceMode(true);
     setVoiceState("recording");
     setInputValue(""); // 清空之前的输入
+    setPartialText("");
+    setCostEstimate(null);
+    pendingPolishRef.current = null;
 
     // 播放开始录音音效
     playStartSound();
 
     try {
-      const { startRecording, getVoiceInputConfig, cancelRecording } =
-        await import("@/lib/api/asrProvider");
+      const {
+        startStreamingTranscription,
+        getVoiceInputConfig,
+        cancelRecording,
+      } = await import("@/lib/api/asrProvider");
 
       // 先尝试取消任何正在进行的录音（可能是设置页面的测试没有停止）
       try {
@@ -148,8 +341,16 @@ export function SmartInputPage() {
       // 获取配置中的设备 ID
       const config = await getVoiceInputConfig();
       console.log("[语音输入] 使用设备ID:", config.selected_device_id);
-      await startRecording(config.selected_device_id);
-      console.log("[语音输入] 开始录音成功");
+      const session = await startStreamingTranscription(
+        config.selected_device_id,
+      );
+      sessionIdRef.current = session.session_id;
+      console.log(
+        "[语音输入] 开始录音成功，会话:",
+        session.session_id,
+        "边录边识别:",
+        session.streaming,
+      );
     } catch (err: any) {
       console.error("[语音输入] 开始录音失败:", err);
       // 检查错误信息是否与权限有关，或者直接给通用提示
@@ -293,17 +494,18 @@ export function SmartInputPage() {
       playStopSoundRef.current();
 
       setVoiceState("transcribing");
+      // 润色等后续步骤失败时释放输出占位
+      let pendingOutputId: number | null = null;
       try {
-        const {
-          stopRecording,
-          transcribeAudio,
-          polishVoiceText,
-          getVoiceInputConfig,
-        } = await import("@/lib/api/asrProvider");
+        const { stopStreamingTranscription } = await import(
+          "@/lib/api/asrProvider"
+        );
 
-        let result;
+        let transcribeResult;
         try {
-          result = await stopRecording();
+          transcribeResult = await stopStreamingTranscription(
+            sessionIdRef.current,
+          );
         } catch (recordingErr: any) {
           // 如果停止录音本身失败（例如后端没在录音，或者设备断开），强制重置
           console.error("停止录音异常:", recordingErr);
@@ -316,80 +518,55 @@ export function SmartInputPage() {
           setVoiceMode(false);
           return;
         }
+        pendingOutputId = transcribeResult.output_id;
+        console.log("[语音识别] 结果:", transcribeResult.text);
 
-        console.log(
-          "[语音输入] 录音完成，时长:",
-          result.duration.toFixed(2),
-          "秒",
-        );
-
-        if (result.duration < 0.5) {
-          console.log("[语音输入] 录音时间过短");
-          // 这里不要 alert，因为用户可能只是误触，直接静默取消即可
+        // 录音过短时后端返回空文本，用户可能只是误触，直接静默取消即可
+        if (!transcribeResult.text.trim()) {
           setVoiceState("idle");
           setVoiceMode(false);
           return;
         }
 
-        const audioData = new Uint8Array(result.audio_data);
-        const transcribeResult = await transcribeAudio(
-          audioData,
-          result.sample_rate,
+        // 按配置润色或翻译
+        const translateInstructionId = translateModeRef.current
+          ? translateInstructionIdRef.current
+          : null;
+        const {
+          text: finalText,
+          script,
+          review,
+          overBudget,
+          abTrialId: trialId,
+        } = await polishTranscript(
+          transcribeResult,
+          translateInstructionId,
+          () => setVoiceState("polishing"),
         );
-        console.log("[语音识别] 结果:", transcribeResult.text);
-
-        if (!transcribeResult.text.trim()) {
-          setVoiceState("idle");
+        setCostEstimate(overBudget);
+        setAbTrialId(trialId);
+        pendingPolishRef.current = overBudget
+          ? { transcribeResult, translateInstructionId }
+          : null;
+
+        // 输出前确认：在悬浮窗中显示草稿，等待确认、编辑或重试
+        if (review) {
+          outputIdRef.current = transcribeResult.output_id;
+          draftActionsRef.current = script !== null;
+          setDraftText(script ?? finalText);
+          setVoiceState("review");
           setVoiceMode(false);
           return;
         }
 
-        // 检查是否启用润色或翻译模式
-        let finalText = transcribeResult.text;
-        try {
-          const config = await getVoiceInputConfig();
-          console.log("[语音输入] 润色配置:", {
-            polish_enabled: config.processor.polish_enabled,
-            polish_model: config.processor.polish_model,
-            default_instruction_id: config.processor.default_instruction_id,
-            translateMode: translateModeRef.current,
-            translateInstructionId: translateInstructionIdRef.current,
-          });
-
-          // 翻译模式：使用指定的翻译指令
-          if (translateModeRef.current && translateInstructionIdRef.current) {
-            console.log("[语音输入] 进入翻译模式分支");
-            setVoiceState("polishing");
-            console.log(
-              "[语音输入] 翻译模式，使用指令:",
-              translateInstructionIdRef.current,
-            );
-            const polished = await polishVoiceText(
-              transcribeResult.text,
-              translateInstructionIdRef.current,
-            );
-            console.log("[语音输入] 翻译完成:", polished.text);
-            finalText = polished.text;
-          } else if (config.processor.polish_enabled) {
-            // 普通模式：使用默认润色
-            console.log("[语音输入] 进入润色模式分支");
-            setVoiceState("polishing");
-            const polished = await polishVoiceText(transcribeResult.text);
-            console.log("[语音输入] 润色完成:", polished.text);
-            finalText = polished.text;
-          } else {
-            console.log("[语音输入] 润色未启用，直接使用原始文本");
-          }
-        } catch (e) {
-          console.error("[语音润色] 失败:", e);
-        }
-
+        releaseOutput(transcribeResult.output_id);
         setInputValue(finalText);
         setVoiceState("idle");
         setVoiceMode(false);
         inputRef.current?.focus();
       } catch (err) {
         console.error("[语音识别] 失败:", err);
+        releaseOutput(pendingOutputId);
         showError("语音识别过程中发生错误");
         setVoiceState("idle");
         setVoiceMode(false);
@@ -398,6 +575,63 @@ export function SmartInputPage() {
     [showError],
   ); // 只依赖 showError，其他通过 Ref 获取
 
+  // 监听边录边识别的中间结果
+  useEffect(() => {
+    if (!voiceMode) return;
+
+    const setupPartialListener = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        const { PARTIAL_TRANSCRIPT_EVENT } = await import(
+          "@/lib/api/asrProvider"
+        );
+        return await listen<PartialTranscript>(
+          PARTIAL_TRANSCRIPT_EVENT,
+          (event) => setPartialText(event.payload.text),
+        );
+      } catch (err) {
+        console.error("[语音输入] 监听识别中间结果失败:", err);
+        return () => {};
+      }
+    };
+
+    const unlistenPromise = setupPartialListener();
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, [voiceMode]);
+
+  // 开头一直没有说话时后端会自动取消录音，提示后关闭语音模式
+  useEffect(() => {
+    if (!voiceMode) return;
+
+    let timer: ReturnType<typeof setTimeout> | undefined;
+    const setupNoSpeechListener = async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        const { NO_SPEECH_EVENT } = await import("@/lib/api/asrProvider");
+        return await listen(NO_SPEECH_EVENT, () => {
+          console.log("[语音输入] 未检测到说话，录音已自动取消");
+          setPartialText("");
+          setVoiceState("no_speech");
+          timer = setTimeout(() => {
+            setVoiceState("idle");
+            setVoiceMode(false);
+          }, 2000);
+        });
+      } catch (err) {
+        console.error("[语音输入] 监听静音取消事件失败:", err);
+        return () => {};
+      }
+    };
+
+    const unlistenPromise = setupNoSpeechListener();
+    return () => {
+      if (timer) clearTimeout(timer);
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, [voiceMode]);
+
   // 监听快捷键释放事件
   useEffect(() => {
     if (!voiceMode) return;
@@ -408,38 +642,25 @@ export function SmartInputPage() {
         const unlisten = await listen("voice-stop-recording", async () => {
           console.log("[语音输入] 收到停止录音事件");
 
+          // 录音已因开头静音被自动取消
+          if (voiceStateRef.current === "no_speech") return;
+
           // 播放停止录音音效
           playStopSoundRef.current();
 
           // 直接在这里执行停止录音逻辑，避免闭包问题
           setVoiceState("transcribing");
+          // 润色等后续步骤失败时释放输出占位
+          let pendingOutputId: number | null = null;
           try {
-            const {
-              stopRecording,
-              transcribeAudio,
-              polishVoiceText,
-              getVoiceInputConfig,
-            } = await import("@/lib/api/asrProvider");
-
-            const result = await stopRecording();
-            console.log(
-              "[语音输入] 录音完成，时长:",
-              result.duration.toFixed(2),
-              "秒",
+            const { stopStreamingTranscription } = await import(
+              "@/lib/api/asrProvider"
             );
 
-            if (result.duration < 0.5) {
-              console.log("[语音输入] 录音时间过短");
-              setVoiceState("idle");
-              setVoiceMode(false);
-              return;
-            }
-
-            const audioData = new Uint8Array(result.audio_data);
-            const transcribeResult = await transcribeAudio(
-              audioData,
-              result.sample_rate,
+            const transcribeResult = await stopStreamingTranscription(
+              sessionIdRef.current,
             );
+            pendingOutputId = transcribeResult.output_id;
             console.log("[语音识别] 结果:", transcribeResult.text);
 
             if (!transcribeResult.text.trim()) {
@@ -448,55 +669,45 @@ export function SmartInputPage() {
               return;
             }
 
-            // 检查是否启用润色或翻译模式
-            let finalText = transcribeResult.text;
-            try {
-              const config = await getVoiceInputConfig();
-              console.log("[语音输入] 润色配置:", {
-                polish_enabled: config.processor.polish_enabled,
-                polish_model: config.processor.polish_model,
-                default_instruction_id: config.processor.default_instruction_id,
-                translateMode: translateModeRef.current,
-                translateInstructionId: translateInstructionIdRef.current,
-              });
-
-              // 翻译模式：使用指定的翻译指令
-              if (
-                translateModeRef.current &&
-                translateInstructionIdRef.current
-              ) {
-                console.log("[语音输入] 进入翻译模式分支");
-                setVoiceState("polishing");
-                console.log(
-                  "[语音输入] 翻译模式，使用指令:",
-                  translateInstructionIdRef.current,
-                );
-                const polished = await polishVoiceText(
-                  transcribeResult.text,
-                  translateInstructionIdRef.current,
-                );
-                console.log("[语音输入] 翻译完成:", polished.text);
-                finalText = polished.text;
-              } else if (config.processor.polish_enabled) {
-                // 普通模式：使用默认润色
-                console.log("[语音输入] 进入润色模式分支");
-                setVoiceState("polishing");
-                const polished = await polishVoiceText(transcribeResult.text);
-                console.log("[语音输入] 润色完成:", polished.text);
-                finalText = polished.text;
-              } else {
-                console.log("[语音输入] 润色未启用，直接使用原始文本");
-              }
-            } catch (e) {
-              console.error("[语音润色] 失败:", e);
+            // 按配置润色或翻译
+            const translateInstructionId = translateModeRef.current
+              ? translateInstructionIdRef.current
+              : null;
+            const {
+              text: finalText,
+              script,
+              review,
+              overBudget,
+              abTrialId: trialId,
+            } = await polishTranscript(
+              transcribeResult,
+              translateInstructionId,
+              () => setVoiceState("polishing"),
+            );
+            setCostEstimate(overBudget);
+            setAbTrialId(trialId);
+            pendingPolishRef.current = overBudget
+              ? { transcribeResult, translateInstructionId }
+              : null;
+
+            // 输出前确认：在悬浮窗中显示草稿，等待确认、编辑或重试
+            if (review) {
+              outputIdRef.current = transcribeResult.output_id;
+              draftActionsRef.current = script !== null;
+              setDraftText(script ?? finalText);
+              setVoiceState("review");
+              setVoiceMode(false);
+              return;
             }
 
+            releaseOutput(transcribeResult.output_id);
             setInputValue(finalText);
             setVoiceState("idle");
             setVoiceMode(false);
             inputRef.current?.focus();
           } catch (err) {
             console.error("[语音识别] 失败:", err);
+            releaseOutput(pendingOutputId);
             showError("语音识别过程中发生错误");
             setVoiceState("idle");
             setVoiceMode(false);
@@ -515,6 +726,103 @@ export function SmartInputPage() {
     };
   }, [voiceMode, showError]);
 
+  // 确认草稿：关闭悬浮窗并输出（可能已编辑过）
+  const handleDraftAccept = useCallback(async () => {
+    if (!draftText.trim()) return;
+    try {
+      const { acceptVoiceDraft } = await import("@/lib/api/asrProvider");
+      await acceptVoiceDraft(
+        draftText,
+        undefined,
+        outputIdRef.current,
+        sessionIdRef.current,
+        draftActionsRef.current,
+      );
+      outputIdRef.current = null;
+    } catch (err) {
+      console.error("[听写草稿] 输出失败:", err);
+      showError(`输出失败: ${err}`);
+    }
+  }, [draftText, showError]);
+
+  // 重试草稿：用保存的录音重新识别并润色
+  const handleDraftRetry = useCallback(async () => {
+    setVoiceState("transcribing");
+    try {
+      const { retryVoiceDraft } = await import("@/lib/api/asrProvider");
+      const transcribeResult = await retryVoiceDraft();
+      const translateInstructionId = translateModeRef.current
+        ? translateInstructionIdRef.current
+        : null;
+      const {
+        text,
+        script,
+        overBudget,
+        abTrialId: trialId,
+      } = await polishTranscript(
+        transcribeResult,
+        translateInstructionId,
+        () => setVoiceState("polishing"),
+      );
+      draftActionsRef.current = script !== null;
+      setDraftText(script ?? text);
+      setCostEstimate(overBudget);
+      setAbTrialId(trialId);
+      pendingPolishRef.current = overBudget
+        ? { transcribeResult, translateInstructionId }
+        : null;
+    } catch (err) {
+      console.error("[听写草稿] 重试失败:", err);
+      showError(`重试失败: ${err}`);
+    }
+    setVoiceState("review");
+  }, [showError]);
+
+  // 费用超出上限时确认润色：跳过费用检查重新润色，结果替换原文
+  const handleCostConfirm = useCallback(async () => {
+    const pending = pendingPolishRef.current;
+    if (!pending) return;
+    const wasReview = voiceState === "review";
+    pendingPolishRef.current = null;
+    setCostEstimate(null);
+    setVoiceState("polishing");
+    const {
+      text,
+      script,
+      abTrialId: trialId,
+    } = await polishTranscript(
+      pending.transcribeResult,
+      pending.translateInstructionId,
+      () => {},
+      true,
+    );
+    setAbTrialId(trialId);
+    if (wasReview) {
+      draftActionsRef.current = script !== null;
+      setDraftText(script ?? text);
+      setVoiceState("review");
+    } else {
+      setInputValue(text);
+      setVoiceState("idle");
+      inputRef.current?.focus();
+    }
+  }, [voiceState]);
+
+  // 评价 A/B 测试变体的润色结果，每次润色只评价一次
+  const handleAbRate = useCallback(
+    async (rating: AbRating) => {
+      if (!abTrialId) return;
+      setAbTrialId(null);
+      try {
+        const { rateVoiceAbTrial } = await import("@/lib/api/asrProvider");
+        await rateVoiceAbTrial(abTrialId, rating);
+      } catch (err) {
+        console.error("[指令 A/B 测试] 保存评价失败:", err);
+      }
+    },
+    [abTrialId],
+  );
+
   // 关闭窗口
   const handleClose = useCallback(async () => {
     // 如果正在录音，先取消
@@ -526,6 +834,16 @@ export function SmartInputPage() {
         console.error("[语音输入] 取消录音失败:", err);
       }
     }
+    // 放弃等待确认的草稿
+    if (voiceState === "review") {
+      try {
+        const { discardVoiceDraft } = await import("@/lib/api/asrProvider");
+        await discardVoiceDraft(outputIdRef.current, sessionIdRef.current);
+        outputIdRef.current = null;
+      } catch (err) {
+        console.error("[听写草稿] 放弃失败:", err);
+      }
+    }
     try {
       await getCurrentWindow().close();
     } catch (err) {
@@ -583,10 +901,54 @@ export function SmartInputPage() {
     }
   };
 
+  // 展开/收起最近的剪贴板历史
+  const toggleClipboardEntries = async () => {
+    if (clipboardEntries) {
+      setClipboardEntries(null);
+      return;
+    }
+    try {
+      const { getClipboardHistory } = await import("@/lib/api/asrProvider");
+      const entries = await getClipboardHistory();
+      if (entries.length === 0) {
+        showError("剪贴板历史为空");
+        return;
+      }
+      setClipboardEntries(entries.slice(0, CLIPBOARD_QUICK_INSERT_COUNT));
+    } catch (err) {
+      console.error("[剪贴板历史] 加载失败:", err);
+      showError(`加载剪贴板历史失败: ${err}`);
+    }
+  };
+
+  // 在光标处插入剪贴板历史（草稿确认时插入草稿）
+  const insertClipboardEntry = (text: string) => {
+    const input = inputRef.current;
+    const current = voiceState === "review" ? draftText : inputValue;
+    const start = input?.selectionStart ?? current.length;
+    const end = input?.selectionEnd ?? current.length;
+    const next = current.slice(0, start) + text + current.slice(end);
+    if (voiceState === "review") {
+      setDraftText(next);
+    } else {
+      setInputValue(next);
+    }
+    setClipboardEntries(null);
+    requestAnimationFrame(() => {
+      const cursor = start + text.length;
+      input?.focus();
+      input?.setSelectionRange(cursor, cursor);
+    });
+  };
+
   const handleInputKeyDown = (e: React.KeyboardEvent) => {
     if (e.key === "Enter" && !e.shiftKey) {
       e.preventDefault();
-      handleSend();
+      if (voiceState === "review") {
+        handleDraftAccept();
+      } else {
+        handleSend();
+      }
     }
   };
 
@@ -616,6 +978,53 @@ export function SmartInputPage() {
           </div>
         )}
 
+        {voiceState === "review" && (
+          <div className="screenshot-attachment">
+            <span>确认后输出</span>
+          </div>
+        )}
+
+        {costEstimate &&
+          (voiceState === "idle" || voiceState === "review") && (
+            <div className="screenshot-attachment">
+              <span>预计 {formatCost(costEstimate)}，超出润色上限</span>
+              <button
+                className="screenshot-attachment-remove"
+                onClick={handleCostConfirm}
+                title="仍然润色"
+              >
+                <Sparkles size={10} />
+              </button>
+            </div>
+          )}
+
+        {abTrialId && (voiceState === "idle" || voiceState === "review") && (
+          <div className="screenshot-attachment">
+            <span>润色效果</span>
+            <button
+              className="screenshot-attachment-remove"
+              onClick={() => handleAbRate("good")}
+              title="好"
+            >
+              <ThumbsUp size={10} />
+            </button>
+            <button
+              className="screenshot-attachment-remove"
+              onClick={() => handleAbRate("bad")}
+              title="差"
+            >
+              <ThumbsDown size={10} />
+            </button>
+          </div>
+        )}
+
+        {voiceState === "no_speech" && (
+          <div className="screenshot-attachment processing">
+            <MicOff size={12} />
+            <span>未检测到语音</span>
+          </div>
+        )}
+
         {/* 图片附件标签 */}
         {imagePath && (
           <div className="screenshot-attachment">
@@ -631,33 +1040,71 @@ export function SmartInputPage() {
           </div>
         )}
 
+        {/* 剪贴板历史，点击插入到光标处 */}
+        {clipboardEntries &&
+          (voiceState === "idle" || voiceState === "review") &&
+          clipboardEntries.map((entry) => (
+            <button
+              key={entry.captured_at}
+              className="screenshot-attachment screenshot-clipboard-entry"
+              onClick={() => insertClipboardEntry(entry.text)}
+              title={entry.text}
+            >
+              <span>
+                {entry.text.length > CLIPBOARD_LABEL_CHARS
+                  ? `${entry.text.slice(0, CLIPBOARD_LABEL_CHARS)}…`
+                  : entry.text}
+              </span>
+            </button>
+          ))}
+
         {/* 录音模式显示波形，非录音模式显示输入框 */}
         {voiceState === "recording" ? (
           <div className="screenshot-recording-container">
             <div className="recording-dot" />
-            <span className="screenshot-recording-text">正在聆听...</span>
+            <span className="screenshot-recording-text">
+              {partialText || "正在聆听..."}
+            </span>
           </div>
         ) : (
           <textarea
             ref={inputRef}
             className="screenshot-input"
             placeholder="Ask anything..."
-            value={inputValue}
+            value={voiceState === "review" ? draftText : inputValue}
             onChange={(e) => {
-              setInputValue(e.target.value);
+              if (voiceState === "review") {
+                setDraftText(e.target.value);
+              } else {
+                setInputValue(e.target.value);
+              }
               // 自动调整高度
               e.target.style.height = "auto";
               e.target.style.height =
                 Math.min(e.target.scrollHeight, 120) + "px";
             }}
             onKeyDown={handleInputKeyDown}
-            disabled={isLoading || voiceState !== "idle"}
+            disabled={
+              isLoading || (voiceState !== "idle" && voiceState !== "review")
+            }
             rows={1}
           />
         )}
 
         {/* 右侧按钮组 */}
         <div className="screenshot-actions">
+          {/* 剪贴板历史快速插入 */}
+          {clipboardEnabled &&
+            (voiceState === "idle" || voiceState === "review") && (
+              <button
+                className="screenshot-mic-btn"
+                onClick={toggleClipboardEntries}
+                title="插入剪贴板历史"
+              >
+                <ClipboardList size={16} />
+              </button>
+            )}
+
           {/* 麦克风按钮 - 点击开始录音 */}
           {voiceState === "idle" && (
             <button
@@ -669,6 +1116,27 @@ export function SmartInputPage() {
             </button>
           )}
 
+          {/* 草稿操作：重新识别、确认输出 */}
+          {voiceState === "review" && (
+            <>
+              <button
+                className="screenshot-mic-btn"
+                onClick={handleDraftRetry}
+                title="重新识别"
+              >
+                <RotateCcw size={16} />
+              </button>
+              <button
+                className={`screenshot-send-btn ${draftText.trim() ? "active" : ""}`}
+                onClick={handleDraftAccept}
+                disabled={!draftText.trim()}
+                title="确认输出"
+              >
+                <Check size={16} />
+              </button>
+            </>
+          )}
+
           {/* 停止录音按钮 */}
           {voiceState === "recording" && (
             <button